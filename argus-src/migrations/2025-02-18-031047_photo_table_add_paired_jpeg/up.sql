@@ -0,0 +1,3 @@
+-- Your SQL goes here
+-- RAW+JPEG 配对后 JPEG 不再作为单独的照片，记录在 RAW 照片上
+ALTER TABLE photo_table ADD COLUMN paired_jpeg TEXT;     -- 同一文件夹中配对的 JPEG 文件名（为空表示没有配对）
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+-- 导入时的图像校验级别：0 不校验、1 文件头、2 完整解码
+ALTER TABLE photo_table ADD COLUMN validation_level INTEGER NOT NULL DEFAULT 0;
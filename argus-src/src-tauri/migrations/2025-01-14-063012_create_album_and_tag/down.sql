@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS photo_tags;
+DROP TABLE IF EXISTS photo_albums;
+DROP TABLE IF EXISTS tags;
+DROP TABLE IF EXISTS albums;
@@ -0,0 +1,42 @@
+-- Your SQL goes here
+-- 相册（支持多级）
+CREATE TABLE albums (
+                        id INTEGER not null PRIMARY KEY AUTOINCREMENT, -- id 自动增长主键
+                        name TEXT NOT NULL,                            -- 相册名称
+                        parent_id INTEGER,                             -- 父级相册，为空表示根相册
+                        description TEXT,                              -- 相册描述
+                        is_delete BOOLEAN NOT NULL DEFAULT 0,          -- 是否删除
+                        create_time BIGINT NOT NULL default 0,         -- 创建时间（Unix 时间戳）
+                        update_time BIGINT NOT NULL default 0          -- 更新时间（Unix 时间戳）
+);
+
+-- 标签（支持多级）
+CREATE TABLE tags (
+                      id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                      name TEXT NOT NULL,                              -- 标签名称
+                      parent_id INTEGER,                               -- 父级标签，为空表示根标签
+                      is_delete BOOLEAN NOT NULL DEFAULT 0,
+                      create_time BIGINT NOT NULL default 0,
+                      update_time BIGINT NOT NULL default 0
+);
+
+-- 相册和照片的关联
+CREATE TABLE photo_albums (
+                              id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                              album_id INTEGER NOT NULL,
+                              photo_id INTEGER NOT NULL,
+                              create_time BIGINT NOT NULL default 0,
+                              UNIQUE (album_id, photo_id)
+);
+
+-- 标签和照片的关联
+CREATE TABLE photo_tags (
+                            id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                            tag_id INTEGER NOT NULL,
+                            photo_id INTEGER NOT NULL,
+                            create_time BIGINT NOT NULL default 0,
+                            UNIQUE (tag_id, photo_id)
+);
+
+CREATE INDEX idx_photo_albums_photo_id ON photo_albums (photo_id);
+CREATE INDEX idx_photo_tags_photo_id ON photo_tags (photo_id);
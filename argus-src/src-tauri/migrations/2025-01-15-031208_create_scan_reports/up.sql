@@ -0,0 +1,12 @@
+-- Your SQL goes here
+-- 扫描报告（记录每次扫描最慢的文件和文件夹）
+CREATE TABLE scan_reports (
+                              id INTEGER not null PRIMARY KEY AUTOINCREMENT, -- 扫描 ID
+                              status INTEGER NOT NULL DEFAULT 0,             -- 状态【0 扫描中、1 完成、2 取消】
+                              file_count INTEGER NOT NULL DEFAULT 0,         -- 处理的文件数量
+                              total_millis BIGINT NOT NULL DEFAULT 0,        -- 所有文件处理耗时之和（毫秒）
+                              slowest_files TEXT NOT NULL DEFAULT '[]',      -- 最慢的文件（JSON）
+                              slowest_folders TEXT NOT NULL DEFAULT '[]',    -- 最慢的文件夹（JSON）
+                              start_time BIGINT NOT NULL default 0,          -- 开始时间（Unix 时间戳）
+                              end_time BIGINT NOT NULL default 0             -- 结束时间（Unix 时间戳）
+);
@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE photo_table DROP COLUMN img_path_raw;
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+-- 完整路径的原始字节（Unix 为原始字节，Windows 为 UTF-16LE），用于保存非 UTF-8 路径
+ALTER TABLE photo_table ADD COLUMN img_path_raw BLOB;
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE photo_table DROP COLUMN is_available;
+
+ALTER TABLE photo_storages DROP COLUMN last_seen_time;
+ALTER TABLE photo_storages DROP COLUMN is_available;
+ALTER TABLE photo_storages DROP COLUMN relative_path;
+ALTER TABLE photo_storages DROP COLUMN volume_label;
+ALTER TABLE photo_storages DROP COLUMN volume_id;
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+-- 照片存储路径所在卷的信息，用于识别离线的移动硬盘、网络共享，以及盘符变化后的路径修复
+ALTER TABLE photo_storages ADD COLUMN volume_id TEXT;                            -- 卷唯一标识（卷 GUID、文件系统 UUID、共享地址）
+ALTER TABLE photo_storages ADD COLUMN volume_label TEXT;                         -- 卷标
+ALTER TABLE photo_storages ADD COLUMN relative_path TEXT;                        -- 相对于挂载点的路径
+ALTER TABLE photo_storages ADD COLUMN is_available BOOLEAN NOT NULL DEFAULT 1;   -- 卷是否在线
+ALTER TABLE photo_storages ADD COLUMN last_seen_time BIGINT NOT NULL DEFAULT 0;  -- 最后一次在线时间
+
+-- 照片是否可访问（所在卷离线时为 0）
+ALTER TABLE photo_table ADD COLUMN is_available BOOLEAN NOT NULL DEFAULT 1;
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+-- 元数据编辑记录（用于撤销、重做）
+CREATE TABLE edit_journal (
+                              id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                              session_id TEXT NOT NULL,                      -- 会话 ID【每次启动程序生成，只能撤销本次会话的编辑】
+                              description TEXT NOT NULL DEFAULT '',          -- 编辑描述
+                              changes TEXT NOT NULL DEFAULT '[]',            -- 修改内容（JSON，包含修改前后的值）
+                              is_undone BOOLEAN NOT NULL DEFAULT 0,          -- 是否已撤销
+                              create_time BIGINT NOT NULL default 0,
+                              update_time BIGINT NOT NULL default 0
+);
+
+CREATE INDEX idx_edit_journal_session_id ON edit_journal (session_id);
@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER IF EXISTS photo_search_after_tag_update;
+DROP TRIGGER IF EXISTS photo_search_after_tag_delete;
+DROP TRIGGER IF EXISTS photo_search_after_tag_insert;
+DROP TRIGGER IF EXISTS photo_search_after_delete;
+DROP TRIGGER IF EXISTS photo_search_after_update;
+DROP TRIGGER IF EXISTS photo_search_after_insert;
+DROP TABLE IF EXISTS photo_search;
+
+ALTER TABLE photo_table DROP COLUMN location_name;
@@ -0,0 +1,96 @@
+-- Your SQL goes here
+-- 照片说明（扫描时从 ImageDescription、XMP dc:description 读取，可在界面编辑）
+ALTER TABLE photo_table ADD COLUMN caption TEXT;
+
+-- 重建全文索引，加入照片说明
+DROP TRIGGER IF EXISTS photo_search_after_tag_update;
+DROP TRIGGER IF EXISTS photo_search_after_tag_delete;
+DROP TRIGGER IF EXISTS photo_search_after_tag_insert;
+DROP TRIGGER IF EXISTS photo_search_after_delete;
+DROP TRIGGER IF EXISTS photo_search_after_update;
+DROP TRIGGER IF EXISTS photo_search_after_insert;
+DROP TABLE IF EXISTS photo_search;
+
+-- 照片全文索引，rowid 与 photo_table.id 一致
+-- 使用 trigram 分词，支持任意子串搜索（中文无需分词）
+CREATE VIRTUAL TABLE photo_search USING fts5(
+    img_name,                                    -- 文件名
+    caption,                                     -- 照片说明
+    notes,                                       -- 备注
+    keywords,                                    -- 标签名称（空格分隔）
+    camera,                                      -- 相机厂商、型号
+    location,                                    -- 地点名称
+    tokenize = 'trigram'
+);
+
+-- 照片新增、修改、删除时同步索引
+CREATE TRIGGER photo_search_after_insert AFTER INSERT ON photo_table
+BEGIN
+    INSERT INTO photo_search (rowid, img_name, caption, notes, keywords, camera, location)
+    VALUES (new.id,
+            new.img_name,
+            new.caption,
+            new.notes,
+            (SELECT group_concat(t.name, ' ')
+             FROM photo_tags pt JOIN tags t ON t.id = pt.tag_id
+             WHERE pt.photo_id = new.id AND t.is_delete = 0),
+            trim(ifnull(new.make, '') || ' ' || ifnull(new.model, '')),
+            new.location_name);
+END;
+
+CREATE TRIGGER photo_search_after_update AFTER UPDATE OF img_name, caption, notes, make, model, location_name ON photo_table
+BEGIN
+    UPDATE photo_search
+    SET img_name = new.img_name,
+        caption  = new.caption,
+        notes    = new.notes,
+        camera   = trim(ifnull(new.make, '') || ' ' || ifnull(new.model, '')),
+        location = new.location_name
+    WHERE rowid = new.id;
+END;
+
+CREATE TRIGGER photo_search_after_delete AFTER DELETE ON photo_table
+BEGIN
+    DELETE FROM photo_search WHERE rowid = old.id;
+END;
+
+-- 标签关联、标签名称变化时更新关键字
+CREATE TRIGGER photo_search_after_tag_insert AFTER INSERT ON photo_tags
+BEGIN
+    UPDATE photo_search
+    SET keywords = (SELECT group_concat(t.name, ' ')
+                    FROM photo_tags pt JOIN tags t ON t.id = pt.tag_id
+                    WHERE pt.photo_id = new.photo_id AND t.is_delete = 0)
+    WHERE rowid = new.photo_id;
+END;
+
+CREATE TRIGGER photo_search_after_tag_delete AFTER DELETE ON photo_tags
+BEGIN
+    UPDATE photo_search
+    SET keywords = (SELECT group_concat(t.name, ' ')
+                    FROM photo_tags pt JOIN tags t ON t.id = pt.tag_id
+                    WHERE pt.photo_id = old.photo_id AND t.is_delete = 0)
+    WHERE rowid = old.photo_id;
+END;
+
+CREATE TRIGGER photo_search_after_tag_update AFTER UPDATE OF name, is_delete ON tags
+BEGIN
+    UPDATE photo_search
+    SET keywords = (SELECT group_concat(t.name, ' ')
+                    FROM photo_tags pt JOIN tags t ON t.id = pt.tag_id
+                    WHERE pt.photo_id = photo_search.rowid AND t.is_delete = 0)
+    WHERE rowid IN (SELECT photo_id FROM photo_tags WHERE tag_id = new.id);
+END;
+
+-- 为已有照片建立索引
+INSERT INTO photo_search (rowid, img_name, caption, notes, keywords, camera, location)
+SELECT p.id,
+       p.img_name,
+       p.caption,
+       p.notes,
+       (SELECT group_concat(t.name, ' ')
+        FROM photo_tags pt JOIN tags t ON t.id = pt.tag_id
+        WHERE pt.photo_id = p.id AND t.is_delete = 0),
+       trim(ifnull(p.make, '') || ' ' || ifnull(p.model, '')),
+       p.location_name
+FROM photo_table p;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_photo_table_stack_id;
+ALTER TABLE photo_table DROP COLUMN stack_id;
+DROP TABLE IF EXISTS photo_stacks;
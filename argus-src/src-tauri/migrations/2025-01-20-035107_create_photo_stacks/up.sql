@@ -0,0 +1,14 @@
+-- Your SQL goes here
+-- 照片版本堆叠（原图和编辑后导出的副本）
+CREATE TABLE photo_stacks (
+                              id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                              original_photo_id INTEGER NOT NULL,            -- 原图
+                              cover_photo_id INTEGER NOT NULL,               -- 网格中显示的版本
+                              create_time BIGINT NOT NULL default 0,
+                              update_time BIGINT NOT NULL default 0
+);
+
+-- 照片所属的堆叠（为空表示未堆叠）
+ALTER TABLE photo_table ADD COLUMN stack_id INTEGER;
+
+CREATE INDEX idx_photo_table_stack_id ON photo_table (stack_id);
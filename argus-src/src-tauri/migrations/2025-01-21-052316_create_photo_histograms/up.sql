@@ -0,0 +1,9 @@
+-- Your SQL goes here
+-- 照片直方图摘要（生成缩略图时计算，按文件 Hash 保存）
+CREATE TABLE photo_histograms (
+                                  hash TEXT NOT NULL PRIMARY KEY,             -- 文件 Hash
+                                  histogram TEXT NOT NULL,                    -- 各通道直方图（JSON）
+                                  highlight_clipping REAL NOT NULL DEFAULT 0, -- 高光溢出像素百分比
+                                  shadow_clipping REAL NOT NULL DEFAULT 0,    -- 暗部溢出像素百分比
+                                  create_time BIGINT NOT NULL default 0
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_photo_colors_hash;
+DROP TABLE IF EXISTS photo_colors;
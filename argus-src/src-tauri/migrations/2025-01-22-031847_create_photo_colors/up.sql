@@ -0,0 +1,13 @@
+-- Your SQL goes here
+-- 照片主色（生成缩略图时从 128px 缩略图提取，按文件 Hash 保存）
+CREATE TABLE photo_colors (
+                              id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                              hash TEXT NOT NULL,                 -- 文件 Hash
+                              l REAL NOT NULL,                    -- CIELAB L*
+                              a REAL NOT NULL,                    -- CIELAB a*
+                              b REAL NOT NULL,                    -- CIELAB b*
+                              weight REAL NOT NULL,               -- 占比（0 - 1）
+                              create_time BIGINT NOT NULL default 0
+);
+
+CREATE INDEX idx_photo_colors_hash ON photo_colors (hash);
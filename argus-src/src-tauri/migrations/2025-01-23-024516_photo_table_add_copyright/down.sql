@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE photo_table DROP COLUMN credit;
+ALTER TABLE photo_table DROP COLUMN copyright;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- 版权（EXIF Copyright）
+ALTER TABLE photo_table ADD COLUMN copyright TEXT;
+-- 署名（IPTC Credit）
+ALTER TABLE photo_table ADD COLUMN credit TEXT;
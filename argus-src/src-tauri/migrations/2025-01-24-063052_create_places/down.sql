@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_place_photos_photo_id;
+DROP INDEX IF EXISTS idx_place_photos_place_id;
+DROP TABLE IF EXISTS place_photos;
+DROP TABLE IF EXISTS places;
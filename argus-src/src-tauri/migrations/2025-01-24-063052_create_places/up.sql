@@ -0,0 +1,23 @@
+-- Your SQL goes here
+-- 地点（按拍摄位置自动聚类，每次重新聚类时整体替换）
+CREATE TABLE places (
+                        id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                        name TEXT NOT NULL,                            -- 名称（逆地理编码或照片中的地点名称）
+                        latitude DOUBLE NOT NULL,                      -- 中心纬度
+                        longitude DOUBLE NOT NULL,                     -- 中心经度
+                        radius_meters DOUBLE NOT NULL default 0,       -- 覆盖半径（米）
+                        photo_count INTEGER NOT NULL default 0,        -- 照片数量
+                        create_time BIGINT NOT NULL default 0,
+                        update_time BIGINT NOT NULL default 0
+);
+
+-- 地点包含的照片
+CREATE TABLE place_photos (
+                              id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                              place_id INTEGER NOT NULL,
+                              photo_id INTEGER NOT NULL,
+                              create_time BIGINT NOT NULL default 0
+);
+
+CREATE INDEX idx_place_photos_place_id ON place_photos (place_id);
+CREATE INDEX idx_place_photos_photo_id ON place_photos (photo_id);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_photo_sequence_members_photo_id;
+DROP INDEX IF EXISTS idx_photo_sequence_members_sequence_id;
+DROP TABLE IF EXISTS photo_sequence_members;
+DROP TABLE IF EXISTS photo_sequences;
@@ -0,0 +1,22 @@
+-- Your SQL goes here
+-- 连拍序列（待拼接的全景、待合成的包围曝光）
+CREATE TABLE photo_sequences (
+                                 id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                                 kind INTEGER NOT NULL,                         -- 类型（0：全景，1：包围曝光）
+                                 photo_count INTEGER NOT NULL default 0,        -- 照片数量
+                                 start_time BIGINT NOT NULL default 0,          -- 第一张的拍摄时间
+                                 end_time BIGINT NOT NULL default 0,            -- 最后一张的拍摄时间
+                                 create_time BIGINT NOT NULL default 0,
+                                 update_time BIGINT NOT NULL default 0
+);
+
+-- 连拍序列包含的照片（每张照片只属于一个序列）
+CREATE TABLE photo_sequence_members (
+                                        id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                                        sequence_id INTEGER NOT NULL,
+                                        photo_id INTEGER NOT NULL,
+                                        create_time BIGINT NOT NULL default 0
+);
+
+CREATE INDEX idx_photo_sequence_members_sequence_id ON photo_sequence_members (sequence_id);
+CREATE UNIQUE INDEX idx_photo_sequence_members_photo_id ON photo_sequence_members (photo_id);
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+-- 照片活动记录（导入、编辑、导出、分享）
+CREATE TABLE photo_activity (
+                                id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                                photo_id INTEGER NOT NULL,
+                                kind INTEGER NOT NULL,                         -- 类型（0：导入，1：编辑，2：导出，3：分享）
+                                detail TEXT,                                   -- 说明（编辑内容、导出路径等）
+                                create_time BIGINT NOT NULL default 0
+);
+
+CREATE INDEX idx_photo_activity_photo_id ON photo_activity (photo_id);
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+-- 保险箱中的照片【照片本身标记为已删除，不出现在普通查询中】
+CREATE TABLE vault_items (
+                             id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                             photo_id INTEGER NOT NULL UNIQUE,
+                             file_name TEXT NOT NULL,                       -- 保险箱中加密后的原图文件名
+                             thumbnail_name TEXT,                           -- 保险箱中加密后的缩略图文件名（生成失败时为空）
+                             file_size BIGINT NOT NULL default 0,           -- 原图大小（字节）
+                             create_time BIGINT NOT NULL default 0
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_photo_table_img_path;
+DROP INDEX IF EXISTS idx_photo_table_date_time_original;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- 按拍摄时间排序、时间线查询
+CREATE INDEX IF NOT EXISTS idx_photo_table_date_time_original ON photo_table (date_time_original);
+-- 按文件夹查询、重新扫描【LIKE 默认不区分大小写，只有 NOCASE 的索引能用于前缀匹配；hash 已有 UNIQUE 索引】
+CREATE INDEX IF NOT EXISTS idx_photo_table_img_path ON photo_table (img_path COLLATE NOCASE);
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE photo_table DROP COLUMN scene_capture_type_code;
+ALTER TABLE photo_table DROP COLUMN white_balance_code;
+ALTER TABLE photo_table DROP COLUMN exposure_program_code;
+ALTER TABLE photo_table DROP COLUMN metering_mode_code;
+ALTER TABLE photo_table DROP COLUMN flash_code;
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+-- EXIF 枚举字段的数值【按数值筛选，展示时再转换为文字】
+-- 闪光灯（按位组合，第 0 位为是否闪光）
+ALTER TABLE photo_table ADD COLUMN flash_code INTEGER;
+-- 测光模式
+ALTER TABLE photo_table ADD COLUMN metering_mode_code INTEGER;
+-- 曝光程序
+ALTER TABLE photo_table ADD COLUMN exposure_program_code INTEGER;
+-- 白平衡
+ALTER TABLE photo_table ADD COLUMN white_balance_code INTEGER;
+-- 场景拍摄类型
+ALTER TABLE photo_table ADD COLUMN scene_capture_type_code INTEGER;
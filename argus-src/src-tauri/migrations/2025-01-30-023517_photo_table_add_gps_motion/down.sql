@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE photo_table DROP COLUMN gps_dest_bearing;
+ALTER TABLE photo_table DROP COLUMN gps_img_direction;
+ALTER TABLE photo_table DROP COLUMN gps_speed;
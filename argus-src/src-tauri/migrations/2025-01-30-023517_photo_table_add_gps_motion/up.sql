@@ -0,0 +1,8 @@
+-- Your SQL goes here
+-- GPS 运动信息【无人机、行车记录仪等设备写入】
+-- 速度（km/h）
+ALTER TABLE photo_table ADD COLUMN gps_speed FLOAT;
+-- 拍摄方向（度，0 ~ 360）
+ALTER TABLE photo_table ADD COLUMN gps_img_direction FLOAT;
+-- 目的地方位（度，0 ~ 360）
+ALTER TABLE photo_table ADD COLUMN gps_dest_bearing FLOAT;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_photo_table_focal_length_35mm;
+ALTER TABLE photo_table DROP COLUMN focal_length_35mm;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- 等效焦距（毫米）【照片中没有时按相机传感器尺寸估算】
+ALTER TABLE photo_table ADD COLUMN focal_length_35mm FLOAT;
+CREATE INDEX IF NOT EXISTS idx_photo_table_focal_length_35mm ON photo_table (focal_length_35mm);
@@ -0,0 +1,14 @@
+-- Your SQL goes here
+-- 无法读取的文件【文件未变化时后续扫描跳过】
+CREATE TABLE problems (
+                          id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                          path TEXT NOT NULL UNIQUE,                      -- 文件路径
+                          kind INTEGER NOT NULL default 0,                -- 错误类型【0 解码失败、1 EXIF 解析失败】
+                          message TEXT NOT NULL default '',               -- 错误信息
+                          sample TEXT NOT NULL default '',                -- 文件开头的字节（十六进制）
+                          file_size BIGINT NOT NULL default 0,            -- 记录时的文件大小（字节）
+                          modified_time BIGINT NOT NULL default 0,        -- 记录时的文件修改时间（毫秒）
+                          fail_count INTEGER NOT NULL default 1,          -- 失败次数
+                          create_time BIGINT NOT NULL default 0,
+                          update_time BIGINT NOT NULL default 0
+);
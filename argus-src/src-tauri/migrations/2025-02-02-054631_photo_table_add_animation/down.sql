@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE photo_table DROP COLUMN animation_duration;
+ALTER TABLE photo_table DROP COLUMN frame_count;
+ALTER TABLE photo_table DROP COLUMN is_animated;
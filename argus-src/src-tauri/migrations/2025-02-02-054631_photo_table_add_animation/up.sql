@@ -0,0 +1,7 @@
+-- Your SQL goes here
+-- 是否为动图（GIF、APNG、动态 WebP）
+ALTER TABLE photo_table ADD COLUMN is_animated BOOLEAN NOT NULL DEFAULT 0;
+-- 动图帧数
+ALTER TABLE photo_table ADD COLUMN frame_count INTEGER;
+-- 动图播放一遍的时长（毫秒）
+ALTER TABLE photo_table ADD COLUMN animation_duration INTEGER;
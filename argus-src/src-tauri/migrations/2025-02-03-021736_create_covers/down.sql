@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS folder_covers;
+ALTER TABLE albums DROP COLUMN cover_photo_id;
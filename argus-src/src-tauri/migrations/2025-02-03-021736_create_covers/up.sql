@@ -0,0 +1,12 @@
+-- Your SQL goes here
+-- 相册封面【为空时使用相册中最新的照片】
+ALTER TABLE albums ADD COLUMN cover_photo_id INTEGER;
+
+-- 文件夹封面【没有记录时使用文件夹中最新的照片】
+CREATE TABLE folder_covers (
+                               id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                               path TEXT NOT NULL UNIQUE,                  -- 文件夹路径
+                               photo_id INTEGER NOT NULL,                  -- 封面照片
+                               create_time BIGINT NOT NULL default 0,
+                               update_time BIGINT NOT NULL default 0
+);
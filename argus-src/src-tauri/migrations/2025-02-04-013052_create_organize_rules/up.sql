@@ -0,0 +1,12 @@
+-- Your SQL goes here
+-- 自动整理规则【导入照片时按 ID 顺序执行】
+CREATE TABLE organize_rules (
+                                id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                                name TEXT NOT NULL,                         -- 规则名称
+                                conditions TEXT NOT NULL,                   -- 条件（JSON 数组，全部满足时执行）
+                                action INTEGER NOT NULL default 0,          -- 操作：0 添加标签，1 加入相册
+                                target TEXT NOT NULL,                       -- 标签或相册名称
+                                is_enable BOOLEAN NOT NULL default 1,
+                                create_time BIGINT NOT NULL default 0,
+                                update_time BIGINT NOT NULL default 0
+);
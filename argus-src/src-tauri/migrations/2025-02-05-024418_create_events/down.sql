@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS event_photos;
+DROP TABLE IF EXISTS events;
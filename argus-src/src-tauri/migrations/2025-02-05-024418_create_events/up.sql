@@ -0,0 +1,24 @@
+-- Your SQL goes here
+-- 事件（按拍摄时间和位置自动分段，每次重新分段时整体替换）
+CREATE TABLE events (
+                        id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                        name TEXT NOT NULL,                            -- 名称（地点和日期）
+                        place_name TEXT,                               -- 地点名称
+                        start_time BIGINT NOT NULL,                    -- 第一张照片的拍摄时间
+                        end_time BIGINT NOT NULL,                      -- 最后一张照片的拍摄时间
+                        photo_count INTEGER NOT NULL default 0,        -- 照片数量
+                        cover_photo_id INTEGER NOT NULL,               -- 封面照片
+                        create_time BIGINT NOT NULL default 0,
+                        update_time BIGINT NOT NULL default 0
+);
+
+-- 事件包含的照片
+CREATE TABLE event_photos (
+                              id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                              event_id INTEGER NOT NULL,
+                              photo_id INTEGER NOT NULL,
+                              create_time BIGINT NOT NULL default 0
+);
+
+CREATE INDEX idx_event_photos_event_id ON event_photos (event_id);
+CREATE INDEX idx_event_photos_photo_id ON event_photos (photo_id);
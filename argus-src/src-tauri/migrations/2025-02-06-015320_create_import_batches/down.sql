@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_photo_table_last_viewed_time;
+DROP TABLE IF EXISTS import_batch_photos;
+DROP TABLE IF EXISTS import_batches;
@@ -0,0 +1,22 @@
+-- Your SQL goes here
+-- 导入批次（相隔不久导入的照片归为同一批）
+CREATE TABLE import_batches (
+                                id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                                source TEXT NOT NULL,                          -- 来源（照片最多的文件夹）
+                                photo_count INTEGER NOT NULL default 0,        -- 照片数量
+                                start_time BIGINT NOT NULL default 0,          -- 第一张照片的导入时间
+                                end_time BIGINT NOT NULL default 0             -- 最后一张照片的导入时间
+);
+
+-- 导入批次包含的照片
+CREATE TABLE import_batch_photos (
+                                     id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                                     batch_id INTEGER NOT NULL,
+                                     photo_id INTEGER NOT NULL,
+                                     create_time BIGINT NOT NULL default 0
+);
+
+CREATE INDEX idx_import_batch_photos_batch_id ON import_batch_photos (batch_id);
+
+-- 最近浏览
+CREATE INDEX IF NOT EXISTS idx_photo_table_last_viewed_time ON photo_table (last_viewed_time);
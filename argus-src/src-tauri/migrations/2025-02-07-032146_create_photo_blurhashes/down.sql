@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_photo_table_captured_at;
+DROP TABLE IF EXISTS photo_blurhashes;
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+-- 照片 BlurHash（生成缩略图时从 128px 缩略图计算，按文件 Hash 保存）
+CREATE TABLE photo_blurhashes (
+                                  hash TEXT NOT NULL PRIMARY KEY,     -- 文件 Hash
+                                  blurhash TEXT NOT NULL,             -- BlurHash 字符串
+                                  create_time BIGINT NOT NULL default 0
+);
+
+-- 照片列表按拍摄时间（没有拍摄时间时使用导入时间）和 ID 分页
+CREATE INDEX IF NOT EXISTS idx_photo_table_captured_at ON photo_table (ifnull(date_time_original, create_time), id);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_albums_source_path;
+ALTER TABLE albums DROP COLUMN source_path;
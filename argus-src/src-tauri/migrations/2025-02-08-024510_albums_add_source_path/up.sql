@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- 由文件夹同步生成的相册对应的文件夹路径（为空表示用户创建的相册，不为空的相册只读）
+ALTER TABLE albums ADD COLUMN source_path TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_albums_source_path ON albums (source_path) WHERE source_path IS NOT NULL;
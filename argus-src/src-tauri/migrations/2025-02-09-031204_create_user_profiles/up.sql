@@ -0,0 +1,17 @@
+-- Your SQL goes here
+-- 用户资料和偏好设置（共享照片库时每个用户一条）
+CREATE TABLE user_profiles (
+                               id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                               display_name TEXT NOT NULL,                         -- 显示名称
+                               language TEXT,                                      -- 界面语言（为空时使用配置文件中的语言）
+                               theme TEXT NOT NULL default 'system',               -- 主题：system、light、dark
+                               default_sort TEXT NOT NULL default 'capturedDesc',  -- 照片列表默认排序
+                               grid_density TEXT NOT NULL default 'comfortable',   -- 网格密度：compact、comfortable、spacious
+                               vault_safe_search BOOLEAN NOT NULL default 1,       -- 搜索结果中隐藏保险箱照片
+                               create_time BIGINT NOT NULL default 0,
+                               update_time BIGINT NOT NULL default 0
+);
+
+-- 默认用户
+INSERT INTO user_profiles (id, display_name, create_time, update_time)
+VALUES (1, 'Default', strftime('%s', 'now'), strftime('%s', 'now'));
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_remote_objects_hash;
+DROP INDEX IF EXISTS idx_remote_objects_key;
+DROP TABLE IF EXISTS remote_objects;
+DROP TABLE IF EXISTS remote_storages;
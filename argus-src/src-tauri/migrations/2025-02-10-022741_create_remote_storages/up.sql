@@ -0,0 +1,39 @@
+-- Your SQL goes here
+-- 远程存储（S3 兼容存储、WebDAV），原图保存在远程，本地只保存缩略图
+CREATE TABLE remote_storages (
+                                 id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                                 name TEXT NOT NULL,                    -- 显示名称
+                                 kind TEXT NOT NULL,                    -- 类型：s3、webdav
+                                 endpoint TEXT NOT NULL,                -- 服务地址（S3 为 endpoint，WebDAV 为根目录地址）
+                                 bucket TEXT,                           -- S3 存储桶
+                                 region TEXT,                           -- S3 区域（为空时使用 us-east-1）
+                                 prefix TEXT,                           -- 照片所在的目录前缀
+                                 access_key TEXT,                       -- S3 Access Key / WebDAV 用户名
+                                 secret_key TEXT,                       -- S3 Secret Key / WebDAV 密码
+                                 is_enable BOOLEAN NOT NULL default 1,
+                                 create_time BIGINT NOT NULL default 0,
+                                 update_time BIGINT NOT NULL default 0
+);
+
+-- 远程存储中的照片
+CREATE TABLE remote_objects (
+                                id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                                storage_id INTEGER NOT NULL,           -- 远程存储 ID
+                                object_key TEXT NOT NULL,              -- 对象路径（相对远程存储根目录）
+                                size BIGINT NOT NULL default 0,        -- 文件大小
+                                etag TEXT,                             -- ETag（用于判断文件是否变化）
+                                modified_time BIGINT NOT NULL default 0, -- 远程修改时间
+                                hash TEXT,                             -- 文件 SHA-256（生成缩略图时计算）
+                                width INTEGER,                         -- 宽度（读取文件头获得）
+                                height INTEGER,                        -- 高度
+                                make TEXT,                             -- 相机制造商
+                                model TEXT,                            -- 相机型号
+                                date_time_original BIGINT,             -- 拍摄时间
+                                probe_time BIGINT NOT NULL default 0,  -- 读取文件头的时间（0 表示还未读取）
+                                thumbnail_time BIGINT NOT NULL default 0, -- 生成缩略图的时间（0 表示还未生成）
+                                create_time BIGINT NOT NULL default 0,
+                                update_time BIGINT NOT NULL default 0
+);
+
+CREATE UNIQUE INDEX idx_remote_objects_key ON remote_objects (storage_id, object_key);
+CREATE INDEX idx_remote_objects_hash ON remote_objects (hash);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_watch_folders_path;
+DROP TABLE IF EXISTS watch_folders;
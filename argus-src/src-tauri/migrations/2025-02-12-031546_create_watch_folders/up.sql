@@ -0,0 +1,15 @@
+-- Your SQL goes here
+-- 监视文件夹【放入的文件按导入配置自动导入】
+CREATE TABLE watch_folders (
+                               id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                               path TEXT NOT NULL,                          -- 监视的文件夹
+                               dest_dir TEXT NOT NULL,                      -- 导入的目标文件夹
+                               dest_template TEXT,                          -- 子文件夹模板，如 {YYYY}/{MM}（为空时直接放入目标文件夹）
+                               album TEXT,                                  -- 加入的相册名称
+                               tags TEXT NOT NULL default '[]',             -- 添加的标签名称（JSON 数组）
+                               move_files BOOLEAN NOT NULL default 0,       -- 是否移动文件（否则复制，保留原文件）
+                               is_enable BOOLEAN NOT NULL default 1,
+                               create_time BIGINT NOT NULL default 0,
+                               update_time BIGINT NOT NULL default 0
+);
+CREATE UNIQUE INDEX idx_watch_folders_path ON watch_folders (path);
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+-- 逆地理编码缓存（按保留 3 位小数的坐标保存，重新扫描、聚类时相同位置不再请求逆地理编码服务）
+CREATE TABLE geocode_cache (
+                               lat_key INTEGER NOT NULL,              -- 纬度 × 1000（四舍五入）
+                               lon_key INTEGER NOT NULL,              -- 经度 × 1000（四舍五入）
+                               name TEXT,                             -- 地点名称（服务没有结果时为空，同样缓存）
+                               source INTEGER NOT NULL default 0,     -- 来源：0 逆地理编码服务，1 离线数据
+                               expire_time BIGINT,                    -- 过期时间（为空时不过期）
+                               create_time BIGINT NOT NULL default 0,
+                               PRIMARY KEY (lat_key, lon_key)
+);
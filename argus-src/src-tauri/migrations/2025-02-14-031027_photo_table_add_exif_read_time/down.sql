@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE photo_table DROP COLUMN exif_read_time;
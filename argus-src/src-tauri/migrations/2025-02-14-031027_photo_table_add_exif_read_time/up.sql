@@ -0,0 +1,3 @@
+-- Your SQL goes here
+-- 读取 EXIF 并写入照片记录的时间【为空时由补全任务从原图读取，已有的值不覆盖】
+ALTER TABLE photo_table ADD COLUMN exif_read_time BIGINT;
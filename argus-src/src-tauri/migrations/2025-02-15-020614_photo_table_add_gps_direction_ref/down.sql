@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE photo_table DROP COLUMN gps_dest_bearing_ref;
+ALTER TABLE photo_table DROP COLUMN gps_img_direction_ref;
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+-- GPS 拍摄方向、目的地方位的参考方向【T 真北，M 磁北】
+ALTER TABLE photo_table ADD COLUMN gps_img_direction_ref TEXT;
+ALTER TABLE photo_table ADD COLUMN gps_dest_bearing_ref TEXT;
+-- 已有方向的照片重新读取 EXIF，补全参考方向
+UPDATE photo_table SET exif_read_time = NULL WHERE gps_img_direction IS NOT NULL OR gps_dest_bearing IS NOT NULL;
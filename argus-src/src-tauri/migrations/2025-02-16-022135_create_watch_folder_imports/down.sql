@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_watch_folder_imports_hash;
+DROP INDEX IF EXISTS idx_watch_folder_imports_source;
+DROP TABLE IF EXISTS watch_folder_imports;
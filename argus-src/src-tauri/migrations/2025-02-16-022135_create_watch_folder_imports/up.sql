@@ -0,0 +1,12 @@
+-- Your SQL goes here
+-- 监视文件夹已导入的文件【重新启动后不再重复导入，照片删除后也不再导入】
+CREATE TABLE watch_folder_imports (
+                                      id INTEGER not null PRIMARY KEY AUTOINCREMENT,
+                                      folder_id INTEGER NOT NULL,                  -- 监视文件夹 ID
+                                      source_path TEXT NOT NULL,                   -- 监视文件夹中的文件路径
+                                      hash TEXT NOT NULL,                          -- 文件 Hash
+                                      file_size BIGINT NOT NULL default 0,         -- 导入时的文件大小
+                                      create_time BIGINT NOT NULL default 0
+);
+CREATE UNIQUE INDEX idx_watch_folder_imports_source ON watch_folder_imports (folder_id, source_path);
+CREATE INDEX idx_watch_folder_imports_hash ON watch_folder_imports (folder_id, hash);
@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM photo_table WHERE remote_object_id IS NOT NULL;
+DROP INDEX idx_photo_table_remote_object_id;
+ALTER TABLE photo_table DROP COLUMN remote_object_id;
+ALTER TABLE remote_objects ADD COLUMN width INTEGER;
+ALTER TABLE remote_objects ADD COLUMN height INTEGER;
+ALTER TABLE remote_objects ADD COLUMN make TEXT;
+ALTER TABLE remote_objects ADD COLUMN model TEXT;
+ALTER TABLE remote_objects ADD COLUMN date_time_original BIGINT;
+ALTER TABLE remote_objects ADD COLUMN probe_time BIGINT NOT NULL default 0;
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+-- 远程存储中的照片也记录到照片表，remote_objects 只保留远程列表信息
+ALTER TABLE photo_table ADD COLUMN remote_object_id INTEGER;     -- 远程存储中的对象 ID（为空表示本地照片）
+CREATE INDEX idx_photo_table_remote_object_id ON photo_table (remote_object_id);
+-- 照片信息改为从照片表读取
+ALTER TABLE remote_objects DROP COLUMN width;
+ALTER TABLE remote_objects DROP COLUMN height;
+ALTER TABLE remote_objects DROP COLUMN make;
+ALTER TABLE remote_objects DROP COLUMN model;
+ALTER TABLE remote_objects DROP COLUMN date_time_original;
+ALTER TABLE remote_objects DROP COLUMN probe_time;
+-- 下次同步时重新生成缩略图，同时写入照片表
+UPDATE remote_objects SET thumbnail_time = 0;
@@ -10,7 +10,7 @@ pub async fn get_example() -> Result<String> {
     // let res = client.get(url).send().await?;
 
     let url = format!("{}", base_url);
-    println!("{}", url);
+    log::debug!("请求地址:{}", url);
     let response = client.get(url).send().await?;
     let string = response.text().await?;
     // let post = response.json::<String>().await?;
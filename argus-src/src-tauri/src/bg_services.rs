@@ -17,7 +17,7 @@ impl BgServes {
     pub fn drop_all(&self) {
         if is_dev_util() { return; }
         let id = self.py_service_id.expect("后台 ID 获取失败！");
-        println!("主程序退出，杀掉子进程...");
+        log::info!("主程序退出，杀掉子进程...");
         if cfg!(unix) {
             let _ = Command::new("kill")
                 .arg("-9") // 强制终止
@@ -53,11 +53,11 @@ pub fn start_python_service() -> Result<()> {
 
     // 文件是否存在
     let string = app_path.clone().display().to_string();
-    println!("准备启动服务 {}", string);
+    log::info!("准备启动服务 {}", string);
     if !file_util::file_exists(&*string) {
         return Err(anyhow::anyhow!("服务文件不存在！"));
     }
-    println!("开始运行后台服务");
+    log::info!("开始运行后台服务");
     // 启动 app.exe
     let mut output = Command::new(app_path)
         // .arg()
@@ -67,7 +67,7 @@ pub fn start_python_service() -> Result<()> {
     // output.kill();
     // 将子进程的句柄存储起来，用于在主程序退出时终止它
     let child_pid = output.id();
-    println!("子进程 PID: {}", child_pid);
+    log::info!("子进程 PID: {}", child_pid);
     SERVES.write().expect("服务信息读取失败！").py_service_id = Some(child_pid);
     Ok(())
 }
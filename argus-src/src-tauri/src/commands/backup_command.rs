@@ -0,0 +1,42 @@
+use crate::services::backup_service;
+use crate::structs::config::sys_config;
+use crate::utils::json_util::JsonUtil;
+use std::path::PathBuf;
+use tokio::task;
+
+/// 备份数据库（可以在使用中备份）
+/// - dest_path 备份保存的文件夹
+/// - include_config 是否包含配置文件【为空时使用设置】
+#[tauri::command]
+pub async fn backup_library(dest_path: String, include_config: Option<bool>) -> Result<String, String> {
+    let include_config =
+        include_config.unwrap_or_else(|| sys_config().backup_include_config.unwrap_or(true));
+    let summary = task::spawn_blocking(move || {
+        backup_service::backup_library(&PathBuf::from(dest_path), include_config)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| {
+        log::error!("数据库备份失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
+
+/// 从备份恢复数据库，恢复后校验完整性
+/// - src_path 备份文件夹或数据库文件
+/// - include_config 是否同时恢复配置文件【重启后生效】
+#[tauri::command]
+pub async fn restore_library(src_path: String, include_config: Option<bool>) -> Result<String, String> {
+    let include_config = include_config.unwrap_or(false);
+    let summary = task::spawn_blocking(move || {
+        backup_service::restore_library(&PathBuf::from(src_path), include_config)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| {
+        log::error!("数据库恢复失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
@@ -0,0 +1,35 @@
+use crate::global_front_emit;
+use crate::services::batch_operation_service;
+use crate::structs::batch_operation::BatchOperation;
+use crate::utils::json_util::JsonUtil;
+use tauri::{AppHandle, Emitter};
+use tokio::task;
+
+/// 对选中的照片执行批量操作【全部成功或全部回滚】
+///
+/// 进度通过 `batch-apply-progress` 事件通知
+/// - photo_ids 照片 ID
+/// - operations 按顺序执行的操作
+#[tauri::command]
+pub async fn batch_apply(
+    app: AppHandle,
+    photo_ids: Vec<i32>,
+    operations: Vec<BatchOperation>,
+) -> Result<String, String> {
+    let summary = task::spawn_blocking(move || {
+        batch_operation_service::batch_apply(&photo_ids, &operations, |progress| {
+            match JsonUtil::stringify(progress) {
+                Ok(payload) => {
+                    if let Err(e) = app.emit(global_front_emit::BATCH_APPLY_PROGRESS, payload) {
+                        log::warn!("批量操作进度通知发送失败: {}", e);
+                    }
+                }
+                Err(e) => log::warn!("批量操作进度序列化失败: {}", e),
+            }
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
@@ -0,0 +1,72 @@
+use crate::global_front_emit;
+use crate::services::{card_import_service, remote_storage_service};
+use crate::utils::json_util::JsonUtil;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+/// 查找插入的存储卡（包含 DCIM 文件夹的可移动卷）
+#[tauri::command]
+pub fn list_camera_cards() -> Result<String, String> {
+    JsonUtil::stringify(&card_import_service::list_camera_cards()).map_err(|e| e.to_string())
+}
+
+/// 列出存储卡 DCIM 文件夹中的文件
+/// - path 存储卡挂载点或 DCIM 文件夹
+#[tauri::command]
+pub fn list_card_files(path: String) -> Result<String, String> {
+    let files =
+        card_import_service::list_card_files(Path::new(&path)).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&files).map_err(|e| e.to_string())
+}
+
+/// 从存储卡导入文件，复制后逐个校验 SHA-256
+///
+/// 进度通过 `card-import-progress` 事件通知
+/// - files 要导入的文件
+/// - dest_path 目标文件夹
+/// - eject 全部校验通过后是否弹出存储卡【默认不弹出】
+/// - remote_storage_id 导入后同时上传到的远程存储【为空时不上传】
+#[tauri::command]
+pub async fn import_from_card(
+    app: AppHandle,
+    files: Vec<String>,
+    dest_path: String,
+    eject: Option<bool>,
+    remote_storage_id: Option<i32>,
+) -> Result<String, String> {
+    let files: Vec<PathBuf> = files.into_iter().map(PathBuf::from).collect();
+    let mut report = card_import_service::import_from_card(
+        &files,
+        Path::new(&dest_path),
+        eject.unwrap_or(false),
+        |msg| match JsonUtil::stringify(&msg) {
+            Ok(payload) => {
+                if let Err(e) = app.emit(global_front_emit::CARD_IMPORT_PROGRESS, payload) {
+                    log::warn!("存储卡导入进度通知发送失败: {}", e);
+                }
+            }
+            Err(e) => log::warn!("存储卡导入进度序列化失败: {}", e),
+        },
+    )
+    .await
+    .map_err(|e| {
+        log::error!("存储卡导入失败: {}", e);
+        e.to_string()
+    })?;
+    if let Some(storage_id) = remote_storage_id {
+        let imported: Vec<PathBuf> = report
+            .items
+            .iter()
+            .filter_map(|x| x.dest.as_ref())
+            .map(PathBuf::from)
+            .collect();
+        let summary = remote_storage_service::upload_files(storage_id, &imported, None)
+            .await
+            .map_err(|e| {
+                log::error!("存储卡导入后上传到远程存储失败: {}", e);
+                e.to_string()
+            })?;
+        report.remote_upload = Some(summary);
+    }
+    JsonUtil::stringify(&report).map_err(|e| e.to_string())
+}
@@ -0,0 +1,39 @@
+use crate::services::checksum_manifest_service;
+use crate::utils::json_util::JsonUtil;
+use std::path::PathBuf;
+use tokio::task;
+
+/// 导出 SHA256SUMS 校验清单（使用已计算的 Hash）
+/// - folder 导出此文件夹下（含子文件夹）的照片
+/// - album_id 导出此相册中的照片【与 folder 二选一】
+/// - dest_path 清单路径【为空时保存到文件夹下的 SHA256SUMS】
+#[tauri::command]
+pub fn export_checksum_manifest(
+    folder: Option<String>,
+    album_id: Option<i32>,
+    dest_path: Option<String>,
+) -> Result<String, String> {
+    let summary = checksum_manifest_service::export_checksum_manifest(
+        folder.as_deref(),
+        album_id,
+        dest_path.as_deref(),
+    )
+    .map_err(|e| {
+        log::error!("校验清单导出失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
+
+/// 重新计算清单中文件的 SHA-256 并与清单对比
+/// - path 清单路径
+#[tauri::command]
+pub async fn verify_manifest(path: String) -> Result<String, String> {
+    let report = task::spawn_blocking(move || {
+        checksum_manifest_service::verify_manifest(&PathBuf::from(path))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&report).map_err(|e| e.to_string())
+}
@@ -1,18 +1,18 @@
 use crate::api::example::get_example;
 use crate::http_client::HttpClient;
 use crate::utils::exif_utils::exif_util;
-use crate::utils::exif_utils::exif_util::ExifUtil;
-use crate::utils::exif_utils::tag::Tags;
+use crate::utils::json_util::JsonUtil;
+use std::path::Path;
 use tauri_plugin_dialog::DialogExt;
 
 #[tauri::command]
 pub fn greet(name: &str) -> String {
-    println!("Hello, {}!", name);
+    log::info!("Hello, {}!", name);
     let builder = tauri::Builder::default();
     let _res = builder.setup(|app| {
-        println!("执行");
+        log::info!("执行");
         app.dialog().message("Tauri is Awesome!").show(|_| {
-            println!("dialog closed");
+            log::info!("dialog closed");
         });
         Ok(())
     });
@@ -28,20 +28,16 @@ pub async fn http_example() {
 
     let example = get_example().await;
     match example {
-        Ok(post) => println!("Fetched Post: {:?}", post),
-        Err(err) => eprintln!("Error fetching post: {}", err),
+        Ok(post) => log::info!("Fetched Post: {:?}", post),
+        Err(err) => log::error!("Error fetching post: {}", err),
     }
 }
 
-/// 读取图像 exif 信息
+/// 读取图像 exif 信息【返回 `ImgExif` 对象】
 #[tauri::command]
 pub async fn get_exif_info(path:String) -> Result<String, String> {
-    let exif_tool = exif_util::ExifToolCmd;
-    let exif_info = exif_tool.read_all_exif(&*path).expect("图像信息读取失败！");
-    let tag = Tags::new(true);
-    let mt = tag.parse(&exif_info);
-    let result = mt.pack_front_tags().expect("数据打包失败！");
-    Ok(result)
+    let img_exif = exif_util::read_img_exif(Path::new(&path)).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&img_exif).map_err(|e| e.to_string())
 }
 
 
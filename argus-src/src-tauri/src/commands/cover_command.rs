@@ -0,0 +1,40 @@
+use crate::services::cover_service;
+use crate::utils::json_util::JsonUtil;
+
+/// 设置文件夹封面
+/// - path 文件夹路径
+/// - photo_id 封面照片【为空时恢复为文件夹中最新的照片】
+#[tauri::command]
+pub fn set_folder_cover(path: String, photo_id: Option<i32>) -> Result<(), String> {
+    cover_service::set_folder_cover(&path, photo_id).map_err(|e| {
+        log::error!("文件夹封面设置失败 {}: {}", path, e);
+        e.to_string()
+    })
+}
+
+/// 获取文件夹封面【没有设置时为最新的照片，文件夹中没有照片时为 null】
+/// - path 文件夹路径
+#[tauri::command]
+pub fn get_folder_cover(path: String) -> Result<String, String> {
+    let cover = cover_service::get_folder_cover(&path).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&cover).map_err(|e| e.to_string())
+}
+
+/// 设置相册封面
+/// - album_id 相册 ID
+/// - photo_id 封面照片【为空时恢复为相册中最新的照片】
+#[tauri::command]
+pub fn set_album_cover(album_id: i32, photo_id: Option<i32>) -> Result<(), String> {
+    cover_service::set_album_cover(album_id, photo_id).map_err(|e| {
+        log::error!("相册封面设置失败 {}: {}", album_id, e);
+        e.to_string()
+    })
+}
+
+/// 获取相册封面【没有设置时为最新的照片，相册中没有照片时为 null】
+/// - album_id 相册 ID
+#[tauri::command]
+pub fn get_album_cover(album_id: i32) -> Result<String, String> {
+    let cover = cover_service::get_album_cover(album_id).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&cover).map_err(|e| e.to_string())
+}
@@ -0,0 +1,58 @@
+use crate::services::duplicate_reclaim_service;
+use crate::structs::duplicate_reclaim::ReclaimMode;
+use crate::utils::json_util::JsonUtil;
+use std::path::PathBuf;
+use tokio::task;
+
+/// 查找内容完全相同的图片文件
+/// - path 只查找此文件夹【为空表示所有在线的存储路径】
+#[tauri::command]
+pub async fn find_exact_duplicates(path: Option<String>) -> Result<String, String> {
+    let groups = task::spawn_blocking(move || {
+        duplicate_reclaim_service::find_exact_duplicates(path.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&groups).map_err(|e| e.to_string())
+}
+
+/// 把重复文件替换为硬链接或 reflink，返回释放的空间和撤销清单
+/// - groups 重复文件分组【每组第一个为保留的文件】
+/// - mode 替换方式【默认为硬链接】
+#[tauri::command]
+pub async fn reclaim_duplicates(
+    groups: Vec<Vec<String>>,
+    mode: Option<ReclaimMode>,
+) -> Result<String, String> {
+    let report = task::spawn_blocking(move || {
+        duplicate_reclaim_service::reclaim_duplicates(&groups, mode.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| {
+        log::error!("重复文件替换失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&report).map_err(|e| e.to_string())
+}
+
+/// 按撤销清单把链接恢复为独立的文件
+/// - manifest_path 撤销清单路径
+#[tauri::command]
+pub async fn undo_reclaim(manifest_path: String) -> Result<String, String> {
+    let report = task::spawn_blocking(move || {
+        duplicate_reclaim_service::undo_reclaim(&PathBuf::from(manifest_path))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&report).map_err(|e| e.to_string())
+}
+
+/// 获取还可以撤销的清单
+#[tauri::command]
+pub fn list_reclaim_manifests() -> Result<String, String> {
+    JsonUtil::stringify(&duplicate_reclaim_service::list_reclaim_manifests())
+        .map_err(|e| e.to_string())
+}
@@ -1,9 +1,10 @@
 use tauri::{AppHandle, Emitter};
 use crate::global_front_emit::DOWNLOAD_STARTED;
+use crate::i18n::{msg, LocalizedMessage};
 
 #[tauri::command]
 pub async fn emit_send_test(app: AppHandle, param: String) -> String {
-    app.emit(DOWNLOAD_STARTED, format!("你好, {}! 来自后端!", param))
-        .unwrap();
+    let greeting = LocalizedMessage::new(msg::DEV_EMIT_GREETING, &[("name", param)]);
+    app.emit(DOWNLOAD_STARTED, greeting).unwrap();
     "12389128391".to_string()
 }
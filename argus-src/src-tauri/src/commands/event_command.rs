@@ -0,0 +1,27 @@
+use crate::services::event_service;
+use crate::utils::json_util::JsonUtil;
+
+/// 按拍摄时间和位置重新分段事件，并为事件命名
+#[tauri::command]
+pub async fn refresh_events() -> Result<String, String> {
+    let summary = event_service::refresh_events().await.map_err(|e| {
+        log::error!("事件分段失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
+
+/// 获取所有事件【按拍摄时间从新到旧】
+#[tauri::command]
+pub fn list_events() -> Result<String, String> {
+    let events = event_service::list_events().map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&events).map_err(|e| e.to_string())
+}
+
+/// 获取事件中的照片【按拍摄时间排序】
+/// - event_id 事件 ID
+#[tauri::command]
+pub fn get_event_photos(event_id: i32) -> Result<String, String> {
+    let photos = event_service::get_event_photos(event_id).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&photos).map_err(|e| e.to_string())
+}
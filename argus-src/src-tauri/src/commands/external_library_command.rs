@@ -0,0 +1,24 @@
+use crate::services::external_library_service;
+use crate::structs::external_library::ExternalLibrarySource;
+use crate::utils::json_util::JsonUtil;
+use std::path::PathBuf;
+use std::str::FromStr;
+use tokio::task;
+
+/// 从其他照片管理软件导入相册、关键字、评分和人物
+/// - source 来源【applePhotos、digikam】
+/// - path Apple 照片图库文件夹（或 Photos.sqlite）、digiKam 数据库文件 digikam4.db
+#[tauri::command]
+pub async fn import_external_library(source: String, path: String) -> Result<String, String> {
+    let source = ExternalLibrarySource::from_str(&source).map_err(|e| e.to_string())?;
+    let summary = task::spawn_blocking(move || {
+        external_library_service::import_external_library(source, &PathBuf::from(path))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| {
+        log::error!("照片库导入失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
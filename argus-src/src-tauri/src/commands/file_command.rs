@@ -1,10 +1,16 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::services::{directory_access_service, photo_file_service};
 use crate::utils::base64_util::base64_encode;
 use crate::utils::file_util::{
-    file_exists, get_all_dir_img, get_all_img, get_all_subfolders, read_binary_file,
+    display_path, file_exists, get_all_dir_img, get_all_img, get_all_subfolders,
+    read_binary_file,
 };
 use crate::utils::json_util::JsonUtil;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use tauri::AppHandle;
+use tokio::task;
 
 /// 返回图像绝对路径
 #[tauri::command]
@@ -13,17 +19,23 @@ pub fn get_image_absolute_path() -> String {
     path
 }
 
-/// 检测是否有指定路径的访问权限
+/// 扫描前检查文件夹的访问权限
+///
+/// 返回文件夹是否可以列出、抽样图片能否读取、云盘占位文件数量等
+/// - directory 文件夹
 #[tauri::command]
-pub fn check_directory_access(directory: String) -> bool {
-    file_exists(&directory)
+pub async fn check_directory_access(directory: String) -> Result<String, String> {
+    let report = task::spawn_blocking(move || directory_access_service::preflight(&directory))
+        .await
+        .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&report).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub fn read_image_as_base64(directory: String) -> Result<String, String> {
     // 检查文件是否存在
     if !file_exists(&directory) {
-        return Err("File does not exist.".to_string());
+        return Err(i18n::t(msg::FILE_DOES_NOT_EXIST, &[]));
     }
 
     // 读取照片
@@ -43,7 +55,7 @@ pub fn get_all_sub_dir(path: String) -> Vec<String> {
     let vec = get_all_subfolders(&*path);
     let mut ans: Vec<String> = Vec::new();
     for x in vec {
-        ans.push(x.to_str().unwrap_or_default().to_string())
+        ans.push(display_path(x))
     }
     ans
 }
@@ -55,14 +67,14 @@ pub fn get_all_imgs(path: String) -> Vec<FolderImage> {
 
     // 使用并发处理文件夹
     for x in &vec {
-        let display = x.display().to_string();
+        let display = display_path(x);
 
         // 获取所有照片
-        let vec1 = get_all_dir_img(&display, Some(-1)); // 获取文件夹中的图像路径
+        let vec1 = get_all_dir_img(x, Some(-1)); // 获取文件夹中的图像路径
         if !vec1.is_empty() {
             for x in vec1 {
                 result.push(FolderImage {
-                    source_file_path: x.clone(),
+                    source_file_path: display_path(x),
                     file_path: display.clone(),
                 })
             }
@@ -80,7 +92,7 @@ pub async fn get_dir_all_subfolders_first_img(_app: AppHandle, path: String) ->
 
     // 使用并发处理文件夹
     for x in &vec {
-        let display = x.display().to_string();
+        let display = display_path(x);
 
         // 获取所有照片
         // let vec1 = get_all_dir_img(&display, None); // 获取文件夹中的图像路径
@@ -94,9 +106,9 @@ pub async fn get_dir_all_subfolders_first_img(_app: AppHandle, path: String) ->
         // }
 
         // 获取当前照片
-        let vec1 = get_all_dir_img(&display, Some(1)); // 获取文件夹中的图像路径
+        let vec1 = get_all_dir_img(x, Some(1)); // 获取文件夹中的图像路径
         if !vec1.is_empty() {
-            let path1 = vec1[0].clone();
+            let path1 = display_path(&vec1[0]);
             result.push(FolderImage {
                 source_file_path: path1.clone(),
                 file_path: display,
@@ -106,6 +118,52 @@ pub async fn get_dir_all_subfolders_first_img(_app: AppHandle, path: String) ->
     result
 }
 
+/// 移动照片到指定文件夹，同时更新数据库中的路径
+/// - photo_ids 照片 ID
+/// - dest_dir 目标文件夹
+#[tauri::command]
+pub fn move_photos(photo_ids: Vec<i32>, dest_dir: String) -> Result<String, String> {
+    let moved = photo_file_service::move_photos(&photo_ids, Path::new(&dest_dir)).map_err(|e| {
+        log::error!("照片移动失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&moved).map_err(|e| e.to_string())
+}
+
+/// 重命名照片，同时更新数据库中的路径
+/// - photo_id 照片 ID
+/// - new_name 新文件名【不含扩展名时沿用原扩展名】
+#[tauri::command]
+pub fn rename_photo(photo_id: i32, new_name: String) -> Result<String, String> {
+    let renamed = photo_file_service::rename_photo(photo_id, &new_name).map_err(|e| {
+        log::error!("照片重命名失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&renamed).map_err(|e| e.to_string())
+}
+
+/// 按模板重命名照片，同时更新数据库中的路径
+/// - photo_ids 照片 ID【序号按该顺序生成】
+/// - template 文件名模板【如 `{YYYY}{MM}{DD}_{HHmmss}_{model}_{seq}`】
+/// - dry_run 只预览新旧文件名【默认 false】
+#[tauri::command]
+pub async fn rename_by_template(
+    photo_ids: Vec<i32>,
+    template: String,
+    dry_run: Option<bool>,
+) -> Result<String, String> {
+    let result = task::spawn_blocking(move || {
+        photo_file_service::rename_by_template(&photo_ids, &template, dry_run.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| {
+        log::error!("照片按模板重命名失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&result).map_err(|e| e.to_string())
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)] // 需要加上这些
 pub struct FolderImage {
     /// 原图路径
@@ -1,3 +1,36 @@
+use crate::services::{color_service, photo_photo_service};
+use crate::structs::photo_search::PhotoSearchParams;
+use crate::utils::json_util::JsonUtil;
+
 /// 获取需要展示的图片及信息
 #[tauri::command]
 pub fn get_need_display_image_info(_path: String) {}
+
+/// 搜索照片
+/// - params 搜索条件【文本部分使用全文索引，支持任意子串】
+#[tauri::command]
+pub fn search_photos(params: PhotoSearchParams) -> Result<String, String> {
+    let photos = photo_photo_service::search_photos(&params).map_err(|e| {
+        log::error!("照片搜索失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&photos).map_err(|e| e.to_string())
+}
+
+/// 按主色搜索照片，如查找青橙色调的照片
+/// - hex 颜色，如 `#1A8C9E`
+/// - tolerance 容差（CIE76 ΔE）【默认 15】
+/// - params 其他搜索条件
+#[tauri::command]
+pub fn search_by_color(
+    hex: String,
+    tolerance: Option<f32>,
+    params: Option<PhotoSearchParams>,
+) -> Result<String, String> {
+    let photos = color_service::search_by_color(&hex, tolerance, params.unwrap_or_default())
+        .map_err(|e| {
+            log::error!("按颜色搜索失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&photos).map_err(|e| e.to_string())
+}
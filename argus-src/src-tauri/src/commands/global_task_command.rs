@@ -1,123 +1,409 @@
-use crate::constant::{IMAGE_COMPRESSION_RATIO, IMAGE_COMPRESSION_STORAGE_FORMAT};
+use crate::constant::{IMAGE_COMPRESSION_RATIO, SCAN_WALK_CHANNEL_SIZE, SCAN_WRITER_IDLE_POLL_MS};
 use crate::global_front_emit;
+use crate::i18n;
+use crate::i18n::{msg, LocalizedMessage};
 use crate::structs::global_error_msg::{
-    GlobalErrorMsg, LoadMsg, GLOBAL_EMIT_APP_HANDLE, GLOBAL_EMIT_IS_INIT, IMG_DISPOSE_IS_CANCEL,
-    IMG_DISPOSE_IS_START,
+    GlobalErrorMsg, LoadMsg, ScanErrorMsg, ScanFinishedMsg, GLOBAL_EMIT_APP_HANDLE, GLOBAL_EMIT_IS_INIT, IMG_DISPOSE_IS_START,
 };
+use crate::services::{
+    cloud_file_service, directory_access_service, folder_album_service, integrity_service, photo_sequence_service,
+    photo_stack_service, problem_service, rescan_service, scan_report_service,
+};
+use crate::structs::problem::ProblemKind;
+use crate::structs::scan_report::{ScanProgress, ScanStatus, ScanTimingCollector};
+use crate::structs::scan_task::SCAN_TASKS;
+use crate::structs::thumbnail_encoding;
 use crate::tuples::Pair;
-use crate::utils::file_util::{get_all_dir_img, get_all_subfolders};
+use crate::utils::{file_util, panic_util, scan_metrics_util, uuid_util};
 use crate::utils::img_util::ImageOperate;
 use crate::utils::json_util::JsonUtil;
+use crate::utils::task_util;
 use crate::utils::task_util::task_h;
+use crate::utils::thumbnail_queue_util::THUMBNAIL_QUEUE;
 use anyhow::Result;
-use std::sync::{Arc, RwLock};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
-use tokio::sync::{mpsc, Semaphore};
+use tokio::sync::mpsc;
 use tokio::task;
 use crate::utils::exif_utils::exif_util;
-use crate::utils::exif_utils::exif_util::ExifUtil;
-use crate::utils::exif_utils::tag::Tags;
 
 #[tauri::command]
-pub async fn add_photo_retrieve_task(
-    app: AppHandle,
-    tasks: Vec<String>,
-    is_cancel: bool,
-) -> Result<String, String> {
-    // 任务是否取消
-    let mut is_cc = IMG_DISPOSE_IS_CANCEL.lock().await;
-    *is_cc = is_cancel;
-
-    println!("add_task: {:?}", tasks);
-    // 获取指定路径下所有的文件
-    let mut result: Vec<String> = Vec::new();
-    for x in tasks {
-        let vec = get_all_subfolders(&x);
-        // 使用并发处理文件夹
-        for x in &vec {
-            let display = x.display().to_string();
-            // 获取所有照片
-            let vec1 = get_all_dir_img(&display, Some(-1)); // 获取文件夹中的图像路径
-            if !vec1.is_empty() {
-                result.extend(vec1)
-            }
+pub async fn add_photo_retrieve_task(app: AppHandle, tasks: Vec<String>) -> Result<String, String> {
+    log::info!("add_task: {:?}", tasks);
+    let tasks = preflight_tasks(tasks).await?;
+    // 扫描耗时记录
+    let scan_id = scan_report_service::start_scan()
+        .map_err(|e| log::error!("扫描记录创建失败: {}", e))
+        .ok();
+    let task_id = uuid_util::uuid_v4();
+    // 任务是否取消【每个任务单独的标记，取消一个扫描不影响同时进行的其他扫描】
+    let cancelled = SCAN_TASKS.register(&task_id, scan_id);
+    log::info!("扫描任务 {} 开始", task_id);
+    scan_metrics_util::start(scan_id);
+    let timings = Arc::new(ScanTimingCollector::new());
+    // 扫描进度【总任务数随遍历不断增长】
+    let progress = Arc::new(ScanProgress::new());
+    // 之前无法读取的文件【只在扫描开始时查询一次】
+    let problems = Arc::new(problem_service::load_known());
+
+    // 并行遍历目录，发现的图片立即进入处理队列
+    let (tx, mut rx) = mpsc::channel::<PathBuf>(SCAN_WALK_CHANNEL_SIZE);
+    let walk_cancelled = Arc::clone(&cancelled);
+    let walker = task::spawn_blocking(move || {
+        for x in tasks {
+            file_util::walk_dir_img_parallel(&x, |img| {
+                !walk_cancelled.load(Ordering::SeqCst) && tx.blocking_send(img).is_ok()
+            });
         }
-    }
+    });
 
-    // 总任务数
-    let lens = result.clone().len();
-    // 当前任务数
-    let data = Arc::new(RwLock::new(0));
-    // 最多 10 个任务
-    let semaphore = Arc::new(Semaphore::new(20)); // 最多 10 个任务同时执行
-     // 添加任务
-    for x in result {
-        let data = Arc::clone(&data);
+    // 添加任务【同时处理的数量和顺序由缩略图队列控制】
+    while let Some(x) = rx.recv().await {
+        let discovered = progress.discover();
+        scan_metrics_util::record_queue_depth(
+            scan_id,
+            u64::from(discovered.saturating_sub(progress.completed())),
+        );
+        let progress = Arc::clone(&progress);
         let ap = app.clone();
-        let permit = Arc::clone(&semaphore);
-        task::spawn(async move {
-            let _permit = permit.acquire().await.unwrap(); // 等待获取一个令牌
-            let is_cc = *IMG_DISPOSE_IS_CANCEL.lock().await;
-            if is_cc {
+        let timings = Arc::clone(&timings);
+        let problems = Arc::clone(&problems);
+        let cancelled = Arc::clone(&cancelled);
+        let task_id = task_id.clone();
+        // 处理期间的 Hash 计算、缩略图生成计入这次扫描的统计
+        task::spawn(scan_metrics_util::scope(scan_id, async move {
+            let _permit = THUMBNAIL_QUEUE.acquire(&x).await; // 等待获取一个令牌
+            if cancelled.load(Ordering::SeqCst) {
+                progress.complete();
+                if progress.try_finish() {
+                    finish_scan(&ap, &task_id, scan_id, ScanStatus::Cancelled, &timings);
+                }
+                return;
+            }
+            // 云盘占位文件不下载，记录为仅云端文件
+            if cloud_file_service::should_skip(&x) {
+                log::debug!("跳过仅云端文件: {}", x.display());
+                cloud_file_service::record(&x);
+                progress.complete();
+                if progress.try_finish() {
+                    finish_scan(
+                        &ap,
+                        &task_id,
+                        scan_id,
+                        scan_end_status(&cancelled),
+                        &timings,
+                    );
+                }
+                return;
+            }
+            // 之前无法读取且没有变化的文件直接跳过
+            if problem_service::should_skip(&problems, &x) {
+                log::debug!("跳过问题文件: {}", x.display());
+                progress.complete();
+                if progress.try_finish() {
+                    finish_scan(
+                        &ap,
+                        &task_id,
+                        scan_id,
+                        scan_end_status(&cancelled),
+                        &timings,
+                    );
+                }
                 return;
             }
+            let started = Instant::now();
+
+            // 获取 exif【随照片记录一起写入】
+            let exif_result = panic_util::catch_panic(|| exif_util::read_img_exif(&x));
 
             // 压缩图像
-            let image_compression = ImageOperate::multi_level_image_compression(
-                x.clone(),
-                IMAGE_COMPRESSION_STORAGE_FORMAT,
+            let result1 = ImageOperate::multi_level_image_compression_with_exif(
+                &x,
+                thumbnail_encoding::storage_format(),
                 IMAGE_COMPRESSION_RATIO.to_vec(),
+                exif_result.as_ref().ok().cloned(),
+            )
+            .await;
+            // 记录无法读取的文件和部分字段无法读取的警告，能正常读取时清除之前的记录
+            match (&result1, &exif_result) {
+                (Err(e), _) => problem_service::record(&x, ProblemKind::Decode, &e.to_string()),
+                (Ok(_), Err(e)) => problem_service::record(&x, ProblemKind::Exif, &e.to_string()),
+                (Ok(_), Ok(exif)) if !exif.warnings.is_empty() => {
+                    problem_service::record(&x, ProblemKind::ExifWarning, &exif.warnings.join("; "))
+                }
+                (Ok(_), Ok(_)) => problem_service::resolve_known(&problems, &x),
+            }
+            let x = file_util::display_path(&x);
+            timings.record(&x, started.elapsed().as_millis() as u64);
+
+            let s = progress.complete();
+            scan_metrics_util::record_queue_depth(
+                scan_id,
+                u64::from(progress.discovered().saturating_sub(s)),
             );
-            
-            // 获取 exif 
-            let exif_tool = exif_util::ExifToolCmd;
-            let exif_info = exif_tool.read_all_exif(&*x).expect("图像信息读取失败！");
-            let tag = Tags::new(true);
-            let mt = tag.parse(&exif_info);
-            let result = mt.pack_object().expect("数据打包失败！");
-            
-
-            let result1 = image_compression.await;
-
-            let mut num = data.write().unwrap(); // 获取写锁
-            *num += 1;
-            let s = *num;
-            match result1 {
-                Ok(_) => {
-                    let lm = LoadMsg {
-                        all_task: lens as u32,
-                        current_task: s,
-                        task_msg: x,
-                    };
-                    let str = JsonUtil::stringify(&lm).unwrap();
-
-                    ap.emit(global_front_emit::PHOTO_LOADING_MSG_TIP, str)
-                        .unwrap();
+            let lm = LoadMsg {
+                task_id: task_id.clone(),
+                all_task: progress.discovered(),
+                current_task: s,
+                task_msg: x,
+                scan_id,
+            };
+            SCAN_TASKS.update_progress(&task_id, lm.all_task, lm.current_task, &lm.task_msg);
+            let str = JsonUtil::stringify(&lm).unwrap();
+            ap.emit(global_front_emit::PHOTO_LOADING_MSG_TIP, str)
+                .unwrap();
+            if let Err(e) = result1 {
+                // 将错误传递到主线程
+                SCAN_TASKS.record_error(&task_id);
+                let err = ScanErrorMsg {
+                    task_id: task_id.clone(),
+                    message: LocalizedMessage::new(
+                        msg::PHOTO_LOAD_FAILED,
+                        &[("path", lm.task_msg.clone()), ("error", e.to_string())],
+                    ),
+                };
+                ap.emit(
+                    global_front_emit::PHOTO_LOADING_ERR_TIP,
+                    JsonUtil::stringify(&err).unwrap(),
+                )
+                .unwrap();
+            }
+
+            // 最后一个任务完成时保存扫描报告
+            if progress.try_finish() {
+                finish_scan(
+                    &ap,
+                    &task_id,
+                    scan_id,
+                    scan_end_status(&cancelled),
+                    &timings,
+                );
+            }
+        }));
+    }
+
+    // 遍历结束，所有任务已在遍历期间完成时由这里保存扫描报告
+    if let Err(e) = walker.await {
+        log::error!("目录遍历失败: {}", e);
+    }
+    progress.finish_walk();
+    if progress.try_finish() {
+        finish_scan(
+            &app,
+            &task_id,
+            scan_id,
+            scan_end_status(&cancelled),
+            &timings,
+        );
+    }
+
+    let count = progress.discovered();
+    Ok(i18n::t(msg::TASK_SUBMITTED, &[("count", count.to_string())]))
+}
+
+/// 扫描前检查文件夹的访问权限，跳过不可访问的文件夹【全部不可访问时返回错误】
+async fn preflight_tasks(tasks: Vec<String>) -> Result<Vec<String>, String> {
+    let reports = task::spawn_blocking(move || {
+        tasks
+            .into_iter()
+            .map(|x| (directory_access_service::preflight(&x), x))
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut accessible = Vec::new();
+    let mut rejected = None;
+    for (report, path) in reports {
+        if !report.can_scan {
+            log::warn!("文件夹不可访问，跳过扫描: {}", path);
+            rejected = Some(path);
+            continue;
+        }
+        if report.unreadable_dirs + report.unreadable_files + report.cloud_placeholders > 0 {
+            log::warn!(
+                "文件夹 {} 部分内容不可访问: 文件夹 {}，图片 {}，云盘占位文件 {}",
+                path,
+                report.unreadable_dirs,
+                report.unreadable_files,
+                report.cloud_placeholders
+            );
+        }
+        accessible.push(path);
+    }
+    match rejected {
+        Some(path) if accessible.is_empty() => {
+            Err(i18n::t(msg::DIRECTORY_NOT_ACCESSIBLE, &[("path", path)]))
+        }
+        _ => Ok(accessible),
+    }
+}
+
+/// 扫描结束时的状态
+/// - cancelled 扫描任务的取消标记
+fn scan_end_status(cancelled: &AtomicBool) -> ScanStatus {
+    if cancelled.load(Ordering::SeqCst) {
+        ScanStatus::Cancelled
+    } else {
+        ScanStatus::Finished
+    }
+}
+
+/// 保存扫描报告，扫描结果全部写入数据库后通知前端扫描结束
+fn finish_scan(
+    app: &AppHandle,
+    task_id: &str,
+    scan_id: Option<i32>,
+    status: ScanStatus,
+    timings: &ScanTimingCollector,
+) {
+    if let Some(scan_id) = scan_id {
+        if let Err(e) = scan_report_service::finish_scan(scan_id, status, timings) {
+            log::error!("扫描报告保存失败: {}", e);
+        }
+    }
+    // 扫描结果全部写入数据库后再处理新照片、结束统计，数据库写入次数、耗时才完整
+    let app = app.clone();
+    let task_id = task_id.to_string();
+    task::spawn(async move {
+        while !task_util::is_writer_idle() {
+            tokio::time::sleep(Duration::from_millis(SCAN_WRITER_IDLE_POLL_MS)).await;
+        }
+        if status == ScanStatus::Finished {
+            // 在新导入的照片中查找待拼接的全景和待合成的包围曝光，并配对同名的 RAW 和 JPEG；
+            // 清除文件已不存在的问题记录
+            task::spawn_blocking(|| {
+                if let Err(e) = photo_sequence_service::detect_sequences(None) {
+                    log::error!("连拍序列检测失败: {}", e);
                 }
-                Err(e) => {
-                    // 将错误传递到主线程
-                    let lm = LoadMsg {
-                        all_task: lens as u32,
-                        current_task: s,
-                        task_msg: x,
-                    };
-                    let str = JsonUtil::stringify(&lm).unwrap();
-
-                    ap.emit(global_front_emit::PHOTO_LOADING_MSG_TIP, str)
-                        .unwrap();
-
-                    ap.emit(
-                        global_front_emit::PHOTO_LOADING_ERR_TIP,
-                        format!("{} 出错: {}", lm.task_msg, e.to_string()),
-                    )
-                    .unwrap();
+                if let Err(e) = photo_stack_service::pair_raw_jpeg(None) {
+                    log::error!("RAW+JPEG 配对失败: {}", e);
                 }
-            }
-        });
+                if let Err(e) = problem_service::prune_missing() {
+                    log::error!("问题文件记录清理失败: {}", e);
+                }
+            });
+        }
+        folder_album_service::request_sync();
+        let metrics = scan_metrics_util::finish(scan_id);
+        log::info!("扫描任务 {} 结束 {:?}: {:?}", task_id, status, metrics);
+        SCAN_TASKS.finish(&task_id, status, metrics.clone());
+        let message = match status {
+            ScanStatus::Cancelled => msg::TASK_CANCELLED,
+            _ => msg::TASK_FINISHED,
+        };
+        let finished = ScanFinishedMsg {
+            task_id,
+            scan_id,
+            status,
+            message: LocalizedMessage::new(message, &[]),
+            metrics,
+        };
+        if let Err(e) = app.emit(
+            global_front_emit::SCAN_FINISHED,
+            JsonUtil::stringify(&finished).unwrap_or_default(),
+        ) {
+            log::error!("扫描结束通知失败: {}", e);
+        }
+    });
+}
+
+/// 获取扫描任务状态【用于前端刷新后恢复进度】
+/// - task_id 扫描任务 ID【由扫描进度、报错、结束消息返回】
+#[tauri::command]
+pub fn get_task_status(task_id: String) -> Result<String, String> {
+    match SCAN_TASKS.get(&task_id) {
+        Some(task) => JsonUtil::stringify(&task).map_err(|e| e.to_string()),
+        None => Err(i18n::t(msg::SCAN_TASK_NOT_FOUND, &[("taskId", task_id)])),
+    }
+}
+
+/// 取消扫描任务【只取消这个任务，同时进行的其他扫描继续】
+/// - task_id 扫描任务 ID【由扫描进度、报错、结束消息返回】
+#[tauri::command]
+pub fn cancel_scan(task_id: String) -> Result<String, String> {
+    if SCAN_TASKS.cancel(&task_id) {
+        log::info!("扫描任务 {} 取消", task_id);
+        return Ok(i18n::t(msg::TASK_CANCELLED, &[]));
+    }
+    // 已结束的任务无需取消
+    match SCAN_TASKS.get(&task_id) {
+        Some(_) => Ok(i18n::t(msg::TASK_FINISHED, &[])),
+        None => Err(i18n::t(msg::SCAN_TASK_NOT_FOUND, &[("taskId", task_id)])),
     }
+}
 
-    Ok(String::from("完成"))
+/// 获取扫描的吞吐量统计【没有扫描记录时返回 null】
+/// - scan_id 扫描 ID【为空时使用最近开始的扫描】
+#[tauri::command]
+pub fn get_scan_metrics(scan_id: Option<i32>) -> Result<String, String> {
+    JsonUtil::stringify(&scan_metrics_util::snapshot(scan_id)).map_err(|e| e.to_string())
+}
+
+/// 重新扫描文件夹，对比磁盘和数据库找出新增、删除和移动的照片
+/// - path 文件夹
+/// - dry_run 为 true 时只返回差异，不修改数据库【用于删除记录前让用户确认】
+#[tauri::command]
+pub async fn rescan_folder(path: String, dry_run: bool) -> Result<String, String> {
+    let plan = rescan_service::rescan_folder(&path, dry_run)
+        .await
+        .map_err(|e| {
+            log::error!("重新扫描失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&plan).map_err(|e| e.to_string())
+}
+
+/// 获取最近一次完整性校验的报告【未校验时为 null】
+#[tauri::command]
+pub fn get_integrity_report() -> Result<String, String> {
+    JsonUtil::stringify(&integrity_service::get_integrity_report()).map_err(|e| e.to_string())
+}
+
+/// 获取无法读取的文件【解码或 EXIF 解析失败，文件未变化时扫描会跳过；以及未下载的仅云端文件】
+#[tauri::command]
+pub fn list_problem_files() -> Result<String, String> {
+    let files = problem_service::list_problem_files().map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&files).map_err(|e| e.to_string())
+}
+
+/// 下载选中的仅云端文件并导入
+/// - paths 文件路径【仅云端文件列表中的路径】
+#[tauri::command]
+pub async fn hydrate_cloud_files(paths: Vec<String>) -> Result<String, String> {
+    let summary = cloud_file_service::hydrate_files(paths)
+        .await
+        .map_err(|e| {
+            log::error!("仅云端文件下载失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
+
+/// 获取扫描报告（最慢的文件及文件夹）
+/// - scan_id 扫描 ID【由照片加载进度信息返回】
+#[tauri::command]
+pub fn get_scan_report(scan_id: i32) -> Result<String, String> {
+    let report = scan_report_service::get_scan_report(scan_id).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&report).map_err(|e| e.to_string())
+}
+
+/// 扫描前预估工作量（图片数量、总大小、预计耗时）
+/// - paths 要扫描的文件夹
+#[tauri::command]
+pub async fn preview_scan(paths: Vec<String>) -> Result<String, String> {
+    let preview = task::spawn_blocking(move || scan_report_service::preview_scan(&paths))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("扫描预估失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&preview).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -126,7 +412,7 @@ pub fn emit_global_msg(app: AppHandle) {
     if is_init.clone() {
         return;
     }
-    println!("前端 emit 初始化!");
+    log::info!("前端 emit 初始化!");
     *is_init = true;
 
     let mut emit = GLOBAL_EMIT_APP_HANDLE.lock().unwrap();
@@ -156,16 +442,17 @@ pub async fn global_msg_emit() -> Result<String, String> {
     };
 
     if let Some(x) = emit_option.as_ref() {
-        let ms = GlobalErrorMsg {
-            title: "标题".parse().unwrap(),
-            msg: "内容".parse().unwrap(),
-            duration: 5000,
-            kind: crate::structs::global_error_msg::GlobalErrorMsgTypeEnum::Success,
-        };
+        let ms = GlobalErrorMsg::localized(
+            msg::DEV_GLOBAL_MSG_TITLE,
+            msg::DEV_GLOBAL_MSG_CONTENT,
+            &[],
+            5000,
+            crate::structs::global_error_msg::GlobalErrorMsgTypeEnum::Success,
+        );
         let result = JsonUtil::stringify(&ms).expect("数据序列化失败!");
         let qqq = x.send(result).await;
         if qqq.is_err() {
-            println!("发送失败！");
+            log::warn!("发送失败！");
         }
     }
     return Ok(String::from("Ok"));
@@ -0,0 +1,46 @@
+use crate::global_front_emit;
+use crate::services::hash_verify_service;
+use crate::utils::json_util::JsonUtil;
+use tauri::{AppHandle, Emitter};
+
+/// 重新计算照片 Hash 并与导入时记录的比较，返回任务 ID
+///
+/// 进度通过 `hash-verify-progress` 事件通知，每个事件只包含刚校验的照片的结果，
+/// 完整的不一致列表通过 `get_verify_report` 获取
+/// - photo_ids 照片 ID
+/// - folder 文件夹【包含子文件夹，与 photo_ids 至少指定一个】
+#[tauri::command]
+pub fn verify_photos(
+    app: AppHandle,
+    photo_ids: Option<Vec<i32>>,
+    folder: Option<String>,
+) -> Result<String, String> {
+    let job_id = hash_verify_service::verify_photos(photo_ids, folder, move |progress| {
+        match JsonUtil::stringify(progress) {
+            Ok(payload) => {
+                if let Err(e) = app.emit(global_front_emit::HASH_VERIFY_PROGRESS, payload) {
+                    log::warn!("Hash 校验进度通知发送失败: {}", e);
+                }
+            }
+            Err(e) => log::warn!("Hash 校验进度序列化失败: {}", e),
+        }
+    })
+    .map_err(|e| {
+        log::error!("Hash 校验失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&job_id).map_err(|e| e.to_string())
+}
+
+/// 取消 Hash 校验任务
+#[tauri::command]
+pub fn cancel_verify_photos(job_id: String) -> Result<(), String> {
+    hash_verify_service::cancel_verify(&job_id).map_err(|e| e.to_string())
+}
+
+/// 获取 Hash 校验报告【校验中时返回当前进度】
+#[tauri::command]
+pub fn get_verify_report(job_id: String) -> Result<String, String> {
+    let report = hash_verify_service::get_verify_report(&job_id).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&report).map_err(|e| e.to_string())
+}
@@ -0,0 +1,44 @@
+use crate::i18n;
+use crate::i18n::{msg, Locale};
+use crate::structs::config::{save_config, sys_config};
+use crate::utils::json_util::JsonUtil;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// 消息目录返回
+#[derive(Serialize)]
+struct MessageCatalog {
+    /// 目录语言
+    locale: Locale,
+    /// 消息 ID -> 单复数模板
+    messages: BTreeMap<&'static str, i18n::MessageEntry>,
+}
+
+/// 获取消息目录
+/// - locale 语言【为空时使用当前语言】
+#[tauri::command]
+pub fn get_message_catalog(locale: Option<String>) -> Result<String, String> {
+    let locale = locale
+        .as_deref()
+        .and_then(Locale::from_str)
+        .unwrap_or_else(i18n::current_locale);
+    let catalog = MessageCatalog {
+        locale,
+        messages: i18n::catalog(locale),
+    };
+    JsonUtil::stringify(&catalog).map_err(|e| e.to_string())
+}
+
+/// 切换后端消息语言并写入配置文件
+/// - locale 语言【zh-CN、en-US】
+#[tauri::command]
+pub fn set_message_locale(locale: String) -> Result<String, String> {
+    let locale = Locale::from_str(&locale)
+        .ok_or_else(|| i18n::t(msg::LOCALE_UNSUPPORTED, &[("locale", locale.clone())]))?;
+    i18n::set_locale(locale);
+
+    let mut config = (*sys_config()).clone();
+    config.locale = Some(locale.as_str().to_string());
+    save_config(&config).map_err(|e| e.to_string())?;
+    Ok(locale.as_str().to_string())
+}
@@ -1,12 +1,19 @@
-use crate::constant::{
-    DEFAULT_THUMBNAIL_SIZE, IMAGE_COMPRESSION_RATIO, IMAGE_COMPRESSION_STORAGE_FORMAT,
-};
+use crate::constant::{DEFAULT_THUMBNAIL_SIZE, IMAGE_COMPRESSION_RATIO};
 use crate::errors::AError;
-use crate::structs::config::SYS_CONFIG;
+use crate::services::{
+    color_service, display_cache_service, histogram_service, mp_image_service,
+    resize_benchmark_service, thumbnail_cache_service, tiff_page_service,
+};
+use crate::services::thumbnail_cache_service::VisibleSource;
+use crate::structs::config::sys_config;
+use crate::structs::thumbnail_encoding;
+use crate::utils::base64_util::base64_encode;
 use crate::utils::file_hash_util::FileHashUtils;
 use crate::utils::file_util::{get_all_dir_img, get_all_subfolders};
 use crate::utils::img_util::ImageOperate;
-use crate::utils::{file_util, image_format_util};
+use crate::utils::json_util::JsonUtil;
+use crate::utils::latency_util::{LatencySpan, LatencyStage};
+use crate::utils::{file_util, image_format_util, image_probe_util, latency_util};
 use anyhow::Result;
 use tokio::task;
 
@@ -43,8 +50,7 @@ pub async fn generate_save_thumbnail(image_path: Vec<String>, emit_command: Stri
     // region 获取目录下所有的图片文件
     let mut need_to_compress_image = vec![];
     for x in need_2_compressed_dir {
-        let display = x.display().to_string();
-        let dir_img = get_all_dir_img(&display, Some(-1));
+        let dir_img = get_all_dir_img(&x, Some(-1));
         need_to_compress_image.extend(dir_img);
     }
     // endregion
@@ -55,10 +61,10 @@ pub async fn generate_save_thumbnail(image_path: Vec<String>, emit_command: Stri
     // region 生成不同规格的缩略图
     let mut result = Vec::new();
     for x in need_to_compress_image {
-        log::info!("正在压缩的文件路径 {}", x);
+        log::info!("正在压缩的文件路径 {}", x.display());
         let image_compression = ImageOperate::multi_level_image_compression(
             x,
-            IMAGE_COMPRESSION_STORAGE_FORMAT,
+            thumbnail_encoding::storage_format(),
             IMAGE_COMPRESSION_RATIO.to_vec(),
         );
         let vec1 = image_compression.await.expect("压缩文件路径获取失败");
@@ -87,13 +93,13 @@ pub async fn get_image_thumbnail_path(image_path: String) -> Result<String, Stri
 
     // 计算指定路径
     // 获取图片压缩比和图片压缩格式
-    let root_dir = SYS_CONFIG.thumbnail_storage_path.clone().ok_or_else(|| {
+    let root_dir = sys_config().thumbnail_storage_path.clone().ok_or_else(|| {
         AError::ThumbnailCacheConfigurationReadFailed
             .message()
             .to_string()
     })?;
     // 获取文件名后缀
-    let fmt = image_format_util::get_suffix_name(IMAGE_COMPRESSION_STORAGE_FORMAT);
+    let fmt = image_format_util::get_suffix_name(thumbnail_encoding::storage_format());
     let file_path =
         FileHashUtils::hash_to_file_path(&*hash, &*root_dir, &*fmt, DEFAULT_THUMBNAIL_SIZE);
 
@@ -103,9 +109,10 @@ pub async fn get_image_thumbnail_path(image_path: String) -> Result<String, Stri
 /// 获取指定图片的缩略图【如果不存在，直接创建】
 #[tauri::command]
 pub async fn get_image_thumbnail(image_path: String) -> Result<String, String> {
+    let _span = LatencySpan::enter(LatencyStage::Ipc);
     let string = ImageOperate::designate_level_image_compression(
         image_path,
-        IMAGE_COMPRESSION_STORAGE_FORMAT,
+        thumbnail_encoding::storage_format(),
         DEFAULT_THUMBNAIL_SIZE,
     )
     .await
@@ -118,6 +125,182 @@ pub async fn get_image_thumbnail(image_path: String) -> Result<String, String> {
             msg
         }
     })?;
-
     Ok(string)
 }
+
+/// 记录前端获取缩略图的往返耗时【前端收到 `get_image_thumbnail` 的结果后上报】
+/// - micros 从发起调用到收到结果的耗时（微秒）
+#[tauri::command]
+pub fn record_thumbnail_round_trip(micros: u64) {
+    latency_util::record(LatencyStage::Respond, micros);
+}
+
+/// 校验缩略图缓存，清理损坏的缩略图并重新生成
+#[tauri::command]
+pub async fn verify_thumbnail_cache() -> Result<String, String> {
+    let report = thumbnail_cache_service::verify_thumbnail_cache()
+        .await
+        .map_err(|e| {
+            log::error!("缩略图缓存校验失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&report).map_err(|e| e.to_string())
+}
+
+/// 报告前端可见范围，范围内的缩略图优先生成，返回提前的任务数量
+/// - source 前端正在显示的照片列表（文件夹，或照片列表的筛选条件和游标）
+/// - from 可见范围起始位置（包含）【照片列表只用于计算数量】
+/// - to 可见范围结束位置（不包含）
+#[tauri::command]
+pub async fn report_visible_range(
+    source: VisibleSource,
+    from: usize,
+    to: usize,
+) -> Result<String, String> {
+    let count = task::spawn_blocking(move || {
+        thumbnail_cache_service::report_visible_range(source, from, to)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&count).map_err(|e| e.to_string())
+}
+
+/// 对比各缩放实现生成缩略图的速度
+/// - sample 随机抽取的照片数量
+#[tauri::command]
+pub async fn benchmark_resize(sample: Option<u32>) -> Result<String, String> {
+    let result = task::spawn_blocking(move || resize_benchmark_service::benchmark_resize(sample))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("缩放性能对比失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&result).map_err(|e| e.to_string())
+}
+
+/// 获取打开照片各阶段耗时统计（p50 / p95）
+/// - reset 统计后是否清空已记录的样本
+#[tauri::command]
+pub fn get_latency_report(reset: Option<bool>) -> Result<String, String> {
+    let report = latency_util::report();
+    if reset.unwrap_or(false) {
+        latency_util::reset();
+    }
+    JsonUtil::stringify(&report).map_err(|e| e.to_string())
+}
+
+/// 获取照片的各通道直方图和高光、暗部溢出百分比
+/// - photo_id 照片 ID
+#[tauri::command]
+pub async fn get_photo_histogram(photo_id: i32) -> Result<String, String> {
+    let summary = task::spawn_blocking(move || histogram_service::get_photo_histogram(photo_id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("直方图获取失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
+
+/// 获取照片主色（按占比从大到小）
+/// - photo_id 照片 ID
+#[tauri::command]
+pub fn get_photo_colors(photo_id: i32) -> Result<String, String> {
+    let colors = color_service::get_photo_colors(photo_id).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&colors).map_err(|e| e.to_string())
+}
+
+/// 为还没有主色的照片提取主色（使用已生成的缩略图），返回处理的数量
+#[tauri::command]
+pub async fn index_photo_colors() -> Result<String, String> {
+    let count = task::spawn_blocking(color_service::index_photo_colors)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("主色提取失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&count).map_err(|e| e.to_string())
+}
+
+/// 获取缩放到显示尺寸的大图（JPEG Base64）【预读过的照片直接从内存返回】
+/// - photo_id 照片 ID
+/// - max_size 长边像素【为空时使用默认显示尺寸】
+#[tauri::command]
+pub async fn get_display_image(photo_id: i32, max_size: Option<u32>) -> Result<String, String> {
+    let data = task::spawn_blocking(move || {
+        display_cache_service::get_display_image(photo_id, max_size)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| {
+        log::error!("大图读取失败 {}: {}", photo_id, e);
+        e.to_string()
+    })?;
+    Ok(base64_encode(data.as_slice()))
+}
+
+/// 在后台预读照片的大图，返回加入队列的数量【前端滚动或切换照片时调用】
+/// - photo_ids 照片 ID【按预读顺序】
+/// - max_size 长边像素【为空时使用默认显示尺寸】
+#[tauri::command]
+pub fn prefetch_photos(photo_ids: Vec<i32>, max_size: Option<u32>) -> Result<usize, String> {
+    display_cache_service::prefetch_photos(&photo_ids, max_size).map_err(|e| e.to_string())
+}
+
+/// 获取多页 TIFF 的页数和每页尺寸
+/// - photo_id 照片 ID
+#[tauri::command]
+pub async fn get_tiff_pages(photo_id: i32) -> Result<String, String> {
+    let info = task::spawn_blocking(move || tiff_page_service::get_photo_pages(photo_id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("TIFF 页面读取失败 {}: {}", photo_id, e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&info).map_err(|e| e.to_string())
+}
+
+/// 获取照片中 MPF 记录的图像（双摄、人像模式、深度图等）【不是 JPEG 或没有 MPF 时为空】
+/// - photo_id 照片 ID
+#[tauri::command]
+pub async fn get_mp_images(photo_id: i32) -> Result<String, String> {
+    let images = task::spawn_blocking(move || mp_image_service::get_photo_mp_images(photo_id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("MP 图像读取失败 {}: {}", photo_id, e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&images).map_err(|e| e.to_string())
+}
+
+/// 获取照片中 MPF 记录的一张图像（JPEG Base64）
+/// - photo_id 照片 ID
+/// - index 图像序号【第一张为主图像】
+#[tauri::command]
+pub async fn get_mp_image(photo_id: i32, index: usize) -> Result<String, String> {
+    let data = task::spawn_blocking(move || mp_image_service::read_photo_mp_image(photo_id, index))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("MP 图像读取失败 {} #{}: {}", photo_id, index, e);
+            e.to_string()
+        })?;
+    Ok(base64_encode(data.as_slice()))
+}
+
+/// 只读取文件头获取图片的尺寸、方向和格式【不解码像素】
+/// - path 图片路径
+#[tauri::command]
+pub async fn probe_image(path: String) -> Result<String, String> {
+    let info = task::spawn_blocking(move || image_probe_util::probe_image(&path))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&info).map_err(|e| e.to_string())
+}
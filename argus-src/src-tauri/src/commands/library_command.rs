@@ -0,0 +1,41 @@
+use crate::services::library_service;
+use crate::utils::json_util::JsonUtil;
+use serde::Serialize;
+use std::path::Path;
+
+/// 获取所有照片库
+#[tauri::command]
+pub fn list_libraries() -> Result<String, String> {
+    to_result(library_service::list_libraries())
+}
+
+/// 新建照片库（独立的数据库、缩略图缓存和配置）
+/// - name 名称
+/// - path 照片库文件夹
+#[tauri::command]
+pub fn create_library(name: String, path: String) -> Result<String, String> {
+    to_result(library_service::create_library(&name, Path::new(&path)))
+}
+
+/// 打开已有的照片库并切换
+/// - path 照片库文件夹
+/// - name 名称【为空时使用文件夹名称】
+#[tauri::command]
+pub fn open_library(path: String, name: Option<String>) -> Result<String, String> {
+    to_result(library_service::open_library(Path::new(&path), name.as_deref()))
+}
+
+/// 切换当前照片库
+/// - path 照片库文件夹
+#[tauri::command]
+pub fn switch_library(path: String) -> Result<String, String> {
+    to_result(library_service::switch_library(Path::new(&path)))
+}
+
+fn to_result<T: Serialize>(result: anyhow::Result<T>) -> Result<String, String> {
+    let value = result.map_err(|e| {
+        log::error!("照片库操作失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&value).map_err(|e| e.to_string())
+}
@@ -1,3 +1,9 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::utils::json_util::JsonUtil;
+use crate::utils::log_util::{self, LogQuery};
+use serde_json::json;
+
 #[tauri::command]
 pub fn log_logs() {
     log::info!("正常信息");
@@ -5,3 +11,34 @@ pub fn log_logs() {
     log::error!("正常信息");
     log::debug!("正常信息");
 }
+
+/// 查询最近的日志（如扫描失败时在软件中查看错误）
+/// - filter 查询条件【最低级别、模块前缀、消息文本、数量】
+#[tauri::command]
+pub fn get_recent_logs(filter: Option<LogQuery>) -> Result<String, String> {
+    let logs = log_util::recent_logs(&filter.unwrap_or_default());
+    JsonUtil::stringify(&logs).map_err(|e| e.to_string())
+}
+
+/// 调整日志级别，立即生效【重启后恢复为设置中的级别】
+/// - module 模块，如 `argus_src_lib::services`【为空时调整默认级别】
+/// - level 级别【trace、debug、info、warn、error、off，为空时删除模块的单独设置】
+#[tauri::command]
+pub fn set_log_level(module: Option<String>, level: Option<String>) -> Result<(), String> {
+    let level = match level.as_deref() {
+        Some(x) => Some(
+            log_util::parse_level(x)
+                .ok_or_else(|| i18n::t(msg::LOG_LEVEL_INVALID, &[("level", x.to_string())]))?,
+        ),
+        None => None,
+    };
+    log_util::set_level(module.as_deref().filter(|x| !x.is_empty()), level);
+    Ok(())
+}
+
+/// 获取当前的日志级别设置
+#[tauri::command]
+pub fn get_log_levels() -> Result<String, String> {
+    let (default, modules) = log_util::get_levels();
+    JsonUtil::stringify(&json!({ "default": default, "modules": modules })).map_err(|e| e.to_string())
+}
@@ -0,0 +1,84 @@
+use crate::services::{
+    database_maintenance_service, maintenance_scheduler_service, reconcile_service,
+};
+use crate::structs::maintenance_schedule::{MaintenanceJob, MaintenanceSchedule};
+use crate::structs::reconcile::ReconcileAction;
+use crate::utils::json_util::JsonUtil;
+use tokio::task;
+
+/// 获取定时维护计划的状态（下一次执行时间、最近一次执行结果）
+#[tauri::command]
+pub fn get_schedule_status() -> Result<String, String> {
+    JsonUtil::stringify(&maintenance_scheduler_service::get_schedule_status())
+        .map_err(|e| e.to_string())
+}
+
+/// 保存定时维护计划【替换所有计划】
+/// - schedules 计划
+#[tauri::command]
+pub fn save_maintenance_schedules(schedules: Vec<MaintenanceSchedule>) -> Result<(), String> {
+    maintenance_scheduler_service::save_schedules(schedules).map_err(|e| e.to_string())
+}
+
+/// 立即执行维护任务，返回执行结果
+/// - job 任务
+#[tauri::command]
+pub async fn run_maintenance_job(job: MaintenanceJob) -> Result<String, String> {
+    let run = task::spawn_blocking(move || maintenance_scheduler_service::run_job(job))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&run).map_err(|e| e.to_string())
+}
+
+/// 优化数据库（PRAGMA optimize、ANALYZE、VACUUM），返回优化前后的大小和耗时
+#[tauri::command]
+pub async fn optimize_database() -> Result<String, String> {
+    let report = task::spawn_blocking(database_maintenance_service::optimize_database)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("数据库优化失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&report).map_err(|e| e.to_string())
+}
+
+/// 找出文件已不存在的照片，区分卷离线和已删除
+#[tauri::command]
+pub async fn find_missing_photos() -> Result<String, String> {
+    let report = task::spawn_blocking(reconcile_service::find_missing_photos)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("照片对账失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&report).map_err(|e| e.to_string())
+}
+
+/// 处理文件已不存在的照片（移入回收站或彻底删除，并清理缓存）
+/// - photo_ids 照片 ID
+/// - action 处理方式
+/// - include_offline 是否一并处理卷离线的照片【默认跳过】
+#[tauri::command]
+pub async fn resolve_missing_photos(
+    photo_ids: Vec<i32>,
+    action: ReconcileAction,
+    include_offline: Option<bool>,
+) -> Result<String, String> {
+    let result = task::spawn_blocking(move || {
+        reconcile_service::resolve_missing_photos(
+            &photo_ids,
+            action,
+            include_offline.unwrap_or(false),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| {
+        log::error!("文件不存在的照片处理失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&result).map_err(|e| e.to_string())
+}
@@ -0,0 +1,10 @@
+use crate::services::memory_service;
+use crate::utils::json_util::JsonUtil;
+
+/// 获取那年今日【之前每一年同一天拍摄的照片，按年份从新到旧】
+/// - date 日期【`YYYY-MM-DD`，为空时为今天】
+#[tauri::command]
+pub fn get_memories(date: Option<String>) -> Result<String, String> {
+    let memories = memory_service::get_memories(date.as_deref()).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&memories).map_err(|e| e.to_string())
+}
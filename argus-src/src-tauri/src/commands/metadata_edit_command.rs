@@ -0,0 +1,87 @@
+use crate::services::metadata_edit_service;
+use crate::structs::edit_journal::EditSummary;
+use crate::utils::exif_utils::gps_util::GpsCoordinate;
+use crate::utils::json_util::JsonUtil;
+
+/// 修改照片评分
+/// - photo_ids 照片 ID
+/// - rating 评分【为空表示清除评分】
+#[tauri::command]
+pub fn set_photo_rating(photo_ids: Vec<i32>, rating: Option<i32>) -> Result<String, String> {
+    to_result(metadata_edit_service::set_rating(&photo_ids, rating))
+}
+
+/// 平移照片拍摄时间
+/// - photo_ids 照片 ID
+/// - seconds 平移的秒数，可以为负数
+#[tauri::command]
+pub fn shift_photo_time(photo_ids: Vec<i32>, seconds: i64) -> Result<String, String> {
+    to_result(metadata_edit_service::shift_date_time(&photo_ids, seconds))
+}
+
+/// 修改照片位置【经纬度任一为空时清除位置】
+/// - photo_ids 照片 ID
+/// - latitude 纬度
+/// - longitude 经度
+/// - altitude 海拔（米）
+#[tauri::command]
+pub fn set_photo_gps(
+    photo_ids: Vec<i32>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    altitude: Option<f64>,
+) -> Result<String, String> {
+    let coordinate = match (latitude, longitude) {
+        (Some(latitude), Some(longitude)) => Some(GpsCoordinate {
+            latitude,
+            longitude,
+            altitude,
+        }),
+        _ => None,
+    };
+    to_result(metadata_edit_service::set_gps(&photo_ids, coordinate))
+}
+
+/// 替换照片标签
+/// - photo_ids 照片 ID
+/// - tag_ids 新的标签 ID 列表
+#[tauri::command]
+pub fn set_photo_tags(photo_ids: Vec<i32>, tag_ids: Vec<i32>) -> Result<String, String> {
+    to_result(metadata_edit_service::set_tags(&photo_ids, &tag_ids))
+}
+
+/// 修改照片说明【根据设置同时写回原图或 XMP 附属文件】
+/// - photo_ids 照片 ID
+/// - caption 说明【为空表示清除】
+#[tauri::command]
+pub fn set_photo_caption(photo_ids: Vec<i32>, caption: Option<String>) -> Result<String, String> {
+    to_result(metadata_edit_service::set_caption(&photo_ids, caption))
+}
+
+/// 修改照片备注【只保存在数据库中】
+/// - photo_ids 照片 ID
+/// - notes 备注【为空表示清除】
+#[tauri::command]
+pub fn set_photo_notes(photo_ids: Vec<i32>, notes: Option<String>) -> Result<String, String> {
+    to_result(metadata_edit_service::set_notes(&photo_ids, notes))
+}
+
+/// 撤销本次会话中最后一次元数据编辑
+#[tauri::command]
+pub fn undo_last_edit() -> Result<String, String> {
+    to_result(metadata_edit_service::undo_last_edit())
+}
+
+/// 重做最早一次被撤销的元数据编辑
+#[tauri::command]
+pub fn redo_edit() -> Result<String, String> {
+    to_result(metadata_edit_service::redo_edit())
+}
+
+fn to_result(summary: anyhow::Result<EditSummary>) -> Result<String, String> {
+    let summary = summary.map_err(|e| {
+        log::error!("元数据编辑失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
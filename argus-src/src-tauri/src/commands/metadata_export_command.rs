@@ -0,0 +1,32 @@
+use crate::services::metadata_export_service;
+use crate::utils::json_util::JsonUtil;
+
+/// 导出照片元数据（EXIF、标签、评分、位置）
+/// - path 导出文件路径
+/// - photo_ids 照片 ID【为空时导出所有照片】
+/// - format 导出格式【json、csv】
+///
+/// 返回导出的照片数量
+#[tauri::command]
+pub fn export_metadata(
+    path: String,
+    photo_ids: Option<Vec<i32>>,
+    format: String,
+) -> Result<String, String> {
+    let count =
+        metadata_export_service::export_metadata(&path, photo_ids, &format).map_err(|e| {
+            log::error!("元数据导出失败: {}", e);
+            e.to_string()
+        })?;
+    Ok(count.to_string())
+}
+
+/// 导入照片元数据，按文件 hash 合并到已有照片【根据文件内容自动识别 json、csv】
+#[tauri::command]
+pub fn import_metadata(path: String) -> Result<String, String> {
+    let summary = metadata_export_service::import_metadata(&path).map_err(|e| {
+        log::error!("元数据导入失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
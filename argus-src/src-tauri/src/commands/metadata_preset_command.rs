@@ -0,0 +1,45 @@
+use crate::services::metadata_preset_service;
+use crate::structs::metadata_preset::MetadataPreset;
+use crate::utils::json_util::JsonUtil;
+use tokio::task;
+
+/// 获取所有元数据预设
+#[tauri::command]
+pub fn list_metadata_presets() -> Result<String, String> {
+    JsonUtil::stringify(&metadata_preset_service::list_presets()).map_err(|e| e.to_string())
+}
+
+/// 新建或修改元数据预设【ID 为空时新建】，返回保存后的预设
+#[tauri::command]
+pub fn save_metadata_preset(preset: MetadataPreset) -> Result<String, String> {
+    let preset = metadata_preset_service::save_preset(preset).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&preset).map_err(|e| e.to_string())
+}
+
+/// 删除元数据预设
+#[tauri::command]
+pub fn delete_metadata_preset(preset_id: String) -> Result<(), String> {
+    metadata_preset_service::delete_preset(&preset_id).map_err(|e| e.to_string())
+}
+
+/// 为照片应用元数据预设（作者、版权、署名）【可撤销】
+/// - photo_ids 照片 ID
+/// - preset_id 预设 ID
+/// - write_back 是否同时写回原图
+#[tauri::command]
+pub async fn apply_metadata_preset(
+    photo_ids: Vec<i32>,
+    preset_id: String,
+    write_back: Option<bool>,
+) -> Result<String, String> {
+    let summary = task::spawn_blocking(move || {
+        metadata_preset_service::apply_preset(&photo_ids, &preset_id, write_back.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| {
+        log::error!("元数据预设应用失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
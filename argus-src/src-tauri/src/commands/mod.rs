@@ -7,3 +7,39 @@ pub mod log_command;
 pub mod photo_storage_command;
 pub mod post_command;
 pub mod global_task_command;
+pub mod organization_command;
+pub mod i18n_command;
+pub mod metadata_edit_command;
+pub mod backup_command;
+pub mod photo_stack_command;
+pub mod library_command;
+pub mod settings_command;
+pub mod metadata_export_command;
+pub mod external_library_command;
+pub mod slideshow_command;
+pub mod photo_export_command;
+pub mod photo_transform_command;
+pub mod metadata_preset_command;
+pub mod privacy_command;
+pub mod place_command;
+pub mod photo_sequence_command;
+pub mod card_import_command;
+pub mod share_bundle_command;
+pub mod photo_activity_command;
+pub mod duplicate_reclaim_command;
+pub mod vault_command;
+pub mod checksum_manifest_command;
+pub mod maintenance_command;
+pub mod hash_verify_command;
+pub mod photo_compare_command;
+pub mod cover_command;
+pub mod organize_rule_command;
+pub mod event_command;
+pub mod recent_feed_command;
+pub mod memory_command;
+pub mod batch_operation_command;
+pub mod photo_list_command;
+pub mod user_profile_command;
+pub mod remote_storage_command;
+pub mod watch_folder_command;
+pub mod space_saver_command;
@@ -0,0 +1,45 @@
+use crate::services::{folder_album_service, organization_service};
+use crate::utils::json_util::JsonUtil;
+use tokio::task;
+
+/// 获取相册、标签树（含数量和 ID）
+#[tauri::command]
+pub fn get_organization_tree() -> Result<String, String> {
+    let tree = organization_service::get_organization_tree().map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&tree).map_err(|e| e.to_string())
+}
+
+/// 导出相册、标签树
+/// - path 导出文件路径
+/// - format 导出格式【json、opml】
+#[tauri::command]
+pub fn export_organization(path: String, format: String) -> Result<String, String> {
+    organization_service::export_organization(&path, &format).map_err(|e| {
+        log::error!("组织结构导出失败: {}", e);
+        e.to_string()
+    })?;
+    Ok(path)
+}
+
+/// 导入相册、标签树【根据文件内容自动识别 json、opml】
+#[tauri::command]
+pub fn import_organization(path: String) -> Result<String, String> {
+    let summary = organization_service::import_organization(&path).map_err(|e| {
+        log::error!("组织结构导入失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
+
+/// 立即把文件夹结构同步为只读相册【未开启文件夹相册时删除所有文件夹相册】
+#[tauri::command]
+pub async fn sync_folder_albums() -> Result<String, String> {
+    let summary = task::spawn_blocking(folder_album_service::sync_folder_albums)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("文件夹相册同步失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
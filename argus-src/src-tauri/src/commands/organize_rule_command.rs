@@ -0,0 +1,40 @@
+use crate::services::organize_rule_service;
+use crate::structs::organize_rule::OrganizeRule;
+use crate::utils::json_util::JsonUtil;
+use tokio::task;
+
+/// 获取所有自动整理规则
+#[tauri::command]
+pub fn list_organize_rules() -> Result<String, String> {
+    let rules = organize_rule_service::list_rules().map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&rules).map_err(|e| e.to_string())
+}
+
+/// 保存自动整理规则【ID 为空时新增】，返回保存后的规则
+/// - rule 规则
+#[tauri::command]
+pub fn save_organize_rule(rule: OrganizeRule) -> Result<String, String> {
+    let rule = organize_rule_service::save_rule(rule).map_err(|e| {
+        log::error!("自动整理规则保存失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&rule).map_err(|e| e.to_string())
+}
+
+/// 删除自动整理规则
+/// - id 规则 ID
+#[tauri::command]
+pub fn delete_organize_rule(id: i32) -> Result<(), String> {
+    organize_rule_service::delete_rule(id).map_err(|e| e.to_string())
+}
+
+/// 试运行自动整理规则【不修改数据】，返回每条规则会影响的照片
+/// - rule_ids 规则 ID【为空时试运行所有启用的规则】
+#[tauri::command]
+pub async fn dry_run_organize_rules(rule_ids: Option<Vec<i32>>) -> Result<String, String> {
+    let result = task::spawn_blocking(move || organize_rule_service::dry_run(rule_ids))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&result).map_err(|e| e.to_string())
+}
@@ -0,0 +1,11 @@
+use crate::services::photo_activity_service;
+use crate::utils::json_util::JsonUtil;
+
+/// 获取照片的备注和活动记录（导入、编辑、导出、分享）
+/// - photo_id 照片 ID
+#[tauri::command]
+pub fn get_photo_activity(photo_id: i32) -> Result<String, String> {
+    let summary =
+        photo_activity_service::get_photo_activity(photo_id).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
@@ -0,0 +1,20 @@
+use crate::services::photo_compare_service;
+use crate::utils::json_util::JsonUtil;
+
+/// 对比 2 - 4 张照片，返回对齐的拍摄参数、时间差和同一级别的预览图路径
+/// - photo_ids 照片 ID【第一张作为基准】
+/// - with_difference 是否计算与第一张照片的像素差异【默认不计算】
+#[tauri::command]
+pub async fn compare_photos(
+    photo_ids: Vec<i32>,
+    with_difference: Option<bool>,
+) -> Result<String, String> {
+    let comparison =
+        photo_compare_service::compare_photos(photo_ids, with_difference.unwrap_or(false))
+            .await
+            .map_err(|e| {
+                log::error!("照片对比失败: {}", e);
+                e.to_string()
+            })?;
+    JsonUtil::stringify(&comparison).map_err(|e| e.to_string())
+}
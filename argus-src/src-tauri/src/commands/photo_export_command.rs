@@ -0,0 +1,27 @@
+use crate::services::photo_export_service;
+use crate::structs::photo_export::PhotoExportOptions;
+use crate::utils::json_util::JsonUtil;
+use std::path::PathBuf;
+use tokio::task;
+
+/// 导出照片到指定文件夹（可添加文字或 logo 水印）
+/// - photo_ids 照片 ID
+/// - dest_path 导出的文件夹
+/// - options 导出选项
+#[tauri::command]
+pub async fn export_photos(
+    photo_ids: Vec<i32>,
+    dest_path: String,
+    options: PhotoExportOptions,
+) -> Result<String, String> {
+    let summary = task::spawn_blocking(move || {
+        photo_export_service::export_photos(&photo_ids, &PathBuf::from(dest_path), &options)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| {
+        log::error!("照片导出失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
@@ -0,0 +1,35 @@
+use crate::services::photo_list_service;
+use crate::structs::photo_list::PhotoListSort;
+use crate::structs::photo_search::PhotoSearchParams;
+use crate::utils::json_util::JsonUtil;
+use tokio::task;
+
+/// 按游标分页列出照片
+/// - cursor 上一页返回的游标【为空时从第一页开始】
+/// - page_size 每页数量
+/// - sort 排序方式
+/// - filters 筛选条件
+#[tauri::command]
+pub fn list_photos(
+    cursor: Option<String>,
+    page_size: Option<i64>,
+    sort: Option<PhotoListSort>,
+    filters: Option<PhotoSearchParams>,
+) -> Result<String, String> {
+    let page = photo_list_service::list_photos(cursor.as_deref(), page_size, sort, filters)
+        .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&page).map_err(|e| e.to_string())
+}
+
+/// 为还没有 BlurHash 的照片计算 BlurHash（使用已生成的缩略图），返回处理的数量
+#[tauri::command]
+pub async fn index_photo_blurhashes() -> Result<String, String> {
+    let count = task::spawn_blocking(photo_list_service::index_photo_blurhashes)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("BlurHash 计算失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&count).map_err(|e| e.to_string())
+}
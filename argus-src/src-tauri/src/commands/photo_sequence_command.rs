@@ -0,0 +1,31 @@
+use crate::services::photo_sequence_service;
+use crate::structs::photo_sequence::SequenceKind;
+use crate::utils::json_util::JsonUtil;
+
+/// 检测全景和包围曝光序列，并给其中的照片添加标签
+/// - path 只处理此文件夹下的照片【为空表示全部】
+#[tauri::command]
+pub fn detect_photo_sequences(path: Option<String>) -> Result<String, String> {
+    let summary = photo_sequence_service::detect_sequences(path.as_deref()).map_err(|e| {
+        log::error!("连拍序列检测失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
+
+/// 获取连拍序列【按拍摄时间从新到旧】
+/// - kind 只包含此类型的序列（panorama、bracket）【为空表示全部】
+#[tauri::command]
+pub fn list_photo_sequences(kind: Option<SequenceKind>) -> Result<String, String> {
+    let sequences = photo_sequence_service::list_sequences(kind).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&sequences).map_err(|e| e.to_string())
+}
+
+/// 获取序列中的照片【按拍摄时间排序】
+/// - sequence_id 序列 ID
+#[tauri::command]
+pub fn get_sequence_photos(sequence_id: i32) -> Result<String, String> {
+    let photos =
+        photo_sequence_service::get_sequence_photos(sequence_id).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&photos).map_err(|e| e.to_string())
+}
@@ -0,0 +1,57 @@
+use crate::services::photo_stack_service;
+use crate::utils::json_util::JsonUtil;
+use serde::Serialize;
+
+/// 自动堆叠编辑副本（文件名带编辑后缀且拍摄时间与原图相同）
+/// - path 只处理此文件夹下的照片【为空表示全部】
+#[tauri::command]
+pub fn auto_stack_photos(path: Option<String>) -> Result<String, String> {
+    to_result(photo_stack_service::auto_stack(path.as_deref()))
+}
+
+/// RAW+JPEG 配对（同一文件夹中与 RAW 同名的 JPEG 关联到 RAW 照片上），返回配对的数量
+/// - path 只处理此文件夹下的照片【为空表示全部】
+#[tauri::command]
+pub fn pair_raw_jpeg_photos(path: Option<String>) -> Result<String, String> {
+    to_result(photo_stack_service::pair_raw_jpeg(path.as_deref()))
+}
+
+/// 手动堆叠照片
+/// - photo_ids 照片 ID，至少两张
+/// - original_photo_id 原图【为空时使用 ID 最小的照片】
+#[tauri::command]
+pub fn stack_photos(photo_ids: Vec<i32>, original_photo_id: Option<i32>) -> Result<String, String> {
+    to_result(photo_stack_service::stack_photos(&photo_ids, original_photo_id))
+}
+
+/// 把照片移出所在的堆叠
+/// - photo_ids 照片 ID
+#[tauri::command]
+pub fn unstack_photos(photo_ids: Vec<i32>) -> Result<(), String> {
+    photo_stack_service::unstack_photos(&photo_ids).map_err(|e| {
+        log::error!("取消堆叠失败: {}", e);
+        e.to_string()
+    })
+}
+
+/// 设置堆叠在网格中显示的版本
+/// - photo_id 要显示的照片
+#[tauri::command]
+pub fn set_stack_cover(photo_id: i32) -> Result<String, String> {
+    to_result(photo_stack_service::set_stack_cover(photo_id))
+}
+
+/// 获取照片所在堆叠中的所有版本
+/// - photo_id 堆叠中的任一照片
+#[tauri::command]
+pub fn get_stack_photos(photo_id: i32) -> Result<String, String> {
+    to_result(photo_stack_service::get_stack_photos(photo_id))
+}
+
+fn to_result<T: Serialize>(result: anyhow::Result<T>) -> Result<String, String> {
+    let value = result.map_err(|e| {
+        log::error!("版本堆叠操作失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&value).map_err(|e| e.to_string())
+}
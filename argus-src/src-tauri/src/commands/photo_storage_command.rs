@@ -1,6 +1,9 @@
+use crate::i18n;
+use crate::i18n::msg;
 use crate::models::photo_storage::PhotoStorage;
 use crate::services;
 use crate::utils::json_util::JsonUtil;
+use tokio::task;
 
 #[tauri::command]
 pub fn get_photo_storage() -> String {
@@ -24,8 +27,31 @@ pub fn delete_photo_storage(id: i32) {
 pub fn add_photo_storage(img2path: String, is_enable: bool) -> Result<String, String> {
     let result = services::photo_photo_service::add_img_path(img2path, is_enable);
     if result.is_err() {
-        Err("数据库读取失败！".to_string())
+        Err(i18n::t(msg::PHOTO_STORAGE_ADD_FAILED, &[]))
     } else {
-        Ok("插入成功.".to_string())
+        Ok(i18n::t(msg::PHOTO_STORAGE_ADD_SUCCESS, &[]))
     }
 }
+
+/// 检查图像存储路径所在的卷【离线标记、盘符变化后自动修正路径】
+#[tauri::command]
+pub fn refresh_storage_volumes() -> Result<String, String> {
+    let result = services::volume_service::refresh_storage_volumes().map_err(|e| {
+        log::error!("存储卷检查失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&result).map_err(|e| e.to_string())
+}
+
+/// 统计各卷上原图、缩略图缓存、数据库的空间占用
+#[tauri::command]
+pub async fn get_storage_breakdown() -> Result<String, String> {
+    let breakdown = task::spawn_blocking(services::disk_space_service::get_storage_breakdown)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("空间占用统计失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&breakdown).map_err(|e| e.to_string())
+}
@@ -0,0 +1,39 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::services::photo_transform_service;
+use crate::utils::exif_utils::orientation_util::ImageTransform;
+use crate::utils::json_util::JsonUtil;
+
+/// 旋转照片【JPEG 优先无损旋转，并重新生成缩略图】
+/// - photo_id 照片 ID
+/// - degrees 顺时针旋转的角度【90 的倍数，可以为负数】
+#[tauri::command]
+pub async fn rotate_photo(photo_id: i32, degrees: i32) -> Result<String, String> {
+    let transform = ImageTransform::from_degrees(degrees).ok_or_else(|| {
+        i18n::t(
+            msg::EDIT_ROTATE_INVALID,
+            &[("degrees", degrees.to_string())],
+        )
+    })?;
+    apply_transform(photo_id, transform).await
+}
+
+/// 翻转照片【JPEG 优先无损翻转，并重新生成缩略图】
+/// - photo_id 照片 ID
+/// - axis 翻转方向【horizontal：左右翻转，vertical：上下翻转】
+#[tauri::command]
+pub async fn flip_photo(photo_id: i32, axis: String) -> Result<String, String> {
+    let transform = ImageTransform::from_axis(&axis)
+        .ok_or_else(|| i18n::t(msg::EDIT_FLIP_INVALID, &[("axis", axis.clone())]))?;
+    apply_transform(photo_id, transform).await
+}
+
+async fn apply_transform(photo_id: i32, transform: ImageTransform) -> Result<String, String> {
+    let result = photo_transform_service::transform_photo(photo_id, transform)
+        .await
+        .map_err(|e| {
+            log::error!("照片 {} 变换失败: {}", photo_id, e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&result).map_err(|e| e.to_string())
+}
@@ -0,0 +1,43 @@
+use crate::services::{geocode_service, place_service};
+use crate::utils::json_util::JsonUtil;
+use std::path::PathBuf;
+use tokio::task;
+
+/// 按拍摄位置重新聚类地点，并为新地点命名
+#[tauri::command]
+pub async fn refresh_places() -> Result<String, String> {
+    let summary = place_service::refresh_places().await.map_err(|e| {
+        log::error!("地点聚类失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
+
+/// 获取所有地点【按照片数量从多到少】
+#[tauri::command]
+pub fn list_places() -> Result<String, String> {
+    let places = place_service::list_places().map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&places).map_err(|e| e.to_string())
+}
+
+/// 获取地点中的照片【按拍摄时间排序】
+/// - place_id 地点 ID
+#[tauri::command]
+pub fn get_place_photos(place_id: i32) -> Result<String, String> {
+    let photos = place_service::get_place_photos(place_id).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&photos).map_err(|e| e.to_string())
+}
+
+/// 从离线数据（CSV：纬度,经度,名称）导入逆地理编码缓存
+/// - path CSV 文件路径
+#[tauri::command]
+pub async fn seed_geocode_cache(path: String) -> Result<String, String> {
+    let summary = task::spawn_blocking(move || geocode_service::seed_cache(&PathBuf::from(path)))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("导入离线地名失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
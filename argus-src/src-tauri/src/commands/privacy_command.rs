@@ -0,0 +1,33 @@
+use crate::services::privacy_service;
+use crate::structs::privacy_zone::PrivacyZone;
+use crate::utils::json_util::JsonUtil;
+
+/// 获取所有隐私区域
+#[tauri::command]
+pub fn list_privacy_zones() -> Result<String, String> {
+    JsonUtil::stringify(&privacy_service::list_zones()).map_err(|e| e.to_string())
+}
+
+/// 新建或修改隐私区域【ID 为空时新建】，返回保存后的区域
+#[tauri::command]
+pub fn save_privacy_zone(zone: PrivacyZone) -> Result<String, String> {
+    let zone = privacy_service::save_zone(zone).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&zone).map_err(|e| e.to_string())
+}
+
+/// 删除隐私区域
+#[tauri::command]
+pub fn delete_privacy_zone(zone_id: String) -> Result<(), String> {
+    privacy_service::delete_zone(&zone_id).map_err(|e| e.to_string())
+}
+
+/// 删除照片位置【数据库中的位置和之后导出的副本，不修改原图；可撤销】
+/// - photo_ids 照片 ID
+#[tauri::command]
+pub fn strip_gps(photo_ids: Vec<i32>) -> Result<String, String> {
+    let summary = privacy_service::strip_gps(&photo_ids).map_err(|e| {
+        log::error!("照片位置删除失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
@@ -0,0 +1,43 @@
+use crate::services::recent_feed_service;
+use crate::utils::json_util::JsonUtil;
+
+/// 获取最近导入的批次【按导入时间从新到旧，每批包含几张预览照片】
+/// - limit 返回数量
+/// - offset 跳过数量
+#[tauri::command]
+pub fn get_recent_imports(limit: Option<i64>, offset: Option<i64>) -> Result<String, String> {
+    let page = recent_feed_service::get_recent_imports(limit, offset).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&page).map_err(|e| e.to_string())
+}
+
+/// 获取导入批次中的照片【按导入顺序】
+/// - batch_id 批次 ID
+/// - limit 返回数量
+/// - offset 跳过数量
+#[tauri::command]
+pub fn get_import_batch_photos(
+    batch_id: i32,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Result<String, String> {
+    let page = recent_feed_service::get_import_batch_photos(batch_id, limit, offset)
+        .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&page).map_err(|e| e.to_string())
+}
+
+/// 记录照片被浏览【打开大图时调用】
+/// - photo_id 照片 ID
+#[tauri::command]
+pub fn record_photo_view(photo_id: i32) -> Result<(), String> {
+    recent_feed_service::record_view(photo_id).map_err(|e| e.to_string())
+}
+
+/// 获取最近浏览的照片【按浏览时间从新到旧】
+/// - limit 返回数量
+/// - offset 跳过数量
+#[tauri::command]
+pub fn get_recently_viewed(limit: Option<i64>, offset: Option<i64>) -> Result<String, String> {
+    let page =
+        recent_feed_service::get_recently_viewed(limit, offset).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&page).map_err(|e| e.to_string())
+}
@@ -0,0 +1,83 @@
+use crate::services::remote_storage_service;
+use crate::structs::remote_storage::RemoteStorage;
+use crate::utils::json_util::JsonUtil;
+use std::path::PathBuf;
+
+/// 获取所有远程存储【不返回密钥】
+#[tauri::command]
+pub fn list_remote_storages() -> Result<String, String> {
+    let storages = remote_storage_service::list_storages().map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&storages).map_err(|e| e.to_string())
+}
+
+/// 保存远程存储【ID 为空时新增】，返回保存后的配置
+/// - storage 远程存储配置【修改时密钥为空表示不修改】
+#[tauri::command]
+pub fn save_remote_storage(storage: RemoteStorage) -> Result<String, String> {
+    let storage = remote_storage_service::save_storage(storage).map_err(|e| {
+        log::error!("远程存储保存失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&storage).map_err(|e| e.to_string())
+}
+
+/// 删除远程存储【远程文件不删除】
+/// - id 远程存储 ID
+#[tauri::command]
+pub fn delete_remote_storage(id: i32) -> Result<(), String> {
+    remote_storage_service::delete_storage(id).map_err(|e| e.to_string())
+}
+
+/// 测试远程存储能否访问，返回远程目录中的图片数量
+/// - storage 远程存储配置
+#[tauri::command]
+pub async fn test_remote_storage(storage: RemoteStorage) -> Result<usize, String> {
+    remote_storage_service::test_storage(storage)
+        .await
+        .map_err(|e| {
+            log::warn!("远程存储访问失败: {}", e);
+            e.to_string()
+        })
+}
+
+/// 同步远程存储中的照片，读取文件头并生成本地缩略图
+/// - id 远程存储 ID
+#[tauri::command]
+pub async fn sync_remote_storage(id: i32) -> Result<String, String> {
+    let summary = remote_storage_service::sync_storage(id)
+        .await
+        .map_err(|e| {
+            log::error!("远程存储同步失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
+
+/// 获取远程存储中的照片
+/// - storage_id 远程存储 ID
+#[tauri::command]
+pub fn list_remote_photos(storage_id: i32) -> Result<String, String> {
+    let photos =
+        remote_storage_service::list_remote_photos(storage_id).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&photos).map_err(|e| e.to_string())
+}
+
+/// 上传本地文件到远程存储
+/// - storage_id 远程存储 ID
+/// - files 本地文件
+/// - folder 上传到的子目录【为空时上传到目录前缀】
+#[tauri::command]
+pub async fn upload_to_remote_storage(
+    storage_id: i32,
+    files: Vec<String>,
+    folder: Option<String>,
+) -> Result<String, String> {
+    let files: Vec<PathBuf> = files.into_iter().map(PathBuf::from).collect();
+    let summary = remote_storage_service::upload_files(storage_id, &files, folder.as_deref())
+        .await
+        .map_err(|e| {
+            log::error!("上传到远程存储失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
@@ -0,0 +1,26 @@
+use crate::global_front_emit;
+use crate::services::{http_server_service, settings_service};
+use crate::utils::json_util::JsonUtil;
+use tauri::{AppHandle, Emitter};
+
+/// 重新加载配置文件，无需重启即可生效，并通知前端配置已变化
+#[tauri::command]
+pub fn reload_settings(app: AppHandle) -> Result<String, String> {
+    let result = settings_service::reload_settings().map_err(|e| {
+        log::error!("配置重新加载失败: {}", e);
+        e.to_string()
+    })?;
+    let payload = JsonUtil::stringify(&result).map_err(|e| e.to_string())?;
+    if !result.changed.is_empty() {
+        if let Err(e) = app.emit(global_front_emit::SETTINGS_CHANGED, payload.clone()) {
+            log::warn!("配置变化通知发送失败: {}", e);
+        }
+    }
+    Ok(payload)
+}
+
+/// 获取局域网 HTTP 服务状态（端口、令牌）
+#[tauri::command]
+pub fn get_http_server_status() -> Result<String, String> {
+    JsonUtil::stringify(&http_server_service::get_http_server_status()).map_err(|e| e.to_string())
+}
@@ -0,0 +1,24 @@
+use crate::services::share_bundle_service;
+use crate::structs::share_bundle::ShareBundleOptions;
+use crate::utils::json_util::JsonUtil;
+use tokio::task;
+
+/// 生成分享包（缩小后的照片和离线浏览的 index.html 相册，不包含任何元数据）
+/// - photo_ids 照片 ID【按顺序排列】
+/// - options 分享包选项
+#[tauri::command]
+pub async fn create_share_bundle(
+    photo_ids: Vec<i32>,
+    options: ShareBundleOptions,
+) -> Result<String, String> {
+    let summary = task::spawn_blocking(move || {
+        share_bundle_service::create_share_bundle(&photo_ids, &options)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| {
+        log::error!("分享包生成失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
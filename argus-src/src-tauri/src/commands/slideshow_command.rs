@@ -0,0 +1,39 @@
+use crate::global_front_emit;
+use crate::services::slideshow_service;
+use crate::structs::slideshow::SlideshowOptions;
+use crate::utils::json_util::JsonUtil;
+use tauri::{AppHandle, Emitter};
+
+/// 把照片生成幻灯片视频（MP4），返回任务 ID
+///
+/// 进度通过 `slideshow-progress` 事件通知
+/// - photo_ids 照片 ID【按顺序播放】
+/// - options 视频选项
+#[tauri::command]
+pub fn create_slideshow(
+    app: AppHandle,
+    photo_ids: Vec<i32>,
+    options: SlideshowOptions,
+) -> Result<String, String> {
+    let job_id = slideshow_service::create_slideshow(&photo_ids, options, move |msg| {
+        match JsonUtil::stringify(&msg) {
+            Ok(payload) => {
+                if let Err(e) = app.emit(global_front_emit::SLIDESHOW_PROGRESS, payload) {
+                    log::warn!("幻灯片进度通知发送失败: {}", e);
+                }
+            }
+            Err(e) => log::warn!("幻灯片进度序列化失败: {}", e),
+        }
+    })
+    .map_err(|e| {
+        log::error!("幻灯片视频生成失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&job_id).map_err(|e| e.to_string())
+}
+
+/// 取消幻灯片视频任务
+#[tauri::command]
+pub fn cancel_slideshow(job_id: String) -> Result<(), String> {
+    slideshow_service::cancel_slideshow(&job_id).map_err(|e| e.to_string())
+}
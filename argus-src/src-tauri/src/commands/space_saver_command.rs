@@ -0,0 +1,42 @@
+use crate::services::space_saver_service;
+use crate::utils::json_util::JsonUtil;
+use tokio::task;
+
+/// 生成节省空间计划，返回可以节省的空间和文件列表
+///
+/// 新文件写入并校验后保存在原文件旁边，确认前不修改原文件
+/// - photo_ids 照片 ID【为空时检查所有 JPEG 和截图类 PNG】
+#[tauri::command]
+pub async fn prepare_space_saver(photo_ids: Option<Vec<i32>>) -> Result<String, String> {
+    let plan = task::spawn_blocking(move || space_saver_service::prepare(photo_ids))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| {
+            log::error!("节省空间计划生成失败: {}", e);
+            e.to_string()
+        })?;
+    JsonUtil::stringify(&plan).map_err(|e| e.to_string())
+}
+
+/// 获取等待确认的节省空间计划【没有时返回 null】
+#[tauri::command]
+pub fn get_space_saver_plan() -> Result<String, String> {
+    JsonUtil::stringify(&space_saver_service::pending_plan()).map_err(|e| e.to_string())
+}
+
+/// 确认节省空间计划，用新文件替换原文件
+/// - plan_id 计划 ID
+#[tauri::command]
+pub async fn commit_space_saver(plan_id: String) -> Result<String, String> {
+    let summary = space_saver_service::commit(&plan_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
+
+/// 放弃节省空间计划，删除已生成的新文件
+/// - plan_id 计划 ID
+#[tauri::command]
+pub fn discard_space_saver(plan_id: String) -> Result<(), String> {
+    space_saver_service::discard(&plan_id).map_err(|e| e.to_string())
+}
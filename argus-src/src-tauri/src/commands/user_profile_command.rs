@@ -0,0 +1,36 @@
+use crate::services::user_profile_service;
+use crate::structs::user_profile::UserProfile;
+use crate::utils::json_util::JsonUtil;
+
+/// 获取所有用户
+#[tauri::command]
+pub fn list_user_profiles() -> Result<String, String> {
+    let profiles = user_profile_service::list_profiles().map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&profiles).map_err(|e| e.to_string())
+}
+
+/// 获取用户资料和偏好设置
+/// - profile_id 用户 ID【为空时返回默认用户】
+#[tauri::command]
+pub fn get_user_profile(profile_id: Option<i32>) -> Result<String, String> {
+    let profile = user_profile_service::get_profile(profile_id).map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&profile).map_err(|e| e.to_string())
+}
+
+/// 保存用户资料和偏好设置【ID 为空时新增】，返回保存后的资料
+/// - profile 用户资料
+#[tauri::command]
+pub fn save_user_profile(profile: UserProfile) -> Result<String, String> {
+    let profile = user_profile_service::save_profile(profile).map_err(|e| {
+        log::error!("用户资料保存失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&profile).map_err(|e| e.to_string())
+}
+
+/// 删除用户【默认用户不能删除】
+/// - id 用户 ID
+#[tauri::command]
+pub fn delete_user_profile(id: i32) -> Result<(), String> {
+    user_profile_service::delete_profile(id).map_err(|e| e.to_string())
+}
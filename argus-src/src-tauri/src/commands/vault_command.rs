@@ -0,0 +1,79 @@
+use crate::services::vault_service;
+use crate::utils::json_util::JsonUtil;
+use tokio::task;
+
+/// 获取保险箱状态（是否已创建、是否已解锁、照片数量）
+#[tauri::command]
+pub fn get_vault_status() -> Result<String, String> {
+    let status = vault_service::vault_status().map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&status).map_err(|e| e.to_string())
+}
+
+/// 创建保险箱并解锁
+/// - passphrase 密码【忘记后无法找回保险箱中的照片】
+#[tauri::command]
+pub async fn create_vault(passphrase: String) -> Result<(), String> {
+    task::spawn_blocking(move || vault_service::create_vault(&passphrase))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// 解锁保险箱，返回保险箱中的照片（缩略图已解密到临时文件夹）
+/// - passphrase 密码
+#[tauri::command]
+pub async fn unlock_vault(passphrase: String) -> Result<String, String> {
+    let items = task::spawn_blocking(move || vault_service::unlock_vault(&passphrase))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&items).map_err(|e| e.to_string())
+}
+
+/// 锁定保险箱，删除所有解密后的临时文件
+#[tauri::command]
+pub fn lock_vault() {
+    vault_service::lock_vault();
+}
+
+/// 获取保险箱中的照片【需要先解锁】
+#[tauri::command]
+pub async fn list_vault_items() -> Result<String, String> {
+    let items = task::spawn_blocking(vault_service::list_vault_items)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&items).map_err(|e| e.to_string())
+}
+
+/// 把保险箱中的原图解密到临时文件夹，返回临时文件路径【需要先解锁】
+/// - item_id 保险箱照片 ID
+#[tauri::command]
+pub async fn open_vault_item(item_id: i32) -> Result<String, String> {
+    task::spawn_blocking(move || vault_service::open_vault_item(item_id))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// 把照片加密移入保险箱【需要先解锁】
+/// - photo_ids 照片 ID
+#[tauri::command]
+pub async fn move_to_vault(photo_ids: Vec<i32>) -> Result<String, String> {
+    let summary = task::spawn_blocking(move || vault_service::move_to_vault(&photo_ids))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
+
+/// 把照片移出保险箱，解密回原来的位置【需要先解锁】
+/// - item_ids 保险箱照片 ID
+#[tauri::command]
+pub async fn restore_from_vault(item_ids: Vec<i32>) -> Result<String, String> {
+    let summary = task::spawn_blocking(move || vault_service::restore_from_vault(&item_ids))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&summary).map_err(|e| e.to_string())
+}
@@ -0,0 +1,30 @@
+use crate::services::watch_folder_service;
+use crate::structs::watch_folder::WatchFolder;
+use crate::utils::json_util::JsonUtil;
+
+/// 获取所有监视文件夹
+#[tauri::command]
+pub fn list_watch_folders() -> Result<String, String> {
+    let folders = watch_folder_service::list_folders().map_err(|e| e.to_string())?;
+    JsonUtil::stringify(&folders).map_err(|e| e.to_string())
+}
+
+/// 保存监视文件夹【ID 为空时新增】，返回保存后的监视文件夹
+///
+/// 保存后立即重新开始监视，文件夹中已有的图片也会按导入配置导入
+/// - folder 监视文件夹及导入配置
+#[tauri::command]
+pub fn save_watch_folder(folder: WatchFolder) -> Result<String, String> {
+    let folder = watch_folder_service::save_folder(folder).map_err(|e| {
+        log::error!("监视文件夹保存失败: {}", e);
+        e.to_string()
+    })?;
+    JsonUtil::stringify(&folder).map_err(|e| e.to_string())
+}
+
+/// 删除监视文件夹【已导入的照片不变】
+/// - id 监视文件夹 ID
+#[tauri::command]
+pub fn delete_watch_folder(id: i32) -> Result<(), String> {
+    watch_folder_service::delete_folder(id).map_err(|e| e.to_string())
+}
@@ -1,3 +1,6 @@
+use crate::structs::metadata_preset::MetadataPreset;
+use crate::structs::maintenance_schedule::MaintenanceSchedule;
+use crate::structs::privacy_zone::PrivacyZone;
 use once_cell::sync::Lazy;
 use std::sync::{Arc, RwLock};
 
@@ -21,6 +24,66 @@ pub struct Conf {
     pub directory_level: u32,
     /// Python 服务地址
     pub python_service_path: String,
+    /// 导入时图像校验级别【none、header、full】
+    pub image_validation_level: String,
+    /// 界面语言【zh-CN、en-US】
+    pub locale: String,
+    /// 扫描时排除的文件、文件夹【gitignore 语法】
+    pub scan_exclude_patterns: Vec<String>,
+    /// 自动备份保存路径【为空时使用程序目录下的 backup 文件夹】
+    pub backup_path: String,
+    /// 自动备份间隔（小时）【0 表示不自动备份】
+    pub backup_interval_hours: u32,
+    /// 自动备份保留数量
+    pub backup_keep_count: u32,
+    /// 备份时是否包含配置文件
+    pub backup_include_config: bool,
+    /// 照片说明修改后写回的位置【none、file、sidecar】
+    pub caption_write_back: String,
+    /// 编辑副本的文件名后缀【自动堆叠时用于匹配原图】
+    pub stack_edit_suffixes: Vec<String>,
+    /// 启动时校验照片文件完整性
+    pub integrity_check_on_startup: bool,
+    /// 每次校验的照片数量【0 表示全部】
+    pub integrity_check_sample: u32,
+    /// 默认日志级别
+    pub log_level: String,
+    /// 启动局域网 HTTP 服务
+    pub http_server_enabled: bool,
+    /// HTTP 服务监听地址
+    pub http_server_address: String,
+    /// HTTP 服务端口
+    pub http_server_port: u16,
+    /// HTTP 服务访问令牌【为空时自动生成】
+    pub http_server_token: String,
+    /// 元数据预设
+    pub metadata_presets: Vec<MetadataPreset>,
+    /// 隐私区域
+    pub privacy_zones: Vec<PrivacyZone>,
+    /// 逆地理编码服务地址【默认不使用】
+    pub reverse_geocode_url: String,
+    /// 定时维护计划
+    pub maintenance_schedules: Vec<MaintenanceSchedule>,
+    /// 缩略图格式【jpeg、webp、avif】
+    pub thumbnail_format: String,
+    /// 缩略图编码质量（1 - 100）
+    pub thumbnail_quality: u8,
+    /// 缩略图编码速度（1 - 10）【越小压缩越好、越慢，用于 WebP、AVIF】
+    pub thumbnail_speed: u8,
+    /// 截图、图形类图片使用无损 WebP【只在缩略图格式为 WebP 时生效】
+    pub thumbnail_lossless_graphics: bool,
+    /// 缩略图缩放实现【image、simd、gpu】
+    pub resize_backend: String,
+    /// 缩略图缓存、导入目标所在磁盘需保留的最小剩余空间（MB）【0 表示不检查】
+    pub min_free_space_mb: u64,
+    /// 剩余空间不足时拒绝生成缩略图和导入【false 时只提醒】
+    pub low_disk_space_refuse: bool,
+    /// 是否把图像存储路径的文件夹结构同步为只读相册
+    pub mirror_folder_albums: bool,
+    /// 扫描时是否跳过云盘占位文件
+    pub skip_cloud_placeholders: bool,
+    /// RAW+JPEG 配对时缩略图、预览优先使用的图像（jpeg 同名 JPEG、raw 内嵌预览图）
+    pub raw_jpeg_primary: String,
 }
 
 pub(crate) static CONF: Lazy<Arc<RwLock<Conf>>> = Lazy::new(|| Arc::new(RwLock::new(Conf::default())));
@@ -28,7 +91,7 @@ pub(crate) static CONF_DEFAULT: Lazy<Conf> = Lazy::new(|| Conf::default());
 
 impl Conf {
     pub fn default() -> Conf {
-        println!("获取默认配置");
+        log::debug!("获取默认配置");
         Conf {
             database_default_link: "db/sqlite.db".to_string(),
             database_name: "sqlite.db".to_string(),
@@ -39,6 +102,49 @@ impl Conf {
             time_basic_fmt: "%Y-%m-%d %H:%M:%S".to_string(),
             directory_level: 3,
             python_service_path: String::from("http://127.0.0.1:5000/"),
+            image_validation_level: String::from("header"),
+            locale: String::from("zh-CN"),
+            scan_exclude_patterns: [
+                "@eaDir/",
+                "#recycle/",
+                "$RECYCLE.BIN/",
+                "System Volume Information/",
+                ".thumbnails/",
+                "node_modules/",
+            ]
+            .iter()
+            .map(|x| x.to_string())
+            .collect(),
+            backup_path: String::from(""),
+            backup_interval_hours: 24,
+            backup_keep_count: 7,
+            backup_include_config: true,
+            caption_write_back: String::from("none"),
+            stack_edit_suffixes: ["_edit", "-edit", "_edited", "-edited", " (edited)"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+            integrity_check_on_startup: false,
+            integrity_check_sample: 200,
+            log_level: String::from("info"),
+            http_server_enabled: false,
+            http_server_address: String::from("127.0.0.1"),
+            http_server_port: 8421,
+            http_server_token: String::from(""),
+            metadata_presets: Vec::new(),
+            privacy_zones: Vec::new(),
+            reverse_geocode_url: String::from(""),
+            maintenance_schedules: Vec::new(),
+            resize_backend: String::from("simd"),
+            thumbnail_format: String::from("jpeg"),
+            thumbnail_quality: 85,
+            thumbnail_speed: 6,
+            thumbnail_lossless_graphics: true,
+            min_free_space_mb: 1024,
+            low_disk_space_refuse: true,
+            mirror_folder_albums: false,
+            skip_cloud_placeholders: true,
+            raw_jpeg_primary: String::from("jpeg"),
         }
     }
 }
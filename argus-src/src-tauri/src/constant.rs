@@ -88,8 +88,8 @@ pub const CURRENT_DB_VERSION: u32 = 1;
 /// 默认 `db_version` 元素的 `id` 因为只能由一个，ID 唯一
 pub const BASE_DB_VERSION_ITEM_ID: u32 = 1;
 
-/// 基础设置 ID
-pub const BASIC_SETTING_ID: i32 = 1;
+/// 默认用户 ID【数据库初始化时创建，不能删除】
+pub const DEFAULT_PROFILE_ID: i32 = 1;
 
 /// 图像压缩比例
 pub const IMAGE_COMPRESSION_RATIO: [ImageSize; 3] = [
@@ -98,11 +98,447 @@ pub const IMAGE_COMPRESSION_RATIO: [ImageSize; 3] = [
     ImageSize { size: 512 },
 ];
 
-/// 图像压缩存储格式
+/// 图像压缩存储格式【配置中的缩略图格式无效时使用】
 pub const IMAGE_COMPRESSION_STORAGE_FORMAT: ImageFormat = ImageFormat::Jpeg;
 
+/// 缩略图缓存版本【缩放、编码实现变化导致旧缩略图需要重新生成时增加】
+pub const THUMBNAIL_CACHE_VERSION: u32 = 1;
+
+/// 缩略图缓存版本目录名前缀
+pub const THUMBNAIL_GENERATION_PREFIX: &str = "g";
+
+/// 缩略图写入时使用的临时文件前缀
+pub const THUMBNAIL_TEMP_FILE_PREFIX: &str = ".argus-tmp-";
+
+/// 缩略图临时文件超过该时间（秒）未修改才视为写入中断【较新的可能正在写入】
+pub const THUMBNAIL_TEMP_FILE_MAX_AGE_SECS: u64 = 10 * 60;
+
+/// 与原文件同目录的临时文件扩展名【写入完成后重命名替换原文件】
+pub const TEMP_FILE_EXTENSION: &str = "argus-tmp";
+
 /// 默认缩略图大小
 pub const DEFAULT_THUMBNAIL_SIZE: u32 = IMAGE_COMPRESSION_RATIO[2].size;
 
 /// 默认配置文件名称
 pub const DEFAULT_PROFILE_NAME: &str = "conf-argus.toml";
+
+/// 扫描报告中保留的最慢文件、文件夹数量
+pub const SCAN_REPORT_TOP_N: usize = 50;
+
+/// 目录级扫描排除规则文件名
+pub const SCAN_IGNORE_FILE_NAME: &str = ".argusignore";
+
+/// 扫描预估参考的最近扫描记录数量
+pub const SCAN_PREVIEW_HISTORY: i64 = 10;
+
+/// 扫描预估参考的扫描记录最少文件数量【文件太少时耗时误差大】
+pub const SCAN_PREVIEW_MIN_HISTORY_FILES: i32 = 100;
+
+/// 没有扫描记录时预估使用的处理速度（文件/秒）
+pub const SCAN_PREVIEW_DEFAULT_FILES_PER_SEC: f64 = 20.0;
+
+/// 扫描时目录遍历与图片处理之间的队列长度
+pub const SCAN_WALK_CHANNEL_SIZE: usize = 1024;
+
+/// 同时生成缩略图的文件数量
+pub const THUMBNAIL_QUEUE_CONCURRENCY: usize = 20;
+
+/// 可见范围最多提前处理的文件数量
+pub const THUMBNAIL_VISIBLE_RANGE_MAX: usize = 500;
+
+/// 可见范围使用的文件夹图片列表缓存时间（秒）【滚动时不重复遍历文件夹】
+pub const THUMBNAIL_VISIBLE_FOLDER_CACHE_SECS: u64 = 30;
+
+/// 缩放性能对比默认抽取的照片数量
+pub const RESIZE_BENCHMARK_DEFAULT_SAMPLE: u32 = 20;
+
+/// 磁盘剩余空间检查结果的缓存时长（秒）【生成缩略图时不必每张都查询磁盘】
+pub const DISK_SPACE_CHECK_INTERVAL_SECS: u64 = 10;
+
+/// 同一路径剩余空间不足提醒的最短间隔（秒）
+pub const LOW_DISK_SPACE_EMIT_INTERVAL_SECS: u64 = 60;
+
+/// 元数据导入导出时每批查询的照片数量【避免超出 SQLite 参数数量上限】
+pub const METADATA_BATCH_SIZE: usize = 500;
+
+/// SQLite 单条语句可以绑定的参数数量上限【3.32 之前的默认值】
+pub const SQLITE_MAX_VARIABLES: usize = 999;
+
+/// 扫描结果每个事务最多写入的照片数量
+pub const PHOTO_INSERT_BATCH_SIZE: usize = 500;
+
+/// 照片评分的最大值【0 - 5 星】
+pub const MAX_RATING: i32 = 5;
+
+/// 从其他软件导入的人物名称所在的根标签
+pub const FACE_TAG_ROOT_NAME: &str = "人物";
+
+/// 内存中保留的已结束扫描任务数量【用于前端刷新后查询任务状态】
+pub const SCAN_TASK_HISTORY_LIMIT: usize = 20;
+
+/// 扫描结束后检查扫描结果是否已全部写入数据库的间隔（毫秒）
+pub const SCAN_WRITER_IDLE_POLL_MS: u64 = 100;
+
+/// 幻灯片视频默认每张照片显示的秒数
+pub const SLIDESHOW_DEFAULT_SLIDE_SECONDS: f32 = 3.0;
+
+/// 幻灯片视频默认分辨率（宽、高）
+pub const SLIDESHOW_DEFAULT_SIZE: (u32, u32) = (1920, 1080);
+
+/// 幻灯片视频默认帧率
+pub const SLIDESHOW_DEFAULT_FPS: u32 = 30;
+
+/// Ken Burns 效果每张照片的最大缩放倍数
+pub const SLIDESHOW_KEN_BURNS_ZOOM: f32 = 1.2;
+
+/// 水印默认不透明度
+pub const WATERMARK_DEFAULT_OPACITY: f32 = 0.6;
+
+/// 水印默认大小（文字、logo 高度相对图像短边的比例）
+pub const WATERMARK_DEFAULT_SCALE: f32 = 0.04;
+
+/// 水印默认边距（相对图像短边的比例）
+pub const WATERMARK_DEFAULT_MARGIN: f32 = 0.02;
+
+/// 隐私区域模糊位置时经纬度保留的小数位数【约 1 公里】
+pub const PRIVACY_BLUR_DECIMALS: u32 = 2;
+
+/// 导出照片默认的 JPEG 质量
+pub const EXPORT_DEFAULT_JPEG_QUALITY: u8 = 92;
+
+/// 分享包中照片默认的长边尺寸（像素）
+pub const SHARE_BUNDLE_DEFAULT_MAX_SIZE: u32 = 2048;
+
+/// 分享包文件夹名称前缀
+pub const SHARE_BUNDLE_PREFIX: &str = "argus-share-";
+
+/// 直方图每个通道的分组数
+pub const HISTOGRAM_BINS: usize = 64;
+
+/// 计算直方图时最多采样的像素数【超出时按间隔采样】
+pub const HISTOGRAM_SAMPLE_PIXELS: u64 = 1_000_000;
+
+/// 溢出判定的容差【高光 >= 255 - 容差，暗部 <= 容差】
+pub const HISTOGRAM_CLIP_TOLERANCE: u8 = 2;
+
+/// 提取主色使用的缩略图大小
+pub const DOMINANT_COLOR_THUMBNAIL_SIZE: u32 = IMAGE_COMPRESSION_RATIO[0].size;
+
+/// BlurHash 的水平、垂直分量数【分量越多占位图越清晰，字符串越长】
+pub const BLURHASH_COMPONENTS: (u32, u32) = (4, 3);
+
+/// 主色聚类数量【占比过小、颜色相近的聚类会被去掉，保留 3 - 5 个】
+pub const DOMINANT_COLOR_CLUSTERS: usize = 5;
+
+/// 至少保留的主色数量
+pub const DOMINANT_COLOR_MIN_COUNT: usize = 3;
+
+/// 主色的最小占比【小于此占比的聚类会被去掉】
+pub const DOMINANT_COLOR_MIN_WEIGHT: f32 = 0.05;
+
+/// 颜色搜索默认容差（CIE76 ΔE）
+pub const COLOR_SEARCH_DEFAULT_TOLERANCE: f32 = 15.0;
+
+/// 地点聚类的邻域半径（米）
+pub const PLACE_CLUSTER_EPS_METERS: f64 = 500.0;
+
+/// 地点至少包含的照片数量【少于此数量的照片不归入任何地点】
+pub const PLACE_MIN_PHOTOS: usize = 3;
+
+/// 逆地理编码请求间隔（毫秒）【Nominatim 要求每秒不超过 1 次】
+pub const REVERSE_GEOCODE_INTERVAL_MS: u64 = 1100;
+
+/// 连拍序列中相邻两张的最大拍摄间隔（秒）
+pub const SEQUENCE_MAX_GAP_SECS: i64 = 5;
+
+/// 连拍序列中拍摄间隔的最大差值（秒）【间隔不均匀时不视为同一组】
+pub const SEQUENCE_INTERVAL_TOLERANCE_SECS: i64 = 2;
+
+/// 连拍序列至少包含的照片数量
+pub const SEQUENCE_MIN_SHOTS: usize = 3;
+
+/// 包围曝光相邻两档的最小曝光差（EV）
+pub const BRACKET_MIN_EV_STEP: f64 = 0.7;
+
+/// 包围曝光各档曝光差的最大偏差（EV）【曝光值需要等间距变化】
+pub const BRACKET_EV_STEP_TOLERANCE: f64 = 0.35;
+
+/// 全景序列的平均拍摄间隔下限（秒）【排除曝光不变的高速连拍】
+pub const PANORAMA_MIN_INTERVAL_SECS: i64 = 1;
+
+/// 待拼接全景的标签
+pub const PANORAMA_TAG_NAME: &str = "全景待拼接";
+
+/// 待合成包围曝光的标签
+pub const BRACKET_TAG_NAME: &str = "HDR 待合成";
+
+/// 存储卡中存放照片的文件夹（DCF 规范）
+pub const DCIM_FOLDER_NAME: &str = "DCIM";
+
+/// 存储卡导入校验失败后的重试次数
+pub const CARD_IMPORT_RETRIES: usize = 1;
+
+/// Windows 弹出卷后确认卷已移除的最长等待时间（毫秒）
+pub const EJECT_CONFIRM_TIMEOUT_MS: u64 = 5000;
+
+/// 重复文件替换为链接后保存撤销清单的文件夹【在当前照片库下】
+pub const RECLAIM_MANIFEST_FOLDER: &str = "reclaim";
+
+/// 撤销清单文件名称前缀
+pub const RECLAIM_MANIFEST_PREFIX: &str = "reclaim-";
+
+/// 保险箱文件夹【在当前照片库下，保存加密后的原图和缩略图】
+pub const VAULT_FOLDER: &str = "vault";
+
+/// 保险箱配置文件名称（盐、迭代次数和密码校验数据）
+pub const VAULT_CONFIG_NAME: &str = "vault.json";
+
+/// 加密数据库中保存的密钥（远程存储密码等）使用的本机密钥文件【在当前照片库下，不随数据库备份】
+pub const SECRET_KEY_FILE_NAME: &str = "secret.key";
+
+/// 保险箱密码的最少字符数
+pub const VAULT_MIN_PASSPHRASE_CHARS: usize = 8;
+
+/// 由密码派生密钥时 PBKDF2-HMAC-SHA256 的迭代次数
+pub const VAULT_KDF_ITERATIONS: u32 = 600_000;
+
+/// 保险箱缩略图的长边尺寸（像素）
+pub const VAULT_THUMBNAIL_SIZE: u32 = 400;
+
+/// 解锁后临时解密文件夹的名称前缀
+pub const VAULT_VIEW_PREFIX: &str = "argus-vault-";
+
+/// 校验清单默认文件名称【与 sha256sum 的习惯一致】
+pub const CHECKSUM_MANIFEST_NAME: &str = "SHA256SUMS";
+
+/// 查看大图时默认的显示尺寸（长边像素）
+pub const DISPLAY_IMAGE_MAX_SIZE: u32 = 2560;
+
+/// 大图缓存占用的内存上限（字节）
+pub const DISPLAY_CACHE_MAX_BYTES: usize = 256 * 1024 * 1024;
+
+/// 每次预读的照片数量上限【只保留最近一次请求的照片】
+pub const DISPLAY_PREFETCH_LIMIT: usize = 20;
+
+/// 大图预读线程数量
+pub const DISPLAY_PREFETCH_THREADS: usize = 2;
+
+/// 打开照片耗时统计中每个阶段保留的最近样本数量
+pub const LATENCY_SAMPLE_LIMIT: usize = 1000;
+
+/// 备份文件夹名称前缀【自动清理时只处理带此前缀的文件夹】
+pub const BACKUP_FOLDER_PREFIX: &str = "argus-backup-";
+
+/// 备份文件夹名称中的时间格式
+pub const BACKUP_TIME_FMT: &str = "%Y%m%d-%H%M%S";
+
+/// 等待确认的节省空间计划留下的临时文件清单【在当前照片库下，异常退出后启动时清理】
+pub const SPACE_SAVER_PENDING_NAME: &str = "space-saver-pending.json";
+
+/// 默认自动备份文件夹名称
+pub const DEFAULT_BACKUP_FOLDER: &str = "backup";
+
+/// 恢复前备份当前数据库的文件夹名称【在自动备份文件夹下，不参与自动清理】
+pub const PRE_RESTORE_BACKUP_FOLDER: &str = "pre-restore";
+
+/// 等待其他连接释放数据库锁的最长时间（毫秒）
+pub const DATABASE_BUSY_TIMEOUT_MS: u64 = 30_000;
+
+/// 在线备份每一步复制的页数【每步之间释放锁，不影响正常读写】
+pub const BACKUP_PAGES_PER_STEP: std::ffi::c_int = 256;
+
+/// 全文索引（trigram）可以匹配的最短字符数
+pub const FTS_MIN_TERM_CHARS: usize = 3;
+
+/// 照片搜索默认返回数量
+pub const PHOTO_SEARCH_DEFAULT_LIMIT: i64 = 200;
+
+/// 照片列表默认每页数量
+pub const PHOTO_LIST_DEFAULT_PAGE_SIZE: i64 = 200;
+
+/// 照片列表每页最大数量
+pub const PHOTO_LIST_MAX_PAGE_SIZE: i64 = 1000;
+
+/// 照片说明写回时使用的 XMP 附属文件扩展名【与原图同名，如 `IMG_0001.xmp`】
+pub const CAPTION_SIDECAR_EXTENSION: &str = "xmp";
+
+/// JPEG 文件扩展名【读取 APP 段中的 XMP、IPTC 等元数据】
+pub const JPEG_EXTENSIONS: [&str; 4] = ["jpg", "jpeg", "jpe", "jfif"];
+
+/// 照片库列表文件名称【保存在程序所在文件夹】
+pub const LIBRARY_REGISTRY_NAME: &str = "libraries.toml";
+
+/// 默认照片库（程序所在文件夹）的名称
+pub const DEFAULT_LIBRARY_NAME: &str = "default";
+
+/// 缩略图目录分级层数的取值范围【每级使用 Hash 的两个字符】
+pub const DIRECTORY_LEVEL_RANGE: std::ops::RangeInclusive<u32> = 1..=4;
+
+/// 缩略图编码质量的取值范围【JPEG、WebP、AVIF 共用】
+pub const THUMBNAIL_QUALITY_RANGE: std::ops::RangeInclusive<u8> = 1..=100;
+
+/// 缩略图编码速度的取值范围【1 最慢、压缩率最高，10 最快】
+pub const THUMBNAIL_SPEED_RANGE: std::ops::RangeInclusive<u8> = 1..=10;
+
+/// 图形类图片检测的采样边长【按步长取 N×N 个像素】
+pub const GRAPHIC_SAMPLE_SIZE: u32 = 256;
+
+/// 图形类图片中相邻像素颜色相同的最低占比
+pub const GRAPHIC_MIN_FLAT_RATIO: f64 = 0.6;
+
+/// 图形类图片中不同颜色数量占采样数的最高比例
+pub const GRAPHIC_MAX_UNIQUE_RATIO: f64 = 0.25;
+
+/// 内存中保留的最近日志条数【用于在软件中查看日志】
+pub const LOG_RECENT_LIMIT: usize = 2000;
+
+/// 日志文件名前缀【按天生成，如 `argus-20250121.jsonl`】
+pub const LOG_FILE_PREFIX: &str = "argus-";
+
+/// 日志文件保留天数【每天第一次写入日志时删除更早的文件】
+pub const LOG_RETENTION_DAYS: i64 = 14;
+
+/// 多页 TIFF 最多读取的页面数量【包括 SubIFD】
+pub const TIFF_MAX_PAGES: usize = 256;
+
+/// 内置解析读取 TIFF、RAW 文件 EXIF 时读取的文件开头字节数【IFD 一般在文件开头，超出范围的字段跳过】
+pub const EXIF_SCAN_MAX_BYTES: u64 = 16 * 1024 * 1024;
+
+/// 问题文件记录的文件开头字节数【用于判断文件类型或是否被截断】
+pub const PROBLEM_SAMPLE_BYTES: usize = 32;
+
+/// 读取图片头信息时 HEIF meta 盒子的大小上限【超过时视为损坏】
+pub const PROBE_HEIF_META_MAX_BYTES: u64 = 4 * 1024 * 1024;
+
+/// 照片对比最少的照片数量
+pub const COMPARE_MIN_PHOTOS: usize = 2;
+
+/// 照片对比最多的照片数量
+pub const COMPARE_MAX_PHOTOS: usize = 4;
+
+/// 像素差异中视为变化的灰度差【0 - 255】
+pub const COMPARE_DIFF_THRESHOLD: u8 = 16;
+
+/// 事件中相邻两张照片的最大拍摄间隔（秒）【超过时分为两个事件】
+pub const EVENT_MAX_GAP_SECS: i64 = 8 * 60 * 60;
+
+/// 视为换了地方的相邻两张照片的距离（米）
+pub const EVENT_MAX_JUMP_METERS: f64 = 100_000.0;
+
+/// 换了地方时分为两个事件的最小拍摄间隔（秒）【避免偶发的定位错误拆分事件】
+pub const EVENT_JUMP_MIN_GAP_SECS: i64 = 60 * 60;
+
+/// 事件至少包含的照片数量
+pub const EVENT_MIN_PHOTOS: usize = 5;
+
+/// 导入批次的空闲时间（秒）【距上一批最后一张照片超过此时间的照片归为新的一批】
+pub const IMPORT_BATCH_IDLE_SECS: i64 = 10 * 60;
+
+/// 最近导入、最近浏览默认返回数量
+pub const RECENT_FEED_DEFAULT_LIMIT: i64 = 20;
+
+/// 最近导入的每一批中返回的预览照片数量
+pub const IMPORT_BATCH_PREVIEW_PHOTOS: i64 = 8;
+
+/// 那年今日每一年返回的代表照片数量
+pub const MEMORY_PREVIEW_PHOTOS: usize = 4;
+
+/// 文件夹相册同步的延迟时间（秒）【期间的多次文件变化合并为一次同步】
+pub const FOLDER_ALBUM_SYNC_DELAY_SECS: u64 = 3;
+
+/// 扫描前访问检查最多抽样的图片数量
+pub const DIRECTORY_PREFLIGHT_SAMPLE_FILES: usize = 200;
+
+/// 扫描前访问检查每个文件夹最多抽样的图片数量【让样本分布到更多文件夹】
+pub const DIRECTORY_PREFLIGHT_FILES_PER_DIR: usize = 10;
+
+/// 扫描前访问检查最多检查的文件夹数量
+pub const DIRECTORY_PREFLIGHT_MAX_DIRS: usize = 100;
+
+/// 扫描前访问检查最多返回的问题示例数量
+pub const DIRECTORY_PREFLIGHT_MAX_ISSUES: usize = 20;
+
+/// 等待 iCloud 占位文件下载完成的最长时间（秒）
+pub const CLOUD_HYDRATE_TIMEOUT_SECS: u64 = 300;
+
+/// 远程存储的连接超时时间（秒）
+pub const REMOTE_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+/// 远程存储的请求超时时间（秒）【包括上传、下载原图】
+pub const REMOTE_REQUEST_TIMEOUT_SECS: u64 = 600;
+
+/// 读取远程照片文件头时读取的字节数【足够包含 JPEG 的 EXIF 段和 SOF 段】
+pub const REMOTE_PROBE_BYTES: u64 = 256 * 1024;
+
+/// 远程照片在照片表中的路径前缀【后面是远程存储 ID 和对象所在的目录】
+pub const REMOTE_PHOTO_PATH_PREFIX: &str = "remote://";
+
+/// 上传到 S3 时每个分片的大小【超过一个分片的文件使用分片上传，S3 要求除最后一片外不小于 5 MiB】
+pub const REMOTE_MULTIPART_PART_SIZE: u64 = 16 * 1024 * 1024;
+
+/// 监视文件夹中的文件最后一次变化后等待的时间（毫秒）【期间大小不变才导入，避免导入还在写入的文件】
+pub const WATCH_FOLDER_SETTLE_MS: u64 = 2000;
+
+/// 检查监视文件夹中待导入文件的间隔（毫秒）
+pub const WATCH_FOLDER_POLL_MS: u64 = 500;
+
+/// 监视文件夹导入队列的长度
+pub const WATCH_IMPORT_QUEUE_SIZE: usize = 100;
+
+/// 节省空间至少需要减小的比例（%）【减小得更少时不替换原文件】
+pub const SPACE_SAVER_MIN_SAVING_PERCENT: u64 = 5;
+
+/// 常驻的 exiftool 进程数量
+pub const EXIFTOOL_POOL_SIZE: usize = 4;
+
+/// exiftool 进程一次处理的最大文件数量【同时到达的读取请求合并为一批】
+pub const EXIFTOOL_BATCH_SIZE: usize = 200;
+
+/// exiftool 进程处理多少个文件后重新启动【避免内存持续增长】
+pub const EXIFTOOL_PROCESS_MAX_FILES: usize = 5000;
+
+/// RAW 文件扩展名【不解码 RAW 数据，缩略图使用同名 JPEG 或内嵌的预览图】
+pub const RAW_EXTENSIONS: [&str; 11] = [
+    "cr2", "nef", "nrw", "arw", "srf", "sr2", "dng", "orf", "rw2", "pef", "srw",
+];
+
+/// 逆地理编码缓存的坐标精度【保留 3 位小数，约 110 米】
+pub const GEOCODE_CACHE_SCALE: f64 = 1000.0;
+
+/// 逆地理编码缓存的有效期（秒）【地名很少变化，180 天后重新请求】
+pub const GEOCODE_CACHE_TTL_SECS: i64 = 180 * 24 * 3600;
+
+/// 补全 EXIF 时每次查询的照片数量
+pub const EXIF_BACKFILL_PAGE_SIZE: i64 = 200;
+
+/// 保留的已结束 Hash 校验报告数量【超过时删除最早结束的】
+pub const HASH_VERIFY_REPORT_KEEP: usize = 8;
+
+/// 水印文字使用的系统字体【按顺序查找第一个存在的，均需支持中文】
+pub const WATERMARK_FONT_CANDIDATES: &[&str] = &[
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    "C:\\Windows\\Fonts\\simhei.ttf",
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+];
+
+/// 相机自动写入 ImageDescription 等字段的默认文字【不作为照片说明，比较时忽略大小写和首尾空白】
+pub const CAMERA_PLACEHOLDER_CAPTIONS: &[&str] = &[
+    "OLYMPUS DIGITAL CAMERA",
+    "SONY DSC",
+    "DIGITAL CAMERA",
+    "KODAK Digital Still Camera",
+    "MINOLTA DIGITAL CAMERA",
+    "LEICA DIGITAL CAMERA",
+    "SAMSUNG DIGITAL CAMERA",
+    "KONICA MINOLTA DIGITAL CAMERA",
+    "PENTAX DIGITAL CAMERA",
+    "Exif_JPEG_PICTURE",
+    "Default",
+    "DCIM",
+    "Untitled",
+];
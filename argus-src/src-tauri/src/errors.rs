@@ -1,3 +1,5 @@
+use crate::i18n;
+use crate::i18n::{msg, LocalizedMessage};
 use std::fmt;
 use tauri::ipc::InvokeError;
 use tauri::Error;
@@ -30,6 +32,12 @@ pub enum AError {
     /// 指定配置文件不存在
     #[error("ConfigFileDoesNotExist")]
     ConfigFileDoesNotExist,
+    /// 图像校验失败
+    #[error("Image validation failed")]
+    ImageValidationFailed,
+    /// 图像处理崩溃【解码库 panic】
+    #[error("Image processing panicked")]
+    ImageProcessPanicked,
 }
 
 impl AError {
@@ -42,33 +50,49 @@ impl AError {
             AError::ThumbnailGenerationFailed => 3,
             AError::SpecifiedFileDoesNotExist => 3,
             AError::ParentPathReadFailed => 3,
+            AError::ImageValidationFailed => 4,
+            AError::ImageProcessPanicked => 4,
             _ => -1,
         }
     }
 
-    pub fn message(&self) -> &'static str {
+    /// 消息目录中的 ID
+    pub fn message_id(&self) -> &'static str {
         match self {
-            AError::ThumbnailCacheConfigurationReadFailed => "配置文件读取失败！",
-            AError::OriginalImageReadFailed => "原图读取失败！",
-            AError::HashConversionFailed => "Hash 转换失败！",
-            AError::FileSaveFailed => "文件保存失败！",
-            AError::ThumbnailGenerationFailed => "缩略图生成失败！",
-            AError::SpecifiedFileDoesNotExist => "指定文件不存在！",
-            AError::ParentPathReadFailed => "父路径读取失败！",
-            _ => "默认报错返回",
+            AError::ThumbnailCacheConfigurationReadFailed => msg::ERROR_THUMBNAIL_CACHE_CONFIG_READ_FAILED,
+            AError::OriginalImageReadFailed => msg::ERROR_ORIGINAL_IMAGE_READ_FAILED,
+            AError::HashConversionFailed => msg::ERROR_HASH_CONVERSION_FAILED,
+            AError::FileSaveFailed => msg::ERROR_FILE_SAVE_FAILED,
+            AError::ThumbnailGenerationFailed => msg::ERROR_THUMBNAIL_GENERATION_FAILED,
+            AError::SpecifiedFileDoesNotExist => msg::ERROR_SPECIFIED_FILE_DOES_NOT_EXIST,
+            AError::ParentPathReadFailed => msg::ERROR_PARENT_PATH_READ_FAILED,
+            AError::ConfigFileDoesNotExist => msg::ERROR_CONFIG_FILE_DOES_NOT_EXIST,
+            AError::ImageValidationFailed => msg::ERROR_IMAGE_VALIDATION_FAILED,
+            AError::ImageProcessPanicked => msg::ERROR_IMAGE_PROCESS_PANICKED,
         }
     }
 
+    pub fn message(&self) -> &'static str {
+        i18n::text(self.message_id())
+    }
+
     pub fn suggestion(&self) -> Option<&'static str> {
         match self {
-            AError::ThumbnailCacheConfigurationReadFailed => Some("检查指定文件是否存在！"),
+            AError::ThumbnailCacheConfigurationReadFailed => {
+                Some(i18n::text(msg::ERROR_THUMBNAIL_CACHE_CONFIG_SUGGESTION))
+            }
             _ => None,
         }
     }
+
+    /// 转换为发送给前端的本地化消息
+    pub fn localized(&self) -> LocalizedMessage {
+        LocalizedMessage::new(self.message_id(), &[])
+    }
 }
 // 实现 Into<InvokeError>
 impl Into<InvokeError> for AError {
     fn into(self) -> InvokeError {
-        InvokeError::from(self.to_string())
+        InvokeError::from(self.localized())
     }
 }
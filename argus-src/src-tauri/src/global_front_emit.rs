@@ -8,3 +8,24 @@ pub const PHOTO_LOADING_ERR_TIP: &str = "photo-loading-err-tip";
 
 /// 照片后台加载进度及信息提示
 pub const PHOTO_LOADING_MSG_TIP: &str = "photo-loading-msg-tip";
+
+/// 照片扫描结束（附带吞吐量统计）
+pub const SCAN_FINISHED: &str = "scan-finished";
+
+/// 配置已变化（重新加载配置文件后）
+pub const SETTINGS_CHANGED: &str = "settings-changed";
+
+/// 幻灯片视频生成进度
+pub const SLIDESHOW_PROGRESS: &str = "slideshow-progress";
+
+/// 存储卡导入进度
+pub const CARD_IMPORT_PROGRESS: &str = "card-import-progress";
+
+/// 照片 Hash 校验进度
+pub const HASH_VERIFY_PROGRESS: &str = "hash-verify-progress";
+
+/// 批量操作进度
+pub const BATCH_APPLY_PROGRESS: &str = "batch-apply-progress";
+
+/// 磁盘剩余空间不足
+pub const LOW_DISK_SPACE: &str = "low-disk-space";
@@ -1,4 +1,5 @@
-use crate::constant::{IMAGE_COMPRESSION_RATIO, IMAGE_COMPRESSION_STORAGE_FORMAT};
+use crate::constant::IMAGE_COMPRESSION_RATIO;
+use crate::structs::thumbnail_encoding;
 use crate::utils::img_util::ImageOperate;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -59,7 +60,7 @@ impl BackgroundImageLoadingTaskManager {
     // 发送任务到后台队列
     pub(crate) async fn send_task(&self, task: String) {
         if let Err(_) = self.tx.send(task).await {
-            eprintln!("Failed to send task");
+            log::error!("Failed to send task");
         }
     }
 
@@ -119,20 +120,20 @@ pub async fn start_image_loading_background_task<F>(
                         paused = false;
                     }
                     // 如果不允许自动开始，进入睡眠，等待任务触发
-                    println!("Waiting for auto_start flag to be true...");
+                    log::debug!("Waiting for auto_start flag to be true...");
                     tokio::time::sleep(Duration::from_secs(auto_manager.pause_check_duration.into())).await;
                     continue;
                 }
                 // 读取图片压缩
                 let image_compression = ImageOperate::multi_level_image_compression(
                     task,
-                    IMAGE_COMPRESSION_STORAGE_FORMAT,
+                    thumbnail_encoding::storage_format(),
                     IMAGE_COMPRESSION_RATIO.to_vec(),
                 );
                 let s = image_compression.await;
                 if s.is_err(){
                     let i_e = s.map_err(|e| e.to_string()).unwrap_err();
-                    println!("isError ,{}",i_e);
+                    log::debug!("isError ,{}", i_e);
                     // f(i_e);
                     paused = true
                 }
@@ -141,7 +142,7 @@ pub async fn start_image_loading_background_task<F>(
                 wait_counter = 0;
                 // 任务完成后，根据 auto_manager_rx 的状态来决定是否暂停
                 if auto_manager.auto_pause {
-                    println!("Automatically pausing after task completion...");
+                    log::debug!("Automatically pausing after task completion...");
                     paused = true;
                 }
             },
@@ -153,7 +154,7 @@ pub async fn start_image_loading_background_task<F>(
 
                 if wait_counter >= auto_manager.wait_count {
                     if auto_manager.auto_pause {
-                        println!("No tasks received for {} rounds, auto pausing...", wait_counter);
+                        log::debug!("No tasks received for {} rounds, auto pausing...", wait_counter);
                         paused = true; // 达到等待轮数后自动暂停
                     }
                 } else {
@@ -165,9 +166,9 @@ pub async fn start_image_loading_background_task<F>(
                 let is_paused = *pause_rx.borrow();
                 paused = is_paused;
                 if paused {
-                    println!("Task paused due to pause_rx update.");
+                    log::debug!("Task paused due to pause_rx update.");
                 } else {
-                    println!("Task resumed due to pause_rx update.");
+                    log::debug!("Task resumed due to pause_rx update.");
                 }
             }
         }
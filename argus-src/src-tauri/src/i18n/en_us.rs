@@ -0,0 +1,741 @@
+use crate::i18n::{msg, MessageEntry};
+use crate::utils::exif_utils::exif_enum::{
+    ExposureProgram, MeteringMode, SceneCaptureType, WhiteBalance,
+};
+use crate::utils::exif_utils::tag::ExifToolDesc;
+
+/// English
+pub const MESSAGES: &[MessageEntry] = &[
+    // region 错误信息
+    MessageEntry::new(
+        msg::ERROR_THUMBNAIL_CACHE_CONFIG_READ_FAILED,
+        "Failed to read the configuration!",
+    ),
+    MessageEntry::new(
+        msg::ERROR_THUMBNAIL_CACHE_CONFIG_SUGGESTION,
+        "Check whether the file exists!",
+    ),
+    MessageEntry::new(msg::ERROR_ORIGINAL_IMAGE_READ_FAILED, "Failed to read the original image!"),
+    MessageEntry::new(msg::ERROR_HASH_CONVERSION_FAILED, "Failed to compute the hash!"),
+    MessageEntry::new(msg::ERROR_FILE_SAVE_FAILED, "Failed to save the file!"),
+    MessageEntry::new(msg::ERROR_PARENT_PATH_READ_FAILED, "Failed to read the parent path!"),
+    MessageEntry::new(msg::ERROR_THUMBNAIL_GENERATION_FAILED, "Failed to generate the thumbnail!"),
+    MessageEntry::new(msg::ERROR_SPECIFIED_FILE_DOES_NOT_EXIST, "The specified file does not exist!"),
+    MessageEntry::new(msg::ERROR_CONFIG_FILE_DOES_NOT_EXIST, "The configuration file does not exist!"),
+    MessageEntry::new(msg::ERROR_IMAGE_VALIDATION_FAILED, "Image validation failed!"),
+    MessageEntry::new(
+        msg::ERROR_IMAGE_PROCESS_PANICKED,
+        "Image processing crashed, the file may be corrupted!",
+    ),
+    // endregion
+
+    // region 照片加载任务
+    MessageEntry::new(msg::PHOTO_LOAD_FAILED, "{path} failed: {error}"),
+    MessageEntry::plural(
+        msg::TASK_SUBMITTED,
+        "Started, {count} task queued",
+        "Started, {count} tasks queued",
+    ),
+    MessageEntry::new(msg::TASK_FINISHED, "Done"),
+    MessageEntry::new(msg::TASK_CANCELLED, "Cancelled"),
+    MessageEntry::new(msg::SCAN_TASK_NOT_FOUND, "Scan task {taskId} does not exist or has expired."),
+    // endregion
+
+    // region 照片存储路径
+    MessageEntry::new(msg::PHOTO_STORAGE_ADD_SUCCESS, "Added."),
+    MessageEntry::new(msg::PHOTO_STORAGE_ADD_FAILED, "Failed to access the database!"),
+    // endregion
+
+    // region 日志
+    MessageEntry::new(msg::LOG_LEVEL_INVALID, "Invalid log level {level}."),
+    // endregion
+
+    // region 文件
+    MessageEntry::new(msg::FILE_DOES_NOT_EXIST, "File does not exist."),
+    MessageEntry::new(
+        msg::DIRECTORY_NOT_ACCESSIBLE,
+        "Folder {path} does not exist or cannot be read.",
+    ),
+    MessageEntry::new(msg::FOLDER_NOT_FOUND, "Folder {path} does not exist."),
+    MessageEntry::new(msg::FILE_ALREADY_EXISTS, "File {path} already exists."),
+    MessageEntry::new(msg::FILE_NAME_INVALID, "Invalid file name: {name}"),
+    MessageEntry::new(msg::FILE_NAME_UNCHANGED, "The file name has not changed."),
+    MessageEntry::new(
+        msg::PHOTO_FILE_NOT_FOUND,
+        "Photo file {path} does not exist.",
+    ),
+    MessageEntry::new(msg::PHOTO_RENAME_FAILED, "Failed to rename photo {id}."),
+    MessageEntry::new(
+        msg::PHOTO_TEMPLATE_NAME_EMPTY,
+        "The template produced an empty file name for photo {name}.",
+    ),
+    MessageEntry::new(msg::FILE_MOVE_FAILED, "Failed to move {path}: {error}"),
+    // endregion
+
+    // region 元数据编辑
+    MessageEntry::plural(
+        msg::EDIT_RATING,
+        "Set rating of {count} photo",
+        "Set rating of {count} photos",
+    ),
+    MessageEntry::plural(
+        msg::EDIT_SHIFT_TIME,
+        "Shift time of {count} photo by {seconds} seconds",
+        "Shift time of {count} photos by {seconds} seconds",
+    ),
+    MessageEntry::plural(
+        msg::EDIT_GPS,
+        "Set location of {count} photo",
+        "Set location of {count} photos",
+    ),
+    MessageEntry::plural(
+        msg::EDIT_TAGS,
+        "Set tags of {count} photo",
+        "Set tags of {count} photos",
+    ),
+    MessageEntry::plural(
+        msg::EDIT_CAPTION,
+        "Set caption of {count} photo",
+        "Set caption of {count} photos",
+    ),
+    MessageEntry::plural(
+        msg::EDIT_PRESET,
+        "Apply metadata preset \"{name}\" to {count} photo",
+        "Apply metadata preset \"{name}\" to {count} photos",
+    ),
+    MessageEntry::plural(
+        msg::EDIT_NOTES,
+        "Set notes of {count} photo",
+        "Set notes of {count} photos",
+    ),
+    MessageEntry::plural(
+        msg::EDIT_BATCH,
+        "Apply {operations} batch operations to {count} photo",
+        "Apply {operations} batch operations to {count} photos",
+    ),
+    MessageEntry::new(msg::EDIT_ROTATE, "Rotate {degrees}° clockwise"),
+    MessageEntry::new(
+        msg::EDIT_ROTATE_INVALID,
+        "Invalid rotation angle {degrees}, only multiples of 90 are supported.",
+    ),
+    MessageEntry::new(msg::EDIT_FLIP_HORIZONTAL, "Flip horizontally"),
+    MessageEntry::new(msg::EDIT_FLIP_VERTICAL, "Flip vertically"),
+    MessageEntry::new(msg::EDIT_FLIP_INVALID, "Invalid flip direction {axis}."),
+    MessageEntry::new(msg::EDIT_NO_CHANGE, "No photos need to be changed."),
+    MessageEntry::new(msg::EDIT_NOTHING_TO_UNDO, "Nothing to undo."),
+    MessageEntry::new(msg::EDIT_NOTHING_TO_REDO, "Nothing to redo."),
+    MessageEntry::new(
+        msg::EDIT_RATING_OUT_OF_RANGE,
+        "Rating {rating} is out of range 0 - {max}.",
+    ),
+    MessageEntry::new(
+        msg::EDIT_JOURNAL_INVALID,
+        "Failed to parse the edit history: {error}",
+    ),
+    // endregion
+
+    // region 分享
+    MessageEntry::new(msg::SHARE_BUNDLE_TITLE, "Shared photos"),
+    MessageEntry::plural(msg::SHARE_BUNDLE_COUNT, "{count} photo", "{count} photos"),
+    MessageEntry::new(msg::SHARE_NO_PHOTOS, "There are no photos to share."),
+    MessageEntry::new(msg::SHARE_ALL_FAILED, "All photos failed: {errors}"),
+    // endregion
+
+    // region 通用
+    MessageEntry::new(msg::LIST_SEPARATOR, ", "),
+    // endregion
+
+    // region 元数据展示
+    MessageEntry::new(msg::EXIF_GPS_INFO, "GPS"),
+    MessageEntry::new(msg::EXIF_FLASH_ON, "On"),
+    MessageEntry::new(msg::EXIF_FLASH_OFF, "Off"),
+    MessageEntry::new(msg::EXIF_FLASH_AUTO, "Auto"),
+    MessageEntry::new(msg::EXIF_FLASH_FIRED, "Fired"),
+    MessageEntry::new(msg::EXIF_FLASH_NOT_FIRED, "Did not fire"),
+    MessageEntry::new(msg::EXIF_FLASH_RED_EYE, "Red-eye reduction"),
+    MessageEntry::new(msg::EXIF_FLASH_NO_FUNCTION, "No flash"),
+    MessageEntry::new(msg::EXIF_NORTH_TRUE, "True north"),
+    MessageEntry::new(msg::EXIF_NORTH_MAGNETIC, "Magnetic north"),
+    MessageEntry::new(ExifToolDesc::MAKE.label_id, "Camera make"),
+    MessageEntry::new(ExifToolDesc::MODEL.label_id, "Camera model"),
+    MessageEntry::new(ExifToolDesc::SOFTWARE.label_id, "Software"),
+    MessageEntry::new(ExifToolDesc::EXPOSURE_TIME.label_id, "Shutter speed"),
+    MessageEntry::new(ExifToolDesc::F_NUMBER.label_id, "Aperture"),
+    MessageEntry::new(ExifToolDesc::ISO.label_id, "ISO"),
+    MessageEntry::new(ExifToolDesc::EXIF_VERSION.label_id, "Exif version"),
+    MessageEntry::new(ExifToolDesc::DATE_TIME_ORIGINAL.label_id, "Date taken"),
+    MessageEntry::new(ExifToolDesc::OFFSET_TIME.label_id, "Time zone"),
+    MessageEntry::new(ExifToolDesc::MAX_APERTURE_VALUE.label_id, "Max aperture"),
+    MessageEntry::new(ExifToolDesc::FOCAL_LENGTH.label_id, "Focal length"),
+    MessageEntry::new(ExifToolDesc::FOCAL_LENGTH_IN_35MM_FORMAT.label_id, "35mm equivalent focal length"),
+    MessageEntry::new(ExifToolDesc::IMAGE_WIDTH.label_id, "Image width"),
+    MessageEntry::new(ExifToolDesc::IMAGE_HEIGHT.label_id, "Image height"),
+    MessageEntry::new(ExifToolDesc::GPS_LATITUDE_REF.label_id, "GPS latitude ref"),
+    MessageEntry::new(ExifToolDesc::GPS_LONGITUDE_REF.label_id, "GPS longitude ref"),
+    MessageEntry::new(ExifToolDesc::GPS_LATITUDE.label_id, "GPS latitude"),
+    MessageEntry::new(ExifToolDesc::GPS_LONGITUDE.label_id, "GPS longitude"),
+    MessageEntry::new(ExifToolDesc::GPS_ALTITUDE_REF.label_id, "GPS altitude ref"),
+    MessageEntry::new(ExifToolDesc::GPS_ALTITUDE.label_id, "GPS altitude"),
+    MessageEntry::new(ExifToolDesc::GPS_SPEED_REF.label_id, "GPS speed unit"),
+    MessageEntry::new(ExifToolDesc::GPS_SPEED.label_id, "GPS speed"),
+    MessageEntry::new(ExifToolDesc::GPS_IMG_DIRECTION_REF.label_id, "GPS image direction ref"),
+    MessageEntry::new(ExifToolDesc::GPS_IMG_DIRECTION.label_id, "GPS image direction"),
+    MessageEntry::new(ExifToolDesc::GPS_DEST_BEARING_REF.label_id, "GPS destination bearing ref"),
+    MessageEntry::new(ExifToolDesc::GPS_DEST_BEARING.label_id, "GPS destination bearing"),
+    MessageEntry::new(ExifToolDesc::EXPOSURE_PROGRAM.label_id, "Exposure program"),
+    MessageEntry::new(ExifToolDesc::METERING_MODE.label_id, "Metering mode"),
+    MessageEntry::new(ExifToolDesc::FLASH.label_id, "Flash"),
+    MessageEntry::new(ExifToolDesc::WHITE_BALANCE.label_id, "White balance"),
+    MessageEntry::new(ExifToolDesc::SCENE_CAPTURE_TYPE.label_id, "Scene type"),
+    MessageEntry::new(ExifToolDesc::ARTIST.label_id, "Artist"),
+    MessageEntry::new(ExifToolDesc::RATING.label_id, "Rating"),
+    MessageEntry::new(ExifToolDesc::IMAGE_DESCRIPTION.label_id, "Image description"),
+    MessageEntry::new(ExifToolDesc::DESCRIPTION.label_id, "Description"),
+    MessageEntry::new(ExifToolDesc::IMAGE_TITLE.label_id, "Image title"),
+    MessageEntry::new(ExifToolDesc::PHOTOGRAPHER.label_id, "Photographer"),
+    MessageEntry::new(ExifToolDesc::IMAGE_EDITOR.label_id, "Image editor"),
+    MessageEntry::new(ExifToolDesc::CAMERA_FIRMWARE.label_id, "Camera firmware"),
+    MessageEntry::new(ExifToolDesc::COPYRIGHT.label_id, "Copyright"),
+    MessageEntry::new(MeteringMode::Unknown.label_id(), "Unknown"),
+    MessageEntry::new(MeteringMode::Average.label_id(), "Average"),
+    MessageEntry::new(MeteringMode::CenterWeightedAverage.label_id(), "Center-weighted average"),
+    MessageEntry::new(MeteringMode::Spot.label_id(), "Spot"),
+    MessageEntry::new(MeteringMode::MultiSpot.label_id(), "Multi-spot"),
+    MessageEntry::new(MeteringMode::MultiSegment.label_id(), "Multi-segment (evaluative)"),
+    MessageEntry::new(MeteringMode::Partial.label_id(), "Partial"),
+    MessageEntry::new(MeteringMode::Other.label_id(), "Other"),
+    MessageEntry::new(ExposureProgram::NotDefined.label_id(), "Not defined"),
+    MessageEntry::new(ExposureProgram::Manual.label_id(), "Manual"),
+    MessageEntry::new(ExposureProgram::Program.label_id(), "Program AE"),
+    MessageEntry::new(ExposureProgram::AperturePriority.label_id(), "Aperture priority"),
+    MessageEntry::new(ExposureProgram::ShutterPriority.label_id(), "Shutter priority"),
+    MessageEntry::new(ExposureProgram::Creative.label_id(), "Creative (slow speed)"),
+    MessageEntry::new(ExposureProgram::Action.label_id(), "Action (high speed)"),
+    MessageEntry::new(ExposureProgram::Portrait.label_id(), "Portrait"),
+    MessageEntry::new(ExposureProgram::Landscape.label_id(), "Landscape"),
+    MessageEntry::new(ExposureProgram::Bulb.label_id(), "Bulb"),
+    MessageEntry::new(WhiteBalance::Auto.label_id(), "Auto"),
+    MessageEntry::new(WhiteBalance::Manual.label_id(), "Manual"),
+    MessageEntry::new(SceneCaptureType::Standard.label_id(), "Standard"),
+    MessageEntry::new(SceneCaptureType::Landscape.label_id(), "Landscape"),
+    MessageEntry::new(SceneCaptureType::Portrait.label_id(), "Portrait"),
+    MessageEntry::new(SceneCaptureType::Night.label_id(), "Night"),
+    MessageEntry::new(SceneCaptureType::Other.label_id(), "Other"),
+    // endregion
+
+    // region 坐标
+    MessageEntry::new(msg::GPS_COORDINATE, "{latitude}, {longitude}"),
+    MessageEntry::new(msg::GPS_LATITUDE_NORTH, "{value} N"),
+    MessageEntry::new(msg::GPS_LATITUDE_SOUTH, "{value} S"),
+    MessageEntry::new(msg::GPS_LONGITUDE_EAST, "{value} E"),
+    MessageEntry::new(msg::GPS_LONGITUDE_WEST, "{value} W"),
+    // endregion
+
+    // region 语言
+    MessageEntry::new(msg::LOCALE_UNSUPPORTED, "Unsupported language: {locale}"),
+    // endregion
+
+    // region 保险箱
+    MessageEntry::new(msg::VAULT_ALREADY_EXISTS, "The vault already exists."),
+    MessageEntry::new(
+        msg::VAULT_PASSPHRASE_TOO_SHORT,
+        "The password must be at least {min} characters.",
+    ),
+    MessageEntry::new(
+        msg::VAULT_NOT_CREATED,
+        "The vault has not been created yet.",
+    ),
+    MessageEntry::new(msg::VAULT_WRONG_PASSPHRASE, "Wrong password."),
+    MessageEntry::new(msg::VAULT_LOCKED, "The vault is locked."),
+    MessageEntry::new(
+        msg::VAULT_REMOTE_PHOTO,
+        "Photos in remote storage cannot be moved into the vault.",
+    ),
+    MessageEntry::new(
+        msg::VAULT_ORIGINAL_DELETE_FAILED,
+        "Failed to delete the original {path}: {error}",
+    ),
+    MessageEntry::new(
+        msg::VAULT_RESTORE_TARGET_EXISTS,
+        "A file with the same name already exists at {path}.",
+    ),
+    MessageEntry::new(
+        msg::VAULT_VERIFY_FAILED,
+        "Failed to verify the encrypted file.",
+    ),
+    MessageEntry::new(msg::VAULT_INVALID_ITERATIONS, "Invalid iteration count."),
+    MessageEntry::new(msg::VAULT_INVALID_KEY, "Invalid key."),
+    MessageEntry::new(msg::VAULT_ENCRYPT_FAILED, "Encryption failed."),
+    MessageEntry::new(
+        msg::VAULT_DECRYPT_FAILED,
+        "Decryption failed: wrong password or damaged file.",
+    ),
+    MessageEntry::new(msg::VAULT_INVALID_FILE, "Not a valid vault file."),
+    MessageEntry::new(msg::VAULT_FILE_INCOMPLETE, "The vault file is incomplete."),
+    MessageEntry::new(msg::VAULT_RANDOM_FAILED, "Failed to generate random bytes."),
+    // endregion
+
+    // region 远程存储
+    MessageEntry::new(
+        msg::REMOTE_STORAGE_NOT_FOUND,
+        "Remote storage {id} does not exist.",
+    ),
+    MessageEntry::new(
+        msg::REMOTE_STORAGE_DISABLED,
+        "Remote storage {name} is disabled.",
+    ),
+    MessageEntry::new(
+        msg::REMOTE_SYNC_RUNNING,
+        "The remote storage is syncing, please try again later.",
+    ),
+    MessageEntry::new(
+        msg::REMOTE_NAME_REQUIRED,
+        "The remote storage name cannot be empty.",
+    ),
+    MessageEntry::new(
+        msg::REMOTE_ENDPOINT_SCHEME,
+        "Invalid service address {endpoint}, it must start with http:// or https://.",
+    ),
+    MessageEntry::new(
+        msg::REMOTE_ENDPOINT_INVALID,
+        "Invalid service address {endpoint}.",
+    ),
+    MessageEntry::new(msg::REMOTE_BUCKET_REQUIRED, "S3 storage requires a bucket."),
+    MessageEntry::new(msg::REMOTE_FILE_NAME_INVALID, "Invalid file name."),
+    MessageEntry::new(
+        msg::REMOTE_REQUEST_FAILED,
+        "Remote storage request failed: {status} {body}",
+    ),
+    MessageEntry::new(
+        msg::REMOTE_MULTIPART_CREATE_FAILED,
+        "Failed to start the multipart upload: {response}",
+    ),
+    MessageEntry::new(
+        msg::REMOTE_MULTIPART_NO_ETAG,
+        "No ETag was returned after uploading part {part}.",
+    ),
+    MessageEntry::new(
+        msg::REMOTE_MULTIPART_COMPLETE_FAILED,
+        "Failed to complete the multipart upload: {code} {response}",
+    ),
+    // endregion
+
+    // region 监视文件夹
+    MessageEntry::new(
+        msg::WATCH_FOLDER_PATH_REQUIRED,
+        "The watch folder cannot be empty.",
+    ),
+    MessageEntry::new(
+        msg::WATCH_FOLDER_DEST_REQUIRED,
+        "The destination folder cannot be empty.",
+    ),
+    MessageEntry::new(
+        msg::WATCH_FOLDER_DEST_INSIDE,
+        "The destination folder cannot be inside the watch folder {path}.",
+    ),
+    MessageEntry::new(
+        msg::WATCH_FOLDER_NOT_FOUND,
+        "Watch folder {id} does not exist.",
+    ),
+    MessageEntry::new(
+        msg::WATCH_FOLDER_TAGS_INVALID,
+        "Failed to parse the tags of watch folder {id}: {error}",
+    ),
+    // endregion
+
+    // region 节省空间
+    MessageEntry::new(
+        msg::SPACE_SAVER_TEMP_LIST_SAVE_FAILED,
+        "Failed to save the space saver temporary file list: {error}",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_METADATA_MISMATCH,
+        "The metadata of the new file does not match the original.",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_METADATA_UNSUPPORTED,
+        "Contains metadata that cannot be preserved: {chunks}",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_BIT_DEPTH_UNSUPPORTED,
+        "A bit depth of {depth} cannot be converted to lossless WebP.",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_ANIMATED_SKIPPED,
+        "Animated images are not converted.",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_WEBP_ENCODE_FAILED,
+        "WebP encoding failed: {error}",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_WEBP_CONFIG_FAILED,
+        "Failed to initialize the WebP encoder settings.",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_SIZE_MISMATCH,
+        "The dimensions of the new file do not match the original.",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_PIXEL_MISMATCH,
+        "The pixels of the new file do not match the original.",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_SOURCE_MODIFIED,
+        "The original file was modified after the plan was created.",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_PLAN_NOT_FOUND,
+        "Space saver plan {id} does not exist or has expired.",
+    ),
+    // endregion
+
+    // region 通用错误
+    MessageEntry::new(msg::PHOTO_NOT_FOUND, "Photo {id} does not exist."),
+    MessageEntry::new(msg::PHOTOS_PARTIALLY_MISSING, "Some photos do not exist."),
+    MessageEntry::new(msg::ALBUM_NOT_FOUND, "Album {id} does not exist."),
+    MessageEntry::new(msg::TAG_NOT_FOUND, "Tag {id} does not exist."),
+    MessageEntry::new(
+        msg::THUMBNAIL_PATH_NOT_CONFIGURED,
+        "The thumbnail path is not configured.",
+    ),
+    MessageEntry::new(
+        msg::EXPORT_FORMAT_UNSUPPORTED,
+        "Unsupported export format: {format}",
+    ),
+    MessageEntry::new(
+        msg::IMAGE_READ_FAILED,
+        "Failed to read image {path}: {error}",
+    ),
+    MessageEntry::new(msg::DATE_INVALID, "Invalid date format: {date}"),
+    // endregion
+
+    // region 外部工具
+    MessageEntry::new(
+        msg::JPEGTRAN_START_FAILED,
+        "Failed to start jpegtran: {error}",
+    ),
+    MessageEntry::new(msg::JPEGTRAN_FAILED, "jpegtran failed: {error}"),
+    MessageEntry::new(
+        msg::FFMPEG_START_FAILED,
+        "Failed to start ffmpeg {path}: {error}",
+    ),
+    MessageEntry::new(msg::FFMPEG_FAILED, "ffmpeg failed ({status}): {error}"),
+    MessageEntry::new(
+        msg::BRCTL_DOWNLOAD_FAILED,
+        "brctl download failed: {status}",
+    ),
+    // endregion
+
+    // region 照片库
+    MessageEntry::new(
+        msg::LIBRARY_NAME_REQUIRED,
+        "The library name cannot be empty.",
+    ),
+    MessageEntry::new(
+        msg::LIBRARY_ALREADY_LIBRARY,
+        "Folder {path} is already a library. Open it instead.",
+    ),
+    MessageEntry::new(msg::LIBRARY_NOT_LIBRARY, "Folder {path} is not a library."),
+    MessageEntry::new(msg::LIBRARY_NOT_ADDED, "Library {path} has not been added."),
+    MessageEntry::new(
+        msg::LIBRARY_ALREADY_ADDED,
+        "Library {path} has already been added.",
+    ),
+    MessageEntry::new(
+        msg::LIBRARY_INACCESSIBLE,
+        "Library folder {path} cannot be accessed.",
+    ),
+    MessageEntry::new(
+        msg::LIBRARY_DATABASE_INIT_FAILED,
+        "Failed to initialize the database of library {path}: {error}",
+    ),
+    MessageEntry::new(
+        msg::SETTINGS_DATABASE_INIT_FAILED,
+        "Failed to initialize the database, the settings were not applied: {error}",
+    ),
+    // endregion
+
+    // region 备份
+    MessageEntry::new(
+        msg::BACKUP_RUNNING,
+        "A backup or restore is already running.",
+    ),
+    MessageEntry::new(
+        msg::BACKUP_FOLDER_EXISTS,
+        "Backup folder {path} already exists.",
+    ),
+    MessageEntry::new(
+        msg::BACKUP_DATABASE_NOT_FOUND,
+        "Backup database {path} does not exist.",
+    ),
+    MessageEntry::new(
+        msg::BACKUP_CORRUPTED,
+        "The backup file is corrupted: {error}",
+    ),
+    MessageEntry::new(
+        msg::BACKUP_RESTORE_BUSY,
+        "Database in use, restore incomplete ({step}). Recover data from {path}.",
+    ),
+    MessageEntry::new(
+        msg::BACKUP_RESTORE_VERIFY_FAILED,
+        "Restored database failed verification, recover data from {path}: {error}",
+    ),
+    MessageEntry::new(
+        msg::BACKUP_MIGRATION_FAILED,
+        "Database migration failed: {error}",
+    ),
+    // endregion
+
+    // region 校验
+    MessageEntry::new(msg::HASH_VERIFY_NO_PHOTOS, "There are no photos to verify."),
+    MessageEntry::new(
+        msg::HASH_VERIFY_TARGET_REQUIRED,
+        "Specify the photos or folder to verify.",
+    ),
+    MessageEntry::new(
+        msg::HASH_VERIFY_START_FAILED,
+        "Failed to start the hash verification: {error}",
+    ),
+    MessageEntry::new(
+        msg::HASH_VERIFY_JOB_NOT_RUNNING,
+        "Verification job {id} does not exist or has finished.",
+    ),
+    MessageEntry::new(
+        msg::HASH_VERIFY_JOB_NOT_FOUND,
+        "Verification job {id} does not exist.",
+    ),
+    MessageEntry::new(
+        msg::CHECKSUM_SOURCE_REQUIRED,
+        "Specify either a folder or an album.",
+    ),
+    MessageEntry::new(msg::CHECKSUM_NO_PHOTOS, "There are no photos to export."),
+    MessageEntry::new(
+        msg::CHECKSUM_NO_COMMON_DIR,
+        "The photos in the album have no common parent folder. Specify the manifest path.",
+    ),
+    MessageEntry::new(
+        msg::CHECKSUM_MANIFEST_READ_FAILED,
+        "Failed to read the checksum manifest {path}: {error}",
+    ),
+    // endregion
+
+    // region 照片整理
+    MessageEntry::new(msg::BATCH_NO_PHOTOS, "No photos are selected."),
+    MessageEntry::new(msg::BATCH_NO_OPERATIONS, "There are no operations to run."),
+    MessageEntry::new(
+        msg::BATCH_MIRROR_ALBUM,
+        "Album {id} is synced from a folder, photos cannot be added manually.",
+    ),
+    MessageEntry::new(
+        msg::BATCH_DEST_REQUIRED,
+        "The destination folder cannot be empty.",
+    ),
+    MessageEntry::new(
+        msg::BATCH_MULTIPLE_MOVES,
+        "Only one move operation is allowed.",
+    ),
+    MessageEntry::new(msg::ORGANIZE_RULE_NOT_FOUND, "Rule {id} does not exist."),
+    MessageEntry::new(
+        msg::ORGANIZE_RULE_CONDITION_INVALID,
+        "Failed to parse the conditions of rule {id}: {error}",
+    ),
+    MessageEntry::new(
+        msg::ORGANIZE_RULE_ACTION_INVALID,
+        "Rule {id} has an invalid action: {action}",
+    ),
+    MessageEntry::new(
+        msg::ORGANIZATION_ALBUM_DUPLICATED,
+        "Album {id} appears more than once in the file.",
+    ),
+    MessageEntry::new(
+        msg::ORGANIZATION_TAG_DUPLICATED,
+        "Tag {id} appears more than once in the file.",
+    ),
+    MessageEntry::new(
+        msg::STACK_TOO_FEW_PHOTOS,
+        "At least two photos are needed to create a stack.",
+    ),
+    MessageEntry::new(
+        msg::STACK_ORIGINAL_NOT_INCLUDED,
+        "Original photo {id} is not among the photos to stack.",
+    ),
+    MessageEntry::new(
+        msg::STACK_PHOTO_NOT_STACKED,
+        "Photo {id} is not in any stack.",
+    ),
+    MessageEntry::new(
+        msg::COVER_NOT_IN_FOLDER,
+        "Photo {id} is not in folder {path}.",
+    ),
+    MessageEntry::new(
+        msg::COVER_NOT_IN_ALBUM,
+        "Photo {id} is not in album {album}.",
+    ),
+    MessageEntry::new(
+        msg::DUPLICATE_MANIFEST_READ_FAILED,
+        "Failed to read the undo manifest {path}: {error}",
+    ),
+    MessageEntry::new(
+        msg::DUPLICATE_CONTENT_MISMATCH,
+        "The file content does not match the kept file.",
+    ),
+    MessageEntry::new(
+        msg::DUPLICATE_HARD_LINK_FAILED,
+        "Failed to create a hard link (both files must be on the same partition): {error}",
+    ),
+    // endregion
+
+    // region 导入导出
+    MessageEntry::new(
+        msg::CARD_IMPORT_COPY_READ_FAILED,
+        "Failed to read the copied file: {error}",
+    ),
+    MessageEntry::new(
+        msg::CARD_IMPORT_VERIFY_FAILED,
+        "Verification failed: the copied file does not match the source.",
+    ),
+    MessageEntry::new(
+        msg::CLOUD_FILE_NOT_DOWNLOADED,
+        "The file has not been downloaded yet.",
+    ),
+    MessageEntry::new(
+        msg::CLOUD_DOWNLOAD_TIMEOUT,
+        "Timed out waiting for the iCloud download.",
+    ),
+    MessageEntry::new(
+        msg::METADATA_CSV_HASH_MISSING,
+        "The CSV file has no hash column.",
+    ),
+    MessageEntry::new(msg::METADATA_CSV_ROW_INVALID, "Line {line}: {error}"),
+    MessageEntry::new(
+        msg::EXPORT_WATERMARK_LOGO_FAILED,
+        "Failed to read the watermark logo {path}: {error}",
+    ),
+    MessageEntry::new(
+        msg::EXPORT_LOCATION_FAILED,
+        "Failed to process the location: {error}",
+    ),
+    MessageEntry::new(
+        msg::SLIDESHOW_OUTPUT_REQUIRED,
+        "The output file path cannot be empty.",
+    ),
+    MessageEntry::new(
+        msg::SLIDESHOW_MUSIC_NOT_FOUND,
+        "Background music file {path} does not exist.",
+    ),
+    MessageEntry::new(
+        msg::SLIDESHOW_NO_PHOTOS,
+        "There are no photos to make a video from.",
+    ),
+    MessageEntry::new(
+        msg::SLIDESHOW_START_FAILED,
+        "Failed to start the slideshow job: {error}",
+    ),
+    MessageEntry::new(
+        msg::SLIDESHOW_JOB_NOT_RUNNING,
+        "Slideshow job {id} does not exist or has finished.",
+    ),
+    // endregion
+
+    // region 照片查看
+    MessageEntry::new(
+        msg::THUMBNAIL_VISIBLE_RANGE_INVALID,
+        "Invalid visible range {from} - {to}.",
+    ),
+    MessageEntry::new(
+        msg::MP_IMAGE_NOT_FOUND,
+        "Photo {id} has no MP image at index {index}.",
+    ),
+    MessageEntry::new(
+        msg::COMPARE_PHOTO_RANGE,
+        "Select {min} - {max} photos to compare.",
+    ),
+    MessageEntry::new(
+        msg::COMPARE_DUPLICATED,
+        "The photos to compare must be different.",
+    ),
+    MessageEntry::new(
+        msg::COMPARE_PREVIEW_READ_FAILED,
+        "Failed to read preview {path}: {error}",
+    ),
+    MessageEntry::new(msg::BENCHMARK_NO_PHOTOS, "There are no photos to compare."),
+    MessageEntry::new(
+        msg::HTTP_THUMBNAIL_SIZE_UNSUPPORTED,
+        "Unsupported thumbnail size: {size}",
+    ),
+    MessageEntry::new(
+        msg::HTTP_ADDRESS_INVALID,
+        "Invalid HTTP server address {address}: {error}",
+    ),
+    // endregion
+
+    // region 设置
+    MessageEntry::new(
+        msg::PRESET_NAME_REQUIRED,
+        "The preset name cannot be empty.",
+    ),
+    MessageEntry::new(msg::PRESET_EMPTY, "Preset {name} has no fields."),
+    MessageEntry::new(msg::PRESET_NOT_FOUND, "Preset {id} does not exist."),
+    MessageEntry::new(
+        msg::PRIVACY_ZONE_NAME_REQUIRED,
+        "The privacy zone name cannot be empty.",
+    ),
+    MessageEntry::new(
+        msg::PRIVACY_ZONE_CENTER_INVALID,
+        "Privacy zone {name} has an invalid center.",
+    ),
+    MessageEntry::new(
+        msg::PRIVACY_ZONE_RADIUS_INVALID,
+        "The radius of privacy zone {name} must be greater than 0.",
+    ),
+    MessageEntry::new(
+        msg::PRIVACY_ZONE_NOT_FOUND,
+        "Privacy zone {id} does not exist.",
+    ),
+    MessageEntry::new(
+        msg::PRIVACY_LOCATION_INVALID,
+        "Failed to parse the location of photo {id}: {error}",
+    ),
+    MessageEntry::new(msg::USER_NOT_FOUND, "User {id} does not exist."),
+    MessageEntry::new(
+        msg::USER_DEFAULT_UNDELETABLE,
+        "The default user cannot be deleted.",
+    ),
+    MessageEntry::new(
+        msg::MAINTENANCE_TASK_RUNNING,
+        "The task is already running.",
+    ),
+    MessageEntry::new(
+        msg::MAINTENANCE_RESCAN_ALL_FAILED,
+        "Rescanning failed for all storage paths: {paths}",
+    ),
+    MessageEntry::new(
+        msg::DISK_SPACE_INSUFFICIENT,
+        "Not enough disk space: {path} has {available} MB free, {required} MB needed.",
+    ),
+    // endregion
+
+    // region 数据库占用
+    MessageEntry::new(
+        msg::DATABASE_BUSY_SCANNING,
+        "Photos are being scanned. Wait for the scan to finish or cancel it, then {action}.",
+    ),
+    MessageEntry::new(
+        msg::DATABASE_BUSY_WRITING,
+        "Scan results are being saved. Try again later to {action}.",
+    ),
+    MessageEntry::new(
+        msg::DATABASE_BUSY_BACKUP,
+        "The database is being backed up or restored. Try again later to {action}.",
+    ),
+    MessageEntry::new(msg::DATABASE_ACTION_SWITCH_LIBRARY, "switch libraries"),
+    MessageEntry::new(msg::DATABASE_ACTION_RESTORE, "restore the database"),
+    // endregion
+
+    // region 调试
+    MessageEntry::new(msg::DEV_EMIT_GREETING, "Hello, {name}! From the backend!"),
+    MessageEntry::new(msg::DEV_GLOBAL_MSG_TITLE, "Title"),
+    MessageEntry::new(msg::DEV_GLOBAL_MSG_CONTENT, "Content"),
+    // endregion
+];
@@ -0,0 +1,327 @@
+//! 后端消息目录
+//!
+//! 所有需要展示给用户的文本都通过消息 ID 在目录中查找，
+//! 前端收到的事件负载中同时带有消息 ID、参数以及按当前语言渲染好的文本
+
+mod en_us;
+pub mod msg;
+mod zh_cn;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+/// 默认语言
+pub const DEFAULT_LOCALE: Locale = Locale::ZhCn;
+
+/// 支持的语言
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+    #[serde(rename = "en-US")]
+    EnUs,
+}
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh-CN",
+            Locale::EnUs => "en-US",
+        }
+    }
+
+    /// 从配置字符串转换，只比较语言部分时也能匹配（如 `en`、`zh_TW`）
+    pub fn from_str(input: &str) -> Option<Locale> {
+        let lower = input.trim().replace('_', "-").to_lowercase();
+        match lower.as_str() {
+            "zh-cn" | "zh" => Some(Locale::ZhCn),
+            "en-us" | "en" => Some(Locale::EnUs),
+            _ if lower.starts_with("zh-") => Some(Locale::ZhCn),
+            _ if lower.starts_with("en-") => Some(Locale::EnUs),
+            _ => None,
+        }
+    }
+
+    /// 当前语言的消息目录
+    fn messages(&self) -> &'static [MessageEntry] {
+        match self {
+            Locale::ZhCn => zh_cn::MESSAGES,
+            Locale::EnUs => en_us::MESSAGES,
+        }
+    }
+}
+
+/// 目录中的一条消息
+///
+/// 模板中使用 `{name}` 引用参数；`one` 为单数形式，
+/// 参数 `count` 为 1 时使用，其余情况使用 `other`
+#[derive(Serialize, Debug, Clone, Copy)]
+pub struct MessageEntry {
+    pub id: &'static str,
+    pub one: Option<&'static str>,
+    pub other: &'static str,
+}
+
+impl MessageEntry {
+    /// 没有复数变化的消息
+    pub const fn new(id: &'static str, other: &'static str) -> MessageEntry {
+        MessageEntry {
+            id,
+            one: None,
+            other,
+        }
+    }
+
+    /// 区分单复数的消息
+    pub const fn plural(id: &'static str, one: &'static str, other: &'static str) -> MessageEntry {
+        MessageEntry {
+            id,
+            one: Some(one),
+            other,
+        }
+    }
+
+    /// 根据数量选择模板
+    fn select(&self, count: Option<i64>) -> &'static str {
+        match (self.one, count) {
+            (Some(one), Some(1)) => one,
+            _ => self.other,
+        }
+    }
+}
+
+/// 发送给前端的本地化消息
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedMessage {
+    /// 消息 ID
+    pub id: String,
+    /// 模板参数
+    #[serde(default)]
+    pub args: BTreeMap<String, String>,
+    /// 按当前语言渲染后的文本
+    pub text: String,
+}
+
+impl LocalizedMessage {
+    /// 使用当前语言构建消息
+    pub fn new(id: &str, args: &[(&str, String)]) -> LocalizedMessage {
+        let args: BTreeMap<String, String> = args
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect();
+        LocalizedMessage {
+            id: id.to_string(),
+            text: render(current_locale(), id, &args),
+            args,
+        }
+    }
+}
+
+/// 当前语言【初始化时读取配置，可在运行时切换】
+static CURRENT_LOCALE: Lazy<RwLock<Locale>> = Lazy::new(|| RwLock::new(configured_locale()));
+
+/// 配置中的语言
+#[cfg(not(test))]
+fn configured_locale() -> Locale {
+    crate::structs::config::sys_config()
+        .locale
+        .as_deref()
+        .and_then(Locale::from_str)
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// 单元测试不打开照片库读取配置，使用默认语言
+#[cfg(test)]
+fn configured_locale() -> Locale {
+    DEFAULT_LOCALE
+}
+
+/// 获取当前语言
+pub fn current_locale() -> Locale {
+    *CURRENT_LOCALE.read().unwrap()
+}
+
+/// 切换当前语言
+pub fn set_locale(locale: Locale) {
+    *CURRENT_LOCALE.write().unwrap() = locale;
+}
+
+/// 查找消息，当前语言缺失时回退到默认语言
+fn lookup(locale: Locale, id: &str) -> Option<&'static MessageEntry> {
+    locale
+        .messages()
+        .iter()
+        .find(|x| x.id == id)
+        .or_else(|| DEFAULT_LOCALE.messages().iter().find(|x| x.id == id))
+}
+
+/// 获取不带参数的消息文本，目录中不存在时返回消息 ID 本身
+pub fn text(id: &'static str) -> &'static str {
+    text_in(current_locale(), id)
+}
+
+/// 获取指定语言不带参数的消息文本
+pub fn text_in(locale: Locale, id: &'static str) -> &'static str {
+    lookup(locale, id).map(|x| x.other).unwrap_or(id)
+}
+
+/// 使用当前语言渲染消息
+/// - id 消息 ID
+/// - args 模板参数
+pub fn t(id: &str, args: &[(&str, String)]) -> String {
+    LocalizedMessage::new(id, args).text
+}
+
+/// 使用当前语言渲染的消息作为错误【错误信息会直接展示给用户】
+/// - id 消息 ID
+/// - args 模板参数
+pub fn error(id: &str, args: &[(&str, String)]) -> anyhow::Error {
+    anyhow::anyhow!(t(id, args))
+}
+
+/// 使用指定语言渲染消息
+pub fn render(locale: Locale, id: &str, args: &BTreeMap<String, String>) -> String {
+    let Some(entry) = lookup(locale, id) else {
+        log::warn!("消息目录中不存在: {}", id);
+        return id.to_string();
+    };
+    let count = args.get("count").and_then(|x| x.parse::<i64>().ok());
+    let mut result = entry.select(count).to_string();
+    for (key, value) in args {
+        result = result.replace(&format!("{{{}}}", key), value);
+    }
+    result
+}
+
+/// 导出指定语言的完整目录，供前端按 ID 自行渲染
+pub fn catalog(locale: Locale) -> BTreeMap<&'static str, MessageEntry> {
+    let mut result: BTreeMap<&'static str, MessageEntry> = DEFAULT_LOCALE
+        .messages()
+        .iter()
+        .map(|x| (x.id, *x))
+        .collect();
+    for entry in locale.messages() {
+        result.insert(entry.id, *entry);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::exif_utils::exif_enum::{
+        ExposureProgram, MeteringMode, SceneCaptureType, WhiteBalance,
+    };
+    use crate::utils::exif_utils::tag::ExifToolDesc;
+    use std::collections::HashSet;
+    use std::fs;
+    use std::path::Path;
+
+    fn args(list: &[(&str, &str)]) -> BTreeMap<String, String> {
+        list.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_plural() {
+        let one = render(Locale::EnUs, msg::TASK_SUBMITTED, &args(&[("count", "1")]));
+        assert_eq!(one, "Started, 1 task queued");
+        let other = render(Locale::EnUs, msg::TASK_SUBMITTED, &args(&[("count", "3")]));
+        assert_eq!(other, "Started, 3 tasks queued");
+        let zh = render(Locale::ZhCn, msg::TASK_SUBMITTED, &args(&[("count", "1")]));
+        assert_eq!(zh, "已开始运行，共 1 个任务");
+    }
+
+    #[test]
+    fn test_render_missing() {
+        assert_eq!(
+            render(Locale::EnUs, "not.exist", &BTreeMap::new()),
+            "not.exist"
+        );
+    }
+
+    #[test]
+    fn test_catalog_complete() {
+        // 每种语言的目录都必须覆盖默认语言中的全部消息，且没有重复 ID
+        let base: HashSet<&str> = DEFAULT_LOCALE.messages().iter().map(|x| x.id).collect();
+        assert_eq!(base.len(), DEFAULT_LOCALE.messages().len());
+        for locale in [Locale::ZhCn, Locale::EnUs] {
+            let ids: HashSet<&str> = locale.messages().iter().map(|x| x.id).collect();
+            assert_eq!(ids.len(), locale.messages().len(), "{:?}", locale);
+            assert_eq!(ids, base, "{:?}", locale);
+        }
+    }
+
+    #[test]
+    fn test_exif_labels() {
+        // 标签和枚举的展示名称都必须在目录中
+        let ids: HashSet<&str> = DEFAULT_LOCALE.messages().iter().map(|x| x.id).collect();
+        for info in ExifToolDesc::EXIF_INFOS {
+            assert!(ids.contains(info.label_id), "{}", info.label_id);
+        }
+        let enum_ids = MeteringMode::ALL
+            .iter()
+            .map(|x| x.label_id())
+            .chain(ExposureProgram::ALL.iter().map(|x| x.label_id()))
+            .chain(WhiteBalance::ALL.iter().map(|x| x.label_id()))
+            .chain(SceneCaptureType::ALL.iter().map(|x| x.label_id()));
+        for id in enum_ids {
+            assert!(ids.contains(id), "{}", id);
+        }
+        assert_eq!(ExifToolDesc::FLASH.label_id, "exif.tag.Flash");
+        assert_eq!(text_in(Locale::EnUs, ExifToolDesc::FLASH.label_id), "Flash");
+    }
+
+    /// 找出目录中第一个参数是中文字面量的 `anyhow!`、`bail!`
+    fn find_hardcoded_errors(dir: &Path, found: &mut Vec<String>) {
+        for entry in fs::read_dir(dir).unwrap().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                find_hardcoded_errors(&path, found);
+                continue;
+            }
+            if path.extension().and_then(|x| x.to_str()) != Some("rs") {
+                continue;
+            }
+            let content = fs::read_to_string(&path).unwrap();
+            for name in ["anyhow!(", "bail!("] {
+                for (index, _) in content.match_indices(name) {
+                    let rest = content[index + name.len()..].trim_start();
+                    let Some(literal) = rest.strip_prefix('"') else {
+                        continue;
+                    };
+                    let literal = literal.split('"').next().unwrap_or_default();
+                    if literal
+                        .chars()
+                        .any(|c| ('\u{4e00}'..='\u{9fff}').contains(&c))
+                    {
+                        let line = content[..index].matches('\n').count() + 1;
+                        found.push(format!("{}:{}", path.display(), line));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_hardcoded_errors() {
+        // 命令和服务返回给前端的错误都要走消息目录
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut found = Vec::new();
+        for dir in ["commands", "services"] {
+            find_hardcoded_errors(&src.join(dir), &mut found);
+        }
+        assert!(found.is_empty(), "{:#?}", found);
+    }
+
+    #[test]
+    fn test_locale_from_str() {
+        assert_eq!(Locale::from_str("en"), Some(Locale::EnUs));
+        assert_eq!(Locale::from_str("zh_TW"), Some(Locale::ZhCn));
+        assert_eq!(Locale::from_str("fr-FR"), None);
+    }
+}
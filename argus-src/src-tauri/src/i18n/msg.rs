@@ -0,0 +1,404 @@
+//! 消息 ID
+
+// region 错误信息
+pub const ERROR_THUMBNAIL_CACHE_CONFIG_READ_FAILED: &str = "error.thumbnailCacheConfigReadFailed";
+pub const ERROR_THUMBNAIL_CACHE_CONFIG_SUGGESTION: &str = "error.thumbnailCacheConfigSuggestion";
+pub const ERROR_ORIGINAL_IMAGE_READ_FAILED: &str = "error.originalImageReadFailed";
+pub const ERROR_HASH_CONVERSION_FAILED: &str = "error.hashConversionFailed";
+pub const ERROR_FILE_SAVE_FAILED: &str = "error.fileSaveFailed";
+pub const ERROR_PARENT_PATH_READ_FAILED: &str = "error.parentPathReadFailed";
+pub const ERROR_THUMBNAIL_GENERATION_FAILED: &str = "error.thumbnailGenerationFailed";
+pub const ERROR_SPECIFIED_FILE_DOES_NOT_EXIST: &str = "error.specifiedFileDoesNotExist";
+pub const ERROR_CONFIG_FILE_DOES_NOT_EXIST: &str = "error.configFileDoesNotExist";
+pub const ERROR_IMAGE_VALIDATION_FAILED: &str = "error.imageValidationFailed";
+pub const ERROR_IMAGE_PROCESS_PANICKED: &str = "error.imageProcessPanicked";
+// endregion
+
+// region 照片加载任务
+/// 参数：path、error
+pub const PHOTO_LOAD_FAILED: &str = "photoLoad.failed";
+/// 参数：count
+pub const TASK_SUBMITTED: &str = "task.submitted";
+pub const TASK_FINISHED: &str = "task.finished";
+pub const TASK_CANCELLED: &str = "task.cancelled";
+/// 参数：taskId
+pub const SCAN_TASK_NOT_FOUND: &str = "task.notFound";
+// endregion
+
+// region 照片存储路径
+pub const PHOTO_STORAGE_ADD_SUCCESS: &str = "photoStorage.addSuccess";
+pub const PHOTO_STORAGE_ADD_FAILED: &str = "photoStorage.addFailed";
+// endregion
+
+// region 日志
+/// 参数：level
+pub const LOG_LEVEL_INVALID: &str = "log.levelInvalid";
+// endregion
+
+// region 文件
+pub const FILE_DOES_NOT_EXIST: &str = "file.doesNotExist";
+/// 参数：path
+pub const DIRECTORY_NOT_ACCESSIBLE: &str = "file.directoryNotAccessible";
+/// 参数：path
+pub const FOLDER_NOT_FOUND: &str = "file.folderNotFound";
+/// 参数：path
+pub const FILE_ALREADY_EXISTS: &str = "file.alreadyExists";
+/// 参数：name
+pub const FILE_NAME_INVALID: &str = "file.nameInvalid";
+pub const FILE_NAME_UNCHANGED: &str = "file.nameUnchanged";
+/// 参数：path
+pub const PHOTO_FILE_NOT_FOUND: &str = "file.photoFileNotFound";
+/// 参数：id
+pub const PHOTO_RENAME_FAILED: &str = "file.photoRenameFailed";
+/// 参数：name
+pub const PHOTO_TEMPLATE_NAME_EMPTY: &str = "file.templateNameEmpty";
+/// 参数：path、error
+pub const FILE_MOVE_FAILED: &str = "file.moveFailed";
+// endregion
+
+// region 元数据编辑
+/// 参数：count
+pub const EDIT_RATING: &str = "edit.rating";
+/// 参数：count、seconds
+pub const EDIT_SHIFT_TIME: &str = "edit.shiftTime";
+/// 参数：count
+pub const EDIT_GPS: &str = "edit.gps";
+/// 参数：count
+pub const EDIT_TAGS: &str = "edit.tags";
+/// 参数：count
+pub const EDIT_CAPTION: &str = "edit.caption";
+/// 参数：count、name
+pub const EDIT_PRESET: &str = "edit.preset";
+/// 参数：count
+pub const EDIT_NOTES: &str = "edit.notes";
+/// 参数：count、operations
+pub const EDIT_BATCH: &str = "edit.batch";
+/// 参数：degrees
+pub const EDIT_ROTATE: &str = "edit.rotate";
+/// 参数：degrees
+pub const EDIT_ROTATE_INVALID: &str = "edit.rotateInvalid";
+pub const EDIT_FLIP_HORIZONTAL: &str = "edit.flipHorizontal";
+pub const EDIT_FLIP_VERTICAL: &str = "edit.flipVertical";
+/// 参数：axis
+pub const EDIT_FLIP_INVALID: &str = "edit.flipInvalid";
+pub const EDIT_NO_CHANGE: &str = "edit.noChange";
+pub const EDIT_NOTHING_TO_UNDO: &str = "edit.nothingToUndo";
+pub const EDIT_NOTHING_TO_REDO: &str = "edit.nothingToRedo";
+/// 参数：rating、max
+pub const EDIT_RATING_OUT_OF_RANGE: &str = "edit.ratingOutOfRange";
+/// 参数：error
+pub const EDIT_JOURNAL_INVALID: &str = "edit.journalInvalid";
+// endregion
+
+// region 分享
+pub const SHARE_BUNDLE_TITLE: &str = "share.bundleTitle";
+/// 参数：count
+pub const SHARE_BUNDLE_COUNT: &str = "share.bundleCount";
+pub const SHARE_NO_PHOTOS: &str = "share.noPhotos";
+/// 参数：errors
+pub const SHARE_ALL_FAILED: &str = "share.allFailed";
+// endregion
+
+// region 通用
+/// 列表分隔符
+pub const LIST_SEPARATOR: &str = "common.listSeparator";
+// endregion
+
+// region 元数据展示【标签和枚举的展示名称使用 `ExifInfo::label_id`、`label_id()` 作为 ID】
+pub const EXIF_GPS_INFO: &str = "exif.gpsInfo";
+pub const EXIF_FLASH_ON: &str = "exif.Flash.on";
+pub const EXIF_FLASH_OFF: &str = "exif.Flash.off";
+pub const EXIF_FLASH_AUTO: &str = "exif.Flash.auto";
+pub const EXIF_FLASH_FIRED: &str = "exif.Flash.fired";
+pub const EXIF_FLASH_NOT_FIRED: &str = "exif.Flash.notFired";
+pub const EXIF_FLASH_RED_EYE: &str = "exif.Flash.redEye";
+pub const EXIF_FLASH_NO_FUNCTION: &str = "exif.Flash.noFunction";
+pub const EXIF_NORTH_TRUE: &str = "exif.North.true";
+pub const EXIF_NORTH_MAGNETIC: &str = "exif.North.magnetic";
+// endregion
+
+// region 坐标
+/// 参数：latitude、longitude
+pub const GPS_COORDINATE: &str = "gps.coordinate";
+/// 参数：value
+pub const GPS_LATITUDE_NORTH: &str = "gps.latitudeNorth";
+pub const GPS_LATITUDE_SOUTH: &str = "gps.latitudeSouth";
+pub const GPS_LONGITUDE_EAST: &str = "gps.longitudeEast";
+pub const GPS_LONGITUDE_WEST: &str = "gps.longitudeWest";
+// endregion
+
+// region 语言
+/// 参数：locale
+pub const LOCALE_UNSUPPORTED: &str = "locale.unsupported";
+// endregion
+
+// region 保险箱
+pub const VAULT_ALREADY_EXISTS: &str = "vault.alreadyExists";
+/// 参数：min
+pub const VAULT_PASSPHRASE_TOO_SHORT: &str = "vault.passphraseTooShort";
+pub const VAULT_NOT_CREATED: &str = "vault.notCreated";
+pub const VAULT_WRONG_PASSPHRASE: &str = "vault.wrongPassphrase";
+pub const VAULT_LOCKED: &str = "vault.locked";
+pub const VAULT_REMOTE_PHOTO: &str = "vault.remotePhoto";
+/// 参数：path、error
+pub const VAULT_ORIGINAL_DELETE_FAILED: &str = "vault.originalDeleteFailed";
+/// 参数：path
+pub const VAULT_RESTORE_TARGET_EXISTS: &str = "vault.restoreTargetExists";
+pub const VAULT_VERIFY_FAILED: &str = "vault.verifyFailed";
+pub const VAULT_INVALID_ITERATIONS: &str = "vault.invalidIterations";
+pub const VAULT_INVALID_KEY: &str = "vault.invalidKey";
+pub const VAULT_ENCRYPT_FAILED: &str = "vault.encryptFailed";
+pub const VAULT_DECRYPT_FAILED: &str = "vault.decryptFailed";
+pub const VAULT_INVALID_FILE: &str = "vault.invalidFile";
+pub const VAULT_FILE_INCOMPLETE: &str = "vault.fileIncomplete";
+pub const VAULT_RANDOM_FAILED: &str = "vault.randomFailed";
+// endregion
+
+// region 远程存储
+/// 参数：id
+pub const REMOTE_STORAGE_NOT_FOUND: &str = "remote.storageNotFound";
+/// 参数：name
+pub const REMOTE_STORAGE_DISABLED: &str = "remote.storageDisabled";
+pub const REMOTE_SYNC_RUNNING: &str = "remote.syncRunning";
+pub const REMOTE_NAME_REQUIRED: &str = "remote.nameRequired";
+/// 参数：endpoint
+pub const REMOTE_ENDPOINT_SCHEME: &str = "remote.endpointScheme";
+/// 参数：endpoint
+pub const REMOTE_ENDPOINT_INVALID: &str = "remote.endpointInvalid";
+pub const REMOTE_BUCKET_REQUIRED: &str = "remote.bucketRequired";
+pub const REMOTE_FILE_NAME_INVALID: &str = "remote.fileNameInvalid";
+/// 参数：status、body
+pub const REMOTE_REQUEST_FAILED: &str = "remote.requestFailed";
+/// 参数：response
+pub const REMOTE_MULTIPART_CREATE_FAILED: &str = "remote.multipartCreateFailed";
+/// 参数：part
+pub const REMOTE_MULTIPART_NO_ETAG: &str = "remote.multipartNoEtag";
+/// 参数：code、response
+pub const REMOTE_MULTIPART_COMPLETE_FAILED: &str = "remote.multipartCompleteFailed";
+// endregion
+
+// region 监视文件夹
+pub const WATCH_FOLDER_PATH_REQUIRED: &str = "watchFolder.pathRequired";
+pub const WATCH_FOLDER_DEST_REQUIRED: &str = "watchFolder.destRequired";
+/// 参数：path
+pub const WATCH_FOLDER_DEST_INSIDE: &str = "watchFolder.destInside";
+/// 参数：id
+pub const WATCH_FOLDER_NOT_FOUND: &str = "watchFolder.notFound";
+/// 参数：id、error
+pub const WATCH_FOLDER_TAGS_INVALID: &str = "watchFolder.tagsInvalid";
+// endregion
+
+// region 节省空间
+/// 参数：error
+pub const SPACE_SAVER_TEMP_LIST_SAVE_FAILED: &str = "spaceSaver.tempListSaveFailed";
+pub const SPACE_SAVER_METADATA_MISMATCH: &str = "spaceSaver.metadataMismatch";
+/// 参数：chunks
+pub const SPACE_SAVER_METADATA_UNSUPPORTED: &str = "spaceSaver.metadataUnsupported";
+/// 参数：depth
+pub const SPACE_SAVER_BIT_DEPTH_UNSUPPORTED: &str = "spaceSaver.bitDepthUnsupported";
+pub const SPACE_SAVER_ANIMATED_SKIPPED: &str = "spaceSaver.animatedSkipped";
+/// 参数：error
+pub const SPACE_SAVER_WEBP_ENCODE_FAILED: &str = "spaceSaver.webpEncodeFailed";
+pub const SPACE_SAVER_WEBP_CONFIG_FAILED: &str = "spaceSaver.webpConfigFailed";
+pub const SPACE_SAVER_SIZE_MISMATCH: &str = "spaceSaver.sizeMismatch";
+pub const SPACE_SAVER_PIXEL_MISMATCH: &str = "spaceSaver.pixelMismatch";
+pub const SPACE_SAVER_SOURCE_MODIFIED: &str = "spaceSaver.sourceModified";
+/// 参数：id
+pub const SPACE_SAVER_PLAN_NOT_FOUND: &str = "spaceSaver.planNotFound";
+// endregion
+
+// region 通用错误
+/// 参数：id
+pub const PHOTO_NOT_FOUND: &str = "common.photoNotFound";
+pub const PHOTOS_PARTIALLY_MISSING: &str = "common.photosPartiallyMissing";
+/// 参数：id
+pub const ALBUM_NOT_FOUND: &str = "common.albumNotFound";
+/// 参数：id
+pub const TAG_NOT_FOUND: &str = "common.tagNotFound";
+pub const THUMBNAIL_PATH_NOT_CONFIGURED: &str = "common.thumbnailPathNotConfigured";
+/// 参数：format
+pub const EXPORT_FORMAT_UNSUPPORTED: &str = "common.exportFormatUnsupported";
+/// 参数：path、error
+pub const IMAGE_READ_FAILED: &str = "common.imageReadFailed";
+/// 参数：date
+pub const DATE_INVALID: &str = "common.dateInvalid";
+// endregion
+
+// region 外部工具
+/// 参数：error
+pub const JPEGTRAN_START_FAILED: &str = "tool.jpegtranStartFailed";
+/// 参数：error
+pub const JPEGTRAN_FAILED: &str = "tool.jpegtranFailed";
+/// 参数：path、error
+pub const FFMPEG_START_FAILED: &str = "tool.ffmpegStartFailed";
+/// 参数：status、error
+pub const FFMPEG_FAILED: &str = "tool.ffmpegFailed";
+/// 参数：status
+pub const BRCTL_DOWNLOAD_FAILED: &str = "tool.brctlDownloadFailed";
+// endregion
+
+// region 照片库
+pub const LIBRARY_NAME_REQUIRED: &str = "library.nameRequired";
+/// 参数：path
+pub const LIBRARY_ALREADY_LIBRARY: &str = "library.alreadyLibrary";
+/// 参数：path
+pub const LIBRARY_NOT_LIBRARY: &str = "library.notLibrary";
+/// 参数：path
+pub const LIBRARY_NOT_ADDED: &str = "library.notAdded";
+/// 参数：path
+pub const LIBRARY_ALREADY_ADDED: &str = "library.alreadyAdded";
+/// 参数：path
+pub const LIBRARY_INACCESSIBLE: &str = "library.inaccessible";
+/// 参数：path、error
+pub const LIBRARY_DATABASE_INIT_FAILED: &str = "library.databaseInitFailed";
+/// 参数：error
+pub const SETTINGS_DATABASE_INIT_FAILED: &str = "library.settingsDatabaseInitFailed";
+// endregion
+
+// region 备份
+pub const BACKUP_RUNNING: &str = "backup.running";
+/// 参数：path
+pub const BACKUP_FOLDER_EXISTS: &str = "backup.folderExists";
+/// 参数：path
+pub const BACKUP_DATABASE_NOT_FOUND: &str = "backup.databaseNotFound";
+/// 参数：error
+pub const BACKUP_CORRUPTED: &str = "backup.corrupted";
+/// 参数：step、path
+pub const BACKUP_RESTORE_BUSY: &str = "backup.restoreBusy";
+/// 参数：path、error
+pub const BACKUP_RESTORE_VERIFY_FAILED: &str = "backup.restoreVerifyFailed";
+/// 参数：error
+pub const BACKUP_MIGRATION_FAILED: &str = "backup.migrationFailed";
+// endregion
+
+// region 校验
+pub const HASH_VERIFY_NO_PHOTOS: &str = "verify.noPhotos";
+pub const HASH_VERIFY_TARGET_REQUIRED: &str = "verify.targetRequired";
+/// 参数：error
+pub const HASH_VERIFY_START_FAILED: &str = "verify.startFailed";
+/// 参数：id
+pub const HASH_VERIFY_JOB_NOT_RUNNING: &str = "verify.jobNotRunning";
+/// 参数：id
+pub const HASH_VERIFY_JOB_NOT_FOUND: &str = "verify.jobNotFound";
+pub const CHECKSUM_SOURCE_REQUIRED: &str = "verify.checksumSourceRequired";
+pub const CHECKSUM_NO_PHOTOS: &str = "verify.checksumNoPhotos";
+pub const CHECKSUM_NO_COMMON_DIR: &str = "verify.checksumNoCommonDir";
+/// 参数：path、error
+pub const CHECKSUM_MANIFEST_READ_FAILED: &str = "verify.checksumManifestReadFailed";
+// endregion
+
+// region 照片整理
+pub const BATCH_NO_PHOTOS: &str = "organize.batchNoPhotos";
+pub const BATCH_NO_OPERATIONS: &str = "organize.batchNoOperations";
+/// 参数：id
+pub const BATCH_MIRROR_ALBUM: &str = "organize.batchMirrorAlbum";
+pub const BATCH_DEST_REQUIRED: &str = "organize.batchDestRequired";
+pub const BATCH_MULTIPLE_MOVES: &str = "organize.batchMultipleMoves";
+/// 参数：id
+pub const ORGANIZE_RULE_NOT_FOUND: &str = "organize.ruleNotFound";
+/// 参数：id、error
+pub const ORGANIZE_RULE_CONDITION_INVALID: &str = "organize.ruleConditionInvalid";
+/// 参数：id、action
+pub const ORGANIZE_RULE_ACTION_INVALID: &str = "organize.ruleActionInvalid";
+/// 参数：id
+pub const ORGANIZATION_ALBUM_DUPLICATED: &str = "organize.albumDuplicated";
+/// 参数：id
+pub const ORGANIZATION_TAG_DUPLICATED: &str = "organize.tagDuplicated";
+pub const STACK_TOO_FEW_PHOTOS: &str = "organize.stackTooFewPhotos";
+/// 参数：id
+pub const STACK_ORIGINAL_NOT_INCLUDED: &str = "organize.stackOriginalNotIncluded";
+/// 参数：id
+pub const STACK_PHOTO_NOT_STACKED: &str = "organize.stackPhotoNotStacked";
+/// 参数：id、path
+pub const COVER_NOT_IN_FOLDER: &str = "organize.coverNotInFolder";
+/// 参数：id、album
+pub const COVER_NOT_IN_ALBUM: &str = "organize.coverNotInAlbum";
+/// 参数：path、error
+pub const DUPLICATE_MANIFEST_READ_FAILED: &str = "organize.duplicateManifestReadFailed";
+pub const DUPLICATE_CONTENT_MISMATCH: &str = "organize.duplicateContentMismatch";
+/// 参数：error
+pub const DUPLICATE_HARD_LINK_FAILED: &str = "organize.duplicateHardLinkFailed";
+// endregion
+
+// region 导入导出
+/// 参数：error
+pub const CARD_IMPORT_COPY_READ_FAILED: &str = "transfer.cardCopyReadFailed";
+pub const CARD_IMPORT_VERIFY_FAILED: &str = "transfer.cardVerifyFailed";
+pub const CLOUD_FILE_NOT_DOWNLOADED: &str = "transfer.cloudFileNotDownloaded";
+pub const CLOUD_DOWNLOAD_TIMEOUT: &str = "transfer.cloudDownloadTimeout";
+pub const METADATA_CSV_HASH_MISSING: &str = "transfer.csvHashMissing";
+/// 参数：line、error
+pub const METADATA_CSV_ROW_INVALID: &str = "transfer.csvRowInvalid";
+/// 参数：path、error
+pub const EXPORT_WATERMARK_LOGO_FAILED: &str = "transfer.watermarkLogoFailed";
+/// 参数：error
+pub const EXPORT_LOCATION_FAILED: &str = "transfer.locationFailed";
+pub const SLIDESHOW_OUTPUT_REQUIRED: &str = "transfer.slideshowOutputRequired";
+/// 参数：path
+pub const SLIDESHOW_MUSIC_NOT_FOUND: &str = "transfer.slideshowMusicNotFound";
+pub const SLIDESHOW_NO_PHOTOS: &str = "transfer.slideshowNoPhotos";
+/// 参数：error
+pub const SLIDESHOW_START_FAILED: &str = "transfer.slideshowStartFailed";
+/// 参数：id
+pub const SLIDESHOW_JOB_NOT_RUNNING: &str = "transfer.slideshowJobNotRunning";
+// endregion
+
+// region 照片查看
+/// 参数：from、to
+pub const THUMBNAIL_VISIBLE_RANGE_INVALID: &str = "view.visibleRangeInvalid";
+/// 参数：id、index
+pub const MP_IMAGE_NOT_FOUND: &str = "view.mpImageNotFound";
+/// 参数：min、max
+pub const COMPARE_PHOTO_RANGE: &str = "view.comparePhotoRange";
+pub const COMPARE_DUPLICATED: &str = "view.compareDuplicated";
+/// 参数：path、error
+pub const COMPARE_PREVIEW_READ_FAILED: &str = "view.comparePreviewReadFailed";
+pub const BENCHMARK_NO_PHOTOS: &str = "view.benchmarkNoPhotos";
+/// 参数：size
+pub const HTTP_THUMBNAIL_SIZE_UNSUPPORTED: &str = "view.httpThumbnailSizeUnsupported";
+/// 参数：address、error
+pub const HTTP_ADDRESS_INVALID: &str = "view.httpAddressInvalid";
+// endregion
+
+// region 设置
+pub const PRESET_NAME_REQUIRED: &str = "settings.presetNameRequired";
+/// 参数：name
+pub const PRESET_EMPTY: &str = "settings.presetEmpty";
+/// 参数：id
+pub const PRESET_NOT_FOUND: &str = "settings.presetNotFound";
+pub const PRIVACY_ZONE_NAME_REQUIRED: &str = "settings.privacyZoneNameRequired";
+/// 参数：name
+pub const PRIVACY_ZONE_CENTER_INVALID: &str = "settings.privacyZoneCenterInvalid";
+/// 参数：name
+pub const PRIVACY_ZONE_RADIUS_INVALID: &str = "settings.privacyZoneRadiusInvalid";
+/// 参数：id
+pub const PRIVACY_ZONE_NOT_FOUND: &str = "settings.privacyZoneNotFound";
+/// 参数：id、error
+pub const PRIVACY_LOCATION_INVALID: &str = "settings.privacyLocationInvalid";
+/// 参数：id
+pub const USER_NOT_FOUND: &str = "settings.userNotFound";
+pub const USER_DEFAULT_UNDELETABLE: &str = "settings.userDefaultUndeletable";
+pub const MAINTENANCE_TASK_RUNNING: &str = "settings.maintenanceTaskRunning";
+/// 参数：paths
+pub const MAINTENANCE_RESCAN_ALL_FAILED: &str = "settings.maintenanceRescanAllFailed";
+/// 参数：path、available、required
+pub const DISK_SPACE_INSUFFICIENT: &str = "settings.diskSpaceInsufficient";
+// endregion
+
+// region 数据库占用
+/// 参数：action
+pub const DATABASE_BUSY_SCANNING: &str = "database.busyScanning";
+/// 参数：action
+pub const DATABASE_BUSY_WRITING: &str = "database.busyWriting";
+/// 参数：action
+pub const DATABASE_BUSY_BACKUP: &str = "database.busyBackup";
+pub const DATABASE_ACTION_SWITCH_LIBRARY: &str = "database.actionSwitchLibrary";
+pub const DATABASE_ACTION_RESTORE: &str = "database.actionRestore";
+// endregion
+
+// region 调试
+/// 参数：name
+pub const DEV_EMIT_GREETING: &str = "dev.emitGreeting";
+pub const DEV_GLOBAL_MSG_TITLE: &str = "dev.globalMsgTitle";
+pub const DEV_GLOBAL_MSG_CONTENT: &str = "dev.globalMsgContent";
+// endregion
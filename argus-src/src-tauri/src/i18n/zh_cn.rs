@@ -0,0 +1,546 @@
+use crate::i18n::{msg, MessageEntry};
+use crate::utils::exif_utils::exif_enum::{
+    ExposureProgram, MeteringMode, SceneCaptureType, WhiteBalance,
+};
+use crate::utils::exif_utils::tag::ExifToolDesc;
+
+/// 简体中文
+pub const MESSAGES: &[MessageEntry] = &[
+    // region 错误信息
+    MessageEntry::new(msg::ERROR_THUMBNAIL_CACHE_CONFIG_READ_FAILED, "配置文件读取失败！"),
+    MessageEntry::new(msg::ERROR_THUMBNAIL_CACHE_CONFIG_SUGGESTION, "检查指定文件是否存在！"),
+    MessageEntry::new(msg::ERROR_ORIGINAL_IMAGE_READ_FAILED, "原图读取失败！"),
+    MessageEntry::new(msg::ERROR_HASH_CONVERSION_FAILED, "Hash 转换失败！"),
+    MessageEntry::new(msg::ERROR_FILE_SAVE_FAILED, "文件保存失败！"),
+    MessageEntry::new(msg::ERROR_PARENT_PATH_READ_FAILED, "父路径读取失败！"),
+    MessageEntry::new(msg::ERROR_THUMBNAIL_GENERATION_FAILED, "缩略图生成失败！"),
+    MessageEntry::new(msg::ERROR_SPECIFIED_FILE_DOES_NOT_EXIST, "指定文件不存在！"),
+    MessageEntry::new(msg::ERROR_CONFIG_FILE_DOES_NOT_EXIST, "配置文件不存在！"),
+    MessageEntry::new(msg::ERROR_IMAGE_VALIDATION_FAILED, "图像校验失败！"),
+    MessageEntry::new(msg::ERROR_IMAGE_PROCESS_PANICKED, "图像处理崩溃，文件可能已损坏！"),
+    // endregion
+
+    // region 照片加载任务
+    MessageEntry::new(msg::PHOTO_LOAD_FAILED, "{path} 出错: {error}"),
+    MessageEntry::new(msg::TASK_SUBMITTED, "已开始运行，共 {count} 个任务"),
+    MessageEntry::new(msg::TASK_FINISHED, "完成"),
+    MessageEntry::new(msg::TASK_CANCELLED, "已取消"),
+    MessageEntry::new(msg::SCAN_TASK_NOT_FOUND, "扫描任务 {taskId} 不存在或已过期"),
+    // endregion
+
+    // region 照片存储路径
+    MessageEntry::new(msg::PHOTO_STORAGE_ADD_SUCCESS, "插入成功."),
+    MessageEntry::new(msg::PHOTO_STORAGE_ADD_FAILED, "数据库读取失败！"),
+    // endregion
+
+    // region 日志
+    MessageEntry::new(msg::LOG_LEVEL_INVALID, "日志级别 {level} 无效！"),
+    // endregion
+
+    // region 文件
+    MessageEntry::new(msg::FILE_DOES_NOT_EXIST, "文件不存在！"),
+    MessageEntry::new(msg::DIRECTORY_NOT_ACCESSIBLE, "文件夹 {path} 不存在或没有读取权限！"),
+    MessageEntry::new(msg::FOLDER_NOT_FOUND, "文件夹 {path} 不存在！"),
+    MessageEntry::new(msg::FILE_ALREADY_EXISTS, "文件 {path} 已存在！"),
+    MessageEntry::new(msg::FILE_NAME_INVALID, "文件名 {name} 无效！"),
+    MessageEntry::new(msg::FILE_NAME_UNCHANGED, "文件名没有变化！"),
+    MessageEntry::new(msg::PHOTO_FILE_NOT_FOUND, "照片文件 {path} 不存在！"),
+    MessageEntry::new(msg::PHOTO_RENAME_FAILED, "照片 {id} 重命名失败！"),
+    MessageEntry::new(
+        msg::PHOTO_TEMPLATE_NAME_EMPTY,
+        "照片 {name} 按模板生成的文件名为空！",
+    ),
+    MessageEntry::new(msg::FILE_MOVE_FAILED, "移动 {path} 失败：{error}"),
+    // endregion
+
+    // region 元数据编辑
+    MessageEntry::new(msg::EDIT_RATING, "修改 {count} 张照片的评分"),
+    MessageEntry::new(msg::EDIT_SHIFT_TIME, "调整 {count} 张照片的拍摄时间 {seconds} 秒"),
+    MessageEntry::new(msg::EDIT_GPS, "修改 {count} 张照片的位置"),
+    MessageEntry::new(msg::EDIT_TAGS, "修改 {count} 张照片的标签"),
+    MessageEntry::new(msg::EDIT_CAPTION, "修改 {count} 张照片的说明"),
+    MessageEntry::new(msg::EDIT_PRESET, "为 {count} 张照片应用元数据预设「{name}」"),
+    MessageEntry::new(msg::EDIT_NOTES, "修改 {count} 张照片的备注"),
+    MessageEntry::new(msg::EDIT_BATCH, "对 {count} 张照片执行 {operations} 项批量操作"),
+    MessageEntry::new(msg::EDIT_ROTATE, "顺时针旋转 {degrees}°"),
+    MessageEntry::new(
+        msg::EDIT_ROTATE_INVALID,
+        "旋转角度 {degrees} 无效，只支持 90 的倍数",
+    ),
+    MessageEntry::new(msg::EDIT_FLIP_HORIZONTAL, "左右翻转"),
+    MessageEntry::new(msg::EDIT_FLIP_VERTICAL, "上下翻转"),
+    MessageEntry::new(msg::EDIT_FLIP_INVALID, "翻转方向 {axis} 无效"),
+    MessageEntry::new(msg::EDIT_NO_CHANGE, "没有需要修改的照片"),
+    MessageEntry::new(msg::EDIT_NOTHING_TO_UNDO, "没有可以撤销的编辑"),
+    MessageEntry::new(msg::EDIT_NOTHING_TO_REDO, "没有可以重做的编辑"),
+    MessageEntry::new(
+        msg::EDIT_RATING_OUT_OF_RANGE,
+        "评分 {rating} 超出范围 0 - {max}",
+    ),
+    MessageEntry::new(msg::EDIT_JOURNAL_INVALID, "编辑记录数据解析失败：{error}"),
+    // endregion
+
+    // region 分享
+    MessageEntry::new(msg::SHARE_BUNDLE_TITLE, "照片分享"),
+    MessageEntry::new(msg::SHARE_BUNDLE_COUNT, "共 {count} 张照片"),
+    MessageEntry::new(msg::SHARE_NO_PHOTOS, "没有可以分享的照片！"),
+    MessageEntry::new(msg::SHARE_ALL_FAILED, "所有照片都处理失败：{errors}"),
+    // endregion
+
+    // region 通用
+    MessageEntry::new(msg::LIST_SEPARATOR, "，"),
+    // endregion
+
+    // region 元数据展示
+    MessageEntry::new(msg::EXIF_GPS_INFO, "GPS 信息"),
+    MessageEntry::new(msg::EXIF_FLASH_ON, "强制"),
+    MessageEntry::new(msg::EXIF_FLASH_OFF, "关闭"),
+    MessageEntry::new(msg::EXIF_FLASH_AUTO, "自动"),
+    MessageEntry::new(msg::EXIF_FLASH_FIRED, "已闪光"),
+    MessageEntry::new(msg::EXIF_FLASH_NOT_FIRED, "未闪光"),
+    MessageEntry::new(msg::EXIF_FLASH_RED_EYE, "防红眼"),
+    MessageEntry::new(msg::EXIF_FLASH_NO_FUNCTION, "无闪光灯"),
+    MessageEntry::new(msg::EXIF_NORTH_TRUE, "真北"),
+    MessageEntry::new(msg::EXIF_NORTH_MAGNETIC, "磁北"),
+    MessageEntry::new(ExifToolDesc::MAKE.label_id, "相机制造商"),
+    MessageEntry::new(ExifToolDesc::MODEL.label_id, "相机型号"),
+    MessageEntry::new(ExifToolDesc::SOFTWARE.label_id, "软件"),
+    MessageEntry::new(ExifToolDesc::EXPOSURE_TIME.label_id, "快门速度"),
+    MessageEntry::new(ExifToolDesc::F_NUMBER.label_id, "光圈数"),
+    MessageEntry::new(ExifToolDesc::ISO.label_id, "ISO 感光度"),
+    MessageEntry::new(ExifToolDesc::EXIF_VERSION.label_id, "Exif 版本"),
+    MessageEntry::new(ExifToolDesc::DATE_TIME_ORIGINAL.label_id, "拍摄时间"),
+    MessageEntry::new(ExifToolDesc::OFFSET_TIME.label_id, "时区"),
+    MessageEntry::new(ExifToolDesc::MAX_APERTURE_VALUE.label_id, "最大光圈"),
+    MessageEntry::new(ExifToolDesc::FOCAL_LENGTH.label_id, "焦距"),
+    MessageEntry::new(ExifToolDesc::FOCAL_LENGTH_IN_35MM_FORMAT.label_id, "等效焦距"),
+    MessageEntry::new(ExifToolDesc::IMAGE_WIDTH.label_id, "图像宽度"),
+    MessageEntry::new(ExifToolDesc::IMAGE_HEIGHT.label_id, "图像长度"),
+    MessageEntry::new(ExifToolDesc::GPS_LATITUDE_REF.label_id, "GPS 纬度参考"),
+    MessageEntry::new(ExifToolDesc::GPS_LONGITUDE_REF.label_id, "GPS 经度参考"),
+    MessageEntry::new(ExifToolDesc::GPS_LATITUDE.label_id, "GPS 纬度"),
+    MessageEntry::new(ExifToolDesc::GPS_LONGITUDE.label_id, "GPS 经度"),
+    MessageEntry::new(ExifToolDesc::GPS_ALTITUDE_REF.label_id, "GPS 海拔参考"),
+    MessageEntry::new(ExifToolDesc::GPS_ALTITUDE.label_id, "GPS 海拔"),
+    MessageEntry::new(ExifToolDesc::GPS_SPEED_REF.label_id, "GPS 速度单位"),
+    MessageEntry::new(ExifToolDesc::GPS_SPEED.label_id, "GPS 速度"),
+    MessageEntry::new(ExifToolDesc::GPS_IMG_DIRECTION_REF.label_id, "GPS 拍摄方向参考"),
+    MessageEntry::new(ExifToolDesc::GPS_IMG_DIRECTION.label_id, "GPS 拍摄方向"),
+    MessageEntry::new(ExifToolDesc::GPS_DEST_BEARING_REF.label_id, "GPS 目的地方位参考"),
+    MessageEntry::new(ExifToolDesc::GPS_DEST_BEARING.label_id, "GPS 目的地方位"),
+    MessageEntry::new(ExifToolDesc::EXPOSURE_PROGRAM.label_id, "曝光程序"),
+    MessageEntry::new(ExifToolDesc::METERING_MODE.label_id, "测光模式"),
+    MessageEntry::new(ExifToolDesc::FLASH.label_id, "闪光灯"),
+    MessageEntry::new(ExifToolDesc::WHITE_BALANCE.label_id, "白平衡"),
+    MessageEntry::new(ExifToolDesc::SCENE_CAPTURE_TYPE.label_id, "场景类型"),
+    MessageEntry::new(ExifToolDesc::ARTIST.label_id, "艺术家"),
+    MessageEntry::new(ExifToolDesc::RATING.label_id, "评级"),
+    MessageEntry::new(ExifToolDesc::IMAGE_DESCRIPTION.label_id, "图像描述"),
+    MessageEntry::new(ExifToolDesc::DESCRIPTION.label_id, "描述"),
+    MessageEntry::new(ExifToolDesc::IMAGE_TITLE.label_id, "图像标题"),
+    MessageEntry::new(ExifToolDesc::PHOTOGRAPHER.label_id, "摄影师"),
+    MessageEntry::new(ExifToolDesc::IMAGE_EDITOR.label_id, "图像编辑者"),
+    MessageEntry::new(ExifToolDesc::CAMERA_FIRMWARE.label_id, "相机固件"),
+    MessageEntry::new(ExifToolDesc::COPYRIGHT.label_id, "版权"),
+    MessageEntry::new(MeteringMode::Unknown.label_id(), "未知"),
+    MessageEntry::new(MeteringMode::Average.label_id(), "平均测光"),
+    MessageEntry::new(MeteringMode::CenterWeightedAverage.label_id(), "中央重点测光"),
+    MessageEntry::new(MeteringMode::Spot.label_id(), "点测光"),
+    MessageEntry::new(MeteringMode::MultiSpot.label_id(), "多点测光"),
+    MessageEntry::new(MeteringMode::MultiSegment.label_id(), "评价测光"),
+    MessageEntry::new(MeteringMode::Partial.label_id(), "局部测光"),
+    MessageEntry::new(MeteringMode::Other.label_id(), "其他"),
+    MessageEntry::new(ExposureProgram::NotDefined.label_id(), "未定义"),
+    MessageEntry::new(ExposureProgram::Manual.label_id(), "手动"),
+    MessageEntry::new(ExposureProgram::Program.label_id(), "程序自动"),
+    MessageEntry::new(ExposureProgram::AperturePriority.label_id(), "光圈优先"),
+    MessageEntry::new(ExposureProgram::ShutterPriority.label_id(), "快门优先"),
+    MessageEntry::new(ExposureProgram::Creative.label_id(), "创意（慢速）"),
+    MessageEntry::new(ExposureProgram::Action.label_id(), "运动（高速）"),
+    MessageEntry::new(ExposureProgram::Portrait.label_id(), "人像"),
+    MessageEntry::new(ExposureProgram::Landscape.label_id(), "风景"),
+    MessageEntry::new(ExposureProgram::Bulb.label_id(), "B 门"),
+    MessageEntry::new(WhiteBalance::Auto.label_id(), "自动"),
+    MessageEntry::new(WhiteBalance::Manual.label_id(), "手动"),
+    MessageEntry::new(SceneCaptureType::Standard.label_id(), "标准"),
+    MessageEntry::new(SceneCaptureType::Landscape.label_id(), "风景"),
+    MessageEntry::new(SceneCaptureType::Portrait.label_id(), "人像"),
+    MessageEntry::new(SceneCaptureType::Night.label_id(), "夜景"),
+    MessageEntry::new(SceneCaptureType::Other.label_id(), "其他"),
+    // endregion
+
+    // region 坐标
+    MessageEntry::new(msg::GPS_COORDINATE, "{latitude}，{longitude}"),
+    MessageEntry::new(msg::GPS_LATITUDE_NORTH, "北纬 {value}"),
+    MessageEntry::new(msg::GPS_LATITUDE_SOUTH, "南纬 {value}"),
+    MessageEntry::new(msg::GPS_LONGITUDE_EAST, "东经 {value}"),
+    MessageEntry::new(msg::GPS_LONGITUDE_WEST, "西经 {value}"),
+    // endregion
+
+    // region 语言
+    MessageEntry::new(msg::LOCALE_UNSUPPORTED, "不支持的语言: {locale}"),
+    // endregion
+
+    // region 保险箱
+    MessageEntry::new(msg::VAULT_ALREADY_EXISTS, "保险箱已存在！"),
+    MessageEntry::new(
+        msg::VAULT_PASSPHRASE_TOO_SHORT,
+        "密码至少需要 {min} 个字符！",
+    ),
+    MessageEntry::new(msg::VAULT_NOT_CREATED, "保险箱还没有创建！"),
+    MessageEntry::new(msg::VAULT_WRONG_PASSPHRASE, "密码错误！"),
+    MessageEntry::new(msg::VAULT_LOCKED, "保险箱未解锁！"),
+    MessageEntry::new(msg::VAULT_REMOTE_PHOTO, "远程存储中的照片不能移入保险箱"),
+    MessageEntry::new(
+        msg::VAULT_ORIGINAL_DELETE_FAILED,
+        "原图删除失败 {path}: {error}",
+    ),
+    MessageEntry::new(
+        msg::VAULT_RESTORE_TARGET_EXISTS,
+        "原位置已有同名文件 {path}",
+    ),
+    MessageEntry::new(msg::VAULT_VERIFY_FAILED, "加密文件校验失败"),
+    MessageEntry::new(msg::VAULT_INVALID_ITERATIONS, "迭代次数无效！"),
+    MessageEntry::new(msg::VAULT_INVALID_KEY, "密钥无效！"),
+    MessageEntry::new(msg::VAULT_ENCRYPT_FAILED, "加密失败！"),
+    MessageEntry::new(
+        msg::VAULT_DECRYPT_FAILED,
+        "解密失败，密码错误或文件已损坏！",
+    ),
+    MessageEntry::new(msg::VAULT_INVALID_FILE, "不是有效的保险箱文件！"),
+    MessageEntry::new(msg::VAULT_FILE_INCOMPLETE, "保险箱文件不完整！"),
+    MessageEntry::new(msg::VAULT_RANDOM_FAILED, "随机数生成失败！"),
+    // endregion
+
+    // region 远程存储
+    MessageEntry::new(msg::REMOTE_STORAGE_NOT_FOUND, "远程存储 {id} 不存在！"),
+    MessageEntry::new(msg::REMOTE_STORAGE_DISABLED, "远程存储 {name} 未启用！"),
+    MessageEntry::new(msg::REMOTE_SYNC_RUNNING, "远程存储正在同步，请稍后再试！"),
+    MessageEntry::new(msg::REMOTE_NAME_REQUIRED, "远程存储名称不能为空！"),
+    MessageEntry::new(
+        msg::REMOTE_ENDPOINT_SCHEME,
+        "服务地址 {endpoint} 无效，需要以 http:// 或 https:// 开头！",
+    ),
+    MessageEntry::new(msg::REMOTE_ENDPOINT_INVALID, "服务地址 {endpoint} 无效！"),
+    MessageEntry::new(msg::REMOTE_BUCKET_REQUIRED, "S3 存储需要填写存储桶！"),
+    MessageEntry::new(msg::REMOTE_FILE_NAME_INVALID, "文件名无效"),
+    MessageEntry::new(
+        msg::REMOTE_REQUEST_FAILED,
+        "远程存储请求失败: {status} {body}",
+    ),
+    MessageEntry::new(
+        msg::REMOTE_MULTIPART_CREATE_FAILED,
+        "创建分片上传失败: {response}",
+    ),
+    MessageEntry::new(
+        msg::REMOTE_MULTIPART_NO_ETAG,
+        "分片 {part} 上传后没有返回 ETag！",
+    ),
+    MessageEntry::new(
+        msg::REMOTE_MULTIPART_COMPLETE_FAILED,
+        "完成分片上传失败: {code} {response}",
+    ),
+    // endregion
+
+    // region 监视文件夹
+    MessageEntry::new(msg::WATCH_FOLDER_PATH_REQUIRED, "监视文件夹不能为空！"),
+    MessageEntry::new(msg::WATCH_FOLDER_DEST_REQUIRED, "目标文件夹不能为空！"),
+    MessageEntry::new(
+        msg::WATCH_FOLDER_DEST_INSIDE,
+        "目标文件夹不能位于监视文件夹 {path} 中！",
+    ),
+    MessageEntry::new(msg::WATCH_FOLDER_NOT_FOUND, "监视文件夹 {id} 不存在"),
+    MessageEntry::new(
+        msg::WATCH_FOLDER_TAGS_INVALID,
+        "监视文件夹 {id} 的标签解析失败: {error}",
+    ),
+    // endregion
+
+    // region 节省空间
+    MessageEntry::new(
+        msg::SPACE_SAVER_TEMP_LIST_SAVE_FAILED,
+        "节省空间临时文件清单保存失败：{error}",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_METADATA_MISMATCH,
+        "新文件的元数据与原文件不一致！",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_METADATA_UNSUPPORTED,
+        "包含无法保留的元数据：{chunks}",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_BIT_DEPTH_UNSUPPORTED,
+        "{depth} 位色深无法无损转换为 WebP！",
+    ),
+    MessageEntry::new(msg::SPACE_SAVER_ANIMATED_SKIPPED, "动图不转换！"),
+    MessageEntry::new(
+        msg::SPACE_SAVER_WEBP_ENCODE_FAILED,
+        "WebP 编码失败：{error}",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_WEBP_CONFIG_FAILED,
+        "WebP 编码参数初始化失败！",
+    ),
+    MessageEntry::new(msg::SPACE_SAVER_SIZE_MISMATCH, "新文件尺寸与原文件不一致！"),
+    MessageEntry::new(
+        msg::SPACE_SAVER_PIXEL_MISMATCH,
+        "新文件像素与原文件不一致！",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_SOURCE_MODIFIED,
+        "原文件在生成计划后被修改！",
+    ),
+    MessageEntry::new(
+        msg::SPACE_SAVER_PLAN_NOT_FOUND,
+        "节省空间计划 {id} 不存在或已过期！",
+    ),
+    // endregion
+
+    // region 通用错误
+    MessageEntry::new(msg::PHOTO_NOT_FOUND, "照片 {id} 不存在！"),
+    MessageEntry::new(msg::PHOTOS_PARTIALLY_MISSING, "部分照片不存在！"),
+    MessageEntry::new(msg::ALBUM_NOT_FOUND, "相册 {id} 不存在！"),
+    MessageEntry::new(msg::TAG_NOT_FOUND, "标签 {id} 不存在！"),
+    MessageEntry::new(msg::THUMBNAIL_PATH_NOT_CONFIGURED, "缩略图路径未配置！"),
+    MessageEntry::new(msg::EXPORT_FORMAT_UNSUPPORTED, "不支持的导出格式：{format}"),
+    MessageEntry::new(msg::IMAGE_READ_FAILED, "图像 {path} 读取失败：{error}"),
+    MessageEntry::new(msg::DATE_INVALID, "日期 {date} 格式错误！"),
+    // endregion
+
+    // region 外部工具
+    MessageEntry::new(msg::JPEGTRAN_START_FAILED, "jpegtran 启动失败：{error}"),
+    MessageEntry::new(msg::JPEGTRAN_FAILED, "jpegtran 执行失败：{error}"),
+    MessageEntry::new(msg::FFMPEG_START_FAILED, "ffmpeg 启动失败 {path}：{error}"),
+    MessageEntry::new(msg::FFMPEG_FAILED, "ffmpeg 执行失败（{status}）：{error}"),
+    MessageEntry::new(msg::BRCTL_DOWNLOAD_FAILED, "brctl download 失败：{status}"),
+    // endregion
+
+    // region 照片库
+    MessageEntry::new(msg::LIBRARY_NAME_REQUIRED, "照片库名称不能为空！"),
+    MessageEntry::new(
+        msg::LIBRARY_ALREADY_LIBRARY,
+        "文件夹 {path} 已是照片库，请直接打开！",
+    ),
+    MessageEntry::new(msg::LIBRARY_NOT_LIBRARY, "文件夹 {path} 不是照片库！"),
+    MessageEntry::new(msg::LIBRARY_NOT_ADDED, "照片库 {path} 未添加！"),
+    MessageEntry::new(msg::LIBRARY_ALREADY_ADDED, "照片库 {path} 已添加！"),
+    MessageEntry::new(msg::LIBRARY_INACCESSIBLE, "照片库文件夹 {path} 无法访问！"),
+    MessageEntry::new(
+        msg::LIBRARY_DATABASE_INIT_FAILED,
+        "照片库 {path} 数据库初始化失败：{error}",
+    ),
+    MessageEntry::new(
+        msg::SETTINGS_DATABASE_INIT_FAILED,
+        "数据库初始化失败，配置未生效：{error}",
+    ),
+    // endregion
+
+    // region 备份
+    MessageEntry::new(msg::BACKUP_RUNNING, "已有备份或恢复任务正在进行！"),
+    MessageEntry::new(msg::BACKUP_FOLDER_EXISTS, "备份文件夹 {path} 已存在！"),
+    MessageEntry::new(msg::BACKUP_DATABASE_NOT_FOUND, "备份数据库 {path} 不存在！"),
+    MessageEntry::new(msg::BACKUP_CORRUPTED, "备份文件已损坏：{error}"),
+    MessageEntry::new(
+        msg::BACKUP_RESTORE_BUSY,
+        "数据库正在被使用，恢复未完成（{step}），可以使用 {path} 找回原数据",
+    ),
+    MessageEntry::new(
+        msg::BACKUP_RESTORE_VERIFY_FAILED,
+        "恢复后数据库校验失败，可以使用 {path} 找回原数据：{error}",
+    ),
+    MessageEntry::new(msg::BACKUP_MIGRATION_FAILED, "数据库迁移失败：{error}"),
+    // endregion
+
+    // region 校验
+    MessageEntry::new(msg::HASH_VERIFY_NO_PHOTOS, "没有需要校验的照片！"),
+    MessageEntry::new(
+        msg::HASH_VERIFY_TARGET_REQUIRED,
+        "请指定要校验的照片或文件夹！",
+    ),
+    MessageEntry::new(
+        msg::HASH_VERIFY_START_FAILED,
+        "Hash 校验任务启动失败：{error}",
+    ),
+    MessageEntry::new(
+        msg::HASH_VERIFY_JOB_NOT_RUNNING,
+        "校验任务 {id} 不存在或已结束！",
+    ),
+    MessageEntry::new(msg::HASH_VERIFY_JOB_NOT_FOUND, "校验任务 {id} 不存在！"),
+    MessageEntry::new(
+        msg::CHECKSUM_SOURCE_REQUIRED,
+        "需要指定文件夹或相册其中之一！",
+    ),
+    MessageEntry::new(msg::CHECKSUM_NO_PHOTOS, "没有可以导出的照片！"),
+    MessageEntry::new(
+        msg::CHECKSUM_NO_COMMON_DIR,
+        "相册中的照片没有共同的上级文件夹，请指定清单路径！",
+    ),
+    MessageEntry::new(
+        msg::CHECKSUM_MANIFEST_READ_FAILED,
+        "校验清单读取失败 {path}：{error}",
+    ),
+    // endregion
+
+    // region 照片整理
+    MessageEntry::new(msg::BATCH_NO_PHOTOS, "没有选中照片！"),
+    MessageEntry::new(msg::BATCH_NO_OPERATIONS, "没有要执行的操作！"),
+    MessageEntry::new(
+        msg::BATCH_MIRROR_ALBUM,
+        "相册 {id} 由文件夹同步生成，不能手动添加照片！",
+    ),
+    MessageEntry::new(msg::BATCH_DEST_REQUIRED, "目标文件夹不能为空！"),
+    MessageEntry::new(msg::BATCH_MULTIPLE_MOVES, "最多只能有一个移动操作！"),
+    MessageEntry::new(msg::ORGANIZE_RULE_NOT_FOUND, "规则 {id} 不存在！"),
+    MessageEntry::new(
+        msg::ORGANIZE_RULE_CONDITION_INVALID,
+        "规则 {id} 的条件解析失败：{error}",
+    ),
+    MessageEntry::new(
+        msg::ORGANIZE_RULE_ACTION_INVALID,
+        "规则 {id} 的操作 {action} 无效！",
+    ),
+    MessageEntry::new(
+        msg::ORGANIZATION_ALBUM_DUPLICATED,
+        "相册 {id} 在文件中重复出现！",
+    ),
+    MessageEntry::new(
+        msg::ORGANIZATION_TAG_DUPLICATED,
+        "标签 {id} 在文件中重复出现！",
+    ),
+    MessageEntry::new(msg::STACK_TOO_FEW_PHOTOS, "至少需要两张照片才能堆叠！"),
+    MessageEntry::new(
+        msg::STACK_ORIGINAL_NOT_INCLUDED,
+        "原图 {id} 不在要堆叠的照片中！",
+    ),
+    MessageEntry::new(msg::STACK_PHOTO_NOT_STACKED, "照片 {id} 不在任何堆叠中！"),
+    MessageEntry::new(msg::COVER_NOT_IN_FOLDER, "照片 {id} 不在文件夹 {path} 中！"),
+    MessageEntry::new(msg::COVER_NOT_IN_ALBUM, "照片 {id} 不在相册 {album} 中！"),
+    MessageEntry::new(
+        msg::DUPLICATE_MANIFEST_READ_FAILED,
+        "撤销清单读取失败 {path}：{error}",
+    ),
+    MessageEntry::new(
+        msg::DUPLICATE_CONTENT_MISMATCH,
+        "文件内容与保留的文件不一致！",
+    ),
+    MessageEntry::new(
+        msg::DUPLICATE_HARD_LINK_FAILED,
+        "创建硬链接失败（两个文件需要在同一个分区）：{error}",
+    ),
+    // endregion
+
+    // region 导入导出
+    MessageEntry::new(
+        msg::CARD_IMPORT_COPY_READ_FAILED,
+        "复制后的文件读取失败：{error}",
+    ),
+    MessageEntry::new(
+        msg::CARD_IMPORT_VERIFY_FAILED,
+        "校验失败，复制后的文件与源文件不一致！",
+    ),
+    MessageEntry::new(msg::CLOUD_FILE_NOT_DOWNLOADED, "文件仍未下载到本地！"),
+    MessageEntry::new(msg::CLOUD_DOWNLOAD_TIMEOUT, "等待 iCloud 下载超时！"),
+    MessageEntry::new(msg::METADATA_CSV_HASH_MISSING, "CSV 缺少 hash 列！"),
+    MessageEntry::new(msg::METADATA_CSV_ROW_INVALID, "第 {line} 行：{error}"),
+    MessageEntry::new(
+        msg::EXPORT_WATERMARK_LOGO_FAILED,
+        "水印 logo {path} 读取失败：{error}",
+    ),
+    MessageEntry::new(msg::EXPORT_LOCATION_FAILED, "位置处理失败：{error}"),
+    MessageEntry::new(msg::SLIDESHOW_OUTPUT_REQUIRED, "输出文件路径不能为空！"),
+    MessageEntry::new(
+        msg::SLIDESHOW_MUSIC_NOT_FOUND,
+        "背景音乐文件 {path} 不存在！",
+    ),
+    MessageEntry::new(msg::SLIDESHOW_NO_PHOTOS, "没有可以生成视频的照片！"),
+    MessageEntry::new(msg::SLIDESHOW_START_FAILED, "幻灯片任务启动失败：{error}"),
+    MessageEntry::new(
+        msg::SLIDESHOW_JOB_NOT_RUNNING,
+        "幻灯片任务 {id} 不存在或已结束！",
+    ),
+    // endregion
+
+    // region 照片查看
+    MessageEntry::new(
+        msg::THUMBNAIL_VISIBLE_RANGE_INVALID,
+        "可见范围 {from} - {to} 无效！",
+    ),
+    MessageEntry::new(
+        msg::MP_IMAGE_NOT_FOUND,
+        "照片 {id} 中没有第 {index} 张 MP 图像！",
+    ),
+    MessageEntry::new(
+        msg::COMPARE_PHOTO_RANGE,
+        "对比的照片数量应为 {min} - {max} 张！",
+    ),
+    MessageEntry::new(msg::COMPARE_DUPLICATED, "对比的照片不能重复！"),
+    MessageEntry::new(
+        msg::COMPARE_PREVIEW_READ_FAILED,
+        "预览图 {path} 读取失败：{error}",
+    ),
+    MessageEntry::new(msg::BENCHMARK_NO_PHOTOS, "没有可用于对比的照片！"),
+    MessageEntry::new(
+        msg::HTTP_THUMBNAIL_SIZE_UNSUPPORTED,
+        "不支持的缩略图大小：{size}",
+    ),
+    MessageEntry::new(
+        msg::HTTP_ADDRESS_INVALID,
+        "HTTP 服务监听地址 {address} 无效：{error}",
+    ),
+    // endregion
+
+    // region 设置
+    MessageEntry::new(msg::PRESET_NAME_REQUIRED, "预设名称不能为空！"),
+    MessageEntry::new(msg::PRESET_EMPTY, "预设 {name} 没有任何字段！"),
+    MessageEntry::new(msg::PRESET_NOT_FOUND, "预设 {id} 不存在！"),
+    MessageEntry::new(msg::PRIVACY_ZONE_NAME_REQUIRED, "隐私区域名称不能为空！"),
+    MessageEntry::new(
+        msg::PRIVACY_ZONE_CENTER_INVALID,
+        "隐私区域 {name} 的中心坐标无效！",
+    ),
+    MessageEntry::new(
+        msg::PRIVACY_ZONE_RADIUS_INVALID,
+        "隐私区域 {name} 的半径必须大于 0！",
+    ),
+    MessageEntry::new(msg::PRIVACY_ZONE_NOT_FOUND, "隐私区域 {id} 不存在！"),
+    MessageEntry::new(
+        msg::PRIVACY_LOCATION_INVALID,
+        "照片 {id} 的位置无法解析：{error}",
+    ),
+    MessageEntry::new(msg::USER_NOT_FOUND, "用户 {id} 不存在！"),
+    MessageEntry::new(msg::USER_DEFAULT_UNDELETABLE, "默认用户不能删除！"),
+    MessageEntry::new(msg::MAINTENANCE_TASK_RUNNING, "任务正在执行中！"),
+    MessageEntry::new(
+        msg::MAINTENANCE_RESCAN_ALL_FAILED,
+        "所有存储路径重新扫描失败：{paths}",
+    ),
+    MessageEntry::new(
+        msg::DISK_SPACE_INSUFFICIENT,
+        "磁盘剩余空间不足：{path} 剩余 {available} MB，至少需要保留 {required} MB",
+    ),
+    // endregion
+
+    // region 数据库占用
+    MessageEntry::new(
+        msg::DATABASE_BUSY_SCANNING,
+        "正在扫描照片，请等待扫描完成或取消后再{action}！",
+    ),
+    MessageEntry::new(
+        msg::DATABASE_BUSY_WRITING,
+        "正在保存扫描结果，请稍后再{action}！",
+    ),
+    MessageEntry::new(
+        msg::DATABASE_BUSY_BACKUP,
+        "正在备份或恢复数据库，请稍后再{action}！",
+    ),
+    MessageEntry::new(msg::DATABASE_ACTION_SWITCH_LIBRARY, "切换照片库"),
+    MessageEntry::new(msg::DATABASE_ACTION_RESTORE, "恢复数据库"),
+    // endregion
+
+    // region 调试
+    MessageEntry::new(msg::DEV_EMIT_GREETING, "你好, {name}! 来自后端!"),
+    MessageEntry::new(msg::DEV_GLOBAL_MSG_TITLE, "标题"),
+    MessageEntry::new(msg::DEV_GLOBAL_MSG_CONTENT, "内容"),
+    // endregion
+];
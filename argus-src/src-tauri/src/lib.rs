@@ -8,6 +8,7 @@ mod errors;
 mod explore;
 mod global_task_manager;
 mod http_client;
+mod i18n;
 mod models;
 mod server;
 mod services;
@@ -28,9 +29,9 @@ use crate::bg_services::{BgServes, SERVES};
 use crate::global_task_manager::{start_image_loading_background_task, BackgroundTaskAutoManager};
 use crate::storage::connection::establish_connection;
 use crate::storage::photo_table::insert_photo;
-use crate::structs::config::SYS_CONFIG;
+use crate::structs::config::sys_config;
 use crate::utils::img_util::ImageOperate;
-use crate::utils::task_util;
+use crate::utils::{log_util, task_util};
 use tauri::{App, Emitter, Listener, Manager, State, WindowEvent};
 use tokio::sync::{mpsc, watch};
 use crate::utils::task_util::PHOTO_LOAD_RECEIVER;
@@ -49,55 +50,23 @@ pub fn run() {
     }
 
     std::env::set_var("RUST_BACKTRACE", "1");
-    // 非生产环境，使用外部日志
+    // JSON 日志写入 tauri-logs 文件夹，各模块级别可在运行时调整
+    let log_level = config::sys_config()
+        .log_level
+        .as_deref()
+        .and_then(log_util::parse_level)
+        .unwrap_or(log::LevelFilter::Info);
+    log_util::init(log_level);
+    // 前端日志通过 log 转发到上面的日志系统
     #[cfg(not(debug_assertions))]
     {
-        let log_plugin = tauri_plugin_log::Builder::new()
-            // 设置文件大小
-            .max_file_size(50_000)
-            .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepAll)
-            .level(log::LevelFilter::Info)
-            // 仅对命令模块进行动词日志
-            .level_for("commands::log_command", log::LevelFilter::Info)
-            // 日志格式
-            .format(|out, message, record| {
-                out.finish(format_args!(
-                    "[{} {}] - {}",
-                    record.level(),
-                    record.target(),
-                    message
-                ))
-            })
-            // 使用本机时间格式化日期
-            .timezone_strategy(tauri_plugin_log::TimezoneStrategy::UseLocal)
-            .target(tauri_plugin_log::Target::new(
-                // 将日志打印到终端
-                // tauri_plugin_log::TargetKind::Stdout,
-                // 记录到 webView
-                // tauri_plugin_log::TargetKind::Webview,
-                // 保存到文件
-                /*
-                Linux      {configDir}/{bundleIdentifier}                    /home/alice/.config/com.tauri.dev
-                macOS      {homeDir}/Library/Logs/{bundleIdentifier}         /Users/Alice/Library/Logs/com.tauri.dev
-                窗户	       {FOLDERID_LocalAppData}/{bundleIdentifier}/logs   C:\Users\Alice\AppData\Local\com.tauri.dev\logs
-                    */
-                // tauri_plugin_log::TargetKind::LogDir {
-                //     file_name: Some("logs".to_string()),
-                // },
-                // 写入自定义位置
-                tauri_plugin_log::TargetKind::Folder {
-                    path: std::path::PathBuf::from(constant::LOG_PATH),
-                    file_name: None,
-                },
-            ))
-            .build();
-        builder = builder.plugin(log_plugin)
+        builder = builder.plugin(tauri_plugin_log::Builder::new().skip_logger().build());
     }
 
     // 初始化配置文件
     let configs = config::init_config();
 
-    print!("配置完毕!!!!!!!!!!!!!");
+    log::info!("配置加载完毕");
 
     // 启动后台服务
     back_a_task();
@@ -111,8 +80,10 @@ pub fn run() {
             match event {
                 // 窗口关闭事件
                 WindowEvent::CloseRequested { api, .. } => {
-                    println!("进入关闭流程！ ");
+                    log::info!("进入关闭流程");
                     SERVES.write().unwrap().drop_all();
+                    // 删除保险箱解密出的临时文件
+                    services::vault_service::lock_vault();
                 }
                 _ => {}
             }
@@ -132,22 +103,172 @@ pub fn run() {
             commands::file_command::get_all_sub_dir,
             commands::file_command::get_all_imgs,
             commands::file_command::get_dir_all_subfolders_first_img,
+            commands::file_command::move_photos,
+            commands::file_command::rename_photo,
+            commands::file_command::rename_by_template,
             commands::post_command::get_all_post,
             commands::post_command::insert_post,
             commands::log_command::log_logs,
+            commands::log_command::get_recent_logs,
+            commands::log_command::set_log_level,
+            commands::log_command::get_log_levels,
             commands::emit_test::emit_send_test,
             commands::photo_storage_command::get_photo_storage,
             commands::photo_storage_command::add_photo_storage,
             commands::photo_storage_command::delete_photo_storage,
             commands::photo_storage_command::update_photo_storage,
+            commands::photo_storage_command::refresh_storage_volumes,
+            commands::photo_storage_command::get_storage_breakdown,
             commands::folder_show_command::get_need_display_image_info,
+            commands::folder_show_command::search_photos,
+            commands::folder_show_command::search_by_color,
             commands::image_command::get_compress_image_address,
             commands::image_command::generate_save_thumbnail,
             commands::image_command::get_image_thumbnail_path,
             commands::image_command::get_image_thumbnail,
+            commands::image_command::get_latency_report,
+            commands::image_command::record_thumbnail_round_trip,
+            commands::image_command::verify_thumbnail_cache,
+            commands::image_command::get_photo_histogram,
+            commands::image_command::get_photo_colors,
+            commands::image_command::index_photo_colors,
             commands::global_task_command::add_photo_retrieve_task,
+            commands::global_task_command::get_scan_report,
+            commands::global_task_command::preview_scan,
+            commands::global_task_command::get_scan_metrics,
+            commands::global_task_command::get_task_status,
+            commands::global_task_command::cancel_scan,
+            commands::global_task_command::rescan_folder,
+            commands::global_task_command::get_integrity_report,
+            commands::global_task_command::list_problem_files,
+            commands::global_task_command::hydrate_cloud_files,
             commands::global_task_command::emit_global_msg,
             commands::global_task_command::global_msg_emit,
+            commands::organization_command::get_organization_tree,
+            commands::organization_command::export_organization,
+            commands::organization_command::import_organization,
+            commands::organization_command::sync_folder_albums,
+            commands::metadata_export_command::export_metadata,
+            commands::metadata_export_command::import_metadata,
+            commands::external_library_command::import_external_library,
+            commands::i18n_command::get_message_catalog,
+            commands::i18n_command::set_message_locale,
+            commands::metadata_edit_command::set_photo_rating,
+            commands::metadata_edit_command::shift_photo_time,
+            commands::metadata_edit_command::set_photo_gps,
+            commands::metadata_edit_command::set_photo_tags,
+            commands::metadata_edit_command::set_photo_caption,
+            commands::metadata_edit_command::set_photo_notes,
+            commands::metadata_edit_command::undo_last_edit,
+            commands::metadata_edit_command::redo_edit,
+            commands::backup_command::backup_library,
+            commands::backup_command::restore_library,
+            commands::photo_stack_command::auto_stack_photos,
+            commands::photo_stack_command::pair_raw_jpeg_photos,
+            commands::photo_stack_command::stack_photos,
+            commands::photo_stack_command::unstack_photos,
+            commands::photo_stack_command::set_stack_cover,
+            commands::photo_stack_command::get_stack_photos,
+            commands::library_command::list_libraries,
+            commands::library_command::create_library,
+            commands::library_command::open_library,
+            commands::library_command::switch_library,
+            commands::settings_command::reload_settings,
+            commands::settings_command::get_http_server_status,
+            commands::slideshow_command::create_slideshow,
+            commands::slideshow_command::cancel_slideshow,
+            commands::hash_verify_command::verify_photos,
+            commands::hash_verify_command::cancel_verify_photos,
+            commands::hash_verify_command::get_verify_report,
+            commands::photo_compare_command::compare_photos,
+            commands::cover_command::set_folder_cover,
+            commands::cover_command::get_folder_cover,
+            commands::cover_command::set_album_cover,
+            commands::cover_command::get_album_cover,
+            commands::organize_rule_command::list_organize_rules,
+            commands::organize_rule_command::save_organize_rule,
+            commands::organize_rule_command::delete_organize_rule,
+            commands::organize_rule_command::dry_run_organize_rules,
+            commands::user_profile_command::list_user_profiles,
+            commands::user_profile_command::get_user_profile,
+            commands::user_profile_command::save_user_profile,
+            commands::user_profile_command::delete_user_profile,
+            commands::remote_storage_command::list_remote_storages,
+            commands::remote_storage_command::save_remote_storage,
+            commands::remote_storage_command::delete_remote_storage,
+            commands::remote_storage_command::test_remote_storage,
+            commands::remote_storage_command::sync_remote_storage,
+            commands::remote_storage_command::list_remote_photos,
+            commands::remote_storage_command::upload_to_remote_storage,
+            commands::watch_folder_command::list_watch_folders,
+            commands::watch_folder_command::save_watch_folder,
+            commands::watch_folder_command::delete_watch_folder,
+            commands::space_saver_command::prepare_space_saver,
+            commands::space_saver_command::get_space_saver_plan,
+            commands::space_saver_command::commit_space_saver,
+            commands::space_saver_command::discard_space_saver,
+            commands::event_command::refresh_events,
+            commands::event_command::list_events,
+            commands::event_command::get_event_photos,
+            commands::recent_feed_command::get_recent_imports,
+            commands::recent_feed_command::get_import_batch_photos,
+            commands::recent_feed_command::record_photo_view,
+            commands::recent_feed_command::get_recently_viewed,
+            commands::memory_command::get_memories,
+            commands::batch_operation_command::batch_apply,
+            commands::photo_list_command::list_photos,
+            commands::photo_list_command::index_photo_blurhashes,
+            commands::image_command::report_visible_range,
+            commands::image_command::benchmark_resize,
+            commands::photo_export_command::export_photos,
+            commands::photo_transform_command::rotate_photo,
+            commands::photo_transform_command::flip_photo,
+            commands::metadata_preset_command::list_metadata_presets,
+            commands::metadata_preset_command::save_metadata_preset,
+            commands::metadata_preset_command::delete_metadata_preset,
+            commands::metadata_preset_command::apply_metadata_preset,
+            commands::privacy_command::list_privacy_zones,
+            commands::privacy_command::save_privacy_zone,
+            commands::privacy_command::delete_privacy_zone,
+            commands::privacy_command::strip_gps,
+            commands::place_command::refresh_places,
+            commands::place_command::list_places,
+            commands::place_command::get_place_photos,
+            commands::place_command::seed_geocode_cache,
+            commands::photo_sequence_command::detect_photo_sequences,
+            commands::photo_sequence_command::list_photo_sequences,
+            commands::photo_sequence_command::get_sequence_photos,
+            commands::card_import_command::list_camera_cards,
+            commands::card_import_command::list_card_files,
+            commands::card_import_command::import_from_card,
+            commands::share_bundle_command::create_share_bundle,
+            commands::photo_activity_command::get_photo_activity,
+            commands::duplicate_reclaim_command::find_exact_duplicates,
+            commands::duplicate_reclaim_command::reclaim_duplicates,
+            commands::duplicate_reclaim_command::undo_reclaim,
+            commands::duplicate_reclaim_command::list_reclaim_manifests,
+            commands::vault_command::get_vault_status,
+            commands::vault_command::create_vault,
+            commands::vault_command::unlock_vault,
+            commands::vault_command::lock_vault,
+            commands::vault_command::list_vault_items,
+            commands::vault_command::open_vault_item,
+            commands::vault_command::move_to_vault,
+            commands::vault_command::restore_from_vault,
+            commands::checksum_manifest_command::export_checksum_manifest,
+            commands::checksum_manifest_command::verify_manifest,
+            commands::maintenance_command::get_schedule_status,
+            commands::maintenance_command::save_maintenance_schedules,
+            commands::maintenance_command::run_maintenance_job,
+            commands::maintenance_command::optimize_database,
+            commands::maintenance_command::find_missing_photos,
+            commands::maintenance_command::resolve_missing_photos,
+            commands::image_command::get_display_image,
+            commands::image_command::prefetch_photos,
+            commands::image_command::get_tiff_pages,
+            commands::image_command::get_mp_images,
+            commands::image_command::get_mp_image,
+            commands::image_command::probe_image,
         ])
         .setup(main_setup())
         .run(tauri::generate_context!())
@@ -169,13 +290,57 @@ fn main_setup() -> fn(&mut App) -> Result<(), Box<dyn Error>> {
         let db = connection::run_migrations().expect("Database initialize should succeed");
         log::info!("创建完毕");
 
+        // 使用默认用户的界面语言
+        services::user_profile_service::apply_default_profile();
+
+        // 加密以明文保存的远程存储密钥
+        match services::remote_storage_service::seal_stored_secrets() {
+            Ok(0) => {}
+            Ok(count) => log::info!("已加密 {} 个远程存储密钥", count),
+            Err(e) => log::error!("远程存储密钥加密失败: {}", e),
+        }
+
+        // 删除上次异常退出时留下的保险箱解密文件、节省空间临时文件
+        services::vault_service::clean_stale_views();
+        services::space_saver_service::clean_pending_files();
+
+        // 检查照片所在的移动硬盘、网络共享是否在线
+        async_runtime::spawn_blocking(|| {
+            if let Err(e) = services::volume_service::refresh_storage_volumes() {
+                log::error!("存储卷检查失败: {}", e);
+            }
+        });
+
+        // 缩略图缓存、导入时的磁盘空间不足提醒
+        services::disk_space_service::init(app.handle().clone());
+
+        // 文件夹相册与文件夹结构保持一致
+        services::folder_album_service::sync_in_background();
+
+        // 补全升级前导入的照片的 EXIF
+        services::exif_backfill_service::start_backfill();
+
+        // 定期自动备份数据库
+        services::backup_service::start_auto_backup();
+
+        // 校验照片文件完整性
+        services::integrity_service::start_startup_check();
+
+        // 定时维护（重新扫描、缓存清理、数据库优化等）
+        services::maintenance_scheduler_service::start_scheduler();
+
+        // 监视文件夹自动导入
+        services::watch_folder_service::start_all();
+
+        // 局域网 HTTP 服务
+        services::http_server_service::start_http_server();
+
         // 创建指定目录
-        let lazy = SYS_CONFIG.thumbnail_storage_path.clone().unwrap();
-        println!("输出的路径：{}", lazy);
+        let lazy = sys_config().thumbnail_storage_path.clone().unwrap();
+        log::info!("缩略图路径: {}", lazy);
 
         // 启动服务
         // `sidecar()` 只需要文件名, 不像 JavaScript 中的整个路径
-        println!("sidecar().");
 
         // 启用 python 算法
         bg_services::start_python_service().unwrap();
@@ -194,6 +359,6 @@ fn main_setup() -> fn(&mut App) -> Result<(), Box<dyn Error>> {
 
 /// 后台服务
 fn back_a_task() {
-    println!("后台服务 初始化");
+    log::info!("后台服务初始化");
     let sender = PHOTO_LOAD_RECEIVER.clone();
 }
\ No newline at end of file
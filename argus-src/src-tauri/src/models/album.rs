@@ -0,0 +1,57 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 相册
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::albums)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Album {
+    pub id: i32,
+    /// 相册名称
+    pub name: String,
+    /// 父级相册【为空表示根相册】
+    pub parent_id: Option<i32>,
+    /// 相册描述
+    pub description: Option<String>,
+    /// 封面照片【为空时使用相册中最新的照片】
+    pub cover_photo_id: Option<i32>,
+    pub is_delete: bool,
+    pub create_time: i64,
+    pub update_time: i64,
+    /// 同步的文件夹路径【不为空表示由文件夹同步生成的只读相册】
+    pub source_path: Option<String>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::albums)]
+pub struct NewAlbum {
+    /// 相册名称
+    pub name: String,
+    /// 父级相册
+    pub parent_id: Option<i32>,
+    /// 相册描述
+    pub description: Option<String>,
+    pub create_time: i64,
+    pub update_time: i64,
+    /// 同步的文件夹路径
+    pub source_path: Option<String>,
+}
+
+/// 相册和照片的关联
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::photo_albums)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct PhotoAlbum {
+    pub id: i32,
+    pub album_id: i32,
+    pub photo_id: i32,
+    pub create_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::photo_albums)]
+pub struct NewPhotoAlbum {
+    pub album_id: i32,
+    pub photo_id: i32,
+    pub create_time: i64,
+}
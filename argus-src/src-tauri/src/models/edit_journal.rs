@@ -0,0 +1,30 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 元数据编辑记录
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::edit_journal)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct EditJournalRecord {
+    pub id: i32,
+    /// 会话 ID
+    pub session_id: String,
+    /// 编辑描述
+    pub description: String,
+    /// 修改内容（JSON）
+    pub changes: String,
+    /// 是否已撤销
+    pub is_undone: bool,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::edit_journal)]
+pub struct NewEditJournal {
+    pub session_id: String,
+    pub description: String,
+    pub changes: String,
+    pub create_time: i64,
+    pub update_time: i64,
+}
@@ -0,0 +1,45 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 事件（按拍摄时间和位置自动分段，如一次旅行、一场活动）
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::events)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Event {
+    pub id: i32,
+    /// 名称（地点和日期）
+    pub name: String,
+    /// 地点名称
+    pub place_name: Option<String>,
+    /// 第一张照片的拍摄时间
+    pub start_time: i64,
+    /// 最后一张照片的拍摄时间
+    pub end_time: i64,
+    /// 照片数量
+    pub photo_count: i32,
+    /// 封面照片
+    pub cover_photo_id: i32,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::events)]
+pub struct NewEvent {
+    pub name: String,
+    pub place_name: Option<String>,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub photo_count: i32,
+    pub cover_photo_id: i32,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::event_photos)]
+pub struct NewEventPhoto {
+    pub event_id: i32,
+    pub photo_id: i32,
+    pub create_time: i64,
+}
@@ -0,0 +1,24 @@
+use diesel::{AsChangeset, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 文件夹封面
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::folder_covers)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct FolderCover {
+    pub id: i32,
+    /// 文件夹路径
+    pub path: String,
+    /// 封面照片
+    pub photo_id: i32,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = crate::storage::schema::folder_covers)]
+pub struct NewFolderCover {
+    pub path: String,
+    pub photo_id: i32,
+    pub update_time: i64,
+}
@@ -0,0 +1,20 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 逆地理编码缓存
+#[derive(Queryable, Selectable, Insertable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::geocode_cache)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct GeocodeCacheRecord {
+    /// 纬度 × 1000（四舍五入）
+    pub lat_key: i32,
+    /// 经度 × 1000（四舍五入）
+    pub lon_key: i32,
+    /// 地点名称【服务没有结果时为空】
+    pub name: Option<String>,
+    /// 来源【见 `GeocodeSource`】
+    pub source: i32,
+    /// 过期时间【为空时不过期】
+    pub expire_time: Option<i64>,
+    pub create_time: i64,
+}
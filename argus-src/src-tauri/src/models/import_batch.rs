@@ -0,0 +1,35 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 导入批次
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::import_batches)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ImportBatch {
+    pub id: i32,
+    /// 来源（照片最多的文件夹）
+    pub source: String,
+    /// 照片数量
+    pub photo_count: i32,
+    /// 第一张照片的导入时间
+    pub start_time: i64,
+    /// 最后一张照片的导入时间
+    pub end_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::import_batches)]
+pub struct NewImportBatch {
+    pub source: String,
+    pub photo_count: i32,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::import_batch_photos)]
+pub struct NewImportBatchPhoto {
+    pub batch_id: i32,
+    pub photo_id: i32,
+    pub create_time: i64,
+}
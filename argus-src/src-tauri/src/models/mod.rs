@@ -1,3 +1,24 @@
 pub mod photo_storage;
 pub mod post;
 pub mod photo;
+pub mod album;
+pub mod tag;
+pub mod scan_report;
+pub mod edit_journal;
+pub mod photo_stack;
+pub mod photo_histogram;
+pub mod photo_color;
+pub mod place;
+pub mod photo_sequence;
+pub mod photo_activity;
+pub mod vault_item;
+pub mod problem;
+pub mod folder_cover;
+pub mod organize_rule;
+pub mod event;
+pub mod import_batch;
+pub mod photo_blurhash;
+pub mod user_profile;
+pub mod remote_storage;
+pub mod watch_folder;
+pub mod geocode_cache;
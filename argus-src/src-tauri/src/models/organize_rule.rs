@@ -0,0 +1,32 @@
+use diesel::{AsChangeset, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 自动整理规则
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::organize_rules)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct OrganizeRuleRecord {
+    pub id: i32,
+    /// 规则名称
+    pub name: String,
+    /// 条件【`RuleCondition` 数组的 JSON】
+    pub conditions: String,
+    /// 操作【见 `RuleAction`】
+    pub action: i32,
+    /// 标签或相册名称
+    pub target: String,
+    pub is_enable: bool,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = crate::storage::schema::organize_rules)]
+pub struct NewOrganizeRule {
+    pub name: String,
+    pub conditions: String,
+    pub action: i32,
+    pub target: String,
+    pub is_enable: bool,
+    pub update_time: i64,
+}
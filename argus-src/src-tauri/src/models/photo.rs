@@ -1,5 +1,7 @@
-use diesel::{Insertable, Queryable, Selectable};
+use crate::utils::file_util;
+use diesel::{AsChangeset, Insertable, Queryable, Selectable};
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 /// 图片信息
 #[derive(Queryable, Selectable, Insertable, Debug, Serialize, Deserialize)]
@@ -11,6 +13,8 @@ pub struct Photo {
     pub img_path: String,
     /// 文件名称
     pub img_name: String,
+    /// 完整路径的原始字节【无损保存非 UTF-8 路径，见 `file_util::path_to_bytes`】
+    pub img_path_raw: Option<Vec<u8>>,
     /// 文件 Hash【唯一 ID】
     pub hash: String,
     /// 图片宽度。
@@ -26,6 +30,8 @@ pub struct Photo {
 
     /// 备注信息。
     pub notes: Option<String>,
+    /// 照片说明【扫描时从 ImageDescription、XMP dc:description 读取】
+    pub caption: Option<String>,
 
     /*
         相册存储：
@@ -98,99 +104,179 @@ pub struct Photo {
     pub exposure_program: Option<String>,
     /// 测光模式
     pub metering_mode: Option<String>,
+    /// 闪光灯数值【按位组合，第 0 位为是否闪光，见 `exif_enum::Flash`】
+    pub flash_code: Option<i32>,
+    /// 测光模式数值
+    pub metering_mode_code: Option<i32>,
+    /// 曝光程序数值
+    pub exposure_program_code: Option<i32>,
+    /// 白平衡数值
+    pub white_balance_code: Option<i32>,
+    /// 场景拍摄类型数值
+    pub scene_capture_type_code: Option<i32>,
+    /// GPS 速度（km/h）
+    pub gps_speed: Option<f32>,
+    /// GPS 拍摄方向（度）
+    pub gps_img_direction: Option<f32>,
+    /// GPS 目的地方位（度）
+    pub gps_dest_bearing: Option<f32>,
+    /// 等效焦距（毫米）【照片中没有时按相机传感器尺寸估算】
+    pub focal_length_35mm: Option<f32>,
     /// 作者（艺术家）
     pub artist: Option<String>,
+    /// 版权
+    pub copyright: Option<String>,
+    /// 署名（IPTC Credit）
+    pub credit: Option<String>,
+    /// 地点名称
+    pub location_name: Option<String>,
+    /// 所属的版本堆叠
+    pub stack_id: Option<i32>,
     // endregion
+
+    /// 导入时的图像校验级别【0 不校验、1 文件头、2 完整解码】
+    pub validation_level: i32,
+    /// 是否为动图（GIF、APNG、动态 WebP）
+    pub is_animated: bool,
+    /// 动图帧数
+    pub frame_count: Option<i32>,
+    /// 动图播放一遍的时长（毫秒）
+    pub animation_duration: Option<i32>,
+
+    /// 是否可访问【所在卷离线时为 false】
+    pub is_available: bool,
     
     pub is_delete: bool,
     pub create_time: i64,
     pub update_time: i64,
+    /// 读取 EXIF 的时间【为空时还未读取】
+    pub exif_read_time: Option<i64>,
+    /// GPS 拍摄方向的参考方向【T 真北，M 磁北】
+    pub gps_img_direction_ref: Option<String>,
+    /// GPS 目的地方位的参考方向【T 真北，M 磁北】
+    pub gps_dest_bearing_ref: Option<String>,
+    /// 远程存储中的对象 ID【为空表示本地照片】
+    pub remote_object_id: Option<i32>,
+    /// RAW+JPEG 配对时同一文件夹中的 JPEG 文件名【为空表示没有配对】
+    pub paired_jpeg: Option<String>,
 }
 
-#[derive(Insertable)]
-#[diesel(table_name = crate::storage::schema::photo_table)]
-pub struct NewExifPhoto {
-    /// 图像路径
-    pub img_path: String,
-    /// 文件名称
-    pub img_name: String,
-    /// 文件 Hash【唯一 ID】
-    pub hash: String,
-    /// 图片宽度。
-    pub width: i32,
-    /// 图片高度
-    pub height: i32,
-    /// 图片比例（宽/高，方便快速排序）。
-    pub aspect_ratio: f32,
-    /// 文件大小（字节）。
-    pub file_size: i64,
-    /// 图片格式（如 JPEG, PNG, WebP）。
-    pub format: String,
+impl Photo {
+    /// 是否为远程存储中的照片【原图不在本地】
+    pub fn is_remote(&self) -> bool {
+        self.remote_object_id.is_some()
+    }
 
-    /// 备注信息。
-    pub notes: Option<String>,
-    
-    /// 是否经过算法【识别 人物、场景 等内容】
-    pub is_algorithm: Option<bool>,
-
-    /// 算法评分
-    pub algorithm_score: Option<i32>,
-
-    /// 上次查看时间
-    pub last_viewed_time: Option<i64>,
+    /// 原图完整路径【优先使用无损保存的原始路径】
+    pub fn full_path(&self) -> PathBuf {
+        match &self.img_path_raw {
+            Some(raw) if !raw.is_empty() => file_util::path_from_bytes(raw),
+            _ => Path::new(&self.img_path).join(&self.img_name),
+        }
+    }
+}
 
-    // region exif 信息
-    /// 时区 存储时区偏移量，如 '+08:00' 或 '-05:00'
+/// 从照片中读取的 EXIF 信息【为空的字段不修改】
+#[derive(AsChangeset, Debug, Default, Clone, PartialEq)]
+#[diesel(table_name = crate::storage::schema::photo_table)]
+pub struct PhotoExifChangeset {
+    /// 说明
+    pub caption: Option<String>,
+    /// 时区
     pub offset_time: Option<String>,
-    /// 评分（用户评分）
+    /// 评分
     pub rating: Option<i32>,
-    // 评分（自动评分）
-    /// 相机制造商
     pub make: Option<String>,
-    /// 相机型号
     pub model: Option<String>,
-    /// 软件版本
     pub software: Option<String>,
-    /// 曝光时间
     pub exposure_time: Option<f32>,
-    /// 闪光灯
     pub flash: Option<String>,
-    /// 光圈
     pub f_number: Option<f32>,
-    /// ISO
     pub iso: Option<i32>,
-    /// 创建日期
     pub date_time_original: Option<i64>,
-    /// 最大光圈值
     pub max_aperture_value: Option<String>,
-    /// 焦距
     pub focal_length: Option<f32>,
-    /// 宽度
     pub image_width: Option<i32>,
-    /// 长度
     pub image_height: Option<i32>,
-    /// gps 信息
+    /// 十进制坐标 JSON
     pub gps_info: Option<String>,
-    /// 曝光程序
     pub exposure_program: Option<String>,
-    /// 测光模式
     pub metering_mode: Option<String>,
-    /// 作者（艺术家）
+    pub flash_code: Option<i32>,
+    pub metering_mode_code: Option<i32>,
+    pub exposure_program_code: Option<i32>,
+    pub white_balance_code: Option<i32>,
+    pub scene_capture_type_code: Option<i32>,
+    pub gps_speed: Option<f32>,
+    pub gps_img_direction: Option<f32>,
+    pub gps_dest_bearing: Option<f32>,
+    pub focal_length_35mm: Option<f32>,
     pub artist: Option<String>,
-    // endregion
-    
-    pub is_delete: bool,
-    pub create_time: i64,
-    pub update_time: i64,
+    pub copyright: Option<String>,
+    /// 读取时间
+    pub exif_read_time: Option<i64>,
+    pub gps_img_direction_ref: Option<String>,
+    pub gps_dest_bearing_ref: Option<String>,
+}
+
+impl PhotoExifChangeset {
+    /// 只保留照片中还没有值的字段【补全已有照片时不覆盖用户修改过的内容】
+    pub fn only_missing(mut self, photo: &Photo) -> PhotoExifChangeset {
+        macro_rules! keep_existing {
+            ($($field:ident),*) => {
+                $(if photo.$field.is_some() {
+                    self.$field = None;
+                })*
+            };
+        }
+        keep_existing!(
+            caption,
+            offset_time,
+            rating,
+            make,
+            model,
+            software,
+            exposure_time,
+            flash,
+            f_number,
+            iso,
+            date_time_original,
+            max_aperture_value,
+            focal_length,
+            image_width,
+            image_height,
+            gps_info,
+            exposure_program,
+            metering_mode,
+            flash_code,
+            metering_mode_code,
+            exposure_program_code,
+            white_balance_code,
+            scene_capture_type_code,
+            gps_speed,
+            gps_img_direction,
+            gps_dest_bearing,
+            focal_length_35mm,
+            artist,
+            copyright,
+            gps_img_direction_ref,
+            gps_dest_bearing_ref
+        );
+        self
+    }
 }
 
-#[derive(Insertable)]
+#[derive(Insertable, Clone)]
 #[diesel(table_name = crate::storage::schema::photo_table)]
+// 空值直接写入 NULL，SQLite 才能多行合并为一条 INSERT
+#[diesel(treat_none_as_default_value = false)]
 pub struct NewPhoto {
     /// 图像路径
     pub img_path: String,
     /// 文件名称
     pub img_name: String,
+    /// 完整路径的原始字节【无损保存非 UTF-8 路径，见 `file_util::path_to_bytes`】
+    pub img_path_raw: Option<Vec<u8>>,
     /// 文件 Hash【唯一 ID】
     pub hash: String,
     /// 图片宽度。
@@ -203,11 +289,43 @@ pub struct NewPhoto {
     pub file_size: i64,
     /// 图片格式（如 JPEG, PNG, WebP）。
     pub format: String,
+    /// 导入时的图像校验级别
+    pub validation_level: i32,
+    /// 是否为动图（GIF、APNG、动态 WebP）
+    pub is_animated: bool,
+    /// 动图帧数
+    pub frame_count: Option<i32>,
+    /// 动图播放一遍的时长（毫秒）
+    pub animation_duration: Option<i32>,
     pub create_time: i64,
     pub update_time: i64,
 
 }
 
+/// 元数据导入时的修改内容【为空的字段不修改】
+#[derive(AsChangeset, Debug, Default)]
+#[diesel(table_name = crate::storage::schema::photo_table)]
+pub struct PhotoMetadataChangeset {
+    pub rating: Option<i32>,
+    pub caption: Option<String>,
+    pub notes: Option<String>,
+    pub date_time_original: Option<i64>,
+    pub offset_time: Option<String>,
+    pub gps_info: Option<String>,
+    pub location_name: Option<String>,
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub software: Option<String>,
+    pub exposure_time: Option<f32>,
+    pub flash: Option<String>,
+    pub f_number: Option<f32>,
+    pub iso: Option<i32>,
+    pub max_aperture_value: Option<String>,
+    pub focal_length: Option<f32>,
+    pub exposure_program: Option<String>,
+    pub metering_mode: Option<String>,
+}
+
 /*
 
 id：图片唯一标识符（主键，自增）。
@@ -0,0 +1,25 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 照片活动记录
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::photo_activity)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct PhotoActivity {
+    pub id: i32,
+    pub photo_id: i32,
+    /// 类型【见 ActivityKind】
+    pub kind: i32,
+    /// 说明（编辑内容、导出路径等）
+    pub detail: Option<String>,
+    pub create_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::photo_activity)]
+pub struct NewPhotoActivity {
+    pub photo_id: i32,
+    pub kind: i32,
+    pub detail: Option<String>,
+    pub create_time: i64,
+}
@@ -0,0 +1,14 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 照片 BlurHash
+#[derive(Queryable, Selectable, Insertable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::photo_blurhashes)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct PhotoBlurhashRecord {
+    /// 文件 Hash
+    pub hash: String,
+    /// BlurHash 字符串
+    pub blurhash: String,
+    pub create_time: i64,
+}
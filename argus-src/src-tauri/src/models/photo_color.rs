@@ -0,0 +1,32 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 照片主色
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::photo_colors)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct PhotoColorRecord {
+    pub id: i32,
+    /// 文件 Hash
+    pub hash: String,
+    /// CIELAB L*
+    pub l: f32,
+    /// CIELAB a*
+    pub a: f32,
+    /// CIELAB b*
+    pub b: f32,
+    /// 占比（0 - 1）
+    pub weight: f32,
+    pub create_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::photo_colors)]
+pub struct NewPhotoColor<'a> {
+    pub hash: &'a str,
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+    pub weight: f32,
+    pub create_time: i64,
+}
@@ -0,0 +1,18 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 照片直方图摘要
+#[derive(Queryable, Selectable, Insertable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::photo_histograms)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct PhotoHistogramRecord {
+    /// 文件 Hash
+    pub hash: String,
+    /// 各通道直方图（JSON）
+    pub histogram: String,
+    /// 高光溢出像素百分比
+    pub highlight_clipping: f32,
+    /// 暗部溢出像素百分比
+    pub shadow_clipping: f32,
+    pub create_time: i64,
+}
@@ -0,0 +1,58 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 连拍序列（待拼接的全景、待合成的包围曝光）
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::photo_sequences)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct PhotoSequence {
+    pub id: i32,
+    /// 类型【见 SequenceKind】
+    pub kind: i32,
+    /// 照片数量
+    pub photo_count: i32,
+    /// 第一张的拍摄时间
+    pub start_time: i64,
+    /// 最后一张的拍摄时间
+    pub end_time: i64,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::photo_sequences)]
+pub struct NewPhotoSequence {
+    pub kind: i32,
+    pub photo_count: i32,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::photo_sequence_members)]
+pub struct NewPhotoSequenceMember {
+    pub sequence_id: i32,
+    pub photo_id: i32,
+    pub create_time: i64,
+}
+
+/// 连拍序列检测的候选照片
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::storage::schema::photo_table)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct SequenceCandidate {
+    pub id: i32,
+    pub img_path: String,
+    pub img_name: String,
+    pub width: i32,
+    pub height: i32,
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub exposure_time: Option<f32>,
+    pub f_number: Option<f32>,
+    pub iso: Option<i32>,
+    pub focal_length: Option<f32>,
+    pub date_time_original: Option<i64>,
+}
@@ -0,0 +1,25 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 照片版本堆叠
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::photo_stacks)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct PhotoStack {
+    pub id: i32,
+    /// 原图
+    pub original_photo_id: i32,
+    /// 网格中显示的版本
+    pub cover_photo_id: i32,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::photo_stacks)]
+pub struct NewPhotoStack {
+    pub original_photo_id: i32,
+    pub cover_photo_id: i32,
+    pub create_time: i64,
+    pub update_time: i64,
+}
@@ -13,6 +13,25 @@ pub struct PhotoStorage {
     pub is_delete: bool,
     pub create_time: i64,
     pub update_time: i64,
+    /// 卷唯一标识【卷 GUID、文件系统 UUID、网络共享地址】
+    #[serde(default)]
+    pub volume_id: Option<String>,
+    /// 卷标
+    #[serde(default)]
+    pub volume_label: Option<String>,
+    /// 相对于挂载点的路径【用于盘符变化后重新定位】
+    #[serde(default)]
+    pub relative_path: Option<String>,
+    /// 卷是否在线
+    #[serde(default = "default_available")]
+    pub is_available: bool,
+    /// 最后一次在线时间
+    #[serde(default)]
+    pub last_seen_time: i64,
+}
+
+fn default_available() -> bool {
+    true
 }
 
 impl PhotoStorage {
@@ -20,11 +39,16 @@ impl PhotoStorage {
     pub fn default() -> Self {
         Self {
             img_paths: String::from(""),
-            id: crate::constant::BASIC_SETTING_ID, // 假设默认 ID 是 1，你可能需要确保唯一性
+            id: 0, // 未保存到数据库时没有 ID
             is_enable: false,
             create_time: TimeUtils::current_timestamp(),
             update_time: TimeUtils::current_timestamp(),
             is_delete: false,
+            volume_id: None,
+            volume_label: None,
+            relative_path: None,
+            is_available: true,
+            last_seen_time: 0,
         }
     }
 }
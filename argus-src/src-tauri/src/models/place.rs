@@ -0,0 +1,42 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 地点（按拍摄位置自动聚类）
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::places)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Place {
+    pub id: i32,
+    /// 名称
+    pub name: String,
+    /// 中心纬度
+    pub latitude: f64,
+    /// 中心经度
+    pub longitude: f64,
+    /// 覆盖半径（米）
+    pub radius_meters: f64,
+    /// 照片数量
+    pub photo_count: i32,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::places)]
+pub struct NewPlace {
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius_meters: f64,
+    pub photo_count: i32,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::place_photos)]
+pub struct NewPlacePhoto {
+    pub place_id: i32,
+    pub photo_id: i32,
+    pub create_time: i64,
+}
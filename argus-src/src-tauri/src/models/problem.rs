@@ -0,0 +1,38 @@
+use diesel::{AsChangeset, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 无法读取的文件
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::problems)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ProblemRecord {
+    pub id: i32,
+    /// 文件路径
+    pub path: String,
+    /// 错误类型【见 `ProblemKind`】
+    pub kind: i32,
+    /// 错误信息
+    pub message: String,
+    /// 文件开头的字节（十六进制）
+    pub sample: String,
+    /// 记录时的文件大小（字节）
+    pub file_size: i64,
+    /// 记录时的文件修改时间（毫秒）
+    pub modified_time: i64,
+    /// 失败次数
+    pub fail_count: i32,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = crate::storage::schema::problems)]
+pub struct NewProblem {
+    pub path: String,
+    pub kind: i32,
+    pub message: String,
+    pub sample: String,
+    pub file_size: i64,
+    pub modified_time: i64,
+    pub update_time: i64,
+}
@@ -0,0 +1,83 @@
+use diesel::{AsChangeset, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 远程存储（S3 兼容存储、WebDAV）
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::remote_storages)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct RemoteStorageRecord {
+    pub id: i32,
+    /// 显示名称
+    pub name: String,
+    /// 类型【见 `RemoteStorageKind`】
+    pub kind: String,
+    /// 服务地址【S3 为 endpoint，WebDAV 为根目录地址】
+    pub endpoint: String,
+    /// S3 存储桶
+    pub bucket: Option<String>,
+    /// S3 区域
+    pub region: Option<String>,
+    /// 照片所在的目录前缀
+    pub prefix: Option<String>,
+    /// S3 Access Key / WebDAV 用户名
+    pub access_key: Option<String>,
+    /// S3 Secret Key / WebDAV 密码
+    pub secret_key: Option<String>,
+    pub is_enable: bool,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = crate::storage::schema::remote_storages)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewRemoteStorage {
+    pub name: String,
+    pub kind: String,
+    pub endpoint: String,
+    pub bucket: Option<String>,
+    pub region: Option<String>,
+    pub prefix: Option<String>,
+    pub access_key: Option<String>,
+    pub secret_key: Option<String>,
+    pub is_enable: bool,
+    pub update_time: i64,
+}
+
+/// 远程存储中的照片
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::remote_objects)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct RemoteObjectRecord {
+    pub id: i32,
+    /// 远程存储 ID
+    pub storage_id: i32,
+    /// 对象路径【相对远程存储根目录】
+    pub object_key: String,
+    /// 文件大小
+    pub size: i64,
+    pub etag: Option<String>,
+    /// 远程修改时间
+    pub modified_time: i64,
+    /// 文件 SHA-256【生成缩略图时计算】
+    pub hash: Option<String>,
+    /// 生成缩略图的时间【0 表示还未生成】
+    pub thumbnail_time: i64,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+/// 远程列表中的对象信息【新增或文件变化时写入，同时清空读取过的信息】
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = crate::storage::schema::remote_objects)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewRemoteObject {
+    pub storage_id: i32,
+    pub object_key: String,
+    pub size: i64,
+    pub etag: Option<String>,
+    pub modified_time: i64,
+    pub hash: Option<String>,
+    pub thumbnail_time: i64,
+    pub update_time: i64,
+}
@@ -0,0 +1,30 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 扫描报告
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::scan_reports)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ScanReportRecord {
+    /// 扫描 ID
+    pub id: i32,
+    /// 状态【0 扫描中、1 完成、2 取消】
+    pub status: i32,
+    /// 处理的文件数量
+    pub file_count: i32,
+    /// 所有文件处理耗时之和（毫秒）
+    pub total_millis: i64,
+    /// 最慢的文件（JSON）
+    pub slowest_files: String,
+    /// 最慢的文件夹（JSON）
+    pub slowest_folders: String,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::scan_reports)]
+pub struct NewScanReport {
+    pub status: i32,
+    pub start_time: i64,
+}
@@ -0,0 +1,47 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 标签
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::tags)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct Tag {
+    pub id: i32,
+    /// 标签名称
+    pub name: String,
+    /// 父级标签【为空表示根标签】
+    pub parent_id: Option<i32>,
+    pub is_delete: bool,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::tags)]
+pub struct NewTag {
+    /// 标签名称
+    pub name: String,
+    /// 父级标签
+    pub parent_id: Option<i32>,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+/// 标签和照片的关联
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::photo_tags)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct PhotoTag {
+    pub id: i32,
+    pub tag_id: i32,
+    pub photo_id: i32,
+    pub create_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::photo_tags)]
+pub struct NewPhotoTag {
+    pub tag_id: i32,
+    pub photo_id: i32,
+    pub create_time: i64,
+}
@@ -0,0 +1,37 @@
+use diesel::{AsChangeset, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 用户资料和偏好设置
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::user_profiles)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct UserProfileRecord {
+    pub id: i32,
+    /// 显示名称
+    pub display_name: String,
+    /// 界面语言【为空时使用配置文件中的语言】
+    pub language: Option<String>,
+    /// 主题【见 `Theme`】
+    pub theme: String,
+    /// 照片列表默认排序【见 `PhotoListSort`】
+    pub default_sort: String,
+    /// 网格密度【见 `GridDensity`】
+    pub grid_density: String,
+    /// 搜索结果中隐藏保险箱照片
+    pub vault_safe_search: bool,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = crate::storage::schema::user_profiles)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewUserProfile {
+    pub display_name: String,
+    pub language: Option<String>,
+    pub theme: String,
+    pub default_sort: String,
+    pub grid_density: String,
+    pub vault_safe_search: bool,
+    pub update_time: i64,
+}
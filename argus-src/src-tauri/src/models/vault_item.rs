@@ -0,0 +1,61 @@
+use crate::models::photo::Photo;
+use diesel::{AsChangeset, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 保险箱中的照片
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::vault_items)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct VaultItem {
+    pub id: i32,
+    pub photo_id: i32,
+    /// 保险箱中加密后的原图文件名
+    pub file_name: String,
+    /// 保险箱中加密后的缩略图文件名
+    pub thumbnail_name: Option<String>,
+    /// 原图大小（字节）
+    pub file_size: i64,
+    pub create_time: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::storage::schema::vault_items)]
+pub struct NewVaultItem {
+    pub photo_id: i32,
+    pub file_name: String,
+    pub thumbnail_name: Option<String>,
+    pub file_size: i64,
+    pub create_time: i64,
+}
+
+/// 移入保险箱时从数据库中清除的照片信息【加密保存在保险箱中，移出时恢复】
+#[derive(AsChangeset, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[diesel(table_name = crate::storage::schema::photo_table)]
+#[diesel(treat_none_as_null = true)]
+pub struct PhotoPrivateFields {
+    pub caption: Option<String>,
+    pub notes: Option<String>,
+    pub gps_info: Option<String>,
+    pub gps_speed: Option<f32>,
+    pub gps_img_direction: Option<f32>,
+    pub gps_img_direction_ref: Option<String>,
+    pub gps_dest_bearing: Option<f32>,
+    pub gps_dest_bearing_ref: Option<String>,
+    pub location_name: Option<String>,
+}
+
+impl PhotoPrivateFields {
+    pub fn from_photo(photo: &Photo) -> PhotoPrivateFields {
+        PhotoPrivateFields {
+            caption: photo.caption.clone(),
+            notes: photo.notes.clone(),
+            gps_info: photo.gps_info.clone(),
+            gps_speed: photo.gps_speed,
+            gps_img_direction: photo.gps_img_direction,
+            gps_img_direction_ref: photo.gps_img_direction_ref.clone(),
+            gps_dest_bearing: photo.gps_dest_bearing,
+            gps_dest_bearing_ref: photo.gps_dest_bearing_ref.clone(),
+            location_name: photo.location_name.clone(),
+        }
+    }
+}
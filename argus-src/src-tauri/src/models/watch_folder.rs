@@ -0,0 +1,52 @@
+use diesel::{AsChangeset, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+/// 监视文件夹
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::storage::schema::watch_folders)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct WatchFolderRecord {
+    pub id: i32,
+    /// 监视的文件夹
+    pub path: String,
+    /// 导入的目标文件夹
+    pub dest_dir: String,
+    /// 子文件夹模板【见 `RenameTemplate`，各级文件夹用 `/` 分隔】
+    pub dest_template: Option<String>,
+    /// 加入的相册名称
+    pub album: Option<String>,
+    /// 添加的标签名称【字符串数组的 JSON】
+    pub tags: String,
+    /// 是否移动文件【否则复制】
+    pub move_files: bool,
+    pub is_enable: bool,
+    pub create_time: i64,
+    pub update_time: i64,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = crate::storage::schema::watch_folders)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewWatchFolder {
+    pub path: String,
+    pub dest_dir: String,
+    pub dest_template: Option<String>,
+    pub album: Option<String>,
+    pub tags: String,
+    pub move_files: bool,
+    pub is_enable: bool,
+    pub update_time: i64,
+}
+
+/// 监视文件夹已导入的文件
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = crate::storage::schema::watch_folder_imports)]
+pub struct NewWatchFolderImport {
+    pub folder_id: i32,
+    /// 监视文件夹中的文件路径
+    pub source_path: String,
+    pub hash: String,
+    /// 导入时的文件大小【文件大小变化时重新导入】
+    pub file_size: i64,
+    pub create_time: i64,
+}
@@ -0,0 +1,349 @@
+use crate::constant::{
+    BACKUP_FOLDER_PREFIX, BACKUP_PAGES_PER_STEP, BACKUP_TIME_FMT, DATABASE_BUSY_TIMEOUT_MS,
+    DEFAULT_BACKUP_FOLDER, DEFAULT_PROFILE_NAME, PRE_RESTORE_BACKUP_FOLDER,
+};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::storage::connection::{self, database_url};
+use crate::structs::config::{get_config_dir, sys_config};
+use crate::structs::library::current_library;
+use crate::utils::file_util;
+use crate::utils::task_util;
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use rusqlite::backup::{Backup, StepResult};
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// 是否正在备份或恢复数据库
+static BACKUP_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// 自动备份配置是否变化【变化时唤醒自动备份线程】
+static AUTO_BACKUP_SIGNAL: Lazy<(Mutex<bool>, Condvar)> =
+    Lazy::new(|| (Mutex::new(false), Condvar::new()));
+
+/// 备份结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSummary {
+    /// 备份文件夹
+    pub path: String,
+    /// 数据库文件大小（字节）
+    pub database_size: u64,
+    /// 是否包含配置文件
+    pub include_config: bool,
+    /// 备份时间（时间戳）
+    pub create_time: i64,
+}
+
+/// 恢复结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreSummary {
+    /// 恢复使用的数据库文件
+    pub path: String,
+    /// 是否恢复了配置文件【重启后生效】
+    pub config_restored: bool,
+    /// 恢复前自动备份的当前数据库
+    pub pre_restore_backup: String,
+}
+
+/// 备份数据库
+///
+/// 在 `dest_path` 下创建 `argus-backup-<时间>` 文件夹，保存数据库快照和配置文件。
+/// 使用 SQLite 在线备份接口分步复制，备份过程中可以正常读写。
+/// - dest_path 备份保存的文件夹
+/// - include_config 是否包含配置文件
+pub fn backup_library(dest_path: &Path, include_config: bool) -> Result<BackupSummary> {
+    let _guard = BackupGuard::acquire()?;
+    write_backup(dest_path, include_config)
+}
+
+/// 是否正在备份或恢复数据库【切换照片库前检查】
+pub fn is_running() -> bool {
+    BACKUP_RUNNING.load(Ordering::SeqCst)
+}
+
+/// 备份进行中的标记【离开作用域时清除】
+struct BackupGuard;
+
+impl BackupGuard {
+    fn acquire() -> Result<BackupGuard> {
+        BACKUP_RUNNING
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .map(|_| BackupGuard)
+            .map_err(|_| anyhow!(i18n::text(msg::BACKUP_RUNNING)))
+    }
+}
+
+impl Drop for BackupGuard {
+    fn drop(&mut self) {
+        BACKUP_RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+fn write_backup(dest_path: &Path, include_config: bool) -> Result<BackupSummary> {
+    let folder = dest_path.join(format!(
+        "{}{}",
+        BACKUP_FOLDER_PREFIX,
+        TimeUtils::current_datetime_string(Some(BACKUP_TIME_FMT))
+    ));
+    if folder.exists() {
+        return Err(i18n::error(
+            msg::BACKUP_FOLDER_EXISTS,
+            &[("path", folder.display().to_string())],
+        ));
+    }
+    fs::create_dir_all(&folder)?;
+
+    let db_file = folder.join(database_file_name());
+    if let Err(e) = snapshot_database(Path::new(&database_url()), &db_file) {
+        // 不保留不完整的备份
+        let _ = fs::remove_dir_all(&folder);
+        return Err(e);
+    }
+    let config_file = PathBuf::from(get_config_dir());
+    let include_config = include_config && config_file.is_file();
+    if include_config {
+        fs::copy(&config_file, folder.join(DEFAULT_PROFILE_NAME))?;
+    }
+
+    let summary = BackupSummary {
+        path: file_util::display_path(&folder),
+        database_size: fs::metadata(&db_file)?.len(),
+        include_config,
+        create_time: TimeUtils::current_timestamp(),
+    };
+    log::info!("数据库备份完成: {:?}", summary);
+    Ok(summary)
+}
+
+/// 从备份恢复数据库
+///
+/// 先校验备份文件，再把当前数据库备份到自动备份文件夹下的 `pre-restore` 文件夹（不会被自动清理），
+/// 然后在一个事务中替换当前数据库的所有页并重新校验，其他连接只会看到恢复前或恢复后的完整数据库。
+/// 扫描、写入扫描结果或备份进行中时不能恢复
+/// - src_path 备份文件夹或数据库文件
+/// - include_config 是否同时恢复配置文件【重启后生效】
+pub fn restore_library(src_path: &Path, include_config: bool) -> Result<RestoreSummary> {
+    let (folder, db_file) = if src_path.is_dir() {
+        (src_path.to_path_buf(), src_path.join(database_file_name()))
+    } else {
+        let folder = src_path.parent().map(Path::to_path_buf).unwrap_or_default();
+        (folder, src_path.to_path_buf())
+    };
+    if !db_file.is_file() {
+        return Err(i18n::error(
+            msg::BACKUP_DATABASE_NOT_FOUND,
+            &[("path", db_file.display().to_string())],
+        ));
+    }
+    task_util::ensure_database_idle(msg::DATABASE_ACTION_RESTORE)?;
+    let _guard = BackupGuard::acquire()?;
+    let src = Connection::open_with_flags(&db_file, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    check_integrity(&src)
+        .map_err(|e| i18n::error(msg::BACKUP_CORRUPTED, &[("error", e.to_string())]))?;
+
+    // 恢复失败时可以用它找回当前数据
+    let pre_restore = write_backup(&auto_backup_dir().join(PRE_RESTORE_BACKUP_FOLDER), false)?;
+
+    let mut live = Connection::open(database_url())?;
+    live.busy_timeout(Duration::from_millis(DATABASE_BUSY_TIMEOUT_MS))?;
+    // 一步复制所有页，复制期间一直持有写锁，不会与其他连接的写入交错
+    let step = Backup::new(&src, &mut live)?.step(-1)?;
+    if step != StepResult::Done {
+        return Err(i18n::error(
+            msg::BACKUP_RESTORE_BUSY,
+            &[
+                ("step", format!("{:?}", step)),
+                ("path", pre_restore.path.to_string()),
+            ],
+        ));
+    }
+    check_integrity(&live).map_err(|e| {
+        i18n::error(
+            msg::BACKUP_RESTORE_VERIFY_FAILED,
+            &[
+                ("path", pre_restore.path.to_string()),
+                ("error", e.to_string()),
+            ],
+        )
+    })?;
+    drop(live);
+    // 旧版本的备份需要升级到当前数据库版本
+    connection::run_migrations()
+        .map_err(|e| i18n::error(msg::BACKUP_MIGRATION_FAILED, &[("error", e.to_string())]))?;
+
+    let config_file = folder.join(DEFAULT_PROFILE_NAME);
+    let config_restored = include_config && config_file.is_file();
+    if config_restored {
+        fs::copy(&config_file, get_config_dir())?;
+    }
+
+    let summary = RestoreSummary {
+        path: file_util::display_path(&db_file),
+        config_restored,
+        pre_restore_backup: pre_restore.path,
+    };
+    log::info!("数据库恢复完成: {:?}", summary);
+    Ok(summary)
+}
+
+/// 启动自动备份线程
+///
+/// 距离最近一次备份超过间隔时立即备份，之后按间隔执行，并清理超出保留数量的旧备份。
+/// 每次等待前重新读取间隔，间隔为 0 时暂停，直到 `reschedule_auto_backup` 唤醒
+pub fn start_auto_backup() {
+    let spawned = std::thread::Builder::new()
+        .name("argus-auto-backup".to_string())
+        .spawn(auto_backup_worker);
+    if let Err(e) = spawned {
+        log::error!("自动备份线程启动失败: {}", e);
+    }
+}
+
+/// 自动备份间隔变化后重新计算下次备份时间【重新加载配置时调用】
+pub fn reschedule_auto_backup() {
+    let (changed, condvar) = &*AUTO_BACKUP_SIGNAL;
+    *changed.lock().unwrap() = true;
+    condvar.notify_all();
+}
+
+/// 自动备份线程【等待到下次备份时间或被唤醒】
+fn auto_backup_worker() {
+    let (changed, condvar) = &*AUTO_BACKUP_SIGNAL;
+    let mut failed = false;
+    loop {
+        let hours = sys_config().backup_interval_hours.unwrap_or_default();
+        let mut guard = changed.lock().unwrap();
+        if hours == 0 {
+            log::info!("自动备份未开启");
+            while !*guard {
+                guard = condvar.wait(guard).unwrap();
+            }
+            *guard = false;
+            continue;
+        }
+        let interval = Duration::from_secs(u64::from(hours) * 3600);
+        // 上次备份失败时等待一个完整间隔，避免反复重试
+        let wait = if failed {
+            interval
+        } else {
+            let elapsed = latest_backup_time(&auto_backup_dir())
+                .and_then(|x| SystemTime::now().duration_since(x).ok())
+                .unwrap_or(interval);
+            interval.saturating_sub(elapsed)
+        };
+        let (mut guard, timeout) = condvar
+            .wait_timeout_while(guard, wait, |changed| !*changed)
+            .unwrap();
+        if !timeout.timed_out() {
+            *guard = false;
+            failed = false;
+            continue;
+        }
+        drop(guard);
+
+        failed = match run_auto_backup() {
+            Ok(_) => false,
+            Err(e) => {
+                log::error!("自动备份失败: {}", e);
+                true
+            }
+        };
+    }
+}
+
+/// 备份到自动备份文件夹，并清理超出保留数量的旧备份
+pub fn run_auto_backup() -> Result<BackupSummary> {
+    let dir = auto_backup_dir();
+    let include_config = sys_config().backup_include_config.unwrap_or(true);
+    let summary = backup_library(&dir, include_config)?;
+    prune_backups(&dir);
+    Ok(summary)
+}
+
+/// 使用在线备份接口复制数据库
+fn snapshot_database(src: &Path, dest: &Path) -> Result<()> {
+    let src = Connection::open(src)?;
+    let mut dest_conn = Connection::open(dest)?;
+    // 每步之间短暂让出锁，避免长时间阻塞写入
+    Backup::new(&src, &mut dest_conn)?.run_to_completion(
+        BACKUP_PAGES_PER_STEP,
+        Duration::from_millis(10),
+        None,
+    )?;
+    // 备份文件不使用 WAL，保证单个文件即可恢复
+    dest_conn.pragma_update(None, "journal_mode", "DELETE")?;
+    check_integrity(&dest_conn)
+}
+
+/// 数据库完整性校验
+fn check_integrity(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    if rows.len() == 1 && rows[0] == "ok" {
+        Ok(())
+    } else {
+        Err(anyhow!("{}", rows.join("; ")))
+    }
+}
+
+/// 自动备份保存的文件夹【默认为当前照片库下的 backup 文件夹】
+fn auto_backup_dir() -> PathBuf {
+    match sys_config().backup_path.as_deref() {
+        Some(path) if !path.is_empty() => PathBuf::from(path),
+        _ => current_library().root.join(DEFAULT_BACKUP_FOLDER),
+    }
+}
+
+fn database_file_name() -> String {
+    sys_config().database_name.clone().unwrap_or_default()
+}
+
+/// 文件夹下的所有备份【按名称排序，即按时间从旧到新】
+fn list_backups(dir: &Path) -> Vec<PathBuf> {
+    let mut backups: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.is_dir()
+                        && p.file_name()
+                            .and_then(|x| x.to_str())
+                            .is_some_and(|x| x.starts_with(BACKUP_FOLDER_PREFIX))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    backups.sort();
+    backups
+}
+
+/// 最近一次备份的时间
+fn latest_backup_time(dir: &Path) -> Option<SystemTime> {
+    let latest = list_backups(dir).pop()?;
+    fs::metadata(latest).and_then(|m| m.modified()).ok()
+}
+
+/// 清理超出保留数量的旧备份
+fn prune_backups(dir: &Path) {
+    let keep = sys_config().backup_keep_count.unwrap_or_default().max(1) as usize;
+    let backups = list_backups(dir);
+    let remove = backups.len().saturating_sub(keep);
+    for old in &backups[..remove] {
+        match fs::remove_dir_all(old) {
+            Ok(_) => log::info!("清理旧备份: {}", old.display()),
+            Err(e) => log::warn!("旧备份清理失败 {}: {}", old.display(), e),
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::constant::MAX_RATING;
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::services::photo_file_service;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::batch_operation::{BatchApplyProgress, BatchApplySummary, BatchOperation};
+use crate::structs::photo_activity::ActivityKind;
+use crate::utils::file_util;
+use anyhow::{anyhow, Result};
+use diesel::{Connection, SqliteConnection};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+/// 对选中的照片执行批量操作
+///
+/// 所有数据库操作在同一个事务中执行，任一操作失败时全部回滚；文件在事务提交后移动，
+/// 移动失败时已移动的文件移回原位置、照片位置还原（其他操作保留）。
+/// 进度按照片数量通知，结束时通知一次最终状态
+/// - photo_ids 照片 ID
+/// - operations 按顺序执行的操作【最多一个移动操作】
+/// - on_progress 进度回调
+pub fn batch_apply<F>(
+    photo_ids: &[i32],
+    operations: &[BatchOperation],
+    on_progress: F,
+) -> Result<BatchApplySummary>
+where
+    F: Fn(&BatchApplyProgress),
+{
+    let photo_ids: Vec<i32> = photo_ids
+        .iter()
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let mut progress = BatchApplyProgress {
+        total: photo_ids.len(),
+        ..Default::default()
+    };
+    let result = apply(&photo_ids, operations, &mut progress, &on_progress);
+    progress.finished = true;
+    if let Err(e) = &result {
+        progress.error = Some(e.to_string());
+    }
+    on_progress(&progress);
+    result
+}
+
+fn apply<F>(
+    photo_ids: &[i32],
+    operations: &[BatchOperation],
+    progress: &mut BatchApplyProgress,
+    on_progress: &F,
+) -> Result<BatchApplySummary>
+where
+    F: Fn(&BatchApplyProgress),
+{
+    let mut conn = establish_connection();
+    let photos = validate(&mut conn, photo_ids, operations)?;
+    let dest_dir = operations.iter().find_map(|x| match x {
+        BatchOperation::Move { dest_dir } => Some(PathBuf::from(dest_dir.trim())),
+        _ => None,
+    });
+    let moves = match &dest_dir {
+        Some(dir) => {
+            fs::create_dir_all(file_util::long_path(dir))?;
+            photo_file_service::plan_moves(photos, dir)
+        }
+        None => Vec::new(),
+    };
+    photo_file_service::check_moves(&moves)?;
+
+    let mut summary = BatchApplySummary {
+        photos: photo_ids.len(),
+        ..Default::default()
+    };
+    conn.transaction(|conn| {
+        // 约每 1% 通知一次进度
+        let step = (photo_ids.len() / 100).max(1);
+        for (index, photo_id) in photo_ids.iter().enumerate() {
+            for operation in operations {
+                summary.changed += apply_operation(conn, *photo_id, operation)?;
+            }
+            progress.done = index + 1;
+            if progress.done % step == 0 {
+                on_progress(&*progress);
+            }
+        }
+        for (photo, to) in &moves {
+            storage::photo_table::update_photo_location(conn, photo.id, to)?;
+        }
+        summary.changed += moves.len();
+        let description = i18n::t(
+            msg::EDIT_BATCH,
+            &[
+                ("count", photo_ids.len().to_string()),
+                ("operations", operations.len().to_string()),
+            ],
+        );
+        storage::photo_activity::insert_activities(
+            conn,
+            photo_ids,
+            ActivityKind::Edited,
+            Some(&description),
+        )?;
+        anyhow::Ok(())
+    })
+    .inspect_err(|e| log::error!("批量操作失败，已回滚: {}", e))?;
+
+    // 不在事务中移动文件，避免长时间占用数据库写锁
+    let done_files = match photo_file_service::move_files(&moves) {
+        Ok(done) => done,
+        Err(e) => {
+            restore_locations(&mut conn, &moves);
+            log::error!("批量操作的文件移动失败，照片位置已还原: {}", e);
+            return Err(e);
+        }
+    };
+    summary.relocations = photo_file_service::finish_moves(&moves, &done_files);
+    log::info!(
+        "批量操作完成: {} 张照片，{} 项修改",
+        summary.photos,
+        summary.changed
+    );
+    Ok(summary)
+}
+
+/// 文件移动失败时把照片位置改回原路径
+fn restore_locations(conn: &mut SqliteConnection, moves: &[(Photo, PathBuf)]) {
+    let result = conn.transaction(|conn| {
+        for (photo, _) in moves {
+            storage::photo_table::update_photo_location(conn, photo.id, &photo.full_path())?;
+        }
+        anyhow::Ok(())
+    });
+    if let Err(e) = result {
+        log::error!("照片位置还原失败: {}", e);
+    }
+}
+
+/// 校验照片和操作，返回照片
+fn validate(
+    conn: &mut SqliteConnection,
+    photo_ids: &[i32],
+    operations: &[BatchOperation],
+) -> Result<Vec<Photo>> {
+    if photo_ids.is_empty() {
+        return Err(anyhow!(i18n::text(msg::BATCH_NO_PHOTOS)));
+    }
+    if operations.is_empty() {
+        return Err(anyhow!(i18n::text(msg::BATCH_NO_OPERATIONS)));
+    }
+    let mut moves = 0;
+    for operation in operations {
+        match operation {
+            BatchOperation::AddTag { tag_id } | BatchOperation::RemoveTag { tag_id } => {
+                if !storage::tag::tag_exists(conn, *tag_id)? {
+                    return Err(i18n::error(
+                        msg::TAG_NOT_FOUND,
+                        &[("id", tag_id.to_string())],
+                    ));
+                }
+            }
+            BatchOperation::AddAlbum { album_id } => {
+                if !storage::album::album_exists(conn, *album_id)? {
+                    return Err(i18n::error(
+                        msg::ALBUM_NOT_FOUND,
+                        &[("id", album_id.to_string())],
+                    ));
+                }
+                if storage::album::is_mirror_album(conn, *album_id)? {
+                    return Err(i18n::error(
+                        msg::BATCH_MIRROR_ALBUM,
+                        &[("id", album_id.to_string())],
+                    ));
+                }
+            }
+            BatchOperation::Move { dest_dir } => {
+                if dest_dir.trim().is_empty() {
+                    return Err(anyhow!(i18n::text(msg::BATCH_DEST_REQUIRED)));
+                }
+                moves += 1;
+            }
+            BatchOperation::SetRating { .. } => {}
+        }
+    }
+    if moves > 1 {
+        return Err(anyhow!(i18n::text(msg::BATCH_MULTIPLE_MOVES)));
+    }
+    let photos = storage::photo_table::search_photos_by_ids(conn, photo_ids)?;
+    if photos.len() != photo_ids.len() {
+        return Err(anyhow!(i18n::text(msg::PHOTOS_PARTIALLY_MISSING)));
+    }
+    Ok(photos)
+}
+
+/// 对单张照片执行数据库操作，返回修改的记录数【移动操作在所有照片处理后统一执行】
+fn apply_operation(
+    conn: &mut SqliteConnection,
+    photo_id: i32,
+    operation: &BatchOperation,
+) -> Result<usize> {
+    match operation {
+        BatchOperation::AddTag { tag_id } => {
+            storage::tag::add_photo_tags(conn, photo_id, &[*tag_id])
+        }
+        BatchOperation::RemoveTag { tag_id } => {
+            storage::tag::remove_photo_tags(conn, photo_id, &[*tag_id])
+        }
+        BatchOperation::SetRating { rating } => {
+            let rating = rating.map(|x| x.clamp(0, MAX_RATING));
+            storage::photo_table::update_photo_rating(conn, photo_id, rating)?;
+            Ok(1)
+        }
+        BatchOperation::AddAlbum { album_id } => {
+            storage::album::add_photo_albums(conn, photo_id, &[*album_id])
+        }
+        BatchOperation::Move { .. } => Ok(0),
+    }
+}
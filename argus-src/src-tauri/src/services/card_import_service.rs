@@ -0,0 +1,347 @@
+use crate::constant::{CARD_IMPORT_RETRIES, DCIM_FOLDER_NAME};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::services::{disk_space_service, folder_album_service};
+use crate::structs::card_import::{
+    CameraCard, CardFile, CardImportItem, CardImportProgressMsg, CardImportReport, CardImportStatus,
+};
+use crate::structs::disk_space::DiskSpacePurpose;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::file_util;
+use crate::utils::volume_util;
+use anyhow::{anyhow, Result};
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+/// 查找插入的存储卡（包含 DCIM 文件夹的可移动卷）
+pub fn list_camera_cards() -> Vec<CameraCard> {
+    volume_util::list_volumes()
+        .into_iter()
+        .filter(|x| x.is_removable)
+        .filter_map(|volume| {
+            let dcim = volume.mount_point.join(DCIM_FOLDER_NAME);
+            if !dcim.is_dir() {
+                return None;
+            }
+            let files = walk_card_files(&dcim);
+            Some(CameraCard {
+                dcim_path: file_util::display_path(&dcim),
+                file_count: files.len(),
+                total_bytes: files.iter().map(|x| x.size).sum(),
+                volume,
+            })
+        })
+        .collect()
+}
+
+/// 列出存储卡 DCIM 文件夹中的文件【按文件夹、文件名排序】
+/// - path 存储卡挂载点或 DCIM 文件夹
+pub fn list_card_files(path: &Path) -> Result<Vec<CardFile>> {
+    let dcim = path.join(DCIM_FOLDER_NAME);
+    let dcim = if dcim.is_dir() {
+        dcim
+    } else {
+        path.to_path_buf()
+    };
+    if !dcim.is_dir() {
+        return Err(i18n::error(
+            msg::FOLDER_NOT_FOUND,
+            &[("path", dcim.display().to_string())],
+        ));
+    }
+    Ok(walk_card_files(&dcim))
+}
+
+/// 从存储卡导入文件
+///
+/// 每个文件先复制到目标文件夹中的临时文件并写入磁盘，再重新读取计算 SHA-256 与源文件对比，
+/// 一致后才重命名为正式文件；不一致时删除临时文件并重试。目标文件夹中已有同名且内容相同的文件时跳过。
+/// 所有文件都校验通过后才会弹出存储卡。
+/// - files 要导入的文件
+/// - dest_dir 目标文件夹
+/// - eject 导入完成且全部校验通过后是否弹出存储卡
+/// - on_progress 进度回调【开始处理每个文件时调用】
+pub async fn import_from_card<F>(
+    files: &[PathBuf],
+    dest_dir: &Path,
+    eject: bool,
+    on_progress: F,
+) -> Result<CardImportReport>
+where
+    F: Fn(CardImportProgressMsg),
+{
+    fs::create_dir_all(file_util::long_path(dest_dir))?;
+    let required = copy_size(files, dest_dir);
+    disk_space_service::ensure_free_space(dest_dir, DiskSpacePurpose::Import, required)?;
+    let mut report = CardImportReport::default();
+    for (index, source) in files.iter().enumerate() {
+        on_progress(CardImportProgressMsg {
+            current: index,
+            total: files.len(),
+            file: file_util::display_path(source),
+        });
+        let item = import_file(source, dest_dir).await;
+        match item.status {
+            CardImportStatus::Verified => {
+                report.verified += 1;
+                report.bytes_copied += fs::metadata(file_util::long_path(source))
+                    .map(|x| x.len())
+                    .unwrap_or_default();
+            }
+            CardImportStatus::Skipped => report.skipped += 1,
+            CardImportStatus::Failed => {
+                log::warn!("存储卡文件导入失败 {}: {:?}", item.source, item.error);
+                report.failed += 1;
+            }
+        }
+        report.items.push(item);
+    }
+    report.safe_to_format = report.failed == 0 && card_fully_imported(&report.items);
+
+    if eject && report.safe_to_format {
+        let mount_points: BTreeSet<PathBuf> = files
+            .iter()
+            .filter_map(volume_util::volume_for_path)
+            .filter(|x| x.is_removable)
+            .map(|x| x.mount_point)
+            .collect();
+        report.ejected = !mount_points.is_empty();
+        for mount_point in mount_points {
+            if let Err(e) = volume_util::eject_volume(&mount_point) {
+                log::warn!("存储卡弹出失败: {}", e);
+                report.ejected = false;
+            }
+        }
+    }
+    folder_album_service::request_sync();
+    log::info!(
+        "存储卡导入完成: 校验通过 {}，跳过 {}，失败 {}",
+        report.verified,
+        report.skipped,
+        report.failed
+    );
+    Ok(report)
+}
+
+/// 估算需要复制的大小（字节）
+///
+/// 目标文件夹中已有同名且大小相同的文件视为已导入（导入时校验内容后跳过），不计入
+fn copy_size(files: &[PathBuf], dest_dir: &Path) -> u64 {
+    files
+        .iter()
+        .filter_map(|source| {
+            let size = fs::metadata(file_util::long_path(source)).ok()?.len();
+            let imported = source
+                .file_name()
+                .and_then(|name| fs::metadata(file_util::long_path(dest_dir.join(name))).ok())
+                .is_some_and(|x| x.is_file() && x.len() == size);
+            (!imported).then_some(size)
+        })
+        .sum()
+}
+
+/// 存储卡 DCIM 文件夹中的所有文件是否都已导入（校验通过或之前已导入）
+///
+/// 只导入了部分文件时格式化会丢失其他文件；找不到 DCIM 文件夹时无法确认，按未全部导入处理
+fn card_fully_imported(items: &[CardImportItem]) -> bool {
+    let imported: BTreeSet<&str> = items
+        .iter()
+        .filter(|x| x.status != CardImportStatus::Failed)
+        .map(|x| x.source.as_str())
+        .collect();
+    let mut cards = BTreeSet::new();
+    for item in items {
+        let dcim = Path::new(&item.source)
+            .ancestors()
+            .find(|x| {
+                x.file_name()
+                    .is_some_and(|x| x.eq_ignore_ascii_case(DCIM_FOLDER_NAME))
+            })
+            .map(Path::to_path_buf);
+        match dcim {
+            Some(dcim) => cards.insert(dcim),
+            None => return false,
+        };
+    }
+    !cards.is_empty()
+        && cards
+            .iter()
+            .flat_map(|dcim| walk_card_files(dcim))
+            .all(|x| imported.contains(x.path.as_str()))
+}
+
+/// 导入单个文件
+async fn import_file(source: &Path, dest_dir: &Path) -> CardImportItem {
+    let mut item = CardImportItem {
+        source: file_util::display_path(source),
+        dest: None,
+        status: CardImportStatus::Failed,
+        hash: None,
+        error: None,
+    };
+    let Some(name) = source.file_name() else {
+        item.error = Some("文件名无效".to_string());
+        return item;
+    };
+    let hash = match FileHashUtils::sha256_async(source).await {
+        Ok(x) => x,
+        Err(e) => {
+            item.error = Some(format!("源文件读取失败: {}", e));
+            return item;
+        }
+    };
+    item.hash = Some(hash.clone());
+
+    let dest = dest_dir.join(name);
+    if dest.exists() && FileHashUtils::sha256_async(&dest).await.ok().as_ref() == Some(&hash) {
+        item.dest = Some(file_util::display_path(&dest));
+        item.status = CardImportStatus::Skipped;
+        return item;
+    }
+    let dest = file_util::unique_path(&dest);
+    for attempt in 0..=CARD_IMPORT_RETRIES {
+        match copy_verified(source, &dest, &hash).await {
+            Ok(()) => {
+                item.dest = Some(file_util::display_path(&dest));
+                item.status = CardImportStatus::Verified;
+                item.error = None;
+                return item;
+            }
+            Err(e) => {
+                log::warn!("第 {} 次复制 {} 失败: {}", attempt + 1, source.display(), e);
+                item.error = Some(e.to_string());
+            }
+        }
+    }
+    item
+}
+
+/// 复制到临时文件并校验，通过后重命名为目标文件
+async fn copy_verified(source: &Path, dest: &Path, hash: &str) -> Result<()> {
+    let temp = file_util::temp_path(dest, None);
+    let (src, tmp) = (source.to_path_buf(), temp.clone());
+    let copied = tokio::task::spawn_blocking(move || copy_synced(&src, &tmp)).await?;
+    let verified = match copied {
+        Ok(()) => FileHashUtils::sha256_async(&temp)
+            .await
+            .map_err(|e| {
+                i18n::error(
+                    msg::CARD_IMPORT_COPY_READ_FAILED,
+                    &[("error", e.to_string())],
+                )
+            })
+            .and_then(|x| {
+                if x == hash {
+                    Ok(())
+                } else {
+                    Err(anyhow!(i18n::text(msg::CARD_IMPORT_VERIFY_FAILED)))
+                }
+            }),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = verified {
+        let _ = fs::remove_file(file_util::long_path(&temp));
+        return Err(e);
+    }
+    fs::rename(file_util::long_path(&temp), file_util::long_path(dest))?;
+    Ok(())
+}
+
+/// 复制文件并写入磁盘，保留修改时间
+fn copy_synced(source: &Path, dest: &Path) -> Result<()> {
+    fs::copy(file_util::long_path(source), file_util::long_path(dest))?;
+    let file = File::options()
+        .write(true)
+        .open(file_util::long_path(dest))?;
+    if let Ok(modified) = fs::metadata(file_util::long_path(source)).and_then(|x| x.modified()) {
+        file.set_modified(modified)?;
+    }
+    // 校验时读取的必须是磁盘上的内容，而不只是写入缓存
+    file.sync_all()?;
+    Ok(())
+}
+
+/// 遍历 DCIM 文件夹中的文件【跳过隐藏文件】
+fn walk_card_files(dcim: &Path) -> Vec<CardFile> {
+    let mut files: Vec<CardFile> = WalkDir::new(file_util::long_path(dcim))
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let metadata = e.metadata().ok()?;
+            let path = file_util::strip_verbatim_prefix(e.path());
+            let folder = path
+                .parent()
+                .and_then(|x| x.strip_prefix(dcim).ok())
+                .map(|x| x.to_string_lossy().to_string())
+                .unwrap_or_default();
+            Some(CardFile {
+                name: e.file_name().to_string_lossy().to_string(),
+                folder,
+                size: metadata.len(),
+                modified_time: metadata
+                    .modified()
+                    .ok()
+                    .and_then(|x| x.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |x| x.as_secs() as i64),
+                is_image: file_util::is_image_file(&path),
+                path: file_util::display_path(&path),
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| (&a.folder, &a.name).cmp(&(&b.folder, &b.name)));
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_import_from_card() {
+        let card = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        let folder = card.path().join(DCIM_FOLDER_NAME).join("100CANON");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("IMG_0001.JPG"), b"first").unwrap();
+        fs::write(folder.join("IMG_0002.CR3"), b"second").unwrap();
+        fs::write(folder.join(".hidden"), b"").unwrap();
+
+        let files: Vec<PathBuf> = list_card_files(card.path())
+            .unwrap()
+            .into_iter()
+            .map(|x| PathBuf::from(x.path))
+            .collect();
+        assert_eq!(files.len(), 2);
+
+        let report = import_from_card(&files, dest.path(), false, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(report.verified, 2);
+        assert!(report.safe_to_format);
+        assert_eq!(
+            fs::read(dest.path().join("IMG_0001.JPG")).unwrap(),
+            b"first"
+        );
+
+        // 内容相同的文件不会重复复制，也不计入需要的空间
+        assert_eq!(copy_size(&files, dest.path()), 0);
+        let report = import_from_card(&files, dest.path(), false, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(report.skipped, 2);
+        assert!(report.safe_to_format);
+
+        // 存储卡中还有没有导入的文件
+        fs::write(folder.join("IMG_0003.JPG"), b"third").unwrap();
+        let report = import_from_card(&files, dest.path(), false, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(report.failed, 0);
+        assert!(!report.safe_to_format);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::constant::CHECKSUM_MANIFEST_NAME;
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::checksum_manifest::{ChecksumExportSummary, ChecksumVerifyReport};
+use crate::utils::checksum_util;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::file_util;
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// 导出 `SHA256SUMS` 校验清单
+///
+/// 直接使用扫描时已计算的 Hash，不重新读取文件。清单中的路径相对清单所在文件夹，
+/// 可以在归档后用 `sha256sum -c SHA256SUMS` 或 `verify_manifest` 校验；
+/// 不在清单所在文件夹下的照片写入绝对路径
+/// - folder 导出此文件夹下（含子文件夹）的照片
+/// - album_id 导出此相册中的照片【与 folder 二选一】
+/// - dest_path 清单路径【为空时保存到文件夹（相册为所有照片的共同上级文件夹）下的 SHA256SUMS】
+pub fn export_checksum_manifest(
+    folder: Option<&str>,
+    album_id: Option<i32>,
+    dest_path: Option<&str>,
+) -> Result<ChecksumExportSummary> {
+    let mut conn = establish_connection();
+    let (photos, base_dir) = match (folder.filter(|x| !x.is_empty()), album_id) {
+        (Some(folder), None) => {
+            let root = PathBuf::from(folder);
+            let photos = storage::photo_table::search_photo_under_root(&mut conn, &root)?;
+            (photos, Some(root))
+        }
+        (None, Some(album_id)) => {
+            let photos = storage::album::get_album_photos(&mut conn, album_id)?;
+            let base_dir = common_parent(&photos);
+            (photos, base_dir)
+        }
+        _ => return Err(anyhow!(i18n::text(msg::CHECKSUM_SOURCE_REQUIRED))),
+    };
+    if photos.is_empty() {
+        return Err(anyhow!(i18n::text(msg::CHECKSUM_NO_PHOTOS)));
+    }
+    let dest = match dest_path.filter(|x| !x.is_empty()) {
+        Some(x) => PathBuf::from(x),
+        None => base_dir
+            .ok_or_else(|| anyhow!(i18n::text(msg::CHECKSUM_NO_COMMON_DIR)))?
+            .join(CHECKSUM_MANIFEST_NAME),
+    };
+    let manifest_dir = dest.parent().unwrap_or(Path::new("")).to_path_buf();
+
+    let mut lines: Vec<(String, String)> = photos
+        .iter()
+        .map(|photo| {
+            let path = photo.full_path();
+            let name = match path.strip_prefix(&manifest_dir) {
+                Ok(relative) => relative
+                    .components()
+                    .map(|x| x.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                Err(_) => file_util::display_path(&path),
+            };
+            (name, photo.hash.to_lowercase())
+        })
+        .collect();
+    lines.sort();
+    lines.dedup();
+    let content: String = lines
+        .iter()
+        .map(|(name, hash)| format!("{}\n", checksum_util::format_line(hash, name)))
+        .collect();
+    fs::write(file_util::long_path(&dest), content)?;
+
+    let summary = ChecksumExportSummary {
+        path: file_util::display_path(&dest),
+        file_count: lines.len(),
+    };
+    log::info!("校验清单导出完成: {:?}", summary);
+    Ok(summary)
+}
+
+/// 校验 `SHA256SUMS` 清单中的文件
+///
+/// 相对路径按清单所在文件夹解析，逐个重新计算 SHA-256 与清单对比
+/// - path 清单路径
+pub fn verify_manifest(path: &Path) -> Result<ChecksumVerifyReport> {
+    let content = fs::read_to_string(file_util::long_path(path)).map_err(|e| {
+        i18n::error(
+            msg::CHECKSUM_MANIFEST_READ_FAILED,
+            &[
+                ("path", path.display().to_string()),
+                ("error", e.to_string()),
+            ],
+        )
+    })?;
+    let manifest_dir = path.parent().unwrap_or(Path::new(""));
+
+    let mut report = ChecksumVerifyReport::default();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some((hash, name)) = checksum_util::parse_line(line) else {
+            report.invalid_lines.push(index + 1);
+            continue;
+        };
+        report.total += 1;
+        let file = manifest_dir.join(&name);
+        match FileHashUtils::sha256(&file) {
+            Ok(actual) if actual == hash => report.passed += 1,
+            Ok(_) => report.mismatched.push(name),
+            Err(e) => {
+                log::warn!("校验文件读取失败 {}: {}", file.display(), e);
+                report.missing.push(name);
+            }
+        }
+    }
+    log::info!(
+        "校验清单验证完成: 共 {}，通过 {}，不一致 {}，缺失 {}",
+        report.total,
+        report.passed,
+        report.mismatched.len(),
+        report.missing.len()
+    );
+    Ok(report)
+}
+
+/// 所有照片的共同上级文件夹
+fn common_parent(photos: &[Photo]) -> Option<PathBuf> {
+    let mut common: Option<Vec<Component>> = None;
+    let paths: Vec<PathBuf> = photos.iter().map(|x| x.full_path()).collect();
+    for path in &paths {
+        let parent: Vec<Component> = path.parent()?.components().collect();
+        common = Some(match common {
+            None => parent,
+            Some(current) => current
+                .into_iter()
+                .zip(parent)
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect(),
+        });
+    }
+    common
+        .filter(|x| x.iter().any(|c| matches!(c, Component::Normal(_))))
+        .map(|x| x.iter().collect())
+}
@@ -0,0 +1,114 @@
+use crate::constant::{CLOUD_HYDRATE_TIMEOUT_SECS, IMAGE_COMPRESSION_RATIO};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::services::{folder_album_service, problem_service};
+use crate::structs::config::sys_config;
+use crate::structs::directory_access::CloudHydrateSummary;
+use crate::structs::problem::ProblemKind;
+use crate::structs::thumbnail_encoding;
+use crate::utils::cloud_placeholder_util;
+use crate::utils::file_util;
+use crate::utils::img_util::ImageOperate;
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 是否跳过云盘占位文件
+pub fn skip_enabled() -> bool {
+    sys_config().skip_cloud_placeholders.unwrap_or(true)
+}
+
+/// 扫描时是否应跳过该文件【开启了跳过且文件为云盘占位文件；iCloud 占位文件没有图片内容，总是跳过】
+pub fn should_skip(path: &Path) -> bool {
+    cloud_placeholder_util::is_icloud_stub(path)
+        || skip_enabled()
+            && fs::metadata(file_util::long_path(path))
+                .is_ok_and(|x| cloud_placeholder_util::is_placeholder(path, &x))
+}
+
+/// 记录为仅云端文件
+pub fn record(path: &Path) {
+    let provider = cloud_placeholder_util::detect_provider(path);
+    problem_service::record_cloud_only(path, provider.as_ref().map(|x| x.as_str()));
+}
+
+/// 下载仅云端文件并导入
+///
+/// 读取整个文件触发云盘客户端下载，iCloud 占位文件通过 `brctl download` 下载；
+/// 下载完成后生成缩略图并导入，成功后删除仅云端记录
+/// - paths 文件路径【仅云端文件列表中的路径】
+pub async fn hydrate_files(paths: Vec<String>) -> Result<CloudHydrateSummary> {
+    let mut summary = CloudHydrateSummary::default();
+    for path in paths {
+        let source = PathBuf::from(&path);
+        let hydrated = tokio::task::spawn_blocking(move || hydrate(&source)).await?;
+        let file = match hydrated {
+            Ok(file) => file,
+            Err(e) => {
+                log::warn!("仅云端文件下载失败 {}: {}", path, e);
+                summary.failed.push(format!("{}: {}", path, e));
+                continue;
+            }
+        };
+        summary.hydrated += 1;
+
+        let result = ImageOperate::multi_level_image_compression(
+            &file,
+            thumbnail_encoding::storage_format(),
+            IMAGE_COMPRESSION_RATIO.to_vec(),
+        )
+        .await;
+        problem_service::resolve(Path::new(&path));
+        match result {
+            Ok(_) => summary.imported += 1,
+            Err(e) => {
+                problem_service::record(&file, ProblemKind::Decode, &e.to_string());
+                summary.failed.push(format!("{}: {}", path, e));
+            }
+        }
+    }
+    if summary.imported > 0 {
+        folder_album_service::request_sync();
+    }
+    log::info!("仅云端文件下载完成: {:?}", summary);
+    Ok(summary)
+}
+
+/// 下载文件，返回下载后的文件路径
+fn hydrate(path: &Path) -> Result<PathBuf> {
+    if let Some(target) = cloud_placeholder_util::icloud_stub_target(path) {
+        return hydrate_icloud_stub(&target);
+    }
+    let mut file = fs::File::open(file_util::long_path(path))?;
+    io::copy(&mut file, &mut io::sink())?;
+    let metadata = fs::metadata(file_util::long_path(path))?;
+    if cloud_placeholder_util::is_placeholder(path, &metadata) {
+        return Err(anyhow!(i18n::text(msg::CLOUD_FILE_NOT_DOWNLOADED)));
+    }
+    Ok(path.to_path_buf())
+}
+
+/// 通过 `brctl download` 下载 iCloud 占位文件，等待文件出现
+fn hydrate_icloud_stub(target: &Path) -> Result<PathBuf> {
+    let status = std::process::Command::new("brctl")
+        .arg("download")
+        .arg(target)
+        .status()?;
+    if !status.success() {
+        return Err(i18n::error(
+            msg::BRCTL_DOWNLOAD_FAILED,
+            &[("status", status.to_string())],
+        ));
+    }
+    let started = Instant::now();
+    while !target.is_file() {
+        if started.elapsed() > Duration::from_secs(CLOUD_HYDRATE_TIMEOUT_SECS) {
+            return Err(anyhow!(i18n::text(msg::CLOUD_DOWNLOAD_TIMEOUT)));
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+    Ok(target.to_path_buf())
+}
@@ -0,0 +1,77 @@
+use crate::constant::DOMINANT_COLOR_THUMBNAIL_SIZE;
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::config::sys_config;
+use crate::structs::dominant_color::DominantColor;
+use crate::structs::photo_search::PhotoSearchParams;
+use crate::structs::thumbnail_encoding;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::{color_util, file_util, image_format_util};
+use anyhow::{anyhow, Result};
+
+/// 按主色搜索照片
+/// - hex 颜色，如 `#1A8C9E`
+/// - tolerance 容差（CIE76 ΔE）
+/// - params 其他搜索条件
+pub fn search_by_color(
+    hex: &str,
+    tolerance: Option<f32>,
+    params: PhotoSearchParams,
+) -> Result<Vec<Photo>> {
+    let params = PhotoSearchParams {
+        color: Some(hex.to_string()),
+        color_tolerance: tolerance,
+        ..params
+    };
+    let mut conn = establish_connection();
+    storage::photo_search::search_photos(&mut conn, &params)
+}
+
+/// 获取照片主色
+pub fn get_photo_colors(photo_id: i32) -> Result<Vec<DominantColor>> {
+    let mut conn = establish_connection();
+    let photo = storage::photo_table::search_photos_by_ids(&mut conn, &[photo_id])?
+        .pop()
+        .ok_or_else(|| i18n::error(msg::PHOTO_NOT_FOUND, &[("id", photo_id.to_string())]))?;
+    storage::photo_color::get_colors(&mut conn, &photo.hash)
+}
+
+/// 为还没有主色的照片提取主色【读取已生成的缩略图，缩略图不存在的照片跳过】
+///
+/// 返回处理的照片数量
+pub fn index_photo_colors() -> Result<usize> {
+    let root_dir = sys_config()
+        .thumbnail_storage_path
+        .clone()
+        .ok_or_else(|| anyhow!(i18n::text(msg::THUMBNAIL_PATH_NOT_CONFIGURED)))?;
+    let suffix = image_format_util::get_suffix_name(thumbnail_encoding::storage_format());
+    let mut conn = establish_connection();
+    let hashes = storage::photo_color::get_hashes_without_colors(&mut conn)?;
+    let mut count = 0;
+    for hash in hashes {
+        let path = FileHashUtils::hash_to_file_path(
+            &hash,
+            &root_dir,
+            &suffix,
+            DOMINANT_COLOR_THUMBNAIL_SIZE,
+        );
+        if !file_util::file_exists(&path) {
+            continue;
+        }
+        let image = match image::open(file_util::long_path(&path)) {
+            Ok(x) => x,
+            Err(e) => {
+                log::warn!("缩略图读取失败 {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        let colors = color_util::dominant_colors(&image.to_rgb8());
+        storage::photo_color::save_colors(&mut conn, &hash, &colors)?;
+        count += 1;
+    }
+    log::info!("主色提取完成: {} 张照片", count);
+    Ok(count)
+}
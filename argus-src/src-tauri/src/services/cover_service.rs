@@ -0,0 +1,109 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::cover::Cover;
+use crate::utils::file_util;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// 设置文件夹封面
+/// - path 文件夹路径
+/// - photo_id 封面照片【必须在文件夹或其子文件夹中，为空时恢复为最新的照片】
+pub fn set_folder_cover(path: &str, photo_id: Option<i32>) -> Result<()> {
+    let folder = normalize_folder(path);
+    let mut conn = establish_connection();
+    let Some(photo_id) = photo_id else {
+        storage::cover::delete_folder_cover(&mut conn, &file_util::display_path(&folder))?;
+        return Ok(());
+    };
+    let photo = storage::photo_table::search_photos_by_ids(&mut conn, &[photo_id])?
+        .pop()
+        .ok_or_else(|| i18n::error(msg::PHOTO_NOT_FOUND, &[("id", photo_id.to_string())]))?;
+    if !Path::new(&photo.img_path).starts_with(&folder) {
+        return Err(i18n::error(
+            msg::COVER_NOT_IN_FOLDER,
+            &[("id", photo_id.to_string()), ("path", path.to_string())],
+        ));
+    }
+    storage::cover::save_folder_cover(&mut conn, &file_util::display_path(&folder), photo_id)
+}
+
+/// 获取文件夹封面
+///
+/// 没有设置封面，或封面照片已删除、已移出文件夹时，使用文件夹中最新的照片；
+/// 文件夹中没有照片时返回空
+/// - path 文件夹路径
+pub fn get_folder_cover(path: &str) -> Result<Option<Cover>> {
+    let folder = normalize_folder(path);
+    let mut conn = establish_connection();
+    if let Some(cover) =
+        storage::cover::get_folder_cover(&mut conn, &file_util::display_path(&folder))?
+    {
+        let photo = storage::photo_table::search_photos_by_ids(&mut conn, &[cover.photo_id])?.pop();
+        if let Some(photo) = photo.filter(|x| Path::new(&x.img_path).starts_with(&folder)) {
+            return Ok(Some(Cover {
+                photo,
+                is_custom: true,
+            }));
+        }
+    }
+    let photo = storage::cover::newest_folder_photo(&mut conn, &folder)?;
+    Ok(photo.map(|photo| Cover {
+        photo,
+        is_custom: false,
+    }))
+}
+
+/// 设置相册封面
+/// - album_id 相册 ID
+/// - photo_id 封面照片【必须在相册中，为空时恢复为最新的照片】
+pub fn set_album_cover(album_id: i32, photo_id: Option<i32>) -> Result<()> {
+    let mut conn = establish_connection();
+    if let Some(photo_id) = photo_id {
+        if !storage::cover::is_album_photo(&mut conn, album_id, photo_id)? {
+            return Err(i18n::error(
+                msg::COVER_NOT_IN_ALBUM,
+                &[
+                    ("id", photo_id.to_string()),
+                    ("album", album_id.to_string()),
+                ],
+            ));
+        }
+    }
+    storage::cover::set_album_cover(&mut conn, album_id, photo_id)
+}
+
+/// 获取相册封面【规则同 `get_folder_cover`，只使用相册本身的照片，不包含子相册】
+/// - album_id 相册 ID
+pub fn get_album_cover(album_id: i32) -> Result<Option<Cover>> {
+    let mut conn = establish_connection();
+    let album = storage::album::get_all_albums(&mut conn)?
+        .into_iter()
+        .find(|x| x.id == album_id)
+        .ok_or_else(|| i18n::error(msg::ALBUM_NOT_FOUND, &[("id", album_id.to_string())]))?;
+    if let Some(photo_id) = album.cover_photo_id {
+        if storage::cover::is_album_photo(&mut conn, album_id, photo_id)? {
+            if let Some(photo) =
+                storage::photo_table::search_photos_by_ids(&mut conn, &[photo_id])?.pop()
+            {
+                return Ok(Some(Cover {
+                    photo,
+                    is_custom: true,
+                }));
+            }
+        }
+    }
+    let photo = storage::cover::newest_album_photo(&mut conn, album_id)?;
+    Ok(photo.map(|photo| Cover {
+        photo,
+        is_custom: false,
+    }))
+}
+
+/// 统一文件夹路径的写法【去掉长路径前缀和末尾的分隔符】
+fn normalize_folder(path: &str) -> PathBuf {
+    file_util::strip_verbatim_prefix(path)
+        .components()
+        .collect()
+}
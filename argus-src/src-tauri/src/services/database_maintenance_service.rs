@@ -0,0 +1,68 @@
+use crate::constant::DATABASE_BUSY_TIMEOUT_MS;
+use crate::storage::connection::database_url;
+use crate::structs::database_optimize::DatabaseOptimizeReport;
+use crate::utils::volume_util;
+use anyhow::Result;
+use rusqlite::Connection;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// 优化数据库
+///
+/// 先执行 `PRAGMA optimize` 和 `ANALYZE` 更新查询计划使用的统计信息，
+/// 再用 VACUUM 回收删除记录后留下的空间。VACUUM 需要与数据库同样大小的临时空间，
+/// 磁盘剩余空间不足或无法查询时跳过
+pub fn optimize_database() -> Result<DatabaseOptimizeReport> {
+    let url = database_url();
+    let path = Path::new(&url);
+    let conn = Connection::open(path)?;
+    // 扫描等任务正在写入时等待锁释放，而不是直接失败
+    conn.busy_timeout(Duration::from_millis(DATABASE_BUSY_TIMEOUT_MS))?;
+
+    let page_size: u64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+    let free_pages: u64 = conn.query_row("PRAGMA freelist_count", [], |row| row.get(0))?;
+    let mut report = DatabaseOptimizeReport {
+        size_before: database_size(path),
+        reclaimable_bytes: page_size * free_pages,
+        ..Default::default()
+    };
+    log::info!(
+        "开始优化数据库，当前大小 {} 字节，预计可回收 {} 字节",
+        report.size_before,
+        report.reclaimable_bytes
+    );
+
+    let start = Instant::now();
+    conn.execute_batch("PRAGMA optimize; ANALYZE;")?;
+    report.analyze_millis = start.elapsed().as_millis() as u64;
+
+    let available = path.parent().and_then(volume_util::available_space);
+    if available.is_some_and(|x| x > report.size_before) {
+        let start = Instant::now();
+        conn.execute_batch("VACUUM;")?;
+        // 把 WAL 中的内容写回数据库文件，优化后的大小才准确
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        report.vacuum_millis = start.elapsed().as_millis() as u64;
+        report.vacuumed = true;
+    } else if let Some(available) = available {
+        log::warn!("磁盘剩余空间 {} 字节不足，跳过 VACUUM", available);
+    } else {
+        log::warn!("无法查询数据库所在磁盘的剩余空间，跳过 VACUUM");
+    }
+    drop(conn);
+    report.size_after = database_size(path);
+    log::info!("数据库优化完成: {:?}", report);
+    Ok(report)
+}
+
+/// 数据库文件和 WAL 文件的总大小
+fn database_size(path: &Path) -> u64 {
+    let mut wal = path.as_os_str().to_owned();
+    wal.push("-wal");
+    [path, Path::new(&wal)]
+        .iter()
+        .filter_map(|x| fs::metadata(x).ok())
+        .map(|x| x.len())
+        .sum()
+}
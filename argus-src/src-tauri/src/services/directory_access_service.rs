@@ -0,0 +1,151 @@
+use crate::constant::{
+    DIRECTORY_PREFLIGHT_FILES_PER_DIR, DIRECTORY_PREFLIGHT_MAX_DIRS,
+    DIRECTORY_PREFLIGHT_MAX_ISSUES, DIRECTORY_PREFLIGHT_SAMPLE_FILES,
+};
+use crate::structs::directory_access::{AccessIssue, AccessIssueKind, DirectoryAccessReport};
+use crate::utils::cloud_placeholder_util;
+use crate::utils::file_util;
+use crate::utils::scan_exclude_util::ScanExcludeRules;
+use std::collections::VecDeque;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// 扫描前检查文件夹的访问权限
+///
+/// 逐层检查子文件夹能否列出，并抽样读取图片：云盘占位文件只读取元数据，不会触发下载。
+/// 子文件夹、图片数量超过上限时只检查一部分（`truncated` 为 true）
+/// - path 文件夹
+pub fn preflight(path: &str) -> DirectoryAccessReport {
+    preflight_with(path, &ScanExcludeRules::from_config())
+}
+
+/// 使用指定的扫描排除规则检查文件夹【跳过被排除的文件和文件夹】
+fn preflight_with(path: &str, rules: &ScanExcludeRules) -> DirectoryAccessReport {
+    let root = PathBuf::from(path);
+    let mut report = DirectoryAccessReport {
+        path: path.to_string(),
+        cloud_provider: cloud_placeholder_util::detect_provider(&root),
+        ..Default::default()
+    };
+    let metadata = match fs::metadata(file_util::long_path(&root)) {
+        Ok(metadata) if metadata.is_dir() => metadata,
+        _ => return report,
+    };
+    report.exists = true;
+    report.writable = !metadata.permissions().readonly();
+
+    let mut dirs = VecDeque::from([root.clone()]);
+    while let Some(dir) = dirs.pop_front() {
+        if report.dirs_checked >= DIRECTORY_PREFLIGHT_MAX_DIRS
+            || report.files_checked >= DIRECTORY_PREFLIGHT_SAMPLE_FILES
+        {
+            report.truncated = true;
+            break;
+        }
+        report.dirs_checked += 1;
+        let entries = match fs::read_dir(file_util::long_path(&dir)) {
+            Ok(entries) => entries,
+            Err(e) => {
+                report.unreadable_dirs += 1;
+                push_issue(&mut report, AccessIssueKind::UnreadableDir, &dir, Some(e));
+                continue;
+            }
+        };
+        if dir == root {
+            report.readable = true;
+        }
+
+        let mut sampled = 0;
+        for entry in entries.flatten() {
+            let path = file_util::strip_verbatim_prefix(entry.path());
+            let is_dir = entry.file_type().is_ok_and(|x| x.is_dir());
+            if rules.is_excluded(&path, is_dir) {
+                continue;
+            }
+            if is_dir {
+                dirs.push_back(path);
+                continue;
+            }
+            let is_image = file_util::is_image_file(&path)
+                || cloud_placeholder_util::icloud_stub_target(&path)
+                    .is_some_and(file_util::is_image_file);
+            if !is_image
+                || sampled >= DIRECTORY_PREFLIGHT_FILES_PER_DIR
+                || report.files_checked >= DIRECTORY_PREFLIGHT_SAMPLE_FILES
+            {
+                continue;
+            }
+            sampled += 1;
+            report.files_checked += 1;
+            check_file(&mut report, &path);
+        }
+    }
+    report.can_scan = report.readable;
+    report
+}
+
+/// 检查图片能否读取【占位文件不打开】
+fn check_file(report: &mut DirectoryAccessReport, path: &Path) {
+    let metadata = match fs::metadata(file_util::long_path(path)) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            report.unreadable_files += 1;
+            push_issue(report, AccessIssueKind::UnreadableFile, path, Some(e));
+            return;
+        }
+    };
+    if cloud_placeholder_util::is_placeholder(path, &metadata) {
+        report.cloud_placeholders += 1;
+        push_issue(report, AccessIssueKind::CloudPlaceholder, path, None);
+        return;
+    }
+    let mut buf = [0u8; 1];
+    if let Err(e) = fs::File::open(file_util::long_path(path)).and_then(|mut x| x.read(&mut buf)) {
+        report.unreadable_files += 1;
+        push_issue(report, AccessIssueKind::UnreadableFile, path, Some(e));
+    }
+}
+
+/// 记录问题示例【超过上限时只计数】
+fn push_issue(
+    report: &mut DirectoryAccessReport,
+    kind: AccessIssueKind,
+    path: &Path,
+    error: Option<std::io::Error>,
+) {
+    if report.issues.len() < DIRECTORY_PREFLIGHT_MAX_ISSUES {
+        report.issues.push(AccessIssue {
+            kind,
+            path: file_util::display_path(path),
+            error: error.map(|e| e.to_string()),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preflight_missing_directory() {
+        let report = preflight_with("/path/does/not/exist", &ScanExcludeRules::new(&[]));
+        assert!(!report.exists);
+        assert!(!report.can_scan);
+    }
+
+    #[test]
+    fn test_preflight_readable_directory() {
+        let dir = std::env::temp_dir().join(format!("argus_preflight_{}", std::process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub").join("a.jpg"), b"not really a jpeg").unwrap();
+
+        let report = preflight_with(&dir.to_string_lossy(), &ScanExcludeRules::new(&[]));
+        assert!(report.can_scan);
+        assert_eq!(report.dirs_checked, 2);
+        assert_eq!(report.files_checked, 1);
+        assert_eq!(report.unreadable_files, 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
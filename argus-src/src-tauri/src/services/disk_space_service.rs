@@ -0,0 +1,253 @@
+use crate::constant::{DISK_SPACE_CHECK_INTERVAL_SECS, LOW_DISK_SPACE_EMIT_INTERVAL_SECS};
+use crate::global_front_emit;
+use crate::i18n;
+use crate::storage;
+use crate::storage::connection::{database_url, establish_connection};
+use crate::structs::config::sys_config;
+use crate::structs::disk_space::{
+    DiskSpacePurpose, LowDiskSpaceMsg, StorageBreakdown, VolumeStorageUsage,
+};
+use crate::utils::volume_util::{self, VolumeInfo};
+use anyhow::Result;
+use once_cell::sync::{Lazy, OnceCell};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// 用于发送 `low-disk-space` 事件
+static APP_HANDLE: OnceCell<AppHandle> = OnceCell::new();
+
+/// 最近一次查询到的剩余空间【目标路径 -> (查询时间, 剩余空间)】
+static AVAILABLE_CACHE: Lazy<Mutex<HashMap<PathBuf, (Instant, Option<u64>)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 最近一次发送提醒的时间
+static LAST_EMITTED: Lazy<Mutex<HashMap<(DiskSpacePurpose, PathBuf), Instant>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 注册事件发送使用的 AppHandle【程序启动时调用】
+pub fn init(app: AppHandle) {
+    let _ = APP_HANDLE.set(app);
+}
+
+/// 写入前检查目标所在磁盘的剩余空间
+///
+/// 剩余空间减去本次写入大小后低于配置的最小值时发送 `low-disk-space` 事件，
+/// 开启了拒绝时返回错误，否则只提醒
+/// - target 写入的目标目录
+/// - purpose 写入用途
+/// - required 本次需要写入的大小（字节）【无法预估时为 0，此时使用缓存的查询结果】
+pub fn ensure_free_space(target: &Path, purpose: DiskSpacePurpose, required: u64) -> Result<()> {
+    let config = sys_config();
+    let min_free = config.min_free_space_mb.unwrap_or_default() * 1024 * 1024;
+    if min_free == 0 {
+        return Ok(());
+    }
+    // 找不到所在磁盘时不检查
+    let Some(available) = available_space(target, required == 0) else {
+        return Ok(());
+    };
+    if !is_low(available, required, min_free) {
+        return Ok(());
+    }
+
+    let msg = LowDiskSpaceMsg {
+        purpose,
+        path: target.display().to_string(),
+        available,
+        required,
+        min_free,
+        refused: config.low_disk_space_refuse.unwrap_or(true),
+    };
+    log::warn!("磁盘剩余空间不足: {:?}", msg);
+    emit_low_disk_space(target, &msg);
+    if msg.refused {
+        return Err(i18n::error(
+            i18n::msg::DISK_SPACE_INSUFFICIENT,
+            &[
+                ("path", msg.path.to_string()),
+                ("available", (available / 1024 / 1024).to_string()),
+                (
+                    "required",
+                    ((required + min_free) / 1024 / 1024).to_string(),
+                ),
+            ],
+        ));
+    }
+    Ok(())
+}
+
+/// 写入后剩余空间是否低于最小值
+fn is_low(available: u64, required: u64, min_free: u64) -> bool {
+    available < required.saturating_add(min_free)
+}
+
+/// 查询目标所在磁盘的剩余空间
+/// - cached 是否使用缓存的查询结果
+fn available_space(target: &Path, cached: bool) -> Option<u64> {
+    let mut cache = AVAILABLE_CACHE.lock().unwrap();
+    if cached {
+        if let Some((time, available)) = cache.get(target) {
+            if time.elapsed() < Duration::from_secs(DISK_SPACE_CHECK_INTERVAL_SECS) {
+                return *available;
+            }
+        }
+    }
+    let available = volume_util::available_space(target);
+    cache.insert(target.to_path_buf(), (Instant::now(), available));
+    available
+}
+
+/// 发送剩余空间不足事件【同一路径、同一用途限制发送频率】
+fn emit_low_disk_space(target: &Path, msg: &LowDiskSpaceMsg) {
+    let Some(app) = APP_HANDLE.get() else {
+        return;
+    };
+    {
+        let mut last = LAST_EMITTED.lock().unwrap();
+        let key = (msg.purpose, target.to_path_buf());
+        if let Some(time) = last.get(&key) {
+            if time.elapsed() < Duration::from_secs(LOW_DISK_SPACE_EMIT_INTERVAL_SECS) {
+                return;
+            }
+        }
+        last.insert(key, Instant::now());
+    }
+    if let Err(e) = app.emit(global_front_emit::LOW_DISK_SPACE, msg.clone()) {
+        log::warn!("磁盘空间不足提醒发送失败: {}", e);
+    }
+}
+
+/// 统计各卷上原图、缩略图缓存、数据库的空间占用
+pub fn get_storage_breakdown() -> Result<StorageBreakdown> {
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::search_all_path_sizes(&mut conn)?;
+    drop(conn);
+
+    let volumes = volume_util::list_volumes();
+    let mut usages: HashMap<Option<String>, VolumeStorageUsage> = HashMap::new();
+
+    for (path, size) in photos {
+        let usage = usage_for(&mut usages, volume_of(&volumes, Path::new(&path)));
+        usage.originals_count += 1;
+        usage.originals_bytes += size.max(0) as u64;
+    }
+
+    if let Some(cache_root) = sys_config().thumbnail_storage_path.clone() {
+        let cache_root = PathBuf::from(cache_root);
+        let cache_bytes = dir_size(&cache_root);
+        usage_for(&mut usages, volume_of(&volumes, &cache_root)).cache_bytes += cache_bytes;
+    }
+
+    let database = PathBuf::from(database_url());
+    let database_bytes = database_size(&database);
+    usage_for(&mut usages, volume_of(&volumes, &database)).database_bytes += database_bytes;
+
+    let mut breakdown = StorageBreakdown {
+        volumes: usages.into_values().collect(),
+        ..Default::default()
+    };
+    for usage in breakdown.volumes.iter_mut() {
+        if let Some(space) = usage
+            .mount_point
+            .as_deref()
+            .and_then(volume_util::disk_space)
+        {
+            usage.total_space = space.total;
+            usage.available_space = space.available;
+        }
+        breakdown.originals_bytes += usage.originals_bytes;
+        breakdown.cache_bytes += usage.cache_bytes;
+        breakdown.database_bytes += usage.database_bytes;
+    }
+    // 离线的卷排在最后
+    breakdown.volumes.sort_by(|a, b| {
+        (a.volume_id.is_none(), &a.mount_point).cmp(&(b.volume_id.is_none(), &b.mount_point))
+    });
+    Ok(breakdown)
+}
+
+/// 路径所在的卷【按挂载点最长匹配】
+fn volume_of<'a>(volumes: &'a [VolumeInfo], path: &Path) -> Option<&'a VolumeInfo> {
+    volumes
+        .iter()
+        .filter(|v| path.starts_with(&v.mount_point))
+        .max_by_key(|v| v.mount_point.as_os_str().len())
+}
+
+/// 获取或创建卷的统计项【找不到所在卷时归入离线】
+fn usage_for<'a>(
+    usages: &'a mut HashMap<Option<String>, VolumeStorageUsage>,
+    volume: Option<&VolumeInfo>,
+) -> &'a mut VolumeStorageUsage {
+    let key = volume.map(|v| v.volume_id.clone());
+    usages.entry(key).or_insert_with(|| match volume {
+        Some(v) => VolumeStorageUsage {
+            volume_id: Some(v.volume_id.clone()),
+            mount_point: Some(v.mount_point.display().to_string()),
+            label: v.label.clone(),
+            ..Default::default()
+        },
+        None => VolumeStorageUsage::default(),
+    })
+}
+
+/// 文件夹中所有文件的大小之和
+fn dir_size(dir: &Path) -> u64 {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|x| x.is_file())
+        .map(|x| x.len())
+        .sum()
+}
+
+/// 数据库文件及 WAL、共享内存文件的总大小
+fn database_size(path: &Path) -> u64 {
+    ["", "-wal", "-shm"]
+        .iter()
+        .map(|suffix| {
+            let mut file = path.as_os_str().to_owned();
+            file.push(suffix);
+            PathBuf::from(file)
+        })
+        .filter_map(|x| fs::metadata(x).ok())
+        .map(|x| x.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_low() {
+        let mb = 1024 * 1024;
+        assert!(!is_low(2048 * mb, 0, 1024 * mb));
+        assert!(is_low(1000 * mb, 0, 1024 * mb));
+        // 写入后低于最小值
+        assert!(is_low(2048 * mb, 1500 * mb, 1024 * mb));
+        assert!(!is_low(u64::MAX, u64::MAX, 1));
+    }
+
+    #[test]
+    fn test_volume_of() {
+        let volume = |id: &str, mount: &str| VolumeInfo {
+            volume_id: id.to_string(),
+            mount_point: PathBuf::from(mount),
+            label: id.to_string(),
+            is_removable: false,
+            is_network: false,
+        };
+        let volumes = vec![volume("root", "/"), volume("data", "/mnt/data")];
+        let found = volume_of(&volumes, Path::new("/mnt/data/photos/a.jpg"));
+        assert_eq!(found.map(|v| v.volume_id.as_str()), Some("data"));
+        let found = volume_of(&volumes, Path::new("/home/a.jpg"));
+        assert_eq!(found.map(|v| v.volume_id.as_str()), Some("root"));
+        assert!(volume_of(&volumes[1..], Path::new("/home/a.jpg")).is_none());
+    }
+}
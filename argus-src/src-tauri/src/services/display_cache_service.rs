@@ -0,0 +1,152 @@
+use crate::constant::{
+    DISPLAY_CACHE_MAX_BYTES, DISPLAY_IMAGE_MAX_SIZE, DISPLAY_PREFETCH_LIMIT,
+    DISPLAY_PREFETCH_THREADS, EXPORT_DEFAULT_JPEG_QUALITY,
+};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::utils::img_util::ImageOperate;
+use crate::utils::lru_cache::LruCache;
+use crate::utils::resize_util;
+use anyhow::Result;
+use image::codecs::jpeg::JpegEncoder;
+use image::DynamicImage;
+use once_cell::sync::Lazy;
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+
+/// 缓存键【文件 Hash 和显示尺寸，照片被修改后 Hash 变化，不会读到旧图】
+type DisplayKey = (String, u32);
+
+/// 已解码并缩放到显示尺寸的大图【JPEG 编码后保存，返回前端时无需再处理】
+static DISPLAY_CACHE: Lazy<Mutex<LruCache<DisplayKey, Arc<Vec<u8>>>>> =
+    Lazy::new(|| Mutex::new(LruCache::new(DISPLAY_CACHE_MAX_BYTES)));
+
+/// 等待预读的照片和正在解码的照片
+#[derive(Default)]
+struct PrefetchQueue {
+    pending: VecDeque<(DisplayKey, PathBuf)>,
+    decoding: HashSet<DisplayKey>,
+}
+
+static PREFETCH_QUEUE: Lazy<(Mutex<PrefetchQueue>, Condvar)> = Lazy::new(|| {
+    for index in 0..DISPLAY_PREFETCH_THREADS {
+        let spawned = std::thread::Builder::new()
+            .name(format!("argus-prefetch-{}", index))
+            .spawn(prefetch_worker);
+        if let Err(e) = spawned {
+            log::error!("大图预读线程启动失败: {}", e);
+        }
+    }
+    (Mutex::new(PrefetchQueue::default()), Condvar::new())
+});
+
+/// 获取缩放到显示尺寸的大图（JPEG）
+///
+/// 已缓存时直接返回，否则解码原图并放入缓存
+/// - photo_id 照片 ID
+/// - max_size 长边像素【为空时使用默认显示尺寸】
+pub fn get_display_image(photo_id: i32, max_size: Option<u32>) -> Result<Arc<Vec<u8>>> {
+    let (key, path) = resolve(photo_id, max_size)?;
+    if let Some(data) = DISPLAY_CACHE.lock().unwrap().get(&key) {
+        return Ok(data.clone());
+    }
+    decode_and_cache(key, &path)
+}
+
+/// 在后台预读照片，返回加入队列的数量
+///
+/// 新的请求会替换还没开始解码的旧请求【用户已经滚动到其他位置】，
+/// 已缓存或正在解码的照片不重复处理
+/// - photo_ids 照片 ID【按预读顺序，超出上限的部分忽略】
+/// - max_size 长边像素【为空时使用默认显示尺寸】
+pub fn prefetch_photos(photo_ids: &[i32], max_size: Option<u32>) -> Result<usize> {
+    let ids = &photo_ids[..photo_ids.len().min(DISPLAY_PREFETCH_LIMIT)];
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::search_photos_by_ids(&mut conn, ids)?;
+    let size = display_size(max_size);
+
+    let mut pending = VecDeque::new();
+    {
+        let cache = DISPLAY_CACHE.lock().unwrap();
+        for id in ids {
+            let Some(photo) = photos.iter().find(|x| x.id == *id) else {
+                continue;
+            };
+            let key = (photo.hash.clone(), size);
+            if !cache.contains(&key) {
+                pending.push_back((key, photo.full_path()));
+            }
+        }
+    }
+
+    let (queue, condvar) = &*PREFETCH_QUEUE;
+    let mut queue = queue.lock().unwrap();
+    pending.retain(|(key, _)| !queue.decoding.contains(key));
+    let count = pending.len();
+    queue.pending = pending;
+    condvar.notify_all();
+    Ok(count)
+}
+
+/// 预读线程【依次取出队列中的照片解码】
+fn prefetch_worker() {
+    let (queue, condvar) = &*PREFETCH_QUEUE;
+    loop {
+        let (key, path) = {
+            let mut guard = queue.lock().unwrap();
+            loop {
+                if let Some(item) = guard.pending.pop_front() {
+                    guard.decoding.insert(item.0.clone());
+                    break item;
+                }
+                guard = condvar.wait(guard).unwrap();
+            }
+        };
+        if let Err(e) = decode_and_cache(key.clone(), &path) {
+            log::warn!("大图预读失败 {}: {}", path.display(), e);
+        }
+        queue.lock().unwrap().decoding.remove(&key);
+    }
+}
+
+/// 查询照片路径，生成缓存键
+fn resolve(photo_id: i32, max_size: Option<u32>) -> Result<(DisplayKey, PathBuf)> {
+    let mut conn = establish_connection();
+    let photo = storage::photo_table::search_photos_by_ids(&mut conn, &[photo_id])?
+        .pop()
+        .ok_or_else(|| i18n::error(msg::PHOTO_NOT_FOUND, &[("id", photo_id.to_string())]))?;
+    Ok((
+        (photo.hash.clone(), display_size(max_size)),
+        photo.full_path(),
+    ))
+}
+
+fn display_size(max_size: Option<u32>) -> u32 {
+    max_size
+        .filter(|x| *x > 0)
+        .unwrap_or(DISPLAY_IMAGE_MAX_SIZE)
+}
+
+/// 解码原图，缩放到显示尺寸后编码为 JPEG 并放入缓存【原图小于显示尺寸时不放大】
+fn decode_and_cache(key: DisplayKey, path: &Path) -> Result<Arc<Vec<u8>>> {
+    let size = key.1;
+    let mut image = ImageOperate::open_oriented(path)?;
+    if image.width() > size || image.height() > size {
+        image = resize_util::resize(&image, size, size, image::imageops::FilterType::Triangle);
+    }
+    let mut data = Vec::new();
+    DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(JpegEncoder::new_with_quality(
+        &mut data,
+        EXPORT_DEFAULT_JPEG_QUALITY,
+    ))?;
+    let data = Arc::new(data);
+    let weight = data.len();
+    DISPLAY_CACHE
+        .lock()
+        .unwrap()
+        .insert(key, data.clone(), weight);
+    Ok(data)
+}
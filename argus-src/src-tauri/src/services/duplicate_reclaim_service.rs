@@ -0,0 +1,346 @@
+use crate::constant::{BACKUP_TIME_FMT, RECLAIM_MANIFEST_FOLDER, RECLAIM_MANIFEST_PREFIX};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::duplicate_reclaim::{
+    DuplicateGroup, ReclaimEntry, ReclaimManifest, ReclaimMode, ReclaimReport, UndoReclaimReport,
+};
+use crate::structs::library::current_library;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::file_util;
+use crate::utils::json_util::JsonUtil;
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use same_file::Handle;
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 查找内容完全相同的图片文件
+///
+/// 先按文件大小分组，只对大小相同的文件计算 SHA-256。
+/// 数据库中同一个 Hash 只记录一张照片，其余副本不会出现在照片库中，所以需要直接遍历文件夹
+/// - path 只查找此文件夹【为空表示所有在线的存储路径】
+pub fn find_exact_duplicates(path: Option<&str>) -> Result<Vec<DuplicateGroup>> {
+    let mut conn = establish_connection();
+    let roots: Vec<PathBuf> = match path.filter(|x| !x.is_empty()) {
+        Some(x) => vec![PathBuf::from(x)],
+        None => storage::photo_storage::get_all_photo_path(&mut conn)?
+            .into_iter()
+            .filter(|x| x.is_available)
+            .map(|x| PathBuf::from(x.img_paths))
+            .collect(),
+    };
+
+    // 存储路径可能互相包含，用集合去重
+    let mut by_size: HashMap<u64, BTreeSet<PathBuf>> = HashMap::new();
+    for root in &roots {
+        file_util::walk_dir_img_parallel(root, |path| {
+            match fs::metadata(&path) {
+                Ok(metadata) if metadata.len() > 0 => {
+                    by_size
+                        .entry(metadata.len())
+                        .or_default()
+                        .insert(file_util::strip_verbatim_prefix(&path));
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("文件读取失败 {}: {}", path.display(), e),
+            }
+            true
+        });
+    }
+
+    let mut groups = Vec::new();
+    for (size, paths) in by_size.into_iter().filter(|(_, x)| x.len() > 1) {
+        let mut by_hash: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        for path in paths {
+            match FileHashUtils::sha256(&path) {
+                Ok(hash) => by_hash.entry(hash).or_default().push(path),
+                Err(e) => log::warn!("文件 Hash 计算失败 {}: {}", path.display(), e),
+            }
+        }
+        for (hash, mut files) in by_hash.into_iter().filter(|(_, x)| x.len() > 1) {
+            // 数据库中记录的照片作为保留的文件
+            let recorded = storage::photo_table::search_photo_by_hash(&mut conn, hash.clone())
+                .ok()
+                .and_then(|x| x.first().map(|photo| photo.full_path()));
+            if let Some(index) = recorded.and_then(|x| files.iter().position(|f| *f == x)) {
+                let item = files.remove(index);
+                files.insert(0, item);
+            }
+            groups.push(DuplicateGroup {
+                reclaimable_bytes: size * distinct_count(&files).saturating_sub(1) as u64,
+                files: files.iter().map(file_util::display_path).collect(),
+                hash,
+                size,
+            });
+        }
+    }
+    groups.sort_by(|a, b| {
+        b.reclaimable_bytes
+            .cmp(&a.reclaimable_bytes)
+            .then_with(|| a.files.cmp(&b.files))
+    });
+    log::info!("找到 {} 组重复文件", groups.len());
+    Ok(groups)
+}
+
+/// 把重复文件替换为指向保留文件的链接，释放磁盘空间
+///
+/// 每个文件替换前都会重新校验内容，先在同一文件夹中创建链接再重命名覆盖，
+/// 任何一步失败都保持原文件不变。每处理完一组就保存撤销清单，中途出错也可以撤销
+/// - groups 重复文件分组【每组第一个为保留的文件】
+/// - mode 替换方式
+pub fn reclaim_duplicates(groups: &[Vec<String>], mode: ReclaimMode) -> Result<ReclaimReport> {
+    let dir = current_library().root.join(RECLAIM_MANIFEST_FOLDER);
+    fs::create_dir_all(&dir)?;
+    let manifest_path = file_util::unique_path(&dir.join(format!(
+        "{}{}.json",
+        RECLAIM_MANIFEST_PREFIX,
+        TimeUtils::current_datetime_string(Some(BACKUP_TIME_FMT))
+    )));
+    let mut manifest = ReclaimManifest {
+        create_time: TimeUtils::current_timestamp(),
+        entries: Vec::new(),
+    };
+
+    let mut report = ReclaimReport::default();
+    for group in groups {
+        let Some((original, duplicates)) = group.split_first() else {
+            continue;
+        };
+        let hash = match FileHashUtils::sha256(original) {
+            Ok(x) => x,
+            Err(e) => {
+                for duplicate in duplicates {
+                    report
+                        .failed
+                        .push(format!("{}: 保留的文件读取失败: {}", duplicate, e));
+                }
+                continue;
+            }
+        };
+        let count = manifest.entries.len();
+        for duplicate in duplicates {
+            match replace_with_link(Path::new(original), Path::new(duplicate), &hash, mode) {
+                Ok(Some(entry)) => {
+                    report.linked += 1;
+                    report.reclaimed_bytes += entry.size;
+                    manifest.entries.push(entry);
+                }
+                Ok(None) => report.skipped += 1,
+                Err(e) => {
+                    log::warn!("重复文件替换失败 {}: {}", duplicate, e);
+                    report.failed.push(format!("{}: {}", duplicate, e));
+                }
+            }
+        }
+        if manifest.entries.len() > count {
+            fs::write(&manifest_path, JsonUtil::stringify(&manifest)?)?;
+        }
+    }
+    if !manifest.entries.is_empty() {
+        report.manifest_path = Some(file_util::display_path(&manifest_path));
+    }
+    log::info!("重复文件替换完成: {:?}", report);
+    Ok(report)
+}
+
+/// 按撤销清单把链接恢复为独立的文件
+///
+/// 从被替换的文件本身复制内容，保留的文件已被删除或移动时也能恢复。
+/// 全部恢复后删除清单，否则清单中只保留失败的文件
+/// - manifest_path 撤销清单路径
+pub fn undo_reclaim(manifest_path: &Path) -> Result<UndoReclaimReport> {
+    let content = fs::read_to_string(manifest_path).map_err(|e| {
+        i18n::error(
+            msg::DUPLICATE_MANIFEST_READ_FAILED,
+            &[
+                ("path", manifest_path.display().to_string()),
+                ("error", e.to_string()),
+            ],
+        )
+    })?;
+    let mut manifest: ReclaimManifest = JsonUtil::from_json(&content)?;
+
+    let mut report = UndoReclaimReport::default();
+    let mut remaining = Vec::new();
+    for entry in manifest.entries {
+        match restore_entry(&entry) {
+            Ok(()) => report.restored += 1,
+            Err(e) => {
+                log::warn!("重复文件恢复失败 {}: {}", entry.duplicate, e);
+                report.failed.push(format!("{}: {}", entry.duplicate, e));
+                remaining.push(entry);
+            }
+        }
+    }
+    if remaining.is_empty() {
+        fs::remove_file(manifest_path)?;
+    } else {
+        manifest.entries = remaining;
+        fs::write(manifest_path, JsonUtil::stringify(&manifest)?)?;
+    }
+    log::info!("重复文件恢复完成: {:?}", report);
+    Ok(report)
+}
+
+/// 当前照片库中还可以撤销的清单【按时间从新到旧】
+pub fn list_reclaim_manifests() -> Vec<String> {
+    let dir = current_library().root.join(RECLAIM_MANIFEST_FOLDER);
+    let mut manifests: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|x| {
+                    x.is_file()
+                        && x.extension().is_some_and(|ext| ext == "json")
+                        && x.file_name().is_some_and(|n| {
+                            n.to_string_lossy().starts_with(RECLAIM_MANIFEST_PREFIX)
+                        })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    manifests.sort();
+    manifests.reverse();
+    manifests.iter().map(file_util::display_path).collect()
+}
+
+/// 替换单个重复文件【已经是同一个文件时返回 None】
+fn replace_with_link(
+    original: &Path,
+    duplicate: &Path,
+    hash: &str,
+    mode: ReclaimMode,
+) -> Result<Option<ReclaimEntry>> {
+    let (original_long, duplicate_long) = (
+        file_util::long_path(original),
+        file_util::long_path(duplicate),
+    );
+    if same_file::is_same_file(&original_long, &duplicate_long)? {
+        return Ok(None);
+    }
+    let metadata = fs::metadata(&duplicate_long)?;
+    if metadata.len() != fs::metadata(&original_long)?.len()
+        || FileHashUtils::sha256(duplicate)? != hash
+    {
+        return Err(anyhow!(i18n::text(msg::DUPLICATE_CONTENT_MISMATCH)));
+    }
+    let modified_time = metadata
+        .modified()
+        .ok()
+        .and_then(|x| x.duration_since(UNIX_EPOCH).ok())
+        .map(|x| x.as_millis() as i64);
+
+    let temp = file_util::long_path(temp_path(duplicate));
+    let _ = fs::remove_file(&temp);
+    let linked = match mode {
+        ReclaimMode::HardLink => fs::hard_link(&original_long, &temp)
+            .map_err(|e| i18n::error(msg::DUPLICATE_HARD_LINK_FAILED, &[("error", e.to_string())])),
+        // reflink 是独立的文件，可以保留原来的修改时间
+        ReclaimMode::Reflink => file_util::reflink_file(&original_long, &temp)
+            .and_then(|_| set_modified_time(&temp, modified_time)),
+    };
+    if let Err(e) = linked.and_then(|_| Ok(fs::rename(&temp, &duplicate_long)?)) {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+    Ok(Some(ReclaimEntry {
+        original: file_util::display_path(original),
+        duplicate: file_util::display_path(duplicate),
+        mode,
+        size: metadata.len(),
+        modified_time,
+    }))
+}
+
+/// 把链接恢复为独立的文件
+fn restore_entry(entry: &ReclaimEntry) -> Result<()> {
+    let duplicate = file_util::long_path(&entry.duplicate);
+    if !duplicate.is_file() {
+        return Err(anyhow!(i18n::text(msg::FILE_DOES_NOT_EXIST)));
+    }
+    let temp = file_util::long_path(temp_path(Path::new(&entry.duplicate)));
+    let _ = fs::remove_file(&temp);
+    let result = fs::copy(&duplicate, &temp)
+        .map_err(anyhow::Error::from)
+        .and_then(|_| set_modified_time(&temp, entry.modified_time))
+        .and_then(|_| Ok(fs::rename(&temp, &duplicate)?));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// 设置文件修改时间（毫秒时间戳）
+fn set_modified_time(path: &Path, millis: Option<i64>) -> Result<()> {
+    if let Some(millis) = millis.and_then(|x| u64::try_from(x).ok()) {
+        let time: SystemTime = UNIX_EPOCH + Duration::from_millis(millis);
+        File::options().write(true).open(path)?.set_modified(time)?;
+    }
+    Ok(())
+}
+
+/// 与目标文件同目录的临时文件【保证可以直接重命名覆盖】
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.argus-link", name))
+}
+
+/// 不同文件的数量【互为硬链接的算作一个】
+fn distinct_count(files: &[PathBuf]) -> usize {
+    let mut handles: Vec<Handle> = Vec::new();
+    for file in files {
+        if let Ok(handle) = Handle::from_path(file_util::long_path(file)) {
+            if !handles.contains(&handle) {
+                handles.push(handle);
+            }
+        }
+    }
+    handles.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_with_hard_link() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("IMG_0001.JPG");
+        let duplicate = dir.path().join("IMG_0001 (1).JPG");
+        fs::write(&original, b"jpeg").unwrap();
+        fs::write(&duplicate, b"jpeg").unwrap();
+        let files = vec![original.clone(), duplicate.clone()];
+        assert_eq!(distinct_count(&files), 2);
+
+        let hash = FileHashUtils::sha256(&original).unwrap();
+        let entry = replace_with_link(&original, &duplicate, &hash, ReclaimMode::HardLink)
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.size, 4);
+        assert_eq!(distinct_count(&files), 1);
+        // 已经是同一个文件时跳过
+        assert!(
+            replace_with_link(&original, &duplicate, &hash, ReclaimMode::HardLink)
+                .unwrap()
+                .is_none()
+        );
+
+        restore_entry(&entry).unwrap();
+        assert_eq!(distinct_count(&files), 2);
+        assert_eq!(fs::read(&duplicate).unwrap(), b"jpeg");
+
+        // 内容不同的文件不会被替换
+        fs::write(&duplicate, b"edited").unwrap();
+        assert!(replace_with_link(&original, &duplicate, &hash, ReclaimMode::HardLink).is_err());
+        assert_eq!(fs::read(&duplicate).unwrap(), b"edited");
+    }
+}
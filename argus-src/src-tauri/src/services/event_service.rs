@@ -0,0 +1,184 @@
+use crate::models::event::{Event, NewEvent};
+use crate::models::photo::Photo;
+use crate::services::place_service::most_common;
+use crate::services::{geocode_service, privacy_service};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::config::sys_config;
+use crate::structs::event::EventRefreshSummary;
+use crate::utils::event_segment_util::{self, EventPoint};
+use crate::utils::exif_utils::gps_util::GpsCoordinate;
+use crate::utils::geo_cluster_util;
+use crate::utils::json_util::JsonUtil;
+use crate::utils::time_util::TimeUtils;
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// 分段得到的事件
+struct EventSegment {
+    start_time: i64,
+    end_time: i64,
+    /// 第一张和最后一张照片的当地日期
+    start_date: String,
+    end_date: String,
+    photo_ids: Vec<i32>,
+    /// 照片位置的中心点【都没有位置时为空】
+    center: Option<GpsCoordinate>,
+    /// 照片中出现次数最多的地点名称
+    location_name: Option<String>,
+}
+
+/// 重新分段事件
+///
+/// 按拍摄时间间隔和位置变化把照片分为事件，然后依次使用照片中的地点名称、
+/// 上一次分段时同一开始时间的事件的地点名称、逆地理编码结果（先查询缓存，设置了服务地址时再请求服务）
+/// 作为地点，与日期一起作为事件名称
+pub async fn refresh_events() -> Result<EventRefreshSummary> {
+    let (segments, total) = tokio::task::spawn_blocking(segment_photos).await??;
+    let mut conn = establish_connection();
+    let previous: HashMap<i64, String> = storage::event::list_events(&mut conn)?
+        .into_iter()
+        .filter_map(|x| Some((x.start_time, x.place_name?)))
+        .collect();
+    let url = sys_config().reverse_geocode_url.clone().unwrap_or_default();
+
+    let mut summary = EventRefreshSummary {
+        events: segments.len(),
+        ..Default::default()
+    };
+    let now = TimeUtils::current_timestamp();
+    let mut items = Vec::with_capacity(segments.len());
+    for segment in segments {
+        let mut place_name = segment
+            .location_name
+            .clone()
+            .or_else(|| previous.get(&segment.start_time).cloned());
+        // 隐私区域内的事件不请求逆地理编码，只使用照片中已有的地点名称
+        let center = segment
+            .center
+            .as_ref()
+            .filter(|x| !privacy_service::in_privacy_zone(x));
+        if let (None, Some(center)) = (&place_name, center) {
+            match geocode_service::reverse_geocode(&url, center).await {
+                Ok(Some(x)) => {
+                    summary.geocoded += 1;
+                    place_name = Some(x);
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("事件 {} 逆地理编码失败: {}", center, e),
+            }
+        }
+        summary.photos += segment.photo_ids.len();
+        let event = NewEvent {
+            name: event_name(
+                place_name.as_deref(),
+                &segment.start_date,
+                &segment.end_date,
+            ),
+            place_name,
+            start_time: segment.start_time,
+            end_time: segment.end_time,
+            photo_count: segment.photo_ids.len() as i32,
+            // 使用中间的照片作为封面
+            cover_photo_id: segment.photo_ids[segment.photo_ids.len() / 2],
+            create_time: now,
+            update_time: now,
+        };
+        items.push((event, segment.photo_ids));
+    }
+    summary.ungrouped = total - summary.photos;
+    storage::event::replace_events(&mut conn, items)?;
+    log::info!("事件分段完成: {:?}", summary);
+    Ok(summary)
+}
+
+/// 获取所有事件【按拍摄时间从新到旧】
+pub fn list_events() -> Result<Vec<Event>> {
+    let mut conn = establish_connection();
+    storage::event::list_events(&mut conn)
+}
+
+/// 获取事件中的照片【按拍摄时间排序】
+pub fn get_event_photos(event_id: i32) -> Result<Vec<Photo>> {
+    let mut conn = establish_connection();
+    storage::event::get_event(&mut conn, event_id)?;
+    storage::event::get_event_photos(&mut conn, event_id)
+}
+
+/// 按拍摄时间和位置分段，同时返回参与分段的照片数量
+fn segment_photos() -> Result<(Vec<EventSegment>, usize)> {
+    let mut conn = establish_connection();
+    let candidates = storage::event::get_event_candidates(&mut conn)?;
+    let points: Vec<EventPoint> = candidates
+        .iter()
+        .map(|(id, time, _, gps_info, _)| EventPoint {
+            time: *time,
+            coordinate: gps_info.as_deref().and_then(|x| {
+                JsonUtil::from_json::<GpsCoordinate>(x)
+                    .map_err(|e| log::warn!("照片 {} 的位置解析失败: {}", id, e))
+                    .ok()
+            }),
+        })
+        .collect();
+
+    let segments = event_segment_util::segment_events(&points)
+        .into_iter()
+        .map(|range| {
+            let coordinates: Vec<&GpsCoordinate> = points[range.clone()]
+                .iter()
+                .filter_map(|x| x.coordinate.as_ref())
+                .collect();
+            let members = &candidates[range.clone()];
+            let (first, last) = (&members[0], &members[members.len() - 1]);
+            EventSegment {
+                start_time: first.1,
+                end_time: last.1,
+                start_date: local_date(first.1, first.2.as_deref()),
+                end_date: local_date(last.1, last.2.as_deref()),
+                photo_ids: members.iter().map(|x| x.0).collect(),
+                center: (!coordinates.is_empty())
+                    .then(|| geo_cluster_util::centroid(&coordinates).0),
+                location_name: most_common(members.iter().filter_map(|x| x.4.as_deref())),
+            }
+        })
+        .collect();
+    Ok((segments, candidates.len()))
+}
+
+/// 事件名称【地点和日期，跨天时为日期范围】
+fn event_name(place_name: Option<&str>, start_date: &str, end_date: &str) -> String {
+    let dates = if start_date == end_date {
+        start_date.to_string()
+    } else {
+        format!("{} - {}", start_date, end_date)
+    };
+    match place_name {
+        Some(place) => format!("{} {}", place, dates),
+        None => dates,
+    }
+}
+
+/// 拍摄地的当地日期
+/// - offset 拍摄时的时区
+fn local_date(time: i64, offset: Option<&str>) -> String {
+    TimeUtils::to_local_date_time(time, offset)
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_name() {
+        assert_eq!(event_name(None, "2025-01-01", "2025-01-01"), "2025-01-01");
+        assert_eq!(
+            event_name(Some("杭州"), "2025-01-01", "2025-01-03"),
+            "杭州 2025-01-01 - 2025-01-03"
+        );
+        // 2024-12-31 16:30:00 UTC，东八区已是第二天
+        assert_eq!(local_date(1735662600, Some("+08:00")), "2025-01-01");
+        assert_eq!(local_date(1735662600, Some("+00:00")), "2024-12-31");
+    }
+}
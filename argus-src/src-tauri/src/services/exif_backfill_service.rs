@@ -0,0 +1,70 @@
+use crate::constant::EXIF_BACKFILL_PAGE_SIZE;
+use crate::models::photo::Photo;
+use crate::services::maintenance_scheduler_service;
+use crate::storage::connection::establish_connection;
+use crate::storage::photo_table;
+use crate::structs::exif_backfill::ExifBackfillSummary;
+use crate::structs::maintenance_schedule::MaintenanceJob;
+use crate::utils::exif_utils::exif_util;
+use crate::utils::{file_util, panic_util};
+use anyhow::Result;
+use diesel::SqliteConnection;
+
+/// 启动时在后台补全 EXIF【升级前导入的照片没有写入 EXIF；作为维护任务执行，避免与定时任务同时执行】
+pub fn start_backfill() {
+    tauri::async_runtime::spawn_blocking(|| {
+        if let Err(e) = maintenance_scheduler_service::run_job(MaintenanceJob::ExifBackfill) {
+            log::warn!("EXIF 补全未执行: {}", e);
+        }
+    });
+}
+
+/// 为还没有读取 EXIF 的照片读取原图的 EXIF
+///
+/// 只填写照片中还没有值的字段，不覆盖用户修改过的评分、说明等；
+/// 原图不存在时跳过，下次补全时重试
+pub fn backfill_exif() -> Result<ExifBackfillSummary> {
+    let mut conn = establish_connection();
+    let mut summary = ExifBackfillSummary::default();
+    let mut after_id = 0;
+    loop {
+        let photos =
+            photo_table::search_photos_without_exif(&mut conn, after_id, EXIF_BACKFILL_PAGE_SIZE)?;
+        let Some(last) = photos.last() else {
+            break;
+        };
+        after_id = last.id;
+        for photo in photos {
+            summary.checked += 1;
+            backfill_photo(&mut conn, &photo, &mut summary)?;
+        }
+    }
+    Ok(summary)
+}
+
+/// 读取一张照片原图的 EXIF 并写入照片记录【重命名等依赖拍摄时间、相机型号的操作前调用】
+/// - summary 累计补全结果
+pub fn backfill_photo(
+    conn: &mut SqliteConnection,
+    photo: &Photo,
+    summary: &mut ExifBackfillSummary,
+) -> Result<()> {
+    let path = photo.full_path();
+    if !file_util::file_exists(&path) {
+        summary.missing += 1;
+        return Ok(());
+    }
+    match panic_util::catch_panic(|| exif_util::read_img_exif(&path)) {
+        Ok(exif) => {
+            let changeset = photo_table::to_exif_changeset(&exif, (photo.width, photo.height));
+            photo_table::fill_photo_exif(conn, photo, changeset)?;
+            summary.filled += 1;
+        }
+        Err(e) => {
+            log::warn!("EXIF 读取失败 {}: {}", path.display(), e);
+            photo_table::mark_exif_read(conn, photo.id)?;
+            summary.unreadable += 1;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::constant::FACE_TAG_ROOT_NAME;
+use crate::services::metadata_export_service::{ensure_tag, load_tag_index};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::external_library::{
+    ExternalImportSummary, ExternalLibrarySource, ExternalPhoto, PhotoMatcher,
+};
+use crate::structs::organization_tree::{NodePathIndex, NODE_PATH_SEPARATOR};
+use crate::utils::external_library::{apple_photos, digikam};
+use crate::utils::file_hash_util::FileHashUtils;
+use anyhow::Result;
+use diesel::{Connection, SqliteConnection};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// 从其他照片管理软件导入相册、关键字、评分和人物
+///
+/// 只处理已经扫描进 argus 的照片：依次按路径、文件 Hash、文件名 + 大小匹配。
+/// 相册、关键字不存在时自动创建，人物作为 `人物/<名称>` 标签导入；已有的相册、标签不会被移除
+/// - source 来源软件
+/// - path 照片库文件夹或数据库文件
+pub fn import_external_library(
+    source: ExternalLibrarySource,
+    path: &Path,
+) -> Result<ExternalImportSummary> {
+    let photos = match source {
+        ExternalLibrarySource::ApplePhotos => apple_photos::read(path)?,
+        ExternalLibrarySource::Digikam => digikam::read(path)?,
+    };
+    log::info!(
+        "从 {:?} 读取到 {} 张照片: {}",
+        source,
+        photos.len(),
+        path.display()
+    );
+
+    let mut summary = ExternalImportSummary::new(source);
+    summary.total = photos.len() as u32;
+
+    let mut conn = establish_connection();
+    let matcher = PhotoMatcher::new(&storage::photo_table::search_all_photos(&mut conn)?);
+    // 先完成匹配【计算 Hash 较慢，不放在事务中】
+    let mut matched = Vec::new();
+    for photo in &photos {
+        match match_photo(&matcher, photo, &mut summary) {
+            Some(id) => matched.push((id, photo)),
+            None => summary
+                .unmatched
+                .push(photo.source_path.display().to_string()),
+        }
+    }
+    summary.matched = matched.len() as u32;
+
+    conn.transaction::<_, anyhow::Error, _>(|conn| {
+        let mut album_index = load_album_index(conn)?;
+        let mut tag_index = load_tag_index(conn)?;
+        for (photo_id, photo) in &matched {
+            apply_photo(
+                conn,
+                *photo_id,
+                photo,
+                &mut album_index,
+                &mut tag_index,
+                &mut summary,
+            )?;
+        }
+        Ok(())
+    })?;
+    log::info!(
+        "导入完成: 共 {} 张，匹配 {} 张（Hash {} 张），新建相册 {} 个、标签 {} 个",
+        summary.total,
+        summary.matched,
+        summary.matched_by_hash,
+        summary.albums_created,
+        summary.tags_created
+    );
+    Ok(summary)
+}
+
+/// 匹配照片【路径匹配不到且原文件存在时计算 Hash】
+fn match_photo(
+    matcher: &PhotoMatcher,
+    photo: &ExternalPhoto,
+    summary: &mut ExternalImportSummary,
+) -> Option<i32> {
+    if let Some(id) = matcher.match_path(photo) {
+        return Some(id);
+    }
+    if photo.source_path.is_file() {
+        match FileHashUtils::sha256(&photo.source_path) {
+            Ok(hash) => {
+                if let Some(id) = matcher.match_hash(&hash) {
+                    summary.matched_by_hash += 1;
+                    return Some(id);
+                }
+            }
+            Err(e) => log::warn!("文件 Hash 计算失败 {}: {}", photo.source_path.display(), e),
+        }
+    }
+    matcher.match_name(photo)
+}
+
+/// 写入单张照片的评分、相册和标签
+fn apply_photo(
+    conn: &mut SqliteConnection,
+    photo_id: i32,
+    photo: &ExternalPhoto,
+    album_index: &mut NodePathIndex,
+    tag_index: &mut NodePathIndex,
+    summary: &mut ExternalImportSummary,
+) -> Result<()> {
+    if let Some(rating) = photo.rating {
+        let current = storage::photo_table::search_photos_by_ids(conn, &[photo_id])?;
+        if current.first().is_some_and(|x| x.rating != Some(rating)) {
+            storage::photo_table::update_photo_rating(conn, photo_id, Some(rating))?;
+            summary.ratings_updated += 1;
+        }
+    }
+
+    let mut album_ids = HashSet::new();
+    for album_path in &photo.albums {
+        let (id, created) = album_index.ensure(album_path, |name, parent| {
+            Ok(storage::album::insert_album(conn, name, parent)?.id)
+        })?;
+        summary.albums_created += created;
+        album_ids.extend(id);
+    }
+    let album_ids: Vec<i32> = album_ids.into_iter().collect();
+    summary.album_links += storage::album::add_photo_albums(conn, photo_id, &album_ids)? as u32;
+
+    let face_paths = photo
+        .faces
+        .iter()
+        .map(|name| format!("{}{}{}", FACE_TAG_ROOT_NAME, NODE_PATH_SEPARATOR, name));
+    let mut tag_ids = HashSet::new();
+    for tag_path in photo.keywords.iter().cloned().chain(face_paths) {
+        tag_ids.extend(ensure_tag(
+            conn,
+            tag_index,
+            &tag_path,
+            &mut summary.tags_created,
+        )?);
+    }
+    let tag_ids: Vec<i32> = tag_ids.into_iter().collect();
+    summary.tag_links += storage::tag::add_photo_tags(conn, photo_id, &tag_ids)? as u32;
+    Ok(())
+}
+
+/// 读取所有相册的路径
+fn load_album_index(conn: &mut SqliteConnection) -> Result<NodePathIndex> {
+    // 文件夹相册只读，不能作为导入的目标
+    let albums: Vec<(i32, Option<i32>, String)> = storage::album::get_all_albums(conn)?
+        .into_iter()
+        .filter(|x| x.source_path.is_none())
+        .map(|x| (x.id, x.parent_id, x.name))
+        .collect();
+    Ok(NodePathIndex::new(&albums))
+}
@@ -0,0 +1,219 @@
+use crate::constant::FOLDER_ALBUM_SYNC_DELAY_SECS;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::config::sys_config;
+use crate::structs::folder_album::{FolderAlbumSyncSummary, FolderNode};
+use crate::utils::file_util;
+use anyhow::Result;
+use diesel::{Connection, SqliteConnection};
+use once_cell::sync::Lazy;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// 是否已有等待执行的同步
+static SYNC_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// 同一时间只执行一次同步
+static SYNC_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// 是否开启了文件夹相册
+pub fn is_enabled() -> bool {
+    sys_config().mirror_folder_albums.unwrap_or(false)
+}
+
+/// 照片文件变化（扫描、重新扫描、移动、重命名、删除）后请求同步文件夹相册
+///
+/// 未开启时忽略；延迟一段时间后在后台执行，期间的多次请求合并为一次
+pub fn request_sync() {
+    if !is_enabled() || SYNC_PENDING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    thread::spawn(|| {
+        thread::sleep(Duration::from_secs(FOLDER_ALBUM_SYNC_DELAY_SECS));
+        SYNC_PENDING.store(false, Ordering::SeqCst);
+        if let Err(e) = sync_folder_albums() {
+            log::error!("文件夹相册同步失败: {}", e);
+        }
+    });
+}
+
+/// 在后台立即同步文件夹相册【程序启动、配置变化时调用，关闭时清理文件夹相册】
+pub fn sync_in_background() {
+    thread::spawn(|| {
+        if let Err(e) = sync_folder_albums() {
+            log::error!("文件夹相册同步失败: {}", e);
+        }
+    });
+}
+
+/// 把图像存储路径的文件夹结构同步为只读相册
+///
+/// 每个图像存储路径对应一个顶级相册，包含照片的文件夹及其上级文件夹对应子相册，
+/// 相册中的照片与文件夹中的照片（不包含子文件夹）保持一致；文件夹已不存在的相册删除。
+/// 未开启文件夹相册时删除所有文件夹相册
+pub fn sync_folder_albums() -> Result<FolderAlbumSyncSummary> {
+    let _guard = SYNC_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let enabled = is_enabled();
+    let mut conn = establish_connection();
+    let summary = conn.transaction(|conn| {
+        let mut summary = FolderAlbumSyncSummary {
+            enabled,
+            ..Default::default()
+        };
+        let folders = if enabled {
+            let roots: Vec<PathBuf> = storage::photo_storage::get_all_photo_path(conn)?
+                .into_iter()
+                .map(|x| PathBuf::from(x.img_paths))
+                .collect();
+            let photos = storage::photo_table::search_all_photo_folders(conn)?;
+            plan_folder_tree(&roots, &photos)
+        } else {
+            BTreeMap::new()
+        };
+        apply_folder_tree(conn, &folders, &mut summary)?;
+        anyhow::Ok(summary)
+    })?;
+    log::info!("文件夹相册同步完成: {:?}", summary);
+    Ok(summary)
+}
+
+/// 按文件夹结构更新相册和照片关联
+fn apply_folder_tree(
+    conn: &mut SqliteConnection,
+    folders: &BTreeMap<String, FolderNode>,
+    summary: &mut FolderAlbumSyncSummary,
+) -> Result<()> {
+    let mut existing: HashMap<String, (i32, String, Option<i32>)> =
+        storage::album::get_mirror_albums(conn)?
+            .into_iter()
+            .filter_map(|x| Some((x.source_path?, (x.id, x.name, x.parent_id))))
+            .collect();
+    let links = storage::album::get_album_photo_ids(
+        conn,
+        &existing.values().map(|(id, _, _)| *id).collect::<Vec<_>>(),
+    )?;
+
+    // 上级文件夹排在子文件夹之前，新增子相册时上级相册已存在
+    let mut album_ids: HashMap<&str, i32> = HashMap::new();
+    for (path, node) in folders {
+        let parent_id = node
+            .parent
+            .as_deref()
+            .and_then(|x| album_ids.get(x).copied());
+        let album_id = match existing.remove(path) {
+            Some((id, name, old_parent)) => {
+                if name != node.name || old_parent != parent_id {
+                    storage::album::update_album_node(conn, id, &node.name, parent_id)?;
+                    summary.albums_updated += 1;
+                }
+                id
+            }
+            None => {
+                summary.albums_created += 1;
+                storage::album::insert_mirror_album(conn, &node.name, parent_id, path)?.id
+            }
+        };
+        album_ids.insert(path, album_id);
+
+        let current = links.get(&album_id).cloned().unwrap_or_default();
+        let wanted: HashSet<i32> = node.photo_ids.iter().copied().collect();
+        let added: Vec<i32> = wanted.difference(&current).copied().collect();
+        let removed: Vec<i32> = current.difference(&wanted).copied().collect();
+        if !added.is_empty() {
+            summary.photos_linked +=
+                storage::album::add_album_photos(conn, album_id, &added)? as u32;
+        }
+        if !removed.is_empty() {
+            summary.photos_unlinked +=
+                storage::album::remove_album_photos(conn, album_id, &removed)? as u32;
+        }
+    }
+
+    let stale: Vec<i32> = existing.values().map(|(id, _, _)| *id).collect();
+    if !stale.is_empty() {
+        summary.albums_removed = storage::album::delete_albums(conn, &stale)? as u32;
+    }
+    Ok(())
+}
+
+/// 根据照片所在文件夹生成文件夹树（文件夹路径 -> 相册）
+///
+/// 照片归入最深的图像存储路径，不在任何存储路径下的照片忽略
+/// - roots 图像存储路径
+/// - photos 照片 ID 和所在文件夹
+fn plan_folder_tree(roots: &[PathBuf], photos: &[(i32, String)]) -> BTreeMap<String, FolderNode> {
+    let mut folders: BTreeMap<String, FolderNode> = BTreeMap::new();
+    for (photo_id, folder) in photos {
+        let dir = Path::new(folder);
+        let Some(root) = roots
+            .iter()
+            .filter(|root| dir.starts_with(root))
+            .max_by_key(|root| root.as_os_str().len())
+        else {
+            continue;
+        };
+
+        // 从照片所在文件夹向上补齐到存储路径
+        let mut current = dir;
+        let mut first = true;
+        loop {
+            let is_root = current == root.as_path();
+            let parent = if is_root { None } else { current.parent() };
+            let key = file_util::display_path(current);
+            let node = folders.entry(key).or_insert_with(|| FolderNode {
+                name: folder_name(current),
+                parent: parent.map(file_util::display_path),
+                photo_ids: Vec::new(),
+            });
+            if first {
+                node.photo_ids.push(*photo_id);
+                first = false;
+            }
+            match parent {
+                Some(parent) => current = parent,
+                None => break,
+            }
+        }
+    }
+    folders
+}
+
+/// 文件夹名称【没有名称时（如磁盘根目录）使用完整路径】
+fn folder_name(path: &Path) -> String {
+    path.file_name()
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_else(|| file_util::display_path(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_folder_tree() {
+        let roots = vec![PathBuf::from("/photos")];
+        let photos = vec![
+            (1, "/photos/2024/trip".to_string()),
+            (2, "/photos/2024/trip".to_string()),
+            (3, "/photos".to_string()),
+            (4, "/elsewhere".to_string()),
+        ];
+        let folders = plan_folder_tree(&roots, &photos);
+        let keys: Vec<&str> = folders.keys().map(|x| x.as_str()).collect();
+        assert_eq!(keys, vec!["/photos", "/photos/2024", "/photos/2024/trip"]);
+
+        assert_eq!(folders["/photos"].parent, None);
+        assert_eq!(folders["/photos"].photo_ids, vec![3]);
+        assert_eq!(folders["/photos/2024"].name, "2024");
+        assert!(folders["/photos/2024"].photo_ids.is_empty());
+        assert_eq!(
+            folders["/photos/2024/trip"].parent.as_deref(),
+            Some("/photos/2024")
+        );
+        assert_eq!(folders["/photos/2024/trip"].photo_ids, vec![1, 2]);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::constant::{GEOCODE_CACHE_SCALE, GEOCODE_CACHE_TTL_SECS, REVERSE_GEOCODE_INTERVAL_MS};
+use crate::models::geocode_cache::GeocodeCacheRecord;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::geocode_cache::{GeocodeSeedSummary, GeocodeSource};
+use crate::utils::exif_utils::gps_util::GpsCoordinate;
+use crate::utils::time_util::TimeUtils;
+use crate::utils::{csv_util, reverse_geocode_util};
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// 上一次请求逆地理编码服务的时间【所有调用共用，保证请求间隔】
+static LAST_REQUEST: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+
+/// 查询坐标对应的地点名称
+///
+/// 先查询缓存（按保留 3 位小数的坐标），没有或已过期时才请求逆地理编码服务，
+/// 服务的结果（包括没有结果）都会缓存；请求失败时不缓存
+/// - url 逆地理编码服务地址【为空时只查询缓存】
+/// - coordinate 十进制坐标
+pub async fn reverse_geocode(url: &str, coordinate: &GpsCoordinate) -> Result<Option<String>> {
+    let key = cache_key(coordinate);
+    let now = TimeUtils::current_timestamp();
+    {
+        let mut conn = establish_connection();
+        if let Some(cached) = storage::geocode_cache::get_cache(&mut conn, key, now)? {
+            return Ok(cached.name);
+        }
+    }
+    if url.is_empty() {
+        return Ok(None);
+    }
+
+    let name = {
+        let mut last = LAST_REQUEST.lock().await;
+        let interval = Duration::from_millis(REVERSE_GEOCODE_INTERVAL_MS);
+        if let Some(wait) = last.and_then(|x| interval.checked_sub(x.elapsed())) {
+            tokio::time::sleep(wait).await;
+        }
+        let result = reverse_geocode_util::reverse_geocode(url, coordinate).await;
+        *last = Some(Instant::now());
+        result?
+    };
+    let record = GeocodeCacheRecord {
+        lat_key: key.0,
+        lon_key: key.1,
+        name: name.clone(),
+        source: GeocodeSource::Service.code(),
+        expire_time: Some(now + GEOCODE_CACHE_TTL_SECS),
+        create_time: now,
+    };
+    let mut conn = establish_connection();
+    if let Err(e) = storage::geocode_cache::save_caches(&mut conn, &[record]) {
+        log::warn!("逆地理编码缓存保存失败 {}: {}", coordinate, e);
+    }
+    Ok(name)
+}
+
+/// 从离线数据导入地名【不过期，覆盖相同位置的缓存】
+///
+/// CSV 每行为 `纬度,经度,名称`，第一行不是坐标时视为标题行跳过
+/// - path CSV 文件路径
+pub fn seed_cache(path: &Path) -> Result<GeocodeSeedSummary> {
+    let content = fs::read_to_string(path)?;
+    let now = TimeUtils::current_timestamp();
+    let (records, skipped) = parse_seed_rows(&csv_util::from_csv(&content)?, now);
+    let mut conn = establish_connection();
+    storage::geocode_cache::save_caches(&mut conn, &records)?;
+    let expired = storage::geocode_cache::delete_expired(&mut conn, now)?;
+    log::info!(
+        "离线地名导入完成: {} 个坐标，跳过 {} 行，清理过期缓存 {} 条",
+        records.len(),
+        skipped,
+        expired
+    );
+    Ok(GeocodeSeedSummary {
+        imported: records.len(),
+        skipped,
+    })
+}
+
+/// 坐标对应的缓存键（纬度、经度各保留 3 位小数后乘以 1000）
+pub fn cache_key(coordinate: &GpsCoordinate) -> (i32, i32) {
+    (
+        (coordinate.latitude * GEOCODE_CACHE_SCALE).round() as i32,
+        (coordinate.longitude * GEOCODE_CACHE_SCALE).round() as i32,
+    )
+}
+
+/// 解析离线数据，返回缓存记录和跳过的行数【同一位置出现多次时使用最后一行】
+fn parse_seed_rows(rows: &[Vec<String>], now: i64) -> (Vec<GeocodeCacheRecord>, usize) {
+    // 同一坐标出现多次时使用最后一次，按最后出现的位置排序
+    let mut records: HashMap<(i32, i32), (usize, GeocodeCacheRecord)> = HashMap::new();
+    let mut skipped = 0;
+    for (index, row) in rows.iter().enumerate() {
+        let parsed = match row.as_slice() {
+            [latitude, longitude, name, ..] => latitude
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .zip(longitude.trim().parse::<f64>().ok())
+                .filter(|(lat, lon)| (-90.0..=90.0).contains(lat) && (-180.0..=180.0).contains(lon))
+                .zip(Some(name.trim()).filter(|x| !x.is_empty())),
+            _ => None,
+        };
+        let Some(((latitude, longitude), name)) = parsed else {
+            // 标题行
+            if index > 0 {
+                skipped += 1;
+            }
+            continue;
+        };
+        let (lat_key, lon_key) = cache_key(&GpsCoordinate {
+            latitude,
+            longitude,
+            altitude: None,
+        });
+        let record = GeocodeCacheRecord {
+            lat_key,
+            lon_key,
+            name: Some(name.to_string()),
+            source: GeocodeSource::Offline.code(),
+            expire_time: None,
+            create_time: now,
+        };
+        records.insert((lat_key, lon_key), (index, record));
+    }
+    let mut records: Vec<(usize, GeocodeCacheRecord)> = records.into_values().collect();
+    records.sort_by_key(|(index, _)| *index);
+    (records.into_iter().map(|(_, x)| x).collect(), skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key() {
+        let coordinate = GpsCoordinate {
+            latitude: 39.90423,
+            longitude: -116.40736,
+            altitude: None,
+        };
+        assert_eq!(cache_key(&coordinate), (39904, -116407));
+    }
+
+    #[test]
+    fn test_parse_seed_rows() {
+        let rows = csv_util::from_csv(
+            "lat,lon,name\n39.9042,116.4074,北京\n31.2304,121.4737,上海\n91,0,无效\n39.90421,116.40741,北京市\n",
+        )
+        .unwrap();
+        let (records, skipped) = parse_seed_rows(&rows, 100);
+        assert_eq!(skipped, 1);
+        let names: Vec<_> = records.iter().map(|x| x.name.as_deref().unwrap()).collect();
+        assert_eq!(names, vec!["上海", "北京市"]);
+        assert_eq!(records[1].expire_time, None);
+        assert_eq!(records[1].source, GeocodeSource::Offline.code());
+    }
+}
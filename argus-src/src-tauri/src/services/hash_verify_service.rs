@@ -0,0 +1,288 @@
+use crate::constant::HASH_VERIFY_REPORT_KEEP;
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::services::integrity_service;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::hash_verify::{
+    HashMismatch, HashVerifyProgress, HashVerifyReport, HashVerifyStatus,
+};
+use crate::structs::integrity_report::IntegrityIssueKind;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::time_util::TimeUtils;
+use crate::utils::{file_util, uuid_util};
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 正在运行的校验任务【任务 ID -> 取消标记】
+static VERIFY_JOBS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 校验报告【任务 ID -> 报告，已结束的只保留最近 `HASH_VERIFY_REPORT_KEEP` 个】
+static VERIFY_REPORTS: Lazy<Mutex<HashMap<String, HashVerifyReport>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 开始校验照片 Hash【后台执行，返回任务 ID】
+///
+/// 重新计算文件 Hash 并与导入时记录的比较，`photo_ids` 和 `folder` 至少指定一个
+/// - photo_ids 照片 ID
+/// - folder 文件夹【包含子文件夹中的照片】
+/// - on_progress 进度回调【每校验一张照片调用一次，结束时以完成、失败或取消状态调用一次】
+pub fn verify_photos<F>(
+    photo_ids: Option<Vec<i32>>,
+    folder: Option<String>,
+    on_progress: F,
+) -> Result<String>
+where
+    F: Fn(&HashVerifyProgress) + Send + 'static,
+{
+    let photos = target_photos(photo_ids, folder)?;
+    if photos.is_empty() {
+        return Err(anyhow!(i18n::text(msg::HASH_VERIFY_NO_PHOTOS)));
+    }
+
+    let job_id = uuid_util::uuid_v4();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    VERIFY_JOBS
+        .lock()
+        .unwrap()
+        .insert(job_id.clone(), cancelled.clone());
+    let report = HashVerifyReport {
+        job_id: job_id.clone(),
+        status: HashVerifyStatus::Running,
+        total: photos.len(),
+        checked: 0,
+        matched: 0,
+        current_path: None,
+        mismatches: Vec::new(),
+        start_time: TimeUtils::current_timestamp(),
+        end_time: None,
+        error: None,
+    };
+    {
+        let mut reports = VERIFY_REPORTS.lock().unwrap();
+        prune_reports(&mut reports);
+        reports.insert(job_id.clone(), report);
+    }
+
+    let id = job_id.clone();
+    let spawned = std::thread::Builder::new()
+        .name("argus-hash-verify".to_string())
+        .spawn(move || {
+            for photo in &photos {
+                if cancelled.load(Ordering::SeqCst) {
+                    break;
+                }
+                let path = file_util::display_path(photo.full_path());
+                update_report(&id, |report| report.current_path = Some(path));
+                let result = verify_photo(photo, &cancelled);
+                // 计算过程中取消
+                if let Err(None) = result {
+                    break;
+                }
+                let mismatch = result.err().flatten();
+                let progress = update_report(&id, |report| {
+                    report.checked += 1;
+                    match &mismatch {
+                        Some(mismatch) => report.mismatches.push(mismatch.clone()),
+                        None => report.matched += 1,
+                    }
+                });
+                if let Some(progress) = progress {
+                    on_progress(&HashVerifyProgress {
+                        mismatch,
+                        ..progress
+                    });
+                }
+            }
+            VERIFY_JOBS.lock().unwrap().remove(&id);
+            let status = if cancelled.load(Ordering::SeqCst) {
+                log::info!("Hash 校验已取消: {}", id);
+                HashVerifyStatus::Cancelled
+            } else {
+                HashVerifyStatus::Completed
+            };
+            let progress = update_report(&id, |report| {
+                report.current_path = None;
+                report.end_time = Some(TimeUtils::current_timestamp());
+                report.status = status;
+                if status == HashVerifyStatus::Completed {
+                    log::info!(
+                        "Hash 校验完成: 校验 {} 张，{} 张不一致",
+                        report.checked,
+                        report.mismatches.len()
+                    );
+                }
+            });
+            if let Some(progress) = progress {
+                on_progress(&progress);
+            }
+        });
+    if let Err(e) = spawned {
+        VERIFY_JOBS.lock().unwrap().remove(&job_id);
+        let mut reports = VERIFY_REPORTS.lock().unwrap();
+        if let Some(report) = reports.get_mut(&job_id) {
+            report.status = HashVerifyStatus::Failed;
+            report.error = Some(e.to_string());
+            report.end_time = Some(TimeUtils::current_timestamp());
+        }
+        return Err(i18n::error(
+            msg::HASH_VERIFY_START_FAILED,
+            &[("error", e.to_string())],
+        ));
+    }
+    Ok(job_id)
+}
+
+/// 取消校验任务
+pub fn cancel_verify(job_id: &str) -> Result<()> {
+    let jobs = VERIFY_JOBS.lock().unwrap();
+    let cancelled = jobs.get(job_id).ok_or_else(|| {
+        i18n::error(
+            msg::HASH_VERIFY_JOB_NOT_RUNNING,
+            &[("id", job_id.to_string())],
+        )
+    })?;
+    cancelled.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// 获取校验报告【校验中时返回当前进度】
+pub fn get_verify_report(job_id: &str) -> Result<HashVerifyReport> {
+    VERIFY_REPORTS
+        .lock()
+        .unwrap()
+        .get(job_id)
+        .cloned()
+        .ok_or_else(|| {
+            i18n::error(
+                msg::HASH_VERIFY_JOB_NOT_FOUND,
+                &[("id", job_id.to_string())],
+            )
+        })
+}
+
+/// 查询要校验的照片
+fn target_photos(photo_ids: Option<Vec<i32>>, folder: Option<String>) -> Result<Vec<Photo>> {
+    let folder = folder.filter(|x| !x.trim().is_empty());
+    if photo_ids.is_none() && folder.is_none() {
+        return Err(anyhow!(i18n::text(msg::HASH_VERIFY_TARGET_REQUIRED)));
+    }
+    let mut conn = establish_connection();
+    let mut photos = match &photo_ids {
+        Some(ids) => storage::photo_table::search_photos_by_ids(&mut conn, ids)?,
+        None => Vec::new(),
+    };
+    if let Some(folder) = folder {
+        let under = storage::photo_table::search_photo_under_root(&mut conn, Path::new(&folder))?;
+        let ids: HashSet<i32> = photos.iter().map(|x| x.id).collect();
+        photos.extend(under.into_iter().filter(|x| !ids.contains(&x.id)));
+    }
+    // 远程存储中的照片原图不在本地
+    photos.retain(|x| !x.is_remote());
+    Ok(photos)
+}
+
+/// 校验单张照片
+///
+/// Hash 一致时返回 `Ok`，不一致时返回 `Err(Some)`，计算过程中取消时返回 `Err(None)`
+fn verify_photo(photo: &Photo, cancelled: &AtomicBool) -> Result<(), Option<HashMismatch>> {
+    let path = file_util::long_path(photo.full_path());
+    let mismatch = |kind, actual_hash| HashMismatch {
+        photo_id: photo.id,
+        path: file_util::display_path(photo.full_path()),
+        kind,
+        expected_hash: photo.hash.clone(),
+        actual_hash,
+    };
+    let Ok(metadata) = fs::metadata(&path) else {
+        return Err(Some(mismatch(IntegrityIssueKind::Missing, None)));
+    };
+    match FileHashUtils::sha256_cancellable(&path, cancelled) {
+        Ok(None) => Err(None),
+        Ok(Some(hash)) if hash == photo.hash => Ok(()),
+        Ok(Some(hash)) => Err(Some(mismatch(
+            integrity_service::mismatch_kind(photo, &metadata),
+            Some(hash),
+        ))),
+        Err(e) => {
+            log::warn!("文件 Hash 计算失败 {}: {}", path.display(), e);
+            Err(Some(mismatch(IntegrityIssueKind::Unreadable, None)))
+        }
+    }
+}
+
+/// 更新校验报告，返回更新后的进度【报告已删除时返回 None】
+///
+/// 直接修改保存的报告，不复制不一致列表
+fn update_report<F>(job_id: &str, update: F) -> Option<HashVerifyProgress>
+where
+    F: FnOnce(&mut HashVerifyReport),
+{
+    let mut reports = VERIFY_REPORTS.lock().unwrap();
+    let report = reports.get_mut(job_id)?;
+    update(report);
+    Some(HashVerifyProgress::new(report, None))
+}
+
+/// 删除多余的已结束报告【保留最近结束的 `HASH_VERIFY_REPORT_KEEP` 个，校验中的不删除】
+fn prune_reports(reports: &mut HashMap<String, HashVerifyReport>) {
+    let mut finished: Vec<(i64, String)> = reports
+        .values()
+        .filter_map(|x| x.end_time.map(|time| (time, x.job_id.clone())))
+        .collect();
+    if finished.len() <= HASH_VERIFY_REPORT_KEEP {
+        return;
+    }
+    finished.sort_unstable();
+    let expired = finished.len() - HASH_VERIFY_REPORT_KEEP;
+    for (_, job_id) in finished.into_iter().take(expired) {
+        reports.remove(&job_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(job_id: &str, end_time: Option<i64>) -> HashVerifyReport {
+        HashVerifyReport {
+            job_id: job_id.to_string(),
+            status: match end_time {
+                Some(_) => HashVerifyStatus::Completed,
+                None => HashVerifyStatus::Running,
+            },
+            total: 1,
+            checked: 0,
+            matched: 0,
+            current_path: None,
+            mismatches: Vec::new(),
+            start_time: 0,
+            end_time,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_prune_reports() {
+        let mut reports = HashMap::new();
+        for i in 0..HASH_VERIFY_REPORT_KEEP as i64 + 2 {
+            let job_id = format!("finished-{}", i);
+            reports.insert(job_id.clone(), report(&job_id, Some(i)));
+        }
+        reports.insert("running".to_string(), report("running", None));
+        prune_reports(&mut reports);
+        assert_eq!(reports.len(), HASH_VERIFY_REPORT_KEEP + 1);
+        // 最早结束的两个被删除，校验中的保留
+        assert!(!reports.contains_key("finished-0"));
+        assert!(!reports.contains_key("finished-1"));
+        assert!(reports.contains_key("finished-2"));
+        assert!(reports.contains_key("running"));
+    }
+}
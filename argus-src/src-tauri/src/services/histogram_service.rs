@@ -0,0 +1,54 @@
+use crate::constant::IMAGE_COMPRESSION_RATIO;
+use crate::i18n;
+use crate::i18n::msg;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::config::sys_config;
+use crate::structs::histogram::HistogramSummary;
+use crate::structs::thumbnail_encoding;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::file_util;
+use crate::utils::image_format_util;
+use anyhow::Result;
+
+/// 获取照片的直方图和溢出统计
+///
+/// 直方图在生成缩略图时计算；没有记录时（如缩略图在此功能之前生成）
+/// 使用最大的缩略图计算并保存，缩略图也不存在时才解码原图
+pub fn get_photo_histogram(photo_id: i32) -> Result<HistogramSummary> {
+    let mut conn = establish_connection();
+    let photo = storage::photo_table::search_photos_by_ids(&mut conn, &[photo_id])?
+        .pop()
+        .ok_or_else(|| i18n::error(msg::PHOTO_NOT_FOUND, &[("id", photo_id.to_string())]))?;
+    if let Some(summary) = storage::photo_histogram::get_histogram(&mut conn, &photo.hash)? {
+        return Ok(summary);
+    }
+
+    let source = largest_thumbnail(&photo.hash)
+        .filter(|x| file_util::file_exists(x))
+        .unwrap_or_else(|| photo.full_path());
+    let image = image::open(file_util::long_path(&source)).map_err(|e| {
+        i18n::error(
+            msg::IMAGE_READ_FAILED,
+            &[
+                ("path", source.display().to_string()),
+                ("error", e.to_string()),
+            ],
+        )
+    })?;
+    let summary = HistogramSummary::from_image(&image);
+    storage::photo_histogram::save_histogram(&mut conn, &photo.hash, &summary)?;
+    Ok(summary)
+}
+
+/// 最大级别的缩略图路径
+fn largest_thumbnail(hash: &str) -> Option<std::path::PathBuf> {
+    let root_dir = sys_config().thumbnail_storage_path.clone()?;
+    let size = IMAGE_COMPRESSION_RATIO.iter().map(|x| x.size).max()?;
+    Some(FileHashUtils::hash_to_file_path(
+        hash,
+        root_dir,
+        &image_format_util::get_suffix_name(thumbnail_encoding::storage_format()),
+        size,
+    ))
+}
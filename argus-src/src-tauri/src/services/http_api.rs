@@ -0,0 +1,131 @@
+//! 局域网 HTTP 接口【只读，需要 http-server 功能】
+//!
+//! - `GET /api/photos?limit=&offset=` 照片列表
+//! - `GET /api/search?text=&minRating=&startTime=&endTime=&limit=&offset=` 搜索照片
+//! - `GET /api/photos/:id/thumbnail?size=` 缩略图
+//!
+//! 所有接口都需要令牌，见 `http_server_service::is_authorized`；
+//! 文字搜索不返回位于隐私区域的照片，见 `http_server_service::is_text_searchable`
+
+use crate::services::http_server_service::{self, RemotePhoto};
+use crate::services::photo_photo_service;
+use crate::structs::photo_search::PhotoSearchParams;
+use crate::utils::url_util;
+use anyhow::Result;
+use axum::extract::{Path, Query, Request};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+use std::net::IpAddr;
+
+/// 分页参数
+#[derive(Deserialize, Debug, Default)]
+struct PageParams {
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+/// 缩略图参数
+#[derive(Deserialize, Debug, Default)]
+struct ThumbnailParams {
+    size: Option<u32>,
+}
+
+/// 启动服务
+/// - address 监听地址
+/// - port 端口
+pub async fn serve(address: IpAddr, port: u16) -> Result<()> {
+    let app = Router::new()
+        .route("/api/photos", get(list_photos))
+        .route("/api/search", get(search_photos))
+        .route("/api/photos/:id/thumbnail", get(get_thumbnail))
+        .layer(middleware::from_fn(require_token));
+    let listener = tokio::net::TcpListener::bind((address, port)).await?;
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+/// 校验令牌
+async fn require_token(request: Request, next: Next) -> Response {
+    let bearer = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|x| x.to_str().ok())
+        .and_then(|x| x.strip_prefix("Bearer "))
+        .map(str::trim);
+    let query = request.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .filter_map(|x| x.split_once('='))
+            .find(|(key, _)| *key == "token")
+            .map(|(_, value)| url_util::decode_component(value))
+    });
+    if !http_server_service::is_authorized(bearer, query.as_deref()) {
+        return (StatusCode::UNAUTHORIZED, "令牌无效").into_response();
+    }
+    next.run(request).await
+}
+
+/// 照片列表（按拍摄时间倒序）
+async fn list_photos(Query(page): Query<PageParams>) -> Response {
+    let params = PhotoSearchParams {
+        limit: page.limit,
+        offset: page.offset,
+        ..Default::default()
+    };
+    search(params).await
+}
+
+/// 搜索照片【参数同 search_photos 命令】
+async fn search_photos(Query(params): Query<PhotoSearchParams>) -> Response {
+    search(params).await
+}
+
+async fn search(params: PhotoSearchParams) -> Response {
+    let text_search = params.text.as_deref().is_some_and(|x| !x.trim().is_empty());
+    let result =
+        tokio::task::spawn_blocking(move || photo_photo_service::search_photos(&params)).await;
+    match result {
+        Ok(Ok(photos)) => {
+            let photos: Vec<RemotePhoto> = photos
+                .iter()
+                .filter(|x| !text_search || http_server_service::is_text_searchable(x))
+                .map(RemotePhoto::from)
+                .collect();
+            Json(photos).into_response()
+        }
+        Ok(Err(e)) => internal_error(e.to_string()),
+        Err(e) => internal_error(e.to_string()),
+    }
+}
+
+/// 缩略图
+async fn get_thumbnail(Path(id): Path<i32>, Query(params): Query<ThumbnailParams>) -> Response {
+    let path = match http_server_service::thumbnail_file(id, params.size).await {
+        Ok(path) => path,
+        Err(e) => return (StatusCode::NOT_FOUND, e.to_string()).into_response(),
+    };
+    // 缩略图格式可配置，按文件后缀返回类型
+    let content_type = image::ImageFormat::from_path(&path)
+        .map(|format| format.to_mime_type())
+        .unwrap_or("image/jpeg");
+    match tokio::fs::read(&path).await {
+        Ok(bytes) => (
+            [
+                (header::CONTENT_TYPE, content_type),
+                (header::CACHE_CONTROL, "private, max-age=86400"),
+            ],
+            bytes,
+        )
+            .into_response(),
+        Err(e) => internal_error(e.to_string()),
+    }
+}
+
+fn internal_error(message: String) -> Response {
+    log::error!("HTTP 请求处理失败: {}", message);
+    (StatusCode::INTERNAL_SERVER_ERROR, message).into_response()
+}
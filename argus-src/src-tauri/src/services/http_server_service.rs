@@ -0,0 +1,248 @@
+// 未开启 http-server 功能时，接口相关的函数不会被使用
+#![cfg_attr(not(feature = "http-server"), allow(dead_code))]
+
+use crate::conf::CONF_DEFAULT;
+use crate::constant::{DEFAULT_THUMBNAIL_SIZE, IMAGE_COMPRESSION_RATIO};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::services::privacy_service;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::config::{save_config, sys_config, Config};
+use crate::structs::thumbnail_encoding;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::file_util::file_exists;
+use crate::utils::image_format_util;
+use crate::utils::img_util::ImageOperate;
+use crate::utils::uuid_util;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// HTTP 服务是否正在运行
+static HTTP_SERVER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// HTTP 服务状态
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpServerStatus {
+    /// 设置中是否开启
+    pub enabled: bool,
+    /// 编译时是否包含 HTTP 服务（http-server 功能）
+    pub available: bool,
+    /// 是否正在运行
+    pub running: bool,
+    /// 监听地址
+    pub address: String,
+    /// 端口
+    pub port: u16,
+    /// 访问令牌【请求时放在 `Authorization: Bearer <令牌>` 或 `?token=<令牌>` 中】
+    pub token: String,
+}
+
+/// 提供给远程访问的照片信息【不包含本机路径】
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RemotePhoto {
+    pub id: i32,
+    /// 文件名称
+    pub name: String,
+    pub width: i32,
+    pub height: i32,
+    /// 拍摄时间（时间戳）
+    pub date_time_original: Option<i64>,
+    /// 评分
+    pub rating: Option<i32>,
+    /// 照片说明
+    pub caption: Option<String>,
+    /// 相机型号
+    pub model: Option<String>,
+    /// 地点名称
+    pub location_name: Option<String>,
+}
+
+impl From<&Photo> for RemotePhoto {
+    fn from(photo: &Photo) -> RemotePhoto {
+        RemotePhoto {
+            id: photo.id,
+            name: photo.img_name.clone(),
+            width: photo.width,
+            height: photo.height,
+            date_time_original: photo.date_time_original,
+            rating: photo.rating,
+            caption: photo.caption.clone(),
+            model: photo.model.clone(),
+            location_name: privacy_service::public_location_name(photo),
+        }
+    }
+}
+
+/// 启动 HTTP 服务【设置中未开启时不启动，令牌为空时自动生成并保存】
+pub fn start_http_server() {
+    let config = sys_config();
+    if !config.http_server_enabled.unwrap_or(false) {
+        log::info!("HTTP 服务未开启");
+        return;
+    }
+    if let Err(e) = ensure_token(&config) {
+        log::error!("HTTP 服务令牌生成失败: {}", e);
+        return;
+    }
+    let address = match listen_address(&config) {
+        Ok(x) => x,
+        Err(e) => {
+            log::error!("{}", e);
+            return;
+        }
+    };
+    let port = config.http_server_port.unwrap_or_default();
+
+    #[cfg(feature = "http-server")]
+    tauri::async_runtime::spawn(async move {
+        HTTP_SERVER_RUNNING.store(true, Ordering::SeqCst);
+        log::info!("HTTP 服务启动，地址 {}:{}", address, port);
+        if let Err(e) = crate::services::http_api::serve(address, port).await {
+            log::error!("HTTP 服务异常退出: {}", e);
+        }
+        HTTP_SERVER_RUNNING.store(false, Ordering::SeqCst);
+    });
+    #[cfg(not(feature = "http-server"))]
+    log::warn!(
+        "当前版本未包含 HTTP 服务（http-server 功能），地址 {}:{} 未启动",
+        address,
+        port
+    );
+}
+
+/// 获取 HTTP 服务状态
+pub fn get_http_server_status() -> HttpServerStatus {
+    let config = sys_config();
+    HttpServerStatus {
+        enabled: config.http_server_enabled.unwrap_or(false),
+        available: cfg!(feature = "http-server"),
+        running: HTTP_SERVER_RUNNING.load(Ordering::SeqCst),
+        address: config.http_server_address.clone().unwrap_or_default(),
+        port: config.http_server_port.unwrap_or_default(),
+        token: config.http_server_token.clone().unwrap_or_default(),
+    }
+}
+
+/// 校验请求中的令牌【每次请求读取当前设置，修改令牌后立即生效】
+/// - bearer `Authorization: Bearer` 中的令牌
+/// - query 地址参数中的令牌【已解码；用于 `<img>` 等无法设置请求头的场景】
+pub fn is_authorized(bearer: Option<&str>, query: Option<&str>) -> bool {
+    let expected = sys_config().http_server_token.clone().unwrap_or_default();
+    token_matches(&expected, bearer.or(query))
+}
+
+/// 远程文字搜索的结果中是否可以包含该照片
+///
+/// 搜索索引包含地点名称，照片位于隐私区域（地点名称不公开）时，
+/// 即使不返回地点名称也能通过搜索地名推断位置，因此不出现在文字搜索结果中
+pub fn is_text_searchable(photo: &Photo) -> bool {
+    photo.location_name.is_none() || privacy_service::public_location_name(photo).is_some()
+}
+
+/// 查询照片缩略图，不存在时生成
+/// - photo_id 照片 ID
+/// - size 缩略图大小【只能是已有的压缩级别，为空时使用默认大小】
+pub async fn thumbnail_file(photo_id: i32, size: Option<u32>) -> Result<PathBuf> {
+    let size = size.unwrap_or(DEFAULT_THUMBNAIL_SIZE);
+    if !IMAGE_COMPRESSION_RATIO.iter().any(|x| x.size == size) {
+        return Err(i18n::error(
+            msg::HTTP_THUMBNAIL_SIZE_UNSUPPORTED,
+            &[("size", size.to_string())],
+        ));
+    }
+    let photo = tokio::task::spawn_blocking(move || {
+        let mut conn = establish_connection();
+        storage::photo_table::search_photos_by_ids(&mut conn, &[photo_id])
+    })
+    .await??
+    .pop()
+    .ok_or_else(|| i18n::error(msg::PHOTO_NOT_FOUND, &[("id", photo_id.to_string())]))?;
+
+    let root_dir = sys_config()
+        .thumbnail_storage_path
+        .clone()
+        .ok_or_else(|| anyhow!(i18n::text(msg::THUMBNAIL_PATH_NOT_CONFIGURED)))?;
+    let path = FileHashUtils::hash_to_file_path(
+        &photo.hash,
+        &root_dir,
+        &image_format_util::get_suffix_name(thumbnail_encoding::storage_format()),
+        size,
+    );
+    if file_exists(&path) {
+        return Ok(path);
+    }
+    let path = ImageOperate::designate_level_image_compression(
+        photo.full_path(),
+        thumbnail_encoding::storage_format(),
+        size,
+    )
+    .await?;
+    Ok(PathBuf::from(path))
+}
+
+/// 监听地址【默认只监听本机，设置为 `0.0.0.0` 等地址时允许局域网访问】
+fn listen_address(config: &Config) -> Result<IpAddr> {
+    let address = config
+        .http_server_address
+        .as_deref()
+        .filter(|x| !x.is_empty())
+        .unwrap_or(&CONF_DEFAULT.http_server_address);
+    address.parse().map_err(|e| {
+        i18n::error(
+            msg::HTTP_ADDRESS_INVALID,
+            &[("address", address.to_string()), ("error", e.to_string())],
+        )
+    })
+}
+
+/// 令牌为空时生成新令牌并保存到设置
+fn ensure_token(config: &Config) -> Result<()> {
+    if config
+        .http_server_token
+        .as_deref()
+        .is_some_and(|x| !x.is_empty())
+    {
+        return Ok(());
+    }
+    let mut config = config.clone();
+    config.http_server_token = Some(uuid_util::uuid_v4());
+    save_config(&config)?;
+    log::info!("已生成新的 HTTP 服务令牌");
+    Ok(())
+}
+
+/// 比较令牌【逐字节比较全部内容，避免通过响应时间猜测令牌；期望值为空时一律拒绝】
+fn token_matches(expected: &str, actual: Option<&str>) -> bool {
+    let Some(actual) = actual else {
+        return false;
+    };
+    if expected.is_empty() || expected.len() != actual.len() {
+        return false;
+    }
+    expected
+        .bytes()
+        .zip(actual.bytes())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_matches() {
+        assert!(token_matches("abc", Some("abc")));
+        assert!(!token_matches("abc", Some("abd")));
+        assert!(!token_matches("abc", Some("ab")));
+        assert!(!token_matches("abc", None));
+        assert!(!token_matches("", Some("")));
+    }
+}
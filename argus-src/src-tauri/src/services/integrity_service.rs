@@ -0,0 +1,119 @@
+use crate::models::photo::Photo;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::config::sys_config;
+use crate::structs::integrity_report::{IntegrityIssue, IntegrityIssueKind, IntegrityReport};
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::file_util;
+use crate::utils::time_util::TimeUtils;
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use std::fs;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+/// 最近一次完整性校验的报告
+static INTEGRITY_REPORT: Lazy<Mutex<Option<IntegrityReport>>> = Lazy::new(|| Mutex::new(None));
+
+/// 启动完整性校验线程【设置中未开启时不启动】
+pub fn start_startup_check() {
+    if !sys_config().integrity_check_on_startup.unwrap_or(false) {
+        log::info!("启动完整性校验未开启");
+        return;
+    }
+    let sample = sys_config().integrity_check_sample.unwrap_or_default();
+    let spawned = std::thread::Builder::new()
+        .name("argus-integrity-check".to_string())
+        .spawn(move || {
+            if let Err(e) = check_integrity(sample) {
+                log::error!("完整性校验失败: {}", e);
+                if let Some(report) = INTEGRITY_REPORT.lock().unwrap().as_mut() {
+                    report.running = false;
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        log::error!("完整性校验线程启动失败: {}", e);
+    }
+}
+
+/// 获取最近一次完整性校验的报告【校验中时返回当前进度】
+pub fn get_integrity_report() -> Option<IntegrityReport> {
+    INTEGRITY_REPORT.lock().unwrap().clone()
+}
+
+/// 校验照片文件完整性
+///
+/// 重新计算文件 Hash 并与导入时记录的比较，找出被修改或损坏的照片，
+/// 并把已不存在的照片标记为不可访问
+/// - sample 随机抽取的照片数量【0 表示全部】
+pub fn check_integrity(sample: u32) -> Result<()> {
+    let mut conn = establish_connection();
+    let limit = (sample > 0).then_some(i64::from(sample));
+    let photos = storage::photo_table::sample_available_photos(&mut conn, limit)?;
+    *INTEGRITY_REPORT.lock().unwrap() = Some(IntegrityReport {
+        running: true,
+        start_time: TimeUtils::current_timestamp(),
+        total: photos.len(),
+        ..Default::default()
+    });
+
+    let mut missing = Vec::new();
+    for photo in &photos {
+        let issue = check_photo(photo);
+        if issue == Some(IntegrityIssueKind::Missing) {
+            missing.push(photo.id);
+        }
+        let mut report = INTEGRITY_REPORT.lock().unwrap();
+        let report = report.get_or_insert_with(IntegrityReport::default);
+        report.checked += 1;
+        if let Some(kind) = issue {
+            report.issues.push(IntegrityIssue {
+                photo_id: photo.id,
+                path: file_util::display_path(photo.full_path()),
+                kind,
+            });
+        }
+    }
+    storage::photo_table::set_photos_available(&mut conn, &missing, false)?;
+
+    let mut report = INTEGRITY_REPORT.lock().unwrap();
+    if let Some(report) = report.as_mut() {
+        report.running = false;
+        report.end_time = Some(TimeUtils::current_timestamp());
+        log::info!(
+            "完整性校验完成: 校验 {} 张，发现 {} 个问题",
+            report.checked,
+            report.issues.len()
+        );
+    }
+    Ok(())
+}
+
+/// 校验单张照片【没有问题时返回 None】
+fn check_photo(photo: &Photo) -> Option<IntegrityIssueKind> {
+    let path = file_util::long_path(photo.full_path());
+    let Ok(metadata) = fs::metadata(&path) else {
+        return Some(IntegrityIssueKind::Missing);
+    };
+    let Ok(hash) = FileHashUtils::sha256(&path) else {
+        return Some(IntegrityIssueKind::Unreadable);
+    };
+    if hash == photo.hash {
+        return None;
+    }
+    Some(mismatch_kind(photo, &metadata))
+}
+
+/// Hash 不一致的原因【修改时间晚于导入时间为修改过，否则可能是磁盘损坏】
+pub fn mismatch_kind(photo: &Photo, metadata: &fs::Metadata) -> IntegrityIssueKind {
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|x| x.duration_since(UNIX_EPOCH).ok())
+        .map(|x| x.as_secs() as i64);
+    match modified {
+        Some(time) if time > photo.create_time => IntegrityIssueKind::Modified,
+        _ => IntegrityIssueKind::Corrupted,
+    }
+}
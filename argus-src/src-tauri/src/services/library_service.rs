@@ -0,0 +1,168 @@
+use crate::constant::{DEFAULT_LIBRARY_NAME, DEFAULT_PROFILE_NAME};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::i18n::Locale;
+use crate::storage::connection;
+use crate::structs::library::{
+    current_library, set_current_library, LibraryContext, LibraryEntry, LibraryRegistry,
+};
+use crate::utils::{file_util, task_util};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// 照片库信息
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryInfo {
+    /// 名称
+    pub name: String,
+    /// 照片库文件夹
+    pub path: String,
+    /// 是否为默认照片库（程序所在文件夹）
+    pub is_default: bool,
+    /// 是否为当前照片库
+    pub is_current: bool,
+    /// 文件夹是否可以访问
+    pub is_available: bool,
+}
+
+/// 获取所有照片库【默认照片库在最前】
+pub fn list_libraries() -> Result<Vec<LibraryInfo>> {
+    let current = file_util::display_path(&current_library().root);
+    let mut libraries = vec![LibraryEntry {
+        name: DEFAULT_LIBRARY_NAME.to_string(),
+        path: default_library_path()?,
+    }];
+    libraries.extend(LibraryRegistry::load().libraries);
+    Ok(libraries
+        .into_iter()
+        .enumerate()
+        .map(|(i, x)| LibraryInfo {
+            is_default: i == 0,
+            is_current: x.path == current,
+            is_available: Path::new(&x.path).is_dir(),
+            name: x.name,
+            path: x.path,
+        })
+        .collect())
+}
+
+/// 新建照片库并添加到列表【不会切换到新照片库】
+/// - name 名称
+/// - path 照片库文件夹，不能已经是照片库
+pub fn create_library(name: &str, path: &Path) -> Result<LibraryInfo> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(anyhow!(i18n::text(msg::LIBRARY_NAME_REQUIRED)));
+    }
+    if path.join(DEFAULT_PROFILE_NAME).is_file() {
+        return Err(i18n::error(
+            msg::LIBRARY_ALREADY_LIBRARY,
+            &[("path", path.display().to_string())],
+        ));
+    }
+    fs::create_dir_all(path)?;
+    // 生成默认配置文件，数据库在首次切换时创建
+    let library = LibraryContext::open(name, path)?;
+    register(&library)?;
+    log::info!("新建照片库: {} {}", name, path.display());
+    library_info(&library)
+}
+
+/// 打开已有的照片库，添加到列表并切换
+/// - path 照片库文件夹
+/// - name 名称【为空时使用文件夹名称】
+pub fn open_library(path: &Path, name: Option<&str>) -> Result<LibraryInfo> {
+    if !path.join(DEFAULT_PROFILE_NAME).is_file() {
+        return Err(i18n::error(
+            msg::LIBRARY_NOT_LIBRARY,
+            &[("path", path.display().to_string())],
+        ));
+    }
+    let path_str = file_util::display_path(path);
+    if path_str != default_library_path()? && LibraryRegistry::load().find(&path_str).is_none() {
+        let name = name
+            .map(|x| x.trim().to_string())
+            .filter(|x| !x.is_empty())
+            .or_else(|| path.file_name().map(|x| x.to_string_lossy().to_string()))
+            .unwrap_or_else(|| path_str.clone());
+        register(&LibraryContext::open(&name, path)?)?;
+    }
+    switch_library(path)
+}
+
+/// 切换当前照片库
+///
+/// 打开照片库的配置并升级数据库，失败时保持原照片库。切换后的数据库、缩略图缓存和设置立即生效。
+/// 扫描、写入扫描结果或备份进行中时不能切换，避免写入到另一个照片库
+/// - path 照片库文件夹【默认照片库或已添加的照片库】
+pub fn switch_library(path: &Path) -> Result<LibraryInfo> {
+    let path_str = file_util::display_path(path);
+    let mut registry = LibraryRegistry::load();
+    let is_default = path_str == default_library_path()?;
+    let name = if is_default {
+        DEFAULT_LIBRARY_NAME.to_string()
+    } else {
+        registry
+            .find(&path_str)
+            .map(|x| x.name.clone())
+            .ok_or_else(|| i18n::error(msg::LIBRARY_NOT_ADDED, &[("path", path_str.to_string())]))?
+    };
+    if !path.is_dir() {
+        return Err(i18n::error(
+            msg::LIBRARY_INACCESSIBLE,
+            &[("path", path_str.to_string())],
+        ));
+    }
+
+    task_util::ensure_database_idle(msg::DATABASE_ACTION_SWITCH_LIBRARY)?;
+
+    let library = LibraryContext::open(&name, path)?;
+    // 数据库升级成功后才切换，失败时其他线程始终使用原照片库
+    connection::migrate_database(&library).map_err(|e| {
+        i18n::error(
+            msg::LIBRARY_DATABASE_INIT_FAILED,
+            &[("path", path_str.to_string()), ("error", e.to_string())],
+        )
+    })?;
+    set_current_library(library.clone());
+    if let Some(locale) = library.config.locale.as_deref().and_then(Locale::from_str) {
+        i18n::set_locale(locale);
+    }
+
+    registry.current = (!is_default).then_some(path_str);
+    registry.save()?;
+    log::info!("切换照片库: {} {}", library.name, library.root.display());
+    library_info(&library)
+}
+
+/// 添加到照片库列表
+fn register(library: &LibraryContext) -> Result<()> {
+    let path = file_util::display_path(&library.root);
+    let mut registry = LibraryRegistry::load();
+    if path == default_library_path()? || registry.find(&path).is_some() {
+        return Err(i18n::error(
+            msg::LIBRARY_ALREADY_ADDED,
+            &[("path", path.to_string())],
+        ));
+    }
+    registry.libraries.push(LibraryEntry {
+        name: library.name.clone(),
+        path,
+    });
+    registry.save()
+}
+
+fn library_info(library: &LibraryContext) -> Result<LibraryInfo> {
+    let path = file_util::display_path(&library.root);
+    list_libraries()?
+        .into_iter()
+        .find(|x| x.path == path)
+        .ok_or_else(|| i18n::error(msg::LIBRARY_NOT_ADDED, &[("path", path.to_string())]))
+}
+
+fn default_library_path() -> Result<String> {
+    Ok(file_util::display_path(file_util::get_root_folder()?))
+}
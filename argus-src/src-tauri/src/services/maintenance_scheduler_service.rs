@@ -0,0 +1,258 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::services::{
+    backup_service, database_maintenance_service, event_service, exif_backfill_service,
+    integrity_service, reconcile_service, rescan_service, space_saver_service,
+    thumbnail_cache_service,
+};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::config::{save_config, sys_config};
+use crate::structs::maintenance_schedule::{
+    MaintenanceJob, MaintenanceRun, MaintenanceSchedule, ScheduleStatus,
+};
+use crate::utils::cron_util::CronSchedule;
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use chrono::{Local, Timelike};
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 任务执行状态
+#[derive(Default)]
+struct JobState {
+    running: bool,
+    last_run: Option<MaintenanceRun>,
+}
+
+/// 各任务的执行状态【只保存在内存中】
+static JOB_STATES: Lazy<Mutex<HashMap<MaintenanceJob, JobState>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 启动定时维护线程
+///
+/// 每分钟读取一次配置中的计划，修改设置后无需重启。任务依次执行，
+/// 执行期间错过的计划在任务结束后补执行一次
+pub fn start_scheduler() {
+    let spawned = std::thread::Builder::new()
+        .name("argus-scheduler".to_string())
+        .spawn(|| {
+            let mut checked = Local::now();
+            loop {
+                // 等到下一分钟开始
+                let now = Local::now();
+                let elapsed_ms = u64::from(now.second()) * 1000
+                    + u64::from(now.timestamp_subsec_millis().min(999));
+                std::thread::sleep(Duration::from_millis(60_000 - elapsed_ms + 100));
+
+                let now = Local::now();
+                let mut due = Vec::new();
+                for (job, cron) in enabled_schedules() {
+                    if !due.contains(&job) && cron.next_after(&checked).is_some_and(|x| x <= now) {
+                        due.push(job);
+                    }
+                }
+                checked = now;
+                for job in due {
+                    log::info!("开始执行定时维护任务: {:?}", job);
+                    if let Err(e) = run_job(job) {
+                        log::warn!("定时维护任务 {:?} 未执行: {}", job, e);
+                    }
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        log::error!("定时维护线程启动失败: {}", e);
+    }
+}
+
+/// 获取所有定时维护计划的状态
+pub fn get_schedule_status() -> Vec<ScheduleStatus> {
+    let schedules = sys_config()
+        .maintenance_schedules
+        .clone()
+        .unwrap_or_default();
+    let states = JOB_STATES.lock().unwrap();
+    let now = Local::now();
+    schedules
+        .into_iter()
+        .map(|schedule| {
+            let state = states.get(&schedule.job);
+            let next_run = schedule
+                .enabled
+                .then(|| CronSchedule::parse(&schedule.cron).ok())
+                .flatten()
+                .and_then(|x| x.next_after(&now))
+                .map(|x| x.timestamp());
+            ScheduleStatus {
+                job: schedule.job,
+                cron: schedule.cron,
+                enabled: schedule.enabled,
+                running: state.is_some_and(|x| x.running),
+                next_run,
+                last_run: state.and_then(|x| x.last_run.clone()),
+            }
+        })
+        .collect()
+}
+
+/// 保存定时维护计划【替换配置中的所有计划】
+/// - schedules 计划
+pub fn save_schedules(schedules: Vec<MaintenanceSchedule>) -> Result<()> {
+    for schedule in &schedules {
+        CronSchedule::parse(&schedule.cron)?;
+    }
+    let mut config = (*sys_config()).clone();
+    config.maintenance_schedules = Some(schedules);
+    save_config(&config)
+}
+
+/// 立即执行维护任务【同一任务正在执行时返回错误】
+/// - job 任务
+pub fn run_job(job: MaintenanceJob) -> Result<MaintenanceRun> {
+    {
+        let mut states = JOB_STATES.lock().unwrap();
+        let state = states.entry(job).or_default();
+        if state.running {
+            return Err(anyhow!(i18n::text(msg::MAINTENANCE_TASK_RUNNING)));
+        }
+        state.running = true;
+    }
+    let start_time = TimeUtils::current_timestamp();
+    let result = execute(job);
+    let run = MaintenanceRun {
+        start_time,
+        end_time: TimeUtils::current_timestamp(),
+        success: result.is_ok(),
+        message: match &result {
+            Ok(message) => message.clone(),
+            Err(e) => e.to_string(),
+        },
+    };
+    log::info!("维护任务 {:?} 执行完毕: {:?}", job, run);
+
+    let mut states = JOB_STATES.lock().unwrap();
+    let state = states.entry(job).or_default();
+    state.running = false;
+    state.last_run = Some(run.clone());
+    Ok(run)
+}
+
+/// 执行任务，返回结果说明
+fn execute(job: MaintenanceJob) -> Result<String> {
+    match job {
+        MaintenanceJob::Rescan => rescan_storages(),
+        MaintenanceJob::CacheGc => {
+            let report = thumbnail_cache_service::collect_unused_thumbnails()?;
+            Ok(format!(
+                "删除 {} 个缩略图，释放 {} 字节",
+                report.removed, report.freed_bytes
+            ))
+        }
+        MaintenanceJob::OptimizeDatabase => {
+            let report = database_maintenance_service::optimize_database()?;
+            Ok(format!(
+                "数据库大小 {} → {} 字节",
+                report.size_before, report.size_after
+            ))
+        }
+        MaintenanceJob::Backup => {
+            let summary = backup_service::run_auto_backup()?;
+            Ok(format!("备份到 {}", summary.path))
+        }
+        MaintenanceJob::IntegritySample => {
+            let sample = sys_config().integrity_check_sample.unwrap_or_default();
+            integrity_service::check_integrity(sample)?;
+            let report = integrity_service::get_integrity_report().unwrap_or_default();
+            Ok(format!(
+                "校验 {} 张，发现 {} 个问题",
+                report.checked,
+                report.issues.len()
+            ))
+        }
+        MaintenanceJob::RefreshEvents => {
+            let summary = tauri::async_runtime::block_on(event_service::refresh_events())?;
+            Ok(format!(
+                "{} 个事件，包含 {} 张照片",
+                summary.events, summary.photos
+            ))
+        }
+        MaintenanceJob::Reconcile => {
+            let report = reconcile_service::find_missing_photos()?;
+            Ok(format!(
+                "检查 {} 张，卷离线 {} 张，已删除 {} 张",
+                report.checked, report.volume_offline, report.gone
+            ))
+        }
+        MaintenanceJob::SpaceSaver => {
+            let plan = space_saver_service::prepare(None)?;
+            Ok(format!(
+                "{} 个文件可节省 {} 字节，确认后替换原文件",
+                plan.items.len(),
+                plan.saved_bytes()
+            ))
+        }
+        MaintenanceJob::ExifBackfill => {
+            let summary = exif_backfill_service::backfill_exif()?;
+            Ok(format!(
+                "检查 {} 张，补全 {} 张，无法读取 {} 张，原图不存在 {} 张",
+                summary.checked, summary.filled, summary.unreadable, summary.missing
+            ))
+        }
+    }
+}
+
+/// 重新扫描所有在线的存储路径【单个路径失败不影响其他路径】
+fn rescan_storages() -> Result<String> {
+    let mut conn = establish_connection();
+    let roots: HashSet<String> = storage::photo_storage::get_all_photo_path(&mut conn)?
+        .into_iter()
+        .filter(|x| x.is_available)
+        .map(|x| x.img_paths)
+        .collect();
+    let (mut added, mut moved, mut removed) = (0, 0, 0);
+    let mut failed = Vec::new();
+    for root in &roots {
+        match tauri::async_runtime::block_on(rescan_service::rescan_folder(root, false)) {
+            Ok(plan) => {
+                added += plan.added.len();
+                moved += plan.moved.len();
+                removed += plan.removed.len();
+            }
+            Err(e) => {
+                log::warn!("存储路径重新扫描失败 {}: {}", root, e);
+                failed.push(root.clone());
+            }
+        }
+    }
+    if !failed.is_empty() && failed.len() == roots.len() {
+        return Err(i18n::error(
+            msg::MAINTENANCE_RESCAN_ALL_FAILED,
+            &[("paths", failed.join(", "))],
+        ));
+    }
+    let mut message = format!("新增 {}，移动 {}，删除 {}", added, moved, removed);
+    if !failed.is_empty() {
+        message.push_str(&format!("；扫描失败: {}", failed.join(", ")));
+    }
+    Ok(message)
+}
+
+/// 已启用且表达式有效的计划
+fn enabled_schedules() -> Vec<(MaintenanceJob, CronSchedule)> {
+    sys_config()
+        .maintenance_schedules
+        .iter()
+        .flatten()
+        .filter(|x| x.enabled)
+        .filter_map(|x| match CronSchedule::parse(&x.cron) {
+            Ok(cron) => Some((x.job, cron)),
+            Err(e) => {
+                log::warn!("定时维护计划 {:?} 表达式无效: {}", x.job, e);
+                None
+            }
+        })
+        .collect()
+}
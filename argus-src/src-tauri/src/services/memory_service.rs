@@ -0,0 +1,87 @@
+use crate::constant::MEMORY_PREVIEW_PHOTOS;
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::memory::MemoryYear;
+use crate::utils::time_util::TimeUtils;
+use anyhow::Result;
+use chrono::{Datelike, Local, NaiveDate};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+/// 获取那年今日【之前每一年同一天拍摄的照片，按年份从新到旧】
+///
+/// 拍摄日期按拍摄地的当地时间计算
+/// - date 日期【`YYYY-MM-DD`，为空时为今天】
+pub fn get_memories(date: Option<&str>) -> Result<Vec<MemoryYear>> {
+    let date = match date.map(str::trim).filter(|x| !x.is_empty()) {
+        Some(x) => NaiveDate::parse_from_str(x, "%Y-%m-%d")
+            .map_err(|_| i18n::error(msg::DATE_INVALID, &[("date", x.to_string())]))?,
+        None => Local::now().date_naive(),
+    };
+    let mut conn = establish_connection();
+    let photos = storage::memory::search_same_day_photos(
+        &mut conn,
+        &date.format("%m-%d").to_string(),
+        date.year(),
+    )?;
+
+    let mut years: BTreeMap<i32, Vec<Photo>> = BTreeMap::new();
+    for photo in photos {
+        let Some(time) = photo.date_time_original else {
+            continue;
+        };
+        let year = TimeUtils::to_local_date_time(time, photo.offset_time.as_deref()).year();
+        years.entry(year).or_default().push(photo);
+    }
+    Ok(years
+        .into_iter()
+        .rev()
+        .map(|(year, photos)| {
+            let photo_ids: Vec<i32> = photos.iter().map(|x| x.id).collect();
+            let keys: Vec<(i32, Option<i32>)> = photos.iter().map(|x| (x.id, x.rating)).collect();
+            let covers = pick_covers(&keys, MEMORY_PREVIEW_PHOTOS);
+            MemoryYear {
+                year,
+                years_ago: date.year() - year,
+                photo_count: photos.len(),
+                cover_photos: photos
+                    .into_iter()
+                    .filter(|x| covers.contains(&x.id))
+                    .collect(),
+                photo_ids,
+            }
+        })
+        .collect())
+}
+
+/// 挑选代表照片【评分高的优先，评分相同时取拍摄较早的】
+/// - photos 按拍摄时间排序的照片 ID 和评分
+/// - count 数量
+fn pick_covers(photos: &[(i32, Option<i32>)], count: usize) -> Vec<i32> {
+    let mut ranked = photos.to_vec();
+    // 稳定排序，评分相同时保持拍摄时间顺序
+    ranked.sort_by_key(|(_, rating)| Reverse(rating.unwrap_or(0)));
+    ranked.into_iter().take(count).map(|(id, _)| id).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_covers() {
+        let photos = [
+            (1, None),
+            (2, Some(5)),
+            (3, Some(3)),
+            (4, None),
+            (5, Some(5)),
+        ];
+        assert_eq!(pick_covers(&photos, 3), vec![2, 5, 3]);
+        assert_eq!(pick_covers(&photos, 10).len(), 5);
+        assert_eq!(pick_covers(&[(1, None), (2, None)], 1), vec![1]);
+    }
+}
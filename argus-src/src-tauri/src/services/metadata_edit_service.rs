@@ -0,0 +1,617 @@
+use crate::constant::{IMAGE_COMPRESSION_RATIO, MAX_RATING};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::edit_journal::EditJournalRecord;
+use crate::models::photo::Photo;
+use crate::services::thumbnail_cache_service;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::caption_write_back::CaptionWriteBack;
+use crate::structs::edit_journal::{EditChange, EditField, EditSummary};
+use crate::structs::metadata_preset::MetadataPreset;
+use crate::structs::photo_activity::ActivityKind;
+use crate::structs::thumbnail_encoding;
+use crate::utils::exif_utils::exif_util::ExifToolCmd;
+use crate::utils::exif_utils::gps_util::GpsCoordinate;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::file_util;
+use crate::utils::img_util::ImageOperate;
+use crate::utils::json_util::JsonUtil;
+use crate::utils::thumbnail_queue_util::THUMBNAIL_QUEUE;
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use diesel::{Connection, SqliteConnection};
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+
+/// 当前会话 ID【每次启动程序生成，撤销、重做只作用于本次会话的编辑】
+static SESSION_ID: Lazy<String> = Lazy::new(|| {
+    format!(
+        "{}-{}",
+        TimeUtils::current_timestamp_millis(),
+        std::process::id()
+    )
+});
+
+/// 修改照片评分
+/// - photo_ids 照片 ID
+/// - rating 评分【0 - `MAX_RATING`，为空表示清除评分】
+pub fn set_rating(photo_ids: &[i32], rating: Option<i32>) -> Result<EditSummary> {
+    if let Some(rating) = rating.filter(|x| !(0..=MAX_RATING).contains(x)) {
+        return Err(i18n::error(
+            msg::EDIT_RATING_OUT_OF_RANGE,
+            &[
+                ("rating", rating.to_string()),
+                ("max", MAX_RATING.to_string()),
+            ],
+        ));
+    }
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    let changes = photos
+        .iter()
+        .filter(|p| p.rating != rating)
+        .map(|p| EditChange {
+            photo_id: p.id,
+            field: EditField::Rating,
+            before: json!(p.rating),
+            after: json!(rating),
+            write_back: false,
+        })
+        .collect();
+    let description = i18n::t(msg::EDIT_RATING, &[("count", photos.len().to_string())]);
+    record_edit(&mut conn, &description, changes)
+}
+
+/// 平移照片拍摄时间【没有拍摄时间的照片不修改】
+/// - photo_ids 照片 ID
+/// - seconds 平移的秒数，可以为负数
+pub fn shift_date_time(photo_ids: &[i32], seconds: i64) -> Result<EditSummary> {
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    let changes: Vec<EditChange> = photos
+        .iter()
+        .filter_map(|p| {
+            let before = p.date_time_original?;
+            Some(EditChange {
+                photo_id: p.id,
+                field: EditField::DateTimeOriginal,
+                before: json!(before),
+                after: json!(before + seconds),
+                write_back: false,
+            })
+        })
+        .collect();
+    let description = i18n::t(
+        msg::EDIT_SHIFT_TIME,
+        &[
+            ("count", changes.len().to_string()),
+            ("seconds", seconds.to_string()),
+        ],
+    );
+    record_edit(&mut conn, &description, changes)
+}
+
+/// 修改照片位置
+/// - photo_ids 照片 ID
+/// - coordinate 十进制坐标【为空表示清除位置】
+pub fn set_gps(photo_ids: &[i32], coordinate: Option<GpsCoordinate>) -> Result<EditSummary> {
+    let after = match &coordinate {
+        Some(x) => {
+            x.validate()?;
+            Some(JsonUtil::stringify(x)?)
+        }
+        None => None,
+    };
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    let changes = photos
+        .iter()
+        .filter(|p| p.gps_info != after)
+        .map(|p| EditChange {
+            photo_id: p.id,
+            field: EditField::Gps,
+            before: json!(p.gps_info),
+            after: json!(after),
+            write_back: false,
+        })
+        .collect();
+    let description = i18n::t(msg::EDIT_GPS, &[("count", photos.len().to_string())]);
+    record_edit(&mut conn, &description, changes)
+}
+
+/// 替换照片标签
+/// - photo_ids 照片 ID
+/// - tag_ids 新的标签 ID 列表
+pub fn set_tags(photo_ids: &[i32], tag_ids: &[i32]) -> Result<EditSummary> {
+    let mut after = tag_ids.to_vec();
+    after.sort_unstable();
+    after.dedup();
+
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    let mut changes = Vec::new();
+    for photo in &photos {
+        let before = storage::tag::get_photo_tag_ids(&mut conn, photo.id)?;
+        if before != after {
+            changes.push(EditChange {
+                photo_id: photo.id,
+                field: EditField::Tags,
+                before: json!(before),
+                after: json!(after),
+                write_back: false,
+            });
+        }
+    }
+    let description = i18n::t(msg::EDIT_TAGS, &[("count", photos.len().to_string())]);
+    record_edit(&mut conn, &description, changes)
+}
+
+/// 修改照片说明【根据设置同时写回原图或 XMP 附属文件】
+/// - photo_ids 照片 ID
+/// - caption 说明【为空表示清除】
+pub fn set_caption(photo_ids: &[i32], caption: Option<String>) -> Result<EditSummary> {
+    let after = caption
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty());
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    let changes = photos
+        .iter()
+        .filter(|p| p.caption != after)
+        .map(|p| EditChange {
+            photo_id: p.id,
+            field: EditField::Caption,
+            before: json!(p.caption),
+            after: json!(after),
+            write_back: false,
+        })
+        .collect();
+    let description = i18n::t(msg::EDIT_CAPTION, &[("count", photos.len().to_string())]);
+    record_edit(&mut conn, &description, changes)
+}
+
+/// 修改照片备注
+/// - photo_ids 照片 ID
+/// - notes 备注【为空表示清除】
+pub fn set_notes(photo_ids: &[i32], notes: Option<String>) -> Result<EditSummary> {
+    let after = notes
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty());
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    let changes = photos
+        .iter()
+        .filter(|p| p.notes != after)
+        .map(|p| EditChange {
+            photo_id: p.id,
+            field: EditField::Notes,
+            before: json!(p.notes),
+            after: json!(after),
+            write_back: false,
+        })
+        .collect();
+    let description = i18n::t(msg::EDIT_NOTES, &[("count", photos.len().to_string())]);
+    record_edit(&mut conn, &description, changes)
+}
+
+/// 应用元数据预设【预设中为空的字段不修改】
+/// - photo_ids 照片 ID
+/// - preset 元数据预设
+/// - write_back 是否同时写回原图
+pub fn apply_preset(
+    photo_ids: &[i32],
+    preset: &MetadataPreset,
+    write_back: bool,
+) -> Result<EditSummary> {
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    let mut changes = Vec::new();
+    for photo in &photos {
+        let fields = [
+            (EditField::Artist, &photo.artist, &preset.artist),
+            (EditField::Copyright, &photo.copyright, &preset.copyright),
+            (EditField::Credit, &photo.credit, &preset.credit),
+        ];
+        for (field, before, after) in fields {
+            if after.is_some() && before != after {
+                changes.push(EditChange {
+                    photo_id: photo.id,
+                    field,
+                    before: json!(before),
+                    after: json!(after),
+                    write_back,
+                });
+            }
+        }
+    }
+    let description = i18n::t(
+        msg::EDIT_PRESET,
+        &[
+            ("count", photos.len().to_string()),
+            ("name", preset.name.clone()),
+        ],
+    );
+    record_edit(&mut conn, &description, changes)
+}
+
+/// 撤销本会话中最后一次编辑
+pub fn undo_last_edit() -> Result<EditSummary> {
+    let mut conn = establish_connection();
+    let (record, reversed) = undo_in(&mut conn, &SESSION_ID)?
+        .ok_or_else(|| anyhow!(i18n::text(msg::EDIT_NOTHING_TO_UNDO)))?;
+    write_back(&mut conn, &reversed);
+    log::info!("撤销编辑 {}: {}", record.id, record.description);
+    Ok(summary(&record, &reversed, true))
+}
+
+/// 重做本会话中最早一次撤销的编辑
+pub fn redo_edit() -> Result<EditSummary> {
+    let mut conn = establish_connection();
+    let (record, changes) = redo_in(&mut conn, &SESSION_ID)?
+        .ok_or_else(|| anyhow!(i18n::text(msg::EDIT_NOTHING_TO_REDO)))?;
+    write_back(&mut conn, &changes);
+    log::info!("重做编辑 {}: {}", record.id, record.description);
+    Ok(summary(&record, &changes, false))
+}
+
+/// 在数据库中撤销指定会话最后一次编辑，返回编辑记录和恢复的修改【没有可撤销的编辑时返回 None】
+fn undo_in(
+    conn: &mut SqliteConnection,
+    session_id: &str,
+) -> Result<Option<(EditJournalRecord, Vec<EditChange>)>> {
+    let Some(record) = storage::edit_journal::get_last_active_edit(conn, session_id)? else {
+        return Ok(None);
+    };
+    let changes: Vec<EditChange> = JsonUtil::from_json(&record.changes)?;
+    // 倒序恢复，同一照片多次修改时以最早的值为准
+    let reversed: Vec<EditChange> = changes.iter().rev().map(EditChange::reversed).collect();
+    conn.transaction(|conn| {
+        for change in &reversed {
+            apply_change(conn, change)?;
+        }
+        storage::edit_journal::set_edit_undone(conn, record.id, true)
+    })?;
+    Ok(Some((record, reversed)))
+}
+
+/// 在数据库中重做指定会话最早一次撤销的编辑，返回编辑记录和修改【没有可重做的编辑时返回 None】
+fn redo_in(
+    conn: &mut SqliteConnection,
+    session_id: &str,
+) -> Result<Option<(EditJournalRecord, Vec<EditChange>)>> {
+    let Some(record) = storage::edit_journal::get_first_undone_edit(conn, session_id)? else {
+        return Ok(None);
+    };
+    let changes: Vec<EditChange> = JsonUtil::from_json(&record.changes)?;
+    conn.transaction(|conn| {
+        for change in &changes {
+            apply_change(conn, change)?;
+        }
+        storage::edit_journal::set_edit_undone(conn, record.id, false)
+    })?;
+    Ok(Some((record, changes)))
+}
+
+/// 应用修改并写入编辑记录
+fn record_edit(
+    conn: &mut SqliteConnection,
+    description: &str,
+    changes: Vec<EditChange>,
+) -> Result<EditSummary> {
+    if changes.is_empty() {
+        return Err(anyhow!(i18n::text(msg::EDIT_NO_CHANGE)));
+    }
+    let record = save_edit(conn, &SESSION_ID, description, &changes)?;
+    write_back(conn, &changes);
+    Ok(summary(&record, &changes, false))
+}
+
+/// 在一个事务中把修改写入数据库并记录到指定会话
+fn save_edit(
+    conn: &mut SqliteConnection,
+    session_id: &str,
+    description: &str,
+    changes: &[EditChange],
+) -> Result<EditJournalRecord> {
+    conn.transaction(|conn| {
+        for change in changes {
+            apply_change(conn, change)?;
+        }
+        let photo_ids: BTreeSet<i32> = changes.iter().map(|x| x.photo_id).collect();
+        let photo_ids: Vec<i32> = photo_ids.into_iter().collect();
+        storage::photo_activity::insert_activities(
+            conn,
+            &photo_ids,
+            ActivityKind::Edited,
+            Some(description),
+        )?;
+        storage::edit_journal::insert_edit(
+            conn,
+            session_id,
+            description,
+            &JsonUtil::stringify(&changes)?,
+        )
+    })
+}
+
+/// 把修改后的值写入数据库
+fn apply_change(conn: &mut SqliteConnection, change: &EditChange) -> Result<()> {
+    let value = change.after.clone();
+    match change.field {
+        EditField::Rating => {
+            storage::photo_table::update_photo_rating(conn, change.photo_id, from_value(value)?)
+        }
+        EditField::DateTimeOriginal => {
+            storage::photo_table::update_photo_date_time(conn, change.photo_id, from_value(value)?)
+        }
+        EditField::Gps => {
+            storage::photo_table::update_photo_gps(conn, change.photo_id, from_value(value)?)
+        }
+        EditField::Tags => {
+            let tag_ids: Vec<i32> = from_value(value)?;
+            storage::tag::replace_photo_tags(conn, change.photo_id, &tag_ids)
+        }
+        EditField::Caption => {
+            storage::photo_table::update_photo_caption(conn, change.photo_id, from_value(value)?)
+        }
+        EditField::Artist => {
+            storage::photo_table::update_photo_artist(conn, change.photo_id, from_value(value)?)
+        }
+        EditField::Copyright => {
+            storage::photo_table::update_photo_copyright(conn, change.photo_id, from_value(value)?)
+        }
+        EditField::Credit => {
+            storage::photo_table::update_photo_credit(conn, change.photo_id, from_value(value)?)
+        }
+        EditField::Notes => {
+            storage::photo_table::update_photo_notes(conn, change.photo_id, from_value(value)?)
+        }
+    }
+}
+
+/// 把修改写回文件【在数据库事务提交后调用，失败时只记录日志，数据库中的修改仍然有效】
+fn write_back(conn: &mut SqliteConnection, changes: &[EditChange]) {
+    write_back_credits(conn, changes);
+    let mode = CaptionWriteBack::from_config();
+    if mode == CaptionWriteBack::None {
+        return;
+    }
+    // 同一照片多次修改时只写入最后的值
+    let mut captions = BTreeMap::new();
+    for change in changes.iter().filter(|x| x.field == EditField::Caption) {
+        captions.insert(change.photo_id, change.after.clone());
+    }
+    for (photo_id, value) in captions {
+        match from_value::<Option<String>>(value) {
+            Ok(caption) => write_back_caption(conn, mode, photo_id, caption.as_deref()),
+            Err(e) => log::warn!("照片 {} 说明写回失败: {}", photo_id, e),
+        }
+    }
+}
+
+/// 把作者、版权、署名写回原图的 EXIF、IPTC 和 XMP【只写回选择了写回的修改，远程存储中的照片不写回】
+fn write_back_credits(conn: &mut SqliteConnection, changes: &[EditChange]) {
+    // 同一照片同一字段多次修改时只写入最后的值
+    let mut values: BTreeMap<i32, BTreeMap<&'static str, Option<String>>> = BTreeMap::new();
+    for change in changes.iter().filter(|x| x.write_back) {
+        let tags = change.field.credit_tags();
+        if tags.is_empty() {
+            continue;
+        }
+        let value = match from_value::<Option<String>>(change.after.clone()) {
+            Ok(x) => x,
+            Err(e) => {
+                log::warn!("照片 {} 元数据写回失败: {}", change.photo_id, e);
+                continue;
+            }
+        };
+        let photo_tags = values.entry(change.photo_id).or_default();
+        for tag in tags {
+            photo_tags.insert(tag, value.clone());
+        }
+    }
+    for (photo_id, tags) in values {
+        let photo = match storage::photo_table::search_photos_by_ids(conn, &[photo_id]) {
+            Ok(mut x) if !x.is_empty() => x.remove(0),
+            _ => continue,
+        };
+        if photo.is_remote() {
+            continue;
+        }
+        let path = photo.full_path();
+        let tags: Vec<(&str, Option<&str>)> =
+            tags.iter().map(|(tag, x)| (*tag, x.as_deref())).collect();
+        if let Err(e) = ExifToolCmd
+            .write_tags(&path, &tags)
+            .and_then(|_| refresh_file_info(conn, &photo))
+        {
+            log::warn!("元数据写回失败 {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// 把照片说明写回原图或 XMP 附属文件【远程存储中的照片不写回】
+fn write_back_caption(
+    conn: &mut SqliteConnection,
+    mode: CaptionWriteBack,
+    photo_id: i32,
+    caption: Option<&str>,
+) {
+    let photo = match storage::photo_table::search_photos_by_ids(conn, &[photo_id]) {
+        Ok(mut x) if !x.is_empty() => x.remove(0),
+        _ => return,
+    };
+    if photo.is_remote() {
+        return;
+    }
+    let path = photo.full_path();
+    let result = match mode {
+        CaptionWriteBack::File => ExifToolCmd
+            .write_tags(
+                &path,
+                &[
+                    ("EXIF:ImageDescription", caption),
+                    ("XMP-dc:Description", caption),
+                ],
+            )
+            .and_then(|_| refresh_file_info(conn, &photo)),
+        CaptionWriteBack::Sidecar => ExifToolCmd.write_tags(
+            &file_util::sidecar_path(&path),
+            &[("XMP-dc:Description", caption)],
+        ),
+        CaptionWriteBack::None => Ok(()),
+    };
+    if let Err(e) = result {
+        log::warn!("照片说明写回失败 {}: {}", path.display(), e);
+    }
+}
+
+/// 原图写回元数据后更新数据库中的 Hash 和文件大小
+///
+/// 按新的 Hash 在后台重新生成缩略图，旧 Hash 的缩略图等数据没有其他照片使用时删除
+fn refresh_file_info(conn: &mut SqliteConnection, photo: &Photo) -> Result<()> {
+    let path = photo.full_path();
+    let hash = FileHashUtils::sha256(file_util::long_path(&path))?;
+    if hash == photo.hash {
+        return Ok(());
+    }
+    let file_size = fs::metadata(file_util::long_path(&path))?.len() as i64;
+    storage::photo_table::update_photo_hash(conn, photo.id, &hash, file_size)?;
+    storage::photo_purge::delete_unused_hash_data(conn, &[photo.hash.clone()])?;
+    thumbnail_cache_service::remove_unused_thumbnails(conn, &photo.hash);
+    tauri::async_runtime::spawn(async move {
+        let _permit = THUMBNAIL_QUEUE.acquire(&path).await;
+        if let Err(e) = ImageOperate::multi_level_image_compression_with_exif(
+            &path,
+            thumbnail_encoding::storage_format(),
+            IMAGE_COMPRESSION_RATIO.to_vec(),
+            None,
+        )
+        .await
+        {
+            log::warn!("缩略图重新生成失败 {}: {}", path.display(), e);
+        }
+    });
+    Ok(())
+}
+
+fn from_value<T: serde::de::DeserializeOwned>(value: Value) -> Result<T> {
+    serde_json::from_value(value)
+        .map_err(|e| i18n::error(msg::EDIT_JOURNAL_INVALID, &[("error", e.to_string())]))
+}
+
+fn summary(record: &EditJournalRecord, changes: &[EditChange], is_undone: bool) -> EditSummary {
+    let mut photo_ids: Vec<i32> = changes.iter().map(|x| x.photo_id).collect();
+    photo_ids.sort_unstable();
+    photo_ids.dedup();
+    EditSummary {
+        edit_id: record.id,
+        description: record.description.clone(),
+        photo_count: photo_ids.len(),
+        is_undone,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::photo::NewPhoto;
+    use crate::storage::connection::test_connection;
+
+    /// 新增一张有拍摄时间、没有评分的照片
+    fn insert_photo(conn: &mut SqliteConnection, name: &str) -> Photo {
+        let photo = NewPhoto {
+            img_path: "/photos".to_string(),
+            img_name: format!("{}.jpg", name),
+            img_path_raw: None,
+            hash: name.to_string(),
+            width: 4000,
+            height: 3000,
+            aspect_ratio: 1.33,
+            file_size: 1024,
+            format: "image/jpeg".to_string(),
+            validation_level: 0,
+            is_animated: false,
+            frame_count: None,
+            animation_duration: None,
+            create_time: 0,
+            update_time: 0,
+        };
+        let photo = storage::photo_table::bulk_upsert(conn, vec![(photo, None)]).unwrap();
+        storage::photo_table::update_photo_date_time(conn, photo[0].id, Some(1_000)).unwrap();
+        find(conn, photo[0].id)
+    }
+
+    fn find(conn: &mut SqliteConnection, photo_id: i32) -> Photo {
+        storage::photo_table::search_photos_by_ids(conn, &[photo_id])
+            .unwrap()
+            .remove(0)
+    }
+
+    fn change(photo_id: i32, field: EditField, before: Value, after: Value) -> EditChange {
+        EditChange {
+            photo_id,
+            field,
+            before,
+            after,
+            write_back: false,
+        }
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut conn = test_connection();
+        let a = insert_photo(&mut conn, "a");
+        let b = insert_photo(&mut conn, "b");
+        let shift: Vec<EditChange> = [a.id, b.id]
+            .iter()
+            .map(|id| change(*id, EditField::DateTimeOriginal, json!(1_000), json!(8_200)))
+            .collect();
+        save_edit(&mut conn, "s", "shift", &shift).unwrap();
+        let rating = vec![change(a.id, EditField::Rating, json!(null), json!(4))];
+        save_edit(&mut conn, "s", "rating", &rating).unwrap();
+        assert_eq!(find(&mut conn, a.id).rating, Some(4));
+
+        // 按相反的顺序撤销
+        let (record, _) = undo_in(&mut conn, "s").unwrap().unwrap();
+        assert_eq!(record.description, "rating");
+        assert_eq!(find(&mut conn, a.id).rating, None);
+        let (record, reversed) = undo_in(&mut conn, "s").unwrap().unwrap();
+        assert_eq!(record.description, "shift");
+        assert_eq!(reversed.len(), 2);
+        assert_eq!(find(&mut conn, b.id).date_time_original, Some(1_000));
+        assert!(undo_in(&mut conn, "s").unwrap().is_none());
+        // 其他会话的编辑不受影响
+        assert!(redo_in(&mut conn, "other").unwrap().is_none());
+
+        // 按原来的顺序重做
+        let (record, _) = redo_in(&mut conn, "s").unwrap().unwrap();
+        assert_eq!(record.description, "shift");
+        assert_eq!(find(&mut conn, a.id).date_time_original, Some(8_200));
+        assert_eq!(find(&mut conn, a.id).rating, None);
+
+        // 新的编辑后不能再重做之前撤销的编辑
+        let notes = vec![change(b.id, EditField::Notes, json!(null), json!("x"))];
+        save_edit(&mut conn, "s", "notes", &notes).unwrap();
+        assert!(redo_in(&mut conn, "s").unwrap().is_none());
+        assert_eq!(find(&mut conn, a.id).rating, None);
+    }
+
+    #[test]
+    fn test_undo_same_photo_twice() {
+        let mut conn = test_connection();
+        let a = insert_photo(&mut conn, "a");
+        // 同一次编辑中多次修改同一照片时，撤销后恢复最早的值
+        let changes = vec![
+            change(a.id, EditField::Rating, json!(null), json!(2)),
+            change(a.id, EditField::Rating, json!(2), json!(5)),
+        ];
+        save_edit(&mut conn, "s", "rating", &changes).unwrap();
+        assert_eq!(find(&mut conn, a.id).rating, Some(5));
+        undo_in(&mut conn, "s").unwrap().unwrap();
+        assert_eq!(find(&mut conn, a.id).rating, None);
+    }
+}
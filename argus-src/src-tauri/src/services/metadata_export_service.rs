@@ -0,0 +1,255 @@
+use crate::constant::METADATA_BATCH_SIZE;
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::{Photo, PhotoMetadataChangeset};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::metadata_export::{
+    MetadataExport, MetadataImportSummary, PhotoMetadata, METADATA_CSV_HEADERS,
+    METADATA_EXPORT_VERSION,
+};
+use crate::structs::organization_tree::NodePathIndex;
+use crate::utils::json_util::JsonUtil;
+use crate::utils::{csv_util, file_util};
+use anyhow::{anyhow, Result};
+use diesel::{Connection, SqliteConnection};
+use std::collections::HashMap;
+
+/// 导出格式
+pub const EXPORT_FORMAT_JSON: &str = "json";
+pub const EXPORT_FORMAT_CSV: &str = "csv";
+
+/// 导出照片元数据（EXIF、标签、评分、位置等）
+/// - path 导出路径
+/// - photo_ids 照片 ID【为空时导出所有照片】
+/// - format 导出格式【json、csv】
+///
+/// 返回导出的照片数量
+pub fn export_metadata(path: &str, photo_ids: Option<Vec<i32>>, format: &str) -> Result<usize> {
+    let format = format.to_lowercase();
+    if format != EXPORT_FORMAT_JSON && format != EXPORT_FORMAT_CSV {
+        return Err(i18n::error(
+            msg::EXPORT_FORMAT_UNSUPPORTED,
+            &[("format", format.to_string())],
+        ));
+    }
+    let mut conn = establish_connection();
+    let photos = match photo_ids {
+        Some(ids) => {
+            let mut photos = Vec::new();
+            for chunk in ids.chunks(METADATA_BATCH_SIZE) {
+                photos.extend(storage::photo_table::search_photos_by_ids(
+                    &mut conn, chunk,
+                )?);
+            }
+            photos.sort_by_key(|x| x.id);
+            photos
+        }
+        None => storage::photo_table::search_all_photos(&mut conn)?,
+    };
+    let tag_index = load_tag_index(&mut conn)?;
+
+    let mut items = Vec::with_capacity(photos.len());
+    for chunk in photos.chunks(METADATA_BATCH_SIZE) {
+        let ids: Vec<i32> = chunk.iter().map(|x| x.id).collect();
+        let tag_ids = storage::tag::get_photos_tag_ids(&mut conn, &ids)?;
+        for photo in chunk {
+            let tags = tag_ids
+                .get(&photo.id)
+                .map(|ids| {
+                    ids.iter()
+                        .filter_map(|id| tag_index.path_of(*id).cloned())
+                        .collect()
+                })
+                .unwrap_or_default();
+            items.push(PhotoMetadata::from_photo(photo, tags));
+        }
+    }
+
+    let content = if format == EXPORT_FORMAT_JSON {
+        serde_json::to_string_pretty(&MetadataExport {
+            version: METADATA_EXPORT_VERSION,
+            photos: items,
+        })?
+    } else {
+        let mut rows = vec![METADATA_CSV_HEADERS.iter().map(|x| x.to_string()).collect()];
+        rows.extend(items.iter().map(PhotoMetadata::to_csv_row));
+        csv_util::to_csv(&rows)
+    };
+    file_util::write_text_file(path, &content).map_err(|e| anyhow!(e))?;
+    log::info!("导出 {} 张照片的元数据到 {}", photos.len(), path);
+    Ok(photos.len())
+}
+
+/// 从文件导入照片元数据【根据文件内容自动识别 json、csv】
+///
+/// 按 hash 匹配照片：评分、说明、备注、拍摄时间、位置使用文件中的值，
+/// 相机等 EXIF 信息只补充数据库中为空的字段，标签只新增不删除，不存在的标签自动创建
+pub fn import_metadata(path: &str) -> Result<MetadataImportSummary> {
+    let content = file_util::read_text_file(path).map_err(|e| anyhow!(e))?;
+    let items = parse_metadata(&content)?;
+
+    let mut conn = establish_connection();
+    let summary = conn.transaction::<_, anyhow::Error, _>(|conn| {
+        let mut summary = MetadataImportSummary {
+            total: items.len() as u32,
+            ..Default::default()
+        };
+        let mut tag_index = load_tag_index(conn)?;
+        for chunk in items.chunks(METADATA_BATCH_SIZE) {
+            let hashes: Vec<String> = chunk.iter().map(|x| x.hash.clone()).collect();
+            let photos: HashMap<String, Photo> =
+                storage::photo_table::search_photos_by_hashes(conn, &hashes)?
+                    .into_iter()
+                    .map(|x| (x.hash.clone(), x))
+                    .collect();
+            for item in chunk {
+                let Some(photo) = photos.get(&item.hash) else {
+                    summary.unmatched.push(item.hash.clone());
+                    continue;
+                };
+                summary.matched += 1;
+                let mut updated = false;
+                if let Some(changeset) = merge_changeset(photo, item) {
+                    storage::photo_table::update_photo_metadata(conn, photo.id, &changeset)?;
+                    updated = true;
+                }
+                let mut tag_ids = Vec::new();
+                for tag_path in &item.tags {
+                    if let Some(id) =
+                        ensure_tag(conn, &mut tag_index, tag_path, &mut summary.tags_created)?
+                    {
+                        tag_ids.push(id);
+                    }
+                }
+                if storage::tag::add_photo_tags(conn, photo.id, &tag_ids)? > 0 {
+                    updated = true;
+                }
+                if updated {
+                    summary.updated += 1;
+                }
+            }
+        }
+        Ok(summary)
+    })?;
+    log::info!(
+        "导入元数据 {}: 共 {} 条，匹配 {} 张，修改 {} 张",
+        path,
+        summary.total,
+        summary.matched,
+        summary.updated
+    );
+    Ok(summary)
+}
+
+/// 解析导入文件
+fn parse_metadata(content: &str) -> Result<Vec<PhotoMetadata>> {
+    if content.trim_start().starts_with('{') {
+        let export: MetadataExport = JsonUtil::from_json(content)?;
+        return Ok(export.photos);
+    }
+    let rows = csv_util::from_csv(content)?;
+    let Some((headers, rows)) = rows.split_first() else {
+        return Ok(Vec::new());
+    };
+    if !headers.iter().any(|x| x.trim() == "hash") {
+        return Err(anyhow!(i18n::text(msg::METADATA_CSV_HASH_MISSING)));
+    }
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            // 表头为第 1 行
+            PhotoMetadata::from_csv_row(headers, row).map_err(|e| {
+                i18n::error(
+                    msg::METADATA_CSV_ROW_INVALID,
+                    &[("line", (i + 2).to_string()), ("error", e.to_string())],
+                )
+            })
+        })
+        .collect()
+}
+
+/// 计算需要修改的字段，没有修改时返回 None
+fn merge_changeset(photo: &Photo, item: &PhotoMetadata) -> Option<PhotoMetadataChangeset> {
+    let mut changed = false;
+    // 使用文件中的值
+    fn replace<T: Clone + PartialEq>(
+        current: &Option<T>,
+        value: &Option<T>,
+        changed: &mut bool,
+    ) -> Option<T> {
+        match value {
+            Some(x) if current.as_ref() != Some(x) => {
+                *changed = true;
+                Some(x.clone())
+            }
+            _ => None,
+        }
+    }
+    // 只补充数据库中为空的字段
+    fn fill<T: Clone>(current: &Option<T>, value: &Option<T>, changed: &mut bool) -> Option<T> {
+        match (current, value) {
+            (None, Some(x)) => {
+                *changed = true;
+                Some(x.clone())
+            }
+            _ => None,
+        }
+    }
+    let changeset = PhotoMetadataChangeset {
+        rating: replace(&photo.rating, &item.rating, &mut changed),
+        caption: replace(&photo.caption, &item.caption, &mut changed),
+        notes: replace(&photo.notes, &item.notes, &mut changed),
+        date_time_original: replace(
+            &photo.date_time_original,
+            &item.date_time_original,
+            &mut changed,
+        ),
+        offset_time: replace(&photo.offset_time, &item.offset_time, &mut changed),
+        gps_info: replace(&photo.gps_info, &item.gps_info, &mut changed),
+        location_name: replace(&photo.location_name, &item.location_name, &mut changed),
+        make: fill(&photo.make, &item.make, &mut changed),
+        model: fill(&photo.model, &item.model, &mut changed),
+        software: fill(&photo.software, &item.software, &mut changed),
+        exposure_time: fill(&photo.exposure_time, &item.exposure_time, &mut changed),
+        flash: fill(&photo.flash, &item.flash, &mut changed),
+        f_number: fill(&photo.f_number, &item.f_number, &mut changed),
+        iso: fill(&photo.iso, &item.iso, &mut changed),
+        max_aperture_value: fill(
+            &photo.max_aperture_value,
+            &item.max_aperture_value,
+            &mut changed,
+        ),
+        focal_length: fill(&photo.focal_length, &item.focal_length, &mut changed),
+        exposure_program: fill(
+            &photo.exposure_program,
+            &item.exposure_program,
+            &mut changed,
+        ),
+        metering_mode: fill(&photo.metering_mode, &item.metering_mode, &mut changed),
+    };
+    changed.then_some(changeset)
+}
+
+/// 读取所有标签的路径
+pub(crate) fn load_tag_index(conn: &mut SqliteConnection) -> Result<NodePathIndex> {
+    let tags: Vec<(i32, Option<i32>, String)> = storage::tag::get_all_tags(conn)?
+        .into_iter()
+        .map(|x| (x.id, x.parent_id, x.name))
+        .collect();
+    Ok(NodePathIndex::new(&tags))
+}
+
+/// 按路径查找标签，不存在时逐级创建
+pub(crate) fn ensure_tag(
+    conn: &mut SqliteConnection,
+    index: &mut NodePathIndex,
+    tag_path: &str,
+    created: &mut u32,
+) -> Result<Option<i32>> {
+    let (id, count) = index.ensure(tag_path, |name, parent| {
+        Ok(storage::tag::insert_tag(conn, name, parent)?.id)
+    })?;
+    *created += count;
+    Ok(id)
+}
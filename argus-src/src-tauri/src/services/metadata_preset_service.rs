@@ -0,0 +1,68 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::services::metadata_edit_service;
+use crate::structs::config::{save_config, sys_config};
+use crate::structs::edit_journal::EditSummary;
+use crate::structs::metadata_preset::MetadataPreset;
+use crate::utils::uuid_util;
+use anyhow::{anyhow, Result};
+
+/// 所有元数据预设
+pub fn list_presets() -> Vec<MetadataPreset> {
+    sys_config().metadata_presets.clone().unwrap_or_default()
+}
+
+/// 新建或修改元数据预设【ID 为空时新建】
+pub fn save_preset(preset: MetadataPreset) -> Result<MetadataPreset> {
+    let mut preset = preset.normalized();
+    if preset.name.is_empty() {
+        return Err(anyhow!(i18n::text(msg::PRESET_NAME_REQUIRED)));
+    }
+    if preset.is_empty() {
+        return Err(i18n::error(
+            msg::PRESET_EMPTY,
+            &[("name", preset.name.to_string())],
+        ));
+    }
+    let mut config = (*sys_config()).clone();
+    let presets = config.metadata_presets.get_or_insert_with(Vec::new);
+    if preset.id.is_empty() {
+        preset.id = uuid_util::uuid_v4();
+        presets.push(preset.clone());
+    } else {
+        let existing = presets
+            .iter_mut()
+            .find(|x| x.id == preset.id)
+            .ok_or_else(|| i18n::error(msg::PRESET_NOT_FOUND, &[("id", preset.id.to_string())]))?;
+        *existing = preset.clone();
+    }
+    save_config(&config)?;
+    Ok(preset)
+}
+
+/// 删除元数据预设
+pub fn delete_preset(preset_id: &str) -> Result<()> {
+    let mut config = (*sys_config()).clone();
+    let presets = config.metadata_presets.get_or_insert_with(Vec::new);
+    let count = presets.len();
+    presets.retain(|x| x.id != preset_id);
+    if presets.len() == count {
+        return Err(i18n::error(
+            msg::PRESET_NOT_FOUND,
+            &[("id", preset_id.to_string())],
+        ));
+    }
+    save_config(&config)
+}
+
+/// 为照片应用元数据预设【可撤销】
+/// - photo_ids 照片 ID
+/// - preset_id 预设 ID
+/// - write_back 是否同时写回原图
+pub fn apply_preset(photo_ids: &[i32], preset_id: &str, write_back: bool) -> Result<EditSummary> {
+    let preset = list_presets()
+        .into_iter()
+        .find(|x| x.id == preset_id)
+        .ok_or_else(|| i18n::error(msg::PRESET_NOT_FOUND, &[("id", preset_id.to_string())]))?;
+    metadata_edit_service::apply_preset(photo_ids, &preset, write_back)
+}
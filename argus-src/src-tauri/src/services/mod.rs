@@ -1,2 +1,61 @@
 pub mod photo_photo_service;
 pub mod post_service;
+pub mod organization_service;
+pub mod thumbnail_cache_service;
+pub mod scan_report_service;
+pub mod volume_service;
+pub mod metadata_edit_service;
+pub mod backup_service;
+pub mod photo_stack_service;
+pub mod rescan_service;
+pub mod photo_file_service;
+pub mod integrity_service;
+pub mod library_service;
+pub mod settings_service;
+pub mod metadata_export_service;
+pub mod external_library_service;
+pub mod http_server_service;
+pub mod slideshow_service;
+pub mod photo_export_service;
+pub mod histogram_service;
+pub mod color_service;
+pub mod photo_transform_service;
+pub mod metadata_preset_service;
+pub mod privacy_service;
+pub mod place_service;
+pub mod photo_sequence_service;
+pub mod card_import_service;
+pub mod share_bundle_service;
+pub mod photo_activity_service;
+pub mod duplicate_reclaim_service;
+pub mod vault_service;
+pub mod checksum_manifest_service;
+pub mod database_maintenance_service;
+pub mod maintenance_scheduler_service;
+pub mod display_cache_service;
+pub mod tiff_page_service;
+pub mod mp_image_service;
+pub mod problem_service;
+pub mod hash_verify_service;
+pub mod photo_compare_service;
+pub mod cover_service;
+pub mod organize_rule_service;
+pub mod event_service;
+pub mod recent_feed_service;
+pub mod memory_service;
+pub mod batch_operation_service;
+pub mod photo_list_service;
+pub mod resize_benchmark_service;
+pub mod disk_space_service;
+pub mod reconcile_service;
+pub mod folder_album_service;
+pub mod user_profile_service;
+pub mod directory_access_service;
+pub mod cloud_file_service;
+pub mod remote_storage_service;
+pub mod watch_folder_service;
+pub mod space_saver_service;
+pub mod geocode_service;
+pub mod exif_backfill_service;
+#[cfg(feature = "http-server")]
+pub mod http_api;
@@ -0,0 +1,54 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::mp_image::MpImageInfo;
+use crate::utils::exif_utils::jpeg_segment;
+use crate::utils::exif_utils::mpf::{self, MpImage};
+use crate::utils::file_util;
+use anyhow::Result;
+
+/// 获取照片中 MPF 记录的图像【不是 JPEG 或没有 MPF 时为空】
+/// - photo_id 照片 ID
+pub fn get_photo_mp_images(photo_id: i32) -> Result<Vec<MpImageInfo>> {
+    let photo = find_photo(photo_id)?;
+    Ok(mp_images(&photo)
+        .iter()
+        .enumerate()
+        .map(|(index, image)| MpImageInfo::new(index, image))
+        .collect())
+}
+
+/// 读取照片中 MPF 记录的图像（JPEG 数据）
+/// - photo_id 照片 ID
+/// - index 图像序号【见 `get_photo_mp_images`】
+pub fn read_photo_mp_image(photo_id: i32, index: usize) -> Result<Vec<u8>> {
+    let photo = find_photo(photo_id)?;
+    let image = mp_images(&photo).into_iter().nth(index).ok_or_else(|| {
+        i18n::error(
+            msg::MP_IMAGE_NOT_FOUND,
+            &[("id", photo_id.to_string()), ("index", index.to_string())],
+        )
+    })?;
+    mpf::read_mp_image(&file_util::long_path(photo.full_path()), &image)
+}
+
+fn find_photo(photo_id: i32) -> Result<Photo> {
+    let mut conn = establish_connection();
+    storage::photo_table::search_photos_by_ids(&mut conn, &[photo_id])?
+        .pop()
+        .ok_or_else(|| i18n::error(msg::PHOTO_NOT_FOUND, &[("id", photo_id.to_string())]))
+}
+
+/// 照片中 MPF 记录的图像【读取失败时为空】
+fn mp_images(photo: &Photo) -> Vec<MpImage> {
+    let path = photo.full_path();
+    match jpeg_segment::read_jpeg_metadata(file_util::long_path(&path)) {
+        Ok(metadata) => metadata.mp_images,
+        Err(e) => {
+            log::debug!("JPEG 元数据读取失败 {}: {}", path.display(), e);
+            Vec::new()
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::organization_tree::{
+    OrganizationImportSummary, OrganizationNode, OrganizationTree, ORGANIZATION_TREE_VERSION,
+};
+use crate::utils::json_util::JsonUtil;
+use crate::utils::{file_util, opml_util};
+use anyhow::{anyhow, Result};
+use diesel::{Connection, SqliteConnection};
+use std::collections::HashSet;
+
+/// 导出格式
+pub const EXPORT_FORMAT_JSON: &str = "json";
+pub const EXPORT_FORMAT_OPML: &str = "opml";
+
+/// 读取完整的相册、标签树
+pub fn get_organization_tree() -> Result<OrganizationTree> {
+    let mut conn = establish_connection();
+
+    let albums: Vec<(i32, Option<i32>, String)> =
+        storage::album::get_all_albums(&mut conn)?
+            .into_iter()
+            .map(|x| (x.id, x.parent_id, x.name))
+            .collect();
+    let album_counts = storage::album::count_album_photos(&mut conn)?;
+
+    let tags: Vec<(i32, Option<i32>, String)> = storage::tag::get_all_tags(&mut conn)?
+        .into_iter()
+        .map(|x| (x.id, x.parent_id, x.name))
+        .collect();
+    let tag_counts = storage::tag::count_tag_photos(&mut conn)?;
+
+    Ok(OrganizationTree {
+        version: ORGANIZATION_TREE_VERSION,
+        albums: OrganizationNode::build_tree(&albums, &album_counts),
+        tags: OrganizationNode::build_tree(&tags, &tag_counts),
+    })
+}
+
+/// 导出组织结构到文件
+/// - path 导出路径
+/// - format 导出格式【json、opml】
+pub fn export_organization(path: &str, format: &str) -> Result<()> {
+    let tree = get_organization_tree()?;
+    let content = match format.to_lowercase().as_str() {
+        EXPORT_FORMAT_JSON => serde_json::to_string_pretty(&tree)?,
+        EXPORT_FORMAT_OPML => opml_util::to_opml(&tree)?,
+        _ => {
+            return Err(i18n::error(
+                msg::EXPORT_FORMAT_UNSUPPORTED,
+                &[("format", format.to_string())],
+            ))
+        }
+    };
+    file_util::write_text_file(path, &content).map_err(|e| anyhow!(e))
+}
+
+/// 从文件导入组织结构
+///
+/// 有 ID 的节点更新名称和层级，没有 ID 的节点新建；文件中不存在的节点保持不变
+pub fn import_organization(path: &str) -> Result<OrganizationImportSummary> {
+    let content = file_util::read_text_file(path).map_err(|e| anyhow!(e))?;
+    let tree: OrganizationTree = if content.trim_start().starts_with('{') {
+        JsonUtil::from_json(&content)?
+    } else {
+        opml_util::from_opml(&content)?
+    };
+
+    let mut conn = establish_connection();
+    conn.transaction::<_, anyhow::Error, _>(|conn| {
+        let mut summary = OrganizationImportSummary::default();
+
+        let albums = storage::album::get_all_albums(conn)?;
+        let existing: HashSet<i32> = albums.iter().map(|x| x.id).collect();
+        // 文件夹相册由文件夹结构决定，导入时跳过
+        let mirrored: HashSet<i32> = albums
+            .iter()
+            .filter(|x| x.source_path.is_some())
+            .map(|x| x.id)
+            .collect();
+        let mut seen = HashSet::new();
+        for node in &tree.albums {
+            import_album(
+                conn,
+                node,
+                None,
+                &existing,
+                &mirrored,
+                &mut seen,
+                &mut summary,
+            )?;
+        }
+
+        let existing: HashSet<i32> = storage::tag::get_all_tags(conn)?
+            .iter()
+            .map(|x| x.id)
+            .collect();
+        let mut seen = HashSet::new();
+        for node in &tree.tags {
+            import_tag(conn, node, None, &existing, &mut seen, &mut summary)?;
+        }
+        Ok(summary)
+    })
+}
+
+/// 递归导入相册节点【文件夹相册及其子节点跳过】
+fn import_album(
+    conn: &mut SqliteConnection,
+    node: &OrganizationNode,
+    parent_id: Option<i32>,
+    existing: &HashSet<i32>,
+    mirrored: &HashSet<i32>,
+    seen: &mut HashSet<i32>,
+    summary: &mut OrganizationImportSummary,
+) -> Result<()> {
+    if node.id.is_some_and(|id| mirrored.contains(&id)) {
+        return Ok(());
+    }
+    let id = match node.id.filter(|id| existing.contains(id)) {
+        Some(id) => {
+            if !seen.insert(id) {
+                return Err(i18n::error(
+                    msg::ORGANIZATION_ALBUM_DUPLICATED,
+                    &[("id", id.to_string())],
+                ));
+            }
+            storage::album::update_album_node(conn, id, &node.name, parent_id)?;
+            summary.albums_updated += 1;
+            id
+        }
+        None => {
+            let album = storage::album::insert_album(conn, &node.name, parent_id)?;
+            summary.albums_created += 1;
+            album.id
+        }
+    };
+    for child in &node.children {
+        import_album(conn, child, Some(id), existing, mirrored, seen, summary)?;
+    }
+    Ok(())
+}
+
+/// 递归导入标签节点
+fn import_tag(
+    conn: &mut SqliteConnection,
+    node: &OrganizationNode,
+    parent_id: Option<i32>,
+    existing: &HashSet<i32>,
+    seen: &mut HashSet<i32>,
+    summary: &mut OrganizationImportSummary,
+) -> Result<()> {
+    let id = match node.id.filter(|id| existing.contains(id)) {
+        Some(id) => {
+            if !seen.insert(id) {
+                return Err(i18n::error(
+                    msg::ORGANIZATION_TAG_DUPLICATED,
+                    &[("id", id.to_string())],
+                ));
+            }
+            storage::tag::update_tag_node(conn, id, &node.name, parent_id)?;
+            summary.tags_updated += 1;
+            id
+        }
+        None => {
+            let tag = storage::tag::insert_tag(conn, &node.name, parent_id)?;
+            summary.tags_created += 1;
+            tag.id
+        }
+    };
+    for child in &node.children {
+        import_tag(conn, child, Some(id), existing, seen, summary)?;
+    }
+    Ok(())
+}
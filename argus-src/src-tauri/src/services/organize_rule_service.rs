@@ -0,0 +1,191 @@
+use crate::constant::SQLITE_MAX_VARIABLES;
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::organize_rule::{NewOrganizeRule, OrganizeRuleRecord};
+use crate::models::photo::Photo;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::organize_rule::{OrganizeRule, RuleAction, RuleCondition, RuleDryRun};
+use crate::utils::json_util::JsonUtil;
+use crate::utils::time_util::TimeUtils;
+use anyhow::Result;
+use diesel::{Connection, SqliteConnection};
+use std::collections::HashSet;
+
+/// 获取所有规则
+pub fn list_rules() -> Result<Vec<OrganizeRule>> {
+    let mut conn = establish_connection();
+    storage::organize_rule::get_all_rules(&mut conn)?
+        .into_iter()
+        .map(to_rule)
+        .collect()
+}
+
+/// 保存规则【ID 为空时新增】
+pub fn save_rule(rule: OrganizeRule) -> Result<OrganizeRule> {
+    rule.validate()?;
+    let item = NewOrganizeRule {
+        name: rule.name.trim().to_string(),
+        conditions: JsonUtil::stringify(&rule.conditions)?,
+        action: rule.action.code(),
+        target: rule.target.trim().to_string(),
+        is_enable: rule.enabled,
+        update_time: TimeUtils::current_timestamp(),
+    };
+    let mut conn = establish_connection();
+    let record = match rule.id {
+        Some(id) => storage::organize_rule::update_rule(&mut conn, id, &item)?,
+        None => storage::organize_rule::insert_rule(&mut conn, &item)?,
+    };
+    to_rule(record)
+}
+
+/// 删除规则【已执行的标签和相册不变】
+pub fn delete_rule(id: i32) -> Result<()> {
+    let mut conn = establish_connection();
+    storage::organize_rule::delete_rule(&mut conn, id)
+}
+
+/// 对新导入的照片执行启用的规则，返回执行的次数
+///
+/// 标签和相册不存在时新建；同一照片满足多条规则时全部执行
+pub fn apply_rules(connection: &mut SqliteConnection, photos: &[Photo]) -> Result<usize> {
+    if photos.is_empty() {
+        return Ok(0);
+    }
+    // 无法解析的规则跳过，不影响其他规则
+    let rules: Vec<OrganizeRule> = storage::organize_rule::get_enabled_rules(connection)?
+        .into_iter()
+        .filter_map(|x| to_rule(x).map_err(|e| log::warn!("{}", e)).ok())
+        .collect();
+    if rules.is_empty() {
+        return Ok(0);
+    }
+    connection.transaction(|conn| {
+        let mut applied = 0;
+        for rule in &rules {
+            let matched: Vec<i32> = photos
+                .iter()
+                .filter(|x| rule.matches(x))
+                .map(|x| x.id)
+                .collect();
+            if matched.is_empty() {
+                continue;
+            }
+            match rule.action {
+                RuleAction::AddTag => {
+                    let tag = storage::tag::get_or_insert_tag(conn, &rule.target)?;
+                    for photo_id in matched {
+                        applied += storage::tag::add_photo_tags(conn, photo_id, &[tag.id])?;
+                    }
+                }
+                RuleAction::AddAlbum => {
+                    let album = storage::album::get_or_insert_album(conn, &rule.target)?;
+                    for photo_id in matched {
+                        applied += storage::album::add_photo_albums(conn, photo_id, &[album.id])?;
+                    }
+                }
+            }
+        }
+        Ok(applied)
+    })
+}
+
+/// 试运行规则【不修改数据】，返回每条规则会影响的照片
+///
+/// - rule_ids 规则 ID【为空时试运行所有启用的规则】
+pub fn dry_run(rule_ids: Option<Vec<i32>>) -> Result<Vec<RuleDryRun>> {
+    let mut conn = establish_connection();
+    let records = match rule_ids {
+        Some(ids) => ids
+            .into_iter()
+            .map(|id| {
+                storage::organize_rule::get_rule(&mut conn, id)?.ok_or_else(|| {
+                    i18n::error(msg::ORGANIZE_RULE_NOT_FOUND, &[("id", id.to_string())])
+                })
+            })
+            .collect::<Result<Vec<_>>>()?,
+        None => storage::organize_rule::get_enabled_rules(&mut conn)?,
+    };
+    let photos = storage::photo_table::search_all_photos(&mut conn)?;
+    let mut result = Vec::with_capacity(records.len());
+    for record in records {
+        let rule = to_rule(record)?;
+        let matched: Vec<i32> = photos
+            .iter()
+            .filter(|x| rule.matches(x))
+            .map(|x| x.id)
+            .collect();
+        let existing = existing_photo_ids(&mut conn, &rule, &matched)?;
+        result.push(RuleDryRun {
+            rule_id: rule.id.unwrap_or_default(),
+            name: rule.name.clone(),
+            matched: matched.len(),
+            photo_ids: matched
+                .into_iter()
+                .filter(|x| !existing.contains(x))
+                .collect(),
+        });
+    }
+    Ok(result)
+}
+
+/// 已经有规则的标签或已在规则的相册中的照片【标签或相册不存在时为空】
+fn existing_photo_ids(
+    conn: &mut SqliteConnection,
+    rule: &OrganizeRule,
+    photo_ids: &[i32],
+) -> Result<HashSet<i32>> {
+    let existing = match rule.action {
+        RuleAction::AddTag => {
+            let Some(tag) = storage::tag::find_root_tag(conn, &rule.target)? else {
+                return Ok(HashSet::new());
+            };
+            let mut existing = HashSet::new();
+            for chunk in photo_ids.chunks(SQLITE_MAX_VARIABLES) {
+                existing.extend(
+                    storage::tag::get_photos_tag_ids(conn, chunk)?
+                        .into_iter()
+                        .filter(|(_, tag_ids)| tag_ids.contains(&tag.id))
+                        .map(|(photo_id, _)| photo_id),
+                );
+            }
+            existing
+        }
+        RuleAction::AddAlbum => {
+            let Some(album) = storage::album::find_root_album(conn, &rule.target)? else {
+                return Ok(HashSet::new());
+            };
+            storage::album::get_album_photos(conn, album.id)?
+                .into_iter()
+                .map(|x| x.id)
+                .collect()
+        }
+    };
+    Ok(existing)
+}
+
+fn to_rule(record: OrganizeRuleRecord) -> Result<OrganizeRule> {
+    let conditions: Vec<RuleCondition> = JsonUtil::from_json(&record.conditions).map_err(|e| {
+        i18n::error(
+            msg::ORGANIZE_RULE_CONDITION_INVALID,
+            &[("id", record.id.to_string()), ("error", e.to_string())],
+        )
+    })?;
+    Ok(OrganizeRule {
+        id: Some(record.id),
+        name: record.name,
+        conditions,
+        action: RuleAction::from_code(record.action).ok_or_else(|| {
+            i18n::error(
+                msg::ORGANIZE_RULE_ACTION_INVALID,
+                &[
+                    ("id", record.id.to_string()),
+                    ("action", record.action.to_string()),
+                ],
+            )
+        })?,
+        target: record.target,
+        enabled: record.is_enable,
+    })
+}
@@ -0,0 +1,32 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::photo_activity::{ActivityKind, PhotoActivitySummary};
+use anyhow::Result;
+
+/// 记录照片活动【失败只记录日志，不影响原操作】
+/// - detail 说明【所有照片相同】
+pub fn record(photo_ids: &[i32], kind: ActivityKind, detail: Option<&str>) {
+    if photo_ids.is_empty() {
+        return;
+    }
+    let mut conn = establish_connection();
+    if let Err(e) = storage::photo_activity::insert_activities(&mut conn, photo_ids, kind, detail) {
+        log::warn!("照片活动记录失败 {:?}: {}", kind, e);
+    }
+}
+
+/// 获取照片的备注和活动记录
+pub fn get_photo_activity(photo_id: i32) -> Result<PhotoActivitySummary> {
+    let mut conn = establish_connection();
+    let photo = storage::photo_table::search_photos_by_ids(&mut conn, &[photo_id])?
+        .pop()
+        .ok_or_else(|| i18n::error(msg::PHOTO_NOT_FOUND, &[("id", photo_id.to_string())]))?;
+    let activities = storage::photo_activity::get_photo_activity(&mut conn, photo_id)?;
+    Ok(PhotoActivitySummary {
+        photo_id,
+        notes: photo.notes,
+        activities: activities.into_iter().map(Into::into).collect(),
+    })
+}
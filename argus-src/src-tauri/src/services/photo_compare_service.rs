@@ -0,0 +1,212 @@
+use crate::constant::{
+    COMPARE_DIFF_THRESHOLD, COMPARE_MAX_PHOTOS, COMPARE_MIN_PHOTOS, DEFAULT_THUMBNAIL_SIZE,
+};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::config::sys_config;
+use crate::structs::photo_compare::{ComparePhoto, PhotoComparison, PixelDifference};
+use crate::structs::thumbnail_encoding;
+use crate::utils::exif_utils::format;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::file_util::file_exists;
+use crate::utils::image_format_util;
+use crate::utils::img_util::ImageOperate;
+use anyhow::{anyhow, Result};
+use image::imageops::FilterType;
+use image::GrayImage;
+use std::collections::{HashMap, HashSet};
+
+/// 对比多张照片【用于在相似的照片中挑选】
+///
+/// 返回各照片对齐的拍摄参数、与第一张照片的时间差，以及同一级别的预览图路径
+/// - photo_ids 照片 ID【2 - 4 张，第一张作为基准】
+/// - with_difference 是否计算与第一张照片的像素差异
+pub async fn compare_photos(photo_ids: Vec<i32>, with_difference: bool) -> Result<PhotoComparison> {
+    if !(COMPARE_MIN_PHOTOS..=COMPARE_MAX_PHOTOS).contains(&photo_ids.len()) {
+        return Err(i18n::error(
+            msg::COMPARE_PHOTO_RANGE,
+            &[
+                ("min", COMPARE_MIN_PHOTOS.to_string()),
+                ("max", COMPARE_MAX_PHOTOS.to_string()),
+            ],
+        ));
+    }
+    if photo_ids.iter().collect::<HashSet<_>>().len() != photo_ids.len() {
+        return Err(anyhow!(i18n::text(msg::COMPARE_DUPLICATED)));
+    }
+
+    let ids = photo_ids.clone();
+    let found = tokio::task::spawn_blocking(move || {
+        let mut conn = establish_connection();
+        storage::photo_table::search_photos_by_ids(&mut conn, &ids)
+    })
+    .await??;
+    let mut found: HashMap<i32, Photo> = found.into_iter().map(|x| (x.id, x)).collect();
+    let photos = photo_ids
+        .iter()
+        .map(|id| {
+            found
+                .remove(id)
+                .ok_or_else(|| i18n::error(msg::PHOTO_NOT_FOUND, &[("id", id.to_string())]))
+        })
+        .collect::<Result<Vec<Photo>>>()?;
+
+    let mut previews = Vec::with_capacity(photos.len());
+    for photo in &photos {
+        previews.push(preview_path(photo).await?);
+    }
+
+    let base_time = photos[0].date_time_original;
+    let items = photos
+        .iter()
+        .zip(&previews)
+        .map(|(photo, path)| to_compare_photo(photo, path.clone(), base_time))
+        .collect();
+    let differences = if with_difference {
+        let task = tokio::task::spawn_blocking(move || pixel_differences(&photo_ids, &previews));
+        Some(task.await??)
+    } else {
+        None
+    };
+    Ok(PhotoComparison {
+        photos: items,
+        differences,
+    })
+}
+
+/// 预览图路径【已生成时直接返回，否则生成】
+async fn preview_path(photo: &Photo) -> Result<String> {
+    let root_dir = sys_config()
+        .thumbnail_storage_path
+        .clone()
+        .ok_or_else(|| anyhow!(i18n::text(msg::THUMBNAIL_PATH_NOT_CONFIGURED)))?;
+    let path = FileHashUtils::hash_to_file_path(
+        &photo.hash,
+        &root_dir,
+        &image_format_util::get_suffix_name(thumbnail_encoding::storage_format()),
+        DEFAULT_THUMBNAIL_SIZE,
+    );
+    if file_exists(&path) {
+        return Ok(path.display().to_string());
+    }
+    ImageOperate::designate_level_image_compression(
+        photo.full_path(),
+        thumbnail_encoding::storage_format(),
+        DEFAULT_THUMBNAIL_SIZE,
+    )
+    .await
+}
+
+/// 整理照片的拍摄参数
+/// - base_time 第一张照片的拍摄时间
+fn to_compare_photo(photo: &Photo, preview_path: String, base_time: Option<i64>) -> ComparePhoto {
+    let exposure_time = photo.exposure_time.map(|x| x as f64);
+    let f_number = photo.f_number.map(|x| x as f64);
+    let exposure_value = match (exposure_time, f_number, photo.iso) {
+        (Some(time), Some(f_number), Some(iso)) => {
+            format::exposure_value(time, f_number, iso as f64)
+        }
+        _ => None,
+    };
+    ComparePhoto {
+        photo_id: photo.id,
+        img_name: photo.img_name.clone(),
+        preview_path,
+        width: photo.width,
+        height: photo.height,
+        file_size: photo.file_size,
+        date_time_original: photo.date_time_original,
+        time_delta: base_time
+            .zip(photo.date_time_original)
+            .map(|(base, time)| time - base),
+        make: photo.make.clone(),
+        model: photo.model.clone(),
+        exposure_time: photo.exposure_time,
+        shutter_speed: exposure_time.and_then(format::format_shutter_speed),
+        f_number: photo.f_number,
+        aperture: f_number.and_then(format::format_aperture),
+        iso: photo.iso,
+        exposure_value,
+        focal_length: photo.focal_length,
+        focal_length_35mm: photo.focal_length_35mm,
+    }
+}
+
+/// 计算第二张及之后的预览图与第一张的像素差异【尺寸不同时缩放到第一张的尺寸】
+fn pixel_differences(photo_ids: &[i32], previews: &[String]) -> Result<Vec<PixelDifference>> {
+    let open = |path: &String| {
+        image::open(path).map(|x| x.to_luma8()).map_err(|e| {
+            i18n::error(
+                msg::COMPARE_PREVIEW_READ_FAILED,
+                &[("path", path.to_string()), ("error", e.to_string())],
+            )
+        })
+    };
+    let base = open(&previews[0])?;
+    let mut result = Vec::with_capacity(previews.len() - 1);
+    for (photo_id, path) in photo_ids.iter().zip(previews).skip(1) {
+        let mut image = open(path)?;
+        if image.dimensions() != base.dimensions() {
+            image =
+                image::imageops::resize(&image, base.width(), base.height(), FilterType::Triangle);
+        }
+        let (mean_difference, changed_ratio) = difference(&base, &image);
+        result.push(PixelDifference {
+            photo_id: *photo_id,
+            mean_difference,
+            changed_ratio,
+        });
+    }
+    Ok(result)
+}
+
+/// 两张相同尺寸的灰度图的平均差异和变化像素占比
+fn difference(a: &GrayImage, b: &GrayImage) -> (f64, f64) {
+    let total = a.as_raw().len().min(b.as_raw().len());
+    if total == 0 {
+        return (0.0, 0.0);
+    }
+    let (sum, changed) =
+        a.as_raw()
+            .iter()
+            .zip(b.as_raw())
+            .fold((0u64, 0usize), |(sum, changed), (x, y)| {
+                let diff = x.abs_diff(*y);
+                (
+                    sum + diff as u64,
+                    changed + (diff > COMPARE_DIFF_THRESHOLD) as usize,
+                )
+            });
+    (
+        sum as f64 / total as f64 / 255.0,
+        changed as f64 / total as f64,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    #[test]
+    fn test_difference() {
+        let black = GrayImage::from_pixel(4, 4, Luma([0]));
+        let white = GrayImage::from_pixel(4, 4, Luma([255]));
+        assert_eq!(difference(&black, &black), (0.0, 0.0));
+        assert_eq!(difference(&black, &white), (1.0, 1.0));
+
+        // 一半像素有明显变化，其余为不超过阈值的噪点
+        let mut noisy = GrayImage::from_pixel(4, 4, Luma([COMPARE_DIFF_THRESHOLD]));
+        for x in 0..4 {
+            for y in 0..2 {
+                noisy.put_pixel(x, y, Luma([128]));
+            }
+        }
+        let (mean, changed) = difference(&black, &noisy);
+        assert_eq!(changed, 0.5);
+        assert!((mean - (128.0 + COMPARE_DIFF_THRESHOLD as f64) / 2.0 / 255.0).abs() < 1e-9);
+    }
+}
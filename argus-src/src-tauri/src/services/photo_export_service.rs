@@ -0,0 +1,251 @@
+use crate::constant::EXPORT_DEFAULT_JPEG_QUALITY;
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::services::{photo_activity_service, privacy_service};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::edit_journal::EditField;
+use crate::structs::photo_activity::ActivityKind;
+use crate::structs::photo_export::{PhotoExportOptions, PhotoExportSummary};
+use crate::structs::watermark;
+use crate::utils::exif_utils::exif_util::ExifToolCmd;
+use crate::utils::img_util::ImageOperate;
+use crate::utils::{file_util, font_util};
+use ab_glyph::FontVec;
+use anyhow::Result;
+use chrono::DateTime;
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, ImageDecoder, ImageEncoder, ImageReader};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// 导出照片到指定文件夹
+///
+/// 没有水印和 JPEG 质量时直接复制原图；否则按 EXIF 方向摆正（并添加水印）后重新编码为 JPEG，
+/// 保留原图的 ICC 配置文件和元数据（不包括方向、缩略图和厂商私有数据）。
+/// 导出的文件均按隐私区域模糊或删除位置，并写入数据库中的作者、版权和署名。
+/// 目标文件已存在时在文件名后添加序号
+/// - photo_ids 照片 ID
+/// - dest_path 导出的文件夹
+/// - options 导出选项
+pub fn export_photos(
+    photo_ids: &[i32],
+    dest_path: &Path,
+    options: &PhotoExportOptions,
+) -> Result<PhotoExportSummary> {
+    fs::create_dir_all(dest_path)?;
+    let logo = match options
+        .watermark
+        .as_ref()
+        .and_then(|x| x.logo_path.as_deref())
+        .filter(|x| !x.is_empty())
+    {
+        Some(path) => Some(image::open(file_util::long_path(path)).map_err(|e| {
+            i18n::error(
+                msg::EXPORT_WATERMARK_LOGO_FAILED,
+                &[("path", path.to_string()), ("error", e.to_string())],
+            )
+        })?),
+        None => None,
+    };
+    // logo 优先，只有使用文字水印时才加载字体
+    let font = match options.watermark.as_ref() {
+        Some(watermark)
+            if logo.is_none()
+                && watermark
+                    .text
+                    .as_deref()
+                    .is_some_and(|x| !x.trim().is_empty()) =>
+        {
+            font_util::load_watermark_font(watermark.font_path.as_deref())?
+        }
+        _ => None,
+    };
+
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    let mut summary = PhotoExportSummary::default();
+    for photo in &photos {
+        let result = if options.watermark.is_some() || options.quality.is_some() {
+            export_encoded(photo, dest_path, font.as_ref(), logo.as_ref(), options)
+        } else {
+            export_copy(photo, dest_path)
+        };
+        match result {
+            Ok(file) => {
+                let file = file_util::display_path(&file);
+                photo_activity_service::record(&[photo.id], ActivityKind::Exported, Some(&file));
+                summary.exported += 1;
+                summary.files.push(file);
+            }
+            Err(e) => {
+                log::warn!("照片导出失败 {}: {}", photo.full_path().display(), e);
+                summary.failed.push(format!("{}: {}", photo.img_name, e));
+            }
+        }
+    }
+    log::info!(
+        "照片导出完成: 成功 {}，失败 {}",
+        summary.exported,
+        summary.failed.len()
+    );
+    Ok(summary)
+}
+
+/// 复制原图
+fn export_copy(photo: &Photo, dest_path: &Path) -> Result<PathBuf> {
+    let src = photo.full_path();
+    let file_name = src
+        .file_name()
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_else(|| photo.img_name.clone());
+    let dest = file_util::unique_path(&dest_path.join(file_name));
+    fs::copy(file_util::long_path(&src), file_util::long_path(&dest))?;
+    if let Err(e) = privacy_service::scrub_exported_copy(photo, &dest) {
+        // 不保留可能包含精确位置的副本
+        let _ = fs::remove_file(file_util::long_path(&dest));
+        return Err(i18n::error(
+            msg::EXPORT_LOCATION_FAILED,
+            &[("error", e.to_string())],
+        ));
+    }
+    write_credits(photo, &dest);
+    Ok(dest)
+}
+
+/// 重新编码为 JPEG【有水印时添加水印】
+fn export_encoded(
+    photo: &Photo,
+    dest_path: &Path,
+    font: Option<&FontVec>,
+    logo: Option<&DynamicImage>,
+    options: &PhotoExportOptions,
+) -> Result<PathBuf> {
+    let src = photo.full_path();
+    let mut image = ImageOperate::open_oriented(&src)?;
+    if let Some(watermark) = &options.watermark {
+        let text = watermark
+            .text
+            .as_deref()
+            .map(|x| watermark::render_template(x, &template_fields(photo)));
+        image = ImageOperate::apply_watermark(image, text.as_deref(), font, logo, watermark);
+    }
+
+    let stem = photo
+        .full_path()
+        .file_stem()
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_else(|| photo.img_name.clone());
+    let dest = file_util::unique_path(&dest_path.join(format!("{}.jpg", stem)));
+    let quality = options
+        .quality
+        .unwrap_or(EXPORT_DEFAULT_JPEG_QUALITY)
+        .clamp(1, 100);
+    let mut writer = BufWriter::new(fs::File::create(file_util::long_path(&dest))?);
+    let mut encoder = JpegEncoder::new_with_quality(&mut writer, quality);
+    if let Some(icc) = read_icc_profile(&src) {
+        if let Err(e) = encoder.set_icc_profile(icc) {
+            log::warn!("ICC 配置文件写入失败 {}: {}", src.display(), e);
+        }
+    }
+    DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(encoder)?;
+    writer.flush()?;
+    drop(writer);
+
+    // 已按方向摆正，不复制方向；缩略图是原图的，厂商私有数据可能很大
+    let excluded = [
+        "Orientation",
+        "ThumbnailImage",
+        "PreviewImage",
+        "MakerNotes:all",
+        "ExifImageWidth",
+        "ExifImageHeight",
+    ];
+    match ExifToolCmd.copy_tags(&src, &dest, &excluded) {
+        Ok(()) => {
+            if let Err(e) = privacy_service::scrub_exported_copy(photo, &dest) {
+                // 不保留可能包含精确位置的副本
+                let _ = fs::remove_file(file_util::long_path(&dest));
+                return Err(i18n::error(
+                    msg::EXPORT_LOCATION_FAILED,
+                    &[("error", e.to_string())],
+                ));
+            }
+        }
+        Err(e) => log::warn!(
+            "元数据复制失败，导出的文件不包含元数据 {}: {}",
+            src.display(),
+            e
+        ),
+    }
+    write_credits(photo, &dest);
+    Ok(dest)
+}
+
+/// 读取原图的 ICC 配置文件【读取失败时返回 None】
+fn read_icc_profile(path: &Path) -> Option<Vec<u8>> {
+    let result = ImageReader::open(file_util::long_path(path))
+        .and_then(|x| x.with_guessed_format())
+        .map_err(anyhow::Error::from)
+        .and_then(|x| Ok(x.into_decoder()?.icc_profile()?));
+    match result {
+        Ok(icc) => icc,
+        Err(e) => {
+            log::warn!("ICC 配置文件读取失败 {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// 把数据库中的作者、版权和署名写入导出的文件【写入失败时只记录日志】
+fn write_credits(photo: &Photo, dest: &Path) {
+    let values = [
+        (EditField::Artist, photo.artist.as_deref()),
+        (EditField::Copyright, photo.copyright.as_deref()),
+        (EditField::Credit, photo.credit.as_deref()),
+    ];
+    let tags: Vec<(&str, Option<&str>)> = values
+        .iter()
+        .filter_map(|(field, value)| value.filter(|x| !x.is_empty()).map(|x| (field, x)))
+        .flat_map(|(field, value)| {
+            field
+                .credit_tags()
+                .iter()
+                .map(move |tag| (*tag, Some(value)))
+        })
+        .collect();
+    if tags.is_empty() {
+        return;
+    }
+    if let Err(e) = ExifToolCmd.write_tags(dest, &tags) {
+        log::warn!("作者和版权写入失败 {}: {}", dest.display(), e);
+    }
+}
+
+/// 水印文字模板可用的字段
+fn template_fields(photo: &Photo) -> HashMap<&'static str, String> {
+    let time = photo
+        .date_time_original
+        .and_then(|x| DateTime::from_timestamp(x, 0));
+    HashMap::from([
+        ("artist", photo.artist.clone().unwrap_or_default()),
+        ("copyright", photo.copyright.clone().unwrap_or_default()),
+        ("credit", photo.credit.clone().unwrap_or_default()),
+        (
+            "year",
+            time.map(|x| x.format("%Y").to_string()).unwrap_or_default(),
+        ),
+        (
+            "date",
+            time.map(|x| x.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+        ),
+        ("make", photo.make.clone().unwrap_or_default()),
+        ("model", photo.model.clone().unwrap_or_default()),
+        ("name", photo.img_name.clone()),
+        ("caption", photo.caption.clone().unwrap_or_default()),
+    ])
+}
@@ -0,0 +1,346 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::services::{exif_backfill_service, folder_album_service};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::exif_backfill::ExifBackfillSummary;
+use crate::structs::rename_template::{RenameByTemplateResult, RenamePreview, RenameTemplate};
+use crate::utils::file_util;
+use anyhow::{anyhow, Result};
+use diesel::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// 照片文件位置变化
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoRelocation {
+    pub photo_id: i32,
+    /// 原路径
+    pub from: String,
+    /// 新路径
+    pub to: String,
+}
+
+/// 移动照片到指定文件夹
+///
+/// 目标文件夹中已有同名文件时不移动任何照片。缩略图按 Hash 存储，文件内容不变，无需重新生成
+/// - photo_ids 照片 ID
+/// - dest_dir 目标文件夹【不存在时自动创建】
+pub fn move_photos(photo_ids: &[i32], dest_dir: &Path) -> Result<Vec<PhotoRelocation>> {
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    if photos.len() != photo_ids.len() {
+        return Err(anyhow!(i18n::text(msg::PHOTOS_PARTIALLY_MISSING)));
+    }
+    fs::create_dir_all(file_util::long_path(dest_dir))?;
+    relocate(plan_moves(photos, dest_dir))
+}
+
+/// 照片移动到指定文件夹后的路径【已在该文件夹中的照片忽略】
+pub fn plan_moves(photos: Vec<Photo>, dest_dir: &Path) -> Vec<(Photo, PathBuf)> {
+    photos
+        .into_iter()
+        .filter_map(|photo| {
+            let from = photo.full_path();
+            let name = from.file_name()?.to_os_string();
+            Some((photo, dest_dir.join(name)))
+        })
+        .filter(|(photo, to)| photo.full_path() != *to)
+        .collect()
+}
+
+/// 重命名照片
+/// - photo_id 照片 ID
+/// - new_name 新文件名【不含扩展名时沿用原扩展名】
+pub fn rename_photo(photo_id: i32, new_name: &str) -> Result<PhotoRelocation> {
+    let new_name = new_name.trim();
+    if new_name.is_empty()
+        || new_name == "."
+        || new_name == ".."
+        || new_name.contains(['/', '\\'])
+    {
+        return Err(i18n::error(
+            msg::FILE_NAME_INVALID,
+            &[("name", new_name.to_string())],
+        ));
+    }
+    let mut conn = establish_connection();
+    let photo = storage::photo_table::search_photos_by_ids(&mut conn, &[photo_id])?
+        .pop()
+        .ok_or_else(|| i18n::error(msg::PHOTO_NOT_FOUND, &[("id", photo_id.to_string())]))?;
+    let from = photo.full_path();
+    let mut to = from.with_file_name(new_name);
+    if to.extension().is_none() {
+        if let Some(ext) = from.extension() {
+            to.set_extension(ext);
+        }
+    }
+    if to == from {
+        return Err(anyhow!(i18n::text(msg::FILE_NAME_UNCHANGED)));
+    }
+    relocate(vec![(photo, to)])?
+        .pop()
+        .ok_or_else(|| i18n::error(msg::PHOTO_RENAME_FAILED, &[("id", photo_id.to_string())]))
+}
+
+/// 按模板重命名照片
+///
+/// 序号按 photo_ids 的顺序生成；新文件名与已有文件或同一批次的其他照片冲突时追加 `_1`、`_2` 等后缀。
+/// 所有文件重命名成功后在一个事务中更新数据库，失败时把文件全部还原
+/// - photo_ids 照片 ID
+/// - template 文件名模板【见 `RenameTemplate`】
+/// - dry_run 只返回新旧文件名，不修改文件
+pub fn rename_by_template(
+    photo_ids: &[i32],
+    template: &str,
+    dry_run: bool,
+) -> Result<RenameByTemplateResult> {
+    let template = RenameTemplate::parse(template)?;
+    let mut conn = establish_connection();
+    let mut photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    if photos.len() != photo_ids.len() {
+        return Err(anyhow!(i18n::text(msg::PHOTOS_PARTIALLY_MISSING)));
+    }
+    // 升级前导入、还没有补全 EXIF 的照片先读取原图，否则拍摄时间、相机型号等字段为空
+    let pending: Vec<&Photo> = photos
+        .iter()
+        .filter(|x| x.exif_read_time.is_none())
+        .collect();
+    if !pending.is_empty() {
+        let mut summary = ExifBackfillSummary::default();
+        for photo in pending {
+            exif_backfill_service::backfill_photo(&mut conn, photo, &mut summary)?;
+        }
+        photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    }
+    photos.sort_by_key(|photo| photo_ids.iter().position(|id| *id == photo.id));
+
+    let moves = plan_renames(&template, photos, |path| {
+        file_util::long_path(path).exists()
+    })?;
+    let items: Vec<RenamePreview> = moves
+        .iter()
+        .map(|(photo, to)| RenamePreview {
+            photo_id: photo.id,
+            old_name: photo.img_name.clone(),
+            new_name: to
+                .file_name()
+                .map(|x| x.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        })
+        .collect();
+    if dry_run {
+        return Ok(RenameByTemplateResult {
+            dry_run,
+            items,
+            renamed: 0,
+        });
+    }
+
+    let changed: Vec<(Photo, PathBuf)> = moves
+        .into_iter()
+        .filter(|(photo, to)| photo.full_path() != *to)
+        .collect();
+    let renamed = if changed.is_empty() {
+        0
+    } else {
+        relocate(changed)?.len()
+    };
+    Ok(RenameByTemplateResult {
+        dry_run,
+        items,
+        renamed,
+    })
+}
+
+/// 按模板生成照片的新路径【照片所在文件夹不变】
+/// - exists 判断路径上是否已有文件
+fn plan_renames(
+    template: &RenameTemplate,
+    photos: Vec<Photo>,
+    exists: impl Fn(&Path) -> bool,
+) -> Result<Vec<(Photo, PathBuf)>> {
+    let total = photos.len();
+    let mut taken: HashSet<PathBuf> = HashSet::new();
+    let mut moves = Vec::with_capacity(total);
+    for (index, photo) in photos.into_iter().enumerate() {
+        let from = photo.full_path();
+        let stem = template.render(&photo, index + 1, total);
+        if stem.is_empty() {
+            return Err(i18n::error(
+                msg::PHOTO_TEMPLATE_NAME_EMPTY,
+                &[("name", photo.img_name.to_string())],
+            ));
+        }
+        let to = unique_path(&from, &stem, &taken, &exists);
+        taken.insert(to.clone());
+        moves.push((photo, to));
+    }
+    Ok(moves)
+}
+
+/// 照片重命名后不冲突的路径【沿用原扩展名，冲突时追加 `_1`、`_2` 等后缀】
+/// - from 照片原路径【与原路径相同不算冲突】
+/// - stem 新文件名【不含扩展名】
+/// - taken 同一批次已分配的路径
+fn unique_path(
+    from: &Path,
+    stem: &str,
+    taken: &HashSet<PathBuf>,
+    exists: impl Fn(&Path) -> bool,
+) -> PathBuf {
+    let ext = from
+        .extension()
+        .map(|x| format!(".{}", x.to_string_lossy()))
+        .unwrap_or_default();
+    let mut to = from.with_file_name(format!("{}{}", stem, ext));
+    let mut suffix = 1;
+    while taken.contains(&to) || (to != from && exists(&to)) {
+        to = from.with_file_name(format!("{}_{}{}", stem, suffix, ext));
+        suffix += 1;
+    }
+    to
+}
+
+/// 移动文件并更新数据库
+///
+/// 任一文件移动失败或数据库更新失败时，把已移动的文件移回原位置
+fn relocate(moves: Vec<(Photo, PathBuf)>) -> Result<Vec<PhotoRelocation>> {
+    let done = move_files(&moves)?;
+    let mut conn = establish_connection();
+    let updated = conn.transaction(|conn| {
+        for (photo, to) in &moves {
+            storage::photo_table::update_photo_location(conn, photo.id, to)?;
+        }
+        anyhow::Ok(())
+    });
+    if let Err(e) = updated {
+        rollback(&done);
+        return Err(e);
+    }
+    Ok(finish_moves(&moves, &done))
+}
+
+/// 检查要移动的文件：原文件存在，目标路径没有文件且互不重复
+pub fn check_moves(moves: &[(Photo, PathBuf)]) -> Result<()> {
+    let mut targets = HashSet::new();
+    for (photo, to) in moves {
+        if file_util::long_path(to).exists() || !targets.insert(to.clone()) {
+            return Err(i18n::error(
+                msg::FILE_ALREADY_EXISTS,
+                &[("path", to.display().to_string())],
+            ));
+        }
+        if !file_util::long_path(photo.full_path()).is_file() {
+            return Err(i18n::error(
+                msg::PHOTO_FILE_NOT_FOUND,
+                &[("path", photo.full_path().display().to_string())],
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 移动文件，返回已移动的文件（原路径、新路径）
+///
+/// 先检查所有目标路径，再逐个移动文件；任一文件移动失败时，把已移动的文件移回原位置
+pub fn move_files(moves: &[(Photo, PathBuf)]) -> Result<Vec<(PathBuf, PathBuf)>> {
+    check_moves(moves)?;
+    let mut done: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for (photo, to) in moves {
+        let from = photo.full_path();
+        if let Err(e) = move_path(&from, to) {
+            rollback(&done);
+            return Err(i18n::error(
+                msg::FILE_MOVE_FAILED,
+                &[
+                    ("path", from.display().to_string()),
+                    ("error", e.to_string()),
+                ],
+            ));
+        }
+        done.push((from, to.clone()));
+    }
+    Ok(done)
+}
+
+/// 数据库更新后移动附属文件，返回位置变化
+/// - done 已移动的文件
+pub fn finish_moves(
+    moves: &[(Photo, PathBuf)],
+    done: &[(PathBuf, PathBuf)],
+) -> Vec<PhotoRelocation> {
+    // 附属文件跟随照片移动，失败不影响结果；目标位置已有其他照片的附属文件时改用新的文件名
+    for (from, to) in done {
+        let sidecar = file_util::sidecar_path(from);
+        if file_util::long_path(&sidecar).is_file() {
+            let target = file_util::unique_path(&file_util::sidecar_path(to));
+            if let Err(e) = move_path(&sidecar, &target) {
+                log::warn!("附属文件移动失败 {}: {}", sidecar.display(), e);
+            }
+        }
+    }
+    folder_album_service::request_sync();
+
+    moves
+        .iter()
+        .zip(done)
+        .map(|((photo, _), (from, to))| PhotoRelocation {
+            photo_id: photo.id,
+            from: file_util::display_path(from),
+            to: file_util::display_path(to),
+        })
+        .collect()
+}
+
+/// 移动文件【只有跨磁盘时才先拷贝后删除，其他错误直接返回】
+pub fn move_path(from: &Path, to: &Path) -> Result<()> {
+    match fs::rename(file_util::long_path(from), file_util::long_path(to)) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == ErrorKind::CrossesDevices => {
+            file_util::move_file(from, to).map_err(|e| anyhow!(e))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 把已移动的文件移回原位置
+pub fn rollback(done: &[(PathBuf, PathBuf)]) {
+    for (from, to) in done.iter().rev() {
+        if let Err(e) = move_path(to, from) {
+            log::error!("文件还原失败 {} -> {}: {}", to.display(), from.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unique_path() {
+        let from = Path::new("/photos/IMG_0001.JPG");
+        let existing = [PathBuf::from("/photos/20240501.JPG")];
+        let exists = |path: &Path| existing.iter().any(|x| x == path);
+
+        let mut taken = HashSet::new();
+        assert_eq!(
+            unique_path(from, "IMG_0001", &taken, exists),
+            PathBuf::from("/photos/IMG_0001.JPG")
+        );
+        assert_eq!(
+            unique_path(from, "20240501", &taken, exists),
+            PathBuf::from("/photos/20240501_1.JPG")
+        );
+        taken.insert(PathBuf::from("/photos/20240501_1.JPG"));
+        assert_eq!(
+            unique_path(from, "20240501", &taken, exists),
+            PathBuf::from("/photos/20240501_2.JPG")
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use crate::constant::{
+    BLURHASH_COMPONENTS, DOMINANT_COLOR_THUMBNAIL_SIZE, PHOTO_LIST_DEFAULT_PAGE_SIZE,
+    PHOTO_LIST_MAX_PAGE_SIZE,
+};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::config::sys_config;
+use crate::structs::photo_list::{PhotoCursor, PhotoListItem, PhotoListSort, PhotoPage};
+use crate::structs::photo_search::PhotoSearchParams;
+use crate::structs::thumbnail_encoding;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::{blurhash_util, file_util, image_format_util};
+use anyhow::{anyhow, Result};
+
+/// 按游标分页列出照片
+///
+/// 返回缩略图 Hash、尺寸和 BlurHash，前端按 Hash 加载缩略图
+/// - cursor 上一页返回的游标【为空时从第一页开始】
+/// - page_size 每页数量
+/// - sort 排序方式【默认按拍摄时间从新到旧】
+/// - filters 筛选条件【同照片搜索，忽略其中的 limit、offset】
+pub fn list_photos(
+    cursor: Option<&str>,
+    page_size: Option<i64>,
+    sort: Option<PhotoListSort>,
+    filters: Option<PhotoSearchParams>,
+) -> Result<PhotoPage> {
+    let after = cursor
+        .filter(|x| !x.trim().is_empty())
+        .map(PhotoCursor::parse)
+        .transpose()?;
+    let page_size = page_size
+        .unwrap_or(PHOTO_LIST_DEFAULT_PAGE_SIZE)
+        .clamp(1, PHOTO_LIST_MAX_PAGE_SIZE);
+    let filters = filters.unwrap_or_default();
+    let mut conn = establish_connection();
+    // 多查一张判断是否还有下一页
+    let mut photos = storage::photo_search::list_photos(
+        &mut conn,
+        &filters,
+        sort.unwrap_or_default(),
+        after.as_ref(),
+        page_size + 1,
+    )?;
+    let has_more = photos.len() as i64 > page_size;
+    photos.truncate(page_size as usize);
+
+    let hashes: Vec<String> = photos.iter().map(|x| x.hash.clone()).collect();
+    let mut blurhashes = storage::photo_blurhash::get_blurhashes(&mut conn, &hashes)?;
+    let next_cursor = has_more
+        .then(|| photos.last().map(|x| PhotoCursor::of(x).to_string()))
+        .flatten();
+    let items = photos
+        .into_iter()
+        .map(|photo| PhotoListItem {
+            captured_at: PhotoCursor::of(&photo).captured_at,
+            blurhash: blurhashes.remove(&photo.hash),
+            id: photo.id,
+            hash: photo.hash,
+            img_name: photo.img_name,
+            width: photo.width,
+            height: photo.height,
+            aspect_ratio: photo.aspect_ratio,
+            rating: photo.rating,
+            is_animated: photo.is_animated,
+        })
+        .collect();
+    Ok(PhotoPage { items, next_cursor })
+}
+
+/// 为还没有 BlurHash 的照片计算 BlurHash【读取已生成的缩略图，缩略图不存在的照片跳过】
+///
+/// 返回处理的照片数量
+pub fn index_photo_blurhashes() -> Result<usize> {
+    let root_dir = sys_config()
+        .thumbnail_storage_path
+        .clone()
+        .ok_or_else(|| anyhow!(i18n::text(msg::THUMBNAIL_PATH_NOT_CONFIGURED)))?;
+    let suffix = image_format_util::get_suffix_name(thumbnail_encoding::storage_format());
+    let (components_x, components_y) = BLURHASH_COMPONENTS;
+    let mut conn = establish_connection();
+    let hashes = storage::photo_blurhash::get_hashes_without_blurhash(&mut conn)?;
+    let mut count = 0;
+    for hash in hashes {
+        let path = FileHashUtils::hash_to_file_path(
+            &hash,
+            &root_dir,
+            &suffix,
+            DOMINANT_COLOR_THUMBNAIL_SIZE,
+        );
+        if !file_util::file_exists(&path) {
+            continue;
+        }
+        let image = match image::open(file_util::long_path(&path)) {
+            Ok(x) => x,
+            Err(e) => {
+                log::warn!("缩略图读取失败 {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        let blurhash = blurhash_util::encode(&image.to_rgb8(), components_x, components_y);
+        storage::photo_blurhash::save_blurhash(&mut conn, &hash, &blurhash)?;
+        count += 1;
+    }
+    log::info!("BlurHash 计算完成: {} 张照片", count);
+    Ok(count)
+}
@@ -1,6 +1,8 @@
+use crate::models::photo::Photo;
 use crate::models::photo_storage::PhotoStorage;
 use crate::storage;
 use crate::storage::connection::establish_connection;
+use crate::structs::photo_search::PhotoSearchParams;
 use crate::utils::json_util::JsonUtil;
 use anyhow::Result;
 use serde_json::{self, Error, Value};
@@ -34,3 +36,9 @@ pub fn delete_img_path(id: i32) -> Result<()> {
     storage::photo_storage::delete_img_path(&mut conn, id)?;
     Ok(())
 }
+
+/// 搜索照片【搜索文本使用全文索引】
+pub fn search_photos(params: &PhotoSearchParams) -> Result<Vec<Photo>> {
+    let mut conn = establish_connection();
+    storage::photo_search::search_photos(&mut conn, params)
+}
@@ -0,0 +1,139 @@
+use crate::models::photo::Photo;
+use crate::models::photo_sequence::{NewPhotoSequence, SequenceCandidate};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::photo_sequence::{SequenceDetectSummary, SequenceKind, SequenceSummary};
+use crate::utils::sequence_detect_util::{self, SequenceShot};
+use crate::utils::time_util::TimeUtils;
+use anyhow::Result;
+use diesel::Connection;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// 一次拍摄【同名的 RAW 与 JPG 视为同一次拍摄】
+struct Capture {
+    shot: SequenceShot,
+    photo_ids: Vec<i32>,
+}
+
+/// 检测全景和包围曝光序列，并给序列中的照片添加对应的标签
+///
+/// 只处理还不在任何序列中的照片，已发现的序列不会重复检测，
+/// 用户拼接、合成后删除标签也不会被重新添加。
+/// - path 只处理此文件夹下的照片【为空表示全部】
+pub fn detect_sequences(path: Option<&str>) -> Result<SequenceDetectSummary> {
+    let mut conn = establish_connection();
+    let candidates = storage::photo_sequence::get_sequence_candidates(&mut conn, path)?;
+
+    let mut summary = SequenceDetectSummary::default();
+    let now = TimeUtils::current_timestamp();
+    conn.transaction(|conn| {
+        let mut tag_ids: HashMap<SequenceKind, i32> = HashMap::new();
+        for captures in group_captures(candidates).values() {
+            let shots: Vec<SequenceShot> = captures.iter().map(|x| x.shot.clone()).collect();
+            for (kind, range) in sequence_detect_util::detect_sequences(&shots) {
+                let photo_ids: Vec<i32> = captures[range.clone()]
+                    .iter()
+                    .flat_map(|x| x.photo_ids.iter().copied())
+                    .collect();
+                let item = NewPhotoSequence {
+                    kind: kind.code(),
+                    photo_count: photo_ids.len() as i32,
+                    start_time: shots[range.start].time,
+                    end_time: shots[range.end - 1].time,
+                    create_time: now,
+                    update_time: now,
+                };
+                storage::photo_sequence::insert_sequence(conn, item, &photo_ids)?;
+                let tag_id = match tag_ids.get(&kind) {
+                    Some(id) => *id,
+                    None => {
+                        let id = storage::tag::get_or_insert_tag(conn, kind.tag_name())?.id;
+                        tag_ids.insert(kind, id);
+                        id
+                    }
+                };
+                for photo_id in &photo_ids {
+                    storage::tag::add_photo_tags(conn, *photo_id, &[tag_id])?;
+                }
+                match kind {
+                    SequenceKind::Panorama => summary.panoramas += 1,
+                    SequenceKind::Bracket => summary.brackets += 1,
+                }
+                summary.photos += photo_ids.len();
+            }
+        }
+        anyhow::Ok(())
+    })?;
+    log::info!("连拍序列检测完成: {:?}", summary);
+    Ok(summary)
+}
+
+/// 获取连拍序列【按拍摄时间从新到旧】
+/// - kind 只包含此类型的序列【为空表示全部】
+pub fn list_sequences(kind: Option<SequenceKind>) -> Result<Vec<SequenceSummary>> {
+    let mut conn = establish_connection();
+    let sequences = storage::photo_sequence::list_sequences(&mut conn, kind.map(|x| x.code()))?;
+    let ids: Vec<i32> = sequences.iter().map(|x| x.id).collect();
+    let mut photo_ids = storage::photo_sequence::get_sequences_photo_ids(&mut conn, &ids)?;
+    Ok(sequences
+        .into_iter()
+        .map(|x| SequenceSummary {
+            sequence_id: x.id,
+            kind: SequenceKind::from_code(x.kind),
+            start_time: x.start_time,
+            end_time: x.end_time,
+            photo_ids: photo_ids.remove(&x.id).unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// 获取序列中的照片【按拍摄时间排序】
+pub fn get_sequence_photos(sequence_id: i32) -> Result<Vec<Photo>> {
+    let mut conn = establish_connection();
+    storage::photo_sequence::get_sequence(&mut conn, sequence_id)?;
+    storage::photo_sequence::get_sequence_photos(&mut conn, sequence_id)
+}
+
+/// 分组依据（文件夹、相机厂商、相机型号）
+type CaptureGroup = (String, Option<String>, Option<String>);
+
+/// 按文件夹和相机分组，组内合并同一次拍摄【候选照片已按拍摄时间排序】
+fn group_captures(candidates: Vec<SequenceCandidate>) -> BTreeMap<CaptureGroup, Vec<Capture>> {
+    let mut groups: BTreeMap<CaptureGroup, Vec<Capture>> = BTreeMap::new();
+    // (分组, 小写文件名, 拍摄时间) -> 在分组中的位置
+    let mut positions: HashMap<(CaptureGroup, String, i64), usize> = HashMap::new();
+    for photo in candidates {
+        let Some(time) = photo.date_time_original else {
+            continue;
+        };
+        let stem = Path::new(&photo.img_name)
+            .file_stem()
+            .map(|x| x.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let group = (
+            photo.img_path.clone(),
+            photo.make.clone(),
+            photo.model.clone(),
+        );
+        let captures = groups.entry(group.clone()).or_default();
+        let key = (group, stem, time);
+        if let Some(&index) = positions.get(&key) {
+            captures[index].photo_ids.push(photo.id);
+            continue;
+        }
+        positions.insert(key, captures.len());
+        captures.push(Capture {
+            shot: SequenceShot {
+                time,
+                exposure_time: photo.exposure_time,
+                f_number: photo.f_number,
+                iso: photo.iso,
+                focal_length: photo.focal_length,
+                landscape: photo.width >= photo.height,
+            },
+            photo_ids: vec![photo.id],
+        });
+    }
+    groups
+}
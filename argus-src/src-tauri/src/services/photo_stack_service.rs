@@ -0,0 +1,262 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::services::thumbnail_cache_service;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::config::sys_config;
+use crate::structs::photo_stack::{edit_base_stem, raw_jpeg_stem, StackSummary};
+use crate::utils::raw_preview_util;
+use anyhow::{anyhow, Result};
+use diesel::{Connection, SqliteConnection};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+/// 自动堆叠编辑副本
+///
+/// 同一文件夹中文件名为 `原图名 + 编辑后缀`、且拍摄时间与原图相同的照片会与原图堆叠，
+/// 网格中默认显示编辑后的版本。已在堆叠中的编辑副本不处理。
+/// - path 只处理此文件夹下的照片【为空表示全部】
+pub fn auto_stack(path: Option<&str>) -> Result<Vec<StackSummary>> {
+    let suffixes = sys_config().stack_edit_suffixes.clone().unwrap_or_default();
+    let mut conn = establish_connection();
+    let candidates = storage::photo_stack::get_stack_candidates(&mut conn, path)?;
+
+    // (文件夹, 小写文件名, 拍摄时间) -> (原图 ID, 所属堆叠)
+    let mut originals: HashMap<(String, String, i64), (i32, Option<i32>)> = HashMap::new();
+    let mut edits = Vec::new();
+    for (id, img_path, img_name, date_time, stack_id) in candidates {
+        let Some(date_time) = date_time else {
+            continue;
+        };
+        let stem = file_stem(&img_name);
+        match edit_base_stem(stem, &suffixes) {
+            Some(base) if stack_id.is_none() => {
+                edits.push((id, (img_path, base.to_lowercase(), date_time)));
+            }
+            Some(_) => {}
+            None => {
+                // 同名的 RAW 与 JPG 以先导入的为原图
+                originals
+                    .entry((img_path, stem.to_lowercase(), date_time))
+                    .or_insert((id, stack_id));
+            }
+        }
+    }
+
+    let stack_ids = conn.transaction(|conn| {
+        let mut stack_ids = BTreeSet::new();
+        for (edit_id, key) in edits {
+            let Some((original_id, stack_id)) = originals.get_mut(&key) else {
+                continue;
+            };
+            let id = match stack_id {
+                Some(id) => *id,
+                None => {
+                    let stack = storage::photo_stack::insert_stack(conn, *original_id, edit_id)?;
+                    storage::photo_stack::set_photos_stack(conn, &[*original_id], Some(stack.id))?;
+                    *stack_id = Some(stack.id);
+                    stack.id
+                }
+            };
+            storage::photo_stack::set_photos_stack(conn, &[edit_id], Some(id))?;
+            stack_ids.insert(id);
+        }
+        anyhow::Ok(stack_ids)
+    })?;
+    log::info!("自动堆叠完成，涉及 {} 个堆叠", stack_ids.len());
+    stack_ids
+        .into_iter()
+        .map(|id| stack_summary(&mut conn, id))
+        .collect()
+}
+
+/// RAW+JPEG 配对
+///
+/// 同一文件夹中与 RAW 同名的 JPEG 关联到 RAW 照片上（记录在 `paired_jpeg` 中），不作为单独的照片，
+/// 缩略图和预览按设置使用其中一个的图像；之前已单独导入的 JPEG 照片记录会被删除（文件保留），
+/// 已在堆叠中的除外。同名的 RAW 或 JPEG 多于一个时不配对，返回配对的数量
+/// - path 只处理此文件夹下的照片【为空表示全部】
+pub fn pair_raw_jpeg(path: Option<&str>) -> Result<usize> {
+    let mut conn = establish_connection();
+    let candidates = storage::photo_stack::get_stack_candidates(&mut conn, path)?;
+
+    // (文件夹, 小写文件名) -> (RAW 照片 (ID, 文件名), JPEG 照片 (ID, 所属堆叠))
+    let mut groups: HashMap<(String, String), (Vec<(i32, String)>, Vec<(i32, Option<i32>)>)> =
+        HashMap::new();
+    for (id, img_path, img_name, _, stack_id) in candidates {
+        let Some((stem, is_raw)) = raw_jpeg_stem(&img_name) else {
+            continue;
+        };
+        let group = groups.entry((img_path, stem.to_lowercase())).or_default();
+        if is_raw {
+            group.0.push((id, img_name.clone()));
+        } else {
+            group.1.push((id, stack_id));
+        }
+    }
+
+    // 以文件夹中实际存在的 JPEG 为准，JPEG 删除后取消配对
+    let mut links = Vec::new();
+    let mut jpeg_ids = Vec::new();
+    for ((folder, _), (raws, jpegs)) in &groups {
+        let [(raw, raw_name)] = raws.as_slice() else {
+            continue;
+        };
+        if jpegs.len() > 1 {
+            continue;
+        }
+        let jpeg = raw_preview_util::sibling_jpeg(&Path::new(folder).join(raw_name))
+            .and_then(|x| x.file_name().map(|x| x.to_string_lossy().to_string()));
+        if jpeg.is_some() {
+            jpeg_ids.extend(jpegs.iter().filter(|x| x.1.is_none()).map(|x| x.0));
+        }
+        links.push((*raw, jpeg));
+    }
+
+    let hashes: Vec<String> = storage::photo_table::search_photos_by_ids(&mut conn, &jpeg_ids)?
+        .into_iter()
+        .map(|x| x.hash)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    conn.transaction(|conn| {
+        for (raw, jpeg) in &links {
+            storage::photo_table::set_paired_jpeg(conn, *raw, jpeg.as_deref())?;
+        }
+        storage::photo_purge::purge_photos(conn, &jpeg_ids)?;
+        storage::photo_purge::delete_unused_hash_data(conn, &hashes)
+    })?;
+    for hash in &hashes {
+        thumbnail_cache_service::remove_unused_thumbnails(&mut conn, hash);
+    }
+    let count = links.iter().filter(|x| x.1.is_some()).count();
+    log::info!("RAW+JPEG 配对完成，共 {} 对", count);
+    Ok(count)
+}
+
+/// 手动堆叠照片【照片原来所在的堆叠会被调整或解散】
+/// - photo_ids 照片 ID，至少两张
+/// - original_photo_id 原图【为空时使用 ID 最小的照片】，同时作为网格中显示的版本
+pub fn stack_photos(photo_ids: &[i32], original_photo_id: Option<i32>) -> Result<StackSummary> {
+    let ids: BTreeSet<i32> = photo_ids.iter().copied().collect();
+    if ids.len() < 2 {
+        return Err(anyhow!(i18n::text(msg::STACK_TOO_FEW_PHOTOS)));
+    }
+    let original = original_photo_id.unwrap_or(*ids.first().unwrap());
+    if !ids.contains(&original) {
+        return Err(i18n::error(
+            msg::STACK_ORIGINAL_NOT_INCLUDED,
+            &[("id", original.to_string())],
+        ));
+    }
+    let ids: Vec<i32> = ids.into_iter().collect();
+
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::search_photos_by_ids(&mut conn, &ids)?;
+    if photos.len() != ids.len() {
+        return Err(anyhow!(i18n::text(msg::PHOTOS_PARTIALLY_MISSING)));
+    }
+    let old_stacks: BTreeSet<i32> = photos.iter().filter_map(|p| p.stack_id).collect();
+    let stack_id = conn.transaction(|conn| {
+        let stack = storage::photo_stack::insert_stack(conn, original, original)?;
+        storage::photo_stack::set_photos_stack(conn, &ids, Some(stack.id))?;
+        for old in old_stacks {
+            tidy_stack(conn, old)?;
+        }
+        anyhow::Ok(stack.id)
+    })?;
+    stack_summary(&mut conn, stack_id)
+}
+
+/// 把照片移出所在的堆叠【剩余不足两张的堆叠会被解散】
+/// - photo_ids 照片 ID
+pub fn unstack_photos(photo_ids: &[i32]) -> Result<()> {
+    let mut conn = establish_connection();
+    conn.transaction(|conn| unstack_photos_in(conn, photo_ids))
+}
+
+/// 在调用方的连接上把照片移出所在的堆叠【需要在事务中调用，删除照片前使用】
+/// - photo_ids 照片 ID
+pub fn unstack_photos_in(conn: &mut SqliteConnection, photo_ids: &[i32]) -> Result<()> {
+    let photos = storage::photo_table::search_photos_by_ids(conn, photo_ids)?;
+    let stacks: BTreeSet<i32> = photos.iter().filter_map(|p| p.stack_id).collect();
+    storage::photo_stack::set_photos_stack(conn, photo_ids, None)?;
+    for stack_id in stacks {
+        tidy_stack(conn, stack_id)?;
+    }
+    Ok(())
+}
+
+/// 设置堆叠在网格中显示的版本
+/// - photo_id 要显示的照片，必须已在堆叠中
+pub fn set_stack_cover(photo_id: i32) -> Result<StackSummary> {
+    let mut conn = establish_connection();
+    let photo = find_photo(&mut conn, photo_id)?;
+    let stack_id = photo.stack_id.ok_or_else(|| {
+        i18n::error(
+            msg::STACK_PHOTO_NOT_STACKED,
+            &[("id", photo_id.to_string())],
+        )
+    })?;
+    let stack = storage::photo_stack::get_stack(&mut conn, stack_id)?;
+    storage::photo_stack::update_stack(&mut conn, stack_id, stack.original_photo_id, photo_id)?;
+    stack_summary(&mut conn, stack_id)
+}
+
+/// 获取照片所在堆叠中的所有版本
+/// - photo_id 堆叠中的任一照片
+pub fn get_stack_photos(photo_id: i32) -> Result<Vec<Photo>> {
+    let mut conn = establish_connection();
+    let photo = find_photo(&mut conn, photo_id)?;
+    match photo.stack_id {
+        Some(stack_id) => storage::photo_stack::get_stack_photos(&mut conn, stack_id),
+        None => Ok(vec![photo]),
+    }
+}
+
+/// 成员变化后整理堆叠：不足两张时解散，原图或显示版本被移出时改用剩余的第一张
+fn tidy_stack(conn: &mut SqliteConnection, stack_id: i32) -> Result<()> {
+    let members = storage::photo_stack::get_stack_photo_ids(conn, stack_id)?;
+    if members.len() < 2 {
+        return storage::photo_stack::delete_stack(conn, stack_id);
+    }
+    let stack = storage::photo_stack::get_stack(conn, stack_id)?;
+    let original = if members.contains(&stack.original_photo_id) {
+        stack.original_photo_id
+    } else {
+        members[0]
+    };
+    let cover = if members.contains(&stack.cover_photo_id) {
+        stack.cover_photo_id
+    } else {
+        original
+    };
+    if original != stack.original_photo_id || cover != stack.cover_photo_id {
+        storage::photo_stack::update_stack(conn, stack_id, original, cover)?;
+    }
+    Ok(())
+}
+
+fn stack_summary(conn: &mut SqliteConnection, stack_id: i32) -> Result<StackSummary> {
+    let stack = storage::photo_stack::get_stack(conn, stack_id)?;
+    Ok(StackSummary {
+        stack_id,
+        original_photo_id: stack.original_photo_id,
+        cover_photo_id: stack.cover_photo_id,
+        photo_ids: storage::photo_stack::get_stack_photo_ids(conn, stack_id)?,
+    })
+}
+
+fn find_photo(conn: &mut SqliteConnection, photo_id: i32) -> Result<Photo> {
+    storage::photo_table::search_photos_by_ids(conn, &[photo_id])?
+        .pop()
+        .ok_or_else(|| i18n::error(msg::PHOTO_NOT_FOUND, &[("id", photo_id.to_string())]))
+}
+
+fn file_stem(img_name: &str) -> &str {
+    Path::new(img_name)
+        .file_stem()
+        .and_then(|x| x.to_str())
+        .unwrap_or(img_name)
+}
@@ -0,0 +1,162 @@
+use crate::constant::IMAGE_COMPRESSION_RATIO;
+use crate::errors::AError;
+use crate::i18n::{self, msg};
+use crate::services::{photo_activity_service, thumbnail_cache_service};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::photo_activity::ActivityKind;
+use crate::structs::photo_transform::{PhotoTransformResult, TransformMethod};
+use crate::structs::thumbnail_encoding;
+use crate::utils::exif_utils::exif_util::ExifToolCmd;
+use crate::utils::exif_utils::jpeg_segment;
+use crate::utils::exif_utils::orientation_util::{self, ImageTransform};
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::img_util::ImageOperate;
+use crate::utils::file_util;
+use anyhow::{anyhow, Result};
+use image::{ImageDecoder, ImageFormat, ImageReader};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// 旋转或翻转照片，并重新生成缩略图
+///
+/// JPEG 优先使用 jpegtran 在 DCT 域无损变换像素（保留所有元数据，方向标记重置为 1）；
+/// 非 JPEG、jpegtran 不可用或尺寸不满足无损变换要求时，只修改 EXIF 方向标记。
+/// - photo_id 照片 ID
+/// - transform 对显示画面的变换
+pub async fn transform_photo(
+    photo_id: i32,
+    transform: ImageTransform,
+) -> Result<PhotoTransformResult> {
+    let mut conn = establish_connection();
+    let photo = storage::photo_table::search_photos_by_ids(&mut conn, &[photo_id])?
+        .pop()
+        .ok_or_else(|| i18n::error(msg::PHOTO_NOT_FOUND, &[("id", photo_id.to_string())]))?;
+    let path = photo.full_path();
+    if !file_util::file_exists(file_util::long_path(&path)) {
+        return Err(anyhow!(AError::SpecifiedFileDoesNotExist.message()));
+    }
+
+    let file = path.clone();
+    let (method, orientation) =
+        tokio::task::spawn_blocking(move || transform_file(&file, transform)).await??;
+
+    // 文件内容已改变，先更新数据库中的 Hash，重新生成缩略图时才不会被当作新照片导入
+    let hash = FileHashUtils::sha256(file_util::long_path(&path))?;
+    let (width, height) = ImageReader::open(file_util::long_path(&path))?
+        .with_guessed_format()?
+        .into_dimensions()?;
+    let file_size = fs::metadata(file_util::long_path(&path))?.len() as i64;
+    storage::photo_table::update_photo_file_info(
+        &mut conn,
+        photo.id,
+        &hash,
+        width as i32,
+        height as i32,
+        file_size,
+    )?;
+    storage::photo_purge::delete_unused_hash_data(&mut conn, &[photo.hash.clone()])?;
+    thumbnail_cache_service::remove_unused_thumbnails(&mut conn, &photo.hash);
+
+    ImageOperate::multi_level_image_compression(
+        &path,
+        thumbnail_encoding::storage_format(),
+        IMAGE_COMPRESSION_RATIO.to_vec(),
+    )
+    .await?;
+    photo_activity_service::record(
+        &[photo_id],
+        ActivityKind::Edited,
+        Some(&transform_description(transform)),
+    );
+    log::info!(
+        "照片 {} 已{:?}，方式: {:?}，方向: {}",
+        path.display(),
+        transform,
+        method,
+        orientation
+    );
+    Ok(PhotoTransformResult {
+        photo_id,
+        method,
+        orientation,
+        hash,
+    })
+}
+
+/// 活动记录中的变换说明
+fn transform_description(transform: ImageTransform) -> String {
+    match transform {
+        ImageTransform::Rotate90 => i18n::t(msg::EDIT_ROTATE, &[("degrees", "90".to_string())]),
+        ImageTransform::Rotate180 => i18n::t(msg::EDIT_ROTATE, &[("degrees", "180".to_string())]),
+        ImageTransform::Rotate270 => i18n::t(msg::EDIT_ROTATE, &[("degrees", "270".to_string())]),
+        ImageTransform::FlipHorizontal => i18n::text(msg::EDIT_FLIP_HORIZONTAL).to_string(),
+        ImageTransform::FlipVertical => i18n::text(msg::EDIT_FLIP_VERTICAL).to_string(),
+    }
+}
+
+/// 修改文件，返回实现方式和文件中的方向标记
+fn transform_file(path: &Path, transform: ImageTransform) -> Result<(TransformMethod, u8)> {
+    let reader = ImageReader::open(file_util::long_path(path))?.with_guessed_format()?;
+    let format = reader.format();
+    let mut decoder = reader.into_decoder()?;
+    let current = decoder.orientation()?.to_exif();
+    drop(decoder);
+    let target = orientation_util::compose(current, transform);
+
+    if format == Some(ImageFormat::Jpeg) {
+        match jpegtran(path, target) {
+            Ok(()) => return Ok((TransformMethod::Lossless, 1)),
+            Err(e) => log::warn!("无损变换失败，改为修改方向标记 {}: {}", path.display(), e),
+        }
+    }
+    write_orientation(path, target)?;
+    Ok((TransformMethod::Orientation, target))
+}
+
+/// 使用 jpegtran 按方向摆正像素，写入临时文件后替换原图
+fn jpegtran(path: &Path, orientation: u8) -> Result<()> {
+    let temp = file_util::temp_path(path, None);
+    let mut cmd = Command::new(file_util::bundled_tool_path("jpegtran"));
+    cmd.args(["-copy", "all", "-perfect"]);
+    if let Some(args) = orientation_util::jpegtran_args(orientation) {
+        cmd.args(args);
+    }
+    let output = cmd
+        .arg("-outfile")
+        .arg(file_util::long_path(&temp))
+        .arg(file_util::long_path(path))
+        .output()
+        .map_err(|e| i18n::error(msg::JPEGTRAN_START_FAILED, &[("error", e.to_string())]))?;
+    if !output.status.success() {
+        let _ = fs::remove_file(file_util::long_path(&temp));
+        return Err(i18n::error(
+            msg::JPEGTRAN_FAILED,
+            &[(
+                "error",
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            )],
+        ));
+    }
+    // 像素已摆正，复制过来的方向标记需要重置
+    let mut data = fs::read(file_util::long_path(&temp))?;
+    if jpeg_segment::set_jpeg_orientation(&mut data, 1) {
+        fs::write(file_util::long_path(&temp), &data)?;
+    }
+    fs::rename(file_util::long_path(&temp), file_util::long_path(path))?;
+    Ok(())
+}
+
+/// 修改方向标记【JPEG 优先直接修改文件，没有方向标签时使用 exiftool 写入】
+fn write_orientation(path: &Path, orientation: u8) -> Result<()> {
+    let mut data = fs::read(file_util::long_path(path))?;
+    if jpeg_segment::set_jpeg_orientation(&mut data, orientation) {
+        let temp = file_util::temp_path(path, None);
+        fs::write(file_util::long_path(&temp), &data)?;
+        fs::rename(file_util::long_path(&temp), file_util::long_path(path))?;
+        return Ok(());
+    }
+    let value = orientation.to_string();
+    ExifToolCmd.write_tags(path, &[("Orientation#", Some(value.as_str()))])
+}
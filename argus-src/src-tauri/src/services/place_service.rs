@@ -0,0 +1,205 @@
+use crate::constant::{PLACE_CLUSTER_EPS_METERS, PLACE_MIN_PHOTOS};
+use crate::models::photo::Photo;
+use crate::models::place::{NewPlace, Place};
+use crate::services::{geocode_service, privacy_service};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::config::sys_config;
+use crate::structs::place::PlaceRefreshSummary;
+use crate::utils::exif_utils::gps_util::GpsCoordinate;
+use crate::utils::geo_cluster_util;
+use crate::utils::json_util::JsonUtil;
+use crate::utils::time_util::TimeUtils;
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// 聚类得到的地点
+struct PlaceCluster {
+    center: GpsCoordinate,
+    radius_meters: f64,
+    photo_ids: Vec<i32>,
+    /// 照片中出现次数最多的地点名称
+    location_name: Option<String>,
+}
+
+/// 重新聚类地点
+///
+/// 按拍摄位置聚类（DBSCAN），然后依次使用照片中的地点名称、上一次聚类时相同位置的名称、
+/// 逆地理编码结果（先查询缓存，设置了服务地址时再请求服务）为地点命名，都没有时使用中心坐标。
+/// 中心位于隐私区域内的地点不请求逆地理编码；与上一次聚类时位置相同的地点保留原来的 ID
+pub async fn refresh_places() -> Result<PlaceRefreshSummary> {
+    let (clusters, unclustered) = tokio::task::spawn_blocking(cluster_photos).await??;
+    let mut conn = establish_connection();
+    let previous = storage::place::list_places(&mut conn)?;
+    let url = sys_config().reverse_geocode_url.clone().unwrap_or_default();
+
+    let mut summary = PlaceRefreshSummary {
+        places: clusters.len(),
+        unclustered,
+        ..Default::default()
+    };
+    let now = TimeUtils::current_timestamp();
+    let mut items = Vec::with_capacity(clusters.len());
+    let mut matched_ids = Vec::new();
+    for cluster in clusters {
+        let matched = match_previous(&previous, &matched_ids, &cluster.center);
+        if let Some(place) = matched {
+            matched_ids.push(place.id);
+        }
+        let mut name = cluster.location_name.clone().or_else(|| {
+            matched
+                .filter(|x| !is_coordinate_name(x))
+                .map(|x| x.name.clone())
+        });
+        // 隐私区域内的地点不请求逆地理编码，只使用照片中已有的地点名称
+        if name.is_none() && !privacy_service::in_privacy_zone(&cluster.center) {
+            match geocode_service::reverse_geocode(&url, &cluster.center).await {
+                Ok(Some(x)) => {
+                    summary.geocoded += 1;
+                    name = Some(x);
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("地点 {} 逆地理编码失败: {}", cluster.center, e),
+            }
+        }
+        summary.clustered += cluster.photo_ids.len();
+        let place = NewPlace {
+            name: name.unwrap_or_else(|| coordinate_name(&cluster.center)),
+            latitude: cluster.center.latitude,
+            longitude: cluster.center.longitude,
+            radius_meters: cluster.radius_meters,
+            photo_count: cluster.photo_ids.len() as i32,
+            create_time: matched.map_or(now, |x| x.create_time),
+            update_time: now,
+        };
+        items.push((matched.map(|x| x.id), place, cluster.photo_ids));
+    }
+    storage::place::replace_places(&mut conn, items)?;
+    log::info!("地点聚类完成: {:?}", summary);
+    Ok(summary)
+}
+
+/// 获取所有地点【按照片数量从多到少】
+pub fn list_places() -> Result<Vec<Place>> {
+    let mut conn = establish_connection();
+    storage::place::list_places(&mut conn)
+}
+
+/// 获取地点中的照片【按拍摄时间排序】
+pub fn get_place_photos(place_id: i32) -> Result<Vec<Photo>> {
+    let mut conn = establish_connection();
+    storage::place::get_place(&mut conn, place_id)?;
+    storage::place::get_place_photos(&mut conn, place_id)
+}
+
+/// 按位置聚类照片，同时返回没有归入任何地点的照片数量
+fn cluster_photos() -> Result<(Vec<PlaceCluster>, usize)> {
+    let mut conn = establish_connection();
+    let mut ids = Vec::new();
+    let mut points = Vec::new();
+    let mut names = Vec::new();
+    for (id, gps_info, location_name) in storage::place::get_geotagged_photos(&mut conn)? {
+        match JsonUtil::from_json::<GpsCoordinate>(&gps_info) {
+            Ok(x) => {
+                ids.push(id);
+                points.push(x);
+                names.push(location_name);
+            }
+            Err(e) => log::warn!("照片 {} 的位置解析失败: {}", id, e),
+        }
+    }
+
+    let labels = geo_cluster_util::dbscan(&points, PLACE_CLUSTER_EPS_METERS, PLACE_MIN_PHOTOS);
+    let count = labels.iter().flatten().max().map_or(0, |x| x + 1);
+    let mut members: Vec<Vec<usize>> = vec![Vec::new(); count];
+    let mut unclustered = 0;
+    for (index, label) in labels.iter().enumerate() {
+        match label {
+            Some(x) => members[*x].push(index),
+            None => unclustered += 1,
+        }
+    }
+
+    let clusters = members
+        .into_iter()
+        .map(|indexes| {
+            let coordinates: Vec<&GpsCoordinate> = indexes.iter().map(|&i| &points[i]).collect();
+            let (center, radius_meters) = geo_cluster_util::centroid(&coordinates);
+            PlaceCluster {
+                center,
+                radius_meters,
+                photo_ids: indexes.iter().map(|&i| ids[i]).collect(),
+                location_name: most_common(indexes.iter().filter_map(|&i| names[i].as_deref())),
+            }
+        })
+        .collect();
+    Ok((clusters, unclustered))
+}
+
+/// 出现次数最多的名称【次数相同时取先出现的】
+pub fn most_common<'a>(values: impl Iterator<Item = &'a str>) -> Option<String> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    for value in values.map(str::trim).filter(|x| !x.is_empty()) {
+        match positions.get(value) {
+            Some(&i) => counts[i].1 += 1,
+            None => {
+                positions.insert(value, counts.len());
+                counts.push((value, 1));
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .rev()
+        .max_by_key(|x| x.1)
+        .map(|x| x.0.to_string())
+}
+
+/// 上一次聚类时位于同一位置的地点【取距离最近且还没有对应新地点的】
+/// - previous 上一次聚类的地点
+/// - matched_ids 已经对应新地点的 ID
+/// - center 新地点的中心
+fn match_previous<'a>(
+    previous: &'a [Place],
+    matched_ids: &[i32],
+    center: &GpsCoordinate,
+) -> Option<&'a Place> {
+    previous
+        .iter()
+        .filter(|x| !matched_ids.contains(&x.id))
+        .map(|x| (x, place_center(x).distance_meters(center)))
+        .filter(|(_, distance)| *distance <= PLACE_CLUSTER_EPS_METERS)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|x| x.0)
+}
+
+/// 是否使用坐标命名【这类地点重新聚类时重新编码】
+fn is_coordinate_name(place: &Place) -> bool {
+    place.name == coordinate_name(&place_center(place))
+}
+
+fn place_center(place: &Place) -> GpsCoordinate {
+    GpsCoordinate {
+        latitude: place.latitude,
+        longitude: place.longitude,
+        altitude: None,
+    }
+}
+
+/// 使用中心坐标作为名称
+fn coordinate_name(center: &GpsCoordinate) -> String {
+    format!("{:.3}, {:.3}", center.latitude, center.longitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_most_common() {
+        let names = ["西湖", "", "灵隐寺", "灵隐寺", "西湖"];
+        assert_eq!(most_common(names.into_iter()).as_deref(), Some("西湖"));
+        assert_eq!(most_common([" "].into_iter()), None);
+    }
+}
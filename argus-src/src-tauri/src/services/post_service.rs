@@ -12,14 +12,12 @@ pub fn get_all_post() {
 
     let vec = post::get_all_post(conn);
     if vec.len() > 0 {
-        println!("Displaying {} posts", vec.len());
+        log::info!("Displaying {} posts", vec.len());
         for post in vec {
-            println!("{}", post.title);
-            println!("-----------\n");
-            println!("{}", post.body);
+            log::info!("{}\n-----------\n{}", post.title, post.body);
         }
     } else {
-        println!("No posts");
+        log::info!("No posts");
     }
 }
 
@@ -28,5 +26,5 @@ pub fn insert_post() {
     let conn = &mut establish_connection();
     log::info!("inserting post");
     let post1 = post::insert_post(conn, "默认标题", "默认Body");
-    println!("Displaying post success {}", post1.title);
+    log::info!("Displaying post success {}", post1.title);
 }
@@ -0,0 +1,177 @@
+use crate::constant::PRIVACY_BLUR_DECIMALS;
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::services::metadata_edit_service;
+use crate::structs::config::{save_config, sys_config};
+use crate::structs::edit_journal::EditSummary;
+use crate::structs::privacy_zone::{PrivacyZone, PublicLocation};
+use crate::utils::exif_utils::exif_util::ExifToolCmd;
+use crate::utils::exif_utils::gps_util::GpsCoordinate;
+use crate::utils::exif_utils::jpeg_segment::{self, IptcDataSetId};
+use crate::utils::json_util::JsonUtil;
+use crate::utils::uuid_util;
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+/// 所有隐私区域
+pub fn list_zones() -> Vec<PrivacyZone> {
+    sys_config().privacy_zones.clone().unwrap_or_default()
+}
+
+/// 坐标是否位于任一隐私区域内【区域内的位置不发送给逆地理编码服务】
+pub fn in_privacy_zone(coordinate: &GpsCoordinate) -> bool {
+    list_zones().iter().any(|x| x.contains(coordinate))
+}
+
+/// 新建或修改隐私区域【ID 为空时新建】
+pub fn save_zone(zone: PrivacyZone) -> Result<PrivacyZone> {
+    let mut zone = zone;
+    zone.name = zone.name.trim().to_string();
+    if zone.name.is_empty() {
+        return Err(anyhow!(i18n::text(msg::PRIVACY_ZONE_NAME_REQUIRED)));
+    }
+    if !(-90.0..=90.0).contains(&zone.latitude) || !(-180.0..=180.0).contains(&zone.longitude) {
+        return Err(i18n::error(
+            msg::PRIVACY_ZONE_CENTER_INVALID,
+            &[("name", zone.name.to_string())],
+        ));
+    }
+    if zone.radius_meters.is_nan() || zone.radius_meters <= 0.0 {
+        return Err(i18n::error(
+            msg::PRIVACY_ZONE_RADIUS_INVALID,
+            &[("name", zone.name.to_string())],
+        ));
+    }
+    let mut config = (*sys_config()).clone();
+    let zones = config.privacy_zones.get_or_insert_with(Vec::new);
+    if zone.id.is_empty() {
+        zone.id = uuid_util::uuid_v4();
+        zones.push(zone.clone());
+    } else {
+        let existing = zones.iter_mut().find(|x| x.id == zone.id).ok_or_else(|| {
+            i18n::error(msg::PRIVACY_ZONE_NOT_FOUND, &[("id", zone.id.to_string())])
+        })?;
+        *existing = zone.clone();
+    }
+    save_config(&config)?;
+    Ok(zone)
+}
+
+/// 删除隐私区域
+pub fn delete_zone(zone_id: &str) -> Result<()> {
+    let mut config = (*sys_config()).clone();
+    let zones = config.privacy_zones.get_or_insert_with(Vec::new);
+    let count = zones.len();
+    zones.retain(|x| x.id != zone_id);
+    if zones.len() == count {
+        return Err(i18n::error(
+            msg::PRIVACY_ZONE_NOT_FOUND,
+            &[("id", zone_id.to_string())],
+        ));
+    }
+    save_config(&config)
+}
+
+/// 删除照片位置【只修改数据库，可撤销；之后导出的副本也不再包含位置】
+pub fn strip_gps(photo_ids: &[i32]) -> Result<EditSummary> {
+    metadata_edit_service::set_gps(photo_ids, None)
+}
+
+/// 照片对外公开的位置
+///
+/// 数据库中没有位置（如已执行 `strip_gps`）时不公开位置；位置无法解析时无法判断是否位于隐私区域，返回错误
+pub fn public_location(photo: &Photo) -> Result<PublicLocation> {
+    let coordinate = match photo.gps_info.as_deref() {
+        Some(x) => Some(JsonUtil::from_json::<GpsCoordinate>(x).map_err(|e| {
+            i18n::error(
+                msg::PRIVACY_LOCATION_INVALID,
+                &[("id", photo.id.to_string()), ("error", e.to_string())],
+            )
+        })?),
+        None => None,
+    };
+    Ok(PublicLocation::resolve(
+        &list_zones(),
+        coordinate.as_ref(),
+        PRIVACY_BLUR_DECIMALS,
+    ))
+}
+
+/// 照片对外公开的地点名称【位置被模糊、删除或无法解析时不公开】
+pub fn public_location_name(photo: &Photo) -> Option<String> {
+    match public_location(photo) {
+        Ok(PublicLocation::Original) => photo.location_name.clone(),
+        _ => None,
+    }
+}
+
+/// 按隐私设置处理导出副本中的位置
+///
+/// 照片位于隐私区域内时模糊或删除位置；数据库中没有位置（如已执行 `strip_gps`）时删除副本中的位置。
+/// 位置被模糊或删除时同时删除副本中的地点名称（城市、国家等）。
+/// 处理失败时返回错误，避免导出精确位置
+/// - photo 照片
+/// - path 导出的副本
+pub fn scrub_exported_copy(photo: &Photo, path: &Path) -> Result<()> {
+    let location_names = [
+        ("IPTC:City", None),
+        ("IPTC:Sub-location", None),
+        ("IPTC:Province-State", None),
+        ("IPTC:Country-PrimaryLocationCode", None),
+        ("IPTC:Country-PrimaryLocationName", None),
+        ("XMP-photoshop:City", None),
+        ("XMP-photoshop:State", None),
+        ("XMP-photoshop:Country", None),
+        ("XMP-iptcCore:Location", None),
+        ("XMP-iptcCore:CountryCode", None),
+    ];
+    match public_location(photo)? {
+        PublicLocation::Original => Ok(()),
+        PublicLocation::Removed if !may_contain_location(path) => Ok(()),
+        PublicLocation::Removed => {
+            let mut tags = vec![("GPS:all", None), ("XMP:GPS*", None)];
+            tags.extend(location_names);
+            ExifToolCmd.write_tags(path, &tags)
+        }
+        PublicLocation::Blurred(x) => {
+            let latitude = x.latitude.abs().to_string();
+            let longitude = x.longitude.abs().to_string();
+            let latitude_ref = if x.latitude < 0.0 { "S" } else { "N" };
+            let longitude_ref = if x.longitude < 0.0 { "W" } else { "E" };
+            let mut tags = vec![
+                ("XMP:GPS*", None),
+                ("GPSLatitude", Some(latitude.as_str())),
+                ("GPSLatitudeRef", Some(latitude_ref)),
+                ("GPSLongitude", Some(longitude.as_str())),
+                ("GPSLongitudeRef", Some(longitude_ref)),
+                ("GPSAltitude", None),
+                ("GPSAltitudeRef", None),
+            ];
+            tags.extend(location_names);
+            ExifToolCmd.write_tags(path, &tags)
+        }
+    }
+}
+
+/// 文件中是否可能包含位置或地点名称【JPEG 直接检查 EXIF、XMP 和 IPTC，其他格式无法快速判断时视为包含】
+fn may_contain_location(path: &Path) -> bool {
+    match jpeg_segment::read_jpeg_metadata(path) {
+        Ok(metadata) => {
+            metadata
+                .exif
+                .as_deref()
+                .is_some_and(jpeg_segment::exif_has_gps)
+                || metadata.xmp.as_deref().is_some_and(|x| {
+                    ["GPSLatitude", "photoshop:City", "Iptc4xmpCore:Location"]
+                        .iter()
+                        .any(|name| x.contains(name))
+                })
+                || metadata
+                    .iptc
+                    .iter()
+                    .any(|x| IptcDataSetId::LOCATIONS.contains(&x.dataset))
+        }
+        Err(_) => true,
+    }
+}
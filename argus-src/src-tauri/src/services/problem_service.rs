@@ -0,0 +1,183 @@
+use crate::constant::PROBLEM_SAMPLE_BYTES;
+use crate::models::problem::{NewProblem, ProblemRecord};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::problem::{ProblemFile, ProblemKind};
+use crate::utils::file_util;
+use crate::utils::time_util::TimeUtils;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// 文件大小和修改时间（毫秒）【读取失败时返回 None】
+fn file_state(path: &Path) -> Option<(i64, i64)> {
+    let metadata = fs::metadata(file_util::long_path(path)).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_millis() as i64;
+    Some((metadata.len() as i64, modified))
+}
+
+/// 读取文件开头的字节并转换为十六进制
+fn read_sample(path: &Path) -> String {
+    let mut buffer = Vec::with_capacity(PROBLEM_SAMPLE_BYTES);
+    if let Ok(file) = fs::File::open(file_util::long_path(path)) {
+        let _ = file
+            .take(PROBLEM_SAMPLE_BYTES as u64)
+            .read_to_end(&mut buffer);
+    }
+    buffer.iter().map(|x| format!("{:02X}", x)).collect()
+}
+
+/// 读取所有问题文件【路径 -> 记录】
+///
+/// 扫描开始时读取一次，扫描期间判断是否跳过、是否需要清除记录，不再逐个查询数据库；
+/// 读取失败时返回空，不跳过任何文件
+pub fn load_known() -> HashMap<String, ProblemRecord> {
+    let mut conn = establish_connection();
+    match storage::problem::list_problems(&mut conn) {
+        Ok(records) => records.into_iter().map(|x| (x.path.clone(), x)).collect(),
+        Err(e) => {
+            log::warn!("问题文件查询失败: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+/// 文件是否为未变化的问题文件【扫描时跳过】
+///
+/// 仅云端文件下载后大小和修改时间通常不变，由扫描时重新检查是否为占位文件，这里不跳过；
+/// 只有 EXIF 警告的文件已正常导入，也不跳过
+/// - known 扫描开始时读取的问题文件
+pub fn should_skip(known: &HashMap<String, ProblemRecord>, path: &Path) -> bool {
+    let Some(record) = known.get(&file_util::display_path(path)) else {
+        return false;
+    };
+    matches!(
+        ProblemKind::from_code(record.kind),
+        ProblemKind::Decode | ProblemKind::Exif
+    ) && file_state(path) == Some((record.file_size, record.modified_time))
+}
+
+/// 记录问题文件
+/// - path 文件路径
+/// - kind 错误类型
+/// - error 错误信息
+pub fn record(path: &Path, kind: ProblemKind, error: &str) {
+    let (file_size, modified_time) = file_state(path).unwrap_or_default();
+    let item = NewProblem {
+        path: file_util::display_path(path),
+        kind: kind.code(),
+        message: error.to_string(),
+        sample: read_sample(path),
+        file_size,
+        modified_time,
+        update_time: TimeUtils::current_timestamp(),
+    };
+    let mut conn = establish_connection();
+    if let Err(e) = storage::problem::save_problem(&mut conn, item) {
+        log::error!("问题文件记录失败 {}: {}", path.display(), e);
+    }
+}
+
+/// 记录仅云端文件【不读取文件内容，避免触发下载】
+/// - path 文件路径
+/// - provider 云盘名称【无法判断时为空】
+pub fn record_cloud_only(path: &Path, provider: Option<&str>) {
+    let (file_size, modified_time) = file_state(path).unwrap_or_default();
+    let item = NewProblem {
+        path: file_util::display_path(path),
+        kind: ProblemKind::CloudOnly.code(),
+        message: provider.unwrap_or_default().to_string(),
+        sample: String::new(),
+        file_size,
+        modified_time,
+        update_time: TimeUtils::current_timestamp(),
+    };
+    let mut conn = establish_connection();
+    if let Err(e) = storage::problem::save_problem(&mut conn, item) {
+        log::error!("仅云端文件记录失败 {}: {}", path.display(), e);
+    }
+}
+
+/// 文件已能正常读取，删除问题记录
+pub fn resolve(path: &Path) {
+    let mut conn = establish_connection();
+    match storage::problem::delete_problem(&mut conn, &file_util::display_path(path)) {
+        Ok(0) => {}
+        Ok(_) => log::info!("问题文件已恢复: {}", path.display()),
+        Err(e) => log::warn!("问题文件记录删除失败 {}: {}", path.display(), e),
+    }
+}
+
+/// 扫描时文件已能正常读取，删除问题记录【扫描开始时没有记录的文件不查询数据库】
+/// - known 扫描开始时读取的问题文件
+pub fn resolve_known(known: &HashMap<String, ProblemRecord>, path: &Path) {
+    if known.contains_key(&file_util::display_path(path)) {
+        resolve(path);
+    }
+}
+
+/// 删除文件已不存在的问题记录【文件被删除或移走后不再显示】
+pub fn prune_missing() -> Result<usize> {
+    let mut conn = establish_connection();
+    let missing: Vec<String> = storage::problem::list_problems(&mut conn)?
+        .into_iter()
+        .filter(|x| {
+            fs::symlink_metadata(file_util::long_path(Path::new(&x.path)))
+                .is_err_and(|e| e.kind() == std::io::ErrorKind::NotFound)
+        })
+        .map(|x| x.path)
+        .collect();
+    let rows = storage::problem::delete_problems(&mut conn, &missing)?;
+    if rows > 0 {
+        log::info!("已删除 {} 条文件不存在的问题记录", rows);
+    }
+    Ok(rows)
+}
+
+/// 所有问题文件
+pub fn list_problem_files() -> Result<Vec<ProblemFile>> {
+    let mut conn = establish_connection();
+    let records = storage::problem::list_problems(&mut conn)?;
+    Ok(records.into_iter().map(ProblemFile::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_skip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.jpg");
+        fs::write(&path, b"broken").unwrap();
+        let (file_size, modified_time) = file_state(&path).unwrap();
+        let record = |kind: ProblemKind| ProblemRecord {
+            id: 1,
+            path: file_util::display_path(&path),
+            kind: kind.code(),
+            message: String::new(),
+            sample: String::new(),
+            file_size,
+            modified_time,
+            fail_count: 1,
+            create_time: 0,
+            update_time: 0,
+        };
+        let known = |kind| HashMap::from([(file_util::display_path(&path), record(kind))]);
+
+        assert!(should_skip(&known(ProblemKind::Decode), &path));
+        assert!(!should_skip(&known(ProblemKind::ExifWarning), &path));
+        assert!(!should_skip(&HashMap::new(), &path));
+        // 文件变化后重新读取
+        fs::write(&path, b"fixed image").unwrap();
+        assert!(!should_skip(&known(ProblemKind::Decode), &path));
+    }
+}
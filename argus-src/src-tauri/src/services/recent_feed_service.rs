@@ -0,0 +1,117 @@
+use crate::constant::{
+    IMPORT_BATCH_IDLE_SECS, IMPORT_BATCH_PREVIEW_PHOTOS, RECENT_FEED_DEFAULT_LIMIT,
+};
+use crate::models::import_batch::NewImportBatch;
+use crate::models::photo::Photo;
+use crate::services::place_service::most_common;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::recent_feed::{FeedPage, ImportBatchSummary};
+use crate::utils::time_util::TimeUtils;
+use anyhow::Result;
+use diesel::SqliteConnection;
+
+/// 把新导入的照片记录到导入批次
+///
+/// 距最近一批的最后一张照片不超过空闲时间时加入这一批，否则新建一批，
+/// 来源为照片最多的文件夹
+pub fn record_import(connection: &mut SqliteConnection, photos: &[Photo]) -> Result<()> {
+    if photos.is_empty() {
+        return Ok(());
+    }
+    let now = TimeUtils::current_timestamp();
+    let batch = match storage::import_batch::latest_batch(connection)? {
+        Some(x) if now - x.end_time <= IMPORT_BATCH_IDLE_SECS => x,
+        _ => storage::import_batch::insert_batch(
+            connection,
+            NewImportBatch {
+                source: most_common(photos.iter().map(|x| x.img_path.as_str())).unwrap_or_default(),
+                photo_count: 0,
+                start_time: now,
+                end_time: now,
+            },
+        )?,
+    };
+    let photo_ids: Vec<i32> = photos.iter().map(|x| x.id).collect();
+    storage::import_batch::add_batch_photos(connection, batch.id, &photo_ids, now)
+}
+
+/// 记录照片被浏览
+pub fn record_view(photo_id: i32) -> Result<()> {
+    let mut conn = establish_connection();
+    storage::photo_table::update_last_viewed_time(&mut conn, photo_id)
+}
+
+/// 获取最近导入的批次【按导入时间从新到旧，每批包含几张预览照片】
+/// - limit 返回数量【为空时使用默认数量】
+/// - offset 跳过数量
+pub fn get_recent_imports(
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Result<FeedPage<ImportBatchSummary>> {
+    let mut conn = establish_connection();
+    let batches = storage::import_batch::list_batches(
+        &mut conn,
+        limit.unwrap_or(RECENT_FEED_DEFAULT_LIMIT),
+        offset.unwrap_or(0),
+    )?;
+    let mut items = Vec::with_capacity(batches.len());
+    for batch in batches {
+        let preview_photos = storage::import_batch::get_batch_photos(
+            &mut conn,
+            batch.id,
+            IMPORT_BATCH_PREVIEW_PHOTOS,
+            0,
+        )?;
+        items.push(ImportBatchSummary {
+            batch_id: batch.id,
+            source: batch.source,
+            photo_count: batch.photo_count,
+            start_time: batch.start_time,
+            end_time: batch.end_time,
+            preview_photos,
+        });
+    }
+    Ok(FeedPage {
+        items,
+        total: storage::import_batch::count_batches(&mut conn)?,
+    })
+}
+
+/// 获取导入批次中的照片【按导入顺序】
+/// - limit 返回数量【为空时使用默认数量】
+/// - offset 跳过数量
+pub fn get_import_batch_photos(
+    batch_id: i32,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Result<FeedPage<Photo>> {
+    let mut conn = establish_connection();
+    let batch = storage::import_batch::get_batch(&mut conn, batch_id)?;
+    let items = storage::import_batch::get_batch_photos(
+        &mut conn,
+        batch_id,
+        limit.unwrap_or(RECENT_FEED_DEFAULT_LIMIT),
+        offset.unwrap_or(0),
+    )?;
+    Ok(FeedPage {
+        items,
+        total: batch.photo_count as i64,
+    })
+}
+
+/// 获取最近浏览的照片【按浏览时间从新到旧】
+/// - limit 返回数量【为空时使用默认数量】
+/// - offset 跳过数量
+pub fn get_recently_viewed(limit: Option<i64>, offset: Option<i64>) -> Result<FeedPage<Photo>> {
+    let mut conn = establish_connection();
+    let items = storage::photo_table::search_recently_viewed(
+        &mut conn,
+        limit.unwrap_or(RECENT_FEED_DEFAULT_LIMIT),
+        offset.unwrap_or(0),
+    )?;
+    Ok(FeedPage {
+        items,
+        total: storage::photo_table::count_recently_viewed(&mut conn)?,
+    })
+}
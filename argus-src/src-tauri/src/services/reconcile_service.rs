@@ -0,0 +1,187 @@
+use crate::models::photo::Photo;
+use crate::services::{folder_album_service, photo_stack_service, thumbnail_cache_service};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::reconcile::{
+    MissingPhoto, MissingReason, ReconcileAction, ReconcileReport, ReconcileResult,
+};
+use crate::utils::file_util;
+use anyhow::Result;
+use diesel::{Connection, SqliteConnection};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// 找出文件已不存在的照片
+///
+/// 区分卷离线和文件已删除，同时更新照片的可访问状态：
+/// 文件不存在的标记为不可访问，文件重新出现的恢复为可访问；远程存储中的照片不检查
+pub fn find_missing_photos() -> Result<ReconcileReport> {
+    let mut conn = establish_connection();
+    let mut photos = storage::photo_table::search_all_photos(&mut conn)?;
+    photos.retain(|x| !x.is_remote());
+    let roots = storage_roots(&mut conn)?;
+
+    let mut report = ReconcileReport {
+        checked: photos.len(),
+        ..Default::default()
+    };
+    let mut unavailable = Vec::new();
+    let mut available = Vec::new();
+    for photo in photos {
+        let path = photo.full_path();
+        if file_util::file_exists(&path) {
+            if !photo.is_available {
+                available.push(photo.id);
+            }
+            continue;
+        }
+        let reason = missing_reason(&path, &roots);
+        match reason {
+            MissingReason::VolumeOffline => report.volume_offline += 1,
+            MissingReason::Gone => report.gone += 1,
+        }
+        if photo.is_available {
+            unavailable.push(photo.id);
+        }
+        report.missing.push(MissingPhoto {
+            photo_id: photo.id,
+            path: file_util::display_path(&path),
+            hash: photo.hash,
+            reason,
+        });
+    }
+    storage::photo_table::set_photos_available(&mut conn, &unavailable, false)?;
+    report.restored = storage::photo_table::set_photos_available(&mut conn, &available, true)?;
+    log::info!(
+        "照片对账完成: 检查 {}，卷离线 {}，已删除 {}，恢复 {}",
+        report.checked,
+        report.volume_offline,
+        report.gone,
+        report.restored
+    );
+    Ok(report)
+}
+
+/// 处理文件已不存在的照片
+///
+/// 处理前重新检查，文件已重新出现的照片跳过；卷离线的照片默认跳过，避免误删
+/// - photo_ids 照片 ID
+/// - action 移入回收站或彻底删除
+/// - include_offline 是否一并处理卷离线的照片
+pub fn resolve_missing_photos(
+    photo_ids: &[i32],
+    action: ReconcileAction,
+    include_offline: bool,
+) -> Result<ReconcileResult> {
+    let mut conn = establish_connection();
+    let roots = storage_roots(&mut conn)?;
+    let photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    let targets: Vec<&Photo> = photos
+        .iter()
+        .filter(|p| {
+            let path = p.full_path();
+            !p.is_remote()
+                && !file_util::file_exists(&path)
+                && (include_offline || missing_reason(&path, &roots) == MissingReason::Gone)
+        })
+        .collect();
+    let ids: Vec<i32> = targets.iter().map(|p| p.id).collect();
+    let hashes: Vec<String> = targets
+        .iter()
+        .map(|p| p.hash.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let mut result = ReconcileResult {
+        action,
+        photos: 0,
+        skipped: photo_ids.len() - ids.len(),
+    };
+    if ids.is_empty() {
+        return Ok(result);
+    }
+
+    result.photos = conn.transaction(|conn| {
+        photo_stack_service::unstack_photos_in(conn, &ids)?;
+        match action {
+            ReconcileAction::Archive => storage::photo_table::mark_photos_deleted(conn, &ids),
+            ReconcileAction::Purge => {
+                let rows = storage::photo_purge::purge_photos(conn, &ids)?;
+                storage::photo_purge::delete_unused_hash_data(conn, &hashes)?;
+                Ok(rows)
+            }
+        }
+    })?;
+    // 没有其他照片使用的缩略图一并删除
+    for hash in &hashes {
+        thumbnail_cache_service::remove_unused_thumbnails(&mut conn, hash);
+    }
+    folder_album_service::request_sync();
+    log::info!("文件不存在的照片已处理: {:?}", result);
+    Ok(result)
+}
+
+/// 图像存储路径及其是否可访问
+fn storage_roots(conn: &mut SqliteConnection) -> Result<Vec<(PathBuf, bool)>> {
+    let roots = storage::photo_storage::get_all_photo_path(conn)?
+        .into_iter()
+        .map(|x| {
+            let root = PathBuf::from(x.img_paths);
+            let accessible = root.is_dir();
+            (root, accessible)
+        })
+        .collect();
+    Ok(roots)
+}
+
+/// 判断文件不存在的原因
+///
+/// 在图像存储路径下时，按存储路径是否可访问判断；
+/// 不在任何存储路径下时，所在文件夹还在说明文件已删除，否则按卷离线处理【宁可保留】
+fn missing_reason(path: &Path, roots: &[(PathBuf, bool)]) -> MissingReason {
+    let root = roots
+        .iter()
+        .filter(|(root, _)| path.starts_with(root))
+        .max_by_key(|(root, _)| root.as_os_str().len());
+    let online = match root {
+        Some((_, accessible)) => *accessible,
+        None => path.parent().is_some_and(|p| p.is_dir()),
+    };
+    if online {
+        MissingReason::Gone
+    } else {
+        MissingReason::VolumeOffline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_reason() {
+        let online = tempfile::tempdir().unwrap();
+        let offline = online.path().join("unmounted");
+        let roots = vec![
+            (online.path().to_path_buf(), true),
+            (offline.clone(), false),
+        ];
+        let gone = online.path().join("a.jpg");
+        assert_eq!(missing_reason(&gone, &roots), MissingReason::Gone);
+        let unreachable = offline.join("b.jpg");
+        assert_eq!(
+            missing_reason(&unreachable, &roots),
+            MissingReason::VolumeOffline
+        );
+        // 不在存储路径下，按所在文件夹是否存在判断
+        let other = tempfile::tempdir().unwrap();
+        assert_eq!(
+            missing_reason(&other.path().join("c.jpg"), &[]),
+            MissingReason::Gone
+        );
+        assert_eq!(
+            missing_reason(&other.path().join("x").join("c.jpg"), &[]),
+            MissingReason::VolumeOffline
+        );
+    }
+}
@@ -0,0 +1,510 @@
+use crate::constant::{IMAGE_COMPRESSION_RATIO, REMOTE_PHOTO_PATH_PREFIX, REMOTE_PROBE_BYTES};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::models::remote_storage::{
+    NewRemoteObject, NewRemoteStorage, RemoteObjectRecord, RemoteStorageRecord,
+};
+use crate::services::{disk_space_service, photo_stack_service, thumbnail_cache_service};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::disk_space::DiskSpacePurpose;
+use crate::structs::remote_storage::{
+    RemoteObject, RemoteStorage, RemoteStorageKind, RemoteSyncSummary, RemoteUploadSummary,
+};
+use crate::structs::thumbnail_encoding;
+use crate::utils::exif_utils::exif_util;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::img_util::ImageOperate;
+use crate::utils::remote_backend::{self, RemoteBackend};
+use crate::utils::time_util::TimeUtils;
+use crate::utils::{file_util, secret_util};
+use anyhow::{anyhow, Result};
+use diesel::{Connection, SqliteConnection};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 是否有正在执行的同步
+static SYNC_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// 获取所有远程存储【不包含密钥】
+pub fn list_storages() -> Result<Vec<RemoteStorage>> {
+    let mut conn = establish_connection();
+    Ok(storage::remote_storage::get_all_storages(&mut conn)?
+        .into_iter()
+        .map(to_storage)
+        .collect())
+}
+
+/// 保存远程存储【ID 为空时新增；修改时密钥为空表示沿用原来的密钥】
+///
+/// 密钥加密后保存，见 `secret_util`
+pub fn save_storage(storage: RemoteStorage) -> Result<RemoteStorage> {
+    storage.validate()?;
+    let mut conn = establish_connection();
+    let secret_key = match (storage.id, &storage.secret_key) {
+        (Some(id), None) => {
+            storage::remote_storage::get_storage(&mut conn, id)?
+                .ok_or_else(|| {
+                    i18n::error(msg::REMOTE_STORAGE_NOT_FOUND, &[("id", id.to_string())])
+                })?
+                .secret_key
+        }
+        (_, secret) => secret
+            .as_deref()
+            .filter(|x| !x.is_empty())
+            .map(secret_util::seal)
+            .transpose()?,
+    };
+    let trimmed = |x: &Option<String>| {
+        x.as_deref()
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+            .map(str::to_string)
+    };
+    let item = NewRemoteStorage {
+        name: storage.name.trim().to_string(),
+        kind: storage.kind.as_str().to_string(),
+        endpoint: storage.endpoint.trim().to_string(),
+        bucket: trimmed(&storage.bucket),
+        region: trimmed(&storage.region),
+        prefix: trimmed(&storage.prefix).map(|x| x.trim_matches('/').to_string()),
+        access_key: trimmed(&storage.access_key),
+        secret_key,
+        is_enable: storage.is_enable,
+        update_time: TimeUtils::current_timestamp(),
+    };
+    let record = match storage.id {
+        Some(id) => storage::remote_storage::update_storage(&mut conn, id, &item)?,
+        None => storage::remote_storage::insert_storage(&mut conn, &item)?,
+    };
+    Ok(to_storage(record))
+}
+
+/// 加密数据库中以明文保存的密钥【程序启动时调用，兼容加密之前保存的远程存储】
+pub fn seal_stored_secrets() -> Result<usize> {
+    let mut conn = establish_connection();
+    let mut count = 0;
+    for record in storage::remote_storage::get_all_storages(&mut conn)? {
+        let Some(secret) = record.secret_key.filter(|x| !secret_util::is_sealed(x)) else {
+            continue;
+        };
+        let sealed = secret_util::seal(&secret)?;
+        storage::remote_storage::update_secret(&mut conn, record.id, &sealed)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// 删除远程存储及其照片记录【远程文件不删除】
+pub fn delete_storage(id: i32) -> Result<()> {
+    let mut conn = establish_connection();
+    let objects: Vec<i32> = storage::remote_storage::get_objects(&mut conn, id)?
+        .iter()
+        .map(|x| x.id)
+        .collect();
+    purge_photos(&mut conn, &objects)?;
+    storage::remote_storage::delete_storage(&mut conn, id)
+}
+
+/// 测试远程存储能否访问，返回远程列表中的图片数量
+/// - storage 远程存储配置【已保存的存储密钥为空时使用保存的密钥】
+pub async fn test_storage(mut storage: RemoteStorage) -> Result<usize> {
+    storage.validate()?;
+    if let (Some(id), None) = (storage.id, &storage.secret_key) {
+        storage.secret_key = load_config(id)?.secret_key;
+    }
+    let objects = RemoteBackend::new(&storage)?.list().await?;
+    Ok(objects
+        .iter()
+        .filter(|x| file_util::is_image_file(&x.key))
+        .count())
+}
+
+/// 获取远程存储中的照片【包括与本地照片内容相同的照片】
+pub fn list_remote_photos(storage_id: i32) -> Result<Vec<Photo>> {
+    let mut conn = establish_connection();
+    storage::photo_table::search_remote_photos(&mut conn, storage_id)
+}
+
+/// 同步远程存储中的照片
+///
+/// 列出远程目录中的所有图片并更新照片记录；新增或变化的照片先按范围读取文件开头获取 EXIF，
+/// 再下载到临时文件计算 Hash、生成本地缩略图，一起写入照片表，原图不保存在本地
+pub async fn sync_storage(storage_id: i32) -> Result<RemoteSyncSummary> {
+    if SYNC_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err(anyhow!(i18n::text(msg::REMOTE_SYNC_RUNNING)));
+    }
+    let result = sync(storage_id).await;
+    SYNC_RUNNING.store(false, Ordering::SeqCst);
+    let summary = result?;
+    log::info!("远程存储 {} 同步完成: {:?}", storage_id, summary);
+    Ok(summary)
+}
+
+async fn sync(storage_id: i32) -> Result<RemoteSyncSummary> {
+    let config = load_config(storage_id)?;
+    if !config.is_enable {
+        return Err(i18n::error(
+            msg::REMOTE_STORAGE_DISABLED,
+            &[("name", config.name.clone())],
+        ));
+    }
+    let backend = RemoteBackend::new(&config)?;
+    let listed: Vec<RemoteObject> = backend
+        .list()
+        .await?
+        .into_iter()
+        .filter(|x| file_util::is_image_file(&x.key))
+        .collect();
+
+    let mut summary = RemoteSyncSummary {
+        listed: listed.len(),
+        ..Default::default()
+    };
+    let mut conn = establish_connection();
+    let mut existing: HashMap<String, RemoteObjectRecord> =
+        storage::remote_storage::get_objects(&mut conn, storage_id)?
+            .into_iter()
+            .map(|x| (x.object_key.clone(), x))
+            .collect();
+    // 内容变化的照片重新写入照片表
+    let mut changed = Vec::new();
+    for object in &listed {
+        let previous = existing.remove(&object.key);
+        match &previous {
+            None => summary.added += 1,
+            Some(record) if is_changed(record, object) => {
+                summary.updated += 1;
+                changed.push(record.id);
+            }
+            Some(record) if record.etag == object.etag => continue,
+            Some(_) => {}
+        }
+        let item = listing_item(storage_id, object, previous.as_ref());
+        storage::remote_storage::upsert_object(&mut conn, &item)?;
+    }
+    let removed: Vec<i32> = existing.values().map(|x| x.id).collect();
+    changed.extend(&removed);
+    purge_photos(&mut conn, &changed)?;
+    if !removed.is_empty() {
+        summary.removed = storage::remote_storage::delete_objects(&mut conn, &removed)?;
+    }
+
+    for record in storage::remote_storage::get_objects(&mut conn, storage_id)? {
+        if record.thumbnail_time == 0 {
+            match cache_thumbnails(&backend, &record).await {
+                Ok(hash) => {
+                    storage::remote_storage::save_thumbnail(&mut conn, record.id, &hash)?;
+                    summary.thumbnails += 1;
+                }
+                Err(e) => {
+                    log::warn!("远程照片缩略图生成失败 {}: {}", record.object_key, e);
+                    summary.failed.push(format!("{}: {}", record.object_key, e));
+                }
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// 上传本地文件到远程存储【导入时使用，远程已有相同内容的文件时跳过】
+///
+/// 上传后直接使用本地文件读取文件头并生成缩略图，同名文件已存在时在文件名后添加 `_1`、`_2`
+/// - storage_id 远程存储 ID
+/// - files 本地文件
+/// - folder 上传到目录前缀下的子目录【为空时上传到目录前缀】
+pub async fn upload_files(
+    storage_id: i32,
+    files: &[PathBuf],
+    folder: Option<&str>,
+) -> Result<RemoteUploadSummary> {
+    let config = load_config(storage_id)?;
+    if !config.is_enable {
+        return Err(i18n::error(
+            msg::REMOTE_STORAGE_DISABLED,
+            &[("name", config.name.clone())],
+        ));
+    }
+    let backend = RemoteBackend::new(&config)?;
+    let mut summary = RemoteUploadSummary::default();
+    for file in files {
+        match upload_file(&backend, &config, storage_id, file, folder).await {
+            Ok(true) => summary.uploaded += 1,
+            Ok(false) => summary.skipped += 1,
+            Err(e) => {
+                log::warn!("上传到远程存储失败 {}: {}", file.display(), e);
+                summary
+                    .failed
+                    .push(format!("{}: {}", file_util::display_path(file), e));
+            }
+        }
+    }
+    log::info!("上传到远程存储 {} 完成: {:?}", storage_id, summary);
+    Ok(summary)
+}
+
+/// 上传单个文件，返回是否上传【远程已有相同内容时返回 false】
+async fn upload_file(
+    backend: &RemoteBackend,
+    config: &RemoteStorage,
+    storage_id: i32,
+    file: &Path,
+    folder: Option<&str>,
+) -> Result<bool> {
+    let name = file
+        .file_name()
+        .map(|x| x.to_string_lossy().to_string())
+        .ok_or_else(|| anyhow!(i18n::text(msg::REMOTE_FILE_NAME_INVALID)))?;
+    let hash = FileHashUtils::sha256_async(file).await?;
+    let mut conn = establish_connection();
+    if storage::remote_storage::find_object_by_hash(&mut conn, storage_id, &hash)?.is_some() {
+        return Ok(false);
+    }
+
+    let dir = remote_backend::join_key(config.prefix.as_deref(), folder.unwrap_or_default());
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) => (stem.to_string(), format!(".{}", extension)),
+        None => (name.clone(), String::new()),
+    };
+    let mut key = remote_backend::join_key(Some(dir.as_str()), &name);
+    let mut index = 1;
+    while storage::remote_storage::find_object_by_key(&mut conn, storage_id, &key)?.is_some() {
+        key = remote_backend::join_key(
+            Some(dir.as_str()),
+            &format!("{}_{}{}", stem, index, extension),
+        );
+        index += 1;
+    }
+    backend.upload(&key, file).await?;
+
+    let now = TimeUtils::current_timestamp();
+    let size = std::fs::metadata(file_util::long_path(file))?.len();
+    let record = storage::remote_storage::upsert_object(
+        &mut conn,
+        &NewRemoteObject {
+            storage_id,
+            object_key: key,
+            size: size as i64,
+            etag: None,
+            modified_time: now,
+            hash: Some(hash.clone()),
+            thumbnail_time: 0,
+            update_time: now,
+        },
+    )?;
+
+    // 原图就在本地，直接读取 EXIF 写入照片表、生成缩略图
+    let imported = match ImageOperate::inspect_image(file).await {
+        Ok(image) => import_photo(&record, image).await,
+        Err(e) => Err(e),
+    };
+    match imported {
+        Ok(_) => storage::remote_storage::save_thumbnail(&mut conn, record.id, &hash)?,
+        Err(e) => log::warn!("远程照片缩略图生成失败 {}: {}", file.display(), e),
+    }
+    Ok(true)
+}
+
+/// 读取远程存储配置【包含解密后的密钥】
+fn load_config(id: i32) -> Result<RemoteStorage> {
+    let mut conn = establish_connection();
+    let record = storage::remote_storage::get_storage(&mut conn, id)?
+        .ok_or_else(|| i18n::error(msg::REMOTE_STORAGE_NOT_FOUND, &[("id", id.to_string())]))?;
+    let secret_key = record
+        .secret_key
+        .as_deref()
+        .map(secret_util::open)
+        .transpose()?;
+    Ok(RemoteStorage {
+        secret_key,
+        ..to_storage(record)
+    })
+}
+
+/// 转换为前端使用的结构【不包含密钥】
+fn to_storage(record: RemoteStorageRecord) -> RemoteStorage {
+    RemoteStorage {
+        id: Some(record.id),
+        name: record.name,
+        kind: RemoteStorageKind::from_str(&record.kind).unwrap_or(RemoteStorageKind::S3),
+        endpoint: record.endpoint,
+        bucket: record.bucket,
+        region: record.region,
+        prefix: record.prefix,
+        access_key: record.access_key,
+        secret_key: None,
+        has_secret: record.secret_key.is_some_and(|x| !x.is_empty()),
+        is_enable: record.is_enable,
+    }
+}
+
+/// 远程文件是否变化【大小不同，或两边都有 ETag 且不同】
+///
+/// 上传时还没有 ETag，下次同步时补充 ETag 不视为变化
+fn is_changed(record: &RemoteObjectRecord, object: &RemoteObject) -> bool {
+    record.size != object.size as i64
+        || (record.etag.is_some() && object.etag.is_some() && record.etag != object.etag)
+}
+
+/// 远程列表中的对象对应的记录【文件变化时清空读取过的信息，否则保留】
+fn listing_item(
+    storage_id: i32,
+    object: &RemoteObject,
+    previous: Option<&RemoteObjectRecord>,
+) -> NewRemoteObject {
+    let kept = previous.filter(|x| !is_changed(x, object));
+    NewRemoteObject {
+        storage_id,
+        object_key: object.key.clone(),
+        size: object.size as i64,
+        etag: object.etag.clone(),
+        modified_time: object.modified,
+        hash: kept.and_then(|x| x.hash.clone()),
+        thumbnail_time: kept.map(|x| x.thumbnail_time).unwrap_or_default(),
+        update_time: TimeUtils::current_timestamp(),
+    }
+}
+
+/// 读取远程照片的 EXIF，下载到临时文件生成缩略图并写入照片表，返回文件 Hash【临时文件用完即删除】
+///
+/// EXIF 从按范围读取的文件开头中读取，下载的原图只用于计算 Hash、校验图像和生成缩略图；
+/// 文件开头中没有 EXIF 时（如 PNG）从下载的原图中读取
+async fn cache_thumbnails(backend: &RemoteBackend, record: &RemoteObjectRecord) -> Result<String> {
+    let header = backend
+        .read_range(&record.object_key, 0, REMOTE_PROBE_BYTES)
+        .await?;
+    let exif = exif_util::read_header_exif(&header);
+    let temp_dir = std::env::temp_dir();
+    disk_space_service::ensure_free_space(&temp_dir, DiskSpacePurpose::Import, record.size as u64)?;
+    let temp = tempfile::Builder::new()
+        .prefix("argus-remote-")
+        .tempfile_in(&temp_dir)?;
+    backend.download(&record.object_key, temp.path()).await?;
+    let image = match exif {
+        Some(exif) => ImageOperate::inspect_image_with_exif(temp.path(), Some(exif)).await?,
+        None => ImageOperate::inspect_image(temp.path()).await?,
+    };
+    import_photo(record, image).await
+}
+
+/// 把远程照片写入照片表并生成缩略图，返回文件 Hash
+///
+/// 照片表中已有相同内容的照片（如本地照片库中的同一张照片）时不重复写入，只生成缺少的缩略图
+/// - record 远程对象
+/// - image 原图的本地文件【下载的临时文件，或刚上传的本地文件】读取的图像信息
+async fn import_photo(record: &RemoteObjectRecord, mut image: ImageOperate) -> Result<String> {
+    let (dir, name) = remote_path(record.storage_id, &record.object_key);
+    image.img_path = dir;
+    image.img_name = name;
+    let (mut photo, exif) = storage::photo_table::to_scanned_photo(image.clone());
+    // 原图不在本地，不保存临时文件的路径
+    photo.img_path_raw = None;
+    let mut conn = establish_connection();
+    let inserted = storage::photo_table::bulk_upsert(&mut conn, vec![(photo, exif)])?;
+    if let Some(photo) = inserted.first() {
+        storage::photo_table::link_remote_object(&mut conn, photo.id, record.id)?;
+    }
+    image
+        .generate_thumbnails(
+            thumbnail_encoding::storage_format(),
+            &IMAGE_COMPRESSION_RATIO,
+        )
+        .await?;
+    Ok(image.hash)
+}
+
+/// 远程照片在照片表中的路径和文件名【路径为 `remote://{存储 ID}/{目录}`】
+fn remote_path(storage_id: i32, key: &str) -> (String, String) {
+    let (dir, name) = key.rsplit_once('/').unwrap_or(("", key));
+    (
+        format!("{}{}/{}", REMOTE_PHOTO_PATH_PREFIX, storage_id, dir),
+        name.to_string(),
+    )
+}
+
+/// 彻底删除远程对象对应的照片记录【远程文件已删除或内容已变化】
+fn purge_photos(conn: &mut SqliteConnection, object_ids: &[i32]) -> Result<usize> {
+    let photos = storage::photo_table::search_photos_by_remote_objects(conn, object_ids)?;
+    if photos.is_empty() {
+        return Ok(0);
+    }
+    let ids: Vec<i32> = photos.iter().map(|x| x.id).collect();
+    let hashes: Vec<String> = photos
+        .iter()
+        .map(|x| x.hash.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let rows = conn.transaction(|conn| {
+        photo_stack_service::unstack_photos_in(conn, &ids)?;
+        let rows = storage::photo_purge::purge_photos(conn, &ids)?;
+        storage::photo_purge::delete_unused_hash_data(conn, &hashes)?;
+        Ok::<_, anyhow::Error>(rows)
+    })?;
+    for hash in &hashes {
+        thumbnail_cache_service::remove_unused_thumbnails(conn, hash);
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(size: i64, etag: Option<&str>) -> RemoteObjectRecord {
+        RemoteObjectRecord {
+            id: 1,
+            storage_id: 1,
+            object_key: "a.jpg".to_string(),
+            size,
+            etag: etag.map(str::to_string),
+            modified_time: 0,
+            hash: Some("hash".to_string()),
+            thumbnail_time: 1,
+            create_time: 0,
+            update_time: 0,
+        }
+    }
+
+    fn object(size: u64, etag: Option<&str>) -> RemoteObject {
+        RemoteObject {
+            key: "a.jpg".to_string(),
+            size,
+            modified: 0,
+            etag: etag.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_is_changed() {
+        assert!(!is_changed(
+            &record(100, Some("a")),
+            &object(100, Some("a"))
+        ));
+        assert!(is_changed(&record(100, Some("a")), &object(100, Some("b"))));
+        assert!(is_changed(&record(100, None), &object(200, Some("b"))));
+        // 上传后第一次同步补充 ETag
+        assert!(!is_changed(&record(100, None), &object(100, Some("b"))));
+
+        let kept = listing_item(1, &object(100, Some("b")), Some(&record(100, None)));
+        assert_eq!(kept.hash.as_deref(), Some("hash"));
+        assert_eq!(kept.etag.as_deref(), Some("b"));
+        let reset = listing_item(1, &object(200, Some("b")), Some(&record(100, Some("a"))));
+        assert_eq!(reset.hash, None);
+        assert_eq!(reset.thumbnail_time, 0);
+    }
+
+    #[test]
+    fn test_remote_path() {
+        assert_eq!(
+            remote_path(3, "photos/2024/a.jpg"),
+            ("remote://3/photos/2024".to_string(), "a.jpg".to_string())
+        );
+        assert_eq!(
+            remote_path(3, "a.jpg"),
+            ("remote://3/".to_string(), "a.jpg".to_string())
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use crate::constant::IMAGE_COMPRESSION_RATIO;
+use crate::i18n;
+use crate::i18n::msg;
+use crate::services::{cloud_file_service, folder_album_service, photo_stack_service};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::rescan::RescanPlan;
+use crate::structs::thumbnail_encoding;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::file_util;
+use crate::utils::img_util::ImageOperate;
+use anyhow::Result;
+use diesel::Connection;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// 重新扫描文件夹（含子文件夹）
+///
+/// 对比磁盘文件和数据库记录，找出新增、删除和移动的照片。
+/// `dry_run` 为 true 时只返回差异，确认后再次调用才会修改数据库：
+/// 移动的照片更新路径，已不存在的照片标记为删除，新增的照片导入并生成缩略图。
+/// - path 文件夹
+/// - dry_run 是否只返回差异
+pub async fn rescan_folder(path: &str, dry_run: bool) -> Result<RescanPlan> {
+    let root = PathBuf::from(path);
+    // 文件夹不可访问时（如卷未挂载）所有记录都会被当作删除，必须拒绝
+    if !root.is_dir() {
+        return Err(i18n::error(
+            msg::FOLDER_NOT_FOUND,
+            &[("path", path.to_string())],
+        ));
+    }
+    let (mut plan, files) = tokio::task::spawn_blocking(move || plan_rescan(&root)).await??;
+    plan.dry_run = dry_run;
+    if dry_run || plan.is_empty() {
+        return Ok(plan);
+    }
+
+    let removed: Vec<i32> = plan.removed.iter().map(|x| x.photo_id).collect();
+    let mut conn = establish_connection();
+    conn.transaction(|conn| {
+        photo_stack_service::unstack_photos_in(conn, &removed)?;
+        for item in &plan.moved {
+            storage::photo_table::update_photo_location(conn, item.photo_id, &files[&item.to])?;
+        }
+        storage::photo_table::mark_photos_deleted(conn, &removed)?;
+        anyhow::Ok(())
+    })?;
+
+    for file in &plan.added {
+        if cloud_file_service::should_skip(&files[file]) {
+            cloud_file_service::record(&files[file]);
+            continue;
+        }
+        if let Err(e) = ImageOperate::multi_level_image_compression(
+            &files[file],
+            thumbnail_encoding::storage_format(),
+            IMAGE_COMPRESSION_RATIO.to_vec(),
+        )
+        .await
+        {
+            log::warn!("照片导入失败 {}: {}", file, e);
+        }
+    }
+    folder_album_service::request_sync();
+    log::info!(
+        "重新扫描 {} 完成: 新增 {}，删除 {}，移动 {}",
+        plan.root,
+        plan.added.len(),
+        plan.removed.len(),
+        plan.moved.len()
+    );
+    Ok(plan)
+}
+
+/// 计算差异，同时返回磁盘文件（展示路径 -> 实际路径）
+fn plan_rescan(root: &Path) -> Result<(RescanPlan, HashMap<String, PathBuf>)> {
+    let mut conn = establish_connection();
+    let records: Vec<(i32, PathBuf, String)> =
+        storage::photo_table::search_photo_under_root(&mut conn, root)?
+            .into_iter()
+            .map(|p| (p.id, p.full_path(), p.hash.clone()))
+            .collect();
+
+    let mut files = Vec::new();
+    file_util::walk_dir_img_parallel(root, |img| {
+        files.push(file_util::strip_verbatim_prefix(img));
+        true
+    });
+    // 云盘占位文件不计算 Hash，避免触发下载
+    let mut plan = RescanPlan::diff(&records, &files, |file| {
+        if cloud_file_service::should_skip(file) {
+            return None;
+        }
+        FileHashUtils::sha256(file_util::long_path(file)).ok()
+    });
+    plan.root = file_util::display_path(root);
+    let files = files
+        .into_iter()
+        .map(|x| (x.display().to_string(), x))
+        .collect();
+    Ok((plan, files))
+}
@@ -0,0 +1,67 @@
+use crate::constant::{IMAGE_COMPRESSION_RATIO, RESIZE_BENCHMARK_DEFAULT_SAMPLE};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::resize_backend::{ResizeBackend, ResizeBenchmark};
+use crate::utils::img_util::ImageOperate;
+use crate::utils::resize_util;
+use anyhow::{anyhow, Result};
+use image::imageops::FilterType;
+use image::DynamicImage;
+use std::time::Instant;
+
+/// 对比各缩放实现生成缩略图的速度
+///
+/// 随机抽取照片解码后，依次使用每种实现生成所有尺寸的缩略图【只计算缩放耗时，不含解码和编码；
+/// 当前环境不可用的实现（如没有显卡）不参与对比】
+/// - sample 抽取的照片数量
+pub fn benchmark_resize(sample: Option<u32>) -> Result<Vec<ResizeBenchmark>> {
+    let sample = sample.unwrap_or(RESIZE_BENCHMARK_DEFAULT_SAMPLE).max(1);
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::sample_available_photos(&mut conn, Some(sample as i64))?;
+    let images: Vec<DynamicImage> = photos
+        .iter()
+        .filter_map(|photo| {
+            ImageOperate::open_oriented(&photo.full_path())
+                .map_err(|e| log::warn!("照片 {} 解码失败: {}", photo.id, e))
+                .ok()
+        })
+        .collect();
+    if images.is_empty() {
+        return Err(anyhow!(i18n::text(msg::BENCHMARK_NO_PHOTOS)));
+    }
+    let megapixels: f64 = images
+        .iter()
+        .map(|x| x.width() as f64 * x.height() as f64 / 1_000_000.0)
+        .sum::<f64>()
+        * IMAGE_COMPRESSION_RATIO.len() as f64;
+
+    let result = ResizeBackend::ALL
+        .into_iter()
+        .filter(|x| resize_util::is_available(*x))
+        .map(|backend| {
+            let started = Instant::now();
+            let mut thumbnails = 0;
+            for image in &images {
+                for size in IMAGE_COMPRESSION_RATIO.iter().map(|x| x.size) {
+                    let _ =
+                        resize_util::resize_with(backend, image, size, size, FilterType::Triangle);
+                    thumbnails += 1;
+                }
+            }
+            let elapsed = started.elapsed();
+            let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+            ResizeBenchmark {
+                backend,
+                images: images.len(),
+                thumbnails,
+                elapsed_ms: elapsed.as_millis() as u64,
+                megapixels_per_sec: megapixels / seconds,
+                thumbnails_per_sec: thumbnails as f64 / seconds,
+            }
+        })
+        .collect();
+    log::info!("缩放性能对比: {:?}", result);
+    Ok(result)
+}
@@ -0,0 +1,110 @@
+use crate::constant::{
+    SCAN_PREVIEW_DEFAULT_FILES_PER_SEC, SCAN_PREVIEW_HISTORY, SCAN_PREVIEW_MIN_HISTORY_FILES,
+    SCAN_REPORT_TOP_N,
+};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::scan_report::{ScanPreview, ScanReport, ScanStatus, ScanTimingCollector};
+use crate::utils::file_util;
+use crate::utils::json_util::JsonUtil;
+use anyhow::Result;
+use std::fs;
+
+/// 开始扫描，返回扫描 ID
+pub fn start_scan() -> Result<i32> {
+    let mut conn = establish_connection();
+    storage::scan_report::insert_scan_report(&mut conn, ScanStatus::Running.code())
+}
+
+/// 结束扫描并保存耗时报告
+pub fn finish_scan(scan_id: i32, status: ScanStatus, collector: &ScanTimingCollector) -> Result<()> {
+    let summary = collector.summarize(SCAN_REPORT_TOP_N);
+    let mut conn = establish_connection();
+    storage::scan_report::finish_scan_report(
+        &mut conn,
+        scan_id,
+        status.code(),
+        summary.file_count as i32,
+        summary.total_millis as i64,
+        &JsonUtil::stringify(&summary.slowest_files)?,
+        &JsonUtil::stringify(&summary.slowest_folders)?,
+    )?;
+    log::info!(
+        "扫描 {} 结束: {} 个文件，共耗时 {} ms",
+        scan_id,
+        summary.file_count,
+        summary.total_millis
+    );
+    Ok(())
+}
+
+/// 获取扫描报告
+pub fn get_scan_report(scan_id: i32) -> Result<ScanReport> {
+    let mut conn = establish_connection();
+    let record = storage::scan_report::get_scan_report(&mut conn, scan_id)?;
+    Ok(ScanReport {
+        scan_id: record.id,
+        status: ScanStatus::from_code(record.status),
+        file_count: record.file_count as u32,
+        total_millis: record.total_millis as u64,
+        start_time: record.start_time,
+        end_time: record.end_time,
+        slowest_files: JsonUtil::from_json(&record.slowest_files)?,
+        slowest_folders: JsonUtil::from_json(&record.slowest_folders)?,
+    })
+}
+
+/// 扫描前预估工作量
+///
+/// 只遍历目录统计图片数量和大小，不计算 Hash、不解码，
+/// 耗时按最近几次扫描的处理速度估算
+/// - paths 要扫描的文件夹
+pub fn preview_scan(paths: &[String]) -> Result<ScanPreview> {
+    let mut preview = ScanPreview::default();
+    for path in paths {
+        file_util::walk_dir_img_parallel(path, |img| {
+            preview.file_count += 1;
+            preview.total_bytes += fs::metadata(&img).map(|x| x.len()).unwrap_or_default();
+            true
+        });
+    }
+
+    let mut conn = establish_connection();
+    let history: Vec<(u64, u64)> = storage::scan_report::recent_finished_reports(
+        &mut conn,
+        ScanStatus::Finished.code(),
+        SCAN_PREVIEW_MIN_HISTORY_FILES,
+        SCAN_PREVIEW_HISTORY,
+    )?
+    .into_iter()
+    .map(|x| (x.file_count as u64, (x.end_time - x.start_time) as u64))
+    .collect();
+    preview.from_history = !history.is_empty();
+    preview.files_per_sec = files_per_sec(&history);
+    preview.estimated_seconds = (preview.file_count as f64 / preview.files_per_sec).ceil() as u64;
+    log::info!("扫描预估: {:?}", preview);
+    Ok(preview)
+}
+
+/// 以往扫描的平均处理速度（文件/秒）
+/// - history 每次扫描处理的文件数量和耗时（秒）
+fn files_per_sec(history: &[(u64, u64)]) -> f64 {
+    let files: u64 = history.iter().map(|x| x.0).sum();
+    let seconds: u64 = history.iter().map(|x| x.1).sum();
+    if files == 0 || seconds == 0 {
+        return SCAN_PREVIEW_DEFAULT_FILES_PER_SEC;
+    }
+    files as f64 / seconds as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_files_per_sec() {
+        assert_eq!(files_per_sec(&[]), SCAN_PREVIEW_DEFAULT_FILES_PER_SEC);
+        assert_eq!(files_per_sec(&[(1000, 50), (500, 25)]), 20.0);
+        assert_eq!(files_per_sec(&[(300, 0)]), SCAN_PREVIEW_DEFAULT_FILES_PER_SEC);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::services::{backup_service, folder_album_service, user_profile_service};
+use crate::storage::connection;
+use crate::structs::config::{self, SettingIssue};
+use crate::structs::library::{self, current_library};
+use crate::utils::log_util;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 修改后需要重启才能生效的配置项
+const RESTART_REQUIRED_KEYS: [&str; 4] = [
+    "python_service_path",
+    "http_server_enabled",
+    "http_server_address",
+    "http_server_port",
+];
+
+/// 重新加载配置的结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsReload {
+    /// 有变化的配置项
+    pub changed: Vec<String>,
+    /// 需要重启才能生效的配置项
+    pub restart_required: Vec<String>,
+    /// 无效并已替换为默认值的配置项
+    pub issues: Vec<SettingIssue>,
+}
+
+/// 重新读取当前照片库的配置文件并立即生效
+///
+/// 数据库位置变化时会初始化新的数据库【初始化失败时继续使用原来的配置并返回错误】
+pub fn reload_settings() -> Result<SettingsReload> {
+    let library = current_library();
+    let (config, issues) = config::load_config_checked(&library.root)?;
+    let changed = changed_keys(&library.config, &config)?;
+    let database_changed = changed
+        .iter()
+        .any(|x| x == "database_path" || x == "database_name");
+
+    let log_level = config.log_level.as_deref().and_then(log_util::parse_level);
+    let old_config = (*library.config).clone();
+    library::set_current_config(config);
+    if database_changed {
+        if let Err(e) = connection::run_migrations() {
+            // 新数据库不可用时继续使用原来的配置
+            library::set_current_config(old_config);
+            return Err(i18n::error(
+                msg::SETTINGS_DATABASE_INIT_FAILED,
+                &[("error", e.to_string())],
+            ));
+        }
+    }
+    if let Some(level) = log_level {
+        log_util::set_level(None, Some(level));
+    }
+    // 默认用户没有设置界面语言时使用配置文件中的语言
+    user_profile_service::apply_default_profile();
+
+    if changed
+        .iter()
+        .any(|x| x == "backup_interval_hours" || x == "backup_path")
+    {
+        backup_service::reschedule_auto_backup();
+    }
+    if changed.iter().any(|x| x == "mirror_folder_albums") {
+        folder_album_service::sync_in_background();
+    }
+
+    let restart_required = changed
+        .iter()
+        .filter(|x| RESTART_REQUIRED_KEYS.contains(&x.as_str()))
+        .cloned()
+        .collect();
+    log::info!("配置已重新加载，变化的配置项: {:?}", changed);
+    Ok(SettingsReload {
+        changed,
+        restart_required,
+        issues,
+    })
+}
+
+/// 比较两份配置，返回值不同的配置项
+fn changed_keys(old: &config::Config, new: &config::Config) -> Result<Vec<String>> {
+    let (Value::Object(old), Value::Object(new)) =
+        (serde_json::to_value(old)?, serde_json::to_value(new)?)
+    else {
+        return Ok(Vec::new());
+    };
+    let mut keys: Vec<String> = new
+        .iter()
+        .filter(|(key, value)| old.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect();
+    keys.extend(old.keys().filter(|x| !new.contains_key(*x)).cloned());
+    keys.sort();
+    Ok(keys)
+}
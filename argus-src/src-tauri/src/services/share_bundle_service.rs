@@ -0,0 +1,213 @@
+use crate::constant::{
+    BACKUP_TIME_FMT, EXPORT_DEFAULT_JPEG_QUALITY, SHARE_BUNDLE_DEFAULT_MAX_SIZE,
+    SHARE_BUNDLE_PREFIX,
+};
+use crate::i18n::{self, msg};
+use crate::models::photo::Photo;
+use crate::services::photo_activity_service;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::photo_activity::ActivityKind;
+use crate::structs::share_bundle::{ShareBundleOptions, ShareBundleSummary};
+use crate::utils::img_util::ImageOperate;
+use crate::utils::time_util::TimeUtils;
+use crate::utils::{file_util, url_util, zip_util};
+use anyhow::{anyhow, Result};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView};
+use quick_xml::escape::escape;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// 分享包中的照片文件夹
+const PHOTOS_FOLDER: &str = "photos";
+
+/// 写入分享包的照片
+struct BundlePhoto {
+    /// 相对分享包的路径
+    file: String,
+    width: u32,
+    height: u32,
+    caption: Option<String>,
+}
+
+/// 生成可以直接发给别人的分享包
+///
+/// 照片按 EXIF 方向摆正、缩小后重新编码为 JPEG，不包含任何元数据（位置、相机、作者等）；
+/// 同时生成可以离线浏览的 `index.html` 相册。任何照片处理失败都不会中断生成
+/// - photo_ids 照片 ID【按顺序排列】
+/// - options 分享包选项
+pub fn create_share_bundle(
+    photo_ids: &[i32],
+    options: &ShareBundleOptions,
+) -> Result<ShareBundleSummary> {
+    let mut conn = establish_connection();
+    let mut photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    photos.sort_by_key(|x| photo_ids.iter().position(|id| *id == x.id));
+    if photos.is_empty() {
+        return Err(anyhow!(i18n::text(msg::SHARE_NO_PHOTOS)));
+    }
+
+    let dest_dir = match options.dest_path.as_deref().filter(|x| !x.is_empty()) {
+        Some(x) => PathBuf::from(x),
+        None => std::env::temp_dir(),
+    };
+    let folder = file_util::unique_path(&dest_dir.join(format!(
+        "{}{}",
+        SHARE_BUNDLE_PREFIX,
+        TimeUtils::current_datetime_string(Some(BACKUP_TIME_FMT))
+    )));
+    fs::create_dir_all(folder.join(PHOTOS_FOLDER))?;
+
+    let mut summary = ShareBundleSummary::default();
+    let mut items = Vec::with_capacity(photos.len());
+    let mut shared_ids = Vec::with_capacity(photos.len());
+    for (index, photo) in photos.iter().enumerate() {
+        match write_photo(photo, index, &folder, options) {
+            Ok(item) => {
+                shared_ids.push(photo.id);
+                items.push(item);
+            }
+            Err(e) => {
+                log::warn!("分享照片处理失败 {}: {}", photo.full_path().display(), e);
+                summary.failed.push(format!("{}: {}", photo.img_name, e));
+            }
+        }
+    }
+    if items.is_empty() {
+        let _ = fs::remove_dir_all(&folder);
+        return Err(i18n::error(
+            msg::SHARE_ALL_FAILED,
+            &[("errors", summary.failed.join("; "))],
+        ));
+    }
+    fs::write(folder.join("index.html"), render_index(&items, options))?;
+    summary.photo_count = items.len();
+
+    let path = if options.zip.unwrap_or(false) {
+        let zip = file_util::unique_path(&folder.with_extension("zip"));
+        let result = zip_util::zip_dir(&folder, &zip);
+        let _ = fs::remove_dir_all(&folder);
+        summary.total_bytes = result.inspect_err(|_| {
+            let _ = fs::remove_file(&zip);
+        })?;
+        zip
+    } else {
+        summary.total_bytes = dir_size(&folder);
+        folder
+    };
+    summary.path = file_util::display_path(&path);
+    photo_activity_service::record(&shared_ids, ActivityKind::Shared, Some(&summary.path));
+    log::info!("分享包生成完成: {:?}", summary);
+    Ok(summary)
+}
+
+/// 缩小并保存照片
+fn write_photo(
+    photo: &Photo,
+    index: usize,
+    folder: &Path,
+    options: &ShareBundleOptions,
+) -> Result<BundlePhoto> {
+    let max_size = options
+        .max_size
+        .unwrap_or(SHARE_BUNDLE_DEFAULT_MAX_SIZE)
+        .max(1);
+    let mut image = ImageOperate::open_oriented(&photo.full_path())?;
+    if image.width().max(image.height()) > max_size {
+        image = image.resize(max_size, max_size, FilterType::Lanczos3);
+    }
+    let stem = Path::new(&photo.img_name)
+        .file_stem()
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_default();
+    // 序号保证排序和文件名唯一
+    let file = format!("{}/{:04}_{}.jpg", PHOTOS_FOLDER, index + 1, stem);
+    let quality = options
+        .quality
+        .unwrap_or(EXPORT_DEFAULT_JPEG_QUALITY)
+        .clamp(1, 100);
+    let mut writer = BufWriter::new(fs::File::create(folder.join(&file))?);
+    DynamicImage::ImageRgb8(image.to_rgb8())
+        .write_with_encoder(JpegEncoder::new_with_quality(&mut writer, quality))?;
+    writer.flush()?;
+
+    let (width, height) = image.dimensions();
+    Ok(BundlePhoto {
+        file,
+        width,
+        height,
+        caption: photo.caption.clone().filter(|x| !x.trim().is_empty()),
+    })
+}
+
+/// 生成相册网页
+fn render_index(items: &[BundlePhoto], options: &ShareBundleOptions) -> String {
+    let title = options
+        .title
+        .clone()
+        .filter(|x| !x.trim().is_empty())
+        .unwrap_or_else(|| i18n::t(msg::SHARE_BUNDLE_TITLE, &[]));
+    let count = i18n::t(
+        msg::SHARE_BUNDLE_COUNT,
+        &[("count", items.len().to_string())],
+    );
+    let include_captions = options.include_captions.unwrap_or(true);
+
+    let mut figures = String::new();
+    for item in items {
+        let caption = item.caption.as_deref().filter(|_| include_captions);
+        figures.push_str(&format!(
+            "<figure><a href=\"{0}\"><img src=\"{0}\" width=\"{1}\" height=\"{2}\" loading=\"lazy\" alt=\"{3}\"></a>{4}</figure>\n",
+            url_util::encode_path(&item.file),
+            item.width,
+            item.height,
+            escape(caption.unwrap_or_default()),
+            caption
+                .map(|x| format!("<figcaption>{}</figcaption>", escape(x)))
+                .unwrap_or_default(),
+        ));
+    }
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<style>
+body {{ margin: 0; padding: 24px; font-family: sans-serif; background: #111; color: #eee; }}
+h1 {{ margin: 0 0 4px; font-weight: normal; }}
+p {{ margin: 0 0 24px; color: #999; }}
+main {{ display: grid; grid-template-columns: repeat(auto-fill, minmax(240px, 1fr)); gap: 12px; }}
+figure {{ margin: 0; }}
+img {{ display: block; width: 100%; height: 240px; object-fit: cover; border-radius: 4px; }}
+figcaption {{ padding: 6px 2px; font-size: 14px; color: #bbb; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<p>{count}</p>
+<main>
+{figures}</main>
+</body>
+</html>
+"#,
+        title = escape(&title),
+        count = escape(&count),
+        figures = figures,
+    )
+}
+
+/// 文件夹中所有文件的大小之和
+fn dir_size(dir: &Path) -> u64 {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|x| x.is_file())
+        .map(|x| x.len())
+        .sum()
+}
@@ -0,0 +1,203 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::slideshow::{SlideshowOptions, SlideshowProgressMsg, SlideshowStatus};
+use crate::utils::{ffmpeg_util, file_util, uuid_util};
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 正在运行的幻灯片任务【任务 ID -> 取消标记】
+static SLIDESHOW_JOBS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 开始生成幻灯片视频【后台执行，返回任务 ID】
+///
+/// 照片按 `photo_ids` 的顺序播放，原图不存在的照片会被跳过
+/// - photo_ids 照片 ID
+/// - options 视频选项
+/// - on_progress 进度回调【结束时一定会以完成、失败或取消状态调用一次】
+pub fn create_slideshow<F>(
+    photo_ids: &[i32],
+    options: SlideshowOptions,
+    on_progress: F,
+) -> Result<String>
+where
+    F: Fn(SlideshowProgressMsg) + Send + 'static,
+{
+    if options.output_path.trim().is_empty() {
+        return Err(anyhow!(i18n::text(msg::SLIDESHOW_OUTPUT_REQUIRED)));
+    }
+    if let Some(music) = options.music_path.as_deref().filter(|x| !x.is_empty()) {
+        if !file_util::file_exists(music) {
+            return Err(i18n::error(
+                msg::SLIDESHOW_MUSIC_NOT_FOUND,
+                &[("path", music.to_string())],
+            ));
+        }
+    }
+    let images = photo_files(photo_ids)?;
+    if images.is_empty() {
+        return Err(anyhow!(i18n::text(msg::SLIDESHOW_NO_PHOTOS)));
+    }
+
+    let job_id = uuid_util::uuid_v4();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    SLIDESHOW_JOBS
+        .lock()
+        .unwrap()
+        .insert(job_id.clone(), cancelled.clone());
+
+    let id = job_id.clone();
+    let spawned = std::thread::Builder::new()
+        .name("argus-slideshow".to_string())
+        .spawn(move || {
+            let progress = |status, percent, error| SlideshowProgressMsg {
+                job_id: id.clone(),
+                status,
+                percent,
+                output_path: options.output_path.clone(),
+                error,
+            };
+            let result = render(&images, &options, &cancelled, |percent| {
+                on_progress(progress(SlideshowStatus::Running, percent, None))
+            });
+            SLIDESHOW_JOBS.lock().unwrap().remove(&id);
+            let msg = match result {
+                Ok(()) if cancelled.load(Ordering::SeqCst) => {
+                    // 不保留不完整的视频
+                    let _ = std::fs::remove_file(&options.output_path);
+                    log::info!("幻灯片视频已取消: {}", id);
+                    progress(SlideshowStatus::Cancelled, 0.0, None)
+                }
+                Ok(()) => {
+                    log::info!("幻灯片视频生成完成: {}", options.output_path);
+                    progress(SlideshowStatus::Completed, 100.0, None)
+                }
+                Err(e) => {
+                    log::error!("幻灯片视频生成失败: {}", e);
+                    progress(SlideshowStatus::Failed, 0.0, Some(e.to_string()))
+                }
+            };
+            on_progress(msg);
+        });
+    if let Err(e) = spawned {
+        SLIDESHOW_JOBS.lock().unwrap().remove(&job_id);
+        return Err(i18n::error(
+            msg::SLIDESHOW_START_FAILED,
+            &[("error", e.to_string())],
+        ));
+    }
+    Ok(job_id)
+}
+
+/// 取消幻灯片视频任务
+pub fn cancel_slideshow(job_id: &str) -> Result<()> {
+    let jobs = SLIDESHOW_JOBS.lock().unwrap();
+    let cancelled = jobs.get(job_id).ok_or_else(|| {
+        i18n::error(
+            msg::SLIDESHOW_JOB_NOT_RUNNING,
+            &[("id", job_id.to_string())],
+        )
+    })?;
+    cancelled.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// 按顺序查询照片原图路径
+fn photo_files(photo_ids: &[i32]) -> Result<Vec<PathBuf>> {
+    let mut conn = establish_connection();
+    let photos: HashMap<i32, PathBuf> =
+        storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?
+            .iter()
+            .map(|x| (x.id, x.full_path()))
+            .collect();
+    Ok(photo_ids
+        .iter()
+        .filter_map(|id| photos.get(id))
+        .filter(|path| {
+            let exists = file_util::file_exists(path);
+            if !exists {
+                log::warn!("幻灯片跳过不存在的照片: {}", path.display());
+            }
+            exists
+        })
+        .cloned()
+        .collect())
+}
+
+/// 调用 ffmpeg 生成视频【照片列表写入临时文件，取消时结束进程】
+fn render<F: Fn(f32)>(
+    images: &[PathBuf],
+    options: &SlideshowOptions,
+    cancelled: &AtomicBool,
+    on_percent: F,
+) -> Result<()> {
+    if let Some(dir) = Path::new(&options.output_path).parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut list = tempfile::Builder::new()
+        .prefix("argus-slideshow-")
+        .suffix(".ffconcat")
+        .tempfile()?;
+    list.write_all(ffmpeg_util::concat_list(images, options).as_bytes())?;
+    list.flush()?;
+    let args = ffmpeg_util::slideshow_args(list.path(), images.len(), options);
+    let ffmpeg = ffmpeg_util::ffmpeg_path();
+    let mut child = Command::new(&ffmpeg)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            i18n::error(
+                msg::FFMPEG_START_FAILED,
+                &[
+                    ("path", ffmpeg.display().to_string()),
+                    ("error", e.to_string()),
+                ],
+            )
+        })?;
+
+    let total_micros = (ffmpeg_util::total_seconds(images.len(), options) * 1_000_000.0) as u64;
+    // 同时读取错误输出，避免错误信息较多时填满管道导致 ffmpeg 阻塞
+    let mut stderr = child.stderr.take().expect("ffmpeg 错误输出获取失败");
+    let stderr = std::thread::spawn(move || {
+        let mut text = String::new();
+        let _ = stderr.read_to_string(&mut text);
+        text
+    });
+    let stdout = child.stdout.take().expect("ffmpeg 标准输出获取失败");
+    for line in BufReader::new(stdout).lines() {
+        if cancelled.load(Ordering::SeqCst) {
+            let _ = child.kill();
+            let _ = child.wait();
+            let _ = stderr.join();
+            return Ok(());
+        }
+        if let Some(micros) = line.ok().and_then(|x| ffmpeg_util::parse_progress_time(&x)) {
+            on_percent((micros as f32 / total_micros.max(1) as f32 * 100.0).min(99.9));
+        }
+    }
+
+    let status = child.wait()?;
+    let stderr = stderr.join().unwrap_or_default();
+    if cancelled.load(Ordering::SeqCst) || status.success() {
+        Ok(())
+    } else {
+        Err(i18n::error(
+            msg::FFMPEG_FAILED,
+            &[
+                ("status", status.to_string()),
+                ("error", stderr.trim().to_string()),
+            ],
+        ))
+    }
+}
@@ -0,0 +1,554 @@
+use crate::constant::{
+    IMAGE_COMPRESSION_RATIO, SPACE_SAVER_MIN_SAVING_PERCENT, SPACE_SAVER_PENDING_NAME,
+};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::services::{folder_album_service, thumbnail_cache_service};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::library::current_library;
+use crate::structs::space_saver::{
+    SpaceSaverCommitSummary, SpaceSaverItem, SpaceSaverMethod, SpaceSaverPendingFile,
+    SpaceSaverPlan,
+};
+use crate::structs::thumbnail_encoding;
+use crate::utils::exif_utils::jpeg_segment;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::img_util::ImageOperate;
+use crate::utils::json_util::JsonUtil;
+use crate::utils::png_chunk_util;
+use crate::utils::time_util::TimeUtils;
+use crate::utils::uuid_util;
+use crate::utils::webp_container_util::{self, WebpMetadata};
+use crate::utils::{file_util, graphic_detect_util};
+use anyhow::{anyhow, Result};
+use diesel::Connection;
+use image::codecs::png::PngDecoder;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
+use once_cell::sync::Lazy;
+use std::fs;
+use std::io::{Cursor, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+/// 等待确认的计划【只保留最近一次，新计划生成时放弃旧计划；写入的临时文件同时记录在照片库中】
+static PENDING_PLAN: Lazy<Mutex<Option<SpaceSaverPlan>>> = Lazy::new(|| Mutex::new(None));
+
+const MIME_JPEG: &str = "image/jpeg";
+const MIME_PNG: &str = "image/png";
+
+/// 生成节省空间计划
+///
+/// 每个文件先写入原文件同目录的临时文件，重新解码与原图逐像素对比、检查元数据完整后才加入计划；
+/// 节省的空间不足 `SPACE_SAVER_MIN_SAVING_PERCENT` 的文件跳过。原文件在确认前不会修改
+/// - photo_ids 照片 ID【为空时检查所有 JPEG 和截图类 PNG】
+pub fn prepare(photo_ids: Option<Vec<i32>>) -> Result<SpaceSaverPlan> {
+    discard_pending();
+    let mut conn = establish_connection();
+    let selected = photo_ids.is_some();
+    let photos: Vec<Photo> = match photo_ids {
+        Some(ids) => storage::photo_table::search_photos_by_ids(&mut conn, &ids)?,
+        None => storage::photo_table::search_all_photos(&mut conn)?
+            .into_iter()
+            .filter(|x| x.is_available && matches!(x.format.as_str(), MIME_JPEG | MIME_PNG))
+            .collect(),
+    };
+
+    let mut plan = SpaceSaverPlan {
+        id: uuid_util::uuid_v4(),
+        create_time: TimeUtils::current_timestamp(),
+        ..Default::default()
+    };
+    for photo in photos {
+        let path = photo.full_path();
+        let method = match photo.format.as_str() {
+            MIME_JPEG => SpaceSaverMethod::JpegOptimize,
+            MIME_PNG => SpaceSaverMethod::PngToWebp,
+            _ => {
+                plan.skipped
+                    .push(format!("{}: 不支持的格式", path.display()));
+                continue;
+            }
+        };
+        plan.checked += 1;
+        // 自动检查时只转换截图、界面等图形类 PNG，照片类 PNG 保持原样
+        match prepare_file(&photo, method, !selected) {
+            Ok(Some(item)) => {
+                plan.old_bytes += item.old_size;
+                plan.new_bytes += item.new_size;
+                plan.items.push(item);
+            }
+            Ok(None) => {}
+            Err(e) => plan.skipped.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    log::info!(
+        "节省空间计划 {}: 检查 {} 个文件，{} 个可节省 {} 字节",
+        plan.id,
+        plan.checked,
+        plan.items.len(),
+        plan.saved_bytes()
+    );
+    // 记录失败时无法保证异常退出后清理，不保留计划
+    if let Err(e) = save_pending_files(&plan.items) {
+        remove_temp_files(&plan.items);
+        return Err(i18n::error(
+            msg::SPACE_SAVER_TEMP_LIST_SAVE_FAILED,
+            &[("error", e.to_string())],
+        ));
+    }
+    *PENDING_PLAN.lock().unwrap_or_else(|e| e.into_inner()) = Some(plan.clone());
+    Ok(plan)
+}
+
+/// 获取等待确认的计划
+pub fn pending_plan() -> Option<SpaceSaverPlan> {
+    PENDING_PLAN
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// 放弃计划，删除已写入的新文件
+/// - plan_id 计划 ID
+pub fn discard(plan_id: &str) -> Result<()> {
+    let plan = take_plan(plan_id)?;
+    remove_temp_files(&plan.items);
+    clear_pending_files();
+    Ok(())
+}
+
+/// 确认计划，用新文件替换原文件
+///
+/// 原文件在生成计划后被修改过的跳过；替换后更新数据库中的 Hash、大小、路径和格式，并重新生成缩略图。
+/// 任一步骤失败时还原原文件
+/// - plan_id 计划 ID
+pub async fn commit(plan_id: &str) -> Result<SpaceSaverCommitSummary> {
+    let plan = take_plan(plan_id)?;
+    let mut summary = SpaceSaverCommitSummary::default();
+    for item in &plan.items {
+        let replaced = {
+            let item = item.clone();
+            tokio::task::spawn_blocking(move || replace_file(&item)).await?
+        };
+        let new_path = match replaced {
+            Ok(x) => x,
+            Err(e) => {
+                let _ = fs::remove_file(file_util::long_path(&item.temp_path));
+                log::warn!("节省空间替换失败 {}: {}", item.path, e);
+                summary.failed.push(format!("{}: {}", item.path, e));
+                continue;
+            }
+        };
+        summary.replaced += 1;
+        summary.saved_bytes += item.old_size.saturating_sub(item.new_size);
+        if let Err(e) = ImageOperate::multi_level_image_compression(
+            &new_path,
+            thumbnail_encoding::storage_format(),
+            IMAGE_COMPRESSION_RATIO.to_vec(),
+        )
+        .await
+        {
+            log::warn!("缩略图重新生成失败 {}: {}", new_path.display(), e);
+        }
+    }
+    if plan
+        .items
+        .iter()
+        .any(|x| x.method == SpaceSaverMethod::PngToWebp)
+    {
+        folder_album_service::request_sync();
+    }
+    clear_pending_files();
+    log::info!(
+        "节省空间计划 {} 已确认: 替换 {} 个文件，节省 {} 字节，失败 {}",
+        plan.id,
+        summary.replaced,
+        summary.saved_bytes,
+        summary.failed.len()
+    );
+    Ok(summary)
+}
+
+/// 写入并校验新文件【节省的空间太少时删除新文件，返回 None】
+/// - graphic_only PNG 只转换图形类图片
+fn prepare_file(
+    photo: &Photo,
+    method: SpaceSaverMethod,
+    graphic_only: bool,
+) -> Result<Option<SpaceSaverItem>> {
+    let path = photo.full_path();
+    let old_size = fs::metadata(file_util::long_path(&path))?.len();
+    let (temp_path, new_path) = match method {
+        SpaceSaverMethod::JpegOptimize => (optimize_jpeg(&path)?, path.clone()),
+        SpaceSaverMethod::PngToWebp => match png_to_webp(&path, graphic_only)? {
+            Some(temp) => (temp, path.with_extension("webp")),
+            None => return Ok(None),
+        },
+    };
+    let new_size = fs::metadata(file_util::long_path(&temp_path))?.len();
+    if new_size * 100 > old_size * (100 - SPACE_SAVER_MIN_SAVING_PERCENT) {
+        let _ = fs::remove_file(file_util::long_path(&temp_path));
+        return Ok(None);
+    }
+    Ok(Some(SpaceSaverItem {
+        photo_id: photo.id,
+        path: file_util::display_path(&path),
+        new_path: file_util::display_path(&new_path),
+        method,
+        old_size,
+        new_size,
+        temp_path,
+        old_hash: photo.hash.clone(),
+    }))
+}
+
+/// 使用 jpegtran 无损优化 JPEG，返回校验通过的临时文件
+///
+/// 保留所有元数据段，优化霍夫曼表并生成渐进式扫描（与 mozjpeg 的 jpegtran 默认行为一致）
+fn optimize_jpeg(path: &Path) -> Result<PathBuf> {
+    let temp = file_util::temp_path(path, None);
+    let output = Command::new(file_util::bundled_tool_path("jpegtran"))
+        .args(["-copy", "all", "-optimize", "-progressive", "-outfile"])
+        .arg(file_util::long_path(&temp))
+        .arg(file_util::long_path(path))
+        .output()
+        .map_err(|e| {
+            i18n::error(
+                msg::JPEGTRAN_START_FAILED,
+                &[("error", e.to_string())],
+            )
+        })?;
+    if !output.status.success() {
+        let _ = fs::remove_file(file_util::long_path(&temp));
+        return Err(i18n::error(
+            msg::JPEGTRAN_FAILED,
+            &[(
+                "error",
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            )],
+        ));
+    }
+    let verified = (|| {
+        let original = fs::read(file_util::long_path(path))?;
+        let optimized = fs::read(file_util::long_path(&temp))?;
+        if jpeg_segment::metadata_segments(&original)?
+            != jpeg_segment::metadata_segments(&optimized)?
+        {
+            return Err(anyhow!(i18n::text(msg::SPACE_SAVER_METADATA_MISMATCH)));
+        }
+        let expected = decode(&original, ImageFormat::Jpeg)?;
+        verify_pixels(&expected, &optimized, ImageFormat::Jpeg)
+    })();
+    if let Err(e) = verified {
+        let _ = fs::remove_file(file_util::long_path(&temp));
+        return Err(e);
+    }
+    Ok(temp)
+}
+
+/// PNG 转换为无损 WebP，返回校验通过的临时文件【不是图形类图片且只转换图形时返回 None】
+///
+/// EXIF、XMP、ICC 配置文件写入 WebP；包含其他文本数据块、16 位色深或动图时不转换
+fn png_to_webp(path: &Path, graphic_only: bool) -> Result<Option<PathBuf>> {
+    let data = fs::read(file_util::long_path(path))?;
+    let png = png_chunk_util::read_png_metadata(&data)?;
+    if !png.unsupported.is_empty() {
+        return Err(i18n::error(
+            msg::SPACE_SAVER_METADATA_UNSUPPORTED,
+            &[("chunks", png.unsupported.join(", "))],
+        ));
+    }
+    if png.bit_depth > 8 {
+        return Err(i18n::error(
+            msg::SPACE_SAVER_BIT_DEPTH_UNSUPPORTED,
+            &[("depth", png.bit_depth.to_string())],
+        ));
+    }
+    if png.animated {
+        return Err(anyhow!(i18n::text(msg::SPACE_SAVER_ANIMATED_SKIPPED)));
+    }
+    let mut decoder = PngDecoder::new(Cursor::new(&data))?;
+    let icc = decoder.icc_profile()?;
+    let image = DynamicImage::from_decoder(decoder)?;
+    if graphic_only && !graphic_detect_util::is_graphic(&image) {
+        return Ok(None);
+    }
+
+    let has_alpha = image.color().has_alpha();
+    let image = if has_alpha {
+        DynamicImage::ImageRgba8(image.to_rgba8())
+    } else {
+        DynamicImage::ImageRgb8(image.to_rgb8())
+    };
+    let encoder = webp::Encoder::from_image(&image).map_err(|e| {
+        i18n::error(
+            msg::SPACE_SAVER_WEBP_ENCODE_FAILED,
+            &[("error", e.to_string())],
+        )
+    })?;
+    let mut config = webp::WebPConfig::new()
+        .map_err(|_| anyhow!(i18n::text(msg::SPACE_SAVER_WEBP_CONFIG_FAILED)))?;
+    config.lossless = 1;
+    // 完全透明的像素也保留原来的颜色，才能逐像素对比
+    config.exact = 1;
+    config.quality = 100.0;
+    config.method = 6;
+    let encoded = encoder.encode_advanced(&config).map_err(|e| {
+        i18n::error(
+            msg::SPACE_SAVER_WEBP_ENCODE_FAILED,
+            &[("error", format!("{:?}", e))],
+        )
+    })?;
+    let metadata = WebpMetadata {
+        icc,
+        exif: png.exif,
+        xmp: png.xmp,
+    };
+    let webp = webp_container_util::embed_metadata(
+        &encoded,
+        image.width(),
+        image.height(),
+        has_alpha,
+        &metadata,
+    )?;
+
+    let temp = file_util::temp_path(path, Some("webp"));
+    fs::write(file_util::long_path(&temp), &webp)?;
+    let verified = (|| {
+        let written = fs::read(file_util::long_path(&temp))?;
+        if webp_container_util::read_metadata(&written)? != metadata {
+            return Err(anyhow!(i18n::text(msg::SPACE_SAVER_METADATA_MISMATCH)));
+        }
+        verify_pixels(&image, &written, ImageFormat::WebP)
+    })();
+    if let Err(e) = verified {
+        let _ = fs::remove_file(file_util::long_path(&temp));
+        return Err(e);
+    }
+    Ok(Some(temp))
+}
+
+/// 解码图片【不应用方向标记】
+fn decode(data: &[u8], format: ImageFormat) -> Result<DynamicImage> {
+    let mut reader = ImageReader::new(Cursor::new(data));
+    reader.set_format(format);
+    Ok(reader.decode()?)
+}
+
+/// 重新解码新文件，与原图逐像素对比
+fn verify_pixels(expected: &DynamicImage, data: &[u8], format: ImageFormat) -> Result<()> {
+    let actual = decode(data, format)?;
+    if actual.width() != expected.width() || actual.height() != expected.height() {
+        return Err(anyhow!(i18n::text(msg::SPACE_SAVER_SIZE_MISMATCH)));
+    }
+    if actual.to_rgba8().as_raw() != expected.to_rgba8().as_raw() {
+        return Err(anyhow!(i18n::text(msg::SPACE_SAVER_PIXEL_MISMATCH)));
+    }
+    Ok(())
+}
+
+/// 用新文件替换原文件并更新数据库，返回替换后的路径
+///
+/// 原文件先改名为备份，新文件就位且数据库更新成功后才删除备份
+fn replace_file(item: &SpaceSaverItem) -> Result<PathBuf> {
+    let path = PathBuf::from(&item.path);
+    let current = FileHashUtils::sha256(file_util::long_path(&path))?;
+    if current != item.old_hash {
+        return Err(anyhow!(i18n::text(msg::SPACE_SAVER_SOURCE_MODIFIED)));
+    }
+    let new_path = match item.method {
+        SpaceSaverMethod::JpegOptimize => path.clone(),
+        SpaceSaverMethod::PngToWebp => file_util::unique_path(Path::new(&item.new_path)),
+    };
+    let backup = backup_path(&path);
+    fs::rename(file_util::long_path(&path), file_util::long_path(&backup))?;
+    let restore = |error: anyhow::Error| {
+        let _ = fs::rename(file_util::long_path(&backup), file_util::long_path(&path));
+        error
+    };
+    if let Err(e) = fs::rename(
+        file_util::long_path(&item.temp_path),
+        file_util::long_path(&new_path),
+    ) {
+        return Err(restore(e.into()));
+    }
+
+    let updated = (|| {
+        let hash = FileHashUtils::sha256(file_util::long_path(&new_path))?;
+        let (width, height) = ImageReader::open(file_util::long_path(&new_path))?
+            .with_guessed_format()?
+            .into_dimensions()?;
+        let mut conn = establish_connection();
+        conn.transaction(|conn| {
+            storage::photo_table::update_photo_file_info(
+                conn,
+                item.photo_id,
+                &hash,
+                width as i32,
+                height as i32,
+                item.new_size as i64,
+            )?;
+            if item.method == SpaceSaverMethod::PngToWebp {
+                storage::photo_table::update_photo_location(conn, item.photo_id, &new_path)?;
+                storage::photo_table::update_photo_format(
+                    conn,
+                    item.photo_id,
+                    ImageFormat::WebP.to_mime_type(),
+                )?;
+            }
+            storage::photo_purge::delete_unused_hash_data(conn, &[item.old_hash.clone()])?;
+            anyhow::Ok(())
+        })?;
+        thumbnail_cache_service::remove_unused_thumbnails(&mut conn, &item.old_hash);
+        anyhow::Ok(())
+    })();
+    if let Err(e) = updated {
+        // 新文件放回临时位置，原文件还原
+        let _ = fs::rename(
+            file_util::long_path(&new_path),
+            file_util::long_path(&item.temp_path),
+        );
+        return Err(restore(e));
+    }
+    if let Err(e) = fs::remove_file(file_util::long_path(&backup)) {
+        log::warn!("备份文件删除失败 {}: {}", backup.display(), e);
+    }
+    Ok(new_path)
+}
+
+/// 取出等待确认的计划【ID 不一致时返回错误】
+fn take_plan(plan_id: &str) -> Result<SpaceSaverPlan> {
+    let mut pending = PENDING_PLAN.lock().unwrap_or_else(|e| e.into_inner());
+    match pending.as_ref() {
+        Some(plan) if plan.id == plan_id => Ok(pending.take().unwrap_or_default()),
+        _ => Err(i18n::error(
+            msg::SPACE_SAVER_PLAN_NOT_FOUND,
+            &[("id", plan_id.to_string())],
+        )),
+    }
+}
+
+/// 放弃等待确认的计划
+fn discard_pending() {
+    let plan = PENDING_PLAN
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take();
+    if let Some(plan) = plan {
+        remove_temp_files(&plan.items);
+        clear_pending_files();
+    }
+}
+
+fn remove_temp_files(items: &[SpaceSaverItem]) {
+    for item in items {
+        let _ = fs::remove_file(file_util::long_path(&item.temp_path));
+    }
+}
+
+/// 清理上次异常退出时等待确认的计划留下的临时文件【启动时执行】
+///
+/// 替换过程中退出时，数据库中仍是原文件 Hash 的，把备份的原文件移回原位置；已完成替换的删除备份
+pub fn clean_pending_files() {
+    let file = pending_file();
+    let files: Vec<SpaceSaverPendingFile> = match fs::read_to_string(&file) {
+        Ok(data) => match JsonUtil::from_json(&data) {
+            Ok(files) => files,
+            Err(e) => {
+                log::warn!("节省空间临时文件清单格式错误 {}: {}", file.display(), e);
+                Vec::new()
+            }
+        },
+        Err(_) => return,
+    };
+    let mut conn = establish_connection();
+    for item in files {
+        let path = PathBuf::from(&item.path);
+        let _ = fs::remove_file(file_util::long_path(&item.temp_path));
+        let backup = backup_path(&path);
+        if !file_util::long_path(&backup).is_file() {
+            continue;
+        }
+        let replaced = storage::photo_table::search_photos_by_ids(&mut conn, &[item.photo_id])
+            .ok()
+            .and_then(|x| x.into_iter().next())
+            .is_some_and(|x| x.hash != item.old_hash);
+        let result = if replaced {
+            fs::remove_file(file_util::long_path(&backup))
+        } else {
+            fs::rename(file_util::long_path(&backup), file_util::long_path(&path))
+        };
+        match result {
+            Ok(()) => log::info!("节省空间未完成的替换已处理: {}", path.display()),
+            Err(e) => log::warn!("节省空间备份文件处理失败 {}: {}", backup.display(), e),
+        }
+    }
+    clear_pending_files();
+}
+
+/// 记录等待确认的计划写入的临时文件
+fn save_pending_files(items: &[SpaceSaverItem]) -> Result<()> {
+    if items.is_empty() {
+        clear_pending_files();
+        return Ok(());
+    }
+    let files: Vec<SpaceSaverPendingFile> = items
+        .iter()
+        .map(|x| SpaceSaverPendingFile {
+            photo_id: x.photo_id,
+            path: x.path.clone(),
+            temp_path: x.temp_path.clone(),
+            old_hash: x.old_hash.clone(),
+        })
+        .collect();
+    fs::write(pending_file(), JsonUtil::stringify(&files)?)?;
+    Ok(())
+}
+
+/// 删除临时文件记录
+fn clear_pending_files() {
+    let file = pending_file();
+    match fs::remove_file(&file) {
+        Err(e) if e.kind() != ErrorKind::NotFound => {
+            log::warn!("节省空间临时文件清单删除失败 {}: {}", file.display(), e)
+        }
+        _ => {}
+    }
+}
+
+fn pending_file() -> PathBuf {
+    current_library().root.join(SPACE_SAVER_PENDING_NAME)
+}
+
+/// 替换时原文件的备份位置
+fn backup_path(path: &Path) -> PathBuf {
+    file_util::temp_path(path, Some("argus-bak"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn test_png_to_webp() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("screenshot.png");
+        // 大面积纯色的截图，包含完全透明的像素
+        let mut image = RgbaImage::from_pixel(64, 64, Rgba([240, 240, 240, 255]));
+        for x in 0..64 {
+            image.put_pixel(x, 10, Rgba([30, 30, 30, 255]));
+            image.put_pixel(x, 20, Rgba([200, 10, 10, 0]));
+        }
+        image.save(&path).unwrap();
+
+        let temp = png_to_webp(&path, false).unwrap().unwrap();
+        assert_eq!(temp, dir.path().join(".screenshot.png.webp.argus-tmp"));
+        let data = fs::read(&temp).unwrap();
+        let decoded = decode(&data, ImageFormat::WebP).unwrap();
+        assert_eq!(decoded.to_rgba8(), image);
+    }
+}
@@ -0,0 +1,425 @@
+use crate::constant::{
+    IMAGE_COMPRESSION_RATIO, THUMBNAIL_TEMP_FILE_MAX_AGE_SECS, THUMBNAIL_TEMP_FILE_PREFIX,
+    THUMBNAIL_VISIBLE_FOLDER_CACHE_SECS, THUMBNAIL_VISIBLE_RANGE_MAX,
+};
+use crate::errors::AError;
+use crate::i18n;
+use crate::i18n::msg;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::storage::photo_table;
+use crate::structs::config::sys_config;
+use crate::structs::dominant_color::DominantColor;
+use crate::structs::histogram::HistogramSummary;
+use crate::structs::photo_list::{PhotoCursor, PhotoListSort};
+use crate::structs::photo_search::PhotoSearchParams;
+use crate::structs::thumbnail_encoding;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::{file_util, image_format_util};
+use crate::utils::img_util::ImageOperate;
+use crate::utils::thumbnail_queue_util::THUMBNAIL_QUEUE;
+use anyhow::{anyhow, Result};
+use diesel::SqliteConnection;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use walkdir::WalkDir;
+
+/// 缩略图缓存校验结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailVerifyReport {
+    /// 检查的缩略图数量
+    pub checked: u32,
+    /// 损坏的缩略图数量
+    pub corrupt: u32,
+    /// 清理的残留临时文件数量
+    pub removed_temp: u32,
+    /// 重新生成成功的原图数量
+    pub regenerated: u32,
+    /// 无法重新生成的文件 Hash（原图丢失或未入库）
+    pub unrecoverable: Vec<String>,
+}
+
+/// 缩略图缓存清理结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailGcReport {
+    /// 删除的缩略图数量
+    pub removed: u32,
+    /// 释放的空间（字节）
+    pub freed_bytes: u64,
+    /// 其中旧版本编码参数生成的缩略图数量
+    pub stale: u32,
+    /// 删除的没有对应照片的主色、直方图、BlurHash 记录数量
+    pub orphan_rows: usize,
+}
+
+/// 前端正在显示的照片列表
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum VisibleSource {
+    /// 文件夹中的图片【包含子文件夹，顺序与 `get_all_imgs` 一致】
+    Folder { path: String },
+    /// 照片列表【顺序与 `list_photos` 一致，忽略筛选条件中的 limit、offset】
+    Query {
+        params: PhotoSearchParams,
+        /// 排序方式【默认按拍摄时间从新到旧】
+        sort: Option<PhotoListSort>,
+        /// 可见范围前一张照片的游标【为空时从第一张开始】
+        cursor: Option<String>,
+    },
+}
+
+/// 最近一次可见范围使用的文件夹图片列表（文件夹、读取时间、图片）
+static VISIBLE_FOLDER: Lazy<Mutex<Option<(String, Instant, Arc<Vec<PathBuf>>)>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// 校验缩略图缓存
+///
+/// 删除写入中断留下的临时文件和无法解码的缩略图，并根据数据库中的原图路径重新生成
+/// 【最近修改过的临时文件可能正在写入，不删除】
+pub async fn verify_thumbnail_cache() -> Result<ThumbnailVerifyReport> {
+    let root_dir = sys_config()
+        .thumbnail_storage_path
+        .clone()
+        .ok_or_else(|| anyhow!(AError::ThumbnailCacheConfigurationReadFailed.message()))?;
+
+    let (mut report, corrupt_hashes) =
+        tokio::task::spawn_blocking(move || scan_thumbnail_dir(Path::new(&root_dir))).await??;
+
+    for hash in corrupt_hashes {
+        match find_original_path(&hash) {
+            Some(original) => {
+                // 只重新生成缩略图，照片已有记录，不需要再读取 EXIF
+                let result = ImageOperate::multi_level_image_compression_with_exif(
+                    &original,
+                    thumbnail_encoding::storage_format(),
+                    IMAGE_COMPRESSION_RATIO.to_vec(),
+                    None,
+                )
+                .await;
+                match result {
+                    Ok(_) => report.regenerated += 1,
+                    Err(e) => {
+                        log::error!("缩略图重新生成失败 {}: {}", original.display(), e);
+                        report.unrecoverable.push(hash);
+                    }
+                }
+            }
+            None => report.unrecoverable.push(hash),
+        }
+    }
+    log::info!("缩略图缓存校验完成: {:?}", report);
+    Ok(report)
+}
+
+/// 扫描缩略图目录，返回统计信息以及损坏缩略图对应的文件 Hash
+fn scan_thumbnail_dir(root: &Path) -> Result<(ThumbnailVerifyReport, HashSet<String>)> {
+    let mut report = ThumbnailVerifyReport::default();
+    let mut corrupt_hashes = HashSet::new();
+    if !root.exists() {
+        return Ok((report, corrupt_hashes));
+    }
+
+    for entry in WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy();
+        // 写入中断留下的临时文件
+        if file_name.starts_with(THUMBNAIL_TEMP_FILE_PREFIX) {
+            if is_abandoned_temp_file(path) && fs::remove_file(path).is_ok() {
+                report.removed_temp += 1;
+            }
+            continue;
+        }
+
+        report.checked += 1;
+        if ImageOperate::verify_image_file(path) {
+            continue;
+        }
+        report.corrupt += 1;
+        log::warn!("发现损坏的缩略图: {}", path.display());
+        if let Err(e) = fs::remove_file(path) {
+            log::error!("损坏的缩略图删除失败 {}: {}", path.display(), e);
+            continue;
+        }
+        if let Some((hash, _)) = thumbnail_owner(path) {
+            corrupt_hashes.insert(hash);
+        }
+    }
+    Ok((report, corrupt_hashes))
+}
+
+/// 临时文件是否已超过保留时间未修改【读取不到修改时间时按正在写入处理】
+fn is_abandoned_temp_file(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|x| x.elapsed().ok())
+        .is_some_and(|x| x >= Duration::from_secs(THUMBNAIL_TEMP_FILE_MAX_AGE_SECS))
+}
+
+/// 解析缩略图所属的文件 Hash 和版本目录
+///
+/// 目录结构为 `.../{hash}/{版本}/{size}.{ext}`，
+/// 加入版本目录之前生成的缩略图为 `.../{hash}/{size}.{ext}`，版本为 None
+fn thumbnail_owner(path: &Path) -> Option<(String, Option<String>)> {
+    let dir = path.parent()?;
+    let name = dir.file_name()?.to_str()?;
+    if thumbnail_encoding::is_generation(name) {
+        let hash = dir.parent()?.file_name()?.to_str()?;
+        Some((hash.to_string(), Some(name.to_string())))
+    } else {
+        Some((name.to_string(), None))
+    }
+}
+
+/// 通过 Hash 查找原图路径
+fn find_original_path(hash: &str) -> Option<PathBuf> {
+    let mut conn = establish_connection();
+    let photos = photo_table::search_photo_by_hash(&mut conn, hash.to_string()).ok()?;
+    photos
+        .into_iter()
+        .map(|p| p.full_path())
+        .find(|p| file_util::file_exists(p))
+}
+
+/// 保存生成缩略图时顺便计算的直方图、主色和 BlurHash【保存失败只记录日志，不影响缩略图】
+/// - colors、blurhash 本次没有生成对应级别的缩略图时为空
+pub fn save_thumbnail_analysis(
+    hash: &str,
+    histogram: &HistogramSummary,
+    colors: Option<&[DominantColor]>,
+    blurhash: Option<&str>,
+) {
+    let mut conn = establish_connection();
+    if let Err(e) = storage::photo_histogram::save_histogram(&mut conn, hash, histogram) {
+        log::warn!("直方图保存失败 {}: {}", hash, e);
+    }
+    if let Some(colors) = colors {
+        if let Err(e) = storage::photo_color::save_colors(&mut conn, hash, colors) {
+            log::warn!("主色保存失败 {}: {}", hash, e);
+        }
+    }
+    if let Some(blurhash) = blurhash {
+        if let Err(e) = storage::photo_blurhash::save_blurhash(&mut conn, hash, blurhash) {
+            log::warn!("BlurHash 保存失败 {}: {}", hash, e);
+        }
+    }
+}
+
+/// 删除 Hash 对应的缩略图【还有其他照片使用同一 Hash 时保留】
+pub fn remove_unused_thumbnails(conn: &mut SqliteConnection, hash: &str) {
+    let in_use = photo_table::search_photo_by_hash(conn, hash.to_string())
+        .map(|x| !x.is_empty())
+        .unwrap_or(true);
+    if in_use {
+        return;
+    }
+    let Some(root_dir) = sys_config().thumbnail_storage_path.clone() else {
+        return;
+    };
+    let thumbnail = FileHashUtils::hash_to_file_path(
+        hash,
+        &root_dir,
+        &image_format_util::get_suffix_name(thumbnail_encoding::storage_format()),
+        IMAGE_COMPRESSION_RATIO[0].size,
+    );
+    // 删除 Hash 目录，包括所有版本的缩略图
+    if let Some(dir) = thumbnail.parent().and_then(Path::parent) {
+        if let Err(e) = fs::remove_dir_all(dir) {
+            log::warn!("旧缩略图删除失败 {}: {}", dir.display(), e);
+        }
+    }
+}
+
+/// 清理缩略图缓存
+///
+/// 删除数据库中已没有对应照片的缩略图【如照片在照片库外被删除后重新扫描】，
+/// 以及编码参数修改前生成的旧版本缩略图，写入中的临时文件不处理
+pub fn collect_unused_thumbnails() -> Result<ThumbnailGcReport> {
+    let root_dir = sys_config()
+        .thumbnail_storage_path
+        .clone()
+        .ok_or_else(|| anyhow!(AError::ThumbnailCacheConfigurationReadFailed.message()))?;
+    let mut conn = establish_connection();
+    let hashes = photo_table::search_all_hashes(&mut conn)?;
+    let current = thumbnail_encoding::generation();
+
+    let mut report = ThumbnailGcReport::default();
+    let mut dirs = HashSet::new();
+    for entry in WalkDir::new(&root_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        if entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(THUMBNAIL_TEMP_FILE_PREFIX)
+        {
+            continue;
+        }
+        let Some((hash, generation)) = thumbnail_owner(path) else {
+            continue;
+        };
+        let stale = generation.as_deref() != Some(current.as_str());
+        if !stale && hashes.contains(&hash) {
+            continue;
+        }
+        let size = entry.metadata().map(|m| m.len()).unwrap_or_default();
+        match fs::remove_file(path) {
+            Ok(_) => {
+                report.removed += 1;
+                report.freed_bytes += size;
+                if stale {
+                    report.stale += 1;
+                }
+                if let Some(dir) = path.parent() {
+                    dirs.insert(dir.to_path_buf());
+                }
+            }
+            Err(e) => log::warn!("缩略图删除失败 {}: {}", path.display(), e),
+        }
+    }
+    // 只删除已经清空的文件夹【版本目录清空后再尝试删除 Hash 目录】
+    for dir in dirs {
+        let is_generation = dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(thumbnail_encoding::is_generation);
+        if fs::remove_dir(&dir).is_ok() && is_generation {
+            if let Some(parent) = dir.parent() {
+                let _ = fs::remove_dir(parent);
+            }
+        }
+    }
+    // 主色、直方图等按 Hash 保存的数据同样清理
+    report.orphan_rows = storage::photo_purge::delete_orphan_hash_data(&mut conn)?;
+    log::info!("缩略图缓存清理完成: {:?}", report);
+    Ok(report)
+}
+
+/// 前端可见范围变化时，把范围内还在等待的缩略图任务提前处理，返回提前的任务数量
+///
+/// 上一次提前但已不在范围内的任务恢复原来的顺序。文件夹的图片列表缓存一段时间，
+/// 滚动时不重复遍历；照片列表按游标查询，与滚动位置无关
+/// - source 前端正在显示的照片列表
+/// - from 可见范围起始位置（包含）【照片列表的起始位置由游标指定，只用于计算数量】
+/// - to 可见范围结束位置（不包含）【包含即将滚动到的位置】
+pub fn report_visible_range(source: VisibleSource, from: usize, to: usize) -> Result<usize> {
+    if to < from {
+        return Err(i18n::error(
+            msg::THUMBNAIL_VISIBLE_RANGE_INVALID,
+            &[("from", from.to_string()), ("to", to.to_string())],
+        ));
+    }
+    let count = (to - from).min(THUMBNAIL_VISIBLE_RANGE_MAX);
+    let paths: Vec<PathBuf> = match source {
+        VisibleSource::Folder { path } => folder_images(&path)
+            .iter()
+            .skip(from)
+            .take(count)
+            .cloned()
+            .collect(),
+        VisibleSource::Query {
+            params,
+            sort,
+            cursor,
+        } => {
+            let after = cursor
+                .as_deref()
+                .filter(|x| !x.trim().is_empty())
+                .map(PhotoCursor::parse)
+                .transpose()?;
+            let mut conn = establish_connection();
+            storage::photo_search::list_photos(
+                &mut conn,
+                &params,
+                sort.unwrap_or_default(),
+                after.as_ref(),
+                count as i64,
+            )?
+            .iter()
+            .map(|x| x.full_path())
+            .collect()
+        }
+    };
+    Ok(THUMBNAIL_QUEUE.prioritize(&paths))
+}
+
+/// 文件夹中的图片【包含子文件夹；同一文件夹在缓存时间内只遍历一次】
+fn folder_images(path: &str) -> Arc<Vec<PathBuf>> {
+    let mut cache = VISIBLE_FOLDER.lock().unwrap();
+    let ttl = Duration::from_secs(THUMBNAIL_VISIBLE_FOLDER_CACHE_SECS);
+    if let Some((cached_path, time, images)) = cache.as_ref() {
+        if cached_path == path && time.elapsed() < ttl {
+            return images.clone();
+        }
+    }
+    let images: Arc<Vec<PathBuf>> = Arc::new(
+        file_util::get_all_subfolders(path)
+            .iter()
+            .flat_map(|x| file_util::get_all_dir_img(x, Some(-1)))
+            .collect(),
+    );
+    *cache = Some((path.to_string(), Instant::now(), images.clone()));
+    images
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    #[test]
+    fn test_thumbnail_owner() {
+        let hash = "ab".repeat(32);
+        let path = PathBuf::from("cache").join("ab").join(&hash).join("g0123abcd").join("256.jpg");
+        assert_eq!(
+            thumbnail_owner(&path),
+            Some((hash.clone(), Some("g0123abcd".to_string())))
+        );
+        // 加入版本目录之前的缩略图
+        let legacy = PathBuf::from("cache").join("ab").join(&hash).join("256.jpg");
+        assert_eq!(thumbnail_owner(&legacy), Some((hash, None)));
+    }
+
+    #[test]
+    fn test_scan_keeps_fresh_temp_file() {
+        let root = tempfile::tempdir().unwrap();
+        let fresh = root
+            .path()
+            .join(format!("{}new", THUMBNAIL_TEMP_FILE_PREFIX));
+        let abandoned = root
+            .path()
+            .join(format!("{}old", THUMBNAIL_TEMP_FILE_PREFIX));
+        fs::write(&fresh, b"writing").unwrap();
+        fs::write(&abandoned, b"interrupted").unwrap();
+        let modified =
+            SystemTime::now() - Duration::from_secs(THUMBNAIL_TEMP_FILE_MAX_AGE_SECS + 60);
+        fs::File::options()
+            .write(true)
+            .open(&abandoned)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+
+        let (report, corrupt_hashes) = scan_thumbnail_dir(root.path()).unwrap();
+        // 正在写入的临时文件保留，写入中断的删除
+        assert!(fresh.exists());
+        assert!(!abandoned.exists());
+        assert_eq!(report.removed_temp, 1);
+        assert_eq!(report.checked, 0);
+        assert!(corrupt_hashes.is_empty());
+    }
+}
@@ -0,0 +1,19 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::tiff_page::TiffPageInfo;
+use crate::utils::img_util::ImageOperate;
+use anyhow::Result;
+
+/// 获取照片的 TIFF 页面信息【不是 TIFF 时页数为 0】
+/// - photo_id 照片 ID
+pub fn get_photo_pages(photo_id: i32) -> Result<TiffPageInfo> {
+    let mut conn = establish_connection();
+    let photo = storage::photo_table::search_photos_by_ids(&mut conn, &[photo_id])?
+        .pop()
+        .ok_or_else(|| i18n::error(msg::PHOTO_NOT_FOUND, &[("id", photo_id.to_string())]))?;
+    Ok(TiffPageInfo::new(ImageOperate::tiff_pages(
+        &photo.full_path(),
+    )))
+}
@@ -0,0 +1,102 @@
+use crate::constant::DEFAULT_PROFILE_ID;
+use crate::i18n::{self, msg, Locale};
+use crate::models::user_profile::{NewUserProfile, UserProfileRecord};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::config::sys_config;
+use crate::structs::photo_list::PhotoListSort;
+use crate::structs::user_profile::{GridDensity, Theme, UserProfile};
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+
+/// 获取所有用户
+pub fn list_profiles() -> Result<Vec<UserProfile>> {
+    let mut conn = establish_connection();
+    Ok(storage::user_profile::get_all_profiles(&mut conn)?
+        .into_iter()
+        .map(to_profile)
+        .collect())
+}
+
+/// 获取用户资料
+/// - profile_id 用户 ID【为空时返回默认用户】
+pub fn get_profile(profile_id: Option<i32>) -> Result<UserProfile> {
+    let id = profile_id.unwrap_or(DEFAULT_PROFILE_ID);
+    let mut conn = establish_connection();
+    storage::user_profile::get_profile(&mut conn, id)?
+        .map(to_profile)
+        .ok_or_else(|| i18n::error(msg::USER_NOT_FOUND, &[("id", id.to_string())]))
+}
+
+/// 保存用户资料【ID 为空时新增】
+///
+/// 默认用户的界面语言立即生效
+pub fn save_profile(profile: UserProfile) -> Result<UserProfile> {
+    profile.validate()?;
+    let item = NewUserProfile {
+        display_name: profile.display_name.trim().to_string(),
+        language: profile
+            .language
+            .as_deref()
+            .and_then(Locale::from_str)
+            .map(|x| x.as_str().to_string()),
+        theme: profile.theme.as_str().to_string(),
+        default_sort: profile.default_sort.as_str().to_string(),
+        grid_density: profile.grid_density.as_str().to_string(),
+        vault_safe_search: profile.vault_safe_search,
+        update_time: TimeUtils::current_timestamp(),
+    };
+    let mut conn = establish_connection();
+    let record = match profile.id {
+        Some(id) => storage::user_profile::update_profile(&mut conn, id, &item)?,
+        None => storage::user_profile::insert_profile(&mut conn, &item)?,
+    };
+    let saved = to_profile(record);
+    if saved.id == Some(DEFAULT_PROFILE_ID) {
+        apply_locale(&saved);
+    }
+    Ok(saved)
+}
+
+/// 删除用户【默认用户不能删除】
+pub fn delete_profile(id: i32) -> Result<()> {
+    if id == DEFAULT_PROFILE_ID {
+        return Err(anyhow!(i18n::text(msg::USER_DEFAULT_UNDELETABLE)));
+    }
+    let mut conn = establish_connection();
+    storage::user_profile::delete_profile(&mut conn, id)
+}
+
+/// 程序启动时使用默认用户的界面语言
+pub fn apply_default_profile() {
+    match get_profile(None) {
+        Ok(profile) => apply_locale(&profile),
+        Err(e) => log::warn!("默认用户读取失败: {}", e),
+    }
+}
+
+/// 切换到用户的界面语言【没有设置时使用配置文件中的语言】
+fn apply_locale(profile: &UserProfile) {
+    let locale = profile
+        .language
+        .clone()
+        .or_else(|| sys_config().locale.clone())
+        .as_deref()
+        .and_then(Locale::from_str);
+    if let Some(locale) = locale {
+        i18n::set_locale(locale);
+    }
+}
+
+/// 数据库记录转换为用户资料【无效的值使用默认值】
+fn to_profile(record: UserProfileRecord) -> UserProfile {
+    UserProfile {
+        id: Some(record.id),
+        display_name: record.display_name,
+        language: record.language,
+        theme: Theme::from_str(&record.theme).unwrap_or_default(),
+        default_sort: PhotoListSort::from_str(&record.default_sort).unwrap_or_default(),
+        grid_density: GridDensity::from_str(&record.grid_density).unwrap_or_default(),
+        vault_safe_search: record.vault_safe_search,
+    }
+}
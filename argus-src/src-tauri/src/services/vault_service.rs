@@ -0,0 +1,424 @@
+use crate::constant::{
+    EXPORT_DEFAULT_JPEG_QUALITY, VAULT_CONFIG_NAME, VAULT_FOLDER, VAULT_KDF_ITERATIONS,
+    VAULT_MIN_PASSPHRASE_CHARS, VAULT_THUMBNAIL_SIZE, VAULT_VIEW_PREFIX,
+};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::models::vault_item::{NewVaultItem, PhotoPrivateFields, VaultItem};
+use crate::services::thumbnail_cache_service;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::library::current_library;
+use crate::structs::vault::{VaultConfig, VaultItemInfo, VaultMetadata, VaultStatus, VaultSummary};
+use crate::utils::file_util;
+use crate::utils::img_util::ImageOperate;
+use crate::utils::json_util::JsonUtil;
+use crate::utils::time_util::TimeUtils;
+use crate::utils::vault_crypto_util::{self, VaultKey};
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use image::codecs::jpeg::JpegEncoder;
+use image::DynamicImage;
+use once_cell::sync::Lazy;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tempfile::TempDir;
+
+/// 加密后保存在配置中的校验数据【解密结果一致说明密码正确】
+const VERIFIER: &[u8] = b"argus-vault";
+
+/// 已解锁的保险箱
+struct VaultSession {
+    /// 所属照片库文件夹【切换照片库后视为未解锁】
+    library_root: PathBuf,
+    key: VaultKey,
+    /// 解密后的文件所在的临时文件夹【锁定时删除】
+    view_dir: TempDir,
+}
+
+/// 当前解锁的保险箱【密钥只保存在内存中】
+static VAULT_SESSION: Lazy<Mutex<Option<VaultSession>>> = Lazy::new(|| Mutex::new(None));
+
+/// 获取保险箱状态
+pub fn vault_status() -> Result<VaultStatus> {
+    let mut conn = establish_connection();
+    Ok(VaultStatus {
+        created: vault_dir().join(VAULT_CONFIG_NAME).is_file(),
+        unlocked: with_session(|_| Ok(())).is_ok(),
+        item_count: storage::vault_item::count_vault_items(&mut conn)?,
+    })
+}
+
+/// 创建保险箱并解锁
+/// - passphrase 密码【忘记后无法找回保险箱中的照片】
+pub fn create_vault(passphrase: &str) -> Result<()> {
+    let config_file = vault_dir().join(VAULT_CONFIG_NAME);
+    if config_file.exists() {
+        return Err(anyhow!(i18n::text(msg::VAULT_ALREADY_EXISTS)));
+    }
+    if passphrase.chars().count() < VAULT_MIN_PASSPHRASE_CHARS {
+        return Err(i18n::error(
+            msg::VAULT_PASSPHRASE_TOO_SHORT,
+            &[("min", VAULT_MIN_PASSPHRASE_CHARS.to_string())],
+        ));
+    }
+    let salt = vault_crypto_util::random_bytes::<16>()?;
+    let key = VaultKey::derive(passphrase, &salt, VAULT_KDF_ITERATIONS)?;
+    let config = VaultConfig {
+        salt: STANDARD.encode(salt),
+        iterations: VAULT_KDF_ITERATIONS,
+        verifier: STANDARD.encode(key.encrypt(VERIFIER)?),
+        create_time: TimeUtils::current_timestamp(),
+    };
+    fs::create_dir_all(vault_dir())?;
+    fs::write(&config_file, JsonUtil::stringify(&config)?)?;
+    start_session(key)?;
+    log::info!("保险箱已创建");
+    Ok(())
+}
+
+/// 解锁保险箱，并把缩略图解密到临时文件夹
+/// - passphrase 密码
+pub fn unlock_vault(passphrase: &str) -> Result<Vec<VaultItemInfo>> {
+    let config_file = vault_dir().join(VAULT_CONFIG_NAME);
+    if !config_file.is_file() {
+        return Err(anyhow!(i18n::text(msg::VAULT_NOT_CREATED)));
+    }
+    let config: VaultConfig = JsonUtil::from_json(&fs::read_to_string(&config_file)?)?;
+    let key = VaultKey::derive(
+        passphrase,
+        &STANDARD.decode(&config.salt)?,
+        config.iterations,
+    )?;
+    match key.decrypt(&STANDARD.decode(&config.verifier)?) {
+        Ok(x) if x == VERIFIER => {}
+        _ => return Err(anyhow!(i18n::text(msg::VAULT_WRONG_PASSPHRASE))),
+    }
+    start_session(key)?;
+    log::info!("保险箱已解锁");
+    list_vault_items()
+}
+
+/// 锁定保险箱【删除所有解密后的临时文件】
+pub fn lock_vault() {
+    if let Some(session) = VAULT_SESSION.lock().unwrap().take() {
+        if let Err(e) = session.view_dir.close() {
+            log::warn!("保险箱临时文件删除失败: {}", e);
+        }
+        log::info!("保险箱已锁定");
+    }
+}
+
+/// 获取保险箱中的照片【需要先解锁】
+pub fn list_vault_items() -> Result<Vec<VaultItemInfo>> {
+    let mut conn = establish_connection();
+    let items = storage::vault_item::list_vault_items(&mut conn)?;
+    with_session(|session| {
+        let thumbnail_dir = session.view_dir.path().join("thumbnails");
+        fs::create_dir_all(&thumbnail_dir)?;
+        let result = items
+            .into_iter()
+            .map(|(item, photo)| {
+                let thumbnail_path = item.thumbnail_name.as_deref().and_then(|name| {
+                    let path = thumbnail_dir.join(format!("{}.jpg", item.id));
+                    if !path.exists() {
+                        decrypt_to(&session.key, &vault_dir().join(name), &path)
+                            .map_err(|e| log::warn!("保险箱缩略图解密失败 {}: {}", name, e))
+                            .ok()?;
+                    }
+                    Some(file_util::display_path(&path))
+                });
+                VaultItemInfo {
+                    id: item.id,
+                    photo_id: item.photo_id,
+                    img_name: photo.img_name,
+                    file_size: item.file_size,
+                    create_time: item.create_time,
+                    thumbnail_path,
+                }
+            })
+            .collect();
+        Ok(result)
+    })
+}
+
+/// 把保险箱中的原图解密到临时文件夹，返回临时文件路径【需要先解锁】
+/// - item_id 保险箱照片 ID
+pub fn open_vault_item(item_id: i32) -> Result<String> {
+    let mut conn = establish_connection();
+    let (item, photo) = storage::vault_item::get_vault_item(&mut conn, item_id)?;
+    with_session(|session| {
+        let path = session
+            .view_dir
+            .path()
+            .join(format!("{}_{}", item.id, photo.img_name));
+        if !path.exists() {
+            decrypt_to(&session.key, &vault_dir().join(&item.file_name), &path)?;
+        }
+        Ok(file_util::display_path(&path))
+    })
+}
+
+/// 把照片移入保险箱【需要先解锁】
+///
+/// 原图和缩略图分别加密保存到保险箱文件夹，重新解密校验一致后才删除原图和缓存的缩略图，
+/// 照片在数据库中标记为已删除，不再出现在普通查询中。说明、备注、位置信息和标签加密保存到保险箱后
+/// 从数据库中清除，移出时恢复
+/// - photo_ids 照片 ID
+pub fn move_to_vault(photo_ids: &[i32]) -> Result<VaultSummary> {
+    let mut conn = establish_connection();
+    let photos = storage::photo_table::search_photos_by_ids(&mut conn, photo_ids)?;
+    with_session(|session| {
+        fs::create_dir_all(vault_dir())?;
+        let mut summary = VaultSummary::default();
+        for photo in &photos {
+            match move_photo(&mut conn, &session.key, photo) {
+                Ok(()) => summary.succeeded += 1,
+                Err(e) => {
+                    log::warn!("照片移入保险箱失败 {}: {}", photo.full_path().display(), e);
+                    summary.failed.push(format!("{}: {}", photo.img_name, e));
+                }
+            }
+        }
+        log::info!("移入保险箱完成: {:?}", summary);
+        Ok(summary)
+    })
+}
+
+/// 把照片移出保险箱，解密回原来的位置【需要先解锁】
+/// - item_ids 保险箱照片 ID
+pub fn restore_from_vault(item_ids: &[i32]) -> Result<VaultSummary> {
+    let mut conn = establish_connection();
+    with_session(|session| {
+        let mut summary = VaultSummary::default();
+        for item_id in item_ids {
+            let result = storage::vault_item::get_vault_item(&mut conn, *item_id)
+                .and_then(|(item, photo)| restore_photo(&mut conn, &session.key, &item, &photo));
+            match result {
+                Ok(()) => summary.succeeded += 1,
+                Err(e) => {
+                    log::warn!("照片移出保险箱失败 {}: {}", item_id, e);
+                    summary.failed.push(format!("{}: {}", item_id, e));
+                }
+            }
+        }
+        log::info!("移出保险箱完成: {:?}", summary);
+        Ok(summary)
+    })
+}
+
+/// 加密单张照片并移入保险箱
+///
+/// 原图删除失败时撤销移入，照片保持原样
+fn move_photo(conn: &mut diesel::SqliteConnection, key: &VaultKey, photo: &Photo) -> Result<()> {
+    if photo.is_remote() {
+        return Err(anyhow!(i18n::text(msg::VAULT_REMOTE_PHOTO)));
+    }
+    let path = photo.full_path();
+    let name = hex(&vault_crypto_util::random_bytes::<16>()?);
+    let file_name = format!("{}.bin", name);
+    let source = File::open(file_util::long_path(&path))?;
+    let file_size = write_encrypted(key, source, &vault_dir().join(&file_name))?;
+
+    let metadata = VaultMetadata {
+        fields: PhotoPrivateFields::from_photo(photo),
+        tag_ids: storage::tag::get_photo_tag_ids(conn, photo.id)?,
+    };
+    let meta_file = vault_dir().join(format!("{}.meta", name));
+    if let Err(e) =
+        JsonUtil::stringify(&metadata).and_then(|x| write_encrypted(key, x.as_bytes(), &meta_file))
+    {
+        remove_vault_files(&name);
+        return Err(e);
+    }
+
+    // 缩略图生成失败（如不支持的格式）不影响移入
+    let thumbnail_name = format!("{}.thumb", name);
+    let thumbnail = thumbnail_jpeg(&path)
+        .and_then(|x| write_encrypted(key, &x[..], &vault_dir().join(&thumbnail_name)));
+    let thumbnail_name = match thumbnail {
+        Ok(_) => Some(thumbnail_name),
+        Err(e) => {
+            log::warn!("保险箱缩略图生成失败 {}: {}", path.display(), e);
+            None
+        }
+    };
+
+    let item = NewVaultItem {
+        photo_id: photo.id,
+        file_name,
+        thumbnail_name,
+        file_size: file_size as i64,
+        create_time: TimeUtils::current_timestamp(),
+    };
+    let item = match storage::vault_item::insert_vault_item(conn, item) {
+        Ok(item) => item,
+        Err(e) => {
+            remove_vault_files(&name);
+            return Err(e);
+        }
+    };
+    // 原图还在时照片不算移入，撤销数据库中的修改
+    if let Err(e) = fs::remove_file(file_util::long_path(&path)) {
+        if let Err(e) = storage::vault_item::remove_vault_item(conn, &item, Some(&metadata)) {
+            log::error!("保险箱照片撤销失败 {}: {}", path.display(), e);
+            return Err(e);
+        }
+        remove_vault_files(&name);
+        return Err(i18n::error(
+            msg::VAULT_ORIGINAL_DELETE_FAILED,
+            &[
+                ("path", path.display().to_string()),
+                ("error", e.to_string()),
+            ],
+        ));
+    }
+    thumbnail_cache_service::remove_unused_thumbnails(conn, &photo.hash);
+    Ok(())
+}
+
+/// 解密单张照片并恢复到原来的位置
+fn restore_photo(
+    conn: &mut diesel::SqliteConnection,
+    key: &VaultKey,
+    item: &VaultItem,
+    photo: &Photo,
+) -> Result<()> {
+    let path = photo.full_path();
+    if path.exists() {
+        return Err(i18n::error(
+            msg::VAULT_RESTORE_TARGET_EXISTS,
+            &[("path", path.display().to_string())],
+        ));
+    }
+    let meta_file = vault_dir().join(Path::new(&item.file_name).with_extension("meta"));
+    let metadata: Option<VaultMetadata> = if meta_file.is_file() {
+        let data = key.decrypt(&fs::read(&meta_file)?)?;
+        Some(JsonUtil::from_json(&String::from_utf8(data)?)?)
+    } else {
+        None
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(file_util::long_path(parent))?;
+    }
+    decrypt_to(key, &vault_dir().join(&item.file_name), &path)?;
+    if let Err(e) = storage::vault_item::remove_vault_item(conn, item, metadata.as_ref()) {
+        let _ = fs::remove_file(file_util::long_path(&path));
+        return Err(e);
+    }
+    let _ = fs::remove_file(vault_dir().join(&item.file_name));
+    let _ = fs::remove_file(meta_file);
+    if let Some(name) = &item.thumbnail_name {
+        let _ = fs::remove_file(vault_dir().join(name));
+    }
+    Ok(())
+}
+
+/// 分块加密后写入文件，并重新读取解密校验，返回明文长度
+fn write_encrypted(key: &VaultKey, source: impl Read, dest: &Path) -> Result<u64> {
+    let result = File::create(dest)
+        .map_err(anyhow::Error::from)
+        .and_then(|file| key.encrypt_stream(BufReader::new(source), BufWriter::new(file)))
+        .and_then(|len| {
+            // 所有块都通过认证且长度一致，说明写入的内容完整
+            let verified = key.decrypt_stream(BufReader::new(File::open(dest)?), io::sink())?;
+            if verified != len {
+                return Err(anyhow!(i18n::text(msg::VAULT_VERIFY_FAILED)));
+            }
+            Ok(len)
+        });
+    if result.is_err() {
+        let _ = fs::remove_file(dest);
+    }
+    result
+}
+
+/// 解密文件【失败时删除不完整的文件】
+fn decrypt_to(key: &VaultKey, src: &Path, dest: &Path) -> Result<()> {
+    let dest = file_util::long_path(dest);
+    let result = File::create(&dest)
+        .map_err(anyhow::Error::from)
+        .and_then(|file| {
+            key.decrypt_stream(BufReader::new(File::open(src)?), BufWriter::new(file))
+        });
+    if let Err(e) = result {
+        let _ = fs::remove_file(&dest);
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// 生成 JPEG 缩略图
+fn thumbnail_jpeg(path: &Path) -> Result<Vec<u8>> {
+    let image =
+        ImageOperate::open_oriented(path)?.thumbnail(VAULT_THUMBNAIL_SIZE, VAULT_THUMBNAIL_SIZE);
+    let mut data = Vec::new();
+    DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(JpegEncoder::new_with_quality(
+        &mut data,
+        EXPORT_DEFAULT_JPEG_QUALITY,
+    ))?;
+    Ok(data)
+}
+
+/// 删除移入失败的照片留下的加密文件
+fn remove_vault_files(name: &str) {
+    for extension in ["bin", "meta", "thumb"] {
+        let _ = fs::remove_file(vault_dir().join(format!("{}.{}", name, extension)));
+    }
+}
+
+/// 删除上次异常退出时留下的解密文件夹【启动时执行】
+pub fn clean_stale_views() {
+    let Ok(entries) = fs::read_dir(std::env::temp_dir()) else {
+        return;
+    };
+    for entry in entries.filter_map(|x| x.ok()) {
+        let is_view = entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(VAULT_VIEW_PREFIX);
+        if !is_view || !entry.path().is_dir() {
+            continue;
+        }
+        match fs::remove_dir_all(entry.path()) {
+            Ok(_) => log::info!("删除保险箱临时文件: {}", entry.path().display()),
+            Err(e) => log::warn!("保险箱临时文件删除失败 {}: {}", entry.path().display(), e),
+        }
+    }
+}
+
+/// 创建解锁会话【替换之前的会话】
+fn start_session(key: VaultKey) -> Result<()> {
+    let view_dir = tempfile::Builder::new()
+        .prefix(VAULT_VIEW_PREFIX)
+        .tempdir()?;
+    lock_vault();
+    *VAULT_SESSION.lock().unwrap() = Some(VaultSession {
+        library_root: current_library().root.clone(),
+        key,
+        view_dir,
+    });
+    Ok(())
+}
+
+/// 在已解锁的会话中执行
+fn with_session<T>(f: impl FnOnce(&VaultSession) -> Result<T>) -> Result<T> {
+    let guard = VAULT_SESSION.lock().unwrap();
+    match guard.as_ref() {
+        Some(session) if session.library_root == current_library().root => f(session),
+        _ => Err(anyhow!(i18n::text(msg::VAULT_LOCKED))),
+    }
+}
+
+/// 当前照片库的保险箱文件夹
+fn vault_dir() -> PathBuf {
+    current_library().root.join(VAULT_FOLDER)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|x| format!("{:02x}", x)).collect()
+}
@@ -0,0 +1,127 @@
+use crate::models::photo_storage::PhotoStorage;
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::utils::file_util;
+use crate::utils::volume_util;
+use crate::utils::volume_util::VolumeInfo;
+use anyhow::Result;
+use diesel::SqliteConnection;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// 图像存储路径所在卷的状态
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageVolumeStatus {
+    /// 图像存储路径 ID
+    pub storage_id: i32,
+    /// 当前存储路径
+    pub img_paths: String,
+    /// 卷唯一标识
+    pub volume_id: Option<String>,
+    /// 是否在线
+    pub is_available: bool,
+    /// 重新挂载前的路径【盘符、挂载点发生变化时有值】
+    pub remounted_from: Option<String>,
+    /// 状态发生变化的照片数量
+    pub affected_photos: usize,
+}
+
+/// 检查所有图像存储路径所在的卷
+///
+/// - 首次检查时记录卷标识和相对路径
+/// - 卷离线时把对应照片标记为不可访问
+/// - 卷挂载到新位置时自动修正存储路径和照片路径
+pub fn refresh_storage_volumes() -> Result<Vec<StorageVolumeStatus>> {
+    let mut conn = establish_connection();
+    let storages = storage::photo_storage::get_all_photo_path(&mut conn)?;
+    let mut result = Vec::with_capacity(storages.len());
+    for item in storages {
+        let status = refresh_storage_volume(&mut conn, &item)?;
+        if status.remounted_from.is_some() || status.is_available != item.is_available {
+            log::info!("存储路径状态变化: {:?}", status);
+        }
+        result.push(status);
+    }
+    Ok(result)
+}
+
+/// 检查单个存储路径
+fn refresh_storage_volume(
+    conn: &mut SqliteConnection,
+    item: &PhotoStorage,
+) -> Result<StorageVolumeStatus> {
+    let root = PathBuf::from(&item.img_paths);
+    let mut status = StorageVolumeStatus {
+        storage_id: item.id,
+        img_paths: item.img_paths.clone(),
+        volume_id: item.volume_id.clone(),
+        is_available: true,
+        remounted_from: None,
+        affected_photos: 0,
+    };
+
+    // 原路径可访问，且仍是同一个卷（或从未记录过卷信息）
+    let current = volume_util::volume_for_path(&root);
+    if let Some(volume) = current.as_ref().filter(|v| {
+        item.volume_id.is_none() || item.volume_id.as_deref() == Some(v.volume_id.as_str())
+    }) {
+        save_volume(conn, item.id, &root, volume)?;
+        status.volume_id = Some(volume.volume_id.clone());
+        if !item.is_available {
+            status.affected_photos =
+                storage::photo_table::set_photos_available_under(conn, &root, true)?;
+        }
+        return Ok(status);
+    }
+
+    // 原路径不可访问或被其他卷占用，按卷标识查找新的挂载位置
+    let remounted = item.volume_id.as_deref().and_then(volume_util::find_volume).and_then(|v| {
+        let relative = item.relative_path.as_deref().unwrap_or_default();
+        let new_root = v.mount_point.join(relative);
+        new_root.exists().then_some((v, new_root))
+    });
+    if let Some((volume, new_root)) = remounted {
+        status.affected_photos = storage::photo_table::rebase_photo_paths(conn, &root, &new_root)?;
+        save_volume(conn, item.id, &new_root, &volume)?;
+        status.img_paths = file_util::display_path(&new_root);
+        status.remounted_from = Some(item.img_paths.clone());
+        return Ok(status);
+    }
+
+    // 卷离线
+    status.is_available = false;
+    if item.is_available {
+        storage::photo_storage::update_storage_volume(
+            conn,
+            item.id,
+            &item.img_paths,
+            item.volume_id.as_deref(),
+            item.volume_label.as_deref(),
+            item.relative_path.as_deref(),
+            false,
+        )?;
+        status.affected_photos = storage::photo_table::set_photos_available_under(conn, &root, false)?;
+    }
+    Ok(status)
+}
+
+/// 保存存储路径当前所在的卷
+fn save_volume(
+    conn: &mut SqliteConnection,
+    storage_id: i32,
+    root: &Path,
+    volume: &VolumeInfo,
+) -> Result<()> {
+    let relative = volume_util::relative_to_mount(root, &volume.mount_point)
+        .map(|p| p.display().to_string());
+    storage::photo_storage::update_storage_volume(
+        conn,
+        storage_id,
+        &file_util::display_path(root),
+        Some(&volume.volume_id),
+        Some(&volume.label),
+        relative.as_deref(),
+        true,
+    )
+}
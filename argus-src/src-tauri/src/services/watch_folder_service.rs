@@ -0,0 +1,562 @@
+use crate::constant::{
+    IMAGE_COMPRESSION_RATIO, WATCH_FOLDER_POLL_MS, WATCH_FOLDER_SETTLE_MS, WATCH_IMPORT_QUEUE_SIZE,
+};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::models::photo::Photo;
+use crate::models::watch_folder::{NewWatchFolder, NewWatchFolderImport, WatchFolderRecord};
+use crate::services::{
+    disk_space_service, folder_album_service, photo_file_service, problem_service,
+};
+use crate::storage;
+use crate::storage::connection::establish_connection;
+use crate::structs::disk_space::DiskSpacePurpose;
+use crate::structs::problem::ProblemKind;
+use crate::structs::thumbnail_encoding;
+use crate::structs::watch_folder::WatchFolder;
+use crate::utils::exif_utils::exif_util;
+use crate::utils::file_hash_util::FileHashUtils;
+use crate::utils::file_util;
+use crate::utils::img_util::ImageOperate;
+use crate::utils::json_util::JsonUtil;
+use crate::utils::thumbnail_queue_util::THUMBNAIL_QUEUE;
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local, NaiveDateTime};
+use diesel::{Connection, SqliteConnection};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use walkdir::WalkDir;
+
+/// 正在监视的文件夹（监视文件夹 ID -> 监视器）【监视器释放时停止监视】
+static WATCHERS: Lazy<Mutex<HashMap<i32, RecommendedWatcher>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 文件变化通知（监视文件夹 ID、文件路径）【等待文件写入完成后放入导入队列】
+static FILE_EVENTS: Lazy<Sender<(i32, PathBuf)>> = Lazy::new(|| {
+    let (tx, rx) = mpsc::channel();
+    thread::Builder::new()
+        .name("argus-watch-folder".to_string())
+        .spawn(move || wait_for_settled(rx))
+        .expect("监视文件夹线程启动失败");
+    tx
+});
+
+/// 导入队列（监视文件夹 ID、文件路径）【依次导入】
+static IMPORT_QUEUE: Lazy<tokio::sync::mpsc::Sender<(i32, PathBuf)>> = Lazy::new(|| {
+    let (tx, rx) = tokio::sync::mpsc::channel(WATCH_IMPORT_QUEUE_SIZE);
+    tauri::async_runtime::spawn(import_task(rx));
+    tx
+});
+
+/// 等待写入数据库的照片（文件 Hash -> 导入配置）【照片写入后加入相册、添加标签】
+static PENDING_PROFILES: Lazy<Mutex<HashMap<String, WatchFolder>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 等待写入完成的文件
+#[derive(Debug, Clone, Copy)]
+struct PendingFile {
+    folder_id: i32,
+    /// 最后一次变化的时间
+    changed: Instant,
+    /// 上次检查时的文件大小
+    size: Option<u64>,
+}
+
+/// 获取所有监视文件夹
+pub fn list_folders() -> Result<Vec<WatchFolder>> {
+    let mut conn = establish_connection();
+    let watching = WATCHERS.lock().unwrap_or_else(|e| e.into_inner());
+    storage::watch_folder::get_all_folders(&mut conn)?
+        .into_iter()
+        .map(|record| {
+            let active = watching.contains_key(&record.id);
+            to_folder(record).map(|mut x| {
+                x.watching = active;
+                x
+            })
+        })
+        .collect()
+}
+
+/// 保存监视文件夹【ID 为空时新增】，保存后重新开始监视
+pub fn save_folder(folder: WatchFolder) -> Result<WatchFolder> {
+    folder.validate()?;
+    let tags: Vec<String> = folder
+        .tags
+        .iter()
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty())
+        .collect();
+    let item = NewWatchFolder {
+        path: folder.path.trim().to_string(),
+        dest_dir: folder.dest_dir.trim().to_string(),
+        dest_template: trimmed(folder.dest_template),
+        album: trimmed(folder.album),
+        tags: JsonUtil::stringify(&tags)?,
+        move_files: folder.move_files,
+        is_enable: folder.enabled,
+        update_time: TimeUtils::current_timestamp(),
+    };
+    let mut conn = establish_connection();
+    let record = match folder.id {
+        Some(id) => storage::watch_folder::update_folder(&mut conn, id, &item)?,
+        None => storage::watch_folder::insert_folder(&mut conn, &item)?,
+    };
+    let mut folder = to_folder(record)?;
+    stop_watching(folder.id.unwrap_or_default());
+    if folder.enabled {
+        folder.watching = start_watching(&folder);
+    }
+    Ok(folder)
+}
+
+/// 删除监视文件夹【已导入的照片不变】
+pub fn delete_folder(id: i32) -> Result<()> {
+    let mut conn = establish_connection();
+    storage::watch_folder::delete_folder(&mut conn, id)?;
+    stop_watching(id);
+    Ok(())
+}
+
+/// 开始监视所有启用的文件夹【程序启动时调用】
+///
+/// 程序未运行期间放入的文件也会导入
+pub fn start_all() {
+    let mut conn = establish_connection();
+    let records = match storage::watch_folder::get_enabled_folders(&mut conn) {
+        Ok(x) => x,
+        Err(e) => {
+            log::error!("监视文件夹读取失败: {}", e);
+            return;
+        }
+    };
+    for record in records {
+        match to_folder(record) {
+            Ok(folder) => {
+                start_watching(&folder);
+            }
+            Err(e) => log::error!("{}", e),
+        }
+    }
+}
+
+/// 照片写入数据库后，按导入配置加入相册、添加标签【由照片批量写入任务调用】
+///
+/// 同一批中没有写入的照片（写入失败等）也释放导入配置
+/// - hashes 这一批照片的 Hash
+/// - photos 写入的照片
+pub fn apply_pending(
+    connection: &mut SqliteConnection,
+    hashes: &[String],
+    photos: &[Photo],
+) -> Result<()> {
+    let matched: Vec<(i32, WatchFolder)> = {
+        let mut pending = PENDING_PROFILES.lock().unwrap_or_else(|e| e.into_inner());
+        if pending.is_empty() {
+            return Ok(());
+        }
+        let matched = photos
+            .iter()
+            .filter_map(|x| pending.remove(&x.hash).map(|folder| (x.id, folder)))
+            .collect();
+        for hash in hashes {
+            if pending.remove(hash).is_some() {
+                log::warn!(
+                    "监视文件夹导入的照片没有写入，不再加入相册、添加标签: {}",
+                    hash
+                );
+            }
+        }
+        matched
+    };
+    for (photo_id, folder) in matched {
+        apply_profile(connection, photo_id, &folder)?;
+    }
+    Ok(())
+}
+
+/// 开始监视文件夹，并把文件夹中已有的图片放入导入队列，返回是否成功
+fn start_watching(folder: &WatchFolder) -> bool {
+    let Some(id) = folder.id else {
+        return false;
+    };
+    let path = PathBuf::from(&folder.path);
+    let sender = FILE_EVENTS.clone();
+    let watcher = notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+        Ok(event) => {
+            if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                for path in event.paths {
+                    let _ = sender.send((id, file_util::strip_verbatim_prefix(path)));
+                }
+            }
+        }
+        Err(e) => log::warn!("监视文件夹事件读取失败: {}", e),
+    });
+    let watcher = watcher.and_then(|mut x| {
+        x.watch(&file_util::long_path(&path), RecursiveMode::Recursive)?;
+        Ok(x)
+    });
+    match watcher {
+        Ok(watcher) => {
+            WATCHERS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(id, watcher);
+        }
+        Err(e) => {
+            log::warn!("监视文件夹 {} 启动失败: {}", folder.path, e);
+            return false;
+        }
+    }
+    log::info!("开始监视文件夹 {}", folder.path);
+    for entry in WalkDir::new(file_util::long_path(&path))
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let _ = FILE_EVENTS.send((id, file_util::strip_verbatim_prefix(entry.path())));
+    }
+    true
+}
+
+/// 停止监视文件夹
+fn stop_watching(id: i32) {
+    WATCHERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&id);
+}
+
+/// 等待文件写入完成，放入导入队列
+fn wait_for_settled(rx: Receiver<(i32, PathBuf)>) {
+    let mut pending: HashMap<PathBuf, PendingFile> = HashMap::new();
+    loop {
+        match rx.recv_timeout(Duration::from_millis(WATCH_FOLDER_POLL_MS)) {
+            Ok((folder_id, path)) => {
+                if is_candidate(&path) {
+                    pending.insert(
+                        path,
+                        PendingFile {
+                            folder_id,
+                            changed: Instant::now(),
+                            size: None,
+                        },
+                    );
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        let ready = take_settled(&mut pending, Instant::now(), |path| {
+            fs::metadata(file_util::long_path(path))
+                .ok()
+                .filter(|x| x.is_file())
+                .map(|x| x.len())
+        });
+        for job in ready {
+            if let Err(e) = IMPORT_QUEUE.blocking_send(job) {
+                log::error!("监视文件夹导入队列已关闭: {}", e);
+            }
+        }
+    }
+}
+
+/// 取出写入完成的文件
+///
+/// 最后一次变化后超过等待时间，且与上次检查时大小相同才算写入完成；
+/// 大小变化时重新等待，文件已不存在时丢弃
+/// - size_of 读取文件大小【文件不存在时为空】
+fn take_settled(
+    pending: &mut HashMap<PathBuf, PendingFile>,
+    now: Instant,
+    size_of: impl Fn(&Path) -> Option<u64>,
+) -> Vec<(i32, PathBuf)> {
+    let settle = Duration::from_millis(WATCH_FOLDER_SETTLE_MS);
+    let mut ready = Vec::new();
+    pending.retain(|path, file| {
+        if now.duration_since(file.changed) < settle {
+            return true;
+        }
+        let Some(size) = size_of(path) else {
+            return false;
+        };
+        if file.size != Some(size) {
+            file.size = Some(size);
+            file.changed = now;
+            return true;
+        }
+        ready.push((file.folder_id, path.clone()));
+        false
+    });
+    ready
+}
+
+/// 是否为需要导入的文件【跳过隐藏文件和下载、复制中的临时文件】
+fn is_candidate(path: &Path) -> bool {
+    let Some(name) = path.file_name().map(|x| x.to_string_lossy().to_lowercase()) else {
+        return false;
+    };
+    if name.starts_with('.') || name.starts_with("~$") {
+        return false;
+    }
+    let temporary = [".tmp", ".part", ".crdownload", ".download", ".partial"];
+    if temporary.iter().any(|x| name.ends_with(x)) {
+        return false;
+    }
+    file_util::is_image_file(path)
+}
+
+/// 依次导入队列中的文件
+async fn import_task(mut rx: tokio::sync::mpsc::Receiver<(i32, PathBuf)>) {
+    while let Some((folder_id, path)) = rx.recv().await {
+        // 每次读取最新的配置，监视文件夹已删除或停用时跳过
+        let folder = {
+            let mut conn = establish_connection();
+            storage::watch_folder::get_folder(&mut conn, folder_id)
+                .and_then(|x| {
+                    x.ok_or_else(|| {
+                        i18n::error(
+                            msg::WATCH_FOLDER_NOT_FOUND,
+                            &[("id", folder_id.to_string())],
+                        )
+                    })
+                })
+                .and_then(to_folder)
+        };
+        let folder = match folder {
+            Ok(x) if x.enabled => x,
+            Ok(_) => continue,
+            Err(e) => {
+                log::warn!("{}", e);
+                continue;
+            }
+        };
+        match import_file(&folder, &path).await {
+            Ok(Some(dest)) => {
+                log::info!("监视文件夹导入 {} -> {}", path.display(), dest.display());
+                folder_album_service::request_sync();
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("监视文件夹导入失败 {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// 按导入配置导入文件，返回导入后的路径
+///
+/// 已从这个文件夹导入过的文件不再导入（程序重新启动时会重新检查文件夹中的所有文件）；
+/// 照片库中已有相同内容的照片时不再复制或移动，只给已有的照片加入相册、添加标签，
+/// 已删除（回收站、私密空间）的照片不重新导入
+async fn import_file(folder: &WatchFolder, source: &Path) -> Result<Option<PathBuf>> {
+    let Some(folder_id) = folder.id else {
+        return Ok(None);
+    };
+    let Ok(metadata) = fs::metadata(file_util::long_path(source)) else {
+        return Ok(None);
+    };
+    if !metadata.is_file() {
+        return Ok(None);
+    }
+    let source_path = file_util::display_path(source);
+    let file_size = metadata.len() as i64;
+    let mut conn = establish_connection();
+    if storage::watch_folder::is_source_imported(&mut conn, folder_id, &source_path, file_size)? {
+        return Ok(None);
+    }
+    let hash = FileHashUtils::sha256_async(source).await?;
+    let record = NewWatchFolderImport {
+        folder_id,
+        source_path,
+        hash: hash.clone(),
+        file_size,
+        create_time: TimeUtils::current_timestamp(),
+    };
+    if storage::watch_folder::is_hash_imported(&mut conn, folder_id, &hash)? {
+        storage::watch_folder::record_import(&mut conn, &record)?;
+        return Ok(None);
+    }
+    let existing =
+        storage::photo_table::search_photos_by_hashes_with_deleted(&mut conn, &[hash.clone()])?;
+    if let Some(photo) = existing.into_iter().next() {
+        if photo.is_delete {
+            log::info!("{} 已删除，跳过导入", source.display());
+        } else {
+            log::info!("照片库中已有 {}，跳过导入", source.display());
+            apply_profile(&mut conn, photo.id, folder)?;
+        }
+        storage::watch_folder::record_import(&mut conn, &record)?;
+        return Ok(None);
+    }
+
+    let (time, make, model) = capture_info(source).await;
+    let dest = folder.dest_path(source, time, make.as_deref(), model.as_deref())?;
+    let dest = file_util::unique_path(&dest);
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(file_util::long_path(parent))?;
+    }
+    if folder.move_files {
+        photo_file_service::move_path(source, &dest)?;
+    } else {
+        let size = fs::metadata(file_util::long_path(source))?.len();
+        disk_space_service::ensure_free_space(
+            Path::new(folder.dest_dir.trim()),
+            DiskSpacePurpose::Import,
+            size,
+        )?;
+        file_util::copy_file(source, &dest).map_err(|e| anyhow!(e))?;
+    }
+    storage::watch_folder::record_import(&mut conn, &record)?;
+
+    PENDING_PROFILES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(hash.clone(), folder.clone());
+    let imported = {
+        // 与扫描共用缩略图任务队列，限制同时处理的数量
+        let _permit = THUMBNAIL_QUEUE.acquire(&dest).await;
+        ImageOperate::multi_level_image_compression(
+            &dest,
+            thumbnail_encoding::storage_format(),
+            IMAGE_COMPRESSION_RATIO.to_vec(),
+        )
+        .await
+    };
+    if let Err(e) = imported {
+        PENDING_PROFILES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&hash);
+        problem_service::record(&dest, ProblemKind::Decode, &e.to_string());
+        return Err(e);
+    }
+    Ok(Some(dest))
+}
+
+/// 读取拍摄时间（拍摄地当地时间）和相机【读取失败时使用文件修改时间】
+async fn capture_info(path: &Path) -> (NaiveDateTime, Option<String>, Option<String>) {
+    let source = path.to_path_buf();
+    let exif = tokio::task::spawn_blocking(move || exif_util::read_img_exif(&source))
+        .await
+        .ok()
+        .and_then(|x| x.ok());
+    let modified = fs::metadata(file_util::long_path(path))
+        .and_then(|x| x.modified())
+        .map(|x| DateTime::<Local>::from(x).naive_local())
+        .unwrap_or_else(|_| Local::now().naive_local());
+    match exif {
+        Some(exif) => {
+            let time = exif
+                .date_time_original
+                .map(|x| TimeUtils::to_local_date_time(x.timestamp(), exif.offset_time.as_deref()))
+                .unwrap_or(modified);
+            (time, exif.make, exif.model)
+        }
+        None => (modified, None, None),
+    }
+}
+
+/// 按导入配置给照片加入相册、添加标签
+fn apply_profile(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    folder: &WatchFolder,
+) -> Result<()> {
+    connection.transaction(|conn| {
+        if let Some(album) = folder.album.as_deref() {
+            let album = storage::album::get_or_insert_album(conn, album)?;
+            storage::album::add_photo_albums(conn, photo_id, &[album.id])?;
+        }
+        for tag in &folder.tags {
+            let tag = storage::tag::get_or_insert_tag(conn, tag)?;
+            storage::tag::add_photo_tags(conn, photo_id, &[tag.id])?;
+        }
+        Ok(())
+    })
+}
+
+/// 去掉首尾空白，空字符串转换为 None
+fn trimmed(value: Option<String>) -> Option<String> {
+    value
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty())
+}
+
+fn to_folder(record: WatchFolderRecord) -> Result<WatchFolder> {
+    let tags: Vec<String> = JsonUtil::from_json(&record.tags).map_err(|e| {
+        i18n::error(
+            msg::WATCH_FOLDER_TAGS_INVALID,
+            &[("id", record.id.to_string()), ("error", e.to_string())],
+        )
+    })?;
+    Ok(WatchFolder {
+        id: Some(record.id),
+        path: record.path,
+        dest_dir: record.dest_dir,
+        dest_template: record.dest_template,
+        album: record.album,
+        tags,
+        move_files: record.move_files,
+        enabled: record.is_enable,
+        watching: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_candidate() {
+        assert!(is_candidate(Path::new("/inbox/IMG_0001.JPG")));
+        assert!(!is_candidate(Path::new("/inbox/.IMG_0001.JPG")));
+        assert!(!is_candidate(Path::new("/inbox/IMG_0001.jpg.crdownload")));
+        assert!(!is_candidate(Path::new("/inbox/notes.txt")));
+    }
+
+    #[test]
+    fn test_take_settled() {
+        let start = Instant::now();
+        let settle = Duration::from_millis(WATCH_FOLDER_SETTLE_MS);
+        let path = PathBuf::from("/inbox/a.jpg");
+        let mut pending = HashMap::from([(
+            path.clone(),
+            PendingFile {
+                folder_id: 1,
+                changed: start,
+                size: None,
+            },
+        )]);
+
+        // 还在等待时间内
+        assert!(take_settled(&mut pending, start, |_| Some(10)).is_empty());
+        // 第一次检查记录大小，重新等待
+        assert!(take_settled(&mut pending, start + settle, |_| Some(10)).is_empty());
+        // 大小变化，继续等待
+        assert!(take_settled(&mut pending, start + settle * 2, |_| Some(20)).is_empty());
+        assert_eq!(
+            take_settled(&mut pending, start + settle * 3, |_| Some(20)),
+            vec![(1, path.clone())]
+        );
+        assert!(pending.is_empty());
+
+        // 文件已删除时丢弃
+        pending.insert(
+            path,
+            PendingFile {
+                folder_id: 1,
+                changed: start,
+                size: None,
+            },
+        );
+        assert!(take_settled(&mut pending, start + settle, |_| None).is_empty());
+        assert!(pending.is_empty());
+    }
+}
@@ -0,0 +1,299 @@
+use crate::constant::SQLITE_MAX_VARIABLES;
+use crate::models::album::{Album, NewAlbum, NewPhotoAlbum};
+use crate::models::photo::Photo;
+use crate::storage::schema::albums;
+use crate::storage::schema::photo_albums;
+use crate::storage::schema::photo_table;
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// 获取所有相册
+pub fn get_all_albums(connection: &mut SqliteConnection) -> Result<Vec<Album>> {
+    let results = albums::table
+        .filter(albums::is_delete.eq(false))
+        .order(albums::id.asc())
+        .load::<Album>(connection)?;
+    Ok(results)
+}
+
+/// 统计每个相册的照片数量
+pub fn count_album_photos(connection: &mut SqliteConnection) -> Result<HashMap<i32, i64>> {
+    let counts = photo_albums::table
+        .group_by(photo_albums::album_id)
+        .select((photo_albums::album_id, count_star()))
+        .load::<(i32, i64)>(connection)?;
+    Ok(counts.into_iter().collect())
+}
+
+/// 获取相册中的照片【不包含子相册】
+pub fn get_album_photos(connection: &mut SqliteConnection, album_id: i32) -> Result<Vec<Photo>> {
+    let photo_ids = photo_albums::table
+        .filter(photo_albums::album_id.eq(album_id))
+        .select(photo_albums::photo_id);
+    let photos = photo_table::table
+        .filter(photo_table::is_delete.eq(false))
+        .filter(photo_table::id.eq_any(photo_ids))
+        .order(photo_table::id.asc())
+        .load::<Photo>(connection)?;
+    Ok(photos)
+}
+
+/// 新增相册
+pub fn insert_album(
+    connection: &mut SqliteConnection,
+    name: &str,
+    parent_id: Option<i32>,
+) -> Result<Album> {
+    if name.trim().is_empty() {
+        return Err(anyhow!("相册名称不能为空!"));
+    }
+    let timestamp = TimeUtils::current_timestamp();
+    let item = NewAlbum {
+        name: name.trim().to_string(),
+        parent_id,
+        description: None,
+        create_time: timestamp,
+        update_time: timestamp,
+        source_path: None,
+    };
+    let album = diesel::insert_into(albums::table)
+        .values(item)
+        .returning(Album::as_returning())
+        .get_result(connection)?;
+    Ok(album)
+}
+
+/// 按名称查找顶级相册
+pub fn find_root_album(connection: &mut SqliteConnection, name: &str) -> Result<Option<Album>> {
+    let album = albums::table
+        .filter(albums::is_delete.eq(false))
+        .filter(albums::parent_id.is_null())
+        .filter(albums::source_path.is_null())
+        .filter(albums::name.eq(name.trim()))
+        .order(albums::id.asc())
+        .first::<Album>(connection)
+        .optional()?;
+    Ok(album)
+}
+
+/// 获取顶级相册，不存在时新增
+pub fn get_or_insert_album(connection: &mut SqliteConnection, name: &str) -> Result<Album> {
+    match find_root_album(connection, name)? {
+        Some(album) => Ok(album),
+        None => insert_album(connection, name, None),
+    }
+}
+
+/// 更新相册名称及父级
+pub fn update_album_node(
+    connection: &mut SqliteConnection,
+    id: i32,
+    name: &str,
+    parent_id: Option<i32>,
+) -> Result<()> {
+    let rows = diesel::update(albums::table.filter(albums::id.eq(id)))
+        .set((
+            albums::name.eq(name.trim()),
+            albums::parent_id.eq(parent_id),
+            albums::update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    if rows == 0 {
+        return Err(anyhow!("相册 {} 不存在!", id));
+    }
+    Ok(())
+}
+
+/// 把照片加入相册【已在相册中的忽略】，返回新增的数量
+pub fn add_photo_albums(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    album_ids: &[i32],
+) -> Result<usize> {
+    let timestamp = TimeUtils::current_timestamp();
+    let items: Vec<NewPhotoAlbum> = album_ids
+        .iter()
+        .map(|album_id| NewPhotoAlbum {
+            album_id: *album_id,
+            photo_id,
+            create_time: timestamp,
+        })
+        .collect();
+    // 每行 3 个参数，分批插入避免超出 SQLite 参数数量限制
+    connection.transaction(|conn| {
+        let mut rows = 0;
+        for chunk in items.chunks(SQLITE_MAX_VARIABLES / 3) {
+            rows += diesel::insert_or_ignore_into(photo_albums::table)
+                .values(chunk)
+                .execute(conn)?;
+        }
+        Ok(rows)
+    })
+}
+
+/// 相册是否存在【不包括已删除的相册】
+pub fn album_exists(connection: &mut SqliteConnection, album_id: i32) -> Result<bool> {
+    let count: i64 = albums::table
+        .filter(albums::id.eq(album_id))
+        .filter(albums::is_delete.eq(false))
+        .count()
+        .get_result(connection)?;
+    Ok(count > 0)
+}
+
+/// 相册是否由文件夹同步生成【只读】
+pub fn is_mirror_album(connection: &mut SqliteConnection, album_id: i32) -> Result<bool> {
+    let count: i64 = albums::table
+        .filter(albums::id.eq(album_id))
+        .filter(albums::source_path.is_not_null())
+        .count()
+        .get_result(connection)?;
+    Ok(count > 0)
+}
+
+/// 获取所有由文件夹同步生成的相册
+pub fn get_mirror_albums(connection: &mut SqliteConnection) -> Result<Vec<Album>> {
+    let results = albums::table
+        .filter(albums::source_path.is_not_null())
+        .order(albums::id.asc())
+        .load::<Album>(connection)?;
+    Ok(results)
+}
+
+/// 新增由文件夹同步生成的相册
+/// - source_path 文件夹路径
+pub fn insert_mirror_album(
+    connection: &mut SqliteConnection,
+    name: &str,
+    parent_id: Option<i32>,
+    source_path: &str,
+) -> Result<Album> {
+    let timestamp = TimeUtils::current_timestamp();
+    let item = NewAlbum {
+        name: name.to_string(),
+        parent_id,
+        description: None,
+        create_time: timestamp,
+        update_time: timestamp,
+        source_path: Some(source_path.to_string()),
+    };
+    let album = diesel::insert_into(albums::table)
+        .values(item)
+        .returning(Album::as_returning())
+        .get_result(connection)?;
+    Ok(album)
+}
+
+/// 删除相册及其照片关联，返回删除的相册数量
+pub fn delete_albums(connection: &mut SqliteConnection, album_ids: &[i32]) -> Result<usize> {
+    connection.transaction(|conn| {
+        let mut rows = 0;
+        for chunk in album_ids.chunks(SQLITE_MAX_VARIABLES) {
+            diesel::delete(photo_albums::table.filter(photo_albums::album_id.eq_any(chunk)))
+                .execute(conn)?;
+            rows += diesel::delete(albums::table.filter(albums::id.eq_any(chunk))).execute(conn)?;
+        }
+        Ok(rows)
+    })
+}
+
+/// 查询相册中的照片 ID（相册 ID -> 照片 ID）
+pub fn get_album_photo_ids(
+    connection: &mut SqliteConnection,
+    album_ids: &[i32],
+) -> Result<HashMap<i32, HashSet<i32>>> {
+    let mut result: HashMap<i32, HashSet<i32>> = HashMap::new();
+    for chunk in album_ids.chunks(SQLITE_MAX_VARIABLES) {
+        let links = photo_albums::table
+            .filter(photo_albums::album_id.eq_any(chunk))
+            .select((photo_albums::album_id, photo_albums::photo_id))
+            .load::<(i32, i32)>(connection)?;
+        for (album_id, photo_id) in links {
+            result.entry(album_id).or_default().insert(photo_id);
+        }
+    }
+    Ok(result)
+}
+
+/// 把照片加入相册【已在相册中的忽略】，返回新增的数量
+pub fn add_album_photos(
+    connection: &mut SqliteConnection,
+    album_id: i32,
+    photo_ids: &[i32],
+) -> Result<usize> {
+    let timestamp = TimeUtils::current_timestamp();
+    let items: Vec<NewPhotoAlbum> = photo_ids
+        .iter()
+        .map(|photo_id| NewPhotoAlbum {
+            album_id,
+            photo_id: *photo_id,
+            create_time: timestamp,
+        })
+        .collect();
+    // 每行 3 个参数，分批插入避免超出 SQLite 参数数量限制
+    connection.transaction(|conn| {
+        let mut rows = 0;
+        for chunk in items.chunks(SQLITE_MAX_VARIABLES / 3) {
+            rows += diesel::insert_or_ignore_into(photo_albums::table)
+                .values(chunk)
+                .execute(conn)?;
+        }
+        Ok(rows)
+    })
+}
+
+/// 把照片移出相册，返回移出的数量
+pub fn remove_album_photos(
+    connection: &mut SqliteConnection,
+    album_id: i32,
+    photo_ids: &[i32],
+) -> Result<usize> {
+    connection.transaction(|conn| {
+        let mut rows = 0;
+        for chunk in photo_ids.chunks(SQLITE_MAX_VARIABLES - 1) {
+            rows += diesel::delete(
+                photo_albums::table
+                    .filter(photo_albums::album_id.eq(album_id))
+                    .filter(photo_albums::photo_id.eq_any(chunk)),
+            )
+            .execute(conn)?;
+        }
+        Ok(rows)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::connection::test_connection;
+
+    #[test]
+    fn test_album_photos_chunked() {
+        let mut conn = test_connection();
+        let album = insert_album(&mut conn, "旅行", None).unwrap();
+        // 超过 SQLite 参数数量限制的照片分批处理
+        let photo_ids: Vec<i32> = (1..=2500).collect();
+        assert_eq!(
+            add_album_photos(&mut conn, album.id, &photo_ids).unwrap(),
+            2500
+        );
+        assert_eq!(
+            add_album_photos(&mut conn, album.id, &photo_ids).unwrap(),
+            0
+        );
+        let links = get_album_photo_ids(&mut conn, &[album.id]).unwrap();
+        assert_eq!(links[&album.id].len(), 2500);
+
+        assert_eq!(
+            remove_album_photos(&mut conn, album.id, &photo_ids[..1500]).unwrap(),
+            1500
+        );
+        assert_eq!(delete_albums(&mut conn, &[album.id]).unwrap(), 1);
+        assert!(get_album_photo_ids(&mut conn, &[album.id])
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,23 +1,38 @@
-use crate::structs::config::SYS_CONFIG;
-use crate::utils::{db_init_util, file_util};
+use crate::storage::photo_table;
+use crate::structs::library::{current_library, LibraryContext};
+use crate::utils::db_init_util;
+use anyhow::{anyhow, Result};
 use diesel::connection::SimpleConnection;
 use diesel::sqlite::SqliteConnection;
 use diesel::{Connection, QueryResult, RunQueryDsl};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
-use dotenvy::dotenv;
-use once_cell::sync::Lazy;
-use std::{env, fs};
+use std::fs;
+use std::path::Path;
 
 /// 获取所有的数据库迁移
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
 
-pub fn run_migrations() -> Result<(), Box<dyn std::error::Error>> {
-    init_path().expect("数据库路径初始化失败!");
-    let lazy = DATABASE_URL.as_str();
-    let mut connection = SqliteConnection::establish(lazy)?;
+/// 升级当前照片库的数据库
+pub fn run_migrations() -> Result<()> {
+    migrate_database(&current_library())
+}
+
+/// 升级指定照片库的数据库【数据库不存在时创建】
+///
+/// 切换照片库时先升级新照片库的数据库，成功后再切换，失败时不影响当前照片库
+/// - library 照片库
+pub fn migrate_database(library: &LibraryContext) -> Result<()> {
+    init_path(&library.database_dir())?;
+    let url = library.database_url();
+    let mut connection =
+        SqliteConnection::establish(&url).map_err(|e| anyhow!("数据库 {} 连接失败: {}", url, e))?;
     connection
         .run_pending_migrations(MIGRATIONS)
-        .expect("TODO: panic message");
+        .map_err(|e| anyhow!("数据库 {} 迁移失败: {}", url, e))?;
+    // 旧版本的 GPS 文本无法用 SQL 解析，迁移后逐条转换
+    if let Err(e) = photo_table::migrate_legacy_gps_info(&mut connection) {
+        log::warn!("旧版本 GPS 信息转换失败: {}", e);
+    }
     Ok(())
 }
 
@@ -37,40 +52,21 @@ pub fn does_table_exist(
     Ok(exists?)
 }
 
-/// 全局惰性变量
-pub static DATABASE_URL: Lazy<String> = Lazy::new(|| {
-    dotenv().ok();
-    // 获取数据库保存路径
-    let database_path = SYS_CONFIG.database_default_link.clone().unwrap();
-    // 数据库路径
-    let db_dir = SYS_CONFIG.database_path.clone().unwrap();
-    let db_name = SYS_CONFIG.database_name.clone().unwrap();
-    // 运行时根据环境变量计算
-    let database_url = env::var(crate::constant::DATABASE_URL_KEY)
-        .unwrap_or_else(|_| database_path.parse().unwrap());
-    let cpath = file_util::get_root_folder().expect("根路径读取失败！ ");
-
-    log::info!("Using database url: {:?},{}", cpath, database_url);
-    let string = format!("{}/{}/{}", cpath.to_string_lossy(), db_dir, db_name);
-    log::info!("Using database - url: {:?}", string);
-    string
-});
+/// 当前照片库的数据库路径
+pub fn database_url() -> String {
+    current_library().database_url()
+}
 
 /// 初始化数据库路径
-pub fn init_path() -> Result<(), rusqlite::Error> {
-    let db_dir = SYS_CONFIG.database_path.clone().unwrap();
-    // 使用 Tauri 提供的路径 API 确保数据库文件存放在用户目录下
-    let cpath = file_util::get_root_folder().expect("TODO: panic message");
-    let app_dir = format!("{}/{}", cpath.to_string_lossy(), db_dir);
-
+/// - app_dir 数据库所在文件夹
+pub fn init_path(app_dir: &Path) -> Result<()> {
     // 如果文件不存在，创建文件，反之无动作
-    let exists = file_util::file_exists(&app_dir);
-    if exists {
-        log::info!("Found existing database url: {}", app_dir);
+    if app_dir.exists() {
+        log::info!("Found existing database url: {}", app_dir.display());
     } else {
-        log::info!("Creating database directory: {}", app_dir);
-        // 获取推荐的数据库路径
-        fs::create_dir_all(&app_dir).expect("The app data directory should be created.");
+        log::info!("Creating database directory: {}", app_dir.display());
+        fs::create_dir_all(app_dir)
+            .map_err(|e| anyhow!("数据库文件夹 {} 创建失败: {}", app_dir.display(), e))?;
     }
 
     log::info!("Initializing database connection");
@@ -79,17 +75,26 @@ pub fn init_path() -> Result<(), rusqlite::Error> {
 
 /// 创建数据库链接
 pub fn establish_connection() -> SqliteConnection {
-    let mut conn = SqliteConnection::establish(&DATABASE_URL)
-        .unwrap_or_else(|err| {
-            log::error!("Error connecting to {:?}: {:?}", *DATABASE_URL, err);
-            panic!("Error connecting to {:?}: {:?}", *DATABASE_URL, err);
-        });
+    let url = database_url();
+    let mut conn = SqliteConnection::establish(&url).unwrap_or_else(|err| {
+        log::error!("Error connecting to {:?}: {:?}", url, err);
+        panic!("Error connecting to {:?}: {:?}", url, err);
+    });
     // 多线程读、单线程写
     conn.batch_execute("PRAGMA journal_mode = WAL;")
         .expect("Failed to enable WAL mode");
     conn
 }
 
+/// 测试使用的内存数据库【已执行所有迁移】
+#[cfg(test)]
+pub fn test_connection() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").expect("内存数据库创建失败");
+    conn.run_pending_migrations(MIGRATIONS)
+        .expect("内存数据库迁移失败");
+    conn
+}
+
 /// 删除指定表
 pub fn drop_table(
     conn: &mut SqliteConnection,
@@ -153,3 +158,40 @@ create table user
     )
     .expect("TODO: panic message");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::config::Config;
+    use std::sync::Arc;
+
+    fn library(root: &Path) -> LibraryContext {
+        LibraryContext {
+            name: "test".to_string(),
+            root: root.to_path_buf(),
+            config: Arc::new(Config::default()),
+        }
+    }
+
+    #[test]
+    fn test_migrate_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = library(dir.path());
+        migrate_database(&library).unwrap();
+        let mut conn = SqliteConnection::establish(&library.database_url()).unwrap();
+        assert!(does_table_exist(&mut conn, "photo_table").unwrap());
+        // 重复执行不报错
+        migrate_database(&library).unwrap();
+    }
+
+    #[test]
+    fn test_migrate_database_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = library(dir.path());
+        // 数据库文件夹被同名文件占用，返回错误而不是 panic
+        let db_dir = library.database_dir();
+        fs::create_dir_all(db_dir.parent().unwrap()).unwrap();
+        fs::write(&db_dir, b"").unwrap();
+        assert!(migrate_database(&library).is_err());
+    }
+}
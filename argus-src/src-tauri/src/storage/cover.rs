@@ -0,0 +1,146 @@
+use crate::models::folder_cover::{FolderCover, NewFolderCover};
+use crate::models::photo::Photo;
+use crate::storage::photo_table::path_prefix_pattern;
+use crate::storage::schema::{albums, folder_covers, photo_albums, photo_table};
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+use std::path::Path;
+
+/// 按路径查找文件夹封面
+pub fn get_folder_cover(
+    connection: &mut SqliteConnection,
+    path: &str,
+) -> Result<Option<FolderCover>> {
+    let cover = folder_covers::table
+        .filter(folder_covers::path.eq(path))
+        .select(FolderCover::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(cover)
+}
+
+/// 保存文件夹封面【已存在时替换】
+pub fn save_folder_cover(
+    connection: &mut SqliteConnection,
+    path: &str,
+    photo_id: i32,
+) -> Result<()> {
+    let timestamp = TimeUtils::current_timestamp();
+    let item = NewFolderCover {
+        path: path.to_string(),
+        photo_id,
+        update_time: timestamp,
+    };
+    connection.transaction(|conn| {
+        let rows = diesel::update(folder_covers::table.filter(folder_covers::path.eq(path)))
+            .set(&item)
+            .execute(conn)?;
+        if rows == 0 {
+            diesel::insert_into(folder_covers::table)
+                .values((&item, folder_covers::create_time.eq(timestamp)))
+                .execute(conn)?;
+        }
+        Ok(())
+    })
+}
+
+/// 删除文件夹封面【恢复为默认封面】
+pub fn delete_folder_cover(connection: &mut SqliteConnection, path: &str) -> Result<usize> {
+    let rows = diesel::delete(folder_covers::table.filter(folder_covers::path.eq(path)))
+        .execute(connection)?;
+    Ok(rows)
+}
+
+/// 设置相册封面【为空时恢复为默认封面】
+pub fn set_album_cover(
+    connection: &mut SqliteConnection,
+    album_id: i32,
+    photo_id: Option<i32>,
+) -> Result<()> {
+    let rows = diesel::update(
+        albums::table
+            .filter(albums::id.eq(album_id))
+            .filter(albums::is_delete.eq(false)),
+    )
+    .set((
+        albums::cover_photo_id.eq(photo_id),
+        albums::update_time.eq(TimeUtils::current_timestamp()),
+    ))
+    .execute(connection)?;
+    if rows == 0 {
+        return Err(anyhow!("相册 {} 不存在!", album_id));
+    }
+    Ok(())
+}
+
+/// 照片是否在相册中
+pub fn is_album_photo(
+    connection: &mut SqliteConnection,
+    album_id: i32,
+    photo_id: i32,
+) -> Result<bool> {
+    let count: i64 = photo_albums::table
+        .filter(photo_albums::album_id.eq(album_id))
+        .filter(photo_albums::photo_id.eq(photo_id))
+        .count()
+        .get_result(connection)?;
+    Ok(count > 0)
+}
+
+/// 相册中最新的照片【按拍摄时间，没有拍摄时间的按导入时间排在后面】
+pub fn newest_album_photo(
+    connection: &mut SqliteConnection,
+    album_id: i32,
+) -> Result<Option<Photo>> {
+    let photo_ids = photo_albums::table
+        .filter(photo_albums::album_id.eq(album_id))
+        .select(photo_albums::photo_id);
+    let photo = photo_table::table
+        .filter(photo_table::is_delete.eq(false))
+        .filter(photo_table::id.eq_any(photo_ids))
+        .order((
+            photo_table::date_time_original.desc(),
+            photo_table::create_time.desc(),
+            photo_table::id.desc(),
+        ))
+        .select(Photo::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(photo)
+}
+
+/// 文件夹（含子文件夹）中最新的照片【排序同 `newest_album_photo`】
+pub fn newest_folder_photo(
+    connection: &mut SqliteConnection,
+    root: &Path,
+) -> Result<Option<Photo>> {
+    // 前缀匹配会包含同名前缀的其他文件夹，按路径组件过滤后取第一张
+    let candidates: Vec<(i32, String)> = photo_table::table
+        .filter(photo_table::is_delete.eq(false))
+        .filter(
+            photo_table::img_path
+                .like(path_prefix_pattern(root))
+                .escape('\\'),
+        )
+        .order((
+            photo_table::date_time_original.desc(),
+            photo_table::create_time.desc(),
+            photo_table::id.desc(),
+        ))
+        .select((photo_table::id, photo_table::img_path))
+        .load(connection)?;
+    let Some(photo_id) = candidates
+        .into_iter()
+        .find(|(_, path)| Path::new(path).starts_with(root))
+        .map(|(id, _)| id)
+    else {
+        return Ok(None);
+    };
+    let photo = photo_table::table
+        .filter(photo_table::id.eq(photo_id))
+        .select(Photo::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(photo)
+}
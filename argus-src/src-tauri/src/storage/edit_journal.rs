@@ -0,0 +1,80 @@
+use crate::models::edit_journal::{EditJournalRecord, NewEditJournal};
+use crate::storage::schema::edit_journal;
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+
+/// 新增编辑记录
+///
+/// 新的编辑会使之前撤销的记录无法再重做，因此同时删除本会话中已撤销的记录
+pub fn insert_edit(
+    connection: &mut SqliteConnection,
+    session_id: &str,
+    description: &str,
+    changes: &str,
+) -> Result<EditJournalRecord> {
+    diesel::delete(
+        edit_journal::table
+            .filter(edit_journal::session_id.eq(session_id))
+            .filter(edit_journal::is_undone.eq(true)),
+    )
+    .execute(connection)?;
+
+    let timestamp = TimeUtils::current_timestamp();
+    let item = NewEditJournal {
+        session_id: session_id.to_string(),
+        description: description.to_string(),
+        changes: changes.to_string(),
+        create_time: timestamp,
+        update_time: timestamp,
+    };
+    let record = diesel::insert_into(edit_journal::table)
+        .values(item)
+        .returning(EditJournalRecord::as_returning())
+        .get_result(connection)?;
+    Ok(record)
+}
+
+/// 获取本会话中最后一条未撤销的记录
+pub fn get_last_active_edit(
+    connection: &mut SqliteConnection,
+    session_id: &str,
+) -> Result<Option<EditJournalRecord>> {
+    let record = edit_journal::table
+        .filter(edit_journal::session_id.eq(session_id))
+        .filter(edit_journal::is_undone.eq(false))
+        .order(edit_journal::id.desc())
+        .select(EditJournalRecord::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(record)
+}
+
+/// 获取本会话中最早一条已撤销的记录【即下一条可以重做的记录】
+pub fn get_first_undone_edit(
+    connection: &mut SqliteConnection,
+    session_id: &str,
+) -> Result<Option<EditJournalRecord>> {
+    let record = edit_journal::table
+        .filter(edit_journal::session_id.eq(session_id))
+        .filter(edit_journal::is_undone.eq(true))
+        .order(edit_journal::id.asc())
+        .select(EditJournalRecord::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(record)
+}
+
+/// 设置记录的撤销状态
+pub fn set_edit_undone(connection: &mut SqliteConnection, id: i32, undone: bool) -> Result<()> {
+    let rows = diesel::update(edit_journal::table.filter(edit_journal::id.eq(id)))
+        .set((
+            edit_journal::is_undone.eq(undone),
+            edit_journal::update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    if rows == 0 {
+        return Err(anyhow!("编辑记录 {} 不存在!", id));
+    }
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::constant::SQLITE_MAX_VARIABLES;
+use crate::models::event::{Event, NewEvent, NewEventPhoto};
+use crate::models::photo::Photo;
+use crate::storage::schema::{event_photos, events, photo_table};
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+
+/// 参与事件分段的照片（ID、拍摄时间、时区、GPS 信息、地点名称）
+pub type EventCandidate = (i32, i64, Option<String>, Option<String>, Option<String>);
+
+/// 获取所有有拍摄时间的照片【按拍摄时间排序】
+pub fn get_event_candidates(connection: &mut SqliteConnection) -> Result<Vec<EventCandidate>> {
+    let rows = photo_table::table
+        .filter(photo_table::is_delete.eq(false))
+        .filter(photo_table::date_time_original.is_not_null())
+        .select((
+            photo_table::id,
+            photo_table::date_time_original.assume_not_null(),
+            photo_table::offset_time,
+            photo_table::gps_info,
+            photo_table::location_name,
+        ))
+        .order((photo_table::date_time_original.asc(), photo_table::id.asc()))
+        .load::<EventCandidate>(connection)?;
+    Ok(rows)
+}
+
+/// 用新的分段结果替换所有事件
+/// - items 事件及其包含的照片 ID
+pub fn replace_events(
+    connection: &mut SqliteConnection,
+    items: Vec<(NewEvent, Vec<i32>)>,
+) -> Result<usize> {
+    let now = TimeUtils::current_timestamp();
+    connection.transaction(|conn| {
+        diesel::delete(event_photos::table).execute(conn)?;
+        diesel::delete(events::table).execute(conn)?;
+        let count = items.len();
+        for (event, photo_ids) in items {
+            let event = diesel::insert_into(events::table)
+                .values(event)
+                .returning(Event::as_returning())
+                .get_result(conn)?;
+            let members: Vec<NewEventPhoto> = photo_ids
+                .into_iter()
+                .map(|photo_id| NewEventPhoto {
+                    event_id: event.id,
+                    photo_id,
+                    create_time: now,
+                })
+                .collect();
+            // 每行 3 个参数，分批插入避免超出 SQLite 参数数量限制
+            for chunk in members.chunks(SQLITE_MAX_VARIABLES / 3) {
+                diesel::insert_into(event_photos::table)
+                    .values(chunk)
+                    .execute(conn)?;
+            }
+        }
+        Ok(count)
+    })
+}
+
+/// 获取所有事件【按拍摄时间从新到旧】
+pub fn list_events(connection: &mut SqliteConnection) -> Result<Vec<Event>> {
+    let rows = events::table
+        .order((events::start_time.desc(), events::id.desc()))
+        .select(Event::as_select())
+        .load(connection)?;
+    Ok(rows)
+}
+
+/// 获取事件
+pub fn get_event(connection: &mut SqliteConnection, event_id: i32) -> Result<Event> {
+    events::table
+        .find(event_id)
+        .select(Event::as_select())
+        .first(connection)
+        .optional()?
+        .ok_or_else(|| anyhow!("事件 {} 不存在!", event_id))
+}
+
+/// 获取事件中的照片【按拍摄时间排序】
+pub fn get_event_photos(connection: &mut SqliteConnection, event_id: i32) -> Result<Vec<Photo>> {
+    let photo_ids = event_photos::table
+        .filter(event_photos::event_id.eq(event_id))
+        .select(event_photos::photo_id);
+    let photos = photo_table::table
+        .filter(photo_table::is_delete.eq(false))
+        .filter(photo_table::id.eq_any(photo_ids))
+        .order((photo_table::date_time_original.asc(), photo_table::id.asc()))
+        .load::<Photo>(connection)?;
+    Ok(photos)
+}
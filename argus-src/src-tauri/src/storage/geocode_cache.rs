@@ -0,0 +1,48 @@
+use crate::models::geocode_cache::GeocodeCacheRecord;
+use crate::storage::schema::geocode_cache;
+use anyhow::Result;
+use diesel::prelude::*;
+
+/// 查询未过期的缓存
+/// - key 坐标键（纬度、经度）
+/// - now 当前时间
+pub fn get_cache(
+    connection: &mut SqliteConnection,
+    key: (i32, i32),
+    now: i64,
+) -> Result<Option<GeocodeCacheRecord>> {
+    let record = geocode_cache::table
+        .find(key)
+        .filter(
+            geocode_cache::expire_time
+                .is_null()
+                .or(geocode_cache::expire_time.gt(now)),
+        )
+        .select(GeocodeCacheRecord::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(record)
+}
+
+/// 批量保存缓存【已存在时覆盖】
+pub fn save_caches(
+    connection: &mut SqliteConnection,
+    records: &[GeocodeCacheRecord],
+) -> Result<usize> {
+    connection.transaction(|conn| {
+        let mut count = 0;
+        for record in records {
+            count += diesel::replace_into(geocode_cache::table)
+                .values(record)
+                .execute(conn)?;
+        }
+        Ok(count)
+    })
+}
+
+/// 删除已过期的缓存
+pub fn delete_expired(connection: &mut SqliteConnection, now: i64) -> Result<usize> {
+    let count = diesel::delete(geocode_cache::table.filter(geocode_cache::expire_time.le(now)))
+        .execute(connection)?;
+    Ok(count)
+}
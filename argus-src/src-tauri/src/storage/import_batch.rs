@@ -0,0 +1,117 @@
+use crate::constant::SQLITE_MAX_VARIABLES;
+use crate::models::import_batch::{ImportBatch, NewImportBatch, NewImportBatchPhoto};
+use crate::models::photo::Photo;
+use crate::storage::schema::{import_batch_photos, import_batches, photo_table};
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+
+/// 获取最近的一批
+pub fn latest_batch(connection: &mut SqliteConnection) -> Result<Option<ImportBatch>> {
+    let batch = import_batches::table
+        .order(import_batches::id.desc())
+        .select(ImportBatch::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(batch)
+}
+
+/// 新增一批
+pub fn insert_batch(
+    connection: &mut SqliteConnection,
+    item: NewImportBatch,
+) -> Result<ImportBatch> {
+    let batch = diesel::insert_into(import_batches::table)
+        .values(item)
+        .returning(ImportBatch::as_returning())
+        .get_result(connection)?;
+    Ok(batch)
+}
+
+/// 把照片加入一批，同时更新数量和最后导入时间
+/// - timestamp 导入时间
+pub fn add_batch_photos(
+    connection: &mut SqliteConnection,
+    batch_id: i32,
+    photo_ids: &[i32],
+    timestamp: i64,
+) -> Result<()> {
+    let items: Vec<NewImportBatchPhoto> = photo_ids
+        .iter()
+        .map(|photo_id| NewImportBatchPhoto {
+            batch_id,
+            photo_id: *photo_id,
+            create_time: timestamp,
+        })
+        .collect();
+    connection.transaction(|conn| {
+        // 每行 3 个参数，分批插入避免超出 SQLite 参数数量限制
+        for chunk in items.chunks(SQLITE_MAX_VARIABLES / 3) {
+            diesel::insert_into(import_batch_photos::table)
+                .values(chunk)
+                .execute(conn)?;
+        }
+        diesel::update(import_batches::table.find(batch_id))
+            .set((
+                import_batches::photo_count
+                    .eq(import_batches::photo_count + photo_ids.len() as i32),
+                import_batches::end_time.eq(timestamp),
+            ))
+            .execute(conn)?;
+        Ok(())
+    })
+}
+
+/// 获取最近导入的批次【按导入时间从新到旧】
+pub fn list_batches(
+    connection: &mut SqliteConnection,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<ImportBatch>> {
+    let rows = import_batches::table
+        .filter(import_batches::photo_count.gt(0))
+        .order((import_batches::end_time.desc(), import_batches::id.desc()))
+        .limit(limit)
+        .offset(offset)
+        .select(ImportBatch::as_select())
+        .load(connection)?;
+    Ok(rows)
+}
+
+/// 批次数量
+pub fn count_batches(connection: &mut SqliteConnection) -> Result<i64> {
+    let count = import_batches::table
+        .filter(import_batches::photo_count.gt(0))
+        .count()
+        .get_result(connection)?;
+    Ok(count)
+}
+
+/// 获取批次
+pub fn get_batch(connection: &mut SqliteConnection, batch_id: i32) -> Result<ImportBatch> {
+    import_batches::table
+        .find(batch_id)
+        .select(ImportBatch::as_select())
+        .first(connection)
+        .optional()?
+        .ok_or_else(|| anyhow!("导入批次 {} 不存在!", batch_id))
+}
+
+/// 获取批次中的照片【按导入顺序，不包含已删除的照片】
+pub fn get_batch_photos(
+    connection: &mut SqliteConnection,
+    batch_id: i32,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<Photo>> {
+    let photo_ids = import_batch_photos::table
+        .filter(import_batch_photos::batch_id.eq(batch_id))
+        .select(import_batch_photos::photo_id);
+    let photos = photo_table::table
+        .filter(photo_table::is_delete.eq(false))
+        .filter(photo_table::id.eq_any(photo_ids))
+        .order(photo_table::id.asc())
+        .limit(limit)
+        .offset(offset)
+        .load::<Photo>(connection)?;
+    Ok(photos)
+}
@@ -0,0 +1,56 @@
+use crate::models::photo::Photo;
+use crate::storage::schema::photo_table::dsl::*;
+use crate::utils::exif_utils::tag::DEFAULT_OFFSET;
+use anyhow::Result;
+use chrono::FixedOffset;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Bool, Integer, Text};
+use std::str::FromStr;
+
+/// 拍摄地当地时间的 SQL 表达式（秒）
+///
+/// 拍摄时间按 UTC 保存，加上 `offset_time` 中的时区（如 `+08:00`）得到当地时间，
+/// 没有时区或格式错误时使用 EXIF 的默认时区
+pub fn local_time_sql() -> String {
+    let default_offset = FixedOffset::from_str(DEFAULT_OFFSET)
+        .map(|x| x.local_minus_utc())
+        .unwrap_or_default();
+    format!(
+        "(date_time_original + CASE WHEN offset_time GLOB '[+-][0-9][0-9]:[0-9][0-9]' \
+         THEN (CASE WHEN substr(offset_time, 1, 1) = '-' THEN -1 ELSE 1 END) \
+         * (CAST(substr(offset_time, 2, 2) AS INTEGER) * 3600 \
+         + CAST(substr(offset_time, 5, 2) AS INTEGER) * 60) \
+         ELSE {} END)",
+        default_offset
+    )
+}
+
+/// 查询之前年份同一天拍摄的照片【按拍摄时间排序】
+/// - month_day 月日【如 `05-01`】
+/// - year 当前年份【只返回更早年份的照片】
+pub fn search_same_day_photos(
+    connection: &mut SqliteConnection,
+    month_day: &str,
+    year: i32,
+) -> Result<Vec<Photo>> {
+    let local_time = local_time_sql();
+    let results = photo_table
+        .filter(is_delete.eq(false))
+        .filter(date_time_original.is_not_null())
+        .filter(
+            sql::<Bool>(&format!(
+                "strftime('%m-%d', {}, 'unixepoch') = ",
+                local_time
+            ))
+            .bind::<Text, _>(month_day.to_string())
+            .sql(&format!(
+                " AND CAST(strftime('%Y', {}, 'unixepoch') AS INTEGER) < ",
+                local_time
+            ))
+            .bind::<Integer, _>(year),
+        )
+        .order((date_time_original.asc(), id.asc()))
+        .load::<Photo>(connection)?;
+    Ok(results)
+}
@@ -3,3 +3,27 @@ pub(crate) mod photo_storage;
 pub(crate) mod post;
 pub mod schema;
 pub mod photo_table;
+pub(crate) mod album;
+pub(crate) mod tag;
+pub(crate) mod scan_report;
+pub(crate) mod edit_journal;
+pub(crate) mod photo_search;
+pub(crate) mod photo_stack;
+pub(crate) mod photo_histogram;
+pub(crate) mod photo_color;
+pub(crate) mod place;
+pub(crate) mod photo_sequence;
+pub(crate) mod photo_activity;
+pub(crate) mod vault_item;
+pub(crate) mod problem;
+pub(crate) mod cover;
+pub(crate) mod organize_rule;
+pub(crate) mod event;
+pub(crate) mod import_batch;
+pub(crate) mod memory;
+pub(crate) mod photo_blurhash;
+pub(crate) mod photo_purge;
+pub(crate) mod user_profile;
+pub(crate) mod remote_storage;
+pub(crate) mod watch_folder;
+pub(crate) mod geocode_cache;
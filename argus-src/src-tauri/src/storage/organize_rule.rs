@@ -0,0 +1,69 @@
+use crate::models::organize_rule::{NewOrganizeRule, OrganizeRuleRecord};
+use crate::storage::schema::organize_rules;
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+
+/// 获取所有规则【按 ID 顺序，即执行顺序】
+pub fn get_all_rules(connection: &mut SqliteConnection) -> Result<Vec<OrganizeRuleRecord>> {
+    let results = organize_rules::table
+        .order(organize_rules::id.asc())
+        .select(OrganizeRuleRecord::as_select())
+        .load(connection)?;
+    Ok(results)
+}
+
+/// 获取启用的规则
+pub fn get_enabled_rules(connection: &mut SqliteConnection) -> Result<Vec<OrganizeRuleRecord>> {
+    let results = organize_rules::table
+        .filter(organize_rules::is_enable.eq(true))
+        .order(organize_rules::id.asc())
+        .select(OrganizeRuleRecord::as_select())
+        .load(connection)?;
+    Ok(results)
+}
+
+/// 按 ID 查找规则
+pub fn get_rule(connection: &mut SqliteConnection, id: i32) -> Result<Option<OrganizeRuleRecord>> {
+    let result = organize_rules::table
+        .filter(organize_rules::id.eq(id))
+        .select(OrganizeRuleRecord::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(result)
+}
+
+/// 新增规则
+pub fn insert_rule(
+    connection: &mut SqliteConnection,
+    item: &NewOrganizeRule,
+) -> Result<OrganizeRuleRecord> {
+    let rule = diesel::insert_into(organize_rules::table)
+        .values((item, organize_rules::create_time.eq(item.update_time)))
+        .returning(OrganizeRuleRecord::as_returning())
+        .get_result(connection)?;
+    Ok(rule)
+}
+
+/// 更新规则
+pub fn update_rule(
+    connection: &mut SqliteConnection,
+    id: i32,
+    item: &NewOrganizeRule,
+) -> Result<OrganizeRuleRecord> {
+    let rule = diesel::update(organize_rules::table.filter(organize_rules::id.eq(id)))
+        .set(item)
+        .returning(OrganizeRuleRecord::as_returning())
+        .get_result(connection)
+        .optional()?;
+    rule.ok_or_else(|| anyhow!("规则 {} 不存在!", id))
+}
+
+/// 删除规则
+pub fn delete_rule(connection: &mut SqliteConnection, id: i32) -> Result<()> {
+    let rows = diesel::delete(organize_rules::table.filter(organize_rules::id.eq(id)))
+        .execute(connection)?;
+    if rows == 0 {
+        return Err(anyhow!("规则 {} 不存在!", id));
+    }
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::constant::SQLITE_MAX_VARIABLES;
+use crate::models::photo_activity::{NewPhotoActivity, PhotoActivity};
+use crate::storage::schema::photo_activity;
+use crate::structs::photo_activity::ActivityKind;
+use crate::utils::time_util::TimeUtils;
+use anyhow::Result;
+use diesel::prelude::*;
+
+/// 给照片添加活动记录
+/// - detail 说明【所有照片相同】
+pub fn insert_activities(
+    connection: &mut SqliteConnection,
+    photo_ids: &[i32],
+    kind: ActivityKind,
+    detail: Option<&str>,
+) -> Result<usize> {
+    let timestamp = TimeUtils::current_timestamp();
+    let items: Vec<NewPhotoActivity> = photo_ids
+        .iter()
+        .map(|photo_id| NewPhotoActivity {
+            photo_id: *photo_id,
+            kind: kind.code(),
+            detail: detail.map(str::to_string),
+            create_time: timestamp,
+        })
+        .collect();
+    let mut rows = 0;
+    for chunk in items.chunks(SQLITE_MAX_VARIABLES / 4) {
+        rows += diesel::insert_into(photo_activity::table)
+            .values(chunk)
+            .execute(connection)?;
+    }
+    Ok(rows)
+}
+
+/// 获取照片的活动记录【按时间从新到旧】
+pub fn get_photo_activity(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+) -> Result<Vec<PhotoActivity>> {
+    let rows = photo_activity::table
+        .filter(photo_activity::photo_id.eq(photo_id))
+        .order((
+            photo_activity::create_time.desc(),
+            photo_activity::id.desc(),
+        ))
+        .select(PhotoActivity::as_select())
+        .load(connection)?;
+    Ok(rows)
+}
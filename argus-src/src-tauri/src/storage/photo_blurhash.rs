@@ -0,0 +1,49 @@
+use crate::constant::SQLITE_MAX_VARIABLES;
+use crate::models::photo_blurhash::PhotoBlurhashRecord;
+use crate::storage::schema::{photo_blurhashes, photo_table};
+use crate::utils::time_util::TimeUtils;
+use anyhow::Result;
+use diesel::prelude::*;
+use std::collections::HashMap;
+
+/// 保存 BlurHash【已存在时覆盖】
+pub fn save_blurhash(connection: &mut SqliteConnection, hash: &str, blurhash: &str) -> Result<()> {
+    let record = PhotoBlurhashRecord {
+        hash: hash.to_string(),
+        blurhash: blurhash.to_string(),
+        create_time: TimeUtils::current_timestamp(),
+    };
+    diesel::replace_into(photo_blurhashes::table)
+        .values(record)
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 批量查询 BlurHash【文件 Hash -> BlurHash，没有的不包含】
+pub fn get_blurhashes(
+    connection: &mut SqliteConnection,
+    hashes: &[String],
+) -> Result<HashMap<String, String>> {
+    let mut result = HashMap::with_capacity(hashes.len());
+    for chunk in hashes.chunks(SQLITE_MAX_VARIABLES) {
+        let rows: Vec<(String, String)> = photo_blurhashes::table
+            .filter(photo_blurhashes::hash.eq_any(chunk))
+            .select((photo_blurhashes::hash, photo_blurhashes::blurhash))
+            .load(connection)?;
+        result.extend(rows);
+    }
+    Ok(result)
+}
+
+/// 还没有计算 BlurHash 的照片 Hash
+pub fn get_hashes_without_blurhash(connection: &mut SqliteConnection) -> Result<Vec<String>> {
+    let hashes = photo_table::table
+        .filter(photo_table::is_delete.eq(false))
+        .filter(diesel::dsl::not(diesel::dsl::exists(
+            photo_blurhashes::table.filter(photo_blurhashes::hash.eq(photo_table::hash)),
+        )))
+        .select(photo_table::hash)
+        .distinct()
+        .load(connection)?;
+    Ok(hashes)
+}
@@ -0,0 +1,59 @@
+use crate::models::photo_color::{NewPhotoColor, PhotoColorRecord};
+use crate::storage::schema::{photo_colors, photo_table};
+use crate::structs::dominant_color::DominantColor;
+use crate::utils::time_util::TimeUtils;
+use anyhow::Result;
+use diesel::prelude::*;
+
+/// 保存照片主色【替换已有的记录】
+pub fn save_colors(
+    connection: &mut SqliteConnection,
+    hash: &str,
+    colors: &[DominantColor],
+) -> Result<()> {
+    let now = TimeUtils::current_timestamp();
+    let items: Vec<NewPhotoColor> = colors
+        .iter()
+        .map(|x| NewPhotoColor {
+            hash,
+            l: x.lab[0],
+            a: x.lab[1],
+            b: x.lab[2],
+            weight: x.weight,
+            create_time: now,
+        })
+        .collect();
+    connection.transaction(|conn| {
+        diesel::delete(photo_colors::table.filter(photo_colors::hash.eq(hash))).execute(conn)?;
+        diesel::insert_into(photo_colors::table)
+            .values(&items)
+            .execute(conn)?;
+        Ok(())
+    })
+}
+
+/// 查询照片主色【按占比从大到小】
+pub fn get_colors(connection: &mut SqliteConnection, hash: &str) -> Result<Vec<DominantColor>> {
+    let records = photo_colors::table
+        .filter(photo_colors::hash.eq(hash))
+        .order(photo_colors::weight.desc())
+        .select(PhotoColorRecord::as_select())
+        .load(connection)?;
+    Ok(records
+        .into_iter()
+        .map(|x| DominantColor::new([x.l, x.a, x.b], x.weight))
+        .collect())
+}
+
+/// 还没有提取主色的照片 Hash
+pub fn get_hashes_without_colors(connection: &mut SqliteConnection) -> Result<Vec<String>> {
+    let hashes = photo_table::table
+        .filter(photo_table::is_delete.eq(false))
+        .filter(diesel::dsl::not(diesel::dsl::exists(
+            photo_colors::table.filter(photo_colors::hash.eq(photo_table::hash)),
+        )))
+        .select(photo_table::hash)
+        .distinct()
+        .load(connection)?;
+    Ok(hashes)
+}
@@ -0,0 +1,42 @@
+use crate::models::photo_histogram::PhotoHistogramRecord;
+use crate::storage::schema::photo_histograms;
+use crate::structs::histogram::HistogramSummary;
+use crate::utils::json_util::JsonUtil;
+use crate::utils::time_util::TimeUtils;
+use anyhow::Result;
+use diesel::prelude::*;
+
+/// 保存直方图【已存在时覆盖】
+pub fn save_histogram(
+    connection: &mut SqliteConnection,
+    hash: &str,
+    summary: &HistogramSummary,
+) -> Result<()> {
+    let record = PhotoHistogramRecord {
+        hash: hash.to_string(),
+        histogram: JsonUtil::stringify(summary)?,
+        highlight_clipping: summary.highlight_clipping,
+        shadow_clipping: summary.shadow_clipping,
+        create_time: TimeUtils::current_timestamp(),
+    };
+    diesel::replace_into(photo_histograms::table)
+        .values(record)
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 查询直方图
+pub fn get_histogram(
+    connection: &mut SqliteConnection,
+    hash: &str,
+) -> Result<Option<HistogramSummary>> {
+    let record = photo_histograms::table
+        .filter(photo_histograms::hash.eq(hash))
+        .select(PhotoHistogramRecord::as_select())
+        .first(connection)
+        .optional()?;
+    match record {
+        Some(record) => Ok(Some(JsonUtil::from_json(&record.histogram)?)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,157 @@
+use crate::constant::SQLITE_MAX_VARIABLES;
+use crate::storage::schema::{
+    albums, event_photos, folder_covers, import_batch_photos, photo_activity, photo_albums,
+    photo_blurhashes, photo_colors, photo_histograms, photo_sequence_members, photo_table,
+    photo_tags, place_photos,
+};
+use anyhow::Result;
+use diesel::prelude::*;
+use std::collections::HashSet;
+
+/// 按关联表重新统计照片数量【删除照片后调用】
+const RECOUNT_SQL: [&str; 4] = [
+    "UPDATE events SET photo_count = \
+     (SELECT COUNT(*) FROM event_photos WHERE event_photos.event_id = events.id)",
+    "UPDATE places SET photo_count = \
+     (SELECT COUNT(*) FROM place_photos WHERE place_photos.place_id = places.id)",
+    "UPDATE photo_sequences SET photo_count = \
+     (SELECT COUNT(*) FROM photo_sequence_members m WHERE m.sequence_id = photo_sequences.id)",
+    "UPDATE import_batches SET photo_count = \
+     (SELECT COUNT(*) FROM import_batch_photos b WHERE b.batch_id = import_batches.id)",
+];
+
+/// 彻底删除照片记录及所有关联数据，返回删除的照片数量
+///
+/// 堆叠需要调用方先解除，Hash 关联的数据见 `delete_unused_hash_data`
+/// 【需要在事务中调用；照片较多时分批删除，避免超出 SQLite 参数数量限制】
+pub fn purge_photos(conn: &mut SqliteConnection, photo_ids: &[i32]) -> Result<usize> {
+    if photo_ids.is_empty() {
+        return Ok(0);
+    }
+    let mut rows = 0;
+    for chunk in photo_ids.chunks(SQLITE_MAX_VARIABLES) {
+        rows += purge_chunk(conn, chunk)?;
+    }
+    for sql in RECOUNT_SQL {
+        diesel::sql_query(sql).execute(conn)?;
+    }
+    Ok(rows)
+}
+
+/// 删除一批照片记录及关联数据【数量不能超过 `SQLITE_MAX_VARIABLES`】
+fn purge_chunk(conn: &mut SqliteConnection, photo_ids: &[i32]) -> Result<usize> {
+    diesel::delete(photo_tags::table.filter(photo_tags::photo_id.eq_any(photo_ids)))
+        .execute(conn)?;
+    diesel::delete(photo_albums::table.filter(photo_albums::photo_id.eq_any(photo_ids)))
+        .execute(conn)?;
+    diesel::delete(event_photos::table.filter(event_photos::photo_id.eq_any(photo_ids)))
+        .execute(conn)?;
+    diesel::delete(place_photos::table.filter(place_photos::photo_id.eq_any(photo_ids)))
+        .execute(conn)?;
+    diesel::delete(
+        photo_sequence_members::table.filter(photo_sequence_members::photo_id.eq_any(photo_ids)),
+    )
+    .execute(conn)?;
+    diesel::delete(
+        import_batch_photos::table.filter(import_batch_photos::photo_id.eq_any(photo_ids)),
+    )
+    .execute(conn)?;
+    diesel::delete(photo_activity::table.filter(photo_activity::photo_id.eq_any(photo_ids)))
+        .execute(conn)?;
+    diesel::delete(folder_covers::table.filter(folder_covers::photo_id.eq_any(photo_ids)))
+        .execute(conn)?;
+    diesel::update(albums::table.filter(albums::cover_photo_id.eq_any(photo_ids)))
+        .set(albums::cover_photo_id.eq(None::<i32>))
+        .execute(conn)?;
+    // 事件必须有封面，改用事件中剩余的照片【事件为空时保留，重新分段时清理】
+    let ids: Vec<String> = photo_ids.iter().map(|x| x.to_string()).collect();
+    diesel::sql_query(format!(
+        "UPDATE events SET cover_photo_id = COALESCE(\
+         (SELECT photo_id FROM event_photos WHERE event_photos.event_id = events.id LIMIT 1), \
+         cover_photo_id) WHERE cover_photo_id IN ({})",
+        ids.join(",")
+    ))
+    .execute(conn)?;
+    let rows = diesel::delete(photo_table::table.filter(photo_table::id.eq_any(photo_ids)))
+        .execute(conn)?;
+    Ok(rows)
+}
+
+/// 删除已没有照片使用的 Hash 关联数据（主色、直方图、BlurHash），返回不再使用的 Hash
+pub fn delete_unused_hash_data(
+    conn: &mut SqliteConnection,
+    hashes: &[String],
+) -> Result<Vec<String>> {
+    let mut in_use: HashSet<String> = HashSet::new();
+    for chunk in hashes.chunks(SQLITE_MAX_VARIABLES) {
+        in_use.extend(
+            photo_table::table
+                .filter(photo_table::hash.eq_any(chunk))
+                .select(photo_table::hash)
+                .load::<String>(conn)?,
+        );
+    }
+    let unused: Vec<String> = hashes
+        .iter()
+        .filter(|x| !in_use.contains(*x))
+        .cloned()
+        .collect();
+    for chunk in unused.chunks(SQLITE_MAX_VARIABLES) {
+        diesel::delete(photo_colors::table.filter(photo_colors::hash.eq_any(chunk)))
+            .execute(conn)?;
+        diesel::delete(photo_histograms::table.filter(photo_histograms::hash.eq_any(chunk)))
+            .execute(conn)?;
+        diesel::delete(photo_blurhashes::table.filter(photo_blurhashes::hash.eq_any(chunk)))
+            .execute(conn)?;
+    }
+    Ok(unused)
+}
+
+/// 删除没有对应照片记录的 Hash 关联数据，返回删除的行数【清理缓存时调用，回收站中的照片仍保留】
+pub fn delete_orphan_hash_data(conn: &mut SqliteConnection) -> Result<usize> {
+    let mut rows = 0;
+    for table in ["photo_colors", "photo_histograms", "photo_blurhashes"] {
+        rows += diesel::sql_query(format!(
+            "DELETE FROM {} WHERE hash NOT IN (SELECT hash FROM photo_table)",
+            table
+        ))
+        .execute(conn)?;
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage;
+    use crate::storage::connection::test_connection;
+    use crate::structs::dominant_color::DominantColor;
+
+    #[test]
+    fn test_delete_orphan_hash_data() {
+        let mut conn = test_connection();
+        diesel::sql_query(
+            "INSERT INTO photo_table (img_path, img_name, hash, width, height, aspect_ratio, \
+             file_size, format, is_delete, create_time, update_time) \
+             VALUES ('/photos', 'a.jpg', 'a', 1, 1, 1.0, 1, 'image/jpeg', 1, 0, 0)",
+        )
+        .execute(&mut conn)
+        .unwrap();
+        let colors = [DominantColor::new([50.0, 0.0, 0.0], 1.0)];
+        for hash in ["a", "gone"] {
+            storage::photo_color::save_colors(&mut conn, hash, &colors).unwrap();
+            storage::photo_blurhash::save_blurhash(&mut conn, hash, "LEHV6nWB2yk8").unwrap();
+        }
+        assert_eq!(delete_orphan_hash_data(&mut conn).unwrap(), 2);
+        // 回收站中的照片的数据保留
+        assert_eq!(
+            storage::photo_color::get_colors(&mut conn, "a")
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(storage::photo_color::get_colors(&mut conn, "gone")
+            .unwrap()
+            .is_empty());
+    }
+}
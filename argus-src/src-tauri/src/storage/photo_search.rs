@@ -0,0 +1,198 @@
+use crate::constant::{COLOR_SEARCH_DEFAULT_TOLERANCE, PHOTO_SEARCH_DEFAULT_LIMIT};
+use crate::models::photo::Photo;
+use crate::storage::schema;
+use crate::storage::schema::photo_stacks;
+use crate::storage::schema::photo_table::dsl::*;
+use crate::structs::photo_list::{PhotoCursor, PhotoListSort};
+use crate::structs::photo_search::{PhotoSearchParams, SearchTerms};
+use crate::utils::color_util;
+use crate::utils::exif_utils::format;
+use anyhow::{anyhow, Result};
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Bool, Float, Integer, Text};
+use diesel::sqlite::Sqlite;
+
+/// 拍摄时间排序键【没有拍摄时间时使用导入时间，与索引 `idx_photo_table_captured_at` 一致】
+const CAPTURED_AT_SQL: &str = "ifnull(date_time_original, create_time)";
+
+/// 搜索照片
+///
+/// 搜索文本通过全文索引 `photo_search` 匹配，其余条件直接过滤 `photo_table`，
+/// 结果按拍摄时间倒序排列。
+pub fn search_photos(
+    connection: &mut SqliteConnection,
+    params: &PhotoSearchParams,
+) -> Result<Vec<Photo>> {
+    let results = filter_photos(params)?
+        .order((date_time_original.desc(), id.desc()))
+        .limit(params.limit.unwrap_or(PHOTO_SEARCH_DEFAULT_LIMIT))
+        .offset(params.offset.unwrap_or(0))
+        .load::<Photo>(connection)?;
+    Ok(results)
+}
+
+/// 按游标分页列出照片
+///
+/// 使用拍摄时间和 ID 作为游标（keyset 分页），翻页速度与页数无关
+/// - params 筛选条件【忽略其中的 limit、offset】
+/// - sort 排序方式
+/// - after 上一页最后一张照片的游标【为空时从第一页开始】
+/// - limit 返回数量
+pub fn list_photos(
+    connection: &mut SqliteConnection,
+    params: &PhotoSearchParams,
+    sort: PhotoListSort,
+    after: Option<&PhotoCursor>,
+    limit: i64,
+) -> Result<Vec<Photo>> {
+    let mut query = filter_photos(params)?;
+    if let Some(cursor) = after {
+        let operator = match sort {
+            PhotoListSort::CapturedDesc => "<",
+            PhotoListSort::CapturedAsc => ">",
+        };
+        query = query.filter(
+            sql::<Bool>(&format!("({}, id) {} (", CAPTURED_AT_SQL, operator))
+                .bind::<BigInt, _>(cursor.captured_at)
+                .sql(", ")
+                .bind::<Integer, _>(cursor.id)
+                .sql(")"),
+        );
+    }
+    let query = match sort {
+        PhotoListSort::CapturedDesc => {
+            query.order((sql::<BigInt>(CAPTURED_AT_SQL).desc(), id.desc()))
+        }
+        PhotoListSort::CapturedAsc => query.order((sql::<BigInt>(CAPTURED_AT_SQL).asc(), id.asc())),
+    };
+    let results = query.limit(limit).load::<Photo>(connection)?;
+    Ok(results)
+}
+
+/// 按搜索条件过滤照片【不含排序和分页】
+fn filter_photos(
+    params: &PhotoSearchParams,
+) -> Result<schema::photo_table::BoxedQuery<'static, Sqlite>> {
+    let mut query = photo_table.filter(is_delete.eq(false)).into_boxed();
+
+    let terms = SearchTerms::parse(params.text.as_deref().unwrap_or_default());
+    if let Some(expr) = terms.match_expr {
+        query = query.filter(
+            sql::<Bool>("id IN (SELECT rowid FROM photo_search WHERE photo_search MATCH ")
+                .bind::<Text, _>(expr)
+                .sql(")"),
+        );
+    }
+    // 短词无法使用 trigram 索引，在索引表中逐行匹配
+    for term in terms.short_terms {
+        query = query.filter(
+            sql::<Bool>(
+                "id IN (SELECT rowid FROM photo_search WHERE \
+                 (ifnull(img_name, '') || ' ' || ifnull(caption, '') || ' ' || ifnull(notes, '') \
+                 || ' ' || ifnull(keywords, '') || ' ' || ifnull(camera, '') \
+                 || ' ' || ifnull(location, '')) LIKE ",
+            )
+            .bind::<Text, _>(format!("%{}%", escape_like(&term)))
+            .sql(" ESCAPE '\\')"),
+        );
+    }
+
+    if let Some(color) = params.color.as_deref().filter(|x| !x.trim().is_empty()) {
+        let rgb = color_util::parse_hex(color).ok_or_else(|| anyhow!("颜色 {} 格式错误", color))?;
+        let lab = color_util::rgb_to_lab(rgb);
+        let tolerance = params
+            .color_tolerance
+            .filter(|x| x.is_finite() && *x > 0.0)
+            .unwrap_or(COLOR_SEARCH_DEFAULT_TOLERANCE);
+        query = query.filter(
+            sql::<Bool>("hash IN (SELECT c.hash FROM photo_colors c, (SELECT ")
+                .bind::<Float, _>(lab[0])
+                .sql(" AS l, ")
+                .bind::<Float, _>(lab[1])
+                .sql(" AS a, ")
+                .bind::<Float, _>(lab[2])
+                .sql(
+                    " AS b) t WHERE (c.l - t.l) * (c.l - t.l) + (c.a - t.a) * (c.a - t.a) \
+                     + (c.b - t.b) * (c.b - t.b) <= ",
+                )
+                .bind::<Float, _>(tolerance * tolerance)
+                .sql(")"),
+        );
+    }
+
+    if params.collapse_stacks.unwrap_or(true) {
+        query = query.filter(
+            stack_id
+                .is_null()
+                .or(id.nullable().eq_any(
+                    photo_stacks::table.select(photo_stacks::cover_photo_id.nullable()),
+                )),
+        );
+    }
+    if let Some(value) = params.min_rating {
+        query = query.filter(rating.ge(value));
+    }
+    if let Some(value) = parse_filter(&params.min_shutter, format::parse_shutter_speed, "快门")? {
+        query = query.filter(exposure_time.ge(value));
+    }
+    if let Some(value) = parse_filter(&params.max_shutter, format::parse_shutter_speed, "快门")? {
+        query = query.filter(exposure_time.le(value));
+    }
+    if let Some(value) = parse_filter(&params.min_aperture, format::parse_aperture, "光圈")? {
+        query = query.filter(f_number.ge(value));
+    }
+    if let Some(value) = parse_filter(&params.max_aperture, format::parse_aperture, "光圈")? {
+        query = query.filter(f_number.le(value));
+    }
+    if let Some(value) = params.min_focal_length_35mm {
+        query = query.filter(focal_length_35mm.ge(value));
+    }
+    if let Some(value) = params.max_focal_length_35mm {
+        query = query.filter(focal_length_35mm.le(value));
+    }
+    if let Some(fired) = params.flash_fired {
+        // 闪光灯数值的第 0 位为是否闪光
+        query = query.filter(sql::<Bool>(if fired {
+            "(flash_code & 1) = 1"
+        } else {
+            "(flash_code & 1) = 0"
+        }));
+    }
+    if let Some(value) = params.animated {
+        query = query.filter(is_animated.eq(value));
+    }
+    if let Some(value) = params.start_time {
+        query = query.filter(date_time_original.ge(value));
+    }
+    if let Some(value) = params.end_time {
+        query = query.filter(date_time_original.le(value));
+    }
+
+    Ok(query)
+}
+
+/// 解析快门、光圈等筛选条件【为空时忽略，格式错误时返回错误】
+/// - value 筛选条件
+/// - parse 解析函数
+/// - name 条件名称【用于错误信息】
+fn parse_filter(
+    value: &Option<String>,
+    parse: fn(&str) -> Option<f64>,
+    name: &str,
+) -> Result<Option<f32>> {
+    match value.as_deref().filter(|x| !x.trim().is_empty()) {
+        Some(x) => parse(x)
+            .map(|x| Some(x as f32))
+            .ok_or_else(|| anyhow!("{} {} 格式错误", name, x)),
+        None => Ok(None),
+    }
+}
+
+/// 转义 LIKE 中的通配符
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
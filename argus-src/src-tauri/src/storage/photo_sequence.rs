@@ -0,0 +1,139 @@
+use crate::constant::SQLITE_MAX_VARIABLES;
+use crate::models::photo::Photo;
+use crate::models::photo_sequence::{
+    NewPhotoSequence, NewPhotoSequenceMember, PhotoSequence, SequenceCandidate,
+};
+use crate::storage::photo_table::path_prefix_pattern;
+use crate::storage::schema::{photo_sequence_members, photo_sequences, photo_table};
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 获取连拍序列检测的候选照片【有拍摄时间且不在任何序列中】
+/// - path_prefix 只包含此文件夹下的照片【为空表示全部】
+pub fn get_sequence_candidates(
+    connection: &mut SqliteConnection,
+    path_prefix: Option<&str>,
+) -> Result<Vec<SequenceCandidate>> {
+    let members = photo_sequence_members::table.select(photo_sequence_members::photo_id);
+    let mut query = photo_table::table
+        .filter(photo_table::is_delete.eq(false))
+        .filter(photo_table::date_time_original.is_not_null())
+        .filter(photo_table::id.ne_all(members))
+        .select(SequenceCandidate::as_select())
+        .into_boxed();
+    if let Some(prefix) = path_prefix {
+        query = query.filter(
+            photo_table::img_path
+                .like(path_prefix_pattern(Path::new(prefix)))
+                .escape('\\'),
+        );
+    }
+    let rows: Vec<SequenceCandidate> = query
+        .order((photo_table::date_time_original.asc(), photo_table::id.asc()))
+        .load(connection)?;
+    // 前缀匹配会包含同名前缀的其他文件夹，按路径组件过滤
+    Ok(rows
+        .into_iter()
+        .filter(|x| path_prefix.is_none_or(|prefix| Path::new(&x.img_path).starts_with(prefix)))
+        .collect())
+}
+
+/// 新增连拍序列
+/// - photo_ids 序列包含的照片
+pub fn insert_sequence(
+    connection: &mut SqliteConnection,
+    item: NewPhotoSequence,
+    photo_ids: &[i32],
+) -> Result<PhotoSequence> {
+    let now = TimeUtils::current_timestamp();
+    connection.transaction(|conn| {
+        let sequence = diesel::insert_into(photo_sequences::table)
+            .values(item)
+            .returning(PhotoSequence::as_returning())
+            .get_result(conn)?;
+        let members: Vec<NewPhotoSequenceMember> = photo_ids
+            .iter()
+            .map(|photo_id| NewPhotoSequenceMember {
+                sequence_id: sequence.id,
+                photo_id: *photo_id,
+                create_time: now,
+            })
+            .collect();
+        diesel::insert_into(photo_sequence_members::table)
+            .values(&members)
+            .execute(conn)?;
+        Ok(sequence)
+    })
+}
+
+/// 获取连拍序列【按拍摄时间从新到旧】
+/// - kind 只包含此类型的序列【为空表示全部】
+pub fn list_sequences(
+    connection: &mut SqliteConnection,
+    kind: Option<i32>,
+) -> Result<Vec<PhotoSequence>> {
+    let mut query = photo_sequences::table
+        .select(PhotoSequence::as_select())
+        .into_boxed();
+    if let Some(kind) = kind {
+        query = query.filter(photo_sequences::kind.eq(kind));
+    }
+    let rows = query
+        .order((
+            photo_sequences::start_time.desc(),
+            photo_sequences::id.desc(),
+        ))
+        .load(connection)?;
+    Ok(rows)
+}
+
+/// 获取连拍序列
+pub fn get_sequence(connection: &mut SqliteConnection, sequence_id: i32) -> Result<PhotoSequence> {
+    photo_sequences::table
+        .find(sequence_id)
+        .select(PhotoSequence::as_select())
+        .first(connection)
+        .optional()?
+        .ok_or_else(|| anyhow!("连拍序列 {} 不存在!", sequence_id))
+}
+
+/// 批量获取序列中的照片 ID【按拍摄时间排序】
+pub fn get_sequences_photo_ids(
+    connection: &mut SqliteConnection,
+    sequence_ids: &[i32],
+) -> Result<HashMap<i32, Vec<i32>>> {
+    let mut result: HashMap<i32, Vec<i32>> = HashMap::new();
+    // 同一序列的照片在同一批中查询，分批不影响序列内的顺序
+    for chunk in sequence_ids.chunks(SQLITE_MAX_VARIABLES - 1) {
+        let pairs = photo_sequence_members::table
+            .inner_join(photo_table::table.on(photo_table::id.eq(photo_sequence_members::photo_id)))
+            .filter(photo_sequence_members::sequence_id.eq_any(chunk))
+            .filter(photo_table::is_delete.eq(false))
+            .order((photo_table::date_time_original.asc(), photo_table::id.asc()))
+            .select((photo_sequence_members::sequence_id, photo_table::id))
+            .load::<(i32, i32)>(connection)?;
+        for (sequence_id, photo_id) in pairs {
+            result.entry(sequence_id).or_default().push(photo_id);
+        }
+    }
+    Ok(result)
+}
+
+/// 获取序列中的照片【按拍摄时间排序】
+pub fn get_sequence_photos(
+    connection: &mut SqliteConnection,
+    sequence_id: i32,
+) -> Result<Vec<Photo>> {
+    let photo_ids = photo_sequence_members::table
+        .filter(photo_sequence_members::sequence_id.eq(sequence_id))
+        .select(photo_sequence_members::photo_id);
+    let photos = photo_table::table
+        .filter(photo_table::is_delete.eq(false))
+        .filter(photo_table::id.eq_any(photo_ids))
+        .order((photo_table::date_time_original.asc(), photo_table::id.asc()))
+        .load::<Photo>(connection)?;
+    Ok(photos)
+}
@@ -0,0 +1,136 @@
+use crate::constant::SQLITE_MAX_VARIABLES;
+use crate::models::photo::Photo;
+use crate::models::photo_stack::{NewPhotoStack, PhotoStack};
+use crate::storage::photo_table::path_prefix_pattern;
+use crate::storage::schema::{photo_stacks, photo_table};
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+use std::path::Path;
+
+/// 自动堆叠的候选照片（ID、文件夹、文件名、拍摄时间、所属堆叠）
+pub type StackCandidate = (i32, String, String, Option<i64>, Option<i32>);
+
+/// 新增版本堆叠
+pub fn insert_stack(
+    connection: &mut SqliteConnection,
+    original_photo_id: i32,
+    cover_photo_id: i32,
+) -> Result<PhotoStack> {
+    let timestamp = TimeUtils::current_timestamp();
+    let item = NewPhotoStack {
+        original_photo_id,
+        cover_photo_id,
+        create_time: timestamp,
+        update_time: timestamp,
+    };
+    let stack = diesel::insert_into(photo_stacks::table)
+        .values(item)
+        .returning(PhotoStack::as_returning())
+        .get_result(connection)?;
+    Ok(stack)
+}
+
+/// 获取版本堆叠
+pub fn get_stack(connection: &mut SqliteConnection, stack_id: i32) -> Result<PhotoStack> {
+    photo_stacks::table
+        .find(stack_id)
+        .select(PhotoStack::as_select())
+        .first(connection)
+        .optional()?
+        .ok_or_else(|| anyhow!("版本堆叠 {} 不存在!", stack_id))
+}
+
+/// 修改堆叠的原图和显示版本
+pub fn update_stack(
+    connection: &mut SqliteConnection,
+    stack_id: i32,
+    original_photo_id: i32,
+    cover_photo_id: i32,
+) -> Result<()> {
+    diesel::update(photo_stacks::table.find(stack_id))
+        .set((
+            photo_stacks::original_photo_id.eq(original_photo_id),
+            photo_stacks::cover_photo_id.eq(cover_photo_id),
+            photo_stacks::update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 删除版本堆叠，并把其中的照片恢复为独立照片
+pub fn delete_stack(connection: &mut SqliteConnection, stack_id: i32) -> Result<()> {
+    diesel::update(photo_table::table.filter(photo_table::stack_id.eq(stack_id)))
+        .set(photo_table::stack_id.eq(None::<i32>))
+        .execute(connection)?;
+    diesel::delete(photo_stacks::table.find(stack_id)).execute(connection)?;
+    Ok(())
+}
+
+/// 设置照片所属的堆叠
+/// - stack_id 堆叠 ID【为空表示移出堆叠】
+pub fn set_photos_stack(
+    connection: &mut SqliteConnection,
+    photo_ids: &[i32],
+    stack_id: Option<i32>,
+) -> Result<()> {
+    for chunk in photo_ids.chunks(SQLITE_MAX_VARIABLES - 1) {
+        diesel::update(photo_table::table.filter(photo_table::id.eq_any(chunk)))
+            .set(photo_table::stack_id.eq(stack_id))
+            .execute(connection)?;
+    }
+    Ok(())
+}
+
+/// 获取堆叠中的照片 ID【按 ID 排序】
+pub fn get_stack_photo_ids(connection: &mut SqliteConnection, stack_id: i32) -> Result<Vec<i32>> {
+    let ids = photo_table::table
+        .filter(photo_table::stack_id.eq(stack_id))
+        .select(photo_table::id)
+        .order(photo_table::id.asc())
+        .load::<i32>(connection)?;
+    Ok(ids)
+}
+
+/// 获取堆叠中的照片【按 ID 排序】
+pub fn get_stack_photos(connection: &mut SqliteConnection, stack_id: i32) -> Result<Vec<Photo>> {
+    let photos = photo_table::table
+        .filter(photo_table::stack_id.eq(stack_id))
+        .filter(photo_table::is_delete.eq(false))
+        .order(photo_table::id.asc())
+        .load::<Photo>(connection)?;
+    Ok(photos)
+}
+
+/// 获取自动堆叠的候选照片
+/// - path_prefix 只包含此文件夹下的照片【为空表示全部】
+pub fn get_stack_candidates(
+    connection: &mut SqliteConnection,
+    path_prefix: Option<&str>,
+) -> Result<Vec<StackCandidate>> {
+    let mut query = photo_table::table
+        .filter(photo_table::is_delete.eq(false))
+        .select((
+            photo_table::id,
+            photo_table::img_path,
+            photo_table::img_name,
+            photo_table::date_time_original,
+            photo_table::stack_id,
+        ))
+        .into_boxed();
+    if let Some(prefix) = path_prefix {
+        query = query.filter(
+            photo_table::img_path
+                .like(path_prefix_pattern(Path::new(prefix)))
+                .escape('\\'),
+        );
+    }
+    let rows = query
+        .order(photo_table::id.asc())
+        .load::<StackCandidate>(connection)?;
+    // 前缀匹配会包含同名前缀的其他文件夹，按路径组件过滤
+    Ok(rows
+        .into_iter()
+        .filter(|x| path_prefix.is_none_or(|prefix| Path::new(&x.1).starts_with(prefix)))
+        .collect())
+}
@@ -103,6 +103,41 @@ pub fn delete_img_path(connection: &mut SqliteConnection, id: i32) -> Result<()>
     }
 }
 
+/// 更新图像存储路径所在卷的信息
+/// - root 存储路径【卷重新挂载到新位置时会随之改变】
+pub fn update_storage_volume(
+    connection: &mut SqliteConnection,
+    id: i32,
+    root: &str,
+    volume_id: Option<&str>,
+    volume_label: Option<&str>,
+    relative_path: Option<&str>,
+    available: bool,
+) -> Result<()> {
+    use crate::storage::schema::photo_storages;
+    let timestamp = TimeUtils::current_timestamp();
+    let target = photo_storages::table.filter(photo_storages::id.eq(id));
+    let rows = diesel::update(target)
+        .set((
+            photo_storages::img_paths.eq(root),
+            photo_storages::volume_id.eq(volume_id),
+            photo_storages::volume_label.eq(volume_label),
+            photo_storages::relative_path.eq(relative_path),
+            photo_storages::is_available.eq(available),
+            photo_storages::update_time.eq(timestamp),
+        ))
+        .execute(connection)?;
+    if rows == 0 {
+        return Err(anyhow!("图像存储路径 {} 不存在!", id));
+    }
+    if available {
+        diesel::update(target)
+            .set(photo_storages::last_seen_time.eq(timestamp))
+            .execute(connection)?;
+    }
+    Ok(())
+}
+
 #[test]
 /// 在内存中使用数据库测试【未测试】
 fn test_with_memory_db() {
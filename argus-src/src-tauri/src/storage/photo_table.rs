@@ -1,135 +1,244 @@
-use crate::constant::IMAGE_COMPRESSION_STORAGE_FORMAT;
-use crate::models::photo::{NewExifPhoto, NewPhoto, Photo};
+use crate::constant::{IMAGE_COMPRESSION_STORAGE_FORMAT, SQLITE_MAX_VARIABLES};
+use crate::models::photo::{NewPhoto, Photo, PhotoExifChangeset, PhotoMetadataChangeset};
+use crate::storage::photo_activity;
 use crate::storage::schema::photo_table::dsl::photo_table;
 use crate::storage::schema::photo_table::{hash, is_delete};
-use crate::utils::exif_utils::tag::{ExifInfo, ImgExif};
+use crate::structs::photo_activity::ActivityKind;
+use crate::utils::exif_utils::gps_util::GpsCoordinate;
+use crate::utils::exif_utils::tag::ImgExif;
+use crate::utils::file_util;
 use crate::utils::img_util::ImageOperate;
+use crate::utils::json_util::JsonUtil;
 use crate::utils::time_util::TimeUtils;
 use anyhow::{anyhow, Result};
 use diesel::associations::HasTable;
 use diesel::prelude::*;
 use diesel::{RunQueryDsl, SqliteConnection, TextExpressionMethods};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// `NewPhoto` 的字段数量【批量插入时每张照片占用的参数数量】
+const NEW_PHOTO_COLUMNS: usize = 15;
 // 获取图片 hash、基础信息（长、宽、比例）、exif 信息
 
 /// 把照片存储到数据库
 pub fn insert_photo(connection: &mut SqliteConnection, img_info: ImageOperate) -> Result<()> {
-    let photos = search_photo_by_hash(connection, img_info.hash.clone()).expect("查询出错");
-    log::debug!("找到 {} 照片", photos.len());
+    bulk_upsert(connection, vec![to_scanned_photo(img_info)])?;
+    Ok(())
+}
+
+/// 把扫描结果转换为待插入的照片和读取的 EXIF
+pub fn to_scanned_photo(img_info: ImageOperate) -> (NewPhoto, Option<PhotoExifChangeset>) {
+    let exif = img_info
+        .exif
+        .as_ref()
+        .map(|x| to_exif_changeset(x, (img_info.width, img_info.height)));
+    (to_new_photo(img_info), exif)
+}
 
+/// 把图像信息转换为待插入的照片
+pub fn to_new_photo(img_info: ImageOperate) -> NewPhoto {
     let op = if let Some(x) = img_info.format {
         x.to_mime_type()
     } else {
         ""
     };
     let timestamp = TimeUtils::current_timestamp();
-    let np = NewPhoto {
+    NewPhoto {
         img_path: img_info.img_path,
         img_name: img_info.img_name,
+        img_path_raw: Some(file_util::path_to_bytes(&img_info.full_path)),
         hash: img_info.hash,
         width: img_info.width,
         height: img_info.height,
         aspect_ratio: img_info.aspect_ratio,
         file_size: img_info.file_size,
         format: op.to_string(),
+        validation_level: img_info.validation_level.code(),
+        is_animated: img_info.animation.is_some(),
+        frame_count: img_info.animation.map(|x| x.frame_count as i32),
+        animation_duration: img_info.animation.map(|x| x.duration_ms as i32),
         create_time: timestamp,
         update_time: timestamp,
-    };
-    return if photos.is_empty() {
-        let res = diesel::insert_into(photo_table::table())
-            .values(np)
-            .returning(Photo::as_returning())
-            .get_result(connection);
-        if res.is_ok() {
-            Ok(())
-        } else {
-            Err(anyhow!(res.unwrap_err()))
+    }
+}
+
+/// 批量写入照片，返回新插入的照片
+///
+/// 在同一个事务中按 SQLite 参数数量上限分批插入。与 `insert_photo` 一致，
+/// Hash 已存在的照片不重复插入，只提升校验级别、补全还没有读取的 EXIF；
+/// 已删除（回收站、私密空间）的照片不恢复也不重新导入；同一批中 Hash 重复时只保留第一张
+pub fn bulk_upsert(
+    connection: &mut SqliteConnection,
+    photos: Vec<(NewPhoto, Option<PhotoExifChangeset>)>,
+) -> Result<Vec<Photo>> {
+    connection.transaction(|conn| {
+        let hashes: Vec<String> = photos.iter().map(|x| x.0.hash.clone()).collect();
+        let mut existing = HashMap::new();
+        for chunk in hashes.chunks(SQLITE_MAX_VARIABLES) {
+            existing.extend(
+                search_photos_by_hashes_with_deleted(conn, chunk)?
+                    .into_iter()
+                    .map(|x| (x.hash.clone(), x)),
+            );
         }
-    } else {
-        Ok(())
-    };
+
+        let mut pending = Vec::new();
+        let mut exifs = HashMap::new();
+        for (photo, exif) in photos {
+            match existing.get(&photo.hash) {
+                Some(x) if x.is_delete => {
+                    log::debug!("照片已删除，不重新导入: {}", photo.img_name);
+                }
+                Some(x) => {
+                    raise_validation_level(conn, &photo.hash, photo.validation_level)?;
+                    if let (Some(exif), None) = (exif, x.exif_read_time) {
+                        fill_photo_exif(conn, x, exif)?;
+                    }
+                }
+                None if exifs.contains_key(&photo.hash) => {}
+                None => {
+                    exifs.insert(photo.hash.clone(), exif);
+                    pending.push(photo);
+                }
+            }
+        }
+
+        // SQLite 的多行 INSERT 不支持 RETURNING，插入后按 Hash 查询
+        for chunk in pending.chunks(SQLITE_MAX_VARIABLES / NEW_PHOTO_COLUMNS) {
+            diesel::insert_or_ignore_into(photo_table::table())
+                .values(chunk)
+                .execute(conn)?;
+        }
+        let pending: Vec<String> = pending.into_iter().map(|x| x.hash).collect();
+        let mut inserted = Vec::with_capacity(pending.len());
+        for chunk in pending.chunks(SQLITE_MAX_VARIABLES - 1) {
+            let photos = search_photos_by_hashes(conn, chunk)?;
+            for photo in &photos {
+                if let Some(exif) = exifs.remove(&photo.hash).flatten() {
+                    fill_photo_exif(conn, photo, exif)?;
+                }
+                record_imported(conn, photo);
+            }
+            // 重新查询写入 EXIF 后的照片【自动整理规则等按 EXIF 判断】
+            inserted.extend(search_photos_by_hashes(conn, chunk)?);
+        }
+        Ok(inserted)
+    })
 }
 
-/// 把照片存储及信息到数据库
-/// - 数据库连结
-/// - 图像信息
-/// - exif 信息1
-pub fn insert_photo_and_info(
+/// 提升照片的校验级别【只升不降，记录是否曾经完整解码成功】
+pub fn raise_validation_level(
     connection: &mut SqliteConnection,
-    img_info: ImageOperate,
-    img_exif: ImgExif,
+    hash_str: &str,
+    level: i32,
 ) -> Result<()> {
-    let photos = search_photo_by_hash(connection, img_info.hash.clone()).expect("查询出错");
-    log::debug!("找到 {} 照片", photos.len());
+    use crate::storage::schema::photo_table::{update_time, validation_level};
+    diesel::update(
+        photo_table
+            .filter(hash.eq(hash_str))
+            .filter(validation_level.lt(level)),
+    )
+    .set((
+        validation_level.eq(level),
+        update_time.eq(TimeUtils::current_timestamp()),
+    ))
+    .execute(connection)?;
+    Ok(())
+}
 
-    let op = if let Some(x) = img_info.format {
-        x.to_mime_type()
-    } else {
-        ""
-    };
-    let timestamp = TimeUtils::current_timestamp();
+/// 把读取的 EXIF 转换为照片记录的修改内容
+/// - img_exif EXIF 信息
+/// - header_size 文件头中读取的长宽【EXIF 中没有长宽时使用】
+pub fn to_exif_changeset(img_exif: &ImgExif, header_size: (i32, i32)) -> PhotoExifChangeset {
+    let header_size = (header_size.0 > 0 && header_size.1 > 0).then_some(header_size);
+    PhotoExifChangeset {
+        caption: img_exif.caption.clone(),
+        offset_time: img_exif.offset_time.clone(),
+        rating: img_exif.rating.map(|x| x as i32),
+        make: img_exif.make.clone(),
+        model: img_exif.model.clone(),
+        software: img_exif.software.clone(),
+        exposure_time: img_exif.exposure_time.map(|x| x.to_f64() as f32),
+        flash: img_exif.flash.clone(),
+        f_number: img_exif.f_number.map(|x| x as f32),
+        iso: img_exif.iso.map(|x| x as i32),
+        date_time_original: img_exif.date_time_original.map(|x| x.timestamp()),
+        max_aperture_value: img_exif.max_aperture_value.map(|x| x.to_string()),
+        focal_length: img_exif.focal_length.map(|x| x as f32),
+        image_width: img_exif
+            .image_width
+            .map(|x| x as i32)
+            .or(header_size.map(|x| x.0)),
+        image_height: img_exif
+            .image_height
+            .map(|x| x as i32)
+            .or(header_size.map(|x| x.1)),
+        // 十进制坐标 JSON
+        gps_info: img_exif
+            .gps_info
+            .as_ref()
+            .and_then(|x| JsonUtil::stringify(x).ok()),
+        exposure_program: img_exif.exposure_program.clone(),
+        metering_mode: img_exif.metering_mode.clone(),
+        flash_code: img_exif.flash_code,
+        metering_mode_code: img_exif.metering_mode_code,
+        exposure_program_code: img_exif.exposure_program_code,
+        white_balance_code: img_exif.white_balance_code,
+        scene_capture_type_code: img_exif.scene_capture_type_code,
+        gps_speed: img_exif.gps_speed.map(|x| x as f32),
+        gps_img_direction: img_exif.gps_img_direction.map(|x| x as f32),
+        gps_dest_bearing: img_exif.gps_dest_bearing.map(|x| x as f32),
+        focal_length_35mm: img_exif.effective_focal_length.map(|x| x as f32),
+        artist: img_exif.artist.clone(),
+        copyright: img_exif.copyright.clone(),
+        exif_read_time: Some(TimeUtils::current_timestamp()),
+        gps_img_direction_ref: img_exif.gps_img_direction_ref.map(|x| x.code().to_string()),
+        gps_dest_bearing_ref: img_exif.gps_dest_bearing_ref.map(|x| x.code().to_string()),
+    }
+}
 
-    println!("{:?}", img_exif.make);
-
-    // gps 信息整理
-    let gps_op: Option<String> = img_exif.gps_info.map(|info| info.to_string());
-    let image_height_op = img_exif.image_height.map(|info| info as i32);
-    let image_width_op = img_exif.image_width.map(|info| info as i32);
-    let rating_op = img_exif.rating.map(|info| info as i32);
-    let exposure_time_op = img_exif.exposure_time.map(|info| info as f32);
-    let f_number_op = img_exif.f_number.map(|info| info as f32);
-    let iso_op = img_exif.iso.map(|info| info as i32);
-    let date_time_original_op = img_exif.date_time_original.map(|info| info.timestamp());
-    let focal_length_op = img_exif.focal_length.map(|info| info as f32);
-    let np = NewExifPhoto {
-        img_path: img_info.img_path,
-        img_name: img_info.img_name,
-        hash: img_info.hash,
-        width: img_info.width,
-        height: img_info.height,
-        aspect_ratio: img_info.aspect_ratio,
-        file_size: img_info.file_size,
-        format: op.to_string(),
-        notes: None,
-        is_algorithm: None,
-        algorithm_score: None,
-        create_time: timestamp,
-        update_time: timestamp,
+/// 写入照片的 EXIF 信息【只填写还没有值的字段，并记录读取时间】
+/// - photo 照片
+/// - changeset 读取的 EXIF
+pub fn fill_photo_exif(
+    connection: &mut SqliteConnection,
+    photo: &Photo,
+    changeset: PhotoExifChangeset,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::id;
+    let changeset = changeset.only_missing(photo);
+    diesel::update(photo_table.filter(id.eq(photo.id)))
+        .set(&changeset)
+        .execute(connection)?;
+    Ok(())
+}
 
-        offset_time: None,
-        rating: rating_op,
-        make: img_exif.make,
-        model: img_exif.model,
-        software: img_exif.software,
-        exposure_time: exposure_time_op,
-        flash: img_exif.flash,
-        f_number: f_number_op,
-        iso: iso_op,
-        date_time_original: date_time_original_op,
-        max_aperture_value: img_exif.max_aperture_value,
-        focal_length: focal_length_op,
-        image_width: image_width_op,
-        image_height: image_height_op,
-        gps_info: gps_op,
-        exposure_program: img_exif.exposure_program,
-        metering_mode: img_exif.metering_mode,
-        artist: img_exif.artist,
-        last_viewed_time: None,
-        is_delete: false,
-    };
-    return if photos.is_empty() {
-        let res = diesel::insert_into(photo_table::table())
-            .values(np)
-            .returning(Photo::as_returning())
-            .get_result(connection);
-        if res.is_ok() {
-            Ok(())
-        } else {
-            Err(anyhow!(res.unwrap_err()))
-        }
-    } else {
-        Ok(())
-    };
+/// 查询还没有读取 EXIF 的照片【按 ID 分页】
+/// - after_id 上一页最后一张照片的 ID
+/// - limit 数量
+pub fn search_photos_without_exif(
+    connection: &mut SqliteConnection,
+    after_id: i32,
+    limit: i64,
+) -> Result<Vec<Photo>> {
+    use crate::storage::schema::photo_table::{exif_read_time, id};
+    let results = photo_table
+        .filter(is_delete.eq(false))
+        .filter(exif_read_time.is_null())
+        .filter(id.gt(after_id))
+        .order(id.asc())
+        .limit(limit)
+        .load::<Photo>(connection)?;
+    Ok(results)
+}
 
+/// 标记照片已读取 EXIF【原图无法读取 EXIF 时也标记，避免每次补全都重复读取】
+pub fn mark_exif_read(connection: &mut SqliteConnection, photo_id: i32) -> Result<()> {
+    use crate::storage::schema::photo_table::{exif_read_time, id};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set(exif_read_time.eq(TimeUtils::current_timestamp()))
+        .execute(connection)?;
     Ok(())
 }
 
@@ -145,6 +254,605 @@ pub fn search_photo_by_hash(
     return Ok(results);
 }
 
+/// 按 ID 查询照片
+pub fn search_photos_by_ids(connection: &mut SqliteConnection, ids: &[i32]) -> Result<Vec<Photo>> {
+    use crate::storage::schema::photo_table::id;
+    let mut results = Vec::new();
+    for chunk in ids.chunks(SQLITE_MAX_VARIABLES) {
+        results.extend(
+            photo_table
+                .filter(is_delete.eq(false))
+                .filter(id.eq_any(chunk))
+                .load::<Photo>(connection)?,
+        );
+    }
+    Ok(results)
+}
+
+/// 查询所有照片的 Hash【清理缩略图缓存时判断是否仍在使用】
+pub fn search_all_hashes(connection: &mut SqliteConnection) -> Result<HashSet<String>> {
+    let results = photo_table
+        .filter(is_delete.eq(false))
+        .select(hash)
+        .load::<String>(connection)?;
+    Ok(results.into_iter().collect())
+}
+
+/// 查询远程存储中的照片【包括与本地照片内容相同、没有单独记录的照片】
+pub fn search_remote_photos(
+    connection: &mut SqliteConnection,
+    storage_id: i32,
+) -> Result<Vec<Photo>> {
+    use crate::storage::schema::photo_table::id;
+    use crate::storage::schema::remote_objects;
+    let hashes = remote_objects::table
+        .filter(remote_objects::storage_id.eq(storage_id))
+        .filter(remote_objects::hash.is_not_null())
+        .select(remote_objects::hash.assume_not_null());
+    let results = photo_table
+        .filter(is_delete.eq(false))
+        .filter(hash.eq_any(hashes))
+        .order(id.asc())
+        .load::<Photo>(connection)?;
+    Ok(results)
+}
+
+/// 查询远程对象对应的照片【包括回收站中的照片】
+pub fn search_photos_by_remote_objects(
+    connection: &mut SqliteConnection,
+    object_ids: &[i32],
+) -> Result<Vec<Photo>> {
+    use crate::storage::schema::photo_table::remote_object_id;
+    let mut results = Vec::new();
+    for chunk in object_ids.chunks(SQLITE_MAX_VARIABLES) {
+        results.extend(
+            photo_table
+                .filter(remote_object_id.eq_any(chunk))
+                .load::<Photo>(connection)?,
+        );
+    }
+    Ok(results)
+}
+
+/// 关联照片和远程对象【原图不在本地，同时标记为不可访问】
+pub fn link_remote_object(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    object_id: i32,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{id, is_available, remote_object_id};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((remote_object_id.eq(object_id), is_available.eq(false)))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 记录 RAW 照片配对的 JPEG 文件名【为空表示取消配对】
+pub fn set_paired_jpeg(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    jpeg_name: Option<&str>,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{id, paired_jpeg};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set(paired_jpeg.eq(jpeg_name))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 查询所有照片的路径和文件大小【包括回收站中的照片，文件仍占用磁盘空间】
+pub fn search_all_path_sizes(connection: &mut SqliteConnection) -> Result<Vec<(String, i64)>> {
+    use crate::storage::schema::photo_table::{file_size, img_path};
+    let results = photo_table
+        .select((img_path, file_size))
+        .load::<(String, i64)>(connection)?;
+    Ok(results)
+}
+
+/// 查询所有照片的 ID 和所在文件夹
+pub fn search_all_photo_folders(connection: &mut SqliteConnection) -> Result<Vec<(i32, String)>> {
+    use crate::storage::schema::photo_table::{id, img_path};
+    let results = photo_table
+        .filter(is_delete.eq(false))
+        .select((id, img_path))
+        .load::<(i32, String)>(connection)?;
+    Ok(results)
+}
+
+/// 查询所有照片
+pub fn search_all_photos(connection: &mut SqliteConnection) -> Result<Vec<Photo>> {
+    use crate::storage::schema::photo_table::id;
+    let results = photo_table
+        .filter(is_delete.eq(false))
+        .order(id.asc())
+        .load::<Photo>(connection)?;
+    Ok(results)
+}
+
+/// 按 Hash 批量查询照片
+pub fn search_photos_by_hashes(
+    connection: &mut SqliteConnection,
+    hashes: &[String],
+) -> Result<Vec<Photo>> {
+    let mut results = Vec::new();
+    for chunk in hashes.chunks(SQLITE_MAX_VARIABLES - 1) {
+        results.extend(
+            photo_table
+                .filter(is_delete.eq(false))
+                .filter(hash.eq_any(chunk))
+                .load::<Photo>(connection)?,
+        );
+    }
+    Ok(results)
+}
+
+/// 按 Hash 批量查询照片【包括已删除的照片】
+pub fn search_photos_by_hashes_with_deleted(
+    connection: &mut SqliteConnection,
+    hashes: &[String],
+) -> Result<Vec<Photo>> {
+    let mut results = Vec::new();
+    for chunk in hashes.chunks(SQLITE_MAX_VARIABLES) {
+        results.extend(
+            photo_table
+                .filter(hash.eq_any(chunk))
+                .load::<Photo>(connection)?,
+        );
+    }
+    Ok(results)
+}
+
+/// 更新照片元数据【changeset 中为空的字段不修改】
+pub fn update_photo_metadata(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    changeset: &PhotoMetadataChangeset,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{id, update_time};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((changeset, update_time.eq(TimeUtils::current_timestamp())))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 更新照片评分
+pub fn update_photo_rating(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    value: Option<i32>,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{id, rating, update_time};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((
+            rating.eq(value),
+            update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 更新照片拍摄时间
+pub fn update_photo_date_time(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    value: Option<i64>,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{date_time_original, id, update_time};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((
+            date_time_original.eq(value),
+            update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 记录照片导入【记录失败不影响导入】
+fn record_imported(connection: &mut SqliteConnection, photo: &Photo) {
+    let path = file_util::display_path(photo.full_path());
+    if let Err(e) = photo_activity::insert_activities(
+        connection,
+        &[photo.id],
+        ActivityKind::Imported,
+        Some(&path),
+    ) {
+        log::warn!("照片导入记录失败 {}: {}", path, e);
+    }
+}
+
+/// 更新照片备注
+pub fn update_photo_notes(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    value: Option<String>,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{id, notes, update_time};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((
+            notes.eq(value),
+            update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 更新照片说明
+pub fn update_photo_caption(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    value: Option<String>,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{caption, id, update_time};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((
+            caption.eq(value),
+            update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 更新照片作者
+pub fn update_photo_artist(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    value: Option<String>,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{artist, id, update_time};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((
+            artist.eq(value),
+            update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 更新照片版权
+pub fn update_photo_copyright(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    value: Option<String>,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{copyright, id, update_time};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((
+            copyright.eq(value),
+            update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 更新照片署名
+pub fn update_photo_credit(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    value: Option<String>,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{credit, id, update_time};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((
+            credit.eq(value),
+            update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 把旧版本保存的 GPS 文本转换为十进制坐标 JSON，返回转换的照片数量
+///
+/// 无法解析的清空并标记为未读取 EXIF，由补全任务从原图重新读取【每次启动时调用，已转换后不再有匹配的记录】
+pub fn migrate_legacy_gps_info(connection: &mut SqliteConnection) -> Result<usize> {
+    use crate::storage::schema::photo_table::{exif_read_time, gps_info, id};
+    let rows: Vec<(i32, String)> = photo_table
+        .filter(gps_info.is_not_null())
+        .filter(gps_info.not_like("{%"))
+        .select((id, gps_info.assume_not_null()))
+        .load(connection)?;
+    if rows.is_empty() {
+        return Ok(0);
+    }
+    connection.transaction(|conn| {
+        for (photo_id, text) in &rows {
+            let coordinate = GpsCoordinate::parse_legacy(text);
+            let value = coordinate
+                .as_ref()
+                .and_then(|x| JsonUtil::stringify(x).ok());
+            let target = photo_table.filter(id.eq(*photo_id));
+            if value.is_some() {
+                diesel::update(target)
+                    .set(gps_info.eq(value))
+                    .execute(conn)?;
+            } else {
+                diesel::update(target)
+                    .set((gps_info.eq(None::<String>), exif_read_time.eq(None::<i64>)))
+                    .execute(conn)?;
+            }
+        }
+        anyhow::Ok(())
+    })?;
+    log::info!("已转换 {} 张照片的旧版本 GPS 信息", rows.len());
+    Ok(rows.len())
+}
+
+/// 更新照片 GPS 信息
+pub fn update_photo_gps(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    value: Option<String>,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{gps_info, id, update_time};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((
+            gps_info.eq(value),
+            update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 更新照片文件信息【文件内容被修改后，如旋转、翻转】
+pub fn update_photo_file_info(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    hash_str: &str,
+    new_width: i32,
+    new_height: i32,
+    new_file_size: i64,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{
+        aspect_ratio, file_size, height, id, update_time, width,
+    };
+    let ratio = if new_height > 0 {
+        (new_width as f32 / new_height as f32 * 100.0).round() / 100.0
+    } else {
+        0.0
+    };
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((
+            hash.eq(hash_str),
+            width.eq(new_width),
+            height.eq(new_height),
+            aspect_ratio.eq(ratio),
+            file_size.eq(new_file_size),
+            update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 更新照片的 Hash 和文件大小【写回元数据等只修改了文件中的元数据，画面不变】
+pub fn update_photo_hash(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    hash_str: &str,
+    new_file_size: i64,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{file_size, id, update_time};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((
+            hash.eq(hash_str),
+            file_size.eq(new_file_size),
+            update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 更新照片格式（MIME 类型）【转换文件格式后使用】
+pub fn update_photo_format(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    mime_type: &str,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{format, id, update_time};
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((
+            format.eq(mime_type),
+            update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 查询指定目录下（含子目录）的照片
+pub fn search_photo_under_root(connection: &mut SqliteConnection, root: &Path) -> Result<Vec<Photo>> {
+    use crate::storage::schema::photo_table::img_path;
+    // 转义 LIKE 通配符，再按路径组件精确过滤
+    let results = photo_table
+        .filter(is_delete.eq(false))
+        .filter(img_path.like(path_prefix_pattern(root)).escape('\\'))
+        .load::<Photo>(connection)?;
+    Ok(results
+        .into_iter()
+        .filter(|p| Path::new(&p.img_path).starts_with(root))
+        .collect())
+}
+
+/// 目录前缀的 LIKE 匹配模式【转义通配符，需配合 `escape('\\')` 使用】
+pub fn path_prefix_pattern(root: &Path) -> String {
+    format!(
+        "{}%",
+        root.display()
+            .to_string()
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    )
+}
+
+/// 标记指定目录下照片的可访问状态，返回更新数量
+pub fn set_photos_available_under(
+    connection: &mut SqliteConnection,
+    root: &Path,
+    available: bool,
+) -> Result<usize> {
+    use crate::storage::schema::photo_table::{id, is_available};
+    let ids: Vec<i32> = search_photo_under_root(connection, root)?
+        .into_iter()
+        .filter(|p| p.is_available != available)
+        .map(|p| p.id)
+        .collect();
+    set_photos_available(connection, &ids, available)
+}
+
+/// 卷挂载位置变化后，把照片路径从旧目录迁移到新目录，返回更新数量
+pub fn rebase_photo_paths(
+    connection: &mut SqliteConnection,
+    old_root: &Path,
+    new_root: &Path,
+) -> Result<usize> {
+    use crate::storage::schema::photo_table::{id, img_path, img_path_raw, is_available, update_time};
+    let timestamp = TimeUtils::current_timestamp();
+    let photos = search_photo_under_root(connection, old_root)?;
+    let mut rows = 0;
+    for photo in photos {
+        let Ok(relative) = photo.full_path().strip_prefix(old_root).map(|p| p.to_path_buf()) else {
+            continue;
+        };
+        let new_path = new_root.join(relative);
+        let parent = new_path.parent().unwrap_or(new_root);
+        rows += diesel::update(photo_table.filter(id.eq(photo.id)))
+            .set((
+                img_path.eq(file_util::display_path(parent)),
+                img_path_raw.eq(Some(file_util::path_to_bytes(&new_path))),
+                is_available.eq(true),
+                update_time.eq(timestamp),
+            ))
+            .execute(connection)?;
+    }
+    Ok(rows)
+}
+
+/// 照片文件移动后更新路径【Hash 不变，缩略图仍然有效】
+pub fn update_photo_location(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    new_path: &Path,
+) -> Result<()> {
+    use crate::storage::schema::photo_table::{
+        id, img_name, img_path, img_path_raw, is_available, update_time,
+    };
+    let parent = new_path.parent().unwrap_or(Path::new(""));
+    let name = new_path
+        .file_name()
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_default();
+    diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set((
+            img_path.eq(file_util::display_path(parent)),
+            img_name.eq(name),
+            img_path_raw.eq(Some(file_util::path_to_bytes(new_path))),
+            is_available.eq(true),
+            update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 把照片标记为已删除，返回更新数量
+pub fn mark_photos_deleted(connection: &mut SqliteConnection, photo_ids: &[i32]) -> Result<usize> {
+    use crate::storage::schema::photo_table::{id, update_time};
+    let timestamp = TimeUtils::current_timestamp();
+    let mut rows = 0;
+    for chunk in photo_ids.chunks(SQLITE_MAX_VARIABLES - 2) {
+        rows += diesel::update(photo_table.filter(id.eq_any(chunk)))
+            .set((is_delete.eq(true), update_time.eq(timestamp)))
+            .execute(connection)?;
+    }
+    Ok(rows)
+}
+
+/// 取消照片的已删除标记，返回更新数量
+pub fn mark_photos_restored(connection: &mut SqliteConnection, photo_ids: &[i32]) -> Result<usize> {
+    use crate::storage::schema::photo_table::{id, update_time};
+    let timestamp = TimeUtils::current_timestamp();
+    let mut rows = 0;
+    for chunk in photo_ids.chunks(SQLITE_MAX_VARIABLES - 2) {
+        rows += diesel::update(photo_table.filter(id.eq_any(chunk)))
+            .set((is_delete.eq(false), update_time.eq(timestamp)))
+            .execute(connection)?;
+    }
+    Ok(rows)
+}
+
+/// 记录照片的浏览时间【不更新修改时间】
+pub fn update_last_viewed_time(connection: &mut SqliteConnection, photo_id: i32) -> Result<()> {
+    use crate::storage::schema::photo_table::{id, last_viewed_time};
+    let rows = diesel::update(photo_table.filter(id.eq(photo_id)))
+        .set(last_viewed_time.eq(TimeUtils::current_timestamp()))
+        .execute(connection)?;
+    if rows == 0 {
+        return Err(anyhow!("照片 {} 不存在!", photo_id));
+    }
+    Ok(())
+}
+
+/// 最近浏览的照片【按浏览时间从新到旧】
+pub fn search_recently_viewed(
+    connection: &mut SqliteConnection,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<Photo>> {
+    use crate::storage::schema::photo_table::{id, last_viewed_time};
+    let results = photo_table
+        .filter(is_delete.eq(false))
+        .filter(last_viewed_time.is_not_null())
+        .order((last_viewed_time.desc(), id.desc()))
+        .limit(limit)
+        .offset(offset)
+        .load::<Photo>(connection)?;
+    Ok(results)
+}
+
+/// 浏览过的照片数量
+pub fn count_recently_viewed(connection: &mut SqliteConnection) -> Result<i64> {
+    use crate::storage::schema::photo_table::last_viewed_time;
+    let count = photo_table
+        .filter(is_delete.eq(false))
+        .filter(last_viewed_time.is_not_null())
+        .count()
+        .get_result(connection)?;
+    Ok(count)
+}
+
+define_sql_function!(fn random() -> Integer);
+
+/// 随机抽取可访问的照片
+/// - limit 数量【为空表示全部】
+pub fn sample_available_photos(
+    connection: &mut SqliteConnection,
+    limit: Option<i64>,
+) -> Result<Vec<Photo>> {
+    use crate::storage::schema::photo_table::is_available;
+    let mut query = photo_table
+        .filter(is_delete.eq(false))
+        .filter(is_available.eq(true))
+        .order(random())
+        .into_boxed();
+    if let Some(limit) = limit {
+        query = query.limit(limit);
+    }
+    Ok(query.load::<Photo>(connection)?)
+}
+
+/// 标记照片的可访问状态，返回更新数量
+pub fn set_photos_available(
+    connection: &mut SqliteConnection,
+    photo_ids: &[i32],
+    available: bool,
+) -> Result<usize> {
+    use crate::storage::schema::photo_table::{id, is_available};
+    let mut rows = 0;
+    for chunk in photo_ids.chunks(SQLITE_MAX_VARIABLES - 1) {
+        rows += diesel::update(photo_table.filter(id.eq_any(chunk)))
+            .set(is_available.eq(available))
+            .execute(connection)?;
+    }
+    Ok(rows)
+}
+
 pub fn search_photo_by_file_path(
     connection: &mut SqliteConnection,
     file_path: String,
@@ -157,3 +865,134 @@ pub fn search_photo_by_file_name(
 ) -> Vec<Photo> {
     return Vec::new();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::connection::test_connection;
+
+    fn new_photo(hash_str: &str) -> NewPhoto {
+        NewPhoto {
+            img_path: "/photos".to_string(),
+            img_name: format!("{}.jpg", hash_str),
+            img_path_raw: None,
+            hash: hash_str.to_string(),
+            width: 4000,
+            height: 3000,
+            aspect_ratio: 1.33,
+            file_size: 1024,
+            format: "image/jpeg".to_string(),
+            validation_level: 0,
+            is_animated: false,
+            frame_count: None,
+            animation_duration: None,
+            create_time: 0,
+            update_time: 0,
+        }
+    }
+
+    fn exif(make: &str, rating: i32) -> PhotoExifChangeset {
+        PhotoExifChangeset {
+            make: Some(make.to_string()),
+            rating: Some(rating),
+            date_time_original: Some(1_700_000_000),
+            exif_read_time: Some(1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_bulk_upsert_exif() {
+        let mut conn = test_connection();
+        let inserted = bulk_upsert(
+            &mut conn,
+            vec![
+                (new_photo("a"), Some(exif("Canon", 3))),
+                (new_photo("b"), None),
+                (new_photo("a"), Some(exif("Nikon", 1))),
+            ],
+        )
+        .unwrap();
+        assert_eq!(inserted.len(), 2);
+        // 返回的照片已包含 EXIF
+        assert!(inserted.iter().any(|x| x.make.as_deref() == Some("Canon")));
+        let a = &search_photos_by_hashes(&mut conn, &["a".to_string()]).unwrap()[0];
+        assert_eq!(a.make.as_deref(), Some("Canon"));
+        assert_eq!(a.date_time_original, Some(1_700_000_000));
+        assert_eq!(a.exif_read_time, Some(1));
+
+        // 没有读取 EXIF 的照片再次扫描时补全，只填写还没有值的字段
+        let b = inserted.iter().find(|x| x.hash == "b").unwrap();
+        update_photo_rating(&mut conn, b.id, Some(5)).unwrap();
+        assert!(
+            bulk_upsert(&mut conn, vec![(new_photo("b"), Some(exif("Sony", 1)))])
+                .unwrap()
+                .is_empty()
+        );
+        let b = &search_photos_by_hashes(&mut conn, &["b".to_string()]).unwrap()[0];
+        assert_eq!(b.make.as_deref(), Some("Sony"));
+        assert_eq!(b.rating, Some(5));
+
+        // 已读取 EXIF 的照片不再修改
+        bulk_upsert(&mut conn, vec![(new_photo("a"), Some(exif("Leica", 1)))]).unwrap();
+        let a = &search_photos_by_hashes(&mut conn, &["a".to_string()]).unwrap()[0];
+        assert_eq!(a.make.as_deref(), Some("Canon"));
+        assert_eq!(
+            search_photos_without_exif(&mut conn, 0, 10).unwrap().len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_bulk_upsert_deleted_hash() {
+        let mut conn = test_connection();
+        let trashed = bulk_upsert(&mut conn, vec![(new_photo("t"), None)]).unwrap();
+        mark_photos_deleted(&mut conn, &[trashed[0].id]).unwrap();
+
+        // 同一批中包含回收站中的照片时，其他照片正常写入
+        let inserted = bulk_upsert(
+            &mut conn,
+            vec![
+                (new_photo("c"), None),
+                (new_photo("t"), Some(exif("Canon", 3))),
+                (new_photo("d"), None),
+            ],
+        )
+        .unwrap();
+        let mut hashes: Vec<_> = inserted.iter().map(|x| x.hash.as_str()).collect();
+        hashes.sort();
+        assert_eq!(hashes, vec!["c", "d"]);
+        // 回收站中的照片不恢复
+        assert!(search_photos_by_hashes(&mut conn, &["t".to_string()])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_migrate_legacy_gps_info() {
+        let mut conn = test_connection();
+        let inserted = bulk_upsert(
+            &mut conn,
+            vec![
+                (new_photo("g"), Some(exif("Canon", 3))),
+                (new_photo("h"), Some(exif("Canon", 3))),
+            ],
+        )
+        .unwrap();
+        let id_of = |name: &str| inserted.iter().find(|x| x.hash == name).unwrap().id;
+        let legacy = Some("N39°54′15.2″E116°24′26.6″".to_string());
+        update_photo_gps(&mut conn, id_of("g"), legacy).unwrap();
+        update_photo_gps(&mut conn, id_of("h"), Some("invalid".to_string())).unwrap();
+
+        assert_eq!(migrate_legacy_gps_info(&mut conn).unwrap(), 2);
+        let g = &search_photos_by_hashes(&mut conn, &["g".to_string()]).unwrap()[0];
+        let coordinate: GpsCoordinate =
+            JsonUtil::from_json(g.gps_info.as_deref().unwrap()).unwrap();
+        assert!((coordinate.latitude - 39.904222).abs() < 1e-6);
+        // 无法解析的清空，等待补全任务重新读取
+        let h = &search_photos_by_hashes(&mut conn, &["h".to_string()]).unwrap()[0];
+        assert_eq!(h.gps_info, None);
+        assert_eq!(h.exif_read_time, None);
+        assert_eq!(migrate_legacy_gps_info(&mut conn).unwrap(), 0);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::models::photo::Photo;
+use crate::models::place::{NewPlace, NewPlacePhoto, Place};
+use crate::storage::schema::{photo_table, place_photos, places};
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+
+/// 有位置的照片（ID、GPS 信息、地点名称）
+pub type GeotaggedPhoto = (i32, String, Option<String>);
+
+/// 获取所有有位置的照片
+pub fn get_geotagged_photos(connection: &mut SqliteConnection) -> Result<Vec<GeotaggedPhoto>> {
+    let rows = photo_table::table
+        .filter(photo_table::is_delete.eq(false))
+        .filter(photo_table::gps_info.is_not_null())
+        .select((
+            photo_table::id,
+            photo_table::gps_info.assume_not_null(),
+            photo_table::location_name,
+        ))
+        .order(photo_table::id.asc())
+        .load::<GeotaggedPhoto>(connection)?;
+    Ok(rows)
+}
+
+/// 用新的聚类结果替换所有地点
+///
+/// 对应原有地点的更新原记录（保留 ID），其余新建；没有对应新地点的原有地点被删除
+/// - items 原有地点的 ID、地点及其包含的照片 ID
+pub fn replace_places(
+    connection: &mut SqliteConnection,
+    items: Vec<(Option<i32>, NewPlace, Vec<i32>)>,
+) -> Result<usize> {
+    let now = TimeUtils::current_timestamp();
+    connection.transaction(|conn| {
+        diesel::delete(place_photos::table).execute(conn)?;
+        let kept: Vec<i32> = items.iter().filter_map(|x| x.0).collect();
+        diesel::delete(places::table.filter(places::id.ne_all(&kept))).execute(conn)?;
+        let count = items.len();
+        for (place_id, place, photo_ids) in items {
+            let place_id = match place_id {
+                Some(id) => {
+                    diesel::update(places::table.find(id))
+                        .set((
+                            places::name.eq(&place.name),
+                            places::latitude.eq(place.latitude),
+                            places::longitude.eq(place.longitude),
+                            places::radius_meters.eq(place.radius_meters),
+                            places::photo_count.eq(place.photo_count),
+                            places::update_time.eq(place.update_time),
+                        ))
+                        .execute(conn)?;
+                    id
+                }
+                None => diesel::insert_into(places::table)
+                    .values(place)
+                    .returning(places::id)
+                    .get_result(conn)?,
+            };
+            let members: Vec<NewPlacePhoto> = photo_ids
+                .into_iter()
+                .map(|photo_id| NewPlacePhoto {
+                    place_id,
+                    photo_id,
+                    create_time: now,
+                })
+                .collect();
+            diesel::insert_into(place_photos::table)
+                .values(&members)
+                .execute(conn)?;
+        }
+        Ok(count)
+    })
+}
+
+/// 获取所有地点【按照片数量从多到少】
+pub fn list_places(connection: &mut SqliteConnection) -> Result<Vec<Place>> {
+    let rows = places::table
+        .order((places::photo_count.desc(), places::id.asc()))
+        .select(Place::as_select())
+        .load(connection)?;
+    Ok(rows)
+}
+
+/// 获取地点
+pub fn get_place(connection: &mut SqliteConnection, place_id: i32) -> Result<Place> {
+    places::table
+        .find(place_id)
+        .select(Place::as_select())
+        .first(connection)
+        .optional()?
+        .ok_or_else(|| anyhow!("地点 {} 不存在!", place_id))
+}
+
+/// 获取地点中的照片【按拍摄时间排序】
+pub fn get_place_photos(connection: &mut SqliteConnection, place_id: i32) -> Result<Vec<Photo>> {
+    let photo_ids = place_photos::table
+        .filter(place_photos::place_id.eq(place_id))
+        .select(place_photos::photo_id);
+    let photos = photo_table::table
+        .filter(photo_table::is_delete.eq(false))
+        .filter(photo_table::id.eq_any(photo_ids))
+        .order((photo_table::date_time_original.asc(), photo_table::id.asc()))
+        .load::<Photo>(connection)?;
+    Ok(photos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::connection::test_connection;
+
+    fn new_place(name: &str) -> NewPlace {
+        NewPlace {
+            name: name.to_string(),
+            latitude: 30.0,
+            longitude: 120.0,
+            radius_meters: 100.0,
+            photo_count: 1,
+            create_time: 1,
+            update_time: 1,
+        }
+    }
+
+    #[test]
+    fn test_replace_places_keeps_ids() {
+        let mut conn = test_connection();
+        replace_places(
+            &mut conn,
+            vec![
+                (None, new_place("西湖"), vec![1]),
+                (None, new_place("灵隐寺"), vec![2]),
+            ],
+        )
+        .unwrap();
+        let old = list_places(&mut conn).unwrap();
+        let west_lake = old.iter().find(|x| x.name == "西湖").unwrap().id;
+
+        replace_places(
+            &mut conn,
+            vec![
+                (Some(west_lake), new_place("西湖景区"), vec![1, 3]),
+                (None, new_place("断桥"), vec![4]),
+            ],
+        )
+        .unwrap();
+        let places = list_places(&mut conn).unwrap();
+        assert_eq!(places.len(), 2);
+        assert_eq!(
+            places.iter().find(|x| x.id == west_lake).unwrap().name,
+            "西湖景区"
+        );
+        assert!(places.iter().all(|x| x.name != "灵隐寺"));
+        let members = place_photos::table
+            .filter(place_photos::place_id.eq(west_lake))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .unwrap();
+        assert_eq!(members, 2);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::constant::SQLITE_MAX_VARIABLES;
+use crate::models::problem::{NewProblem, ProblemRecord};
+use crate::storage::schema::problems;
+use crate::utils::time_util::TimeUtils;
+use anyhow::Result;
+use diesel::prelude::*;
+
+/// 按路径查找问题文件
+pub fn get_problem(connection: &mut SqliteConnection, path: &str) -> Result<Option<ProblemRecord>> {
+    let record = problems::table
+        .filter(problems::path.eq(path))
+        .select(ProblemRecord::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(record)
+}
+
+/// 保存问题文件【已存在时更新错误信息并增加失败次数】
+pub fn save_problem(connection: &mut SqliteConnection, item: NewProblem) -> Result<()> {
+    connection.transaction(|conn| {
+        let rows = diesel::update(problems::table.filter(problems::path.eq(&item.path)))
+            .set((&item, problems::fail_count.eq(problems::fail_count + 1)))
+            .execute(conn)?;
+        if rows == 0 {
+            diesel::insert_into(problems::table)
+                .values((&item, problems::create_time.eq(TimeUtils::current_timestamp())))
+                .execute(conn)?;
+        }
+        Ok(())
+    })
+}
+
+/// 删除问题文件记录【文件已能正常读取】
+pub fn delete_problem(connection: &mut SqliteConnection, path: &str) -> Result<usize> {
+    let rows = diesel::delete(problems::table.filter(problems::path.eq(path))).execute(connection)?;
+    Ok(rows)
+}
+
+/// 批量删除问题文件记录【文件已不存在】
+pub fn delete_problems(connection: &mut SqliteConnection, paths: &[String]) -> Result<usize> {
+    connection.transaction(|conn| {
+        let mut rows = 0;
+        for chunk in paths.chunks(SQLITE_MAX_VARIABLES) {
+            rows += diesel::delete(problems::table.filter(problems::path.eq_any(chunk)))
+                .execute(conn)?;
+        }
+        Ok(rows)
+    })
+}
+
+/// 所有问题文件【最近失败的在前】
+pub fn list_problems(connection: &mut SqliteConnection) -> Result<Vec<ProblemRecord>> {
+    let records = problems::table
+        .order((problems::update_time.desc(), problems::id.desc()))
+        .select(ProblemRecord::as_select())
+        .load(connection)?;
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::connection::test_connection;
+
+    fn problem(path: &str, update_time: i64) -> NewProblem {
+        NewProblem {
+            path: path.to_string(),
+            kind: 0,
+            message: "解码失败".to_string(),
+            sample: String::new(),
+            file_size: 10,
+            modified_time: 0,
+            update_time,
+        }
+    }
+
+    #[test]
+    fn test_save_and_delete_problems() {
+        let mut conn = test_connection();
+        save_problem(&mut conn, problem("/photos/a.jpg", 1)).unwrap();
+        save_problem(&mut conn, problem("/photos/b.jpg", 2)).unwrap();
+        save_problem(&mut conn, problem("/photos/a.jpg", 3)).unwrap();
+
+        // 再次失败时更新记录并增加失败次数
+        let record = get_problem(&mut conn, "/photos/a.jpg").unwrap().unwrap();
+        assert_eq!(record.fail_count, 2);
+        assert_eq!(record.update_time, 3);
+        let paths: Vec<String> = list_problems(&mut conn)
+            .unwrap()
+            .into_iter()
+            .map(|x| x.path)
+            .collect();
+        assert_eq!(paths, ["/photos/a.jpg", "/photos/b.jpg"]);
+
+        let rows = delete_problems(
+            &mut conn,
+            &["/photos/b.jpg".to_string(), "/photos/c.jpg".to_string()],
+        )
+        .unwrap();
+        assert_eq!(rows, 1);
+        assert!(get_problem(&mut conn, "/photos/b.jpg").unwrap().is_none());
+        assert_eq!(delete_problem(&mut conn, "/photos/a.jpg").unwrap(), 1);
+        assert!(list_problems(&mut conn).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::constant::SQLITE_MAX_VARIABLES;
+use crate::models::remote_storage::{
+    NewRemoteObject, NewRemoteStorage, RemoteObjectRecord, RemoteStorageRecord,
+};
+use crate::storage::schema::{remote_objects, remote_storages};
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+
+/// 获取所有远程存储
+pub fn get_all_storages(connection: &mut SqliteConnection) -> Result<Vec<RemoteStorageRecord>> {
+    let results = remote_storages::table
+        .order(remote_storages::id.asc())
+        .select(RemoteStorageRecord::as_select())
+        .load(connection)?;
+    Ok(results)
+}
+
+/// 按 ID 查找远程存储
+pub fn get_storage(
+    connection: &mut SqliteConnection,
+    id: i32,
+) -> Result<Option<RemoteStorageRecord>> {
+    let result = remote_storages::table
+        .filter(remote_storages::id.eq(id))
+        .select(RemoteStorageRecord::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(result)
+}
+
+/// 新增远程存储
+pub fn insert_storage(
+    connection: &mut SqliteConnection,
+    item: &NewRemoteStorage,
+) -> Result<RemoteStorageRecord> {
+    let storage = diesel::insert_into(remote_storages::table)
+        .values((item, remote_storages::create_time.eq(item.update_time)))
+        .returning(RemoteStorageRecord::as_returning())
+        .get_result(connection)?;
+    Ok(storage)
+}
+
+/// 更新远程存储
+pub fn update_storage(
+    connection: &mut SqliteConnection,
+    id: i32,
+    item: &NewRemoteStorage,
+) -> Result<RemoteStorageRecord> {
+    let storage = diesel::update(remote_storages::table.filter(remote_storages::id.eq(id)))
+        .set(item)
+        .returning(RemoteStorageRecord::as_returning())
+        .get_result(connection)
+        .optional()?;
+    storage.ok_or_else(|| anyhow!("远程存储 {} 不存在!", id))
+}
+
+/// 更新远程存储的密钥
+pub fn update_secret(connection: &mut SqliteConnection, id: i32, secret_key: &str) -> Result<()> {
+    diesel::update(remote_storages::table.filter(remote_storages::id.eq(id)))
+        .set(remote_storages::secret_key.eq(secret_key))
+        .execute(connection)?;
+    Ok(())
+}
+
+/// 删除远程存储及其照片记录
+pub fn delete_storage(connection: &mut SqliteConnection, id: i32) -> Result<()> {
+    connection.transaction(|conn| {
+        diesel::delete(remote_objects::table.filter(remote_objects::storage_id.eq(id)))
+            .execute(conn)?;
+        let rows = diesel::delete(remote_storages::table.filter(remote_storages::id.eq(id)))
+            .execute(conn)?;
+        if rows == 0 {
+            return Err(anyhow!("远程存储 {} 不存在!", id));
+        }
+        Ok(())
+    })
+}
+
+/// 获取远程存储中的所有照片
+pub fn get_objects(
+    connection: &mut SqliteConnection,
+    storage_id: i32,
+) -> Result<Vec<RemoteObjectRecord>> {
+    let results = remote_objects::table
+        .filter(remote_objects::storage_id.eq(storage_id))
+        .order(remote_objects::object_key.asc())
+        .select(RemoteObjectRecord::as_select())
+        .load(connection)?;
+    Ok(results)
+}
+
+/// 按 ID 查找远程照片
+pub fn get_object(
+    connection: &mut SqliteConnection,
+    id: i32,
+) -> Result<Option<RemoteObjectRecord>> {
+    let result = remote_objects::table
+        .filter(remote_objects::id.eq(id))
+        .select(RemoteObjectRecord::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(result)
+}
+
+/// 查找远程存储中内容相同的照片
+pub fn find_object_by_hash(
+    connection: &mut SqliteConnection,
+    storage_id: i32,
+    hash: &str,
+) -> Result<Option<RemoteObjectRecord>> {
+    let result = remote_objects::table
+        .filter(remote_objects::storage_id.eq(storage_id))
+        .filter(remote_objects::hash.eq(hash))
+        .select(RemoteObjectRecord::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(result)
+}
+
+/// 按对象路径查找远程照片
+pub fn find_object_by_key(
+    connection: &mut SqliteConnection,
+    storage_id: i32,
+    key: &str,
+) -> Result<Option<RemoteObjectRecord>> {
+    let result = remote_objects::table
+        .filter(remote_objects::storage_id.eq(storage_id))
+        .filter(remote_objects::object_key.eq(key))
+        .select(RemoteObjectRecord::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(result)
+}
+
+/// 新增或更新远程照片【按远程存储和对象路径判断】
+pub fn upsert_object(
+    connection: &mut SqliteConnection,
+    item: &NewRemoteObject,
+) -> Result<RemoteObjectRecord> {
+    let object = diesel::insert_into(remote_objects::table)
+        .values((item, remote_objects::create_time.eq(item.update_time)))
+        .on_conflict((remote_objects::storage_id, remote_objects::object_key))
+        .do_update()
+        .set(item)
+        .returning(RemoteObjectRecord::as_returning())
+        .get_result(connection)?;
+    Ok(object)
+}
+
+/// 删除远程照片记录，返回删除的数量
+pub fn delete_objects(connection: &mut SqliteConnection, ids: &[i32]) -> Result<usize> {
+    let mut rows = 0;
+    for chunk in ids.chunks(SQLITE_MAX_VARIABLES) {
+        rows += diesel::delete(remote_objects::table.filter(remote_objects::id.eq_any(chunk)))
+            .execute(connection)?;
+    }
+    Ok(rows)
+}
+
+/// 保存文件 Hash 并标记缩略图已生成
+pub fn save_thumbnail(connection: &mut SqliteConnection, id: i32, hash: &str) -> Result<()> {
+    let now = TimeUtils::current_timestamp();
+    diesel::update(remote_objects::table.filter(remote_objects::id.eq(id)))
+        .set((
+            remote_objects::hash.eq(hash),
+            remote_objects::thumbnail_time.eq(now),
+            remote_objects::update_time.eq(now),
+        ))
+        .execute(connection)?;
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::models::scan_report::{NewScanReport, ScanReportRecord};
+use crate::storage::schema::scan_reports;
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+
+/// 新增扫描记录，返回扫描 ID
+pub fn insert_scan_report(connection: &mut SqliteConnection, status: i32) -> Result<i32> {
+    let item = NewScanReport {
+        status,
+        start_time: TimeUtils::current_timestamp(),
+    };
+    let report = diesel::insert_into(scan_reports::table)
+        .values(item)
+        .returning(ScanReportRecord::as_returning())
+        .get_result(connection)?;
+    Ok(report.id)
+}
+
+/// 写入扫描结果
+pub fn finish_scan_report(
+    connection: &mut SqliteConnection,
+    id: i32,
+    status: i32,
+    file_count: i32,
+    total_millis: i64,
+    slowest_files: &str,
+    slowest_folders: &str,
+) -> Result<()> {
+    let rows = diesel::update(scan_reports::table.filter(scan_reports::id.eq(id)))
+        .set((
+            scan_reports::status.eq(status),
+            scan_reports::file_count.eq(file_count),
+            scan_reports::total_millis.eq(total_millis),
+            scan_reports::slowest_files.eq(slowest_files),
+            scan_reports::slowest_folders.eq(slowest_folders),
+            scan_reports::end_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    if rows == 0 {
+        return Err(anyhow!("扫描记录 {} 不存在!", id));
+    }
+    Ok(())
+}
+
+/// 获取扫描记录
+pub fn get_scan_report(connection: &mut SqliteConnection, id: i32) -> Result<ScanReportRecord> {
+    let report = scan_reports::table
+        .filter(scan_reports::id.eq(id))
+        .select(ScanReportRecord::as_select())
+        .first(connection)
+        .optional()?;
+    report.ok_or_else(|| anyhow!("扫描记录 {} 不存在!", id))
+}
+
+/// 最近完成的扫描记录【用于预估扫描耗时】
+/// - status 完成状态的存储值
+/// - min_files 最少处理的文件数量
+/// - limit 最多返回的数量
+pub fn recent_finished_reports(
+    connection: &mut SqliteConnection,
+    status: i32,
+    min_files: i32,
+    limit: i64,
+) -> Result<Vec<ScanReportRecord>> {
+    let reports = scan_reports::table
+        .filter(scan_reports::status.eq(status))
+        .filter(scan_reports::file_count.ge(min_files))
+        .filter(scan_reports::end_time.gt(scan_reports::start_time))
+        .order(scan_reports::id.desc())
+        .limit(limit)
+        .select(ScanReportRecord::as_select())
+        .load(connection)?;
+    Ok(reports)
+}
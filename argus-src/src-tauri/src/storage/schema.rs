@@ -1,5 +1,187 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    albums (id) {
+        id -> Integer,
+        name -> Text,
+        parent_id -> Nullable<Integer>,
+        description -> Nullable<Text>,
+        cover_photo_id -> Nullable<Integer>,
+        is_delete -> Bool,
+        create_time -> BigInt,
+        update_time -> BigInt,
+        source_path -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    edit_journal (id) {
+        id -> Integer,
+        session_id -> Text,
+        description -> Text,
+        changes -> Text,
+        is_undone -> Bool,
+        create_time -> BigInt,
+        update_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    event_photos (id) {
+        id -> Integer,
+        event_id -> Integer,
+        photo_id -> Integer,
+        create_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    events (id) {
+        id -> Integer,
+        name -> Text,
+        place_name -> Nullable<Text>,
+        start_time -> BigInt,
+        end_time -> BigInt,
+        photo_count -> Integer,
+        cover_photo_id -> Integer,
+        create_time -> BigInt,
+        update_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    folder_covers (id) {
+        id -> Integer,
+        path -> Text,
+        photo_id -> Integer,
+        create_time -> BigInt,
+        update_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    geocode_cache (lat_key, lon_key) {
+        lat_key -> Integer,
+        lon_key -> Integer,
+        name -> Nullable<Text>,
+        source -> Integer,
+        expire_time -> Nullable<BigInt>,
+        create_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    import_batch_photos (id) {
+        id -> Integer,
+        batch_id -> Integer,
+        photo_id -> Integer,
+        create_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    import_batches (id) {
+        id -> Integer,
+        source -> Text,
+        photo_count -> Integer,
+        start_time -> BigInt,
+        end_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    organize_rules (id) {
+        id -> Integer,
+        name -> Text,
+        conditions -> Text,
+        action -> Integer,
+        target -> Text,
+        is_enable -> Bool,
+        create_time -> BigInt,
+        update_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    photo_activity (id) {
+        id -> Integer,
+        photo_id -> Integer,
+        kind -> Integer,
+        detail -> Nullable<Text>,
+        create_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    photo_albums (id) {
+        id -> Integer,
+        album_id -> Integer,
+        photo_id -> Integer,
+        create_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    photo_blurhashes (hash) {
+        hash -> Text,
+        blurhash -> Text,
+        create_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    photo_colors (id) {
+        id -> Integer,
+        hash -> Text,
+        l -> Float,
+        a -> Float,
+        b -> Float,
+        weight -> Float,
+        create_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    photo_histograms (hash) {
+        hash -> Text,
+        histogram -> Text,
+        highlight_clipping -> Float,
+        shadow_clipping -> Float,
+        create_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    photo_sequence_members (id) {
+        id -> Integer,
+        sequence_id -> Integer,
+        photo_id -> Integer,
+        create_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    photo_sequences (id) {
+        id -> Integer,
+        kind -> Integer,
+        photo_count -> Integer,
+        start_time -> BigInt,
+        end_time -> BigInt,
+        create_time -> BigInt,
+        update_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    photo_stacks (id) {
+        id -> Integer,
+        original_photo_id -> Integer,
+        cover_photo_id -> Integer,
+        create_time -> BigInt,
+        update_time -> BigInt,
+    }
+}
+
 diesel::table! {
     photo_storages (id) {
         id -> Integer,
@@ -8,6 +190,11 @@ diesel::table! {
         is_delete -> Bool,
         create_time -> BigInt,
         update_time -> BigInt,
+        volume_id -> Nullable<Text>,
+        volume_label -> Nullable<Text>,
+        relative_path -> Nullable<Text>,
+        is_available -> Bool,
+        last_seen_time -> BigInt,
     }
 }
 
@@ -16,6 +203,7 @@ diesel::table! {
         id -> Integer,
         img_path -> Text,
         img_name -> Text,
+        img_path_raw -> Nullable<Binary>,
         hash -> Text,
         width -> Integer,
         height -> Integer,
@@ -23,6 +211,7 @@ diesel::table! {
         file_size -> BigInt,
         format -> Text,
         notes -> Nullable<Text>,
+        caption -> Nullable<Text>,
         is_algorithm -> Nullable<Bool>,
         algorithm_score -> Nullable<Integer>,
         last_viewed_time -> Nullable<BigInt>,
@@ -43,11 +232,64 @@ diesel::table! {
         gps_info -> Nullable<Text>,
         exposure_program -> Nullable<Text>,
         metering_mode -> Nullable<Text>,
+        flash_code -> Nullable<Integer>,
+        metering_mode_code -> Nullable<Integer>,
+        exposure_program_code -> Nullable<Integer>,
+        white_balance_code -> Nullable<Integer>,
+        scene_capture_type_code -> Nullable<Integer>,
+        gps_speed -> Nullable<Float>,
+        gps_img_direction -> Nullable<Float>,
+        gps_dest_bearing -> Nullable<Float>,
+        focal_length_35mm -> Nullable<Float>,
         artist -> Nullable<Text>,
+        copyright -> Nullable<Text>,
+        credit -> Nullable<Text>,
+        location_name -> Nullable<Text>,
+        stack_id -> Nullable<Integer>,
+        validation_level -> Integer,
+        is_animated -> Bool,
+        frame_count -> Nullable<Integer>,
+        animation_duration -> Nullable<Integer>,
+        is_available -> Bool,
         is_delete -> Bool,
         create_time -> BigInt,
         update_time -> BigInt,
+        exif_read_time -> Nullable<BigInt>,
+        gps_img_direction_ref -> Nullable<Text>,
+        gps_dest_bearing_ref -> Nullable<Text>,
+        remote_object_id -> Nullable<Integer>,
+        paired_jpeg -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    photo_tags (id) {
+        id -> Integer,
+        tag_id -> Integer,
+        photo_id -> Integer,
+        create_time -> BigInt,
+    }
+}
 
+diesel::table! {
+    place_photos (id) {
+        id -> Integer,
+        place_id -> Integer,
+        photo_id -> Integer,
+        create_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    places (id) {
+        id -> Integer,
+        name -> Text,
+        latitude -> Double,
+        longitude -> Double,
+        radius_meters -> Double,
+        photo_count -> Integer,
+        create_time -> BigInt,
+        update_time -> BigInt,
     }
 }
 
@@ -63,4 +305,159 @@ diesel::table! {
     }
 }
 
-diesel::allow_tables_to_appear_in_same_query!(photo_storages, photo_table, posts,);
+diesel::table! {
+    problems (id) {
+        id -> Integer,
+        path -> Text,
+        kind -> Integer,
+        message -> Text,
+        sample -> Text,
+        file_size -> BigInt,
+        modified_time -> BigInt,
+        fail_count -> Integer,
+        create_time -> BigInt,
+        update_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    remote_objects (id) {
+        id -> Integer,
+        storage_id -> Integer,
+        object_key -> Text,
+        size -> BigInt,
+        etag -> Nullable<Text>,
+        modified_time -> BigInt,
+        hash -> Nullable<Text>,
+        thumbnail_time -> BigInt,
+        create_time -> BigInt,
+        update_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    remote_storages (id) {
+        id -> Integer,
+        name -> Text,
+        kind -> Text,
+        endpoint -> Text,
+        bucket -> Nullable<Text>,
+        region -> Nullable<Text>,
+        prefix -> Nullable<Text>,
+        access_key -> Nullable<Text>,
+        secret_key -> Nullable<Text>,
+        is_enable -> Bool,
+        create_time -> BigInt,
+        update_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    scan_reports (id) {
+        id -> Integer,
+        status -> Integer,
+        file_count -> Integer,
+        total_millis -> BigInt,
+        slowest_files -> Text,
+        slowest_folders -> Text,
+        start_time -> BigInt,
+        end_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    tags (id) {
+        id -> Integer,
+        name -> Text,
+        parent_id -> Nullable<Integer>,
+        is_delete -> Bool,
+        create_time -> BigInt,
+        update_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    user_profiles (id) {
+        id -> Integer,
+        display_name -> Text,
+        language -> Nullable<Text>,
+        theme -> Text,
+        default_sort -> Text,
+        grid_density -> Text,
+        vault_safe_search -> Bool,
+        create_time -> BigInt,
+        update_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    vault_items (id) {
+        id -> Integer,
+        photo_id -> Integer,
+        file_name -> Text,
+        thumbnail_name -> Nullable<Text>,
+        file_size -> BigInt,
+        create_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    watch_folders (id) {
+        id -> Integer,
+        path -> Text,
+        dest_dir -> Text,
+        dest_template -> Nullable<Text>,
+        album -> Nullable<Text>,
+        tags -> Text,
+        move_files -> Bool,
+        is_enable -> Bool,
+        create_time -> BigInt,
+        update_time -> BigInt,
+    }
+}
+
+diesel::table! {
+    watch_folder_imports (id) {
+        id -> Integer,
+        folder_id -> Integer,
+        source_path -> Text,
+        hash -> Text,
+        file_size -> BigInt,
+        create_time -> BigInt,
+    }
+}
+
+diesel::allow_tables_to_appear_in_same_query!(
+    albums,
+    edit_journal,
+    event_photos,
+    events,
+    folder_covers,
+    geocode_cache,
+    import_batch_photos,
+    import_batches,
+    organize_rules,
+    photo_activity,
+    photo_albums,
+    photo_blurhashes,
+    photo_colors,
+    photo_histograms,
+    photo_sequence_members,
+    photo_sequences,
+    photo_stacks,
+    photo_storages,
+    photo_table,
+    photo_tags,
+    place_photos,
+    places,
+    posts,
+    problems,
+    remote_objects,
+    remote_storages,
+    scan_reports,
+    tags,
+    user_profiles,
+    vault_items,
+    watch_folder_imports,
+    watch_folders,
+);
@@ -0,0 +1,191 @@
+use crate::constant::SQLITE_MAX_VARIABLES;
+use crate::models::tag::{NewPhotoTag, NewTag, Tag};
+use crate::storage::schema::photo_tags;
+use crate::storage::schema::tags;
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+use std::collections::HashMap;
+
+/// 获取所有标签
+pub fn get_all_tags(connection: &mut SqliteConnection) -> Result<Vec<Tag>> {
+    let results = tags::table
+        .filter(tags::is_delete.eq(false))
+        .order(tags::id.asc())
+        .load::<Tag>(connection)?;
+    Ok(results)
+}
+
+/// 统计每个标签的照片数量
+pub fn count_tag_photos(connection: &mut SqliteConnection) -> Result<HashMap<i32, i64>> {
+    let counts = photo_tags::table
+        .group_by(photo_tags::tag_id)
+        .select((photo_tags::tag_id, count_star()))
+        .load::<(i32, i64)>(connection)?;
+    Ok(counts.into_iter().collect())
+}
+
+/// 新增标签
+pub fn insert_tag(
+    connection: &mut SqliteConnection,
+    name: &str,
+    parent_id: Option<i32>,
+) -> Result<Tag> {
+    if name.trim().is_empty() {
+        return Err(anyhow!("标签名称不能为空!"));
+    }
+    let timestamp = TimeUtils::current_timestamp();
+    let item = NewTag {
+        name: name.trim().to_string(),
+        parent_id,
+        create_time: timestamp,
+        update_time: timestamp,
+    };
+    let tag = diesel::insert_into(tags::table)
+        .values(item)
+        .returning(Tag::as_returning())
+        .get_result(connection)?;
+    Ok(tag)
+}
+
+/// 按名称查找顶级标签
+pub fn find_root_tag(connection: &mut SqliteConnection, name: &str) -> Result<Option<Tag>> {
+    let tag = tags::table
+        .filter(tags::is_delete.eq(false))
+        .filter(tags::parent_id.is_null())
+        .filter(tags::name.eq(name.trim()))
+        .order(tags::id.asc())
+        .first::<Tag>(connection)
+        .optional()?;
+    Ok(tag)
+}
+
+/// 获取顶级标签，不存在时新增
+pub fn get_or_insert_tag(connection: &mut SqliteConnection, name: &str) -> Result<Tag> {
+    match find_root_tag(connection, name)? {
+        Some(tag) => Ok(tag),
+        None => insert_tag(connection, name, None),
+    }
+}
+
+/// 更新标签名称及父级
+pub fn update_tag_node(
+    connection: &mut SqliteConnection,
+    id: i32,
+    name: &str,
+    parent_id: Option<i32>,
+) -> Result<()> {
+    let rows = diesel::update(tags::table.filter(tags::id.eq(id)))
+        .set((
+            tags::name.eq(name.trim()),
+            tags::parent_id.eq(parent_id),
+            tags::update_time.eq(TimeUtils::current_timestamp()),
+        ))
+        .execute(connection)?;
+    if rows == 0 {
+        return Err(anyhow!("标签 {} 不存在!", id));
+    }
+    Ok(())
+}
+
+/// 获取照片的所有标签 ID
+pub fn get_photo_tag_ids(connection: &mut SqliteConnection, photo_id: i32) -> Result<Vec<i32>> {
+    let ids = photo_tags::table
+        .filter(photo_tags::photo_id.eq(photo_id))
+        .order(photo_tags::tag_id.asc())
+        .select(photo_tags::tag_id)
+        .load::<i32>(connection)?;
+    Ok(ids)
+}
+
+/// 批量获取照片的标签 ID
+pub fn get_photos_tag_ids(
+    connection: &mut SqliteConnection,
+    photo_ids: &[i32],
+) -> Result<HashMap<i32, Vec<i32>>> {
+    let mut result: HashMap<i32, Vec<i32>> = HashMap::new();
+    for chunk in photo_ids.chunks(SQLITE_MAX_VARIABLES) {
+        let pairs = photo_tags::table
+            .filter(photo_tags::photo_id.eq_any(chunk))
+            .order(photo_tags::tag_id.asc())
+            .select((photo_tags::photo_id, photo_tags::tag_id))
+            .load::<(i32, i32)>(connection)?;
+        for (photo_id, tag_id) in pairs {
+            result.entry(photo_id).or_default().push(tag_id);
+        }
+    }
+    Ok(result)
+}
+
+/// 给照片添加标签【已有的标签忽略】，返回新增的数量
+pub fn add_photo_tags(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    tag_ids: &[i32],
+) -> Result<usize> {
+    let timestamp = TimeUtils::current_timestamp();
+    let items: Vec<NewPhotoTag> = tag_ids
+        .iter()
+        .map(|tag_id| NewPhotoTag {
+            tag_id: *tag_id,
+            photo_id,
+            create_time: timestamp,
+        })
+        .collect();
+    let rows = diesel::insert_or_ignore_into(photo_tags::table)
+        .values(items)
+        .execute(connection)?;
+    Ok(rows)
+}
+
+/// 移除照片的标签，返回移除的数量
+pub fn remove_photo_tags(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    tag_ids: &[i32],
+) -> Result<usize> {
+    let mut rows = 0;
+    for chunk in tag_ids.chunks(SQLITE_MAX_VARIABLES - 1) {
+        rows += diesel::delete(
+            photo_tags::table
+                .filter(photo_tags::photo_id.eq(photo_id))
+                .filter(photo_tags::tag_id.eq_any(chunk)),
+        )
+        .execute(connection)?;
+    }
+    Ok(rows)
+}
+
+/// 标签是否存在【不包括已删除的标签】
+pub fn tag_exists(connection: &mut SqliteConnection, tag_id: i32) -> Result<bool> {
+    let count: i64 = tags::table
+        .filter(tags::id.eq(tag_id))
+        .filter(tags::is_delete.eq(false))
+        .count()
+        .get_result(connection)?;
+    Ok(count > 0)
+}
+
+/// 替换照片的标签
+pub fn replace_photo_tags(
+    connection: &mut SqliteConnection,
+    photo_id: i32,
+    tag_ids: &[i32],
+) -> Result<()> {
+    diesel::delete(photo_tags::table.filter(photo_tags::photo_id.eq(photo_id)))
+        .execute(connection)?;
+    let timestamp = TimeUtils::current_timestamp();
+    let items: Vec<NewPhotoTag> = tag_ids
+        .iter()
+        .map(|tag_id| NewPhotoTag {
+            tag_id: *tag_id,
+            photo_id,
+            create_time: timestamp,
+        })
+        .collect();
+    diesel::insert_into(photo_tags::table)
+        .values(items)
+        .execute(connection)?;
+    Ok(())
+}
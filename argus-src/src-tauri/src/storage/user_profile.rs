@@ -0,0 +1,62 @@
+use crate::models::user_profile::{NewUserProfile, UserProfileRecord};
+use crate::storage::schema::user_profiles;
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+
+/// 获取所有用户
+pub fn get_all_profiles(connection: &mut SqliteConnection) -> Result<Vec<UserProfileRecord>> {
+    let results = user_profiles::table
+        .order(user_profiles::id.asc())
+        .select(UserProfileRecord::as_select())
+        .load(connection)?;
+    Ok(results)
+}
+
+/// 按 ID 查找用户
+pub fn get_profile(
+    connection: &mut SqliteConnection,
+    id: i32,
+) -> Result<Option<UserProfileRecord>> {
+    let result = user_profiles::table
+        .filter(user_profiles::id.eq(id))
+        .select(UserProfileRecord::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(result)
+}
+
+/// 新增用户
+pub fn insert_profile(
+    connection: &mut SqliteConnection,
+    item: &NewUserProfile,
+) -> Result<UserProfileRecord> {
+    let profile = diesel::insert_into(user_profiles::table)
+        .values((item, user_profiles::create_time.eq(item.update_time)))
+        .returning(UserProfileRecord::as_returning())
+        .get_result(connection)?;
+    Ok(profile)
+}
+
+/// 更新用户
+pub fn update_profile(
+    connection: &mut SqliteConnection,
+    id: i32,
+    item: &NewUserProfile,
+) -> Result<UserProfileRecord> {
+    let profile = diesel::update(user_profiles::table.filter(user_profiles::id.eq(id)))
+        .set(item)
+        .returning(UserProfileRecord::as_returning())
+        .get_result(connection)
+        .optional()?;
+    profile.ok_or_else(|| anyhow!("用户 {} 不存在!", id))
+}
+
+/// 删除用户
+pub fn delete_profile(connection: &mut SqliteConnection, id: i32) -> Result<()> {
+    let rows = diesel::delete(user_profiles::table.filter(user_profiles::id.eq(id)))
+        .execute(connection)?;
+    if rows == 0 {
+        return Err(anyhow!("用户 {} 不存在!", id));
+    }
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use crate::models::photo::Photo;
+use crate::models::vault_item::{NewVaultItem, PhotoPrivateFields, VaultItem};
+use crate::storage::photo_table::{mark_photos_deleted, mark_photos_restored};
+use crate::storage::schema::{photo_table, vault_items};
+use crate::storage::tag;
+use crate::structs::vault::VaultMetadata;
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+
+/// 新增保险箱照片，并把照片标记为已删除【不再出现在普通查询中】
+///
+/// 同时清除照片的说明、位置信息和标签，返回新增的保险箱照片
+pub fn insert_vault_item(
+    connection: &mut SqliteConnection,
+    item: NewVaultItem,
+) -> Result<VaultItem> {
+    connection.transaction(|conn| {
+        let item = diesel::insert_into(vault_items::table)
+            .values(&item)
+            .returning(VaultItem::as_returning())
+            .get_result(conn)?;
+        mark_photos_deleted(conn, &[item.photo_id])?;
+        diesel::update(photo_table::table.find(item.photo_id))
+            .set(&PhotoPrivateFields::default())
+            .execute(conn)?;
+        tag::replace_photo_tags(conn, item.photo_id, &[])?;
+        Ok(item)
+    })
+}
+
+/// 删除保险箱照片，并恢复照片
+/// - metadata 移入时清除的照片信息【旧版本移入的照片没有】
+pub fn remove_vault_item(
+    connection: &mut SqliteConnection,
+    item: &VaultItem,
+    metadata: Option<&VaultMetadata>,
+) -> Result<()> {
+    connection.transaction(|conn| {
+        diesel::delete(vault_items::table.find(item.id)).execute(conn)?;
+        mark_photos_restored(conn, &[item.photo_id])?;
+        if let Some(metadata) = metadata {
+            diesel::update(photo_table::table.find(item.photo_id))
+                .set(&metadata.fields)
+                .execute(conn)?;
+            // 移入期间被删除的标签不再恢复
+            let mut tag_ids = Vec::new();
+            for tag_id in &metadata.tag_ids {
+                if tag::tag_exists(conn, *tag_id)? {
+                    tag_ids.push(*tag_id);
+                }
+            }
+            tag::add_photo_tags(conn, item.photo_id, &tag_ids)?;
+        }
+        Ok(())
+    })
+}
+
+/// 获取保险箱中的照片【按移入时间从新到旧】
+pub fn list_vault_items(connection: &mut SqliteConnection) -> Result<Vec<(VaultItem, Photo)>> {
+    let rows = vault_items::table
+        .inner_join(photo_table::table.on(photo_table::id.eq(vault_items::photo_id)))
+        .order((vault_items::create_time.desc(), vault_items::id.desc()))
+        .select((VaultItem::as_select(), Photo::as_select()))
+        .load(connection)?;
+    Ok(rows)
+}
+
+/// 获取保险箱照片
+pub fn get_vault_item(
+    connection: &mut SqliteConnection,
+    item_id: i32,
+) -> Result<(VaultItem, Photo)> {
+    vault_items::table
+        .inner_join(photo_table::table.on(photo_table::id.eq(vault_items::photo_id)))
+        .filter(vault_items::id.eq(item_id))
+        .select((VaultItem::as_select(), Photo::as_select()))
+        .first(connection)
+        .optional()?
+        .ok_or_else(|| anyhow!("保险箱中没有照片 {}!", item_id))
+}
+
+/// 保险箱中的照片数量
+pub fn count_vault_items(connection: &mut SqliteConnection) -> Result<i64> {
+    Ok(vault_items::table.count().get_result(connection)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::photo::NewPhoto;
+    use crate::storage::connection::test_connection;
+    use crate::storage::photo_table::bulk_upsert;
+
+    #[test]
+    fn test_vault_item_scrub_and_restore() {
+        let mut conn = test_connection();
+        let photo = NewPhoto {
+            img_path: "/photos".to_string(),
+            img_name: "a.jpg".to_string(),
+            img_path_raw: None,
+            hash: "a".to_string(),
+            width: 4000,
+            height: 3000,
+            aspect_ratio: 1.33,
+            file_size: 1024,
+            format: "image/jpeg".to_string(),
+            validation_level: 0,
+            is_animated: false,
+            frame_count: None,
+            animation_duration: None,
+            create_time: 0,
+            update_time: 0,
+        };
+        let photo = bulk_upsert(&mut conn, vec![(photo, None)])
+            .unwrap()
+            .remove(0);
+        let fields = PhotoPrivateFields {
+            caption: Some("生日".to_string()),
+            gps_info: Some("31.23,121.47".to_string()),
+            location_name: Some("上海".to_string()),
+            ..Default::default()
+        };
+        diesel::update(photo_table::table.find(photo.id))
+            .set(&fields)
+            .execute(&mut conn)
+            .unwrap();
+        let tag = tag::insert_tag(&mut conn, "家人", None).unwrap();
+        tag::add_photo_tags(&mut conn, photo.id, &[tag.id]).unwrap();
+        let metadata = VaultMetadata {
+            fields: fields.clone(),
+            tag_ids: vec![tag.id],
+        };
+
+        let item = insert_vault_item(
+            &mut conn,
+            NewVaultItem {
+                photo_id: photo.id,
+                file_name: "x.bin".to_string(),
+                thumbnail_name: None,
+                file_size: 1024,
+                create_time: 0,
+            },
+        )
+        .unwrap();
+        // 数据库中不再保留说明、位置和标签
+        let scrubbed = photo_table::table
+            .find(photo.id)
+            .first::<Photo>(&mut conn)
+            .unwrap();
+        assert!(scrubbed.is_delete);
+        assert_eq!(
+            PhotoPrivateFields::from_photo(&scrubbed),
+            PhotoPrivateFields::default()
+        );
+        assert!(tag::get_photo_tag_ids(&mut conn, photo.id)
+            .unwrap()
+            .is_empty());
+
+        remove_vault_item(&mut conn, &item, Some(&metadata)).unwrap();
+        let restored = photo_table::table
+            .find(photo.id)
+            .first::<Photo>(&mut conn)
+            .unwrap();
+        assert!(!restored.is_delete);
+        assert_eq!(PhotoPrivateFields::from_photo(&restored), fields);
+        assert_eq!(
+            tag::get_photo_tag_ids(&mut conn, photo.id).unwrap(),
+            vec![tag.id]
+        );
+        assert_eq!(count_vault_items(&mut conn).unwrap(), 0);
+    }
+}
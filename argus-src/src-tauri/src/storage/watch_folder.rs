@@ -0,0 +1,111 @@
+use crate::models::watch_folder::{NewWatchFolder, NewWatchFolderImport, WatchFolderRecord};
+use crate::storage::schema::{watch_folder_imports, watch_folders};
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+
+/// 获取所有监视文件夹
+pub fn get_all_folders(connection: &mut SqliteConnection) -> Result<Vec<WatchFolderRecord>> {
+    let results = watch_folders::table
+        .order(watch_folders::id.asc())
+        .select(WatchFolderRecord::as_select())
+        .load(connection)?;
+    Ok(results)
+}
+
+/// 获取启用的监视文件夹
+pub fn get_enabled_folders(connection: &mut SqliteConnection) -> Result<Vec<WatchFolderRecord>> {
+    let results = watch_folders::table
+        .filter(watch_folders::is_enable.eq(true))
+        .order(watch_folders::id.asc())
+        .select(WatchFolderRecord::as_select())
+        .load(connection)?;
+    Ok(results)
+}
+
+/// 按 ID 查找监视文件夹
+pub fn get_folder(connection: &mut SqliteConnection, id: i32) -> Result<Option<WatchFolderRecord>> {
+    let result = watch_folders::table
+        .filter(watch_folders::id.eq(id))
+        .select(WatchFolderRecord::as_select())
+        .first(connection)
+        .optional()?;
+    Ok(result)
+}
+
+/// 新增监视文件夹
+pub fn insert_folder(
+    connection: &mut SqliteConnection,
+    item: &NewWatchFolder,
+) -> Result<WatchFolderRecord> {
+    let folder = diesel::insert_into(watch_folders::table)
+        .values((item, watch_folders::create_time.eq(item.update_time)))
+        .returning(WatchFolderRecord::as_returning())
+        .get_result(connection)?;
+    Ok(folder)
+}
+
+/// 更新监视文件夹
+pub fn update_folder(
+    connection: &mut SqliteConnection,
+    id: i32,
+    item: &NewWatchFolder,
+) -> Result<WatchFolderRecord> {
+    let folder = diesel::update(watch_folders::table.filter(watch_folders::id.eq(id)))
+        .set(item)
+        .returning(WatchFolderRecord::as_returning())
+        .get_result(connection)
+        .optional()?;
+    folder.ok_or_else(|| anyhow!("监视文件夹 {} 不存在!", id))
+}
+
+/// 删除监视文件夹【同时删除导入记录】
+pub fn delete_folder(connection: &mut SqliteConnection, id: i32) -> Result<()> {
+    connection.transaction(|conn| {
+        let rows =
+            diesel::delete(watch_folders::table.filter(watch_folders::id.eq(id))).execute(conn)?;
+        if rows == 0 {
+            return Err(anyhow!("监视文件夹 {} 不存在!", id));
+        }
+        diesel::delete(watch_folder_imports::table.filter(watch_folder_imports::folder_id.eq(id)))
+            .execute(conn)?;
+        Ok(())
+    })
+}
+
+/// 文件是否已经导入过【路径相同且大小没有变化】
+pub fn is_source_imported(
+    connection: &mut SqliteConnection,
+    folder_id: i32,
+    source_path: &str,
+    file_size: i64,
+) -> Result<bool> {
+    let count: i64 = watch_folder_imports::table
+        .filter(watch_folder_imports::folder_id.eq(folder_id))
+        .filter(watch_folder_imports::source_path.eq(source_path))
+        .filter(watch_folder_imports::file_size.eq(file_size))
+        .count()
+        .get_result(connection)?;
+    Ok(count > 0)
+}
+
+/// 相同内容的文件是否已经从监视文件夹导入过
+pub fn is_hash_imported(
+    connection: &mut SqliteConnection,
+    folder_id: i32,
+    hash: &str,
+) -> Result<bool> {
+    let count: i64 = watch_folder_imports::table
+        .filter(watch_folder_imports::folder_id.eq(folder_id))
+        .filter(watch_folder_imports::hash.eq(hash))
+        .count()
+        .get_result(connection)?;
+    Ok(count > 0)
+}
+
+/// 记录已导入的文件【同一路径已有记录时更新】
+pub fn record_import(connection: &mut SqliteConnection, item: &NewWatchFolderImport) -> Result<()> {
+    diesel::replace_into(watch_folder_imports::table)
+        .values(item)
+        .execute(connection)?;
+    Ok(())
+}
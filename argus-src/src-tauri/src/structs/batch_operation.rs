@@ -0,0 +1,71 @@
+use crate::services::photo_file_service::PhotoRelocation;
+use serde::{Deserialize, Serialize};
+
+/// 批量操作【对选中的每张照片执行】
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum BatchOperation {
+    /// 添加标签
+    AddTag { tag_id: i32 },
+    /// 移除标签
+    RemoveTag { tag_id: i32 },
+    /// 修改评分【为空表示清除评分】
+    SetRating { rating: Option<i32> },
+    /// 加入相册
+    AddAlbum { album_id: i32 },
+    /// 移动到指定文件夹【不存在时自动创建】
+    Move { dest_dir: String },
+}
+
+/// 批量操作进度
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchApplyProgress {
+    /// 照片数量
+    pub total: usize,
+    /// 已处理的照片数量
+    pub done: usize,
+    /// 是否已结束【成功或失败】
+    pub finished: bool,
+    /// 失败原因【全部操作已回滚】
+    pub error: Option<String>,
+}
+
+/// 批量操作结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchApplySummary {
+    /// 照片数量
+    pub photos: usize,
+    /// 实际修改的记录数【已有的标签、相册和相同的评分不计入】
+    pub changed: usize,
+    /// 移动的照片
+    pub relocations: Vec<PhotoRelocation>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operation_json() {
+        let operations: Vec<BatchOperation> = serde_json::from_str(
+            r#"[{"type":"addTag","tagId":1},{"type":"setRating","rating":null},{"type":"move","destDir":"/photos/2025"}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            operations,
+            vec![
+                BatchOperation::AddTag { tag_id: 1 },
+                BatchOperation::SetRating { rating: None },
+                BatchOperation::Move {
+                    dest_dir: "/photos/2025".to_string()
+                },
+            ]
+        );
+    }
+}
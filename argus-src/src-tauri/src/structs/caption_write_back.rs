@@ -0,0 +1,37 @@
+use crate::structs::config::sys_config;
+use serde::{Deserialize, Serialize};
+
+/// 照片说明修改后写回的位置
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptionWriteBack {
+    /// 只保存到数据库
+    #[serde(rename = "none")]
+    None,
+    /// 写入原图（ImageDescription、XMP dc:description）
+    #[serde(rename = "file")]
+    File,
+    /// 写入同名 `.xmp` 附属文件，不修改原图
+    #[serde(rename = "sidecar")]
+    Sidecar,
+}
+
+impl CaptionWriteBack {
+    /// 读取配置，配置无效时不写回
+    pub fn from_config() -> CaptionWriteBack {
+        sys_config()
+            .caption_write_back
+            .as_deref()
+            .and_then(CaptionWriteBack::from_str)
+            .unwrap_or(CaptionWriteBack::None)
+    }
+
+    /// 从配置字符串转换
+    pub fn from_str(input: &str) -> Option<CaptionWriteBack> {
+        match input.trim().to_lowercase().as_str() {
+            "none" => Some(CaptionWriteBack::None),
+            "file" => Some(CaptionWriteBack::File),
+            "sidecar" => Some(CaptionWriteBack::Sidecar),
+            _ => None,
+        }
+    }
+}
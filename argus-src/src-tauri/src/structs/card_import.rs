@@ -0,0 +1,95 @@
+use crate::structs::remote_storage::RemoteUploadSummary;
+use crate::utils::volume_util::VolumeInfo;
+use serde::{Deserialize, Serialize};
+
+/// 插入的存储卡（包含 DCIM 文件夹的可移动卷）
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CameraCard {
+    /// 卷信息
+    pub volume: VolumeInfo,
+    /// DCIM 文件夹
+    pub dcim_path: String,
+    /// 文件数量
+    pub file_count: usize,
+    /// 文件总大小（字节）
+    pub total_bytes: u64,
+}
+
+/// 存储卡中的文件
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CardFile {
+    /// 文件路径
+    pub path: String,
+    /// 文件名
+    pub name: String,
+    /// 相对 DCIM 的文件夹，如 `100CANON`
+    pub folder: String,
+    /// 文件大小（字节）
+    pub size: u64,
+    /// 修改时间（时间戳）
+    pub modified_time: i64,
+    /// 是否为支持的图片
+    pub is_image: bool,
+}
+
+/// 单个文件的导入结果
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CardImportStatus {
+    /// 已复制并校验通过
+    Verified,
+    /// 目标文件夹已有内容相同的文件，未复制
+    Skipped,
+    /// 复制或校验失败【不会留下不完整的文件】
+    Failed,
+}
+
+/// 单个文件的导入结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CardImportItem {
+    /// 源文件
+    pub source: String,
+    /// 目标文件【失败时为空】
+    pub dest: Option<String>,
+    pub status: CardImportStatus,
+    /// 源文件的 SHA-256【读取失败时为空】
+    pub hash: Option<String>,
+    /// 失败原因
+    pub error: Option<String>,
+}
+
+/// 存储卡导入结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CardImportReport {
+    pub items: Vec<CardImportItem>,
+    /// 复制并校验通过的数量
+    pub verified: usize,
+    /// 已存在而跳过的数量
+    pub skipped: usize,
+    /// 失败的数量
+    pub failed: usize,
+    /// 复制的字节数
+    pub bytes_copied: u64,
+    /// 存储卡 DCIM 文件夹中的所有文件都已在目标文件夹中校验通过（包括之前导入过的），可以放心格式化存储卡
+    pub safe_to_format: bool,
+    /// 是否已弹出存储卡
+    pub ejected: bool,
+    /// 上传到远程存储的结果【没有选择远程存储时为空】
+    pub remote_upload: Option<RemoteUploadSummary>,
+}
+
+/// 存储卡导入进度
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CardImportProgressMsg {
+    /// 已处理的文件数量
+    pub current: usize,
+    /// 文件总数
+    pub total: usize,
+    /// 当前文件
+    pub file: String,
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// 校验清单导出结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecksumExportSummary {
+    /// 清单文件路径
+    pub path: String,
+    /// 写入的文件数量
+    pub file_count: usize,
+}
+
+/// 校验清单验证结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecksumVerifyReport {
+    /// 清单中的文件数量
+    pub total: usize,
+    /// 校验通过的数量
+    pub passed: usize,
+    /// 内容不一致的文件
+    pub mismatched: Vec<String>,
+    /// 不存在或无法读取的文件
+    pub missing: Vec<String>,
+    /// 格式错误的行号【从 1 开始】
+    pub invalid_lines: Vec<usize>,
+}
@@ -1,11 +1,22 @@
 use crate::conf;
 use crate::conf::{Conf, CONF_DEFAULT};
-use crate::constant::DEFAULT_PROFILE_NAME;
-use crate::utils::file_util;
-use crate::utils::file_util::{create_folder, get_root_folder};
-use crate::utils::json_util::JsonUtil;
+use crate::constant::{
+    DEFAULT_PROFILE_NAME, DIRECTORY_LEVEL_RANGE, THUMBNAIL_QUALITY_RANGE, THUMBNAIL_SPEED_RANGE,
+};
+use crate::i18n::Locale;
+use crate::structs::caption_write_back::CaptionWriteBack;
+use crate::structs::image_validation::ImageValidationLevel;
+use crate::structs::library;
+use crate::structs::maintenance_schedule::MaintenanceSchedule;
+use crate::structs::metadata_preset::MetadataPreset;
+use crate::structs::privacy_zone::PrivacyZone;
+use crate::structs::raw_jpeg_primary::RawJpegPrimary;
+use crate::structs::resize_backend::ResizeBackend;
+use crate::structs::thumbnail_encoding::ThumbnailFormat;
+use crate::utils::cron_util::CronSchedule;
+use crate::utils::{file_util, log_util};
+use crate::utils::file_util::create_folder;
 use anyhow::Result;
-use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::cmp::PartialEq;
 use std::collections::HashMap;
@@ -13,7 +24,9 @@ use std::env::temp_dir;
 use std::fs;
 use std::fs::File;
 use std::io::Write;
+use std::net::IpAddr;
 use std::path::Path;
+use std::sync::Arc;
 use toml::{from_str, to_string_pretty};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -41,6 +54,93 @@ pub struct Config {
     /// Python 服务地址
     pub python_service_path: Option<String>,
 
+    /// 导入时图像校验级别【none、header、full】
+    pub image_validation_level: Option<String>,
+
+    /// 界面语言【zh-CN、en-US】
+    pub locale: Option<String>,
+
+    /// 扫描时排除的文件、文件夹【gitignore 语法，如 `@eaDir`、`*.tmp`】
+    pub scan_exclude_patterns: Option<Vec<String>>,
+
+    /// 自动备份保存路径【为空时使用程序目录下的 backup 文件夹】
+    pub backup_path: Option<String>,
+    /// 自动备份间隔（小时）【0 表示不自动备份】
+    pub backup_interval_hours: Option<u32>,
+    /// 自动备份保留数量
+    pub backup_keep_count: Option<u32>,
+    /// 备份时是否包含配置文件
+    pub backup_include_config: Option<bool>,
+
+    /// 照片说明修改后写回的位置【none、file、sidecar】
+    pub caption_write_back: Option<String>,
+
+    /// 编辑副本的文件名后缀【自动堆叠时用于匹配原图，如 `_edit`】
+    pub stack_edit_suffixes: Option<Vec<String>>,
+
+    /// 启动时校验照片文件完整性
+    pub integrity_check_on_startup: Option<bool>,
+
+    /// 每次校验的照片数量【随机抽取，0 表示全部】
+    pub integrity_check_sample: Option<u32>,
+
+    /// 默认日志级别【trace、debug、info、warn、error、off】
+    pub log_level: Option<String>,
+
+    /// 启动局域网 HTTP 服务【需要编译时开启 http-server 功能】
+    pub http_server_enabled: Option<bool>,
+
+    /// HTTP 服务监听地址【默认只允许本机访问，允许局域网访问时设置为 `0.0.0.0`】
+    pub http_server_address: Option<String>,
+
+    /// HTTP 服务端口
+    pub http_server_port: Option<u16>,
+
+    /// HTTP 服务访问令牌【为空时启动服务时自动生成】
+    pub http_server_token: Option<String>,
+
+    /// 元数据预设（作者、版权、署名）
+    pub metadata_presets: Option<Vec<MetadataPreset>>,
+
+    /// 隐私区域（导出时模糊或删除区域内照片的位置）
+    pub privacy_zones: Option<Vec<PrivacyZone>>,
+
+    /// 逆地理编码服务地址（Nominatim 兼容的 reverse 接口，为空时不使用）【会把地点中心坐标发送到该服务】
+    pub reverse_geocode_url: Option<String>,
+
+    /// 定时维护计划（重新扫描、缓存清理、数据库优化、备份、完整性抽检）
+    pub maintenance_schedules: Option<Vec<MaintenanceSchedule>>,
+
+    /// 缩略图缩放实现（image：image 库，simd：SIMD 加速，gpu：显卡计算）
+    pub resize_backend: Option<String>,
+
+    /// 缩略图格式（jpeg、webp、avif）【修改后新生成的缩略图使用新格式】
+    pub thumbnail_format: Option<String>,
+
+    /// 缩略图编码质量（1 - 100）
+    pub thumbnail_quality: Option<u8>,
+
+    /// 缩略图编码速度（1 - 10，越小压缩越好、越慢）【只用于 WebP、AVIF】
+    pub thumbnail_speed: Option<u8>,
+
+    /// 截图、界面、图形类图片的缩略图使用无损 WebP【只在缩略图格式为 WebP 时生效】
+    pub thumbnail_lossless_graphics: Option<bool>,
+
+    /// 缩略图缓存、导入目标所在磁盘需保留的最小剩余空间（MB），0 表示不检查
+    pub min_free_space_mb: Option<u64>,
+
+    /// 剩余空间低于最小值时拒绝生成缩略图和导入，关闭后只发出提醒
+    pub low_disk_space_refuse: Option<bool>,
+
+    /// 把图像存储路径的文件夹结构同步为只读相册，文件夹变化时自动更新
+    pub mirror_folder_albums: Option<bool>,
+
+    /// 扫描时跳过云盘占位文件（OneDrive、iCloud、Dropbox 的仅云端文件），不下载、不计算 Hash，记录为仅云端文件
+    pub skip_cloud_placeholders: Option<bool>,
+
+    /// RAW+JPEG 配对时缩略图、预览优先使用的图像【jpeg 同名 JPEG、raw 内嵌预览图】
+    pub raw_jpeg_primary: Option<String>,
+
     #[serde(flatten)] // 收集多余的字段
     extra: HashMap<String, String>,
 }
@@ -61,11 +161,205 @@ impl Config {
             time_basic_fmt: Some(CONF_DEFAULT.time_basic_fmt.clone()),
             directory_level: Some(CONF_DEFAULT.directory_level.clone()),
             python_service_path: Some(CONF_DEFAULT.python_service_path.clone()),
+            image_validation_level: Some(CONF_DEFAULT.image_validation_level.clone()),
+            locale: Some(CONF_DEFAULT.locale.clone()),
+            scan_exclude_patterns: Some(CONF_DEFAULT.scan_exclude_patterns.clone()),
+            backup_path: Some(CONF_DEFAULT.backup_path.clone()),
+            backup_interval_hours: Some(CONF_DEFAULT.backup_interval_hours),
+            backup_keep_count: Some(CONF_DEFAULT.backup_keep_count),
+            backup_include_config: Some(CONF_DEFAULT.backup_include_config),
+            caption_write_back: Some(CONF_DEFAULT.caption_write_back.clone()),
+            stack_edit_suffixes: Some(CONF_DEFAULT.stack_edit_suffixes.clone()),
+            integrity_check_on_startup: Some(CONF_DEFAULT.integrity_check_on_startup),
+            integrity_check_sample: Some(CONF_DEFAULT.integrity_check_sample),
+            log_level: Some(CONF_DEFAULT.log_level.clone()),
+            http_server_enabled: Some(CONF_DEFAULT.http_server_enabled),
+            http_server_address: Some(CONF_DEFAULT.http_server_address.clone()),
+            http_server_port: Some(CONF_DEFAULT.http_server_port),
+            http_server_token: Some(CONF_DEFAULT.http_server_token.clone()),
+            metadata_presets: Some(CONF_DEFAULT.metadata_presets.clone()),
+            privacy_zones: Some(CONF_DEFAULT.privacy_zones.clone()),
+            reverse_geocode_url: Some(CONF_DEFAULT.reverse_geocode_url.clone()),
+            maintenance_schedules: Some(CONF_DEFAULT.maintenance_schedules.clone()),
+            resize_backend: Some(CONF_DEFAULT.resize_backend.clone()),
+            thumbnail_format: Some(CONF_DEFAULT.thumbnail_format.clone()),
+            thumbnail_quality: Some(CONF_DEFAULT.thumbnail_quality),
+            thumbnail_speed: Some(CONF_DEFAULT.thumbnail_speed),
+            thumbnail_lossless_graphics: Some(CONF_DEFAULT.thumbnail_lossless_graphics),
+            min_free_space_mb: Some(CONF_DEFAULT.min_free_space_mb),
+            low_disk_space_refuse: Some(CONF_DEFAULT.low_disk_space_refuse),
+            mirror_folder_albums: Some(CONF_DEFAULT.mirror_folder_albums),
+            skip_cloud_placeholders: Some(CONF_DEFAULT.skip_cloud_placeholders),
+            raw_jpeg_primary: Some(CONF_DEFAULT.raw_jpeg_primary.clone()),
             extra: HashMap::new(),
         }
     }
 }
 
+/// 配置项校验问题
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingIssue {
+    /// 配置项
+    pub key: String,
+    /// 配置文件中的值
+    pub value: String,
+    /// 问题说明【无效的值已替换为默认值】
+    pub message: String,
+}
+
+impl Config {
+    /// 校验配置项，无效的值替换为默认值并返回问题列表
+    pub fn validate(&mut self) -> Vec<SettingIssue> {
+        let mut issues = Vec::new();
+        let mut report = |key: &str, value: String, message: String| {
+            issues.push(SettingIssue {
+                key: key.to_string(),
+                value,
+                message,
+            })
+        };
+
+        if let Some(level) = self.directory_level {
+            if !DIRECTORY_LEVEL_RANGE.contains(&level) {
+                report(
+                    "directory_level",
+                    level.to_string(),
+                    format!(
+                        "目录分级层数必须在 {} ~ {} 之间",
+                        DIRECTORY_LEVEL_RANGE.start(),
+                        DIRECTORY_LEVEL_RANGE.end()
+                    ),
+                );
+                self.directory_level = Some(CONF_DEFAULT.directory_level);
+            }
+        }
+        if self.time_basic_fmt.as_deref().is_some_and(|x| x.trim().is_empty()) {
+            report("time_basic_fmt", String::new(), "时间格式不能为空".to_string());
+            self.time_basic_fmt = Some(CONF_DEFAULT.time_basic_fmt.clone());
+        }
+        if self.database_name.as_deref().is_some_and(|x| x.trim().is_empty()) {
+            report("database_name", String::new(), "数据库名称不能为空".to_string());
+            self.database_name = Some(CONF_DEFAULT.database_name.clone());
+        }
+        if let Some(value) = self.image_validation_level.clone() {
+            if ImageValidationLevel::from_str(&value).is_none() {
+                report(
+                    "image_validation_level",
+                    value,
+                    "图像校验级别只能是 none、header、full".to_string(),
+                );
+                self.image_validation_level = Some(CONF_DEFAULT.image_validation_level.clone());
+            }
+        }
+        if let Some(value) = self.locale.clone() {
+            if Locale::from_str(&value).is_none() {
+                report("locale", value, "不支持的界面语言".to_string());
+                self.locale = Some(CONF_DEFAULT.locale.clone());
+            }
+        }
+        if let Some(value) = self.caption_write_back.clone() {
+            if CaptionWriteBack::from_str(&value).is_none() {
+                report(
+                    "caption_write_back",
+                    value,
+                    "照片说明写回位置只能是 none、file、sidecar".to_string(),
+                );
+                self.caption_write_back = Some(CONF_DEFAULT.caption_write_back.clone());
+            }
+        }
+        if let Some(value) = self.raw_jpeg_primary.clone() {
+            if RawJpegPrimary::from_str(&value).is_none() {
+                report(
+                    "raw_jpeg_primary",
+                    value,
+                    "RAW+JPEG 配对的原图只能是 jpeg、raw".to_string(),
+                );
+                self.raw_jpeg_primary = Some(CONF_DEFAULT.raw_jpeg_primary.clone());
+            }
+        }
+        if let Some(value) = self.resize_backend.clone() {
+            if ResizeBackend::from_str(&value).is_none() {
+                report(
+                    "resize_backend",
+                    value,
+                    "缩略图缩放实现只能是 image、simd".to_string(),
+                );
+                self.resize_backend = Some(CONF_DEFAULT.resize_backend.clone());
+            }
+        }
+        if let Some(value) = self.thumbnail_format.clone() {
+            if ThumbnailFormat::from_str(&value).is_none() {
+                report(
+                    "thumbnail_format",
+                    value,
+                    "缩略图格式只能是 jpeg、webp、avif".to_string(),
+                );
+                self.thumbnail_format = Some(CONF_DEFAULT.thumbnail_format.clone());
+            }
+        }
+        if let Some(value) = self.thumbnail_quality {
+            if !THUMBNAIL_QUALITY_RANGE.contains(&value) {
+                report(
+                    "thumbnail_quality",
+                    value.to_string(),
+                    "缩略图编码质量必须在 1 ~ 100 之间".to_string(),
+                );
+                self.thumbnail_quality = Some(CONF_DEFAULT.thumbnail_quality);
+            }
+        }
+        if let Some(value) = self.thumbnail_speed {
+            if !THUMBNAIL_SPEED_RANGE.contains(&value) {
+                report(
+                    "thumbnail_speed",
+                    value.to_string(),
+                    "缩略图编码速度必须在 1 ~ 10 之间".to_string(),
+                );
+                self.thumbnail_speed = Some(CONF_DEFAULT.thumbnail_speed);
+            }
+        }
+        if let Some(value) = self.log_level.clone() {
+            if log_util::parse_level(&value).is_none() {
+                report(
+                    "log_level",
+                    value,
+                    "日志级别只能是 trace、debug、info、warn、error、off".to_string(),
+                );
+                self.log_level = Some(CONF_DEFAULT.log_level.clone());
+            }
+        }
+        if let Some(value) = self.http_server_address.clone() {
+            if value.parse::<IpAddr>().is_err() {
+                report(
+                    "http_server_address",
+                    value,
+                    "HTTP 服务监听地址必须是 IP 地址".to_string(),
+                );
+                self.http_server_address = Some(CONF_DEFAULT.http_server_address.clone());
+            }
+        }
+        if self.http_server_port == Some(0) {
+            report("http_server_port", "0".to_string(), "HTTP 服务端口不能为 0".to_string());
+            self.http_server_port = Some(CONF_DEFAULT.http_server_port);
+        }
+        if self.backup_keep_count == Some(0) {
+            report("backup_keep_count", "0".to_string(), "备份保留数量至少为 1".to_string());
+            self.backup_keep_count = Some(CONF_DEFAULT.backup_keep_count);
+        }
+        // 表达式无效的计划保留在配置中，但不再启用
+        for schedule in self.maintenance_schedules.iter_mut().flatten() {
+            if !schedule.enabled {
+                continue;
+            }
+            if let Err(e) = CronSchedule::parse(&schedule.cron) {
+                report("maintenance_schedules", schedule.cron.clone(), e.to_string());
+                schedule.enabled = false;
+            }
+        }
+        issues
+    }
+}
+
 impl PartialEq for Config {
     fn eq(&self, other: &Self) -> bool {
         self.database_default_link == other.database_default_link
@@ -77,43 +371,82 @@ impl PartialEq for Config {
             && self.time_basic_fmt == other.time_basic_fmt
             && self.directory_level == other.directory_level
             && self.python_service_path == other.python_service_path
+            && self.image_validation_level == other.image_validation_level
+            && self.locale == other.locale
+            && self.scan_exclude_patterns == other.scan_exclude_patterns
+            && self.backup_path == other.backup_path
+            && self.backup_interval_hours == other.backup_interval_hours
+            && self.backup_keep_count == other.backup_keep_count
+            && self.backup_include_config == other.backup_include_config
+            && self.caption_write_back == other.caption_write_back
+            && self.stack_edit_suffixes == other.stack_edit_suffixes
+            && self.integrity_check_on_startup == other.integrity_check_on_startup
+            && self.integrity_check_sample == other.integrity_check_sample
+            && self.log_level == other.log_level
+            && self.http_server_enabled == other.http_server_enabled
+            && self.http_server_address == other.http_server_address
+            && self.http_server_port == other.http_server_port
+            && self.http_server_token == other.http_server_token
+            && self.metadata_presets == other.metadata_presets
+            && self.privacy_zones == other.privacy_zones
+            && self.reverse_geocode_url == other.reverse_geocode_url
+            && self.maintenance_schedules == other.maintenance_schedules
+            && self.resize_backend == other.resize_backend
+            && self.thumbnail_format == other.thumbnail_format
+            && self.thumbnail_quality == other.thumbnail_quality
+            && self.thumbnail_speed == other.thumbnail_speed
+            && self.thumbnail_lossless_graphics == other.thumbnail_lossless_graphics
+            && self.min_free_space_mb == other.min_free_space_mb
+            && self.low_disk_space_refuse == other.low_disk_space_refuse
+            && self.mirror_folder_albums == other.mirror_folder_albums
+            && self.skip_cloud_placeholders == other.skip_cloud_placeholders
+            && self.raw_jpeg_primary == other.raw_jpeg_primary
             && self.extra == other.extra
     }
 }
 
-pub static SYS_CONFIG: Lazy<Config> = Lazy::new(|| {
-    let config = load_config().expect("系统配置文件加载失败! ");
-    // 构建必须参数
-    config
-});
+/// 获取当前照片库的配置
+pub fn sys_config() -> Arc<Config> {
+    library::current_library().config.clone()
+}
 
-/// 获取配置文件存放路径
-fn get_config_dir() -> String {
-    let root_dir = get_root_folder().expect("根路径获取失败! ");
-    root_dir.join(DEFAULT_PROFILE_NAME).display().to_string()
+/// 获取当前照片库的配置文件路径
+pub fn get_config_dir() -> String {
+    library::current_library().config_file().display().to_string()
 }
 
+/// 保存当前照片库的配置，并立即生效
 pub fn save_config(config: &Config) -> Result<()> {
-    let path = get_config_dir();
+    write_config(Path::new(&get_config_dir()), config)?;
+    library::set_current_config(config.clone());
+    Ok(())
+}
+
+fn write_config(path: &Path, config: &Config) -> Result<()> {
     let toml_string = to_string_pretty(&config)?;
     let mut file = File::create(path)?;
     file.write_all(toml_string.as_bytes())?;
     Ok(())
 }
 
-fn load_config() -> Result<Config> {
-    log::info!("load_config");
-    let path = get_config_dir();
-    log::info!("餐速回构建完毕!!!!!!!!!!!!!  ");
-    let str = JsonUtil::stringify(&path)?;
-    log::info!("{}  ", str);
+/// 读取照片库文件夹中的配置【缺少的配置项使用默认值补全并保存】
+/// - root 照片库文件夹
+pub fn load_config(root: &Path) -> Result<Config> {
+    Ok(load_config_checked(root)?.0)
+}
+
+/// 读取照片库文件夹中的配置，同时返回校验问题【无效的值替换为默认值并保存】
+/// - root 照片库文件夹
+pub fn load_config_checked(root: &Path) -> Result<(Config, Vec<SettingIssue>)> {
+    let path = root.join(DEFAULT_PROFILE_NAME).display().to_string();
+    log::info!("读取配置: {}", path);
     let mut data = conf::CONF.write().expect("write 报错了");
     let mut config;
 
     // 检查配置文件是否存在
     if file_util::file_exists(&*path) {
         // 如果文件存在，读取并反序列化
-        let config_str = fs::read_to_string(path)?;
+        let config_str = fs::read_to_string(&path)?;
 
         let conf: Config = from_str(&config_str).unwrap_or_else(|_| {
             log::info!("配置文件存在，但格式不正确，使用默认配置修复...");
@@ -124,7 +457,7 @@ fn load_config() -> Result<Config> {
     } else {
         // 如果文件不存在，创建一个默认配置文件
         let default_config = Config::default();
-        save_config(&default_config)?;
+        write_config(Path::new(&path), &default_config)?;
         config = default_config;
     }
     let config_clone = config.clone();
@@ -137,9 +470,7 @@ fn load_config() -> Result<Config> {
         .unwrap_or_else(|| data.image_cache_path.clone());
     // 缩略图保存路径
     let thumbnail_storage_path_merged = if config_clone.thumbnail_storage_path == None {
-        // 获取 exe 文件路径
-        let root_dir = get_root_folder()?;
-        let thumbnail_path = root_dir
+        let thumbnail_path = root
             .join(cache_path_merged.clone())
             .join(image_cache_path_merged.clone());
         let string = thumbnail_path.display().to_string();
@@ -148,7 +479,7 @@ fn load_config() -> Result<Config> {
     } else {
         config_clone.thumbnail_storage_path
     };
-    let merged_config = Config {
+    let mut merged_config = Config {
         database_default_link: Some(
             config_clone
                 .database_default_link
@@ -182,19 +513,206 @@ fn load_config() -> Result<Config> {
                 .python_service_path
                 .unwrap_or_else(|| data.python_service_path.clone()),
         ),
+        image_validation_level: Some(
+            config_clone
+                .image_validation_level
+                .unwrap_or_else(|| data.image_validation_level.clone()),
+        ),
+        locale: Some(
+            config_clone
+                .locale
+                .unwrap_or_else(|| data.locale.clone()),
+        ),
+        scan_exclude_patterns: Some(
+            config_clone
+                .scan_exclude_patterns
+                .unwrap_or_else(|| data.scan_exclude_patterns.clone()),
+        ),
+        backup_path: Some(
+            config_clone
+                .backup_path
+                .unwrap_or_else(|| data.backup_path.clone()),
+        ),
+        backup_interval_hours: Some(
+            config_clone
+                .backup_interval_hours
+                .unwrap_or(data.backup_interval_hours),
+        ),
+        backup_keep_count: Some(
+            config_clone
+                .backup_keep_count
+                .unwrap_or(data.backup_keep_count),
+        ),
+        backup_include_config: Some(
+            config_clone
+                .backup_include_config
+                .unwrap_or(data.backup_include_config),
+        ),
+        caption_write_back: Some(
+            config_clone
+                .caption_write_back
+                .unwrap_or_else(|| data.caption_write_back.clone()),
+        ),
+        stack_edit_suffixes: Some(
+            config_clone
+                .stack_edit_suffixes
+                .unwrap_or_else(|| data.stack_edit_suffixes.clone()),
+        ),
+        integrity_check_on_startup: Some(
+            config_clone
+                .integrity_check_on_startup
+                .unwrap_or(data.integrity_check_on_startup),
+        ),
+        integrity_check_sample: Some(
+            config_clone
+                .integrity_check_sample
+                .unwrap_or(data.integrity_check_sample),
+        ),
+        log_level: Some(
+            config_clone
+                .log_level
+                .unwrap_or_else(|| data.log_level.clone()),
+        ),
+        http_server_enabled: Some(
+            config_clone
+                .http_server_enabled
+                .unwrap_or(data.http_server_enabled),
+        ),
+        http_server_address: Some(
+            config_clone
+                .http_server_address
+                .unwrap_or_else(|| data.http_server_address.clone()),
+        ),
+        http_server_port: Some(
+            config_clone
+                .http_server_port
+                .unwrap_or(data.http_server_port),
+        ),
+        http_server_token: Some(
+            config_clone
+                .http_server_token
+                .unwrap_or_else(|| data.http_server_token.clone()),
+        ),
+        metadata_presets: Some(
+            config_clone
+                .metadata_presets
+                .unwrap_or_else(|| data.metadata_presets.clone()),
+        ),
+        privacy_zones: Some(
+            config_clone
+                .privacy_zones
+                .unwrap_or_else(|| data.privacy_zones.clone()),
+        ),
+        reverse_geocode_url: Some(
+            config_clone
+                .reverse_geocode_url
+                .unwrap_or_else(|| data.reverse_geocode_url.clone()),
+        ),
+        maintenance_schedules: Some(
+            config_clone
+                .maintenance_schedules
+                .unwrap_or_else(|| data.maintenance_schedules.clone()),
+        ),
+        resize_backend: Some(
+            config_clone
+                .resize_backend
+                .unwrap_or_else(|| data.resize_backend.clone()),
+        ),
+        thumbnail_format: Some(
+            config_clone
+                .thumbnail_format
+                .unwrap_or_else(|| data.thumbnail_format.clone()),
+        ),
+        thumbnail_quality: Some(
+            config_clone
+                .thumbnail_quality
+                .unwrap_or(data.thumbnail_quality),
+        ),
+        thumbnail_speed: Some(
+            config_clone
+                .thumbnail_speed
+                .unwrap_or(data.thumbnail_speed),
+        ),
+        thumbnail_lossless_graphics: Some(
+            config_clone
+                .thumbnail_lossless_graphics
+                .unwrap_or(data.thumbnail_lossless_graphics),
+        ),
+        min_free_space_mb: Some(
+            config_clone
+                .min_free_space_mb
+                .unwrap_or(data.min_free_space_mb),
+        ),
+        low_disk_space_refuse: Some(
+            config_clone
+                .low_disk_space_refuse
+                .unwrap_or(data.low_disk_space_refuse),
+        ),
+        mirror_folder_albums: Some(
+            config_clone
+                .mirror_folder_albums
+                .unwrap_or(data.mirror_folder_albums),
+        ),
+        skip_cloud_placeholders: Some(
+            config_clone
+                .skip_cloud_placeholders
+                .unwrap_or(data.skip_cloud_placeholders),
+        ),
+        raw_jpeg_primary: Some(
+            config_clone
+                .raw_jpeg_primary
+                .unwrap_or_else(|| data.raw_jpeg_primary.clone()),
+        ),
         extra: Default::default(),
     };
+    let issues = merged_config.validate();
+    for issue in &issues {
+        log::warn!("配置项 {} 的值 {:?} 无效: {}", issue.key, issue.value, issue.message);
+    }
     // 如果配置有变动，保存修复后的配置
     if config != merged_config {
         log::info!("保存修复后的配置...");
-        save_config(&merged_config)?;
-        return Ok(merged_config);
+        write_config(Path::new(&path), &merged_config)?;
+        return Ok((merged_config, issues));
     }
-    Ok(config)
+    Ok((config, issues))
 }
 
 /// 公开的初始化
 pub fn init_config() -> Config {
     log::info!("进入初始化");
-    SYS_CONFIG.clone()
+    (*sys_config()).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let mut config = Config::default();
+        assert!(config.validate().is_empty());
+
+        config.directory_level = Some(9);
+        config.locale = Some("fr".to_string());
+        config.backup_keep_count = Some(0);
+        let keys: Vec<String> = config.validate().into_iter().map(|x| x.key).collect();
+        assert_eq!(keys, vec!["directory_level", "locale", "backup_keep_count"]);
+        assert_eq!(config.directory_level, Some(CONF_DEFAULT.directory_level));
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn test_toml_presets() {
+        let mut config = Config::default();
+        config.metadata_presets = Some(vec![MetadataPreset {
+            id: "1".to_string(),
+            name: "CC-BY".to_string(),
+            copyright: Some("© 2024 Jane Doe, CC-BY".to_string()),
+            ..Default::default()
+        }]);
+        let text = to_string_pretty(&config).unwrap();
+        let parsed: Config = from_str(&text).unwrap();
+        assert_eq!(parsed.metadata_presets, config.metadata_presets);
+    }
 }
@@ -0,0 +1,12 @@
+use crate::models::photo::Photo;
+use serde::{Deserialize, Serialize};
+
+/// 文件夹、相册的封面
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Cover {
+    /// 封面照片
+    pub photo: Photo,
+    /// 是否为用户设置的封面【false 表示使用最新的照片】
+    pub is_custom: bool,
+}
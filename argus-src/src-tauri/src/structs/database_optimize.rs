@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// 数据库优化结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseOptimizeReport {
+    /// 优化前的数据库大小（字节，含 WAL 文件）
+    pub size_before: u64,
+    /// 优化后的数据库大小（字节，含 WAL 文件）
+    pub size_after: u64,
+    /// 优化前估算的可回收空间（字节，空闲页）
+    pub reclaimable_bytes: u64,
+    /// 是否执行了 VACUUM【磁盘剩余空间不足或无法查询时跳过】
+    pub vacuumed: bool,
+    /// PRAGMA optimize、ANALYZE 耗时（毫秒）
+    pub analyze_millis: u64,
+    /// VACUUM 耗时（毫秒）
+    pub vacuum_millis: u64,
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+/// 云盘服务【根据路径判断】
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CloudProvider {
+    OneDrive,
+    ICloud,
+    Dropbox,
+}
+
+impl CloudProvider {
+    /// 名称【与前端传入的名称相同】
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CloudProvider::OneDrive => "oneDrive",
+            CloudProvider::ICloud => "iCloud",
+            CloudProvider::Dropbox => "dropbox",
+        }
+    }
+}
+
+/// 访问问题类型
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AccessIssueKind {
+    /// 文件夹无法列出
+    UnreadableDir,
+    /// 文件无法读取
+    UnreadableFile,
+    /// 云盘占位文件【内容不在本地，读取时会触发下载】
+    CloudPlaceholder,
+}
+
+/// 访问问题
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessIssue {
+    pub kind: AccessIssueKind,
+    pub path: String,
+    /// 错误信息【占位文件为空】
+    pub error: Option<String>,
+}
+
+/// 扫描前的文件夹访问检查结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryAccessReport {
+    pub path: String,
+    /// 文件夹是否存在
+    pub exists: bool,
+    /// 文件夹是否可以列出
+    pub readable: bool,
+    /// 文件夹是否可写【移动、重命名照片和写入附属文件需要】
+    pub writable: bool,
+    /// 所在的云盘【不在云盘中时为空】
+    pub cloud_provider: Option<CloudProvider>,
+    /// 检查的文件夹数量
+    pub dirs_checked: usize,
+    /// 抽样检查的图片数量
+    pub files_checked: usize,
+    /// 无法列出的文件夹数量
+    pub unreadable_dirs: usize,
+    /// 无法读取的图片数量
+    pub unreadable_files: usize,
+    /// 云盘占位文件数量
+    pub cloud_placeholders: usize,
+    /// 问题示例【数量有上限】
+    pub issues: Vec<AccessIssue>,
+    /// 是否达到抽样上限【未检查全部文件】
+    pub truncated: bool,
+    /// 是否可以扫描【文件夹存在且可以列出】
+    pub can_scan: bool,
+}
+
+/// 下载仅云端文件的结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudHydrateSummary {
+    /// 下载完成的文件数量
+    pub hydrated: usize,
+    /// 下载后导入成功的数量
+    pub imported: usize,
+    /// 失败的文件及原因
+    pub failed: Vec<String>,
+}
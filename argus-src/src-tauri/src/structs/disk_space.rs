@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+/// 需要检查磁盘剩余空间的操作
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiskSpacePurpose {
+    /// 生成缩略图
+    #[serde(rename = "thumbnail")]
+    Thumbnail,
+    /// 导入照片
+    #[serde(rename = "import")]
+    Import,
+}
+
+/// 磁盘剩余空间不足提醒【`low-disk-space` 事件】
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LowDiskSpaceMsg {
+    pub purpose: DiskSpacePurpose,
+    /// 写入的目标路径
+    pub path: String,
+    /// 剩余空间（字节）
+    pub available: u64,
+    /// 本次操作需要写入的大小（字节）【缩略图为 0】
+    pub required: u64,
+    /// 需保留的最小剩余空间（字节）
+    pub min_free: u64,
+    /// 是否拒绝了本次操作【为 false 时只是提醒】
+    pub refused: bool,
+}
+
+/// 单个卷上照片库的空间占用
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeStorageUsage {
+    /// 卷唯一标识【卷离线时为空】
+    pub volume_id: Option<String>,
+    /// 挂载点【卷离线时为空】
+    pub mount_point: Option<String>,
+    /// 卷标
+    pub label: String,
+    /// 总空间（字节）
+    pub total_space: u64,
+    /// 剩余空间（字节）
+    pub available_space: u64,
+    /// 原图数量
+    pub originals_count: usize,
+    /// 原图大小（字节）
+    pub originals_bytes: u64,
+    /// 缩略图缓存大小（字节）
+    pub cache_bytes: u64,
+    /// 数据库大小（字节）【包括 WAL 文件】
+    pub database_bytes: u64,
+}
+
+/// 照片库的空间占用
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageBreakdown {
+    /// 按卷统计【原图所在卷离线时单独列出】
+    pub volumes: Vec<VolumeStorageUsage>,
+    pub originals_bytes: u64,
+    pub cache_bytes: u64,
+    pub database_bytes: u64,
+}
@@ -0,0 +1,24 @@
+use crate::utils::color_util;
+use serde::{Deserialize, Serialize};
+
+/// 照片主色
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DominantColor {
+    /// CIELAB 颜色（L*、a*、b*）
+    pub lab: [f32; 3],
+    /// 占比（0 - 1）
+    pub weight: f32,
+    /// 十六进制颜色，如 `#1A8C9E`【用于显示】
+    pub hex: String,
+}
+
+impl DominantColor {
+    pub fn new(lab: [f32; 3], weight: f32) -> DominantColor {
+        DominantColor {
+            lab,
+            weight,
+            hex: color_util::rgb_to_hex(color_util::lab_to_rgb(lab)),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+/// 内容完全相同的一组文件
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroup {
+    /// 文件内容的 SHA-256
+    pub hash: String,
+    /// 单个文件大小（字节）
+    pub size: u64,
+    /// 文件路径【第一个为保留的文件：数据库中记录的照片优先】
+    pub files: Vec<String>,
+    /// 全部替换为链接后可以释放的空间（字节）【已经互为硬链接的文件不计算】
+    pub reclaimable_bytes: u64,
+}
+
+/// 重复文件的替换方式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ReclaimMode {
+    /// 硬链接【所有文件共享同一份数据，原地修改其中一个会影响全部】
+    #[default]
+    HardLink,
+    /// reflink（写时复制）【只有 Btrfs、XFS、APFS 等文件系统支持，修改互不影响】
+    Reflink,
+}
+
+/// 撤销清单中的一项
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReclaimEntry {
+    /// 保留的文件
+    pub original: String,
+    /// 被替换为链接的文件
+    pub duplicate: String,
+    pub mode: ReclaimMode,
+    /// 文件大小（字节）
+    pub size: u64,
+    /// 替换前的修改时间（毫秒时间戳）【硬链接共享修改时间，撤销时恢复】
+    pub modified_time: Option<i64>,
+}
+
+/// 撤销清单
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReclaimManifest {
+    /// 创建时间（时间戳）
+    pub create_time: i64,
+    pub entries: Vec<ReclaimEntry>,
+}
+
+/// 重复文件替换结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReclaimReport {
+    /// 替换为链接的文件数量
+    pub linked: usize,
+    /// 已经是同一个文件而跳过的数量
+    pub skipped: usize,
+    /// 失败的文件及原因【失败的文件保持原样】
+    pub failed: Vec<String>,
+    /// 释放的空间（字节）
+    pub reclaimed_bytes: u64,
+    /// 撤销清单路径【没有替换任何文件时为空】
+    pub manifest_path: Option<String>,
+}
+
+/// 撤销替换的结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoReclaimReport {
+    /// 恢复为独立文件的数量
+    pub restored: usize,
+    /// 失败的文件及原因
+    pub failed: Vec<String>,
+}
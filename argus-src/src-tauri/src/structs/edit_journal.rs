@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 可撤销的元数据字段
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditField {
+    /// 评分
+    #[serde(rename = "rating")]
+    Rating,
+    /// 拍摄时间（时间戳）
+    #[serde(rename = "dateTimeOriginal")]
+    DateTimeOriginal,
+    /// GPS 信息（十进制坐标 JSON）
+    #[serde(rename = "gps")]
+    Gps,
+    /// 标签 ID 列表
+    #[serde(rename = "tags")]
+    Tags,
+    /// 照片说明
+    #[serde(rename = "caption")]
+    Caption,
+    /// 作者
+    #[serde(rename = "artist")]
+    Artist,
+    /// 版权
+    #[serde(rename = "copyright")]
+    Copyright,
+    /// 署名
+    #[serde(rename = "credit")]
+    Credit,
+    /// 备注（只保存在数据库中）
+    #[serde(rename = "notes")]
+    Notes,
+}
+
+impl EditField {
+    /// 作者、版权、署名写入文件时对应的 EXIF、IPTC、XMP 标签【其他字段为空】
+    pub fn credit_tags(&self) -> &'static [&'static str] {
+        match self {
+            EditField::Artist => &["EXIF:Artist", "IPTC:By-line", "XMP-dc:Creator"],
+            EditField::Copyright => &["EXIF:Copyright", "IPTC:CopyrightNotice", "XMP-dc:Rights"],
+            EditField::Credit => &["IPTC:Credit", "XMP-photoshop:Credit"],
+            _ => &[],
+        }
+    }
+}
+
+/// 单张照片单个字段的修改
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EditChange {
+    /// 照片 ID
+    pub photo_id: i32,
+    /// 修改的字段
+    pub field: EditField,
+    /// 修改前的值
+    pub before: Value,
+    /// 修改后的值
+    pub after: Value,
+    /// 是否写回原图【作者、版权、署名只在应用预设时选择写回才写入文件，撤销、重做时同样写回】
+    #[serde(default)]
+    pub write_back: bool,
+}
+
+impl EditChange {
+    /// 反向修改【撤销时使用】
+    pub fn reversed(&self) -> EditChange {
+        EditChange {
+            photo_id: self.photo_id,
+            field: self.field,
+            before: self.after.clone(),
+            after: self.before.clone(),
+            write_back: self.write_back,
+        }
+    }
+}
+
+/// 编辑记录摘要【返回给前端】
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EditSummary {
+    /// 编辑记录 ID
+    pub edit_id: i32,
+    /// 编辑描述
+    pub description: String,
+    /// 涉及的照片数量
+    pub photo_count: usize,
+    /// 是否已撤销
+    pub is_undone: bool,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// 重新分段事件的结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EventRefreshSummary {
+    /// 事件数量
+    pub events: usize,
+    /// 归入事件的照片数量
+    pub photos: usize,
+    /// 有拍摄时间但没有归入任何事件的照片数量
+    pub ungrouped: usize,
+    /// 通过逆地理编码命名的事件数量
+    pub geocoded: usize,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// 补全 EXIF 的结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExifBackfillSummary {
+    /// 检查的照片数量
+    pub checked: usize,
+    /// 写入 EXIF 的照片数量
+    pub filled: usize,
+    /// 原图无法读取 EXIF 的照片数量【已标记，不再重复读取】
+    pub unreadable: usize,
+    /// 原图不存在（卷离线等）而跳过的照片数量【下次补全时重试】
+    pub missing: usize,
+}
@@ -0,0 +1,179 @@
+use crate::models::photo::Photo;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// 可以导入的其他照片管理软件
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalLibrarySource {
+    /// Apple 照片（Photos.sqlite）
+    #[serde(rename = "applePhotos")]
+    ApplePhotos,
+    /// digiKam（digikam4.db）
+    #[serde(rename = "digikam")]
+    Digikam,
+}
+
+impl FromStr for ExternalLibrarySource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "applephotos" | "apple_photos" | "photos" => Ok(ExternalLibrarySource::ApplePhotos),
+            "digikam" => Ok(ExternalLibrarySource::Digikam),
+            _ => Err(anyhow!("不支持的照片库类型: {}", s)),
+        }
+    }
+}
+
+/// 从其他软件读取的照片信息
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExternalPhoto {
+    /// 文件在原软件中的位置
+    pub source_path: PathBuf,
+    /// 原始文件名
+    pub file_name: String,
+    /// 文件大小（字节）
+    pub file_size: Option<i64>,
+    /// 评分 1 ~ 5
+    pub rating: Option<i32>,
+    /// 所在相册路径，如 `旅行/日本`
+    pub albums: Vec<String>,
+    /// 关键字路径
+    pub keywords: Vec<String>,
+    /// 人物名称
+    pub faces: Vec<String>,
+}
+
+/// 导入结果统计
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalImportSummary {
+    /// 来源
+    pub source: ExternalLibrarySource,
+    /// 原软件中的照片数量
+    pub total: u32,
+    /// 匹配到的照片数量
+    pub matched: u32,
+    /// 其中通过文件 Hash 匹配的数量
+    pub matched_by_hash: u32,
+    /// 修改评分的照片数量
+    pub ratings_updated: u32,
+    /// 新建相册数
+    pub albums_created: u32,
+    /// 新加入相册的照片数
+    pub album_links: u32,
+    /// 新建标签数（含人物）
+    pub tags_created: u32,
+    /// 新添加的照片标签数
+    pub tag_links: u32,
+    /// 没有匹配到的文件
+    pub unmatched: Vec<String>,
+}
+
+impl ExternalImportSummary {
+    pub fn new(source: ExternalLibrarySource) -> ExternalImportSummary {
+        ExternalImportSummary {
+            source,
+            total: 0,
+            matched: 0,
+            matched_by_hash: 0,
+            ratings_updated: 0,
+            albums_created: 0,
+            album_links: 0,
+            tags_created: 0,
+            tag_links: 0,
+            unmatched: Vec::new(),
+        }
+    }
+}
+
+/// 把其他软件中的文件对应到已导入的照片
+///
+/// 依次按完整路径、文件 Hash、文件名 + 文件大小匹配，文件名匹配到多张照片时视为无法匹配
+#[derive(Debug, Default)]
+pub struct PhotoMatcher {
+    by_path: HashMap<String, i32>,
+    by_hash: HashMap<String, i32>,
+    by_name: HashMap<String, Vec<(i32, i64)>>,
+}
+
+impl PhotoMatcher {
+    pub fn new(photos: &[Photo]) -> PhotoMatcher {
+        let mut matcher = PhotoMatcher::default();
+        for photo in photos {
+            matcher
+                .by_path
+                .insert(path_key(&photo.full_path()), photo.id);
+            matcher
+                .by_hash
+                .entry(photo.hash.clone())
+                .or_insert(photo.id);
+            matcher
+                .by_name
+                .entry(photo.img_name.to_lowercase())
+                .or_default()
+                .push((photo.id, photo.file_size));
+        }
+        matcher
+    }
+
+    /// 按路径匹配
+    pub fn match_path(&self, photo: &ExternalPhoto) -> Option<i32> {
+        self.by_path.get(&path_key(&photo.source_path)).copied()
+    }
+
+    /// 按文件 Hash 匹配
+    pub fn match_hash(&self, hash: &str) -> Option<i32> {
+        self.by_hash.get(hash).copied()
+    }
+
+    /// 按文件名 + 文件大小匹配【没有文件大小时只按文件名】
+    pub fn match_name(&self, photo: &ExternalPhoto) -> Option<i32> {
+        let candidates: Vec<i32> = self
+            .by_name
+            .get(&photo.file_name.to_lowercase())?
+            .iter()
+            .filter(|(_, size)| photo.file_size.is_none_or(|x| x == *size))
+            .map(|(id, _)| *id)
+            .collect();
+        match candidates[..] {
+            [id] => Some(id),
+            _ => None,
+        }
+    }
+}
+
+/// 路径比较时使用的键【Windows 下不区分大小写】
+fn path_key(path: &std::path::Path) -> String {
+    let key = path.display().to_string().replace('\\', "/");
+    if cfg!(windows) {
+        key.to_lowercase()
+    } else {
+        key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_name() {
+        let mut matcher = PhotoMatcher::default();
+        matcher
+            .by_name
+            .insert("a.jpg".to_string(), vec![(1, 100), (2, 200)]);
+        let mut photo = ExternalPhoto {
+            file_name: "A.JPG".to_string(),
+            file_size: Some(200),
+            ..Default::default()
+        };
+        assert_eq!(matcher.match_name(&photo), Some(2));
+        // 同名文件无法区分
+        photo.file_size = None;
+        assert_eq!(matcher.match_name(&photo), None);
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// 文件夹相册同步结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderAlbumSyncSummary {
+    /// 是否开启了文件夹相册【关闭时删除所有文件夹相册】
+    pub enabled: bool,
+    /// 新增的相册数量
+    pub albums_created: u32,
+    /// 名称或层级变化的相册数量
+    pub albums_updated: u32,
+    /// 文件夹已不存在而删除的相册数量
+    pub albums_removed: u32,
+    /// 加入相册的照片数量
+    pub photos_linked: u32,
+    /// 移出相册的照片数量
+    pub photos_unlinked: u32,
+}
+
+/// 文件夹对应的相册
+#[derive(Debug, Clone, PartialEq)]
+pub struct FolderNode {
+    /// 相册名称【文件夹名称】
+    pub name: String,
+    /// 上级文件夹【为空表示图像存储路径】
+    pub parent: Option<String>,
+    /// 文件夹中的照片【不包含子文件夹】
+    pub photo_ids: Vec<i32>,
+}
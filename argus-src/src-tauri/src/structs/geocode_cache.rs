@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// 逆地理编码缓存的来源
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeocodeSource {
+    /// 逆地理编码服务【过期后重新请求】
+    #[serde(rename = "service")]
+    Service,
+    /// 导入的离线数据【不过期】
+    #[serde(rename = "offline")]
+    Offline,
+}
+
+impl GeocodeSource {
+    /// 数据库存储值
+    pub fn code(&self) -> i32 {
+        match self {
+            GeocodeSource::Service => 0,
+            GeocodeSource::Offline => 1,
+        }
+    }
+
+    /// 从数据库存储值转换
+    pub fn from_code(code: i32) -> GeocodeSource {
+        match code {
+            1 => GeocodeSource::Offline,
+            _ => GeocodeSource::Service,
+        }
+    }
+}
+
+/// 导入离线地名数据的结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GeocodeSeedSummary {
+    /// 导入的坐标数量
+    pub imported: usize,
+    /// 坐标或名称无效而跳过的行数
+    pub skipped: usize,
+}
@@ -1,7 +1,11 @@
+use crate::i18n::LocalizedMessage;
+use crate::structs::scan_report::ScanStatus;
 use crate::utils::img_util::ImageOperate;
+use crate::utils::scan_metrics_util::ScanMetrics;
 use once_cell::sync::Lazy;
 use serde;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Window};
 use tokio::sync::mpsc::Sender;
@@ -10,8 +14,6 @@ use tokio::sync::mpsc::Sender;
 pub static GLOBAL_EMIT_IS_INIT: Lazy<Arc<Mutex<bool>>> = Lazy::new(|| Arc::new(Mutex::new(false)));
 /// 后台处理任务是否已经开始
 pub static IMG_DISPOSE_IS_START: Lazy<Arc<tokio::sync::Mutex<bool>>> = Lazy::new(|| Arc::new(tokio::sync::Mutex::new(false)));
-/// 后台处理任务是否取消
-pub static IMG_DISPOSE_IS_CANCEL: Lazy<Arc<tokio::sync::Mutex<bool>>> = Lazy::new(|| Arc::new(tokio::sync::Mutex::new(false)));
 /// 全局触发实例
 pub static GLOBAL_EMIT_APP_HANDLE: Lazy<Arc<Mutex<Option<Sender<String>>>>> =
     Lazy::new(|| Arc::new(Mutex::new(None::<Sender<String>>)));
@@ -20,12 +22,46 @@ pub static GLOBAL_EMIT_APP_HANDLE: Lazy<Arc<Mutex<Option<Sender<String>>>>> =
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct LoadMsg{
+    /// 扫描任务 ID【区分同时进行的多个扫描】
+    pub task_id: String,
     /// 总任务数
     pub all_task:u32,
     /// 当前任务数（已完成）
     pub current_task:u32,
     /// 任务信息
     pub task_msg:String,
+    /// 扫描 ID【用于获取扫描报告】
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scan_id: Option<i32>,
+}
+
+/// 扫描过程中的报错信息
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanErrorMsg {
+    /// 扫描任务 ID
+    pub task_id: String,
+    /// 报错内容【字段直接展开，保持与原有消息格式一致】
+    #[serde(flatten)]
+    pub message: LocalizedMessage,
+}
+
+/// 扫描结束信息
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanFinishedMsg {
+    /// 扫描任务 ID
+    pub task_id: String,
+    /// 扫描 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scan_id: Option<i32>,
+    /// 扫描结束状态
+    pub status: ScanStatus,
+    /// 结束提示【完成或已取消】
+    pub message: LocalizedMessage,
+    /// 吞吐量统计
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<ScanMetrics>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -42,6 +78,44 @@ pub struct GlobalErrorMsg {
     /// 通知类型
     #[serde(rename = "type")] // 指定序列化/反序列化时的字段名为 "type"
     pub kind: GlobalErrorMsgTypeEnum,
+
+    /// 标题的消息 ID【前端可据此自行翻译】
+    #[serde(rename = "titleId", default, skip_serializing_if = "Option::is_none")]
+    pub title_id: Option<String>,
+
+    /// 消息的消息 ID
+    #[serde(rename = "msgId", default, skip_serializing_if = "Option::is_none")]
+    pub msg_id: Option<String>,
+
+    /// 消息模板参数
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub args: BTreeMap<String, String>,
+}
+
+impl GlobalErrorMsg {
+    /// 使用消息目录构建通知
+    /// - title_id 标题消息 ID
+    /// - msg_id 内容消息 ID
+    /// - args 模板参数（标题与内容共用）
+    pub fn localized(
+        title_id: &str,
+        msg_id: &str,
+        args: &[(&str, String)],
+        duration: i32,
+        kind: GlobalErrorMsgTypeEnum,
+    ) -> GlobalErrorMsg {
+        let title = LocalizedMessage::new(title_id, args);
+        let msg = LocalizedMessage::new(msg_id, args);
+        GlobalErrorMsg {
+            title: title.text,
+            msg: msg.text,
+            duration,
+            kind,
+            title_id: Some(title.id),
+            msg_id: Some(msg.id),
+            args: msg.args,
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -107,6 +181,9 @@ mod test {
             msg: "信息".to_string(),
             duration: 0,
             kind: GlobalErrorMsgTypeEnum::Success,
+            title_id: None,
+            msg_id: None,
+            args: Default::default(),
         };
         let result = JsonUtil::stringify(&gem).expect("序列化报错");
 
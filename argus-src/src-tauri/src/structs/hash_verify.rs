@@ -0,0 +1,95 @@
+use crate::structs::integrity_report::IntegrityIssueKind;
+use serde::{Deserialize, Serialize};
+
+/// Hash 校验任务状态
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum HashVerifyStatus {
+    /// 校验中
+    Running,
+    /// 已完成
+    Completed,
+    /// 失败
+    Failed,
+    /// 已取消
+    Cancelled,
+}
+
+/// Hash 不一致的照片
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HashMismatch {
+    pub photo_id: i32,
+    /// 文件路径
+    pub path: String,
+    pub kind: IntegrityIssueKind,
+    /// 导入时记录的 Hash
+    pub expected_hash: String,
+    /// 重新计算的 Hash【文件不存在或无法读取时为空】
+    pub actual_hash: Option<String>,
+}
+
+/// Hash 校验报告
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HashVerifyReport {
+    /// 任务 ID
+    pub job_id: String,
+    pub status: HashVerifyStatus,
+    /// 要校验的照片数量
+    pub total: usize,
+    /// 已校验的照片数量
+    pub checked: usize,
+    /// Hash 一致的照片数量
+    pub matched: usize,
+    /// 正在校验的文件
+    pub current_path: Option<String>,
+    /// Hash 不一致、不存在或无法读取的照片
+    pub mismatches: Vec<HashMismatch>,
+    /// 开始时间（时间戳）
+    pub start_time: i64,
+    /// 结束时间（时间戳）
+    pub end_time: Option<i64>,
+    /// 失败原因
+    pub error: Option<String>,
+}
+
+/// Hash 校验进度【每校验一张照片通知一次，只包含这张照片的结果】
+///
+/// 完整的不一致列表通过校验报告获取
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HashVerifyProgress {
+    /// 任务 ID
+    pub job_id: String,
+    pub status: HashVerifyStatus,
+    /// 要校验的照片数量
+    pub total: usize,
+    /// 已校验的照片数量
+    pub checked: usize,
+    /// Hash 一致的照片数量
+    pub matched: usize,
+    /// 不一致的照片数量
+    pub mismatched: usize,
+    /// 正在校验的文件
+    pub current_path: Option<String>,
+    /// 刚校验的照片不一致时的结果
+    pub mismatch: Option<HashMismatch>,
+}
+
+impl HashVerifyProgress {
+    /// 根据报告的当前状态生成进度
+    /// - mismatch 刚校验的照片不一致时的结果
+    pub fn new(report: &HashVerifyReport, mismatch: Option<HashMismatch>) -> HashVerifyProgress {
+        HashVerifyProgress {
+            job_id: report.job_id.clone(),
+            status: report.status,
+            total: report.total,
+            checked: report.checked,
+            matched: report.matched,
+            mismatched: report.mismatches.len(),
+            current_path: report.current_path.clone(),
+            mismatch,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::constant::{HISTOGRAM_BINS, HISTOGRAM_CLIP_TOLERANCE, HISTOGRAM_SAMPLE_PIXELS};
+use image::{DynamicImage, GenericImageView};
+use serde::{Deserialize, Serialize};
+
+/// 直方图摘要
+///
+/// 每个通道 64 组，数值为该组像素占采样像素的万分比
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistogramSummary {
+    pub red: Vec<u16>,
+    pub green: Vec<u16>,
+    pub blue: Vec<u16>,
+    /// 亮度（Rec.601）
+    pub luma: Vec<u16>,
+    /// 高光溢出（任一通道接近 255）的像素百分比
+    pub highlight_clipping: f32,
+    /// 暗部溢出（所有通道接近 0）的像素百分比
+    pub shadow_clipping: f32,
+}
+
+impl HistogramSummary {
+    /// 计算图像的直方图【像素较多时按间隔采样】
+    pub fn from_image(image: &DynamicImage) -> HistogramSummary {
+        let (width, height) = image.dimensions();
+        let total = width as u64 * height as u64;
+        let step = if total > HISTOGRAM_SAMPLE_PIXELS {
+            ((total as f64 / HISTOGRAM_SAMPLE_PIXELS as f64)
+                .sqrt()
+                .ceil() as u32)
+                .max(1)
+        } else {
+            1
+        };
+
+        let mut counts = [[0u64; 256]; 4];
+        let mut highlight = 0u64;
+        let mut shadow = 0u64;
+        let mut sampled = 0u64;
+        let high = 255 - HISTOGRAM_CLIP_TOLERANCE;
+        for y in (0..height).step_by(step as usize) {
+            for x in (0..width).step_by(step as usize) {
+                let [r, g, b, _] = image.get_pixel(x, y).0;
+                let luma =
+                    (0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32).round() as usize;
+                counts[0][r as usize] += 1;
+                counts[1][g as usize] += 1;
+                counts[2][b as usize] += 1;
+                counts[3][luma.min(255)] += 1;
+                if r >= high || g >= high || b >= high {
+                    highlight += 1;
+                }
+                if r <= HISTOGRAM_CLIP_TOLERANCE
+                    && g <= HISTOGRAM_CLIP_TOLERANCE
+                    && b <= HISTOGRAM_CLIP_TOLERANCE
+                {
+                    shadow += 1;
+                }
+                sampled += 1;
+            }
+        }
+
+        let sampled = sampled.max(1);
+        let bins = |counts: &[u64; 256]| -> Vec<u16> {
+            counts
+                .chunks(256 / HISTOGRAM_BINS)
+                .map(|x| (x.iter().sum::<u64>() * 10000 / sampled) as u16)
+                .collect()
+        };
+        HistogramSummary {
+            red: bins(&counts[0]),
+            green: bins(&counts[1]),
+            blue: bins(&counts[2]),
+            luma: bins(&counts[3]),
+            highlight_clipping: highlight as f32 * 100.0 / sampled as f32,
+            shadow_clipping: shadow as f32 * 100.0 / sampled as f32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    #[test]
+    fn test_from_image() {
+        // 左半白、右半黑
+        let image = RgbImage::from_fn(10, 10, |x, _| {
+            if x < 5 {
+                Rgb([255, 255, 255])
+            } else {
+                Rgb([0, 0, 0])
+            }
+        });
+        let summary = HistogramSummary::from_image(&DynamicImage::ImageRgb8(image));
+        assert_eq!(summary.luma.len(), HISTOGRAM_BINS);
+        assert_eq!(summary.luma[0], 5000);
+        assert_eq!(summary.luma[HISTOGRAM_BINS - 1], 5000);
+        assert_eq!(summary.highlight_clipping, 50.0);
+        assert_eq!(summary.shadow_clipping, 50.0);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::structs::config::sys_config;
+use serde::{Deserialize, Serialize};
+
+/// 导入时图像校验级别
+///
+/// 级别越高越安全，同时也越慢
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ImageValidationLevel {
+    /// 不校验，直接信任文件扩展名
+    #[serde(rename = "none")]
+    None,
+    /// 校验文件头（魔数 + 长宽）
+    #[serde(rename = "header")]
+    Header,
+    /// 完整解码
+    #[serde(rename = "full")]
+    Full,
+}
+
+impl ImageValidationLevel {
+    /// 数据库存储值
+    pub fn code(&self) -> i32 {
+        match self {
+            ImageValidationLevel::None => 0,
+            ImageValidationLevel::Header => 1,
+            ImageValidationLevel::Full => 2,
+        }
+    }
+
+    /// 从数据库存储值转换
+    pub fn from_code(code: i32) -> ImageValidationLevel {
+        match code {
+            2 => ImageValidationLevel::Full,
+            1 => ImageValidationLevel::Header,
+            _ => ImageValidationLevel::None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageValidationLevel::None => "none",
+            ImageValidationLevel::Header => "header",
+            ImageValidationLevel::Full => "full",
+        }
+    }
+
+    /// 读取配置中的校验级别，配置无效时默认校验文件头
+    pub fn from_config() -> ImageValidationLevel {
+        sys_config()
+            .image_validation_level
+            .as_deref()
+            .and_then(ImageValidationLevel::from_str)
+            .unwrap_or(ImageValidationLevel::Header)
+    }
+
+    /// 从配置字符串转换
+    pub fn from_str(input: &str) -> Option<ImageValidationLevel> {
+        match input.trim().to_lowercase().as_str() {
+            "none" => Some(ImageValidationLevel::None),
+            "header" => Some(ImageValidationLevel::Header),
+            "full" => Some(ImageValidationLevel::Full),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ImageValidationLevel;
+
+    #[test]
+    fn test_code_round_trip() {
+        for level in [
+            ImageValidationLevel::None,
+            ImageValidationLevel::Header,
+            ImageValidationLevel::Full,
+        ] {
+            assert_eq!(ImageValidationLevel::from_code(level.code()), level);
+            assert_eq!(ImageValidationLevel::from_str(level.as_str()), Some(level));
+        }
+        assert!(ImageValidationLevel::Full > ImageValidationLevel::Header);
+        assert_eq!(ImageValidationLevel::from_str("FULL "), Some(ImageValidationLevel::Full));
+        assert_eq!(ImageValidationLevel::from_str("deep"), None);
+    }
+}
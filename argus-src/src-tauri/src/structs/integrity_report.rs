@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// 完整性问题类型
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrityIssueKind {
+    /// 文件不存在
+    #[serde(rename = "missing")]
+    Missing,
+    /// 文件在导入后被修改过（修改时间晚于导入时间）
+    #[serde(rename = "modified")]
+    Modified,
+    /// 内容变化但修改时间没有变化，可能是磁盘损坏
+    #[serde(rename = "corrupted")]
+    Corrupted,
+    /// 文件无法读取
+    #[serde(rename = "unreadable")]
+    Unreadable,
+}
+
+/// 完整性问题
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityIssue {
+    pub photo_id: i32,
+    /// 文件路径
+    pub path: String,
+    pub kind: IntegrityIssueKind,
+}
+
+/// 完整性校验报告
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    /// 是否正在校验
+    pub running: bool,
+    /// 开始时间（时间戳）
+    pub start_time: i64,
+    /// 结束时间（时间戳）
+    pub end_time: Option<i64>,
+    /// 要校验的照片数量
+    pub total: usize,
+    /// 已校验的照片数量
+    pub checked: usize,
+    /// 发现的问题
+    pub issues: Vec<IntegrityIssue>,
+}
@@ -0,0 +1,143 @@
+use crate::constant::{DEFAULT_LIBRARY_NAME, DEFAULT_PROFILE_NAME, LIBRARY_REGISTRY_NAME};
+use crate::structs::config::{self, Config};
+use crate::utils::file_util::get_root_folder;
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// 照片库
+///
+/// 每个照片库是一个独立的文件夹，包含配置文件、数据库和缩略图缓存，
+/// 数据库和缓存的位置由照片库自己的配置决定。默认照片库为程序所在文件夹
+#[derive(Debug, Clone)]
+pub struct LibraryContext {
+    /// 名称
+    pub name: String,
+    /// 照片库文件夹
+    pub root: PathBuf,
+    /// 照片库配置
+    pub config: Arc<Config>,
+}
+
+impl LibraryContext {
+    /// 打开照片库【配置文件不存在时创建默认配置】
+    pub fn open(name: &str, root: &Path) -> Result<LibraryContext> {
+        let config = config::load_config(root)?;
+        Ok(LibraryContext {
+            name: name.to_string(),
+            root: root.to_path_buf(),
+            config: Arc::new(config),
+        })
+    }
+
+    /// 配置文件路径
+    pub fn config_file(&self) -> PathBuf {
+        self.root.join(DEFAULT_PROFILE_NAME)
+    }
+
+    /// 数据库所在文件夹
+    pub fn database_dir(&self) -> PathBuf {
+        self.root
+            .join(self.config.database_path.clone().unwrap_or_default())
+    }
+
+    /// 数据库文件路径
+    pub fn database_url(&self) -> String {
+        self.database_dir()
+            .join(self.config.database_name.clone().unwrap_or_default())
+            .display()
+            .to_string()
+    }
+}
+
+/// 已添加的照片库
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LibraryEntry {
+    /// 名称
+    pub name: String,
+    /// 照片库文件夹
+    pub path: String,
+}
+
+/// 照片库列表【不包含默认照片库】
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct LibraryRegistry {
+    /// 当前打开的照片库文件夹【为空表示默认照片库】
+    pub current: Option<String>,
+    #[serde(default)]
+    pub libraries: Vec<LibraryEntry>,
+}
+
+impl LibraryRegistry {
+    /// 读取照片库列表【文件不存在或格式错误时为空】
+    pub fn load() -> LibraryRegistry {
+        let Ok(path) = LibraryRegistry::file() else {
+            return LibraryRegistry::default();
+        };
+        fs::read_to_string(&path)
+            .ok()
+            .and_then(|x| {
+                toml::from_str(&x)
+                    .map_err(|e| log::warn!("照片库列表格式错误 {}: {}", path.display(), e))
+                    .ok()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 保存照片库列表
+    pub fn save(&self) -> Result<()> {
+        fs::write(LibraryRegistry::file()?, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 按文件夹查找照片库
+    pub fn find(&self, path: &str) -> Option<&LibraryEntry> {
+        self.libraries.iter().find(|x| x.path == path)
+    }
+
+    fn file() -> Result<PathBuf> {
+        Ok(get_root_folder()?.join(LIBRARY_REGISTRY_NAME))
+    }
+}
+
+/// 当前照片库【启动时打开上次使用的照片库，可在运行时切换】
+static CURRENT_LIBRARY: Lazy<RwLock<Arc<LibraryContext>>> = Lazy::new(|| {
+    let registry = LibraryRegistry::load();
+    let last = registry
+        .current
+        .as_deref()
+        .and_then(|path| registry.find(path))
+        .filter(|x| Path::new(&x.path).is_dir())
+        .and_then(|x| {
+            LibraryContext::open(&x.name, Path::new(&x.path))
+                .map_err(|e| log::error!("照片库 {} 打开失败，使用默认照片库: {}", x.path, e))
+                .ok()
+        });
+    let library = last.unwrap_or_else(|| {
+        let root = get_root_folder().expect("根路径获取失败! ");
+        LibraryContext::open(DEFAULT_LIBRARY_NAME, &root).expect("系统配置文件加载失败! ")
+    });
+    log::info!("当前照片库: {} {}", library.name, library.root.display());
+    RwLock::new(Arc::new(library))
+});
+
+/// 获取当前照片库
+pub fn current_library() -> Arc<LibraryContext> {
+    CURRENT_LIBRARY.read().unwrap().clone()
+}
+
+/// 切换当前照片库
+pub fn set_current_library(library: LibraryContext) {
+    *CURRENT_LIBRARY.write().unwrap() = Arc::new(library);
+}
+
+/// 替换当前照片库的配置【配置文件保存后调用】
+pub fn set_current_config(config: Config) {
+    let mut current = CURRENT_LIBRARY.write().unwrap();
+    let mut library = (**current).clone();
+    library.config = Arc::new(config);
+    *current = Arc::new(library);
+}
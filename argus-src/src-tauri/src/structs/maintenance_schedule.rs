@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+/// 定时维护任务
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum MaintenanceJob {
+    /// 增量重新扫描所有在线的存储路径
+    Rescan,
+    /// 清理不再使用的缩略图缓存
+    CacheGc,
+    /// 数据库 ANALYZE、VACUUM
+    OptimizeDatabase,
+    /// 备份数据库到自动备份文件夹
+    Backup,
+    /// 抽样校验照片文件完整性
+    IntegritySample,
+    /// 按拍摄时间和位置重新分段事件
+    RefreshEvents,
+    /// 找出文件已不存在的照片并标记为不可访问
+    Reconcile,
+    /// 找出可以无损压缩的 JPEG 和截图 PNG【只生成计划，确认后才替换原文件】
+    SpaceSaver,
+    /// 为还没有读取 EXIF 的照片读取原图的 EXIF
+    ExifBackfill,
+}
+
+/// 定时维护计划【保存在配置文件中】
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceSchedule {
+    /// 任务
+    pub job: MaintenanceJob,
+    /// cron 表达式【分 时 日 月 周，按本地时间，如 `30 3 * * 0` 表示每周日 3:30】
+    pub cron: String,
+    /// 是否启用
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 维护任务的一次执行记录
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceRun {
+    /// 开始时间（时间戳）
+    pub start_time: i64,
+    /// 结束时间（时间戳）
+    pub end_time: i64,
+    /// 是否成功
+    pub success: bool,
+    /// 执行结果或错误信息
+    pub message: String,
+}
+
+/// 定时维护计划的状态
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleStatus {
+    /// 任务
+    pub job: MaintenanceJob,
+    /// cron 表达式
+    pub cron: String,
+    /// 是否启用
+    pub enabled: bool,
+    /// 是否正在执行
+    pub running: bool,
+    /// 下一次执行时间（时间戳）【未启用或表达式无效时为空】
+    pub next_run: Option<i64>,
+    /// 最近一次执行记录【包括手动执行，程序重启后清空】
+    pub last_run: Option<MaintenanceRun>,
+}
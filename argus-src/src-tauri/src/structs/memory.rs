@@ -0,0 +1,18 @@
+use crate::models::photo::Photo;
+use serde::{Deserialize, Serialize};
+
+/// 那年今日中的一年
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryYear {
+    /// 年份
+    pub year: i32,
+    /// 距今年数
+    pub years_ago: i32,
+    /// 照片数量
+    pub photo_count: usize,
+    /// 代表照片【评分高的优先，按拍摄时间排序】
+    pub cover_photos: Vec<Photo>,
+    /// 所有照片 ID【按拍摄时间排序】
+    pub photo_ids: Vec<i32>,
+}
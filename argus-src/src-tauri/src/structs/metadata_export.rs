@@ -0,0 +1,284 @@
+use crate::models::photo::Photo;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// 导出文件版本
+pub const METADATA_EXPORT_VERSION: u32 = 1;
+
+/// CSV 中多个标签的分隔符
+const CSV_TAG_SEPARATOR: char = ';';
+
+/// CSV 表头【顺序与 `PhotoMetadata::to_csv_row` 一致】
+pub const METADATA_CSV_HEADERS: [&str; 25] = [
+    "hash",
+    "path",
+    "fileSize",
+    "width",
+    "height",
+    "format",
+    "rating",
+    "caption",
+    "notes",
+    "tags",
+    "dateTimeOriginal",
+    "offsetTime",
+    "gpsInfo",
+    "locationName",
+    "make",
+    "model",
+    "software",
+    "exposureTime",
+    "flash",
+    "fNumber",
+    "iso",
+    "maxApertureValue",
+    "focalLength",
+    "exposureProgram",
+    "meteringMode",
+];
+
+/// 单张照片的元数据【导入时按 hash 匹配照片】
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoMetadata {
+    /// 文件 Hash
+    pub hash: String,
+    /// 完整路径【仅供参考，导入时忽略】
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub file_size: Option<i64>,
+    #[serde(default)]
+    pub width: Option<i32>,
+    #[serde(default)]
+    pub height: Option<i32>,
+    #[serde(default)]
+    pub format: Option<String>,
+    /// 评分
+    #[serde(default)]
+    pub rating: Option<i32>,
+    /// 照片说明
+    #[serde(default)]
+    pub caption: Option<String>,
+    /// 备注
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// 标签路径，如 `旅行/日本`
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 拍摄时间（时间戳）
+    #[serde(default)]
+    pub date_time_original: Option<i64>,
+    #[serde(default)]
+    pub offset_time: Option<String>,
+    /// gps 信息
+    #[serde(default)]
+    pub gps_info: Option<String>,
+    /// 地点名称
+    #[serde(default)]
+    pub location_name: Option<String>,
+    #[serde(default)]
+    pub make: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub software: Option<String>,
+    #[serde(default)]
+    pub exposure_time: Option<f32>,
+    #[serde(default)]
+    pub flash: Option<String>,
+    #[serde(default)]
+    pub f_number: Option<f32>,
+    #[serde(default)]
+    pub iso: Option<i32>,
+    #[serde(default)]
+    pub max_aperture_value: Option<String>,
+    #[serde(default)]
+    pub focal_length: Option<f32>,
+    #[serde(default)]
+    pub exposure_program: Option<String>,
+    #[serde(default)]
+    pub metering_mode: Option<String>,
+}
+
+/// JSON 导出文件
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataExport {
+    /// 文件版本
+    pub version: u32,
+    /// 照片元数据
+    #[serde(default)]
+    pub photos: Vec<PhotoMetadata>,
+}
+
+/// 导入结果统计
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataImportSummary {
+    /// 文件中的照片数量
+    pub total: u32,
+    /// 按 hash 匹配到的照片数量
+    pub matched: u32,
+    /// 有修改的照片数量
+    pub updated: u32,
+    /// 新建标签数
+    pub tags_created: u32,
+    /// 没有匹配到的 hash
+    pub unmatched: Vec<String>,
+}
+
+impl PhotoMetadata {
+    /// 由照片记录和标签路径生成
+    pub fn from_photo(photo: &Photo, tags: Vec<String>) -> PhotoMetadata {
+        PhotoMetadata {
+            hash: photo.hash.clone(),
+            path: Some(photo.full_path().display().to_string()),
+            file_size: Some(photo.file_size),
+            width: Some(photo.width),
+            height: Some(photo.height),
+            format: Some(photo.format.clone()),
+            rating: photo.rating,
+            caption: photo.caption.clone(),
+            notes: photo.notes.clone(),
+            tags,
+            date_time_original: photo.date_time_original,
+            offset_time: photo.offset_time.clone(),
+            gps_info: photo.gps_info.clone(),
+            location_name: photo.location_name.clone(),
+            make: photo.make.clone(),
+            model: photo.model.clone(),
+            software: photo.software.clone(),
+            exposure_time: photo.exposure_time,
+            flash: photo.flash.clone(),
+            f_number: photo.f_number,
+            iso: photo.iso,
+            max_aperture_value: photo.max_aperture_value.clone(),
+            focal_length: photo.focal_length,
+            exposure_program: photo.exposure_program.clone(),
+            metering_mode: photo.metering_mode.clone(),
+        }
+    }
+
+    /// 转换为 CSV 行【空值输出为空字符串】
+    pub fn to_csv_row(&self) -> Vec<String> {
+        fn opt<T: ToString>(value: &Option<T>) -> String {
+            value.as_ref().map(|x| x.to_string()).unwrap_or_default()
+        }
+        vec![
+            self.hash.clone(),
+            opt(&self.path),
+            opt(&self.file_size),
+            opt(&self.width),
+            opt(&self.height),
+            opt(&self.format),
+            opt(&self.rating),
+            opt(&self.caption),
+            opt(&self.notes),
+            self.tags.join(&CSV_TAG_SEPARATOR.to_string()),
+            opt(&self.date_time_original),
+            opt(&self.offset_time),
+            opt(&self.gps_info),
+            opt(&self.location_name),
+            opt(&self.make),
+            opt(&self.model),
+            opt(&self.software),
+            opt(&self.exposure_time),
+            opt(&self.flash),
+            opt(&self.f_number),
+            opt(&self.iso),
+            opt(&self.max_aperture_value),
+            opt(&self.focal_length),
+            opt(&self.exposure_program),
+            opt(&self.metering_mode),
+        ]
+    }
+
+    /// 由 CSV 行解析【按表头名称取值，缺少的列视为空】
+    /// - headers 表头
+    /// - row 数据行
+    pub fn from_csv_row(headers: &[String], row: &[String]) -> Result<PhotoMetadata> {
+        let values: HashMap<&str, &str> = headers
+            .iter()
+            .zip(row)
+            .map(|(k, v)| (k.trim(), v.as_str()))
+            .filter(|(_, v)| !v.trim().is_empty())
+            .collect();
+        let text = |key: &str| values.get(key).map(|x| x.to_string());
+        fn parse<T: FromStr>(values: &HashMap<&str, &str>, key: &str) -> Result<Option<T>> {
+            match values.get(key) {
+                Some(x) => x
+                    .trim()
+                    .parse()
+                    .map(Some)
+                    .map_err(|_| anyhow!("字段 {} 的值 {} 无效", key, x)),
+                None => Ok(None),
+            }
+        }
+
+        let hash = text("hash").ok_or_else(|| anyhow!("缺少 hash"))?;
+        let tags = values
+            .get("tags")
+            .map(|x| {
+                x.split(CSV_TAG_SEPARATOR)
+                    .map(|t| t.trim().to_string())
+                    .filter(|t| !t.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(PhotoMetadata {
+            hash,
+            path: text("path"),
+            file_size: parse(&values, "fileSize")?,
+            width: parse(&values, "width")?,
+            height: parse(&values, "height")?,
+            format: text("format"),
+            rating: parse(&values, "rating")?,
+            caption: text("caption"),
+            notes: text("notes"),
+            tags,
+            date_time_original: parse(&values, "dateTimeOriginal")?,
+            offset_time: text("offsetTime"),
+            gps_info: text("gpsInfo"),
+            location_name: text("locationName"),
+            make: text("make"),
+            model: text("model"),
+            software: text("software"),
+            exposure_time: parse(&values, "exposureTime")?,
+            flash: text("flash"),
+            f_number: parse(&values, "fNumber")?,
+            iso: parse(&values, "iso")?,
+            max_aperture_value: text("maxApertureValue"),
+            focal_length: parse(&values, "focalLength")?,
+            exposure_program: text("exposureProgram"),
+            metering_mode: text("meteringMode"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::csv_util;
+
+    #[test]
+    fn test_csv_row_round_trip() {
+        let metadata = PhotoMetadata {
+            hash: "abc".to_string(),
+            rating: Some(4),
+            caption: Some("海边, \"日落\"".to_string()),
+            tags: vec!["旅行/日本".to_string(), "风景".to_string()],
+            f_number: Some(2.8),
+            ..Default::default()
+        };
+        let headers: Vec<String> = METADATA_CSV_HEADERS.iter().map(|x| x.to_string()).collect();
+        let content = csv_util::to_csv(&[headers, metadata.to_csv_row()]);
+        let rows = csv_util::from_csv(&content).unwrap();
+        assert_eq!(
+            PhotoMetadata::from_csv_row(&rows[0], &rows[1]).unwrap(),
+            metadata
+        );
+    }
+}
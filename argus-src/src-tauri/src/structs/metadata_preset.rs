@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// 元数据预设【如 `© 2024 Jane Doe, CC-BY`，保存在配置文件中】
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataPreset {
+    /// 预设 ID【新建时为空，保存时自动生成】
+    #[serde(default)]
+    pub id: String,
+    /// 预设名称
+    pub name: String,
+    /// 作者（EXIF Artist）【为空时不修改】
+    pub artist: Option<String>,
+    /// 版权（EXIF Copyright）【为空时不修改】
+    pub copyright: Option<String>,
+    /// 署名（IPTC Credit）【为空时不修改】
+    pub credit: Option<String>,
+}
+
+impl MetadataPreset {
+    /// 去除首尾空白，空字符串视为不修改
+    pub fn normalized(mut self) -> MetadataPreset {
+        let trim = |x: Option<String>| x.map(|x| x.trim().to_string()).filter(|x| !x.is_empty());
+        self.name = self.name.trim().to_string();
+        self.artist = trim(self.artist);
+        self.copyright = trim(self.copyright);
+        self.credit = trim(self.credit);
+        self
+    }
+
+    /// 是否没有任何要写入的字段
+    pub fn is_empty(&self) -> bool {
+        self.artist.is_none() && self.copyright.is_none() && self.credit.is_none()
+    }
+}
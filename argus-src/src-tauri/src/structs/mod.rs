@@ -1,3 +1,60 @@
 pub mod config;
 pub mod image_size;
 pub mod global_error_msg;
+pub mod image_validation;
+pub mod organization_tree;
+pub mod scan_report;
+pub mod edit_journal;
+pub mod photo_search;
+pub mod caption_write_back;
+pub mod photo_stack;
+pub mod rescan;
+pub mod integrity_report;
+pub mod library;
+pub mod scan_task;
+pub mod metadata_export;
+pub mod external_library;
+pub mod slideshow;
+pub mod watermark;
+pub mod photo_export;
+pub mod histogram;
+pub mod dominant_color;
+pub mod photo_transform;
+pub mod metadata_preset;
+pub mod privacy_zone;
+pub mod place;
+pub mod photo_sequence;
+pub mod card_import;
+pub mod share_bundle;
+pub mod photo_activity;
+pub mod duplicate_reclaim;
+pub mod vault;
+pub mod checksum_manifest;
+pub mod maintenance_schedule;
+pub mod database_optimize;
+pub mod tiff_page;
+pub mod mp_image;
+pub mod problem;
+pub mod hash_verify;
+pub mod photo_compare;
+pub mod cover;
+pub mod organize_rule;
+pub mod event;
+pub mod recent_feed;
+pub mod memory;
+pub mod batch_operation;
+pub mod photo_list;
+pub mod resize_backend;
+pub mod thumbnail_encoding;
+pub mod disk_space;
+pub mod reconcile;
+pub mod rename_template;
+pub mod folder_album;
+pub mod user_profile;
+pub mod directory_access;
+pub mod remote_storage;
+pub mod watch_folder;
+pub mod space_saver;
+pub mod raw_jpeg_primary;
+pub mod geocode_cache;
+pub mod exif_backfill;
@@ -0,0 +1,26 @@
+use crate::utils::exif_utils::mpf::{MpImage, MpImageKind};
+use serde::{Deserialize, Serialize};
+
+/// MPF 记录的图像【双摄、人像模式、深度图等照片在主图像之后追加的图像】
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MpImageInfo {
+    /// 序号（从 0 开始）【第一张为主图像】
+    pub index: usize,
+    pub kind: MpImageKind,
+    /// 是否为代表图像
+    pub representative: bool,
+    /// 大小（字节）
+    pub size: u32,
+}
+
+impl MpImageInfo {
+    pub fn new(index: usize, image: &MpImage) -> MpImageInfo {
+        MpImageInfo {
+            index,
+            kind: image.kind,
+            representative: image.representative,
+            size: image.size,
+        }
+    }
+}
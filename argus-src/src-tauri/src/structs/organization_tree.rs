@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// 导出文件版本
+pub const ORGANIZATION_TREE_VERSION: u32 = 1;
+
+/// 相册、标签树的节点
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizationNode {
+    /// 数据库 ID【外部新增的节点为空】
+    #[serde(default)]
+    pub id: Option<i32>,
+    /// 名称
+    pub name: String,
+    /// 照片数量【仅导出时参考，导入时忽略】
+    #[serde(default)]
+    pub count: i64,
+    /// 子节点
+    #[serde(default)]
+    pub children: Vec<OrganizationNode>,
+}
+
+/// 完整的组织结构（相册 + 标签）
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizationTree {
+    /// 文件版本
+    pub version: u32,
+    /// 相册树
+    #[serde(default)]
+    pub albums: Vec<OrganizationNode>,
+    /// 标签树
+    #[serde(default)]
+    pub tags: Vec<OrganizationNode>,
+}
+
+/// 导入结果统计
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizationImportSummary {
+    /// 新建相册数
+    pub albums_created: u32,
+    /// 更新相册数
+    pub albums_updated: u32,
+    /// 新建标签数
+    pub tags_created: u32,
+    /// 更新标签数
+    pub tags_updated: u32,
+}
+
+impl OrganizationNode {
+    /// 由扁平数据构建树
+    /// - items (id, 父级 id, 名称)
+    /// - counts 每个 id 的照片数量
+    ///
+    /// 父级不存在或处在父级循环（如 A→B→A）中的节点会被当作根节点
+    pub fn build_tree(
+        items: &[(i32, Option<i32>, String)],
+        counts: &HashMap<i32, i64>,
+    ) -> Vec<OrganizationNode> {
+        let parents: HashMap<i32, Option<i32>> =
+            items.iter().map(|(id, parent, _)| (*id, *parent)).collect();
+        let mut children_map: HashMap<Option<i32>, Vec<&(i32, Option<i32>, String)>> =
+            HashMap::new();
+        for item in items {
+            // 父级丢失或沿父级向上会回到自身时挂到根上
+            let parent = item
+                .1
+                .filter(|p| parents.contains_key(p) && !is_in_cycle(item.0, &parents));
+            children_map.entry(parent).or_default().push(item);
+        }
+
+        fn build(
+            parent: Option<i32>,
+            children_map: &HashMap<Option<i32>, Vec<&(i32, Option<i32>, String)>>,
+            counts: &HashMap<i32, i64>,
+        ) -> Vec<OrganizationNode> {
+            children_map
+                .get(&parent)
+                .map(|list| {
+                    list.iter()
+                        .map(|(id, _, name)| OrganizationNode {
+                            id: Some(*id),
+                            name: name.clone(),
+                            count: counts.get(id).copied().unwrap_or(0),
+                            children: build(Some(*id), children_map, counts),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+
+        build(None, &children_map, counts)
+    }
+}
+
+/// 沿父级向上查找是否会回到节点自身
+/// - parents 每个节点的父级
+fn is_in_cycle(id: i32, parents: &HashMap<i32, Option<i32>>) -> bool {
+    let mut visited = HashSet::new();
+    let mut current = parents.get(&id).copied().flatten();
+    while let Some(node) = current {
+        if node == id {
+            return true;
+        }
+        // 上级之间的循环，节点自身不在其中
+        if !visited.insert(node) {
+            return false;
+        }
+        current = parents.get(&node).copied().flatten();
+    }
+    false
+}
+
+/// 相册、标签路径中父子节点的分隔符，如 `旅行/日本`
+pub const NODE_PATH_SEPARATOR: char = '/';
+
+/// 相册、标签 ID 与路径的相互查找
+#[derive(Debug, Default)]
+pub struct NodePathIndex {
+    paths: HashMap<i32, String>,
+    ids: HashMap<String, i32>,
+}
+
+impl NodePathIndex {
+    /// 由扁平数据构建
+    /// - items (id, 父级 id, 名称)
+    pub fn new(items: &[(i32, Option<i32>, String)]) -> NodePathIndex {
+        let nodes: HashMap<i32, (Option<i32>, &str)> = items
+            .iter()
+            .map(|(id, parent, name)| (*id, (*parent, name.as_str())))
+            .collect();
+        let mut index = NodePathIndex::default();
+        for (id, _, _) in items {
+            let mut names = Vec::new();
+            let mut current = Some(*id);
+            // 父级循环引用时停止
+            while let Some(node) = current.and_then(|x| nodes.get(&x)) {
+                if names.len() > nodes.len() {
+                    break;
+                }
+                names.push(node.1);
+                current = node.0;
+            }
+            names.reverse();
+            index.insert(*id, &names.join(&NODE_PATH_SEPARATOR.to_string()));
+        }
+        index
+    }
+
+    /// 节点路径
+    pub fn path_of(&self, id: i32) -> Option<&String> {
+        self.paths.get(&id)
+    }
+
+    /// 按路径查找节点 ID
+    pub fn find(&self, path: &str) -> Option<i32> {
+        self.ids.get(path).copied()
+    }
+
+    /// 记录节点【同名路径已存在时保留 ID 较小的】
+    pub fn insert(&mut self, id: i32, path: &str) {
+        self.paths.insert(id, path.to_string());
+        let entry = self.ids.entry(path.to_string()).or_insert(id);
+        *entry = (*entry).min(id);
+    }
+
+    /// 按路径查找节点，不存在的层级使用 create 逐级创建
+    /// - path 节点路径
+    /// - create 创建节点，参数为 (名称, 父级 id)，返回新节点 ID
+    ///
+    /// 返回最后一级节点 ID【路径为空时为 None】和新建的节点数量
+    pub fn ensure<F>(&mut self, path: &str, mut create: F) -> anyhow::Result<(Option<i32>, u32)>
+    where
+        F: FnMut(&str, Option<i32>) -> anyhow::Result<i32>,
+    {
+        let mut parent: Option<i32> = None;
+        let mut current = String::new();
+        let mut created = 0;
+        for name in split_node_path(path) {
+            if !current.is_empty() {
+                current.push(NODE_PATH_SEPARATOR);
+            }
+            current.push_str(name);
+            let id = match self.find(&current) {
+                Some(id) => id,
+                None => {
+                    let id = create(name, parent)?;
+                    self.insert(id, &current);
+                    created += 1;
+                    id
+                }
+            };
+            parent = Some(id);
+        }
+        Ok((parent, created))
+    }
+}
+
+/// 拆分节点路径【去除各级名称两端空白和空的层级】
+pub fn split_node_path(path: &str) -> Vec<&str> {
+    path.split(NODE_PATH_SEPARATOR)
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_tree() {
+        let items = vec![
+            (1, None, "旅行".to_string()),
+            (2, Some(1), "2024 日本".to_string()),
+            (3, Some(99), "孤立".to_string()),
+        ];
+        let counts = HashMap::from([(2, 5)]);
+        let tree = OrganizationNode::build_tree(&items, &counts);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].children.len(), 1);
+        assert_eq!(tree[0].children[0].count, 5);
+        assert_eq!(tree[1].name, "孤立");
+    }
+
+    #[test]
+    fn test_build_tree_cycle() {
+        // 1、2 互为父级，3 挂在循环中的 1 下
+        let items = vec![
+            (1, Some(2), "甲".to_string()),
+            (2, Some(1), "乙".to_string()),
+            (3, Some(1), "丙".to_string()),
+        ];
+        let tree = OrganizationNode::build_tree(&items, &HashMap::new());
+        let roots: Vec<Option<i32>> = tree.iter().map(|x| x.id).collect();
+        assert_eq!(roots, vec![Some(1), Some(2)]);
+        assert_eq!(tree[0].children.len(), 1);
+        assert_eq!(tree[0].children[0].id, Some(3));
+        assert!(tree[1].children.is_empty());
+    }
+
+    #[test]
+    fn test_node_path_index() {
+        let items = vec![
+            (1, None, "旅行".to_string()),
+            (2, Some(1), "日本".to_string()),
+            (3, Some(2), "京都".to_string()),
+        ];
+        let mut index = NodePathIndex::new(&items);
+        assert_eq!(index.path_of(3).unwrap(), "旅行/日本/京都");
+        assert_eq!(index.find("旅行/日本"), Some(2));
+
+        let mut next_id = 10;
+        let mut parents = Vec::new();
+        let (id, created) = index
+            .ensure(" 旅行 / /日本/大阪", |_, parent| {
+                parents.push(parent);
+                next_id += 1;
+                Ok(next_id)
+            })
+            .unwrap();
+        assert_eq!((id, created), (Some(11), 1));
+        assert_eq!(parents, vec![Some(2)]);
+        assert_eq!(index.find("旅行/日本/大阪"), Some(11));
+    }
+}
@@ -0,0 +1,313 @@
+use crate::models::photo::Photo;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// 规则条件使用的照片字段
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RuleField {
+    /// 相机【制造商和型号，任意一个满足即可】
+    Camera,
+    /// 相机制造商
+    Make,
+    /// 相机型号
+    Model,
+    /// 所在文件夹路径
+    Folder,
+    /// 文件名称
+    FileName,
+    /// 图片格式（MIME 类型）
+    Format,
+    /// 感光度
+    Iso,
+    /// 光圈数
+    FNumber,
+    /// 焦距（毫米）
+    FocalLength,
+    /// 评分
+    Rating,
+}
+
+impl RuleField {
+    /// 是否为数值字段
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            RuleField::Iso | RuleField::FNumber | RuleField::FocalLength | RuleField::Rating
+        )
+    }
+
+    /// 读取照片的字段值【没有值时返回空】
+    pub fn values(&self, photo: &Photo) -> Vec<FieldValue> {
+        let text = |x: &Option<String>| x.clone().map(FieldValue::Text);
+        let value = match self {
+            RuleField::Camera => {
+                return [text(&photo.make), text(&photo.model)]
+                    .into_iter()
+                    .flatten()
+                    .collect()
+            }
+            RuleField::Make => text(&photo.make),
+            RuleField::Model => text(&photo.model),
+            RuleField::Folder => Some(FieldValue::Text(photo.img_path.clone())),
+            RuleField::FileName => Some(FieldValue::Text(photo.img_name.clone())),
+            RuleField::Format => Some(FieldValue::Text(photo.format.clone())),
+            RuleField::Iso => photo.iso.map(|x| FieldValue::Number(x as f64)),
+            RuleField::FNumber => photo.f_number.map(|x| FieldValue::Number(x as f64)),
+            RuleField::FocalLength => photo.focal_length.map(|x| FieldValue::Number(x as f64)),
+            RuleField::Rating => photo.rating.map(|x| FieldValue::Number(x as f64)),
+        };
+        value.into_iter().collect()
+    }
+}
+
+/// 条件的比较方式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RuleOperator {
+    /// 等于【文本不区分大小写】
+    Equals,
+    /// 包含【只用于文本，不区分大小写】
+    Contains,
+    /// 大于【只用于数值】
+    Gt,
+    /// 大于等于【只用于数值】
+    Ge,
+    /// 小于【只用于数值】
+    Lt,
+    /// 小于等于【只用于数值】
+    Le,
+}
+
+/// 照片的字段值
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Text(String),
+    Number(f64),
+}
+
+/// 规则条件【如 `ISO > 6400`】
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleCondition {
+    pub field: RuleField,
+    pub operator: RuleOperator,
+    /// 比较的值【数值字段需要能解析为数字】
+    pub value: String,
+}
+
+impl RuleCondition {
+    /// 校验字段、比较方式和值是否匹配
+    pub fn validate(&self) -> Result<()> {
+        if self.value.trim().is_empty() {
+            return Err(anyhow!("条件的值不能为空"));
+        }
+        if self.field.is_numeric() {
+            if self.operator == RuleOperator::Contains {
+                return Err(anyhow!("数值字段不支持“包含”条件"));
+            }
+            self.value
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| anyhow!("条件的值不是数字: {}", self.value))?;
+        } else if !matches!(self.operator, RuleOperator::Equals | RuleOperator::Contains) {
+            return Err(anyhow!("文本字段只支持“等于”和“包含”条件"));
+        }
+        Ok(())
+    }
+
+    /// 照片是否满足条件【字段没有值时不满足】
+    pub fn matches(&self, photo: &Photo) -> bool {
+        self.field.values(photo).iter().any(|x| self.test(x))
+    }
+
+    /// 字段值是否满足条件
+    pub fn test(&self, value: &FieldValue) -> bool {
+        let expected = self.value.trim();
+        match value {
+            FieldValue::Text(text) => {
+                let text = text.to_lowercase();
+                let expected = expected.to_lowercase();
+                match self.operator {
+                    RuleOperator::Equals => text.trim() == expected,
+                    RuleOperator::Contains => text.contains(&expected),
+                    _ => false,
+                }
+            }
+            FieldValue::Number(number) => {
+                let Ok(expected) = expected.parse::<f64>() else {
+                    return false;
+                };
+                match self.operator {
+                    RuleOperator::Equals => (number - expected).abs() < f64::EPSILON,
+                    RuleOperator::Gt => *number > expected,
+                    RuleOperator::Ge => *number >= expected,
+                    RuleOperator::Lt => *number < expected,
+                    RuleOperator::Le => *number <= expected,
+                    RuleOperator::Contains => false,
+                }
+            }
+        }
+    }
+}
+
+/// 规则满足时执行的操作
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RuleAction {
+    /// 添加标签【不存在时新建顶级标签】
+    AddTag,
+    /// 加入相册【不存在时新建顶级相册】
+    AddAlbum,
+}
+
+impl RuleAction {
+    /// 数据库存储值
+    pub fn code(&self) -> i32 {
+        match self {
+            RuleAction::AddTag => 0,
+            RuleAction::AddAlbum => 1,
+        }
+    }
+
+    /// 从数据库存储值转换【未知的值返回空，不能当作其他操作执行】
+    pub fn from_code(code: i32) -> Option<RuleAction> {
+        match code {
+            0 => Some(RuleAction::AddTag),
+            1 => Some(RuleAction::AddAlbum),
+            _ => None,
+        }
+    }
+}
+
+/// 自动整理规则【导入照片时按顺序执行】
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizeRule {
+    /// 规则 ID【新建时为空】
+    #[serde(default)]
+    pub id: Option<i32>,
+    /// 规则名称
+    pub name: String,
+    /// 条件【全部满足时执行操作】
+    pub conditions: Vec<RuleCondition>,
+    pub action: RuleAction,
+    /// 标签或相册名称
+    pub target: String,
+    /// 是否启用
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl OrganizeRule {
+    /// 校验规则
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(anyhow!("规则名称不能为空"));
+        }
+        if self.target.trim().is_empty() {
+            return Err(anyhow!("规则的标签或相册不能为空"));
+        }
+        if self.conditions.is_empty() {
+            return Err(anyhow!("规则至少需要一个条件"));
+        }
+        self.conditions.iter().try_for_each(|x| x.validate())
+    }
+
+    /// 照片是否满足所有条件
+    pub fn matches(&self, photo: &Photo) -> bool {
+        self.conditions.iter().all(|x| x.matches(photo))
+    }
+}
+
+/// 规则试运行结果
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleDryRun {
+    pub rule_id: i32,
+    /// 规则名称
+    pub name: String,
+    /// 满足条件的照片数量
+    pub matched: usize,
+    /// 会被修改的照片【满足条件且还没有对应的标签或不在相册中】
+    pub photo_ids: Vec<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(field: RuleField, operator: RuleOperator, value: &str) -> RuleCondition {
+        RuleCondition {
+            field,
+            operator,
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_condition() {
+        let camera = condition(RuleField::Camera, RuleOperator::Equals, "dji");
+        assert!(camera.test(&FieldValue::Text("DJI".to_string())));
+        assert!(!camera.test(&FieldValue::Text("FC3582".to_string())));
+
+        let folder = condition(RuleField::Folder, RuleOperator::Contains, "Screenshots");
+        assert!(folder.test(&FieldValue::Text(
+            "/home/me/Pictures/screenshots".to_string()
+        )));
+
+        let iso = condition(RuleField::Iso, RuleOperator::Gt, "6400");
+        assert!(iso.test(&FieldValue::Number(12800.0)));
+        assert!(!iso.test(&FieldValue::Number(6400.0)));
+        assert!(
+            condition(RuleField::Iso, RuleOperator::Le, "6400").test(&FieldValue::Number(6400.0))
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(condition(RuleField::Iso, RuleOperator::Gt, "6400")
+            .validate()
+            .is_ok());
+        assert!(condition(RuleField::Iso, RuleOperator::Gt, "high")
+            .validate()
+            .is_err());
+        assert!(condition(RuleField::Iso, RuleOperator::Contains, "64")
+            .validate()
+            .is_err());
+        assert!(condition(RuleField::Make, RuleOperator::Lt, "a")
+            .validate()
+            .is_err());
+        assert!(condition(RuleField::Make, RuleOperator::Equals, " ")
+            .validate()
+            .is_err());
+
+        let rule = OrganizeRule {
+            id: None,
+            name: "无人机".to_string(),
+            conditions: vec![],
+            action: RuleAction::AddAlbum,
+            target: "Drone".to_string(),
+            enabled: true,
+        };
+        assert!(rule.validate().is_err());
+        let rule = OrganizeRule {
+            conditions: vec![condition(RuleField::Camera, RuleOperator::Equals, "DJI")],
+            ..rule
+        };
+        assert!(rule.validate().is_ok());
+    }
+
+    #[test]
+    fn test_action_code() {
+        for action in [RuleAction::AddTag, RuleAction::AddAlbum] {
+            assert_eq!(RuleAction::from_code(action.code()), Some(action));
+        }
+        assert_eq!(RuleAction::from_code(2), None);
+        assert_eq!(RuleAction::from_code(-1), None);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::models::photo_activity::PhotoActivity;
+use serde::{Deserialize, Serialize};
+
+/// 照片活动类型
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityKind {
+    /// 导入（扫描入库）
+    #[serde(rename = "imported")]
+    Imported,
+    /// 编辑（元数据修改、旋转翻转）
+    #[serde(rename = "edited")]
+    Edited,
+    /// 导出
+    #[serde(rename = "exported")]
+    Exported,
+    /// 分享
+    #[serde(rename = "shared")]
+    Shared,
+}
+
+impl ActivityKind {
+    /// 数据库存储值
+    pub fn code(&self) -> i32 {
+        match self {
+            ActivityKind::Imported => 0,
+            ActivityKind::Edited => 1,
+            ActivityKind::Exported => 2,
+            ActivityKind::Shared => 3,
+        }
+    }
+
+    /// 从数据库存储值转换
+    pub fn from_code(code: i32) -> ActivityKind {
+        match code {
+            1 => ActivityKind::Edited,
+            2 => ActivityKind::Exported,
+            3 => ActivityKind::Shared,
+            _ => ActivityKind::Imported,
+        }
+    }
+}
+
+/// 照片活动记录
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityItem {
+    pub kind: ActivityKind,
+    /// 说明（编辑内容、导出路径等）
+    pub detail: Option<String>,
+    /// 发生时间（时间戳）
+    pub create_time: i64,
+}
+
+impl From<PhotoActivity> for ActivityItem {
+    fn from(value: PhotoActivity) -> Self {
+        ActivityItem {
+            kind: ActivityKind::from_code(value.kind),
+            detail: value.detail,
+            create_time: value.create_time,
+        }
+    }
+}
+
+/// 照片的备注和活动记录
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoActivitySummary {
+    pub photo_id: i32,
+    /// 备注
+    pub notes: Option<String>,
+    /// 活动记录【按时间从新到旧】
+    pub activities: Vec<ActivityItem>,
+}
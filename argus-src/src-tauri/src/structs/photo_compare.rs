@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+/// 对比中的一张照片【各照片的字段一一对应，没有的值为空】
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparePhoto {
+    pub photo_id: i32,
+    /// 文件名称
+    pub img_name: String,
+    /// 预览图路径【所有照片使用同一级别的缩略图】
+    pub preview_path: String,
+    pub width: i32,
+    pub height: i32,
+    pub file_size: i64,
+    /// 拍摄时间（秒级时间戳）
+    pub date_time_original: Option<i64>,
+    /// 与第一张照片的拍摄时间差（秒）【任意一张没有拍摄时间时为空】
+    pub time_delta: Option<i64>,
+    pub make: Option<String>,
+    pub model: Option<String>,
+    /// 曝光时间（秒）
+    pub exposure_time: Option<f32>,
+    /// 快门速度【如 `1/800`】
+    pub shutter_speed: Option<String>,
+    pub f_number: Option<f32>,
+    /// 光圈【如 `f/2.8`】
+    pub aperture: Option<String>,
+    pub iso: Option<i32>,
+    /// 曝光值（EV100）
+    pub exposure_value: Option<f64>,
+    /// 焦距（毫米）
+    pub focal_length: Option<f32>,
+    /// 等效焦距（毫米）
+    pub focal_length_35mm: Option<f32>,
+}
+
+/// 与第一张照片的像素差异【按预览图的灰度计算】
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PixelDifference {
+    pub photo_id: i32,
+    /// 平均灰度差【0 - 1】
+    pub mean_difference: f64,
+    /// 灰度差超过阈值的像素占比【0 - 1】
+    pub changed_ratio: f64,
+}
+
+/// 照片对比结果
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoComparison {
+    /// 按传入顺序排列的照片
+    pub photos: Vec<ComparePhoto>,
+    /// 第二张及之后的照片与第一张的像素差异【未要求计算时为空】
+    pub differences: Option<Vec<PixelDifference>>,
+}
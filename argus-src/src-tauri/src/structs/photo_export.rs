@@ -0,0 +1,24 @@
+use crate::structs::watermark::WatermarkOptions;
+use serde::{Deserialize, Serialize};
+
+/// 照片导出选项
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoExportOptions {
+    /// 水印【水印和 JPEG 质量都为空时直接复制原图】
+    pub watermark: Option<WatermarkOptions>,
+    /// JPEG 质量（1 - 100）【设置时即使没有水印也重新编码为 JPEG】
+    pub quality: Option<u8>,
+}
+
+/// 照片导出结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoExportSummary {
+    /// 导出的照片数量
+    pub exported: u32,
+    /// 导出的文件
+    pub files: Vec<String>,
+    /// 导出失败的照片及原因
+    pub failed: Vec<String>,
+}
@@ -0,0 +1,117 @@
+use crate::models::photo::Photo;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 照片列表排序方式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum PhotoListSort {
+    /// 按拍摄时间从新到旧【没有拍摄时间时使用导入时间】
+    #[default]
+    CapturedDesc,
+    /// 按拍摄时间从旧到新
+    CapturedAsc,
+}
+
+impl PhotoListSort {
+    /// 保存到数据库的名称【与前端传入的名称相同】
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PhotoListSort::CapturedDesc => "capturedDesc",
+            PhotoListSort::CapturedAsc => "capturedAsc",
+        }
+    }
+
+    /// 从名称转换
+    pub fn from_str(input: &str) -> Option<PhotoListSort> {
+        match input.trim() {
+            "capturedDesc" => Some(PhotoListSort::CapturedDesc),
+            "capturedAsc" => Some(PhotoListSort::CapturedAsc),
+            _ => None,
+        }
+    }
+}
+
+/// 照片列表游标【上一页最后一张照片的拍摄时间和 ID，格式为 `拍摄时间:ID`】
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhotoCursor {
+    /// 拍摄时间【没有拍摄时间时为导入时间】
+    pub captured_at: i64,
+    pub id: i32,
+}
+
+impl PhotoCursor {
+    /// 照片的游标
+    pub fn of(photo: &Photo) -> PhotoCursor {
+        PhotoCursor {
+            captured_at: photo.date_time_original.unwrap_or(photo.create_time),
+            id: photo.id,
+        }
+    }
+
+    /// 解析前端传回的游标
+    pub fn parse(text: &str) -> Result<PhotoCursor> {
+        let invalid = || anyhow!("游标 {} 格式错误", text);
+        let (captured_at, id) = text.trim().split_once(':').ok_or_else(invalid)?;
+        Ok(PhotoCursor {
+            captured_at: captured_at.parse().map_err(|_| invalid())?,
+            id: id.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl fmt::Display for PhotoCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.captured_at, self.id)
+    }
+}
+
+/// 照片列表中的照片
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoListItem {
+    pub id: i32,
+    /// 文件 Hash【用于获取缩略图】
+    pub hash: String,
+    /// 文件名称
+    pub img_name: String,
+    pub width: i32,
+    pub height: i32,
+    /// 宽高比
+    pub aspect_ratio: f32,
+    /// 拍摄时间【没有拍摄时间时为导入时间】
+    pub captured_at: i64,
+    /// 评分
+    pub rating: Option<i32>,
+    /// 是否为动图
+    pub is_animated: bool,
+    /// 缩略图加载前显示的占位图【还没有生成缩略图时为空】
+    pub blurhash: Option<String>,
+}
+
+/// 照片列表的一页
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoPage {
+    pub items: Vec<PhotoListItem>,
+    /// 下一页的游标【已是最后一页时为空】
+    pub next_cursor: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor() {
+        let cursor = PhotoCursor {
+            captured_at: -86400,
+            id: 42,
+        };
+        assert_eq!(cursor.to_string(), "-86400:42");
+        assert_eq!(PhotoCursor::parse(" -86400:42 ").unwrap(), cursor);
+        assert!(PhotoCursor::parse("86400").is_err());
+        assert!(PhotoCursor::parse("a:1").is_err());
+    }
+}
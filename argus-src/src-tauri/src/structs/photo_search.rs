@@ -0,0 +1,86 @@
+use crate::constant::FTS_MIN_TERM_CHARS;
+use serde::{Deserialize, Serialize};
+
+/// 照片搜索条件
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoSearchParams {
+    /// 搜索文本【匹配文件名、说明、备注、标签、相机、地点，空格分隔的多个词需要同时匹配】
+    pub text: Option<String>,
+    /// 最低评分
+    pub min_rating: Option<i32>,
+    /// 拍摄时间起（时间戳，包含）
+    pub start_time: Option<i64>,
+    /// 拍摄时间止（时间戳，包含）
+    pub end_time: Option<i64>,
+    /// 主色，如 `#1A8C9E`【匹配任一主色与其相近的照片】
+    pub color: Option<String>,
+    /// 颜色容差（CIE76 ΔE）【默认 15，越大匹配的颜色越宽泛】
+    pub color_tolerance: Option<f32>,
+    /// 最快快门速度，如 `1/1000`（包含）
+    pub min_shutter: Option<String>,
+    /// 最慢快门速度，如 `1/30`、`2s`（包含）
+    pub max_shutter: Option<String>,
+    /// 最大光圈（最小光圈数），如 `f/2.8`（包含）
+    pub min_aperture: Option<String>,
+    /// 最小光圈（最大光圈数），如 `f/8`（包含）
+    pub max_aperture: Option<String>,
+    /// 最小等效焦距（毫米，包含）
+    pub min_focal_length_35mm: Option<f32>,
+    /// 最大等效焦距（毫米，包含）
+    pub max_focal_length_35mm: Option<f32>,
+    /// 是否闪光【true 只返回闪光的照片，false 只返回未闪光的照片】
+    pub flash_fired: Option<bool>,
+    /// 是否为动图【true 只返回动图，false 只返回静态图片】
+    pub animated: Option<bool>,
+    /// 堆叠只显示选定的版本【默认开启】
+    pub collapse_stacks: Option<bool>,
+    /// 返回数量
+    pub limit: Option<i64>,
+    /// 跳过数量
+    pub offset: Option<i64>,
+}
+
+/// 拆分后的搜索文本
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchTerms {
+    /// FTS5 匹配表达式【每个词作为短语，多个词同时匹配】
+    pub match_expr: Option<String>,
+    /// 少于 3 个字符的词【trigram 索引无法匹配，需要使用 LIKE】
+    pub short_terms: Vec<String>,
+}
+
+impl SearchTerms {
+    /// 按空白拆分搜索文本
+    pub fn parse(text: &str) -> SearchTerms {
+        let mut phrases = Vec::new();
+        let mut short_terms = Vec::new();
+        for term in text.split_whitespace() {
+            if term.chars().count() >= FTS_MIN_TERM_CHARS {
+                phrases.push(format!("\"{}\"", term.replace('"', "\"\"")));
+            } else {
+                short_terms.push(term.to_string());
+            }
+        }
+        SearchTerms {
+            match_expr: (!phrases.is_empty()).then(|| phrases.join(" ")),
+            short_terms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_terms() {
+        let terms = SearchTerms::parse("  海边日落 eos R5 say\"hi\" ");
+        assert_eq!(
+            terms.match_expr.as_deref(),
+            Some("\"海边日落\" \"eos\" \"say\"\"hi\"\"\"")
+        );
+        assert_eq!(terms.short_terms, vec!["R5".to_string()]);
+        assert_eq!(SearchTerms::parse("   "), SearchTerms::default());
+    }
+}
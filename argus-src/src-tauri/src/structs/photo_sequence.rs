@@ -0,0 +1,67 @@
+use crate::constant::{BRACKET_TAG_NAME, PANORAMA_TAG_NAME};
+use serde::{Deserialize, Serialize};
+
+/// 连拍序列类型
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SequenceKind {
+    /// 待拼接的全景（曝光参数相同、间隔均匀的连续照片）
+    #[serde(rename = "panorama")]
+    Panorama,
+    /// 待合成的包围曝光（曝光值等间距变化的连续照片）
+    #[serde(rename = "bracket")]
+    Bracket,
+}
+
+impl SequenceKind {
+    /// 数据库存储值
+    pub fn code(&self) -> i32 {
+        match self {
+            SequenceKind::Panorama => 0,
+            SequenceKind::Bracket => 1,
+        }
+    }
+
+    /// 从数据库存储值转换
+    pub fn from_code(code: i32) -> SequenceKind {
+        match code {
+            1 => SequenceKind::Bracket,
+            _ => SequenceKind::Panorama,
+        }
+    }
+
+    /// 自动添加到序列照片上的标签
+    pub fn tag_name(&self) -> &'static str {
+        match self {
+            SequenceKind::Panorama => PANORAMA_TAG_NAME,
+            SequenceKind::Bracket => BRACKET_TAG_NAME,
+        }
+    }
+}
+
+/// 连拍序列信息
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SequenceSummary {
+    /// 序列 ID
+    pub sequence_id: i32,
+    /// 类型
+    pub kind: SequenceKind,
+    /// 第一张的拍摄时间
+    pub start_time: i64,
+    /// 最后一张的拍摄时间
+    pub end_time: i64,
+    /// 包含的照片【按拍摄时间排序】
+    pub photo_ids: Vec<i32>,
+}
+
+/// 检测连拍序列的结果
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SequenceDetectSummary {
+    /// 新发现的全景数量
+    pub panoramas: usize,
+    /// 新发现的包围曝光数量
+    pub brackets: usize,
+    /// 新归入序列的照片数量
+    pub photos: usize,
+}
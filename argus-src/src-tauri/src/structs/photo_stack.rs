@@ -0,0 +1,75 @@
+use crate::constant::{JPEG_EXTENSIONS, RAW_EXTENSIONS};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 版本堆叠信息
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StackSummary {
+    /// 堆叠 ID
+    pub stack_id: i32,
+    /// 原图
+    pub original_photo_id: i32,
+    /// 网格中显示的版本
+    pub cover_photo_id: i32,
+    /// 所有版本（包含原图）
+    pub photo_ids: Vec<i32>,
+}
+
+/// 编辑副本对应的原图文件名（不含扩展名）
+///
+/// 文件名去掉扩展名后以任一后缀结尾（忽略大小写）时，返回去掉后缀的部分，
+/// 如 `IMG_0001_edit` -> `IMG_0001`
+/// - stem 不含扩展名的文件名
+/// - suffixes 编辑副本的文件名后缀
+pub fn edit_base_stem<'a>(stem: &'a str, suffixes: &[String]) -> Option<&'a str> {
+    let lower = stem.to_lowercase();
+    suffixes
+        .iter()
+        .filter(|x| !x.is_empty())
+        .find(|x| lower.ends_with(&x.to_lowercase()))
+        .and_then(|x| stem.len().checked_sub(x.len()))
+        .filter(|len| *len > 0 && stem.is_char_boundary(*len))
+        .map(|len| &stem[..len])
+}
+
+/// RAW+JPEG 配对使用的文件名（不含扩展名）和是否为 RAW
+///
+/// 按扩展名判断（忽略大小写），不是 RAW 或 JPEG 时返回 None，如 `IMG_0001.CR2` -> (`IMG_0001`, true)
+/// - img_name 文件名
+pub fn raw_jpeg_stem(img_name: &str) -> Option<(&str, bool)> {
+    let path = Path::new(img_name);
+    let stem = path.file_stem()?.to_str()?;
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    if RAW_EXTENSIONS.contains(&extension.as_str()) {
+        Some((stem, true))
+    } else if JPEG_EXTENSIONS.contains(&extension.as_str()) {
+        Some((stem, false))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_base_stem() {
+        let suffixes: Vec<String> = ["_edit", " (edited)"].iter().map(|x| x.to_string()).collect();
+        assert_eq!(edit_base_stem("IMG_0001_edit", &suffixes), Some("IMG_0001"));
+        assert_eq!(edit_base_stem("IMG_0001_EDIT", &suffixes), Some("IMG_0001"));
+        assert_eq!(edit_base_stem("海边 (edited)", &suffixes), Some("海边"));
+        assert_eq!(edit_base_stem("IMG_0001", &suffixes), None);
+        assert_eq!(edit_base_stem("_edit", &suffixes), None);
+    }
+
+    #[test]
+    fn test_raw_jpeg_stem() {
+        assert_eq!(raw_jpeg_stem("IMG_0001.CR2"), Some(("IMG_0001", true)));
+        assert_eq!(raw_jpeg_stem("IMG_0001.jpg"), Some(("IMG_0001", false)));
+        assert_eq!(raw_jpeg_stem("DSC.0001.nef"), Some(("DSC.0001", true)));
+        assert_eq!(raw_jpeg_stem("IMG_0001.png"), None);
+        assert_eq!(raw_jpeg_stem("IMG_0001"), None);
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// 旋转、翻转的实现方式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TransformMethod {
+    /// 使用 jpegtran 在 DCT 域无损变换像素，方向标记重置为 1
+    Lossless,
+    /// 只修改 EXIF 方向标记【非 JPEG 或无法无损变换时】
+    Orientation,
+}
+
+/// 旋转、翻转结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoTransformResult {
+    /// 照片 ID
+    pub photo_id: i32,
+    /// 实现方式
+    pub method: TransformMethod,
+    /// 文件中的方向标记
+    pub orientation: u8,
+    /// 修改后的文件 Hash
+    pub hash: String,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// 重新聚类地点的结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceRefreshSummary {
+    /// 地点数量
+    pub places: usize,
+    /// 归入地点的照片数量
+    pub clustered: usize,
+    /// 有位置但没有归入任何地点的照片数量
+    pub unclustered: usize,
+    /// 通过逆地理编码命名的地点数量
+    pub geocoded: usize,
+}
@@ -0,0 +1,135 @@
+use crate::utils::exif_utils::gps_util::GpsCoordinate;
+use serde::{Deserialize, Serialize};
+
+/// 照片位于隐私区域内时对公开位置的处理方式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum PrivacyAction {
+    /// 模糊位置（降低经纬度精度）
+    #[default]
+    Blur,
+    /// 删除位置
+    Remove,
+}
+
+/// 隐私区域【如家庭住址，保存在配置文件中】
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivacyZone {
+    /// 区域 ID【新建时为空，保存时自动生成】
+    #[serde(default)]
+    pub id: String,
+    /// 区域名称
+    pub name: String,
+    /// 中心纬度
+    pub latitude: f64,
+    /// 中心经度
+    pub longitude: f64,
+    /// 半径（米）
+    pub radius_meters: f64,
+    /// 处理方式
+    #[serde(default)]
+    pub action: PrivacyAction,
+}
+
+impl PrivacyZone {
+    /// 坐标是否位于区域内
+    pub fn contains(&self, coordinate: &GpsCoordinate) -> bool {
+        let center = GpsCoordinate {
+            latitude: self.latitude,
+            longitude: self.longitude,
+            altitude: None,
+        };
+        self.radius_meters > 0.0 && center.distance_meters(coordinate) <= self.radius_meters
+    }
+}
+
+/// 导出时对外公开的位置
+#[derive(Debug, Clone, PartialEq)]
+pub enum PublicLocation {
+    /// 保留原始位置
+    Original,
+    /// 模糊后的位置
+    Blurred(GpsCoordinate),
+    /// 不公开位置
+    Removed,
+}
+
+impl PublicLocation {
+    /// 根据隐私区域计算公开位置【同时位于多个区域时删除优先】
+    /// - zones 隐私区域
+    /// - coordinate 照片位置【为空时不公开位置】
+    /// - decimals 模糊时经纬度保留的小数位数
+    pub fn resolve(
+        zones: &[PrivacyZone],
+        coordinate: Option<&GpsCoordinate>,
+        decimals: u32,
+    ) -> PublicLocation {
+        let Some(coordinate) = coordinate else {
+            return PublicLocation::Removed;
+        };
+        let actions: Vec<PrivacyAction> = zones
+            .iter()
+            .filter(|x| x.contains(coordinate))
+            .map(|x| x.action)
+            .collect();
+        if actions.contains(&PrivacyAction::Remove) {
+            PublicLocation::Removed
+        } else if actions.contains(&PrivacyAction::Blur) {
+            PublicLocation::Blurred(coordinate.blurred(decimals))
+        } else {
+            PublicLocation::Original
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let home = PrivacyZone {
+            name: "home".to_string(),
+            latitude: 31.2304,
+            longitude: 121.4737,
+            radius_meters: 500.0,
+            ..Default::default()
+        };
+        let inside = GpsCoordinate {
+            latitude: 31.2321,
+            longitude: 121.4745,
+            altitude: Some(10.0),
+        };
+        let outside = GpsCoordinate {
+            latitude: 31.25,
+            longitude: 121.4737,
+            altitude: None,
+        };
+        let zones = vec![home.clone()];
+        assert_eq!(
+            PublicLocation::resolve(&zones, Some(&inside), 2),
+            PublicLocation::Blurred(inside.blurred(2))
+        );
+        assert_eq!(
+            PublicLocation::resolve(&zones, Some(&outside), 2),
+            PublicLocation::Original
+        );
+        assert_eq!(
+            PublicLocation::resolve(&zones, None, 2),
+            PublicLocation::Removed
+        );
+
+        let zones = vec![
+            home.clone(),
+            PrivacyZone {
+                action: PrivacyAction::Remove,
+                ..home
+            },
+        ];
+        assert_eq!(
+            PublicLocation::resolve(&zones, Some(&inside), 2),
+            PublicLocation::Removed
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use crate::models::problem::ProblemRecord;
+use serde::{Deserialize, Serialize};
+
+/// 问题文件的错误类型
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProblemKind {
+    /// 图像解码失败
+    #[serde(rename = "decode")]
+    Decode,
+    /// EXIF 解析失败
+    #[serde(rename = "exif")]
+    Exif,
+    /// 云盘占位文件【内容不在本地，未下载】
+    #[serde(rename = "cloudOnly")]
+    CloudOnly,
+    /// EXIF 部分字段无法读取【其余字段正常读取，扫描时不跳过】
+    #[serde(rename = "exifWarning")]
+    ExifWarning,
+}
+
+impl ProblemKind {
+    /// 数据库存储值
+    pub fn code(&self) -> i32 {
+        match self {
+            ProblemKind::Decode => 0,
+            ProblemKind::Exif => 1,
+            ProblemKind::CloudOnly => 2,
+            ProblemKind::ExifWarning => 3,
+        }
+    }
+
+    /// 从数据库存储值转换
+    pub fn from_code(code: i32) -> ProblemKind {
+        match code {
+            1 => ProblemKind::Exif,
+            2 => ProblemKind::CloudOnly,
+            3 => ProblemKind::ExifWarning,
+            _ => ProblemKind::Decode,
+        }
+    }
+}
+
+/// 无法读取的文件【返回给前端】
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProblemFile {
+    /// 文件路径
+    pub path: String,
+    /// 错误类型
+    pub kind: ProblemKind,
+    /// 错误信息
+    pub message: String,
+    /// 文件开头的字节（十六进制）
+    pub sample: String,
+    /// 文件大小（字节）
+    pub file_size: i64,
+    /// 失败次数
+    pub fail_count: i32,
+    /// 首次失败时间
+    pub create_time: i64,
+    /// 最近一次失败时间
+    pub update_time: i64,
+}
+
+impl From<ProblemRecord> for ProblemFile {
+    fn from(record: ProblemRecord) -> Self {
+        ProblemFile {
+            path: record.path,
+            kind: ProblemKind::from_code(record.kind),
+            message: record.message,
+            sample: record.sample,
+            file_size: record.file_size,
+            fail_count: record.fail_count,
+            create_time: record.create_time,
+            update_time: record.update_time,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::structs::config::sys_config;
+use serde::{Deserialize, Serialize};
+
+/// RAW+JPEG 配对时缩略图、预览优先使用的图像【照片记录始终为 RAW，JPEG 关联到 RAW 上】
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawJpegPrimary {
+    #[serde(rename = "jpeg")]
+    Jpeg,
+    #[serde(rename = "raw")]
+    Raw,
+}
+
+impl RawJpegPrimary {
+    /// 读取配置，配置无效时使用 JPEG
+    pub fn from_config() -> RawJpegPrimary {
+        sys_config()
+            .raw_jpeg_primary
+            .as_deref()
+            .and_then(RawJpegPrimary::from_str)
+            .unwrap_or(RawJpegPrimary::Jpeg)
+    }
+
+    /// 从配置字符串转换
+    pub fn from_str(input: &str) -> Option<RawJpegPrimary> {
+        match input.trim().to_lowercase().as_str() {
+            "jpeg" => Some(RawJpegPrimary::Jpeg),
+            "raw" => Some(RawJpegPrimary::Raw),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::models::photo::Photo;
+use serde::{Deserialize, Serialize};
+
+/// 分页结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedPage<T> {
+    pub items: Vec<T>,
+    /// 总数量
+    pub total: i64,
+}
+
+/// 最近导入的一批照片
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportBatchSummary {
+    pub batch_id: i32,
+    /// 来源（照片最多的文件夹）
+    pub source: String,
+    /// 照片数量
+    pub photo_count: i32,
+    /// 第一张照片的导入时间
+    pub start_time: i64,
+    /// 最后一张照片的导入时间
+    pub end_time: i64,
+    /// 预览照片【批次中的前几张】
+    pub preview_photos: Vec<Photo>,
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+/// 照片文件不存在的原因
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingReason {
+    /// 所在的卷（移动硬盘、网络共享）未挂载【重新连接后即可恢复，不应清理】
+    #[serde(rename = "volumeOffline")]
+    VolumeOffline,
+    /// 卷在线但文件已不存在【在照片库外被删除】
+    #[serde(rename = "gone")]
+    Gone,
+}
+
+/// 文件不存在的照片
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingPhoto {
+    pub photo_id: i32,
+    pub path: String,
+    pub hash: String,
+    pub reason: MissingReason,
+}
+
+/// 数据库记录与磁盘文件的对账结果
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcileReport {
+    /// 检查的照片数量
+    pub checked: usize,
+    /// 文件不存在的照片
+    pub missing: Vec<MissingPhoto>,
+    /// 其中卷离线的数量
+    pub volume_offline: usize,
+    /// 其中已被删除的数量
+    pub gone: usize,
+    /// 文件重新出现、恢复为可访问的照片数量
+    pub restored: usize,
+}
+
+/// 文件不存在的照片的处理方式
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconcileAction {
+    /// 移入回收站【保留标签、相册等信息，可以恢复】
+    #[serde(rename = "archive")]
+    Archive,
+    /// 彻底删除记录及关联的标签、相册、缓存等数据
+    #[serde(rename = "purge")]
+    Purge,
+}
+
+/// 处理结果
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcileResult {
+    pub action: ReconcileAction,
+    /// 处理的照片数量
+    pub photos: usize,
+    /// 跳过的照片数量【文件已重新出现，或卷离线且未要求一并处理】
+    pub skipped: usize,
+}
@@ -0,0 +1,198 @@
+use crate::i18n;
+use crate::i18n::msg;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 远程存储类型
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RemoteStorageKind {
+    /// S3 兼容存储【AWS S3、MinIO、Cloudflare R2、Backblaze B2 等】
+    S3,
+    WebDav,
+}
+
+impl RemoteStorageKind {
+    /// 保存到数据库的名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RemoteStorageKind::S3 => "s3",
+            RemoteStorageKind::WebDav => "webdav",
+        }
+    }
+
+    /// 从名称转换
+    pub fn from_str(input: &str) -> Option<RemoteStorageKind> {
+        match input.trim().to_lowercase().as_str() {
+            "s3" => Some(RemoteStorageKind::S3),
+            "webdav" => Some(RemoteStorageKind::WebDav),
+            _ => None,
+        }
+    }
+}
+
+/// 远程存储配置
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteStorage {
+    /// 远程存储 ID【为空表示新增】
+    pub id: Option<i32>,
+    /// 显示名称
+    pub name: String,
+    pub kind: RemoteStorageKind,
+    /// 服务地址【S3 为 endpoint，如 `https://s3.us-east-1.amazonaws.com`；WebDAV 为根目录地址】
+    pub endpoint: String,
+    /// S3 存储桶
+    #[serde(default)]
+    pub bucket: Option<String>,
+    /// S3 区域【为空时使用 us-east-1】
+    #[serde(default)]
+    pub region: Option<String>,
+    /// 照片所在的目录前缀【为空表示根目录】
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// S3 Access Key / WebDAV 用户名
+    #[serde(default)]
+    pub access_key: Option<String>,
+    /// S3 Secret Key / WebDAV 密码【不返回给前端；保存时为空表示不修改】
+    #[serde(default, skip_serializing)]
+    pub secret_key: Option<String>,
+    /// 是否已保存密钥
+    #[serde(default)]
+    pub has_secret: bool,
+    #[serde(default = "default_enable")]
+    pub is_enable: bool,
+}
+
+fn default_enable() -> bool {
+    true
+}
+
+/// 日志中不输出密钥
+impl fmt::Debug for RemoteStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteStorage")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("kind", &self.kind)
+            .field("endpoint", &self.endpoint)
+            .field("bucket", &self.bucket)
+            .field("region", &self.region)
+            .field("prefix", &self.prefix)
+            .field("access_key", &self.access_key)
+            .field("secret_key", &self.secret_key.as_ref().map(|_| "***"))
+            .field("has_secret", &self.has_secret)
+            .field("is_enable", &self.is_enable)
+            .finish()
+    }
+}
+
+impl RemoteStorage {
+    /// 检查名称、服务地址和存储桶
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(anyhow!(i18n::text(msg::REMOTE_NAME_REQUIRED)));
+        }
+        let endpoint = self.endpoint.trim();
+        if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+            return Err(i18n::error(
+                msg::REMOTE_ENDPOINT_SCHEME,
+                &[("endpoint", endpoint.to_string())],
+            ));
+        }
+        if self.kind == RemoteStorageKind::S3
+            && self.bucket.as_deref().map_or(true, |x| x.trim().is_empty())
+        {
+            return Err(anyhow!(i18n::text(msg::REMOTE_BUCKET_REQUIRED)));
+        }
+        Ok(())
+    }
+}
+
+/// 远程列表中的文件
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteObject {
+    /// 对象路径【相对存储桶或 WebDAV 根目录，包含目录前缀】
+    pub key: String,
+    /// 文件大小
+    pub size: u64,
+    /// 修改时间（时间戳）
+    pub modified: i64,
+    pub etag: Option<String>,
+}
+
+/// 远程存储同步结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteSyncSummary {
+    /// 远程列表中的图片数量
+    pub listed: usize,
+    /// 新增的照片数量
+    pub added: usize,
+    /// 文件变化的照片数量
+    pub updated: usize,
+    /// 远程已删除的照片数量
+    pub removed: usize,
+    /// 生成了缩略图的照片数量
+    pub thumbnails: usize,
+    /// 处理失败的文件及原因
+    pub failed: Vec<String>,
+}
+
+/// 上传到远程存储的结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteUploadSummary {
+    /// 上传成功的数量
+    pub uploaded: usize,
+    /// 远程已有相同内容而跳过的数量
+    pub skipped: usize,
+    /// 上传失败的文件及原因
+    pub failed: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_remote_storage() {
+        let mut storage: RemoteStorage = serde_json::from_str(
+            r#"{"id": null, "name": "NAS", "kind": "webDav", "endpoint": "https://dav.example.com/photos"}"#,
+        )
+        .unwrap();
+        assert!(storage.validate().is_ok());
+        assert!(storage.is_enable);
+
+        storage.kind = RemoteStorageKind::S3;
+        assert!(storage.validate().is_err());
+        storage.bucket = Some("photos".to_string());
+        assert!(storage.validate().is_ok());
+
+        storage.endpoint = "s3.example.com".to_string();
+        assert!(storage.validate().is_err());
+    }
+
+    #[test]
+    fn test_secret_not_serialized() {
+        let storage = RemoteStorage {
+            id: Some(1),
+            name: "S3".to_string(),
+            kind: RemoteStorageKind::S3,
+            endpoint: "https://s3.example.com".to_string(),
+            bucket: Some("photos".to_string()),
+            region: None,
+            prefix: None,
+            access_key: Some("key".to_string()),
+            secret_key: Some("secret".to_string()),
+            has_secret: true,
+            is_enable: true,
+        };
+        let json = serde_json::to_string(&storage).unwrap();
+        assert!(!json.contains("secretKey"));
+        assert!(!format!("{:?}", storage).contains("\"secret\""));
+        assert!(json.contains("\"hasSecret\":true"));
+    }
+}
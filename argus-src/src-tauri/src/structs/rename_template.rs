@@ -0,0 +1,284 @@
+use crate::models::photo::Photo;
+use crate::utils::time_util::TimeUtils;
+use anyhow::{anyhow, Result};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// 文件名模板中的字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    /// 四位年份
+    Year,
+    /// 两位年份
+    ShortYear,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    /// 时分秒【HHmmss】
+    Time,
+    /// 相机制造商
+    Make,
+    /// 相机型号
+    Model,
+    /// 原文件名【不含扩展名】
+    Name,
+    /// 批次内序号
+    Seq,
+}
+
+impl Token {
+    fn parse(name: &str) -> Option<Token> {
+        Some(match name {
+            "YYYY" => Token::Year,
+            "YY" => Token::ShortYear,
+            "MM" => Token::Month,
+            "DD" => Token::Day,
+            "HH" => Token::Hour,
+            "mm" => Token::Minute,
+            "ss" => Token::Second,
+            "HHmmss" => Token::Time,
+            "make" => Token::Make,
+            "model" => Token::Model,
+            "name" => Token::Name,
+            "seq" => Token::Seq,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    Field(Token),
+}
+
+/// 按模板生成文件名
+///
+/// 字段写作 `{字段名}`：YYYY、YY、MM、DD、HH、mm、ss、HHmmss、make、model、name、seq，
+/// 如 `{YYYY}{MM}{DD}_{HHmmss}_{model}_{seq}`；扩展名沿用原文件
+#[derive(Debug, Clone)]
+pub struct RenameTemplate {
+    parts: Vec<Part>,
+}
+
+/// 序号的最小位数
+const SEQ_MIN_WIDTH: usize = 3;
+
+/// 文件名（不含扩展名）的最大字节数【文件系统限制为 255 字节，留出冲突后缀和扩展名的位置】
+const FILE_STEM_MAX_BYTES: usize = 200;
+
+/// Windows 保留的设备名【不区分大小写，带扩展名时也不能使用】
+const WINDOWS_RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+impl RenameTemplate {
+    /// 解析模板【字段未知或没有闭合时返回错误】
+    pub fn parse(template: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut rest = template.trim();
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                parts.push(Part::Text(rest[..start].to_string()));
+            }
+            let tail = &rest[start + 1..];
+            let end = tail
+                .find('}')
+                .ok_or_else(|| anyhow!("模板 {} 缺少 }}!", template))?;
+            let token = Token::parse(&tail[..end])
+                .ok_or_else(|| anyhow!("模板字段 {{{}}} 无效!", &tail[..end]))?;
+            parts.push(Part::Field(token));
+            rest = &tail[end + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Text(rest.to_string()));
+        }
+        if parts.is_empty() {
+            return Err(anyhow!("模板不能为空!"));
+        }
+        Ok(Self { parts })
+    }
+
+    /// 生成文件名【不含扩展名，文件名中不允许的字符替换为 `_`】
+    /// - photo 照片【没有拍摄时间时使用入库时间】
+    /// - seq 批次内序号【从 1 开始】
+    /// - total 批次照片数量【决定序号位数】
+    pub fn render(&self, photo: &Photo, seq: usize, total: usize) -> String {
+        let stem = photo
+            .img_name
+            .rsplit_once('.')
+            .map(|(stem, _)| stem)
+            .unwrap_or(&photo.img_name);
+        self.render_with(
+            Self::photo_time(photo),
+            photo.make.as_deref(),
+            photo.model.as_deref(),
+            stem,
+            seq,
+            total,
+        )
+    }
+
+    /// 按指定的字段值生成文件名【还未入库的文件使用】
+    /// - time 拍摄地当地时间
+    /// - stem 原文件名【不含扩展名】
+    pub fn render_with(
+        &self,
+        time: NaiveDateTime,
+        make: Option<&str>,
+        model: Option<&str>,
+        stem: &str,
+        seq: usize,
+        total: usize,
+    ) -> String {
+        let width = total.to_string().len().max(SEQ_MIN_WIDTH);
+        let mut name = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => name.push_str(text),
+                Part::Field(token) => name.push_str(&match token {
+                    Token::Year => time.format("%Y").to_string(),
+                    Token::ShortYear => time.format("%y").to_string(),
+                    Token::Month => time.format("%m").to_string(),
+                    Token::Day => time.format("%d").to_string(),
+                    Token::Hour => time.format("%H").to_string(),
+                    Token::Minute => time.format("%M").to_string(),
+                    Token::Second => time.format("%S").to_string(),
+                    Token::Time => time.format("%H%M%S").to_string(),
+                    Token::Make => make.unwrap_or_default().to_string(),
+                    Token::Model => model.unwrap_or_default().to_string(),
+                    Token::Name => stem.to_string(),
+                    Token::Seq => format!("{:0width$}", seq, width = width),
+                }),
+            }
+        }
+        sanitize_file_name(&name)
+    }
+
+    /// 照片的拍摄地当地时间
+    fn photo_time(photo: &Photo) -> NaiveDateTime {
+        match photo.date_time_original {
+            Some(time) => TimeUtils::to_local_date_time(time, photo.offset_time.as_deref()),
+            None => TimeUtils::to_local_date_time(photo.create_time, None),
+        }
+    }
+}
+
+/// 替换文件名中不允许的字符
+///
+/// 空白合并为一个空格，`_`、`-` 连续重复时合并（字段为空时留下的分隔符），去掉首尾的分隔符和 `.`；
+/// 超过 `FILE_STEM_MAX_BYTES` 时截断，Windows 保留的设备名后追加 `_`
+pub fn sanitize_file_name(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let mut result = String::with_capacity(replaced.len());
+    for c in replaced
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+    {
+        if matches!(c, '_' | '-') && result.ends_with(c) {
+            continue;
+        }
+        result.push(c);
+    }
+    let trim = |x: &str| {
+        x.trim_matches(|c: char| matches!(c, '_' | '-' | '.' | ' '))
+            .to_string()
+    };
+    let mut result = trim(&result);
+    if result.len() > FILE_STEM_MAX_BYTES {
+        let mut end = FILE_STEM_MAX_BYTES;
+        while !result.is_char_boundary(end) {
+            end -= 1;
+        }
+        result = trim(&result[..end]);
+    }
+    let device = result.split('.').next().unwrap_or_default().trim_end();
+    if WINDOWS_RESERVED_NAMES
+        .iter()
+        .any(|x| x.eq_ignore_ascii_case(device))
+    {
+        result.push('_');
+    }
+    result
+}
+
+/// 重命名预览
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamePreview {
+    pub photo_id: i32,
+    /// 原文件名
+    pub old_name: String,
+    /// 新文件名【与原文件名相同时不重命名】
+    pub new_name: String,
+}
+
+/// 按模板重命名结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameByTemplateResult {
+    /// 是否只预览
+    pub dry_run: bool,
+    /// 文件名变化
+    pub items: Vec<RenamePreview>,
+    /// 实际重命名的照片数量
+    pub renamed: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_template() {
+        assert!(RenameTemplate::parse("{YYYY}{MM}{DD}_{HHmmss}_{model}_{seq}").is_ok());
+        assert!(RenameTemplate::parse("{YYYY}_{unknown}").is_err());
+        assert!(RenameTemplate::parse("{YYYY").is_err());
+        assert!(RenameTemplate::parse("  ").is_err());
+    }
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(
+            sanitize_file_name("20240501_120000__001"),
+            "20240501_120000_001"
+        );
+        assert_eq!(sanitize_file_name("a/b:c"), "a_b_c");
+        assert_eq!(sanitize_file_name("_Canon  EOS R5_"), "Canon EOS R5");
+        assert_eq!(sanitize_file_name(".."), "");
+        assert_eq!(sanitize_file_name("con"), "con_");
+        assert_eq!(sanitize_file_name("LPT1.backup"), "LPT1.backup_");
+        assert_eq!(sanitize_file_name("CONSOLE"), "CONSOLE");
+        let long = sanitize_file_name(&"照".repeat(100));
+        assert_eq!(long.len(), 198);
+        assert!(long.chars().all(|c| c == '照'));
+    }
+
+    #[test]
+    fn test_render_with() {
+        let template = RenameTemplate::parse("{YYYY}{MM}{DD}_{HHmmss}_{model}_{seq}").unwrap();
+        let time =
+            NaiveDateTime::parse_from_str("2024-05-01 12:30:45", "%Y-%m-%d %H:%M:%S").unwrap();
+        assert_eq!(
+            template.render_with(time, Some("Canon"), Some("EOS R5"), "IMG_0001", 7, 12),
+            "20240501_123045_EOS R5_007"
+        );
+        // 没有相机型号时不留下重复的分隔符
+        assert_eq!(
+            template.render_with(time, None, None, "IMG_0001", 12, 1200),
+            "20240501_123045_0012"
+        );
+    }
+}
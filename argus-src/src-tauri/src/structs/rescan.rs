@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// 数据库中的照片记录
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RescanEntry {
+    pub photo_id: i32,
+    /// 文件路径
+    pub path: String,
+}
+
+/// 被移动的照片（内容相同，路径变化）
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RescanMove {
+    pub photo_id: i32,
+    /// 原路径
+    pub from: String,
+    /// 新路径
+    pub to: String,
+}
+
+/// 文件夹重新扫描的差异
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RescanPlan {
+    /// 扫描的文件夹
+    pub root: String,
+    /// 是否只返回差异，不修改数据库
+    pub dry_run: bool,
+    /// 新增的文件
+    pub added: Vec<String>,
+    /// 文件已不存在的照片记录
+    pub removed: Vec<RescanEntry>,
+    /// 移动过的照片
+    pub moved: Vec<RescanMove>,
+}
+
+impl RescanPlan {
+    /// 对比数据库记录和磁盘文件
+    ///
+    /// 只在磁盘上的文件为新增，只在数据库中的记录为删除；
+    /// 新增文件的 Hash 与删除记录相同时视为移动。只有存在删除记录时才计算新增文件的 Hash
+    /// - records 数据库中的照片（ID、路径、Hash）
+    /// - files 磁盘上的图片文件
+    /// - hash_of 计算文件 Hash【失败时返回 None】
+    pub fn diff<F>(records: &[(i32, PathBuf, String)], files: &[PathBuf], mut hash_of: F) -> RescanPlan
+    where
+        F: FnMut(&Path) -> Option<String>,
+    {
+        let known: HashSet<&PathBuf> = records.iter().map(|(_, path, _)| path).collect();
+        let on_disk: HashSet<&PathBuf> = files.iter().collect();
+
+        // Hash -> 文件已不存在的记录
+        let mut missing: HashMap<&str, Vec<(i32, &PathBuf)>> = HashMap::new();
+        for (id, path, hash) in records.iter().filter(|(_, path, _)| !on_disk.contains(path)) {
+            missing.entry(hash.as_str()).or_default().push((*id, path));
+        }
+
+        let mut plan = RescanPlan::default();
+        let mut added: Vec<&PathBuf> = files.iter().filter(|x| !known.contains(x)).collect();
+        added.sort();
+        for file in added {
+            let moved_from = if missing.is_empty() {
+                None
+            } else {
+                hash_of(file)
+                    .and_then(|hash| missing.get_mut(hash.as_str()))
+                    .and_then(|x| x.pop())
+            };
+            match moved_from {
+                Some((photo_id, from)) => plan.moved.push(RescanMove {
+                    photo_id,
+                    from: from.display().to_string(),
+                    to: file.display().to_string(),
+                }),
+                None => plan.added.push(file.display().to_string()),
+            }
+        }
+        plan.removed = missing
+            .into_values()
+            .flatten()
+            .map(|(photo_id, path)| RescanEntry {
+                photo_id,
+                path: path.display().to_string(),
+            })
+            .collect();
+        plan.removed.sort_by_key(|x| x.photo_id);
+        plan
+    }
+
+    /// 是否没有任何差异
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.moved.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let records = vec![
+            (1, PathBuf::from("/a/1.jpg"), "h1".to_string()),
+            (2, PathBuf::from("/a/2.jpg"), "h2".to_string()),
+            (3, PathBuf::from("/a/3.jpg"), "h3".to_string()),
+        ];
+        let files = vec![
+            PathBuf::from("/a/1.jpg"),
+            PathBuf::from("/a/b/2.jpg"),
+            PathBuf::from("/a/4.jpg"),
+        ];
+        let plan = RescanPlan::diff(&records, &files, |p| {
+            Some(if p.ends_with("2.jpg") { "h2" } else { "h4" }.to_string())
+        });
+        assert_eq!(plan.added, vec![PathBuf::from("/a/4.jpg").display().to_string()]);
+        assert_eq!(plan.moved.len(), 1);
+        assert_eq!(plan.moved[0].photo_id, 2);
+        assert_eq!(plan.removed.len(), 1);
+        assert_eq!(plan.removed[0].photo_id, 3);
+
+        // 没有删除记录时不计算 Hash
+        let plan = RescanPlan::diff(&records[..1], &files, |_| panic!());
+        assert_eq!(plan.added.len(), 2);
+        assert!(plan.removed.is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::structs::config::sys_config;
+use serde::{Deserialize, Serialize};
+
+/// 缩略图缩放实现
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeBackend {
+    /// image 库自带的缩放
+    #[serde(rename = "image")]
+    Image,
+    /// fast_image_resize（SIMD 加速）【不支持的像素格式自动使用 image 库】
+    #[serde(rename = "simd")]
+    Simd,
+    /// 显卡计算（wgpu）【需要 gpu-resize 功能；没有可用显卡、放大或不支持的像素格式时使用 SIMD 加速】
+    #[serde(rename = "gpu")]
+    Gpu,
+}
+
+impl ResizeBackend {
+    /// 所有实现【用于性能对比】
+    pub const ALL: [ResizeBackend; 3] = [
+        ResizeBackend::Image,
+        ResizeBackend::Simd,
+        ResizeBackend::Gpu,
+    ];
+
+    /// 读取配置，配置无效时使用 SIMD 加速
+    pub fn from_config() -> ResizeBackend {
+        sys_config()
+            .resize_backend
+            .as_deref()
+            .and_then(ResizeBackend::from_str)
+            .unwrap_or(ResizeBackend::Simd)
+    }
+
+    /// 从配置字符串转换
+    pub fn from_str(input: &str) -> Option<ResizeBackend> {
+        match input.trim().to_lowercase().as_str() {
+            "image" => Some(ResizeBackend::Image),
+            "simd" => Some(ResizeBackend::Simd),
+            "gpu" => Some(ResizeBackend::Gpu),
+            _ => None,
+        }
+    }
+}
+
+/// 缩放实现的性能对比结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResizeBenchmark {
+    pub backend: ResizeBackend,
+    /// 参与对比的图片数量
+    pub images: usize,
+    /// 生成的缩略图数量【每张图片生成所有尺寸】
+    pub thumbnails: usize,
+    /// 总耗时（毫秒）
+    pub elapsed_ms: u64,
+    /// 每秒处理的原图像素（百万）
+    pub megapixels_per_sec: f64,
+    /// 每秒生成的缩略图数量
+    pub thumbnails_per_sec: f64,
+}
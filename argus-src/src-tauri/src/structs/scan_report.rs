@@ -0,0 +1,260 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
+
+/// 扫描状态
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanStatus {
+    /// 扫描中
+    #[serde(rename = "running")]
+    Running,
+    /// 完成
+    #[serde(rename = "finished")]
+    Finished,
+    /// 取消
+    #[serde(rename = "cancelled")]
+    Cancelled,
+}
+
+impl ScanStatus {
+    /// 数据库存储值
+    pub fn code(&self) -> i32 {
+        match self {
+            ScanStatus::Running => 0,
+            ScanStatus::Finished => 1,
+            ScanStatus::Cancelled => 2,
+        }
+    }
+
+    /// 从数据库存储值转换
+    pub fn from_code(code: i32) -> ScanStatus {
+        match code {
+            1 => ScanStatus::Finished,
+            2 => ScanStatus::Cancelled,
+            _ => ScanStatus::Running,
+        }
+    }
+}
+
+/// 单个文件的处理耗时
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanFileTiming {
+    /// 文件路径
+    pub path: String,
+    /// 处理耗时（毫秒）
+    pub millis: u64,
+    /// 文件大小（字节）
+    pub bytes: u64,
+}
+
+/// 单个文件夹的处理耗时
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanFolderTiming {
+    /// 文件夹路径
+    pub path: String,
+    /// 文件夹内文件处理耗时之和（毫秒）
+    pub millis: u64,
+    /// 文件数量
+    pub file_count: u32,
+}
+
+/// 扫描报告
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanReport {
+    /// 扫描 ID
+    pub scan_id: i32,
+    /// 状态
+    pub status: ScanStatus,
+    /// 处理的文件数量
+    pub file_count: u32,
+    /// 所有文件处理耗时之和（毫秒）
+    pub total_millis: u64,
+    /// 开始时间
+    pub start_time: i64,
+    /// 结束时间【扫描中时为 0】
+    pub end_time: i64,
+    /// 最慢的文件（按耗时倒序）
+    pub slowest_files: Vec<ScanFileTiming>,
+    /// 最慢的文件夹（按耗时倒序）
+    pub slowest_folders: Vec<ScanFolderTiming>,
+}
+
+/// 扫描前的工作量预估
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanPreview {
+    /// 待处理的图片数量
+    pub file_count: u64,
+    /// 图片总大小（字节）
+    pub total_bytes: u64,
+    /// 预计耗时（秒）
+    pub estimated_seconds: u64,
+    /// 预估使用的处理速度（文件/秒）
+    pub files_per_sec: f64,
+    /// 处理速度是否来自以往的扫描记录【没有记录时使用默认值】
+    pub from_history: bool,
+}
+
+/// 扫描过程中各文件耗时的汇总
+#[derive(Debug, Clone, Default)]
+pub struct ScanTimingSummary {
+    pub file_count: u32,
+    pub total_millis: u64,
+    pub slowest_files: Vec<ScanFileTiming>,
+    pub slowest_folders: Vec<ScanFolderTiming>,
+}
+
+/// 扫描耗时收集器，在多个任务间共享
+#[derive(Debug, Default)]
+pub struct ScanTimingCollector {
+    timings: Mutex<Vec<ScanFileTiming>>,
+}
+
+impl ScanTimingCollector {
+    pub fn new() -> ScanTimingCollector {
+        ScanTimingCollector::default()
+    }
+
+    /// 记录单个文件的处理耗时
+    pub fn record(&self, path: &str, millis: u64) {
+        let bytes = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        self.timings.lock().unwrap().push(ScanFileTiming {
+            path: path.to_string(),
+            millis,
+            bytes,
+        });
+    }
+
+    /// 汇总耗时
+    /// - top_n 保留最慢的文件、文件夹数量
+    pub fn summarize(&self, top_n: usize) -> ScanTimingSummary {
+        let timings = self.timings.lock().unwrap();
+
+        let mut folders: HashMap<String, ScanFolderTiming> = HashMap::new();
+        for timing in timings.iter() {
+            let folder = Path::new(&timing.path)
+                .parent()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default();
+            let entry = folders
+                .entry(folder.clone())
+                .or_insert_with(|| ScanFolderTiming {
+                    path: folder,
+                    millis: 0,
+                    file_count: 0,
+                });
+            entry.millis += timing.millis;
+            entry.file_count += 1;
+        }
+
+        let mut slowest_files = timings.clone();
+        slowest_files.sort_by(|a, b| b.millis.cmp(&a.millis));
+        slowest_files.truncate(top_n);
+
+        let mut slowest_folders: Vec<ScanFolderTiming> = folders.into_values().collect();
+        slowest_folders.sort_by(|a, b| b.millis.cmp(&a.millis));
+        slowest_folders.truncate(top_n);
+
+        ScanTimingSummary {
+            file_count: timings.len() as u32,
+            total_millis: timings.iter().map(|x| x.millis).sum(),
+            slowest_files,
+            slowest_folders,
+        }
+    }
+}
+
+/// 边遍历边处理时的扫描进度
+///
+/// 目录遍历完成前总数会不断增长，只有遍历结束且所有已发现的文件都处理完后才算完成
+#[derive(Debug, Default)]
+pub struct ScanProgress {
+    /// 已发现的文件数量
+    discovered: AtomicU32,
+    /// 已处理的文件数量
+    completed: AtomicU32,
+    /// 目录是否遍历完成
+    walk_done: AtomicBool,
+    /// 是否已结束【保证结束逻辑只执行一次】
+    finished: AtomicBool,
+}
+
+impl ScanProgress {
+    pub fn new() -> ScanProgress {
+        ScanProgress::default()
+    }
+
+    /// 发现一个新文件，返回当前已发现的数量
+    pub fn discover(&self) -> u32 {
+        self.discovered.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// 已发现的文件数量
+    pub fn discovered(&self) -> u32 {
+        self.discovered.load(Ordering::SeqCst)
+    }
+
+    /// 已处理的文件数量
+    pub fn completed(&self) -> u32 {
+        self.completed.load(Ordering::SeqCst)
+    }
+
+    /// 完成一个文件，返回当前已处理的数量
+    pub fn complete(&self) -> u32 {
+        self.completed.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// 标记目录遍历完成
+    pub fn finish_walk(&self) {
+        self.walk_done.store(true, Ordering::SeqCst);
+    }
+
+    /// 扫描是否刚好全部完成【只有第一次满足条件的调用返回 true】
+    pub fn try_finish(&self) -> bool {
+        self.walk_done.load(Ordering::SeqCst)
+            && self.completed.load(Ordering::SeqCst) == self.discovered.load(Ordering::SeqCst)
+            && !self.finished.swap(true, Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize() {
+        let collector = ScanTimingCollector::new();
+        collector.record("/photos/a/1.jpg", 10);
+        collector.record("/photos/a/2.tif", 900);
+        collector.record("/photos/b/3.jpg", 50);
+        collector.record("/photos/b/4.jpg", 60);
+
+        let summary = collector.summarize(1);
+        assert_eq!(summary.file_count, 4);
+        assert_eq!(summary.total_millis, 1020);
+        assert_eq!(summary.slowest_files.len(), 1);
+        assert_eq!(summary.slowest_files[0].path, "/photos/a/2.tif");
+        assert_eq!(summary.slowest_folders.len(), 1);
+        assert_eq!(summary.slowest_folders[0].file_count, 2);
+        assert_eq!(summary.slowest_folders[0].millis, 910);
+    }
+
+    #[test]
+    fn test_scan_progress() {
+        let progress = ScanProgress::new();
+        progress.discover();
+        progress.discover();
+        progress.complete();
+        progress.complete();
+        // 遍历未完成时不能结束
+        assert!(!progress.try_finish());
+        progress.finish_walk();
+        assert!(progress.try_finish());
+        assert!(!progress.try_finish());
+    }
+}
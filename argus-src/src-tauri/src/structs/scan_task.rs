@@ -0,0 +1,194 @@
+use crate::constant::SCAN_TASK_HISTORY_LIMIT;
+use crate::structs::scan_report::ScanStatus;
+use crate::utils::scan_metrics_util::ScanMetrics;
+use crate::utils::time_util::TimeUtils;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 所有扫描任务【前端刷新后可以据此恢复进度】
+pub static SCAN_TASKS: Lazy<ScanTaskRegistry> =
+    Lazy::new(|| ScanTaskRegistry::new(SCAN_TASK_HISTORY_LIMIT));
+
+/// 扫描任务状态
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanTaskStatus {
+    /// 任务 ID（UUID）
+    pub task_id: String,
+    /// 扫描 ID【用于获取扫描报告，扫描记录创建失败时为空】
+    pub scan_id: Option<i32>,
+    /// 状态
+    pub status: ScanStatus,
+    /// 总任务数【目录遍历完成前会不断增长】
+    pub all_task: u32,
+    /// 已完成的任务数
+    pub current_task: u32,
+    /// 处理失败的文件数量
+    pub error_count: u32,
+    /// 最近处理的文件
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_msg: Option<String>,
+    /// 开始时间
+    pub start_time: i64,
+    /// 结束时间【扫描中时为空】
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<i64>,
+    /// 吞吐量统计【扫描结束后才有】
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<ScanMetrics>,
+}
+
+/// 扫描任务登记表
+///
+/// 进行中的任务一直保留，已结束的任务只保留最近 `history_limit` 个
+#[derive(Debug)]
+pub struct ScanTaskRegistry {
+    history_limit: usize,
+    tasks: Mutex<VecDeque<ScanTaskStatus>>,
+    /// 进行中的任务【任务 ID -> 取消标记】
+    cancel_flags: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl ScanTaskRegistry {
+    pub fn new(history_limit: usize) -> ScanTaskRegistry {
+        ScanTaskRegistry {
+            history_limit,
+            tasks: Mutex::new(VecDeque::new()),
+            cancel_flags: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 登记新的扫描任务，返回任务的取消标记
+    pub fn register(&self, task_id: &str, scan_id: Option<i32>) -> Arc<AtomicBool> {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.cancel_flags
+            .lock()
+            .unwrap()
+            .insert(task_id.to_string(), cancelled.clone());
+        let mut tasks = self.tasks.lock().unwrap();
+        tasks.push_back(ScanTaskStatus {
+            task_id: task_id.to_string(),
+            scan_id,
+            status: ScanStatus::Running,
+            all_task: 0,
+            current_task: 0,
+            error_count: 0,
+            last_msg: None,
+            start_time: TimeUtils::current_timestamp(),
+            end_time: None,
+            metrics: None,
+        });
+        // 清理最早结束的任务
+        let finished = tasks.iter().filter(|x| x.end_time.is_some()).count();
+        for _ in self.history_limit..finished {
+            if let Some(index) = tasks.iter().position(|x| x.end_time.is_some()) {
+                tasks.remove(index);
+            }
+        }
+        cancelled
+    }
+
+    /// 更新任务进度
+    pub fn update_progress(&self, task_id: &str, all_task: u32, current_task: u32, msg: &str) {
+        self.update(task_id, |task| {
+            task.all_task = task.all_task.max(all_task);
+            task.current_task = task.current_task.max(current_task);
+            task.last_msg = Some(msg.to_string());
+        });
+    }
+
+    /// 记录一个处理失败的文件
+    pub fn record_error(&self, task_id: &str) {
+        self.update(task_id, |task| task.error_count += 1);
+    }
+
+    /// 结束任务
+    pub fn finish(&self, task_id: &str, status: ScanStatus, metrics: Option<ScanMetrics>) {
+        self.cancel_flags.lock().unwrap().remove(task_id);
+        self.update(task_id, |task| {
+            task.status = status;
+            task.end_time = Some(TimeUtils::current_timestamp());
+            task.metrics = metrics;
+        });
+    }
+
+    /// 取消进行中的任务【只影响这个任务，任务不在进行中时返回 false】
+    pub fn cancel(&self, task_id: &str) -> bool {
+        match self.cancel_flags.lock().unwrap().get(task_id) {
+            Some(cancelled) => {
+                cancelled.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 是否有进行中的扫描任务
+    pub fn has_running(&self) -> bool {
+        let tasks = self.tasks.lock().unwrap();
+        tasks.iter().any(|x| x.end_time.is_none())
+    }
+
+    /// 获取任务状态
+    pub fn get(&self, task_id: &str) -> Option<ScanTaskStatus> {
+        let tasks = self.tasks.lock().unwrap();
+        tasks.iter().find(|x| x.task_id == task_id).cloned()
+    }
+
+    fn update<F: FnOnce(&mut ScanTaskStatus)>(&self, task_id: &str, f: F) {
+        let mut tasks = self.tasks.lock().unwrap();
+        match tasks.iter_mut().find(|x| x.task_id == task_id) {
+            Some(task) => f(task),
+            None => log::warn!("扫描任务不存在: {}", task_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_task_registry() {
+        let registry = ScanTaskRegistry::new(1);
+        registry.register("a", Some(1));
+        registry.update_progress("a", 3, 1, "1.jpg");
+        // 并发完成时进度不回退
+        registry.update_progress("a", 3, 0, "2.jpg");
+        registry.record_error("a");
+        let task = registry.get("a").unwrap();
+        assert_eq!((task.all_task, task.current_task, task.error_count), (3, 1, 1));
+        assert_eq!(task.status, ScanStatus::Running);
+
+        assert!(registry.has_running());
+
+        registry.finish("a", ScanStatus::Finished, None);
+        assert!(!registry.has_running());
+        registry.register("b", None);
+        registry.finish("b", ScanStatus::Cancelled, None);
+        registry.register("c", None);
+        // 只保留最近一个已结束的任务，进行中的任务不清理
+        assert!(registry.get("a").is_none());
+        assert_eq!(registry.get("b").unwrap().status, ScanStatus::Cancelled);
+        assert!(registry.get("c").unwrap().end_time.is_none());
+    }
+
+    #[test]
+    fn test_scan_task_cancel() {
+        let registry = ScanTaskRegistry::new(1);
+        let a = registry.register("a", None);
+        let b = registry.register("b", None);
+        // 取消一个任务不影响同时进行的其他任务
+        assert!(registry.cancel("a"));
+        assert!(a.load(Ordering::SeqCst));
+        assert!(!b.load(Ordering::SeqCst));
+
+        // 已结束和不存在的任务无法取消
+        registry.finish("b", ScanStatus::Finished, None);
+        assert!(!registry.cancel("b"));
+        assert!(!registry.cancel("c"));
+    }
+}
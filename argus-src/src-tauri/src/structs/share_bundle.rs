@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// 分享包选项
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareBundleOptions {
+    /// 照片长边尺寸（像素）【为空时使用 2048，小于此尺寸的照片不放大】
+    pub max_size: Option<u32>,
+    /// JPEG 质量（1 - 100）
+    pub quality: Option<u8>,
+    /// 是否打包为 zip【默认为文件夹】
+    pub zip: Option<bool>,
+    /// 网页标题【为空时使用默认标题】
+    pub title: Option<String>,
+    /// 网页中是否显示照片说明【默认显示】
+    pub include_captions: Option<bool>,
+    /// 保存的文件夹【为空时使用系统临时文件夹】
+    pub dest_path: Option<String>,
+}
+
+/// 分享包生成结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareBundleSummary {
+    /// 分享包路径（文件夹或 zip 文件）
+    pub path: String,
+    /// 包含的照片数量
+    pub photo_count: usize,
+    /// 总大小（字节）
+    pub total_bytes: u64,
+    /// 处理失败的照片及原因
+    pub failed: Vec<String>,
+}
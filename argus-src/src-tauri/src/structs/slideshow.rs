@@ -0,0 +1,102 @@
+use crate::constant::{
+    SLIDESHOW_DEFAULT_FPS, SLIDESHOW_DEFAULT_SIZE, SLIDESHOW_DEFAULT_SLIDE_SECONDS,
+};
+use serde::{Deserialize, Serialize};
+
+/// 幻灯片视频选项
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SlideshowOptions {
+    /// 输出文件路径（.mp4）
+    pub output_path: String,
+    /// 每张照片显示的秒数
+    pub slide_seconds: Option<f32>,
+    /// 是否使用 Ken Burns 效果（缓慢平移缩放）
+    pub ken_burns: Option<bool>,
+    /// 背景音乐文件【比视频短时循环播放，比视频长时截断】
+    pub music_path: Option<String>,
+    /// 视频宽度
+    pub width: Option<u32>,
+    /// 视频高度
+    pub height: Option<u32>,
+    /// 帧率
+    pub fps: Option<u32>,
+}
+
+impl SlideshowOptions {
+    /// 每张照片显示的秒数【不合法时使用默认值】
+    pub fn slide_seconds(&self) -> f32 {
+        self.slide_seconds
+            .filter(|x| x.is_finite() && *x > 0.0)
+            .unwrap_or(SLIDESHOW_DEFAULT_SLIDE_SECONDS)
+    }
+
+    /// 视频分辨率【编码要求宽高为偶数】
+    pub fn size(&self) -> (u32, u32) {
+        let width = self
+            .width
+            .filter(|x| *x > 0)
+            .unwrap_or(SLIDESHOW_DEFAULT_SIZE.0);
+        let height = self
+            .height
+            .filter(|x| *x > 0)
+            .unwrap_or(SLIDESHOW_DEFAULT_SIZE.1);
+        (width & !1, height & !1)
+    }
+
+    pub fn fps(&self) -> u32 {
+        self.fps.filter(|x| *x > 0).unwrap_or(SLIDESHOW_DEFAULT_FPS)
+    }
+
+    /// 每张照片的帧数
+    pub fn frames_per_slide(&self) -> u32 {
+        ((self.slide_seconds() * self.fps() as f32).round() as u32).max(1)
+    }
+}
+
+/// 幻灯片视频任务状态
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SlideshowStatus {
+    /// 生成中
+    Running,
+    /// 已完成
+    Completed,
+    /// 失败
+    Failed,
+    /// 已取消
+    Cancelled,
+}
+
+/// 幻灯片视频生成进度
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SlideshowProgressMsg {
+    /// 任务 ID
+    pub job_id: String,
+    pub status: SlideshowStatus,
+    /// 进度百分比（0 - 100）
+    pub percent: f32,
+    /// 输出文件路径
+    pub output_path: String,
+    /// 失败原因
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_defaults() {
+        let options = SlideshowOptions {
+            slide_seconds: Some(-1.0),
+            width: Some(1281),
+            fps: Some(25),
+            ..Default::default()
+        };
+        assert_eq!(options.slide_seconds(), SLIDESHOW_DEFAULT_SLIDE_SECONDS);
+        assert_eq!(options.size(), (1280, SLIDESHOW_DEFAULT_SIZE.1));
+        assert_eq!(options.frames_per_slide(), 75);
+    }
+}
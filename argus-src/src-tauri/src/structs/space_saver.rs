@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 节省空间的方式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SpaceSaverMethod {
+    /// JPEG 无损优化【优化霍夫曼表并转换为渐进式，像素不变】
+    JpegOptimize,
+    /// PNG 转换为无损 WebP【像素不变，扩展名改为 .webp】
+    PngToWebp,
+}
+
+/// 可以节省空间的文件
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SpaceSaverItem {
+    pub photo_id: i32,
+    /// 原文件
+    pub path: String,
+    /// 确认后的文件路径【JPEG 与原文件相同】
+    pub new_path: String,
+    pub method: SpaceSaverMethod,
+    /// 原文件大小（字节）
+    pub old_size: u64,
+    /// 新文件大小（字节）
+    pub new_size: u64,
+    /// 已写入并校验的新文件【与原文件同目录的临时文件】
+    #[serde(skip)]
+    pub temp_path: PathBuf,
+    /// 原文件的 Hash【确认时检查原文件是否被修改】
+    #[serde(skip)]
+    pub old_hash: String,
+}
+
+/// 等待确认的计划写入照片文件夹的临时文件【保存在照片库中，异常退出后启动时清理】
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpaceSaverPendingFile {
+    pub photo_id: i32,
+    /// 原文件
+    pub path: String,
+    /// 已写入的新文件
+    pub temp_path: PathBuf,
+    /// 原文件的 Hash【数据库中仍是此 Hash 时说明替换没有完成】
+    pub old_hash: String,
+}
+
+/// 节省空间计划【新文件已写入并校验，确认后才替换原文件】
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SpaceSaverPlan {
+    /// 计划 ID【确认、放弃时使用】
+    pub id: String,
+    /// 创建时间（时间戳）
+    pub create_time: i64,
+    pub items: Vec<SpaceSaverItem>,
+    /// 检查的文件数量
+    pub checked: usize,
+    /// 原文件总大小（字节）
+    pub old_bytes: u64,
+    /// 新文件总大小（字节）
+    pub new_bytes: u64,
+    /// 跳过的文件及原因【节省的空间太少、包含无法保留的元数据、校验失败等】
+    pub skipped: Vec<String>,
+}
+
+impl SpaceSaverPlan {
+    /// 确认后可以节省的空间（字节）
+    pub fn saved_bytes(&self) -> u64 {
+        self.old_bytes.saturating_sub(self.new_bytes)
+    }
+}
+
+/// 确认节省空间计划的结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SpaceSaverCommitSummary {
+    /// 替换成功的文件数量
+    pub replaced: usize,
+    /// 实际节省的空间（字节）
+    pub saved_bytes: u64,
+    /// 失败的文件及原因【原文件保持不变】
+    pub failed: Vec<String>,
+}
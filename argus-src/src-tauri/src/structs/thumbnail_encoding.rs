@@ -0,0 +1,172 @@
+use crate::conf::CONF_DEFAULT;
+use crate::constant::{
+    IMAGE_COMPRESSION_STORAGE_FORMAT, THUMBNAIL_CACHE_VERSION, THUMBNAIL_GENERATION_PREFIX,
+};
+use crate::structs::config::sys_config;
+use crate::utils::graphic_detect_util;
+use crate::utils::hash_util::HashUtil;
+use image::{DynamicImage, ImageFormat};
+use serde::{Deserialize, Serialize};
+
+/// 缩略图格式
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailFormat {
+    #[serde(rename = "jpeg")]
+    Jpeg,
+    #[serde(rename = "webp")]
+    Webp,
+    #[serde(rename = "avif")]
+    Avif,
+}
+
+impl ThumbnailFormat {
+    /// 读取配置，配置无效时使用默认存储格式
+    pub fn from_config() -> Option<ThumbnailFormat> {
+        sys_config()
+            .thumbnail_format
+            .as_deref()
+            .and_then(ThumbnailFormat::from_str)
+    }
+
+    /// 从配置字符串转换
+    pub fn from_str(input: &str) -> Option<ThumbnailFormat> {
+        match input.trim().to_lowercase().as_str() {
+            "jpeg" | "jpg" => Some(ThumbnailFormat::Jpeg),
+            "webp" => Some(ThumbnailFormat::Webp),
+            "avif" => Some(ThumbnailFormat::Avif),
+            _ => None,
+        }
+    }
+
+    pub fn image_format(&self) -> ImageFormat {
+        match self {
+            ThumbnailFormat::Jpeg => ImageFormat::Jpeg,
+            ThumbnailFormat::Webp => ImageFormat::WebP,
+            ThumbnailFormat::Avif => ImageFormat::Avif,
+        }
+    }
+}
+
+/// 缩略图的存储格式【决定缩略图文件的后缀名】
+pub fn storage_format() -> ImageFormat {
+    ThumbnailFormat::from_config()
+        .map(|format| format.image_format())
+        .unwrap_or(IMAGE_COMPRESSION_STORAGE_FORMAT)
+}
+
+/// 缩略图缓存的版本目录名【编码参数变化后生成到新的目录，旧版本由缓存清理删除】
+pub fn generation() -> String {
+    let config = sys_config();
+    let defaults = EncodeOptions::new(storage_format());
+    let options = EncodeOptions {
+        quality: config.thumbnail_quality.unwrap_or(defaults.quality),
+        speed: config.thumbnail_speed.unwrap_or(defaults.speed),
+        lossless: config.thumbnail_lossless_graphics.unwrap_or(true),
+        ..defaults
+    };
+    generation_of(&options)
+}
+
+/// 按编码参数计算版本目录名【只使用对该格式有影响的参数，格式为 `g` + 8 位十六进制】
+pub fn generation_of(options: &EncodeOptions) -> String {
+    let key = match options.format {
+        ImageFormat::Jpeg => format!("jpeg|q{}", options.quality),
+        ImageFormat::WebP => format!(
+            "webp|q{}|s{}|l{}",
+            options.quality, options.speed, options.lossless
+        ),
+        ImageFormat::Avif => format!("avif|q{}|s{}", options.quality, options.speed),
+        format => format!("{:?}", format),
+    };
+    let hash = HashUtil::sha256(&format!("{}|{}", THUMBNAIL_CACHE_VERSION, key));
+    format!("{}{}", THUMBNAIL_GENERATION_PREFIX, &hash[..8])
+}
+
+/// 目录名是否为缩略图缓存的版本目录
+pub fn is_generation(name: &str) -> bool {
+    name.strip_prefix(THUMBNAIL_GENERATION_PREFIX)
+        .is_some_and(|x| x.len() == 8 && x.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// 图片编码参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EncodeOptions {
+    pub format: ImageFormat,
+    /// 编码质量（1 - 100）
+    pub quality: u8,
+    /// 编码速度（1 - 10）【只用于 WebP、AVIF】
+    pub speed: u8,
+    /// 无损编码【只用于 WebP】
+    pub lossless: bool,
+}
+
+impl EncodeOptions {
+    /// 使用默认质量、速度的有损编码【与配置的默认值一致】
+    pub fn new(format: ImageFormat) -> EncodeOptions {
+        EncodeOptions {
+            format,
+            quality: CONF_DEFAULT.thumbnail_quality,
+            speed: CONF_DEFAULT.thumbnail_speed,
+            lossless: false,
+        }
+    }
+
+    /// 按配置生成缩略图的编码参数
+    ///
+    /// 缩略图格式为 WebP 且开启了图形类无损时，截图、界面等图形类图片使用无损编码
+    /// - format 缩略图格式【与文件后缀保持一致】
+    /// - image 待编码的缩略图
+    pub fn thumbnail(format: ImageFormat, image: &DynamicImage) -> EncodeOptions {
+        let config = sys_config();
+        let mut options = EncodeOptions::new(format);
+        if let Some(quality) = config.thumbnail_quality {
+            options.quality = quality;
+        }
+        if let Some(speed) = config.thumbnail_speed {
+            options.speed = speed;
+        }
+        options.lossless = format == ImageFormat::WebP
+            && config.thumbnail_lossless_graphics.unwrap_or(true)
+            && graphic_detect_util::is_graphic(image);
+        options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generation_of() {
+        let jpeg = EncodeOptions::new(ImageFormat::Jpeg);
+        let generation = generation_of(&jpeg);
+        assert!(is_generation(&generation));
+        // JPEG 不使用速度、无损参数
+        let faster = EncodeOptions {
+            speed: 10,
+            lossless: true,
+            ..jpeg
+        };
+        assert_eq!(generation_of(&faster), generation);
+        let lower = EncodeOptions {
+            quality: 70,
+            ..jpeg
+        };
+        assert_ne!(generation_of(&lower), generation);
+        let webp = EncodeOptions::new(ImageFormat::WebP);
+        assert_ne!(generation_of(&webp), generation);
+        let lossless = EncodeOptions {
+            lossless: true,
+            ..webp
+        };
+        assert_ne!(generation_of(&lossless), generation_of(&webp));
+    }
+
+    #[test]
+    fn test_is_generation() {
+        assert!(is_generation("g0123abcd"));
+        assert!(!is_generation("g0123abc"));
+        assert!(!is_generation("0123abcd"));
+        assert!(!is_generation("gxyz12345"));
+    }
+}
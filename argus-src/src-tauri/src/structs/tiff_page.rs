@@ -0,0 +1,52 @@
+use crate::utils::exif_utils::tiff;
+use serde::{Deserialize, Serialize};
+
+/// TIFF 页面尺寸
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TiffPage {
+    /// 页序号（从 0 开始）【SubIFD 为所属页面的序号】
+    pub index: usize,
+    /// 是否为 SubIFD（金字塔 TIFF 的缩小图等）
+    pub sub_ifd: bool,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl From<tiff::Page> for TiffPage {
+    fn from(page: tiff::Page) -> Self {
+        TiffPage {
+            index: page.index,
+            sub_ifd: page.sub_ifd,
+            width: page.width,
+            height: page.height,
+        }
+    }
+}
+
+/// 多页 TIFF 的页面信息
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TiffPageInfo {
+    /// 页数【不包括 SubIFD】
+    pub page_count: usize,
+    /// 所有页面【每页之后是它的 SubIFD】
+    pub pages: Vec<TiffPage>,
+    /// 生成缩略图时使用的页面（像素最多）在 pages 中的位置
+    pub largest: Option<usize>,
+}
+
+impl TiffPageInfo {
+    pub fn new(pages: Vec<tiff::Page>) -> TiffPageInfo {
+        let largest = pages
+            .iter()
+            .enumerate()
+            .reduce(|a, b| if b.1.area() > a.1.area() { b } else { a })
+            .map(|(i, _)| i);
+        TiffPageInfo {
+            page_count: pages.iter().filter(|x| !x.sub_ifd).count(),
+            pages: pages.into_iter().map(TiffPage::from).collect(),
+            largest,
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::i18n::Locale;
+use crate::structs::photo_list::PhotoListSort;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// 界面主题
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum Theme {
+    /// 跟随系统
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    /// 保存到数据库的名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Theme::System => "system",
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+
+    /// 从名称转换
+    pub fn from_str(input: &str) -> Option<Theme> {
+        match input.trim().to_lowercase().as_str() {
+            "system" => Some(Theme::System),
+            "light" => Some(Theme::Light),
+            "dark" => Some(Theme::Dark),
+            _ => None,
+        }
+    }
+}
+
+/// 照片网格密度
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum GridDensity {
+    /// 紧凑【小缩略图】
+    Compact,
+    #[default]
+    Comfortable,
+    /// 宽松【大缩略图】
+    Spacious,
+}
+
+impl GridDensity {
+    /// 保存到数据库的名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GridDensity::Compact => "compact",
+            GridDensity::Comfortable => "comfortable",
+            GridDensity::Spacious => "spacious",
+        }
+    }
+
+    /// 从名称转换
+    pub fn from_str(input: &str) -> Option<GridDensity> {
+        match input.trim().to_lowercase().as_str() {
+            "compact" => Some(GridDensity::Compact),
+            "comfortable" => Some(GridDensity::Comfortable),
+            "spacious" => Some(GridDensity::Spacious),
+            _ => None,
+        }
+    }
+}
+
+/// 用户资料和偏好设置
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserProfile {
+    /// 用户 ID【为空表示新用户】
+    pub id: Option<i32>,
+    /// 显示名称
+    pub display_name: String,
+    /// 界面语言【为空时使用配置文件中的语言】
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub theme: Theme,
+    /// 照片列表默认排序
+    #[serde(default)]
+    pub default_sort: PhotoListSort,
+    #[serde(default)]
+    pub grid_density: GridDensity,
+    /// 搜索结果中隐藏保险箱照片
+    #[serde(default = "default_vault_safe_search")]
+    pub vault_safe_search: bool,
+}
+
+fn default_vault_safe_search() -> bool {
+    true
+}
+
+impl UserProfile {
+    /// 检查显示名称和界面语言
+    pub fn validate(&self) -> Result<()> {
+        if self.display_name.trim().is_empty() {
+            return Err(anyhow!("显示名称不能为空!"));
+        }
+        if let Some(language) = &self.language {
+            if Locale::from_str(language).is_none() {
+                return Err(anyhow!("不支持的界面语言: {}", language));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_enum_names() {
+        for theme in [Theme::System, Theme::Light, Theme::Dark] {
+            assert_eq!(Theme::from_str(theme.as_str()), Some(theme));
+        }
+        for density in [
+            GridDensity::Compact,
+            GridDensity::Comfortable,
+            GridDensity::Spacious,
+        ] {
+            assert_eq!(GridDensity::from_str(density.as_str()), Some(density));
+        }
+        assert_eq!(Theme::from_str("blue"), None);
+    }
+
+    #[test]
+    fn test_validate_profile() {
+        let mut profile: UserProfile =
+            serde_json::from_str(r#"{"id": null, "displayName": "Alice"}"#).unwrap();
+        assert!(profile.validate().is_ok());
+        assert!(profile.vault_safe_search);
+        profile.language = Some("fr".to_string());
+        assert!(profile.validate().is_err());
+        profile.language = None;
+        profile.display_name = " ".to_string();
+        assert!(profile.validate().is_err());
+    }
+}
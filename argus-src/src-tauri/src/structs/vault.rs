@@ -0,0 +1,62 @@
+use crate::models::vault_item::PhotoPrivateFields;
+use serde::{Deserialize, Serialize};
+
+/// 保险箱配置【保存在保险箱文件夹中，不包含密钥】
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultConfig {
+    /// 派生密钥的随机盐（Base64）
+    pub salt: String,
+    /// PBKDF2 迭代次数
+    pub iterations: u32,
+    /// 用密钥加密的校验数据（Base64）【解锁时用来判断密码是否正确】
+    pub verifier: String,
+    pub create_time: i64,
+}
+
+/// 保险箱状态
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultStatus {
+    /// 是否已创建
+    pub created: bool,
+    /// 是否已解锁
+    pub unlocked: bool,
+    /// 照片数量
+    pub item_count: i64,
+}
+
+/// 保险箱中的照片【只在解锁后返回】
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultItemInfo {
+    pub id: i32,
+    pub photo_id: i32,
+    pub img_name: String,
+    /// 原图大小（字节）
+    pub file_size: i64,
+    /// 移入保险箱的时间（时间戳）
+    pub create_time: i64,
+    /// 解密到临时文件夹中的缩略图【没有缩略图时为空】
+    pub thumbnail_path: Option<String>,
+}
+
+/// 移入、移出保险箱的结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultSummary {
+    /// 成功的数量
+    pub succeeded: usize,
+    /// 失败的照片及原因【失败的照片保持原样】
+    pub failed: Vec<String>,
+}
+
+/// 移入保险箱的照片的私密信息【加密后保存在保险箱中，与原图同名】
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultMetadata {
+    /// 照片说明、备注和位置信息
+    pub fields: PhotoPrivateFields,
+    /// 标签 ID
+    pub tag_ids: Vec<i32>,
+}
@@ -0,0 +1,180 @@
+use crate::i18n;
+use crate::i18n::msg;
+use crate::structs::rename_template::{sanitize_file_name, RenameTemplate};
+use anyhow::{anyhow, Result};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// 监视文件夹及其导入配置
+///
+/// 放入监视文件夹（包括子文件夹）的图片自动复制或移动到目标文件夹并导入，
+/// 导入后加入指定相册、添加指定标签
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchFolder {
+    /// 监视文件夹 ID【新建时为空】
+    #[serde(default)]
+    pub id: Option<i32>,
+    /// 监视的文件夹
+    pub path: String,
+    /// 导入的目标文件夹
+    pub dest_dir: String,
+    /// 子文件夹模板【字段见 `RenameTemplate`，各级文件夹用 `/` 分隔，如 `{YYYY}/{YYYY}-{MM}-{DD}`；
+    /// 为空时直接放入目标文件夹】
+    #[serde(default)]
+    pub dest_template: Option<String>,
+    /// 加入的相册【不存在时新建顶级相册】
+    #[serde(default)]
+    pub album: Option<String>,
+    /// 添加的标签【不存在时新建顶级标签】
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 是否移动文件【否则复制，监视文件夹中保留原文件】
+    #[serde(default)]
+    pub move_files: bool,
+    /// 是否启用
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 是否正在监视【文件夹不存在或无法监视时为 false，保存时忽略】
+    #[serde(default)]
+    pub watching: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl WatchFolder {
+    /// 校验监视文件夹、目标文件夹和子文件夹模板
+    pub fn validate(&self) -> Result<()> {
+        let path = self.path.trim();
+        let dest_dir = self.dest_dir.trim();
+        if path.is_empty() {
+            return Err(anyhow!(i18n::text(msg::WATCH_FOLDER_PATH_REQUIRED)));
+        }
+        if dest_dir.is_empty() {
+            return Err(anyhow!(i18n::text(msg::WATCH_FOLDER_DEST_REQUIRED)));
+        }
+        // 目标文件夹在监视文件夹中时，导入的文件会再次触发导入
+        if Path::new(dest_dir).starts_with(path) {
+            return Err(i18n::error(
+                msg::WATCH_FOLDER_DEST_INSIDE,
+                &[("path", path.to_string())],
+            ));
+        }
+        if let Some(template) = self.template() {
+            for segment in template.split('/').filter(|x| !x.trim().is_empty()) {
+                RenameTemplate::parse(segment)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 文件导入后的路径【文件名不变，不检查是否已有同名文件】
+    /// - source 监视文件夹中的文件
+    /// - time 拍摄地当地时间
+    pub fn dest_path(
+        &self,
+        source: &Path,
+        time: NaiveDateTime,
+        make: Option<&str>,
+        model: Option<&str>,
+    ) -> Result<PathBuf> {
+        let name = source
+            .file_name()
+            .ok_or_else(|| anyhow!("文件名 {} 无效!", source.display()))?;
+        let stem = source
+            .file_stem()
+            .map(|x| x.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut dest = PathBuf::from(self.dest_dir.trim());
+        if let Some(template) = self.template() {
+            for segment in template.split('/').filter(|x| !x.trim().is_empty()) {
+                let folder =
+                    RenameTemplate::parse(segment)?.render_with(time, make, model, &stem, 1, 1);
+                // 字段为空时跳过这一级文件夹
+                let folder = sanitize_file_name(&folder);
+                if !folder.is_empty() {
+                    dest.push(folder);
+                }
+            }
+        }
+        dest.push(name);
+        Ok(dest)
+    }
+
+    /// 去掉空白的子文件夹模板
+    fn template(&self) -> Option<&str> {
+        self.dest_template
+            .as_deref()
+            .map(|x| x.trim().trim_matches('/'))
+            .filter(|x| !x.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn folder(template: Option<&str>) -> WatchFolder {
+        WatchFolder {
+            id: None,
+            path: "/home/me/Inbox".to_string(),
+            dest_dir: "/home/me/Pictures".to_string(),
+            dest_template: template.map(|x| x.to_string()),
+            album: None,
+            tags: Vec::new(),
+            move_files: false,
+            enabled: true,
+            watching: false,
+        }
+    }
+
+    #[test]
+    fn test_validate_watch_folder() {
+        assert!(folder(None).validate().is_ok());
+        assert!(folder(Some("{YYYY}/{YYYY}-{MM}-{DD}")).validate().is_ok());
+        assert!(folder(Some("{YYYY}/{unknown}")).validate().is_err());
+
+        let mut nested = folder(None);
+        nested.dest_dir = "/home/me/Inbox/imported".to_string();
+        assert_eq!(
+            nested.validate().unwrap_err().to_string(),
+            i18n::t(
+                msg::WATCH_FOLDER_DEST_INSIDE,
+                &[("path", nested.path.clone())]
+            )
+        );
+        // 只是名称前缀相同不算在监视文件夹中
+        nested.dest_dir = "/home/me/Inbox2".to_string();
+        assert!(nested.validate().is_ok());
+    }
+
+    #[test]
+    fn test_dest_path() {
+        let time = NaiveDate::from_ymd_opt(2024, 5, 1)
+            .unwrap()
+            .and_hms_opt(8, 30, 0)
+            .unwrap();
+        let source = Path::new("/home/me/Inbox/IMG_0001.JPG");
+        assert_eq!(
+            folder(None).dest_path(source, time, None, None).unwrap(),
+            PathBuf::from("/home/me/Pictures/IMG_0001.JPG")
+        );
+        assert_eq!(
+            folder(Some("/{YYYY}/{YYYY}-{MM}-{DD}/"))
+                .dest_path(source, time, None, None)
+                .unwrap(),
+            PathBuf::from("/home/me/Pictures/2024/2024-05-01/IMG_0001.JPG")
+        );
+        // 相机型号为空时跳过这一级
+        assert_eq!(
+            folder(Some("{model}/{YYYY}"))
+                .dest_path(source, time, Some("Canon"), None)
+                .unwrap(),
+            PathBuf::from("/home/me/Pictures/2024/IMG_0001.JPG")
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use crate::constant::{
+    WATERMARK_DEFAULT_MARGIN, WATERMARK_DEFAULT_OPACITY, WATERMARK_DEFAULT_SCALE,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 水印位置
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum WatermarkPosition {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    #[default]
+    BottomRight,
+}
+
+impl WatermarkPosition {
+    /// 水印左上角坐标
+    /// - canvas 图像宽高
+    /// - mark 水印宽高
+    /// - margin 边距
+    pub fn offset(&self, canvas: (u32, u32), mark: (u32, u32), margin: u32) -> (i64, i64) {
+        let align = |canvas: u32, mark: u32, index: u8| -> i64 {
+            let free = canvas.saturating_sub(mark);
+            match index {
+                0 => margin.min(free) as i64,
+                1 => (free / 2) as i64,
+                _ => free.saturating_sub(margin) as i64,
+            }
+        };
+        let (column, row) = match self {
+            WatermarkPosition::TopLeft => (0, 0),
+            WatermarkPosition::Top => (1, 0),
+            WatermarkPosition::TopRight => (2, 0),
+            WatermarkPosition::Left => (0, 1),
+            WatermarkPosition::Center => (1, 1),
+            WatermarkPosition::Right => (2, 1),
+            WatermarkPosition::BottomLeft => (0, 2),
+            WatermarkPosition::Bottom => (1, 2),
+            WatermarkPosition::BottomRight => (2, 2),
+        };
+        (
+            align(canvas.0, mark.0, column),
+            align(canvas.1, mark.1, row),
+        )
+    }
+}
+
+/// 水印选项
+///
+/// 文字和 logo 同时设置时只使用 logo
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WatermarkOptions {
+    /// 文字模板，如 `© {artist} {year}`【可用字段见 `render_template`；没有可用字体时只支持 ASCII 字符和 ©】
+    pub text: Option<String>,
+    /// 文字字体（TTF、OTF、TTC）【为空时使用系统中支持中文的字体】
+    pub font_path: Option<String>,
+    /// logo 图片（PNG，支持透明）
+    pub logo_path: Option<String>,
+    /// 位置【默认右下角】
+    pub position: Option<WatermarkPosition>,
+    /// 不透明度（0 - 1）
+    pub opacity: Option<f32>,
+    /// 大小：文字、logo 高度相对图像短边的比例
+    pub scale: Option<f32>,
+    /// 边距：相对图像短边的比例
+    pub margin: Option<f32>,
+    /// 文字颜色，如 `#FFFFFF`【默认白色】
+    pub color: Option<String>,
+}
+
+impl WatermarkOptions {
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+            .filter(|x| x.is_finite())
+            .unwrap_or(WATERMARK_DEFAULT_OPACITY)
+            .clamp(0.0, 1.0)
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.scale
+            .filter(|x| x.is_finite() && *x > 0.0 && *x <= 1.0)
+            .unwrap_or(WATERMARK_DEFAULT_SCALE)
+    }
+
+    pub fn margin(&self) -> f32 {
+        self.margin
+            .filter(|x| x.is_finite() && *x >= 0.0 && *x < 0.5)
+            .unwrap_or(WATERMARK_DEFAULT_MARGIN)
+    }
+
+    /// 文字颜色【格式错误时使用白色】
+    pub fn color_rgb(&self) -> [u8; 3] {
+        let parse = |hex: &str| -> Option<[u8; 3]> {
+            let hex = hex.trim().trim_start_matches('#');
+            if hex.len() != 6 {
+                return None;
+            }
+            let value = u32::from_str_radix(hex, 16).ok()?;
+            Some([(value >> 16) as u8, (value >> 8) as u8, value as u8])
+        };
+        self.color
+            .as_deref()
+            .and_then(parse)
+            .unwrap_or([255, 255, 255])
+    }
+}
+
+/// 替换水印文字模板中的字段
+///
+/// 字段写作 `{字段名}`，照片没有该字段的值时替换为空；未知字段保持原样
+/// - template 模板
+/// - fields 字段值【artist、copyright、credit、year、date、make、model、name、caption】
+pub fn render_template(template: &str, fields: &HashMap<&str, String>) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        match tail.find('}') {
+            Some(end) if fields.contains_key(&tail[1..end]) => {
+                result.push_str(&fields[&tail[1..end]]);
+                rest = &tail[end + 1..];
+            }
+            _ => {
+                result.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    // 字段为空时去掉多余的空格
+    result.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let fields = HashMap::from([
+            ("artist", "Jane Doe".to_string()),
+            ("year", "2024".to_string()),
+            ("model", String::new()),
+        ]);
+        assert_eq!(
+            render_template("© {artist} {model} {year} {unknown}", &fields),
+            "© Jane Doe 2024 {unknown}"
+        );
+    }
+
+    #[test]
+    fn test_position_offset() {
+        let canvas = (1000, 500);
+        let mark = (100, 50);
+        assert_eq!(
+            WatermarkPosition::TopLeft.offset(canvas, mark, 10),
+            (10, 10)
+        );
+        assert_eq!(
+            WatermarkPosition::Center.offset(canvas, mark, 10),
+            (450, 225)
+        );
+        assert_eq!(
+            WatermarkPosition::BottomRight.offset(canvas, mark, 10),
+            (890, 440)
+        );
+    }
+}
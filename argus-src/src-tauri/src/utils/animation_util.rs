@@ -0,0 +1,334 @@
+//! 动图（GIF、APNG、动态 WebP）
+//!
+//! 帧数和时长只解析文件结构读取，不解码像素；缩略图使用第一帧
+
+use crate::utils::file_util;
+use anyhow::Result;
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
+use image::{AnimationDecoder, DynamicImage, ImageFormat};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// GIF 扩展块
+const GIF_EXTENSION: u8 = 0x21;
+/// GIF 图像描述符
+const GIF_IMAGE_DESCRIPTOR: u8 = 0x2C;
+/// GIF 结束符
+const GIF_TRAILER: u8 = 0x3B;
+/// GIF 图形控制扩展【包含下一帧的延时】
+const GIF_GRAPHIC_CONTROL: u8 = 0xF9;
+/// PNG 文件签名
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// 动图信息
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationInfo {
+    /// 帧数
+    pub frame_count: u32,
+    /// 播放一遍的时长（毫秒）
+    pub duration_ms: u32,
+}
+
+/// 读取动图信息【不是动图（只有一帧）或格式不支持时返回 None】
+///
+/// 按块读取文件结构，跳过图像数据，内存占用与文件大小无关
+/// - format 图片格式
+pub fn read_animation(path: &Path, format: Option<ImageFormat>) -> Option<AnimationInfo> {
+    if !matches!(
+        format,
+        Some(ImageFormat::Gif | ImageFormat::Png | ImageFormat::WebP)
+    ) {
+        return None;
+    }
+    let mut reader = BufReader::new(File::open(file_util::long_path(path)).ok()?);
+    let info = match format? {
+        ImageFormat::Gif => gif_animation(&mut reader),
+        ImageFormat::Png => apng_animation(&mut reader),
+        _ => webp_animation(&mut reader),
+    }?;
+    (info.frame_count > 1).then_some(info)
+}
+
+/// 解码动图的第一帧【不是动图时返回 None，由调用方按普通图片解码】
+///
+/// APNG 的默认图像可以不属于动画，动态 WebP 需要按帧合成，都不能直接解码
+pub fn decode_first_frame(path: &Path, format: ImageFormat) -> Result<Option<DynamicImage>> {
+    let reader = BufReader::new(File::open(file_util::long_path(path))?);
+    let mut frames = match format {
+        ImageFormat::Gif => GifDecoder::new(reader)?.into_frames(),
+        ImageFormat::Png => {
+            let decoder = PngDecoder::new(reader)?;
+            if !decoder.is_apng()? {
+                return Ok(None);
+            }
+            decoder.apng()?.into_frames()
+        }
+        ImageFormat::WebP => {
+            let decoder = WebPDecoder::new(reader)?;
+            if !decoder.has_animation() {
+                return Ok(None);
+            }
+            decoder.into_frames()
+        }
+        _ => return Ok(None),
+    };
+    let frame = frames.next().transpose()?;
+    Ok(frame.map(|x| DynamicImage::ImageRgba8(x.into_buffer())))
+}
+
+/// 读取固定长度的字节【文件被截断时返回 None】
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> Option<[u8; N]> {
+    let mut buf = [0u8; N];
+    reader.read_exact(&mut buf).ok()?;
+    Some(buf)
+}
+
+/// 向后跳过若干字节【超出文件末尾时由之后的读取返回 None】
+fn skip_bytes(reader: &mut impl Seek, count: u64) -> Option<()> {
+    reader.seek(SeekFrom::Current(count as i64)).ok()?;
+    Some(())
+}
+
+/// GIF：统计图像描述符的数量，累加图形控制扩展中的延时（1/100 秒）
+fn gif_animation(reader: &mut (impl Read + Seek)) -> Option<AnimationInfo> {
+    // 文件头 6 字节、逻辑屏幕描述符 7 字节，之后可能是全局颜色表
+    let header = read_bytes::<13>(reader)?;
+    if !header.starts_with(b"GIF87a") && !header.starts_with(b"GIF89a") {
+        return None;
+    }
+    skip_bytes(reader, color_table_size(header[10]))?;
+    let mut frame_count = 0;
+    let mut duration = 0u32;
+    // 图形控制扩展的延时作用于之后的一帧
+    let mut delay = 0u32;
+    loop {
+        let [block] = read_bytes(reader)?;
+        match block {
+            GIF_EXTENSION => {
+                let [label] = read_bytes(reader)?;
+                if label == GIF_GRAPHIC_CONTROL {
+                    // 子块长度、标志位各 1 字节，之后是 2 字节的延时
+                    let bytes = read_bytes::<4>(reader)?;
+                    delay = u16::from_le_bytes([bytes[2], bytes[3]]) as u32;
+                    skip_bytes(reader, (bytes[0] as u64).checked_sub(3)?)?;
+                }
+                skip_sub_blocks(reader)?;
+            }
+            GIF_IMAGE_DESCRIPTOR => {
+                frame_count += 1;
+                duration = duration.saturating_add(delay * 10);
+                delay = 0;
+                // 描述符剩余 9 字节、局部颜色表、LZW 最小码长 1 字节，之后是数据子块
+                let descriptor = read_bytes::<9>(reader)?;
+                skip_bytes(reader, color_table_size(descriptor[8]) + 1)?;
+                skip_sub_blocks(reader)?;
+            }
+            GIF_TRAILER => break,
+            _ => return None,
+        }
+    }
+    Some(AnimationInfo {
+        frame_count,
+        duration_ms: duration,
+    })
+}
+
+/// 颜色表的字节数【标志位最高位表示是否存在，低 3 位为大小】
+fn color_table_size(packed: u8) -> u64 {
+    if packed & 0x80 == 0 {
+        0
+    } else {
+        3 << ((packed & 0x07) + 1)
+    }
+}
+
+/// 跳过 GIF 的数据子块【每块以长度开头，长度为 0 时结束】
+fn skip_sub_blocks(reader: &mut impl Read) -> Option<()> {
+    let mut buf = [0u8; 255];
+    loop {
+        let [size] = read_bytes(reader)?;
+        if size == 0 {
+            return Some(());
+        }
+        reader.read_exact(&mut buf[..size as usize]).ok()?;
+    }
+}
+
+/// APNG：acTL 块中的帧数，累加 fcTL 块中的延时
+fn apng_animation(reader: &mut (impl Read + Seek)) -> Option<AnimationInfo> {
+    if read_bytes::<8>(reader)? != PNG_SIGNATURE {
+        return None;
+    }
+    let mut frame_count = None;
+    let mut duration = 0f64;
+    // 块长度、类型各 4 字节，之后是内容和 4 字节的 CRC
+    while let Some(header) = read_bytes::<8>(reader) {
+        let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as u64;
+        let mut remaining = length;
+        match &header[4..8] {
+            b"acTL" if length >= 4 => {
+                frame_count = Some(u32::from_be_bytes(read_bytes(reader)?));
+                remaining -= 4;
+            }
+            // 延时为分子/分母（秒），分母为 0 时按 1/100 秒
+            b"fcTL" if length >= 24 => {
+                let chunk = read_bytes::<24>(reader)?;
+                let numerator = u16::from_be_bytes([chunk[20], chunk[21]]) as f64;
+                let denominator = match u16::from_be_bytes([chunk[22], chunk[23]]) {
+                    0 => 100.0,
+                    x => x as f64,
+                };
+                duration += numerator / denominator * 1000.0;
+                remaining -= 24;
+            }
+            b"IEND" => break,
+            _ => {}
+        }
+        skip_bytes(reader, remaining + 4)?;
+    }
+    Some(AnimationInfo {
+        frame_count: frame_count?,
+        duration_ms: duration.round() as u32,
+    })
+}
+
+/// 动态 WebP：统计 ANMF 块的数量，累加每帧的时长（毫秒）
+fn webp_animation(reader: &mut (impl Read + Seek)) -> Option<AnimationInfo> {
+    let header = read_bytes::<12>(reader)?;
+    if &header[0..4] != b"RIFF" || &header[8..12] != b"WEBP" {
+        return None;
+    }
+    let mut frame_count = 0;
+    let mut duration = 0u32;
+    // 块类型、长度各 4 字节，长度为奇数时补一个字节
+    while let Some(header) = read_bytes::<8>(reader) {
+        let length = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as u64;
+        let mut remaining = length + (length & 1);
+        if &header[0..4] == b"ANMF" && length >= 15 {
+            // 偏移 X、Y 和宽高减 1 各 3 字节，之后是 3 字节的时长
+            let bytes = read_bytes::<15>(reader)?;
+            frame_count += 1;
+            duration =
+                duration.saturating_add(u32::from_le_bytes([bytes[12], bytes[13], bytes[14], 0]));
+            remaining -= 15;
+        }
+        skip_bytes(reader, remaining)?;
+    }
+    Some(AnimationInfo {
+        frame_count,
+        duration_ms: duration,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_gif_animation() {
+        let mut data = b"GIF89a".to_vec();
+        // 1x1，全局颜色表 2 色
+        data.extend([1, 0, 1, 0, 0x80, 0, 0]);
+        data.extend([0u8; 6]);
+        // 循环播放扩展
+        data.extend([0x21, 0xFF, 0x0B]);
+        data.extend(b"NETSCAPE2.0");
+        data.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+        for delay in [10u16, 25] {
+            data.extend([0x21, GIF_GRAPHIC_CONTROL, 0x04, 0x00]);
+            data.extend(delay.to_le_bytes());
+            data.extend([0x00, 0x00]);
+            data.extend([0x2C, 0, 0, 0, 0, 1, 0, 1, 0, 0x00]);
+            data.extend([0x02, 0x02, 0x44, 0x01, 0x00]);
+        }
+        data.push(GIF_TRAILER);
+        assert_eq!(
+            gif_animation(&mut Cursor::new(&data)),
+            Some(AnimationInfo {
+                frame_count: 2,
+                duration_ms: 350,
+            })
+        );
+        // 数据被截断
+        assert_eq!(gif_animation(&mut Cursor::new(&data[..40])), None);
+    }
+
+    #[test]
+    fn test_apng_animation() {
+        let chunk = |kind: &[u8], content: &[u8]| {
+            let mut data = (content.len() as u32).to_be_bytes().to_vec();
+            data.extend(kind);
+            data.extend(content);
+            data.extend([0u8; 4]);
+            data
+        };
+        let fctl = |numerator: u16, denominator: u16| {
+            let mut content = vec![0u8; 20];
+            content.extend(numerator.to_be_bytes());
+            content.extend(denominator.to_be_bytes());
+            content.extend([0, 0]);
+            chunk(b"fcTL", &content)
+        };
+        let mut data = PNG_SIGNATURE.to_vec();
+        data.extend(chunk(b"IHDR", &[0u8; 13]));
+        data.extend(chunk(b"acTL", &[0, 0, 0, 3, 0, 0, 0, 0]));
+        data.extend(fctl(1, 10));
+        data.extend(chunk(b"IDAT", &[0u8; 4]));
+        data.extend(fctl(50, 0));
+        data.extend(chunk(b"fdAT", &[0u8; 8]));
+        data.extend(fctl(1, 4));
+        data.extend(chunk(b"fdAT", &[0u8; 8]));
+        data.extend(chunk(b"IEND", &[]));
+        assert_eq!(
+            apng_animation(&mut Cursor::new(&data)),
+            Some(AnimationInfo {
+                frame_count: 3,
+                duration_ms: 850,
+            })
+        );
+
+        // 普通 PNG 没有 acTL
+        let mut png = PNG_SIGNATURE.to_vec();
+        png.extend(chunk(b"IHDR", &[0u8; 13]));
+        png.extend(chunk(b"IEND", &[]));
+        assert_eq!(apng_animation(&mut Cursor::new(&png)), None);
+    }
+
+    #[test]
+    fn test_webp_animation() {
+        let chunk = |kind: &[u8], content: &[u8]| {
+            let mut data = kind.to_vec();
+            data.extend((content.len() as u32).to_le_bytes());
+            data.extend(content);
+            if content.len() % 2 == 1 {
+                data.push(0);
+            }
+            data
+        };
+        let anmf = |duration: u32| {
+            let mut content = vec![0u8; 12];
+            content.extend(&duration.to_le_bytes()[..3]);
+            content.extend([0u8; 2]);
+            chunk(b"ANMF", &content)
+        };
+        let mut data = b"RIFF\0\0\0\0WEBP".to_vec();
+        data.extend(chunk(b"VP8X", &[0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0]));
+        data.extend(chunk(b"ANIM", &[0u8; 6]));
+        data.extend(anmf(100));
+        data.extend(anmf(70000));
+        assert_eq!(
+            webp_animation(&mut Cursor::new(&data)),
+            Some(AnimationInfo {
+                frame_count: 2,
+                duration_ms: 70100,
+            })
+        );
+        assert_eq!(webp_animation(&mut Cursor::new(b"RIFF\0\0\0\0WAVE")), None);
+    }
+}
@@ -0,0 +1,132 @@
+use image::RgbImage;
+use std::f64::consts::PI;
+
+/// BlurHash 使用的 Base83 字符
+const BASE83_CHARS: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~";
+
+/// 计算图片的 BlurHash【前端加载缩略图之前显示的模糊占位图】
+///
+/// 按 BlurHash 规范对图片做二维 DCT，保留前几个分量后编码为 Base83 字符串
+/// - image 图片【使用小尺寸缩略图即可】
+/// - components_x 水平分量数（1 - 9）
+/// - components_y 垂直分量数（1 - 9）
+pub fn encode(image: &RgbImage, components_x: u32, components_y: u32) -> String {
+    let components_x = components_x.clamp(1, 9);
+    let components_y = components_y.clamp(1, 9);
+    let (width, height) = image.dimensions();
+    let linear: Vec<[f64; 3]> = image.pixels().map(|x| x.0.map(srgb_to_linear)).collect();
+
+    let mut factors = Vec::with_capacity((components_x * components_y) as usize);
+    for j in 0..components_y {
+        for i in 0..components_x {
+            factors.push(component(&linear, width, height, i, j));
+        }
+    }
+
+    let mut hash = String::new();
+    encode83(&mut hash, (components_x - 1) + (components_y - 1) * 9, 1);
+    let (dc, ac) = (&factors[0], &factors[1..]);
+    let max_value = if ac.is_empty() {
+        encode83(&mut hash, 0, 1);
+        1.0
+    } else {
+        let actual_max = ac
+            .iter()
+            .flat_map(|x| x.iter())
+            .fold(0.0_f64, |max, x| max.max(x.abs()));
+        let quantised_max = ((actual_max * 166.0 - 0.5).floor() as i64).clamp(0, 82) as u32;
+        encode83(&mut hash, quantised_max, 1);
+        (quantised_max + 1) as f64 / 166.0
+    };
+    encode83(&mut hash, encode_dc(dc), 4);
+    for factor in ac {
+        encode83(&mut hash, encode_ac(factor, max_value), 2);
+    }
+    hash
+}
+
+/// 计算一个 DCT 分量
+fn component(linear: &[[f64; 3]], width: u32, height: u32, i: u32, j: u32) -> [f64; 3] {
+    let normalisation = if i == 0 && j == 0 { 1.0 } else { 2.0 };
+    let mut sum = [0.0; 3];
+    if width == 0 || height == 0 {
+        return sum;
+    }
+    for y in 0..height {
+        let cos_y = (PI * j as f64 * y as f64 / height as f64).cos();
+        for x in 0..width {
+            let basis = cos_y * (PI * i as f64 * x as f64 / width as f64).cos();
+            let pixel = linear[(y * width + x) as usize];
+            for c in 0..3 {
+                sum[c] += basis * pixel[c];
+            }
+        }
+    }
+    let scale = normalisation / (width as f64 * height as f64);
+    sum.map(|x| x * scale)
+}
+
+fn encode_dc(value: &[f64; 3]) -> u32 {
+    let [r, g, b] = value.map(linear_to_srgb);
+    (r << 16) + (g << 8) + b
+}
+
+fn encode_ac(value: &[f64; 3], max_value: f64) -> u32 {
+    let [r, g, b] = value.map(|x| {
+        let v = x / max_value;
+        let v = v.signum() * v.abs().sqrt();
+        ((v * 9.0 + 9.5).floor() as i64).clamp(0, 18) as u32
+    });
+    r * 19 * 19 + g * 19 + b
+}
+
+fn srgb_to_linear(value: u8) -> f64 {
+    let v = value as f64 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f64) -> u32 {
+    let v = value.clamp(0.0, 1.0);
+    let srgb = if v <= 0.003_130_8 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (srgb * 255.0 + 0.5) as u32
+}
+
+/// 把数值编码为指定长度的 Base83 字符
+fn encode83(output: &mut String, value: u32, length: u32) {
+    for i in 1..=length {
+        let digit = (value / 83_u32.pow(length - i)) % 83;
+        output.push(BASE83_CHARS[digit as usize] as char);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    #[test]
+    fn test_encode_solid_color() {
+        let image = RgbImage::from_pixel(32, 24, Rgb([255, 255, 255]));
+        let hash = encode(&image, 4, 3);
+        assert_eq!(hash.len(), 4 + 2 * 4 * 3);
+        // 纯色图片没有 AC 分量
+        assert_eq!(hash, format!("L0TSUA{}", "fQ".repeat(11)));
+    }
+
+    #[test]
+    fn test_encode_gradient() {
+        let image = RgbImage::from_fn(32, 32, |x, _| Rgb([(x * 8) as u8, 0, 0]));
+        let hash = encode(&image, 4, 3);
+        assert_eq!(hash.len(), 28);
+        assert_ne!(&hash[6..8], "fQ");
+    }
+}
@@ -0,0 +1,85 @@
+/// 生成 `sha256sum` 兼容的校验清单中的一行【不包含换行符】
+///
+/// 格式为 `Hash + 两个空格 + 路径`；路径中包含 `\` 或换行时，行首加 `\` 并转义，
+/// 与 GNU coreutils 的规则一致
+/// - hash 十六进制 Hash
+/// - path 相对清单所在文件夹的路径【分隔符使用 `/`】
+pub fn format_line(hash: &str, path: &str) -> String {
+    if path.contains(['\\', '\n', '\r']) {
+        let escaped = path
+            .replace('\\', "\\\\")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r");
+        format!("\\{}  {}", hash, escaped)
+    } else {
+        format!("{}  {}", hash, path)
+    }
+}
+
+/// 解析清单中的一行，返回 (小写 Hash, 路径)【格式错误时为 None】
+///
+/// 同时支持文本模式（两个空格）和二进制模式（空格 + `*`）
+pub fn parse_line(line: &str) -> Option<(String, String)> {
+    let (escaped, line) = match line.strip_prefix('\\') {
+        Some(x) => (true, x),
+        None => (false, line),
+    };
+    let (hash, rest) = line.split_once(' ')?;
+    let path = rest.strip_prefix([' ', '*'])?;
+    if hash.is_empty() || !hash.chars().all(|x| x.is_ascii_hexdigit()) || path.is_empty() {
+        return None;
+    }
+    let path = if escaped {
+        unescape(path)?
+    } else {
+        path.to_string()
+    };
+    Some((hash.to_lowercase(), path))
+}
+
+fn unescape(path: &str) -> Option<String> {
+    let mut result = String::with_capacity(path.len());
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => result.push('\\'),
+            'n' => result.push('\n'),
+            'r' => result.push('\r'),
+            _ => return None,
+        }
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_parse_line() {
+        let hash = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+        let line = format_line(hash, "2024/照片 1.jpg");
+        assert_eq!(line, format!("{}  2024/照片 1.jpg", hash));
+        assert_eq!(
+            parse_line(&line),
+            Some((hash.to_string(), "2024/照片 1.jpg".to_string()))
+        );
+
+        let line = format_line(hash, "a\\b\nc.jpg");
+        assert!(line.starts_with('\\'));
+        assert_eq!(parse_line(&line).unwrap().1, "a\\b\nc.jpg");
+
+        // 二进制模式、大写 Hash
+        let line = format!("{} *IMG_0001.JPG", hash.to_uppercase());
+        assert_eq!(
+            parse_line(&line),
+            Some((hash.to_string(), "IMG_0001.JPG".to_string()))
+        );
+        assert_eq!(parse_line("not a checksum line"), None);
+        assert_eq!(parse_line(""), None);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::structs::directory_access::CloudProvider;
+use crate::utils::file_util;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+
+/// iCloud 未下载文件的占位文件扩展名【`.IMG_0001.JPG.icloud`】
+const ICLOUD_STUB_EXTENSION: &str = ".icloud";
+
+/// 判断文件是否为云盘占位文件（内容不在本地）
+///
+/// 只读取元数据，不会触发下载：
+/// - Windows：OneDrive、Dropbox、iCloud 的“按需文件”带有 RECALL_ON_DATA_ACCESS、RECALL_ON_OPEN 或 OFFLINE 属性
+/// - macOS：File Provider 的未下载文件带有 SF_DATALESS 标记，旧版 iCloud 使用 `.icloud` 占位文件
+/// - 其他系统：有大小但没有占用磁盘块的文件（rclone、onedriver 等挂载的按需文件）
+pub fn is_placeholder(path: &Path, metadata: &Metadata) -> bool {
+    is_icloud_stub(path) || has_placeholder_flag(metadata)
+}
+
+#[cfg(windows)]
+fn has_placeholder_flag(metadata: &Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_OFFLINE: u32 = 0x0000_1000;
+    const FILE_ATTRIBUTE_RECALL_ON_OPEN: u32 = 0x0004_0000;
+    const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x0040_0000;
+    metadata.file_attributes()
+        & (FILE_ATTRIBUTE_OFFLINE
+            | FILE_ATTRIBUTE_RECALL_ON_OPEN
+            | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS)
+        != 0
+}
+
+#[cfg(target_os = "macos")]
+fn has_placeholder_flag(metadata: &Metadata) -> bool {
+    use std::os::macos::fs::MetadataExt;
+    const SF_DATALESS: u32 = 0x4000_0000;
+    metadata.st_flags() & SF_DATALESS != 0
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn has_placeholder_flag(metadata: &Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    // 很小的文件可能内联保存在元数据中，不占用磁盘块
+    const INLINE_MAX_SIZE: u64 = 4096;
+    metadata.is_file() && metadata.len() > INLINE_MAX_SIZE && metadata.blocks() == 0
+}
+
+#[cfg(not(any(windows, unix)))]
+fn has_placeholder_flag(_metadata: &Metadata) -> bool {
+    false
+}
+
+/// 是否为 iCloud 占位文件【`.文件名.icloud`】
+pub fn is_icloud_stub(path: &Path) -> bool {
+    icloud_stub_target(path).is_some()
+}
+
+/// 是否为图片的 iCloud 占位文件【扫描时记录为仅云端文件】
+pub fn is_icloud_image_stub(path: &Path) -> bool {
+    icloud_stub_target(path).is_some_and(file_util::is_image_file)
+}
+
+/// iCloud 占位文件对应的文件路径
+pub fn icloud_stub_target(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    let target = name
+        .strip_prefix('.')?
+        .strip_suffix(ICLOUD_STUB_EXTENSION)?;
+    if target.is_empty() {
+        return None;
+    }
+    Some(path.with_file_name(target))
+}
+
+/// 根据路径判断所在的云盘
+pub fn detect_provider(path: &Path) -> Option<CloudProvider> {
+    path.components().find_map(|x| {
+        let name = x.as_os_str().to_string_lossy().to_lowercase();
+        if name == "onedrive" || name.starts_with("onedrive - ") || name.starts_with("onedrive-") {
+            Some(CloudProvider::OneDrive)
+        } else if name == "icloud drive"
+            || name == "iclouddrive"
+            || name == "mobile documents"
+            || name.starts_with("icloud~")
+        {
+            Some(CloudProvider::ICloud)
+        } else if name == "dropbox" || name.starts_with("dropbox (") {
+            Some(CloudProvider::Dropbox)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_icloud_stub_target() {
+        assert_eq!(
+            icloud_stub_target(Path::new("/photos/.IMG_0001.JPG.icloud")),
+            Some(PathBuf::from("/photos/IMG_0001.JPG"))
+        );
+        assert_eq!(icloud_stub_target(Path::new("/photos/IMG_0001.JPG")), None);
+        assert_eq!(icloud_stub_target(Path::new("/photos/..icloud")), None);
+    }
+
+    #[test]
+    fn test_detect_provider() {
+        assert_eq!(
+            detect_provider(Path::new("/Users/a/OneDrive - Contoso/Pictures")),
+            Some(CloudProvider::OneDrive)
+        );
+        assert_eq!(
+            detect_provider(Path::new(
+                "/Users/a/Library/Mobile Documents/com~apple~CloudDocs"
+            )),
+            Some(CloudProvider::ICloud)
+        );
+        assert_eq!(
+            detect_provider(Path::new("/home/a/Dropbox/Camera Uploads")),
+            Some(CloudProvider::Dropbox)
+        );
+        assert_eq!(detect_provider(Path::new("/home/a/Pictures")), None);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::constant::{
+    DOMINANT_COLOR_CLUSTERS, DOMINANT_COLOR_MIN_COUNT, DOMINANT_COLOR_MIN_WEIGHT,
+};
+use crate::structs::dominant_color::DominantColor;
+use image::RgbImage;
+
+/// D65 白点
+const WHITE_POINT: [f32; 3] = [0.95047, 1.0, 1.08883];
+/// k-means 最大迭代次数
+const KMEANS_MAX_ITERATIONS: usize = 20;
+/// 颜色差小于此值的聚类视为同一种颜色（CIE76 ΔE）
+const MERGE_DISTANCE: f32 = 10.0;
+
+/// 解析十六进制颜色，如 `#1A8C9E`、`1a8c9e`
+pub fn parse_hex(hex: &str) -> Option<[u8; 3]> {
+    let hex = hex.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    Some([(value >> 16) as u8, (value >> 8) as u8, value as u8])
+}
+
+pub fn rgb_to_hex(rgb: [u8; 3]) -> String {
+    format!("#{:02X}{:02X}{:02X}", rgb[0], rgb[1], rgb[2])
+}
+
+/// sRGB 转 CIELAB（D65）
+pub fn rgb_to_lab(rgb: [u8; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb.map(|c| {
+        let c = c as f32 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    });
+    let xyz = [
+        0.4124 * r + 0.3576 * g + 0.1805 * b,
+        0.2126 * r + 0.7152 * g + 0.0722 * b,
+        0.0193 * r + 0.1192 * g + 0.9505 * b,
+    ];
+    let f = |t: f32| {
+        if t > 0.008856 {
+            t.cbrt()
+        } else {
+            t / 0.128418 + 4.0 / 29.0
+        }
+    };
+    let [fx, fy, fz] = [0, 1, 2].map(|i| f(xyz[i] / WHITE_POINT[i]));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// CIELAB 转 sRGB【超出色域的值截断】
+pub fn lab_to_rgb(lab: [f32; 3]) -> [u8; 3] {
+    let fy = (lab[0] + 16.0) / 116.0;
+    let f = [fy + lab[1] / 500.0, fy, fy - lab[2] / 200.0];
+    let [x, y, z] = [0, 1, 2].map(|i| {
+        let t = f[i];
+        let t = if t > 6.0 / 29.0 {
+            t * t * t
+        } else {
+            0.128418 * (t - 4.0 / 29.0)
+        };
+        t * WHITE_POINT[i]
+    });
+    [
+        3.2406 * x - 1.5372 * y - 0.4986 * z,
+        -0.9689 * x + 1.8758 * y + 0.0415 * z,
+        0.0557 * x - 0.2040 * y + 1.0570 * z,
+    ]
+    .map(|c| {
+        let c = if c <= 0.0031308 {
+            12.92 * c
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        };
+        (c.clamp(0.0, 1.0) * 255.0).round() as u8
+    })
+}
+
+/// 两个颜色的差（CIE76 ΔE）
+pub fn delta_e(x: &[f32; 3], y: &[f32; 3]) -> f32 {
+    distance_squared(x, y).sqrt()
+}
+
+fn distance_squared(x: &[f32; 3], y: &[f32; 3]) -> f32 {
+    (x[0] - y[0]).powi(2) + (x[1] - y[1]).powi(2) + (x[2] - y[2]).powi(2)
+}
+
+/// 提取主色（在 CIELAB 空间做 k-means 聚类）
+///
+/// 去掉占比过小的聚类并合并相近的颜色，按占比从大到小返回 3 - 5 个颜色
+pub fn dominant_colors(image: &RgbImage) -> Vec<DominantColor> {
+    let pixels: Vec<[f32; 3]> = image.pixels().map(|p| rgb_to_lab(p.0)).collect();
+    if pixels.is_empty() {
+        return Vec::new();
+    }
+    let (centers, counts) = kmeans(&pixels, DOMINANT_COLOR_CLUSTERS);
+
+    let mut clusters: Vec<([f32; 3], f32)> = centers
+        .into_iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .map(|(center, count)| (center, count as f32 / pixels.len() as f32))
+        .collect();
+    clusters.sort_by(|x, y| y.1.total_cmp(&x.1));
+
+    // 合并相近的颜色（并入占比较大的一方）
+    let mut merged: Vec<([f32; 3], f32)> = Vec::with_capacity(clusters.len());
+    for (center, weight) in clusters {
+        match merged
+            .iter_mut()
+            .find(|(x, _)| delta_e(x, &center) < MERGE_DISTANCE)
+        {
+            Some((_, total)) => *total += weight,
+            None => merged.push((center, weight)),
+        }
+    }
+    merged.sort_by(|x, y| y.1.total_cmp(&x.1));
+    let keep = merged
+        .iter()
+        .filter(|(_, weight)| *weight >= DOMINANT_COLOR_MIN_WEIGHT)
+        .count()
+        .max(DOMINANT_COLOR_MIN_COUNT);
+    merged
+        .into_iter()
+        .take(keep)
+        .map(|(lab, weight)| DominantColor::new(lab, weight))
+        .collect()
+}
+
+/// k-means 聚类，返回聚类中心和每个聚类的像素数量
+///
+/// 初始中心使用最远点选取（第一个中心为平均色），结果是确定的
+fn kmeans(pixels: &[[f32; 3]], k: usize) -> (Vec<[f32; 3]>, Vec<usize>) {
+    let mut mean = [0f32; 3];
+    for pixel in pixels {
+        for i in 0..3 {
+            mean[i] += pixel[i] / pixels.len() as f32;
+        }
+    }
+    let mut centers = vec![mean];
+    while centers.len() < k {
+        let farthest = pixels
+            .iter()
+            .max_by(|x, y| nearest(&centers, x).1.total_cmp(&nearest(&centers, y).1));
+        match farthest {
+            Some(x) if nearest(&centers, x).1 > 0.0 => centers.push(*x),
+            // 颜色种类少于聚类数量
+            _ => break,
+        }
+    }
+
+    let mut assignments = vec![usize::MAX; pixels.len()];
+    let mut counts = vec![0usize; centers.len()];
+    for _ in 0..KMEANS_MAX_ITERATIONS {
+        let mut changed = false;
+        for (pixel, assignment) in pixels.iter().zip(assignments.iter_mut()) {
+            let (index, _) = nearest(&centers, pixel);
+            if *assignment != index {
+                *assignment = index;
+                changed = true;
+            }
+        }
+        let mut sums = vec![[0f32; 3]; centers.len()];
+        counts = vec![0usize; centers.len()];
+        for (pixel, index) in pixels.iter().zip(&assignments) {
+            for i in 0..3 {
+                sums[*index][i] += pixel[i];
+            }
+            counts[*index] += 1;
+        }
+        for (center, (sum, count)) in centers.iter_mut().zip(sums.iter().zip(&counts)) {
+            if *count > 0 {
+                *center = sum.map(|x| x / *count as f32);
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    (centers, counts)
+}
+
+/// 最近的聚类中心及距离的平方
+fn nearest(centers: &[[f32; 3]], pixel: &[f32; 3]) -> (usize, f32) {
+    centers
+        .iter()
+        .enumerate()
+        .map(|(i, center)| (i, distance_squared(center, pixel)))
+        .min_by(|x, y| x.1.total_cmp(&y.1))
+        .unwrap_or((0, 0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    #[test]
+    fn test_lab_round_trip() {
+        let white = rgb_to_lab([255, 255, 255]);
+        assert!((white[0] - 100.0).abs() < 0.1 && white[1].abs() < 0.1 && white[2].abs() < 0.1);
+        for rgb in [[0, 128, 128], [255, 140, 0], [12, 34, 56]] {
+            assert_eq!(lab_to_rgb(rgb_to_lab(rgb)), rgb);
+        }
+        assert_eq!(parse_hex("#008080"), Some([0, 128, 128]));
+        assert_eq!(parse_hex("teal"), None);
+    }
+
+    #[test]
+    fn test_dominant_colors() {
+        // 3/4 青色、1/4 橙色
+        let image = RgbImage::from_fn(16, 16, |x, _| {
+            if x < 12 {
+                Rgb([0, 128, 128])
+            } else {
+                Rgb([255, 140, 0])
+            }
+        });
+        let colors = dominant_colors(&image);
+        assert_eq!(colors.len(), 2);
+        assert_eq!(colors[0].hex, "#008080");
+        assert!((colors[0].weight - 0.75).abs() < 1e-6);
+        assert_eq!(colors[1].hex, "#FF8C00");
+    }
+}
@@ -0,0 +1,162 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike};
+
+/// cron 表达式【分 时 日 月 周，周日为 0 或 7】
+///
+/// 每个字段支持 `*`、数字、范围 `1-5`、列表 `1,3,5` 和步长 `*/15`、`0-30/10`。
+/// 日和周都有限制时满足其一即可，与标准 cron 一致
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    day_restricted: bool,
+    weekday_restricted: bool,
+}
+
+impl CronSchedule {
+    /// 解析 cron 表达式
+    pub fn parse(expr: &str) -> Result<CronSchedule> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(anyhow!(
+                "cron 表达式需要 5 个字段（分 时 日 月 周）: {}",
+                expr
+            ));
+        }
+        let mut weekdays = parse_field(fields[4], 0, 7)?;
+        // 7 与 0 都表示周日
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(CronSchedule {
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            weekdays,
+            day_restricted: fields[2] != "*",
+            weekday_restricted: fields[4] != "*",
+        })
+    }
+
+    /// 晚于 after 的下一次执行时间【4 年内没有匹配的时间时返回 None】
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let limit = after.clone() + Duration::days(366 * 4);
+        let mut time = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        while time < limit {
+            // 日期或小时不匹配时直接跳到下一个整点
+            if !self.matches_day(&time) || !has(self.hours, time.hour()) {
+                time += Duration::minutes(60 - i64::from(time.minute()));
+                continue;
+            }
+            if has(self.minutes, time.minute()) {
+                return Some(time);
+            }
+            time += Duration::minutes(1);
+        }
+        None
+    }
+
+    fn matches_day<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> bool {
+        if !has(self.months, time.month()) {
+            return false;
+        }
+        let day = has(self.days, time.day());
+        let weekday = has(self.weekdays, time.weekday().num_days_from_sunday());
+        if self.day_restricted && self.weekday_restricted {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+}
+
+fn has(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+/// 解析单个字段，返回匹配值的位掩码
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64> {
+    let invalid = || anyhow!("cron 字段 {} 无效（范围 {} ~ {}）", field, min, max);
+    let number = |x: &str| x.parse::<u32>().map_err(|_| invalid());
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, Some(number(step)?)),
+            None => (part, None),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (number(start)?, number(end)?)
+        } else {
+            let value = number(range)?;
+            // `5/10` 表示从 5 开始每 10 个
+            (value, if step.is_some() { max } else { value })
+        };
+        let step = step.unwrap_or(1);
+        if step == 0 || start < min || end > max || start > end {
+            return Err(invalid());
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn next_after_follows_fields() {
+        let daily = CronSchedule::parse("30 3 * * *").unwrap();
+        assert_eq!(
+            daily.next_after(&at("2025-01-27T03:30:00Z")),
+            Some(at("2025-01-28T03:30:00Z"))
+        );
+
+        // 2025-01-27 是周一，下一个周日
+        let sunday = CronSchedule::parse("0 2 * * 7").unwrap();
+        assert_eq!(
+            sunday.next_after(&at("2025-01-27T10:00:00Z")),
+            Some(at("2025-02-02T02:00:00Z"))
+        );
+
+        let every = CronSchedule::parse("*/15 9-17 * * 1-5").unwrap();
+        assert_eq!(
+            every.next_after(&at("2025-01-27T17:50:00Z")),
+            Some(at("2025-01-28T09:00:00Z"))
+        );
+        // 周六不执行
+        assert_eq!(
+            every.next_after(&at("2025-02-01T12:00:00Z")),
+            Some(at("2025-02-03T09:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid() {
+        for expr in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "* 5-3 * * *",
+            "*/0 * * * *",
+            "a * * * *",
+        ] {
+            assert!(CronSchedule::parse(expr).is_err(), "{}", expr);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::{anyhow, Result};
+
+/// 将多行数据转换为 CSV【RFC 4180，含逗号、引号、换行的字段加引号】
+pub fn to_csv(rows: &[Vec<String>]) -> String {
+    let mut out = String::new();
+    for row in rows {
+        let line: Vec<String> = row.iter().map(|x| escape_field(x)).collect();
+        out.push_str(&line.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// 解析 CSV 内容【支持引号内的逗号、换行和 `""` 转义，忽略空行】
+pub fn from_csv(content: &str) -> Result<Vec<Vec<String>>> {
+    // 跳过 Excel 导出时可能带有的 BOM
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                row.push(std::mem::take(&mut field));
+                if row.len() > 1 || !row[0].is_empty() {
+                    rows.push(std::mem::take(&mut row));
+                } else {
+                    row.clear();
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(anyhow!("CSV 格式错误: 引号未闭合"));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn escape_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_round_trip() {
+        let rows = vec![
+            vec!["hash".to_string(), "caption".to_string(), "".to_string()],
+            vec![
+                "a1".to_string(),
+                "第一行\n\"引号\", 逗号".to_string(),
+                "".to_string(),
+            ],
+        ];
+        let content = to_csv(&rows);
+        assert_eq!(from_csv(&content).unwrap(), rows);
+        assert_eq!(
+            from_csv("\u{feff}a,b\n\n1,2").unwrap(),
+            vec![vec!["a", "b"], vec!["1", "2"]]
+        );
+        assert!(from_csv("a,\"b").is_err());
+    }
+}
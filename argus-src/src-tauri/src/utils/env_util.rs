@@ -5,12 +5,11 @@ pub fn is_dev_util() -> bool {
     let environment = env::var("ENV").unwrap_or_else(|_| String::from("development"));
 
     if environment == "development" {
-        println!("Running in development environment");
+        log::debug!("Running in development environment");
     } else if environment == "production" {
-        println!("Running in production environment");
+        log::debug!("Running in production environment");
     } else {
-        println!("Unknown environment");
+        log::warn!("Unknown environment: {}", environment);
     }
-    println!("environment {} !!!!!!!!!!",environment);
     return environment == "development";
 }
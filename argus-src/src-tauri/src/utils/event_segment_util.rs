@@ -0,0 +1,107 @@
+use crate::constant::{
+    EVENT_JUMP_MIN_GAP_SECS, EVENT_MAX_GAP_SECS, EVENT_MAX_JUMP_METERS, EVENT_MIN_PHOTOS,
+};
+use crate::utils::exif_utils::gps_util::GpsCoordinate;
+use std::ops::Range;
+
+/// 参与事件分段的照片
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventPoint {
+    /// 拍摄时间（秒）
+    pub time: i64,
+    /// 拍摄位置【没有位置的照片只按时间分段】
+    pub coordinate: Option<GpsCoordinate>,
+}
+
+/// 按拍摄时间和位置把照片分为事件
+///
+/// 相邻两张的拍摄间隔超过上限时分段；与上一张有位置的照片相距过远，
+/// 且间隔超过换地方的最小间隔时也分段。照片数量不足的分段不作为事件。
+/// - points 按拍摄时间排序的照片
+pub fn segment_events(points: &[EventPoint]) -> Vec<Range<usize>> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    // 当前分段中最后一张有位置的照片的拍摄时间和位置
+    let mut last_located: Option<(i64, &GpsCoordinate)> = None;
+    for (index, point) in points.iter().enumerate() {
+        if index > start && is_boundary(&points[index - 1], last_located, point) {
+            if index - start >= EVENT_MIN_PHOTOS {
+                result.push(start..index);
+            }
+            start = index;
+            last_located = None;
+        }
+        if let Some(coordinate) = &point.coordinate {
+            last_located = Some((point.time, coordinate));
+        }
+    }
+    if points.len() - start >= EVENT_MIN_PHOTOS {
+        result.push(start..points.len());
+    }
+    result
+}
+
+/// 是否在此照片之前分段
+/// - previous 上一张照片
+/// - last_located 当前分段中最后一张有位置的照片
+fn is_boundary(
+    previous: &EventPoint,
+    last_located: Option<(i64, &GpsCoordinate)>,
+    point: &EventPoint,
+) -> bool {
+    if point.time - previous.time > EVENT_MAX_GAP_SECS {
+        return true;
+    }
+    match (last_located, &point.coordinate) {
+        (Some((time, from)), Some(coordinate)) => {
+            point.time - time > EVENT_JUMP_MIN_GAP_SECS
+                && from.distance_meters(coordinate) > EVENT_MAX_JUMP_METERS
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 60 * 60;
+
+    fn point(time: i64, coordinate: Option<(f64, f64)>) -> EventPoint {
+        EventPoint {
+            time,
+            coordinate: coordinate.map(|(latitude, longitude)| GpsCoordinate {
+                latitude,
+                longitude,
+                altitude: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_segment_by_time() {
+        let mut points: Vec<EventPoint> = (0..6).map(|i| point(i * 600, None)).collect();
+        // 间隔超过上限
+        points.extend((0..5).map(|i| point(EVENT_MAX_GAP_SECS + 4000 + i * 60, None)));
+        // 数量不足
+        points.extend((0..2).map(|i| point(EVENT_MAX_GAP_SECS * 3 + i, None)));
+        assert_eq!(segment_events(&points), vec![0..6, 6..11]);
+    }
+
+    #[test]
+    fn test_segment_by_location() {
+        let hangzhou = Some((30.25, 120.16));
+        let beijing = Some((39.90, 116.40));
+        let mut points: Vec<EventPoint> = (0..5).map(|i| point(i * 60, hangzhou)).collect();
+        // 没有位置的照片不影响分段
+        points.push(point(600, None));
+        // 相距很远且间隔超过 1 小时
+        points.extend((0..5).map(|i| point(3 * HOUR + i * 60, beijing)));
+        assert_eq!(segment_events(&points), vec![0..6, 6..11]);
+
+        // 间隔很短时视为定位错误，不分段
+        let mut points: Vec<EventPoint> = (0..5).map(|i| point(i * 60, hangzhou)).collect();
+        points.push(point(360, beijing));
+        assert_eq!(segment_events(&points), vec![0..6]);
+    }
+}
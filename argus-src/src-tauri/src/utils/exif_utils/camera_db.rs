@@ -0,0 +1,167 @@
+//! 相机传感器尺寸数据
+//!
+//! 照片中没有 FocalLengthIn35mmFormat 时，按相机型号查找传感器尺寸估算等效焦距
+
+use crate::utils::csv_util;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+/// 全画幅（36 × 24 mm）传感器对角线长度（毫米）
+const FULL_FRAME_DIAGONAL: f64 = 43.266_615_305_567_87;
+
+/// 传感器尺寸（毫米）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SensorSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl SensorSize {
+    pub const fn new(width: f64, height: f64) -> SensorSize {
+        SensorSize { width, height }
+    }
+
+    /// 裁切系数【全画幅对角线与传感器对角线之比】
+    pub fn crop_factor(&self) -> f64 {
+        FULL_FRAME_DIAGONAL / self.width.hypot(self.height)
+    }
+}
+
+/// 相机传感器数据【CSV：制造商关键字、型号（与 exiftool 输出一致，不区分大小写）、传感器宽、高（毫米）】
+const CAMERA_DATA: &str = include_str!("../../../resources/camera_sensors.csv");
+
+/// 相机型号对应的传感器尺寸【键为小写型号，值为制造商关键字和传感器尺寸】
+static CAMERAS: Lazy<HashMap<String, Vec<(String, SensorSize)>>> = Lazy::new(|| {
+    let (cameras, skipped) = parse_cameras(CAMERA_DATA);
+    if skipped > 0 {
+        log::warn!("相机传感器数据中有 {} 行格式错误", skipped);
+    }
+    cameras
+});
+
+/// 解析相机传感器数据，返回相机型号和跳过的行数【第一行为标题行】
+fn parse_cameras(content: &str) -> (HashMap<String, Vec<(String, SensorSize)>>, usize) {
+    let mut cameras: HashMap<String, Vec<(String, SensorSize)>> = HashMap::new();
+    let mut skipped = 0;
+    let rows = match csv_util::from_csv(content) {
+        Ok(rows) => rows,
+        Err(e) => {
+            log::error!("相机传感器数据解析失败: {}", e);
+            return (cameras, 0);
+        }
+    };
+    for row in rows.iter().skip(1) {
+        let parsed = match row.as_slice() {
+            [make, model, width, height] => width
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .zip(height.trim().parse::<f64>().ok())
+                .filter(|(w, h)| *w > 0.0 && *h > 0.0)
+                .map(|(w, h)| {
+                    (
+                        make.trim().to_lowercase(),
+                        model.trim(),
+                        SensorSize::new(w, h),
+                    )
+                }),
+            _ => None,
+        };
+        match parsed {
+            Some((make, model, size)) if !make.is_empty() && !model.is_empty() => cameras
+                .entry(model.to_lowercase())
+                .or_default()
+                .push((make, size)),
+            _ => skipped += 1,
+        }
+    }
+    (cameras, skipped)
+}
+
+/// 按相机制造商和型号查找传感器尺寸
+pub fn sensor_size(make: &str, model: &str) -> Option<SensorSize> {
+    let make = make.trim().to_lowercase();
+    CAMERAS
+        .get(&model.trim().to_lowercase())?
+        .iter()
+        .find(|(m, _)| make.contains(m.as_str()))
+        .map(|(_, size)| *size)
+}
+
+/// 按相机制造商和型号查找裁切系数
+pub fn crop_factor(make: &str, model: &str) -> Option<f64> {
+    sensor_size(make, model).map(|x| x.crop_factor())
+}
+
+/// 等效焦距（毫米）
+///
+/// 优先使用照片中的 FocalLengthIn35mmFormat（为 0 时视为没有），
+/// 否则使用焦距乘以相机的裁切系数
+/// - focal_length 焦距
+/// - in_35mm_format 照片中的等效焦距
+/// - crop_factor 裁切系数
+pub fn effective_focal_length(
+    focal_length: Option<f64>,
+    in_35mm_format: Option<f64>,
+    crop_factor: Option<f64>,
+) -> Option<f64> {
+    if let Some(x) = in_35mm_format.filter(|x| x.is_finite() && *x > 0.0) {
+        return Some(x);
+    }
+    let focal_length = focal_length.filter(|x| x.is_finite() && *x > 0.0)?;
+    crop_factor.map(|x| (focal_length * x).round())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL_FRAME: SensorSize = SensorSize::new(36.0, 24.0);
+    const CANON_APS_C: SensorSize = SensorSize::new(22.3, 14.9);
+    const APS_C: SensorSize = SensorSize::new(23.5, 15.6);
+    const FOUR_THIRDS: SensorSize = SensorSize::new(17.3, 13.0);
+
+    #[test]
+    fn test_camera_data() {
+        let (cameras, skipped) = parse_cameras(CAMERA_DATA);
+        assert_eq!(skipped, 0);
+        assert!(cameras.values().map(|x| x.len()).sum::<usize>() > 300);
+        assert_eq!(sensor_size("Canon", "canon eos r6m2"), Some(FULL_FRAME));
+        assert_eq!(
+            sensor_size("OM Digital Solutions", "OM-1"),
+            Some(FOUR_THIRDS)
+        );
+        let (_, skipped) =
+            parse_cameras("make,model,sensor_width,sensor_height\nsony,ILCE-1,0,24\nsony,ZV-E10\n");
+        assert_eq!(skipped, 2);
+    }
+
+    #[test]
+    fn test_crop_factor() {
+        assert!((FULL_FRAME.crop_factor() - 1.0).abs() < 1e-9);
+        assert!((CANON_APS_C.crop_factor() - 1.6).abs() < 0.02);
+        assert!((FOUR_THIRDS.crop_factor() - 2.0).abs() < 0.01);
+        assert_eq!(
+            crop_factor("NIKON CORPORATION", "nikon d7500"),
+            Some(APS_C.crop_factor())
+        );
+        assert_eq!(crop_factor("Canon", "Unknown"), None);
+        // 型号相同但制造商不匹配
+        assert_eq!(crop_factor("Leica", "X-T4"), None);
+    }
+
+    #[test]
+    fn test_effective_focal_length() {
+        assert_eq!(
+            effective_focal_length(Some(23.0), Some(35.0), Some(1.5)),
+            Some(35.0)
+        );
+        assert_eq!(
+            effective_focal_length(Some(25.0), Some(0.0), Some(2.0)),
+            Some(50.0)
+        );
+        assert_eq!(effective_focal_length(Some(25.0), None, None), None);
+        let fuji = crop_factor("FUJIFILM", "X-T4");
+        assert_eq!(effective_focal_length(Some(23.0), None, fuji), Some(35.0));
+    }
+}
@@ -0,0 +1,132 @@
+use encoding_rs::ISO_2022_JP;
+
+/// UserComment 字符编码标识的长度
+const USER_COMMENT_PREFIX_LEN: usize = 8;
+/// UserComment 的 ASCII 编码标识
+const USER_COMMENT_ASCII: &[u8] = b"ASCII\0\0\0";
+/// UserComment 的 JIS 编码标识
+const USER_COMMENT_JIS: &[u8] = b"JIS\0\0\0\0\0";
+/// UserComment 的 Unicode（UCS-2）编码标识
+const USER_COMMENT_UNICODE: &[u8] = b"UNICODE\0";
+/// ISO-2022-JP 的转义字符
+const ESC: u8 = 0x1B;
+/// ISO-2022-JP 中切换到 JIS X 0208 的转义序列
+const ESC_JIS_X_0208: &[u8] = b"\x1B$B";
+/// ISO-2022-JP 中切换回 ASCII 的转义序列
+const ESC_ASCII: &[u8] = b"\x1B(B";
+
+/// 解码 UserComment【前 8 字节为字符编码标识，去掉首尾的 NUL 和空白】
+///
+/// - ASCII：按 UTF-8 解码（部分软件直接写入 UTF-8）
+/// - JIS：包含转义序列时按 ISO-2022-JP 解码，否则为不带转义序列的 JIS X 0208 双字节编码
+/// - UNICODE：UCS-2，有 BOM 时按 BOM，否则按 TIFF 的字节序
+/// - 未定义（全为 NUL 或空格）：按 UTF-8 解码
+/// - little_endian TIFF 是否为小端字节序
+pub fn decode_user_comment(bytes: &[u8], little_endian: bool) -> Option<String> {
+    if bytes.len() < USER_COMMENT_PREFIX_LEN {
+        return None;
+    }
+    let (prefix, value) = bytes.split_at(USER_COMMENT_PREFIX_LEN);
+    let text = match prefix {
+        USER_COMMENT_ASCII => String::from_utf8_lossy(value).into_owned(),
+        USER_COMMENT_JIS => decode_jis(value),
+        USER_COMMENT_UNICODE => decode_utf16(value, little_endian),
+        _ if prefix.iter().all(|x| *x == 0 || *x == b' ') => {
+            String::from_utf8_lossy(value).into_owned()
+        }
+        _ => return None,
+    };
+    non_empty(&text)
+}
+
+/// 解码 Windows 资源管理器写入的 XP 标签（XPTitle、XPComment、XPAuthor 等）
+///
+/// 类型为 BYTE，内容固定为 UTF-16LE，以 NUL 结尾
+pub fn decode_xp(bytes: &[u8]) -> Option<String> {
+    non_empty(&decode_utf16(bytes, true))
+}
+
+/// 解码 UTF-16【有 BOM 时按 BOM 判断字节序，遇到 NUL 结束】
+fn decode_utf16(bytes: &[u8], little_endian: bool) -> String {
+    let (bytes, little_endian) = match bytes {
+        [0xFF, 0xFE, rest @ ..] => (rest, true),
+        [0xFE, 0xFF, rest @ ..] => (rest, false),
+        _ => (bytes, little_endian),
+    };
+    let units = bytes
+        .chunks_exact(2)
+        .map(|x| {
+            if little_endian {
+                u16::from_le_bytes([x[0], x[1]])
+            } else {
+                u16::from_be_bytes([x[0], x[1]])
+            }
+        })
+        .take_while(|x| *x != 0);
+    char::decode_utf16(units)
+        .map(|x| x.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+/// 解码 JIS 编码的文字
+///
+/// 没有转义序列时在前后加上 JIS X 0208 的转义序列，再按 ISO-2022-JP 解码
+fn decode_jis(bytes: &[u8]) -> String {
+    let bytes = if bytes.contains(&ESC) {
+        bytes.to_vec()
+    } else {
+        let end = bytes.iter().rposition(|x| *x != 0).map_or(0, |x| x + 1);
+        [ESC_JIS_X_0208, &bytes[..end], ESC_ASCII].concat()
+    };
+    ISO_2022_JP
+        .decode_without_bom_handling(&bytes)
+        .0
+        .into_owned()
+}
+
+/// 去掉首尾的 NUL 和空白，为空时返回 None
+fn non_empty(text: &str) -> Option<String> {
+    let text = text.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_user_comment() {
+        assert_eq!(
+            decode_user_comment(b"ASCII\0\0\0Harbour  \0\0", true).as_deref(),
+            Some("Harbour")
+        );
+        let mut unicode = USER_COMMENT_UNICODE.to_vec();
+        unicode.extend("港口".encode_utf16().flat_map(|x| x.to_be_bytes()));
+        assert_eq!(
+            decode_user_comment(&unicode, false).as_deref(),
+            Some("港口")
+        );
+        let mut jis = USER_COMMENT_JIS.to_vec();
+        jis.extend([0x46, 0x7C, 0x4B, 0x5C, 0, 0]);
+        assert_eq!(decode_user_comment(&jis, true).as_deref(), Some("日本"));
+        let mut escaped = USER_COMMENT_JIS.to_vec();
+        escaped.extend(b"Tokyo \x1B$B\x46\x7C\x4B\x5C\x1B(B");
+        assert_eq!(
+            decode_user_comment(&escaped, true).as_deref(),
+            Some("Tokyo 日本")
+        );
+        assert_eq!(decode_user_comment(&[0; 16], true), None);
+        assert_eq!(decode_user_comment(b"ASCII", true), None);
+    }
+
+    #[test]
+    fn test_decode_xp() {
+        let mut bytes: Vec<u8> = "海边"
+            .encode_utf16()
+            .flat_map(|x| x.to_le_bytes())
+            .collect();
+        bytes.extend([0, 0]);
+        assert_eq!(decode_xp(&bytes).as_deref(), Some("海边"));
+        assert_eq!(decode_xp(&[0, 0]), None);
+    }
+}
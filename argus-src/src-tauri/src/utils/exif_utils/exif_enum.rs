@@ -0,0 +1,312 @@
+use crate::i18n;
+use crate::i18n::{msg, Locale};
+
+/// 生成 EXIF 枚举【每行依次为：变体、EXIF 数值、exiftool 的文字描述】
+///
+/// 展示名称在消息目录中维护，消息 ID 为 `exif.枚举名.变体名`
+macro_rules! exif_enum {
+    (
+        $(#[$attr:meta])*
+        $name:ident {
+            $(($variant:ident, $code:literal, $exif_tool_desc:literal))+
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum $name {
+            $($variant,)+
+        }
+
+        impl $name {
+            /// 所有取值
+            pub const ALL: &'static [$name] = &[$($name::$variant),+];
+
+            /// EXIF 中的数值
+            pub fn code(&self) -> i32 {
+                match self {
+                    $($name::$variant => $code,)+
+                }
+            }
+
+            /// 按 EXIF 数值转换【未定义的数值返回 None】
+            pub fn from_code(code: i32) -> Option<$name> {
+                match code {
+                    $($code => Some($name::$variant),)+
+                    _ => None,
+                }
+            }
+
+            /// 解析 exiftool 的输出【文字描述或数值（`-n` 输出）】
+            pub fn from_exiftool(value: &str) -> Option<$name> {
+                let value = value.trim();
+                $(
+                    if value.eq_ignore_ascii_case($exif_tool_desc) {
+                        return Some($name::$variant);
+                    }
+                )+
+                parse_code(value).and_then($name::from_code)
+            }
+
+            /// exiftool 的文字描述
+            pub const fn exif_tool_desc(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $exif_tool_desc,)+
+                }
+            }
+
+            /// 展示名称的消息 ID
+            pub const fn label_id(&self) -> &'static str {
+                match self {
+                    $($name::$variant => concat!("exif.", stringify!($name), ".", stringify!($variant)),)+
+                }
+            }
+
+            /// 当前语言的展示名称
+            pub fn display(&self) -> &'static str {
+                self.display_in(i18n::current_locale())
+            }
+
+            /// 指定语言的展示名称
+            pub fn display_in(&self, locale: Locale) -> &'static str {
+                i18n::text_in(locale, self.label_id())
+            }
+        }
+    };
+}
+
+exif_enum! {
+    /// 测光模式（MeteringMode）
+    MeteringMode {
+        (Unknown, 0, "Unknown")
+        (Average, 1, "Average")
+        (CenterWeightedAverage, 2, "Center-weighted average")
+        (Spot, 3, "Spot")
+        (MultiSpot, 4, "Multi-spot")
+        (MultiSegment, 5, "Multi-segment")
+        (Partial, 6, "Partial")
+        (Other, 255, "Other")
+    }
+}
+
+exif_enum! {
+    /// 曝光程序（ExposureProgram）
+    ExposureProgram {
+        (NotDefined, 0, "Not Defined")
+        (Manual, 1, "Manual")
+        (Program, 2, "Program AE")
+        (AperturePriority, 3, "Aperture-priority AE")
+        (ShutterPriority, 4, "Shutter speed priority AE")
+        (Creative, 5, "Creative (Slow speed)")
+        (Action, 6, "Action (High speed)")
+        (Portrait, 7, "Portrait")
+        (Landscape, 8, "Landscape")
+        (Bulb, 9, "Bulb")
+    }
+}
+
+exif_enum! {
+    /// 白平衡（WhiteBalance）
+    WhiteBalance {
+        (Auto, 0, "Auto")
+        (Manual, 1, "Manual")
+    }
+}
+
+exif_enum! {
+    /// 场景拍摄类型（SceneCaptureType）
+    SceneCaptureType {
+        (Standard, 0, "Standard")
+        (Landscape, 1, "Landscape")
+        (Portrait, 2, "Portrait")
+        (Night, 3, "Night")
+        (Other, 4, "Other")
+    }
+}
+
+/// 闪光灯（Flash）【按位组合的数值】
+///
+/// - 第 0 位：是否闪光
+/// - 第 1、2 位：回闪检测（10 未检测到、11 检测到）
+/// - 第 3、4 位：模式（01 强制开、10 强制关、11 自动）
+/// - 第 5 位：没有闪光灯
+/// - 第 6 位：防红眼
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Flash(pub i32);
+
+impl Flash {
+    const FIRED: i32 = 0x01;
+    const RETURN_NOT_DETECTED: i32 = 0x04;
+    const RETURN_DETECTED: i32 = 0x06;
+    const MODE_ON: i32 = 0x08;
+    const MODE_OFF: i32 = 0x10;
+    const MODE_AUTO: i32 = 0x18;
+    const MODE_MASK: i32 = 0x18;
+    const NO_FUNCTION: i32 = 0x20;
+    const RED_EYE: i32 = 0x40;
+
+    /// 解析 exiftool 的输出【如 `Auto, Fired, Red-eye reduction`，也支持数值】
+    pub fn from_exiftool(value: &str) -> Option<Flash> {
+        let value = value.trim();
+        if let Some(code) = parse_code(value) {
+            return Some(Flash(code));
+        }
+        let mut code = 0;
+        let mut did_not_fire = false;
+        for part in value.split(',').map(str::trim) {
+            match part.to_ascii_lowercase().as_str() {
+                "fired" => code |= Flash::FIRED,
+                "did not fire" | "no flash" => did_not_fire = true,
+                "on" => code |= Flash::MODE_ON,
+                "off" => code |= Flash::MODE_OFF,
+                "auto" => code |= Flash::MODE_AUTO,
+                "return not detected" => code |= Flash::RETURN_NOT_DETECTED,
+                "return detected" => code |= Flash::RETURN_DETECTED,
+                "no flash function" => code |= Flash::NO_FUNCTION,
+                "red-eye reduction" => code |= Flash::RED_EYE,
+                _ => return None,
+            }
+        }
+        // 强制开启时只有 `Did not fire` 表示没有闪光
+        if code & Flash::MODE_MASK == Flash::MODE_ON && !did_not_fire {
+            code |= Flash::FIRED;
+        }
+        Some(Flash(code))
+    }
+
+    /// 转换为 exiftool 的文字描述【如 `Auto, Fired, Red-eye reduction`，与 `from_exiftool` 对应】
+    pub fn exif_tool_desc(&self) -> String {
+        if self.0 == 0 {
+            return "No Flash".to_string();
+        }
+        let mut parts = Vec::new();
+        match self.0 & Flash::MODE_MASK {
+            Flash::MODE_ON => parts.push("On"),
+            Flash::MODE_OFF => parts.push("Off"),
+            Flash::MODE_AUTO => parts.push("Auto"),
+            _ => {}
+        }
+        if self.0 & Flash::NO_FUNCTION != 0 {
+            parts.push("No flash function");
+            return parts.join(", ");
+        }
+        parts.push(if self.fired() {
+            "Fired"
+        } else {
+            "Did not fire"
+        });
+        match self.0 & Flash::RETURN_DETECTED {
+            Flash::RETURN_NOT_DETECTED => parts.push("Return not detected"),
+            Flash::RETURN_DETECTED => parts.push("Return detected"),
+            _ => {}
+        }
+        if self.0 & Flash::RED_EYE != 0 {
+            parts.push("Red-eye reduction");
+        }
+        parts.join(", ")
+    }
+
+    /// 是否闪光
+    pub fn fired(&self) -> bool {
+        self.0 & Flash::FIRED != 0
+    }
+
+    /// 当前语言的展示名称
+    pub fn display(&self) -> String {
+        self.display_in(i18n::current_locale())
+    }
+
+    /// 指定语言的展示名称【如 `自动，已闪光，防红眼`】
+    pub fn display_in(&self, locale: Locale) -> String {
+        if self.0 & Flash::NO_FUNCTION != 0 {
+            return i18n::text_in(locale, msg::EXIF_FLASH_NO_FUNCTION).to_string();
+        }
+        let mut parts = Vec::new();
+        match self.0 & Flash::MODE_MASK {
+            Flash::MODE_ON => parts.push(msg::EXIF_FLASH_ON),
+            Flash::MODE_OFF => parts.push(msg::EXIF_FLASH_OFF),
+            Flash::MODE_AUTO => parts.push(msg::EXIF_FLASH_AUTO),
+            _ => {}
+        }
+        parts.push(if self.fired() {
+            msg::EXIF_FLASH_FIRED
+        } else {
+            msg::EXIF_FLASH_NOT_FIRED
+        });
+        if self.0 & Flash::RED_EYE != 0 {
+            parts.push(msg::EXIF_FLASH_RED_EYE);
+        }
+        parts
+            .into_iter()
+            .map(|x| i18n::text_in(locale, x))
+            .collect::<Vec<_>>()
+            .join(i18n::text_in(locale, msg::LIST_SEPARATOR))
+    }
+}
+
+/// 解析数值【支持十进制和 exiftool 未知值的 `Unknown (0x41)` 形式】
+fn parse_code(value: &str) -> Option<i32> {
+    if let Ok(code) = value.parse::<i32>() {
+        return Some(code);
+    }
+    let inner = value.strip_prefix("Unknown (")?.strip_suffix(')')?;
+    match inner.strip_prefix("0x") {
+        Some(hex) => i32::from_str_radix(hex, 16).ok(),
+        None => inner.parse::<i32>().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enums() {
+        assert_eq!(
+            MeteringMode::from_exiftool("Multi-segment"),
+            Some(MeteringMode::MultiSegment)
+        );
+        assert_eq!(
+            MeteringMode::from_exiftool("255"),
+            Some(MeteringMode::Other)
+        );
+        let program = ExposureProgram::from_code(3).unwrap();
+        assert_eq!(program.label_id(), "exif.ExposureProgram.AperturePriority");
+        assert_eq!(program.display_in(Locale::ZhCn), "光圈优先");
+        assert_eq!(program.display_in(Locale::EnUs), "Aperture priority");
+        assert_eq!(
+            WhiteBalance::from_exiftool("Manual").map(|x| x.code()),
+            Some(1)
+        );
+        assert_eq!(SceneCaptureType::from_exiftool("Unknown (9)"), None);
+        assert_eq!(
+            SceneCaptureType::from_exiftool("night"),
+            Some(SceneCaptureType::Night)
+        );
+    }
+
+    #[test]
+    fn test_flash() {
+        let cases = [
+            ("No Flash", 0x00),
+            ("Off, Did not fire", 0x10),
+            ("On, Return not detected", 0x0D),
+            ("On, Did not fire", 0x08),
+            ("Auto, Fired, Red-eye reduction, Return detected", 0x5F),
+            ("Off, No flash function", 0x30),
+            ("Unknown (0x41)", 0x41),
+        ];
+        for (value, code) in cases {
+            assert_eq!(Flash::from_exiftool(value), Some(Flash(code)), "{}", value);
+        }
+        for code in [0x00, 0x10, 0x0D, 0x08, 0x19, 0x5F, 0x20, 0x30] {
+            let desc = Flash(code).exif_tool_desc();
+            assert_eq!(Flash::from_exiftool(&desc), Some(Flash(code)), "{}", desc);
+        }
+        assert_eq!(MeteringMode::Spot.exif_tool_desc(), "Spot");
+        assert!(Flash(0x19).fired());
+        assert_eq!(Flash(0x59).display_in(Locale::ZhCn), "自动，已闪光，防红眼");
+        assert_eq!(Flash(0x59).display_in(Locale::EnUs), "Auto, Fired, Red-eye reduction");
+        assert_eq!(Flash(0x20).display_in(Locale::ZhCn), "无闪光灯");
+        assert_eq!(Flash::from_exiftool("Sometimes"), None);
+    }
+}
@@ -0,0 +1,315 @@
+//! EXIF 读取
+//!
+//! 把 TIFF 结构中的 IFD0、IFD1 以及它们指向的 EXIF、GPS、互操作 IFD 解码为字段列表，
+//! 并按标签和 IFD 序号建立索引，查找字段不需要遍历
+
+use crate::utils::exif_utils::tiff::{Ifd, Parser};
+use crate::utils::exif_utils::value::Value;
+use anyhow::{anyhow, Error, Result};
+use std::collections::HashMap;
+use std::fmt;
+
+/// 标签所在的 IFD 类型【GPS、互操作 IFD 的标签 ID 与其他 IFD 重复，需要一起区分】
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Context {
+    /// IFD0、IFD1 中的 TIFF 标签
+    Tiff,
+    Exif,
+    Gps,
+    /// 互操作 IFD【位于 EXIF IFD 中】
+    Interop,
+}
+
+impl fmt::Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Context::Tiff => "TIFF",
+            Context::Exif => "EXIF",
+            Context::Gps => "GPS",
+            Context::Interop => "Interop",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 标签（所在的 IFD 类型、标签 ID）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tag(pub Context, pub u16);
+
+impl Tag {
+    pub const MAKE: Tag = Tag(Context::Tiff, 0x010F);
+    pub const MODEL: Tag = Tag(Context::Tiff, 0x0110);
+    pub const DATE_TIME_ORIGINAL: Tag = Tag(Context::Exif, 0x9003);
+    pub const OFFSET_TIME_ORIGINAL: Tag = Tag(Context::Exif, 0x9011);
+    pub const GPS_LATITUDE_REF: Tag = Tag(Context::Gps, 0x0001);
+    pub const GPS_LATITUDE: Tag = Tag(Context::Gps, 0x0002);
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} 0x{:04X}", self.0, self.1)
+    }
+}
+
+/// 字段所属的图像【IFD 序号】
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct In(pub u16);
+
+impl In {
+    /// 主图像（IFD0）
+    pub const PRIMARY: In = In(0);
+    /// 缩略图（IFD1）
+    pub const THUMBNAIL: In = In(1);
+}
+
+/// EXIF 字段
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub tag: Tag,
+    pub ifd_num: In,
+    pub value: Value,
+}
+
+/// 解析后的 EXIF【保留字段在文件中的顺序，同时按标签和 IFD 序号索引】
+#[derive(Debug, Clone, Default)]
+pub struct Exif {
+    fields: Vec<Field>,
+    index: HashMap<(Tag, In), usize>,
+    little_endian: bool,
+}
+
+impl Exif {
+    /// 由字段列表建立索引【如宽松模式解析得到的字段】
+    /// - fields 字段列表
+    /// - little_endian 是否为小端字节序
+    pub fn new(fields: Vec<Field>, little_endian: bool) -> Exif {
+        let mut exif = Exif {
+            little_endian,
+            ..Default::default()
+        };
+        for field in fields {
+            exif.push(field);
+        }
+        exif
+    }
+
+    /// 所有字段【按文件中的顺序】
+    pub fn fields(&self) -> impl Iterator<Item = &Field> {
+        self.fields.iter()
+    }
+
+    /// 查找字段【同一个标签出现多次时返回第一个】
+    /// - tag 标签
+    /// - in_ 所属的图像
+    pub fn get_field(&self, tag: Tag, in_: In) -> Option<&Field> {
+        self.index.get(&(tag, in_)).map(|x| &self.fields[*x])
+    }
+
+    /// 字段的文字值【去掉首尾空白，为空或不是文字时返回 None】
+    pub fn get_text(&self, tag: Tag, in_: In) -> Option<&str> {
+        self.get_field(tag, in_)
+            .and_then(|x| x.value.as_ascii_str().ok())
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+    }
+
+    /// 是否为小端字节序
+    pub fn little_endian(&self) -> bool {
+        self.little_endian
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    fn push(&mut self, field: Field) {
+        self.index
+            .entry((field.tag, field.ifd_num))
+            .or_insert(self.fields.len());
+        self.fields.push(field);
+    }
+}
+
+/// EXIF IFD 指针
+const TAG_EXIF_IFD: u16 = 0x8769;
+/// GPS IFD 指针
+const TAG_GPS_IFD: u16 = 0x8825;
+/// 互操作 IFD 指针
+const TAG_INTEROP_IFD: u16 = 0xA005;
+
+/// 解析 EXIF 数据（TIFF 结构，不含 `Exif\0\0` 标识）
+///
+/// 读取主图像和缩略图的 IFD；IFD 指针本身不作为字段返回，任一字段无法读取时返回错误
+pub fn parse_exif(data: &[u8]) -> Result<Exif> {
+    let mut reader = Reader {
+        continue_on_error: false,
+        errors: Vec::new(),
+    };
+    let (fields, little_endian) = reader.read(data)?;
+    Ok(Exif::new(fields, little_endian))
+}
+
+/// 宽松模式解析 EXIF 数据，返回（能读取的字段、遇到的错误、是否为小端字节序）
+///
+/// 字段或子 IFD 无法读取时记录错误后跳过，继续读取其他字段【如 GPS IFD 损坏时仍返回拍摄时间、相机等信息】；
+/// TIFF 头无效时没有字段，只返回错误
+pub fn parse_exif_lenient(data: &[u8]) -> (Vec<Field>, Vec<Error>, bool) {
+    let mut reader = Reader {
+        continue_on_error: true,
+        errors: Vec::new(),
+    };
+    match reader.read(data) {
+        Ok((fields, little_endian)) => (fields, reader.errors, little_endian),
+        Err(e) => {
+            reader.errors.push(e);
+            (Vec::new(), reader.errors, false)
+        }
+    }
+}
+
+/// IFD 读取过程
+struct Reader {
+    /// 遇到错误时是否继续读取
+    continue_on_error: bool,
+    /// 继续读取时跳过的错误
+    errors: Vec<Error>,
+}
+
+impl Reader {
+    /// 读取 IFD0、IFD1 及其子 IFD，返回（字段、是否为小端字节序）
+    fn read(&mut self, data: &[u8]) -> Result<(Vec<Field>, bool)> {
+        let parser = Parser::new(data)?;
+        let ifds = parser.ifds();
+        if ifds.is_empty() {
+            return Err(anyhow!("EXIF 中没有 IFD0"));
+        }
+        let mut fields = Vec::new();
+        for (num, ifd) in ifds.into_iter().take(2).enumerate() {
+            self.read_ifd(&parser, ifd, Context::Tiff, In(num as u16), &mut fields)?;
+        }
+        Ok((fields, parser.little_endian()))
+    }
+
+    /// 读取 IFD 中的字段，遇到子 IFD 指针时读取子 IFD
+    fn read_ifd(
+        &mut self,
+        parser: &Parser<'_>,
+        ifd: Ifd<'_>,
+        context: Context,
+        in_: In,
+        fields: &mut Vec<Field>,
+    ) -> Result<()> {
+        for entry in ifd.entries() {
+            let child = match (context, entry.tag) {
+                (Context::Tiff, TAG_EXIF_IFD) => Some(Context::Exif),
+                (Context::Tiff, TAG_GPS_IFD) => Some(Context::Gps),
+                (Context::Exif, TAG_INTEROP_IFD) => Some(Context::Interop),
+                _ => None,
+            };
+            if let Some(child) = child {
+                let sub = entry
+                    .as_u32()
+                    .and_then(|x| parser.ifd(x as usize))
+                    .ok_or_else(|| anyhow!("{} IFD 的偏移量无效", child));
+                match sub {
+                    Ok(sub) => self.read_ifd(parser, sub, child, in_, fields)?,
+                    Err(e) => self.skip(e)?,
+                }
+                continue;
+            }
+            let tag = Tag(context, entry.tag);
+            match entry.value() {
+                Ok(value) => fields.push(Field {
+                    tag,
+                    ifd_num: in_,
+                    value,
+                }),
+                Err(e) => self.skip(anyhow!("标签 {} 读取失败: {}", tag, e))?,
+            }
+        }
+        Ok(())
+    }
+
+    /// 宽松模式下记录错误，否则返回错误
+    fn skip(&mut self, error: Error) -> Result<()> {
+        if !self.continue_on_error {
+            return Err(error);
+        }
+        self.errors.push(error);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::exif_utils::value::Rational;
+
+    fn sample_tiff() -> Vec<u8> {
+        // 小端，IFD0 有 Make、GPS IFD 指针；GPS IFD 有 GPSLatitudeRef（与互操作标签 ID 相同）、GPSLatitude
+        let mut tiff = b"II*\0\x08\0\0\0".to_vec();
+        tiff.extend(2u16.to_le_bytes());
+        tiff.extend([0x0F, 0x01, 2, 0, 6, 0, 0, 0, 38, 0, 0, 0]);
+        tiff.extend([0x25, 0x88, 4, 0, 1, 0, 0, 0, 44, 0, 0, 0]);
+        tiff.extend(0u32.to_le_bytes());
+        tiff.extend(b"Canon\0");
+        // GPS IFD 在 44，2 个条目共 30 字节，纬度的值在 74
+        tiff.extend(2u16.to_le_bytes());
+        tiff.extend([0x01, 0x00, 2, 0, 2, 0, 0, 0, b'N', 0, 0, 0]);
+        tiff.extend([0x02, 0x00, 5, 0, 3, 0, 0, 0, 74, 0, 0, 0]);
+        tiff.extend(0u32.to_le_bytes());
+        for (numerator, denominator) in [(22u32, 1u32), (30, 1), (3600, 100)] {
+            tiff.extend(numerator.to_le_bytes());
+            tiff.extend(denominator.to_le_bytes());
+        }
+        tiff
+    }
+
+    #[test]
+    fn test_parse_exif() {
+        let exif = parse_exif(&sample_tiff()).unwrap();
+        assert!(exif.little_endian());
+        // 指针不作为字段返回，保留文件中的顺序
+        let tags: Vec<Tag> = exif.fields().map(|x| x.tag).collect();
+        assert_eq!(
+            tags,
+            vec![Tag::MAKE, Tag::GPS_LATITUDE_REF, Tag::GPS_LATITUDE]
+        );
+        assert_eq!(exif.get_text(Tag::MAKE, In::PRIMARY), Some("Canon"));
+        assert_eq!(exif.get_text(Tag::GPS_LATITUDE_REF, In::PRIMARY), Some("N"));
+        // 相同 ID 的互操作标签不会匹配到 GPS 字段
+        assert!(exif
+            .get_field(Tag(Context::Interop, 0x0001), In::PRIMARY)
+            .is_none());
+        assert!(exif.get_field(Tag::MAKE, In::THUMBNAIL).is_none());
+        let latitude = exif.get_field(Tag::GPS_LATITUDE, In::PRIMARY).unwrap();
+        assert_eq!(
+            latitude.value.as_rationals().unwrap()[2],
+            Rational::new(3600, 100)
+        );
+
+        assert!(parse_exif(b"not tiff").is_err());
+        // GPS 指针越界
+        let mut broken = sample_tiff();
+        broken[30..34].copy_from_slice(&1000u32.to_le_bytes());
+        assert!(parse_exif(&broken).is_err());
+
+        // 宽松模式跳过损坏的 GPS IFD，保留其他字段
+        let (fields, errors, little_endian) = parse_exif_lenient(&broken);
+        assert!(little_endian);
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].tag, Tag::MAKE);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].to_string().contains("GPS"));
+        let exif = Exif::new(fields, little_endian);
+        assert_eq!(exif.get_text(Tag::MAKE, In::PRIMARY), Some("Canon"));
+
+        let (fields, errors, _) = parse_exif_lenient(b"not tiff");
+        assert!(fields.is_empty());
+        assert_eq!(errors.len(), 1);
+    }
+}
@@ -1,4 +1,9 @@
-use crate::utils::exif_utils::tag::Tags;
+use crate::constant::{EXIF_SCAN_MAX_BYTES, JPEG_EXTENSIONS};
+use crate::utils::exif_utils::exif_reader::{self, Exif};
+use crate::utils::exif_utils::exiftool;
+use crate::utils::exif_utils::jpeg_segment;
+use crate::utils::exif_utils::tag::{DateTimeParseMode, ImgExif, Tags};
+use crate::utils::exif_utils::tiff;
 use crate::utils::file_util;
 use anyhow::{anyhow, Result};
 use diesel::query_dsl::InternalJoinDsl;
@@ -8,6 +13,7 @@ use lazy_static::lazy_static;
 use std::env;
 use std::fs::File;
 use std::io::{BufReader, Cursor, Read, Write};
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 
@@ -19,43 +25,126 @@ lazy_static! {
 
 pub trait ExifUtil {
     // todo: 2025/1/6 13:39 基础信息读取
-    /// 读取所有 exif 信息
-    fn read_all_exif(&self, path: &str) -> Result<String>;
+    /// 读取所有 exif 信息（标签名称、值）
+    fn read_all_exif(&self, path: &Path) -> Result<Vec<(String, String)>>;
     fn write_exif(&self, exif_data: Vec<u8>);
 }
 
+/// 读取图像的元数据
+///
+/// 优先使用内置解析读取 EXIF（JPEG 的 APP1 段、基于 TIFF 结构的文件开头），exiftool 可用时只补充内置解析
+/// 没有读取到的标签；JPEG 文件再补充 APP 段中的 XMP、IPTC 和注释
+///
+/// 日期时间使用宽松模式解析，非标准格式的拍摄时间也能读取；JPEG 的 EXIF 中损坏的 IFD 跳过，
+/// 错误记录在 `warnings` 中
+pub fn read_img_exif(path: &Path) -> Result<ImgExif> {
+    if !file_util::file_exists(path) {
+        return Err(anyhow!("文件不存在"));
+    }
+    let jpeg_metadata = if is_jpeg(path) {
+        jpeg_segment::read_jpeg_metadata(file_util::long_path(path))
+            .map_err(|e| log::warn!("JPEG 元数据段读取失败 {}: {}", path.display(), e))
+            .ok()
+    } else {
+        None
+    };
+    let mut warnings = Vec::new();
+    let native = match jpeg_metadata.as_ref().and_then(|x| x.exif.as_deref()) {
+        Some(tiff) => {
+            let (fields, errors, little_endian) = exif_reader::parse_exif_lenient(tiff);
+            warnings.extend(errors.iter().map(|e| e.to_string()));
+            Some(Exif::new(fields, little_endian))
+        }
+        None => read_tiff_exif(path),
+    };
+    let mut tags = Tags::new(true).with_datetime_mode(DateTimeParseMode::Lenient);
+    if let Some(exif) = &native {
+        tags = tags.with_exif(exif);
+    }
+    if exiftool::is_available() {
+        match ExifToolCmd.read_all_exif(path) {
+            Ok(entries) => tags = tags.with_extra_entries(entries),
+            // 内置解析没有读取到 EXIF 时 exiftool 是唯一的来源
+            Err(e) if native.is_none() => return Err(e),
+            Err(e) => log::warn!(
+                "exiftool 读取失败，只使用内置解析 {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+    let mut img_exif = tags.pack_object()?;
+    if img_exif.date_time_normalized {
+        log::info!("拍摄时间为非标准格式，已规范化: {}", path.display());
+    }
+    if let Some(metadata) = &jpeg_metadata {
+        metadata.merge_into(&mut img_exif);
+    }
+    img_exif.warnings = warnings;
+    Ok(img_exif)
+}
+
+/// 从文件开头的数据中读取 EXIF【只使用内置解析，不调用 exiftool，如远程存储中只按范围读取了文件开头的照片】
+///
+/// 支持 JPEG 和基于 TIFF 结构的文件，数据中没有 EXIF 时返回 None；超出数据范围的字段跳过
+pub fn read_header_exif(data: &[u8]) -> Option<ImgExif> {
+    let jpeg_metadata = jpeg_segment::parse_jpeg_metadata(data).ok();
+    let tiff = match &jpeg_metadata {
+        Some(metadata) => metadata.exif.as_deref()?,
+        None => {
+            tiff::Parser::new(data).ok()?;
+            data
+        }
+    };
+    let (fields, errors, little_endian) = exif_reader::parse_exif_lenient(tiff);
+    let exif = Exif::new(fields, little_endian);
+    let mut img_exif = Tags::new(true)
+        .with_datetime_mode(DateTimeParseMode::Lenient)
+        .with_exif(&exif)
+        .pack_object()
+        .ok()?;
+    if let Some(metadata) = &jpeg_metadata {
+        metadata.merge_into(&mut img_exif);
+    }
+    img_exif.warnings = errors.iter().map(|e| e.to_string()).collect();
+    Some(img_exif)
+}
+
+/// 内置解析基于 TIFF 结构的文件（TIFF、CR2、NEF、ARW、DNG 等）的 EXIF【只读取文件开头，不是 TIFF 结构时返回 None】
+///
+/// 超出读取范围的字段跳过，不作为读取错误
+fn read_tiff_exif(path: &Path) -> Option<Exif> {
+    let file = File::open(file_util::long_path(path)).ok()?;
+    let mut data = Vec::new();
+    file.take(EXIF_SCAN_MAX_BYTES).read_to_end(&mut data).ok()?;
+    tiff::Parser::new(&data).ok()?;
+    let (fields, errors, little_endian) = exif_reader::parse_exif_lenient(&data);
+    for e in errors {
+        log::debug!("EXIF 字段跳过 {}: {}", path.display(), e);
+    }
+    Some(Exif::new(fields, little_endian))
+}
+
+/// 按扩展名判断是否为 JPEG
+fn is_jpeg(path: &Path) -> bool {
+    path.extension()
+        .and_then(|x| x.to_str())
+        .is_some_and(|x| JPEG_EXTENSIONS.contains(&x.to_lowercase().as_str()))
+}
+
 pub(crate) struct ExifToolCmd;
 pub(crate) struct ArgusExif;
 
 impl ExifUtil for ExifToolCmd {
-    fn read_all_exif(&self, path: &str) -> Result<String> {
+    fn read_all_exif(&self, path: &Path) -> Result<Vec<(String, String)>> {
         // 检测文件是否存在
         if !file_util::file_exists(path) {
             return Err(anyhow!("文件不存在"));
         }
-
-        let exiftool_path = ExifToolCmd::get_exiftool_path();
-        println!("exiftool: {}", exiftool_path);
-        if !file_util::file_exists(exiftool_path.as_str()) {
-            eprintln!("exiftool: {}", exiftool_path);
+        if !exiftool::is_available() {
             return Err(anyhow!("执行文件 exiftool 不存在! "));
         }
-
-        let output = std::process::Command::new(exiftool_path.as_str())
-            .arg(path)
-            .output()
-            .expect("failed to execute process");
-
-        // 检查命令是否成功执行
-        if output.status.success() {
-            // 获取标准输出并转为字符串
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            Ok(stdout.into_owned())
-        } else {
-            // 获取标准错误并转为字符串
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(anyhow!(stderr.to_string()))
-        }
+        exiftool::read_tags(path)
     }
 
     fn write_exif(&self, exif_data: Vec<u8>) {
@@ -72,8 +161,54 @@ impl ExifUtil for ExifToolCmd {
 }
 
 impl ExifToolCmd {
+    /// 写入标签【值为空时删除该标签，目标为不存在的 `.xmp` 文件时自动创建】
+    /// - path 图像或 XMP 附属文件路径
+    /// - tags 标签名（如 `XMP-dc:Description`）及值
+    pub fn write_tags(&self, path: &Path, tags: &[(&str, Option<&str>)]) -> Result<()> {
+        let exiftool_path = ExifToolCmd::get_exiftool_path();
+        if !file_util::file_exists(exiftool_path.as_str()) {
+            return Err(anyhow!("执行文件 exiftool 不存在! "));
+        }
+        let mut cmd = std::process::Command::new(exiftool_path.as_str());
+        cmd.arg("-overwrite_original");
+        for (tag, value) in tags {
+            cmd.arg(format!("-{}={}", tag, value.unwrap_or_default()));
+        }
+        let output = cmd.arg(file_util::long_path(path)).output()?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(anyhow!(String::from_utf8_lossy(&output.stderr).to_string()))
+        }
+    }
+
+    /// 从原图复制元数据（EXIF、IPTC、XMP）到另一个文件【覆盖目标文件中的同名标签】
+    /// - src 原图
+    /// - dest 目标文件
+    /// - excluded 不复制的标签（如 `ThumbnailImage`、`MakerNotes:all`）
+    pub fn copy_tags(&self, src: &Path, dest: &Path, excluded: &[&str]) -> Result<()> {
+        let exiftool_path = ExifToolCmd::get_exiftool_path();
+        if !file_util::file_exists(exiftool_path.as_str()) {
+            return Err(anyhow!("执行文件 exiftool 不存在! "));
+        }
+        let mut cmd = std::process::Command::new(exiftool_path.as_str());
+        cmd.arg("-overwrite_original")
+            .arg("-TagsFromFile")
+            .arg(file_util::long_path(src))
+            .arg("-all:all");
+        for tag in excluded {
+            cmd.arg(format!("--{}", tag));
+        }
+        let output = cmd.arg(file_util::long_path(dest)).output()?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(anyhow!(String::from_utf8_lossy(&output.stderr).to_string()))
+        }
+    }
+
     /// 获取 exiftool 路径
-    fn get_exiftool_path() -> Arc<String> {
+    pub(crate) fn get_exiftool_path() -> Arc<String> {
         // 使用 AtomicBool 确保只初始化一次
         if !INIT.load(Ordering::Acquire) {
             let mut exif_cmd_path = EXIF_CMD_PATH.write().unwrap();
@@ -109,14 +244,41 @@ mod test {
     fn test_exif_tool() {
         let exif_tool = ExifToolCmd;
         let exif_data = exif_tool
-            .read_all_exif("./resources/image/image-1-1.JPG")
+            .read_all_exif(Path::new("./resources/image/image-1-1.JPG"))
             .unwrap();
-        let mut tag = Tags::new(true);
         println!("{:?}", exif_data);
-        let mt = tag.parse(&exif_data);
+        let mt = Tags::new(true).with_entries(exif_data);
         let option = mt
             .entry_map
             .get(crate::utils::exif_utils::tag::ExifToolDesc::MAKE.exif_tool_desc);
         println!("{:?}", option);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_header_exif() {
+        // 小端，IFD0 只有 Make
+        let mut tiff = b"II*\0\x08\0\0\0".to_vec();
+        tiff.extend(1u16.to_le_bytes());
+        tiff.extend([0x0F, 0x01, 2, 0, 6, 0, 0, 0, 26, 0, 0, 0]);
+        tiff.extend(0u32.to_le_bytes());
+        tiff.extend(b"Canon\0");
+        let exif = read_header_exif(&tiff).unwrap();
+        assert_eq!(exif.make.as_deref(), Some("Canon"));
+
+        // 只读取到压缩数据开头的 JPEG
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend(((tiff.len() + 8) as u16).to_be_bytes());
+        jpeg.extend(b"Exif\0\0");
+        jpeg.extend(&tiff);
+        jpeg.extend([0xFF, 0xDA, 0x00, 0x08, 0x01]);
+        let exif = read_header_exif(&jpeg).unwrap();
+        assert_eq!(exif.make.as_deref(), Some("Canon"));
+
+        assert!(read_header_exif(b"\x89PNG\r\n\x1a\n").is_none());
+    }
+}
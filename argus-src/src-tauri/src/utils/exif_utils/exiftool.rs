@@ -0,0 +1,188 @@
+//! exiftool 适配器
+//!
+//! exiftool 作为可选的补充读取方式：检测安装的版本，使用 `-j` 输出 JSON 后按标签名称读取，
+//! 不再解析文字输出；没有 exiftool 或版本过低时只使用内置解析
+
+use crate::utils::exif_utils::exif_util::ExifToolCmd;
+use crate::utils::exif_utils::exiftool_pool;
+use crate::utils::file_util;
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+
+/// 最低版本【`-charset filename=utf8` 从 9.79 开始支持，更早的版本无法读取非 ASCII 路径】
+const MIN_VERSION: ExifToolVersion = ExifToolVersion {
+    major: 9,
+    minor: 79,
+};
+/// JSON 输出中的文件路径字段
+const SOURCE_FILE_KEY: &str = "SourceFile";
+
+/// 检测到的 exiftool 版本【只检测一次】
+static VERSION: Lazy<Option<ExifToolVersion>> = Lazy::new(detect_version);
+
+/// exiftool 版本【如 `12.76`】
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExifToolVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl ExifToolVersion {
+    /// 解析 `-ver` 的输出【如 `12.76`、`13.00`】
+    pub fn parse(text: &str) -> Option<ExifToolVersion> {
+        let (major, minor) = text.trim().split_once('.').unwrap_or((text.trim(), "0"));
+        Some(ExifToolVersion {
+            major: major.parse().ok()?,
+            minor: minor.parse().ok()?,
+        })
+    }
+}
+
+impl fmt::Display for ExifToolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:02}", self.major, self.minor)
+    }
+}
+
+/// 可用的 exiftool 版本【不存在、无法执行或版本过低时为 None】
+pub fn version() -> Option<ExifToolVersion> {
+    *VERSION
+}
+
+/// exiftool 是否可用
+pub fn is_available() -> bool {
+    version().is_some()
+}
+
+/// 使用 exiftool 读取所有标签（标签名称、值）【通过常驻的进程池读取】
+///
+/// 值为 exiftool 转换后的文字【如 `1/250`、`Auto, Fired`】，列表类型的值用 `, ` 连接
+pub fn read_tags(path: &Path) -> Result<Vec<(String, String)>> {
+    let Some(version) = version() else {
+        return Err(anyhow!("exiftool 不可用!"));
+    };
+    let json = exiftool_pool::read_json(path)
+        .map_err(|e| anyhow!("exiftool {} 读取失败: {}", version, e))?;
+    parse_json(&json)
+}
+
+/// 解析 `-j` 输出的 JSON（只读取第一个文件）
+///
+/// 字符串原样保留，数字和布尔值转换为文字，列表用 `, ` 连接；空值、嵌套结构忽略
+pub fn parse_json(json: &str) -> Result<Vec<(String, String)>> {
+    let files: Vec<BTreeMap<String, Value>> =
+        serde_json::from_str(json).map_err(|e| anyhow!("exiftool 输出的 JSON 解析失败: {}", e))?;
+    let Some(file) = files.into_iter().next() else {
+        return Ok(Vec::new());
+    };
+    Ok(file
+        .into_iter()
+        .filter(|(key, _)| key != SOURCE_FILE_KEY)
+        .filter_map(|(key, value)| value_text(&value).map(|x| (key, x)))
+        .collect())
+}
+
+/// JSON 值转换为文字
+fn value_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(x) => Some(x.clone()),
+        Value::Number(x) => Some(x.to_string()),
+        Value::Bool(x) => Some(x.to_string()),
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().filter_map(value_text).collect();
+            (!items.is_empty()).then(|| items.join(", "))
+        }
+        Value::Null | Value::Object(_) => None,
+    }
+}
+
+/// 执行 `-ver` 检测版本
+fn detect_version() -> Option<ExifToolVersion> {
+    let path = ExifToolCmd::get_exiftool_path();
+    if !file_util::file_exists(path.as_str()) {
+        log::info!("exiftool 不存在，只使用内置解析: {}", path);
+        return None;
+    }
+    let output = match Command::new(path.as_str()).arg("-ver").output() {
+        Ok(x) if x.status.success() => x,
+        Ok(x) => {
+            log::warn!(
+                "exiftool 版本检测失败: {}",
+                String::from_utf8_lossy(&x.stderr).trim()
+            );
+            return None;
+        }
+        Err(e) => {
+            log::warn!("exiftool 无法执行 {}: {}", path, e);
+            return None;
+        }
+    };
+    let text = String::from_utf8_lossy(&output.stdout);
+    match ExifToolVersion::parse(&text) {
+        Some(version) if version >= MIN_VERSION => {
+            log::info!("exiftool 版本 {}", version);
+            Some(version)
+        }
+        Some(version) => {
+            log::warn!("exiftool 版本 {} 过低，需要 {} 以上", version, MIN_VERSION);
+            None
+        }
+        None => {
+            log::warn!("无法识别的 exiftool 版本: {}", text.trim());
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        let version = ExifToolVersion::parse("12.76\n").unwrap();
+        assert_eq!(
+            version,
+            ExifToolVersion {
+                major: 12,
+                minor: 76
+            }
+        );
+        assert_eq!(version.to_string(), "12.76");
+        assert!(version >= MIN_VERSION);
+        assert_eq!(ExifToolVersion::parse("13").unwrap().to_string(), "13.00");
+        assert!(ExifToolVersion::parse("9.70").unwrap() < MIN_VERSION);
+        assert!(ExifToolVersion::parse("unknown").is_none());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let json = r#"[{
+            "SourceFile": "D:/photos/IMG_0001.JPG",
+            "Make": "Canon",
+            "Model": "Canon EOS R5",
+            "ISO": 400,
+            "FNumber": 2.8,
+            "ExposureTime": "1/250",
+            "Flash": "Auto, Fired",
+            "Keywords": ["港口", "夜景"],
+            "ThumbnailImage": null
+        }]"#;
+        let tags: BTreeMap<String, String> = parse_json(json).unwrap().into_iter().collect();
+        assert!(!tags.contains_key(SOURCE_FILE_KEY));
+        assert!(!tags.contains_key("ThumbnailImage"));
+        assert_eq!(tags["Model"], "Canon EOS R5");
+        assert_eq!(tags["ISO"], "400");
+        assert_eq!(tags["FNumber"], "2.8");
+        assert_eq!(tags["ExposureTime"], "1/250");
+        assert_eq!(tags["Keywords"], "港口, 夜景");
+
+        assert!(parse_json("[]").unwrap().is_empty());
+        assert!(parse_json("Make : Canon").is_err());
+    }
+}
@@ -0,0 +1,275 @@
+//! exiftool 进程池
+//!
+//! 每个进程以 `-stay_open True -@ -` 模式常驻，从标准输入读取参数；同时到达的读取请求合并为一批，
+//! 一次写入后依次读取每个文件的结果，避免每张照片启动一次 exiftool
+
+use crate::constant::{EXIFTOOL_BATCH_SIZE, EXIFTOOL_POOL_SIZE, EXIFTOOL_PROCESS_MAX_FILES};
+use crate::utils::exif_utils::exif_util::ExifToolCmd;
+use crate::utils::file_util;
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// 读取请求
+struct Request {
+    path: PathBuf,
+    reply: Sender<Result<String>>,
+}
+
+/// 请求队列【第一次使用时启动工作线程，每个线程管理一个 exiftool 进程】
+static QUEUE: Lazy<Sender<Request>> = Lazy::new(|| {
+    let (tx, rx) = mpsc::channel::<Request>();
+    let rx = Arc::new(Mutex::new(rx));
+    for index in 0..EXIFTOOL_POOL_SIZE {
+        let rx = Arc::clone(&rx);
+        let spawned = thread::Builder::new()
+            .name(format!("exiftool-{}", index))
+            .spawn(move || worker(rx));
+        if let Err(e) = spawned {
+            log::error!("exiftool 工作线程启动失败: {}", e);
+        }
+    }
+    tx
+});
+
+/// 使用进程池读取文件，返回 `-j` 输出的 JSON
+pub fn read_json(path: &Path) -> Result<String> {
+    let (reply, result) = mpsc::channel();
+    QUEUE
+        .send(Request {
+            path: path.to_path_buf(),
+            reply,
+        })
+        .map_err(|_| anyhow!("exiftool 进程池已关闭!"))?;
+    result
+        .recv()
+        .map_err(|_| anyhow!("exiftool 进程池没有返回结果!"))?
+}
+
+/// 工作线程：取出一批请求交给进程执行
+fn worker(rx: Arc<Mutex<Receiver<Request>>>) {
+    let mut process: Option<ExifToolProcess> = None;
+    loop {
+        let batch = {
+            let rx = rx.lock().unwrap_or_else(|e| e.into_inner());
+            let Ok(first) = rx.recv() else {
+                return;
+            };
+            let mut batch = vec![first];
+            while batch.len() < EXIFTOOL_BATCH_SIZE {
+                match rx.try_recv() {
+                    Ok(x) => batch.push(x),
+                    Err(_) => break,
+                }
+            }
+            batch
+        };
+
+        // 处理的文件过多时重启进程，避免 exiftool 内存持续增长
+        if process
+            .as_ref()
+            .is_some_and(|x| x.executed >= EXIFTOOL_PROCESS_MAX_FILES)
+        {
+            process = None;
+        }
+        if process.is_none() {
+            match ExifToolProcess::spawn() {
+                Ok(x) => process = Some(x),
+                Err(e) => {
+                    let message = e.to_string();
+                    for request in batch {
+                        let _ = request.reply.send(Err(anyhow!("{}", message)));
+                    }
+                    continue;
+                }
+            }
+        }
+        let Some(current) = process.as_mut() else {
+            continue;
+        };
+        let paths: Vec<&Path> = batch.iter().map(|x| x.path.as_path()).collect();
+        let results = current.execute(&paths);
+        for (request, result) in batch.into_iter().zip(results) {
+            let _ = request.reply.send(result);
+        }
+        if current.broken {
+            log::warn!("exiftool 进程已退出，下一批重新启动");
+            process = None;
+        }
+    }
+}
+
+/// 常驻的 exiftool 进程
+struct ExifToolProcess {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    /// 命令序号【用于匹配 `{readyN}` 结束标记】
+    sequence: u64,
+    /// 已处理的文件数量
+    executed: usize,
+    /// 读写失败，需要重新启动
+    broken: bool,
+}
+
+impl ExifToolProcess {
+    fn spawn() -> Result<ExifToolProcess> {
+        let mut child = Command::new(ExifToolCmd::get_exiftool_path().as_str())
+            .args(["-stay_open", "True", "-@", "-"])
+            .args(["-common_args", "-j", "-charset", "filename=utf8"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| anyhow!("exiftool 启动失败: {}", e))?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("exiftool 标准输入获取失败"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("exiftool 标准输出获取失败"))?;
+        log::debug!("exiftool 进程启动: {}", child.id());
+        Ok(ExifToolProcess {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            sequence: 0,
+            executed: 0,
+            broken: false,
+        })
+    }
+
+    /// 写入所有文件的命令，同时按顺序读取结果【与 paths 一一对应】
+    ///
+    /// 写入在单独的线程中进行，避免输出管道写满后双方互相等待
+    fn execute(&mut self, paths: &[&Path]) -> Vec<Result<String>> {
+        let args: Vec<String> = paths
+            .iter()
+            .map(|x| file_util::long_path(x).to_string_lossy().to_string())
+            .collect();
+        let (commands, sequences) = batch_commands(&args, self.sequence);
+        self.sequence += args.len() as u64;
+
+        let stdin = &mut self.stdin;
+        let stdout = &mut self.stdout;
+        let mut broken = false;
+        let mut executed = 0;
+        let results = thread::scope(|scope| {
+            let writer = scope.spawn(move || {
+                stdin.write_all(commands.as_bytes())?;
+                stdin.flush()
+            });
+            let mut results = Vec::with_capacity(sequences.len());
+            for sequence in &sequences {
+                let Some(sequence) = sequence else {
+                    results.push(Err(anyhow!("文件路径中包含换行，exiftool 无法读取")));
+                    continue;
+                };
+                if broken {
+                    results.push(Err(anyhow!("exiftool 进程意外退出")));
+                    continue;
+                }
+                executed += 1;
+                match read_response(stdout, *sequence) {
+                    Ok(output) if output.trim().is_empty() => {
+                        results.push(Err(anyhow!("exiftool 没有返回结果，文件不存在或无法读取")))
+                    }
+                    Ok(output) => results.push(Ok(output)),
+                    Err(e) => {
+                        broken = true;
+                        results.push(Err(e));
+                    }
+                }
+            }
+            if let Ok(Err(e)) = writer.join() {
+                log::warn!("exiftool 命令写入失败: {}", e);
+                broken = true;
+            }
+            results
+        });
+        self.executed += executed;
+        self.broken = broken;
+        results
+    }
+}
+
+/// 读取到 `{readyN}` 为止的输出
+fn read_response(stdout: &mut BufReader<ChildStdout>, sequence: u64) -> Result<String> {
+    let ready = format!("{{ready{}}}", sequence);
+    let mut output = String::new();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if stdout.read_line(&mut line)? == 0 {
+            return Err(anyhow!("exiftool 进程意外退出"));
+        }
+        if line.trim_end() == ready {
+            break;
+        }
+        output.push_str(&line);
+    }
+    Ok(output)
+}
+
+impl Drop for ExifToolProcess {
+    fn drop(&mut self) {
+        // 通知 exiftool 退出，没有响应时结束进程
+        let _ = self.stdin.write_all(b"-stay_open\nFalse\n");
+        let _ = self.stdin.flush();
+        if !matches!(self.child.try_wait(), Ok(Some(_))) {
+            thread::sleep(Duration::from_millis(100));
+            if !matches!(self.child.try_wait(), Ok(Some(_))) {
+                let _ = self.child.kill();
+            }
+        }
+        let _ = self.child.wait();
+    }
+}
+
+/// 生成一批文件的参数（每个文件以 `-executeN` 结束），返回参数和每个文件的命令序号
+///
+/// 参数文件每行一个参数，路径中有换行时无法传递，对应的序号为 None
+/// - last_sequence 上一个命令序号
+fn batch_commands(paths: &[String], last_sequence: u64) -> (String, Vec<Option<u64>>) {
+    let mut commands = String::new();
+    let mut sequences = Vec::with_capacity(paths.len());
+    for (index, path) in paths.iter().enumerate() {
+        if path.contains(['\n', '\r']) {
+            sequences.push(None);
+            continue;
+        }
+        let sequence = last_sequence + index as u64 + 1;
+        commands.push_str(path);
+        commands.push_str(&format!("\n-execute{}\n", sequence));
+        sequences.push(Some(sequence));
+    }
+    (commands, sequences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_commands() {
+        let paths = vec![
+            "D:/photos/a.jpg".to_string(),
+            "D:/photos/b\nc.jpg".to_string(),
+            "D:/photos/照片.jpg".to_string(),
+        ];
+        let (commands, sequences) = batch_commands(&paths, 10);
+        assert_eq!(
+            commands,
+            "D:/photos/a.jpg\n-execute11\nD:/photos/照片.jpg\n-execute13\n"
+        );
+        assert_eq!(sequences, vec![Some(11), None, Some(13)]);
+    }
+}
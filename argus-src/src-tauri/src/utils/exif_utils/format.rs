@@ -0,0 +1,129 @@
+use crate::utils::exif_utils::value::Rational;
+
+/// 约分【分母为 0 时返回 None】
+pub fn reduce(numerator: u32, denominator: u32) -> Option<Rational> {
+    if denominator == 0 {
+        return None;
+    }
+    let divisor = gcd(numerator, denominator).max(1);
+    Some(Rational::new(numerator / divisor, denominator / divisor))
+}
+
+/// 不超过该值（秒）的曝光时间显示为 `1/n`【与 ExifTool 一致，更长的显示为小数，如 `0.6s`】
+const SHUTTER_FRACTION_MAX_SECONDS: f64 = 0.25001;
+
+/// 将以秒为单位的曝光时间转换为快门速度【不超过 1/4 秒时为 `1/n`，否则保留一位小数】
+pub fn shutter_from_seconds(seconds: f64) -> Option<Rational> {
+    if !seconds.is_finite() || seconds <= 0.0 {
+        return None;
+    }
+    if seconds < SHUTTER_FRACTION_MAX_SECONDS {
+        let denominator = (1.0 / seconds).round().max(1.0) as u32;
+        return Some(Rational::new(1, denominator));
+    }
+    reduce((seconds * 10.0).round() as u32, 10)
+}
+
+/// 格式化快门速度【如 `1/800`、`2s`、`1.3s`】
+/// - seconds 曝光时间（秒）
+pub fn format_shutter_speed(seconds: f64) -> Option<String> {
+    let shutter = shutter_from_seconds(seconds)?;
+    Some(if shutter.numerator == 1 && shutter.denominator > 1 {
+        shutter.to_string()
+    } else {
+        format!("{}s", trim_decimal(shutter.to_f64(), 1))
+    })
+}
+
+/// 解析快门速度，返回曝光时间（秒）【支持 `1/800`、`0.5`、`2s`、`2"`】
+pub fn parse_shutter_speed(value: &str) -> Option<f64> {
+    let value = value.trim().trim_end_matches(['s', '"']).trim();
+    let seconds = match value.split_once('/') {
+        Some((n, d)) => n.trim().parse::<f64>().ok()? / d.trim().parse::<f64>().ok()?,
+        None => value.parse::<f64>().ok()?,
+    };
+    (seconds.is_finite() && seconds > 0.0).then_some(seconds)
+}
+
+/// 格式化光圈【如 `f/2.8`、`f/8`】
+pub fn format_aperture(f_number: f64) -> Option<String> {
+    (f_number.is_finite() && f_number > 0.0).then(|| format!("f/{}", trim_decimal(f_number, 1)))
+}
+
+/// 解析光圈【支持 `f/2.8`、`F2.8`、`2.8`】
+pub fn parse_aperture(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let value = value
+        .strip_prefix("f/")
+        .or_else(|| value.strip_prefix("F/"))
+        .or_else(|| value.strip_prefix(['f', 'F']))
+        .unwrap_or(value);
+    let f_number = value.trim().parse::<f64>().ok()?;
+    (f_number.is_finite() && f_number > 0.0).then_some(f_number)
+}
+
+/// 曝光值（EV100）【由快门、光圈、ISO 计算，参数无效时返回 None】
+/// - exposure_time 曝光时间（秒）
+/// - f_number 光圈数
+/// - iso 感光度
+pub fn exposure_value(exposure_time: f64, f_number: f64, iso: f64) -> Option<f64> {
+    if exposure_time <= 0.0 || f_number <= 0.0 || iso <= 0.0 {
+        return None;
+    }
+    let ev = (f_number * f_number / exposure_time).log2() - (iso / 100.0).log2();
+    ev.is_finite().then_some(ev)
+}
+
+/// 保留指定位数的小数，并去掉末尾的 0
+fn trim_decimal(value: f64, digits: usize) -> String {
+    let text = format!("{:.*}", digits, value);
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shutter_speed() {
+        assert_eq!(reduce(125, 100000), Some(Rational::new(1, 800)));
+        assert_eq!(reduce(1, 0), None);
+        assert_eq!(format_shutter_speed(0.00125).as_deref(), Some("1/800"));
+        assert_eq!(format_shutter_speed(0.0333).as_deref(), Some("1/30"));
+        assert_eq!(format_shutter_speed(2.0).as_deref(), Some("2s"));
+        assert_eq!(format_shutter_speed(1.3).as_deref(), Some("1.3s"));
+        assert_eq!(format_shutter_speed(0.25).as_deref(), Some("1/4"));
+        assert_eq!(format_shutter_speed(0.6).as_deref(), Some("0.6s"));
+        assert_eq!(format_shutter_speed(0.5).as_deref(), Some("1/2"));
+        assert_eq!(shutter_from_seconds(0.6), Some(Rational::new(3, 5)));
+        assert_eq!(format_shutter_speed(0.0), None);
+        assert_eq!(parse_shutter_speed("1/800"), Some(0.00125));
+        assert_eq!(parse_shutter_speed("2\""), Some(2.0));
+        assert_eq!(parse_shutter_speed("1/0"), None);
+    }
+
+    #[test]
+    fn test_aperture_and_ev() {
+        assert_eq!(format_aperture(2.8).as_deref(), Some("f/2.8"));
+        assert_eq!(format_aperture(8.0).as_deref(), Some("f/8"));
+        assert_eq!(parse_aperture("f/2.8"), Some(2.8));
+        assert_eq!(parse_aperture("F4"), Some(4.0));
+        assert_eq!(parse_aperture("f/"), None);
+        // f/16、1/100 秒、ISO 100 为 EV 14.64
+        let ev = exposure_value(0.01, 16.0, 100.0).unwrap();
+        assert!((ev - 14.64).abs() < 0.01);
+        assert_eq!(exposure_value(0.0, 16.0, 100.0), None);
+    }
+}
@@ -1,8 +1,224 @@
+use crate::i18n::{self, msg, Locale};
 use crate::utils::exif_utils::tag::{ExifToolDesc, Tags};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
+/// 地球平均半径（米）
+const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
+/// 十进制 GPS 坐标【南纬、西经为负数】
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GpsCoordinate {
+    /// 纬度
+    pub latitude: f64,
+    /// 经度
+    pub longitude: f64,
+    /// 海拔（米）【海平面以下为负数】
+    pub altitude: Option<f64>,
+}
+
+impl fmt::Display for GpsCoordinate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.6}, {:.6}", self.latitude, self.longitude)?;
+        if let Some(x) = self.altitude {
+            write!(f, ", {}m", x)?;
+        }
+        Ok(())
+    }
+}
+
+impl GpsCoordinate {
+    /// 到另一个坐标的球面距离（米）【忽略海拔】
+    pub fn distance_meters(&self, other: &GpsCoordinate) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2)
+            + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_METERS * a.sqrt().asin()
+    }
+
+    /// 模糊后的坐标【经纬度保留指定小数位数，去掉海拔】
+    pub fn blurred(&self, decimals: u32) -> GpsCoordinate {
+        let factor = 10f64.powi(decimals as i32);
+        GpsCoordinate {
+            latitude: (self.latitude * factor).round() / factor,
+            longitude: (self.longitude * factor).round() / factor,
+            altitude: None,
+        }
+    }
+
+    /// 解析用户输入的十进制坐标【纬度在前，如 `39.9042, 116.4074`、`39.9042° N, 116.4074° E`】
+    ///
+    /// 纬度、经度用逗号分隔（没有逗号时用空白分隔），可以带 `°` 和方向（N/S/E/W、北纬/南纬/东经/西经），
+    /// 带方向时数值不能为负数；十进制格式的格式化结果可以重新解析
+    pub fn parse(input: &str) -> Result<GpsCoordinate> {
+        let error = || anyhow!("坐标 {} 格式错误", input);
+        let separators: &[char] = &[',', '，'];
+        let parts: Vec<&str> = if input.contains(separators) {
+            input.split(separators).collect()
+        } else {
+            input.split_whitespace().collect()
+        };
+        let [latitude, longitude] = parts.as_slice() else {
+            return Err(error());
+        };
+        let latitude = parse_decimal_degrees(latitude, true).ok_or_else(error)?;
+        let longitude = parse_decimal_degrees(longitude, false).ok_or_else(error)?;
+        let coordinate = GpsCoordinate {
+            latitude,
+            longitude,
+            altitude: None,
+        };
+        coordinate.validate()?;
+        Ok(coordinate)
+    }
+
+    /// 解析旧版本保存的 GPS 文本【如 `N39°54′15.2″E116°24′26.6″`】
+    ///
+    /// 旧版本按度分秒拼接保存，海拔读取的是经度字段，不可用，统一丢弃
+    pub fn parse_legacy(text: &str) -> Option<GpsCoordinate> {
+        let re = Regex::new(r"^([NS])?(\d+)°(\d+)′([\d.]+)″([EW])?(\d+)°(\d+)′([\d.]+)″").unwrap();
+        let caps = re.captures(text.trim())?;
+        let degrees = |index: usize| -> Option<f64> {
+            let dms = DMS::new(
+                caps[index].parse().ok()?,
+                caps[index + 1].parse().ok()?,
+                caps[index + 2].parse().ok()?,
+            );
+            Some(dms.to_decimal())
+        };
+        let sign = |index: usize| match caps.get(index).map(|x| x.as_str()) {
+            Some("S") | Some("W") => -1.0,
+            _ => 1.0,
+        };
+        let coordinate = GpsCoordinate {
+            latitude: degrees(2)? * sign(1),
+            longitude: degrees(6)? * sign(5),
+            altitude: None,
+        };
+        coordinate.validate().ok()?;
+        Some(coordinate)
+    }
+
+    /// 校验坐标范围【纬度 -90 ~ 90，经度 -180 ~ 180，海拔为有限值】
+    pub fn validate(&self) -> Result<()> {
+        if !(-90.0..=90.0).contains(&self.latitude)
+            || !(-180.0..=180.0).contains(&self.longitude)
+            || self.altitude.is_some_and(|x| !x.is_finite())
+        {
+            return Err(anyhow!("坐标 {} 超出范围", self));
+        }
+        Ok(())
+    }
+
+    /// 按语言格式化经纬度【不包含海拔】
+    /// - format 显示格式
+    /// - locale 语言
+    pub fn format(&self, format: CoordinateFormat, locale: Locale) -> String {
+        let latitude = if self.latitude < 0.0 {
+            Direction::South
+        } else {
+            Direction::North
+        };
+        let longitude = if self.longitude < 0.0 {
+            Direction::West
+        } else {
+            Direction::East
+        };
+        let part = |direction: Direction, value: f64| {
+            let args = [("value".to_string(), format.format_degrees(value.abs()))];
+            i18n::render(locale, direction.label_id(), &args.into_iter().collect())
+        };
+        let args = [
+            ("latitude".to_string(), part(latitude, self.latitude)),
+            ("longitude".to_string(), part(longitude, self.longitude)),
+        ];
+        i18n::render(locale, msg::GPS_COORDINATE, &args.into_iter().collect())
+    }
+}
+
+/// 解析一个十进制度数【带方向时按方向确定符号，并校验是纬度还是经度】
+/// - latitude 是否为纬度
+fn parse_decimal_degrees(input: &str, latitude: bool) -> Option<f64> {
+    let mut text = input.trim();
+    let mut direction = None;
+    for (prefix, value) in [
+        ("北纬", Direction::North),
+        ("南纬", Direction::South),
+        ("东经", Direction::East),
+        ("西经", Direction::West),
+    ] {
+        if let Some(rest) = text.strip_prefix(prefix) {
+            text = rest.trim_start();
+            direction = Some(value);
+            break;
+        }
+    }
+    if let Some(c) = text.chars().last().filter(|c| c.is_ascii_alphabetic()) {
+        if direction.is_some() {
+            return None;
+        }
+        direction = Some(Direction::from_str(&c.to_string())?);
+        text = text[..text.len() - 1].trim_end();
+    }
+    let text = text.strip_suffix('°').unwrap_or(text).trim_end();
+    let value: f64 = text.parse().ok().filter(|x: &f64| x.is_finite())?;
+    match direction {
+        None => Some(value),
+        Some(_) if value < 0.0 => None,
+        Some(Direction::North) if latitude => Some(value),
+        Some(Direction::South) if latitude => Some(-value),
+        Some(Direction::East) if !latitude => Some(value),
+        Some(Direction::West) if !latitude => Some(-value),
+        Some(_) => None,
+    }
+}
+
+/// 坐标显示格式
+#[derive(Deserialize, Serialize, Default, Clone, Copy, Debug, PartialEq)]
+pub enum CoordinateFormat {
+    /// 十进制度数（39.904200°）
+    #[default]
+    #[serde(rename = "decimal")]
+    Decimal,
+    /// 度、十进制分（39°54.2520′）
+    #[serde(rename = "ddm")]
+    Ddm,
+    /// 度、分、秒（39°54′15.12″）
+    #[serde(rename = "dms")]
+    Dms,
+}
+
+impl CoordinateFormat {
+    /// 格式化度数的绝对值【先按显示精度取整，避免出现 60 分、60 秒】
+    pub fn format_degrees(&self, degrees: f64) -> String {
+        match self {
+            CoordinateFormat::Decimal => format!("{:.6}°", degrees),
+            CoordinateFormat::Ddm => {
+                // 0.0001 分
+                let total = (degrees * 600_000.0).round() as i64;
+                let minutes = (total % 600_000) as f64 / 10_000.0;
+                format!("{}°{:.4}′", total / 600_000, minutes)
+            }
+            CoordinateFormat::Dms => {
+                // 0.01 秒
+                let total = (degrees * 360_000.0).round() as i64;
+                let seconds = (total % 6_000) as f64 / 100.0;
+                format!(
+                    "{}°{}′{:.2}″",
+                    total / 360_000,
+                    total % 360_000 / 6_000,
+                    seconds
+                )
+            }
+        }
+    }
+}
+
 /// exif 中的 gps 信息
 #[derive(Default, Clone, Debug)]
 pub struct GpsInfo {
@@ -16,17 +232,21 @@ pub struct GpsInfo {
     /// 经度
     pub longitude: Option<DMS>,
 
-    /// 海拔
+    /// 海拔参考【海平面以上或以下】
     pub altitude_ref: Option<SeaLevel>,
-    /// 海拔
-    pub altitude: Option<String>,
+    /// 海拔（米）【绝对值，符号由 altitude_ref 决定】
+    pub altitude: Option<f64>,
 
-    /// 速度单位【不支持速度】
-    /// - K: kilometers per hour
-    /// - M: miles per hour
-    /// - N: knots
-    // pub speed_ref: Option<char>,
-    // pub speed: Option<URational>,
+    /// 速度（km/h）【按 GPSSpeedRef 换算】
+    pub speed: Option<f64>,
+    /// 拍摄方向（度，0 ~ 360）
+    pub img_direction: Option<f64>,
+    /// 拍摄方向的参考方向
+    pub img_direction_ref: Option<NorthRef>,
+    /// 目的地方位（度，0 ~ 360）
+    pub dest_bearing: Option<f64>,
+    /// 目的地方位的参考方向
+    pub dest_bearing_ref: Option<NorthRef>,
 
     /// 遇到错误时继续
     continue_on_error: bool,
@@ -49,11 +269,8 @@ impl fmt::Display for GpsInfo {
             ans_str.push_str(x.to_string().as_str());
         }
         // 海拔
-        if let Some(x) = &self.altitude_ref {
-            ans_str.push_str(x.to_string().as_str());
-        }
-        if let Some(x) = &self.altitude {
-            ans_str.push_str(x.to_string().as_str());
+        if let Some(x) = self.signed_altitude() {
+            ans_str.push_str(format!("{}m", x).as_str());
         }
         write!(f, "{}", ans_str)
     }
@@ -71,8 +288,8 @@ impl GpsInfo {
         let longitude_ref: Option<Direction>;
         let longitude: Option<DMS>;
 
-        let altitude_ref: Option<SeaLevel> = Some(SeaLevel::AboveSeaLevel);
-        let altitude: Option<String>;
+        let mut altitude_ref: Option<SeaLevel>;
+        let altitude: Option<f64>;
 
         // 经度
         latitude_ref = if let Some(x) = tags.get(ExifToolDesc::GPS_LATITUDE_REF.exif_tool_desc) {
@@ -98,29 +315,91 @@ impl GpsInfo {
             None
         };
 
-        // 海拔
-        altitude = if let Some(x) = tags.get(ExifToolDesc::GPS_LONGITUDE.exif_tool_desc) {
-            let result = SeaLevel::parse_with_exiftool(x.as_str());
-            if continue_on_error {
-                if result.is_err() {
-                    None
-                } else {
-                    Some(result?)
+        // 海拔参考
+        altitude_ref = if let Some(x) = tags.get(ExifToolDesc::GPS_ALTITUDE_REF.exif_tool_desc) {
+            SeaLevel::from_str(x.as_str())
+        } else {
+            None
+        };
+        // 海拔【exiftool 可能把参考合并在数值后面，如 `6 m Below Sea Level`】
+        altitude = if let Some(x) = tags.get(ExifToolDesc::GPS_ALTITUDE.exif_tool_desc) {
+            match SeaLevel::parse_with_exiftool(x.as_str()) {
+                Ok((value, level)) => {
+                    // 单独的 GPSAltitudeRef 优先
+                    altitude_ref = altitude_ref.or(level);
+                    Some(value)
                 }
-            } else {
-                Some(result?)
+                Err(_) if continue_on_error => None,
+                Err(e) => return Err(e),
             }
         } else {
             None
         };
-        Ok(GpsInfo::new(
-            latitude_ref,
-            latitude,
-            longitude_ref,
-            longitude,
-            altitude_ref,
-            altitude,
-        ))
+        if altitude.is_some() && altitude_ref.is_none() {
+            altitude_ref = Some(SeaLevel::AboveSeaLevel);
+        }
+
+        // 速度【没有单位时按 km/h 处理】
+        let speed_unit = tags
+            .get(ExifToolDesc::GPS_SPEED_REF.exif_tool_desc)
+            .and_then(|x| SpeedUnit::from_str(x.as_str()))
+            .unwrap_or_default();
+        let speed = tags
+            .get_f64(&ExifToolDesc::GPS_SPEED)?
+            .filter(|x| x.is_finite() && *x >= 0.0)
+            .map(|x| speed_unit.to_kmh(x));
+
+        // 方向【缺少磁偏角数据，不换算为真北，保留参考方向】
+        let north_ref = |desc: &ExifToolDesc| {
+            tags.get(desc.exif_tool_desc)
+                .and_then(|x| NorthRef::from_str(x.as_str()))
+        };
+        let img_direction = tags
+            .get_f64(&ExifToolDesc::GPS_IMG_DIRECTION)?
+            .and_then(normalize_degrees);
+        let dest_bearing = tags
+            .get_f64(&ExifToolDesc::GPS_DEST_BEARING)?
+            .and_then(normalize_degrees);
+
+        Ok(GpsInfo {
+            speed,
+            img_direction,
+            img_direction_ref: img_direction.and(north_ref(&ExifToolDesc::GPS_IMG_DIRECTION_REF)),
+            dest_bearing,
+            dest_bearing_ref: dest_bearing.and(north_ref(&ExifToolDesc::GPS_DEST_BEARING_REF)),
+            ..GpsInfo::new(
+                latitude_ref,
+                latitude,
+                longitude_ref,
+                longitude,
+                altitude_ref,
+                altitude,
+            )
+        })
+    }
+
+    /// 转换为十进制坐标【缺少经纬度时返回 None】
+    pub fn to_coordinate(&self) -> Option<GpsCoordinate> {
+        let latitude = self.latitude.as_ref()?.to_decimal();
+        let longitude = self.longitude.as_ref()?.to_decimal();
+        let sign = |direction: &Option<Direction>| match direction {
+            Some(Direction::South) | Some(Direction::West) => -1.0,
+            _ => 1.0,
+        };
+        Some(GpsCoordinate {
+            latitude: latitude * sign(&self.latitude_ref),
+            longitude: longitude * sign(&self.longitude_ref),
+            altitude: self.signed_altitude(),
+        })
+    }
+
+    /// 海拔（米）【海平面以下为负数】
+    pub fn signed_altitude(&self) -> Option<f64> {
+        let altitude = self.altitude?;
+        Some(match self.altitude_ref {
+            Some(SeaLevel::BelowSeaLevel) => -altitude.abs(),
+            _ => altitude,
+        })
     }
 
     pub fn new(
@@ -129,7 +408,7 @@ impl GpsInfo {
         longitude_ref: Option<Direction>,
         longitude: Option<DMS>,
         altitude_ref: Option<SeaLevel>,
-        altitude: Option<String>,
+        altitude: Option<f64>,
     ) -> Self {
         Self {
             latitude_ref,
@@ -138,11 +417,94 @@ impl GpsInfo {
             longitude,
             altitude_ref,
             altitude,
+            speed: None,
+            img_direction: None,
+            img_direction_ref: None,
+            dest_bearing: None,
+            dest_bearing_ref: None,
             continue_on_error: true,
         }
     }
 }
 
+/// 将角度规范到 0 ~ 360 度【非有限数返回 None】
+fn normalize_degrees(degrees: f64) -> Option<f64> {
+    degrees.is_finite().then(|| degrees.rem_euclid(360.0))
+}
+
+/// GPS 速度单位（GPSSpeedRef）
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub enum SpeedUnit {
+    /// 千米每小时（K）
+    #[default]
+    KilometersPerHour,
+    /// 英里每小时（M）
+    MilesPerHour,
+    /// 节（N）
+    Knots,
+}
+
+impl SpeedUnit {
+    /// 解析 GPSSpeedRef【exiftool 的文字描述或原始字符】
+    pub fn from_str(s: &str) -> Option<SpeedUnit> {
+        let s = s.trim().to_lowercase();
+        match s.as_str() {
+            "km/h" | "k" => Some(SpeedUnit::KilometersPerHour),
+            "mph" | "m" => Some(SpeedUnit::MilesPerHour),
+            "knots" | "n" => Some(SpeedUnit::Knots),
+            _ => None,
+        }
+    }
+
+    /// 换算为 km/h
+    pub fn to_kmh(&self, value: f64) -> f64 {
+        match self {
+            SpeedUnit::KilometersPerHour => value,
+            SpeedUnit::MilesPerHour => value * 1.609_344,
+            SpeedUnit::Knots => value * 1.852,
+        }
+    }
+}
+
+/// 方向的参考方向（GPSImgDirectionRef、GPSDestBearingRef）
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum NorthRef {
+    /// 真北（T）
+    #[serde(rename = "T")]
+    True,
+    /// 磁北（M）
+    #[serde(rename = "M")]
+    Magnetic,
+}
+
+impl NorthRef {
+    /// 解析参考方向【exiftool 的文字描述或原始字符】
+    pub fn from_str(s: &str) -> Option<NorthRef> {
+        let s = s.trim().to_lowercase();
+        match s.as_str() {
+            "true north" | "t" => Some(NorthRef::True),
+            "magnetic north" | "m" => Some(NorthRef::Magnetic),
+            _ => None,
+        }
+    }
+
+    /// 数据库中存储的代码
+    pub fn code(&self) -> &'static str {
+        match self {
+            NorthRef::True => "T",
+            NorthRef::Magnetic => "M",
+        }
+    }
+
+    /// 展示名称
+    pub fn display(&self) -> &'static str {
+        match self {
+            NorthRef::True => i18n::text(msg::EXIF_NORTH_TRUE),
+            NorthRef::Magnetic => i18n::text(msg::EXIF_NORTH_MAGNETIC),
+        }
+    }
+}
+
 /// 方向
 #[derive(Default, Clone, Debug)]
 pub enum Direction {
@@ -178,6 +540,16 @@ impl Direction {
             _ => None, // 无匹配项
         }
     }
+
+    /// 格式化坐标时使用的消息 ID
+    fn label_id(&self) -> &'static str {
+        match self {
+            Direction::South => msg::GPS_LATITUDE_SOUTH,
+            Direction::North => msg::GPS_LATITUDE_NORTH,
+            Direction::West => msg::GPS_LONGITUDE_WEST,
+            Direction::East => msg::GPS_LONGITUDE_EAST,
+        }
+    }
 }
 
 /// 表示度、分、秒
@@ -204,6 +576,27 @@ impl DMS {
         }
     }
 
+    /// 转换为十进制度数
+    pub fn to_decimal(&self) -> f64 {
+        self.degrees as f64 + self.minutes as f64 / 60.0 + self.seconds / 3600.0
+    }
+
+    /// 从十进制度数转换【取绝对值，方向由 Direction 表示】
+    pub fn from_decimal(decimal: f64) -> DMS {
+        let decimal = decimal.abs();
+        let minutes = decimal.fract() * 60.0;
+        DMS::new(
+            decimal.trunc() as i32,
+            minutes.trunc() as i32,
+            minutes.fract() * 60.0,
+        )
+    }
+
+    /// 转换为度、十进制分
+    pub fn to_ddm(&self) -> DDM {
+        DDM::new(self.degrees, self.minutes as f64 + self.seconds / 60.0)
+    }
+
     /// 解析度分秒数据【只针对 exiftool 数据】
     pub fn parse_with_exiftool(dms: &str) -> Option<DMS> {
         // 匹配度数、分度、秒度和方向
@@ -232,13 +625,52 @@ impl DMS {
     }
 }
 
+/// 表示度、十进制分【导航设备常用，如 `39°54.252′`】
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DDM {
+    pub degrees: i32, // 度（int）
+    pub minutes: f64, // 分（float）
+}
+
+impl fmt::Display for DDM {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}°{}′", self.degrees, self.minutes)
+    }
+}
+
+impl DDM {
+    pub fn new(degrees: i32, minutes: f64) -> Self {
+        DDM { degrees, minutes }
+    }
+
+    /// 转换为十进制度数
+    pub fn to_decimal(&self) -> f64 {
+        self.degrees as f64 + self.minutes / 60.0
+    }
+
+    /// 从十进制度数转换【取绝对值，方向由 Direction 表示】
+    pub fn from_decimal(decimal: f64) -> DDM {
+        let decimal = decimal.abs();
+        DDM::new(decimal.trunc() as i32, decimal.fract() * 60.0)
+    }
+
+    /// 转换为度、分、秒
+    pub fn to_dms(&self) -> DMS {
+        DMS::new(
+            self.degrees,
+            self.minutes.trunc() as i32,
+            self.minutes.fract() * 60.0,
+        )
+    }
+}
+
 /// 海平面信息
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, PartialEq)]
 pub enum SeaLevel {
     /// 海平面以上
     #[default]
     AboveSeaLevel,
-    /// 海平面以上
+    /// 海平面以下
     BelowSeaLevel,
 }
 
@@ -253,17 +685,53 @@ impl fmt::Display for SeaLevel {
 }
 
 impl SeaLevel {
-    /// 解析海拔
-    pub fn parse_with_exiftool(coordinate: &str) -> Result<String> {
-        let string = coordinate
-            .replace(" m ", "m")
-            .replace("Above Sea Level", "");
-        Ok(string)
+    /// 解析 GPSAltitudeRef【文字描述或数值，0 为海平面以上，1 为海平面以下】
+    pub fn from_str(s: &str) -> Option<SeaLevel> {
+        let s = s.trim().to_lowercase();
+        match s.as_str() {
+            "above sea level" | "0" => Some(SeaLevel::AboveSeaLevel),
+            "below sea level" | "1" => Some(SeaLevel::BelowSeaLevel),
+            _ => None,
+        }
+    }
+
+    /// 解析海拔【如 `6 m`、`6 m Above Sea Level`、`6.1 m Below Sea Level`、`-6.1`】
+    ///
+    /// 返回海拔的绝对值（米）和数据中附带的参考，数值为负数时视为海平面以下
+    pub fn parse_with_exiftool(altitude: &str) -> Result<(f64, Option<SeaLevel>)> {
+        let mut parts = altitude.split_whitespace();
+        let number = parts.next().unwrap_or_default().trim_end_matches('m');
+        let value: f64 = number
+            .parse()
+            .map_err(|_| anyhow!("海拔 {} 格式错误", altitude))?;
+        let rest = parts
+            .skip_while(|x| *x == "m")
+            .collect::<Vec<_>>()
+            .join(" ");
+        let level = if value < 0.0 {
+            Some(SeaLevel::BelowSeaLevel)
+        } else {
+            SeaLevel::from_str(&rest)
+        };
+        Ok((value.abs(), level))
     }
 }
 
+#[cfg(test)]
 mod tests {
-    use crate::utils::exif_utils::gps_util::{SeaLevel, DMS};
+    use crate::i18n::Locale;
+    use crate::utils::exif_utils::gps_util::{
+        CoordinateFormat, GpsCoordinate, GpsInfo, SeaLevel, SpeedUnit, DDM, DMS,
+    };
+    use crate::utils::exif_utils::tag::Tags;
+
+    fn tags(entries: &[(&str, &str)]) -> Tags {
+        let entries = entries
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        Tags::new(false).with_entries(entries)
+    }
 
     #[test]
     fn test1() {
@@ -272,6 +740,226 @@ mod tests {
         println!("{:?}", string.unwrap().to_string())
     }
 
+    #[test]
+    fn test_to_decimal() {
+        let dms = DMS::parse_with_exiftool("22 deg 30' 36.00\" N").unwrap();
+        assert!((dms.to_decimal() - 22.51).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_altitude() {
+        let below = SeaLevel::parse_with_exiftool("6.1 m Below Sea Level").unwrap();
+        assert_eq!(below, (6.1, Some(SeaLevel::BelowSeaLevel)));
+        let above = SeaLevel::parse_with_exiftool("6 m Above Sea Level").unwrap();
+        assert_eq!(above, (6.0, Some(SeaLevel::AboveSeaLevel)));
+        assert_eq!(
+            SeaLevel::parse_with_exiftool("12.5 m").unwrap(),
+            (12.5, None)
+        );
+        assert_eq!(
+            SeaLevel::parse_with_exiftool("-3").unwrap(),
+            (3.0, Some(SeaLevel::BelowSeaLevel))
+        );
+        assert!(SeaLevel::parse_with_exiftool("unknown").is_err());
+        assert_eq!(SeaLevel::from_str("1"), Some(SeaLevel::BelowSeaLevel));
+    }
+
+    #[test]
+    fn test_altitude_hemispheres() {
+        // 死海（北纬、东经，海平面以下）
+        let dead_sea = tags(&[
+            ("GPSLatitudeRef", "North"),
+            ("GPSLatitude", "31 deg 30' 0.00\" N"),
+            ("GPSLongitudeRef", "East"),
+            ("GPSLongitude", "35 deg 30' 0.00\" E"),
+            ("GPSAltitudeRef", "Below Sea Level"),
+            ("GPSAltitude", "430 m"),
+        ]);
+        let coordinate = GpsInfo::parse(&dead_sea, false)
+            .unwrap()
+            .to_coordinate()
+            .unwrap();
+        assert_eq!(coordinate.altitude, Some(-430.0));
+        assert!(coordinate.latitude > 0.0 && coordinate.longitude > 0.0);
+
+        // 拉巴斯（南纬、西经，海平面以上，参考合并在海拔中）
+        let la_paz = tags(&[
+            ("GPSLatitudeRef", "South"),
+            ("GPSLatitude", "16 deg 30' 0.00\" S"),
+            ("GPSLongitudeRef", "West"),
+            ("GPSLongitude", "68 deg 9' 0.00\" W"),
+            ("GPSAltitude", "3640 m Above Sea Level"),
+        ]);
+        let coordinate = GpsInfo::parse(&la_paz, false)
+            .unwrap()
+            .to_coordinate()
+            .unwrap();
+        assert_eq!(coordinate.altitude, Some(3640.0));
+        assert!(coordinate.latitude < 0.0 && coordinate.longitude < 0.0);
+
+        // 单独的参考优先于数值中附带的参考
+        let conflict = tags(&[
+            ("GPSAltitudeRef", "1"),
+            ("GPSAltitude", "5 m Above Sea Level"),
+        ]);
+        let info = GpsInfo::parse(&conflict, false).unwrap();
+        assert_eq!(info.signed_altitude(), Some(-5.0));
+
+        // 没有参考时按海平面以上处理
+        let no_ref = tags(&[("GPSAltitude", "5 m")]);
+        let info = GpsInfo::parse(&no_ref, false).unwrap();
+        assert_eq!(info.altitude_ref, Some(SeaLevel::AboveSeaLevel));
+        assert_eq!(info.signed_altitude(), Some(5.0));
+
+        assert!(GpsInfo::parse(&tags(&[("GPSAltitude", "n/a")]), false).is_err());
+        let info = GpsInfo::parse(&tags(&[("GPSAltitude", "n/a")]), true).unwrap();
+        assert_eq!(info.signed_altitude(), None);
+    }
+
+    #[test]
+    fn test_speed_and_direction() {
+        let drone = tags(&[
+            ("GPSSpeedRef", "knots"),
+            ("GPSSpeed", "10"),
+            ("GPSImgDirectionRef", "True North"),
+            ("GPSImgDirection", "370.5"),
+            ("GPSDestBearingRef", "Magnetic North"),
+            ("GPSDestBearing", "-90"),
+        ]);
+        let info = GpsInfo::parse(&drone, false).unwrap();
+        assert!((info.speed.unwrap() - 18.52).abs() < 1e-9);
+        assert!((info.img_direction.unwrap() - 10.5).abs() < 1e-9);
+        assert_eq!(info.img_direction_ref, Some(NorthRef::True));
+        assert_eq!(info.dest_bearing, Some(270.0));
+        // 磁北方向保留参考方向，不按真北处理
+        assert_eq!(info.dest_bearing_ref, Some(NorthRef::Magnetic));
+        assert_eq!(NorthRef::from_str("M").map(|x| x.code()), Some("M"));
+
+        let dashcam = tags(&[("GPSSpeed", "42.5")]);
+        let info = GpsInfo::parse(&dashcam, false).unwrap();
+        assert_eq!(info.speed, Some(42.5));
+        assert_eq!(info.img_direction, None);
+        assert_eq!(SpeedUnit::from_str("M"), Some(SpeedUnit::MilesPerHour));
+        assert!((SpeedUnit::MilesPerHour.to_kmh(60.0) - 96.56064).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_distance() {
+        let a = GpsCoordinate {
+            latitude: 39.9042,
+            longitude: 116.4074,
+            altitude: Some(44.0),
+        };
+        let b = GpsCoordinate {
+            latitude: 31.2304,
+            longitude: 121.4737,
+            altitude: None,
+        };
+        // 北京到上海约 1067 公里
+        assert!((a.distance_meters(&b) / 1000.0 - 1067.0).abs() < 5.0);
+        let blurred = a.blurred(2);
+        assert_eq!((blurred.latitude, blurred.longitude), (39.9, 116.41));
+        assert_eq!(blurred.altitude, None);
+    }
+
+    #[test]
+    fn test_dms_ddm_conversion() {
+        let dms = DMS::from_decimal(-39.9042);
+        assert_eq!((dms.degrees, dms.minutes), (39, 54));
+        assert!((dms.seconds - 15.12).abs() < 1e-6);
+        let ddm = dms.to_ddm();
+        assert_eq!(ddm.degrees, 39);
+        assert!((ddm.minutes - 54.252).abs() < 1e-9);
+        assert!((ddm.to_decimal() - 39.9042).abs() < 1e-9);
+        assert!((ddm.to_dms().to_decimal() - 39.9042).abs() < 1e-9);
+        assert_eq!(DDM::from_decimal(116.5), DDM::new(116, 30.0));
+    }
+
+    #[test]
+    fn test_format_coordinate() {
+        let beijing = GpsCoordinate {
+            latitude: 39.9042,
+            longitude: 116.4074,
+            altitude: Some(44.0),
+        };
+        assert_eq!(
+            beijing.format(CoordinateFormat::Decimal, Locale::EnUs),
+            "39.904200° N, 116.407400° E"
+        );
+        assert_eq!(
+            beijing.format(CoordinateFormat::Dms, Locale::ZhCn),
+            "北纬 39°54′15.12″，东经 116°24′26.64″"
+        );
+        let la_paz = GpsCoordinate {
+            latitude: -16.5,
+            longitude: -68.15,
+            altitude: None,
+        };
+        assert_eq!(
+            la_paz.format(CoordinateFormat::Ddm, Locale::EnUs),
+            "16°30.0000′ S, 68°9.0000′ W"
+        );
+        // 取整后进位，不出现 60 秒
+        assert_eq!(
+            CoordinateFormat::Dms.format_degrees(10.999_999_9),
+            "11°0′0.00″"
+        );
+    }
+
+    #[test]
+    fn test_parse_coordinate() {
+        let coordinate = GpsCoordinate::parse("39.9042, 116.4074").unwrap();
+        assert_eq!(
+            (coordinate.latitude, coordinate.longitude),
+            (39.9042, 116.4074)
+        );
+        let coordinate = GpsCoordinate::parse("-16.5 -68.15").unwrap();
+        assert_eq!((coordinate.latitude, coordinate.longitude), (-16.5, -68.15));
+        let coordinate = GpsCoordinate::parse("16.5°S，68.15° W").unwrap();
+        assert_eq!((coordinate.latitude, coordinate.longitude), (-16.5, -68.15));
+
+        // 十进制格式化结果可以重新解析
+        for locale in [Locale::ZhCn, Locale::EnUs] {
+            let text = coordinate.format(CoordinateFormat::Decimal, locale);
+            assert_eq!(GpsCoordinate::parse(&text).unwrap(), coordinate, "{}", text);
+        }
+
+        assert!(GpsCoordinate::parse("39.9042").is_err());
+        assert!(GpsCoordinate::parse("91, 0").is_err());
+        assert!(GpsCoordinate::parse("116.4 E, 39.9 N").is_err());
+        assert!(GpsCoordinate::parse("-39.9 N, 116.4").is_err());
+        assert!(GpsCoordinate::parse("北纬 39.9 N, 116.4").is_err());
+        assert!(GpsCoordinate::parse("abc, 116.4").is_err());
+    }
+
+    #[test]
+    fn test_validate_coordinate() {
+        let coordinate = |latitude, longitude, altitude| GpsCoordinate {
+            latitude,
+            longitude,
+            altitude,
+        };
+        assert!(coordinate(-90.0, 180.0, Some(-430.0)).validate().is_ok());
+        assert!(coordinate(90.5, 0.0, None).validate().is_err());
+        assert!(coordinate(0.0, -180.5, None).validate().is_err());
+        assert!(coordinate(f64::NAN, 0.0, None).validate().is_err());
+        assert!(coordinate(0.0, 0.0, Some(f64::INFINITY))
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_legacy_coordinate() {
+        let coordinate = GpsCoordinate::parse_legacy("N39°54′15.2″E116°24′26.6″").unwrap();
+        assert!((coordinate.latitude - 39.904222).abs() < 1e-6);
+        assert!((coordinate.longitude - 116.407389).abs() < 1e-6);
+        assert_eq!(coordinate.altitude, None);
+        let coordinate = GpsCoordinate::parse_legacy("S33°52′4″W151°12′36″-12").unwrap();
+        assert!(coordinate.latitude < 0.0 && coordinate.longitude < 0.0);
+        assert!(GpsCoordinate::parse_legacy("N95°0′0″E0°0′0″").is_none());
+        assert!(GpsCoordinate::parse_legacy("{\"latitude\":1.0}").is_none());
+    }
+
     #[test]
     fn test2() {
         let str = "6 m Above Sea Level";
@@ -0,0 +1,893 @@
+use crate::utils::exif_utils::charset;
+use crate::utils::exif_utils::mpf::{self, MpImage};
+use crate::utils::exif_utils::tag::{self, ImgExif};
+use crate::utils::exif_utils::tiff;
+use anyhow::{anyhow, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// 图像开始
+const MARKER_SOI: u8 = 0xD8;
+/// 图像结束
+const MARKER_EOI: u8 = 0xD9;
+/// 扫描开始【之后是压缩数据，不再包含元数据段】
+const MARKER_SOS: u8 = 0xDA;
+const MARKER_APP1: u8 = 0xE1;
+const MARKER_APP2: u8 = 0xE2;
+const MARKER_APP13: u8 = 0xED;
+const MARKER_APP14: u8 = 0xEE;
+/// 注释
+const MARKER_COM: u8 = 0xFE;
+
+/// APP1 中 EXIF 数据的标识
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+/// APP1 中 XMP 数据的标识
+const XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+/// APP1 中扩展 XMP 数据的标识【XMP 超过一个段的大小时，剩余部分分段保存在之后的 APP1 中】
+const XMP_EXTENSION_HEADER: &[u8] = b"http://ns.adobe.com/xmp/extension/\0";
+/// 扩展 XMP 分段头的长度（GUID 32 字节、总长度 4 字节、偏移量 4 字节）
+const XMP_EXTENSION_CHUNK_HEADER: usize = 40;
+/// APP2 中 MPF 数据的标识
+const MPF_HEADER: &[u8] = b"MPF\0";
+/// APP13 中 Photoshop 图像资源块的标识
+const PHOTOSHOP_HEADER: &[u8] = b"Photoshop 3.0\0";
+/// 图像资源块签名
+const IRB_SIGNATURE: &[u8] = b"8BIM";
+/// IPTC-IIM 所在的图像资源 ID
+const IRB_IPTC_ID: u16 = 0x0404;
+/// IPTC-IIM 数据集标记
+const IIM_TAG_MARKER: u8 = 0x1C;
+/// IPTC 应用记录编号
+const IIM_APPLICATION_RECORD: u8 = 2;
+/// EXIF 方向标签
+const TAG_ORIENTATION: u16 = 0x0112;
+/// GPS 信息 IFD 指针标签
+const TAG_GPS_IFD: u16 = 0x8825;
+/// EXIF 信息 IFD 指针标签
+const TAG_EXIF_IFD: u16 = 0x8769;
+/// 图像说明
+const TAG_IMAGE_DESCRIPTION: u16 = 0x010E;
+/// 相机制造商
+const TAG_MAKE: u16 = 0x010F;
+/// 相机型号
+const TAG_MODEL: u16 = 0x0110;
+/// 软件
+const TAG_SOFTWARE: u16 = 0x0131;
+/// 作者
+const TAG_ARTIST: u16 = 0x013B;
+/// 版权
+const TAG_COPYRIGHT: u16 = 0x8298;
+/// 图像标题【Exif 3.0，位于 EXIF IFD】
+const TAG_IMAGE_TITLE: u16 = 0xA436;
+/// 摄影师【Exif 3.0，位于 EXIF IFD】
+const TAG_PHOTOGRAPHER: u16 = 0xA437;
+/// 用户注释【前 8 字节为字符编码标识】
+const TAG_USER_COMMENT: u16 = 0x9286;
+/// Windows 标题【UTF-16LE】
+const TAG_XP_TITLE: u16 = 0x9C9B;
+/// Windows 备注【UTF-16LE】
+const TAG_XP_COMMENT: u16 = 0x9C9C;
+/// Windows 作者【UTF-16LE】
+const TAG_XP_AUTHOR: u16 = 0x9C9D;
+/// Windows 标记（关键字）【UTF-16LE，以分号分隔】
+const TAG_XP_KEYWORDS: u16 = 0x9C9E;
+
+/// IPTC 数据集编号（应用记录）
+pub struct IptcDataSetId {}
+
+impl IptcDataSetId {
+    /// 关键字
+    pub const KEYWORDS: u8 = 25;
+    /// 作者
+    pub const BY_LINE: u8 = 80;
+    /// 城市
+    pub const CITY: u8 = 90;
+    /// 城市内的具体地点
+    pub const SUB_LOCATION: u8 = 92;
+    /// 省、州
+    pub const PROVINCE_STATE: u8 = 95;
+    /// 国家代码
+    pub const COUNTRY_CODE: u8 = 100;
+    /// 国家名称
+    pub const COUNTRY_NAME: u8 = 101;
+    /// 所有地点名称相关的数据集
+    pub const LOCATIONS: [u8; 5] = [
+        Self::CITY,
+        Self::SUB_LOCATION,
+        Self::PROVINCE_STATE,
+        Self::COUNTRY_CODE,
+        Self::COUNTRY_NAME,
+    ];
+    /// 标题
+    pub const HEADLINE: u8 = 105;
+    /// 版权声明
+    pub const COPYRIGHT_NOTICE: u8 = 116;
+    /// 说明
+    pub const CAPTION: u8 = 120;
+}
+
+/// IPTC-IIM 数据集
+#[derive(Debug, Clone, PartialEq)]
+pub struct IptcDataSet {
+    /// 记录编号
+    pub record: u8,
+    /// 数据集编号
+    pub dataset: u8,
+    /// 值
+    pub value: String,
+}
+
+/// JPEG 各个 APP 段、注释段中的元数据
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JpegMetadata {
+    /// EXIF 数据（TIFF 结构，不含 `Exif\0\0` 标识）
+    pub exif: Option<Vec<u8>>,
+    /// XMP 数据包
+    pub xmp: Option<String>,
+    /// 扩展 XMP 数据包【由多个 APP1 段拼接】
+    pub xmp_extension: Option<String>,
+    /// IPTC-IIM 数据集（只保留应用记录）
+    pub iptc: Vec<IptcDataSet>,
+    /// COM 注释
+    pub comments: Vec<String>,
+    /// MPF 记录的图像【双摄、深度图等照片，第一张为主图像】
+    pub mp_images: Vec<MpImage>,
+}
+
+impl JpegMetadata {
+    /// 指定数据集的所有值
+    pub fn iptc_values(&self, dataset: u8) -> Vec<&str> {
+        self.iptc
+            .iter()
+            .filter(|x| x.dataset == dataset)
+            .map(|x| x.value.as_str())
+            .collect()
+    }
+
+    /// 合并到统一的元数据对象【说明、作者、版权按下面的优先级覆盖，其他字段只补充没有读取到的】
+    ///
+    /// - XMP 中没有的字段使用扩展 XMP 中的值
+    /// - 说明：XMP dc:description > IPTC Caption-Abstract > COM 注释 > EXIF ImageTitle > EXIF ImageDescription > XPTitle > XPComment > EXIF UserComment
+    /// - 作者：XMP dc:creator > IPTC By-line > EXIF Photographer > EXIF Artist > XPAuthor
+    /// - 版权：XMP dc:rights > IPTC CopyrightNotice > EXIF Copyright
+    /// - 评分：XMP xmp:Rating
+    /// - 关键字：XMP dc:subject、IPTC Keywords 和 XPKeywords 合并去重
+    /// - 制造商、型号、软件：EXIF IFD0
+    pub fn merge_into(&self, exif: &mut ImgExif) {
+        let xmp = self.xmp.as_deref().map(XmpFields::parse).unwrap_or_default();
+        let xmp = match self.xmp_extension.as_deref() {
+            Some(extension) => xmp.or(XmpFields::parse(extension)),
+            None => xmp,
+        };
+
+        // 说明、作者、版权按上面的优先级选择，EXIF 中的值只在 XMP、IPTC、注释都没有时使用
+        let caption = xmp
+            .description
+            .clone()
+            .or_else(|| first_non_empty(self.iptc_values(IptcDataSetId::CAPTION)))
+            .or_else(|| first_non_empty(self.comments.iter().map(|x| x.as_str())));
+        if caption.is_some() {
+            exif.caption = caption;
+        }
+        let creators = if xmp.creators.is_empty() {
+            self.iptc_values(IptcDataSetId::BY_LINE)
+        } else {
+            xmp.creators.iter().map(|x| x.as_str()).collect()
+        };
+        if !creators.is_empty() {
+            exif.artist = Some(creators.join("; "));
+        }
+        let copyright = xmp
+            .rights
+            .clone()
+            .or_else(|| first_non_empty(self.iptc_values(IptcDataSetId::COPYRIGHT_NOTICE)));
+        if copyright.is_some() {
+            exif.copyright = copyright;
+        }
+        if exif.rating.is_none() {
+            exif.rating = xmp.rating;
+        }
+        let keywords = xmp
+            .subjects
+            .iter()
+            .map(|x| x.as_str())
+            .chain(self.iptc_values(IptcDataSetId::KEYWORDS));
+        for keyword in keywords {
+            let keyword = keyword.trim();
+            if !keyword.is_empty() && !exif.keywords.iter().any(|x| x == keyword) {
+                exif.keywords.push(keyword.to_string());
+            }
+        }
+        if let Some(tiff) = self.exif.as_deref() {
+            merge_exif_text(tiff, exif);
+        }
+    }
+}
+
+/// 用 EXIF 中的文字字段补充元数据【直接读取原始数据中的切片，只有需要补充的字段才分配】
+fn merge_exif_text(tiff: &[u8], exif: &mut ImgExif) {
+    let Ok(parser) = tiff::Parser::new(tiff) else {
+        return;
+    };
+    let Some(ifd0) = parser.ifd0() else {
+        return;
+    };
+    let exif_ifd = ifd0
+        .find(TAG_EXIF_IFD)
+        .and_then(|x| x.as_u32())
+        .and_then(|x| parser.ifd(x as usize));
+    let text = |ifd: Option<tiff::Ifd<'_>>, tag: u16| {
+        ifd.and_then(|x| x.find(tag))
+            .and_then(|x| x.as_text())
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+    };
+    // 相机写入的默认说明（如 `OLYMPUS DIGITAL CAMERA`）不作为照片说明
+    let make = text(Some(ifd0), TAG_MAKE);
+    let model = text(Some(ifd0), TAG_MODEL);
+    let is_placeholder = |x: &str| tag::is_camera_placeholder(x, make, model);
+    // Exif 3.0 的 Photographer、ImageTitle 优先于 Artist、ImageDescription
+    let fields = [
+        (&mut exif.make, text(Some(ifd0), TAG_MAKE)),
+        (&mut exif.model, text(Some(ifd0), TAG_MODEL)),
+        (&mut exif.software, text(Some(ifd0), TAG_SOFTWARE)),
+        (
+            &mut exif.artist,
+            text(exif_ifd, TAG_PHOTOGRAPHER).or_else(|| text(Some(ifd0), TAG_ARTIST)),
+        ),
+        (
+            &mut exif.caption,
+            text(exif_ifd, TAG_IMAGE_TITLE)
+                .filter(|x| !is_placeholder(x))
+                .or_else(|| text(Some(ifd0), TAG_IMAGE_DESCRIPTION))
+                .filter(|x| !is_placeholder(x)),
+        ),
+        (&mut exif.copyright, text(Some(ifd0), TAG_COPYRIGHT)),
+    ];
+    for (field, value) in fields {
+        if field.is_none() {
+            *field = value.map(str::to_string);
+        }
+    }
+
+    // Windows 资源管理器写入的 XP 标签
+    let xp = |tag: u16| {
+        ifd0.find(tag)
+            .and_then(|x| x.value_bytes())
+            .and_then(charset::decode_xp)
+    };
+    if exif.artist.is_none() {
+        exif.artist = xp(TAG_XP_AUTHOR);
+    }
+    if exif.caption.is_none() {
+        exif.caption = xp(TAG_XP_TITLE)
+            .or_else(|| xp(TAG_XP_COMMENT))
+            .or_else(|| {
+                exif_ifd
+                    .and_then(|x| x.find(TAG_USER_COMMENT))
+                    .and_then(|x| x.as_undefined())
+                    .and_then(|x| charset::decode_user_comment(x, parser.little_endian()))
+            })
+            .filter(|x| !x.trim().is_empty() && !is_placeholder(x));
+    }
+    for keyword in xp(TAG_XP_KEYWORDS).unwrap_or_default().split(';') {
+        let keyword = keyword.trim();
+        if !keyword.is_empty() && !exif.keywords.iter().any(|x| x == keyword) {
+            exif.keywords.push(keyword.to_string());
+        }
+    }
+}
+
+/// 读取 JPEG 文件中的元数据段【读到 SOS 为止，不读取压缩数据】
+pub fn read_jpeg_metadata<P: AsRef<Path>>(path: P) -> Result<JpegMetadata> {
+    let file = File::open(path)?;
+    parse_jpeg_metadata(BufReader::new(file))
+}
+
+/// 遍历所有标记段，提取 EXIF、XMP、IPTC、注释和 MPF 图像
+///
+/// 有多个 APP1 段时继续读取：使用第一个有效的 EXIF，拼接扩展 XMP 的所有分段
+pub fn parse_jpeg_metadata<R: Read>(reader: R) -> Result<JpegMetadata> {
+    let mut reader = CountingReader {
+        inner: reader,
+        position: 0,
+    };
+    let mut soi = [0u8; 2];
+    reader.read_exact(&mut soi)?;
+    if soi != [0xFF, MARKER_SOI] {
+        return Err(anyhow!("不是 JPEG 文件"));
+    }
+
+    let mut metadata = JpegMetadata::default();
+    let mut xmp_extension = XmpExtension::default();
+    loop {
+        let marker = read_marker(&mut reader)?;
+        match marker {
+            MARKER_EOI | MARKER_SOS => break,
+            // 没有长度的独立标记
+            0x01 | 0xD0..=0xD7 => continue,
+            _ => {}
+        }
+        let mut len = [0u8; 2];
+        reader.read_exact(&mut len)?;
+        let len = u16::from_be_bytes(len) as usize;
+        if len < 2 {
+            return Err(anyhow!("JPEG 段长度错误: {}", len));
+        }
+        let start = reader.position;
+        let mut data = vec![0u8; len - 2];
+        reader.read_exact(&mut data)?;
+
+        match marker {
+            MARKER_APP1 => {
+                if let Some(exif) = data.strip_prefix(EXIF_HEADER) {
+                    // 跳过无效的 EXIF 段【部分软件会写入空的 EXIF 段后再写入完整的】
+                    if metadata.exif.is_none() && tiff::Parser::new(exif).is_ok() {
+                        metadata.exif = Some(exif.to_vec());
+                    }
+                } else if let Some(xmp) = data.strip_prefix(XMP_HEADER) {
+                    metadata
+                        .xmp
+                        .get_or_insert_with(|| decode_text(xmp).trim_end_matches('\0').to_string());
+                } else if let Some(chunk) = data.strip_prefix(XMP_EXTENSION_HEADER) {
+                    xmp_extension.push(chunk);
+                }
+            }
+            MARKER_APP2 => {
+                if let Some(mpf) = data.strip_prefix(MPF_HEADER) {
+                    let header_offset = start + MPF_HEADER.len() as u64;
+                    match mpf::parse_mp_index(mpf, header_offset) {
+                        Ok(images) if metadata.mp_images.is_empty() => metadata.mp_images = images,
+                        Ok(_) => {}
+                        Err(e) => log::warn!("MPF 读取失败: {}", e),
+                    }
+                }
+            }
+            MARKER_APP13 => {
+                if let Some(irb) = data.strip_prefix(PHOTOSHOP_HEADER) {
+                    metadata.iptc.extend(parse_irb(irb));
+                }
+            }
+            MARKER_COM => {
+                let comment = decode_text(&data);
+                let comment = comment.trim_end_matches('\0').trim();
+                if !comment.is_empty() {
+                    metadata.comments.push(comment.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    metadata.xmp_extension = xmp_extension.assemble(metadata.xmp.as_deref());
+    Ok(metadata)
+}
+
+/// 记录已读取字节数的读取器【用于计算 MPF 图像在文件中的偏移量】
+struct CountingReader<R> {
+    inner: R,
+    position: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+/// 扩展 XMP 的分段（GUID -> (总长度、(偏移量、数据)）)
+#[derive(Default)]
+struct XmpExtension {
+    chunks: BTreeMap<String, (usize, Vec<(usize, Vec<u8>)>)>,
+}
+
+impl XmpExtension {
+    /// 添加一个分段【分段头不完整时忽略】
+    fn push(&mut self, chunk: &[u8]) {
+        let Some(header) = chunk.get(..XMP_EXTENSION_CHUNK_HEADER) else {
+            return;
+        };
+        let guid = String::from_utf8_lossy(&header[..32]).to_string();
+        let total = u32::from_be_bytes([header[32], header[33], header[34], header[35]]) as usize;
+        let offset = u32::from_be_bytes([header[36], header[37], header[38], header[39]]) as usize;
+        let entry = self.chunks.entry(guid).or_insert((total, Vec::new()));
+        entry
+            .1
+            .push((offset, chunk[XMP_EXTENSION_CHUNK_HEADER..].to_vec()));
+    }
+
+    /// 按偏移量拼接分段【优先使用主 XMP 中 xmpNote:HasExtendedXMP 指定的 GUID，分段不完整时返回 None】
+    /// - xmp 主 XMP 数据包
+    fn assemble(mut self, xmp: Option<&str>) -> Option<String> {
+        let guid = self
+            .chunks
+            .keys()
+            .find(|guid| xmp.is_some_and(|x| x.contains(guid.as_str())))
+            .or_else(|| self.chunks.keys().next())?
+            .clone();
+        let (total, mut chunks) = self.chunks.remove(&guid)?;
+        chunks.sort_by_key(|x| x.0);
+        let mut data = Vec::with_capacity(total);
+        for (offset, chunk) in chunks {
+            if offset != data.len() {
+                log::warn!("扩展 XMP 分段不连续: {}", guid);
+                return None;
+            }
+            data.extend(chunk);
+        }
+        if data.len() != total {
+            log::warn!("扩展 XMP 长度错误: {}", guid);
+            return None;
+        }
+        Some(decode_text(&data).trim_end_matches('\0').to_string())
+    }
+}
+
+/// 直接修改 JPEG 数据中 IFD0 的方向标签【不改变文件长度】
+///
+/// 返回是否修改成功，文件中没有 EXIF 或方向标签时返回 false
+pub fn set_jpeg_orientation(data: &mut [u8], value: u8) -> bool {
+    if !data.starts_with(&[0xFF, MARKER_SOI]) {
+        return false;
+    }
+    let mut pos = 2;
+    while pos + 4 <= data.len() && data[pos] == 0xFF {
+        let marker = data[pos + 1];
+        if matches!(marker, MARKER_EOI | MARKER_SOS) {
+            break;
+        }
+        if marker == 0xFF || marker == 0x01 || (0xD0..=0xD7).contains(&marker) {
+            pos += if marker == 0xFF { 1 } else { 2 };
+            continue;
+        }
+        let len = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        let start = pos + 4;
+        let end = (pos + 2 + len).min(data.len());
+        if len < 2 {
+            break;
+        }
+        if marker == MARKER_APP1 && data[start..end].starts_with(EXIF_HEADER) {
+            let tiff = &mut data[start + EXIF_HEADER.len()..end];
+            if set_tiff_orientation(tiff, value) {
+                return true;
+            }
+        }
+        pos = end;
+    }
+    false
+}
+
+/// 修改 TIFF 结构 IFD0 中的方向标签
+fn set_tiff_orientation(tiff: &mut [u8], value: u8) -> bool {
+    let Ok(parser) = tiff::Parser::new(tiff) else {
+        return false;
+    };
+    let little = parser.little_endian();
+    let Some(entry) = parser.ifd0().and_then(|ifd| ifd.find(TAG_ORIENTATION)) else {
+        return false;
+    };
+    if entry.field_type != tiff::TYPE_SHORT {
+        return false;
+    }
+    let entry = entry.offset;
+    // SHORT 类型的值直接保存在值字段的前两个字节中
+    let value = if little {
+        (value as u16).to_le_bytes()
+    } else {
+        (value as u16).to_be_bytes()
+    };
+    tiff[entry + 8..entry + 10].copy_from_slice(&value);
+    true
+}
+
+/// EXIF 数据（TIFF 结构）中是否包含 GPS 信息
+pub fn exif_has_gps(tiff: &[u8]) -> bool {
+    tiff::Parser::new(tiff)
+        .ok()
+        .and_then(|parser| parser.ifd0())
+        .is_some_and(|ifd| ifd.find(TAG_GPS_IFD).is_some())
+}
+
+/// 读取保存元数据的段（标记、内容）【按文件中的顺序】
+///
+/// 包括 APP1 - APP15 和注释段；APP0（JFIF）和 APP14（Adobe）记录的是编码参数，不算元数据
+pub fn metadata_segments(data: &[u8]) -> Result<Vec<(u8, Vec<u8>)>> {
+    let mut reader = data;
+    let mut soi = [0u8; 2];
+    reader.read_exact(&mut soi)?;
+    if soi != [0xFF, MARKER_SOI] {
+        return Err(anyhow!("不是 JPEG 文件"));
+    }
+    let mut segments = Vec::new();
+    loop {
+        let marker = read_marker(&mut reader)?;
+        match marker {
+            MARKER_EOI | MARKER_SOS => break,
+            0x01 | 0xD0..=0xD7 => continue,
+            _ => {}
+        }
+        let mut len = [0u8; 2];
+        reader.read_exact(&mut len)?;
+        let len = u16::from_be_bytes(len) as usize;
+        if len < 2 {
+            return Err(anyhow!("JPEG 段长度错误: {}", len));
+        }
+        let mut payload = vec![0u8; len - 2];
+        reader.read_exact(&mut payload)?;
+        if matches!(marker, MARKER_APP1..=0xEF | MARKER_COM) && marker != MARKER_APP14 {
+            segments.push((marker, payload));
+        }
+    }
+    Ok(segments)
+}
+
+/// 读取下一个标记【跳过填充的 0xFF】
+fn read_marker<R: Read>(reader: &mut R) -> Result<u8> {
+    let mut byte = [0u8; 1];
+    reader.read_exact(&mut byte)?;
+    if byte[0] != 0xFF {
+        return Err(anyhow!("JPEG 标记错误: {:#04x}", byte[0]));
+    }
+    while byte[0] == 0xFF {
+        reader.read_exact(&mut byte)?;
+    }
+    Ok(byte[0])
+}
+
+/// 解析 Photoshop 图像资源块，返回其中的 IPTC-IIM 数据集
+fn parse_irb(data: &[u8]) -> Vec<IptcDataSet> {
+    let mut result = Vec::new();
+    let mut pos = 0;
+    while pos + 6 < data.len() && &data[pos..pos + 4] == IRB_SIGNATURE {
+        let id = u16::from_be_bytes([data[pos + 4], data[pos + 5]]);
+        pos += 6;
+        // 名称为 Pascal 字符串，长度字节和内容合计补齐到偶数
+        let name_len = data[pos] as usize + 1;
+        pos += name_len + (name_len & 1);
+        let Some(size) = data.get(pos..pos + 4) else {
+            break;
+        };
+        let size = u32::from_be_bytes([size[0], size[1], size[2], size[3]]) as usize;
+        pos += 4;
+        let end = (pos + size).min(data.len());
+        if id == IRB_IPTC_ID {
+            if let Some(block) = data.get(pos..end) {
+                result.extend(parse_iim(block));
+            }
+        }
+        pos += size + (size & 1);
+    }
+    result
+}
+
+/// 解析 IPTC-IIM 数据集
+fn parse_iim(data: &[u8]) -> Vec<IptcDataSet> {
+    let mut result = Vec::new();
+    let mut pos = 0;
+    while pos + 5 <= data.len() && data[pos] == IIM_TAG_MARKER {
+        let record = data[pos + 1];
+        let dataset = data[pos + 2];
+        let mut size = u16::from_be_bytes([data[pos + 3], data[pos + 4]]) as usize;
+        pos += 5;
+        // 扩展长度：低 15 位为长度字段的字节数
+        if size & 0x8000 != 0 {
+            let count = size & 0x7FFF;
+            let Some(bytes) = data.get(pos..pos + count) else {
+                break;
+            };
+            size = bytes.iter().fold(0usize, |acc, x| (acc << 8) | *x as usize);
+            pos += count;
+        }
+        let Some(value) = data.get(pos..pos + size) else {
+            break;
+        };
+        if record == IIM_APPLICATION_RECORD {
+            result.push(IptcDataSet {
+                record,
+                dataset,
+                value: decode_text(value).trim_end_matches('\0').to_string(),
+            });
+        }
+        pos += size;
+    }
+    result
+}
+
+/// 文本解码【优先 UTF-8，失败时按 Latin-1 处理】
+fn decode_text(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(x) => x.to_string(),
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+fn first_non_empty<'a, I: IntoIterator<Item = &'a str>>(values: I) -> Option<String> {
+    values
+        .into_iter()
+        .map(str::trim)
+        .find(|x| !x.is_empty())
+        .map(str::to_string)
+}
+
+/// XMP 中常用的字段
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XmpFields {
+    /// dc:description【多语言时取第一项】
+    pub description: Option<String>,
+    /// dc:creator
+    pub creators: Vec<String>,
+    /// dc:subject
+    pub subjects: Vec<String>,
+    /// dc:rights【多语言时取第一项】
+    pub rights: Option<String>,
+    /// xmp:Rating
+    pub rating: Option<u32>,
+}
+
+impl XmpFields {
+    /// 解析 XMP 数据包【按常用前缀 dc、xmp 匹配，解析失败时返回已读取的部分】
+    pub fn parse(xmp: &str) -> XmpFields {
+        let mut fields = XmpFields::default();
+        let mut reader = Reader::from_str(xmp);
+        reader.config_mut().trim_text(true);
+        // 当前所在的属性
+        let mut current: Option<Vec<u8>> = None;
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => {
+                    fields.read_attributes(&e);
+                    let name = e.name().as_ref().to_vec();
+                    if XmpFields::is_property(&name) {
+                        current = Some(name);
+                    }
+                }
+                Ok(Event::Empty(e)) => fields.read_attributes(&e),
+                Ok(Event::Text(t)) => {
+                    if let (Some(name), Ok(text)) = (current.as_deref(), t.unescape()) {
+                        fields.set(name, text.trim());
+                    }
+                }
+                Ok(Event::End(e)) => {
+                    if current.as_deref() == Some(e.name().as_ref()) {
+                        current = None;
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => {
+                    log::warn!("XMP 解析失败: {}", e);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        fields
+    }
+
+    /// 用另一个数据包补充没有的字段【如扩展 XMP】
+    fn or(mut self, other: XmpFields) -> XmpFields {
+        if self.description.is_none() {
+            self.description = other.description;
+        }
+        if self.creators.is_empty() {
+            self.creators = other.creators;
+        }
+        if self.subjects.is_empty() {
+            self.subjects = other.subjects;
+        }
+        if self.rights.is_none() {
+            self.rights = other.rights;
+        }
+        if self.rating.is_none() {
+            self.rating = other.rating;
+        }
+        self
+    }
+
+    fn is_property(name: &[u8]) -> bool {
+        matches!(
+            name,
+            b"dc:description" | b"dc:creator" | b"dc:subject" | b"dc:rights" | b"xmp:Rating"
+        )
+    }
+
+    /// 简单属性可能以 XML 属性的形式出现，如 `<rdf:Description xmp:Rating="5">`
+    fn read_attributes(&mut self, e: &BytesStart) {
+        for attr in e.attributes().flatten() {
+            if XmpFields::is_property(attr.key.as_ref()) {
+                if let Ok(value) = attr.unescape_value() {
+                    self.set(attr.key.as_ref(), value.trim());
+                }
+            }
+        }
+    }
+
+    fn set(&mut self, name: &[u8], value: &str) {
+        if value.is_empty() {
+            return;
+        }
+        match name {
+            b"dc:description" => {
+                self.description.get_or_insert_with(|| value.to_string());
+            }
+            b"dc:creator" => self.creators.push(value.to_string()),
+            b"dc:subject" => self.subjects.push(value.to_string()),
+            b"dc:rights" => {
+                self.rights.get_or_insert_with(|| value.to_string());
+            }
+            b"xmp:Rating" => self.rating = value.parse().ok(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造 JPEG 段
+    fn segment(marker: u8, payload: &[u8]) -> Vec<u8> {
+        let mut data = vec![0xFF, marker];
+        data.extend(((payload.len() + 2) as u16).to_be_bytes());
+        data.extend(payload);
+        data
+    }
+
+    fn iim(dataset: u8, value: &str) -> Vec<u8> {
+        let mut data = vec![IIM_TAG_MARKER, IIM_APPLICATION_RECORD, dataset];
+        data.extend((value.len() as u16).to_be_bytes());
+        data.extend(value.as_bytes());
+        data
+    }
+
+    #[test]
+    fn test_metadata_segments() {
+        let mut data = vec![0xFF, MARKER_SOI];
+        data.extend(segment(0xE0, b"JFIF\0\x01\x01"));
+        data.extend(segment(MARKER_APP1, b"Exif\0\0MM"));
+        data.extend(segment(0xE2, b"ICC_PROFILE\0"));
+        data.extend(segment(MARKER_APP14, b"Adobe"));
+        data.extend(segment(MARKER_COM, b"hello"));
+        data.extend(segment(MARKER_SOS, b"\x01"));
+        assert_eq!(
+            metadata_segments(&data).unwrap(),
+            vec![
+                (MARKER_APP1, b"Exif\0\0MM".to_vec()),
+                (0xE2, b"ICC_PROFILE\0".to_vec()),
+                (MARKER_COM, b"hello".to_vec()),
+            ]
+        );
+        assert!(metadata_segments(b"PNG").is_err());
+    }
+
+    #[test]
+    fn test_parse_jpeg_metadata() {
+        let xmp = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+            <rdf:Description xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:xmp="http://ns.adobe.com/xap/1.0/" xmp:Rating="4">
+            <dc:description><rdf:Alt><rdf:li xml:lang="x-default">Harbour &amp; boats</rdf:li></rdf:Alt></dc:description>
+            <dc:subject><rdf:Bag><rdf:li>sea</rdf:li><rdf:li>boat</rdf:li></rdf:Bag></dc:subject>
+            </rdf:Description></rdf:RDF></x:xmpmeta>"#;
+
+        let mut iptc = [
+            iim(IptcDataSetId::KEYWORDS, "boat"),
+            iim(IptcDataSetId::KEYWORDS, "夏天"),
+            iim(IptcDataSetId::BY_LINE, "Du"),
+            iim(IptcDataSetId::COPYRIGHT_NOTICE, "© Du"),
+        ]
+        .concat();
+        // 资源名称为空（长度字节 + 1 字节补齐），数据长度为奇数时补齐
+        let mut irb = b"8BIM".to_vec();
+        irb.extend(IRB_IPTC_ID.to_be_bytes());
+        irb.extend([0, 0]);
+        irb.extend((iptc.len() as u32).to_be_bytes());
+        let odd = iptc.len() & 1 == 1;
+        irb.append(&mut iptc);
+        if odd {
+            irb.push(0);
+        }
+
+        let mut jpeg = vec![0xFF, MARKER_SOI];
+        jpeg.extend(segment(MARKER_APP1, &[EXIF_HEADER, b"II*\0"].concat()));
+        jpeg.extend(segment(MARKER_APP1, &[XMP_HEADER, xmp.as_bytes()].concat()));
+        jpeg.extend(segment(MARKER_APP13, &[PHOTOSHOP_HEADER, &irb].concat()));
+        jpeg.extend(segment(MARKER_COM, b"scanned\0"));
+        jpeg.extend(segment(MARKER_SOS, &[0; 10]));
+        jpeg.extend([0x12, 0x34, 0xFF, MARKER_EOI]);
+
+        let metadata = parse_jpeg_metadata(jpeg.as_slice()).unwrap();
+        assert_eq!(metadata.exif.as_deref(), Some(&b"II*\0"[..]));
+        assert_eq!(metadata.iptc_values(IptcDataSetId::KEYWORDS), vec!["boat", "夏天"]);
+        assert_eq!(metadata.comments, vec!["scanned".to_string()]);
+
+        let mut exif = ImgExif::default();
+        metadata.merge_into(&mut exif);
+        assert_eq!(exif.caption.as_deref(), Some("Harbour & boats"));
+        assert_eq!(exif.artist.as_deref(), Some("Du"));
+        assert_eq!(exif.copyright.as_deref(), Some("© Du"));
+        assert_eq!(exif.rating, Some(4));
+        assert_eq!(exif.keywords, vec!["sea", "boat", "夏天"]);
+
+        assert!(parse_jpeg_metadata(&b"\x89PNG"[..]).is_err());
+    }
+
+    #[test]
+    fn test_parse_jpeg_multiple_segments() {
+        let guid = "0123456789ABCDEF0123456789ABCDEF";
+        let xmp = format!(r#"<x:xmpmeta xmpNote:HasExtendedXMP="{}"/>"#, guid);
+        let extension = r#"<rdf:Description xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:description>depth</dc:description></rdf:Description>"#;
+        let chunk = |offset: usize, data: &str| {
+            let mut chunk = XMP_EXTENSION_HEADER.to_vec();
+            chunk.extend(guid.as_bytes());
+            chunk.extend((extension.len() as u32).to_be_bytes());
+            chunk.extend((offset as u32).to_be_bytes());
+            chunk.extend(data.as_bytes());
+            chunk
+        };
+        // 大端 MP 索引 IFD，有版本、图像数量和一个 MP 条目（在 50）
+        let mut mpf = MPF_HEADER.to_vec();
+        mpf.extend(b"MM\0*\0\0\0\x08");
+        mpf.extend(3u16.to_be_bytes());
+        mpf.extend([0xB0, 0x00, 0, 7, 0, 0, 0, 4, b'0', b'1', b'0', b'0']);
+        mpf.extend([0xB0, 0x01, 0, 4, 0, 0, 0, 1, 0, 0, 0, 1]);
+        mpf.extend([0xB0, 0x02, 0, 7, 0, 0, 0, 16, 0, 0, 0, 50]);
+        mpf.extend(0u32.to_be_bytes());
+        mpf.extend(0x0000_0000u32.to_be_bytes());
+        mpf.extend(100u32.to_be_bytes());
+        mpf.extend(200u32.to_be_bytes());
+        mpf.extend([0; 4]);
+
+        let mut jpeg = vec![0xFF, MARKER_SOI];
+        // 第一个 EXIF 段无效，使用第二个
+        jpeg.extend(segment(MARKER_APP1, EXIF_HEADER));
+        jpeg.extend(segment(MARKER_APP1, &[EXIF_HEADER, b"II*\0"].concat()));
+        jpeg.extend(segment(MARKER_APP1, &[XMP_HEADER, xmp.as_bytes()].concat()));
+        // 扩展 XMP 分段顺序颠倒
+        jpeg.extend(segment(MARKER_APP1, &chunk(20, &extension[20..])));
+        jpeg.extend(segment(MARKER_APP1, &chunk(0, &extension[..20])));
+        let header_offset = jpeg.len() + 4 + MPF_HEADER.len();
+        jpeg.extend(segment(MARKER_APP2, &mpf));
+        jpeg.extend(segment(MARKER_SOS, &[0; 4]));
+
+        let metadata = parse_jpeg_metadata(jpeg.as_slice()).unwrap();
+        assert_eq!(metadata.exif.as_deref(), Some(&b"II*\0"[..]));
+        assert_eq!(metadata.xmp_extension.as_deref(), Some(extension));
+        assert_eq!(metadata.mp_images.len(), 1);
+        assert_eq!(metadata.mp_images[0].offset, (header_offset + 200) as u64);
+        assert_eq!(metadata.mp_images[0].kind, mpf::MpImageKind::Undefined);
+
+        let mut exif = ImgExif::default();
+        metadata.merge_into(&mut exif);
+        assert_eq!(exif.caption.as_deref(), Some("depth"));
+
+        // 缺少分段时不使用扩展 XMP
+        let mut extension_only = XmpExtension::default();
+        extension_only.push(&chunk(0, &extension[..20])[XMP_EXTENSION_HEADER.len()..]);
+        assert_eq!(extension_only.assemble(None), None);
+    }
+
+    #[test]
+    fn test_set_jpeg_orientation() {
+        // 大端 TIFF，IFD0 只有方向标签，值为 6
+        let mut tiff = b"MM\0*\0\0\0\x08\0\x01".to_vec();
+        tiff.extend([0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, 6, 0, 0]);
+        tiff.extend([0, 0, 0, 0]);
+        let mut jpeg = vec![0xFF, MARKER_SOI];
+        jpeg.extend(segment(MARKER_COM, b"x"));
+        jpeg.extend(segment(MARKER_APP1, &[EXIF_HEADER, &tiff].concat()));
+        jpeg.extend(segment(MARKER_SOS, &[0; 4]));
+
+        assert!(set_jpeg_orientation(&mut jpeg, 1));
+        let metadata = parse_jpeg_metadata(jpeg.as_slice()).unwrap();
+        let exif = metadata.exif.unwrap();
+        assert_eq!(&exif[18..20], &[0, 1]);
+        assert!(!exif_has_gps(&exif));
+
+        let mut no_exif = vec![0xFF, MARKER_SOI];
+        no_exif.extend(segment(MARKER_SOS, &[0; 4]));
+        assert!(!set_jpeg_orientation(&mut no_exif, 1));
+    }
+}
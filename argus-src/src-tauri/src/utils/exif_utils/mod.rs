@@ -1,4 +1,15 @@
 pub mod exif_util;
 pub mod tag;
 pub mod value;
-mod gps_util;
+pub mod gps_util;
+pub mod jpeg_segment;
+pub mod orientation_util;
+pub mod tiff;
+pub mod charset;
+pub mod format;
+pub mod exif_enum;
+pub mod camera_db;
+pub mod exiftool;
+pub mod exiftool_pool;
+pub mod exif_reader;
+pub mod mpf;
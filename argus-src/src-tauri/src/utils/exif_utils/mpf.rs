@@ -0,0 +1,190 @@
+//! 多图像格式（MPF，CIPA DC-007）
+//!
+//! 双摄、人像模式、深度图等照片在主图像之后追加其他 JPEG，由 APP2 段中的 MP 索引 IFD 记录每张图像的
+//! 类型、大小和位置；偏移量相对于 MP 头（`MPF\0` 之后的 TIFF 头），第一张图像（主图像）的偏移量为 0
+
+use crate::utils::exif_utils::tiff::Parser;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// MP 格式版本
+const TAG_MPF_VERSION: u16 = 0xB000;
+/// 图像数量
+const TAG_NUMBER_OF_IMAGES: u16 = 0xB001;
+/// MP 条目【每张图像 16 字节】
+const TAG_MP_ENTRY: u16 = 0xB002;
+/// MP 条目大小
+const MP_ENTRY_SIZE: usize = 16;
+/// 图像类型在属性中的位【低 24 位】
+const TYPE_CODE_MASK: u32 = 0x00FF_FFFF;
+/// 代表图像标记
+const REPRESENTATIVE_FLAG: u32 = 1 << 29;
+
+/// MP 图像类型
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MpImageKind {
+    /// 基线主图像
+    Primary,
+    /// 大尺寸缩略图（VGA、全高清）
+    LargeThumbnail,
+    /// 多帧全景
+    Panorama,
+    /// 视差图像【双摄立体照片】
+    Disparity,
+    /// 多角度图像
+    MultiAngle,
+    /// 未定义【深度图、增益图等厂商自定义的图像】
+    Undefined,
+    /// 无法识别的类型代码
+    Other(u32),
+}
+
+impl MpImageKind {
+    /// 从 MP 类型代码转换
+    pub fn from_code(code: u32) -> MpImageKind {
+        match code {
+            0x030000 => MpImageKind::Primary,
+            0x010001 | 0x010002 => MpImageKind::LargeThumbnail,
+            0x020001 => MpImageKind::Panorama,
+            0x020002 => MpImageKind::Disparity,
+            0x020003 => MpImageKind::MultiAngle,
+            0x000000 => MpImageKind::Undefined,
+            x => MpImageKind::Other(x),
+        }
+    }
+}
+
+/// MPF 记录的图像
+#[derive(Debug, Clone, PartialEq)]
+pub struct MpImage {
+    pub kind: MpImageKind,
+    /// 是否为代表图像【一般为主图像】
+    pub representative: bool,
+    /// 在文件中的偏移量（字节）
+    pub offset: u64,
+    /// 大小（字节）
+    pub size: u32,
+}
+
+/// 解析 MP 索引 IFD
+/// - data APP2 段中 `MPF\0` 之后的数据
+/// - header_offset MP 头在文件中的偏移量
+pub fn parse_mp_index(data: &[u8], header_offset: u64) -> Result<Vec<MpImage>> {
+    let parser = Parser::new(data)?;
+    let ifd = parser
+        .ifd0()
+        .ok_or_else(|| anyhow!("MP 索引 IFD 的偏移量无效"))?;
+    if ifd.find(TAG_MPF_VERSION).is_none() {
+        return Err(anyhow!("MP 索引 IFD 中没有版本"));
+    }
+    let count = ifd
+        .find(TAG_NUMBER_OF_IMAGES)
+        .and_then(|x| x.as_u32())
+        .ok_or_else(|| anyhow!("MP 索引 IFD 中没有图像数量"))? as usize;
+    let entries = ifd
+        .find(TAG_MP_ENTRY)
+        .and_then(|x| x.as_undefined())
+        .ok_or_else(|| anyhow!("MP 索引 IFD 中没有 MP 条目"))?;
+    if entries.len() < count * MP_ENTRY_SIZE {
+        return Err(anyhow!("MP 条目长度错误: {}", entries.len()));
+    }
+    let read_u32 = |b: &[u8]| {
+        let b = [b[0], b[1], b[2], b[3]];
+        if parser.little_endian() {
+            u32::from_le_bytes(b)
+        } else {
+            u32::from_be_bytes(b)
+        }
+    };
+    Ok(entries
+        .chunks_exact(MP_ENTRY_SIZE)
+        .take(count)
+        .map(|entry| {
+            let attribute = read_u32(&entry[0..4]);
+            let offset = read_u32(&entry[8..12]);
+            MpImage {
+                kind: MpImageKind::from_code(attribute & TYPE_CODE_MASK),
+                representative: attribute & REPRESENTATIVE_FLAG != 0,
+                // 主图像从文件开头开始
+                offset: if offset == 0 {
+                    0
+                } else {
+                    header_offset + offset as u64
+                },
+                size: read_u32(&entry[4..8]),
+            }
+        })
+        .collect())
+}
+
+/// 读取文件中的 MP 图像【校验 JPEG 文件头】
+/// - path 图像路径
+/// - image 图像位置
+pub fn read_mp_image(path: &Path, image: &MpImage) -> Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(image.offset))?;
+    let mut data = Vec::with_capacity(image.size as usize);
+    file.take(image.size as u64).read_to_end(&mut data)?;
+    if data.len() != image.size as usize {
+        return Err(anyhow!("MP 图像超出文件范围"));
+    }
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return Err(anyhow!("MP 图像不是 JPEG"));
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mp_index() {
+        // 大端，MP 索引 IFD 有版本、图像数量和两个 MP 条目（在 50）
+        let mut data = b"MM\0*\0\0\0\x08".to_vec();
+        data.extend(3u16.to_be_bytes());
+        data.extend([0xB0, 0x00, 0, 7, 0, 0, 0, 4]);
+        data.extend(b"0100");
+        data.extend([0xB0, 0x01, 0, 4, 0, 0, 0, 1, 0, 0, 0, 2]);
+        data.extend([0xB0, 0x02, 0, 7, 0, 0, 0, 32, 0, 0, 0, 50]);
+        data.extend(0u32.to_be_bytes());
+        // 代表图像 + 基线主图像
+        data.extend(0x2003_0000u32.to_be_bytes());
+        data.extend(5000u32.to_be_bytes());
+        data.extend(0u32.to_be_bytes());
+        data.extend([0; 4]);
+        // 视差图像
+        data.extend(0x0002_0002u32.to_be_bytes());
+        data.extend(3000u32.to_be_bytes());
+        data.extend(4900u32.to_be_bytes());
+        data.extend([0; 4]);
+
+        let images = parse_mp_index(&data, 100).unwrap();
+        assert_eq!(
+            images,
+            vec![
+                MpImage {
+                    kind: MpImageKind::Primary,
+                    representative: true,
+                    offset: 0,
+                    size: 5000,
+                },
+                MpImage {
+                    kind: MpImageKind::Disparity,
+                    representative: false,
+                    offset: 5000,
+                    size: 3000,
+                },
+            ]
+        );
+
+        // 图像数量大于条目数量
+        data[33] = 3;
+        assert!(parse_mp_index(&data, 100).is_err());
+        assert!(parse_mp_index(b"MPF", 0).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+//! EXIF 方向（Orientation）计算
+//!
+//! 每个方向值对应一个把存储的像素变换为显示画面的 2x2 矩阵（坐标 y 轴向下），
+//! 在显示画面上再做旋转、翻转，就是两个矩阵相乘
+
+/// 方向值及其变换矩阵
+const ORIENTATION_MATRICES: [(u8, [[i8; 2]; 2]); 8] = [
+    (1, [[1, 0], [0, 1]]),
+    // 水平翻转
+    (2, [[-1, 0], [0, 1]]),
+    // 旋转 180°
+    (3, [[-1, 0], [0, -1]]),
+    // 垂直翻转
+    (4, [[1, 0], [0, -1]]),
+    // 沿左上-右下对角线翻转（transpose）
+    (5, [[0, 1], [1, 0]]),
+    // 顺时针旋转 90°
+    (6, [[0, -1], [1, 0]]),
+    // 沿右上-左下对角线翻转（transverse）
+    (7, [[0, -1], [-1, 0]]),
+    // 顺时针旋转 270°
+    (8, [[0, 1], [-1, 0]]),
+];
+
+/// 对显示画面的变换
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageTransform {
+    /// 顺时针旋转 90°
+    Rotate90,
+    Rotate180,
+    /// 顺时针旋转 270°（逆时针 90°）
+    Rotate270,
+    /// 水平翻转（左右镜像）
+    FlipHorizontal,
+    /// 垂直翻转（上下镜像）
+    FlipVertical,
+}
+
+impl ImageTransform {
+    /// 按角度旋转【顺时针，只支持 90 的倍数，可以为负数】
+    pub fn from_degrees(degrees: i32) -> Option<ImageTransform> {
+        if degrees % 90 != 0 {
+            return None;
+        }
+        match degrees.rem_euclid(360) {
+            90 => Some(ImageTransform::Rotate90),
+            180 => Some(ImageTransform::Rotate180),
+            270 => Some(ImageTransform::Rotate270),
+            _ => None,
+        }
+    }
+
+    /// 按翻转轴【horizontal、vertical】
+    pub fn from_axis(axis: &str) -> Option<ImageTransform> {
+        match axis.to_lowercase().as_str() {
+            "horizontal" | "h" => Some(ImageTransform::FlipHorizontal),
+            "vertical" | "v" => Some(ImageTransform::FlipVertical),
+            _ => None,
+        }
+    }
+
+    /// 对应的方向值
+    fn orientation(&self) -> u8 {
+        match self {
+            ImageTransform::Rotate90 => 6,
+            ImageTransform::Rotate180 => 3,
+            ImageTransform::Rotate270 => 8,
+            ImageTransform::FlipHorizontal => 2,
+            ImageTransform::FlipVertical => 4,
+        }
+    }
+}
+
+/// 在当前方向的基础上做变换，返回新的方向值
+/// - current 当前方向值【无效值视为 1】
+/// - transform 对显示画面的变换
+pub fn compose(current: u8, transform: ImageTransform) -> u8 {
+    let a = matrix(transform.orientation());
+    let b = matrix(current);
+    let mut result = [[0i8; 2]; 2];
+    for (i, row) in result.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = a[i][0] * b[0][j] + a[i][1] * b[1][j];
+        }
+    }
+    ORIENTATION_MATRICES
+        .iter()
+        .find(|(_, m)| *m == result)
+        .map(|(x, _)| *x)
+        .unwrap_or(1)
+}
+
+/// 把像素变换为指定方向的显示画面所需的 jpegtran 参数【方向为 1 时不需要变换】
+pub fn jpegtran_args(orientation: u8) -> Option<&'static [&'static str]> {
+    match orientation {
+        2 => Some(&["-flip", "horizontal"]),
+        3 => Some(&["-rotate", "180"]),
+        4 => Some(&["-flip", "vertical"]),
+        5 => Some(&["-transpose"]),
+        6 => Some(&["-rotate", "90"]),
+        7 => Some(&["-transverse"]),
+        8 => Some(&["-rotate", "270"]),
+        _ => None,
+    }
+}
+
+fn matrix(orientation: u8) -> [[i8; 2]; 2] {
+    ORIENTATION_MATRICES
+        .iter()
+        .find(|(x, _)| *x == orientation)
+        .map(|(_, m)| *m)
+        .unwrap_or(ORIENTATION_MATRICES[0].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose() {
+        assert_eq!(compose(1, ImageTransform::Rotate90), 6);
+        assert_eq!(compose(6, ImageTransform::Rotate90), 3);
+        assert_eq!(compose(8, ImageTransform::Rotate90), 1);
+        assert_eq!(compose(1, ImageTransform::FlipHorizontal), 2);
+        assert_eq!(compose(2, ImageTransform::FlipHorizontal), 1);
+        // 先顺时针旋转 90° 再水平翻转等于沿对角线翻转
+        assert_eq!(compose(6, ImageTransform::FlipHorizontal), 5);
+        assert_eq!(compose(6, ImageTransform::FlipVertical), 7);
+        assert_eq!(
+            ImageTransform::from_degrees(-90),
+            Some(ImageTransform::Rotate270)
+        );
+        assert_eq!(ImageTransform::from_degrees(45), None);
+    }
+}
@@ -1,15 +1,29 @@
+use crate::constant::CAMERA_PLACEHOLDER_CAPTIONS;
+use crate::i18n;
+use crate::i18n::msg;
 use crate::tuples::Pair;
-use crate::utils::exif_utils::gps_util::GpsInfo;
-use crate::utils::exif_utils::value::ValueType;
+use crate::utils::exif_utils::camera_db;
+use crate::utils::exif_utils::exif_enum::{
+    ExposureProgram, Flash, MeteringMode, SceneCaptureType, WhiteBalance,
+};
+use crate::utils::exif_utils::exif_reader::{Context, Exif, In, Tag};
+use crate::utils::exif_utils::format;
+use crate::utils::exif_utils::gps_util::{GpsCoordinate, GpsInfo, NorthRef};
+use crate::utils::exif_utils::value::{Rational, Value, ValueType};
 use crate::utils::json_util::JsonUtil;
 use anyhow::{anyhow, Result};
-use chrono::{DateTime, FixedOffset, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
+use std::sync::Mutex;
 
-#[derive(Debug, Clone)]
+/// 默认时区【exif 中没有时区信息时使用】
+pub const DEFAULT_OFFSET: &str = "+08:00";
+
+#[derive(Debug)]
 pub struct Tags {
     /// 原始数据保存
     pub entries: Vec<(String, String)>,
@@ -17,21 +31,106 @@ pub struct Tags {
     pub entry_map: HashMap<String, String>,
     /// 遇到错误时继续
     continue_on_error: bool,
+    /// 遇到错误继续时收集的转换错误
+    errors: Mutex<Vec<TagError>>,
+    /// 日期时间解析模式
+    datetime_mode: DateTimeParseMode,
+    /// 宽松模式下经过规范化的日期时间标签【exiftool 的文字描述】
+    normalized: Mutex<Vec<String>>,
+}
+
+impl Clone for Tags {
+    fn clone(&self) -> Self {
+        Tags {
+            entries: self.entries.clone(),
+            entry_map: self.entry_map.clone(),
+            continue_on_error: self.continue_on_error,
+            errors: Mutex::new(self.errors()),
+            datetime_mode: self.datetime_mode,
+            normalized: Mutex::new(self.normalized()),
+        }
+    }
+}
+
+/// 日期时间解析模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DateTimeParseMode {
+    /// 只接受 exiftool 标准格式 `%Y:%m:%d %H:%M:%S`
+    #[default]
+    Strict,
+    /// 标准格式解析失败时，接受 `-`、`/`、`.` 日期分隔符，`T` 日期时间分隔符和缺少的秒数
+    Lenient,
+}
+
+/// 标签数据转换错误
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TagError {
+    /// exiftool 的文字描述
+    pub key: String,
+    /// 原始数据
+    pub value: String,
+    /// 错误信息
+    pub message: String,
+}
+
+impl fmt::Display for TagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} {}", self.key, self.value, self.message)
+    }
 }
 
 impl Tags {
-    pub fn parse(mut self, info: &str) -> Self {
-        for line in info.lines() {
-            if let Some((key, value)) = line.split_once(':') {
-                let key = key.trim().to_string();
-                let value = value.trim().to_string();
-                self.entry_map.insert(key.clone(), value.clone());
-                self.entries.push((key, value));
+    /// 读取 exiftool JSON 输出中的标签（标签名称、值）
+    ///
+    /// 已知标签按 exiftool 的文字描述保存；内置解析不认识的标签按标签名称保留
+    pub fn with_entries(mut self, entries: Vec<(String, String)>) -> Self {
+        for (name, value) in entries {
+            self.insert(entry_key(name), value.trim().to_string());
+        }
+        self
+    }
+
+    /// 补充 exiftool 读取的标签【已有的标签保留原来的值，只添加缺少的标签】
+    pub fn with_extra_entries(mut self, entries: Vec<(String, String)>) -> Self {
+        for (name, value) in entries {
+            let key = entry_key(name);
+            if !self.entry_map.contains_key(&key) {
+                self.insert(key, value.trim().to_string());
+            }
+        }
+        self
+    }
+
+    /// 读取内置解析的 EXIF 字段【只读取主图像中已声明的标签，值转换为与 exiftool 输出相同的文字】
+    ///
+    /// 宽高不读取，RAW 的 IFD0 记录的可能是预览图的尺寸；没有 `OffsetTime` 时使用 `OffsetTimeOriginal`
+    pub fn with_exif(mut self, exif: &Exif) -> Self {
+        for field in exif.fields().filter(|x| x.ifd_num == In::PRIMARY) {
+            let Some(info) = native_info(field.tag) else {
+                continue;
+            };
+            if self.entry_map.contains_key(info.exif_tool_desc) {
+                continue;
+            }
+            if let Some(value) = native_text(info, &field.value) {
+                self.insert(info.exif_tool_desc.to_string(), value);
+            }
+        }
+        let offset_key = ExifToolDesc::OFFSET_TIME.exif_tool_desc;
+        if !self.entry_map.contains_key(offset_key) {
+            if let Some(offset) = exif.get_text(Tag::OFFSET_TIME_ORIGINAL, In::PRIMARY) {
+                self.insert(offset_key.to_string(), offset.to_string());
             }
         }
         self
     }
 
+    fn insert(&mut self, key: String, value: String) {
+        self.entry_map.insert(key.clone(), value.clone());
+        self.entries.push((key, value));
+    }
+
     pub fn is_empty(&self) -> bool {
         self.entries.is_empty() || self.entry_map.is_empty()
     }
@@ -42,6 +141,98 @@ impl Tags {
             .map(|v| Cow::Borrowed(v).to_string())
     }
 
+    /// 读取无符号整数【忽略单位，如 `6000`、`100`】
+    pub fn get_u32(&self, info: &ExifInfo) -> Result<Option<u32>> {
+        self.get_typed(info, ValueType::U32, |x| parse_leading_number(x))
+    }
+
+    /// 读取浮点数【忽略单位，如 `50.0 mm`；有理数转换为小数】
+    pub fn get_f64(&self, info: &ExifInfo) -> Result<Option<f64>> {
+        let is_rational = info.value_type == ValueType::Rational;
+        self.get_typed(info, ValueType::F64, |x| {
+            if is_rational {
+                Rational::parse(x).map(|r| r.to_f64())
+            } else {
+                parse_leading_number(x)
+            }
+        })
+    }
+
+    /// 读取有理数【如 `1/200`】
+    pub fn get_rational(&self, info: &ExifInfo) -> Result<Option<Rational>> {
+        self.get_typed(info, ValueType::Rational, Rational::parse)
+    }
+
+    /// 读取日期时间并转换为 UTC
+    ///
+    /// 时区优先使用数据本身携带的偏移，其次使用 `Offset Time`，都没有时按东八区处理
+    ///
+    /// 宽松模式下经过规范化的标签会被记录，通过 `normalized` 获取
+    pub fn get_datetime(&self, info: &ExifInfo) -> Result<Option<DateTime<Utc>>> {
+        let offset = self.get(ExifToolDesc::OFFSET_TIME.exif_tool_desc);
+        let parsed = self.get_typed(info, ValueType::Time, |x| {
+            parse_exif_datetime(x, offset.as_deref(), self.datetime_mode)
+        })?;
+        Ok(parsed.map(|(time, normalized)| {
+            if normalized {
+                self.normalized.lock().unwrap().push(info.exif_tool_desc.to_string());
+            }
+            time
+        }))
+    }
+
+    /// 宽松模式下经过规范化的日期时间标签
+    pub fn normalized(&self) -> Vec<String> {
+        self.normalized.lock().unwrap().clone()
+    }
+
+    /// 设置日期时间解析模式
+    pub fn with_datetime_mode(mut self, mode: DateTimeParseMode) -> Self {
+        self.datetime_mode = mode;
+        self
+    }
+
+    /// 遇到错误继续时收集到的转换错误
+    pub fn errors(&self) -> Vec<TagError> {
+        self.errors.lock().unwrap().clone()
+    }
+
+    /// 按 `ExifInfo` 中声明的类型读取数据
+    /// - info 标签描述
+    /// - target 读取的目标类型
+    /// - parse 转换函数，无法转换时返回 None
+    fn get_typed<T, F>(&self, info: &ExifInfo, target: ValueType, parse: F) -> Result<Option<T>>
+    where
+        F: FnOnce(&str) -> Option<T>,
+    {
+        let Some(value) = self.get(info.exif_tool_desc) else {
+            return Ok(None);
+        };
+        if !info.value_type.readable_as(&target) {
+            let message = format!("类型 {:?} 不能按 {:?} 读取", info.value_type, target);
+            return self.on_error(info, value, message);
+        }
+        match parse(&value) {
+            Some(x) => Ok(Some(x)),
+            None => self.on_error(info, value, format!("无法转换为 {:?}", target)),
+        }
+    }
+
+    /// 转换失败时，遇到错误继续则记录错误并返回 None，否则返回错误
+    fn on_error<T>(&self, info: &ExifInfo, value: String, message: String) -> Result<Option<T>> {
+        let error = TagError {
+            key: info.exif_tool_desc.to_string(),
+            value,
+            message,
+        };
+        if !self.continue_on_error {
+            return Err(anyhow!("数据: {} 转换失败! ", error));
+        }
+        log::warn!("exif 数据转换失败: {}", error);
+        self.errors.lock().unwrap().push(error);
+        Ok(None)
+    }
+
     /// 打包数据
     pub fn pack_tags(&self) -> Result<String> {
         let mut res: Vec<Pair<String, String>> = Vec::new();
@@ -50,7 +241,7 @@ impl Tags {
             // 如果数据有值
             if ans.is_some() {
                 res.push(Pair {
-                    first: info.dis.to_string(),
+                    first: info.dis().to_string(),
                     second: ans.unwrap().to_string(),
                 });
             }
@@ -72,66 +263,130 @@ impl Tags {
         };
 
         if let Some(x) = img_exif.make {
-            add_tag(ExifToolDesc::MAKE.dis.to_string(), x);
+            add_tag(ExifToolDesc::MAKE.dis().to_string(), x);
         }
 
         if let Some(x) = img_exif.software {
-            add_tag(ExifToolDesc::SOFTWARE.dis.to_string(), x);
+            add_tag(ExifToolDesc::SOFTWARE.dis().to_string(), x);
         }
 
         if let Some(x) = img_exif.exposure_time {
-            add_tag(ExifToolDesc::EXPOSURE_TIME.dis.to_string(), x.to_string());
+            let shutter = format::format_shutter_speed(x.to_f64()).unwrap_or(x.to_string());
+            add_tag(ExifToolDesc::EXPOSURE_TIME.dis().to_string(), shutter);
         }
 
         if let Some(x) = img_exif.flash {
-            add_tag(ExifToolDesc::FLASH.dis.to_string(), x);
+            let flash = img_exif.flash_code.map(|x| Flash(x).display()).unwrap_or(x);
+            add_tag(ExifToolDesc::FLASH.dis().to_string(), flash);
         }
 
         if let Some(x) = img_exif.f_number {
-            add_tag(ExifToolDesc::F_NUMBER.dis.to_string(), x.to_string());
+            let aperture = format::format_aperture(x).unwrap_or(x.to_string());
+            add_tag(ExifToolDesc::F_NUMBER.dis().to_string(), aperture);
         }
 
         if let Some(x) = img_exif.iso {
-            add_tag(ExifToolDesc::ISO.dis.to_string(), x.to_string());
+            add_tag(ExifToolDesc::ISO.dis().to_string(), x.to_string());
         }
 
         if let Some(x) = img_exif.date_time_original {
             add_tag(
-                ExifToolDesc::DATE_TIME_ORIGINAL.dis.to_string(),
+                ExifToolDesc::DATE_TIME_ORIGINAL.dis().to_string(),
                 x.to_string(),
             );
         }
 
         if let Some(x) = img_exif.max_aperture_value {
-            add_tag(ExifToolDesc::MAX_APERTURE_VALUE.dis.to_string(), x);
+            add_tag(ExifToolDesc::MAX_APERTURE_VALUE.dis().to_string(), x.to_string());
         }
 
         if let Some(x) = img_exif.focal_length {
-            add_tag(ExifToolDesc::FOCAL_LENGTH.dis.to_string(), x.to_string());
+            add_tag(ExifToolDesc::FOCAL_LENGTH.dis().to_string(), x.to_string());
+        }
+
+        if let Some(x) = img_exif.effective_focal_length {
+            add_tag(
+                ExifToolDesc::FOCAL_LENGTH_IN_35MM_FORMAT.dis().to_string(),
+                x.to_string(),
+            );
         }
 
         if let Some(x) = img_exif.image_width {
-            add_tag(ExifToolDesc::IMAGE_WIDTH.dis.to_string(), x.to_string());
+            add_tag(ExifToolDesc::IMAGE_WIDTH.dis().to_string(), x.to_string());
         }
 
         if let Some(x) = img_exif.image_height {
-            add_tag(ExifToolDesc::IMAGE_HEIGHT.dis.to_string(), x.to_string());
+            add_tag(ExifToolDesc::IMAGE_HEIGHT.dis().to_string(), x.to_string());
         }
 
         if let Some(x) = img_exif.gps_info {
-            add_tag(String::from("GPS 信息"), x.to_string());
+            add_tag(i18n::text(msg::EXIF_GPS_INFO).to_string(), x.to_string());
+        }
+
+        if let Some(x) = img_exif.gps_speed {
+            add_tag(ExifToolDesc::GPS_SPEED.dis().to_string(), format!("{:.1} km/h", x));
+        }
+
+        // 方向后面标注参考方向（真北、磁北）
+        let direction = |degrees: f64, north: Option<NorthRef>| match north {
+            Some(north) => format!("{:.1}° ({})", degrees, north.display()),
+            None => format!("{:.1}°", degrees),
+        };
+
+        if let Some(x) = img_exif.gps_img_direction {
+            add_tag(
+                ExifToolDesc::GPS_IMG_DIRECTION.dis().to_string(),
+                direction(x, img_exif.gps_img_direction_ref),
+            );
+        }
+
+        if let Some(x) = img_exif.gps_dest_bearing {
+            add_tag(
+                ExifToolDesc::GPS_DEST_BEARING.dis().to_string(),
+                direction(x, img_exif.gps_dest_bearing_ref),
+            );
         }
 
         if let Some(x) = img_exif.exposure_program {
-            add_tag(ExifToolDesc::EXPOSURE_PROGRAM.dis.to_string(), x);
+            let program = img_exif
+                .exposure_program_code
+                .and_then(ExposureProgram::from_code)
+                .map_or(x, |x| x.display().to_string());
+            add_tag(ExifToolDesc::EXPOSURE_PROGRAM.dis().to_string(), program);
         }
 
         if let Some(x) = img_exif.metering_mode {
-            add_tag(ExifToolDesc::METERING_MODE.dis.to_string(), x);
+            let mode = img_exif
+                .metering_mode_code
+                .and_then(MeteringMode::from_code)
+                .map_or(x, |x| x.display().to_string());
+            add_tag(ExifToolDesc::METERING_MODE.dis().to_string(), mode);
+        }
+
+        if let Some(x) = img_exif.white_balance_code.and_then(WhiteBalance::from_code) {
+            add_tag(ExifToolDesc::WHITE_BALANCE.dis().to_string(), x.display().to_string());
+        }
+
+        if let Some(x) = img_exif
+            .scene_capture_type_code
+            .and_then(SceneCaptureType::from_code)
+        {
+            add_tag(
+                ExifToolDesc::SCENE_CAPTURE_TYPE.dis().to_string(),
+                x.display().to_string(),
+            );
         }
 
         if let Some(x) = img_exif.artist {
-            add_tag(ExifToolDesc::ARTIST.dis.to_string(), x);
+            add_tag(ExifToolDesc::ARTIST.dis().to_string(), x);
+        }
+
+        if let Some(x) = img_exif.copyright {
+            add_tag(ExifToolDesc::COPYRIGHT.dis().to_string(), x);
+        }
+
+        if let Some(x) = img_exif.caption {
+            add_tag(ExifToolDesc::DESCRIPTION.dis().to_string(), x);
         }
 
         JsonUtil::stringify(&res)
@@ -139,87 +394,125 @@ impl Tags {
 
     /// 打包为对象
     pub fn pack_object(&self) -> Result<ImgExif> {
-        let make: Option<String>;
-        let model: Option<String>;
-        let software: Option<String>;
-        let exposure_time: Option<f64>;
-        let flash: Option<String>;
-        let f_number: Option<f64>;
-        let iso: Option<u32>;
-        let date_time_original: Option<DateTime<Utc>>;
-        let max_aperture_value: Option<String>;
-        let focal_length: Option<f64>;
-        let image_width: Option<u32>;
-        let image_height: Option<u32>;
-        let gps_info: Option<GpsInfo>;
-        let exposure_program: Option<String>;
-        let metering_mode: Option<String>;
-        let artist: Option<String>;
-        let rating: Option<u32>;
-
-        make = self.get(ExifToolDesc::MAKE.exif_tool_desc);
-        model = self.get(ExifToolDesc::MODEL.exif_tool_desc);
-        software = self.get(ExifToolDesc::SOFTWARE.exif_tool_desc);
-        artist = self.get(ExifToolDesc::ARTIST.exif_tool_desc);
-        flash = self.get(ExifToolDesc::FLASH.exif_tool_desc);
-        focal_length = self.parse_number_data(ExifToolDesc::FOCAL_LENGTH.exif_tool_desc)?;
-        // 解析曝光时间信息
-        exposure_time = self.parse_exposure_time();
-        // 光圈数解析
-        f_number = self.parse_number_data(ExifToolDesc::F_NUMBER.exif_tool_desc)?;
-        image_width = self.parse_number_data(ExifToolDesc::IMAGE_WIDTH.exif_tool_desc)?;
-        image_height = self.parse_number_data(ExifToolDesc::IMAGE_HEIGHT.exif_tool_desc)?;
-        max_aperture_value =
-            self.parse_number_data(ExifToolDesc::MAX_APERTURE_VALUE.exif_tool_desc)?;
-        iso = self.parse_number_data(ExifToolDesc::ISO.exif_tool_desc)?;
-        exposure_program = self.get(ExifToolDesc::EXPOSURE_PROGRAM.exif_tool_desc);
-        metering_mode = self.get(ExifToolDesc::METERING_MODE.exif_tool_desc);
         // 解析 GPS
-        gps_info = Option::from(GpsInfo::parse(self, self.continue_on_error)?);
-        // 解析时间
-        date_time_original = self.parse_create_time();
-        // 评分
-        rating = self.parse_number_data(ExifToolDesc::RATING.exif_tool_desc)?;
+        let gps = GpsInfo::parse(self, self.continue_on_error)?;
+        // 焦距
+        let focal_length = self.get_f64(&ExifToolDesc::FOCAL_LENGTH)?;
+        let focal_length_in_35mm_format =
+            self.get_f64(&ExifToolDesc::FOCAL_LENGTH_IN_35MM_FORMAT)?;
+        let make = self.get(ExifToolDesc::MAKE.exif_tool_desc);
+        let model = self.get(ExifToolDesc::MODEL.exif_tool_desc);
+        let crop_factor = match (&make, &model) {
+            (Some(make), Some(model)) => camera_db::crop_factor(make, model),
+            _ => None,
+        };
         Ok(ImgExif {
             make,
             model,
-            software,
-            exposure_time,
-            flash,
-            f_number,
-            iso,
-            date_time_original,
-            max_aperture_value,
+            software: self.get(ExifToolDesc::SOFTWARE.exif_tool_desc),
+            // 曝光时间
+            exposure_time: self.get_rational(&ExifToolDesc::EXPOSURE_TIME)?,
+            flash: self.get(ExifToolDesc::FLASH.exif_tool_desc),
+            // 光圈数
+            f_number: self.get_f64(&ExifToolDesc::F_NUMBER)?,
+            iso: self.get_u32(&ExifToolDesc::ISO)?,
+            // 拍摄时间
+            date_time_original: self.get_datetime(&ExifToolDesc::DATE_TIME_ORIGINAL)?,
+            date_time_normalized: self
+                .normalized()
+                .iter()
+                .any(|x| x == ExifToolDesc::DATE_TIME_ORIGINAL.exif_tool_desc),
+            offset_time: self.get(ExifToolDesc::OFFSET_TIME.exif_tool_desc),
+            max_aperture_value: self.get_f64(&ExifToolDesc::MAX_APERTURE_VALUE)?,
             focal_length,
-            image_width,
-            image_height,
-            gps_info,
-            exposure_program,
-            metering_mode,
-            artist,
-            rating
+            focal_length_in_35mm_format,
+            effective_focal_length: camera_db::effective_focal_length(
+                focal_length,
+                focal_length_in_35mm_format,
+                crop_factor,
+            ),
+            crop_factor,
+            image_width: self.get_u32(&ExifToolDesc::IMAGE_WIDTH)?,
+            image_height: self.get_u32(&ExifToolDesc::IMAGE_HEIGHT)?,
+            gps_info: gps.to_coordinate(),
+            gps_speed: gps.speed,
+            gps_img_direction: gps.img_direction,
+            gps_img_direction_ref: gps.img_direction_ref,
+            gps_dest_bearing: gps.dest_bearing,
+            gps_dest_bearing_ref: gps.dest_bearing_ref,
+            exposure_program: self.get(ExifToolDesc::EXPOSURE_PROGRAM.exif_tool_desc),
+            metering_mode: self.get(ExifToolDesc::METERING_MODE.exif_tool_desc),
+            flash_code: self.get_code(&ExifToolDesc::FLASH, |x| {
+                Flash::from_exiftool(x).map(|x| x.0)
+            }),
+            metering_mode_code: self.get_code(&ExifToolDesc::METERING_MODE, |x| {
+                MeteringMode::from_exiftool(x).map(|x| x.code())
+            }),
+            exposure_program_code: self.get_code(&ExifToolDesc::EXPOSURE_PROGRAM, |x| {
+                ExposureProgram::from_exiftool(x).map(|x| x.code())
+            }),
+            white_balance_code: self.get_code(&ExifToolDesc::WHITE_BALANCE, |x| {
+                WhiteBalance::from_exiftool(x).map(|x| x.code())
+            }),
+            scene_capture_type_code: self.get_code(&ExifToolDesc::SCENE_CAPTURE_TYPE, |x| {
+                SceneCaptureType::from_exiftool(x).map(|x| x.code())
+            }),
+            artist: self.get_artist(),
+            copyright: self.first_non_empty(&[&ExifToolDesc::COPYRIGHT]),
+            // 评分
+            rating: self.get_u32(&ExifToolDesc::RATING)?,
+            caption: self.get_caption(),
+            keywords: Vec::new(),
+            warnings: Vec::new(),
         })
     }
 
-    /// 解析时间
-    pub fn parse_create_time(&self) -> Option<DateTime<Utc>> {
-        let create_time: Option<String> = self.get(ExifToolDesc::DATE_TIME_ORIGINAL.exif_tool_desc);
-        let offset_time: Option<String> = self.get(ExifToolDesc::OFFSET_TIME.exif_tool_desc);
-
-        // 如果 create_time 是 None，直接返回 None
-        let date_str = create_time?;
-
-        // 如果 offset_time 是 None，则使用默认的东八区时区 "+08:00"
-        let offset_str = offset_time.unwrap_or_else(|| "+08:00".to_string());
-
-        // 解析 Date/Time Original 字符串为 DateTime<FixedOffset>
-        let date_time = DateTime::parse_from_str(&date_str, "%Y:%m:%d %H:%M:%S").ok()?;
+    /// 读取枚举字段的数值【无法识别的文字描述记录日志后忽略】
+    fn get_code<F>(&self, info: &ExifInfo, parse: F) -> Option<i32>
+    where
+        F: FnOnce(&str) -> Option<i32>,
+    {
+        let value = self.get(info.exif_tool_desc)?;
+        let code = parse(&value);
+        if code.is_none() {
+            log::debug!("无法识别的 {}: {}", info.exif_tool_desc, value);
+        }
+        code
+    }
 
-        // 解析 Offset Time 字符串为 FixedOffset
-        let offset = FixedOffset::from_str(&offset_str).ok()?;
+    /// 照片说明【XMP dc:description 优先，Exif 3.0 ImageTitle 优先于 ImageDescription，
+    /// 忽略空白内容和相机写入的默认文字，见 `is_camera_placeholder`】
+    pub fn get_caption(&self) -> Option<String> {
+        let make = self.get(ExifToolDesc::MAKE.exif_tool_desc);
+        let model = self.get(ExifToolDesc::MODEL.exif_tool_desc);
+        [
+            &ExifToolDesc::DESCRIPTION,
+            &ExifToolDesc::IMAGE_TITLE,
+            &ExifToolDesc::IMAGE_DESCRIPTION,
+        ]
+        .iter()
+        .filter_map(|info| self.get(info.exif_tool_desc))
+        .find(|x| {
+            !x.trim().is_empty() && !is_camera_placeholder(x, make.as_deref(), model.as_deref())
+        })
+    }
+    /// 作者【Exif 3.0 Photographer 优先于 Artist，忽略空白内容】
+    pub fn get_artist(&self) -> Option<String> {
+        self.first_non_empty(&[&ExifToolDesc::PHOTOGRAPHER, &ExifToolDesc::ARTIST])
+    }
+    /// 按顺序取第一个非空的值
+    fn first_non_empty(&self, infos: &[&ExifInfo]) -> Option<String> {
+        infos
+            .iter()
+            .filter_map(|info| self.get(info.exif_tool_desc))
+            .find(|x| !x.trim().is_empty())
+    }
 
-        // 使用时区偏移创建 DateTime<FixedOffset>，然后转换为 UTC 时间
-        Some(date_time.with_timezone(&offset).with_timezone(&Utc))
+    /// 解析时间
+    pub fn parse_create_time(&self) -> Option<DateTime<Utc>> {
+        self.get_datetime(&ExifToolDesc::DATE_TIME_ORIGINAL)
+            .ok()
+            .flatten()
     }
 
     pub fn parse_number_data<T>(&self, str: &str) -> Result<Option<T>>
@@ -234,20 +527,7 @@ impl Tags {
     }
     /// 曝光时间
     pub fn parse_exposure_time(&self) -> Option<f64> {
-        let option = self.get(ExifToolDesc::EXPOSURE_TIME.exif_tool_desc);
-        if let Some(x) = option {
-            let parts: Vec<&str> = x.split('/').collect();
-            if parts.len() == 2 {
-                let numerator: f64 = parts[0].parse().unwrap_or(1.0);
-                let denominator: f64 = parts[1].parse().unwrap_or(1.0);
-                Some(numerator / denominator)
-            } else {
-                // 如果格式不对
-                None
-            }
-        } else {
-            None
-        }
+        self.get_f64(&ExifToolDesc::EXPOSURE_TIME).ok().flatten()
     }
 
     /// 焦距解析
@@ -279,11 +559,197 @@ impl Tags {
             entries: Vec::new(),
             entry_map: HashMap::new(),
             continue_on_error,
+            errors: Mutex::new(Vec::new()),
+            datetime_mode: DateTimeParseMode::Strict,
+            normalized: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+/// 标签名称转换为 exiftool 的文字描述【未声明的标签保留标签名称】
+/// 是否为相机自动写入的默认说明【如 `OLYMPUS DIGITAL CAMERA`，或与制造商、型号相同】
+/// - text 说明
+/// - make 制造商
+/// - model 型号
+pub fn is_camera_placeholder(text: &str, make: Option<&str>, model: Option<&str>) -> bool {
+    let text = text.trim();
+    let same = |x: &str| x.trim().eq_ignore_ascii_case(text);
+    CAMERA_PLACEHOLDER_CAPTIONS.iter().any(|x| same(x))
+        || make.is_some_and(same)
+        || model.is_some_and(same)
+        || make
+            .zip(model)
+            .is_some_and(|(make, model)| same(&format!("{} {}", make.trim(), model.trim())))
+}
+
+fn entry_key(name: String) -> String {
+    ExifToolDesc::by_name(&name).map_or(name, |info| info.exif_tool_desc.to_string())
+}
+
+/// 内置解析的标签对应的标签描述【GPS IFD 的标签 ID 与其他 IFD 重复，按所在的 IFD 区分】
+fn native_info(tag: Tag) -> Option<&'static ExifInfo> {
+    let gps = match tag.0 {
+        Context::Tiff | Context::Exif => false,
+        Context::Gps => true,
+        Context::Interop => return None,
+    };
+    let skipped = [
+        ExifToolDesc::IMAGE_WIDTH.name,
+        ExifToolDesc::IMAGE_HEIGHT.name,
+    ];
+    ExifToolDesc::EXIF_INFOS.into_iter().find(|x| {
+        x.id == Some(tag.1) && x.name.starts_with("GPS") == gps && !skipped.contains(&x.name)
+    })
+}
+
+/// 内置解析的值转换为 exiftool 输出的文字【无法转换或为空时返回 None】
+fn native_text(info: &ExifInfo, value: &Value) -> Option<String> {
+    let text = match value {
+        Value::Ascii(_) => value.as_ascii_str().ok()?.trim().to_string(),
+        Value::Rational(x) if info.value_type == ValueType::Gps => dms_text(x)?,
+        Value::Rational(x) if info.value_type == ValueType::Rational => {
+            let x = x.first()?;
+            format!("{}/{}", x.numerator, x.denominator)
+        }
+        Value::Rational(x) => x.first()?.to_f64().to_string(),
+        Value::SRational(x) => x.first()?.to_f64().to_string(),
+        Value::Byte(_) | Value::Short(_) | Value::Long(_) => {
+            enum_text(info, *value.as_u32s().ok()?.first()? as i32)
         }
+        _ => return None,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// 枚举标签的数值转换为 exiftool 的文字描述【不是枚举或未定义的数值保留数值】
+fn enum_text(info: &ExifInfo, code: i32) -> String {
+    let desc = if info.name == ExifToolDesc::FLASH.name {
+        Some(Flash(code).exif_tool_desc())
+    } else if info.name == ExifToolDesc::EXPOSURE_PROGRAM.name {
+        ExposureProgram::from_code(code).map(|x| x.exif_tool_desc().to_string())
+    } else if info.name == ExifToolDesc::METERING_MODE.name {
+        MeteringMode::from_code(code).map(|x| x.exif_tool_desc().to_string())
+    } else if info.name == ExifToolDesc::WHITE_BALANCE.name {
+        WhiteBalance::from_code(code).map(|x| x.exif_tool_desc().to_string())
+    } else if info.name == ExifToolDesc::SCENE_CAPTURE_TYPE.name {
+        SceneCaptureType::from_code(code).map(|x| x.exif_tool_desc().to_string())
+    } else {
+        None
+    };
+    desc.unwrap_or_else(|| code.to_string())
+}
+
+/// 度、分、秒转换为 exiftool 的格式【如 `22 deg 30' 36.00"`，度、分带小数时换算到秒】
+fn dms_text(values: &[Rational]) -> Option<String> {
+    if values.is_empty() {
+        return None;
+    }
+    let degrees: f64 = values
+        .iter()
+        .zip([1.0, 60.0, 3600.0])
+        .map(|(x, unit)| x.to_f64() / unit)
+        .sum();
+    if !degrees.is_finite() {
+        return None;
+    }
+    // 按百分之一秒取整后拆分，避免秒数进位为 60
+    let total = (degrees * 360_000.0).round() as u64;
+    let seconds = (total % 6000) as f64 / 100.0;
+    Some(format!(
+        "{} deg {}' {:.2}\"",
+        total / 360_000,
+        total % 360_000 / 6000,
+        seconds
+    ))
+}
+
+/// 解析数据开头的数字部分【忽略后面的单位】
+fn parse_leading_number<T: FromStr>(value: &str) -> Option<T> {
+    value.split_whitespace().next()?.parse::<T>().ok()
+}
+
+/// 解析 exiftool 输出的日期时间，返回 UTC 时间和是否经过规范化
+/// - value 日期时间
+/// - offset 数据本身不带时区时使用的时区
+/// - mode 解析模式【宽松模式下标准格式解析失败时，规范化后再解析】
+fn parse_exif_datetime(
+    value: &str,
+    offset: Option<&str>,
+    mode: DateTimeParseMode,
+) -> Option<(DateTime<Utc>, bool)> {
+    if let Some(x) = parse_standard_datetime(value, offset) {
+        return Some((x, false));
+    }
+    if mode == DateTimeParseMode::Strict {
+        return None;
     }
+    let normalized = normalize_exif_datetime(value)?;
+    parse_standard_datetime(&normalized, offset).map(|x| (x, true))
 }
 
-/// 图像的 exif 信息对象
+/// 解析标准格式的日期时间【如 `2024:05:01 12:30:45`、`2024:05:01 12:30:45.12+08:00`】
+fn parse_standard_datetime(value: &str, offset: Option<&str>) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    let date = value.get(..19)?;
+    let rest = value.get(19..)?;
+    let date_time = NaiveDateTime::parse_from_str(date, "%Y:%m:%d %H:%M:%S").ok()?;
+    // 跳过毫秒部分，剩余部分为时区
+    let suffix = rest.trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+    let offset_str = if suffix.starts_with(['+', '-']) {
+        suffix
+    } else {
+        offset.unwrap_or(DEFAULT_OFFSET)
+    };
+    let offset = FixedOffset::from_str(offset_str.trim()).ok()?;
+    offset
+        .from_local_datetime(&date_time)
+        .single()
+        .map(|x| x.with_timezone(&Utc))
+}
+
+/// 将非标准的日期时间转换为标准格式【如 `2016-05-04T03:02`、`2016/05/04 03:02:01Z`】
+///
+/// 日期分隔符可以是 `-`、`/`、`.`，日期和时间之间可以是 `T`，秒数缺少时补 0，`Z` 转换为 `+00:00`
+fn normalize_exif_datetime(value: &str) -> Option<String> {
+    let value = value.trim();
+    let split = value.find(['T', ' '])?;
+    let (date, time) = (&value[..split], value[split + 1..].trim_start());
+
+    let date: Vec<u32> = date
+        .split(['-', '/', '.', ':'])
+        .map(|x| x.parse::<u32>().ok())
+        .collect::<Option<_>>()?;
+    let [year, month, day] = date[..] else {
+        return None;
+    };
+
+    let clock_end = time
+        .find(|c: char| !c.is_ascii_digit() && c != ':')
+        .unwrap_or(time.len());
+    let (clock, rest) = time.split_at(clock_end);
+    let clock: Vec<u32> = clock
+        .split(':')
+        .map(|x| x.parse::<u32>().ok())
+        .collect::<Option<_>>()?;
+    let (hour, minute, second) = match clock[..] {
+        [hour, minute] => (hour, minute, 0),
+        [hour, minute, second] => (hour, minute, second),
+        _ => return None,
+    };
+    let rest = rest.trim();
+    let (rest, utc) = match rest.strip_suffix('Z') {
+        Some(x) => (x, "+00:00"),
+        None => (rest, ""),
+    };
+    Some(format!(
+        "{:04}:{:02}:{:02} {:02}:{:02}:{:02}{}{}",
+        year, month, day, hour, minute, second, rest, utc
+    ))
+}
+
+/// 图像的 exif 信息对象【数据库存储和前端展示统一使用】
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct ImgExif {
     /// 相机制造商
     pub make: Option<String>,
@@ -291,8 +757,8 @@ pub struct ImgExif {
     pub model: Option<String>,
     /// 软件版本
     pub software: Option<String>,
-    /// 曝光时间
-    pub exposure_time: Option<f64>,
+    /// 曝光时间（秒）
+    pub exposure_time: Option<Rational>,
     /// 闪光灯
     pub flash: Option<String>,
     /// 光圈
@@ -301,26 +767,67 @@ pub struct ImgExif {
     pub iso: Option<u32>,
     /// exif 信息版本
     // exif_version:OptionString>,
-    /// 创建日期
+    /// 创建日期（UTC）
     pub date_time_original: Option<DateTime<Utc>>,
+    /// 创建日期是否由非标准格式规范化得到【宽松模式】
+    #[serde(default)]
+    pub date_time_normalized: bool,
+    /// 时区【如 '+08:00'】
+    pub offset_time: Option<String>,
     /// 最大光圈值
-    pub max_aperture_value: Option<String>,
-    /// 焦距
+    pub max_aperture_value: Option<f64>,
+    /// 焦距（毫米）
     pub focal_length: Option<f64>,
+    /// 等效焦距（毫米）【照片中的 FocalLengthIn35mmFormat】
+    pub focal_length_in_35mm_format: Option<f64>,
+    /// 计算后的等效焦距（毫米）【照片中没有时按相机传感器尺寸估算】
+    pub effective_focal_length: Option<f64>,
+    /// 裁切系数【相机不在传感器数据中时为 None】
+    pub crop_factor: Option<f64>,
     /// 宽度
     pub image_width: Option<u32>,
     /// 长度
     pub image_height: Option<u32>,
-    /// gps 信息
-    pub gps_info: Option<GpsInfo>,
+    /// gps 信息（十进制）
+    pub gps_info: Option<GpsCoordinate>,
+    /// GPS 速度（km/h）
+    pub gps_speed: Option<f64>,
+    /// GPS 拍摄方向（度，0 ~ 360）
+    pub gps_img_direction: Option<f64>,
+    /// GPS 拍摄方向的参考方向
+    pub gps_img_direction_ref: Option<NorthRef>,
+    /// GPS 目的地方位（度，0 ~ 360）
+    pub gps_dest_bearing: Option<f64>,
+    /// GPS 目的地方位的参考方向
+    pub gps_dest_bearing_ref: Option<NorthRef>,
     /// 曝光程序
     pub exposure_program: Option<String>,
     /// 测光模式
     pub metering_mode: Option<String>,
+    /// 闪光灯数值【按位组合，第 0 位为是否闪光】
+    pub flash_code: Option<i32>,
+    /// 测光模式数值
+    pub metering_mode_code: Option<i32>,
+    /// 曝光程序数值
+    pub exposure_program_code: Option<i32>,
+    /// 白平衡数值
+    pub white_balance_code: Option<i32>,
+    /// 场景拍摄类型数值
+    pub scene_capture_type_code: Option<i32>,
     /// 作者（艺术家）
     pub artist: Option<String>,
+    /// 版权【XMP dc:rights、IPTC CopyrightNotice 优先于 EXIF Copyright】
+    pub copyright: Option<String>,
     /// 等级【评分】
     pub rating: Option<u32>,
+    /// 说明【优先使用 XMP dc:description，其次 ImageDescription】
+    pub caption: Option<String>,
+    /// 关键字【JPEG 中的 XMP dc:subject、IPTC Keywords】
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// 读取时跳过的错误【如损坏的 GPS IFD，其余字段正常读取；不保存】
+    #[serde(skip)]
+    pub warnings: Vec<String>,
 }
 
 impl fmt::Display for ImgExif {
@@ -396,188 +903,384 @@ impl fmt::Display for ImgExif {
     }
 }
 
-pub struct ExifToolDesc {}
+/// 统计标签数量
+macro_rules! count_tags {
+    () => { 0usize };
+    ($head:ident $($tail:ident)*) => { 1usize + count_tags!($($tail)*) };
+}
 
-impl ExifToolDesc {
-    pub const MAKE: ExifInfo = ExifInfo {
-        dis: "相机制造商",
-        exif_tool_desc: "Make",
-        value_type: ValueType::String,
-    };
-    pub const MODEL: ExifInfo = ExifInfo {
-        dis: "相机型号",
-        exif_tool_desc: "Camera Model Name",
-        value_type: ValueType::String,
-    };
-    pub const SOFTWARE: ExifInfo = ExifInfo {
-        dis: "软件",
-        exif_tool_desc: "Software",
-        value_type: ValueType::String,
-    };
-    pub const EXPOSURE_TIME: ExifInfo = ExifInfo {
-        dis: "快门速度",
-        exif_tool_desc: "Exposure Time",
-        value_type: ValueType::String,
-    };
-    pub const F_NUMBER: ExifInfo = ExifInfo {
-        dis: "光圈数",
-        exif_tool_desc: "F Number",
-        value_type: ValueType::String,
-    };
-    pub const ISO: ExifInfo = ExifInfo {
-        dis: "ISO 感光度",
-        exif_tool_desc: "ISO",
-        value_type: ValueType::String,
-    };
-    pub const EXIF_VERSION: ExifInfo = ExifInfo {
-        dis: "Exif 版本",
-        exif_tool_desc: "Exif Version",
-        value_type: ValueType::String,
-    };
-    pub const DATE_TIME_ORIGINAL: ExifInfo = ExifInfo {
-        dis: "拍摄时间",
-        exif_tool_desc: "Date/Time Original",
-        value_type: ValueType::String,
-    };
-    pub const OFFSET_TIME: ExifInfo = ExifInfo {
-        dis: "时区",
-        exif_tool_desc: "Offset Time",
-        value_type: ValueType::String,
-    };
-    pub const MAX_APERTURE_VALUE: ExifInfo = ExifInfo {
-        dis: "最大光圈",
-        exif_tool_desc: "Max Aperture Value",
-        value_type: ValueType::String,
-    };
-    pub const FOCAL_LENGTH: ExifInfo = ExifInfo {
-        dis: "焦距",
-        exif_tool_desc: "Focal Length",
-        value_type: ValueType::String,
-    };
-    pub const FOCAL_LENGTH_IN_35MM_FORMAT: ExifInfo = ExifInfo {
-        dis: "等效焦距",
-        exif_tool_desc: "Focal Length In 35mm Format",
-        value_type: ValueType::String,
-    };
-    pub const IMAGE_WIDTH: ExifInfo = ExifInfo {
-        dis: "图像宽度",
-        exif_tool_desc: "Image Width",
-        value_type: ValueType::String,
-    };
-    pub const IMAGE_HEIGHT: ExifInfo = ExifInfo {
-        dis: "图像长度",
-        exif_tool_desc: "Image Height",
-        value_type: ValueType::String,
-    };
-    pub const GPS_LATITUDE_REF: ExifInfo = ExifInfo {
-        dis: "GPS 纬度参考",
-        exif_tool_desc: "GPS Latitude Ref",
-        value_type: ValueType::String,
-    };
-    pub const GPS_LONGITUDE_REF: ExifInfo = ExifInfo {
-        dis: "GPS 经度参考",
-        exif_tool_desc: "GPS Longitude Ref",
-        value_type: ValueType::String,
-    };
-    pub const GPS_LATITUDE: ExifInfo = ExifInfo {
-        dis: "GPS 纬度",
-        exif_tool_desc: "GPS Latitude",
-        value_type: ValueType::String,
-    };
-    pub const GPS_LONGITUDE: ExifInfo = ExifInfo {
-        dis: "GPS 经度",
-        exif_tool_desc: "GPS Longitude",
-        value_type: ValueType::String,
-    };
-    pub const GPS_ALTITUDE: ExifInfo = ExifInfo {
-        dis: "GPS 海拔",
-        exif_tool_desc: "GPS Altitude",
-        value_type: ValueType::String,
-    };
-    pub const EXPOSURE_PROGRAM: ExifInfo = ExifInfo {
-        dis: "曝光程序",
-        exif_tool_desc: "Exposure Program",
-        value_type: ValueType::String,
-    };
-    pub const METERING_MODE: ExifInfo = ExifInfo {
-        dis: "测光模式",
-        exif_tool_desc: "Metering Mode",
-        value_type: ValueType::String,
-    };
-    pub const FLASH: ExifInfo = ExifInfo {
-        dis: "闪光灯",
-        exif_tool_desc: "Flash",
-        value_type: ValueType::String,
-    };
-    pub const ARTIST: ExifInfo = ExifInfo {
-        dis: "艺术家",
-        exif_tool_desc: "Artist",
-        value_type: ValueType::String,
-    };
-    pub const RATING: ExifInfo = ExifInfo {
-        dis: "评级",
-        exif_tool_desc: "Rating",
-        value_type: ValueType::String,
+/// 生成标签常量【添加标签只需要在 `generate_tag_constants!` 中增加一行】
+///
+/// 每行依次为：常量名、标签 ID、标签名称、exiftool 的文字描述、数据类型、默认值
+///
+/// 展示名称在消息目录中维护，消息 ID 为 `exif.tag.` 加标签名称
+macro_rules! generate_tag_constants {
+    (
+        $(
+            $(#[$attr:meta])*
+            ($name:ident, $id:expr, $tag_name:literal, $exif_tool_desc:expr, $value_type:ident, $default:expr)
+        )+
+    ) => {
+        /// 标签数量
+        const EXIF_TAG_COUNT: usize = count_tags!($($name)+);
+
+        pub struct ExifToolDesc {}
+
+        impl ExifToolDesc {
+            $(
+                $(#[$attr])*
+                pub const $name: ExifInfo = ExifInfo {
+                    id: $id,
+                    name: $tag_name,
+                    label_id: concat!("exif.tag.", $tag_name),
+                    exif_tool_desc: $exif_tool_desc,
+                    value_type: ValueType::$value_type,
+                    default: $default,
+                };
+            )+
+            /// 所有标签【按声明顺序】
+            pub const EXIF_INFOS: [&'static ExifInfo; EXIF_TAG_COUNT] = [$(&Self::$name),+];
+        }
     };
+}
 
-    pub const EXIF_INFOS: [&'static ExifInfo; 24] = [
-        &Self::MAKE,
-        &Self::MODEL,
-        &Self::SOFTWARE,
-        &Self::EXPOSURE_TIME,
-        &Self::F_NUMBER,
-        &Self::ISO,
-        &Self::EXIF_VERSION,
-        &Self::DATE_TIME_ORIGINAL,
-        &Self::OFFSET_TIME,
-        &Self::MAX_APERTURE_VALUE,
-        &Self::FOCAL_LENGTH,
-        &Self::FOCAL_LENGTH_IN_35MM_FORMAT,
-        &Self::IMAGE_WIDTH,
-        &Self::IMAGE_HEIGHT,
-        &Self::GPS_LATITUDE_REF,
-        &Self::GPS_LONGITUDE_REF,
-        &Self::GPS_LATITUDE,
-        &Self::GPS_LONGITUDE,
-        &Self::GPS_ALTITUDE,
-        &Self::EXPOSURE_PROGRAM,
-        &Self::METERING_MODE,
-        &Self::FLASH,
-        &Self::ARTIST,
-        &Self::RATING,
-    ];
+generate_tag_constants! {
+    (MAKE, Some(0x010F), "Make", "Make", String, None)
+    (MODEL, Some(0x0110), "Model", "Camera Model Name", String, None)
+    (SOFTWARE, Some(0x0131), "Software", "Software", String, None)
+    (EXPOSURE_TIME, Some(0x829A), "ExposureTime", "Exposure Time", Rational, None)
+    (F_NUMBER, Some(0x829D), "FNumber", "F Number", F64, None)
+    (ISO, Some(0x8827), "ISO", "ISO", U32, None)
+    (EXIF_VERSION, Some(0x9000), "ExifVersion", "Exif Version", String, None)
+    (DATE_TIME_ORIGINAL, Some(0x9003), "DateTimeOriginal", "Date/Time Original", Time, None)
+    (OFFSET_TIME, Some(0x9010), "OffsetTime", "Offset Time", String, Some(DEFAULT_OFFSET))
+    (MAX_APERTURE_VALUE, Some(0x9205), "MaxApertureValue", "Max Aperture Value", F64, None)
+    (FOCAL_LENGTH, Some(0x920A), "FocalLength", "Focal Length", F64, None)
+    (FOCAL_LENGTH_IN_35MM_FORMAT, Some(0xA405), "FocalLengthIn35mmFormat", "Focal Length In 35mm Format", F64, None)
+    (IMAGE_WIDTH, Some(0x0100), "ImageWidth", "Image Width", U32, None)
+    (IMAGE_HEIGHT, Some(0x0101), "ImageHeight", "Image Height", U32, None)
+    (GPS_LATITUDE_REF, Some(0x0001), "GPSLatitudeRef", "GPS Latitude Ref", String, None)
+    (GPS_LONGITUDE_REF, Some(0x0003), "GPSLongitudeRef", "GPS Longitude Ref", String, None)
+    (GPS_LATITUDE, Some(0x0002), "GPSLatitude", "GPS Latitude", Gps, None)
+    (GPS_LONGITUDE, Some(0x0004), "GPSLongitude", "GPS Longitude", Gps, None)
+    (GPS_ALTITUDE_REF, Some(0x0005), "GPSAltitudeRef", "GPS Altitude Ref", String, None)
+    (GPS_ALTITUDE, Some(0x0006), "GPSAltitude", "GPS Altitude", String, None)
+    (GPS_SPEED_REF, Some(0x000C), "GPSSpeedRef", "GPS Speed Ref", String, None)
+    (GPS_SPEED, Some(0x000D), "GPSSpeed", "GPS Speed", F64, None)
+    (GPS_IMG_DIRECTION_REF, Some(0x0010), "GPSImgDirectionRef", "GPS Img Direction Ref", String, None)
+    (GPS_IMG_DIRECTION, Some(0x0011), "GPSImgDirection", "GPS Img Direction", F64, None)
+    (GPS_DEST_BEARING_REF, Some(0x0017), "GPSDestBearingRef", "GPS Dest Bearing Ref", String, None)
+    (GPS_DEST_BEARING, Some(0x0018), "GPSDestBearing", "GPS Dest Bearing", F64, None)
+    (EXPOSURE_PROGRAM, Some(0x8822), "ExposureProgram", "Exposure Program", String, None)
+    (METERING_MODE, Some(0x9207), "MeteringMode", "Metering Mode", String, None)
+    (FLASH, Some(0x9209), "Flash", "Flash", String, None)
+    (WHITE_BALANCE, Some(0xA403), "WhiteBalance", "White Balance", String, None)
+    (SCENE_CAPTURE_TYPE, Some(0xA406), "SceneCaptureType", "Scene Capture Type", String, None)
+    (ARTIST, Some(0x013B), "Artist", "Artist", String, None)
+    (RATING, Some(0x4746), "Rating", "Rating", U32, None)
+    (IMAGE_DESCRIPTION, Some(0x010E), "ImageDescription", "Image Description", String, None)
+    /// XMP dc:description【不是 EXIF 标签，没有 ID】
+    (DESCRIPTION, None, "Description", "Description", String, None)
+    /// Exif 3.0 图像标题【UTF-8】
+    (IMAGE_TITLE, Some(0xA436), "ImageTitle", "Image Title", String, None)
+    /// Exif 3.0 摄影师【UTF-8，优先于 Artist】
+    (PHOTOGRAPHER, Some(0xA437), "Photographer", "Photographer", String, None)
+    /// Exif 3.0 图像编辑者【UTF-8】
+    (IMAGE_EDITOR, Some(0xA438), "ImageEditor", "Image Editor", String, None)
+    /// Exif 3.0 相机固件版本
+    (CAMERA_FIRMWARE, Some(0xA439), "CameraFirmware", "Camera Firmware", String, None)
+    (COPYRIGHT, Some(0x8298), "Copyright", "Copyright", String, None)
+}
+
+impl ExifToolDesc {
     /// 前端展示的数据
-    pub const EXIF_INFOS_FRONT: [&'static ExifInfo; 24] = ExifToolDesc::EXIF_INFOS;
+    pub const EXIF_INFOS_FRONT: [&'static ExifInfo; EXIF_TAG_COUNT] = ExifToolDesc::EXIF_INFOS;
+
+    /// 按标签 ID 查找【GPS IFD 的 ID 与其他 IFD 可能重复，返回先声明的】
+    pub fn by_id(id: u16) -> Option<&'static ExifInfo> {
+        Self::EXIF_INFOS.into_iter().find(|x| x.id == Some(id))
+    }
+
+    /// 按标签名称查找【也可以使用 exiftool 的文字描述】
+    pub fn by_name(name: &str) -> Option<&'static ExifInfo> {
+        Self::EXIF_INFOS
+            .into_iter()
+            .find(|x| x.name == name || x.exif_tool_desc == name)
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct ExifInfo {
-    pub dis: &'static str,
+    /// 标签 ID【XMP 等非 EXIF 字段为 None】
+    pub id: Option<u16>,
+    /// 标签名称【如 `DateTimeOriginal`】
+    pub name: &'static str,
+    /// 展示名称的消息 ID
+    pub label_id: &'static str,
     /// exiftool 的文字描述（匹配数据）
     pub exif_tool_desc: &'static str,
     /// 数据类型
     pub value_type: ValueType,
+    /// 没有数据时使用的默认值
+    pub default: Option<&'static str>,
+}
+
+impl ExifInfo {
+    /// 当前语言的展示名称
+    pub fn dis(&self) -> &'static str {
+        i18n::text(self.label_id)
+    }
 }
 
-/*
-现在的目的是实现结构体的定义和现有数据的匹配，数据的解析等功能暂不考虑
-*/
-// 名称、描述、类型、默认值
-// 生成标签常量
-// macro_rules! generate_tag_constants {
-//     (
-//         $(
-//             // 捕获传递的属性【可空】
-//             // $(#[$attr:meta])*
-//             ($name:ident ,$value:expr,$defval:expr, $desc:expr,$ExifToolDesc:expr, $type:ty)
-//         )+
-//
-//     ) => (
-//         struct ExifTag {
-//             pub const $name: Tag = Tag($ctx, $num);
-//         }
-//
-//     );
-// }
-//
-// generate_tag_constants!();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::exif_utils::exif_reader;
+
+    fn tags(continue_on_error: bool, entries: &[(&str, &str)]) -> Tags {
+        let entries = entries
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        Tags::new(continue_on_error).with_entries(entries)
+    }
+
+    #[test]
+    fn test_typed_getters() {
+        let tags = tags(
+            false,
+            &[
+                ("ISO", "200"),
+                ("FocalLength", "50.0 mm"),
+                ("ExposureTime", "1/250"),
+                ("DateTimeOriginal", "2024:05:01 12:30:45"),
+                ("OffsetTime", "+09:00"),
+            ],
+        );
+        assert_eq!(tags.get_u32(&ExifToolDesc::ISO).unwrap(), Some(200));
+        assert_eq!(tags.get_f64(&ExifToolDesc::FOCAL_LENGTH).unwrap(), Some(50.0));
+        assert_eq!(
+            tags.get_rational(&ExifToolDesc::EXPOSURE_TIME).unwrap(),
+            Some(Rational::new(1, 250))
+        );
+        assert_eq!(tags.get_f64(&ExifToolDesc::EXPOSURE_TIME).unwrap(), Some(0.004));
+        let time = tags.get_datetime(&ExifToolDesc::DATE_TIME_ORIGINAL).unwrap();
+        assert_eq!(time.unwrap().to_rfc3339(), "2024-05-01T03:30:45+00:00");
+        assert_eq!(tags.get_u32(&ExifToolDesc::RATING).unwrap(), None);
+        // 类型不匹配
+        assert!(tags.get_u32(&ExifToolDesc::FOCAL_LENGTH).is_err());
+    }
+
+    #[test]
+    fn test_lenient_datetime() {
+        let info = &[
+            ("DateTimeOriginal", "2016-05-04T03:02"),
+            ("OffsetTime", "+00:00"),
+        ];
+        let strict = tags(true, info);
+        assert_eq!(strict.get_datetime(&ExifToolDesc::DATE_TIME_ORIGINAL).unwrap(), None);
+        assert!(strict.normalized().is_empty());
+
+        let lenient = tags(true, info).with_datetime_mode(DateTimeParseMode::Lenient);
+        let exif = lenient.pack_object().unwrap();
+        assert_eq!(
+            exif.date_time_original.unwrap().to_rfc3339(),
+            "2016-05-04T03:02:00+00:00"
+        );
+        assert!(exif.date_time_normalized);
+
+        assert_eq!(
+            normalize_exif_datetime("2016/5/4 03:02:01.5Z").as_deref(),
+            Some("2016:05:04 03:02:01.5+00:00")
+        );
+        assert_eq!(normalize_exif_datetime("2016-05-04"), None);
+        // 标准格式不记录规范化
+        let standard = tags(true, &[("DateTimeOriginal", "2016:05:04 03:02:01")])
+            .with_datetime_mode(DateTimeParseMode::Lenient);
+        assert!(!standard.pack_object().unwrap().date_time_normalized);
+    }
+
+    #[test]
+    fn test_continue_on_error() {
+        let tags = tags(true, &[("ISO", "Auto"), ("ExposureTime", "0.5")]);
+        assert_eq!(tags.get_u32(&ExifToolDesc::ISO).unwrap(), None);
+        assert_eq!(
+            tags.get_rational(&ExifToolDesc::EXPOSURE_TIME).unwrap(),
+            Some(Rational::new(1, 2))
+        );
+        let errors = tags.errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].key, "ISO");
+        assert_eq!(errors[0].value, "Auto");
+    }
+
+    #[test]
+    fn test_tag_lookup() {
+        assert_eq!(ExifToolDesc::EXIF_INFOS.len(), EXIF_TAG_COUNT);
+        let info = ExifToolDesc::by_id(0x9003).unwrap();
+        assert_eq!(info.name, "DateTimeOriginal");
+        assert_eq!(info.value_type, ValueType::Time);
+        assert_eq!(ExifToolDesc::by_name("DateTimeOriginal").unwrap().id, Some(0x9003));
+        assert_eq!(ExifToolDesc::by_name("Camera Model Name").unwrap().id, Some(0x0110));
+        assert_eq!(ExifToolDesc::by_name("Description").unwrap().id, None);
+        assert_eq!(ExifToolDesc::OFFSET_TIME.default, Some(DEFAULT_OFFSET));
+        assert!(ExifToolDesc::by_id(0xFFFF).is_none());
+    }
+
+    #[test]
+    fn test_with_entries() {
+        let entries = vec![
+            ("Model".to_string(), "Canon EOS R5".to_string()),
+            ("DateTimeOriginal".to_string(), "2024:05:01 12:30:45".to_string()),
+            ("OffsetTimeOriginal".to_string(), "+09:00".to_string()),
+            ("ISO".to_string(), "400".to_string()),
+        ];
+        let tags = Tags::new(false).with_entries(entries);
+        // 已知标签按文字描述读取
+        assert_eq!(tags.get("Camera Model Name").as_deref(), Some("Canon EOS R5"));
+        assert_eq!(tags.get_u32(&ExifToolDesc::ISO).unwrap(), Some(400));
+        assert!(tags
+            .get_datetime(&ExifToolDesc::DATE_TIME_ORIGINAL)
+            .unwrap()
+            .is_some());
+        // 未知标签保留标签名称
+        assert_eq!(tags.get("OffsetTimeOriginal").as_deref(), Some("+09:00"));
+        assert_eq!(tags.entries.len(), 4);
+    }
+
+    #[test]
+    fn test_with_exif() {
+        // 小端，IFD0 有 Make 和 EXIF IFD 指针；EXIF IFD 有曝光时间、拍摄时间、时区和闪光灯
+        let mut tiff = b"II*\0\x08\0\0\0".to_vec();
+        tiff.extend(2u16.to_le_bytes());
+        tiff.extend([0x0F, 0x01, 2, 0, 6, 0, 0, 0, 38, 0, 0, 0]);
+        tiff.extend([0x69, 0x87, 4, 0, 1, 0, 0, 0, 44, 0, 0, 0]);
+        tiff.extend(0u32.to_le_bytes());
+        tiff.extend(b"Canon\0");
+        tiff.extend(4u16.to_le_bytes());
+        tiff.extend([0x9A, 0x82, 5, 0, 1, 0, 0, 0, 125, 0, 0, 0]);
+        tiff.extend([0x03, 0x90, 2, 0, 20, 0, 0, 0, 98, 0, 0, 0]);
+        tiff.extend([0x11, 0x90, 2, 0, 7, 0, 0, 0, 118, 0, 0, 0]);
+        tiff.extend([0x09, 0x92, 3, 0, 1, 0, 0, 0, 0x19, 0, 0, 0]);
+        tiff.extend(0u32.to_le_bytes());
+        tiff.extend(b"2024:05:01 20:00:00\0");
+        tiff.extend(b"+02:00\0");
+        tiff.extend(1u32.to_le_bytes());
+        tiff.extend(250u32.to_le_bytes());
+
+        let (fields, errors, little_endian) = exif_reader::parse_exif_lenient(&tiff);
+        assert!(errors.is_empty());
+        let entries = vec![
+            ("Make".to_string(), "Nikon".to_string()),
+            ("ISO".to_string(), "400".to_string()),
+        ];
+        let tags = Tags::new(false)
+            .with_exif(&Exif::new(fields, little_endian))
+            .with_extra_entries(entries);
+        let exif = tags.pack_object().unwrap();
+        // 内置解析的值优先，exiftool 只补充缺少的标签
+        assert_eq!(exif.make.as_deref(), Some("Canon"));
+        assert_eq!(exif.iso, Some(400));
+        assert_eq!(exif.exposure_time, Some(Rational::new(1, 250)));
+        assert_eq!(exif.flash.as_deref(), Some("Auto, Fired"));
+        assert_eq!(exif.flash_code, Some(0x19));
+        // 2024-05-01 18:00:00 UTC
+        assert_eq!(exif.date_time_original.unwrap().timestamp(), 1714586400);
+
+        let dms = |values: &[(u32, u32)]| {
+            let values: Vec<Rational> = values.iter().map(|(n, d)| Rational::new(*n, *d)).collect();
+            dms_text(&values)
+        };
+        assert_eq!(
+            dms(&[(22, 1), (30, 1), (3600, 100)]).as_deref(),
+            Some("22 deg 30' 36.00\"")
+        );
+        // 度带小数
+        assert_eq!(
+            dms(&[(225, 10), (0, 1), (0, 1)]).as_deref(),
+            Some("22 deg 30' 0.00\"")
+        );
+        assert_eq!(
+            dms(&[(59, 1), (59, 1), (599999, 10000)]).as_deref(),
+            Some("60 deg 0' 0.00\"")
+        );
+        assert_eq!(dms(&[]), None);
+    }
+
+    #[test]
+    fn test_exif3_preferred() {
+        let exif3 = tags(
+            false,
+            &[
+                ("Artist", "DU"),
+                ("Photographer", "杜"),
+                ("ImageDescription", "IMG_0001"),
+                ("ImageTitle", "港口"),
+            ],
+        );
+        assert_eq!(exif3.get_artist().as_deref(), Some("杜"));
+        assert_eq!(exif3.get_caption().as_deref(), Some("港口"));
+
+        let blank = tags(false, &[("Artist", "DU"), ("Photographer", "")]);
+        assert_eq!(blank.get_artist().as_deref(), Some("DU"));
+    }
+
+    #[test]
+    fn test_camera_placeholder_caption() {
+        let olympus = tags(false, &[("ImageDescription", "OLYMPUS DIGITAL CAMERA   ")]);
+        assert_eq!(olympus.get_caption(), None);
+        let model = tags(
+            false,
+            &[("Model", "DMC-GX7"), ("ImageDescription", "dmc-gx7")],
+        );
+        assert_eq!(model.get_caption(), None);
+        let title = tags(
+            false,
+            &[("ImageTitle", "SONY DSC"), ("ImageDescription", "港口日落")],
+        );
+        assert_eq!(title.get_caption().as_deref(), Some("港口日落"));
+        let (make, model) = (Some("Canon"), Some("EOS R5"));
+        assert!(is_camera_placeholder("Canon EOS R5", make, model));
+        assert!(!is_camera_placeholder("Canon 镜头测试", make, model));
+    }
+
+    #[test]
+    fn test_pack_object() {
+        let tags = tags(
+            true,
+            &[
+                ("Make", "Canon"),
+                ("ISO", "400"),
+                ("ExposureTime", "1/60"),
+                ("ImageWidth", "6000"),
+                ("GPSLatitudeRef", "South"),
+                ("GPSLatitude", "33 deg 51' 54.00\" S"),
+                ("GPSLongitudeRef", "East"),
+                ("GPSLongitude", "151 deg 12' 36.00\" E"),
+                ("ImageDescription", "Harbour at dusk"),
+                ("Description", ""),
+                ("Flash", "Auto, Fired"),
+                ("MeteringMode", "Spot"),
+                ("WhiteBalance", "Manual"),
+            ],
+        );
+        let exif = tags.pack_object().unwrap();
+        assert_eq!(exif.make.as_deref(), Some("Canon"));
+        assert_eq!(exif.flash_code, Some(0x19));
+        assert_eq!(exif.metering_mode_code, Some(3));
+        assert_eq!(exif.white_balance_code, Some(1));
+        assert_eq!(exif.exposure_program_code, None);
+        assert_eq!(exif.iso, Some(400));
+        assert_eq!(exif.exposure_time, Some(Rational::new(1, 60)));
+        assert_eq!(exif.image_width, Some(6000));
+        assert_eq!(exif.caption.as_deref(), Some("Harbour at dusk"));
+        let gps = exif.gps_info.clone().unwrap();
+        assert!((gps.latitude + 33.865).abs() < 1e-9);
+        assert!((gps.longitude - 151.21).abs() < 1e-9);
+
+        let json = JsonUtil::stringify(&exif).unwrap();
+        assert!(json.contains("\"exposureTime\":{\"numerator\":1,\"denominator\":60}"));
+        let parsed: ImgExif = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, exif);
+    }
+}
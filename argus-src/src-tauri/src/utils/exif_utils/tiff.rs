@@ -0,0 +1,820 @@
+use crate::utils::exif_utils::value::{self, Rational, SRational, Value};
+use anyhow::{anyhow, Result};
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// 字段类型 BYTE
+pub const TYPE_BYTE: u16 = 1;
+/// 字段类型 ASCII
+pub const TYPE_ASCII: u16 = 2;
+/// 字段类型 SHORT
+pub const TYPE_SHORT: u16 = 3;
+/// 字段类型 LONG
+pub const TYPE_LONG: u16 = 4;
+/// 字段类型 RATIONAL
+pub const TYPE_RATIONAL: u16 = 5;
+/// 字段类型 SBYTE
+pub const TYPE_SBYTE: u16 = 6;
+/// 字段类型 UNDEFINED
+pub const TYPE_UNDEFINED: u16 = 7;
+/// 字段类型 SSHORT
+pub const TYPE_SSHORT: u16 = 8;
+/// 字段类型 SLONG
+pub const TYPE_SLONG: u16 = 9;
+/// 字段类型 SRATIONAL
+pub const TYPE_SRATIONAL: u16 = 10;
+/// 字段类型 FLOAT
+pub const TYPE_FLOAT: u16 = 11;
+/// 字段类型 DOUBLE
+pub const TYPE_DOUBLE: u16 = 12;
+/// 字段类型 IFD【值为 IFD 偏移量，与 LONG 相同】
+pub const TYPE_IFD: u16 = 13;
+/// 字段类型 UTF-8【Exif 3.0 新增，以 NUL 结尾】
+pub const TYPE_UTF8: u16 = 129;
+
+/// 标签 ImageWidth
+pub const TAG_IMAGE_WIDTH: u16 = 0x0100;
+/// 标签 ImageLength（高度）
+pub const TAG_IMAGE_LENGTH: u16 = 0x0101;
+/// 标签 SubIFDs【金字塔 TIFF、DNG 的缩小图或原始数据】
+pub const TAG_SUB_IFDS: u16 = 0x014A;
+
+/// 每个 IFD 条目的长度
+const ENTRY_SIZE: usize = 12;
+
+/// 解析限制【防止损坏或恶意构造的文件导致大量读取、死循环】
+///
+/// 默认值适用于照片，多页扫描件、金字塔 TIFF 需要调用方按需放宽
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// 最多读取的 IFD 数量
+    pub max_ifds: usize,
+    /// 单个 IFD 最多包含的条目数量【超出时视为损坏】
+    pub max_entries: usize,
+    /// 单个值最多占用的字节数【超出时不读取】
+    pub max_value_size: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_ifds: 8,
+            max_entries: 1024,
+            max_value_size: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// TIFF 结构读取器【EXIF 数据也是 TIFF 结构】
+///
+/// 只借用原始数据，ASCII、UNDEFINED 字段直接返回原始数据中的切片，不复制
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+    limits: Limits,
+}
+
+impl<'a> Parser<'a> {
+    /// 校验字节序标记和 TIFF 标识 42【使用默认的解析限制】
+    pub fn new(data: &'a [u8]) -> Result<Self> {
+        Self::with_limits(data, Limits::default())
+    }
+
+    /// 使用指定的解析限制
+    /// - limits 解析限制
+    pub fn with_limits(data: &'a [u8], limits: Limits) -> Result<Self> {
+        let little_endian = match data.get(0..2) {
+            Some(b"II") => true,
+            Some(b"MM") => false,
+            _ => return Err(anyhow!("不是 TIFF 数据")),
+        };
+        let parser = Parser {
+            data,
+            little_endian,
+            limits,
+        };
+        if parser.read_u16(2) != Some(42) {
+            return Err(anyhow!("不是 TIFF 数据"));
+        }
+        Ok(parser)
+    }
+
+    /// 是否为小端字节序
+    pub fn little_endian(&self) -> bool {
+        self.little_endian
+    }
+
+    /// 第一个 IFD（IFD0）
+    pub fn ifd0(&self) -> Option<Ifd<'a>> {
+        self.ifd(self.read_u32(4)? as usize)
+    }
+
+    /// 读取指定偏移量的 IFD【偏移量越界、条目数量超出限制时返回 None】
+    pub fn ifd(&self, offset: usize) -> Option<Ifd<'a>> {
+        let count = self.read_u16(offset)? as usize;
+        if count > self.limits.max_entries {
+            return None;
+        }
+        let end = offset + 2 + count * ENTRY_SIZE;
+        if end > self.data.len() {
+            return None;
+        }
+        Some(Ifd {
+            parser: *self,
+            offset,
+            count,
+        })
+    }
+
+    /// 按链接顺序读取所有 IFD【多页 TIFF 每页一个 IFD，超出数量限制或出现循环时停止】
+    pub fn ifds(&self) -> Vec<Ifd<'a>> {
+        let mut result: Vec<Ifd<'a>> = Vec::new();
+        let mut next = self.ifd0();
+        while let Some(ifd) = next {
+            if result.len() >= self.limits.max_ifds || result.iter().any(|x| x.offset == ifd.offset)
+            {
+                break;
+            }
+            next = ifd.next_offset().and_then(|x| self.ifd(x));
+            result.push(ifd);
+        }
+        result
+    }
+
+    /// 所有页面的尺寸【按页面顺序，每页之后是它的 SubIFDs，总数不超过 IFD 数量限制】
+    ///
+    /// 没有长宽标签的 IFD 不计入
+    pub fn pages(&self) -> Vec<Page> {
+        let mut result: Vec<Page> = Vec::new();
+        for (index, ifd) in self.ifds().into_iter().enumerate() {
+            let subs = ifd
+                .find(TAG_SUB_IFDS)
+                .map(|x| x.as_u32_values())
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|x| self.ifd(x as usize));
+            for (sub_ifd, ifd) in std::iter::once((false, ifd)).chain(subs.map(|x| (true, x))) {
+                if result.len() >= self.limits.max_ifds {
+                    return result;
+                }
+                if let Some((width, height)) = ifd.dimensions() {
+                    result.push(Page {
+                        index,
+                        sub_ifd,
+                        offset: ifd.offset,
+                        width,
+                        height,
+                    });
+                }
+            }
+        }
+        result
+    }
+
+    /// 像素最多的页面【相同时取靠前的】
+    pub fn largest_page(&self) -> Option<Page> {
+        largest_page(self.pages())
+    }
+
+    /// 从文件中读取指定偏移量的 IFD【只读取条目和 SubIFDs 的偏移量，读取失败时返回 None】
+    fn read_ifd<R: Read + Seek>(&self, reader: &mut R, offset: usize) -> Option<FileIfd> {
+        reader.seek(SeekFrom::Start(offset as u64)).ok()?;
+        let mut count = [0u8; 2];
+        reader.read_exact(&mut count).ok()?;
+        let count = self.decode_u16(count) as usize;
+        if count > self.limits.max_entries {
+            return None;
+        }
+        let mut entries = vec![0u8; count * ENTRY_SIZE + 4];
+        reader.read_exact(&mut entries).ok()?;
+        let (entries, next) = entries.split_at(count * ENTRY_SIZE);
+
+        let (mut width, mut height, mut sub_ifds) = (None, None, None);
+        for entry in entries.chunks_exact(ENTRY_SIZE) {
+            let tag = self.decode_u16([entry[0], entry[1]]);
+            let field_type = self.decode_u16([entry[2], entry[3]]);
+            let count = self.decode_u32([entry[4], entry[5], entry[6], entry[7]]);
+            let value = [entry[8], entry[9], entry[10], entry[11]];
+            let first = match field_type {
+                _ if count == 0 => None,
+                TYPE_SHORT => Some(u32::from(self.decode_u16([value[0], value[1]]))),
+                TYPE_LONG | TYPE_IFD => Some(self.decode_u32(value)),
+                _ => None,
+            };
+            match tag {
+                TAG_IMAGE_WIDTH => width = width.or(first),
+                TAG_IMAGE_LENGTH => height = height.or(first),
+                TAG_SUB_IFDS if sub_ifds.is_none() => {
+                    sub_ifds = Some(self.read_offsets(reader, field_type, count, value));
+                }
+                _ => {}
+            }
+        }
+        Some(FileIfd {
+            offset,
+            dimensions: width.zip(height),
+            sub_ifds: sub_ifds.unwrap_or_default(),
+            next: self.decode_u32(next.try_into().ok()?) as usize,
+        })
+    }
+
+    /// 从文件中读取 LONG、IFD 类型的偏移量列表【不超过 4 字节时保存在条目中】
+    fn read_offsets<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        field_type: u16,
+        count: u32,
+        value: [u8; 4],
+    ) -> Vec<u32> {
+        if field_type != TYPE_LONG && field_type != TYPE_IFD {
+            return Vec::new();
+        }
+        let size = count as usize * 4;
+        if size <= 4 {
+            return (count == 1)
+                .then(|| self.decode_u32(value))
+                .into_iter()
+                .collect();
+        }
+        if size > self.limits.max_value_size {
+            return Vec::new();
+        }
+        let mut data = vec![0u8; size];
+        let read = reader
+            .seek(SeekFrom::Start(u64::from(self.decode_u32(value))))
+            .and_then(|_| reader.read_exact(&mut data));
+        if read.is_err() {
+            return Vec::new();
+        }
+        data.chunks_exact(4)
+            .map(|b| self.decode_u32([b[0], b[1], b[2], b[3]]))
+            .collect()
+    }
+
+    fn read_u16(&self, at: usize) -> Option<u16> {
+        Some(self.decode_u16(self.data.get(at..at + 2)?.try_into().ok()?))
+    }
+
+    fn read_u32(&self, at: usize) -> Option<u32> {
+        Some(self.decode_u32(self.data.get(at..at + 4)?.try_into().ok()?))
+    }
+
+    fn decode_u16(&self, b: [u8; 2]) -> u16 {
+        if self.little_endian {
+            u16::from_le_bytes(b)
+        } else {
+            u16::from_be_bytes(b)
+        }
+    }
+
+    fn decode_u32(&self, b: [u8; 4]) -> u32 {
+        if self.little_endian {
+            u32::from_le_bytes(b)
+        } else {
+            u32::from_be_bytes(b)
+        }
+    }
+
+    fn decode_u64(&self, b: [u8; 8]) -> u64 {
+        if self.little_endian {
+            u64::from_le_bytes(b)
+        } else {
+            u64::from_be_bytes(b)
+        }
+    }
+}
+
+/// 图像文件目录
+#[derive(Debug, Clone, Copy)]
+pub struct Ifd<'a> {
+    parser: Parser<'a>,
+    offset: usize,
+    count: usize,
+}
+
+impl<'a> Ifd<'a> {
+    /// 所有条目
+    pub fn entries(&self) -> impl Iterator<Item = Entry<'a>> + '_ {
+        (0..self.count).filter_map(|i| self.entry(i))
+    }
+
+    /// 查找标签
+    pub fn find(&self, tag: u16) -> Option<Entry<'a>> {
+        self.entries().find(|x| x.tag == tag)
+    }
+
+    /// 下一个 IFD 的偏移量【0 表示没有下一个】
+    pub fn next_offset(&self) -> Option<usize> {
+        let next = self
+            .parser
+            .read_u32(self.offset + 2 + self.count * ENTRY_SIZE)?;
+        (next != 0).then_some(next as usize)
+    }
+
+    /// 图像宽度和高度【缺少任一标签时返回 None】
+    pub fn dimensions(&self) -> Option<(u32, u32)> {
+        let width = self.find(TAG_IMAGE_WIDTH)?.as_u32()?;
+        let height = self.find(TAG_IMAGE_LENGTH)?.as_u32()?;
+        Some((width, height))
+    }
+
+    fn entry(&self, index: usize) -> Option<Entry<'a>> {
+        let at = self.offset + 2 + index * ENTRY_SIZE;
+        Some(Entry {
+            parser: self.parser,
+            tag: self.parser.read_u16(at)?,
+            field_type: self.parser.read_u16(at + 2)?,
+            count: self.parser.read_u32(at + 4)?,
+            offset: at,
+        })
+    }
+}
+
+/// IFD 条目
+#[derive(Debug, Clone, Copy)]
+pub struct Entry<'a> {
+    parser: Parser<'a>,
+    /// 标签
+    pub tag: u16,
+    /// 字段类型
+    pub field_type: u16,
+    /// 值的数量
+    pub count: u32,
+    /// 条目在数据中的偏移量【用于直接修改值】
+    pub offset: usize,
+}
+
+impl<'a> Entry<'a> {
+    /// 值的原始字节【不超过 4 字节时保存在条目中，否则保存在偏移量指向的位置】
+    pub fn value_bytes(&self) -> Option<&'a [u8]> {
+        let (start, size) = self.value_range()?;
+        self.parser.data.get(start..start.checked_add(size)?)
+    }
+
+    /// ASCII 字段的值【去掉末尾的 NUL，不是有效的 UTF-8 时返回 None】
+    pub fn as_ascii(&self) -> Option<&'a str> {
+        if self.field_type != TYPE_ASCII {
+            return None;
+        }
+        let bytes = self.value_bytes()?;
+        let end = bytes.iter().position(|x| *x == 0).unwrap_or(bytes.len());
+        std::str::from_utf8(&bytes[..end]).ok()
+    }
+
+    /// ASCII 或 UTF-8 字段的值【Exif 3.0 的文字字段可能使用 UTF-8 类型】
+    pub fn as_text(&self) -> Option<&'a str> {
+        if self.field_type != TYPE_ASCII && self.field_type != TYPE_UTF8 {
+            return None;
+        }
+        let bytes = self.value_bytes()?;
+        let end = bytes.iter().position(|x| *x == 0).unwrap_or(bytes.len());
+        std::str::from_utf8(&bytes[..end]).ok()
+    }
+
+    /// UNDEFINED 字段的值
+    pub fn as_undefined(&self) -> Option<&'a [u8]> {
+        (self.field_type == TYPE_UNDEFINED)
+            .then(|| self.value_bytes())
+            .flatten()
+    }
+
+    /// SHORT、LONG、IFD 字段的第一个值
+    pub fn as_u32(&self) -> Option<u32> {
+        if self.count == 0 {
+            return None;
+        }
+        let (start, _) = self.value_range()?;
+        match self.field_type {
+            TYPE_SHORT => self.parser.read_u16(start).map(u32::from),
+            TYPE_LONG | TYPE_IFD => self.parser.read_u32(start),
+            _ => None,
+        }
+    }
+
+    /// SHORT、LONG、IFD 字段的所有值【值无法读取时返回空】
+    pub fn as_u32_values(&self) -> Vec<u32> {
+        let Some((start, _)) = self.value_range() else {
+            return Vec::new();
+        };
+        (0..self.count as usize)
+            .map_while(|i| match self.field_type {
+                TYPE_SHORT => self.parser.read_u16(start + i * 2).map(u32::from),
+                TYPE_LONG | TYPE_IFD => self.parser.read_u32(start + i * 4),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// 按字段类型解码所有值【复制到新的数组中；未知类型返回 `Value::Unknown`】
+    pub fn value(&self) -> Result<Value> {
+        if value::get_type_info(self.field_type).is_none() {
+            return Ok(Value::Unknown(self.field_type, self.count));
+        }
+        let bytes = self
+            .value_bytes()
+            .ok_or_else(|| anyhow!("标签 0x{:04X} 的值超出数据范围或长度限制", self.tag))?;
+        let parser = &self.parser;
+        let u16s = || {
+            bytes
+                .chunks_exact(2)
+                .map(|b| parser.decode_u16([b[0], b[1]]))
+        };
+        let u32s = || {
+            bytes
+                .chunks_exact(4)
+                .map(|b| parser.decode_u32([b[0], b[1], b[2], b[3]]))
+        };
+        let pairs = || {
+            u32s()
+                .collect::<Vec<u32>>()
+                .chunks_exact(2)
+                .map(|x| (x[0], x[1]))
+                .collect::<Vec<_>>()
+        };
+        Ok(match self.field_type {
+            TYPE_BYTE => Value::Byte(bytes.to_vec()),
+            TYPE_ASCII | TYPE_UTF8 => {
+                let text = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+                Value::Ascii(text.split(|x| *x == 0).map(|x| x.to_vec()).collect())
+            }
+            TYPE_SHORT => Value::Short(u16s().collect()),
+            TYPE_LONG | TYPE_IFD => Value::Long(u32s().collect()),
+            TYPE_RATIONAL => Value::Rational(
+                pairs()
+                    .into_iter()
+                    .map(|(n, d)| Rational::new(n, d))
+                    .collect(),
+            ),
+            TYPE_SBYTE => Value::SByte(bytes.iter().map(|x| *x as i8).collect()),
+            TYPE_SSHORT => Value::SShort(u16s().map(|x| x as i16).collect()),
+            TYPE_SLONG => Value::SLong(u32s().map(|x| x as i32).collect()),
+            TYPE_SRATIONAL => Value::SRational(
+                pairs()
+                    .into_iter()
+                    .map(|(n, d)| SRational::new(n as i32, d as i32))
+                    .collect(),
+            ),
+            TYPE_FLOAT => Value::Float(u32s().map(f32::from_bits).collect()),
+            TYPE_DOUBLE => Value::Double(
+                bytes
+                    .chunks_exact(8)
+                    .map(|b| f64::from_bits(parser.decode_u64(b.try_into().unwrap_or_default())))
+                    .collect(),
+            ),
+            _ => Value::Undefined(bytes.to_vec()),
+        })
+    }
+
+    /// 值的起始位置和长度
+    fn value_range(&self) -> Option<(usize, usize)> {
+        let unit = value::get_type_info(self.field_type)?.size;
+        let size = unit.checked_mul(self.count as usize)?;
+        if size > self.parser.limits.max_value_size {
+            return None;
+        }
+        let start = if size <= 4 {
+            self.offset + 8
+        } else {
+            self.parser.read_u32(self.offset + 8)? as usize
+        };
+        Some((start, size))
+    }
+}
+
+/// TIFF 页面
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    /// 页序号【SubIFD 为所属页面的序号】
+    pub index: usize,
+    /// 是否为 SubIFD
+    pub sub_ifd: bool,
+    /// IFD 在数据中的偏移量
+    pub offset: usize,
+    /// 宽度
+    pub width: u32,
+    /// 高度
+    pub height: u32,
+}
+
+impl Page {
+    /// 像素数量
+    pub fn area(&self) -> u64 {
+        self.width as u64 * self.height as u64
+    }
+}
+
+/// 从文件中读取的 IFD
+struct FileIfd {
+    offset: usize,
+    dimensions: Option<(u32, u32)>,
+    sub_ifds: Vec<u32>,
+    next: usize,
+}
+
+/// 像素最多的页面【相同时取靠前的】
+pub fn largest_page(pages: Vec<Page>) -> Option<Page> {
+    pages
+        .into_iter()
+        .reduce(|a, b| if b.area() > a.area() { b } else { a })
+}
+
+/// 从文件中读取所有页面的尺寸【结果与 `Parser::pages` 相同】
+///
+/// 按偏移量定位读取各个 IFD，不读取图像数据，几百 MB 的多页 TIFF 也只读取很少的数据
+/// - limits 解析限制
+pub fn read_pages<R: Read + Seek>(reader: &mut R, limits: Limits) -> Result<Vec<Page>> {
+    let mut header = [0u8; 8];
+    reader.seek(SeekFrom::Start(0))?;
+    reader.read_exact(&mut header)?;
+    let parser = Parser::with_limits(&header, limits)?;
+
+    let mut ifds: Vec<FileIfd> = Vec::new();
+    let mut next = parser.read_u32(4).unwrap_or(0) as usize;
+    while next != 0 && ifds.len() < limits.max_ifds && !ifds.iter().any(|x| x.offset == next) {
+        let Some(ifd) = parser.read_ifd(reader, next) else {
+            break;
+        };
+        next = ifd.next;
+        ifds.push(ifd);
+    }
+
+    let mut result: Vec<Page> = Vec::new();
+    for (index, ifd) in ifds.iter().enumerate() {
+        let mut candidates = vec![(false, ifd.offset, ifd.dimensions)];
+        for offset in &ifd.sub_ifds {
+            if result.len() + candidates.len() > limits.max_ifds {
+                break;
+            }
+            if let Some(sub) = parser.read_ifd(reader, *offset as usize) {
+                candidates.push((true, sub.offset, sub.dimensions));
+            }
+        }
+        for (sub_ifd, offset, dimensions) in candidates {
+            if result.len() >= limits.max_ifds {
+                return Ok(result);
+            }
+            if let Some((width, height)) = dimensions {
+                result.push(Page {
+                    index,
+                    sub_ifd,
+                    offset,
+                    width,
+                    height,
+                });
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// 把 IFD0 偏移量替换为指定页面的读取器
+///
+/// 只解码第一页的解码器会改为解码指定的页面，与 `set_ifd0_offset` 相同，但不需要把整个文件读入内存
+pub struct Ifd0Reader<R> {
+    inner: R,
+    /// 替换后的 IFD0 偏移量（文件中的第 4 ~ 8 字节）
+    ifd0: [u8; 4],
+    position: u64,
+}
+
+impl<R: Read + Seek> Ifd0Reader<R> {
+    /// - offset 页面的 IFD 偏移量
+    pub fn new(mut inner: R, offset: usize) -> Result<Self> {
+        let mut header = [0u8; 8];
+        inner.seek(SeekFrom::Start(0))?;
+        inner.read_exact(&mut header)?;
+        if !set_ifd0_offset(&mut header, offset) {
+            return Err(anyhow!("不是 TIFF 数据或页面偏移量无效"));
+        }
+        inner.seek(SeekFrom::Start(0))?;
+        Ok(Ifd0Reader {
+            inner,
+            ifd0: [header[4], header[5], header[6], header[7]],
+            position: 0,
+        })
+    }
+}
+
+impl<R: Read> Read for Ifd0Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if self.position < 8 {
+            for (i, byte) in buf[..n].iter_mut().enumerate() {
+                let at = self.position + i as u64;
+                if (4..8).contains(&at) {
+                    *byte = self.ifd0[at as usize - 4];
+                }
+            }
+        }
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: Seek> Seek for Ifd0Reader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.position = self.inner.seek(pos)?;
+        Ok(self.position)
+    }
+}
+
+/// 修改 IFD0 的偏移量【只解码第一页的解码器会改为解码指定的页面】
+/// - offset 页面的 IFD 偏移量
+pub fn set_ifd0_offset(tiff: &mut [u8], offset: usize) -> bool {
+    let little_endian = match Parser::new(tiff) {
+        Ok(parser) => parser.little_endian(),
+        Err(_) => return false,
+    };
+    let (Ok(offset), Some(target)) = (u32::try_from(offset), tiff.get_mut(4..8)) else {
+        return false;
+    };
+    let value = if little_endian {
+        offset.to_le_bytes()
+    } else {
+        offset.to_be_bytes()
+    };
+    target.copy_from_slice(&value);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borrowed_values() {
+        // 小端，IFD0 有 Make（ASCII，超过 4 字节）、ExifVersion（UNDEFINED）、Orientation（SHORT）
+        let mut tiff = b"II*\0\x08\0\0\0".to_vec();
+        tiff.extend(3u16.to_le_bytes());
+        tiff.extend([0x0F, 0x01, 2, 0, 6, 0, 0, 0, 50, 0, 0, 0]);
+        tiff.extend([0x00, 0x90, 7, 0, 4, 0, 0, 0]);
+        tiff.extend(b"0232");
+        tiff.extend([0x12, 0x01, 3, 0, 1, 0, 0, 0, 6, 0, 0, 0]);
+        tiff.extend(0u32.to_le_bytes());
+        tiff.extend(b"Canon\0");
+
+        let parser = Parser::new(&tiff).unwrap();
+        let ifd = parser.ifd0().unwrap();
+        let make = ifd.find(0x010F).unwrap().as_ascii().unwrap();
+        assert_eq!(make, "Canon");
+        // 返回的是原始数据中的切片
+        assert!(tiff.as_ptr_range().contains(&make.as_ptr()));
+        assert_eq!(ifd.find(0x9000).unwrap().as_undefined(), Some(&b"0232"[..]));
+        assert_eq!(ifd.find(0x0112).unwrap().as_u32(), Some(6));
+
+        assert!(Parser::new(b"II+\0").is_err());
+    }
+
+    #[test]
+    fn test_entry_value() {
+        // 大端，IFD0 有 XResolution（RATIONAL）、ExposureBias（SRATIONAL）、Make（两个字符串）
+        let mut tiff = b"MM\0*\0\0\0\x08".to_vec();
+        tiff.extend(3u16.to_be_bytes());
+        tiff.extend([0x01, 0x1A, 0, 5, 0, 0, 0, 1, 0, 0, 0, 50]);
+        tiff.extend([0x92, 0x04, 0, 10, 0, 0, 0, 1, 0, 0, 0, 58]);
+        tiff.extend([0x01, 0x0F, 0, 2, 0, 0, 0, 4, b'a', 0, b'b', 0]);
+        tiff.extend(0u32.to_be_bytes());
+        tiff.extend(72u32.to_be_bytes());
+        tiff.extend(1u32.to_be_bytes());
+        tiff.extend((-1i32).to_be_bytes());
+        tiff.extend(3i32.to_be_bytes());
+
+        let ifd = Parser::new(&tiff).unwrap().ifd0().unwrap();
+        let resolution = ifd.find(0x011A).unwrap().value().unwrap();
+        assert_eq!(resolution, Value::Rational(vec![Rational::new(72, 1)]));
+        let bias = ifd.find(0x9204).unwrap().value().unwrap();
+        assert_eq!(bias, Value::SRational(vec![SRational::new(-1, 3)]));
+        let make = ifd.find(0x010F).unwrap().value().unwrap();
+        assert_eq!(make, Value::Ascii(vec![b"a".to_vec(), b"b".to_vec()]));
+        assert_eq!(make.as_ascii_str().unwrap(), "a");
+    }
+
+    #[test]
+    fn test_utf8_text() {
+        // 小端，IFD0 只有 Artist（UTF-8 类型）
+        let name = "杜".as_bytes();
+        let mut tiff = b"II*\0\x08\0\0\0".to_vec();
+        tiff.extend(1u16.to_le_bytes());
+        tiff.extend([0x3B, 0x01, 129, 0, name.len() as u8 + 1, 0, 0, 0]);
+        tiff.extend(name);
+        tiff.push(0);
+        tiff.extend(0u32.to_le_bytes());
+
+        let parser = Parser::new(&tiff).unwrap();
+        let artist = parser.ifd0().unwrap().find(0x013B).unwrap();
+        assert_eq!(artist.as_text(), Some("杜"));
+        assert_eq!(artist.as_ascii(), None);
+    }
+
+    #[test]
+    fn test_ifd_limits() {
+        // 大端，3 个依次链接的 IFD，每个只有一个条目
+        let mut tiff = b"MM\0*\0\0\0\x08".to_vec();
+        for index in 0..3u32 {
+            let next = if index < 2 { 8 + (index + 1) * 18 } else { 0 };
+            tiff.extend(1u16.to_be_bytes());
+            tiff.extend([0x01, 0x00, 0, 4, 0, 0, 0, 1]);
+            tiff.extend((index + 1).to_be_bytes());
+            tiff.extend(next.to_be_bytes());
+        }
+
+        assert_eq!(Parser::new(&tiff).unwrap().ifds().len(), 3);
+        let limits = Limits {
+            max_ifds: 2,
+            ..Default::default()
+        };
+        let pages = Parser::with_limits(&tiff, limits).unwrap().ifds();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[1].find(0x0100).unwrap().as_u32(), Some(2));
+
+        let limits = Limits {
+            max_entries: 0,
+            ..Default::default()
+        };
+        assert!(Parser::with_limits(&tiff, limits).unwrap().ifd0().is_none());
+        let limits = Limits {
+            max_value_size: 2,
+            ..Default::default()
+        };
+        let parser = Parser::with_limits(&tiff, limits).unwrap();
+        assert_eq!(parser.ifd0().unwrap().find(0x0100).unwrap().as_u32(), None);
+    }
+
+    #[test]
+    fn test_pages() {
+        // 小端，IFD0 100x50 且有一个 400x200 的 SubIFD，IFD1 300x300
+        let entry = |tag: u16, field_type: u16, count: u32, value: u32| {
+            let mut x = tag.to_le_bytes().to_vec();
+            x.extend(field_type.to_le_bytes());
+            x.extend(count.to_le_bytes());
+            x.extend(value.to_le_bytes());
+            x
+        };
+        let ifd = |entries: Vec<Vec<u8>>, next: u32| {
+            let mut x = (entries.len() as u16).to_le_bytes().to_vec();
+            entries.iter().for_each(|e| x.extend(e));
+            x.extend(next.to_le_bytes());
+            x
+        };
+        // IFD0 在 8，3 个条目共 42 字节；SubIFD 在 50，IFD1 在 80
+        let mut tiff = b"II*\0\x08\0\0\0".to_vec();
+        tiff.extend(ifd(
+            vec![
+                entry(TAG_IMAGE_WIDTH, TYPE_SHORT, 1, 100),
+                entry(TAG_IMAGE_LENGTH, TYPE_SHORT, 1, 50),
+                entry(TAG_SUB_IFDS, TYPE_IFD, 1, 50),
+            ],
+            80,
+        ));
+        tiff.extend(ifd(
+            vec![
+                entry(TAG_IMAGE_WIDTH, TYPE_LONG, 1, 400),
+                entry(TAG_IMAGE_LENGTH, TYPE_LONG, 1, 200),
+            ],
+            0,
+        ));
+        tiff.extend(ifd(
+            vec![
+                entry(TAG_IMAGE_WIDTH, TYPE_SHORT, 1, 300),
+                entry(TAG_IMAGE_LENGTH, TYPE_SHORT, 1, 300),
+            ],
+            0,
+        ));
+
+        let parser = Parser::new(&tiff).unwrap();
+        let pages = parser.pages();
+        assert_eq!(pages.len(), 3);
+        assert_eq!(
+            (pages[0].width, pages[0].height, pages[0].sub_ifd),
+            (100, 50, false)
+        );
+        assert_eq!(
+            (pages[1].index, pages[1].offset, pages[1].sub_ifd),
+            (0, 50, true)
+        );
+        assert_eq!((pages[2].index, pages[2].width), (1, 300));
+        let largest = parser.largest_page().unwrap();
+        assert_eq!((largest.width, largest.height), (400, 200));
+
+        let limits = Limits {
+            max_ifds: 2,
+            ..Default::default()
+        };
+        assert_eq!(Parser::with_limits(&tiff, limits).unwrap().pages().len(), 2);
+
+        // 从文件中按偏移量读取的结果相同
+        let mut reader = io::Cursor::new(&tiff);
+        assert_eq!(read_pages(&mut reader, Limits::default()).unwrap(), pages);
+        assert_eq!(read_pages(&mut reader, limits).unwrap().len(), 2);
+
+        let mut patched = Vec::new();
+        Ifd0Reader::new(io::Cursor::new(&tiff), largest.offset)
+            .unwrap()
+            .read_to_end(&mut patched)
+            .unwrap();
+        assert!(set_ifd0_offset(&mut tiff, largest.offset));
+        assert_eq!(patched, tiff);
+        let parser = Parser::new(&tiff).unwrap();
+        assert_eq!(parser.ifd0().unwrap().dimensions(), Some((400, 200)));
+    }
+}
@@ -1,11 +1,232 @@
-/// 数值类型【目前仅处理 gps ，默认均为字符串】
-#[derive(Clone, Debug)]
+use crate::utils::exif_utils::format;
+use crate::utils::exif_utils::tiff::{
+    TYPE_ASCII, TYPE_BYTE, TYPE_DOUBLE, TYPE_FLOAT, TYPE_IFD, TYPE_LONG, TYPE_RATIONAL, TYPE_SBYTE,
+    TYPE_SHORT, TYPE_SLONG, TYPE_SRATIONAL, TYPE_SSHORT, TYPE_UNDEFINED, TYPE_UTF8,
+};
+use anyhow::{anyhow, Result};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// EXIF 日期时间格式
+const EXIF_DATE_TIME_FMT: &str = "%Y:%m:%d %H:%M:%S";
+
+/// 数值类型【决定 `Tags` 中类型化读取时的解析方式，默认为字符串】
+#[derive(Clone, Debug, PartialEq)]
 pub enum ValueType {
     String,
     Gps,
-    // 时间处理暂未使用
+    /// 日期时间【exiftool 格式 `%Y:%m:%d %H:%M:%S`】
     Time,
+    /// 无符号整数【如 ISO、宽高，可带单位】
+    U32,
+    /// 浮点数【如光圈、焦距，可带单位】
+    F64,
+    /// 有理数【如曝光时间 `1/200`】
+    Rational,
+}
+
+impl ValueType {
+    /// 能否按指定类型读取【整数、有理数可以作为浮点数读取】
+    pub fn readable_as(&self, target: &ValueType) -> bool {
+        match target {
+            ValueType::F64 => matches!(self, ValueType::F64 | ValueType::U32 | ValueType::Rational),
+            _ => self == target,
+        }
+    }
+}
+
+/// 无符号有理数（分子 / 分母）
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rational {
+    pub numerator: u32,
+    pub denominator: u32,
 }
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl Rational {
+    pub fn new(numerator: u32, denominator: u32) -> Rational {
+        Rational {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// 转换为小数
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    /// 解析 exiftool 输出的有理数【支持 `1/200`、`0.5`、`30` 等形式，结果为最简分数】
+    pub fn parse(value: &str) -> Option<Rational> {
+        let value = value.split_whitespace().next()?;
+        let (numerator, denominator) = match value.split_once('/') {
+            Some((n, d)) => (n.parse::<u32>().ok()?, d.parse::<u32>().ok()?),
+            None => match value.split_once('.') {
+                Some((int, frac)) => {
+                    let denominator = 10u32.checked_pow(frac.len() as u32)?;
+                    let numerator = format!("{}{}", int, frac).parse::<u32>().ok()?;
+                    (numerator, denominator)
+                }
+                None => (value.parse::<u32>().ok()?, 1),
+            },
+        };
+        format::reduce(numerator, denominator)
+    }
+}
+
+/// 有符号有理数（分子 / 分母）【如曝光补偿 `-1/3`】
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SRational {
+    pub numerator: i32,
+    pub denominator: i32,
+}
+
+impl SRational {
+    pub fn new(numerator: i32, denominator: i32) -> SRational {
+        SRational {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// 转换为小数
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+/// IFD 条目按字段类型解码后的值【每种类型保存该条目的所有值】
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// 8 位无符号整数
+    Byte(Vec<u8>),
+    /// 字符串【按 NUL 分隔为多个，不含结尾的 NUL；Exif 3.0 的 UTF-8 类型也保存为此类型】
+    Ascii(Vec<Vec<u8>>),
+    /// 16 位无符号整数
+    Short(Vec<u16>),
+    /// 32 位无符号整数【IFD 偏移量也保存为此类型】
+    Long(Vec<u32>),
+    /// 无符号有理数【如曝光时间、焦距】
+    Rational(Vec<Rational>),
+    /// 8 位有符号整数
+    SByte(Vec<i8>),
+    /// 格式不明确的原始数据【如 ExifVersion、UserComment】
+    Undefined(Vec<u8>),
+    /// 16 位有符号整数
+    SShort(Vec<i16>),
+    /// 32 位有符号整数
+    SLong(Vec<i32>),
+    /// 有符号有理数
+    SRational(Vec<SRational>),
+    /// 32 位浮点数
+    Float(Vec<f32>),
+    /// 64 位浮点数
+    Double(Vec<f64>),
+    /// 未知类型（类型编号、值的数量）
+    Unknown(u16, u32),
+}
+
+impl Value {
+    /// 类型名称【用于错误信息】
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Byte(_) => "BYTE",
+            Value::Ascii(_) => "ASCII",
+            Value::Short(_) => "SHORT",
+            Value::Long(_) => "LONG",
+            Value::Rational(_) => "RATIONAL",
+            Value::SByte(_) => "SBYTE",
+            Value::Undefined(_) => "UNDEFINED",
+            Value::SShort(_) => "SSHORT",
+            Value::SLong(_) => "SLONG",
+            Value::SRational(_) => "SRATIONAL",
+            Value::Float(_) => "FLOAT",
+            Value::Double(_) => "DOUBLE",
+            Value::Unknown(..) => "UNKNOWN",
+        }
+    }
+
+    /// 读取为无符号整数【BYTE、SHORT、LONG】
+    pub fn as_u32s(&self) -> Result<Vec<u32>> {
+        match self {
+            Value::Byte(x) => Ok(x.iter().map(|x| u32::from(*x)).collect()),
+            Value::Short(x) => Ok(x.iter().map(|x| u32::from(*x)).collect()),
+            Value::Long(x) => Ok(x.clone()),
+            _ => Err(self.type_error("无符号整数")),
+        }
+    }
+
+    /// 读取为无符号有理数【RATIONAL】
+    pub fn as_rationals(&self) -> Result<&[Rational]> {
+        match self {
+            Value::Rational(x) => Ok(x),
+            _ => Err(self.type_error("有理数")),
+        }
+    }
+
+    /// 读取第一个字符串【不是有效的 UTF-8 时返回错误】
+    pub fn as_ascii_str(&self) -> Result<&str> {
+        let Value::Ascii(strings) = self else {
+            return Err(self.type_error("字符串"));
+        };
+        let bytes = strings.first().ok_or_else(|| anyhow!("字符串为空"))?;
+        std::str::from_utf8(bytes).map_err(|e| anyhow!("字符串不是有效的 UTF-8: {}", e))
+    }
+
+    /// 读取为日期时间【格式为 `%Y:%m:%d %H:%M:%S`，不含时区】
+    pub fn as_datetime(&self) -> Result<NaiveDateTime> {
+        let text = self.as_ascii_str()?.trim();
+        NaiveDateTime::parse_from_str(text, EXIF_DATE_TIME_FMT)
+            .map_err(|e| anyhow!("日期时间 `{}` 格式错误: {}", text, e))
+    }
+
+    fn type_error(&self, target: &str) -> anyhow::Error {
+        anyhow!("{} 类型的值不能读取为{}", self.type_name(), target)
+    }
+}
+
+/// EXIF 字段类型信息
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TypeInfo {
+    /// 类型名称
+    pub name: &'static str,
+    /// 单个值占用的字节数
+    pub size: usize,
+}
+
+/// 获取字段类型的名称和单个值的长度【未知类型返回 None】
+/// - field_type IFD 条目中的类型编号
+pub fn get_type_info(field_type: u16) -> Option<TypeInfo> {
+    let (name, size) = match field_type {
+        TYPE_BYTE => ("BYTE", 1),
+        TYPE_ASCII => ("ASCII", 1),
+        TYPE_SHORT => ("SHORT", 2),
+        TYPE_LONG => ("LONG", 4),
+        TYPE_RATIONAL => ("RATIONAL", 8),
+        TYPE_SBYTE => ("SBYTE", 1),
+        TYPE_UNDEFINED => ("UNDEFINED", 1),
+        TYPE_SSHORT => ("SSHORT", 2),
+        TYPE_SLONG => ("SLONG", 4),
+        TYPE_SRATIONAL => ("SRATIONAL", 8),
+        TYPE_FLOAT => ("FLOAT", 4),
+        TYPE_DOUBLE => ("DOUBLE", 8),
+        TYPE_IFD => ("IFD", 4),
+        TYPE_UTF8 => ("UTF-8", 1),
+        _ => return None,
+    };
+    Some(TypeInfo { name, size })
+}
+
 pub trait ExifValueConverter {
     fn convert(value: &str) -> Self;
 }
@@ -27,42 +248,30 @@ impl ExifValueConverter for ValueType {
 //     }
 // }
 
-// 1. Byte(Vec<u8>)
-// 用途：表示一组 8 位无符号整数（字节）。通常用于存储像 JPEG 图像文件中的二进制数据。
-// 为什么需要：EXIF 数据中可能包含一些二进制文件，比如缩略图，或者用于存储不符合其他类型格式的数据。
-// 2. Ascii(Vec<Vec<u8>>)
-// 用途：表示一个或多个 ASCII 字符串，每个字符串为一个字节的向量。
-// 为什么需要：一些 EXIF 字段（如相机品牌、型号）使用 ASCII 编码，通常是由一组 ASCII 字符组成。
-// 3. Short(Vec<u16>)
-// 用途：表示 16 位无符号整数的向量。
-// 为什么需要：EXIF 中的一些字段（如曝光时间）使用 16 位无符号整数表示。
-// 4. Long(Vec<u32>)
-// 用途：表示 32 位无符号整数的向量。
-// 为什么需要：EXIF 中的某些字段需要使用 32 位无符号整数来表示数据。
-// 5. Rational(Vec<Rational>)
-// 用途：表示有理数（即两个整数的比值）的向量。
-// 为什么需要：EXIF 中的某些数据字段（如曝光时间、焦距等）可能会用有理数来表示。Rational 类型是为了处理这种情况的。
-// 6. SByte(Vec<i8>)
-// 用途：表示 8 位有符号整数的向量，通常在 EXIF 中不常用。
-// 为什么需要：虽然 EXIF 标准中很少使用 SByte，但这个类型仍然被保留，因为某些扩展可能会使用它。
-// 7. Undefined(Vec<u8>, u32)
-// 用途：表示不明确的数据，它存储的是原始字节流和相关的偏移量信息。
-// 为什么需要：一些 EXIF 数据可能不遵循明确的格式，Undefined 类型提供了一种通用方式来处理这类数据。
-// 8. SShort(Vec<i16>)
-// 用途：表示 16 位有符号整数的向量。EXIF 规范中未指定使用，但这个类型可以处理一些特定情况。
-// 为什么需要：尽管未在 EXIF 规范中广泛使用，但为了扩展性和通用性，仍然定义了 SShort 类型。
-// 9. SLong(Vec<i32>)
-// 用途：表示 32 位有符号整数的向量。
-// 为什么需要：EXIF 中的一些字段可能需要有符号整数表示，例如某些表示偏移量或计数的字段。
-// 10. SRational(Vec<SRational>)
-// 用途：表示带符号的有理数向量。SRational 是由两个带符号的整数组成的。
-// 为什么需要：EXIF 数据中可能包含带符号的有理数值，比如某些计算结果可能使用带符号的数值。
-// 11. Float(Vec<f32>)
-// 用途：表示 32 位单精度浮点数的向量。
-// 为什么需要：一些 EXIF 字段可能包含浮点数数据，虽然 EXIF 标准中并不广泛使用浮点类型，但它依然被保留以支持其他使用场景。
-// 12. Double(Vec<f64>)
-// 用途：表示 64 位双精度浮点数的向量。
-// 为什么需要：与 Float 类型类似，这个类型用于处理精度要求更高的浮点数数据。
-// 13. Unknown(u16, u32, u32)
-// 用途：表示未知类型的数据，这包括未知类型的值、计数和偏移量。
-// 为什么需要：如果 EXIF 数据中遇到不符合标准的数据格式，或者是某些扩展字段，Unknown 类型提供了一个通用的方式来存储这些数据。
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_conversion() {
+        let short = Value::Short(vec![6, 1]);
+        assert_eq!(short.as_u32s().unwrap(), vec![6, 1]);
+        let error = short.as_rationals().unwrap_err().to_string();
+        assert_eq!(error, "SHORT 类型的值不能读取为有理数");
+
+        let rational = Value::Rational(vec![Rational::new(1, 250)]);
+        assert_eq!(rational.as_rationals().unwrap()[0].to_f64(), 0.004);
+        assert!(rational.as_u32s().is_err());
+
+        let time = Value::Ascii(vec![b"2024:05:01 12:30:45".to_vec()]);
+        assert_eq!(time.as_ascii_str().unwrap(), "2024:05:01 12:30:45");
+        assert_eq!(
+            time.as_datetime().unwrap().to_string(),
+            "2024-05-01 12:30:45"
+        );
+        let blank = Value::Ascii(vec![b"    :  :     :  :  ".to_vec()]);
+        assert!(blank.as_datetime().is_err());
+        assert!(Value::Ascii(vec![vec![0xFF]]).as_ascii_str().is_err());
+        assert!(Value::Ascii(Vec::new()).as_ascii_str().is_err());
+    }
+}
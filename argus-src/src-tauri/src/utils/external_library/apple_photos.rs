@@ -0,0 +1,265 @@
+//! 读取 Apple 照片图库（macOS 10.15 及以上的 Photos.sqlite）
+//!
+//! 照片库是 Core Data 生成的数据库，多对多关联表及其列名带有实体编号（如 `Z_28ASSETS.Z_3ASSETS`），
+//! 不同系统版本编号不同，因此按列名规则查找关联表
+
+use super::{group_pairs, has_table, open_read_only, table_columns};
+use crate::structs::external_library::ExternalPhoto;
+use crate::structs::organization_tree::NodePathIndex;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// 用户相册
+const ALBUM_KIND_USER: i64 = 2;
+/// 相册文件夹
+const ALBUM_KIND_FOLDER: i64 = 4000;
+
+/// 收藏的照片导入后的评分【照片中没有评分，只有收藏】
+const FAVORITE_RATING: i32 = 5;
+
+/// 读取照片库
+/// - path `.photoslibrary` 文件夹或其中的 `database/Photos.sqlite`
+pub fn read(path: &Path) -> Result<Vec<ExternalPhoto>> {
+    let db_path = if path.is_dir() {
+        path.join("database").join("Photos.sqlite")
+    } else {
+        path.to_path_buf()
+    };
+    // 原图保存在照片库下的 originals 文件夹中
+    let library_root = db_path
+        .parent()
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let conn = open_read_only(&db_path)?;
+
+    // macOS 10.15 中照片表为 ZGENERICASSET
+    let asset_table = if has_table(&conn, "ZASSET")? {
+        "ZASSET"
+    } else if has_table(&conn, "ZGENERICASSET")? {
+        "ZGENERICASSET"
+    } else {
+        return Err(anyhow!("{} 不是 Apple 照片图库数据库", db_path.display()));
+    };
+
+    let albums = read_albums(&conn, asset_table)?;
+    let keywords = read_keywords(&conn)?;
+    let faces = read_faces(&conn)?;
+
+    let sql = format!(
+        "SELECT a.Z_PK, a.ZDIRECTORY, a.ZFILENAME, a.ZFAVORITE, \
+                attr.ZORIGINALFILENAME, attr.ZORIGINALFILESIZE, attr.Z_PK \
+         FROM {} a LEFT JOIN ZADDITIONALASSETATTRIBUTES attr ON attr.ZASSET = a.Z_PK \
+         WHERE a.ZTRASHEDSTATE = 0",
+        asset_table
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, Option<i64>>(3)?,
+            row.get::<_, Option<String>>(4)?,
+            row.get::<_, Option<i64>>(5)?,
+            row.get::<_, Option<i64>>(6)?,
+        ))
+    })?;
+
+    let mut photos = Vec::new();
+    for row in rows {
+        let (id, directory, file_name, favorite, original_name, original_size, attr_id) = row?;
+        let Some(file_name) = file_name else {
+            continue;
+        };
+        let source_path: PathBuf = library_root
+            .join("originals")
+            .join(directory.unwrap_or_default())
+            .join(&file_name);
+        photos.push(ExternalPhoto {
+            source_path,
+            file_name: original_name.unwrap_or(file_name),
+            file_size: original_size,
+            rating: (favorite == Some(1)).then_some(FAVORITE_RATING),
+            albums: albums.get(&id).cloned().unwrap_or_default(),
+            keywords: attr_id
+                .and_then(|x| keywords.get(&x).cloned())
+                .unwrap_or_default(),
+            faces: faces.get(&id).cloned().unwrap_or_default(),
+        });
+    }
+    Ok(photos)
+}
+
+/// 每张照片所在的相册路径【文件夹作为上级相册】
+fn read_albums(conn: &Connection, asset_table: &str) -> Result<HashMap<i64, Vec<String>>> {
+    let mut stmt = conn.prepare(
+        "SELECT Z_PK, ZPARENTFOLDER, ZTITLE FROM ZGENERICALBUM \
+         WHERE ZTRASHEDSTATE = 0 AND ZTITLE IS NOT NULL AND ZKIND IN (?1, ?2)",
+    )?;
+    let items = stmt
+        .query_map([ALBUM_KIND_USER, ALBUM_KIND_FOLDER], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<i64>>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    // 顶层的根文件夹没有标题，不在结果中，以它为父级的相册成为根相册
+    let items: Vec<(i32, Option<i32>, String)> = items
+        .into_iter()
+        .map(|(id, parent, name)| (id as i32, parent.map(|x| x as i32), name))
+        .collect();
+    let index = NodePathIndex::new(&items);
+
+    let Some((table, album_col, asset_col)) = find_link_table(conn, "ALBUMS", "ASSETS")? else {
+        log::warn!("未找到相册与照片的关联表，跳过相册");
+        return Ok(HashMap::new());
+    };
+    log::debug!("{} 相册关联表: {}", asset_table, table);
+    let pairs = load_pairs(conn, &table, &asset_col, &album_col)?;
+    let pairs = pairs
+        .into_iter()
+        .filter_map(|(asset, album)| Some((asset, index.path_of(album as i32)?.clone())))
+        .collect();
+    Ok(group_pairs(pairs))
+}
+
+/// 每张照片的关键字【按照片附加属性 ID 分组】
+fn read_keywords(conn: &Connection) -> Result<HashMap<i64, Vec<String>>> {
+    if !has_table(conn, "ZKEYWORD")? {
+        return Ok(HashMap::new());
+    }
+    let Some((table, attr_col, keyword_col)) =
+        find_link_table(conn, "ASSETATTRIBUTES", "KEYWORDS")?
+    else {
+        return Ok(HashMap::new());
+    };
+    let sql = format!(
+        "SELECT l.{}, k.ZTITLE FROM {} l JOIN ZKEYWORD k ON k.Z_PK = l.{} WHERE k.ZTITLE IS NOT NULL",
+        attr_col, table, keyword_col
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let pairs = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(group_pairs(pairs))
+}
+
+/// 每张照片中已命名的人物
+fn read_faces(conn: &Connection) -> Result<HashMap<i64, Vec<String>>> {
+    if !has_table(conn, "ZDETECTEDFACE")? || !has_table(conn, "ZPERSON")? {
+        return Ok(HashMap::new());
+    }
+    // 新版本的列名带有 FORFACE 后缀
+    let columns = table_columns(conn, "ZDETECTEDFACE")?;
+    let pick = |names: [&'static str; 2]| {
+        names
+            .into_iter()
+            .find(|x| columns.iter().any(|c| c == x))
+    };
+    let (Some(asset_col), Some(person_col)) = (
+        pick(["ZASSETFORFACE", "ZASSET"]),
+        pick(["ZPERSONFORFACE", "ZPERSON"]),
+    ) else {
+        return Ok(HashMap::new());
+    };
+    let sql = format!(
+        "SELECT DISTINCT f.{}, coalesce(nullif(p.ZFULLNAME, ''), p.ZDISPLAYNAME) AS name \
+         FROM ZDETECTEDFACE f JOIN ZPERSON p ON p.Z_PK = f.{} \
+         WHERE name IS NOT NULL AND name != ''",
+        asset_col, person_col
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let pairs = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(group_pairs(pairs))
+}
+
+/// 查找多对多关联表
+///
+/// 关联表的两列分别为 `Z_<编号><left>`、`Z_<编号><right>`，返回 (表名, left 列, right 列)
+fn find_link_table(
+    conn: &Connection,
+    left: &str,
+    right: &str,
+) -> Result<Option<(String, String, String)>> {
+    let mut stmt = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name GLOB 'Z_[0-9]*'")?;
+    let tables = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    let left_re = Regex::new(&format!("^Z_[0-9]+{}$", left))?;
+    let right_re = Regex::new(&format!("^Z_[0-9]+{}$", right))?;
+    for table in tables {
+        let columns = table_columns(conn, &table)?;
+        let left_col = columns.iter().find(|x| left_re.is_match(x));
+        let right_col = columns.iter().find(|x| right_re.is_match(x));
+        if let (Some(l), Some(r)) = (left_col, right_col) {
+            return Ok(Some((table, l.clone(), r.clone())));
+        }
+    }
+    Ok(None)
+}
+
+/// 读取关联表中的 (a, b) 对
+fn load_pairs(conn: &Connection, table: &str, a: &str, b: &str) -> Result<Vec<(i64, i64)>> {
+    let sql = format!("SELECT {}, {} FROM {}", a, b, table);
+    let mut stmt = conn.prepare(&sql)?;
+    let pairs = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_dir = dir.path().join("database");
+        std::fs::create_dir_all(&db_dir).unwrap();
+        let conn = Connection::open(db_dir.join("Photos.sqlite")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE ZASSET (Z_PK INTEGER PRIMARY KEY, ZDIRECTORY TEXT, ZFILENAME TEXT, ZFAVORITE INTEGER, ZTRASHEDSTATE INTEGER);
+             CREATE TABLE ZADDITIONALASSETATTRIBUTES (Z_PK INTEGER PRIMARY KEY, ZASSET INTEGER, ZORIGINALFILENAME TEXT, ZORIGINALFILESIZE INTEGER);
+             CREATE TABLE ZGENERICALBUM (Z_PK INTEGER PRIMARY KEY, ZPARENTFOLDER INTEGER, ZTITLE TEXT, ZKIND INTEGER, ZTRASHEDSTATE INTEGER);
+             CREATE TABLE Z_28ASSETS (Z_28ALBUMS INTEGER, Z_3ASSETS INTEGER, Z_FOK_3ASSETS INTEGER);
+             CREATE TABLE ZKEYWORD (Z_PK INTEGER PRIMARY KEY, ZTITLE TEXT);
+             CREATE TABLE Z_1KEYWORDS (Z_1ASSETATTRIBUTES INTEGER, Z_38KEYWORDS INTEGER);
+             CREATE TABLE ZPERSON (Z_PK INTEGER PRIMARY KEY, ZFULLNAME TEXT, ZDISPLAYNAME TEXT);
+             CREATE TABLE ZDETECTEDFACE (Z_PK INTEGER PRIMARY KEY, ZASSETFORFACE INTEGER, ZPERSONFORFACE INTEGER);
+             INSERT INTO ZASSET VALUES (1, 'A', 'UUID1.jpeg', 1, 0), (2, 'B', 'UUID2.heic', 0, 1);
+             INSERT INTO ZADDITIONALASSETATTRIBUTES VALUES (10, 1, 'IMG_0001.JPG', 1234);
+             INSERT INTO ZGENERICALBUM VALUES (1, NULL, NULL, 3999, 0), (2, 1, '旅行', 4000, 0), (3, 2, '日本', 2, 0);
+             INSERT INTO Z_28ASSETS VALUES (3, 1, 1);
+             INSERT INTO ZKEYWORD VALUES (5, '樱花');
+             INSERT INTO Z_1KEYWORDS VALUES (10, 5);
+             INSERT INTO ZPERSON VALUES (7, '', '小明');
+             INSERT INTO ZDETECTEDFACE VALUES (1, 1, 7);",
+        )
+        .unwrap();
+
+        let photos = read(dir.path()).unwrap();
+        assert_eq!(photos.len(), 1);
+        let photo = &photos[0];
+        assert_eq!(photo.file_name, "IMG_0001.JPG");
+        assert_eq!(photo.source_path, dir.path().join("originals/A/UUID1.jpeg"));
+        assert_eq!(photo.file_size, Some(1234));
+        assert_eq!(photo.rating, Some(FAVORITE_RATING));
+        assert_eq!(photo.albums, vec!["旅行/日本"]);
+        assert_eq!(photo.keywords, vec!["樱花"]);
+        assert_eq!(photo.faces, vec!["小明"]);
+    }
+}
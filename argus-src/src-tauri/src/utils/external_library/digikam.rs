@@ -0,0 +1,242 @@
+//! 读取 digiKam 数据库（digikam4.db）
+//!
+//! digiKam 的相册就是磁盘上的文件夹，标签树中 `_Digikam_Internal_Tags_` 下为内部标签，
+//! 带有 `person` 属性的标签为人物
+
+use super::{group_pairs, has_table, open_read_only};
+use crate::structs::external_library::ExternalPhoto;
+use crate::structs::organization_tree::{NodePathIndex, NODE_PATH_SEPARATOR};
+use anyhow::{anyhow, Result};
+use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// 内部标签的根标签名称
+const INTERNAL_TAG_ROOT: &str = "_Digikam_Internal_Tags_";
+/// 照片状态：正常显示【其他状态为隐藏、已删除等】
+const IMAGE_STATUS_VISIBLE: i64 = 1;
+
+/// 读取数据库
+/// - db_path digikam4.db 文件
+pub fn read(db_path: &Path) -> Result<Vec<ExternalPhoto>> {
+    let conn = open_read_only(db_path)?;
+    if !has_table(&conn, "Images")? || !has_table(&conn, "AlbumRoots")? {
+        return Err(anyhow!("{} 不是 digiKam 数据库", db_path.display()));
+    }
+
+    let roots = read_album_roots(&conn)?;
+    // 相册 ID -> (文件夹路径, 相册路径)
+    let mut albums: HashMap<i64, (PathBuf, String)> = HashMap::new();
+    let mut stmt = conn.prepare("SELECT id, albumRoot, relativePath FROM Albums")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+    for row in rows {
+        let (id, root_id, relative_path) = row?;
+        let Some(root) = roots.get(&root_id) else {
+            continue;
+        };
+        let relative_path = relative_path.trim_matches('/');
+        let folder = if relative_path.is_empty() {
+            root.clone()
+        } else {
+            root.join(relative_path)
+        };
+        let album_path = relative_path.replace('/', &NODE_PATH_SEPARATOR.to_string());
+        albums.insert(id, (folder, album_path));
+    }
+
+    let (keywords, faces) = read_tags(&conn)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT i.id, i.album, i.name, i.fileSize, ii.rating \
+         FROM Images i LEFT JOIN ImageInformation ii ON ii.imageid = i.id \
+         WHERE i.status = ?1 AND i.album IS NOT NULL",
+    )?;
+    let rows = stmt.query_map([IMAGE_STATUS_VISIBLE], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<i64>>(3)?,
+            row.get::<_, Option<i32>>(4)?,
+        ))
+    })?;
+    let mut photos = Vec::new();
+    for row in rows {
+        let (id, album_id, name, file_size, rating) = row?;
+        let Some((folder, album_path)) = albums.get(&album_id) else {
+            continue;
+        };
+        photos.push(ExternalPhoto {
+            source_path: folder.join(&name),
+            file_name: name,
+            file_size,
+            // -1 表示未评分，0 星不导入
+            rating: rating.filter(|x| (1..=5).contains(x)),
+            // 根文件夹中的照片不属于任何相册
+            albums: if album_path.is_empty() {
+                Vec::new()
+            } else {
+                vec![album_path.clone()]
+            },
+            keywords: keywords.get(&id).cloned().unwrap_or_default(),
+            faces: faces.get(&id).cloned().unwrap_or_default(),
+        });
+    }
+    Ok(photos)
+}
+
+/// 读取相册根目录
+///
+/// `identifier` 形如 `volumeid:?path=%2Fhome%2Fphotos` 时使用其中的路径，
+/// 否则使用 `specificPath`（按卷 UUID 识别时为相对卷挂载点的路径，只能尽量匹配）
+fn read_album_roots(conn: &Connection) -> Result<HashMap<i64, PathBuf>> {
+    let mut stmt = conn.prepare("SELECT id, identifier, specificPath FROM AlbumRoots")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+        ))
+    })?;
+    let mut roots = HashMap::new();
+    for row in rows {
+        let (id, identifier, specific_path) = row?;
+        let from_identifier = identifier.as_deref().and_then(|x| {
+            let (_, path) = x.split_once("path=")?;
+            Some(percent_decode(path.split('&').next().unwrap_or_default()))
+        });
+        let path = from_identifier.or(specific_path).unwrap_or_default();
+        roots.insert(id, PathBuf::from(path));
+    }
+    Ok(roots)
+}
+
+/// 读取每张照片的关键字路径和人物名称
+#[allow(clippy::type_complexity)]
+fn read_tags(conn: &Connection) -> Result<(HashMap<i64, Vec<String>>, HashMap<i64, Vec<String>>)> {
+    let mut stmt = conn.prepare("SELECT id, pid, name FROM Tags")?;
+    let tags = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<i64>>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    // pid 为 0 表示根标签
+    let items: Vec<(i32, Option<i32>, String)> = tags
+        .iter()
+        .map(|(id, pid, name)| {
+            (
+                *id as i32,
+                pid.filter(|x| *x > 0).map(|x| x as i32),
+                name.clone(),
+            )
+        })
+        .collect();
+    let index = NodePathIndex::new(&items);
+    let names: HashMap<i64, &String> = tags.iter().map(|(id, _, name)| (*id, name)).collect();
+
+    let people: HashSet<i64> = if has_table(conn, "TagProperties")? {
+        let mut stmt =
+            conn.prepare("SELECT DISTINCT tagid FROM TagProperties WHERE property = 'person'")?;
+        let ids = stmt
+            .query_map([], |row| row.get::<_, i64>(0))?
+            .collect::<rusqlite::Result<HashSet<i64>>>()?;
+        ids
+    } else {
+        HashSet::new()
+    };
+
+    let mut stmt = conn.prepare("SELECT imageid, tagid FROM ImageTags")?;
+    let pairs = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    let mut keywords = Vec::new();
+    let mut faces = Vec::new();
+    for (image_id, tag_id) in pairs {
+        if people.contains(&tag_id) {
+            if let Some(name) = names.get(&tag_id) {
+                faces.push((image_id, (*name).clone()));
+            }
+            continue;
+        }
+        let Some(path) = index.path_of(tag_id as i32) else {
+            continue;
+        };
+        if path.split(NODE_PATH_SEPARATOR).next() == Some(INTERNAL_TAG_ROOT) {
+            continue;
+        }
+        keywords.push((image_id, path.clone()));
+    }
+    Ok((group_pairs(keywords), group_pairs(faces)))
+}
+
+/// 解码 URL 中的 `%XX`
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(b) = hex.and_then(|x| u8::from_str_radix(x, 16).ok()) {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("digikam4.db");
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE AlbumRoots (id INTEGER PRIMARY KEY, identifier TEXT, specificPath TEXT);
+             CREATE TABLE Albums (id INTEGER PRIMARY KEY, albumRoot INTEGER, relativePath TEXT);
+             CREATE TABLE Images (id INTEGER PRIMARY KEY, album INTEGER, name TEXT, status INTEGER, fileSize INTEGER);
+             CREATE TABLE ImageInformation (imageid INTEGER PRIMARY KEY, rating INTEGER);
+             CREATE TABLE Tags (id INTEGER PRIMARY KEY, pid INTEGER, name TEXT);
+             CREATE TABLE TagProperties (tagid INTEGER, property TEXT, value TEXT);
+             CREATE TABLE ImageTags (imageid INTEGER, tagid INTEGER);
+             INSERT INTO AlbumRoots VALUES (1, 'volumeid:?path=%2Fdata%2Fphotos', '/');
+             INSERT INTO Albums VALUES (1, 1, '/'), (2, 1, '/2024/京都');
+             INSERT INTO Images VALUES (1, 2, 'a.jpg', 1, 100), (2, 1, 'b.jpg', 1, 200), (3, 2, 'c.jpg', 3, 300);
+             INSERT INTO ImageInformation VALUES (1, 4), (2, -1);
+             INSERT INTO Tags VALUES (1, 0, '旅行'), (2, 1, '寺庙'), (3, 0, 'People'), (4, 3, '小明'), (5, 0, '_Digikam_Internal_Tags_'), (6, 5, 'Color Label Red');
+             INSERT INTO TagProperties VALUES (4, 'person', '小明');
+             INSERT INTO ImageTags VALUES (1, 2), (1, 4), (1, 6);",
+        )
+        .unwrap();
+
+        let mut photos = read(&db_path).unwrap();
+        photos.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+        assert_eq!(photos.len(), 2);
+        let a = &photos[0];
+        assert_eq!(a.source_path, PathBuf::from("/data/photos/2024/京都/a.jpg"));
+        assert_eq!(a.rating, Some(4));
+        assert_eq!(a.albums, vec!["2024/京都"]);
+        assert_eq!(a.keywords, vec!["旅行/寺庙"]);
+        assert_eq!(a.faces, vec!["小明"]);
+        let b = &photos[1];
+        assert_eq!(b.rating, None);
+        assert!(b.albums.is_empty());
+    }
+}
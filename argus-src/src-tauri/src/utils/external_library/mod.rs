@@ -0,0 +1,44 @@
+pub mod apple_photos;
+pub mod digikam;
+
+use anyhow::{anyhow, Result};
+use rusqlite::{Connection, OpenFlags};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 以只读方式打开其他软件的数据库
+pub(crate) fn open_read_only(db_path: &Path) -> Result<Connection> {
+    if !db_path.is_file() {
+        return Err(anyhow!("数据库文件 {} 不存在!", db_path.display()));
+    }
+    Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| anyhow!("数据库 {} 打开失败: {}", db_path.display(), e))
+}
+
+/// 数据库中是否存在指定的表
+pub(crate) fn has_table(conn: &Connection, table: &str) -> Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT count(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [table],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+/// 表的所有列名
+pub(crate) fn table_columns(conn: &Connection, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT name FROM pragma_table_info(?1)")?;
+    let columns = stmt
+        .query_map([table], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    Ok(columns)
+}
+
+/// 把 (照片, 值) 对按照片分组
+pub(crate) fn group_pairs<V>(pairs: Vec<(i64, V)>) -> HashMap<i64, Vec<V>> {
+    let mut result: HashMap<i64, Vec<V>> = HashMap::new();
+    for (key, value) in pairs {
+        result.entry(key).or_default().push(value);
+    }
+    result
+}
@@ -0,0 +1,203 @@
+use crate::constant::SLIDESHOW_KEN_BURNS_ZOOM;
+use crate::structs::slideshow::SlideshowOptions;
+use crate::utils::file_util;
+use std::path::{Path, PathBuf};
+
+/// ffmpeg 路径【优先使用程序目录下 service 文件夹中随程序发布的版本，不存在时使用系统 PATH 中的 ffmpeg】
+pub fn ffmpeg_path() -> PathBuf {
+    file_util::bundled_tool_path("ffmpeg")
+}
+
+/// 幻灯片视频的照片列表（concat 输入文件）
+///
+/// 照片较多时命令行会超出长度限制，改为通过列表文件输入。
+/// 普通模式每张照片显示 `slide_seconds`（按 concat 的要求最后一张需要重复一次）；
+/// Ken Burns 模式每张照片只输入一帧，由 zoompan 展开
+/// - images 照片路径（按播放顺序）
+/// - options 视频选项
+pub fn concat_list(images: &[PathBuf], options: &SlideshowOptions) -> String {
+    let ken_burns = options.ken_burns.unwrap_or(false);
+    let mut list = String::from("ffconcat version 1.0\n");
+    for image in images {
+        list.push_str(&format!("file {}\n", quote_path(image)));
+        if !ken_burns {
+            list.push_str(&format!("duration {}\n", options.slide_seconds()));
+        }
+    }
+    if let (false, Some(last)) = (ken_burns, images.last()) {
+        list.push_str(&format!("file {}\n", quote_path(last)));
+    }
+    list
+}
+
+/// 生成幻灯片视频的 ffmpeg 参数
+///
+/// 照片从列表文件读取（见 `concat_list`），缩放到视频分辨率（不足部分补黑边）后使用 H.264 编码。
+/// 进度以 `key=value` 的形式输出到标准输出，见 `parse_progress_time`
+/// - list_path 照片列表文件
+/// - image_count 照片数量
+/// - options 视频选项
+pub fn slideshow_args(
+    list_path: &Path,
+    image_count: usize,
+    options: &SlideshowOptions,
+) -> Vec<String> {
+    let (width, height) = options.size();
+    let fps = options.fps();
+    let frames = options.frames_per_slide();
+    let ken_burns = options.ken_burns.unwrap_or(false);
+
+    let mut args: Vec<String> = [
+        "-hide_banner",
+        "-loglevel",
+        "error",
+        "-nostats",
+        "-f",
+        "concat",
+        "-safe",
+        "0",
+        "-i",
+    ]
+    .iter()
+    .map(|x| x.to_string())
+    .collect();
+    args.push(path_arg(list_path));
+    let music = options.music_path.as_deref().filter(|x| !x.is_empty());
+    if let Some(music) = music {
+        args.extend(["-stream_loop", "-1", "-i"].iter().map(|x| x.to_string()));
+        args.push(path_arg(Path::new(music)));
+    }
+
+    let filter = if ken_burns {
+        // zoompan 每个输入帧输出 d 帧；先放大到两倍分辨率，减少缩放时的抖动
+        format!(
+            "[0:v]{},zoompan=z='{}':x='iw/2-(iw/zoom/2)':y='ih/2-(ih/zoom/2)':d={frames}:s={width}x{height}:fps={fps},setsar=1,format=yuv420p[out]",
+            fit_filter(width * 2, height * 2),
+            ken_burns_zoom(frames),
+        )
+    } else {
+        format!(
+            "[0:v]{},fps={fps},setsar=1,format=yuv420p[out]",
+            fit_filter(width, height)
+        )
+    };
+    args.extend(["-filter_complex".to_string(), filter]);
+    args.extend(["-map", "[out]"].iter().map(|x| x.to_string()));
+    if music.is_some() {
+        args.extend(
+            ["-map", "1:a", "-c:a", "aac", "-b:a", "192k"]
+                .iter()
+                .map(|x| x.to_string()),
+        );
+    }
+    args.extend(
+        [
+            "-c:v",
+            "libx264",
+            "-preset",
+            "medium",
+            "-crf",
+            "20",
+            "-r",
+            &fps.to_string(),
+            "-t",
+            &total_seconds(image_count, options).to_string(),
+            "-movflags",
+            "+faststart",
+            "-progress",
+            "pipe:1",
+            "-y",
+        ]
+        .iter()
+        .map(|x| x.to_string()),
+    );
+    args.push(path_arg(Path::new(&options.output_path)));
+    args
+}
+
+/// 视频总时长（秒）
+pub fn total_seconds(image_count: usize, options: &SlideshowOptions) -> f32 {
+    (image_count as u32 * options.frames_per_slide()) as f32 / options.fps() as f32
+}
+
+/// 解析 `-progress` 输出中已编码的时长（微秒）
+///
+/// `out_time_ms` 实际单位也是微秒，与 `out_time_us` 相同
+pub fn parse_progress_time(line: &str) -> Option<u64> {
+    let (key, value) = line.trim().split_once('=')?;
+    match key {
+        "out_time_us" | "out_time_ms" => value.parse().ok(),
+        _ => None,
+    }
+}
+
+/// 等比缩放到指定分辨率，不足部分居中补黑边
+fn fit_filter(width: u32, height: u32) -> String {
+    format!(
+        "scale={width}:{height}:force_original_aspect_ratio=decrease,pad={width}:{height}:(ow-iw)/2:(oh-ih)/2"
+    )
+}
+
+/// Ken Burns 缩放表达式【偶数张放大、奇数张缩小，避免画面单调】
+///
+/// 所有照片在同一个 zoompan 中处理，`on - in * d` 为当前照片的帧序号
+fn ken_burns_zoom(frames: u32) -> String {
+    let step = (SLIDESHOW_KEN_BURNS_ZOOM - 1.0) / frames as f32;
+    let frame = format!("(on-in*{frames})");
+    format!(
+        "if(mod(in,2),max({SLIDESHOW_KEN_BURNS_ZOOM}-{step}*{frame},1),min(1+{step}*{frame},{SLIDESHOW_KEN_BURNS_ZOOM}))"
+    )
+}
+
+fn path_arg(path: &Path) -> String {
+    file_util::long_path(path).display().to_string()
+}
+
+/// concat 列表中的路径【单引号内只需要转义单引号】
+fn quote_path(path: &Path) -> String {
+    format!("'{}'", path_arg(path).replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slideshow_args() {
+        let images = vec![PathBuf::from("a.jpg"), PathBuf::from("it's.jpg")];
+        let options = SlideshowOptions {
+            output_path: "out.mp4".to_string(),
+            slide_seconds: Some(2.0),
+            music_path: Some("music.mp3".to_string()),
+            width: Some(1280),
+            height: Some(720),
+            fps: Some(25),
+            ..Default::default()
+        };
+        assert_eq!(
+            concat_list(&images, &options),
+            "ffconcat version 1.0\nfile 'a.jpg'\nduration 2\nfile 'it'\\''s.jpg'\nduration 2\nfile 'it'\\''s.jpg'\n"
+        );
+        let args = slideshow_args(Path::new("list.txt"), images.len(), &options);
+        assert!(args.windows(2).any(|x| x == ["-i", "list.txt"]));
+        let filter = &args[args.iter().position(|x| x == "-filter_complex").unwrap() + 1];
+        assert!(filter.starts_with("[0:v]scale=1280:720"));
+        assert!(filter.ends_with("format=yuv420p[out]"));
+        assert!(args.windows(2).any(|x| x == ["-map", "1:a"]));
+        assert!(args.windows(2).any(|x| x == ["-t", "4"]));
+        assert_eq!(args.last().unwrap(), "out.mp4");
+
+        let options = SlideshowOptions {
+            ken_burns: Some(true),
+            ..options
+        };
+        assert!(!concat_list(&images, &options).contains("duration"));
+        let args = slideshow_args(Path::new("list.txt"), images.len(), &options);
+        assert!(args
+            .iter()
+            .any(|x| x.contains("zoompan") && x.contains("d=50") && x.contains("on-in*50")));
+
+        assert_eq!(parse_progress_time("out_time_us=1500000"), Some(1500000));
+        assert_eq!(parse_progress_time("frame=10"), None);
+    }
+}
@@ -1,72 +1,88 @@
-use crate::structs::config::SYS_CONFIG;
+use crate::structs::config::sys_config;
+use crate::structs::thumbnail_encoding;
 use crate::utils::file_util;
-use crate::utils::file_util::file_size;
 use anyhow::Result;
 use image::ImageFormat;
 use sha2::{Digest, Sha256};
 use std::fs;
-use std::path::PathBuf;
-use tokio::fs::File;
-use tokio::io::{self, AsyncReadExt};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 同步计算 Hash 时的读取缓冲区大小
+const HASH_BUFFER_SIZE: usize = 128 * 1024;
 
 pub struct FileHashUtils;
 
 impl FileHashUtils {
     /// 计算文件内容的 SHA-256 哈希值
-    pub fn sha256(file_path: &str) -> std::io::Result<String> {
-        let result = file_util::read_binary_file(file_path);
-        println!("=========== {} ========== ", file_path);
-        let content = fs::read(file_path)?; // 一次性读取文件内容
-        Ok(format!("{:x}", Sha256::digest(&content)))
+    ///
+    /// 分块读取，内存占用与文件大小无关【几百 MB 的 TIFF、DNG 不会整个读入内存】
+    pub fn sha256<P: AsRef<Path>>(file_path: P) -> io::Result<String> {
+        let hash = Self::sha256_chunked(file_path, None)?;
+        Ok(hash.expect("未指定取消标记时不会取消"))
     }
 
-    pub async fn sha256_async(file_path: &str) -> io::Result<String> {
-        let metadata = tokio::fs::metadata(file_path).await?;
-        let file_size = metadata.len();
-
-        // 缓冲区大小根据文件大小动态选择
-        let m10 = 10 * 1024 * 1024; // 10 MB
-        let m100 = 100 * 1024 * 1024; // 100 MB
-        let buffer_size = if file_size < m10 {
-            32 * 1024 // 小于等于 10MB，用 16KB 缓冲
-        } else if file_size < m100 {
-            64 * 1024 // 10MB 到 100MB 用 64KB
-        } else {
-            128 * 1024 // 超过 100MB，用 256KB 缓冲
-        };
+    /// 计算文件内容的 SHA-256 哈希值【可取消，每读取一块检查一次取消标记】
+    ///
+    /// 取消时返回 `Ok(None)`
+    /// - cancelled 取消标记
+    pub fn sha256_cancellable<P: AsRef<Path>>(
+        file_path: P,
+        cancelled: &AtomicBool,
+    ) -> io::Result<Option<String>> {
+        Self::sha256_chunked(file_path, Some(cancelled))
+    }
 
+    /// 分块读取文件计算 SHA-256【`sha256`、`sha256_cancellable` 共用】
+    /// - cancelled 取消标记【为空时不可取消】
+    fn sha256_chunked<P: AsRef<Path>>(
+        file_path: P,
+        cancelled: Option<&AtomicBool>,
+    ) -> io::Result<Option<String>> {
+        let mut file = fs::File::open(file_util::long_path(file_path))?;
+        let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
         let mut hasher = Sha256::new();
-
-        let mut file = File::open(file_path).await?; // 异步打开文件
-        let mut buffer = vec![0u8; buffer_size];
-
-        // let mut buffer = fs::read(file_path)?;
-
         loop {
-            let bytes_read = file.read(&mut buffer).await?;
-            if bytes_read == 0 {
-                break; // 文件读取完毕
+            if cancelled.is_some_and(|x| x.load(Ordering::SeqCst)) {
+                return Ok(None);
             }
-            hasher.update(&buffer[..bytes_read]); // 更新哈希计算
+            let n = match file.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            hasher.update(&buffer[..n]);
         }
+        Ok(Some(format!("{:x}", hasher.finalize())))
+    }
 
-        Ok(format!("{:x}", hasher.finalize())) // 返回最终哈希值
+    /// 异步计算文件内容的 SHA-256 哈希值【在阻塞线程池中按 `sha256` 分块读取】
+    pub async fn sha256_async<P: AsRef<Path>>(file_path: P) -> io::Result<String> {
+        let file_path = file_path.as_ref().to_path_buf();
+        tokio::task::spawn_blocking(move || FileHashUtils::sha256(file_path))
+            .await
+            .map_err(io::Error::other)?
     }
 
     /// 获取 Hash 文件路径
+    ///
+    /// 目录结构为 `{base}/{分级目录}/{hash}/{版本}/{级别}.{后缀}`，
+    /// 版本由缩略图编码参数决定，修改格式、质量后不会继续使用旧的缩略图
     /// - hash 文件 Hash
     /// - base_path 基础路径
     /// - suffix_name 后缀名
     /// - compression_level 压缩级别
     pub fn hash_to_file_path(
         hash: &str,
-        base_path: &str,
+        base_path: impl AsRef<Path>,
         suffix_name: &str,
         compression_level: u32,
     ) -> PathBuf {
-        let dir_level = SYS_CONFIG.directory_level.clone().unwrap();
+        let dir_level = sys_config().directory_level.clone().unwrap();
         // 定义目录分级层数
-        let mut path = PathBuf::from(base_path);
+        let mut path = base_path.as_ref().to_path_buf();
 
         // 将 hash 分割为多级目录
         for i in 0..dir_level {
@@ -78,6 +94,7 @@ impl FileHashUtils {
 
         // 将剩余的 hash 用作文件名
         path.push(hash);
+        path.push(thumbnail_encoding::generation());
         path.push(format!("{}.{}", compression_level.to_string(), suffix_name));
 
         path
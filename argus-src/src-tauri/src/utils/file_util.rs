@@ -1,88 +1,99 @@
+use crate::constant::{CAPTION_SIDECAR_EXTENSION, RAW_EXTENSIONS, TEMP_FILE_EXTENSION};
 use crate::errors::AError;
+use crate::structs::photo_stack::raw_jpeg_stem;
+use crate::utils::cloud_placeholder_util;
+use crate::utils::scan_exclude_util::ScanExcludeRules;
 use anyhow::{anyhow, Result};
 use glob::glob;
 use sha2::digest::typenum::op;
+use std::collections::HashSet;
 use std::env;
 use std::fs::{self, File};
 use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use walkdir::WalkDir;
 
 /// 读取文本文件内容
-pub fn read_text_file(file_path: &str) -> Result<String, String> {
-    fs::read_to_string(file_path).map_err(|e| format!("读取文件失败: {}", e))
+pub fn read_text_file<P: AsRef<Path>>(file_path: P) -> Result<String, String> {
+    fs::read_to_string(long_path(file_path)).map_err(|e| format!("读取文件失败: {}", e))
 }
 
 /// 写入文本到文件
-pub fn write_text_file(file_path: &str, content: &str) -> Result<(), String> {
-    fs::write(file_path, content).map_err(|e| format!("写入文件失败: {}", e))
+pub fn write_text_file<P: AsRef<Path>>(file_path: P, content: &str) -> Result<(), String> {
+    fs::write(long_path(file_path), content).map_err(|e| format!("写入文件失败: {}", e))
 }
 
 /// 读取二进制文件内容
-pub fn read_binary_file(file_path: &str) -> Result<Vec<u8>, String> {
-    fs::read(file_path).map_err(|e| format!("读取文件失败: {}", e))
+pub fn read_binary_file<P: AsRef<Path>>(file_path: P) -> Result<Vec<u8>, String> {
+    fs::read(long_path(file_path)).map_err(|e| format!("读取文件失败: {}", e))
 }
 
 /// 写入二进制数据到文件
-pub fn write_binary_file(file_path: &str, data: &[u8]) -> Result<(), String> {
-    File::create(file_path)
+pub fn write_binary_file<P: AsRef<Path>>(file_path: P, data: &[u8]) -> Result<(), String> {
+    File::create(long_path(file_path))
         .and_then(|mut file| file.write_all(data))
         .map_err(|e| format!("写入二进制文件失败: {}", e))
 }
 
 /// 检查文件是否存在
-pub fn file_exists(file_path: &str) -> bool {
-    Path::new(file_path).exists()
+pub fn file_exists<P: AsRef<Path>>(file_path: P) -> bool {
+    long_path(file_path).exists()
 }
 
 /// 获取文件大小
-pub fn file_size(file_path: &str) -> Result<u64, String> {
-    fs::metadata(file_path)
+pub fn file_size<P: AsRef<Path>>(file_path: P) -> Result<u64, String> {
+    fs::metadata(long_path(file_path))
         .map(|metadata| metadata.len())
         .map_err(|e| format!("获取文件大小失败: {}", e))
 }
 
 /// 创建目录
-pub fn create_directory(dir_path: &str) -> Result<(), String> {
-    fs::create_dir_all(dir_path).map_err(|e| format!("创建目录失败: {}", e))
+pub fn create_directory<P: AsRef<Path>>(dir_path: P) -> Result<(), String> {
+    fs::create_dir_all(long_path(dir_path)).map_err(|e| format!("创建目录失败: {}", e))
 }
 
 /// 删除文件
-pub fn delete_file(file_path: &str) -> Result<(), String> {
-    fs::remove_file(file_path).map_err(|e| format!("删除文件失败: {}", e))
+pub fn delete_file<P: AsRef<Path>>(file_path: P) -> Result<(), String> {
+    fs::remove_file(long_path(file_path)).map_err(|e| format!("删除文件失败: {}", e))
 }
 
 /// 删除目录及其内容
-pub fn delete_directory(dir_path: &str) -> Result<(), String> {
-    fs::remove_dir_all(dir_path).map_err(|e| format!("删除目录失败: {}", e))
+pub fn delete_directory<P: AsRef<Path>>(dir_path: P) -> Result<(), String> {
+    fs::remove_dir_all(long_path(dir_path)).map_err(|e| format!("删除目录失败: {}", e))
 }
 
 /// 列出目录中的文件
-pub fn list_directory(dir_path: &str) -> Result<Vec<PathBuf>, String> {
-    fs::read_dir(dir_path)
+pub fn list_directory<P: AsRef<Path>>(dir_path: P) -> Result<Vec<PathBuf>, String> {
+    fs::read_dir(long_path(dir_path))
         .map_err(|e| format!("读取目录失败: {}", e))?
         .map(|entry| entry.map(|e| e.path()).map_err(|e| format!("{}", e)))
         .collect()
 }
 
 /// 拷贝文件
-pub fn copy_file(src_path: &str, dest_path: &str) -> Result<(), String> {
-    fs::copy(src_path, dest_path)
+pub fn copy_file<P: AsRef<Path>, Q: AsRef<Path>>(src_path: P, dest_path: Q) -> Result<(), String> {
+    fs::copy(long_path(src_path), long_path(dest_path))
         .map(|_| ())
         .map_err(|e| format!("拷贝文件失败: {}", e))
 }
 
 /// 移动文件（先拷贝后删除源文件）
-pub fn move_file(src_path: &str, dest_path: &str) -> Result<(), String> {
-    copy_file(src_path, dest_path)?;
+pub fn move_file<P: AsRef<Path>, Q: AsRef<Path>>(src_path: P, dest_path: Q) -> Result<(), String> {
+    copy_file(&src_path, dest_path)?;
     delete_file(src_path)
 }
 
 /// 获取所有指定文件夹的子目录
-pub fn get_all_subfolders(path: &str) -> Vec<PathBuf> {
-    WalkDir::new(path)
+pub fn get_all_subfolders<P: AsRef<Path>>(path: P) -> Vec<PathBuf> {
+    let rules = ScanExcludeRules::from_config();
+    WalkDir::new(long_path(path))
         .min_depth(0) // 忽略起始目录本身
         .into_iter()
+        // 跳过被排除的文件夹及其子目录【起始目录本身除外】
+        .filter_entry(|entry| {
+            entry.depth() == 0 || !rules.is_excluded(entry.path(), entry.file_type().is_dir())
+        })
         .filter_map(|entry| entry.ok()) // 忽略无效条目
         .filter(|entry| entry.file_type().is_dir()) // 只保留文件夹
         .map(|entry| entry.path().to_path_buf()) // 转换为 PathBuf
@@ -90,13 +101,12 @@ pub fn get_all_subfolders(path: &str) -> Vec<PathBuf> {
 }
 
 /// 获取所有照片
-pub fn get_all_img(path: &str) -> Vec<String> {
+pub fn get_all_img<P: AsRef<Path>>(path: P) -> Vec<PathBuf> {
     let vec = get_all_subfolders(path);
-    let mut res:Vec<String> = Vec::new();
+    let mut res: Vec<PathBuf> = Vec::new();
     for x in vec {
-        let display = x.display().to_string();
-        get_all_dir_img(&*display, Some(-1));
-        res.push(display);
+        get_all_dir_img(&x, Some(-1));
+        res.push(x);
     }
     res
 }
@@ -104,41 +114,315 @@ pub fn get_all_img(path: &str) -> Vec<String> {
 /// 获取指定路径下所有图片
 /// * `path` 指定路径
 /// * `img_num` 获取多少张图片，如果是0直接返回，如果为负数则获取所有图片
-pub fn get_all_dir_img(path: &str, img_num: Option<i32>) -> Vec<String> {
+pub fn get_all_dir_img<P: AsRef<Path>>(path: P, img_num: Option<i32>) -> Vec<PathBuf> {
     let mut i = 0;
     // 默认张数
     let nums = img_num.unwrap_or(-1);
     if nums == 0 {
         return [].to_vec();
     }
-    let valid_extensions = ["jpg", "png", "gif", "jpeg"]; // 图片文件扩展名
-                                                          // 数据返回合集
-    let mut all_img: Vec<String> = vec![];
+    // 数据返回合集
+    let mut all_img: Vec<PathBuf> = vec![];
+    let rules = ScanExcludeRules::from_config();
 
-    if let Ok(entries) = fs::read_dir(path) {
+    if let Ok(entries) = fs::read_dir(long_path(path)) {
         for entry in entries {
             if let Ok(entry) = entry {
                 let path = entry.path();
-                if path.is_file() {
-                    if let Some(extension) = path.extension() {
-                        if valid_extensions.contains(&extension.to_str().unwrap_or_default()) {
-                            i += 1;
-                            let x = i == nums;
-                            all_img.push(String::from(path.to_str().unwrap()));
-                            if x {
-                                break;
-                            }
-                        }
+                if path.is_file() && is_image_file(&path) && !rules.is_excluded(&path, false) {
+                    i += 1;
+                    let x = i == nums;
+                    all_img.push(path);
+                    if x {
+                        break;
                     }
                 }
             }
         }
     } else {
-        eprintln!("Failed to read directory.");
+        log::warn!("Failed to read directory.");
     }
     all_img
 }
 
+/// 图片文件扩展名
+const IMAGE_EXTENSIONS: [&str; 4] = ["jpg", "png", "gif", "jpeg"];
+
+/// 是否为支持的图片文件（只判断扩展名）【包括 RAW 文件】
+pub fn is_image_file<P: AsRef<Path>>(path: P) -> bool {
+    path.as_ref()
+        .extension()
+        // 扩展名本身非 UTF-8 时不可能是图片，文件名其余部分不受影响
+        .and_then(|x| x.to_str())
+        .map(|x| IMAGE_EXTENSIONS.contains(&x))
+        .unwrap_or(false)
+        || is_raw_file(path)
+}
+
+/// 是否为 RAW 文件（只判断扩展名，忽略大小写）
+pub fn is_raw_file<P: AsRef<Path>>(path: P) -> bool {
+    path.as_ref()
+        .extension()
+        .and_then(|x| x.to_str())
+        .is_some_and(|x| RAW_EXTENSIONS.contains(&x.to_lowercase().as_str()))
+}
+
+/// 并行遍历指定路径下所有图片
+///
+/// 多个线程同时读取不同的子目录，每发现一张图片立即交给 `on_image` 处理，
+/// 不需要等整个目录树遍历完成；`on_image` 返回 false 时停止遍历。
+/// 会跳过扫描排除规则（设置、`.argusignore`）匹配的文件和文件夹，以及与 RAW 配对的同名 JPEG
+/// * `path` 指定路径
+/// * `on_image` 图片处理回调
+pub fn walk_dir_img_parallel<P, F>(path: P, mut on_image: F)
+where
+    P: AsRef<Path>,
+    F: FnMut(PathBuf) -> bool,
+{
+    let rules = Arc::new(ScanExcludeRules::from_config());
+    let walker = jwalk::WalkDir::new(long_path(path))
+        .skip_hidden(false)
+        .follow_links(false)
+        // 跳过被排除的文件、文件夹【起始目录本身除外】
+        .process_read_dir(move |depth, _, _, children| {
+            if depth.is_none() {
+                return;
+            }
+            // RAW+JPEG 配对时 JPEG 关联到同名的 RAW 上，不单独作为照片
+            let raw_stems: HashSet<String> = children
+                .iter()
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy();
+                    match raw_jpeg_stem(&name) {
+                        Some((stem, true)) => Some(stem.to_lowercase()),
+                        _ => None,
+                    }
+                })
+                .collect();
+            children.retain(|entry| match entry {
+                Ok(entry) => {
+                    let paired = match raw_jpeg_stem(&entry.file_name().to_string_lossy()) {
+                        Some((stem, false)) => raw_stems.contains(&stem.to_lowercase()),
+                        _ => false,
+                    };
+                    !paired && !rules.is_excluded(&entry.path(), entry.file_type().is_dir())
+                }
+                Err(_) => true,
+            });
+        });
+    for entry in walker {
+        match entry {
+            Ok(entry) => {
+                let name = Path::new(entry.file_name());
+                // iCloud 占位文件也交给回调，记录为仅云端文件
+                if entry.file_type().is_file()
+                    && (is_image_file(name) || cloud_placeholder_util::is_icloud_image_stub(name))
+                    && !on_image(entry.path())
+                {
+                    break;
+                }
+            }
+            Err(e) => log::warn!("目录读取失败: {}", e),
+        }
+    }
+}
+
+/// 以 reflink（写时复制）方式复制文件，两个文件共享磁盘上的数据，修改互不影响
+///
+/// 只有 Btrfs、XFS（Linux）和 APFS（macOS）等文件系统支持，且两个文件必须在同一个文件系统中；
+/// 不支持时返回错误，不会退化为普通复制
+/// - src 源文件
+/// - dest 目标文件【不能已存在】
+pub fn reflink_file(src: &Path, dest: &Path) -> Result<()> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        use std::os::unix::io::AsRawFd;
+        let source = File::open(src)?;
+        let target = File::options().write(true).create_new(true).open(dest)?;
+        // SAFETY: 两个文件描述符在调用期间都有效
+        let ret = unsafe { libc::ioctl(target.as_raw_fd(), libc::FICLONE, source.as_raw_fd()) };
+        if ret != 0 {
+            let e = io::Error::last_os_error();
+            drop(target);
+            let _ = fs::remove_file(dest);
+            return Err(anyhow!("文件系统不支持 reflink: {}", e));
+        }
+        Ok(())
+    }
+    #[cfg(target_os = "macos")]
+    {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+        let source = CString::new(src.as_os_str().as_bytes())?;
+        let target = CString::new(dest.as_os_str().as_bytes())?;
+        // SAFETY: 两个路径都是以 NUL 结尾的有效字符串
+        if unsafe { libc::clonefile(source.as_ptr(), target.as_ptr(), 0) } != 0 {
+            return Err(anyhow!(
+                "文件系统不支持 reflink: {}",
+                io::Error::last_os_error()
+            ));
+        }
+        Ok(())
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+    {
+        let _ = (src, dest);
+        Err(anyhow!("当前系统不支持 reflink!"))
+    }
+}
+
+/// 文件已存在时在文件名后添加序号，如 `IMG_0001-1.jpg`
+pub fn unique_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+    let stem = path
+        .file_stem()
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path.extension().map(|x| x.to_string_lossy().to_string());
+    (1..)
+        .map(|i| {
+            let name = match &extension {
+                Some(ext) => format!("{}-{}.{}", stem, i, ext),
+                None => format!("{}-{}", stem, i),
+            };
+            path.with_file_name(name)
+        })
+        .find(|x| !x.exists())
+        .expect("文件序号已用尽")
+}
+
+/// 照片的 XMP 附属文件路径，如 `IMG_0001.CR2.xmp`
+///
+/// 保留原来的扩展名，同名的 JPEG 和 RAW 各自使用自己的附属文件
+pub fn sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(CAPTION_SIDECAR_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// 与原文件同目录的隐藏临时文件，如 `.IMG_0001.JPG.argus-tmp`【保证可以直接重命名替换】
+/// - suffix 追加的扩展名
+pub fn temp_path(path: &Path, suffix: Option<&str>) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match suffix {
+        Some(suffix) => {
+            path.with_file_name(format!(".{}.{}.{}", name, suffix, TEMP_FILE_EXTENSION))
+        }
+        None => path.with_file_name(format!(".{}.{}", name, TEMP_FILE_EXTENSION)),
+    }
+}
+
+/// Windows 传统路径长度上限（目录还需预留 8.3 文件名的空间）
+#[cfg(windows)]
+const WINDOWS_MAX_PATH: usize = 248;
+
+/// 转换为可访问超长路径的形式
+///
+/// Windows 下超过 `MAX_PATH` 的绝对路径需要加上 `\\?\` 前缀才能访问，
+/// 其他平台原样返回
+pub fn long_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let path = path.as_ref();
+    #[cfg(windows)]
+    {
+        if path.as_os_str().len() >= WINDOWS_MAX_PATH {
+            if let Some(verbatim) = path.to_str().and_then(to_verbatim_path) {
+                return PathBuf::from(verbatim);
+            }
+        }
+    }
+    path.to_path_buf()
+}
+
+/// 为 Windows 绝对路径添加 `\\?\` 前缀
+///
+/// 带前缀的路径不会再被系统规范化，因此相对路径、包含 `.`、`..` 的路径返回 `None`
+pub fn to_verbatim_path(path: &str) -> Option<String> {
+    let path = path.replace('/', "\\");
+    if path.starts_with(r"\\?\") || path.starts_with(r"\\.\") {
+        return Some(path);
+    }
+    if path.split('\\').any(|x| x == "." || x == "..") {
+        return None;
+    }
+    // UNC 路径：\\server\share -> \\?\UNC\server\share
+    if let Some(unc) = path.strip_prefix(r"\\") {
+        return Some(format!(r"\\?\UNC\{}", unc));
+    }
+    let bytes = path.as_bytes();
+    if bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\' {
+        return Some(format!(r"\\?\{}", path));
+    }
+    None
+}
+
+/// 去掉 `\\?\` 前缀，用于界面展示
+pub fn strip_verbatim_prefix<P: AsRef<Path>>(path: P) -> PathBuf {
+    let path = path.as_ref();
+    match path.to_str() {
+        Some(s) if s.starts_with(r"\\?\UNC\") => PathBuf::from(format!(r"\\{}", &s[8..])),
+        Some(s) if s.starts_with(r"\\?\") => PathBuf::from(&s[4..]),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// 转换为展示给前端的字符串【去掉长路径前缀，非 UTF-8 部分有损替换】
+pub fn display_path<P: AsRef<Path>>(path: P) -> String {
+    strip_verbatim_prefix(path).to_string_lossy().to_string()
+}
+
+/// 将路径无损转换为字节，用于数据库存储
+///
+/// Unix 下为原始字节，Windows 下为 UTF-16 小端字节
+pub fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    let path = path.as_ref();
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes().to_vec()
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        path.as_os_str()
+            .encode_wide()
+            .flat_map(|x| x.to_le_bytes())
+            .collect()
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        path.to_string_lossy().as_bytes().to_vec()
+    }
+}
+
+/// 从 [`path_to_bytes`] 的结果还原路径
+pub fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        PathBuf::from(std::ffi::OsStr::from_bytes(bytes))
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStringExt;
+        let wide: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|x| u16::from_le_bytes([x[0], x[1]]))
+            .collect();
+        PathBuf::from(std::ffi::OsString::from_wide(&wide))
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        PathBuf::from(String::from_utf8_lossy(bytes).to_string())
+    }
+}
+
 /// 获取运行环境文件路径根目录
 pub fn get_root_folder() -> Result<PathBuf> {
     // 获取基础路径，默认为当前 EXE 所在目录
@@ -149,6 +433,21 @@ pub fn get_root_folder() -> Result<PathBuf> {
     Ok(buf)
 }
 
+/// 外部工具路径【优先使用程序目录下 service 文件夹中随程序发布的版本，不存在时使用系统 PATH 中的同名程序】
+/// - name 程序名称【Windows 下自动追加 .exe】
+pub fn bundled_tool_path(name: &str) -> PathBuf {
+    let name = if cfg!(windows) {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    };
+    env::current_exe()
+        .ok()
+        .and_then(|x| x.parent().map(|dir| dir.join("service").join(&name)))
+        .filter(|x| file_exists(x))
+        .unwrap_or_else(|| PathBuf::from(name))
+}
+
 /// 创建指定的文件夹
 pub fn create_folder(base_dir: Option<&str>, relative_path: &str) -> Result<String, String> {
     // 获取基础路径，默认为当前 EXE 所在目录
@@ -183,6 +482,31 @@ pub fn create_folder(base_dir: Option<&str>, relative_path: &str) -> Result<Stri
 mod tests {
     use super::*; // 引入当前模块的所有内容
 
+    #[test]
+    fn test_sidecar_path() {
+        assert_eq!(
+            sidecar_path(Path::new("photos/IMG_0001.CR2")),
+            PathBuf::from("photos/IMG_0001.CR2.xmp")
+        );
+        assert_ne!(
+            sidecar_path(Path::new("IMG_0001.JPG")),
+            sidecar_path(Path::new("IMG_0001.CR2"))
+        );
+    }
+
+    #[test]
+    fn test_temp_path() {
+        let path = Path::new("/photos/IMG_0001.JPG");
+        assert_eq!(
+            temp_path(path, None),
+            PathBuf::from("/photos/.IMG_0001.JPG.argus-tmp")
+        );
+        assert_eq!(
+            temp_path(path, Some("argus-bak")),
+            PathBuf::from("/photos/.IMG_0001.JPG.argus-bak.argus-tmp")
+        );
+    }
+
     #[test]
     fn test_write_and_read_text_file() {
         let file_path = "test_file.txt";
@@ -214,6 +538,72 @@ mod tests {
         assert!(!file_exists(file_path));
     }
 
+    #[test]
+    fn test_walk_dir_img_parallel() {
+        let dir = tempfile::tempdir().unwrap();
+        for sub in ["a", "a/b", "c"] {
+            fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        let files = [
+            "1.jpg",
+            "a/2.png",
+            "a/b/3.jpeg",
+            "a/b/3.CR2",
+            "c/4.txt",
+            "c/5.gif",
+            "c/.6.jpg.icloud",
+            "c/.7.txt.icloud",
+        ];
+        for file in files {
+            write_text_file(dir.path().join(file), "").unwrap();
+        }
+
+        let mut found = Vec::new();
+        walk_dir_img_parallel(dir.path(), |p| {
+            found.push(p.file_name().unwrap().to_string_lossy().to_string());
+            true
+        });
+        found.sort();
+        // 与 RAW 同名的 JPEG 不单独返回，图片的 iCloud 占位文件也返回
+        assert_eq!(found, [".6.jpg.icloud", "1.jpg", "2.png", "3.CR2", "5.gif"]);
+
+        // 回调返回 false 时立即停止
+        let mut count = 0;
+        walk_dir_img_parallel(dir.path(), |_| {
+            count += 1;
+            false
+        });
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_verbatim_path() {
+        assert_eq!(
+            to_verbatim_path("D:/argus/img/1.jpg").as_deref(),
+            Some(r"\\?\D:\argus\img\1.jpg")
+        );
+        assert_eq!(
+            to_verbatim_path(r"\\nas\photos\1.jpg").as_deref(),
+            Some(r"\\?\UNC\nas\photos\1.jpg")
+        );
+        assert_eq!(to_verbatim_path(r"D:\argus\..\1.jpg"), None);
+        assert_eq!(to_verbatim_path("img/1.jpg"), None);
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\UNC\nas\photos"),
+            PathBuf::from(r"\\nas\photos")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_path_bytes_round_trip() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        // 非 UTF-8 文件名
+        let path = Path::new("/photos").join(OsStr::from_bytes(b"caf\xe9.jpg"));
+        assert_eq!(path_from_bytes(&path_to_bytes(&path)), path);
+    }
+
     #[test]
     fn test_file_size() {
         let file_path = "test_size.txt";
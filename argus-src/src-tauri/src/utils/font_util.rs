@@ -0,0 +1,94 @@
+//! 水印文字绘制（TrueType / OpenType 字体）
+//!
+//! 内置的点阵字体只有 ASCII 字符，中文等字符需要使用系统字体
+
+use crate::constant::WATERMARK_FONT_CANDIDATES;
+use crate::utils::file_util;
+use ab_glyph::{point, Font, FontVec, Glyph, PxScale, ScaleFont};
+use anyhow::{anyhow, Result};
+use image::{GrayImage, Luma};
+use std::fs;
+use std::path::Path;
+
+/// 加载字体文件（TTF、OTF、TTC 的第一个字体）
+pub fn load_font(path: &Path) -> Result<FontVec> {
+    let data = fs::read(file_util::long_path(path))?;
+    FontVec::try_from_vec_and_index(data, 0)
+        .map_err(|e| anyhow!("字体 {} 读取失败: {}", path.display(), e))
+}
+
+/// 加载水印字体【指定了字体时读取失败返回错误；未指定时依次查找系统中支持中文的字体，都没有时返回 None】
+/// - path 字体文件
+pub fn load_watermark_font(path: Option<&str>) -> Result<Option<FontVec>> {
+    if let Some(path) = path.filter(|x| !x.trim().is_empty()) {
+        return load_font(Path::new(path)).map(Some);
+    }
+    let font = WATERMARK_FONT_CANDIDATES
+        .iter()
+        .map(Path::new)
+        .filter(|x| x.is_file())
+        .find_map(|x| load_font(x).map_err(|e| log::warn!("{}", e)).ok());
+    if font.is_none() {
+        log::warn!("没有找到支持中文的系统字体，水印文字只支持 ASCII 字符");
+    }
+    Ok(font)
+}
+
+/// 把单行文本绘制为灰度遮罩【文字为 255，背景为 0，高度为 `height` 像素；字体中没有的字符跳过】
+pub fn render_text_mask(font: &FontVec, text: &str, height: u32) -> GrayImage {
+    // 字体高度（上升部分 + 下降部分）等于遮罩高度
+    let scale = PxScale::from(height.max(1) as f32);
+    let scaled = font.as_scaled(scale);
+    let mut glyphs: Vec<Glyph> = Vec::new();
+    let mut caret = 0.0f32;
+    let mut previous = None;
+    for c in text.chars().filter(|c| !c.is_control()) {
+        let id = font.glyph_id(c);
+        if id.0 == 0 && c != ' ' {
+            continue;
+        }
+        if let Some(previous) = previous {
+            caret += scaled.kern(previous, id);
+        }
+        glyphs.push(id.with_scale_and_position(scale, point(caret, scaled.ascent())));
+        caret += scaled.h_advance(id);
+        previous = Some(id);
+    }
+
+    let mut mask = GrayImage::new((caret.ceil() as u32).max(1), height.max(1));
+    for glyph in glyphs {
+        let Some(outline) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outline.px_bounds();
+        outline.draw(|x, y, coverage| {
+            let x = bounds.min.x as i64 + x as i64;
+            let y = bounds.min.y as i64 + y as i64;
+            if x < 0 || y < 0 || x >= mask.width() as i64 || y >= mask.height() as i64 {
+                return;
+            }
+            let pixel = mask.get_pixel_mut(x as u32, y as u32);
+            let value = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+            *pixel = Luma([pixel[0].max(value)]);
+        });
+    }
+    mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_text_mask() {
+        assert!(load_watermark_font(Some("/nonexistent/font.ttf")).is_err());
+        // 没有中文字体的环境跳过
+        let Some(font) = load_watermark_font(None).unwrap() else {
+            return;
+        };
+        let mask = render_text_mask(&font, "© 张三", 32);
+        assert_eq!(mask.height(), 32);
+        assert!(mask.width() > 32);
+        assert!(mask.pixels().any(|x| x[0] > 0));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::utils::exif_utils::gps_util::GpsCoordinate;
+use std::collections::{HashMap, VecDeque};
+
+/// 每度纬度对应的距离（米）
+const METERS_PER_DEGREE: f64 = 111_320.0;
+
+/// 使用 DBSCAN 按距离聚类坐标
+///
+/// 返回每个坐标所属的聚类编号（从 0 开始），噪声点为 None。
+/// 使用网格索引查找邻居，避免两两比较
+/// - points 十进制坐标
+/// - eps_meters 邻域半径（米）
+/// - min_points 核心点邻域内至少包含的坐标数量（含自身）
+pub fn dbscan(points: &[GpsCoordinate], eps_meters: f64, min_points: usize) -> Vec<Option<usize>> {
+    let grid = GeoGrid::new(points, eps_meters);
+    let mut labels: Vec<Option<usize>> = vec![None; points.len()];
+    let mut visited = vec![false; points.len()];
+    let mut cluster = 0;
+
+    for i in 0..points.len() {
+        if visited[i] {
+            continue;
+        }
+        visited[i] = true;
+        let neighbors = grid.neighbors(points, i);
+        if neighbors.len() < min_points {
+            continue;
+        }
+        labels[i] = Some(cluster);
+        let mut queue: VecDeque<usize> = neighbors.into();
+        while let Some(j) = queue.pop_front() {
+            if labels[j].is_none() {
+                labels[j] = Some(cluster);
+            }
+            if visited[j] {
+                continue;
+            }
+            visited[j] = true;
+            let expanded = grid.neighbors(points, j);
+            if expanded.len() >= min_points {
+                queue.extend(expanded);
+            }
+        }
+        cluster += 1;
+    }
+    labels
+}
+
+/// 按纬度方向 eps 大小划分的网格
+struct GeoGrid {
+    eps_meters: f64,
+    /// 网格边长（度）
+    cell_degrees: f64,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl GeoGrid {
+    fn new(points: &[GpsCoordinate], eps_meters: f64) -> GeoGrid {
+        let cell_degrees = (eps_meters / METERS_PER_DEGREE).max(1e-6);
+        let mut cells: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (i, point) in points.iter().enumerate() {
+            cells
+                .entry(GeoGrid::cell(point, cell_degrees))
+                .or_default()
+                .push(i);
+        }
+        GeoGrid {
+            eps_meters,
+            cell_degrees,
+            cells,
+        }
+    }
+
+    fn cell(point: &GpsCoordinate, cell_degrees: f64) -> (i64, i64) {
+        (
+            (point.latitude / cell_degrees).floor() as i64,
+            (point.longitude / cell_degrees).floor() as i64,
+        )
+    }
+
+    /// 邻域内的坐标（含自身）
+    fn neighbors(&self, points: &[GpsCoordinate], index: usize) -> Vec<usize> {
+        let point = &points[index];
+        let (row, col) = GeoGrid::cell(point, self.cell_degrees);
+        // 高纬度地区同样的距离跨越更多经度
+        let cos = point.latitude.to_radians().cos().abs().max(0.01);
+        let span = (1.0 / cos).ceil() as i64;
+        let mut result = Vec::new();
+        for r in row - 1..=row + 1 {
+            for c in col - span..=col + span {
+                let Some(items) = self.cells.get(&(r, c)) else {
+                    continue;
+                };
+                result.extend(
+                    items
+                        .iter()
+                        .filter(|&&j| point.distance_meters(&points[j]) <= self.eps_meters),
+                );
+            }
+        }
+        result
+    }
+}
+
+/// 坐标的中心点和覆盖半径（米）
+pub fn centroid(points: &[&GpsCoordinate]) -> (GpsCoordinate, f64) {
+    let count = points.len().max(1) as f64;
+    let center = GpsCoordinate {
+        latitude: points.iter().map(|x| x.latitude).sum::<f64>() / count,
+        longitude: points.iter().map(|x| x.longitude).sum::<f64>() / count,
+        altitude: None,
+    };
+    let radius = points
+        .iter()
+        .map(|x| center.distance_meters(x))
+        .fold(0.0, f64::max);
+    (center, radius)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(latitude: f64, longitude: f64) -> GpsCoordinate {
+        GpsCoordinate {
+            latitude,
+            longitude,
+            altitude: None,
+        }
+    }
+
+    #[test]
+    fn test_dbscan() {
+        let points = vec![
+            // 外滩附近
+            point(31.2400, 121.4900),
+            point(31.2410, 121.4905),
+            point(31.2395, 121.4912),
+            // 西湖附近
+            point(30.2500, 120.1500),
+            point(30.2504, 120.1508),
+            point(30.2490, 120.1496),
+            point(30.2510, 120.1502),
+            // 孤立点
+            point(39.9042, 116.4074),
+        ];
+        let labels = dbscan(&points, 500.0, 3);
+        assert_eq!(labels[0], Some(0));
+        assert!(labels[..3].iter().all(|x| *x == Some(0)));
+        assert!(labels[3..7].iter().all(|x| *x == Some(1)));
+        assert_eq!(labels[7], None);
+
+        let members: Vec<&GpsCoordinate> = points[3..7].iter().collect();
+        let (center, radius) = centroid(&members);
+        assert!((center.latitude - 30.2501).abs() < 1e-4);
+        assert!(radius < 200.0);
+    }
+}
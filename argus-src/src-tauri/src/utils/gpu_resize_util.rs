@@ -0,0 +1,258 @@
+//! GPU 缩放（wgpu 计算着色器）
+//!
+//! 只用于缩小：每个目标像素取覆盖的原图区域的平均值（预乘透明度），不区分滤波算法。
+//! 放大、像素格式不是 8 位或超出显卡缓冲区大小时返回错误，由调用方使用 SIMD 加速
+
+use anyhow::{anyhow, Result};
+use image::{ColorType, DynamicImage, RgbaImage};
+use once_cell::sync::Lazy;
+use std::borrow::Cow;
+use std::sync::mpsc;
+use wgpu::util::DeviceExt;
+
+/// 计算着色器的工作组大小（宽、高）
+const WORKGROUP_SIZE: u32 = 8;
+
+/// 区域平均缩放的计算着色器【像素按 RGBA8 打包为 u32】
+const SHADER: &str = r#"
+struct Params {
+    src_width: u32,
+    src_height: u32,
+    dst_width: u32,
+    dst_height: u32,
+}
+
+@group(0) @binding(0) var<storage, read> src: array<u32>;
+@group(0) @binding(1) var<storage, read_write> dst: array<u32>;
+@group(0) @binding(2) var<uniform> params: Params;
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.dst_width || id.y >= params.dst_height) {
+        return;
+    }
+    let scale_x = f32(params.src_width) / f32(params.dst_width);
+    let scale_y = f32(params.src_height) / f32(params.dst_height);
+    let x0 = min(u32(floor(f32(id.x) * scale_x)), params.src_width - 1u);
+    let y0 = min(u32(floor(f32(id.y) * scale_y)), params.src_height - 1u);
+    let x1 = clamp(u32(ceil(f32(id.x + 1u) * scale_x)), x0 + 1u, params.src_width);
+    let y1 = clamp(u32(ceil(f32(id.y + 1u) * scale_y)), y0 + 1u, params.src_height);
+    var color = vec3<f32>(0.0);
+    var alpha = 0.0;
+    for (var y = y0; y < y1; y++) {
+        for (var x = x0; x < x1; x++) {
+            let pixel = unpack4x8unorm(src[y * params.src_width + x]);
+            color += pixel.rgb * pixel.a;
+            alpha += pixel.a;
+        }
+    }
+    var rgb = vec3<f32>(0.0);
+    if (alpha > 0.0) {
+        rgb = color / alpha;
+    }
+    let count = f32((x1 - x0) * (y1 - y0));
+    dst[id.y * params.dst_width + id.x] = pack4x8unorm(vec4<f32>(rgb, alpha / count));
+}
+"#;
+
+/// 显卡设备和编译好的着色器【第一次使用时创建，没有可用显卡时为 None】
+static GPU: Lazy<Option<GpuContext>> = Lazy::new(|| match GpuContext::new() {
+    Ok(context) => Some(context),
+    Err(e) => {
+        log::warn!("GPU 缩放不可用: {}", e);
+        None
+    }
+});
+
+struct GpuContext {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+}
+
+impl GpuContext {
+    fn new() -> Result<GpuContext> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))
+        .ok_or_else(|| anyhow!("没有可用的显卡"))?;
+        // 使用显卡支持的最大缓冲区，默认限制只有 128 MB，放不下大尺寸照片
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("argus-resize"),
+                required_features: wgpu::Features::empty(),
+                required_limits: adapter.limits(),
+                memory_hints: wgpu::MemoryHints::Performance,
+            },
+            None,
+        ))?;
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("argus-resize"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(SHADER)),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("argus-resize"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        log::info!("GPU 缩放使用显卡: {}", adapter.get_info().name);
+        Ok(GpuContext {
+            device,
+            queue,
+            pipeline,
+        })
+    }
+}
+
+/// 是否有可用的显卡
+pub fn is_available() -> bool {
+    GPU.is_some()
+}
+
+/// 使用 GPU 缩小到指定宽高【保持原图的像素格式】
+/// - width 目标宽度
+/// - height 目标高度
+pub fn resize(image: &DynamicImage, width: u32, height: u32) -> Result<DynamicImage> {
+    let gpu = GPU.as_ref().ok_or_else(|| anyhow!("没有可用的显卡"))?;
+    if width == 0 || height == 0 || width > image.width() || height > image.height() {
+        return Err(anyhow!("GPU 缩放只支持缩小"));
+    }
+    let color = image.color();
+    if !matches!(
+        color,
+        ColorType::L8 | ColorType::La8 | ColorType::Rgb8 | ColorType::Rgba8
+    ) {
+        return Err(anyhow!("GPU 缩放不支持的像素格式: {:?}", color));
+    }
+    let source = image.to_rgba8();
+    let limits = gpu.device.limits();
+    let limit = (limits.max_storage_buffer_binding_size as u64).min(limits.max_buffer_size);
+    if source.len() as u64 > limit {
+        return Err(anyhow!("图片超出显卡缓冲区大小: {} 字节", source.len()));
+    }
+
+    let pixels = resize_rgba(gpu, &source, width, height)?;
+    let resized = RgbaImage::from_raw(width, height, pixels)
+        .map(DynamicImage::ImageRgba8)
+        .ok_or_else(|| anyhow!("GPU 缩放结果大小不正确"))?;
+    Ok(match color {
+        ColorType::L8 => DynamicImage::ImageLuma8(resized.to_luma8()),
+        ColorType::La8 => DynamicImage::ImageLumaA8(resized.to_luma_alpha8()),
+        ColorType::Rgb8 => DynamicImage::ImageRgb8(resized.to_rgb8()),
+        _ => resized,
+    })
+}
+
+/// 在显卡上缩放 RGBA8 像素，返回缩放后的像素
+fn resize_rgba(gpu: &GpuContext, source: &RgbaImage, width: u32, height: u32) -> Result<Vec<u8>> {
+    let device = &gpu.device;
+    let params: Vec<u8> = [source.width(), source.height(), width, height]
+        .iter()
+        .flat_map(|x| x.to_le_bytes())
+        .collect();
+    let output_size = width as u64 * height as u64 * 4;
+
+    let input = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("argus-resize-input"),
+        contents: source.as_raw(),
+        usage: wgpu::BufferUsages::STORAGE,
+    });
+    let output = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("argus-resize-output"),
+        size: output_size,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    let uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("argus-resize-params"),
+        contents: &params,
+        usage: wgpu::BufferUsages::UNIFORM,
+    });
+    let staging = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("argus-resize-staging"),
+        size: output_size,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("argus-resize"),
+        layout: &gpu.pipeline.get_bind_group_layout(0),
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: input.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: output.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: uniform.as_entire_binding(),
+            },
+        ],
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("argus-resize"),
+    });
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("argus-resize"),
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&gpu.pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(
+            width.div_ceil(WORKGROUP_SIZE),
+            height.div_ceil(WORKGROUP_SIZE),
+            1,
+        );
+    }
+    encoder.copy_buffer_to_buffer(&output, 0, &staging, 0, output_size);
+    gpu.queue.submit(Some(encoder.finish()));
+
+    // 等待计算完成并读回结果
+    let slice = staging.slice(..);
+    let (tx, rx) = mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = tx.send(result);
+    });
+    device.poll(wgpu::Maintain::Wait);
+    rx.recv()??;
+    let pixels = slice.get_mapped_range().to_vec();
+    staging.unmap();
+    Ok(pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn test_gpu_resize() {
+        // 没有显卡的环境跳过
+        if !is_available() {
+            return;
+        }
+        // 左半边红色、右半边透明，缩小后透明像素不影响颜色
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_fn(4, 4, |x, _| {
+            if x < 2 {
+                Rgba([255, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 255, 0])
+            }
+        }));
+        let resized = resize(&image, 2, 2).unwrap().to_rgba8();
+        assert_eq!(resized.get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+        assert_eq!(resized.get_pixel(1, 1)[3], 0);
+        assert!(resize(&image, 8, 8).is_err());
+    }
+}
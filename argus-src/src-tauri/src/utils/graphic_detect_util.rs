@@ -0,0 +1,75 @@
+//! 图形类图片检测
+//!
+//! 截图、界面、图表等图片颜色少、大面积纯色，有损编码会在边缘产生明显的噪点，
+//! 使用无损编码体积也不大
+
+use crate::constant::{GRAPHIC_MAX_UNIQUE_RATIO, GRAPHIC_MIN_FLAT_RATIO, GRAPHIC_SAMPLE_SIZE};
+use image::{DynamicImage, GenericImageView};
+use std::collections::HashSet;
+
+/// 判断图片是否为图形类图片
+///
+/// 按步长采样，相邻采样点颜色相同的占比足够高、且颜色种类足够少时视为图形
+pub fn is_graphic(image: &DynamicImage) -> bool {
+    let (width, height) = image.dimensions();
+    if width < 2 || height < 2 {
+        return false;
+    }
+    let step_x = (width / GRAPHIC_SAMPLE_SIZE).max(1);
+    let step_y = (height / GRAPHIC_SAMPLE_SIZE).max(1);
+
+    let mut samples = 0usize;
+    let mut flat = 0usize;
+    let mut colors = HashSet::new();
+    let mut y = 0;
+    while y < height {
+        let mut previous = None;
+        let mut x = 0;
+        while x < width {
+            let pixel = image.get_pixel(x, y).0;
+            if previous == Some(pixel) {
+                flat += 1;
+            }
+            previous = Some(pixel);
+            colors.insert(pixel);
+            samples += 1;
+            x += step_x;
+        }
+        y += step_y;
+    }
+
+    let flat_ratio = flat as f64 / samples as f64;
+    let unique_ratio = colors.len() as f64 / samples as f64;
+    flat_ratio >= GRAPHIC_MIN_FLAT_RATIO && unique_ratio <= GRAPHIC_MAX_UNIQUE_RATIO
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    #[test]
+    fn solid_blocks_are_graphic() {
+        let image = RgbImage::from_fn(400, 300, |x, _| {
+            if x < 200 {
+                Rgb([255, 255, 255])
+            } else {
+                Rgb([30, 120, 200])
+            }
+        });
+        assert!(is_graphic(&DynamicImage::ImageRgb8(image)));
+    }
+
+    #[test]
+    fn noisy_gradient_is_not_graphic() {
+        let image = RgbImage::from_fn(400, 300, |x, y| {
+            let noise = ((x * 7919 + y * 104729) % 23) as u8;
+            Rgb([
+                (x % 256) as u8 ^ noise,
+                (y % 256) as u8,
+                noise.wrapping_mul(11),
+            ])
+        });
+        assert!(!is_graphic(&DynamicImage::ImageRgb8(image)));
+    }
+}
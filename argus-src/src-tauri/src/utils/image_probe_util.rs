@@ -0,0 +1,536 @@
+//! 只读取文件头获取图片信息
+//!
+//! 不解码像素，按格式读取 JPEG 的 SOF 段、PNG 的 IHDR 块、WebP 的 VP8X/VP8/VP8L 块、
+//! HEIF/AVIF 的 ispe 属性，得到尺寸、方向和格式
+
+use crate::constant::PROBE_HEIF_META_MAX_BYTES;
+use crate::utils::exif_utils::orientation_util::{self, ImageTransform};
+use crate::utils::exif_utils::tiff;
+use crate::utils::file_util;
+use anyhow::{anyhow, Result};
+use image::ImageFormat;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// JPEG 图像开始
+const JPEG_SOI: &[u8] = &[0xFF, 0xD8];
+/// JPEG 扫描开始【之后是压缩数据】
+const JPEG_SOS: u8 = 0xDA;
+/// JPEG 图像结束
+const JPEG_EOI: u8 = 0xD9;
+/// JPEG APP1 段
+const JPEG_APP1: u8 = 0xE1;
+/// APP1 中 EXIF 数据的标识
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+/// EXIF 方向标签
+const TAG_ORIENTATION: u16 = 0x0112;
+/// PNG 文件签名
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+/// VP8 关键帧的起始码
+const VP8_START_CODE: &[u8] = &[0x9D, 0x01, 0x2A];
+/// VP8L 签名
+const VP8L_SIGNATURE: u8 = 0x2F;
+/// HEIF 品牌
+const HEIF_BRANDS: &[&[u8]] = &[
+    b"heic", b"heix", b"heim", b"heis", b"hevc", b"mif1", b"msf1",
+];
+/// AVIF 品牌
+const AVIF_BRANDS: &[&[u8]] = &[b"avif", b"avis"];
+
+/// 文件头中识别出的格式
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeFormat {
+    #[serde(rename = "jpeg")]
+    Jpeg,
+    #[serde(rename = "png")]
+    Png,
+    #[serde(rename = "webp")]
+    WebP,
+    #[serde(rename = "heif")]
+    Heif,
+    #[serde(rename = "avif")]
+    Avif,
+}
+
+impl ProbeFormat {
+    /// MIME 类型
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ProbeFormat::Jpeg => "image/jpeg",
+            ProbeFormat::Png => "image/png",
+            ProbeFormat::WebP => "image/webp",
+            ProbeFormat::Heif => "image/heif",
+            ProbeFormat::Avif => "image/avif",
+        }
+    }
+
+    /// 对应的图像库格式【图像库不支持的 HEIF 为 None】
+    pub fn image_format(&self) -> Option<ImageFormat> {
+        match self {
+            ProbeFormat::Jpeg => Some(ImageFormat::Jpeg),
+            ProbeFormat::Png => Some(ImageFormat::Png),
+            ProbeFormat::WebP => Some(ImageFormat::WebP),
+            ProbeFormat::Heif => None,
+            ProbeFormat::Avif => Some(ImageFormat::Avif),
+        }
+    }
+}
+
+/// 图片头信息
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageProbe {
+    /// 格式
+    pub format: ProbeFormat,
+    /// 存储的宽度【未按方向旋转】
+    pub width: u32,
+    /// 存储的高度【未按方向旋转】
+    pub height: u32,
+    /// EXIF 方向值（1-8）【JPEG 来自 EXIF，HEIF 由 irot、imir 换算，没有时为 None】
+    pub orientation: Option<u8>,
+}
+
+impl ImageProbe {
+    /// 按方向旋转后的显示尺寸
+    pub fn display_size(&self) -> (u32, u32) {
+        match self.orientation {
+            Some(5..=8) => (self.height, self.width),
+            _ => (self.width, self.height),
+        }
+    }
+}
+
+/// 读取图片文件头信息
+pub fn probe_image<P: AsRef<Path>>(path: P) -> Result<ImageProbe> {
+    let file = File::open(file_util::long_path(path.as_ref()))?;
+    probe_reader(&mut BufReader::new(file))
+}
+
+/// 从读取器中读取图片头信息【按文件开头的魔数判断格式】
+pub fn probe_reader<R: Read + Seek>(reader: &mut R) -> Result<ImageProbe> {
+    let mut magic = [0u8; 12];
+    reader.read_exact(&mut magic)?;
+    reader.seek(SeekFrom::Start(0))?;
+    if magic.starts_with(JPEG_SOI) {
+        probe_jpeg(reader)
+    } else if magic.starts_with(PNG_SIGNATURE) {
+        probe_png(reader)
+    } else if &magic[0..4] == b"RIFF" && &magic[8..12] == b"WEBP" {
+        probe_webp(reader)
+    } else if &magic[4..8] == b"ftyp" {
+        probe_heif(reader)
+    } else {
+        Err(anyhow!("无法识别的图片格式"))
+    }
+}
+
+/// JPEG：遍历标记段直到 SOF，途中读取 APP1 中的方向
+fn probe_jpeg<R: Read + Seek>(reader: &mut R) -> Result<ImageProbe> {
+    reader.seek(SeekFrom::Start(JPEG_SOI.len() as u64))?;
+    let mut orientation = None;
+    loop {
+        let marker = next_jpeg_marker(reader)?;
+        match marker {
+            JPEG_SOS | JPEG_EOI => return Err(anyhow!("JPEG 中没有 SOF 段")),
+            // 没有长度的独立标记
+            0x01 | 0xD0..=0xD7 => continue,
+            _ => {}
+        }
+        let length = read_u16_be(reader)?
+            .checked_sub(2)
+            .ok_or_else(|| anyhow!("JPEG 段长度错误"))? as usize;
+        if is_jpeg_sof(marker) {
+            let mut sof = [0u8; 5];
+            reader.read_exact(&mut sof)?;
+            return Ok(ImageProbe {
+                format: ProbeFormat::Jpeg,
+                width: u16::from_be_bytes([sof[3], sof[4]]) as u32,
+                height: u16::from_be_bytes([sof[1], sof[2]]) as u32,
+                orientation,
+            });
+        }
+        if marker == JPEG_APP1 && orientation.is_none() {
+            let mut data = vec![0u8; length];
+            reader.read_exact(&mut data)?;
+            orientation = data.strip_prefix(EXIF_HEADER).and_then(exif_orientation);
+            continue;
+        }
+        reader.seek(SeekFrom::Current(length as i64))?;
+    }
+}
+
+/// 读取下一个 JPEG 标记【跳过填充的 0xFF】
+fn next_jpeg_marker<R: Read>(reader: &mut R) -> Result<u8> {
+    let mut byte = [0u8; 1];
+    reader.read_exact(&mut byte)?;
+    if byte[0] != 0xFF {
+        return Err(anyhow!("JPEG 标记错误"));
+    }
+    while byte[0] == 0xFF {
+        reader.read_exact(&mut byte)?;
+    }
+    Ok(byte[0])
+}
+
+/// 是否为 SOF 段【排除 DHT（C4）、JPG（C8）、DAC（CC）】
+fn is_jpeg_sof(marker: u8) -> bool {
+    matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC)
+}
+
+/// 读取 EXIF 中的方向值
+fn exif_orientation(data: &[u8]) -> Option<u8> {
+    let value = tiff::Parser::new(data)
+        .ok()?
+        .ifd0()?
+        .find(TAG_ORIENTATION)?
+        .as_u32()?;
+    (1..=8).contains(&value).then_some(value as u8)
+}
+
+/// PNG：签名之后的第一个块就是 IHDR
+fn probe_png<R: Read + Seek>(reader: &mut R) -> Result<ImageProbe> {
+    let mut header = [0u8; 24];
+    reader.read_exact(&mut header)?;
+    if &header[12..16] != b"IHDR" {
+        return Err(anyhow!("PNG 中没有 IHDR 块"));
+    }
+    Ok(ImageProbe {
+        format: ProbeFormat::Png,
+        width: u32::from_be_bytes([header[16], header[17], header[18], header[19]]),
+        height: u32::from_be_bytes([header[20], header[21], header[22], header[23]]),
+        orientation: None,
+    })
+}
+
+/// WebP：RIFF 头之后的第一个块为 VP8X（扩展）、VP8（有损）或 VP8L（无损）
+fn probe_webp<R: Read + Seek>(reader: &mut R) -> Result<ImageProbe> {
+    let mut header = [0u8; 30];
+    reader.read_exact(&mut header)?;
+    let chunk = &header[20..];
+    let (width, height) = match &header[12..16] {
+        // 画布宽高减 1，各 24 位小端
+        b"VP8X" => (
+            u32::from_le_bytes([chunk[4], chunk[5], chunk[6], 0]) + 1,
+            u32::from_le_bytes([chunk[7], chunk[8], chunk[9], 0]) + 1,
+        ),
+        // 3 字节帧标记、3 字节起始码之后是 14 位宽高
+        b"VP8 " => {
+            if &chunk[3..6] != VP8_START_CODE {
+                return Err(anyhow!("WebP 中 VP8 起始码错误"));
+            }
+            (
+                (u16::from_le_bytes([chunk[6], chunk[7]]) & 0x3FFF) as u32,
+                (u16::from_le_bytes([chunk[8], chunk[9]]) & 0x3FFF) as u32,
+            )
+        }
+        // 签名之后是 14 位的宽减 1、14 位的高减 1
+        b"VP8L" => {
+            if chunk[0] != VP8L_SIGNATURE {
+                return Err(anyhow!("WebP 中 VP8L 签名错误"));
+            }
+            let bits = u32::from_le_bytes([chunk[1], chunk[2], chunk[3], chunk[4]]);
+            ((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1)
+        }
+        _ => return Err(anyhow!("WebP 中没有图像块")),
+    };
+    Ok(ImageProbe {
+        format: ProbeFormat::WebP,
+        width,
+        height,
+        orientation: None,
+    })
+}
+
+/// HEIF/AVIF：按 ftyp 的品牌判断格式，在 meta/iprp/ipco 中读取 ispe、irot、imir 属性
+///
+/// 网格图、缩略图各有自己的 ispe，取像素最多的一个作为主图尺寸
+fn probe_heif<R: Read + Seek>(reader: &mut R) -> Result<ImageProbe> {
+    let mut format = None;
+    loop {
+        let Some((box_type, size)) = read_box_header(reader)? else {
+            return Err(anyhow!("HEIF 中没有 meta 盒子"));
+        };
+        match &box_type {
+            b"ftyp" => {
+                let mut data = vec![0u8; size as usize];
+                reader.read_exact(&mut data)?;
+                format = heif_format(&data);
+            }
+            b"meta" => {
+                let format = format.ok_or_else(|| anyhow!("不支持的 HEIF 品牌"))?;
+                if size > PROBE_HEIF_META_MAX_BYTES {
+                    return Err(anyhow!("HEIF meta 盒子过大"));
+                }
+                let mut data = vec![0u8; size as usize];
+                reader.read_exact(&mut data)?;
+                return parse_heif_meta(&data, format);
+            }
+            _ => {
+                reader.seek(SeekFrom::Current(size as i64))?;
+            }
+        }
+    }
+}
+
+/// 按 ftyp 中的主品牌和兼容品牌判断格式【AVIF 优先】
+fn heif_format(ftyp: &[u8]) -> Option<ProbeFormat> {
+    // 主品牌 4 字节、版本 4 字节，之后是兼容品牌
+    let brands: Vec<&[u8]> = ftyp
+        .get(0..4)
+        .into_iter()
+        .chain(ftyp.get(8..).unwrap_or_default().chunks_exact(4))
+        .collect();
+    if brands.iter().any(|x| AVIF_BRANDS.contains(x)) {
+        Some(ProbeFormat::Avif)
+    } else if brands.iter().any(|x| HEIF_BRANDS.contains(x)) {
+        Some(ProbeFormat::Heif)
+    } else {
+        None
+    }
+}
+
+/// 读取盒子的类型和内容长度【到达文件末尾时返回 None】
+fn read_box_header<R: Read + Seek>(reader: &mut R) -> Result<Option<([u8; 4], u64)>> {
+    let mut header = [0u8; 8];
+    match reader.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    let box_type = [header[4], header[5], header[6], header[7]];
+    let size = match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+        // 长度为 0 表示一直到文件末尾
+        0 => {
+            let position = reader.stream_position()?;
+            let end = reader.seek(SeekFrom::End(0))?;
+            reader.seek(SeekFrom::Start(position))?;
+            end - position
+        }
+        // 长度为 1 表示之后 8 字节为 64 位长度
+        1 => {
+            let mut large = [0u8; 8];
+            reader.read_exact(&mut large)?;
+            u64::from_be_bytes(large)
+                .checked_sub(16)
+                .ok_or_else(|| anyhow!("HEIF 盒子长度错误"))?
+        }
+        size => (size as u64)
+            .checked_sub(8)
+            .ok_or_else(|| anyhow!("HEIF 盒子长度错误"))?,
+    };
+    Ok(Some((box_type, size)))
+}
+
+/// 解析 meta 盒子的内容
+fn parse_heif_meta(meta: &[u8], format: ProbeFormat) -> Result<ImageProbe> {
+    // meta 是 FullBox，先跳过 4 字节的版本和标志
+    let ipco = meta
+        .get(4..)
+        .and_then(|x| find_box(x, b"iprp"))
+        .and_then(|x| find_box(x, b"ipco"))
+        .ok_or_else(|| anyhow!("HEIF 中没有 ipco 盒子"))?;
+    let mut size: Option<(u32, u32)> = None;
+    let mut orientation = 1;
+    for (box_type, data) in boxes(ipco) {
+        match &box_type {
+            // FullBox，4 字节版本和标志之后是宽、高
+            b"ispe" if data.len() >= 12 => {
+                let width = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+                let height = u32::from_be_bytes([data[8], data[9], data[10], data[11]]);
+                let area = size.map_or(0, |(w, h)| w as u64 * h as u64);
+                if width as u64 * height as u64 > area {
+                    size = Some((width, height));
+                }
+            }
+            // 逆时针旋转的角度（90 的倍数）
+            b"irot" if !data.is_empty() => {
+                for _ in 0..(data[0] & 0x03) {
+                    orientation = orientation_util::compose(orientation, ImageTransform::Rotate270);
+                }
+            }
+            // 0 为沿垂直轴镜像（左右翻转），1 为沿水平轴镜像（上下翻转）
+            b"imir" if !data.is_empty() => {
+                let transform = if data[0] & 0x01 == 0 {
+                    ImageTransform::FlipHorizontal
+                } else {
+                    ImageTransform::FlipVertical
+                };
+                orientation = orientation_util::compose(orientation, transform);
+            }
+            _ => {}
+        }
+    }
+    let (width, height) = size.ok_or_else(|| anyhow!("HEIF 中没有 ispe 属性"))?;
+    Ok(ImageProbe {
+        format,
+        width,
+        height,
+        orientation: Some(orientation),
+    })
+}
+
+/// 在盒子列表中查找指定类型的盒子，返回其内容
+fn find_box<'a>(data: &'a [u8], box_type: &[u8; 4]) -> Option<&'a [u8]> {
+    boxes(data).find(|(x, _)| x == box_type).map(|(_, x)| x)
+}
+
+/// 遍历内存中的盒子列表【长度错误时停止】
+fn boxes(mut data: &[u8]) -> impl Iterator<Item = ([u8; 4], &[u8])> {
+    std::iter::from_fn(move || {
+        let size = u32::from_be_bytes(data.get(0..4)?.try_into().ok()?) as usize;
+        let box_type: [u8; 4] = data.get(4..8)?.try_into().ok()?;
+        let (start, end) = match size {
+            0 => (8, data.len()),
+            1 => {
+                let large = u64::from_be_bytes(data.get(8..16)?.try_into().ok()?);
+                (16, usize::try_from(large).ok()?)
+            }
+            size => (8, size),
+        };
+        if end < start || end > data.len() {
+            return None;
+        }
+        let content = &data[start..end];
+        data = &data[end..];
+        Some((box_type, content))
+    })
+}
+
+fn read_u16_be<R: Read>(reader: &mut R) -> Result<u16> {
+    let mut buf = [0u8; 2];
+    reader.read_exact(&mut buf)?;
+    Ok(u16::from_be_bytes(buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn probe(data: Vec<u8>) -> Result<ImageProbe> {
+        probe_reader(&mut Cursor::new(data))
+    }
+
+    /// 生成盒子
+    fn mp4_box(box_type: &[u8; 4], content: &[u8]) -> Vec<u8> {
+        let mut data = ((content.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend(box_type);
+        data.extend(content);
+        data
+    }
+
+    #[test]
+    fn test_probe_jpeg() {
+        // 方向为 6 的大端 EXIF
+        let mut exif = EXIF_HEADER.to_vec();
+        exif.extend(b"MM\0\x2A\0\0\0\x08\0\x01");
+        exif.extend([0x01, 0x12, 0x00, 0x03, 0, 0, 0, 1, 0, 6, 0, 0]);
+        exif.extend([0, 0, 0, 0]);
+        let mut data = JPEG_SOI.to_vec();
+        data.extend([0xFF, JPEG_APP1]);
+        data.extend(((exif.len() + 2) as u16).to_be_bytes());
+        data.extend(&exif);
+        // DHT 段不是 SOF
+        data.extend([0xFF, 0xC4, 0x00, 0x04, 0x00, 0x00]);
+        data.extend([0xFF, 0xFF, 0xC2, 0x00, 0x11, 0x08, 0x0B, 0xB8, 0x0F, 0xA0]);
+        data.extend([0u8; 12]);
+        let info = probe(data).unwrap();
+        assert_eq!(info.format, ProbeFormat::Jpeg);
+        assert_eq!((info.width, info.height), (4000, 3000));
+        assert_eq!(info.orientation, Some(6));
+        assert_eq!(info.display_size(), (3000, 4000));
+
+        let mut no_sof = JPEG_SOI.to_vec();
+        no_sof.extend([0xFF, JPEG_SOS, 0x00, 0x02, 0, 0, 0, 0, 0, 0]);
+        assert!(probe(no_sof).is_err());
+    }
+
+    #[test]
+    fn test_probe_png() {
+        let mut data = PNG_SIGNATURE.to_vec();
+        data.extend([0, 0, 0, 13]);
+        data.extend(b"IHDR");
+        data.extend(1920u32.to_be_bytes());
+        data.extend(1080u32.to_be_bytes());
+        data.extend([8, 6, 0, 0, 0]);
+        let info = probe(data).unwrap();
+        assert_eq!(info.format, ProbeFormat::Png);
+        assert_eq!((info.width, info.height), (1920, 1080));
+        assert_eq!(info.orientation, None);
+    }
+
+    #[test]
+    fn test_probe_webp() {
+        let webp = |fourcc: &[u8], chunk: &[u8]| {
+            let mut data = b"RIFF\0\0\0\0WEBP".to_vec();
+            data.extend(fourcc);
+            data.extend((chunk.len() as u32).to_le_bytes());
+            data.extend(chunk);
+            data.resize(30, 0);
+            data
+        };
+        let vp8x = webp(b"VP8X", &[0x10, 0, 0, 0, 0x7F, 0x07, 0, 0x37, 0x04, 0]);
+        assert_eq!(
+            probe(vp8x).map(|x| (x.format, x.width, x.height)).unwrap(),
+            (ProbeFormat::WebP, 1920, 1080)
+        );
+        let vp8 = webp(
+            b"VP8 ",
+            &[0, 0, 0, 0x9D, 0x01, 0x2A, 0x80, 0x02, 0xE0, 0x01],
+        );
+        assert_eq!(probe(vp8).map(|x| (x.width, x.height)).unwrap(), (640, 480));
+        let bits: u32 = 799 | (599 << 14);
+        let mut lossless = vec![VP8L_SIGNATURE];
+        lossless.extend(bits.to_le_bytes());
+        let vp8l = webp(b"VP8L", &lossless);
+        assert_eq!(
+            probe(vp8l).map(|x| (x.width, x.height)).unwrap(),
+            (800, 600)
+        );
+    }
+
+    #[test]
+    fn test_probe_heif() {
+        let ispe = |width: u32, height: u32| {
+            let mut content = vec![0u8; 4];
+            content.extend(width.to_be_bytes());
+            content.extend(height.to_be_bytes());
+            mp4_box(b"ispe", &content)
+        };
+        let mut ipco = ispe(512, 384);
+        ipco.extend(ispe(4032, 3024));
+        ipco.extend(mp4_box(b"irot", &[3]));
+        let iprp = mp4_box(b"iprp", &mp4_box(b"ipco", &ipco));
+        let mut meta = vec![0u8; 4];
+        meta.extend(mp4_box(b"hdlr", &[0u8; 20]));
+        meta.extend(iprp);
+
+        let mut data = mp4_box(b"ftyp", b"heic\0\0\0\0mif1heic");
+        data.extend(mp4_box(b"meta", &meta));
+        data.extend(mp4_box(b"mdat", &[0u8; 16]));
+        let info = probe(data).unwrap();
+        assert_eq!(info.format, ProbeFormat::Heif);
+        assert_eq!(info.format.image_format(), None);
+        assert_eq!((info.width, info.height), (4032, 3024));
+        // 逆时针旋转 270° 即顺时针旋转 90°
+        assert_eq!(info.orientation, Some(6));
+
+        let mut avif = mp4_box(b"ftyp", b"avif\0\0\0\0mif1miaf");
+        avif.extend(mp4_box(b"meta", &meta));
+        let format = probe(avif).unwrap().format;
+        assert_eq!(format, ProbeFormat::Avif);
+        assert_eq!(format.image_format(), Some(ImageFormat::Avif));
+
+        let mut video = mp4_box(b"ftyp", b"isom\0\0\0\0mp41");
+        video.extend(mp4_box(b"meta", &meta));
+        assert!(probe(video).is_err());
+    }
+
+    #[test]
+    fn test_probe_unknown() {
+        assert!(probe(b"GIF89a\x01\0\x01\0\0\0\0\0".to_vec()).is_err());
+        assert!(probe(vec![0xFF, 0xD8]).is_err());
+    }
+}
@@ -1,23 +1,43 @@
-use crate::computed_value::ComputedValue;
+use crate::constant::TIFF_MAX_PAGES;
+use crate::constant::{
+    BLURHASH_COMPONENTS, DOMINANT_COLOR_THUMBNAIL_SIZE, THUMBNAIL_TEMP_FILE_PREFIX,
+};
 use crate::errors::AError;
-use crate::structs::config::SYS_CONFIG;
+use crate::services::{disk_space_service, thumbnail_cache_service};
+use crate::structs::config::sys_config;
+use crate::structs::disk_space::DiskSpacePurpose;
+use crate::structs::histogram::HistogramSummary;
 use crate::structs::image_size::ImageSize;
+use crate::structs::image_validation::ImageValidationLevel;
+use crate::structs::thumbnail_encoding::EncodeOptions;
+use crate::structs::watermark::WatermarkOptions;
 use crate::utils::base64_util::base64_encode;
+use crate::utils::exif_utils::exif_util;
+use crate::utils::exif_utils::tag::ImgExif;
+use crate::utils::exif_utils::tiff;
 use crate::utils::file_hash_util::FileHashUtils;
 use crate::utils::file_util::file_exists;
+use crate::utils::latency_util::{LatencySpan, LatencyStage};
 use crate::utils::system_state_util::get_memory_as_percentage;
 use crate::utils::task_util::PHOTO_LOAD_RECEIVER;
-use crate::utils::{file_util, image_format_util};
+use crate::utils::animation_util::{self, AnimationInfo};
+use crate::utils::{
+    bitmap_font, blurhash_util, color_util, file_util, font_util, image_format_util,
+    image_probe_util, panic_util, raw_preview_util, resize_util, scan_metrics_util,
+};
+use ab_glyph::FontVec;
 use anyhow::{anyhow, Context, Result};
 use image::{imageops, DynamicImage, GenericImageView, ImageError, ImageFormat};
+use image::{ImageDecoder, Pixel, Rgba, RgbaImage};
 use image::{imageops::FilterType, ImageReader};
+use image::codecs::avif::AvifEncoder;
+use image::codecs::jpeg::JpegEncoder;
 use log::{error, info, warn};
-use std::io::{BufReader, Cursor};
+use std::io::{BufRead, BufReader, BufWriter, Cursor, Seek, Write};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
 use std::time::Instant;
-use std::{fs, panic};
-use tokio::sync::{mpsc, Mutex};
+use std::fs;
+use tokio::sync::mpsc;
 use tokio::task::JoinSet;
 
 #[derive(Debug, Clone)]
@@ -28,6 +48,8 @@ pub struct ImageOperate {
     pub img_path: String,
     /// 文件名称
     pub img_name: String,
+    /// 完整路径【无损保存非 UTF-8 路径】
+    pub full_path: PathBuf,
     /// 文件 Hash【唯一 ID】
     pub hash: String,
     /// 图片宽度。
@@ -40,82 +62,237 @@ pub struct ImageOperate {
     pub file_size: i64,
     /// 图片格式（如 JPEG, PNG, WebP）。
     pub format: Option<ImageFormat>,
+    /// 导入时完成的校验级别
+    pub validation_level: ImageValidationLevel,
+    /// 动图信息【不是动图时为 None】
+    pub animation: Option<AnimationInfo>,
+    /// 读取的 EXIF【读取失败时为 None，随照片记录一起写入】
+    pub exif: Option<ImgExif>,
+    /// 所属的扫描【不在扫描中时为空，用于统计数据库写入】
+    pub scan_id: Option<i32>,
 }
 
 impl ImageOperate {
-    /// 读取基础图像信息
-    pub async fn read_image(image_path: &str) -> Result<ImageOperate> {
+    /// 读取基础图像信息和 EXIF
+    pub async fn read_image<P: AsRef<Path>>(image_path: P) -> Result<ImageOperate> {
+        let exif = read_exif_logged(image_path.as_ref());
+        ImageOperate::read_image_with_exif(image_path, exif).await
+    }
+
+    /// 读取基础图像信息，并写入照片记录
+    /// - image_path 图像路径
+    /// - exif 已经读取的 EXIF【扫描时与缩略图并行读取，这里不再重复读取】
+    pub async fn read_image_with_exif<P: AsRef<Path>>(
+        image_path: P,
+        exif: Option<ImgExif>,
+    ) -> Result<ImageOperate> {
+        let rs = ImageOperate::inspect_image_with_exif(image_path, exif).await?;
+
+        let arc = PHOTO_LOAD_RECEIVER.clone();
+        let qqq = arc.send(rs.clone()).await;
+
+        if qqq.is_err() {}
+
+        Ok(rs)
+    }
+
+    /// 读取基础图像信息和 EXIF【不写入照片记录，如远程存储中的照片下载后的临时文件】
+    pub async fn inspect_image<P: AsRef<Path>>(image_path: P) -> Result<ImageOperate> {
+        let exif = read_exif_logged(image_path.as_ref());
+        ImageOperate::inspect_image_with_exif(image_path, exif).await
+    }
+
+    /// 读取基础图像信息【不写入照片记录，EXIF 已经读取，如远程照片从文件开头读取的 EXIF】
+    pub async fn inspect_image_with_exif<P: AsRef<Path>>(
+        image_path: P,
+        exif: Option<ImgExif>,
+    ) -> Result<ImageOperate> {
+        let image_path = file_util::strip_verbatim_prefix(image_path);
         // 检测文件是否存在
-        if !file_exists(image_path) {
+        if !file_exists(&image_path) {
             return Err(anyhow!(AError::SpecifiedFileDoesNotExist.message()));
         };
 
-        // 猜测文件类型并打开
-        let reader = image::ImageReader::open(image_path)?.with_guessed_format()?;
-
-        // 格式
-        let format = (&reader).format();
-
-        // 获取图像长宽信息
-        let (width, height) = reader.into_dimensions()?;
+        // 按配置的级别校验图像，并获取格式和长宽信息
+        let validation_level = ImageValidationLevel::from_config();
+        let (format, width, height) = panic_util::catch_panic(|| {
+            ImageOperate::validate_image(&image_path, validation_level)
+        })?;
+        // 动图只读取文件结构获取帧数和时长
+        let animation = animation_util::read_animation(&image_path, format);
         // 计算长宽比例信息
         let res = width.clone() as f32 / height.clone() as f32;
         let aspect_ratio = (res * 100.0).round() / 100.0;
 
         // 获取文件大小
-        let metadata = tokio::fs::metadata(image_path).await?;
+        let metadata = tokio::fs::metadata(file_util::long_path(&image_path)).await?;
         let file_size = metadata.len();
 
         // 获取图像名称和路径
-        let file_path = Path::new(image_path);
+        let file_path = image_path.as_path();
         // 获取路径部分（去除文件名）
-        let file_parent = file_path
-            .parent()
-            .unwrap_or(Path::new(""))
-            .display()
-            .to_string();
-        // 获取文件名部分
-        // let file_name = file_path.file_name().unwrap_or(Path::new("").as_ref()).to_str().to_string();
+        let file_parent = file_util::display_path(file_path.parent().unwrap_or(Path::new("")));
+        // 获取文件名部分【非 UTF-8 文件名有损展示，完整路径另行保存】
         let file_name = file_path
             .file_name()
-            .and_then(|os_str| os_str.to_str())
-            .unwrap_or("") // 默认值为空字符串
-            .to_string();
+            .map(|os_str| os_str.to_string_lossy().to_string())
+            .unwrap_or_default(); // 默认值为空字符串
 
         // 计算 Hash
-        let hash = FileHashUtils::sha256_async(image_path)
+        let hash_started = Instant::now();
+        let hash = FileHashUtils::sha256_async(&image_path)
             .await
             .map_err(|e| anyhow!(AError::HashConversionFailed.message()))?;
+        scan_metrics_util::record_hash(file_size, hash_started.elapsed().as_micros() as u64);
 
         let rs = ImageOperate {
             img_path: file_parent,
             format,
             hash,
             img_name: String::from(file_name),
+            full_path: image_path.clone(),
             file_size: file_size as i64,
             aspect_ratio,
             width: width.clone() as i32,
             height: height.clone() as i32,
             image_dynamic: None,
+            validation_level,
+            animation,
+            exif,
+            scan_id: scan_metrics_util::current_scan(),
         };
+        Ok(rs)
+    }
 
-        let arc = PHOTO_LOAD_RECEIVER.clone();
-        let qqq = arc.send(rs.clone()).await;
+    /// 按指定级别校验图像
+    ///
+    /// - `None` 直接信任扩展名，要求长宽可读
+    /// - `Header` 校验文件头魔数，并要求长宽可读
+    /// - `Full` 在文件头校验基础上完整解码一次
+    ///
+    /// 图像库读取不了长宽时（如 HEIF）只读取文件头获取格式和长宽，
+    /// 图像库不支持的格式无法通过 `Full` 校验
+    ///
+    /// RAW 文件没有对应的图像格式，长宽为 TIFF 结构中最大图像的长宽（读取不到时使用预览图的长宽），
+    /// `None` 以外的级别要求有可用的预览图
+    pub fn validate_image(
+        image_path: &Path,
+        level: ImageValidationLevel,
+    ) -> Result<(Option<ImageFormat>, u32, u32)> {
+        if file_util::is_raw_file(image_path) {
+            let dimensions = raw_preview_util::raw_dimensions(image_path);
+            if let (Some((width, height)), ImageValidationLevel::None) = (dimensions, level) {
+                return Ok((None, width, height));
+            }
+            let data = raw_preview_util::read_thumbnail_source(image_path)?;
+            let preview = ImageReader::with_format(Cursor::new(data), ImageFormat::Jpeg)
+                .into_dimensions()
+                .map_err(|e| anyhow!("{}: {}", AError::ImageValidationFailed.message(), e))?;
+            let (width, height) = dimensions.unwrap_or(preview);
+            return Ok((None, width, height));
+        }
+        let open_path = file_util::long_path(image_path);
+        if level == ImageValidationLevel::None {
+            // 只根据扩展名判断格式，不读取文件内容猜测
+            let format = ImageFormat::from_path(image_path).ok();
+            // 图像库不支持的格式（如 HEIF）只读取文件头获取长宽
+            let (width, height) = image::ImageReader::open(&open_path)?
+                .into_dimensions()
+                .ok()
+                .or_else(|| {
+                    image_probe_util::probe_image(image_path)
+                        .ok()
+                        .map(|x| (x.width, x.height))
+                })
+                .ok_or_else(|| {
+                    anyhow!(
+                        "{}: {}",
+                        AError::ImageValidationFailed.message(),
+                        image_path.display()
+                    )
+                })?;
+            let (width, height) = largest_dimensions(image_path, format, (width, height));
+            return Ok((format, width, height));
+        }
 
-        if qqq.is_err() {}
+        // 根据魔数猜测文件类型
+        let reader = image::ImageReader::open(&open_path)?.with_guessed_format()?;
+        let guessed = reader.format();
+        let dimensions = guessed.and_then(|_| reader.into_dimensions().ok());
+        let (format, (width, height)) = match (guessed, dimensions) {
+            (Some(format), Some(size)) => (Some(format), size),
+            // 图像库不支持的格式（如 HEIF）只读取文件头获取格式和长宽
+            _ => {
+                let probe = image_probe_util::probe_image(image_path)
+                    .map_err(|e| anyhow!("{}: {}", AError::ImageValidationFailed.message(), e))?;
+                (probe.format.image_format(), (probe.width, probe.height))
+            }
+        };
 
-        Ok(rs)
+        if level == ImageValidationLevel::Full {
+            image::ImageReader::open(&open_path)?
+                .with_guessed_format()?
+                .decode()
+                .map_err(|e| anyhow!("{}: {}", AError::ImageValidationFailed.message(), e))?;
+        }
+
+        let (width, height) = largest_dimensions(image_path, format, (width, height));
+        Ok((format, width, height))
     }
 
     /// 解析图片信息并存储
     pub fn read_image_dynamic(&self) -> Result<DynamicImage> {
         // 图像本体信息
-        let full_path = Path::new(&self.img_path).join(&self.img_name); // 合并路径和文件名
+        let full_path = file_util::long_path(&self.full_path);
         let reader = image::ImageReader::open(full_path)?.with_guessed_format()?;
         let image_data = reader.decode()?;
         Ok(image_data)
     }
 
+    /// 解析图片信息并按 EXIF 方向摆正【用于生成缩略图】
+    pub fn read_image_oriented(&self) -> Result<DynamicImage> {
+        ImageOperate::open_oriented(&self.full_path)
+    }
+
+    /// 读取图像并按 EXIF 方向摆正【重新编码后不再保留方向标记】
+    ///
+    /// 多页 TIFF 使用像素最多的页面，动图使用第一帧，RAW 文件使用同名 JPEG 或内嵌的预览图
+    pub fn open_oriented(path: &Path) -> Result<DynamicImage> {
+        if file_util::is_raw_file(path) {
+            let data = raw_preview_util::read_thumbnail_source(path)?;
+            return decode_oriented(ImageReader::with_format(
+                Cursor::new(data),
+                ImageFormat::Jpeg,
+            ));
+        }
+        let reader = ImageReader::open(file_util::long_path(path))?.with_guessed_format()?;
+        if let Some(format @ (ImageFormat::Gif | ImageFormat::Png | ImageFormat::WebP)) =
+            reader.format()
+        {
+            if let Some(frame) = animation_util::decode_first_frame(path, format)? {
+                return Ok(frame);
+            }
+        }
+        if reader.format() == Some(ImageFormat::Tiff) {
+            if let Some(page) = largest_tiff_page(path) {
+                return decode_oriented(ImageReader::with_format(page, ImageFormat::Tiff));
+            }
+        }
+        decode_oriented(reader)
+    }
+
+    /// 读取 TIFF 所有页面的尺寸【只按偏移量读取 IFD；不是 TIFF 或解析失败时返回空】
+    pub fn tiff_pages(path: &Path) -> Vec<tiff::Page> {
+        let Ok(file) = fs::File::open(file_util::long_path(path)) else {
+            return Vec::new();
+        };
+        let limits = tiff::Limits {
+            max_ifds: TIFF_MAX_PAGES,
+            ..Default::default()
+        };
+        tiff::read_pages(&mut BufReader::new(file), limits).unwrap_or_default()
+    }
+
     /// 将图像压缩返回
     pub async fn compression(&self, scale: f32) -> Result<DynamicImage> {
         // 获取图像的原始尺寸
@@ -129,8 +306,9 @@ impl ImageOperate {
         let start_resize = Instant::now();
 
         let image = self.read_image_dynamic()?;
-        let result = image.resize_exact(new_width, new_height, FilterType::Triangle);
-        println!(
+        let result =
+            resize_util::resize_exact(&image, new_width, new_height, FilterType::Triangle);
+        log::debug!(
             "图片：{}, 压缩: {:?}, 内存占用:{}",
             self.img_path,
             start_resize.elapsed(),
@@ -148,7 +326,7 @@ impl ImageOperate {
         filter: imageops::FilterType,
     ) -> Result<DynamicImage> {
         let image = self.read_image_dynamic()?;
-        Ok(image.resize(new_width, new_height, filter))
+        Ok(resize_util::resize(&image, new_width, new_height, filter))
     }
 
     /// 按照指定的宽高进行压缩
@@ -159,7 +337,7 @@ impl ImageOperate {
         filter: imageops::FilterType,
     ) -> Result<DynamicImage> {
         let image = self.read_image_dynamic()?;
-        Ok(image.resize_exact(new_width, new_height, filter))
+        Ok(resize_util::resize_exact(&image, new_width, new_height, filter))
     }
 
     /// 转换为 BASE64
@@ -172,135 +350,275 @@ impl ImageOperate {
     }
 
     /// 保存图像到磁盘
-    pub async fn save_image(
-        path: String,
+    ///
+    /// 先写入同目录下的临时文件，写入完成后再原子重命名为目标文件，
+    /// 避免程序中途退出时留下不完整的缩略图
+    pub async fn save_image<P: AsRef<Path>>(
+        path: P,
         image: DynamicImage,
         image_format: ImageFormat,
     ) -> Result<()> {
-        let output_path = PathBuf::from(path);
-        if let Some(parent) = output_path.parent() {
-            fs::create_dir_all(parent).expect("Failed to create directory");
-        }
+        ImageOperate::save_image_with(path, image, &EncodeOptions::new(image_format)).await
+    }
+
+    /// 按指定的编码参数保存图像到磁盘
+    /// - options 编码格式、质量、速度等参数
+    pub async fn save_image_with<P: AsRef<Path>>(
+        path: P,
+        image: DynamicImage,
+        options: &EncodeOptions,
+    ) -> Result<()> {
+        let output_path = file_util::long_path(path);
+        let parent = output_path
+            .parent()
+            .ok_or_else(|| anyhow!(AError::ParentPathReadFailed.message()))?;
+        fs::create_dir_all(parent)?;
         let start_time = Instant::now();
 
-        // 如果保存为 jpg 格式，检查是否为 Rgb8
-        if &image_format == &ImageFormat::Jpeg {
-            let img = image.to_rgb8();
-            let result = img.save_with_format(output_path, image_format);
-            if result.is_err() {
-                return Err(anyhow!("{}", result.unwrap_err().to_string()));
-            }
-        } else {
-            let result = image.save_with_format(output_path, image_format);
-            if result.is_err() {
-                return Err(anyhow!("{}", result.unwrap_err().to_string()));
-            }
+        let mut temp_file = tempfile::Builder::new()
+            .prefix(THUMBNAIL_TEMP_FILE_PREFIX)
+            .tempfile_in(parent)?;
+        {
+            let mut writer = BufWriter::new(temp_file.as_file_mut());
+            panic_util::catch_panic(|| encode_image(&mut writer, image, options))?;
+            writer.flush()?;
         }
+        temp_file.as_file().sync_all()?;
+        temp_file
+            .persist(&output_path)
+            .map_err(|e| anyhow!("{}: {}", AError::FileSaveFailed.message(), e))?;
 
-        println!("保存文件: {:?} 完成", start_time.elapsed());
+        log::debug!("保存文件: {:?} 完成", start_time.elapsed());
         Ok(())
     }
 
-    /// 多级别图片压缩
+    /// 添加水印
+    ///
+    /// 文字使用字体绘制（没有字体时使用内置点阵字体并平滑缩放），带半透明阴影；logo 按高度等比缩放，保留透明度
+    /// - image 原图
+    /// - text 水印文字【已替换模板字段】
+    /// - font 文字字体【见 `font_util::load_watermark_font`】
+    /// - logo logo 图片
+    /// - options 位置、不透明度、大小等选项
+    pub fn apply_watermark(
+        image: DynamicImage,
+        text: Option<&str>,
+        font: Option<&FontVec>,
+        logo: Option<&DynamicImage>,
+        options: &WatermarkOptions,
+    ) -> DynamicImage {
+        let mut canvas = image.to_rgba8();
+        let (width, height) = canvas.dimensions();
+        let short_side = width.min(height) as f32;
+        let size = (short_side * options.scale()).round().max(1.0) as u32;
+        let margin = (short_side * options.margin()).round() as u32;
+        let max_width = width.saturating_sub(margin * 2).max(1);
+        let opacity = options.opacity();
+
+        let mark = match (logo, text.filter(|x| !x.trim().is_empty())) {
+            (Some(logo), _) => {
+                let mut mark = logo.resize(max_width, size, FilterType::Lanczos3).to_rgba8();
+                for pixel in mark.pixels_mut() {
+                    pixel[3] = (pixel[3] as f32 * opacity).round() as u8;
+                }
+                mark
+            }
+            (None, Some(text)) => {
+                let mut mask = match font {
+                    Some(font) => font_util::render_text_mask(font, text, size),
+                    None => {
+                        let glyphs = bitmap_font::render_text_mask(text);
+                        let scale = size as f32 / bitmap_font::GLYPH_SIZE as f32;
+                        let width = ((glyphs.width() as f32 * scale).round() as u32).max(1);
+                        imageops::resize(&glyphs, width, size, FilterType::Triangle)
+                    }
+                };
+                // 超出图像宽度时压缩文字宽度
+                if mask.width() > max_width {
+                    mask = imageops::resize(&mask, max_width, size, FilterType::Triangle);
+                }
+                let mark_width = mask.width();
+                // 阴影偏移文字高度的 1/16，保证浅色背景上也能看清
+                let shadow = (size / 16).max(1);
+                let color = options.color_rgb();
+                let mut mark = RgbaImage::new(mark_width + shadow, size + shadow);
+                for (x, y, alpha) in mask.enumerate_pixels() {
+                    let alpha = alpha[0] as f32 * opacity;
+                    let shadow_alpha = (alpha * 0.5).round() as u8;
+                    mark.put_pixel(x + shadow, y + shadow, Rgba([0, 0, 0, shadow_alpha]));
+                }
+                for (x, y, alpha) in mask.enumerate_pixels() {
+                    let alpha = (alpha[0] as f32 * opacity).round() as u8;
+                    if alpha > 0 {
+                        let pixel = Rgba([color[0], color[1], color[2], alpha]);
+                        mark.get_pixel_mut(x, y).blend(&pixel);
+                    }
+                }
+                mark
+            }
+            (None, None) => return image,
+        };
+
+        let position = options.position.unwrap_or_default();
+        let (x, y) = position.offset((width, height), mark.dimensions(), margin);
+        imageops::overlay(&mut canvas, &mark, x, y);
+        DynamicImage::ImageRgba8(canvas)
+    }
+
+    /// 检查缩略图文件是否完整可读
+    pub fn verify_image_file(path: &Path) -> bool {
+        match image::ImageReader::open(path).and_then(|r| r.with_guessed_format()) {
+            Ok(reader) => reader.decode().is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// 多级别图片压缩【读取图片信息写入照片记录，再生成各级缩略图，见 `generate_thumbnails`】
     /// - dir 图像地址
     /// - fmt 压缩格式
     /// - compression_level 压缩级别
-    pub async fn multi_level_image_compression(
-        dir: String,
+    pub async fn multi_level_image_compression<P: AsRef<Path>>(
+        dir: P,
         fmt: ImageFormat,
         compression_level: Vec<ImageSize>,
     ) -> Result<Vec<String>> {
-        // 结果图片地址
-        let result = Arc::new(Mutex::new(Vec::new()));
+        let exif = read_exif_logged(dir.as_ref());
+        ImageOperate::multi_level_image_compression_with_exif(dir, fmt, compression_level, exif)
+            .await
+    }
+
+    /// 多级别图片压缩【使用已经读取的 EXIF，见 `multi_level_image_compression`】
+    /// - exif 已经读取的 EXIF【为 None 时照片记录中不写入 EXIF】
+    pub async fn multi_level_image_compression_with_exif<P: AsRef<Path>>(
+        dir: P,
+        fmt: ImageFormat,
+        compression_level: Vec<ImageSize>,
+        exif: Option<ImgExif>,
+    ) -> Result<Vec<String>> {
+        // 读取图片
+        let image = ImageOperate::read_image_with_exif(dir, exif).await?;
+        image.generate_thumbnails(fmt, &compression_level).await
+    }
 
+    /// 生成各级缩略图【已存在的级别跳过】，返回各级缩略图的路径
+    ///
+    /// 完整解码成功时提升照片记录的校验级别，照片记录需要已经写入或正在写入；
+    /// 原图只解码一次，先生成最大的尺寸，较小的尺寸由上一级缩略图缩放得到；
+    /// 原图比目标尺寸小时不放大，直接按原图尺寸保存
+    /// - fmt 压缩格式
+    /// - compression_level 压缩级别
+    pub async fn generate_thumbnails(
+        &self,
+        fmt: ImageFormat,
+        compression_level: &[ImageSize],
+    ) -> Result<Vec<String>> {
         // 获取根目录
-        let root_dir = Arc::new(SYS_CONFIG.thumbnail_storage_path.clone().unwrap());
+        let root_dir = sys_config()
+            .thumbnail_storage_path
+            .clone()
+            .ok_or_else(|| anyhow!(AError::ThumbnailCacheConfigurationReadFailed.message()))?;
         // 获取文件名
-        let file_name = Arc::new(image_format_util::get_suffix_name(fmt.clone()));
+        let file_name = image_format_util::get_suffix_name(fmt);
 
-        // 读取图片
-        let img = Arc::new(ImageOperate::read_image(&dir.clone()).await?); // 使用 Arc 包装图像
-        let mut join_set = JoinSet::new();
-        let shared_img_dyc = Arc::new(Mutex::new(ComputedValue::<DynamicImage>::new()));
-
-        for level in compression_level {
-            log::info!("获取图片尺寸 {}", &level.size);
-            let vec_clone = Arc::clone(&result);
-            let image = img.clone();
-            let root_dir_clone = Arc::clone(&root_dir); // 克隆 Arc 来传递给闭包
-            let file_name_clone = Arc::clone(&file_name);
-            let shared_img_dyc_clone = Arc::clone(&shared_img_dyc);
-
-            join_set.spawn(async move {
-                // 获取 Hash
-                let hash = &image.hash;
-                // 获取保存路径
+        // 各级别的保存路径【结果按传入的级别顺序返回】
+        let levels: Vec<(u32, String)> = compression_level
+            .iter()
+            .map(|level| {
                 let save_path = FileHashUtils::hash_to_file_path(
-                    hash.as_str(),
-                    &root_dir_clone,
-                    &file_name_clone,
+                    self.hash.as_str(),
+                    root_dir.as_str(),
+                    &file_name,
                     level.size,
                 )
                 .display()
                 .to_string();
-                log::info!("save_path {}", &save_path);
-                // 保存结果数据
-                let mut vec = vec_clone.lock().await;
-                // 检测缩略图文件是否存在
-                let exists = file_exists(&save_path);
-                if !exists {
-                    let mut img_dyc = shared_img_dyc_clone.lock().await;
-                    let img = img_dyc.get_or_compute(|| {
-                        let result1 = image.read_image_dynamic();
-                        if result1.is_err() {
-                            let result2 = result1.map_err(|e| e.to_string());
-                            panic!("{}", result2.err().expect("异常报错信息！"))
-                        } else {
-                            result1.expect("可计算图像获取失败！")
-                        }
-                    });
-                    // 压缩
-                    let x1 = img.resize(level.size, level.size, FilterType::Triangle);
-                    // 保存
-                    ImageOperate::save_image(save_path.clone(), x1, fmt)
-                        .await
-                        .expect("文件保存失败! ");
-                }
-                vec.push(save_path)
-            });
+                (level.size, save_path)
+            })
+            .collect();
+        let result: Vec<String> = levels.iter().map(|(_, path)| path.clone()).collect();
+
+        // 只生成不存在的缩略图，从大到小处理
+        let mut missing: Vec<(u32, String)> = levels
+            .into_iter()
+            .filter(|(_, path)| !file_exists(path))
+            .collect();
+        if missing.is_empty() {
+            return Ok(result);
         }
-
-        while let Some(res) = join_set.join_next().await {
-            match res {
-                Ok(result) => (),
-                Err(e) => {
-                    // 如果有错误，抛出
-                    // 捕获错误并精简输出
-                    let error_message = format!("{}", e);
-                    anyhow::bail!("{}", error_message);
-                }
+        missing.sort_by(|a, b| b.0.cmp(&a.0));
+        missing.dedup_by(|a, b| a.1 == b.1);
+        disk_space_service::ensure_free_space(
+            Path::new(&root_dir),
+            DiskSpacePurpose::Thumbnail,
+            0,
+        )?;
+
+        // 解码原图【损坏的文件可能让解码库崩溃】
+        let source = panic_util::catch_panic(|| self.read_image_oriented())?;
+        // 完整解码成功，提升数据库中记录的校验级别
+        if self.validation_level < ImageValidationLevel::Full {
+            let mut upgraded = self.clone();
+            upgraded.validation_level = ImageValidationLevel::Full;
+            let _ = PHOTO_LOAD_RECEIVER.try_send(upgraded);
+        }
+        let source_size = source.width().max(source.height());
+        // 原图已解码，顺便计算直方图，查看曝光分析时无需再次解码
+        let histogram = HistogramSummary::from_image(&source);
+        let mut colors = None;
+        let mut blurhash = None;
+
+        // 上一级生成的缩略图
+        let mut previous: Option<DynamicImage> = None;
+        for (size, save_path) in missing {
+            log::info!("save_path {}", &save_path);
+            let thumbnail_started = Instant::now();
+            let thumbnail = if size >= source_size {
+                // 原图比目标尺寸小，不放大
+                source.clone()
+            } else {
+                let base = previous.as_ref().unwrap_or(&source);
+                let resized = panic_util::catch_panic(|| {
+                    Ok(resize_util::resize(base, size, size, FilterType::Triangle))
+                })?;
+                previous = Some(resized.clone());
+                resized
+            };
+            if size == DOMINANT_COLOR_THUMBNAIL_SIZE {
+                let rgb = thumbnail.to_rgb8();
+                colors = Some(color_util::dominant_colors(&rgb));
+                let (x, y) = BLURHASH_COMPONENTS;
+                blurhash = Some(blurhash_util::encode(&rgb, x, y));
             }
+            let options = EncodeOptions::thumbnail(fmt, &thumbnail);
+            ImageOperate::save_image_with(&save_path, thumbnail, &options)
+                .await
+                .map_err(|e| anyhow!("{}: {}", AError::FileSaveFailed.message(), e))?;
+            scan_metrics_util::record_thumbnail(thumbnail_started.elapsed().as_micros() as u64);
         }
-        let vec = {
-            let vec = result.lock().await; // 再次获取锁
-            vec.clone() // 克隆 Vec 以得到一个新的 Vec
-        };
-        Ok(vec)
+        thumbnail_cache_service::save_thumbnail_analysis(
+            &self.hash,
+            &histogram,
+            colors.as_deref(),
+            blurhash.as_deref(),
+        );
+        Ok(result)
     }
 
     /// 生成指定级别的压缩图
-    pub async fn designate_level_image_compression(
-        dir: String,
+    pub async fn designate_level_image_compression<P: AsRef<Path>>(
+        dir: P,
         fmt: ImageFormat,
         compression_level: u32,
     ) -> Result<String> {
         // 获取根目录
-        let root_dir = SYS_CONFIG
+        let root_dir = sys_config()
             .thumbnail_storage_path
             .clone()
             .ok_or_else(|| anyhow!(AError::ThumbnailCacheConfigurationReadFailed.message()))?;
-        // 读取图片
-        let read_img = ImageOperate::read_image(&dir.clone()).await.map_err(|e| {
+        // 读取图片并计算缩略图路径
+        let cache_span = LatencySpan::enter(LatencyStage::CacheLookup);
+        let read_img = ImageOperate::read_image(&dir).await.map_err(|e| {
+            let dir = dir.as_ref().display();
             let err = e.to_string();
             return if err.is_empty() {
                 anyhow!(format!(
@@ -326,18 +644,37 @@ impl ImageOperate {
 
         // 检测缩略图文件是否存在
         let exists = file_exists(&save_path);
+        drop(cache_span);
         if !exists {
+            disk_space_service::ensure_free_space(
+                Path::new(&root_dir),
+                DiskSpacePurpose::Thumbnail,
+                0,
+            )?;
             let img = read_img;
-            // 压缩
-            let x1 = img.compression_with_size(
-                compression_level,
-                compression_level,
-                FilterType::Triangle,
-            );
-            let image1 = x1.await.expect("可处理信息获取失败! ");
-            // let x = img.format.unwrap_or(fmt);
-            // 保存
-            ImageOperate::save_image(save_path.clone(), image1, fmt)
+            // 解码
+            let image = {
+                let _span = LatencySpan::enter(LatencyStage::Decode);
+                panic_util::catch_panic(|| img.read_image_oriented())
+                    .map_err(|e| anyhow!("可处理信息获取失败! {}", e))?
+            };
+            // 压缩并保存
+            let _span = LatencySpan::enter(LatencyStage::Encode);
+            // 原图比目标尺寸小时不放大
+            let image1 = if compression_level >= image.width().max(image.height()) {
+                image
+            } else {
+                panic_util::catch_panic(|| {
+                    Ok(resize_util::resize(
+                        &image,
+                        compression_level,
+                        compression_level,
+                        FilterType::Triangle,
+                    ))
+                })?
+            };
+            let options = EncodeOptions::thumbnail(fmt, &image1);
+            ImageOperate::save_image_with(save_path.clone(), image1, &options)
                 .await
                 .map_err(|e| anyhow!(AError::FileSaveFailed.message()))?;
         }
@@ -346,7 +683,98 @@ impl ImageOperate {
     }
 }
 
+/// 按编码参数写出图像
+///
+/// image 库的 WebP 编码只支持无损，有损 WebP 使用 libwebp
+fn encode_image<W: Write + Seek>(
+    writer: &mut W,
+    image: DynamicImage,
+    options: &EncodeOptions,
+) -> Result<()> {
+    match options.format {
+        // JPEG 不支持透明通道，统一转为 Rgb8
+        ImageFormat::Jpeg => {
+            let encoder = JpegEncoder::new_with_quality(writer, options.quality);
+            DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(encoder)?;
+        }
+        ImageFormat::WebP => {
+            let image = if image.color().has_alpha() {
+                DynamicImage::ImageRgba8(image.to_rgba8())
+            } else {
+                DynamicImage::ImageRgb8(image.to_rgb8())
+            };
+            let encoder =
+                webp::Encoder::from_image(&image).map_err(|e| anyhow!("WebP 编码失败: {}", e))?;
+            let mut config =
+                webp::WebPConfig::new().map_err(|_| anyhow!("WebP 编码参数初始化失败"))?;
+            config.quality = options.quality as f32;
+            config.lossless = options.lossless as i32;
+            config.method = webp_method(options.speed);
+            let memory = encoder
+                .encode_advanced(&config)
+                .map_err(|e| anyhow!("WebP 编码失败: {:?}", e))?;
+            writer.write_all(&memory)?;
+        }
+        ImageFormat::Avif => {
+            let image = if image.color().has_alpha() {
+                DynamicImage::ImageRgba8(image.to_rgba8())
+            } else {
+                DynamicImage::ImageRgb8(image.to_rgb8())
+            };
+            let encoder =
+                AvifEncoder::new_with_speed_quality(writer, options.speed, options.quality);
+            image.write_with_encoder(encoder)?;
+        }
+        format => image.write_to(writer, format)?,
+    }
+    Ok(())
+}
+
+/// 读取 EXIF【读取失败时记录日志并返回 None，不影响导入】
+fn read_exif_logged(path: &Path) -> Option<ImgExif> {
+    panic_util::catch_panic(|| exif_util::read_img_exif(path))
+        .map_err(|e| log::warn!("EXIF 读取失败 {}: {}", path.display(), e))
+        .ok()
+}
+
+/// 编码速度（1 - 10）转换为 libwebp 的 method（6 - 0）【method 越大越慢、压缩越好】
+fn webp_method(speed: u8) -> i32 {
+    let speed = speed.clamp(1, 10) as f32;
+    ((10.0 - speed) * 6.0 / 9.0).round() as i32
+}
+
 /// 图像压缩测试
+/// 解码并按 EXIF 方向摆正
+fn decode_oriented<R: BufRead + Seek>(reader: ImageReader<R>) -> Result<DynamicImage> {
+    let mut decoder = reader.into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+/// 多页 TIFF 使用像素最多的页面的尺寸【其他格式返回原尺寸】
+fn largest_dimensions(path: &Path, format: Option<ImageFormat>, size: (u32, u32)) -> (u32, u32) {
+    if format != Some(ImageFormat::Tiff) {
+        return size;
+    }
+    ImageOperate::tiff_pages(path)
+        .into_iter()
+        .map(|x| (x.width, x.height))
+        .filter(|x| x.0 as u64 * x.1 as u64 > size.0 as u64 * size.1 as u64)
+        .max_by_key(|x| x.0 as u64 * x.1 as u64)
+        .unwrap_or(size)
+}
+
+/// 打开 TIFF 并把 IFD0 指向像素最多的页面【按需读取，不把整个文件读入内存】
+fn largest_tiff_page(path: &Path) -> Option<BufReader<tiff::Ifd0Reader<fs::File>>> {
+    let page = tiff::largest_page(ImageOperate::tiff_pages(path))?;
+    let file = fs::File::open(file_util::long_path(path)).ok()?;
+    tiff::Ifd0Reader::new(file, page.offset)
+        .ok()
+        .map(BufReader::new)
+}
+
 #[tokio::test]
 async fn test_async_function() {
     let str = "D:/argus/img/img1.jpg";
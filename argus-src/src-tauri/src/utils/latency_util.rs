@@ -0,0 +1,168 @@
+use crate::constant::LATENCY_SAMPLE_LIMIT;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// 打开照片过程中的各个阶段
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LatencyStage {
+    /// 命令处理总耗时（收到前端调用到返回结果）
+    #[serde(rename = "ipc")]
+    Ipc,
+    /// 缓存查找（读取原图信息、计算 Hash、检查缩略图是否存在）
+    #[serde(rename = "cacheLookup")]
+    CacheLookup,
+    /// 原图解码
+    #[serde(rename = "decode")]
+    Decode,
+    /// 缩放并编码保存
+    #[serde(rename = "encode")]
+    Encode,
+    /// 前端从发起调用到收到结果的耗时【由前端上报，减去 ipc 即为结果序列化和传输的耗时】
+    #[serde(rename = "respond")]
+    Respond,
+}
+
+impl LatencyStage {
+    /// 所有阶段（按执行顺序）
+    pub const ALL: [LatencyStage; 5] = [
+        LatencyStage::Ipc,
+        LatencyStage::CacheLookup,
+        LatencyStage::Decode,
+        LatencyStage::Encode,
+        LatencyStage::Respond,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LatencyStage::Ipc => "ipc",
+            LatencyStage::CacheLookup => "cacheLookup",
+            LatencyStage::Decode => "decode",
+            LatencyStage::Encode => "encode",
+            LatencyStage::Respond => "respond",
+        }
+    }
+}
+
+/// 单个阶段的耗时统计（微秒）
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyStageSummary {
+    /// 阶段
+    pub stage: LatencyStage,
+    /// 样本数量
+    pub count: u32,
+    pub p50_micros: u64,
+    pub p95_micros: u64,
+    pub max_micros: u64,
+}
+
+/// 打开照片耗时报告
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyReport {
+    /// 每个阶段保留的最近样本数量上限
+    pub sample_limit: usize,
+    /// 各阶段统计（按执行顺序）
+    pub stages: Vec<LatencyStageSummary>,
+}
+
+/// 各阶段最近的耗时样本
+static LATENCY_SAMPLES: Lazy<Mutex<HashMap<LatencyStage, VecDeque<u64>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 耗时记录区间，离开作用域时记录耗时
+///
+/// ```ignore
+/// let _span = LatencySpan::enter(LatencyStage::Decode);
+/// ```
+#[derive(Debug)]
+pub struct LatencySpan {
+    stage: LatencyStage,
+    started: Instant,
+}
+
+impl LatencySpan {
+    /// 开始记录指定阶段
+    pub fn enter(stage: LatencyStage) -> LatencySpan {
+        LatencySpan {
+            stage,
+            started: Instant::now(),
+        }
+    }
+}
+
+impl Drop for LatencySpan {
+    fn drop(&mut self) {
+        let micros = self.started.elapsed().as_micros() as u64;
+        log::debug!("open photo [{}] {}us", self.stage.as_str(), micros);
+        record(self.stage, micros);
+    }
+}
+
+/// 记录一次耗时，超出上限时丢弃最早的样本
+pub fn record(stage: LatencyStage, micros: u64) {
+    let mut samples = LATENCY_SAMPLES.lock().unwrap();
+    let queue = samples.entry(stage).or_default();
+    if queue.len() >= LATENCY_SAMPLE_LIMIT {
+        queue.pop_front();
+    }
+    queue.push_back(micros);
+}
+
+/// 汇总各阶段耗时
+pub fn report() -> LatencyReport {
+    let samples = LATENCY_SAMPLES.lock().unwrap();
+    let stages = LatencyStage::ALL
+        .iter()
+        .filter_map(|stage| {
+            let queue = samples.get(stage)?;
+            let mut sorted: Vec<u64> = queue.iter().copied().collect();
+            sorted.sort_unstable();
+            Some(LatencyStageSummary {
+                stage: *stage,
+                count: sorted.len() as u32,
+                p50_micros: percentile(&sorted, 50),
+                p95_micros: percentile(&sorted, 95),
+                max_micros: sorted.last().copied().unwrap_or(0),
+            })
+        })
+        .collect();
+    LatencyReport {
+        sample_limit: LATENCY_SAMPLE_LIMIT,
+        stages,
+    }
+}
+
+/// 清空已记录的样本
+pub fn reset() {
+    LATENCY_SAMPLES.lock().unwrap().clear();
+}
+
+/// 计算百分位数（最近秩法）
+/// - sorted 已排序的样本
+/// - p 百分位 0 ~ 100
+fn percentile(sorted: &[u64], p: usize) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        let sorted: Vec<u64> = (1..=100).collect();
+        assert_eq!(percentile(&sorted, 50), 50);
+        assert_eq!(percentile(&sorted, 95), 95);
+        assert_eq!(percentile(&[7], 95), 7);
+        assert_eq!(percentile(&[], 50), 0);
+        assert_eq!(percentile(&[1, 2, 3], 50), 2);
+    }
+}
@@ -0,0 +1,401 @@
+use crate::constant::{LOG_FILE_PREFIX, LOG_PATH, LOG_RECENT_LIMIT, LOG_RETENTION_DAYS};
+use crate::utils::time_util::TimeUtils;
+use chrono::{Duration, Local};
+use log::LevelFilter;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Debug;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use tracing::field::{Field, Visit};
+use tracing::subscriber::Interest;
+use tracing::{span, Event, Metadata, Subscriber};
+
+/// 一条日志
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogRecord {
+    /// 时间（毫秒时间戳）
+    pub time: i64,
+    /// 级别【ERROR、WARN、INFO、DEBUG、TRACE】
+    pub level: String,
+    /// 模块
+    pub target: String,
+    pub message: String,
+    /// 结构化字段
+    #[serde(skip_serializing_if = "Map::is_empty", default)]
+    pub fields: Map<String, Value>,
+}
+
+/// 日志查询条件
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LogQuery {
+    /// 最低级别【如 warn 表示只返回警告和错误】
+    pub level: Option<String>,
+    /// 模块前缀【如 argus_src_lib::services】
+    pub target: Option<String>,
+    /// 消息包含的文本
+    pub text: Option<String>,
+    /// 返回数量【返回最近的若干条】
+    pub limit: Option<usize>,
+}
+
+/// 日志级别设置
+#[derive(Debug)]
+struct LevelConfig {
+    /// 默认级别
+    default: LevelFilter,
+    /// 各模块的级别【按模块前缀匹配，最长的前缀优先】
+    modules: BTreeMap<String, LevelFilter>,
+}
+
+impl LevelConfig {
+    fn level_of(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .filter(|(module, _)| {
+                target == module.as_str() || target.starts_with(&format!("{}::", module))
+            })
+            .max_by_key(|(module, _)| module.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default)
+    }
+}
+
+static LEVELS: Lazy<RwLock<LevelConfig>> = Lazy::new(|| {
+    RwLock::new(LevelConfig {
+        default: LevelFilter::Info,
+        modules: BTreeMap::new(),
+    })
+});
+
+/// 最近的日志
+static RECENT_LOGS: Lazy<Mutex<VecDeque<LogRecord>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(LOG_RECENT_LIMIT)));
+
+/// 当前日志文件（日期、文件）
+static LOG_FILE: Lazy<Mutex<Option<(String, File)>>> = Lazy::new(|| Mutex::new(None));
+
+/// 初始化日志系统
+///
+/// 日志按 JSON Lines 格式写入 `tauri-logs` 文件夹，`tracing` 事件与 `log` 宏的日志都会被记录
+/// - default_level 默认日志级别
+pub fn init(default_level: LevelFilter) {
+    LEVELS.write().unwrap().default = default_level;
+    if let Err(e) = tracing::subscriber::set_global_default(JsonLogSubscriber::default()) {
+        eprintln!("tracing 日志初始化失败: {}", e);
+    }
+    match log::set_boxed_logger(Box::new(LogBridge)) {
+        // 级别由本模块过滤，便于运行时调整
+        Ok(_) => log::set_max_level(LevelFilter::Trace),
+        Err(e) => eprintln!("log 日志初始化失败: {}", e),
+    }
+}
+
+/// 解析日志级别【trace、debug、info、warn、error、off】
+pub fn parse_level(level: &str) -> Option<LevelFilter> {
+    level.trim().parse().ok()
+}
+
+/// 设置日志级别
+/// - module 模块【为空时设置默认级别】
+/// - level 级别【为空时删除模块的单独设置】
+pub fn set_level(module: Option<&str>, level: Option<LevelFilter>) {
+    let mut levels = LEVELS.write().unwrap();
+    match (module, level) {
+        (None, Some(level)) => levels.default = level,
+        (None, None) => {}
+        (Some(module), Some(level)) => {
+            levels.modules.insert(module.to_string(), level);
+        }
+        (Some(module), None) => {
+            levels.modules.remove(module);
+        }
+    }
+}
+
+/// 当前的日志级别设置（默认级别、各模块级别）
+pub fn get_levels() -> (String, BTreeMap<String, String>) {
+    let levels = LEVELS.read().unwrap();
+    let modules = levels
+        .modules
+        .iter()
+        .map(|(module, level)| (module.clone(), level.to_string()))
+        .collect();
+    (levels.default.to_string(), modules)
+}
+
+/// 查询最近的日志【按时间顺序】
+pub fn recent_logs(query: &LogQuery) -> Vec<LogRecord> {
+    let min_level = query.level.as_deref().and_then(parse_level);
+    let text = query.text.as_deref().map(str::to_lowercase);
+    let logs = RECENT_LOGS.lock().unwrap();
+    let mut matched: Vec<LogRecord> = logs
+        .iter()
+        .rev()
+        .filter(|x| {
+            min_level.map_or(true, |min| {
+                x.level.parse::<log::Level>().is_ok_and(|level| level <= min)
+            })
+        })
+        .filter(|x| {
+            query
+                .target
+                .as_deref()
+                .map_or(true, |target| x.target.starts_with(target))
+        })
+        .filter(|x| {
+            text.as_deref()
+                .map_or(true, |text| x.message.to_lowercase().contains(text))
+        })
+        .take(query.limit.unwrap_or(LOG_RECENT_LIMIT))
+        .cloned()
+        .collect();
+    matched.reverse();
+    matched
+}
+
+fn is_enabled(target: &str, level: log::Level) -> bool {
+    level <= LEVELS.read().unwrap().level_of(target)
+}
+
+/// 记录日志：写入文件并保存到最近日志
+fn emit(record: LogRecord) {
+    let line = match serde_json::to_string(&record) {
+        Ok(x) => x,
+        Err(_) => return,
+    };
+    #[cfg(debug_assertions)]
+    eprintln!("[{} {}] {}", record.level, record.target, record.message);
+    write_line(&line);
+
+    let mut logs = RECENT_LOGS.lock().unwrap();
+    if logs.len() >= LOG_RECENT_LIMIT {
+        logs.pop_front();
+    }
+    logs.push_back(record);
+}
+
+/// 追加到当天的日志文件【切换到新的一天时删除超过保留天数的文件】
+///
+/// 这里不能读取系统配置，加载配置时输出的日志也会进入这里
+fn write_line(line: &str) {
+    let now = Local::now();
+    let date = now.format("%Y%m%d").to_string();
+    let mut file = LOG_FILE.lock().unwrap();
+    if file.as_ref().map_or(true, |(day, _)| *day != date) {
+        let dir = PathBuf::from(LOG_PATH);
+        let oldest = (now - Duration::days(LOG_RETENTION_DAYS - 1)).format("%Y%m%d");
+        prune_log_files(&dir, &oldest.to_string());
+        let path = dir.join(format!("{}{}.jsonl", LOG_FILE_PREFIX, date));
+        *file = fs::create_dir_all(&dir)
+            .and_then(|_| OpenOptions::new().create(true).append(true).open(&path))
+            .map(|x| (date, x))
+            .map_err(|e| eprintln!("日志文件打开失败 {}: {}", path.display(), e))
+            .ok();
+    }
+    if let Some((_, file)) = file.as_mut() {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+/// 删除早于指定日期的日志文件
+///
+/// 持有日志文件锁时调用，失败时不能再写日志，直接忽略
+/// - dir 日志文件夹
+/// - oldest 保留的最早日期【`%Y%m%d`】
+fn prune_log_files(dir: &Path, oldest: &str) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let date = name
+            .to_str()
+            .and_then(|x| x.strip_prefix(LOG_FILE_PREFIX))
+            .and_then(|x| x.strip_suffix(".jsonl"));
+        // 日期格式固定，可以直接按字符串比较
+        if date.is_some_and(|x| x.len() == oldest.len() && x < oldest) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+fn to_log_level(level: &tracing::Level) -> log::Level {
+    match *level {
+        tracing::Level::ERROR => log::Level::Error,
+        tracing::Level::WARN => log::Level::Warn,
+        tracing::Level::INFO => log::Level::Info,
+        tracing::Level::DEBUG => log::Level::Debug,
+        tracing::Level::TRACE => log::Level::Trace,
+    }
+}
+
+/// 把 `tracing` 事件写为 JSON 日志
+#[derive(Default)]
+struct JsonLogSubscriber {
+    next_span_id: AtomicU64,
+}
+
+impl Subscriber for JsonLogSubscriber {
+    fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+        // 级别可以在运行时调整，每次都重新判断
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        is_enabled(metadata.target(), to_log_level(metadata.level()))
+    }
+
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(self.next_span_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        emit(LogRecord {
+            time: TimeUtils::current_timestamp_millis(),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        });
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+/// 读取事件中的消息和字段
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: Map<String, Value>,
+}
+
+impl FieldVisitor {
+    fn insert(&mut self, field: &Field, value: Value) {
+        if field.name() == "message" {
+            self.message = match value {
+                Value::String(x) => x,
+                other => other.to_string(),
+            };
+        } else {
+            self.fields.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.insert(field, Value::from(format!("{:?}", value)));
+    }
+}
+
+/// 把 `log` 宏的日志转发到 JSON 日志
+struct LogBridge;
+
+impl log::Log for LogBridge {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        is_enabled(metadata.target(), metadata.level())
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        emit(LogRecord {
+            time: TimeUtils::current_timestamp_millis(),
+            level: record.level().to_string(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+            fields: Map::new(),
+        });
+    }
+
+    fn flush(&self) {
+        if let Some((_, file)) = LOG_FILE.lock().unwrap().as_mut() {
+            let _ = file.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_of() {
+        let mut modules = BTreeMap::new();
+        modules.insert("argus::services".to_string(), LevelFilter::Debug);
+        modules.insert("argus::services::backup".to_string(), LevelFilter::Error);
+        let levels = LevelConfig {
+            default: LevelFilter::Info,
+            modules,
+        };
+        assert_eq!(levels.level_of("argus::services::scan"), LevelFilter::Debug);
+        assert_eq!(levels.level_of("argus::services::backup"), LevelFilter::Error);
+        assert_eq!(levels.level_of("argus::services_x"), LevelFilter::Info);
+        assert_eq!(levels.level_of("diesel"), LevelFilter::Info);
+    }
+
+    #[test]
+    fn test_prune_log_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "argus-20250101.jsonl",
+            "argus-20250114.jsonl",
+            "argus-20250115.jsonl",
+            "other-20250101.jsonl",
+        ] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+        prune_log_files(dir.path(), "20250114");
+        let mut names: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|x| x.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "argus-20250114.jsonl",
+                "argus-20250115.jsonl",
+                "other-20250101.jsonl"
+            ]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// 按占用大小淘汰的 LRU 缓存
+///
+/// 插入后总大小超过上限时，从最久未使用的条目开始淘汰
+pub struct LruCache<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    /// 使用顺序 -> 键【顺序越小越久未使用】
+    order: BTreeMap<u64, K>,
+    tick: u64,
+    weight: usize,
+    max_weight: usize,
+}
+
+struct Entry<V> {
+    value: V,
+    weight: usize,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// - max_weight 总大小上限
+    pub fn new(max_weight: usize) -> Self {
+        LruCache {
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+            weight: 0,
+            max_weight,
+        }
+    }
+
+    /// 获取并标记为最近使用
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(key)?;
+        self.order.remove(&entry.tick);
+        self.order.insert(tick, key.clone());
+        entry.tick = tick;
+        Some(&entry.value)
+    }
+
+    /// 是否包含【不改变使用顺序】
+    pub fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// 插入或替换【单个条目超过上限时不缓存】
+    /// - weight 条目大小
+    pub fn insert(&mut self, key: K, value: V, weight: usize) {
+        self.remove(&key);
+        if weight > self.max_weight {
+            return;
+        }
+        let tick = self.next_tick();
+        self.order.insert(tick, key.clone());
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                weight,
+                tick,
+            },
+        );
+        self.weight += weight;
+        while self.weight > self.max_weight {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&oldest) {
+                self.weight -= entry.weight;
+            }
+        }
+    }
+
+    /// 删除
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let entry = self.entries.remove(key)?;
+        self.order.remove(&entry.tick);
+        self.weight -= entry.weight;
+        Some(entry.value)
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = LruCache::new(10);
+        cache.insert("a", 1, 4);
+        cache.insert("b", 2, 4);
+        // 访问 a 后 b 成为最久未使用
+        assert_eq!(cache.get(&"a"), Some(&1));
+        cache.insert("c", 3, 4);
+        assert!(cache.contains(&"a"));
+        assert!(!cache.contains(&"b"));
+        assert_eq!(cache.weight, 8);
+
+        // 超过上限的条目不缓存
+        cache.insert("d", 4, 11);
+        assert!(!cache.contains(&"d"));
+        assert_eq!(cache.weight, 8);
+    }
+}
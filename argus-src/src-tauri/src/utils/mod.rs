@@ -7,8 +7,46 @@ pub mod file_hash_util;
 pub mod file_util;
 pub mod hash_util;
 pub mod image_format_util;
+pub mod image_probe_util;
+pub mod animation_util;
 pub mod img_util;
 pub mod json_util;
+pub mod latency_util;
 pub mod system_state_util;
+pub mod opml_util;
+pub mod scan_exclude_util;
+pub mod volume_util;
 pub mod time_util;
 pub mod task_util;
+pub mod log_util;
+pub mod scan_metrics_util;
+pub mod uuid_util;
+pub mod csv_util;
+pub mod external_library;
+pub mod ffmpeg_util;
+pub mod bitmap_font;
+pub mod font_util;
+pub mod color_util;
+pub mod geo_cluster_util;
+pub mod reverse_geocode_util;
+pub mod sequence_detect_util;
+pub mod event_segment_util;
+pub mod zip_util;
+pub mod url_util;
+pub mod vault_crypto_util;
+pub mod checksum_util;
+pub mod cron_util;
+pub mod lru_cache;
+pub mod blurhash_util;
+pub mod thumbnail_queue_util;
+pub mod resize_util;
+#[cfg(feature = "gpu-resize")]
+pub mod gpu_resize_util;
+pub mod graphic_detect_util;
+pub mod panic_util;
+pub mod cloud_placeholder_util;
+pub mod remote_backend;
+pub mod webp_container_util;
+pub mod png_chunk_util;
+pub mod raw_preview_util;
+pub mod secret_util;
@@ -0,0 +1,193 @@
+use crate::structs::organization_tree::{
+    OrganizationNode, OrganizationTree, ORGANIZATION_TREE_VERSION,
+};
+use anyhow::{anyhow, Result};
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use std::io::Cursor;
+
+/// 相册分组的 outline 类型
+const OUTLINE_TYPE_ALBUMS: &str = "albums";
+/// 标签分组的 outline 类型
+const OUTLINE_TYPE_TAGS: &str = "tags";
+
+/// 将组织结构转换为 OPML
+///
+/// 相册和标签分别放在 `type="albums"`、`type="tags"` 的顶层 outline 中，
+/// 每个节点使用 `argusId`、`count` 属性记录 ID 和照片数量
+pub fn to_opml(tree: &OrganizationTree) -> Result<String> {
+    let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    let mut opml = BytesStart::new("opml");
+    opml.push_attribute(("version", "2.0"));
+    writer.write_event(Event::Start(opml))?;
+
+    writer.write_event(Event::Start(BytesStart::new("head")))?;
+    writer.write_event(Event::Start(BytesStart::new("title")))?;
+    writer.write_event(Event::Text(BytesText::new("argus")))?;
+    writer.write_event(Event::End(BytesEnd::new("title")))?;
+    writer.write_event(Event::End(BytesEnd::new("head")))?;
+
+    writer.write_event(Event::Start(BytesStart::new("body")))?;
+    write_group(&mut writer, OUTLINE_TYPE_ALBUMS, &tree.albums)?;
+    write_group(&mut writer, OUTLINE_TYPE_TAGS, &tree.tags)?;
+    writer.write_event(Event::End(BytesEnd::new("body")))?;
+    writer.write_event(Event::End(BytesEnd::new("opml")))?;
+
+    let bytes = writer.into_inner().into_inner();
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// 写入一个分组
+fn write_group(
+    writer: &mut Writer<Cursor<Vec<u8>>>,
+    kind: &str,
+    nodes: &[OrganizationNode],
+) -> Result<()> {
+    let mut group = BytesStart::new("outline");
+    group.push_attribute(("text", kind));
+    group.push_attribute(("type", kind));
+    writer.write_event(Event::Start(group))?;
+    for node in nodes {
+        write_node(writer, node)?;
+    }
+    writer.write_event(Event::End(BytesEnd::new("outline")))?;
+    Ok(())
+}
+
+/// 递归写入节点
+fn write_node(writer: &mut Writer<Cursor<Vec<u8>>>, node: &OrganizationNode) -> Result<()> {
+    let mut outline = BytesStart::new("outline");
+    outline.push_attribute(("text", node.name.as_str()));
+    if let Some(id) = node.id {
+        outline.push_attribute(("argusId", id.to_string().as_str()));
+    }
+    outline.push_attribute(("count", node.count.to_string().as_str()));
+
+    if node.children.is_empty() {
+        writer.write_event(Event::Empty(outline))?;
+    } else {
+        writer.write_event(Event::Start(outline))?;
+        for child in &node.children {
+            write_node(writer, child)?;
+        }
+        writer.write_event(Event::End(BytesEnd::new("outline")))?;
+    }
+    Ok(())
+}
+
+/// 解析 OPML 为组织结构
+pub fn from_opml(content: &str) -> Result<OrganizationTree> {
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+
+    // (节点, 顶层分组类型)
+    let mut stack: Vec<(OrganizationNode, Option<String>)> = Vec::new();
+    let mut tree = OrganizationTree {
+        version: ORGANIZATION_TREE_VERSION,
+        albums: Vec::new(),
+        tags: Vec::new(),
+    };
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.name().as_ref() == b"outline" => {
+                stack.push(parse_outline(&e)?);
+            }
+            Ok(Event::Empty(e)) if e.name().as_ref() == b"outline" => {
+                let item = parse_outline(&e)?;
+                attach(&mut stack, &mut tree, item);
+            }
+            Ok(Event::End(e)) if e.name().as_ref() == b"outline" => {
+                let item = stack.pop().ok_or_else(|| anyhow!("OPML 结构错误!"))?;
+                attach(&mut stack, &mut tree, item);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(anyhow!(
+                    "OPML 解析失败，位置 {}: {}",
+                    reader.buffer_position(),
+                    e
+                ))
+            }
+            _ => {}
+        }
+    }
+    Ok(tree)
+}
+
+/// 读取 outline 的属性
+fn parse_outline(e: &BytesStart) -> Result<(OrganizationNode, Option<String>)> {
+    let mut node = OrganizationNode {
+        id: None,
+        name: String::new(),
+        count: 0,
+        children: Vec::new(),
+    };
+    let mut kind = None;
+    for attr in e.attributes() {
+        let attr = attr?;
+        let value = attr.unescape_value()?.to_string();
+        match attr.key.as_ref() {
+            b"text" => node.name = value,
+            b"argusId" => node.id = value.parse().ok(),
+            b"count" => node.count = value.parse().unwrap_or(0),
+            b"type" => kind = Some(value),
+            _ => {}
+        }
+    }
+    Ok((node, kind))
+}
+
+/// 把解析完成的节点挂到父节点或顶层分组上
+fn attach(
+    stack: &mut Vec<(OrganizationNode, Option<String>)>,
+    tree: &mut OrganizationTree,
+    item: (OrganizationNode, Option<String>),
+) {
+    let (node, kind) = item;
+    if let Some((parent, _)) = stack.last_mut() {
+        parent.children.push(node);
+        return;
+    }
+    match kind.as_deref() {
+        Some(OUTLINE_TYPE_ALBUMS) => tree.albums.extend(node.children),
+        Some(OUTLINE_TYPE_TAGS) => tree.tags.extend(node.children),
+        // 不属于任何分组的顶层节点忽略
+        _ => log::warn!("忽略未知的 OPML 节点: {}", node.name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opml_round_trip() {
+        let tree = OrganizationTree {
+            version: ORGANIZATION_TREE_VERSION,
+            albums: vec![OrganizationNode {
+                id: Some(1),
+                name: "旅行 & 风景".to_string(),
+                count: 3,
+                children: vec![OrganizationNode {
+                    id: None,
+                    name: "新相册".to_string(),
+                    count: 0,
+                    children: vec![],
+                }],
+            }],
+            tags: vec![OrganizationNode {
+                id: Some(7),
+                name: "猫".to_string(),
+                count: 12,
+                children: vec![],
+            }],
+        };
+        let opml = to_opml(&tree).unwrap();
+        assert!(opml.contains("argusId=\"1\""));
+        let parsed = from_opml(&opml).unwrap();
+        assert_eq!(parsed, tree);
+    }
+}
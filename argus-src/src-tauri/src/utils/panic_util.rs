@@ -0,0 +1,59 @@
+//! 第三方解码库崩溃隔离
+//!
+//! 损坏的图片可能让解码库直接 panic，捕获后转换为普通错误，
+//! 只影响当前文件，不会中断整个扫描任务
+
+use crate::errors::AError;
+use anyhow::{anyhow, Result};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+/// 执行可能 panic 的图像处理步骤，panic 时返回错误
+///
+/// 【发布版本需要使用 unwind 模式，abort 模式下无法捕获】
+pub fn catch_panic<T, F>(f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = panic_message(payload.as_ref());
+            log::error!("图像处理崩溃: {}", message);
+            Err(anyhow!(
+                "{}: {}",
+                AError::ImageProcessPanicked.message(),
+                message
+            ))
+        }
+    }
+}
+
+/// 读取 panic 信息【`panic!` 传入字符串字面量或格式化字符串】
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_panic() {
+        let value = catch_panic(|| Ok(1)).unwrap();
+        assert_eq!(value, 1);
+
+        let err = catch_panic(|| -> Result<()> { Err(anyhow!("normal error")) }).unwrap_err();
+        assert_eq!(err.to_string(), "normal error");
+
+        let index = 3;
+        let err = catch_panic(|| -> Result<()> { panic!("bad segment {}", index) }).unwrap_err();
+        assert!(err.to_string().ends_with("bad segment 3"));
+    }
+}
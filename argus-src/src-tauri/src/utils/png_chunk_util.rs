@@ -0,0 +1,130 @@
+//! PNG 数据块中的元数据
+//!
+//! 转换为其他格式前读取 EXIF（`eXIf`）和 XMP（关键字为 `XML:com.adobe.xmp` 的 `iTXt`），
+//! 并找出无法在其他格式中保留的文本数据块；ICC 配置文件由图像库解压读取
+
+use anyhow::{anyhow, Result};
+
+/// PNG 文件标识
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+/// XMP 所在 iTXt 数据块的关键字
+const XMP_KEYWORD: &[u8] = b"XML:com.adobe.xmp";
+
+/// PNG 中的元数据
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PngMetadata {
+    /// 位深度
+    pub bit_depth: u8,
+    /// 是否为动图（APNG）
+    pub animated: bool,
+    /// EXIF（TIFF 结构）
+    pub exif: Option<Vec<u8>>,
+    pub xmp: Option<Vec<u8>>,
+    /// 无法在其他格式中保留的数据块【如 `tEXt`、压缩的 XMP】
+    pub unsupported: Vec<String>,
+}
+
+/// 读取 PNG 中的元数据
+pub fn read_png_metadata(data: &[u8]) -> Result<PngMetadata> {
+    let Some(mut rest) = data.strip_prefix(PNG_SIGNATURE) else {
+        return Err(anyhow!("不是 PNG 文件"));
+    };
+    let mut metadata = PngMetadata::default();
+    while rest.len() >= 12 {
+        let len = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        let kind = &rest[4..8];
+        if rest.len() < 12 + len {
+            return Err(anyhow!("PNG 数据块长度错误"));
+        }
+        let payload = &rest[8..8 + len];
+        match kind {
+            b"IHDR" if len >= 9 => metadata.bit_depth = payload[8],
+            b"acTL" => metadata.animated = true,
+            b"eXIf" => metadata.exif = Some(payload.to_vec()),
+            b"iTXt" => match read_xmp(payload) {
+                Some(xmp) => metadata.xmp = Some(xmp.to_vec()),
+                None => metadata.unsupported.push(chunk_name(kind, payload)),
+            },
+            b"tEXt" | b"zTXt" => metadata.unsupported.push(chunk_name(kind, payload)),
+            b"IEND" => break,
+            _ => {}
+        }
+        rest = &rest[12 + len..];
+    }
+    Ok(metadata)
+}
+
+/// 读取未压缩的 XMP【不是 XMP 或已压缩时返回 None】
+///
+/// iTXt 结构：关键字、0、压缩标记、压缩方法、语言标签、0、翻译后的关键字、0、文本
+fn read_xmp(payload: &[u8]) -> Option<&[u8]> {
+    let rest = payload.strip_prefix(XMP_KEYWORD)?.strip_prefix(b"\0")?;
+    let (&compressed, rest) = rest.split_first()?;
+    if compressed != 0 {
+        return None;
+    }
+    let rest = rest.get(1..)?;
+    let language_end = rest.iter().position(|x| *x == 0)?;
+    let rest = &rest[language_end + 1..];
+    let keyword_end = rest.iter().position(|x| *x == 0)?;
+    Some(&rest[keyword_end + 1..])
+}
+
+/// 数据块说明，如 `tEXt(Software)`
+fn chunk_name(kind: &[u8], payload: &[u8]) -> String {
+    let keyword_end = payload
+        .iter()
+        .position(|x| *x == 0)
+        .unwrap_or(payload.len());
+    format!(
+        "{}({})",
+        String::from_utf8_lossy(kind),
+        String::from_utf8_lossy(&payload[..keyword_end])
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(kind: &[u8], payload: &[u8]) -> Vec<u8> {
+        let mut data = (payload.len() as u32).to_be_bytes().to_vec();
+        data.extend(kind);
+        data.extend(payload);
+        // 不校验 CRC
+        data.extend([0; 4]);
+        data
+    }
+
+    #[test]
+    fn test_read_png_metadata() {
+        let mut ihdr = vec![0, 0, 0, 1, 0, 0, 0, 1];
+        ihdr.extend([8, 6, 0, 0, 0]);
+        let mut xmp = XMP_KEYWORD.to_vec();
+        xmp.extend(b"\0\0\0\0\0<x:xmpmeta/>");
+
+        let mut data = PNG_SIGNATURE.to_vec();
+        data.extend(chunk(b"IHDR", &ihdr));
+        data.extend(chunk(b"eXIf", b"MM\0*"));
+        data.extend(chunk(b"iTXt", &xmp));
+        data.extend(chunk(b"IDAT", &[0; 4]));
+        data.extend(chunk(b"IEND", &[]));
+        let metadata = read_png_metadata(&data).unwrap();
+        assert_eq!(metadata.bit_depth, 8);
+        assert!(!metadata.animated);
+        assert_eq!(metadata.exif.as_deref(), Some(&b"MM\0*"[..]));
+        assert_eq!(metadata.xmp.as_deref(), Some(&b"<x:xmpmeta/>"[..]));
+        assert!(metadata.unsupported.is_empty());
+
+        // 文本数据块无法保留
+        let mut data = PNG_SIGNATURE.to_vec();
+        data.extend(chunk(b"IHDR", &ihdr));
+        data.extend(chunk(b"tEXt", b"Software\0Snipaste"));
+        data.extend(chunk(b"IEND", &[]));
+        assert_eq!(
+            read_png_metadata(&data).unwrap().unsupported,
+            vec!["tEXt(Software)".to_string()]
+        );
+        assert!(read_png_metadata(b"GIF89a").is_err());
+    }
+}
@@ -0,0 +1,207 @@
+//! RAW 文件的预览图
+//!
+//! 图像库无法解码 RAW 数据，缩略图和预览按设置优先使用同一文件夹中同名的 JPEG（RAW+JPEG 拍摄）
+//! 或 RAW 内嵌的 JPEG 预览图（CR2、NEF、ARW、DNG 等基于 TIFF 结构的格式），没有时使用另一个
+
+use crate::constant::{EXIF_SCAN_MAX_BYTES, JPEG_EXTENSIONS};
+use crate::structs::raw_jpeg_primary::RawJpegPrimary;
+use crate::utils::exif_utils::tiff::{Ifd, Parser};
+use crate::utils::file_util;
+use anyhow::{anyhow, Result};
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// 压缩方式
+const TAG_COMPRESSION: u16 = 0x0103;
+const TAG_STRIP_OFFSETS: u16 = 0x0111;
+const TAG_STRIP_BYTE_COUNTS: u16 = 0x0117;
+/// 子 IFD【NEF、DNG 的预览图、RAW 数据保存在子 IFD 中】
+const TAG_SUB_IFDS: u16 = 0x014A;
+/// JPEG 数据的偏移量
+const TAG_JPEG_OFFSET: u16 = 0x0201;
+/// JPEG 数据的长度
+const TAG_JPEG_LENGTH: u16 = 0x0202;
+/// JPEG 压缩（旧版、新版）
+const COMPRESSION_JPEG: [u32; 2] = [6, 7];
+/// 最多查找的 IFD 数量【防止子 IFD 循环引用】
+const MAX_IFDS: usize = 32;
+/// 判断 JPEG 编码方式时读取的开头大小【帧头在 EXIF 等 APP 段之后】
+const PREVIEW_PROBE_BYTES: usize = 256 * 1024;
+
+/// 同一文件夹中同名的 JPEG 文件【如 `IMG_0001.CR2` -> `IMG_0001.JPG`】
+pub fn sibling_jpeg(path: &Path) -> Option<PathBuf> {
+    JPEG_EXTENSIONS
+        .iter()
+        .flat_map(|x| [x.to_string(), x.to_uppercase()])
+        .map(|x| path.with_extension(x))
+        .find(|x| file_util::file_exists(x))
+}
+
+/// 读取 RAW 文件用于生成缩略图的 JPEG 数据【按设置优先使用同名 JPEG 或内嵌的最大预览图】
+pub fn read_thumbnail_source(path: &Path) -> Result<Vec<u8>> {
+    let sibling = || {
+        let jpeg = sibling_jpeg(path)?;
+        fs::read(file_util::long_path(jpeg)).ok()
+    };
+    let embedded = || match read_embedded_preview(path) {
+        Ok(data) => Some(data),
+        Err(e) => {
+            log::debug!("RAW 内嵌预览图读取失败 {}: {}", path.display(), e);
+            None
+        }
+    };
+    let data = match RawJpegPrimary::from_config() {
+        RawJpegPrimary::Jpeg => sibling().or_else(embedded),
+        RawJpegPrimary::Raw => embedded().or_else(sibling),
+    };
+    data.ok_or_else(|| anyhow!("RAW 文件中没有可用的预览图"))
+}
+
+/// 读取 RAW 内嵌的最大预览图
+///
+/// 只读取文件开头的 TIFF 结构和预览图所在的位置，不读取整个文件【跳过无损 JPEG 压缩的 RAW 数据】
+pub fn read_embedded_preview(path: &Path) -> Result<Vec<u8>> {
+    let mut file = File::open(file_util::long_path(path))?;
+    let file_len = file.metadata()?.len();
+    let mut ranges = preview_ranges(&read_header(&mut file)?);
+    ranges.sort_by_key(|(_, len)| Reverse(*len));
+    for (offset, len) in ranges {
+        if (offset as u64).saturating_add(len as u64) > file_len {
+            continue;
+        }
+        file.seek(SeekFrom::Start(offset as u64))?;
+        let probe = len.min(PREVIEW_PROBE_BYTES);
+        let mut data = vec![0; probe];
+        file.read_exact(&mut data)?;
+        if !is_baseline_jpeg(&data) {
+            continue;
+        }
+        data.resize(len, 0);
+        file.read_exact(&mut data[probe..])?;
+        return Ok(data);
+    }
+    Err(anyhow!("RAW 文件中没有可用的预览图"))
+}
+
+/// RAW 图像的尺寸【TIFF 结构中最大的图像，即 RAW 数据或全尺寸的预览图】
+pub fn raw_dimensions(path: &Path) -> Option<(u32, u32)> {
+    let mut file = File::open(file_util::long_path(path)).ok()?;
+    let header = read_header(&mut file).ok()?;
+    let page = Parser::new(&header).ok()?.largest_page()?;
+    Some((page.width, page.height))
+}
+
+/// 读取文件开头的 TIFF 结构【IFD 都在文件开头，预览图、RAW 数据按偏移量另外读取】
+fn read_header(file: &mut File) -> io::Result<Vec<u8>> {
+    let mut header = Vec::new();
+    file.take(EXIF_SCAN_MAX_BYTES).read_to_end(&mut header)?;
+    Ok(header)
+}
+
+/// TIFF 结构中所有 JPEG 数据的位置（偏移量、长度），包括子 IFD 中的
+pub fn preview_ranges(data: &[u8]) -> Vec<(usize, usize)> {
+    let Ok(parser) = Parser::new(data) else {
+        return Vec::new();
+    };
+    let mut ifds = parser.ifds();
+    let mut index = 0;
+    while index < ifds.len() && ifds.len() < MAX_IFDS {
+        let children: Vec<Ifd<'_>> = ifds[index]
+            .find(TAG_SUB_IFDS)
+            .map(|x| x.as_u32_values())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|x| parser.ifd(x as usize))
+            .collect();
+        ifds.extend(children);
+        index += 1;
+    }
+    ifds.iter().filter_map(jpeg_range).collect()
+}
+
+/// IFD 中 JPEG 数据的位置（偏移量、长度）
+fn jpeg_range(ifd: &Ifd<'_>) -> Option<(usize, usize)> {
+    let value = |tag: u16| ifd.find(tag).and_then(|x| x.as_u32());
+    if let (Some(offset), Some(len)) = (value(TAG_JPEG_OFFSET), value(TAG_JPEG_LENGTH)) {
+        return Some((offset as usize, len as usize));
+    }
+    // 只有一个条带的 JPEG 压缩图像【如 CR2 IFD0 的预览图】
+    if !COMPRESSION_JPEG.contains(&value(TAG_COMPRESSION)?) {
+        return None;
+    }
+    let offsets = ifd.find(TAG_STRIP_OFFSETS)?.as_u32_values();
+    let counts = ifd.find(TAG_STRIP_BYTE_COUNTS)?.as_u32_values();
+    match (offsets.as_slice(), counts.as_slice()) {
+        ([offset], [len]) => Some((*offset as usize, *len as usize)),
+        _ => None,
+    }
+}
+
+/// 是否为图像库能解码的 JPEG（基线、扩展、渐进式）
+fn is_baseline_jpeg(data: &[u8]) -> bool {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return false;
+    }
+    let mut pos = 2;
+    while let Some(&[0xFF, marker, high, low]) = data.get(pos..pos + 4) {
+        match marker {
+            0xC0..=0xC2 => return true,
+            // 无损、分层、算术编码
+            0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF | 0xDA => return false,
+            _ => pos += 2 + u16::from_be_bytes([high, low]) as usize,
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jpeg(sof: u8, len: usize) -> Vec<u8> {
+        let mut data = vec![0xFF, 0xD8, 0xFF, 0xE0, 0, 4, 0, 0, 0xFF, sof, 0, 2];
+        data.resize(len, 0);
+        data
+    }
+
+    #[test]
+    fn test_read_embedded_preview() {
+        // 小端，IFD0 有 JPEG 偏移量、长度和子 IFD 指针；子 IFD 为无损 JPEG 条带（RAW 数据）
+        let mut tiff = b"II*\0\x08\0\0\0".to_vec();
+        tiff.extend(3u16.to_le_bytes());
+        tiff.extend([0x01, 0x02, 4, 0, 1, 0, 0, 0, 100, 0, 0, 0]);
+        tiff.extend([0x02, 0x02, 4, 0, 1, 0, 0, 0, 20, 0, 0, 0]);
+        tiff.extend([0x4A, 0x01, 4, 0, 1, 0, 0, 0, 50, 0, 0, 0]);
+        tiff.extend(0u32.to_le_bytes());
+        // 子 IFD 在 50
+        tiff.extend(3u16.to_le_bytes());
+        tiff.extend([0x03, 0x01, 3, 0, 1, 0, 0, 0, 7, 0, 0, 0]);
+        tiff.extend([0x11, 0x01, 4, 0, 1, 0, 0, 0, 120, 0, 0, 0]);
+        tiff.extend([0x17, 0x01, 4, 0, 1, 0, 0, 0, 40, 0, 0, 0]);
+        tiff.extend(0u32.to_le_bytes());
+        tiff.resize(100, 0);
+        tiff.extend(jpeg(0xC0, 20));
+        tiff.extend(jpeg(0xC3, 40));
+        assert_eq!(preview_ranges(&tiff), [(100, 20), (120, 40)]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("IMG_0001.DNG");
+        fs::write(&path, &tiff).unwrap();
+        assert_eq!(read_embedded_preview(&path).unwrap(), jpeg(0xC0, 20));
+
+        // 子 IFD 的条带改为渐进式 JPEG 后使用较大的一个
+        tiff[120 + 9] = 0xC2;
+        fs::write(&path, &tiff).unwrap();
+        assert_eq!(read_embedded_preview(&path).unwrap().len(), 40);
+
+        // 超出文件长度的预览图跳过
+        fs::write(&path, &tiff[..150]).unwrap();
+        assert_eq!(read_embedded_preview(&path).unwrap().len(), 20);
+
+        fs::write(&path, b"not tiff").unwrap();
+        assert!(read_embedded_preview(&path).is_err());
+        assert!(preview_ranges(b"not tiff").is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+pub mod s3;
+pub mod webdav;
+
+use crate::constant::{REMOTE_CONNECT_TIMEOUT_SECS, REMOTE_REQUEST_TIMEOUT_SECS};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::structs::remote_storage::{RemoteObject, RemoteStorage, RemoteStorageKind};
+use anyhow::Result;
+use reqwest::{Body, Client, ClientBuilder, Response, StatusCode};
+use s3::S3Backend;
+use std::path::Path;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use webdav::WebDavBackend;
+
+/// 远程存储后端
+///
+/// 原图保存在 S3 兼容存储或 WebDAV 中，提供列出文件、下载和上传
+pub enum RemoteBackend {
+    S3(S3Backend),
+    WebDav(WebDavBackend),
+}
+
+impl RemoteBackend {
+    /// 按远程存储配置创建后端
+    pub fn new(config: &RemoteStorage) -> Result<Self> {
+        let client = http_client()?;
+        Ok(match config.kind {
+            RemoteStorageKind::S3 => RemoteBackend::S3(S3Backend::new(client, config)?),
+            RemoteStorageKind::WebDav => RemoteBackend::WebDav(WebDavBackend::new(client, config)?),
+        })
+    }
+
+    /// 列出目录前缀下的所有文件【包含子目录】
+    pub async fn list(&self) -> Result<Vec<RemoteObject>> {
+        match self {
+            RemoteBackend::S3(x) => x.list().await,
+            RemoteBackend::WebDav(x) => x.list().await,
+        }
+    }
+
+    /// 读取文件的一部分【服务端不支持范围请求时只读取需要的部分】
+    /// - key 对象路径
+    /// - start 起始位置
+    /// - len 读取的字节数
+    pub async fn read_range(&self, key: &str, start: u64, len: u64) -> Result<Vec<u8>> {
+        let mut response = match self {
+            RemoteBackend::S3(x) => x.get(key, Some((start, len))).await?,
+            RemoteBackend::WebDav(x) => x.get(key, Some((start, len))).await?,
+        };
+        // 返回整个文件时跳过起始位置之前的内容
+        let skip = match response.status() {
+            StatusCode::PARTIAL_CONTENT => 0,
+            _ => start as usize,
+        };
+        let end = skip + len as usize;
+        let mut data = Vec::new();
+        while data.len() < end {
+            match response.chunk().await? {
+                Some(chunk) => data.extend_from_slice(&chunk),
+                None => break,
+            }
+        }
+        data.truncate(end);
+        Ok(data.split_off(skip.min(data.len())))
+    }
+
+    /// 下载文件到本地
+    /// - key 对象路径
+    /// - dest 保存路径
+    pub async fn download(&self, key: &str, dest: &Path) -> Result<()> {
+        let mut response = match self {
+            RemoteBackend::S3(x) => x.get(key, None).await?,
+            RemoteBackend::WebDav(x) => x.get(key, None).await?,
+        };
+        let mut file = tokio::fs::File::create(dest).await?;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+        Ok(())
+    }
+
+    /// 上传本地文件【不把整个文件读入内存：S3 超过一个分片时分片上传，WebDAV 按流上传】
+    /// - key 对象路径
+    /// - source 本地文件
+    pub async fn upload(&self, key: &str, source: &Path) -> Result<()> {
+        let file = tokio::fs::File::open(source).await?;
+        let size = file.metadata().await?.len();
+        match self {
+            RemoteBackend::S3(x) => x.upload(key, file, size).await,
+            RemoteBackend::WebDav(x) => x.put(key, Body::from(file), size).await,
+        }
+    }
+}
+
+/// 远程存储使用的 HTTP 客户端【上传、下载大文件，超时时间比普通请求长】
+fn http_client() -> Result<Client> {
+    Ok(ClientBuilder::new()
+        .connect_timeout(Duration::from_secs(REMOTE_CONNECT_TIMEOUT_SECS))
+        .timeout(Duration::from_secs(REMOTE_REQUEST_TIMEOUT_SECS))
+        .build()?)
+}
+
+/// 检查响应状态，失败时返回状态码和响应内容
+pub(crate) async fn check_status(response: Response) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(i18n::error(
+        msg::REMOTE_REQUEST_FAILED,
+        &[
+            ("status", status.to_string()),
+            ("body", body.trim().to_string()),
+        ],
+    ))
+}
+
+/// 范围请求头的值
+pub(crate) fn range_header(start: u64, len: u64) -> String {
+    format!("bytes={}-{}", start, start + len.max(1) - 1)
+}
+
+/// 对路径进行 URI 编码【保留 `/` 时用于对象路径，不保留时用于查询参数】
+pub(crate) fn uri_encode(input: &str, keep_slash: bool) -> String {
+    let mut result = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                result.push(byte as char)
+            }
+            b'/' if keep_slash => result.push('/'),
+            _ => result.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    result
+}
+
+/// 解码 URI 编码的路径【无效的编码原样保留】
+pub(crate) fn uri_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|x| u8::from_str_radix(x, 16).ok()) {
+                result.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        result.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&result).to_string()
+}
+
+/// 拼接目录前缀和文件路径【去掉多余的 `/`】
+pub fn join_key(prefix: Option<&str>, name: &str) -> String {
+    let prefix = prefix.unwrap_or_default().trim_matches('/');
+    let name = name.trim_start_matches('/');
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", prefix, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uri_encode_decode() {
+        assert_eq!(uri_encode("2024/a b+c.jpg", true), "2024/a%20b%2Bc.jpg");
+        assert_eq!(uri_encode("a/b", false), "a%2Fb");
+        assert_eq!(uri_encode("照片", true), "%E7%85%A7%E7%89%87");
+        assert_eq!(uri_decode("2024/a%20b%2Bc.jpg"), "2024/a b+c.jpg");
+        assert_eq!(uri_decode("%E7%85%A7%E7%89%87"), "照片");
+        assert_eq!(uri_decode("100%"), "100%");
+    }
+
+    #[test]
+    fn test_join_key() {
+        assert_eq!(join_key(None, "a.jpg"), "a.jpg");
+        assert_eq!(
+            join_key(Some("/photos/"), "/2024/a.jpg"),
+            "photos/2024/a.jpg"
+        );
+        assert_eq!(join_key(Some(""), "a.jpg"), "a.jpg");
+        assert_eq!(range_header(0, 10), "bytes=0-9");
+    }
+}
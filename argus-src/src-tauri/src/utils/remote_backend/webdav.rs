@@ -0,0 +1,319 @@
+//! WebDAV 存储
+//!
+//! 使用 `PROPFIND`（Depth: 1）逐层列出目录，很多服务端禁用了 `Depth: infinity`；
+//! 上传前使用 `MKCOL` 创建上级目录
+
+use super::{check_status, range_header, uri_decode, uri_encode};
+use crate::i18n;
+use crate::i18n::msg;
+use crate::structs::remote_storage::{RemoteObject, RemoteStorage};
+use anyhow::Result;
+use chrono::DateTime;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use reqwest::{Body, Client, Method, RequestBuilder, Response, StatusCode, Url};
+use std::collections::VecDeque;
+
+/// PROPFIND 请求内容【只读取需要的属性】
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:">
+  <d:prop>
+    <d:resourcetype/>
+    <d:getcontentlength/>
+    <d:getlastmodified/>
+    <d:getetag/>
+  </d:prop>
+</d:propfind>"#;
+
+/// PROPFIND 返回的条目
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct DavEntry {
+    /// 解码后的路径
+    path: String,
+    is_dir: bool,
+    size: u64,
+    modified: i64,
+    etag: Option<String>,
+}
+
+/// WebDAV 存储
+pub struct WebDavBackend {
+    client: Client,
+    /// 根目录地址【以 `/` 结尾】
+    root: Url,
+    /// 根目录的路径部分（解码后）【用于把返回的路径转换为对象路径】
+    root_path: String,
+    prefix: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+impl WebDavBackend {
+    pub fn new(client: Client, config: &RemoteStorage) -> Result<Self> {
+        let endpoint = format!("{}/", config.endpoint.trim().trim_end_matches('/'));
+        let root = Url::parse(&endpoint)?;
+        if root.host_str().is_none() {
+            return Err(i18n::error(
+                msg::REMOTE_ENDPOINT_INVALID,
+                &[("endpoint", endpoint)],
+            ));
+        }
+        Ok(WebDavBackend {
+            client,
+            root_path: uri_decode(root.path()),
+            root,
+            prefix: config.prefix.clone(),
+            username: config.access_key.clone().filter(|x| !x.is_empty()),
+            password: config.secret_key.clone(),
+        })
+    }
+
+    /// 逐层列出目录前缀下的所有文件
+    pub async fn list(&self) -> Result<Vec<RemoteObject>> {
+        let start = self
+            .prefix
+            .as_deref()
+            .unwrap_or_default()
+            .trim_matches('/')
+            .to_string();
+        let mut objects = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(dir) = queue.pop_front() {
+            let url = if dir.is_empty() {
+                self.root.clone()
+            } else {
+                self.url(&format!("{}/", dir))?
+            };
+            let response = self
+                .request(Method::from_bytes(b"PROPFIND")?, url)
+                .header("Depth", "1")
+                .header(reqwest::header::CONTENT_TYPE, "application/xml")
+                .body(PROPFIND_BODY)
+                .send()
+                .await?;
+            let body = check_status(response).await?.text().await?;
+            for entry in parse_multistatus(&body)? {
+                let Some(key) = self.to_key(&entry.path) else {
+                    continue;
+                };
+                // 返回结果中包含目录本身
+                if key.is_empty() || key == dir {
+                    continue;
+                }
+                if entry.is_dir {
+                    queue.push_back(key);
+                } else {
+                    objects.push(RemoteObject {
+                        key,
+                        size: entry.size,
+                        modified: entry.modified,
+                        etag: entry.etag,
+                    });
+                }
+            }
+        }
+        Ok(objects)
+    }
+
+    /// 读取文件
+    /// - range 读取范围（起始位置、字节数）【为空时读取整个文件】
+    pub async fn get(&self, key: &str, range: Option<(u64, u64)>) -> Result<Response> {
+        let mut request = self.request(Method::GET, self.url(key)?);
+        if let Some((start, len)) = range {
+            request = request.header(reqwest::header::RANGE, range_header(start, len));
+        }
+        check_status(request.send().await?).await
+    }
+
+    /// 上传文件【先创建上级目录】
+    /// - body 文件内容
+    /// - len 文件大小
+    pub async fn put(&self, key: &str, body: Body, len: u64) -> Result<()> {
+        let parts: Vec<&str> = key.split('/').collect();
+        for depth in 1..parts.len() {
+            let dir = format!("{}/", parts[..depth].join("/"));
+            let response = self
+                .request(Method::from_bytes(b"MKCOL")?, self.url(&dir)?)
+                .send()
+                .await?;
+            // 目录已存在时返回 405
+            let status = response.status();
+            if !status.is_success() && status != StatusCode::METHOD_NOT_ALLOWED {
+                check_status(response).await?;
+            }
+        }
+        let response = self
+            .request(Method::PUT, self.url(key)?)
+            .header(reqwest::header::CONTENT_LENGTH, len)
+            .body(body)
+            .send()
+            .await?;
+        check_status(response).await?;
+        Ok(())
+    }
+
+    /// 对象路径对应的地址
+    fn url(&self, key: &str) -> Result<Url> {
+        Ok(self.root.join(&uri_encode(key, true))?)
+    }
+
+    /// 创建请求【设置了用户名时使用 Basic 认证】
+    fn request(&self, method: Method, url: Url) -> RequestBuilder {
+        let request = self.client.request(method, url);
+        match &self.username {
+            Some(username) => request.basic_auth(username, self.password.as_ref()),
+            None => request,
+        }
+    }
+
+    /// 把返回的路径转换为对象路径【不在根目录下时返回 None】
+    fn to_key(&self, path: &str) -> Option<String> {
+        path.strip_prefix(self.root_path.as_str())
+            .or_else(|| {
+                // 部分服务端返回的目录路径不以 `/` 结尾
+                (path == self.root_path.trim_end_matches('/')).then_some("")
+            })
+            .map(|x| x.trim_matches('/').to_string())
+    }
+}
+
+/// 解析 PROPFIND 返回的 multistatus XML【忽略命名空间前缀】
+fn parse_multistatus(xml: &str) -> Result<Vec<DavEntry>> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+    let mut entries = Vec::new();
+    let mut current: Option<DavEntry> = None;
+    let mut tag = String::new();
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                tag = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                if tag == "response" {
+                    current = Some(DavEntry::default());
+                }
+            }
+            Event::Empty(e) => {
+                if e.local_name().as_ref() == b"collection" {
+                    if let Some(entry) = current.as_mut() {
+                        entry.is_dir = true;
+                    }
+                }
+            }
+            Event::Text(e) => {
+                let Some(entry) = current.as_mut() else {
+                    continue;
+                };
+                let text = e.unescape()?.to_string();
+                match tag.as_str() {
+                    "href" => entry.path = href_path(&text),
+                    "getcontentlength" => entry.size = text.parse().unwrap_or_default(),
+                    "getlastmodified" => {
+                        entry.modified = DateTime::parse_from_rfc2822(&text)
+                            .map(|x| x.timestamp())
+                            .unwrap_or_default()
+                    }
+                    "getetag" => {
+                        entry.etag =
+                            Some(text.trim_start_matches("W/").trim_matches('"').to_string())
+                    }
+                    _ => {}
+                }
+            }
+            Event::End(e) => {
+                if e.local_name().as_ref() == b"response" {
+                    if let Some(entry) = current.take() {
+                        entries.push(entry);
+                    }
+                }
+                tag.clear();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(entries)
+}
+
+/// href 中的路径（解码后）【href 可能是完整地址，也可能只有路径】
+fn href_path(href: &str) -> String {
+    let path = match Url::parse(href) {
+        Ok(url) => url.path().to_string(),
+        Err(_) => href.to_string(),
+    };
+    uri_decode(&path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::remote_storage::RemoteStorageKind;
+
+    #[test]
+    fn test_parse_multistatus() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response>
+    <d:href>/dav/photos/</d:href>
+    <d:propstat>
+      <d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>https://nas.example.com/dav/photos/2024%20trip/IMG_0001.jpg</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:resourcetype/>
+        <d:getcontentlength>4096</d:getcontentlength>
+        <d:getlastmodified>Mon, 15 Jan 2024 10:00:00 GMT</d:getlastmodified>
+        <d:getetag>W/"abc123"</d:getetag>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#;
+        let entries = parse_multistatus(xml).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, "/dav/photos/");
+        assert!(entries[0].is_dir);
+        assert_eq!(
+            entries[1],
+            DavEntry {
+                path: "/dav/photos/2024 trip/IMG_0001.jpg".to_string(),
+                is_dir: false,
+                size: 4096,
+                modified: 1705312800,
+                etag: Some("abc123".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_to_key() {
+        let config = RemoteStorage {
+            id: None,
+            name: "NAS".to_string(),
+            kind: RemoteStorageKind::WebDav,
+            endpoint: "https://nas.example.com/dav/photos".to_string(),
+            bucket: None,
+            region: None,
+            prefix: None,
+            access_key: None,
+            secret_key: None,
+            has_secret: false,
+            is_enable: true,
+        };
+        let backend = WebDavBackend::new(Client::new(), &config).unwrap();
+        assert_eq!(backend.to_key("/dav/photos/"), Some(String::new()));
+        assert_eq!(backend.to_key("/dav/photos"), Some(String::new()));
+        assert_eq!(
+            backend.to_key("/dav/photos/2024 trip/a.jpg"),
+            Some("2024 trip/a.jpg".to_string())
+        );
+        assert_eq!(backend.to_key("/other/a.jpg"), None);
+        assert_eq!(
+            backend.url("2024 trip/a.jpg").unwrap().as_str(),
+            "https://nas.example.com/dav/photos/2024%20trip/a.jpg"
+        );
+    }
+}
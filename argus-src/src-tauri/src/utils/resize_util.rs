@@ -0,0 +1,150 @@
+use crate::structs::resize_backend::ResizeBackend;
+#[cfg(feature = "gpu-resize")]
+use crate::utils::gpu_resize_util;
+use anyhow::Result;
+use fast_image_resize::{self as fr, ResizeAlg, ResizeOptions, Resizer};
+use image::imageops::FilterType;
+use image::DynamicImage;
+use std::cell::RefCell;
+
+thread_local! {
+    /// 每个线程复用缩放器【避免重复检测 CPU 指令集和分配缓冲区】
+    static RESIZER: RefCell<Resizer> = RefCell::new(Resizer::new());
+}
+
+/// 按比例缩放到指定范围内，使用配置的缩放实现
+/// - width 最大宽度
+/// - height 最大高度
+pub fn resize(image: &DynamicImage, width: u32, height: u32, filter: FilterType) -> DynamicImage {
+    resize_with(ResizeBackend::from_config(), image, width, height, filter)
+}
+
+/// 缩放到指定宽高【不保持比例】，使用配置的缩放实现
+pub fn resize_exact(
+    image: &DynamicImage,
+    width: u32,
+    height: u32,
+    filter: FilterType,
+) -> DynamicImage {
+    resize_exact_with(ResizeBackend::from_config(), image, width, height, filter)
+}
+
+/// 使用指定的缩放实现按比例缩放到指定范围内
+pub fn resize_with(
+    backend: ResizeBackend,
+    image: &DynamicImage,
+    width: u32,
+    height: u32,
+    filter: FilterType,
+) -> DynamicImage {
+    let (width, height) = fit_dimensions(image.width(), image.height(), width, height);
+    resize_exact_with(backend, image, width, height, filter)
+}
+
+/// 使用指定的缩放实现缩放到指定宽高
+pub fn resize_exact_with(
+    backend: ResizeBackend,
+    image: &DynamicImage,
+    width: u32,
+    height: u32,
+    filter: FilterType,
+) -> DynamicImage {
+    match backend {
+        ResizeBackend::Image => image.resize_exact(width, height, filter),
+        ResizeBackend::Simd => simd_resize(image, width, height, filter).unwrap_or_else(|e| {
+            log::debug!("SIMD 缩放失败，使用 image 库: {}", e);
+            image.resize_exact(width, height, filter)
+        }),
+        ResizeBackend::Gpu => gpu_resize(image, width, height).unwrap_or_else(|e| {
+            log::debug!("GPU 缩放失败，使用 SIMD 加速: {}", e);
+            resize_exact_with(ResizeBackend::Simd, image, width, height, filter)
+        }),
+    }
+}
+
+/// 缩放实现在当前环境是否可用【GPU 需要 gpu-resize 功能和可用的显卡】
+pub fn is_available(backend: ResizeBackend) -> bool {
+    match backend {
+        ResizeBackend::Image | ResizeBackend::Simd => true,
+        #[cfg(feature = "gpu-resize")]
+        ResizeBackend::Gpu => gpu_resize_util::is_available(),
+        #[cfg(not(feature = "gpu-resize"))]
+        ResizeBackend::Gpu => false,
+    }
+}
+
+/// 使用显卡缩小【区域平均，不区分滤波算法】
+#[cfg(feature = "gpu-resize")]
+fn gpu_resize(image: &DynamicImage, width: u32, height: u32) -> Result<DynamicImage> {
+    gpu_resize_util::resize(image, width, height)
+}
+
+#[cfg(not(feature = "gpu-resize"))]
+fn gpu_resize(_: &DynamicImage, _: u32, _: u32) -> Result<DynamicImage> {
+    Err(anyhow::anyhow!(
+        "当前版本未包含 GPU 缩放（gpu-resize 功能）"
+    ))
+}
+
+/// 使用 fast_image_resize 缩放【带透明通道的图片自动预乘】
+fn simd_resize(
+    image: &DynamicImage,
+    width: u32,
+    height: u32,
+    filter: FilterType,
+) -> Result<DynamicImage> {
+    let mut target = DynamicImage::new(width, height, image.color());
+    let options = ResizeOptions::new().resize_alg(resize_alg(filter));
+    RESIZER.with(|resizer| resizer.borrow_mut().resize(image, &mut target, &options))?;
+    Ok(target)
+}
+
+/// image 库的缩放算法对应的 fast_image_resize 算法
+fn resize_alg(filter: FilterType) -> ResizeAlg {
+    match filter {
+        FilterType::Nearest => ResizeAlg::Nearest,
+        FilterType::Triangle => ResizeAlg::Convolution(fr::FilterType::Bilinear),
+        FilterType::CatmullRom => ResizeAlg::Convolution(fr::FilterType::CatmullRom),
+        // 没有高斯滤波，使用同样平滑的 Mitchell
+        FilterType::Gaussian => ResizeAlg::Convolution(fr::FilterType::Mitchell),
+        FilterType::Lanczos3 => ResizeAlg::Convolution(fr::FilterType::Lanczos3),
+    }
+}
+
+/// 按比例缩放到指定范围内的尺寸【与 `DynamicImage::resize` 一致，至少为 1】
+pub fn fit_dimensions(width: u32, height: u32, max_width: u32, max_height: u32) -> (u32, u32) {
+    if width == 0 || height == 0 {
+        return (max_width.max(1), max_height.max(1));
+    }
+    let ratio = f64::min(
+        max_width as f64 / width as f64,
+        max_height as f64 / height as f64,
+    );
+    let fit = |x: u32| ((x as f64 * ratio).round() as u32).max(1);
+    (fit(width), fit(height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    #[test]
+    fn test_fit_dimensions() {
+        assert_eq!(fit_dimensions(4000, 3000, 512, 512), (512, 384));
+        assert_eq!(fit_dimensions(3000, 4000, 256, 256), (192, 256));
+        assert_eq!(fit_dimensions(10000, 10, 128, 128), (128, 1));
+    }
+
+    #[test]
+    fn test_backends_match() {
+        let image = DynamicImage::ImageRgb8(RgbImage::from_fn(400, 300, |x, y| {
+            Rgb([(x % 256) as u8, (y % 256) as u8, 128])
+        }));
+        for backend in ResizeBackend::ALL {
+            let resized = resize_with(backend, &image, 128, 128, FilterType::Triangle);
+            assert_eq!((resized.width(), resized.height()), (128, 96));
+            assert_eq!(resized.color(), image.color());
+        }
+    }
+}
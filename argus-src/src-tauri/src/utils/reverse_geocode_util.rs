@@ -0,0 +1,98 @@
+use crate::http_client::HttpClient;
+use crate::utils::exif_utils::gps_util::GpsCoordinate;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// 区域级别的地址字段【按优先级排列】
+const AREA_KEYS: [&str; 4] = ["suburb", "neighbourhood", "quarter", "city_district"];
+/// 城镇级别的地址字段【按优先级排列】
+const LOCALITY_KEYS: [&str; 6] = ["city", "town", "village", "municipality", "county", "state"];
+
+/// Nominatim reverse 接口的返回内容
+#[derive(Deserialize, Debug, Default)]
+struct ReverseResponse {
+    name: Option<String>,
+    display_name: Option<String>,
+    #[serde(default)]
+    address: HashMap<String, String>,
+    error: Option<String>,
+}
+
+/// 查询坐标对应的地点名称
+/// - url Nominatim 兼容的 reverse 接口地址
+/// - coordinate 十进制坐标
+pub async fn reverse_geocode(url: &str, coordinate: &GpsCoordinate) -> Result<Option<String>> {
+    let response = HttpClient::new()
+        .client
+        .get(url)
+        .query(&[
+            ("format", "jsonv2".to_string()),
+            ("lat", coordinate.latitude.to_string()),
+            ("lon", coordinate.longitude.to_string()),
+            ("zoom", "14".to_string()),
+        ])
+        .header(
+            reqwest::header::USER_AGENT,
+            concat!("argus/", env!("CARGO_PKG_VERSION")),
+        )
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("逆地理编码请求失败: {}", response.status()));
+    }
+    parse_place_name(&response.text().await?)
+}
+
+/// 从返回内容中提取地点名称【区域 + 城镇，都没有时使用名称或完整地址】
+fn parse_place_name(body: &str) -> Result<Option<String>> {
+    let response: ReverseResponse = serde_json::from_str(body)?;
+    if let Some(error) = response.error {
+        return Err(anyhow!("逆地理编码失败: {}", error));
+    }
+    let first = |keys: &[&str]| {
+        keys.iter()
+            .filter_map(|x| response.address.get(*x))
+            .map(|x| x.trim())
+            .find(|x| !x.is_empty())
+            .map(str::to_string)
+    };
+    let mut parts: Vec<String> = Vec::new();
+    for part in [first(&AREA_KEYS), first(&LOCALITY_KEYS)]
+        .into_iter()
+        .flatten()
+    {
+        if !parts.contains(&part) {
+            parts.push(part);
+        }
+    }
+    if !parts.is_empty() {
+        return Ok(Some(parts.join(", ")));
+    }
+    Ok(response
+        .name
+        .or(response.display_name)
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_place_name() {
+        let body = r#"{"name":"","display_name":"外滩, 黄浦区, 上海市, 中国",
+            "address":{"suburb":"黄浦区","city":"上海市","country":"中国"}}"#;
+        assert_eq!(
+            parse_place_name(body).unwrap().as_deref(),
+            Some("黄浦区, 上海市")
+        );
+        let body = r#"{"display_name":"Atlantic Ocean","address":{}}"#;
+        assert_eq!(
+            parse_place_name(body).unwrap().as_deref(),
+            Some("Atlantic Ocean")
+        );
+        assert!(parse_place_name(r#"{"error":"Unable to geocode"}"#).is_err());
+    }
+}
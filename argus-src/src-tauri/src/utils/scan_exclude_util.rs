@@ -0,0 +1,123 @@
+use crate::constant::SCAN_IGNORE_FILE_NAME;
+use crate::structs::config::sys_config;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// 扫描排除规则
+///
+/// 由设置中的全局规则和各目录下的 `.argusignore` 文件组成，均使用 gitignore 语法：
+/// - 全局规则只按文件名、文件夹名匹配（任意层级），如 `@eaDir`、`*.tmp`
+/// - 以 `/` 结尾的规则只匹配文件夹
+/// - 目录规则优先于全局规则，越靠近文件的目录优先级越高，可以用 `!` 取消排除
+#[derive(Debug)]
+pub struct ScanExcludeRules {
+    /// 设置中的全局规则
+    global: Gitignore,
+    /// 已加载的目录规则【目录下没有规则文件时为 None】
+    dir_rules: Mutex<HashMap<PathBuf, Option<Arc<Gitignore>>>>,
+}
+
+impl ScanExcludeRules {
+    /// 使用指定的全局规则创建
+    pub fn new(patterns: &[String]) -> ScanExcludeRules {
+        let mut builder = GitignoreBuilder::new("");
+        for pattern in patterns {
+            if let Err(e) = builder.add_line(None, pattern) {
+                log::warn!("扫描排除规则无效 {}: {}", pattern, e);
+            }
+        }
+        let global = builder.build().unwrap_or_else(|e| {
+            log::error!("扫描排除规则加载失败: {}", e);
+            Gitignore::empty()
+        });
+        ScanExcludeRules {
+            global,
+            dir_rules: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 使用系统配置中的全局规则创建
+    pub fn from_config() -> ScanExcludeRules {
+        ScanExcludeRules::new(sys_config().scan_exclude_patterns.as_deref().unwrap_or_default())
+    }
+
+    /// 指定路径是否被排除
+    /// - path 文件或文件夹路径
+    /// - is_dir 是否为文件夹
+    pub fn is_excluded(&self, path: &Path, is_dir: bool) -> bool {
+        for dir in path.ancestors().skip(1) {
+            let Some(rules) = self.rules_of(dir) else {
+                continue;
+            };
+            match rules.matched_path_or_any_parents(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        self.is_globally_excluded(path, is_dir)
+    }
+
+    /// 路径本身或任意上级文件夹的名称是否匹配全局规则
+    fn is_globally_excluded(&self, path: &Path, is_dir: bool) -> bool {
+        let mut is_dir = is_dir;
+        for item in path.ancestors() {
+            let Some(name) = item.file_name() else {
+                break;
+            };
+            if self.global.matched(Path::new(name), is_dir).is_ignore() {
+                return true;
+            }
+            is_dir = true;
+        }
+        false
+    }
+
+    /// 读取目录下的规则文件【结果会被缓存】
+    fn rules_of(&self, dir: &Path) -> Option<Arc<Gitignore>> {
+        let mut dir_rules = self.dir_rules.lock().unwrap();
+        dir_rules
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| {
+                let file = dir.join(SCAN_IGNORE_FILE_NAME);
+                if !file.is_file() {
+                    return None;
+                }
+                let (rules, err) = Gitignore::new(&file);
+                if let Some(e) = err {
+                    log::warn!("排除规则文件读取失败 {}: {}", file.display(), e);
+                }
+                Some(Arc::new(rules))
+            })
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_is_excluded() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("a/@eaDir")).unwrap();
+        fs::create_dir_all(root.join("a/keep")).unwrap();
+        fs::write(root.join(SCAN_IGNORE_FILE_NAME), "*.png\n").unwrap();
+        fs::write(root.join("a/keep").join(SCAN_IGNORE_FILE_NAME), "!*.png\n").unwrap();
+
+        let rules = ScanExcludeRules::new(&["@eaDir/".to_string(), "*.tmp".to_string()]);
+        // 全局规则
+        assert!(rules.is_excluded(&root.join("a/@eaDir"), true));
+        assert!(rules.is_excluded(&root.join("a/@eaDir/1.jpg"), false));
+        assert!(rules.is_excluded(&root.join("a/1.tmp"), false));
+        assert!(!rules.is_excluded(&root.join("a/1.jpg"), false));
+        // 目录规则，子目录可以取消排除
+        assert!(rules.is_excluded(&root.join("a/1.png"), false));
+        assert!(!rules.is_excluded(&root.join("a/keep/1.png"), false));
+    }
+}
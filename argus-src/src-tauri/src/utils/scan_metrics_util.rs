@@ -0,0 +1,235 @@
+use crate::constant::SCAN_TASK_HISTORY_LIMIT;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// 扫描吞吐量统计
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanMetrics {
+    /// 扫描 ID
+    pub scan_id: Option<i32>,
+    /// 是否正在扫描
+    pub running: bool,
+    /// 已耗时（毫秒）
+    pub elapsed_millis: u64,
+    /// 已计算 Hash 的文件数量
+    pub files_hashed: u64,
+    /// 已计算 Hash 的字节数
+    pub bytes_hashed: u64,
+    /// 每秒计算 Hash 的文件数量
+    pub files_per_sec: f64,
+    /// 每秒读取的数据量（MB）
+    pub mb_per_sec: f64,
+    /// Hash 计算耗时之和（毫秒）
+    pub hash_millis: u64,
+    /// 已生成的缩略图数量
+    pub thumbnails: u64,
+    /// 每秒生成的缩略图数量
+    pub thumbnails_per_sec: f64,
+    /// 缩略图生成耗时之和（毫秒）
+    pub thumbnail_millis: u64,
+    /// 数据库写入次数
+    pub db_writes: u64,
+    /// 每秒数据库写入次数
+    pub db_writes_per_sec: f64,
+    /// 数据库写入耗时之和（毫秒）
+    pub db_write_millis: u64,
+    /// 等待处理的文件数量（已发现未完成）
+    pub queue_depth: u64,
+    /// 等待处理文件数量的最大值
+    pub max_queue_depth: u64,
+}
+
+/// 正在统计的扫描
+#[derive(Debug)]
+struct MetricsState {
+    started: Instant,
+    finished_millis: Option<u64>,
+    metrics: ScanMetrics,
+    hash_micros: u64,
+    thumbnail_micros: u64,
+    db_write_micros: u64,
+}
+
+/// 各次扫描的统计【扫描 ID -> 统计，已结束的扫描只保留最近 `SCAN_TASK_HISTORY_LIMIT` 个】
+static SCAN_METRICS: Lazy<Mutex<BTreeMap<i32, MetricsState>>> =
+    Lazy::new(|| Mutex::new(BTreeMap::new()));
+
+tokio::task_local! {
+    /// 当前任务处理的文件所属的扫描
+    static CURRENT_SCAN: Option<i32>;
+}
+
+/// 开始统计新的扫描【扫描记录创建失败、没有扫描 ID 时不统计】
+pub fn start(scan_id: Option<i32>) {
+    let Some(scan_id) = scan_id else {
+        return;
+    };
+    let mut states = SCAN_METRICS.lock().unwrap();
+    states.insert(
+        scan_id,
+        MetricsState {
+            started: Instant::now(),
+            finished_millis: None,
+            metrics: ScanMetrics {
+                scan_id: Some(scan_id),
+                running: true,
+                ..Default::default()
+            },
+            hash_micros: 0,
+            thumbnail_micros: 0,
+            db_write_micros: 0,
+        },
+    );
+    // 清理最早结束的扫描
+    let finished: Vec<i32> = states
+        .iter()
+        .filter(|(_, state)| !state.metrics.running)
+        .map(|(id, _)| *id)
+        .collect();
+    let excess = finished.len().saturating_sub(SCAN_TASK_HISTORY_LIMIT);
+    for id in &finished[..excess] {
+        states.remove(id);
+    }
+}
+
+/// 在指定扫描中执行，期间的 Hash 计算、缩略图生成计入这次扫描
+pub async fn scope<F: Future>(scan_id: Option<i32>, f: F) -> F::Output {
+    CURRENT_SCAN.scope(scan_id, f).await
+}
+
+/// 当前任务所属的扫描【不在扫描中时为空】
+pub fn current_scan() -> Option<i32> {
+    CURRENT_SCAN.try_with(|x| *x).ok().flatten()
+}
+
+/// 结束统计，返回最终结果
+pub fn finish(scan_id: Option<i32>) -> Option<ScanMetrics> {
+    let scan_id = scan_id?;
+    {
+        let mut states = SCAN_METRICS.lock().unwrap();
+        let state = states.get_mut(&scan_id)?;
+        state.metrics.running = false;
+        state.finished_millis = Some(state.started.elapsed().as_millis() as u64);
+    }
+    snapshot(Some(scan_id))
+}
+
+/// 记录当前扫描的一次 Hash 计算
+pub fn record_hash(bytes: u64, micros: u64) {
+    update(current_scan(), |state| {
+        state.metrics.files_hashed += 1;
+        state.metrics.bytes_hashed += bytes;
+        state.hash_micros += micros;
+    });
+}
+
+/// 记录当前扫描的一张缩略图的生成
+pub fn record_thumbnail(micros: u64) {
+    update(current_scan(), |state| {
+        state.metrics.thumbnails += 1;
+        state.thumbnail_micros += micros;
+    });
+}
+
+/// 记录一次数据库写入
+/// - rows 写入的照片数量【批量写入时一次写入多张】
+pub fn record_db_write(scan_id: Option<i32>, rows: u64, micros: u64) {
+    update(scan_id, |state| {
+        state.metrics.db_writes += rows;
+        state.db_write_micros += micros;
+    });
+}
+
+/// 记录等待处理的文件数量
+pub fn record_queue_depth(scan_id: Option<i32>, depth: u64) {
+    update(scan_id, |state| {
+        state.metrics.queue_depth = depth;
+        state.metrics.max_queue_depth = state.metrics.max_queue_depth.max(depth);
+    });
+}
+
+/// 指定扫描的统计
+/// - scan_id 扫描 ID【为空时使用最近开始的扫描】
+pub fn snapshot(scan_id: Option<i32>) -> Option<ScanMetrics> {
+    let states = SCAN_METRICS.lock().unwrap();
+    let state = match scan_id {
+        Some(id) => states.get(&id)?,
+        None => states.values().max_by_key(|x| x.started)?,
+    };
+    let elapsed_millis = state
+        .finished_millis
+        .unwrap_or_else(|| state.started.elapsed().as_millis() as u64);
+    let seconds = (elapsed_millis as f64 / 1000.0).max(0.001);
+    let metrics = &state.metrics;
+    Some(ScanMetrics {
+        elapsed_millis,
+        files_per_sec: metrics.files_hashed as f64 / seconds,
+        mb_per_sec: metrics.bytes_hashed as f64 / 1024.0 / 1024.0 / seconds,
+        hash_millis: state.hash_micros / 1000,
+        thumbnails_per_sec: metrics.thumbnails as f64 / seconds,
+        thumbnail_millis: state.thumbnail_micros / 1000,
+        db_writes_per_sec: metrics.db_writes as f64 / seconds,
+        db_write_millis: state.db_write_micros / 1000,
+        ..metrics.clone()
+    })
+}
+
+/// 只在扫描进行中时记录
+fn update<F: FnOnce(&mut MetricsState)>(scan_id: Option<i32>, f: F) {
+    let Some(scan_id) = scan_id else {
+        return;
+    };
+    if let Some(state) = SCAN_METRICS.lock().unwrap().get_mut(&scan_id) {
+        if state.metrics.running {
+            f(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scan_metrics() {
+        start(Some(1));
+        start(Some(2));
+        scope(Some(1), async {
+            assert_eq!(current_scan(), Some(1));
+            record_hash(2 * 1024 * 1024, 1500);
+            record_hash(1024 * 1024, 500);
+            record_thumbnail(3000);
+        })
+        .await;
+        scope(Some(2), async { record_hash(1, 1) }).await;
+        // 不在扫描中时不记录
+        record_hash(1, 1);
+        assert_eq!(current_scan(), None);
+        record_db_write(Some(1), 1, 100);
+        record_queue_depth(Some(1), 5);
+        record_queue_depth(Some(1), 2);
+        let metrics = finish(Some(1)).unwrap();
+        assert!(!metrics.running);
+        assert_eq!(metrics.scan_id, Some(1));
+        assert_eq!(metrics.files_hashed, 2);
+        assert_eq!(metrics.bytes_hashed, 3 * 1024 * 1024);
+        assert_eq!(metrics.hash_millis, 2);
+        assert_eq!(metrics.thumbnails, 1);
+        assert_eq!(metrics.db_writes, 1);
+        assert_eq!(metrics.queue_depth, 2);
+        assert_eq!(metrics.max_queue_depth, 5);
+        assert!(metrics.files_per_sec > 0.0);
+        // 结束后不再记录，其他扫描不受影响
+        scope(Some(1), async { record_hash(1, 1) }).await;
+        assert_eq!(snapshot(Some(1)).unwrap().files_hashed, 2);
+        let other = snapshot(Some(2)).unwrap();
+        assert!(other.running);
+        assert_eq!(other.files_hashed, 1);
+        assert!(finish(None).is_none());
+    }
+}
@@ -0,0 +1,107 @@
+//! 数据库中保存的密钥（远程存储密码等）的加密
+//!
+//! 使用照片库中随机生成的本机密钥文件加密（AES-256-GCM，见 `vault_crypto_util`），
+//! 数据库文件或备份被复制到其他位置时无法直接读取密钥
+
+use crate::constant::SECRET_KEY_FILE_NAME;
+use crate::structs::library::current_library;
+use crate::utils::base64_util;
+use crate::utils::vault_crypto_util::{self, VaultKey};
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// 加密后的密钥前缀【没有前缀的是加密之前保存的明文】
+const SEALED_PREFIX: &str = "sealed:";
+
+/// 本机密钥长度
+const KEY_LEN: usize = 32;
+
+/// 加密密钥，返回保存到数据库的文本
+pub fn seal(secret: &str) -> Result<String> {
+    seal_with(&local_key()?, secret)
+}
+
+/// 解密数据库中保存的密钥【明文原样返回】
+pub fn open(stored: &str) -> Result<String> {
+    if !is_sealed(stored) {
+        return Ok(stored.to_string());
+    }
+    open_with(&local_key()?, stored)
+}
+
+/// 是否已加密
+pub fn is_sealed(stored: &str) -> bool {
+    stored.starts_with(SEALED_PREFIX)
+}
+
+fn seal_with(key: &VaultKey, secret: &str) -> Result<String> {
+    let data = key.encrypt(secret.as_bytes())?;
+    Ok(format!(
+        "{}{}",
+        SEALED_PREFIX,
+        base64_util::base64_encode(data)
+    ))
+}
+
+fn open_with(key: &VaultKey, stored: &str) -> Result<String> {
+    let encoded = stored.strip_prefix(SEALED_PREFIX).unwrap_or(stored);
+    let data = STANDARD
+        .decode(encoded)
+        .map_err(|e| anyhow!("密钥格式错误: {}", e))?;
+    let secret = key
+        .decrypt(&data)
+        .map_err(|_| anyhow!("密钥无法解密，本机密钥文件已变化，请重新填写!"))?;
+    Ok(String::from_utf8(secret)?)
+}
+
+/// 读取当前照片库的本机密钥【不存在时生成】
+fn local_key() -> Result<VaultKey> {
+    let path = current_library().root.join(SECRET_KEY_FILE_NAME);
+    if !path.exists() {
+        create_key_file(&path)?;
+    }
+    let data = fs::read(&path)?;
+    if data.len() != KEY_LEN {
+        return Err(anyhow!("本机密钥文件 {} 已损坏!", path.display()));
+    }
+    VaultKey::from_bytes(&data)
+}
+
+/// 生成本机密钥文件【只允许当前用户读写】
+fn create_key_file(path: &Path) -> Result<()> {
+    let key = vault_crypto_util::random_bytes::<KEY_LEN>()?;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(&key)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_open() {
+        let key =
+            VaultKey::from_bytes(&vault_crypto_util::random_bytes::<KEY_LEN>().unwrap()).unwrap();
+        let sealed = seal_with(&key, "wJalrXUtnFEMI").unwrap();
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.contains("wJalrXUtnFEMI"));
+        assert_eq!(open_with(&key, &sealed).unwrap(), "wJalrXUtnFEMI");
+
+        let other = VaultKey::from_bytes(&[7u8; KEY_LEN]).unwrap();
+        assert!(open_with(&other, &sealed).is_err());
+        assert!(!is_sealed("plain"));
+    }
+}
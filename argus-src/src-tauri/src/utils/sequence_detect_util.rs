@@ -0,0 +1,186 @@
+use crate::constant::{
+    BRACKET_EV_STEP_TOLERANCE, BRACKET_MIN_EV_STEP, PANORAMA_MIN_INTERVAL_SECS,
+    SEQUENCE_INTERVAL_TOLERANCE_SECS, SEQUENCE_MAX_GAP_SECS, SEQUENCE_MIN_SHOTS,
+};
+use crate::structs::photo_sequence::SequenceKind;
+use crate::utils::exif_utils::format;
+use std::ops::Range;
+
+/// 参与连拍序列检测的一次拍摄
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SequenceShot {
+    /// 拍摄时间（秒）
+    pub time: i64,
+    pub exposure_time: Option<f32>,
+    pub f_number: Option<f32>,
+    pub iso: Option<i32>,
+    pub focal_length: Option<f32>,
+    /// 是否为横向画面
+    pub landscape: bool,
+}
+
+impl SequenceShot {
+    /// 曝光值（EV100）【曝光参数不完整时为 None】
+    pub fn ev100(&self) -> Option<f64> {
+        format::exposure_value(
+            f64::from(self.exposure_time?),
+            f64::from(self.f_number?),
+            f64::from(self.iso?),
+        )
+    }
+
+    /// 曝光参数和画面方向是否完全相同【参数不完整时视为不同】
+    fn same_exposure(&self, other: &SequenceShot) -> bool {
+        self.ev100().is_some()
+            && self.focal_length.is_some()
+            && self.exposure_time == other.exposure_time
+            && self.f_number == other.f_number
+            && self.iso == other.iso
+            && self.focal_length == other.focal_length
+            && self.landscape == other.landscape
+    }
+}
+
+/// 检测全景和包围曝光序列
+///
+/// 依次尝试从每张照片开始匹配包围曝光（光圈、焦距不变，曝光值等间距变化），
+/// 再匹配全景（曝光参数完全相同），匹配成功后从序列之后继续，序列之间不重叠。
+/// 两种序列都要求相邻间隔不超过上限且间隔均匀。
+/// - shots 同一台相机的拍摄，按拍摄时间排序
+pub fn detect_sequences(shots: &[SequenceShot]) -> Vec<(SequenceKind, Range<usize>)> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    while start < shots.len() {
+        if let Some(end) = bracket_end(shots, start) {
+            result.push((SequenceKind::Bracket, start..end));
+            start = end;
+        } else if let Some(end) = panorama_end(shots, start) {
+            result.push((SequenceKind::Panorama, start..end));
+            start = end;
+        } else {
+            start += 1;
+        }
+    }
+    result
+}
+
+/// 从 start 开始的包围曝光的结束位置（不包含）
+fn bracket_end(shots: &[SequenceShot], start: usize) -> Option<usize> {
+    let first = &shots[start];
+    let mut evs = vec![first.ev100()?];
+    for (prev, shot) in shots[start..].iter().zip(&shots[start + 1..]) {
+        if shot.time - prev.time > SEQUENCE_MAX_GAP_SECS
+            || shot.f_number != first.f_number
+            || shot.focal_length != first.focal_length
+        {
+            break;
+        }
+        let Some(ev) = shot.ev100() else {
+            break;
+        };
+        // 曝光值重复说明下一组包围曝光已经开始
+        if evs
+            .iter()
+            .any(|x| (x - ev).abs() < BRACKET_MIN_EV_STEP / 2.0)
+        {
+            break;
+        }
+        evs.push(ev);
+    }
+    while evs.len() >= SEQUENCE_MIN_SHOTS {
+        let end = start + evs.len();
+        if is_evenly_stepped(&evs) && is_evenly_spaced(&shots[start..end]) {
+            return Some(end);
+        }
+        evs.pop();
+    }
+    None
+}
+
+/// 从 start 开始的全景的结束位置（不包含）
+fn panorama_end(shots: &[SequenceShot], start: usize) -> Option<usize> {
+    let first = &shots[start];
+    let mut end = start + 1;
+    while end < shots.len()
+        && shots[end].time - shots[end - 1].time <= SEQUENCE_MAX_GAP_SECS
+        && shots[end].same_exposure(first)
+    {
+        end += 1;
+    }
+    (start + SEQUENCE_MIN_SHOTS..=end).rev().find(|&end| {
+        let run = &shots[start..end];
+        let span = run[run.len() - 1].time - run[0].time;
+        is_evenly_spaced(run) && span >= PANORAMA_MIN_INTERVAL_SECS * (run.len() as i64 - 1)
+    })
+}
+
+/// 排序后的曝光值是否等间距变化
+fn is_evenly_stepped(evs: &[f64]) -> bool {
+    let mut sorted = evs.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let steps: Vec<f64> = sorted.windows(2).map(|x| x[1] - x[0]).collect();
+    let min = steps.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = steps.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    min >= BRACKET_MIN_EV_STEP && max - min <= BRACKET_EV_STEP_TOLERANCE
+}
+
+/// 拍摄间隔是否均匀
+fn is_evenly_spaced(shots: &[SequenceShot]) -> bool {
+    let gaps: Vec<i64> = shots.windows(2).map(|x| x[1].time - x[0].time).collect();
+    match (gaps.iter().min(), gaps.iter().max()) {
+        (Some(min), Some(max)) => max - min <= SEQUENCE_INTERVAL_TOLERANCE_SECS,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shot(time: i64, exposure_time: f32) -> SequenceShot {
+        SequenceShot {
+            time,
+            exposure_time: Some(exposure_time),
+            f_number: Some(8.0),
+            iso: Some(100),
+            focal_length: Some(24.0),
+            landscape: true,
+        }
+    }
+
+    #[test]
+    fn test_detect_sequences() {
+        let shots = vec![
+            // 包围曝光 0, -2, +2 EV
+            shot(0, 1.0 / 60.0),
+            shot(0, 1.0 / 250.0),
+            shot(1, 1.0 / 15.0),
+            // 曝光不变、间隔均匀的全景
+            shot(30, 1.0 / 125.0),
+            shot(32, 1.0 / 125.0),
+            shot(35, 1.0 / 125.0),
+            shot(37, 1.0 / 125.0),
+            // 间隔过长的单张
+            shot(100, 1.0 / 125.0),
+            // 同一秒内的高速连拍
+            shot(200, 1.0 / 500.0),
+            shot(200, 1.0 / 500.0),
+            shot(200, 1.0 / 500.0),
+        ];
+        assert_eq!(
+            detect_sequences(&shots),
+            vec![
+                (SequenceKind::Bracket, 0..3),
+                (SequenceKind::Panorama, 3..7)
+            ]
+        );
+
+        // 曝光变化过小的自动曝光连拍不是包围曝光
+        let shots = vec![
+            shot(0, 1.0 / 60.0),
+            shot(2, 1.0 / 70.0),
+            shot(4, 1.0 / 80.0),
+        ];
+        assert!(detect_sequences(&shots).is_empty());
+    }
+}
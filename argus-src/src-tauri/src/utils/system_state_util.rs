@@ -38,13 +38,13 @@ pub fn main() {
 
     // 获取 CPU 信息
     let cpus = system.cpus();
-    println!("CPU 信息:");
+    log::info!("CPU 信息:");
     for (i, cpu) in cpus.iter().enumerate() {
-        println!("核心 {}: {}%", i, cpu.cpu_usage());
+        log::info!("核心 {}: {}%", i, cpu.cpu_usage());
     }
 
     // 获取总内存和可用内存
-    println!(
+    log::info!(
         "总内存: {} MB, 可用内存: {} MB",
         system.total_memory() / 1024,
         system.available_memory() / 1024
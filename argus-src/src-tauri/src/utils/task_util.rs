@@ -1,15 +1,25 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::collections::BTreeMap;
 use std::thread;
 use crate::models::photo::Photo;
 use crate::utils::img_util::ImageOperate;
 use once_cell::sync::Lazy;
 use crate::storage::connection::establish_connection;
-use crate::storage::photo_table::insert_photo;
+use crate::constant::PHOTO_INSERT_BATCH_SIZE;
 use crate::utils::task_util;
+use crate::utils::scan_metrics_util;
+use std::time::Instant;
 use std::sync::mpsc::{self, Sender, Receiver};
 use diesel::SqliteConnection;
 use rusqlite::{params, Connection};
 use crate::storage::photo_table;
+use crate::services::{
+    backup_service, organize_rule_service, recent_feed_service, watch_folder_service,
+};
+use crate::structs::scan_task::SCAN_TASKS;
+use crate::i18n;
+use crate::i18n::msg;
 // pub static PHOTO_LOAD_RECEIVER1: Lazy<Arc<Sender<ImageOperate>>> =
 //     Lazy::new(|| {
 //         let (photo_handler_tx, photo_handler_rx) = mpsc::channel::<ImageOperate>(100);
@@ -52,22 +62,102 @@ pub static PHOTO_LOAD_RECEIVER: Lazy<Arc<tauri::async_runtime::Sender<ImageOpera
     Lazy::new(|| {
         // 使用 tokio 的 mpsc 通道
         let (photo_handler_tx, photo_handler_rx) = tokio::sync::mpsc::channel::<ImageOperate>(100);
-        let f = |io: ImageOperate| {
-            let mut conn = establish_connection();
-            insert_photo(&mut conn, io);
-        };
         // 在一个新的线程中启动 Tokio 运行时
         thread::spawn(move || {
             // 创建 Tokio 运行时并运行异步任务
             let runtime = tokio::runtime::Runtime::new().unwrap();
             runtime.block_on(async move{
-                task_h(photo_handler_rx, f).await;
+                photo_batch_task(photo_handler_rx).await;
             });
         });
 
         Arc::new(photo_handler_tx)
     });
 
+/// 是否正在写入一批扫描结果
+static PHOTO_BATCH_WRITING: AtomicBool = AtomicBool::new(false);
+
+/// 扫描结果是否已全部写入数据库【通道为空且没有正在写入的批次】
+pub fn is_writer_idle() -> bool {
+    let queued = Lazy::get(&PHOTO_LOAD_RECEIVER)
+        .is_some_and(|tx| tx.capacity() < tx.max_capacity());
+    !queued && !PHOTO_BATCH_WRITING.load(Ordering::SeqCst)
+}
+
+/// 检查是否有正在使用当前数据库的后台任务【切换照片库、恢复数据库前检查】
+/// - action 要执行的操作的消息 ID，用于提示
+pub fn ensure_database_idle(action: &str) -> anyhow::Result<()> {
+    let busy = if SCAN_TASKS.has_running() {
+        msg::DATABASE_BUSY_SCANNING
+    } else if !is_writer_idle() {
+        msg::DATABASE_BUSY_WRITING
+    } else if backup_service::is_running() {
+        msg::DATABASE_BUSY_BACKUP
+    } else {
+        return Ok(());
+    };
+    Err(i18n::error(busy, &[("action", i18n::t(action, &[]))]))
+}
+
+/// 批量写入扫描结果
+///
+/// 等到第一张照片后，把通道中已排队的照片一起取出，在同一个事务中写入
+async fn photo_batch_task(mut rx: tokio::sync::mpsc::Receiver<ImageOperate>) {
+    while let Some(first) = rx.recv().await {
+        PHOTO_BATCH_WRITING.store(true, Ordering::SeqCst);
+        // 各扫描写入的照片数量【同一批次可能包含多个扫描的照片】
+        let mut scans: BTreeMap<Option<i32>, u64> = BTreeMap::new();
+        *scans.entry(first.scan_id).or_default() += 1;
+        let mut batch = vec![photo_table::to_scanned_photo(first)];
+        while batch.len() < PHOTO_INSERT_BATCH_SIZE {
+            match rx.try_recv() {
+                Ok(io) => {
+                    *scans.entry(io.scan_id).or_default() += 1;
+                    batch.push(photo_table::to_scanned_photo(io));
+                }
+                Err(_) => break,
+            }
+        }
+        let started = Instant::now();
+        let rows = batch.len() as u64;
+        let hashes: Vec<String> = batch.iter().map(|x| x.0.hash.clone()).collect();
+        let mut conn = establish_connection();
+        let inserted = match photo_table::bulk_upsert(&mut conn, batch.clone()) {
+            Ok(inserted) => inserted,
+            Err(e) => {
+                // 整批回滚，逐张重新写入，避免一张照片的错误影响同一批的其他照片
+                log::error!("照片批量写入失败，逐张重试: {}", e);
+                let mut inserted = Vec::new();
+                for photo in batch {
+                    let name = photo.0.img_name.clone();
+                    match photo_table::bulk_upsert(&mut conn, vec![photo]) {
+                        Ok(x) => inserted.extend(x),
+                        Err(e) => log::error!("照片写入失败 {}: {}", name, e),
+                    }
+                }
+                inserted
+            }
+        };
+        // 写入耗时按照片数量分摊到各扫描
+        let micros = started.elapsed().as_micros() as u64;
+        for (scan_id, count) in scans {
+            scan_metrics_util::record_db_write(scan_id, count, micros * count / rows);
+        }
+        if let Err(e) = recent_feed_service::record_import(&mut conn, &inserted) {
+            log::error!("导入批次记录失败: {}", e);
+        }
+        // 新导入的照片执行自动整理规则
+        if let Err(e) = organize_rule_service::apply_rules(&mut conn, &inserted) {
+            log::error!("自动整理规则执行失败: {}", e);
+        }
+        // 监视文件夹导入的照片加入相册、添加标签
+        if let Err(e) = watch_folder_service::apply_pending(&mut conn, &hashes, &inserted) {
+            log::error!("监视文件夹导入配置执行失败: {}", e);
+        }
+        PHOTO_BATCH_WRITING.store(false, Ordering::SeqCst);
+    }
+}
+
 pub async fn task_h<T, F>(mut rx: tokio::sync::mpsc::Receiver<T>, f: F)
 where
     F: Fn(T),
@@ -80,7 +170,7 @@ where
             }
             None => {
                 // 如果通道关闭，可以选择如何处理（这里是等待并继续）
-                println!("通道关闭，继续等待...");
+                log::debug!("通道关闭，继续等待...");
             }
         }
     }
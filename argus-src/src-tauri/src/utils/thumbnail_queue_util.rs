@@ -0,0 +1,225 @@
+use crate::constant::THUMBNAIL_QUEUE_CONCURRENCY;
+use once_cell::sync::Lazy;
+use std::collections::{BTreeMap, HashMap};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+/// 扫描时生成缩略图的任务队列
+pub static THUMBNAIL_QUEUE: Lazy<ThumbnailQueue> =
+    Lazy::new(|| ThumbnailQueue::new(THUMBNAIL_QUEUE_CONCURRENCY));
+
+/// 任务优先级【值越小越先处理】
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Priority {
+    /// 在前端可见范围内
+    Visible,
+    /// 按加入队列的顺序处理
+    Normal,
+}
+
+/// 等待中的任务的排序键【优先级、同一优先级内的顺序、加入队列的序号】
+type JobKey = (Priority, u64, u64);
+
+struct PendingJob {
+    path: PathBuf,
+    sender: oneshot::Sender<ThumbnailPermit>,
+}
+
+struct QueueState {
+    /// 同时处理的任务数量上限
+    limit: usize,
+    /// 正在处理的任务数量
+    running: usize,
+    next_seq: u64,
+    /// 按处理顺序排列的等待任务
+    pending: BTreeMap<JobKey, PendingJob>,
+    /// 文件路径 -> 等待任务【同一文件可能被多个扫描任务加入】
+    index: HashMap<PathBuf, Vec<JobKey>>,
+    /// 当前提前处理的文件
+    boosted: Vec<PathBuf>,
+}
+
+/// 带优先级的缩略图任务队列
+///
+/// 与信号量类似，限制同时处理的任务数量；等待中的任务默认按加入顺序处理，
+/// 前端可见范围内的任务提前处理
+pub struct ThumbnailQueue {
+    state: Arc<Mutex<QueueState>>,
+}
+
+/// 处理名额【释放时分配给下一个等待的任务】
+pub struct ThumbnailPermit {
+    /// 所属队列【为空时释放不做处理】
+    state: Option<Arc<Mutex<QueueState>>>,
+}
+
+impl Drop for ThumbnailPermit {
+    fn drop(&mut self) {
+        if let Some(shared) = self.state.take() {
+            let mut state = shared.lock().unwrap();
+            state.running -= 1;
+            dispatch(&shared, &mut state);
+        }
+    }
+}
+
+impl ThumbnailQueue {
+    /// - limit 同时处理的任务数量
+    pub fn new(limit: usize) -> ThumbnailQueue {
+        ThumbnailQueue {
+            state: Arc::new(Mutex::new(QueueState {
+                limit: limit.max(1),
+                running: 0,
+                next_seq: 0,
+                pending: BTreeMap::new(),
+                index: HashMap::new(),
+                boosted: Vec::new(),
+            })),
+        }
+    }
+
+    /// 等待处理文件的名额
+    /// - path 要生成缩略图的文件
+    pub async fn acquire(&self, path: &Path) -> ThumbnailPermit {
+        // 等待任务只会在分配名额时移出队列，发送端不会提前释放
+        self.enqueue(path)
+            .await
+            .expect("缩略图任务在分配名额前被移出队列")
+    }
+
+    /// 加入队列，返回接收名额的通道
+    fn enqueue(&self, path: &Path) -> oneshot::Receiver<ThumbnailPermit> {
+        let (sender, receiver) = oneshot::channel();
+        let mut state = self.state.lock().unwrap();
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        let path = path.to_path_buf();
+        // 已在可见范围内的文件直接提前
+        let key = match state.boosted.iter().position(|x| *x == path) {
+            Some(position) => (Priority::Visible, position as u64, seq),
+            None => (Priority::Normal, 0, seq),
+        };
+        state.index.entry(path.clone()).or_default().push(key);
+        state.pending.insert(key, PendingJob { path, sender });
+        dispatch(&self.state, &mut state);
+        receiver
+    }
+
+    /// 把可见范围内的文件提前处理，返回提前的等待任务数量
+    ///
+    /// 上一次提前但不在本次范围内的任务恢复原来的顺序
+    /// - paths 可见范围内的文件【按显示顺序】
+    pub fn prioritize(&self, paths: &[PathBuf]) -> usize {
+        let mut state = self.state.lock().unwrap();
+        for path in mem::take(&mut state.boosted) {
+            requeue(&mut state, &path, |seq| (Priority::Normal, 0, seq));
+        }
+        let mut count = 0;
+        for (position, path) in paths.iter().enumerate() {
+            count += requeue(&mut state, path, |seq| {
+                (Priority::Visible, position as u64, seq)
+            });
+        }
+        state.boosted = paths.to_vec();
+        count
+    }
+
+    /// 等待中的任务数量
+    pub fn pending(&self) -> usize {
+        self.state.lock().unwrap().pending.len()
+    }
+}
+
+/// 修改文件的等待任务的排序键，返回修改的数量
+/// - key 根据加入队列的序号生成新的排序键
+fn requeue<F>(state: &mut QueueState, path: &Path, key: F) -> usize
+where
+    F: Fn(u64) -> JobKey,
+{
+    let Some(keys) = state.index.get_mut(path) else {
+        return 0;
+    };
+    let old_keys = mem::take(keys);
+    let new_keys: Vec<JobKey> = old_keys.iter().map(|x| key(x.2)).collect();
+    *keys = new_keys.clone();
+    for (old, new) in old_keys.iter().zip(&new_keys) {
+        if let Some(job) = state.pending.remove(old) {
+            state.pending.insert(*new, job);
+        }
+    }
+    new_keys.len()
+}
+
+/// 有空闲名额时分配给排在最前面的任务
+fn dispatch(shared: &Arc<Mutex<QueueState>>, state: &mut QueueState) {
+    while state.running < state.limit {
+        let Some((key, job)) = state.pending.pop_first() else {
+            return;
+        };
+        if let Some(keys) = state.index.get_mut(&job.path) {
+            keys.retain(|x| *x != key);
+            if keys.is_empty() {
+                state.index.remove(&job.path);
+            }
+        }
+        state.running += 1;
+        let permit = ThumbnailPermit {
+            state: Some(Arc::clone(shared)),
+        };
+        if let Err(mut permit) = job.sender.send(permit) {
+            // 等待的任务已取消，直接收回名额【此时持有锁，不能经过 Drop 释放】
+            permit.state = None;
+            state.running -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prioritize() {
+        let queue = ThumbnailQueue::new(1);
+        let mut a = queue.enqueue(Path::new("a"));
+        let mut b = queue.enqueue(Path::new("b"));
+        let mut c = queue.enqueue(Path::new("c"));
+        let mut d = queue.enqueue(Path::new("d"));
+        let permit = a.try_recv().unwrap();
+        assert!(b.try_recv().is_err());
+        assert_eq!(queue.pending(), 3);
+
+        assert_eq!(
+            queue.prioritize(&[PathBuf::from("d"), PathBuf::from("x")]),
+            1
+        );
+        drop(permit);
+        let permit = d.try_recv().unwrap();
+        assert!(b.try_recv().is_err());
+
+        // 不在新范围内的任务恢复原来的顺序
+        assert_eq!(queue.prioritize(&[PathBuf::from("c")]), 1);
+        assert_eq!(queue.prioritize(&[]), 0);
+        drop(permit);
+        let permit = b.try_recv().unwrap();
+        assert!(c.try_recv().is_err());
+        drop(permit);
+        assert!(c.try_recv().is_ok());
+        assert_eq!(queue.pending(), 0);
+    }
+
+    #[test]
+    fn test_cancelled_job() {
+        let queue = ThumbnailQueue::new(1);
+        let mut a = queue.enqueue(Path::new("a"));
+        let b = queue.enqueue(Path::new("b"));
+        let mut c = queue.enqueue(Path::new("c"));
+        let permit = a.try_recv().unwrap();
+        // 取消的任务不占用名额
+        drop(b);
+        drop(permit);
+        assert!(c.try_recv().is_ok());
+    }
+}
@@ -1,6 +1,8 @@
 extern crate chrono;
-use crate::structs::config::SYS_CONFIG;
-pub use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use crate::structs::config::sys_config;
+use crate::utils::exif_utils::tag::DEFAULT_OFFSET;
+pub use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use std::str::FromStr;
 
 pub struct TimeUtils;
 
@@ -22,7 +24,7 @@ impl TimeUtils {
 
     /// 将时间戳（秒）转换为 `String` 格式的日期
     pub fn timestamp_to_string(timestamp: i64, fmt: Option<&str>) -> String {
-        let time_fmt = SYS_CONFIG.time_basic_fmt.clone().unwrap();
+        let time_fmt = sys_config().time_basic_fmt.clone().unwrap();
         let naive = TimeUtils::timestamp_to_naive_date_time(timestamp);
         let fmt = fmt.unwrap_or(&*time_fmt);
         naive.format(fmt).to_string()
@@ -30,17 +32,29 @@ impl TimeUtils {
 
     /// 获取当前时间的 `String` 格式的日期
     pub fn current_datetime_string(fmt: Option<&str>) -> String {
-        let time_fmt = SYS_CONFIG.time_basic_fmt.clone().unwrap();
+        let time_fmt = sys_config().time_basic_fmt.clone().unwrap();
         let fmt = fmt.unwrap_or(&*time_fmt);
         Utc::now().format(fmt).to_string()
     }
 
     /// 将格式化日期字符串转换为 `NaiveDateTime`
     pub fn string_to_naive_date_time(date_str: &str, fmt: Option<&str>) -> Option<NaiveDateTime> {
-        let time_fmt = SYS_CONFIG.time_basic_fmt.clone().unwrap();
+        let time_fmt = sys_config().time_basic_fmt.clone().unwrap();
         let fmt = fmt.unwrap_or(&*time_fmt);
         NaiveDateTime::parse_from_str(date_str, fmt).ok()
     }
+
+    /// 将拍摄时间（秒）转换为拍摄地的当地时间
+    /// - offset 拍摄时的时区【如 `+08:00`，为空或无法解析时使用 EXIF 的默认时区】
+    pub fn to_local_date_time(timestamp: i64, offset: Option<&str>) -> NaiveDateTime {
+        let offset = offset
+            .and_then(|x| FixedOffset::from_str(x.trim()).ok())
+            .or_else(|| FixedOffset::from_str(DEFAULT_OFFSET).ok())
+            .expect("invalid default offset");
+        TimeUtils::timestamp_to_naive_date_time(timestamp)
+            .with_timezone(&offset)
+            .naive_local()
+    }
 }
 
 #[cfg(test)]
@@ -104,6 +118,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_to_local_date_time() {
+        // 2025-01-01 00:30:00 UTC
+        let timestamp = 1735691400;
+        let local = TimeUtils::to_local_date_time(timestamp, Some("-05:00"));
+        assert_eq!(local.format(TIME_BASIC_FMT).to_string(), "2024-12-31 19:30:00");
+        let local = TimeUtils::to_local_date_time(timestamp, None);
+        assert_eq!(local.format(TIME_BASIC_FMT).to_string(), "2025-01-01 08:30:00");
+        let local = TimeUtils::to_local_date_time(timestamp, Some("invalid"));
+        assert_eq!(local.format(TIME_BASIC_FMT).to_string(), "2025-01-01 08:30:00");
+    }
+
     #[test]
     fn test_string_to_naive_date_time_invalid() {
         let date_str = "invalid-date";
@@ -0,0 +1,69 @@
+/// 对 URL 路径做百分号编码【保留 `/` 和不需要编码的字符，其余按 UTF-8 字节编码】
+/// - path 相对路径，分隔符为 `/`
+pub fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~' | b'/') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// 解码地址参数中的值【`+` 解码为空格，无效的编码保持原样】
+pub fn decode_component(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = match bytes[index] {
+            b'%' => bytes.get(index + 1..index + 3).and_then(hex_byte),
+            _ => None,
+        };
+        match (escaped, bytes[index]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (None, b'+') => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            (None, byte) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// 两位十六进制数字
+fn hex_byte(hex: &[u8]) -> Option<u8> {
+    let digit = |x: u8| (x as char).to_digit(16);
+    Some((digit(hex[0])? * 16 + digit(hex[1])?) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_path() {
+        assert_eq!(encode_path("photos/0001_a.jpg"), "photos/0001_a.jpg");
+        assert_eq!(
+            encode_path("photos/0002_照片 #1?.jpg"),
+            "photos/0002_%E7%85%A7%E7%89%87%20%231%3F.jpg"
+        );
+    }
+
+    #[test]
+    fn test_decode_component() {
+        assert_eq!(decode_component("a%2Bb%3D%3D"), "a+b==");
+        assert_eq!(decode_component("%E7%85%A7+1"), "照 1");
+        assert_eq!(decode_component("100%25%"), "100%%");
+        assert_eq!(decode_component("%zz%+1"), "%zz% 1");
+    }
+}
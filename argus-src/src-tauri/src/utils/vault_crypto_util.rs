@@ -0,0 +1,277 @@
+use crate::i18n;
+use crate::i18n::msg;
+use anyhow::{anyhow, Result};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::io::{ErrorKind, Read, Write};
+use std::num::NonZeroU32;
+
+/// 加密文件头【标识格式版本，同时作为附加认证数据】
+const MAGIC: &[u8; 8] = b"ARGVLT01";
+
+/// 分块加密的文件头
+const STREAM_MAGIC: &[u8; 8] = b"ARGVLT02";
+
+/// 分块加密时每块的明文长度
+const STREAM_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// 分块加密的随机 Nonce 前缀长度【后 4 字节为块序号】
+const STREAM_PREFIX_LEN: usize = NONCE_LEN - 4;
+
+/// 保险箱密钥（AES-256-GCM）
+///
+/// 加密结果为 `文件头 + 随机 Nonce + 密文 + 认证标签`，每次加密都使用新的随机 Nonce
+#[derive(Clone)]
+pub struct VaultKey(LessSafeKey);
+
+impl VaultKey {
+    /// 由密码派生密钥（PBKDF2-HMAC-SHA256）
+    /// - passphrase 密码
+    /// - salt 随机盐
+    /// - iterations 迭代次数
+    pub fn derive(passphrase: &str, salt: &[u8], iterations: u32) -> Result<VaultKey> {
+        let iterations = NonZeroU32::new(iterations)
+            .ok_or_else(|| anyhow!(i18n::text(msg::VAULT_INVALID_ITERATIONS)))?;
+        let mut key = [0u8; 32];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            iterations,
+            salt,
+            passphrase.as_bytes(),
+            &mut key,
+        );
+        let unbound = UnboundKey::new(&AES_256_GCM, &key)
+            .map_err(|_| anyhow!(i18n::text(msg::VAULT_INVALID_KEY)));
+        key.fill(0);
+        Ok(VaultKey(LessSafeKey::new(unbound?)))
+    }
+
+    /// 使用随机生成的密钥【32 字节】
+    pub fn from_bytes(key: &[u8]) -> Result<VaultKey> {
+        let unbound = UnboundKey::new(&AES_256_GCM, key)
+            .map_err(|_| anyhow!(i18n::text(msg::VAULT_INVALID_KEY)))?;
+        Ok(VaultKey(LessSafeKey::new(unbound)))
+    }
+
+    /// 加密数据
+    pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let nonce: [u8; NONCE_LEN] = random_bytes::<NONCE_LEN>()?;
+        let header = MAGIC.len() + NONCE_LEN;
+        let mut buffer = Vec::with_capacity(header + data.len() + AES_256_GCM.tag_len());
+        buffer.extend_from_slice(MAGIC);
+        buffer.extend_from_slice(&nonce);
+        buffer.extend_from_slice(data);
+        let tag = self
+            .0
+            .seal_in_place_separate_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(MAGIC),
+                &mut buffer[header..],
+            )
+            .map_err(|_| anyhow!(i18n::text(msg::VAULT_ENCRYPT_FAILED)))?;
+        buffer.extend_from_slice(tag.as_ref());
+        Ok(buffer)
+    }
+
+    /// 解密数据【密钥错误或数据被修改时返回错误】
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let header = MAGIC.len() + NONCE_LEN;
+        if data.len() < header + AES_256_GCM.tag_len() || !data.starts_with(MAGIC) {
+            return Err(anyhow!(i18n::text(msg::VAULT_INVALID_FILE)));
+        }
+        let nonce = Nonce::try_assume_unique_for_key(&data[MAGIC.len()..header])
+            .map_err(|_| anyhow!(i18n::text(msg::VAULT_INVALID_FILE)))?;
+        let mut buffer = data[header..].to_vec();
+        let len = self
+            .0
+            .open_in_place(nonce, Aad::from(MAGIC), &mut buffer)
+            .map_err(|_| anyhow!(i18n::text(msg::VAULT_DECRYPT_FAILED)))?
+            .len();
+        buffer.truncate(len);
+        Ok(buffer)
+    }
+
+    /// 分块加密数据流，返回明文长度【大文件不需要整体读入内存】
+    ///
+    /// 加密结果为 `文件头 + 随机 Nonce 前缀 + 若干个(密文块 + 认证标签)`，每块的 Nonce 为前缀加块序号，
+    /// 最后一块（可以为空）的附加认证数据带结束标记，截断、调换顺序都会导致解密失败
+    /// - reader 明文
+    /// - writer 加密结果
+    pub fn encrypt_stream(&self, mut reader: impl Read, mut writer: impl Write) -> Result<u64> {
+        let prefix = random_bytes::<STREAM_PREFIX_LEN>()?;
+        writer.write_all(STREAM_MAGIC)?;
+        writer.write_all(&prefix)?;
+        let mut buffer = vec![0u8; STREAM_CHUNK_SIZE];
+        let mut total = 0u64;
+        for index in 0u32.. {
+            let len = read_full(&mut reader, &mut buffer)?;
+            let last = len < STREAM_CHUNK_SIZE;
+            let tag = self
+                .0
+                .seal_in_place_separate_tag(
+                    chunk_nonce(&prefix, index),
+                    chunk_aad(last),
+                    &mut buffer[..len],
+                )
+                .map_err(|_| anyhow!(i18n::text(msg::VAULT_ENCRYPT_FAILED)))?;
+            writer.write_all(&buffer[..len])?;
+            writer.write_all(tag.as_ref())?;
+            total += len as u64;
+            if last {
+                break;
+            }
+        }
+        writer.flush()?;
+        Ok(total)
+    }
+
+    /// 解密数据流，返回明文长度【兼容整体加密的旧文件】
+    ///
+    /// 每块通过认证后才写出，解密失败时已写出的内容不可信，调用方需要删除
+    /// - reader 加密数据
+    /// - writer 明文
+    pub fn decrypt_stream(&self, mut reader: impl Read, mut writer: impl Write) -> Result<u64> {
+        let mut magic = [0u8; 8];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|_| anyhow!(i18n::text(msg::VAULT_INVALID_FILE)))?;
+        if &magic == MAGIC {
+            let mut data = magic.to_vec();
+            reader.read_to_end(&mut data)?;
+            let plain = self.decrypt(&data)?;
+            writer.write_all(&plain)?;
+            writer.flush()?;
+            return Ok(plain.len() as u64);
+        }
+        if &magic != STREAM_MAGIC {
+            return Err(anyhow!(i18n::text(msg::VAULT_INVALID_FILE)));
+        }
+        let mut prefix = [0u8; STREAM_PREFIX_LEN];
+        reader
+            .read_exact(&mut prefix)
+            .map_err(|_| anyhow!(i18n::text(msg::VAULT_INVALID_FILE)))?;
+
+        let tag_len = AES_256_GCM.tag_len();
+        let mut buffer = vec![0u8; STREAM_CHUNK_SIZE + tag_len];
+        let mut total = 0u64;
+        for index in 0u32.. {
+            let len = read_full(&mut reader, &mut buffer)?;
+            if len < tag_len {
+                return Err(anyhow!(i18n::text(msg::VAULT_FILE_INCOMPLETE)));
+            }
+            let last = len < buffer.len();
+            let plain = self
+                .0
+                .open_in_place(
+                    chunk_nonce(&prefix, index),
+                    chunk_aad(last),
+                    &mut buffer[..len],
+                )
+                .map_err(|_| anyhow!(i18n::text(msg::VAULT_DECRYPT_FAILED)))?;
+            writer.write_all(plain)?;
+            total += plain.len() as u64;
+            if last {
+                break;
+            }
+        }
+        writer.flush()?;
+        Ok(total)
+    }
+}
+
+/// 分块加密中第 `index` 块的 Nonce
+fn chunk_nonce(prefix: &[u8; STREAM_PREFIX_LEN], index: u32) -> Nonce {
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce[..STREAM_PREFIX_LEN].copy_from_slice(prefix);
+    nonce[STREAM_PREFIX_LEN..].copy_from_slice(&index.to_be_bytes());
+    Nonce::assume_unique_for_key(nonce)
+}
+
+/// 分块加密的附加认证数据【文件头 + 是否为最后一块】
+fn chunk_aad(last: bool) -> Aad<[u8; 9]> {
+    let mut aad = [0u8; 9];
+    aad[..8].copy_from_slice(STREAM_MAGIC);
+    aad[8] = last as u8;
+    Aad::from(aad)
+}
+
+/// 读满缓冲区，返回读取的长度【小于缓冲区长度说明已读到末尾】
+fn read_full(reader: &mut impl Read, buffer: &mut [u8]) -> Result<usize> {
+    let mut len = 0;
+    while len < buffer.len() {
+        match reader.read(&mut buffer[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(len)
+}
+
+/// 生成安全随机数
+pub fn random_bytes<const N: usize>() -> Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| anyhow!(i18n::text(msg::VAULT_RANDOM_FAILED)))?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let salt = random_bytes::<16>().unwrap();
+        let key = VaultKey::derive("correct horse", &salt, 1000).unwrap();
+        let data = key.encrypt(b"jpeg data").unwrap();
+        assert!(!data.windows(9).any(|x| x == b"jpeg data"));
+        assert_eq!(key.decrypt(&data).unwrap(), b"jpeg data");
+
+        // 密码错误或数据被修改时无法解密
+        let wrong = VaultKey::derive("wrong horse", &salt, 1000).unwrap();
+        assert_eq!(
+            wrong.decrypt(&data).unwrap_err().to_string(),
+            i18n::text(msg::VAULT_DECRYPT_FAILED)
+        );
+        let mut tampered = data.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(key.decrypt(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_stream() {
+        let key = VaultKey::from_bytes(&random_bytes::<32>().unwrap()).unwrap();
+        for size in [0, 5, STREAM_CHUNK_SIZE, STREAM_CHUNK_SIZE * 2 + 5] {
+            let data: Vec<u8> = (0..size).map(|x| (x % 251) as u8).collect();
+            let mut encrypted = Vec::new();
+            assert_eq!(
+                key.encrypt_stream(&data[..], &mut encrypted).unwrap(),
+                size as u64
+            );
+            let mut decrypted = Vec::new();
+            assert_eq!(
+                key.decrypt_stream(&encrypted[..], &mut decrypted).unwrap(),
+                size as u64
+            );
+            assert_eq!(decrypted, data);
+
+            // 截断到块边界也能发现
+            let chunk = STREAM_CHUNK_SIZE + AES_256_GCM.tag_len();
+            let header = STREAM_MAGIC.len() + STREAM_PREFIX_LEN;
+            if encrypted.len() > header + chunk {
+                let truncated = &encrypted[..header + chunk];
+                assert!(key.decrypt_stream(truncated, std::io::sink()).is_err());
+            }
+        }
+
+        // 整体加密的旧文件仍可解密
+        let old = key.encrypt(b"jpeg data").unwrap();
+        let mut decrypted = Vec::new();
+        key.decrypt_stream(&old[..], &mut decrypted).unwrap();
+        assert_eq!(decrypted, b"jpeg data");
+    }
+}
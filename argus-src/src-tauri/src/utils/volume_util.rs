@@ -0,0 +1,273 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use sysinfo::{Disk, Disks};
+
+/// 网络文件系统类型
+const NETWORK_FILE_SYSTEMS: [&str; 8] = [
+    "nfs", "nfs4", "cifs", "smb", "smb2", "smb3", "smbfs", "afpfs",
+];
+
+/// 卷信息
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeInfo {
+    /// 卷唯一标识【Windows 为卷 GUID，Linux 为文件系统 UUID，网络共享为共享地址】
+    pub volume_id: String,
+    /// 当前挂载点（盘符）
+    pub mount_point: PathBuf,
+    /// 卷标
+    pub label: String,
+    /// 是否为可移动设备
+    pub is_removable: bool,
+    /// 是否为网络共享
+    pub is_network: bool,
+}
+
+/// 磁盘空间（字节）
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskSpace {
+    pub total: u64,
+    pub available: u64,
+}
+
+/// 获取当前所有已挂载的卷
+pub fn list_volumes() -> Vec<VolumeInfo> {
+    let disks = Disks::new_with_refreshed_list();
+    disks.list().iter().map(disk_to_volume).collect()
+}
+
+/// 获取路径所在的卷【路径不可访问时返回 None】
+pub fn volume_for_path<P: AsRef<Path>>(path: P) -> Option<VolumeInfo> {
+    let path = path.as_ref();
+    // UNC 路径直接使用共享地址作为标识
+    if let Some(share) = path.to_str().and_then(unc_share) {
+        if !path.exists() {
+            return None;
+        }
+        return Some(VolumeInfo {
+            volume_id: format!("net:{}", share),
+            mount_point: PathBuf::from(share_root(path.to_str().unwrap_or_default())),
+            label: share.clone(),
+            is_removable: false,
+            is_network: true,
+        });
+    }
+    if !path.exists() {
+        return None;
+    }
+    // 挂载点最长匹配
+    list_volumes()
+        .into_iter()
+        .filter(|v| path.starts_with(&v.mount_point))
+        .max_by_key(|v| v.mount_point.as_os_str().len())
+}
+
+/// 在当前挂载的卷中查找指定标识的卷
+pub fn find_volume(volume_id: &str) -> Option<VolumeInfo> {
+    list_volumes().into_iter().find(|v| v.volume_id == volume_id)
+}
+
+/// 获取路径所在磁盘的可用空间（字节）【找不到所在磁盘时返回 None】
+pub fn available_space<P: AsRef<Path>>(path: P) -> Option<u64> {
+    disk_space(path).map(|x| x.available)
+}
+
+/// 获取路径所在磁盘的总空间和可用空间（字节）【找不到所在磁盘时返回 None】
+///
+/// 相对路径先转换为绝对路径，否则无法与挂载点匹配
+pub fn disk_space<P: AsRef<Path>>(path: P) -> Option<DiskSpace> {
+    let path = std::path::absolute(path.as_ref()).ok()?;
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|d| path.starts_with(d.mount_point()))
+        .max_by_key(|d| d.mount_point().as_os_str().len())
+        .map(|d| DiskSpace {
+            total: d.total_space(),
+            available: d.available_space(),
+        })
+}
+
+/// 计算路径相对于挂载点的部分
+pub fn relative_to_mount<P: AsRef<Path>>(path: P, mount_point: &Path) -> Option<PathBuf> {
+    path.as_ref()
+        .strip_prefix(mount_point)
+        .ok()
+        .map(|p| p.to_path_buf())
+}
+
+/// 安全弹出卷【先由系统刷新缓存并卸载，之后才可以拔出】
+/// - mount_point 挂载点（盘符）
+pub fn eject_volume(mount_point: &Path) -> Result<()> {
+    let output = eject_command(mount_point)
+        .output()
+        .map_err(|e| anyhow!("弹出命令启动失败: {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "弹出 {} 失败: {}",
+            mount_point.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    // “弹出”菜单没有返回值，卷正在被使用时命令也会成功结束，需要确认卷已经移除
+    #[cfg(windows)]
+    wait_removed(mount_point)?;
+    Ok(())
+}
+
+/// 等待卷移除【超时表示弹出失败】
+#[cfg(windows)]
+fn wait_removed(mount_point: &Path) -> Result<()> {
+    use crate::constant::EJECT_CONFIRM_TIMEOUT_MS;
+    use std::time::{Duration, Instant};
+    let started = Instant::now();
+    while mount_point.exists() {
+        if started.elapsed() >= Duration::from_millis(EJECT_CONFIRM_TIMEOUT_MS) {
+            return Err(anyhow!("弹出 {} 失败: 卷正在被使用", mount_point.display()));
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+    Ok(())
+}
+
+/// Windows 下通过资源管理器的“弹出”菜单弹出
+#[cfg(windows)]
+fn eject_command(mount_point: &Path) -> Command {
+    let drive = mount_point
+        .to_string_lossy()
+        .trim_end_matches('\\')
+        .replace('\'', "''");
+    let mut cmd = Command::new("powershell");
+    cmd.args([
+        "-NoProfile",
+        "-Command",
+        &format!(
+            "(New-Object -ComObject Shell.Application).Namespace(17).ParseName('{}').InvokeVerb('Eject')",
+            drive
+        ),
+    ]);
+    cmd
+}
+
+#[cfg(target_os = "macos")]
+fn eject_command(mount_point: &Path) -> Command {
+    let mut cmd = Command::new("diskutil");
+    cmd.arg("eject").arg(mount_point);
+    cmd
+}
+
+/// Linux 下使用 gio 卸载并弹出（桌面环境自动挂载的卷不需要 root 权限）
+#[cfg(not(any(windows, target_os = "macos")))]
+fn eject_command(mount_point: &Path) -> Command {
+    let mut cmd = Command::new("gio");
+    cmd.args(["mount", "--eject"]).arg(mount_point);
+    cmd
+}
+
+/// 解析 UNC 路径中的共享地址【`\\server\share\...` -> `server/share`，统一小写】
+pub fn unc_share(path: &str) -> Option<String> {
+    let normalized = path.replace('\\', "/");
+    let rest = normalized.strip_prefix("//")?;
+    // 长路径形式 //?/UNC/server/share
+    let rest = match rest.strip_prefix("?/") {
+        Some(x) => x.strip_prefix("UNC/")?,
+        None => rest,
+    };
+    let mut parts = rest.split('/').filter(|x| !x.is_empty());
+    let server = parts.next()?;
+    let share = parts.next()?;
+    Some(format!("{}/{}", server, share).to_lowercase())
+}
+
+/// UNC 路径的共享根目录【保留原始大小写】
+fn share_root(path: &str) -> String {
+    let sep = if path.contains('\\') { '\\' } else { '/' };
+    let parts: Vec<&str> = path
+        .trim_start_matches(['\\', '/'])
+        .split(['\\', '/'])
+        .filter(|x| !x.is_empty())
+        .take(2)
+        .collect();
+    format!("{0}{0}{1}", sep, parts.join(&sep.to_string()))
+}
+
+fn disk_to_volume(disk: &Disk) -> VolumeInfo {
+    let name = disk.name().to_string_lossy().to_string();
+    let file_system = disk.file_system().to_string_lossy().to_lowercase();
+    let is_network = NETWORK_FILE_SYSTEMS.contains(&file_system.as_str());
+    let volume_id = if is_network {
+        // 网络挂载的名称为共享地址，如 //nas/photos、nas:/export
+        format!(
+            "net:{}",
+            unc_share(&name).unwrap_or_else(|| name.to_lowercase())
+        )
+    } else {
+        native_volume_id(disk).unwrap_or_else(|| {
+            format!("disk:{}:{}:{}", name, file_system, disk.total_space())
+        })
+    };
+    VolumeInfo {
+        volume_id,
+        mount_point: disk.mount_point().to_path_buf(),
+        label: name,
+        is_removable: disk.is_removable(),
+        is_network,
+    }
+}
+
+/// Windows 下读取卷 GUID 路径
+#[cfg(windows)]
+fn native_volume_id(disk: &Disk) -> Option<String> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetVolumeNameForVolumeMountPointW;
+
+    // 挂载点必须以反斜杠结尾
+    let mut mount: Vec<u16> = disk.mount_point().as_os_str().encode_wide().collect();
+    if mount.last() != Some(&(b'\\' as u16)) {
+        mount.push(b'\\' as u16);
+    }
+    mount.push(0);
+    let mut buffer = [0u16; 64];
+    let ok = unsafe {
+        GetVolumeNameForVolumeMountPointW(mount.as_ptr(), buffer.as_mut_ptr(), buffer.len() as u32)
+    };
+    if ok == 0 {
+        return None;
+    }
+    let len = buffer.iter().position(|x| *x == 0).unwrap_or(buffer.len());
+    Some(format!("guid:{}", String::from_utf16_lossy(&buffer[..len])))
+}
+
+/// Linux 下通过 `/dev/disk/by-uuid` 读取文件系统 UUID
+#[cfg(target_os = "linux")]
+fn native_volume_id(disk: &Disk) -> Option<String> {
+    let device = std::fs::canonicalize(disk.name()).ok()?;
+    std::fs::read_dir("/dev/disk/by-uuid")
+        .ok()?
+        .filter_map(|e| e.ok())
+        .find(|e| std::fs::canonicalize(e.path()).ok().as_ref() == Some(&device))
+        .map(|e| format!("uuid:{}", e.file_name().to_string_lossy()))
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn native_volume_id(_disk: &Disk) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unc_share() {
+        assert_eq!(unc_share(r"\\NAS\Photos\2024").as_deref(), Some("nas/photos"));
+        assert_eq!(unc_share(r"\\?\UNC\nas\photos\a.jpg").as_deref(), Some("nas/photos"));
+        assert_eq!(unc_share("//nas/photos").as_deref(), Some("nas/photos"));
+        assert_eq!(unc_share(r"D:\photos"), None);
+        assert_eq!(unc_share(r"\\nas"), None);
+        assert_eq!(share_root(r"\\NAS\Photos\2024"), r"\\NAS\Photos");
+    }
+}
@@ -0,0 +1,183 @@
+//! WebP 容器（RIFF）中的元数据
+//!
+//! 编码器输出的是只包含图像数据的简单格式，ICC、EXIF、XMP 需要使用扩展格式（VP8X）保存：
+//! `VP8X`、`ICCP`、图像数据（`ALPH`、`VP8 ` 或 `VP8L`）、`EXIF`、`XMP `
+
+use anyhow::{anyhow, Result};
+
+const FLAG_ICC: u8 = 0x20;
+const FLAG_ALPHA: u8 = 0x10;
+const FLAG_EXIF: u8 = 0x08;
+const FLAG_XMP: u8 = 0x04;
+
+/// WebP 中的元数据
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WebpMetadata {
+    /// ICC 配置文件
+    pub icc: Option<Vec<u8>>,
+    /// EXIF（TIFF 结构，不含 `Exif\0\0` 标识）
+    pub exif: Option<Vec<u8>>,
+    pub xmp: Option<Vec<u8>>,
+}
+
+impl WebpMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.icc.is_none() && self.exif.is_none() && self.xmp.is_none()
+    }
+}
+
+/// 把元数据写入编码器输出的 WebP【没有元数据时原样返回】
+/// - webp 编码后的 WebP 文件内容
+/// - width、height 图像尺寸
+/// - has_alpha 是否包含透明通道
+pub fn embed_metadata(
+    webp: &[u8],
+    width: u32,
+    height: u32,
+    has_alpha: bool,
+    metadata: &WebpMetadata,
+) -> Result<Vec<u8>> {
+    if metadata.is_empty() {
+        return Ok(webp.to_vec());
+    }
+    if width == 0 || height == 0 || width > 1 << 24 || height > 1 << 24 {
+        return Err(anyhow!("WebP 尺寸 {}x{} 无效!", width, height));
+    }
+    let image: Vec<(&[u8], &[u8])> = chunks(webp)?
+        .into_iter()
+        .filter(|(id, _)| matches!(&id[..], b"ALPH" | b"VP8 " | b"VP8L"))
+        .collect();
+    if image.is_empty() {
+        return Err(anyhow!("WebP 中没有图像数据!"));
+    }
+
+    let mut flags = 0;
+    if metadata.icc.is_some() {
+        flags |= FLAG_ICC;
+    }
+    if has_alpha {
+        flags |= FLAG_ALPHA;
+    }
+    if metadata.exif.is_some() {
+        flags |= FLAG_EXIF;
+    }
+    if metadata.xmp.is_some() {
+        flags |= FLAG_XMP;
+    }
+    let mut vp8x = vec![flags, 0, 0, 0];
+    vp8x.extend_from_slice(&(width - 1).to_le_bytes()[..3]);
+    vp8x.extend_from_slice(&(height - 1).to_le_bytes()[..3]);
+
+    let mut body = b"WEBP".to_vec();
+    write_chunk(&mut body, b"VP8X", &vp8x);
+    if let Some(icc) = &metadata.icc {
+        write_chunk(&mut body, b"ICCP", icc);
+    }
+    for (id, data) in image {
+        write_chunk(&mut body, id, data);
+    }
+    if let Some(exif) = &metadata.exif {
+        write_chunk(&mut body, b"EXIF", exif);
+    }
+    if let Some(xmp) = &metadata.xmp {
+        write_chunk(&mut body, b"XMP ", xmp);
+    }
+    let mut result = b"RIFF".to_vec();
+    result.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    result.extend(body);
+    Ok(result)
+}
+
+/// 读取 WebP 中的元数据
+pub fn read_metadata(webp: &[u8]) -> Result<WebpMetadata> {
+    let mut metadata = WebpMetadata::default();
+    for (id, data) in chunks(webp)? {
+        match id {
+            b"ICCP" => metadata.icc = Some(data.to_vec()),
+            b"EXIF" => metadata.exif = Some(data.to_vec()),
+            b"XMP " => metadata.xmp = Some(data.to_vec()),
+            _ => {}
+        }
+    }
+    Ok(metadata)
+}
+
+/// 拆分 RIFF 中的数据块（类型、内容）
+fn chunks(webp: &[u8]) -> Result<Vec<(&[u8], &[u8])>> {
+    if webp.len() < 12 || &webp[..4] != b"RIFF" || &webp[8..12] != b"WEBP" {
+        return Err(anyhow!("不是 WebP 文件"));
+    }
+    let riff_end = (u32::from_le_bytes([webp[4], webp[5], webp[6], webp[7]]) as usize)
+        .saturating_add(8)
+        .min(webp.len());
+    let mut result = Vec::new();
+    let mut pos = 12;
+    while pos + 8 <= riff_end {
+        let id = &webp[pos..pos + 4];
+        let size = u32::from_le_bytes([webp[pos + 4], webp[pos + 5], webp[pos + 6], webp[pos + 7]])
+            as usize;
+        let start = pos + 8;
+        let end = start
+            .checked_add(size)
+            .filter(|x| *x <= riff_end)
+            .ok_or_else(|| anyhow!("WebP 数据块长度错误"))?;
+        result.push((id, &webp[start..end]));
+        // 数据块补齐到偶数长度
+        pos = end + (size & 1);
+    }
+    Ok(result)
+}
+
+fn write_chunk(out: &mut Vec<u8>, id: &[u8], data: &[u8]) {
+    out.extend_from_slice(id);
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(data);
+    if data.len() % 2 == 1 {
+        out.push(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 只包含 VP8L 数据块的简单格式
+    fn simple_webp(image: &[u8]) -> Vec<u8> {
+        let mut body = b"WEBP".to_vec();
+        write_chunk(&mut body, b"VP8L", image);
+        let mut data = b"RIFF".to_vec();
+        data.extend((body.len() as u32).to_le_bytes());
+        data.extend(body);
+        data
+    }
+
+    #[test]
+    fn test_embed_metadata() {
+        let webp = simple_webp(&[0x2F, 1, 2]);
+        let metadata = WebpMetadata {
+            icc: Some(b"icc".to_vec()),
+            exif: Some(b"MM\0*".to_vec()),
+            xmp: Some(b"<x:xmpmeta/>".to_vec()),
+        };
+        let data = embed_metadata(&webp, 640, 480, true, &metadata).unwrap();
+        assert_eq!(read_metadata(&data).unwrap(), metadata);
+
+        let ids: Vec<&[u8]> = chunks(&data).unwrap().into_iter().map(|x| x.0).collect();
+        assert_eq!(ids, vec![&b"VP8X"[..], b"ICCP", b"VP8L", b"EXIF", b"XMP "]);
+        let (_, vp8x) = chunks(&data).unwrap()[0];
+        assert_eq!(vp8x[0], FLAG_ICC | FLAG_ALPHA | FLAG_EXIF | FLAG_XMP);
+        assert_eq!(&vp8x[4..7], &639u32.to_le_bytes()[..3]);
+        assert_eq!(&vp8x[7..10], &479u32.to_le_bytes()[..3]);
+        assert_eq!(
+            u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize,
+            data.len() - 8
+        );
+
+        // 没有元数据时不修改
+        assert_eq!(
+            embed_metadata(&webp, 640, 480, false, &WebpMetadata::default()).unwrap(),
+            webp
+        );
+        assert!(read_metadata(b"RIFF\0\0\0\0WAVE").is_err());
+    }
+}
@@ -0,0 +1,271 @@
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, Local, Timelike};
+use crc32fast::Hasher;
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// 本地文件头标记
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+/// 中央目录标记
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+/// 中央目录结束标记
+const END_OF_CENTRAL_DIR_SIGNATURE: u32 = 0x0605_4b50;
+/// ZIP64 中央目录结束标记
+const ZIP64_END_OF_CENTRAL_DIR_SIGNATURE: u32 = 0x0606_4b50;
+/// ZIP64 中央目录结束定位标记
+const ZIP64_END_LOCATOR_SIGNATURE: u32 = 0x0706_4b50;
+/// ZIP64 扩展字段 ID
+const ZIP64_EXTRA_ID: u16 = 0x0001;
+/// 解压需要的版本（2.0）
+const ZIP_VERSION: u16 = 20;
+/// 使用 ZIP64 时解压需要的版本（4.5）
+const ZIP64_VERSION: u16 = 45;
+/// 文件名使用 UTF-8 编码
+const FLAG_UTF8: u16 = 0x0800;
+/// 不压缩
+const METHOD_STORED: u16 = 0;
+/// 超过时需要使用 ZIP64 的 4 字节字段的值
+const ZIP64_U32_LIMIT: u64 = 0xFFFF_FFFF;
+/// 超过时需要使用 ZIP64 的文件数量
+const ZIP64_COUNT_LIMIT: u64 = 0xFFFF;
+/// 本地文件头中 CRC 字段的位置
+const LOCAL_HEADER_CRC_OFFSET: u64 = 14;
+
+/// 中央目录中的一项
+struct CentralEntry {
+    name: Vec<u8>,
+    crc: u32,
+    size: u64,
+    offset: u64,
+}
+
+impl CentralEntry {
+    /// 中央目录中需要写入 ZIP64 扩展字段的值（原大小、压缩后大小、本地文件头位置）
+    fn zip64_values(&self) -> Vec<u64> {
+        let mut values = Vec::new();
+        if self.size >= ZIP64_U32_LIMIT {
+            values.extend([self.size, self.size]);
+        }
+        if self.offset >= ZIP64_U32_LIMIT {
+            values.push(self.offset);
+        }
+        values
+    }
+}
+
+/// 把文件夹打包为 zip（不压缩，JPEG 等已压缩的文件再压缩几乎没有收益）
+///
+/// 压缩包内使用相对文件夹的路径，分隔符统一为 `/`。文件逐块读取写入，不整个读入内存；
+/// 单个文件或压缩包超过 4 GB、文件数量超过 65535 时使用 ZIP64
+/// - src_dir 要打包的文件夹
+/// - dest 压缩包路径
+pub fn zip_dir(src_dir: &Path, dest: &Path) -> Result<u64> {
+    let (time, date) = dos_datetime();
+    let mut writer = BufWriter::new(File::create(dest)?);
+    let mut entries = Vec::new();
+    let mut offset: u64 = 0;
+
+    for entry in WalkDir::new(src_dir).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(src_dir)?;
+        let name = relative
+            .components()
+            .map(|x| x.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+            .into_bytes();
+        let mut file = File::open(entry.path())?;
+        let size = file.metadata()?.len();
+        let zip64 = size >= ZIP64_U32_LIMIT;
+
+        // CRC 在写入文件内容时计算，写完后回填
+        writer.write_all(&LOCAL_HEADER_SIGNATURE.to_le_bytes())?;
+        writer.write_all(&version_needed(zip64).to_le_bytes())?;
+        writer.write_all(&FLAG_UTF8.to_le_bytes())?;
+        writer.write_all(&METHOD_STORED.to_le_bytes())?;
+        writer.write_all(&time.to_le_bytes())?;
+        writer.write_all(&date.to_le_bytes())?;
+        writer.write_all(&0u32.to_le_bytes())?;
+        writer.write_all(&clamp_u32(size).to_le_bytes())?;
+        writer.write_all(&clamp_u32(size).to_le_bytes())?;
+        writer.write_all(&u16_len(name.len())?.to_le_bytes())?;
+        writer.write_all(&(if zip64 { 20u16 } else { 0u16 }).to_le_bytes())?;
+        writer.write_all(&name)?;
+        if zip64 {
+            write_zip64_extra(&mut writer, &[size, size])?;
+        }
+        let data_offset = offset + 30 + name.len() as u64 + if zip64 { 20 } else { 0 };
+
+        let crc = copy_with_crc(&mut file, &mut writer, size)
+            .map_err(|e| anyhow!("{}: {}", entry.path().display(), e))?;
+        writer.seek(SeekFrom::Start(offset + LOCAL_HEADER_CRC_OFFSET))?;
+        writer.write_all(&crc.to_le_bytes())?;
+        writer.seek(SeekFrom::Start(data_offset + size))?;
+
+        entries.push(CentralEntry {
+            name,
+            crc,
+            size,
+            offset,
+        });
+        offset = data_offset + size;
+    }
+
+    let central_offset = offset;
+    for item in &entries {
+        let zip64_values = item.zip64_values();
+        let extra_len = if zip64_values.is_empty() {
+            0
+        } else {
+            4 + zip64_values.len() as u16 * 8
+        };
+        writer.write_all(&CENTRAL_HEADER_SIGNATURE.to_le_bytes())?;
+        writer.write_all(&ZIP64_VERSION.to_le_bytes())?;
+        writer.write_all(&version_needed(!zip64_values.is_empty()).to_le_bytes())?;
+        writer.write_all(&FLAG_UTF8.to_le_bytes())?;
+        writer.write_all(&METHOD_STORED.to_le_bytes())?;
+        writer.write_all(&time.to_le_bytes())?;
+        writer.write_all(&date.to_le_bytes())?;
+        writer.write_all(&item.crc.to_le_bytes())?;
+        writer.write_all(&clamp_u32(item.size).to_le_bytes())?;
+        writer.write_all(&clamp_u32(item.size).to_le_bytes())?;
+        writer.write_all(&u16_len(item.name.len())?.to_le_bytes())?;
+        writer.write_all(&extra_len.to_le_bytes())?;
+        // 注释长度，磁盘号，内部、外部属性
+        writer.write_all(&[0u8; 10])?;
+        writer.write_all(&clamp_u32(item.offset).to_le_bytes())?;
+        writer.write_all(&item.name)?;
+        if !zip64_values.is_empty() {
+            write_zip64_extra(&mut writer, &zip64_values)?;
+        }
+        offset += 46 + item.name.len() as u64 + extra_len as u64;
+    }
+
+    let count = entries.len() as u64;
+    let central_size = offset - central_offset;
+    if count >= ZIP64_COUNT_LIMIT
+        || central_size >= ZIP64_U32_LIMIT
+        || central_offset >= ZIP64_U32_LIMIT
+    {
+        let zip64_end_offset = offset;
+        writer.write_all(&ZIP64_END_OF_CENTRAL_DIR_SIGNATURE.to_le_bytes())?;
+        // 记录剩余部分的大小
+        writer.write_all(&44u64.to_le_bytes())?;
+        writer.write_all(&ZIP64_VERSION.to_le_bytes())?;
+        writer.write_all(&ZIP64_VERSION.to_le_bytes())?;
+        writer.write_all(&0u32.to_le_bytes())?;
+        writer.write_all(&0u32.to_le_bytes())?;
+        writer.write_all(&count.to_le_bytes())?;
+        writer.write_all(&count.to_le_bytes())?;
+        writer.write_all(&central_size.to_le_bytes())?;
+        writer.write_all(&central_offset.to_le_bytes())?;
+        writer.write_all(&ZIP64_END_LOCATOR_SIGNATURE.to_le_bytes())?;
+        writer.write_all(&0u32.to_le_bytes())?;
+        writer.write_all(&zip64_end_offset.to_le_bytes())?;
+        writer.write_all(&1u32.to_le_bytes())?;
+        offset += 56 + 20;
+    }
+
+    let count = count.min(ZIP64_COUNT_LIMIT) as u16;
+    writer.write_all(&END_OF_CENTRAL_DIR_SIGNATURE.to_le_bytes())?;
+    writer.write_all(&0u16.to_le_bytes())?;
+    writer.write_all(&0u16.to_le_bytes())?;
+    writer.write_all(&count.to_le_bytes())?;
+    writer.write_all(&count.to_le_bytes())?;
+    writer.write_all(&clamp_u32(central_size).to_le_bytes())?;
+    writer.write_all(&clamp_u32(central_offset).to_le_bytes())?;
+    writer.write_all(&0u16.to_le_bytes())?;
+    writer.flush()?;
+    Ok(offset + 22)
+}
+
+/// 逐块复制文件内容并计算 CRC32【实际大小与 `size` 不同时返回错误】
+fn copy_with_crc(reader: &mut impl Read, writer: &mut impl Write, size: u64) -> Result<u32> {
+    let mut hasher = Hasher::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut copied: u64 = 0;
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        writer.write_all(&buffer[..read])?;
+        copied += read as u64;
+    }
+    if copied != size {
+        return Err(anyhow!("打包过程中文件大小发生变化"));
+    }
+    Ok(hasher.finalize())
+}
+
+/// 写入 ZIP64 扩展字段
+fn write_zip64_extra(writer: &mut impl Write, values: &[u64]) -> Result<()> {
+    writer.write_all(&ZIP64_EXTRA_ID.to_le_bytes())?;
+    writer.write_all(&(values.len() as u16 * 8).to_le_bytes())?;
+    for value in values {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn version_needed(zip64: bool) -> u16 {
+    if zip64 {
+        ZIP64_VERSION
+    } else {
+        ZIP_VERSION
+    }
+}
+
+/// 4 字节字段的值【超出时写入 0xFFFFFFFF，实际值在 ZIP64 扩展字段中】
+fn clamp_u32(value: u64) -> u32 {
+    value.min(ZIP64_U32_LIMIT) as u32
+}
+
+fn u16_len(len: usize) -> Result<u16> {
+    u16::try_from(len).map_err(|_| anyhow!("压缩包中的文件名过长!"))
+}
+
+/// 当前本地时间的 DOS 格式（时间, 日期）
+fn dos_datetime() -> (u16, u16) {
+    let now = Local::now();
+    let time = (now.hour() << 11) | (now.minute() << 5) | (now.second() / 2);
+    let date = ((now.year().clamp(1980, 2107) as u32 - 1980) << 9) | (now.month() << 5) | now.day();
+    (time as u16, date as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_zip_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("bundle");
+        fs::create_dir_all(src.join("photos")).unwrap();
+        fs::write(src.join("index.html"), b"<html></html>").unwrap();
+        fs::write(src.join("photos").join("照片.jpg"), b"jpeg").unwrap();
+
+        let dest = dir.path().join("bundle.zip");
+        let size = zip_dir(&src, &dest).unwrap();
+        let data = fs::read(&dest).unwrap();
+        assert_eq!(data.len() as u64, size);
+        assert_eq!(&data[..4], &LOCAL_HEADER_SIGNATURE.to_le_bytes());
+        // 中央目录结束记录中的文件数量
+        let end = &data[data.len() - 22..];
+        assert_eq!(&end[..4], &END_OF_CENTRAL_DIR_SIGNATURE.to_le_bytes());
+        assert_eq!(u16::from_le_bytes([end[10], end[11]]), 2);
+        assert!(data
+            .windows("photos/照片.jpg".len())
+            .any(|x| x == "photos/照片.jpg".as_bytes()));
+        // 回填的 CRC 与文件内容一致
+        let crc = crc32fast::hash(b"<html></html>");
+        assert_eq!(&data[14..18], &crc.to_le_bytes());
+    }
+}
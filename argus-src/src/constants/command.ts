@@ -59,3 +59,375 @@ export const getAllImgsCommand = 'get_all_imgs'
  * 添加图像检索任务
  */
 export const addPhotoRetrieveTaskCommand = 'add_photo_retrieve_task'
+/**
+ * 获取相册、标签树
+ */
+export const getOrganizationTreeCommand = 'get_organization_tree'
+/**
+ * 导出相册、标签树【json、opml】
+ */
+export const exportOrganizationCommand = 'export_organization'
+/**
+ * 导入相册、标签树
+ */
+export const importOrganizationCommand = 'import_organization'
+/**
+ * 导出照片元数据（json、csv）
+ */
+export const exportMetadataCommand = 'export_metadata'
+/**
+ * 导入照片元数据（按文件 hash 合并）
+ */
+export const importMetadataCommand = 'import_metadata'
+/**
+ * 从 Apple 照片、digiKam 导入相册、关键字、评分和人物
+ */
+export const importExternalLibraryCommand = 'import_external_library'
+/**
+ * 校验缩略图缓存，重新生成损坏的缩略图
+ */
+export const verifyThumbnailCacheCommand = 'verify_thumbnail_cache'
+/**
+ * 获取后端消息目录（消息 ID -> 单复数模板）
+ */
+export const getMessageCatalogCommand = 'get_message_catalog'
+/**
+ * 切换后端消息语言
+ */
+export const setMessageLocaleCommand = 'set_message_locale'
+/**
+ * 获取扫描报告（最慢的文件及文件夹）
+ */
+export const getScanReportCommand = 'get_scan_report'
+/**
+ * 获取当前（或最近一次）扫描的吞吐量统计
+ */
+export const getScanMetricsCommand = 'get_scan_metrics'
+/**
+ * 获取扫描任务状态（前端刷新后恢复进度）
+ */
+export const getTaskStatusCommand = 'get_task_status'
+/**
+ * 取消扫描任务（只取消指定任务）
+ */
+export const cancelScanCommand = 'cancel_scan'
+/**
+ * 检查图像存储路径所在的卷（离线标记、盘符变化后自动修正路径）
+ */
+export const refreshStorageVolumesCommand = 'refresh_storage_volumes'
+/**
+ * 获取打开照片各阶段耗时统计（p50 / p95）
+ */
+export const getLatencyReportCommand = 'get_latency_report'
+/**
+ * 上报获取缩略图的往返耗时
+ */
+export const recordThumbnailRoundTripCommand = 'record_thumbnail_round_trip'
+/**
+ * 修改照片评分（可撤销）
+ */
+export const setPhotoRatingCommand = 'set_photo_rating'
+/**
+ * 平移照片拍摄时间（可撤销）
+ */
+export const shiftPhotoTimeCommand = 'shift_photo_time'
+/**
+ * 修改照片位置（可撤销）
+ */
+export const setPhotoGpsCommand = 'set_photo_gps'
+/**
+ * 替换照片标签（可撤销）
+ */
+export const setPhotoTagsCommand = 'set_photo_tags'
+/**
+ * 修改照片说明（可撤销，可按设置写回文件）
+ */
+export const setPhotoCaptionCommand = 'set_photo_caption'
+/**
+ * 修改照片备注
+ */
+export const setPhotoNotesCommand = 'set_photo_notes'
+/**
+ * 撤销最后一次元数据编辑
+ */
+export const undoLastEditCommand = 'undo_last_edit'
+/**
+ * 重做被撤销的元数据编辑
+ */
+export const redoEditCommand = 'redo_edit'
+/**
+ * 备份数据库（可选包含配置文件）
+ */
+export const backupLibraryCommand = 'backup_library'
+/**
+ * 从备份恢复数据库（恢复后校验完整性）
+ */
+export const restoreLibraryCommand = 'restore_library'
+/**
+ * 搜索照片（文件名、备注、标签、相机、地点全文搜索）
+ */
+export const searchPhotosCommand = 'search_photos'
+/**
+ * 自动堆叠编辑副本（文件名后缀与拍摄时间匹配原图）
+ */
+export const autoStackPhotosCommand = 'auto_stack_photos'
+/**
+ * 把多张照片堆叠为同一照片的不同版本
+ */
+export const stackPhotosCommand = 'stack_photos'
+/**
+ * 把照片移出所在的版本堆叠
+ */
+export const unstackPhotosCommand = 'unstack_photos'
+/**
+ * 设置版本堆叠在网格中显示的版本
+ */
+export const setStackCoverCommand = 'set_stack_cover'
+/**
+ * 获取照片所在堆叠中的所有版本
+ */
+export const getStackPhotosCommand = 'get_stack_photos'
+/**
+ * 重新扫描文件夹（dryRun 为 true 时只返回新增、删除、移动的差异）
+ */
+export const rescanFolderCommand = 'rescan_folder'
+/**
+ * 移动照片到指定文件夹（同时更新数据库中的路径）
+ */
+export const movePhotosCommand = 'move_photos'
+/**
+ * 重命名照片（同时更新数据库中的路径）
+ */
+export const renamePhotoCommand = 'rename_photo'
+/**
+ * 获取最近一次照片文件完整性校验的报告
+ */
+export const getIntegrityReportCommand = 'get_integrity_report'
+/**
+ * 获取所有照片库
+ */
+export const listLibrariesCommand = 'list_libraries'
+/**
+ * 新建照片库（独立的数据库、缩略图缓存和配置）
+ */
+export const createLibraryCommand = 'create_library'
+/**
+ * 打开已有的照片库并切换
+ */
+export const openLibraryCommand = 'open_library'
+/**
+ * 切换当前照片库
+ */
+export const switchLibraryCommand = 'switch_library'
+/**
+ * 重新加载配置文件（无需重启）
+ */
+export const reloadSettingsCommand = 'reload_settings'
+/**
+ * 查询最近的日志（按级别、模块、文本过滤）
+ */
+export const getRecentLogsCommand = 'get_recent_logs'
+/**
+ * 调整日志级别（可按模块设置）
+ */
+export const setLogLevelCommand = 'set_log_level'
+/**
+ * 获取当前的日志级别设置
+ */
+export const getLogLevelsCommand = 'get_log_levels'
+/**
+ * 获取局域网 HTTP 服务状态（端口、令牌）
+ */
+export const getHttpServerStatusCommand = 'get_http_server_status'
+/**
+ * 把照片生成幻灯片视频（MP4），返回任务 ID
+ */
+export const createSlideshowCommand = 'create_slideshow'
+/**
+ * 取消幻灯片视频任务
+ */
+export const cancelSlideshowCommand = 'cancel_slideshow'
+/**
+ * 导出照片到指定文件夹（可添加文字或 logo 水印）
+ */
+export const exportPhotosCommand = 'export_photos'
+/**
+ * 旋转照片（JPEG 无损旋转）
+ */
+export const rotatePhotoCommand = 'rotate_photo'
+/**
+ * 翻转照片（JPEG 无损翻转）
+ */
+export const flipPhotoCommand = 'flip_photo'
+/**
+ * 获取所有元数据预设
+ */
+export const listMetadataPresetsCommand = 'list_metadata_presets'
+/**
+ * 新建或修改元数据预设
+ */
+export const saveMetadataPresetCommand = 'save_metadata_preset'
+/**
+ * 删除元数据预设
+ */
+export const deleteMetadataPresetCommand = 'delete_metadata_preset'
+/**
+ * 为照片应用元数据预设（作者、版权、署名）
+ */
+export const applyMetadataPresetCommand = 'apply_metadata_preset'
+/**
+ * 获取所有隐私区域
+ */
+export const listPrivacyZonesCommand = 'list_privacy_zones'
+/**
+ * 新建或修改隐私区域
+ */
+export const savePrivacyZoneCommand = 'save_privacy_zone'
+/**
+ * 删除隐私区域
+ */
+export const deletePrivacyZoneCommand = 'delete_privacy_zone'
+/**
+ * 删除照片位置（数据库和导出的副本）
+ */
+export const stripGpsCommand = 'strip_gps'
+/**
+ * 按拍摄位置重新聚类地点
+ */
+export const refreshPlacesCommand = 'refresh_places'
+/**
+ * 获取所有地点
+ */
+export const listPlacesCommand = 'list_places'
+/**
+ * 获取地点中的照片
+ */
+export const getPlacePhotosCommand = 'get_place_photos'
+/**
+ * 检测全景和包围曝光序列
+ */
+export const detectPhotoSequencesCommand = 'detect_photo_sequences'
+/**
+ * 获取连拍序列
+ */
+export const listPhotoSequencesCommand = 'list_photo_sequences'
+/**
+ * 获取序列中的照片
+ */
+export const getSequencePhotosCommand = 'get_sequence_photos'
+/**
+ * 查找插入的存储卡
+ */
+export const listCameraCardsCommand = 'list_camera_cards'
+/**
+ * 列出存储卡 DCIM 文件夹中的文件
+ */
+export const listCardFilesCommand = 'list_card_files'
+/**
+ * 从存储卡导入文件并校验
+ */
+export const importFromCardCommand = 'import_from_card'
+/**
+ * 生成分享包（缩小后的照片和离线相册网页）
+ */
+export const createShareBundleCommand = 'create_share_bundle'
+/**
+ * 获取照片的备注和活动记录
+ */
+export const getPhotoActivityCommand = 'get_photo_activity'
+/**
+ * 查找内容完全相同的图片文件
+ */
+export const findExactDuplicatesCommand = 'find_exact_duplicates'
+/**
+ * 把重复文件替换为硬链接或 reflink
+ */
+export const reclaimDuplicatesCommand = 'reclaim_duplicates'
+/**
+ * 按撤销清单恢复被替换的重复文件
+ */
+export const undoReclaimCommand = 'undo_reclaim'
+/**
+ * 获取还可以撤销的重复文件替换清单
+ */
+export const listReclaimManifestsCommand = 'list_reclaim_manifests'
+/**
+ * 获取保险箱状态
+ */
+export const getVaultStatusCommand = 'get_vault_status'
+/**
+ * 创建保险箱并解锁
+ */
+export const createVaultCommand = 'create_vault'
+/**
+ * 解锁保险箱
+ */
+export const unlockVaultCommand = 'unlock_vault'
+/**
+ * 锁定保险箱
+ */
+export const lockVaultCommand = 'lock_vault'
+/**
+ * 获取保险箱中的照片
+ */
+export const listVaultItemsCommand = 'list_vault_items'
+/**
+ * 解密查看保险箱中的原图
+ */
+export const openVaultItemCommand = 'open_vault_item'
+/**
+ * 把照片移入保险箱
+ */
+export const moveToVaultCommand = 'move_to_vault'
+/**
+ * 把照片移出保险箱
+ */
+export const restoreFromVaultCommand = 'restore_from_vault'
+/**
+ * 导出 SHA256SUMS 校验清单
+ */
+export const exportChecksumManifestCommand = 'export_checksum_manifest'
+/**
+ * 校验 SHA256SUMS 清单中的文件
+ */
+export const verifyManifestCommand = 'verify_manifest'
+/**
+ * 获取定时维护计划的状态
+ */
+export const getScheduleStatusCommand = 'get_schedule_status'
+/**
+ * 保存定时维护计划
+ */
+export const saveMaintenanceSchedulesCommand = 'save_maintenance_schedules'
+/**
+ * 立即执行维护任务
+ */
+export const runMaintenanceJobCommand = 'run_maintenance_job'
+/**
+ * 优化数据库（ANALYZE、VACUUM）
+ */
+export const optimizeDatabaseCommand = 'optimize_database'
+/**
+ * 获取缩放到显示尺寸的大图（JPEG Base64）
+ */
+export const getDisplayImageCommand = 'get_display_image'
+/**
+ * 在后台预读照片的大图
+ */
+export const prefetchPhotosCommand = 'prefetch_photos'
+/**
+ * 获取照片的各通道直方图和高光、暗部溢出百分比
+ */
+export const getPhotoHistogramCommand = 'get_photo_histogram'
+/**
+ * 按主色搜索照片（十六进制颜色和容差）
+ */
+export const searchByColorCommand = 'search_by_color'
+/**
+ * 获取照片主色
+ */
+export const getPhotoColorsCommand = 'get_photo_colors'
+/**
+ * 为还没有主色的照片提取主色
+ */
+export const indexPhotoColorsCommand = 'index_photo_colors'
@@ -33,7 +33,27 @@ const EmitOrder = {
   /**
    * 照片后台加载进度及信息提示
    */
-  photoLoadingMsgTip: 'photo-loading-msg-tip'
+  photoLoadingMsgTip: 'photo-loading-msg-tip',
+
+  /**
+   * 照片扫描结束（附带吞吐量统计）
+   */
+  scanFinished: 'scan-finished',
+
+  /**
+   * 配置已变化（重新加载配置文件后）
+   */
+  settingsChanged: 'settings-changed',
+
+  /**
+   * 幻灯片视频生成进度
+   */
+  slideshowProgress: 'slideshow-progress',
+
+  /**
+   * 存储卡导入进度
+   */
+  cardImportProgress: 'card-import-progress'
 } as const
 
 export default EmitOrder
@@ -32,11 +32,15 @@ export class GlobalErrorMsg {
  * 进度信息提示
  */
 export type loadMsg = {
+  // 扫描任务 ID（区分同时进行的多个扫描）
+  taskId: string,
   // 总任务数
   allTask: number,
   // 当前任务数（已完成）
   currentTask: number,
   // 任务信息
   taskMsg: string,
+  // 扫描 ID（用于获取扫描报告）
+  scanId?: number,
 
 }
\ No newline at end of file
@@ -1,15 +1,34 @@
 import {invoke} from "@tauri-apps/api/core";
 import {checkDirectoryAccessCommand} from '@/constants/command';
 
-type ans = {
-    ans: boolean
-    msg: string
+/**
+ * 扫描前的文件夹访问检查结果
+ */
+export type DirectoryAccessReport = {
+    path: string
+    exists: boolean
+    readable: boolean
+    writable: boolean
+    cloudProvider: 'oneDrive' | 'iCloud' | 'dropbox' | null
+    dirsChecked: number
+    filesChecked: number
+    unreadableDirs: number
+    unreadableFiles: number
+    cloudPlaceholders: number
+    issues: {
+        kind: 'unreadableDir' | 'unreadableFile' | 'cloudPlaceholder'
+        path: string
+        error: string | null
+    }[]
+    truncated: boolean
+    canScan: boolean
 }
 
 /**
- * 检查软件是否有对应权限
+ * 扫描前检查文件夹的访问权限
  * @param filePath
  */
-export function checkDirectoryAccess(filePath: string): Promise<ans> {
-    return invoke<ans>(checkDirectoryAccessCommand, {directory: filePath});
+export async function checkDirectoryAccess(filePath: string): Promise<DirectoryAccessReport> {
+    const report = await invoke<string>(checkDirectoryAccessCommand, {directory: filePath});
+    return JSON.parse(report);
 }
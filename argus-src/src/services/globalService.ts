@@ -1,10 +1,18 @@
 import { invoke } from '@tauri-apps/api/core'
 import type { ImageDirRustInfo } from '@/models/ImageShowInfo'
-import { addPhotoRetrieveTaskCommand } from '@/constants/command'
+import { addPhotoRetrieveTaskCommand, cancelScanCommand } from '@/constants/command'
 
 /**
  * 添加图像处理任务
  */
-export function addPhotoRetrieveTask(tasks: string[], isCancel: boolean) {
-  return invoke<string[]>(addPhotoRetrieveTaskCommand, { tasks,  isCancel })
+export function addPhotoRetrieveTask(tasks: string[]) {
+  return invoke<string[]>(addPhotoRetrieveTaskCommand, { tasks })
+}
+
+/**
+ * 取消扫描任务
+ * @param taskId 扫描任务 ID（由进度信息返回）
+ */
+export function cancelScan(taskId: string) {
+  return invoke<string>(cancelScanCommand, { taskId })
 }
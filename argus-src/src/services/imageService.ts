@@ -12,7 +12,7 @@ import {invoke} from "@tauri-apps/api/core";
 import {
   generateSaveThumbnailCommand,
   getImageAbsolutePathCommand, getImageThumbnailCommand, getImageThumbnailPathCommand,
-  readImageAsBase64Command
+  readImageAsBase64Command, recordThumbnailRoundTripCommand
 } from '@/constants/command'
 
 /**
@@ -29,6 +29,11 @@ export  function generateSaveThumbnail (imagePath:string[], emitCommand:string)
 export function getImageThumbnailPath(imagePath:string) {
   return invoke<string>(getImageThumbnailPathCommand,{imagePath});
 }
-export function getImageThumbnail(imagePath:string) {
-  return invoke<string>(getImageThumbnailCommand,{imagePath});
+export async function getImageThumbnail(imagePath:string) {
+  const started = performance.now();
+  const path = await invoke<string>(getImageThumbnailCommand,{imagePath});
+  // 往返耗时计入打开照片的 respond 阶段
+  const micros = Math.round((performance.now() - started) * 1000);
+  invoke(recordThumbnailRoundTripCommand,{micros}).catch(() => {});
+  return path;
 }
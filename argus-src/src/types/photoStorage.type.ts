@@ -19,4 +19,14 @@ export type photoStorageType = {
   is_delete: boolean
   create_time: number
   update_time: number
+  // 卷唯一标识（卷 GUID、文件系统 UUID、网络共享地址）
+  volume_id?: string | null
+  // 卷标
+  volume_label?: string | null
+  // 相对于挂载点的路径
+  relative_path?: string | null
+  // 卷是否在线
+  is_available?: boolean
+  // 最后一次在线时间
+  last_seen_time?: number
 }
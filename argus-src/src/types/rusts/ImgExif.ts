@@ -0,0 +1,56 @@
+/**
+ * Time:2025/01/17 10:21 36
+ * Name:ImgExif.ts
+ * Path:src/types/rusts
+ * ProjectName:argus-src
+ * Author:charlatans
+ *
+ *  Il n'ya qu'un héroïsme au monde :
+ *     c'est de voir le monde tel qu'il est et de l'aimer.
+ */
+
+/**
+ * 有理数（分子 / 分母）
+ */
+export type Rational = {
+  numerator: number
+  denominator: number
+}
+
+/**
+ * 十进制 GPS 坐标（南纬、西经为负数）
+ */
+export type GpsCoordinate = {
+  latitude: number
+  longitude: number
+  // 海拔（米）
+  altitude: number | null
+}
+
+/**
+ * 图像的 exif 信息
+ */
+export type ImgExif = {
+  make: string | null
+  model: string | null
+  software: string | null
+  // 曝光时间（秒）
+  exposureTime: Rational | null
+  flash: string | null
+  fNumber: number | null
+  iso: number | null
+  // 拍摄时间（UTC，RFC 3339）
+  dateTimeOriginal: string | null
+  offsetTime: string | null
+  maxApertureValue: number | null
+  // 焦距（毫米）
+  focalLength: number | null
+  focalLengthIn35mmFormat: number | null
+  imageWidth: number | null
+  imageHeight: number | null
+  gpsInfo: GpsCoordinate | null
+  exposureProgram: string | null
+  meteringMode: string | null
+  artist: string | null
+  rating: number | null
+}